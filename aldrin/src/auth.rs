@@ -0,0 +1,79 @@
+use crate::core::{SerializeError, SerializedValue, SerializedValueSlice};
+use std::fmt;
+
+/// Provides credentials when connecting to a broker.
+///
+/// Auth providers are used with [`ClientBuilder::with_auth`](crate::ClientBuilder::with_auth).
+/// Their [`credentials`](Self::credentials) are sent to the broker along with the initial
+/// connection message. Brokers may then send an arbitrary number of challenges, each of which is
+/// answered with [`respond`](Self::respond).
+///
+/// Brokers verify credentials with an `Authenticator` (see the `aldrin-broker` crate).
+pub trait AuthProvider {
+    /// Returns the credentials, that are sent to the broker.
+    fn credentials(&mut self) -> Result<SerializedValue, SerializeError>;
+
+    /// Returns the response to a challenge from the broker.
+    ///
+    /// The default implementation sends the [`credentials`](Self::credentials) again.
+    fn respond(
+        &mut self,
+        challenge: &SerializedValueSlice,
+    ) -> Result<SerializedValue, SerializeError> {
+        let _ = challenge;
+        self.credentials()
+    }
+}
+
+/// Auth provider, that authenticates with a plain token.
+///
+/// This corresponds to the `TokenAuthenticator` of the `aldrin-broker` crate.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin::{Client, TokenAuth};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let broker = aldrin_test::tokio::TestBroker::new();
+/// # let mut handle = broker.clone();
+/// # let (async_transport, t2) = aldrin::core::channel::unbounded();
+/// # let conn = tokio::spawn(async move { handle.connect(t2).await });
+/// let client = Client::builder(async_transport)
+///     .with_auth(TokenAuth::new("secret"))
+///     .connect()
+///     .await?;
+/// # tokio::spawn(conn.await??.run());
+/// # let handle = client.handle().clone();
+/// # let join = tokio::spawn(client.run());
+/// # handle.shutdown();
+/// # join.await??;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TokenAuth {
+    token: String,
+}
+
+impl TokenAuth {
+    /// Creates a new `TokenAuth` with the given token.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+        }
+    }
+}
+
+impl fmt::Debug for TokenAuth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TokenAuth").finish_non_exhaustive()
+    }
+}
+
+impl AuthProvider for TokenAuth {
+    fn credentials(&mut self) -> Result<SerializedValue, SerializeError> {
+        SerializedValue::serialize(&self.token)
+    }
+}
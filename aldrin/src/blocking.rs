@@ -0,0 +1,377 @@
+//! Blocking API for non-async applications
+//!
+//! This module provides a synchronous facade over a [`Handle`], similar to `reqwest::blocking`. All
+//! functions block the current thread until the respective operation has completed.
+//!
+//! A [`BlockingHandle`] can either be created with [`BlockingHandle::connect`], in which case it
+//! runs the [`Client`] on an internal Tokio runtime, or from an existing [`Handle`] with
+//! [`BlockingHandle::new`]. The latter is useful if an application is partially async and already
+//! runs a client on its own runtime.
+//!
+//! None of the functions in this module may be called from within an async execution context, as
+//! they would block it. Doing so causes a panic.
+//!
+//! # Examples
+//!
+//! ```
+//! use aldrin::blocking::BlockingHandle;
+//! use aldrin::core::{ObjectUuid, ServiceUuid};
+//! use aldrin::low_level::ServiceInfo;
+//! # use aldrin_broker::Broker;
+//! # use aldrin::core::channel;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let rt = tokio::runtime::Runtime::new()?;
+//! # let broker = Broker::new();
+//! # let mut broker_handle = broker.handle().clone();
+//! # rt.spawn(broker.run());
+//! # let (transport, t2) = channel::unbounded();
+//! # rt.spawn(async move { broker_handle.connect(t2).await.unwrap().run().await });
+//! let client = BlockingHandle::connect(transport)?;
+//!
+//! let obj = client.create_object(ObjectUuid::new_v4())?;
+//! let mut svc = obj.create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))?;
+//!
+//! let proxy = client.create_proxy(svc.id())?;
+//! let reply = std::thread::spawn(move || proxy.call(1, "ping"));
+//!
+//! let call = svc.next_call().unwrap();
+//! assert_eq!(call.deserialize::<String>()?, "ping");
+//! call.into_promise().ok("pong")?;
+//!
+//! let reply = reply.join().unwrap()?.unwrap();
+//! assert_eq!(reply.deserialize::<String>()?, "pong");
+//!
+//! client.shutdown();
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(test)]
+mod test;
+
+use crate::core::transport::AsyncTransport;
+use crate::core::{ObjectId, ObjectUuid, Serialize, SerializedValue, ServiceId, ServiceUuid};
+use crate::error::{ConnectError, Error};
+use crate::low_level::{Call, Event, Proxy, Service, ServiceInfo};
+use crate::{Client, Handle, Object};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::{self, Runtime};
+
+/// Blocking wrapper around a [`Handle`].
+///
+/// See the [module documentation](self) for more information.
+#[derive(Debug, Clone)]
+pub struct BlockingHandle {
+    inner: Handle,
+    rt: Rt,
+}
+
+impl BlockingHandle {
+    /// Connects to a broker and runs the client on an internal runtime.
+    ///
+    /// The runtime is shut down when the last `BlockingHandle` and all objects derived from it have
+    /// been dropped.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the internal Tokio runtime cannot be created.
+    #[allow(clippy::result_large_err)]
+    pub fn connect<T>(t: T) -> Result<Self, ConnectError<T::Error>>
+    where
+        T: AsyncTransport + Unpin + Send + 'static,
+        T::Error: Send,
+    {
+        let runtime = runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("aldrin-blocking")
+            .enable_all()
+            .build()
+            .expect("failed to create runtime");
+
+        let client = runtime.block_on(Client::connect(t))?;
+        let inner = client.handle().clone();
+        runtime.spawn(client.run());
+
+        Ok(Self {
+            inner,
+            rt: Rt {
+                handle: runtime.handle().clone(),
+                _runtime: Some(Arc::new(runtime)),
+            },
+        })
+    }
+
+    /// Creates a `BlockingHandle` from an existing [`Handle`].
+    ///
+    /// The client must be run elsewhere, e.g. on the runtime `runtime`, which is used only to block
+    /// on operations.
+    pub fn new(handle: Handle, runtime: runtime::Handle) -> Self {
+        Self {
+            inner: handle,
+            rt: Rt {
+                handle: runtime,
+                _runtime: None,
+            },
+        }
+    }
+
+    /// Returns the underlying [`Handle`].
+    pub fn handle(&self) -> &Handle {
+        &self.inner
+    }
+
+    /// Runs a future to completion, blocking the current thread.
+    ///
+    /// This can be used for operations, that have no blocking equivalent in this module.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.rt.block_on(future)
+    }
+
+    /// Shuts down the client.
+    ///
+    /// See [`Handle::shutdown`].
+    pub fn shutdown(&self) {
+        self.inner.shutdown();
+    }
+
+    /// Synchronizes with the broker.
+    ///
+    /// See [`Handle::sync_broker`].
+    pub fn sync_broker(&self) -> Result<(), Error> {
+        self.rt.block_on(self.inner.sync_broker())
+    }
+
+    /// Creates a new object on the bus.
+    ///
+    /// See [`Handle::create_object`].
+    pub fn create_object(&self, uuid: impl Into<ObjectUuid>) -> Result<BlockingObject, Error> {
+        let inner = self.rt.block_on(self.inner.create_object(uuid))?;
+
+        Ok(BlockingObject {
+            inner,
+            rt: self.rt.clone(),
+        })
+    }
+
+    /// Creates a proxy to a service.
+    ///
+    /// See [`Handle::create_proxy`].
+    pub fn create_proxy(&self, service: ServiceId) -> Result<BlockingProxy, Error> {
+        let inner = self.rt.block_on(self.inner.create_proxy(service))?;
+
+        Ok(BlockingProxy {
+            inner,
+            rt: self.rt.clone(),
+        })
+    }
+}
+
+/// Blocking wrapper around an [`Object`].
+#[derive(Debug)]
+pub struct BlockingObject {
+    inner: Object,
+    rt: Rt,
+}
+
+impl BlockingObject {
+    /// Returns the id of the object.
+    pub fn id(&self) -> ObjectId {
+        self.inner.id()
+    }
+
+    /// Returns a reference to the underlying [`Object`].
+    pub fn inner(&self) -> &Object {
+        &self.inner
+    }
+
+    /// Converts this object into the underlying [`Object`].
+    pub fn into_inner(self) -> Object {
+        self.inner
+    }
+
+    /// Destroys the object.
+    ///
+    /// See [`Object::destroy`].
+    pub fn destroy(&self) -> Result<(), Error> {
+        self.rt.block_on(self.inner.destroy())
+    }
+
+    /// Creates a service on the object.
+    ///
+    /// See [`Object::create_service`].
+    pub fn create_service(
+        &self,
+        uuid: impl Into<ServiceUuid>,
+        info: ServiceInfo,
+    ) -> Result<BlockingService, Error> {
+        let inner = self.rt.block_on(self.inner.create_service(uuid, info))?;
+
+        Ok(BlockingService {
+            inner,
+            rt: self.rt.clone(),
+        })
+    }
+}
+
+/// Blocking wrapper around a low-level [`Service`].
+#[derive(Debug)]
+pub struct BlockingService {
+    inner: Service,
+    rt: Rt,
+}
+
+impl BlockingService {
+    /// Returns the id of the service.
+    pub fn id(&self) -> ServiceId {
+        self.inner.id()
+    }
+
+    /// Returns a reference to the underlying [`Service`].
+    pub fn inner(&self) -> &Service {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying [`Service`].
+    pub fn inner_mut(&mut self) -> &mut Service {
+        &mut self.inner
+    }
+
+    /// Converts this service into the underlying [`Service`].
+    pub fn into_inner(self) -> Service {
+        self.inner
+    }
+
+    /// Destroys the service.
+    ///
+    /// See [`Service::destroy`].
+    pub fn destroy(&self) -> Result<(), Error> {
+        self.rt.block_on(self.inner.destroy())
+    }
+
+    /// Shuts down the service gracefully.
+    ///
+    /// See [`Service::shutdown`].
+    pub fn shutdown(&mut self, timeout: Option<Duration>) -> Result<(), Error> {
+        self.rt.block_on(self.inner.shutdown(timeout))
+    }
+
+    /// Waits for the next call.
+    ///
+    /// Calls are replied to with their [`Promise`](crate::low_level::Promise), which doesn't block.
+    ///
+    /// See [`Service::next_call`].
+    pub fn next_call(&mut self) -> Option<Call> {
+        self.rt.block_on(self.inner.next_call())
+    }
+
+    /// Emits an event.
+    ///
+    /// See [`Service::emit`].
+    pub fn emit<T: Serialize + ?Sized>(&self, event: u32, args: &T) -> Result<(), Error> {
+        self.inner.emit(event, args)
+    }
+}
+
+/// Blocking wrapper around a low-level [`Proxy`].
+#[derive(Debug)]
+pub struct BlockingProxy {
+    inner: Proxy,
+    rt: Rt,
+}
+
+impl BlockingProxy {
+    /// Returns the id of the proxy's service.
+    pub fn id(&self) -> ServiceId {
+        self.inner.id()
+    }
+
+    /// Returns a reference to the underlying [`Proxy`].
+    pub fn inner(&self) -> &Proxy {
+        &self.inner
+    }
+
+    /// Converts this proxy into the underlying [`Proxy`].
+    pub fn into_inner(self) -> Proxy {
+        self.inner
+    }
+
+    /// Calls a function and waits for the reply.
+    ///
+    /// See [`Proxy::call`].
+    pub fn call<Args: Serialize + ?Sized>(
+        &self,
+        function: u32,
+        args: &Args,
+    ) -> Result<Result<SerializedValue, SerializedValue>, Error> {
+        self.rt.block_on(self.inner.call(function, args))
+    }
+
+    /// Subscribes to an event.
+    ///
+    /// See [`Proxy::subscribe`].
+    pub fn subscribe(&self, event: u32) -> Result<(), Error> {
+        self.rt.block_on(self.inner.subscribe(event))
+    }
+
+    /// Unsubscribes from an event.
+    ///
+    /// See [`Proxy::unsubscribe`].
+    pub fn unsubscribe(&self, event: u32) -> Result<(), Error> {
+        self.rt.block_on(self.inner.unsubscribe(event))
+    }
+
+    /// Waits for the next event.
+    ///
+    /// See [`Proxy::next_event`].
+    pub fn next_event(&mut self) -> Option<Event> {
+        self.rt.block_on(self.inner.next_event())
+    }
+
+    /// Waits for the next event and acknowledges it.
+    ///
+    /// See [`Proxy::next_event_acked`].
+    pub fn next_event_acked(&mut self) -> Option<Event> {
+        self.rt.block_on(self.inner.next_event_acked())
+    }
+
+    /// Returns an iterator over all events.
+    ///
+    /// The iterator blocks while waiting for events. It ends under the same conditions as
+    /// [`next_event`](Self::next_event) returns `None`.
+    pub fn events(&mut self) -> Events<'_> {
+        Events { proxy: self }
+    }
+}
+
+/// Blocking iterator over the events of a [`BlockingProxy`].
+///
+/// See [`BlockingProxy::events`].
+#[derive(Debug)]
+pub struct Events<'a> {
+    proxy: &'a mut BlockingProxy,
+}
+
+impl Iterator for Events<'_> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        self.proxy.next_event()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Rt {
+    handle: runtime::Handle,
+
+    // Keeps the internal runtime alive.
+    _runtime: Option<Arc<Runtime>>,
+}
+
+impl Rt {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.handle.block_on(future)
+    }
+}
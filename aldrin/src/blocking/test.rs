@@ -0,0 +1,78 @@
+use aldrin_test::aldrin::blocking::BlockingHandle;
+use aldrin_test::aldrin::core::channel;
+use aldrin_test::aldrin::core::{ObjectUuid, ServiceUuid};
+use aldrin_test::aldrin::low_level::ServiceInfo;
+use aldrin_test::aldrin_broker::Broker;
+use aldrin_test::tokio::TestBroker;
+use std::thread;
+use tokio::runtime::Runtime;
+
+#[test]
+fn internal_runtime() {
+    let rt = Runtime::new().unwrap();
+    let broker = Broker::new();
+    let mut broker_handle = broker.handle().clone();
+    rt.spawn(broker.run());
+
+    let (t1, t2) = channel::unbounded();
+    rt.spawn(async move { broker_handle.connect(t2).await.unwrap().run().await });
+
+    let client = BlockingHandle::connect(t1).unwrap();
+    client.sync_broker().unwrap();
+
+    let obj = client.create_object(ObjectUuid::new_v4()).unwrap();
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .unwrap();
+
+    let proxy = client.create_proxy(svc.id()).unwrap();
+    let reply = thread::spawn(move || proxy.call(1, &2u32));
+
+    let call = svc.next_call().unwrap();
+    assert_eq!(call.id(), 1);
+    assert_eq!(call.deserialize(), Ok(2u32));
+    call.into_promise().ok(&3u32).unwrap();
+
+    let reply = reply.join().unwrap().unwrap().unwrap();
+    assert_eq!(reply.deserialize(), Ok(3u32));
+
+    svc.destroy().unwrap();
+    obj.destroy().unwrap();
+    client.shutdown();
+}
+
+#[test]
+fn existing_handle() {
+    let rt = Runtime::new().unwrap();
+
+    let mut broker = {
+        let _guard = rt.enter();
+        TestBroker::new()
+    };
+
+    let client = rt.block_on(broker.add_client());
+    let client = BlockingHandle::new(client.handle().clone(), rt.handle().clone());
+
+    let obj = client.create_object(ObjectUuid::new_v4()).unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .unwrap();
+
+    let mut proxy = client.create_proxy(svc.id()).unwrap();
+    proxy.subscribe(1).unwrap();
+
+    // Ensure, that the service's client knows about the subscription.
+    client.sync_broker().unwrap();
+
+    svc.emit(1, &0u32).unwrap();
+    svc.emit(1, &1u32).unwrap();
+    svc.destroy().unwrap();
+
+    let events = proxy
+        .events()
+        .map(|ev| ev.deserialize::<u32>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(events, [0, 1]);
+
+    rt.block_on(broker.join());
+}
@@ -148,6 +148,11 @@ impl BusListener {
     /// Note that new filters do not affect events which are already in the bus listener's internal
     /// queue.
     ///
+    /// [Layout filters](BusListenerFilter::Layout) match services by their
+    /// [`LexicalId`](crate::core::LexicalId), e.g. all services named `Room` from the schema
+    /// `chat`. They require protocol version 1.19 and never match anything when connected to an
+    /// older broker.
+    ///
     /// # Examples
     ///
     /// ```
@@ -516,8 +521,15 @@ impl BusListenerHandle {
         }
     }
 
-    pub fn emit_current(&self, event: BusEvent) -> bool {
-        if self.includes_current() && !self.current_finished {
+    /// Emits an event, that was addressed to this bus listener specifically.
+    ///
+    /// These are all events of the current phase and events of the new phase, that match only
+    /// layout filters.
+    pub fn emit_addressed(&self, event: BusEvent) -> bool {
+        let current = self.includes_current() && !self.current_finished;
+        let new = self.includes_new() && self.current_finished;
+
+        if current || new {
             let _ = self.events.unbounded_send(BusListenerEvent::Event(event));
             true
         } else {
@@ -1,4 +1,9 @@
+use crate::core::{
+    BusEvent, BusListenerFilter, BusListenerScope, LexicalId, ObjectUuid, ServiceUuid,
+};
+use aldrin_test::aldrin::low_level::ServiceInfo;
 use aldrin_test::tokio::TestBroker;
+use uuid::uuid;
 
 #[tokio::test]
 async fn create_and_explicit_destroy() {
@@ -23,3 +28,61 @@ async fn create_and_implicit_destroy() {
     client.join().await;
     broker.join().await;
 }
+
+#[tokio::test]
+async fn layout_filter() {
+    const ROOM: LexicalId = LexicalId(uuid!("a21ab5d6-5e43-4b9e-9c35-0ef3d4e1e7f2"));
+    const USER: LexicalId = LexicalId(uuid!("5c3b0e1f-8f43-4a52-8d6d-2b0a4b6e1c9d"));
+
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+
+    let info = ServiceInfo::new(0).set_lexical_id(ROOM);
+    let svc1 = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let info = ServiceInfo::new(0).set_lexical_id(USER);
+    obj.create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let info = ServiceInfo::new(0);
+    obj.create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let mut bus_listener = client.create_bus_listener().await.unwrap();
+    bus_listener
+        .add_filter(BusListenerFilter::any_object_layout(ROOM))
+        .unwrap();
+    bus_listener.start(BusListenerScope::All).await.unwrap();
+
+    let event = bus_listener.next_event().await.unwrap();
+    assert_eq!(event, BusEvent::ServiceCreated(svc1.id()));
+
+    let info = ServiceInfo::new(0).set_lexical_id(USER);
+    obj.create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let info = ServiceInfo::new(0).set_lexical_id(ROOM);
+    let svc2 = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let event = bus_listener.next_event().await.unwrap();
+    assert_eq!(event, BusEvent::ServiceCreated(svc2.id()));
+
+    svc1.destroy().await.unwrap();
+
+    let event = bus_listener.next_event().await.unwrap();
+    assert_eq!(event, BusEvent::ServiceDestroyed(svc1.id()));
+
+    client.join().await;
+    broker.join().await;
+}
@@ -0,0 +1,382 @@
+#[cfg(test)]
+mod test;
+
+use crate::bus_listener::BusListener;
+use crate::core::{
+    BusEvent, BusListenerFilter, BusListenerScope, ObjectId, ObjectUuid, ServiceId, ServiceUuid,
+};
+use crate::error::Error;
+use crate::handle::Handle;
+use futures_core::stream::{FusedStream, Stream};
+use std::collections::hash_map::{self, HashMap};
+use std::collections::HashSet;
+use std::future;
+use std::iter::FusedIterator;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Tracks all objects and their services on the bus.
+///
+/// A `BusTracker` maintains a live model of the objects on the bus and of the services, that
+/// belong to them. The model can be queried at any time, e.g. with [`object`](Self::object) or
+/// [`iter`](Self::iter). It is updated as the tracker is polled for events, with either
+/// [`next_event`](Self::next_event) or through the [`Stream`] implementation. Every event is
+/// applied to the model before it is returned.
+///
+/// By default, all services are tracked. The builder can restrict this to a set of
+/// [`ServiceUuid`s](ServiceUuid) with [`service`](BusTrackerBuilder::service) and
+/// [`services`](BusTrackerBuilder::services). Objects are always tracked, regardless of their
+/// services.
+///
+/// # Examples
+///
+/// ```
+/// # use aldrin::BusTracker;
+/// # use aldrin::core::{BusEvent, ObjectUuid, ServiceUuid};
+/// # use aldrin::low_level::ServiceInfo;
+/// # use aldrin_test::tokio::TestBroker;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let mut broker = TestBroker::new();
+/// # let handle = broker.add_client().await;
+/// let mut tracker = BusTracker::builder(&handle).build().await?;
+///
+/// let obj = handle.create_object(ObjectUuid::new_v4()).await?;
+/// let svc = obj.create_service(ServiceUuid::new_v4(), ServiceInfo::new(0)).await?;
+///
+/// assert_eq!(tracker.next_event().await, Some(BusEvent::ObjectCreated(obj.id())));
+/// assert_eq!(tracker.next_event().await, Some(BusEvent::ServiceCreated(svc.id())));
+///
+/// let tracked = tracker.object(obj.id().uuid).unwrap();
+/// assert_eq!(tracked.service_id(svc.id().uuid), Some(svc.id()));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BusTracker {
+    listener: BusListener,
+    services: Option<HashSet<ServiceUuid>>,
+    objects: HashMap<ObjectUuid, TrackedObject>,
+}
+
+impl BusTracker {
+    async fn new(
+        client: &Handle,
+        services: Option<HashSet<ServiceUuid>>,
+        current_only: bool,
+    ) -> Result<Self, Error> {
+        let mut listener = client.create_bus_listener().await?;
+        listener.add_filter(BusListenerFilter::any_object())?;
+
+        match services {
+            Some(ref services) => {
+                for &service in services {
+                    listener.add_filter(BusListenerFilter::any_object_specific_service(service))?;
+                }
+            }
+
+            None => listener.add_filter(BusListenerFilter::any_object_any_service())?,
+        }
+
+        if current_only {
+            listener.start(BusListenerScope::Current).await?;
+        } else {
+            listener.start(BusListenerScope::All).await?;
+        }
+
+        Ok(Self {
+            listener,
+            services,
+            objects: HashMap::new(),
+        })
+    }
+
+    /// Create a builder for a `BusTracker`.
+    pub fn builder(client: &Handle) -> BusTrackerBuilder<'_> {
+        BusTrackerBuilder::new(client)
+    }
+
+    /// Returns a handle to the client that was used to create the tracker.
+    pub fn client(&self) -> &Handle {
+        self.listener.client()
+    }
+
+    async fn stop(&mut self) -> Result<(), Error> {
+        self.listener.stop().await?;
+        while self.listener.next_event().await.is_some() {}
+        self.objects.clear();
+        Ok(())
+    }
+
+    /// Restarts the tracker.
+    ///
+    /// All pending events will be discarded and the model will be cleared. The tracker will be
+    /// configured to consider all objects and services on the bus, as if it was built again with
+    /// [`BusTrackerBuilder::build`].
+    pub async fn restart(&mut self) -> Result<(), Error> {
+        self.stop().await?;
+        self.listener.start(BusListenerScope::All).await?;
+        Ok(())
+    }
+
+    /// Restarts the tracker and configures it to consider only current objects and services.
+    ///
+    /// All pending events will be discarded and the model will be cleared. The tracker will be
+    /// configured to consider only current objects and services on the bus, as if it was built
+    /// again with [`BusTrackerBuilder::build_current_only`].
+    pub async fn restart_current_only(&mut self) -> Result<(), Error> {
+        self.stop().await?;
+        self.listener.start(BusListenerScope::Current).await?;
+        Ok(())
+    }
+
+    /// Indicates whether the tracker can return more events.
+    ///
+    /// Trackers can only finish if they are considering only current objects and services, i.e.
+    /// built with [`build_current_only`](BusTrackerBuilder::build_current_only) or restarted with
+    /// [`restart_current_only`](Self::restart_current_only). The model is complete once the
+    /// tracker has finished.
+    pub fn is_finished(&self) -> bool {
+        self.listener.is_finished()
+    }
+
+    /// Returns whether a service UUID is tracked.
+    pub fn tracks_service(&self, service: ServiceUuid) -> bool {
+        self.services
+            .as_ref()
+            .map(|services| services.contains(&service))
+            .unwrap_or(true)
+    }
+
+    /// Returns the number of objects, that are currently known.
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Indicates whether no objects are currently known.
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Returns an object, if it is currently known.
+    pub fn object(&self, object: ObjectUuid) -> Option<&TrackedObject> {
+        self.objects.get(&object)
+    }
+
+    /// Queries a specific service id.
+    pub fn service_id(&self, object: ObjectUuid, service: ServiceUuid) -> Option<ServiceId> {
+        self.object(object)?.service_id(service)
+    }
+
+    /// Returns an iterator over all currently known objects.
+    pub fn iter(&self) -> BusTrackerIter<'_> {
+        BusTrackerIter {
+            inner: self.objects.values(),
+        }
+    }
+
+    /// Returns an iterator over all currently known objects, that have a specific service.
+    pub fn objects_with_service(
+        &self,
+        service: ServiceUuid,
+    ) -> impl Iterator<Item = &TrackedObject> + '_ {
+        self.iter().filter(move |obj| obj.has_service(service))
+    }
+
+    /// Returns a snapshot of all currently known objects.
+    ///
+    /// Unlike [`iter`](Self::iter), the snapshot does not borrow the tracker and can be kept
+    /// while the tracker is polled for further events.
+    pub fn snapshot(&self) -> Vec<TrackedObject> {
+        self.objects.values().cloned().collect()
+    }
+
+    /// Polls the tracker for an event.
+    ///
+    /// The event has already been applied to the model, when it is returned.
+    pub fn poll_next_event(&mut self, cx: &mut Context) -> Poll<Option<BusEvent>> {
+        loop {
+            let event = match self.listener.poll_next_event(cx) {
+                Poll::Ready(Some(event)) => event,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if self.apply(event) {
+                return Poll::Ready(Some(event));
+            }
+        }
+    }
+
+    /// Awaits an event from the tracker.
+    ///
+    /// The event has already been applied to the model, when it is returned.
+    pub async fn next_event(&mut self) -> Option<BusEvent> {
+        future::poll_fn(|cx| self.poll_next_event(cx)).await
+    }
+
+    fn apply(&mut self, event: BusEvent) -> bool {
+        match event {
+            BusEvent::ObjectCreated(id) => {
+                self.objects
+                    .entry(id.uuid)
+                    .or_insert_with(|| TrackedObject::new(id));
+                true
+            }
+
+            BusEvent::ObjectDestroyed(id) => self.objects.remove(&id.uuid).is_some(),
+
+            BusEvent::ServiceCreated(id) => {
+                if !self.tracks_service(id.uuid) {
+                    return false;
+                }
+
+                self.objects
+                    .entry(id.object_id.uuid)
+                    .or_insert_with(|| TrackedObject::new(id.object_id))
+                    .services
+                    .insert(id.uuid, id);
+
+                true
+            }
+
+            BusEvent::ServiceDestroyed(id) => self
+                .objects
+                .get_mut(&id.object_id.uuid)
+                .and_then(|obj| obj.services.remove(&id.uuid))
+                .is_some(),
+        }
+    }
+}
+
+impl Stream for BusTracker {
+    type Item = BusEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.poll_next_event(cx)
+    }
+}
+
+impl FusedStream for BusTracker {
+    fn is_terminated(&self) -> bool {
+        self.is_finished()
+    }
+}
+
+impl<'a> IntoIterator for &'a BusTracker {
+    type IntoIter = BusTrackerIter<'a>;
+    type Item = &'a TrackedObject;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Builder for `BusTracker`s.
+///
+/// See [`BusTracker`] for usage examples.
+#[derive(Debug)]
+pub struct BusTrackerBuilder<'a> {
+    client: &'a Handle,
+    services: Option<HashSet<ServiceUuid>>,
+}
+
+impl<'a> BusTrackerBuilder<'a> {
+    /// Creates a new `BusTrackerBuilder`.
+    pub fn new(client: &'a Handle) -> Self {
+        Self {
+            client,
+            services: None,
+        }
+    }
+
+    /// Builds the tracker.
+    pub async fn build(self) -> Result<BusTracker, Error> {
+        BusTracker::new(self.client, self.services, false).await
+    }
+
+    /// Builds the tracker and configures it to consider only current objects and services.
+    ///
+    /// Unlike [`build`](Self::build), the tracker will consider only those objects and services
+    /// that exist already on the bus.
+    pub async fn build_current_only(self) -> Result<BusTracker, Error> {
+        BusTracker::new(self.client, self.services, true).await
+    }
+
+    /// Restricts the tracker to a service UUID.
+    ///
+    /// This can be called multiple times to track several service UUIDs. If it is never called,
+    /// then all services are tracked.
+    pub fn service(self, service: ServiceUuid) -> Self {
+        self.services([service])
+    }
+
+    /// Restricts the tracker to a set of service UUIDs.
+    ///
+    /// This extends the set of previously added service UUIDs.
+    pub fn services(mut self, services: impl IntoIterator<Item = ServiceUuid>) -> Self {
+        self.services
+            .get_or_insert_with(HashSet::new)
+            .extend(services);
+
+        self
+    }
+}
+
+/// Object tracked by a [`BusTracker`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedObject {
+    id: ObjectId,
+    services: HashMap<ServiceUuid, ServiceId>,
+}
+
+impl TrackedObject {
+    fn new(id: ObjectId) -> Self {
+        Self {
+            id,
+            services: HashMap::new(),
+        }
+    }
+
+    /// Returns the id of the object.
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    /// Queries a specific service id.
+    pub fn service_id(&self, service: ServiceUuid) -> Option<ServiceId> {
+        self.services.get(&service).copied()
+    }
+
+    /// Indicates whether the object has a specific service.
+    pub fn has_service(&self, service: ServiceUuid) -> bool {
+        self.services.contains_key(&service)
+    }
+
+    /// Returns an iterator over the ids of all tracked services of the object.
+    pub fn services(&self) -> impl ExactSizeIterator<Item = ServiceId> + '_ {
+        self.services.values().copied()
+    }
+}
+
+/// Iterator over all objects of a [`BusTracker`].
+#[derive(Debug, Clone)]
+pub struct BusTrackerIter<'a> {
+    inner: hash_map::Values<'a, ObjectUuid, TrackedObject>,
+}
+
+impl<'a> Iterator for BusTrackerIter<'a> {
+    type Item = &'a TrackedObject;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for BusTrackerIter<'_> {}
+
+impl FusedIterator for BusTrackerIter<'_> {}
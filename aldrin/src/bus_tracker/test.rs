@@ -0,0 +1,142 @@
+use crate::core::{BusEvent, ObjectUuid, ServiceUuid};
+use aldrin_test::aldrin::low_level::ServiceInfo;
+use aldrin_test::tokio::TestBroker;
+
+#[tokio::test]
+async fn track_objects_and_services() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let info = ServiceInfo::new(0);
+    let obj1 = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc1 = obj1
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let mut tracker = client.create_bus_tracker().build().await.unwrap();
+
+    assert_eq!(
+        tracker.next_event().await,
+        Some(BusEvent::ObjectCreated(obj1.id()))
+    );
+    assert_eq!(
+        tracker.next_event().await,
+        Some(BusEvent::ServiceCreated(svc1.id()))
+    );
+    assert_eq!(tracker.len(), 1);
+    assert_eq!(
+        tracker.service_id(obj1.id().uuid, svc1.id().uuid),
+        Some(svc1.id())
+    );
+
+    let obj2 = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc2 = obj2.create_service(svc1.id().uuid, info).await.unwrap();
+
+    assert_eq!(
+        tracker.next_event().await,
+        Some(BusEvent::ObjectCreated(obj2.id()))
+    );
+    assert_eq!(
+        tracker.next_event().await,
+        Some(BusEvent::ServiceCreated(svc2.id()))
+    );
+    assert_eq!(tracker.iter().len(), 2);
+    assert_eq!(tracker.objects_with_service(svc1.id().uuid).count(), 2);
+
+    let snapshot = tracker.snapshot();
+
+    svc1.destroy().await.unwrap();
+    assert_eq!(
+        tracker.next_event().await,
+        Some(BusEvent::ServiceDestroyed(svc1.id()))
+    );
+    assert!(!tracker
+        .object(obj1.id().uuid)
+        .unwrap()
+        .has_service(svc1.id().uuid));
+    assert_eq!(tracker.objects_with_service(svc1.id().uuid).count(), 1);
+
+    obj2.destroy().await.unwrap();
+    assert_eq!(
+        tracker.next_event().await,
+        Some(BusEvent::ServiceDestroyed(svc2.id()))
+    );
+    assert_eq!(
+        tracker.next_event().await,
+        Some(BusEvent::ObjectDestroyed(obj2.id()))
+    );
+    assert_eq!(tracker.object(obj2.id().uuid), None);
+    assert_eq!(tracker.len(), 1);
+
+    // Snapshots are not affected by later events.
+    assert_eq!(snapshot.len(), 2);
+    assert!(snapshot.iter().all(|obj| obj.services().len() == 1));
+}
+
+#[tokio::test]
+async fn filter_services() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let svc_uuid = ServiceUuid::new_v4();
+
+    let mut tracker = client
+        .create_bus_tracker()
+        .service(svc_uuid)
+        .build()
+        .await
+        .unwrap();
+    assert!(tracker.tracks_service(svc_uuid));
+    assert!(!tracker.tracks_service(ServiceUuid::new_v4()));
+
+    let info = ServiceInfo::new(0);
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let _other = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+    let svc = obj.create_service(svc_uuid, info).await.unwrap();
+
+    assert_eq!(
+        tracker.next_event().await,
+        Some(BusEvent::ObjectCreated(obj.id()))
+    );
+    assert_eq!(
+        tracker.next_event().await,
+        Some(BusEvent::ServiceCreated(svc.id()))
+    );
+
+    let tracked = tracker.object(obj.id().uuid).unwrap();
+    assert_eq!(tracked.services().collect::<Vec<_>>(), [svc.id()]);
+}
+
+#[tokio::test]
+async fn current_only() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let mut tracker = client
+        .create_bus_tracker()
+        .build_current_only()
+        .await
+        .unwrap();
+
+    while tracker.next_event().await.is_some() {}
+    assert!(tracker.is_finished());
+    assert_eq!(
+        tracker.service_id(obj.id().uuid, svc.id().uuid),
+        Some(svc.id())
+    );
+
+    tracker.restart_current_only().await.unwrap();
+    assert!(tracker.is_empty());
+    while tracker.next_event().await.is_some() {}
+    assert_eq!(tracker.len(), 1);
+}
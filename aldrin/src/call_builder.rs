@@ -0,0 +1,80 @@
+use crate::core::{Serialize, SerializeError, SerializedValue};
+use crate::low_level::{self, CallOptions, CallPriority, Proxy};
+use crate::reply::Reply;
+use std::fmt;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// Builder for a call with [`CallOptions`].
+///
+/// Generated proxies provide a `*_with` method for every function, which returns a
+/// `CallBuilder`. The call is made once [`call`](Self::call) is invoked.
+#[must_use = "builders do nothing unless `call` is invoked"]
+pub struct CallBuilder<'a, T, E> {
+    proxy: &'a Proxy,
+    function: u32,
+    args: Result<SerializedValue, SerializeError>,
+    options: CallOptions,
+    phantom: PhantomData<fn() -> (T, E)>,
+}
+
+impl<'a, T, E> CallBuilder<'a, T, E> {
+    /// Creates a new `CallBuilder` for a call to `function`.
+    pub fn new<Args>(proxy: &'a Proxy, function: u32, args: &Args) -> Self
+    where
+        Args: Serialize + ?Sized,
+    {
+        Self {
+            proxy,
+            function,
+            args: SerializedValue::serialize(args),
+            options: CallOptions::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets the timeout of the call.
+    ///
+    /// See [`CallOptions::set_timeout`] for details.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.set_timeout(timeout);
+        self
+    }
+
+    /// Sets the priority of the call.
+    ///
+    /// See [`CallOptions::set_priority`] for details.
+    pub fn priority(mut self, priority: CallPriority) -> Self {
+        self.options = self.options.set_priority(priority);
+        self
+    }
+
+    /// Replaces all options of the call.
+    pub fn options(mut self, options: CallOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Makes the call.
+    pub fn call(self) -> Reply<T, E> {
+        self.proxy
+            .call_serialized(
+                self.function,
+                self.args,
+                low_level::current_trace_context(),
+                self.options,
+            )
+            .cast()
+    }
+}
+
+impl<T, E> fmt::Debug for CallBuilder<'_, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CallBuilder")
+            .field("proxy", &self.proxy)
+            .field("function", &self.function)
+            .field("args", &self.args)
+            .field("options", &self.options)
+            .finish()
+    }
+}
@@ -1,3 +1,4 @@
+mod broadcast;
 mod builder;
 mod established;
 mod pending;
@@ -6,6 +7,7 @@ mod test;
 mod unbound;
 mod unclaimed;
 
+pub use broadcast::{BroadcastReceiver, BroadcastSender, UnboundBroadcastReceiver};
 pub use builder::ChannelBuilder;
 pub use established::{Receiver, Sender};
 pub use pending::{PendingReceiver, PendingSender};
@@ -0,0 +1,363 @@
+#[cfg(feature = "introspection")]
+use crate::core::introspection::{BuiltInType, Introspectable, Layout, LexicalId, References};
+use crate::core::{
+    AsSerializeArg, ChannelCookie, Deserialize, DeserializeError, Deserializer, Serialize,
+    SerializeArg, SerializeError, Serializer,
+};
+use crate::error::Error;
+use crate::handle::Handle;
+use crate::low_level;
+use futures_core::stream::{FusedStream, Stream};
+#[cfg(feature = "sink")]
+use futures_sink::Sink;
+use std::fmt;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The sending end of a broadcast channel.
+///
+/// Broadcast channels have a single sender and any number of receivers. Every item is delivered to
+/// all receivers, that are claimed at the time the item is sent.
+///
+/// [`BroadcastSender`s](Self) are created with
+/// [`ChannelBuilder::broadcast`](super::ChannelBuilder::broadcast). Receivers are claimed from an
+/// [`UnboundBroadcastReceiver`], which can be acquired with [`receiver`](Self::receiver) and then
+/// be sent to other clients.
+///
+/// Each receiver has its own capacity. The sender can only send items as long as all receivers have
+/// some capacity left, such that the slowest receiver determines the rate of the channel. The sender
+/// has no capacity as long as there are no receivers.
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let mut broker = aldrin_test::tokio::TestBroker::new();
+/// # let handle1 = broker.add_client().await;
+/// # let handle2 = broker.add_client().await;
+/// let mut sender = handle1.create_channel::<String>().broadcast().await?;
+///
+/// // Receivers can be claimed at most once per client:
+/// let mut receiver1 = sender.receiver().claim(handle1.clone(), 16).await?;
+/// let mut receiver2 = sender.receiver().claim(handle2.clone(), 16).await?;
+///
+/// sender.send_item("Hello :)").await?;
+///
+/// assert_eq!(receiver1.next_item().await?.as_deref(), Some("Hello :)"));
+/// assert_eq!(receiver2.next_item().await?.as_deref(), Some("Hello :)"));
+/// # Ok(())
+/// # }
+/// ```
+pub struct BroadcastSender<T: ?Sized> {
+    inner: low_level::Sender,
+    phantom: PhantomData<fn(T)>,
+}
+
+impl<T: ?Sized> BroadcastSender<T> {
+    pub(crate) fn new(inner: low_level::Sender) -> Self {
+        Self {
+            inner,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a [`Handle`] to the associated client.
+    pub fn client(&self) -> &Handle {
+        self.inner.client()
+    }
+
+    /// Returns the [`ChannelCookie`], which identifies this channel.
+    pub fn cookie(&self) -> ChannelCookie {
+        self.inner.cookie()
+    }
+
+    /// Initiates closing the sender and polls for progress.
+    ///
+    /// See [`close`](Self::close) for more information.
+    pub fn poll_close(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
+        self.inner.poll_close(cx)
+    }
+
+    /// Closes the sender.
+    ///
+    /// This will prevent further items from being sent. All receivers will still be able pull
+    /// already sent items from the channel.
+    pub async fn close(&mut self) -> Result<(), Error> {
+        self.inner.close().await
+    }
+
+    /// Polls the channel for capacity to send at least one item.
+    ///
+    /// See [`send_ready`](Self::send_ready) for more information.
+    pub fn poll_send_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
+        self.inner.poll_send_ready(cx)
+    }
+
+    /// Waits until the channel has capacity to send at least one item.
+    ///
+    /// Before sending an item, this method must be used to ensure there is capacity.
+    pub async fn send_ready(&mut self) -> Result<(), Error> {
+        self.inner.send_ready().await
+    }
+}
+
+impl<T> BroadcastSender<T> {
+    /// Returns an [`UnboundBroadcastReceiver`] for this channel.
+    ///
+    /// The returned value can be claimed any number of times, but at most once per client.
+    pub fn receiver(&self) -> UnboundBroadcastReceiver<T> {
+        UnboundBroadcastReceiver::new(self.cookie())
+    }
+}
+
+impl<T: AsSerializeArg + ?Sized> BroadcastSender<T> {
+    /// Starts sending an item on the channel.
+    ///
+    /// It must be ensured that there is enough capacity by calling [`send_ready`](Self::send_ready)
+    /// prior to sending an item.
+    pub fn start_send_item(&mut self, item: SerializeArg<T>) -> Result<(), Error> {
+        self.inner.start_send_item(&item)
+    }
+
+    /// Sends an item on the channel.
+    ///
+    /// This method is a shorthand for calling [`send_ready`](Self::send_ready) followed by
+    /// [`start_send_item`](Self::start_send_item).
+    pub async fn send_item(&mut self, item: SerializeArg<'_, T>) -> Result<(), Error> {
+        self.inner.send_item(&item).await
+    }
+}
+
+impl<T: Serialize + ?Sized> BroadcastSender<T> {
+    /// Starts sending an item on the channel.
+    ///
+    /// It must be ensured that there is enough capacity by calling [`send_ready`](Self::send_ready)
+    /// prior to sending an item.
+    pub fn start_send_ref(&mut self, item: &T) -> Result<(), Error> {
+        self.inner.start_send_item(item)
+    }
+
+    /// Sends an item on the channel.
+    ///
+    /// This method is a shorthand for calling [`send_ready`](Self::send_ready) followed by
+    /// [`start_send_ref`](Self::start_send_ref).
+    pub async fn send_ref(&mut self, item: &T) -> Result<(), Error> {
+        self.inner.send_item(item).await
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for BroadcastSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BroadcastSender")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg(feature = "sink")]
+impl<T: AsSerializeArg + ?Sized> Sink<SerializeArg<'_, T>> for BroadcastSender<T> {
+    type Error = Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.poll_send_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: SerializeArg<T>) -> Result<(), Self::Error> {
+        self.start_send_item(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Self::poll_close(&mut self, cx)
+    }
+}
+
+/// A receiver of a broadcast channel that isn't bound to any client.
+///
+/// [`UnboundBroadcastReceiver`s](Self) can be sent to other clients, which can then claim it with
+/// [`claim`](Self::claim). Unlike [`UnboundReceiver`](super::UnboundReceiver), they can be claimed
+/// multiple times, once per client.
+///
+/// On the wire, this type is identical to [`UnboundReceiver`](super::UnboundReceiver).
+///
+/// Note that this type is [`Copy`] and is thus not consumed by any of its methods.
+pub struct UnboundBroadcastReceiver<T> {
+    inner: low_level::UnboundReceiver,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> UnboundBroadcastReceiver<T> {
+    /// Creates a new [`UnboundBroadcastReceiver`] from a [`ChannelCookie`].
+    pub fn new(cookie: ChannelCookie) -> Self {
+        Self {
+            inner: low_level::UnboundReceiver::new(cookie),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the [`ChannelCookie`], which identifies this channel.
+    pub fn cookie(self) -> ChannelCookie {
+        self.inner.cookie()
+    }
+
+    /// Casts the item type to a different type `U`.
+    pub fn cast<U>(self) -> UnboundBroadcastReceiver<U> {
+        UnboundBroadcastReceiver::new(self.cookie())
+    }
+
+    /// Binds the receiver to a client and claims it.
+    ///
+    /// A capacity of 0 will be treated as if 1 was specified instead.
+    pub async fn claim(self, client: Handle, capacity: u32) -> Result<BroadcastReceiver<T>, Error> {
+        let inner = low_level::UnclaimedReceiver::new(client, self.cookie())
+            .claim(capacity)
+            .await?;
+
+        Ok(BroadcastReceiver::new(inner))
+    }
+}
+
+impl<T> From<ChannelCookie> for UnboundBroadcastReceiver<T> {
+    fn from(cookie: ChannelCookie) -> Self {
+        Self::new(cookie)
+    }
+}
+
+impl<T> fmt::Debug for UnboundBroadcastReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UnboundBroadcastReceiver")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<T> Clone for UnboundBroadcastReceiver<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UnboundBroadcastReceiver<T> {}
+
+impl<T> Serialize for UnboundBroadcastReceiver<T> {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        self.inner.serialize(serializer)
+    }
+}
+
+impl<T> Deserialize for UnboundBroadcastReceiver<T> {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        low_level::UnboundReceiver::deserialize(deserializer).map(|inner| Self::new(inner.cookie()))
+    }
+}
+
+impl<T> AsSerializeArg for UnboundBroadcastReceiver<T> {
+    type SerializeArg<'a>
+        = Self
+    where
+        Self: 'a;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        *self
+    }
+}
+
+#[cfg(feature = "introspection")]
+impl<T: Introspectable> Introspectable for UnboundBroadcastReceiver<T> {
+    fn layout() -> Layout {
+        BuiltInType::Receiver(T::lexical_id()).into()
+    }
+
+    fn lexical_id() -> LexicalId {
+        LexicalId::receiver(T::lexical_id())
+    }
+
+    fn add_references(references: &mut References) {
+        references.add::<T>();
+    }
+}
+
+/// A receiver of a broadcast channel.
+///
+/// [`BroadcastReceiver`s](Self) are acquired with [`UnboundBroadcastReceiver::claim`].
+pub struct BroadcastReceiver<T> {
+    inner: low_level::Receiver,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> BroadcastReceiver<T> {
+    pub(crate) fn new(inner: low_level::Receiver) -> Self {
+        Self {
+            inner,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a [`Handle`] to the associated client.
+    pub fn client(&self) -> &Handle {
+        self.inner.client()
+    }
+
+    /// Returns the [`ChannelCookie`], which identifies this channel.
+    pub fn cookie(&self) -> ChannelCookie {
+        self.inner.cookie()
+    }
+
+    /// Initiates closing the receiver and polls for progress.
+    ///
+    /// See [`close`](Self::close) for more information.
+    pub fn poll_close(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
+        self.inner.poll_close(cx)
+    }
+
+    /// Closes the receiver.
+    ///
+    /// This only affects this receiver. The sender and all other receivers stay open.
+    pub async fn close(&mut self) -> Result<(), Error> {
+        self.inner.close().await
+    }
+}
+
+impl<T: Deserialize> BroadcastReceiver<T> {
+    /// Polls the channel for the next item.
+    pub fn poll_next_item(&mut self, cx: &mut Context) -> Poll<Result<Option<T>, Error>> {
+        self.inner.poll_next_item(cx)
+    }
+
+    /// Waits for the next item on the channel.
+    pub async fn next_item(&mut self) -> Result<Option<T>, Error> {
+        self.inner.next_item().await
+    }
+}
+
+impl<T> fmt::Debug for BroadcastReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BroadcastReceiver")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<T: Deserialize> Stream for BroadcastReceiver<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.poll_next_item(cx).map(Result::transpose)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Deserialize> FusedStream for BroadcastReceiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_terminated()
+    }
+}
@@ -1,4 +1,4 @@
-use super::{PendingReceiver, PendingSender, UnclaimedReceiver, UnclaimedSender};
+use super::{BroadcastSender, PendingReceiver, PendingSender, UnclaimedReceiver, UnclaimedSender};
 use crate::error::Error;
 use crate::handle::Handle;
 use crate::low_level;
@@ -74,6 +74,25 @@ impl<'a, T> ChannelBuilder<'a, T> {
         let (sender, receiver) = self.inner.claim_receiver(capacity).await?;
         Ok((sender.cast(), receiver.cast()))
     }
+
+    /// Creates a new channel and claims the receiver with a capacity measured in bytes.
+    ///
+    /// See [`low_level::ChannelBuilder::claim_receiver_bytes`] for more information.
+    pub async fn claim_receiver_bytes(
+        self,
+        max_bytes: u32,
+    ) -> Result<(UnclaimedSender<T>, PendingReceiver<T>), Error> {
+        let (sender, receiver) = self.inner.claim_receiver_bytes(max_bytes).await?;
+        Ok((sender.cast(), receiver.cast()))
+    }
+
+    /// Creates a new broadcast channel.
+    ///
+    /// See [`low_level::ChannelBuilder::broadcast`] for more information.
+    pub async fn broadcast(self) -> Result<BroadcastSender<T>, Error> {
+        let sender = self.inner.broadcast().await?;
+        Ok(BroadcastSender::new(sender))
+    }
 }
 
 impl<T> fmt::Debug for ChannelBuilder<'_, T> {
@@ -166,6 +166,48 @@ async fn send_and_receive() {
     broker.join().await;
 }
 
+#[tokio::test]
+async fn bytes_capacity() {
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+
+    let (sender, receiver) = client
+        .create_channel::<String>()
+        .claim_receiver_bytes(16)
+        .await
+        .unwrap();
+
+    let mut sender = sender.claim().await.unwrap();
+    let mut receiver = receiver.establish().await.unwrap();
+
+    // Each item is 12 bytes when serialized. The second item is allowed to exceed the capacity.
+    sender.send_item("0123456789").await.unwrap();
+    sender.send_item("0123456789").await.unwrap();
+
+    let res = time::timeout(Duration::from_millis(100), sender.send_ready()).await;
+    assert!(res.is_err());
+
+    assert_eq!(
+        receiver.next_item().await,
+        Ok(Some("0123456789".to_string()))
+    );
+
+    sender.send_ready().await.unwrap();
+    sender.send_item("0123456789").await.unwrap();
+
+    assert_eq!(
+        receiver.next_item().await,
+        Ok(Some("0123456789".to_string()))
+    );
+    assert_eq!(
+        receiver.next_item().await,
+        Ok(Some("0123456789".to_string()))
+    );
+
+    client.join().await;
+    broker.join().await;
+}
+
 #[tokio::test]
 async fn multiple_clients() {
     let mut broker = TestBroker::new();
@@ -189,6 +231,55 @@ async fn multiple_clients() {
     broker.join().await;
 }
 
+#[tokio::test]
+async fn broadcast() {
+    let mut broker = TestBroker::new();
+    let mut client1 = broker.add_client().await;
+    let mut client2 = broker.add_client().await;
+
+    let mut sender = client1
+        .create_channel::<String>()
+        .broadcast()
+        .await
+        .unwrap();
+
+    // There is no capacity without receivers.
+    let res = time::timeout(Duration::from_millis(100), sender.send_ready()).await;
+    assert!(res.is_err());
+
+    let mut receiver1 = sender.receiver().claim(client1.clone(), 1).await.unwrap();
+    let mut receiver2 = sender.receiver().claim(client2.clone(), 16).await.unwrap();
+
+    assert_eq!(
+        sender
+            .receiver()
+            .claim(client2.clone(), 16)
+            .await
+            .unwrap_err(),
+        Error::InvalidChannel
+    );
+
+    // The first receiver limits the capacity to 1.
+    sender.send_item("hello").await.unwrap();
+    let res = time::timeout(Duration::from_millis(100), sender.send_ready()).await;
+    assert!(res.is_err());
+
+    assert_eq!(receiver1.next_item().await, Ok(Some("hello".to_string())));
+    assert_eq!(receiver2.next_item().await, Ok(Some("hello".to_string())));
+
+    // Closing the first receiver leaves only the second one.
+    receiver1.close().await.unwrap();
+    sender.send_item("world").await.unwrap();
+    sender.close().await.unwrap();
+
+    assert_eq!(receiver2.next_item().await, Ok(Some("world".to_string())));
+    assert_eq!(receiver2.next_item().await, Ok(None));
+
+    client1.join().await;
+    client2.join().await;
+    broker.join().await;
+}
+
 #[tokio::test]
 async fn send_error_when_receiver_is_closed() {
     let mut broker = TestBroker::new();
@@ -255,6 +255,14 @@ impl<T> UnclaimedReceiver<T> {
         let inner = self.inner.claim(capacity).await?;
         Ok(Receiver::new(inner))
     }
+
+    /// Claims the receiver with a capacity measured in bytes and establishes the channel.
+    ///
+    /// See [`low_level::UnclaimedReceiver::claim_bytes`] for more information.
+    pub async fn claim_bytes(self, max_bytes: u32) -> Result<Receiver<T>, Error> {
+        let inner = self.inner.claim_bytes(max_bytes).await?;
+        Ok(Receiver::new(inner))
+    }
 }
 
 impl<T> fmt::Debug for UnclaimedReceiver<T> {
@@ -1,66 +1,89 @@
 mod broker_subscriptions;
+mod builder;
+mod call_timer;
+mod keep_alive;
 mod proxies;
+mod request_queue;
 mod select;
 
+use crate::auth::AuthProvider;
 use crate::bus_listener::{BusListener, BusListenerHandle};
 #[cfg(feature = "introspection")]
 use crate::core::introspection::{DynIntrospectable, Introspection, References};
+#[cfg(feature = "tracing")]
+use crate::core::message::MessageOps;
 use crate::core::message::{
-    AbortFunctionCall, AddBusListenerFilter, AddChannelCapacity, BusListenerCurrentFinished,
-    CallFunction, CallFunctionReply, CallFunctionResult, ChannelEndClaimed, ChannelEndClosed,
-    ClaimChannelEnd, ClaimChannelEndReply, ClaimChannelEndResult, ClearBusListenerFilters,
-    CloseChannelEnd, CloseChannelEndReply, CloseChannelEndResult, Connect2, ConnectData,
-    ConnectResult, CreateBusListener, CreateBusListenerReply, CreateChannel, CreateChannelReply,
-    CreateObject, CreateObjectReply, CreateObjectResult, CreateService, CreateService2,
-    CreateServiceReply, CreateServiceResult, DestroyBusListener, DestroyBusListenerReply,
-    DestroyBusListenerResult, DestroyObject, DestroyObjectReply, DestroyObjectResult,
-    DestroyService, DestroyServiceReply, DestroyServiceResult, EmitBusEvent, EmitEvent,
-    ItemReceived, Message, QueryIntrospection, QueryIntrospectionReply, QueryIntrospectionResult,
+    AbortFunctionCall, AckEvent, AddBusListenerFilter, AddChannelCapacity, AuthResponse,
+    BusListenerCurrentFinished, CallFunction, CallFunctionReply, CallFunctionResult,
+    ChannelEndClaimed, ChannelEndClosed, ClaimChannelEnd, ClaimChannelEndReply,
+    ClaimChannelEndResult, ClearBusListenerFilters, CloseChannelEnd, CloseChannelEndReply,
+    CloseChannelEndResult, Connect2, ConnectData, ConnectResult, CreateBusListener,
+    CreateBusListenerReply, CreateChannel, CreateChannelReply, CreateObject, CreateObjectReply,
+    CreateObjectResult, CreateService, CreateService2, CreateServiceReply, CreateServiceResult,
+    CreateServices, CreateServicesReply, CreateServicesResult, DestroyBusListener,
+    DestroyBusListenerReply, DestroyBusListenerResult, DestroyObject, DestroyObjectReply,
+    DestroyObjectResult, DestroyService, DestroyServiceReply, DestroyServiceResult,
+    DestroyServices, DestroyServicesReply, DestroyServicesResult, EmitBusEvent, EmitEvent,
+    EmitEventAcked, EmitEventAckedReply, EmitReliableEvent, EmitRetainedEvent, ItemReceived,
+    Message, QueryIntrospection, QueryIntrospectionReply, QueryIntrospectionResult,
     QueryServiceInfo, QueryServiceInfoReply, QueryServiceInfoResult, QueryServiceVersion,
-    QueryServiceVersionReply, QueryServiceVersionResult, RemoveBusListenerFilter, SendItem,
-    ServiceDestroyed, Shutdown, StartBusListener, StartBusListenerReply, StartBusListenerResult,
-    StopBusListener, StopBusListenerReply, StopBusListenerResult, SubscribeAllEvents,
-    SubscribeAllEventsReply, SubscribeAllEventsResult, SubscribeEvent, SubscribeEventReply,
-    SubscribeEventResult, SubscribeService, SubscribeServiceReply, SubscribeServiceResult, Sync,
-    SyncReply, UnsubscribeAllEvents, UnsubscribeAllEventsReply, UnsubscribeAllEventsResult,
-    UnsubscribeEvent, UnsubscribeService,
+    QueryServiceVersionReply, QueryServiceVersionResult, ReliableEventReceived,
+    RemoveBusListenerFilter, SendItem, ServiceDestroyed, Shutdown, StartBusListener,
+    StartBusListenerReply, StartBusListenerResult, StopBusListener, StopBusListenerReply,
+    StopBusListenerResult, SubscribeAllEvents, SubscribeAllEventsReply, SubscribeAllEventsResult,
+    SubscribeEvent, SubscribeEventReply, SubscribeEventResult, SubscribeService,
+    SubscribeServiceReply, SubscribeServiceResult, Sync, SyncReply, UnsubscribeAllEvents,
+    UnsubscribeAllEventsReply, UnsubscribeAllEventsResult, UnsubscribeEvent, UnsubscribeService,
 };
 use crate::core::transport::{AsyncTransport, AsyncTransportExt};
-#[cfg(feature = "introspection")]
-use crate::core::TypeId;
 use crate::core::{
-    BusListenerCookie, ChannelCookie, ChannelEnd, ChannelEndWithCapacity, Deserialize, ObjectId,
-    ProtocolVersion, Serialize, SerializedValue, SerializedValueSlice, ServiceCookie, ServiceId,
-    ServiceInfo,
+    BusListenerCookie, BusListenerFilter, CallPriority, ChannelCookie, ChannelEnd,
+    ChannelEndWithCapacity, Deserialize, ObjectId, ProtocolVersion, Serialize, SerializedValue,
+    SerializedValueSlice, ServiceCookie, ServiceId, ServiceInfo, TraceContext,
 };
+#[cfg(feature = "introspection")]
+use crate::core::{Compression, TypeId};
 use crate::error::{ConnectError, RunError};
 use crate::function_call_map::FunctionCallMap;
 #[cfg(feature = "introspection")]
 use crate::handle::request::QueryIntrospectionRequest;
 use crate::handle::request::{
-    CallFunctionReplyRequest, CallFunctionRequest, ClaimReceiverRequest, ClaimSenderRequest,
-    CloseChannelEndRequest, CreateBusListenerRequest, CreateClaimedReceiverRequest,
-    CreateClaimedSenderRequest, CreateLifetimeListenerRequest, CreateObjectRequest,
-    CreateProxyRequest, CreateServiceRequest, DestroyBusListenerRequest, DestroyObjectRequest,
-    DestroyServiceRequest, EmitEventRequest, HandleRequest, SendItemRequest,
-    StartBusListenerRequest, StopBusListenerRequest, SubscribeAllEventsRequest,
-    SubscribeEventRequest, SyncBrokerRequest, SyncClientRequest, UnsubscribeAllEventsRequest,
-    UnsubscribeEventRequest,
+    AddProxyEventStreamRequest, CallFunctionReplyRequest, CallFunctionRequest,
+    ClaimReceiverRequest, ClaimSenderRequest, CloseChannelEndRequest, CreateBroadcastRequest,
+    CreateBusListenerRequest, CreateClaimedReceiverRequest, CreateClaimedSenderRequest,
+    CreateLifetimeListenerRequest, CreateObjectRequest, CreateProxyRequest, CreateServiceRequest,
+    CreateServicesRequest, DestroyBusListenerRequest, DestroyObjectRequest, DestroyServiceRequest,
+    DestroyServicesRequest, EmitEventAckedRequest, EmitEventRequest, EmitKind,
+    EventSubscriptionsRequest, HandleRequest, SendItemRequest, StartBusListenerRequest,
+    StopBusListenerRequest, SubscribeAllEventsRequest, SubscribeEventRequest, SyncBrokerRequest,
+    SyncClientRequest, UnsubscribeAllEventsRequest, UnsubscribeEventRequest,
 };
 use crate::lifetime::LifetimeListener;
 use crate::low_level::{
-    PendingReceiver, PendingSender, ProxyId, RawCall, Service, UnclaimedReceiver, UnclaimedSender,
+    ChannelCapacity, EventSubscriptions, PendingReceiver, PendingSender, ProxyId, RawCall, Service,
+    UnclaimedReceiver, UnclaimedSender,
 };
 use crate::serial_map::SerialMap;
+#[cfg(feature = "introspection")]
+use crate::IntrospectionCache;
 use crate::{Error, Handle, Object};
 use broker_subscriptions::BrokerSubscriptions;
 use futures_channel::{mpsc, oneshot};
+use keep_alive::KeepAlive;
 use proxies::{Proxies, SubscribeResult};
+
+pub use builder::ClientBuilder;
+pub(crate) use call_timer::{CallTimer, Sleep};
+pub use request_queue::OverflowPolicy;
+pub(crate) use request_queue::{RequestQueue, RequestQueueGuard};
 use select::{Select, Selected};
 use std::collections::HashMap;
+#[cfg(feature = "introspection")]
+use std::collections::HashSet;
 use std::mem;
+use std::pin::Pin;
 
-const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_18;
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_19;
 
 /// Aldrin client used to connect to a broker.
 ///
@@ -93,6 +116,9 @@ where
     destroy_object: SerialMap<oneshot::Sender<DestroyObjectResult>>,
     create_service: SerialMap<CreateServiceRequest>,
     destroy_service: SerialMap<DestroyServiceRequest>,
+    create_services: SerialMap<CreateServicesRequest>,
+    destroy_services: SerialMap<DestroyServicesRequest>,
+    emit_event_acked: SerialMap<oneshot::Sender<Result<usize, Error>>>,
     function_calls: FunctionCallMap,
     services: HashMap<ServiceCookie, mpsc::UnboundedSender<RawCall>>,
     broker_subscriptions: BrokerSubscriptions,
@@ -102,6 +128,8 @@ where
     senders: HashMap<ChannelCookie, SenderState>,
     receivers: HashMap<ChannelCookie, ReceiverState>,
     sync: SerialMap<SyncBrokerRequest>,
+    keep_alive: Option<KeepAlive>,
+    queue: Option<RequestQueueGuard>,
     create_bus_listener: SerialMap<CreateBusListenerData>,
     destroy_bus_listener: SerialMap<DestroyBusListenerRequest>,
     start_bus_listener: SerialMap<StartBusListenerRequest>,
@@ -115,10 +143,18 @@ where
     subscribe_all_events: SerialMap<SubscribeAllEventsRequest>,
     unsubscribe_all_events: SerialMap<UnsubscribeAllEventsRequest>,
     proxies: Proxies,
+    raw: bool,
+    raw_messages: Vec<mpsc::UnboundedSender<Message>>,
     #[cfg(feature = "introspection")]
     introspection: HashMap<TypeId, SerializedValue>,
     #[cfg(feature = "introspection")]
-    query_introspection: SerialMap<QueryIntrospectionRequest>,
+    introspection_cache: IntrospectionCache,
+    #[cfg(feature = "introspection")]
+    submitted_introspection: HashSet<TypeId>,
+    #[cfg(feature = "introspection")]
+    query_introspection: SerialMap<TypeId>,
+    #[cfg(feature = "introspection")]
+    pending_introspection: HashMap<TypeId, Vec<oneshot::Sender<Option<SerializedValue>>>>,
 }
 
 impl<T> Client<T>
@@ -174,7 +210,7 @@ where
     /// After creating a client, it must be continuously polled and run to completion with the
     /// [`run`](Client::run) method.
     pub async fn connect_with_data<D: Serialize + ?Sized>(
-        mut t: T,
+        t: T,
         data: Option<&D>,
     ) -> Result<(Self, Option<SerializedValue>), ConnectError<T::Error>> {
         let mut connect_data = ConnectData::new();
@@ -183,6 +219,35 @@ where
             connect_data.serialize_user(data)?;
         }
 
+        Self::connect_impl(t, connect_data, None).await
+    }
+
+    /// Creates a [`ClientBuilder`] for connecting to a broker.
+    ///
+    /// The builder provides additional options, such as authentication with an
+    /// [`AuthProvider`](crate::AuthProvider).
+    pub fn builder(t: T) -> ClientBuilder<T> {
+        ClientBuilder::new(t)
+    }
+
+    pub(crate) async fn connect_impl(
+        mut t: T,
+        mut connect_data: ConnectData,
+        mut auth: Option<&mut (dyn AuthProvider + Send)>,
+    ) -> Result<(Self, Option<SerializedValue>), ConnectError<T::Error>> {
+        if let Some(ref mut auth) = auth {
+            connect_data.auth = Some(auth.credentials()?);
+        }
+
+        connect_data.compression = t.supported_compression().to_vec();
+        connect_data.fd_passing = t.supports_fd_passing();
+        connect_data.wire_formats = t.supported_wire_formats().to_vec();
+
+        // Limits, that don't fit into the length prefix of messages, are equivalent to no limit.
+        connect_data.max_message_size = t
+            .max_message_size()
+            .and_then(|size| u32::try_from(size).ok());
+
         let connect = Connect2::with_serialize_data(
             PROTOCOL_VERSION.major(),
             PROTOCOL_VERSION.minor(),
@@ -193,16 +258,40 @@ where
             .await
             .map_err(ConnectError::Transport)?;
 
-        let connect_reply = match t.receive().await.map_err(ConnectError::Transport)? {
-            Message::ConnectReply2(connect_reply) => connect_reply,
-            msg => return Err(ConnectError::UnexpectedMessageReceived(msg)),
+        let connect_reply = loop {
+            match t.receive().await.map_err(ConnectError::Transport)? {
+                Message::ConnectReply2(connect_reply) => break connect_reply,
+
+                Message::AuthChallenge(msg) => {
+                    let Some(ref mut auth) = auth else {
+                        return Err(ConnectError::UnexpectedMessageReceived(
+                            Message::AuthChallenge(msg),
+                        ));
+                    };
+
+                    let value = auth.respond(&msg.value)?;
+
+                    t.send_and_flush(AuthResponse { value })
+                        .await
+                        .map_err(ConnectError::Transport)?;
+                }
+
+                msg => return Err(ConnectError::UnexpectedMessageReceived(msg)),
+            }
         };
 
         let connect_reply_data = connect_reply.deserialize_connect_data()?;
 
         let minor_version = match connect_reply.result {
             ConnectResult::Ok(minor_version) => minor_version,
-            ConnectResult::Rejected => return Err(ConnectError::Rejected(connect_reply_data.user)),
+
+            ConnectResult::Rejected => {
+                return match connect_reply_data.auth_rejection {
+                    Some(reason) => Err(ConnectError::AuthenticationFailed(reason)),
+                    None => Err(ConnectError::Rejected(connect_reply_data.user)),
+                };
+            }
+
             ConnectResult::IncompatibleVersion => return Err(ConnectError::IncompatibleVersion),
         };
 
@@ -213,6 +302,43 @@ where
             return Err(ConnectError::IncompatibleVersion);
         }
 
+        if let Some(compression) = connect_reply_data.compression {
+            if !connect_data.compression.contains(&compression) {
+                return Err(ConnectError::UnexpectedMessageReceived(
+                    Message::ConnectReply2(connect_reply),
+                ));
+            }
+
+            Pin::new(&mut t).enable_compression(compression);
+        }
+
+        if connect_reply_data.fd_passing {
+            if !connect_data.fd_passing {
+                return Err(ConnectError::UnexpectedMessageReceived(
+                    Message::ConnectReply2(connect_reply),
+                ));
+            }
+
+            Pin::new(&mut t).enable_fd_passing();
+        }
+
+        if let Some(wire_format) = connect_reply_data.wire_format {
+            if !connect_data.wire_formats.contains(&wire_format) {
+                return Err(ConnectError::UnexpectedMessageReceived(
+                    Message::ConnectReply2(connect_reply),
+                ));
+            }
+
+            Pin::new(&mut t).enable_wire_format(wire_format);
+        }
+
+        if let Some(max_message_size) = connect_reply_data.max_message_size {
+            Pin::new(&mut t).set_peer_max_message_size(max_message_size as usize);
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(version = %protocol_version, "connected to broker");
+
         let (send, recv) = mpsc::unbounded();
         let client = Self {
             select: Select::new(),
@@ -225,6 +351,9 @@ where
             destroy_object: SerialMap::new(),
             create_service: SerialMap::new(),
             destroy_service: SerialMap::new(),
+            create_services: SerialMap::new(),
+            destroy_services: SerialMap::new(),
+            emit_event_acked: SerialMap::new(),
             function_calls: FunctionCallMap::new(),
             services: HashMap::new(),
             broker_subscriptions: BrokerSubscriptions::new(),
@@ -234,6 +363,8 @@ where
             senders: HashMap::new(),
             receivers: HashMap::new(),
             sync: SerialMap::new(),
+            keep_alive: None,
+            queue: None,
             create_bus_listener: SerialMap::new(),
             destroy_bus_listener: SerialMap::new(),
             start_bus_listener: SerialMap::new(),
@@ -247,10 +378,18 @@ where
             subscribe_all_events: SerialMap::new(),
             unsubscribe_all_events: SerialMap::new(),
             proxies: Proxies::new(),
+            raw: false,
+            raw_messages: Vec::new(),
             #[cfg(feature = "introspection")]
             introspection: HashMap::new(),
             #[cfg(feature = "introspection")]
+            introspection_cache: IntrospectionCache::new(),
+            #[cfg(feature = "introspection")]
+            submitted_introspection: HashSet::new(),
+            #[cfg(feature = "introspection")]
             query_introspection: SerialMap::new(),
+            #[cfg(feature = "introspection")]
+            pending_introspection: HashMap::new(),
         };
 
         Ok((client, connect_reply_data.user))
@@ -329,10 +468,36 @@ where
     /// automatically shut down when the last [`Handle`] has been dropped. Be aware, that some
     /// types (such as e.g. [`Service`]) hold an internal [`Handle`] and will thus keep the
     /// [`Client`] running. [`Client`s](Client) can also be instructed by the broker to shut down.
-    pub async fn run(mut self) -> Result<(), RunError<T::Error>> {
+    pub async fn run(self) -> Result<(), RunError<T::Error>> {
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::Instrument;
+
+            let span = tracing::debug_span!("aldrin_client", version = %self.protocol_version);
+            let res = self.run_impl().instrument(span.clone()).await;
+
+            span.in_scope(|| {
+                if res.is_ok() {
+                    tracing::debug!("client shut down");
+                } else {
+                    tracing::debug!("client failed");
+                }
+            });
+
+            res
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        self.run_impl().await
+    }
+
+    async fn run_impl(mut self) -> Result<(), RunError<T::Error>> {
         loop {
             match self.select().await {
                 Selected::Transport(Ok(Message::Shutdown(Shutdown))) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!("shutdown requested by broker");
+
                     self.t.send_and_flush(Shutdown).await?;
                     return Ok(());
                 }
@@ -342,6 +507,7 @@ where
                 Selected::Handle(HandleRequest::Shutdown) => break,
                 Selected::Handle(req) => self.handle_request(req).await?,
                 Selected::AbortFunctionCall(serial) => self.abort_function_call(serial).await?,
+                Selected::KeepAlive => self.keep_alive().await?,
             }
 
             if self.num_handles == 1 {
@@ -355,9 +521,37 @@ where
     }
 
     async fn select(&mut self) -> Selected<T> {
-        self.select
-            .select(&mut self.t, &mut self.recv, &mut self.function_calls)
+        let selected = self
+            .select
+            .select(
+                &mut self.t,
+                &mut self.recv,
+                &mut self.function_calls,
+                &mut self.keep_alive,
+            )
+            .await;
+
+        if let (Selected::Transport(Ok(_)), Some(keep_alive)) = (&selected, &mut self.keep_alive) {
+            keep_alive.received();
+        }
+
+        selected
+    }
+
+    async fn keep_alive(&mut self) -> Result<(), RunError<T::Error>> {
+        // Unwrap is fine, because the keep-alive can only elapse if it is set.
+        if !self.keep_alive.as_mut().unwrap().tick() {
+            return Err(RunError::KeepAliveTimeout);
+        }
+
+        // The reply is only needed to prove that the broker is alive. It is otherwise ignored.
+        let (send, _) = oneshot::channel();
+        let serial = self.sync.insert(send);
+
+        self.t
+            .send_and_flush(Sync { serial })
             .await
+            .map_err(Into::into)
     }
 
     async fn drain_transport(&mut self) -> Result<(), RunError<T::Error>> {
@@ -369,6 +563,22 @@ where
     }
 
     async fn handle_message(&mut self, msg: Message) -> Result<(), RunError<T::Error>> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(kind = ?msg.kind(), "message received");
+
+        if !self.raw_messages.is_empty() {
+            self.raw_messages
+                .retain(|send| send.unbounded_send(msg.clone()).is_ok());
+        }
+
+        match self.dispatch_message(msg).await {
+            // Replies to raw messages are unknown to the client.
+            Err(RunError::UnexpectedMessageReceived(_)) if self.raw => Ok(()),
+            res => res,
+        }
+    }
+
+    async fn dispatch_message(&mut self, msg: Message) -> Result<(), RunError<T::Error>> {
         match msg {
             Message::CreateObjectReply(msg) => self.msg_create_object_reply(msg)?,
             Message::DestroyObjectReply(msg) => self.msg_destroy_object_reply(msg),
@@ -411,6 +621,10 @@ where
             Message::UnsubscribeAllEventsReply(msg) => {
                 self.msg_unsubscribe_all_events_reply(msg)?
             }
+            Message::CreateServicesReply(msg) => self.msg_create_services_reply(msg)?,
+            Message::DestroyServicesReply(msg) => self.msg_destroy_services_reply(msg),
+            Message::EmitEventAckedReply(msg) => self.msg_emit_event_acked_reply(msg)?,
+            Message::ReliableEventReceived(msg) => self.msg_reliable_event_received(msg).await?,
 
             Message::Connect(_)
             | Message::ConnectReply(_)
@@ -437,9 +651,15 @@ where
             | Message::CreateService2(_)
             | Message::QueryServiceInfo(_)
             | Message::SubscribeService(_)
-            | Message::UnsubscribeService(_) => {
-                return Err(RunError::UnexpectedMessageReceived(msg))
-            }
+            | Message::UnsubscribeService(_)
+            | Message::AuthChallenge(_)
+            | Message::EmitEventAcked(_)
+            | Message::AuthResponse(_)
+            | Message::EmitRetainedEvent(_)
+            | Message::CreateServices(_)
+            | Message::DestroyServices(_)
+            | Message::EmitReliableEvent(_)
+            | Message::AckEvent(_) => return Err(RunError::UnexpectedMessageReceived(msg)),
 
             Message::Shutdown(Shutdown) => unreachable!(), // Handled in run.
         }
@@ -525,7 +745,77 @@ where
         let _ = req.reply.send(reply);
     }
 
+    fn msg_create_services_reply(
+        &mut self,
+        msg: CreateServicesReply,
+    ) -> Result<(), RunError<T::Error>> {
+        let Some(req) = self.create_services.remove(msg.serial) else {
+            return Err(RunError::UnexpectedMessageReceived(msg.into()));
+        };
+
+        let reply = match msg.result {
+            CreateServicesResult::Ok(ref cookies) if cookies.len() != req.services.len() => {
+                return Err(RunError::UnexpectedMessageReceived(msg.into()));
+            }
+
+            CreateServicesResult::Ok(cookies) => Ok(req
+                .services
+                .into_iter()
+                .zip(cookies)
+                .map(|((service_uuid, info), cookie)| {
+                    let (send, function_calls) = mpsc::unbounded();
+                    let dup = self.services.insert(cookie, send);
+                    debug_assert!(dup.is_none());
+
+                    Service::new_impl(
+                        ServiceId::new(req.object_id, service_uuid, cookie),
+                        info,
+                        self.handle.clone(),
+                        function_calls,
+                    )
+                })
+                .collect()),
+
+            CreateServicesResult::DuplicateService => Err(Error::DuplicateService),
+            CreateServicesResult::InvalidObject => Err(Error::InvalidObject),
+            CreateServicesResult::ForeignObject => unreachable!(),
+        };
+
+        let _ = req.reply.send(reply);
+        Ok(())
+    }
+
+    fn msg_destroy_services_reply(&mut self, msg: DestroyServicesReply) {
+        let Some(req) = self.destroy_services.remove(msg.serial) else {
+            return;
+        };
+
+        let reply = match msg.result {
+            DestroyServicesResult::Ok => {
+                for id in req.ids {
+                    self.services.remove(&id.cookie);
+                    self.broker_subscriptions.remove_service(id.cookie);
+                }
+
+                Ok(())
+            }
+
+            DestroyServicesResult::InvalidService => Err(Error::InvalidService),
+            DestroyServicesResult::ForeignObject => unreachable!(),
+        };
+
+        let _ = req.reply.send(reply);
+    }
+
     async fn msg_call_function(&mut self, msg: CallFunction) -> Result<(), RunError<T::Error>> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            serial = msg.serial,
+            service = %msg.service_cookie,
+            function = msg.function,
+            "function call received",
+        );
+
         let send = self
             .services
             .get_mut(&msg.service_cookie)
@@ -537,6 +827,7 @@ where
             serial: msg.serial,
             function: msg.function,
             args: msg.value,
+            trace_context: msg.trace_context,
             aborted: abort_recv,
         };
 
@@ -556,17 +847,34 @@ where
     }
 
     fn msg_call_function_reply(&mut self, msg: CallFunctionReply) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(serial = msg.serial, "function call finished");
+
         if let Some(send) = self.function_calls.remove(msg.serial) {
-            let _ = send.send(Ok(msg.result));
+            let _ = send.send(Ok((msg.serial, msg.result)));
         }
     }
 
     fn msg_subscribe_event(&mut self, msg: SubscribeEvent) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            service = %msg.service_cookie,
+            event = msg.event,
+            "event subscribed by broker",
+        );
+
         self.broker_subscriptions
             .subscribe(msg.service_cookie, msg.event);
     }
 
     fn msg_unsubscribe_event(&mut self, msg: UnsubscribeEvent) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            service = %msg.service_cookie,
+            event = msg.event,
+            "event unsubscribed by broker",
+        );
+
         self.broker_subscriptions
             .unsubscribe(msg.service_cookie, msg.event);
     }
@@ -586,6 +894,16 @@ where
                 Ok(())
             }
 
+            Some(CreateChannelData::Broadcast(reply)) => {
+                let (send, recv) = mpsc::unbounded();
+                let dup = self
+                    .senders
+                    .insert(msg.cookie, SenderState::Established(send));
+                debug_assert!(dup.is_none());
+                let _ = reply.send(Ok((msg.cookie, recv)));
+                Ok(())
+            }
+
             Some(CreateChannelData::Receiver(req)) => {
                 let (send, recv) = oneshot::channel();
                 let sender = UnclaimedSender::new(self.handle.clone(), msg.cookie);
@@ -595,7 +913,7 @@ where
                     .receivers
                     .insert(msg.cookie, ReceiverState::Pending(send));
                 debug_assert!(dup.is_none());
-                let _ = req.reply.send((sender, receiver));
+                let _ = req.reply.send(Ok((sender, receiver)));
                 Ok(())
             }
 
@@ -697,7 +1015,16 @@ where
                         .senders
                         .insert(req.cookie, SenderState::Established(send));
                     debug_assert!(dup.is_none());
-                    let _ = req.reply.send(Ok((recv, capacity)));
+                    let _ = req.reply.send(Ok((recv, ChannelCapacity::Items(capacity))));
+                }
+
+                ClaimChannelEndResult::SenderClaimedBytes(capacity) => {
+                    let (send, recv) = mpsc::unbounded();
+                    let dup = self
+                        .senders
+                        .insert(req.cookie, SenderState::Established(send));
+                    debug_assert!(dup.is_none());
+                    let _ = req.reply.send(Ok((recv, ChannelCapacity::Bytes(capacity))));
                 }
 
                 ClaimChannelEndResult::ReceiverClaimed => {
@@ -710,7 +1037,8 @@ where
             },
 
             ClaimChannelEndData::Receiver(req) => match msg.result {
-                ClaimChannelEndResult::SenderClaimed(_) => {
+                ClaimChannelEndResult::SenderClaimed(_)
+                | ClaimChannelEndResult::SenderClaimedBytes(_) => {
                     return Err(RunError::UnexpectedMessageReceived(msg.into()))
                 }
 
@@ -756,7 +1084,13 @@ where
                 }
             }
 
-            ChannelEndWithCapacity::Receiver(capacity) => {
+            ChannelEndWithCapacity::BroadcastSender => {
+                Err(RunError::UnexpectedMessageReceived(msg.into()))
+            }
+
+            ChannelEndWithCapacity::Receiver(_) | ChannelEndWithCapacity::ReceiverBytes(_) => {
+                let capacity = ChannelCapacity::from_core(msg.end).unwrap();
+
                 let Some(sender) = self.senders.get_mut(&msg.cookie) else {
                     return Err(RunError::UnexpectedMessageReceived(msg.into()));
                 };
@@ -804,6 +1138,18 @@ where
         Ok(())
     }
 
+    fn msg_emit_event_acked_reply(
+        &mut self,
+        msg: EmitEventAckedReply,
+    ) -> Result<(), RunError<T::Error>> {
+        let Some(reply) = self.emit_event_acked.remove(msg.serial) else {
+            return Err(RunError::UnexpectedMessageReceived(msg.into()));
+        };
+
+        let _ = reply.send(Ok(msg.delivered as usize));
+        Ok(())
+    }
+
     fn msg_create_bus_listener_reply(
         &mut self,
         msg: CreateBusListenerReply,
@@ -907,7 +1253,7 @@ where
                 return Err(RunError::UnexpectedMessageReceived(msg.into()));
             };
 
-            if bus_listener.emit_current(msg.event) {
+            if bus_listener.emit_addressed(msg.event) {
                 Ok(())
             } else {
                 Err(RunError::UnexpectedMessageReceived(msg.into()))
@@ -995,18 +1341,36 @@ where
             return Err(RunError::UnexpectedMessageReceived(msg.into()));
         }
 
-        let Some(req) = self.query_introspection.remove(msg.serial) else {
+        let Some(type_id) = self.query_introspection.remove(msg.serial) else {
             return Err(RunError::UnexpectedMessageReceived(msg.into()));
         };
 
-        match msg.result {
-            QueryIntrospectionResult::Ok(introspection) => {
-                let _ = req.reply.send(Some(introspection));
-            }
+        let pending = self
+            .pending_introspection
+            .remove(&type_id)
+            .unwrap_or_default();
 
-            QueryIntrospectionResult::Unavailable => {
-                let _ = req.reply.send(None);
-            }
+        // TypeIds are computed from the introspection itself. Cached entries can thus never become
+        // outdated. Unavailable types are not cached, because they may be registered later.
+        //
+        // Bundles contain referenced types as well. All of them are cached, so that they need not
+        // be queried separately.
+        let mut introspections = msg.result.into_introspections(type_id).unwrap_or_default();
+
+        let introspection = introspections.remove(&type_id);
+
+        if let Some(ref introspection) = introspection {
+            self.introspection_cache
+                .insert_serialized(type_id, introspection.clone());
+        }
+
+        for (type_id, introspection) in introspections {
+            self.introspection_cache
+                .insert_serialized(type_id, introspection);
+        }
+
+        for reply in pending {
+            let _ = reply.send(introspection.clone());
         }
 
         Ok(())
@@ -1111,8 +1475,36 @@ where
         }
     }
 
-    fn msg_emit_event(&self, msg: EmitEvent) {
-        self.proxies.emit(msg.service_cookie, msg.event, msg.value);
+    fn msg_emit_event(&mut self, msg: EmitEvent) {
+        self.proxies.emit(
+            msg.service_cookie,
+            msg.event,
+            msg.value,
+            msg.trace_context,
+            None,
+        );
+    }
+
+    async fn msg_reliable_event_received(
+        &mut self,
+        msg: ReliableEventReceived,
+    ) -> Result<(), RunError<T::Error>> {
+        let delivered = self.proxies.emit(
+            msg.service_cookie,
+            msg.event,
+            msg.value,
+            msg.trace_context,
+            Some(msg.serial),
+        );
+
+        // No proxy can acknowledge the event, which would otherwise stay buffered in the broker.
+        if !delivered {
+            self.t
+                .send_and_flush(AckEvent { serial: msg.serial })
+                .await?;
+        }
+
+        Ok(())
     }
 
     fn msg_service_destroyed(&mut self, msg: ServiceDestroyed) {
@@ -1141,6 +1533,9 @@ where
         msg: SubscribeAllEvents,
     ) -> Result<(), RunError<T::Error>> {
         if (self.protocol_version >= ProtocolVersion::V1_18) && msg.serial.is_none() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(service = %msg.service_cookie, "all events subscribed by broker");
+
             self.broker_subscriptions.subscribe_all(msg.service_cookie);
             Ok(())
         } else {
@@ -1174,6 +1569,9 @@ where
         msg: UnsubscribeAllEvents,
     ) -> Result<(), RunError<T::Error>> {
         if (self.protocol_version >= ProtocolVersion::V1_18) && msg.serial.is_none() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(service = %msg.service_cookie, "all events unsubscribed by broker");
+
             self.broker_subscriptions
                 .unsubscribe_all(msg.service_cookie);
             Ok(())
@@ -1204,6 +1602,12 @@ where
     }
 
     async fn handle_request(&mut self, req: HandleRequest) -> Result<(), RunError<T::Error>> {
+        if let Some(ref queue) = self.queue {
+            if req.is_queued() {
+                queue.0.release();
+            }
+        }
+
         match req {
             HandleRequest::HandleCloned => self.req_handle_cloned(),
             HandleRequest::HandleDropped => self.req_handle_dropped(),
@@ -1211,10 +1615,16 @@ where
             HandleRequest::DestroyObject(req) => self.req_destroy_object(req).await?,
             HandleRequest::CreateService(req) => self.req_create_service(req).await?,
             HandleRequest::DestroyService(req) => self.req_destroy_service(req).await?,
+            HandleRequest::CreateServices(req) => self.req_create_services(req).await?,
+            HandleRequest::DestroyServices(req) => self.req_destroy_services(req).await?,
             HandleRequest::CallFunction(req) => self.req_call_function(req).await?,
             HandleRequest::CallFunctionReply(req) => self.req_call_function_reply(req).await?,
             HandleRequest::EmitEvent(req) => self.req_emit_event(req).await?,
+            HandleRequest::EmitEventAcked(req) => self.req_emit_event_acked(req).await?,
+            HandleRequest::AckEvent(serial) => self.req_ack_event(serial).await?,
+            HandleRequest::EventSubscriptions(req) => self.req_event_subscriptions(req),
             HandleRequest::CreateClaimedSender(req) => self.req_create_claimed_sender(req).await?,
+            HandleRequest::CreateBroadcast(req) => self.req_create_broadcast(req).await?,
             HandleRequest::CreateClaimedReceiver(req) => {
                 self.req_create_claimed_receiver(req).await?
             }
@@ -1246,12 +1656,15 @@ where
             }
             HandleRequest::CreateProxy(req) => self.req_create_proxy(req).await?,
             HandleRequest::DestroyProxy(proxy) => self.req_destroy_proxy(proxy).await?,
+            HandleRequest::AddProxyEventStream(req) => self.req_add_proxy_event_stream(req),
             HandleRequest::SubscribeEvent(req) => self.req_subscribe_event(req).await?,
             HandleRequest::UnsubscribeEvent(req) => self.req_unsubscribe_event(req).await?,
             HandleRequest::SubscribeAllEvents(req) => self.req_subscribe_all_events(req).await?,
             HandleRequest::UnsubscribeAllEvents(req) => {
                 self.req_unsubscribe_all_events(req).await?
             }
+            HandleRequest::SendRawMessage(msg) => self.req_send_raw_message(msg).await?,
+            HandleRequest::RawMessages(send) => self.req_raw_messages(send),
             #[cfg(feature = "introspection")]
             HandleRequest::RegisterIntrospection(ty) => self.req_register_introspection(ty),
             #[cfg(feature = "introspection")]
@@ -1351,18 +1764,71 @@ where
             .map_err(Into::into)
     }
 
+    async fn req_create_services(
+        &mut self,
+        req: CreateServicesRequest,
+    ) -> Result<(), RunError<T::Error>> {
+        if self.protocol_version < ProtocolVersion::V1_19 {
+            let _ = req.reply.send(Err(Error::NotSupported));
+            return Ok(());
+        }
+
+        let object_cookie = req.object_id.cookie;
+
+        let services = req
+            .services
+            .iter()
+            .map(|(uuid, info)| (*uuid, info.to_core().set_subscribe_all(true)))
+            .collect::<Vec<_>>();
+
+        let serial = self.create_services.insert(req);
+
+        let msg = CreateServices::with_serialize_services(serial, object_cookie, &services)
+            .map_err(RunError::Serialize)?;
+
+        self.t.send_and_flush(msg).await.map_err(Into::into)
+    }
+
+    async fn req_destroy_services(
+        &mut self,
+        req: DestroyServicesRequest,
+    ) -> Result<(), RunError<T::Error>> {
+        if self.protocol_version < ProtocolVersion::V1_19 {
+            let _ = req.reply.send(Err(Error::NotSupported));
+            return Ok(());
+        }
+
+        let cookies = req.ids.iter().map(|id| id.cookie).collect();
+        let serial = self.destroy_services.insert(req);
+
+        self.t
+            .send_and_flush(DestroyServices { serial, cookies })
+            .await
+            .map_err(Into::into)
+    }
+
     async fn req_call_function(
         &mut self,
         req: CallFunctionRequest,
     ) -> Result<(), RunError<T::Error>> {
         let serial = self.function_calls.insert(req.reply);
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            serial,
+            service = %req.service_cookie,
+            function = req.function,
+            "function call started",
+        );
+
         self.t
             .send_and_flush(CallFunction {
                 serial,
                 service_cookie: req.service_cookie,
                 function: req.function,
                 value: req.value,
+                trace_context: self.trace_context(req.trace_context),
+                priority: self.call_priority(req.priority),
             })
             .await
             .map_err(Into::into)
@@ -1374,6 +1840,9 @@ where
     ) -> Result<(), RunError<T::Error>> {
         self.abort_call_handles.remove(&req.serial);
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(serial = req.serial, "function call replied");
+
         self.t
             .send_and_flush(CallFunctionReply {
                 serial: req.serial,
@@ -1384,7 +1853,31 @@ where
     }
 
     async fn req_emit_event(&mut self, req: EmitEventRequest) -> Result<(), RunError<T::Error>> {
-        if self
+        let supported = self.protocol_version >= ProtocolVersion::V1_19;
+
+        if (req.kind == EmitKind::Retained) && supported {
+            // Retained events must always reach the broker, because it stores them for future
+            // subscribers.
+            self.t
+                .send_and_flush(EmitRetainedEvent {
+                    service_cookie: req.service_cookie,
+                    event: req.event,
+                    value: req.value,
+                    trace_context: self.trace_context(req.trace_context),
+                })
+                .await?
+        } else if (req.kind == EmitKind::Reliable) && supported {
+            // Reliable events must always reach the broker as well, because it buffers them for
+            // subscribers, that are currently disconnected.
+            self.t
+                .send_and_flush(EmitReliableEvent {
+                    service_cookie: req.service_cookie,
+                    event: req.event,
+                    value: req.value,
+                    trace_context: self.trace_context(req.trace_context),
+                })
+                .await?
+        } else if self
             .broker_subscriptions
             .emit(req.service_cookie, req.event)
         {
@@ -1393,6 +1886,7 @@ where
                     service_cookie: req.service_cookie,
                     event: req.event,
                     value: req.value,
+                    trace_context: self.trace_context(req.trace_context),
                 })
                 .await?
         }
@@ -1400,6 +1894,69 @@ where
         Ok(())
     }
 
+    async fn req_emit_event_acked(
+        &mut self,
+        req: EmitEventAckedRequest,
+    ) -> Result<(), RunError<T::Error>> {
+        if self.protocol_version < ProtocolVersion::V1_19 {
+            let _ = req.reply.send(Err(Error::NotSupported));
+            return Ok(());
+        }
+
+        // Acknowledged events always go to the broker, even if there are no subscribers, because
+        // only the broker can reply with the number of deliveries.
+        let serial = self.emit_event_acked.insert(req.reply);
+
+        self.t
+            .send_and_flush(EmitEventAcked {
+                serial,
+                service_cookie: req.service_cookie,
+                event: req.event,
+                value: req.value,
+                trace_context: req.trace_context,
+            })
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn req_ack_event(&mut self, serial: u32) -> Result<(), RunError<T::Error>> {
+        self.t
+            .send_and_flush(AckEvent { serial })
+            .await
+            .map_err(Into::into)
+    }
+
+    fn req_event_subscriptions(&mut self, req: EventSubscriptionsRequest) {
+        let res = if self.services.contains_key(&req.service_cookie) {
+            let (send, recv) = mpsc::unbounded();
+            let (events, all_events) = self
+                .broker_subscriptions
+                .add_listener(req.service_cookie, send);
+
+            Ok(EventSubscriptions::new(events, all_events, recv))
+        } else {
+            Err(Error::InvalidService)
+        };
+
+        let _ = req.reply.send(res);
+    }
+
+    fn trace_context(&self, trace_context: Option<TraceContext>) -> Option<TraceContext> {
+        if self.protocol_version >= ProtocolVersion::V1_19 {
+            trace_context
+        } else {
+            None
+        }
+    }
+
+    fn call_priority(&self, priority: CallPriority) -> CallPriority {
+        if self.protocol_version >= ProtocolVersion::V1_19 {
+            priority
+        } else {
+            CallPriority::Normal
+        }
+    }
+
     async fn req_create_claimed_sender(
         &mut self,
         req: CreateClaimedSenderRequest,
@@ -1415,22 +1972,46 @@ where
             .map_err(Into::into)
     }
 
-    async fn req_create_claimed_receiver(
+    async fn req_create_broadcast(
         &mut self,
-        req: CreateClaimedReceiverRequest,
+        req: CreateBroadcastRequest,
     ) -> Result<(), RunError<T::Error>> {
-        let capacity = req.capacity.get();
-        let serial = self.create_channel.insert(CreateChannelData::Receiver(req));
+        if self.protocol_version < ProtocolVersion::V1_19 {
+            let _ = req.send(Err(Error::NotSupported));
+            return Ok(());
+        }
+
+        let serial = self
+            .create_channel
+            .insert(CreateChannelData::Broadcast(req));
 
         self.t
             .send_and_flush(CreateChannel {
                 serial,
-                end: ChannelEndWithCapacity::Receiver(capacity),
+                end: ChannelEndWithCapacity::BroadcastSender,
             })
             .await
             .map_err(Into::into)
     }
 
+    async fn req_create_claimed_receiver(
+        &mut self,
+        req: CreateClaimedReceiverRequest,
+    ) -> Result<(), RunError<T::Error>> {
+        if req.capacity.is_bytes() && self.protocol_version < ProtocolVersion::V1_19 {
+            let _ = req.reply.send(Err(Error::NotSupported));
+            return Ok(());
+        }
+
+        let end = req.capacity.to_core();
+        let serial = self.create_channel.insert(CreateChannelData::Receiver(req));
+
+        self.t
+            .send_and_flush(CreateChannel { serial, end })
+            .await
+            .map_err(Into::into)
+    }
+
     async fn req_close_channel_end(
         &mut self,
         req: CloseChannelEndRequest,
@@ -1474,8 +2055,13 @@ where
         &mut self,
         req: ClaimReceiverRequest,
     ) -> Result<(), RunError<T::Error>> {
+        if req.capacity.is_bytes() && self.protocol_version < ProtocolVersion::V1_19 {
+            let _ = req.reply.send(Err(Error::NotSupported));
+            return Ok(());
+        }
+
         let cookie = req.cookie;
-        let capacity = req.capacity.get();
+        let end = req.capacity.to_core();
 
         let serial = self
             .claim_channel_end
@@ -1485,7 +2071,7 @@ where
             .send_and_flush(ClaimChannelEnd {
                 serial,
                 cookie,
-                end: ChannelEndWithCapacity::Receiver(capacity),
+                end,
             })
             .await
             .map_err(Into::into)
@@ -1559,6 +2145,12 @@ where
             return Ok(());
         };
 
+        if let BusListenerFilter::Layout(_) = req.filter {
+            if self.protocol_version < ProtocolVersion::V1_19 {
+                return Ok(());
+            }
+        }
+
         self.t.send_and_flush(req).await?;
         bus_listener.add_filter(req.filter);
 
@@ -1655,6 +2247,10 @@ where
         self.t.send_and_flush(msg).await.map_err(Into::into)
     }
 
+    fn req_add_proxy_event_stream(&mut self, req: AddProxyEventStreamRequest) {
+        self.proxies.add_event_stream(req.proxy, req.send);
+    }
+
     async fn req_destroy_proxy(&mut self, proxy: ProxyId) -> Result<(), RunError<T::Error>> {
         if let Some(res) = self.proxies.remove(proxy) {
             if res.unsubscribe && (self.protocol_version >= ProtocolVersion::V1_18) {
@@ -1699,6 +2295,9 @@ where
                 let event = req.event;
                 let serial = self.subscribe_event.insert(req);
 
+                #[cfg(feature = "tracing")]
+                tracing::debug!(serial, service = %service_cookie, event, "subscribing event");
+
                 self.t
                     .send_and_flush(SubscribeEvent {
                         serial: Some(serial),
@@ -1726,6 +2325,9 @@ where
     ) -> Result<(), RunError<T::Error>> {
         match self.proxies.unsubscribe(req.proxy, req.event) {
             SubscribeResult::Forward(service_cookie) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(service = %service_cookie, event = req.event, "unsubscribing event");
+
                 self.t
                     .send_and_flush(UnsubscribeEvent {
                         service_cookie,
@@ -1757,6 +2359,9 @@ where
                 SubscribeResult::Forward(service_cookie) => {
                     let serial = self.subscribe_all_events.insert(req);
 
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(serial, service = %service_cookie, "subscribing all events");
+
                     self.t
                         .send_and_flush(SubscribeAllEvents {
                             serial: Some(serial),
@@ -1789,6 +2394,9 @@ where
             return Ok(());
         };
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(service = %res.service, "unsubscribing events");
+
         for event in res.events {
             self.t
                 .send(UnsubscribeEvent {
@@ -1816,6 +2424,16 @@ where
         Ok(())
     }
 
+    async fn req_send_raw_message(&mut self, msg: Message) -> Result<(), RunError<T::Error>> {
+        self.raw = true;
+        self.t.send_and_flush(msg).await.map_err(Into::into)
+    }
+
+    fn req_raw_messages(&mut self, send: mpsc::UnboundedSender<Message>) {
+        self.raw = true;
+        self.raw_messages.push(send);
+    }
+
     #[cfg(feature = "introspection")]
     fn req_register_introspection(&mut self, ty: DynIntrospectable) {
         use std::collections::hash_map::Entry;
@@ -1842,19 +2460,29 @@ where
     async fn req_submit_introspection(&mut self) -> Result<(), RunError<T::Error>> {
         use crate::core::message::RegisterIntrospection;
 
-        if (self.protocol_version >= ProtocolVersion::V1_17) && !self.introspection.is_empty() {
-            let type_ids = self.introspection.keys().copied().collect();
+        if self.protocol_version < ProtocolVersion::V1_17 {
+            return Ok(());
+        }
 
-            let register_introspection = RegisterIntrospection::with_serialize_type_ids(&type_ids)
-                .map_err(RunError::Serialize)?;
+        // Only types, that haven't been submitted before, are sent to the broker. Submitting is
+        // thus idempotent and cheap when nothing new has been registered.
+        let type_ids: HashSet<_> = self
+            .introspection
+            .keys()
+            .filter(|type_id| !self.submitted_introspection.contains(type_id))
+            .copied()
+            .collect();
 
-            self.t
-                .send_and_flush(register_introspection)
-                .await
-                .map_err(Into::into)
-        } else {
-            Ok(())
+        if type_ids.is_empty() {
+            return Ok(());
         }
+
+        let register_introspection = RegisterIntrospection::with_serialize_type_ids(&type_ids)
+            .map_err(RunError::Serialize)?;
+
+        self.t.send_and_flush(register_introspection).await?;
+        self.submitted_introspection.extend(type_ids);
+        Ok(())
     }
 
     #[cfg(feature = "introspection")]
@@ -1862,17 +2490,43 @@ where
         &mut self,
         req: QueryIntrospectionRequest,
     ) -> Result<(), RunError<T::Error>> {
-        if let Some(introspection) = self.introspection.get(&req.type_id) {
-            let _ = req.reply.send(Some(introspection.clone()));
+        use std::collections::hash_map::Entry;
+
+        let introspection = self
+            .introspection
+            .get(&req.type_id)
+            .cloned()
+            .or_else(|| self.introspection_cache.get_serialized(req.type_id));
+
+        if let Some(introspection) = introspection {
+            let _ = req.reply.send(Some(introspection));
             Ok(())
         } else if self.protocol_version >= ProtocolVersion::V1_17 {
-            let type_id = req.type_id;
-            let serial = self.query_introspection.insert(req);
+            match self.pending_introspection.entry(req.type_id) {
+                // Another query for the same type is already in flight.
+                Entry::Occupied(mut pending) => {
+                    pending.get_mut().push(req.reply);
+                    Ok(())
+                }
 
-            self.t
-                .send_and_flush(QueryIntrospection { serial, type_id })
-                .await
-                .map_err(Into::into)
+                Entry::Vacant(pending) => {
+                    pending.insert(vec![req.reply]);
+
+                    let type_id = req.type_id;
+                    let serial = self.query_introspection.insert(type_id);
+                    let mut msg = QueryIntrospection::new(serial, type_id);
+
+                    // Advertise all known types, so that the broker can leave them out of the
+                    // bundle it replies with.
+                    if self.protocol_version >= ProtocolVersion::V1_19 {
+                        msg.known.extend(self.introspection.keys().copied());
+                        msg.known.extend(self.introspection_cache.type_ids());
+                        msg.compression = Compression::SUPPORTED.first().copied();
+                    }
+
+                    self.t.send_and_flush(msg).await.map_err(Into::into)
+                }
+            }
         } else {
             let _ = req.reply.send(None);
             Ok(())
@@ -1894,6 +2548,7 @@ where
 enum CreateChannelData {
     Sender(CreateClaimedSenderRequest),
     Receiver(CreateClaimedReceiverRequest),
+    Broadcast(CreateBroadcastRequest),
 }
 
 #[derive(Debug)]
@@ -1904,7 +2559,7 @@ enum ClaimChannelEndData {
 
 #[derive(Debug)]
 enum SenderState {
-    Pending(oneshot::Sender<Result<(mpsc::UnboundedReceiver<u32>, u32), Error>>),
+    Pending(oneshot::Sender<Result<(mpsc::UnboundedReceiver<u32>, ChannelCapacity), Error>>),
     Established(mpsc::UnboundedSender<u32>),
     ReceiverClosed,
 }
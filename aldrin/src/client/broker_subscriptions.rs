@@ -1,40 +1,83 @@
+use crate::low_level::SubscriptionChange;
 use aldrin_core::ServiceCookie;
+use futures_channel::mpsc::UnboundedSender;
 use std::collections::hash_map::{Entry, HashMap};
 use std::collections::HashSet;
+use std::mem;
 
 #[derive(Debug)]
 pub(crate) struct BrokerSubscriptions {
     entries: HashMap<ServiceCookie, Service>,
+    listeners: HashMap<ServiceCookie, Vec<UnboundedSender<SubscriptionChange>>>,
 }
 
 impl BrokerSubscriptions {
     pub fn new() -> Self {
         Self {
             entries: HashMap::new(),
+            listeners: HashMap::new(),
         }
     }
 
     pub fn subscribe(&mut self, service: ServiceCookie, event: u32) {
-        self.entries.entry(service).or_default().subscribe(event);
+        if self.entries.entry(service).or_default().subscribe(event) {
+            self.notify(service, SubscriptionChange::Subscribed(event));
+        }
     }
 
     pub fn unsubscribe(&mut self, service: ServiceCookie, event: u32) {
         if let Entry::Occupied(mut entry) = self.entries.entry(service) {
-            entry.get_mut().unsubscribe(event);
+            let changed = entry.get_mut().unsubscribe(event);
 
             if entry.get().is_empty() {
                 entry.remove();
             }
+
+            if changed {
+                self.notify(service, SubscriptionChange::Unsubscribed(event));
+            }
         }
     }
 
     pub fn subscribe_all(&mut self, service: ServiceCookie) {
-        self.entries.entry(service).or_default().subscribe_all();
+        if self.entries.entry(service).or_default().subscribe_all() {
+            self.notify(service, SubscriptionChange::AllSubscribed);
+        }
     }
 
     pub fn unsubscribe_all(&mut self, service: ServiceCookie) {
         if let Entry::Occupied(mut entry) = self.entries.entry(service) {
-            entry.get_mut().unsubscribe_all();
+            let changed = entry.get_mut().unsubscribe_all();
+
+            if entry.get().is_empty() {
+                entry.remove();
+            }
+
+            if changed {
+                self.notify(service, SubscriptionChange::AllUnsubscribed);
+            }
+        }
+    }
+
+    /// Adds a listener for subscription changes and returns the current subscriptions.
+    pub fn add_listener(
+        &mut self,
+        service: ServiceCookie,
+        listener: UnboundedSender<SubscriptionChange>,
+    ) -> (HashSet<u32>, bool) {
+        self.listeners.entry(service).or_default().push(listener);
+
+        self.entries
+            .get(&service)
+            .map(|entry| (entry.events.clone(), entry.all_events))
+            .unwrap_or_default()
+    }
+
+    fn notify(&mut self, service: ServiceCookie, change: SubscriptionChange) {
+        if let Entry::Occupied(mut entry) = self.listeners.entry(service) {
+            entry
+                .get_mut()
+                .retain(|listener| listener.unbounded_send(change).is_ok());
 
             if entry.get().is_empty() {
                 entry.remove();
@@ -51,6 +94,7 @@ impl BrokerSubscriptions {
 
     pub fn remove_service(&mut self, service: ServiceCookie) {
         self.entries.remove(&service);
+        self.listeners.remove(&service);
     }
 }
 
@@ -65,20 +109,20 @@ impl Service {
         !self.all_events && self.events.is_empty()
     }
 
-    fn subscribe(&mut self, event: u32) {
-        self.events.insert(event);
+    fn subscribe(&mut self, event: u32) -> bool {
+        self.events.insert(event)
     }
 
-    fn unsubscribe(&mut self, event: u32) {
-        self.events.remove(&event);
+    fn unsubscribe(&mut self, event: u32) -> bool {
+        self.events.remove(&event)
     }
 
-    fn subscribe_all(&mut self) {
-        self.all_events = true;
+    fn subscribe_all(&mut self) -> bool {
+        !mem::replace(&mut self.all_events, true)
     }
 
-    fn unsubscribe_all(&mut self) {
-        self.all_events = false;
+    fn unsubscribe_all(&mut self) -> bool {
+        mem::replace(&mut self.all_events, false)
     }
 
     fn emit(&self, event: u32) -> bool {
@@ -0,0 +1,386 @@
+use super::call_timer::CallTimer;
+use super::keep_alive::KeepAlive;
+use super::{Client, OverflowPolicy, RequestQueue, RequestQueueGuard};
+use crate::auth::AuthProvider;
+use crate::core::message::ConnectData;
+use crate::core::transport::AsyncTransport;
+use crate::core::{Serialize, SerializedValue};
+use crate::error::ConnectError;
+#[cfg(feature = "introspection")]
+use crate::IntrospectionCache;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Builder for connecting a [`Client`] to a broker.
+///
+/// Create a [`ClientBuilder`] with either [`Client::builder`] or [`ClientBuilder::new`].
+///
+/// # Examples
+///
+/// ```
+/// use aldrin::Client;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let broker = aldrin_test::tokio::TestBroker::new();
+/// # let mut handle = broker.clone();
+/// # let (async_transport, t2) = aldrin::core::channel::unbounded();
+/// # let conn = tokio::spawn(async move { handle.connect(t2).await });
+/// let (client, data) = Client::builder(async_transport)
+///     .with_serialize_data(&"Hi!")?
+///     .connect_with_data()
+///     .await?;
+/// # tokio::spawn(conn.await??.run());
+///
+/// println!("Data the broker sent back: {:?}.", data);
+/// # let handle = client.handle().clone();
+/// # let join = tokio::spawn(client.run());
+/// # handle.shutdown();
+/// # join.await??;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ClientBuilder<T> {
+    t: T,
+    data: Option<SerializedValue>,
+    auth: Option<Box<dyn AuthProvider + Send>>,
+    keep_alive: Option<KeepAlive>,
+    timer: Option<CallTimer>,
+    queue: Option<(usize, OverflowPolicy)>,
+    name: Option<String>,
+    metadata: HashMap<String, String>,
+    session: Option<Uuid>,
+    namespace: Option<String>,
+    visible_namespaces: Vec<String>,
+    #[cfg(feature = "introspection")]
+    introspection_cache: Option<IntrospectionCache>,
+}
+
+impl<T> ClientBuilder<T>
+where
+    T: AsyncTransport + Unpin,
+{
+    /// Creates a new `ClientBuilder` for the given transport.
+    pub fn new(t: T) -> Self {
+        Self {
+            t,
+            data: None,
+            auth: None,
+            keep_alive: None,
+            timer: None,
+            queue: None,
+            name: None,
+            metadata: HashMap::new(),
+            session: None,
+            namespace: None,
+            visible_namespaces: Vec::new(),
+            #[cfg(feature = "introspection")]
+            introspection_cache: None,
+        }
+    }
+
+    /// Sets custom data, that is sent to the broker.
+    pub fn with_data(mut self, data: SerializedValue) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Serializes and sets custom data, that is sent to the broker.
+    pub fn with_serialize_data<D: Serialize + ?Sized>(
+        self,
+        data: &D,
+    ) -> Result<Self, ConnectError<T::Error>> {
+        let data = SerializedValue::serialize(data)?;
+        Ok(self.with_data(data))
+    }
+
+    /// Sets a human-readable name of the client.
+    ///
+    /// The name is visible to the broker and, through it, possibly to other clients. It is meant
+    /// to identify the process, that owns objects and services, e.g. while debugging.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin::Client;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let broker = aldrin_test::tokio::TestBroker::new();
+    /// # let mut handle = broker.clone();
+    /// # let (async_transport, t2) = aldrin::core::channel::unbounded();
+    /// # let conn = tokio::spawn(async move { handle.connect(t2).await });
+    /// let client = Client::builder(async_transport)
+    ///     .with_name("downloader")
+    ///     .with_metadata("pid", std::process::id().to_string())
+    ///     .connect()
+    ///     .await?;
+    /// # tokio::spawn(conn.await??.run());
+    /// # let handle = client.handle().clone();
+    /// # let join = tokio::spawn(client.run());
+    /// # handle.shutdown();
+    /// # join.await??;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Adds a metadata entry, that is sent to the broker.
+    ///
+    /// Metadata is an arbitrary map of strings, such as a user-agent or a process id. Like the
+    /// [name](Self::with_name), it is visible to the broker and possibly to other clients. Adding
+    /// an entry with the same key again replaces the previous value.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the session of the client.
+    ///
+    /// The broker buffers reliable events for each subscriber until they are acknowledged (see
+    /// [`Proxy::ack_event`](crate::low_level::Proxy::ack_event)). When a client disconnects, its
+    /// buffered events are normally lost. A client, that reconnects with the same session within
+    /// the broker's reconnect window, receives them again, once it subscribes to the events again.
+    /// This includes reliable events, that were emitted while the client was disconnected.
+    ///
+    /// Only one connection can use a session at a time. Sessions require protocol version 1.19.
+    pub fn with_session(mut self, session: Uuid) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Sets the namespace of the client.
+    ///
+    /// Namespaces separate several logical applications on the same bus. All objects and services
+    /// of the client belong to its namespace. Bus listeners, and thus also
+    /// [`Discoverer`s](crate::Discoverer), see only objects and services of the client's own
+    /// namespace and of the default namespace. The default namespace consists of all clients
+    /// without a namespace. Use [`with_visible_namespace`](Self::with_visible_namespace) to see
+    /// other namespaces as well.
+    ///
+    /// Namespaces affect only visibility. Object UUIDs are still unique across the whole bus and
+    /// services can still be used, if their id is known. The broker may also override the
+    /// namespace.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Makes other namespaces visible to the client.
+    ///
+    /// `pattern` is either the name of a namespace or a prefix followed by `*`, which matches all
+    /// namespaces starting with the prefix. A single `*` thus makes all namespaces visible. See
+    /// [`with_namespace`](Self::with_namespace) for more information.
+    pub fn with_visible_namespace(mut self, pattern: impl Into<String>) -> Self {
+        self.visible_namespaces.push(pattern.into());
+        self
+    }
+
+    /// Authenticates with the broker using an [`AuthProvider`].
+    ///
+    /// If the broker rejects the credentials, then connecting fails with
+    /// [`ConnectError::AuthenticationFailed`].
+    pub fn with_auth(mut self, auth: impl AuthProvider + Send + 'static) -> Self {
+        self.auth = Some(Box::new(auth));
+        self
+    }
+
+    /// Enables keep-alive and dead-peer detection.
+    ///
+    /// Every time a future created by `interval` completes, the client sends a ping to the broker.
+    /// If no message at all is received from the broker during an entire interval after a ping,
+    /// then the broker is considered dead and [`Client::run`] fails with
+    /// [`RunError::KeepAliveTimeout`](crate::error::RunError::KeepAliveTimeout). This detects
+    /// connections, that have died silently, without this crate depending on any specific async
+    /// runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin::Client;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let broker = aldrin_test::tokio::TestBroker::new();
+    /// # let mut handle = broker.clone();
+    /// # let (async_transport, t2) = aldrin::core::channel::unbounded();
+    /// # let conn = tokio::spawn(async move { handle.connect(t2).await });
+    /// let client = Client::builder(async_transport)
+    ///     .with_keep_alive(|| tokio::time::sleep(Duration::from_secs(10)))
+    ///     .connect()
+    ///     .await?;
+    /// # tokio::spawn(conn.await??.run());
+    /// # let handle = client.handle().clone();
+    /// # let join = tokio::spawn(client.run());
+    /// # handle.shutdown();
+    /// # join.await??;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_keep_alive<F, Fut>(mut self, interval: F) -> Self
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.keep_alive = Some(KeepAlive::new(interval));
+        self
+    }
+
+    /// Provides timers for call timeouts.
+    ///
+    /// `sleep` is called with the timeout of a call and must return a future, that completes after
+    /// that duration has elapsed. Without a timer, calls with a timeout fail with
+    /// [`Error::NotSupported`](crate::Error::NotSupported). See
+    /// [`CallOptions::set_timeout`](crate::low_level::CallOptions::set_timeout).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin::Client;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let broker = aldrin_test::tokio::TestBroker::new();
+    /// # let mut handle = broker.clone();
+    /// # let (async_transport, t2) = aldrin::core::channel::unbounded();
+    /// # let conn = tokio::spawn(async move { handle.connect(t2).await });
+    /// let client = Client::builder(async_transport)
+    ///     .with_timer(tokio::time::sleep)
+    ///     .connect()
+    ///     .await?;
+    /// # tokio::spawn(conn.await??.run());
+    /// # let handle = client.handle().clone();
+    /// # let join = tokio::spawn(client.run());
+    /// # handle.shutdown();
+    /// # join.await??;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_timer<F, Fut>(mut self, sleep: F) -> Self
+    where
+        F: Fn(Duration) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.timer = Some(CallTimer::new(sleep));
+        self
+    }
+
+    /// Bounds the number of requests, which the client hasn't processed yet.
+    ///
+    /// By default, the queue of requests between [`Handle`s](crate::Handle) and the [`Client`] is
+    /// unbounded. With this option, at most `capacity` function calls, replies, events and channel
+    /// items can be pending. `policy` determines what happens to requests beyond that limit.
+    ///
+    /// See also [`Handle::queue_ready`](crate::Handle::queue_ready).
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `capacity` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin::{Client, OverflowPolicy};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let broker = aldrin_test::tokio::TestBroker::new();
+    /// # let mut handle = broker.clone();
+    /// # let (async_transport, t2) = aldrin::core::channel::unbounded();
+    /// # let conn = tokio::spawn(async move { handle.connect(t2).await });
+    /// let client = Client::builder(async_transport)
+    ///     .with_queue_capacity(1024, OverflowPolicy::DropEvents)
+    ///     .connect()
+    ///     .await?;
+    /// # tokio::spawn(conn.await??.run());
+    /// # let handle = client.handle().clone();
+    /// # let join = tokio::spawn(client.run());
+    /// # handle.shutdown();
+    /// # join.await??;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_queue_capacity(mut self, capacity: usize, policy: OverflowPolicy) -> Self {
+        assert!(capacity > 0, "queue capacity must be greater than 0");
+        self.queue = Some((capacity, policy));
+        self
+    }
+
+    /// Sets the cache for introspections received from the broker.
+    ///
+    /// By default, each client uses its own, empty cache. Setting a cache allows sharing it between
+    /// clients and persisting it across runs. See [`IntrospectionCache`] for details.
+    #[cfg(feature = "introspection")]
+    pub fn with_introspection_cache(mut self, cache: IntrospectionCache) -> Self {
+        self.introspection_cache = Some(cache);
+        self
+    }
+
+    /// Connects to the broker.
+    ///
+    /// Any custom data, that the broker sends back, is discarded.
+    pub async fn connect(self) -> Result<Client<T>, ConnectError<T::Error>> {
+        let (client, _) = self.connect_with_data().await?;
+        Ok(client)
+    }
+
+    /// Connects to the broker and returns the broker's custom data.
+    pub async fn connect_with_data(
+        self,
+    ) -> Result<(Client<T>, Option<SerializedValue>), ConnectError<T::Error>> {
+        let mut connect_data = ConnectData::new();
+        connect_data.user = self.data;
+        connect_data.name = self.name;
+        connect_data.metadata = self.metadata;
+        connect_data.session = self.session;
+        connect_data.namespace = self.namespace;
+        connect_data.visible_namespaces = self.visible_namespaces;
+
+        let mut auth = self.auth;
+        let auth = auth.as_mut().map(|auth| &mut **auth as _);
+
+        let (mut client, data) = Client::connect_impl(self.t, connect_data, auth).await?;
+        client.keep_alive = self.keep_alive;
+
+        #[cfg(feature = "introspection")]
+        if let Some(cache) = self.introspection_cache {
+            client.introspection_cache = cache;
+        }
+
+        if let Some(timer) = self.timer {
+            client.handle.set_timer(timer);
+        }
+
+        if let Some((capacity, policy)) = self.queue {
+            let queue = Arc::new(RequestQueue::new(capacity, policy));
+            client.handle.set_queue(queue.clone());
+            client.queue = Some(RequestQueueGuard(queue));
+        }
+
+        Ok((client, data))
+    }
+}
+
+impl<T> fmt::Debug for ClientBuilder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("data", &self.data)
+            .field("auth", &self.auth.is_some())
+            .field("keep_alive", &self.keep_alive.is_some())
+            .field("timer", &self.timer.is_some())
+            .field("queue", &self.queue)
+            .field("name", &self.name)
+            .field("metadata", &self.metadata)
+            .field("namespace", &self.namespace)
+            .field("visible_namespaces", &self.visible_namespaces)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+type Timer = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Creates timers for call timeouts.
+///
+/// Aldrin doesn't depend on a specific runtime. The timers are instead provided by the user with
+/// [`ClientBuilder::with_timer`](super::ClientBuilder::with_timer).
+#[derive(Clone)]
+pub(crate) struct CallTimer(Arc<dyn Fn(Duration) -> Timer + Send + Sync>);
+
+impl CallTimer {
+    pub fn new<F, Fut>(sleep: F) -> Self
+    where
+        F: Fn(Duration) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self(Arc::new(move |duration| Box::pin(sleep(duration)) as Timer))
+    }
+
+    pub fn sleep(&self, duration: Duration) -> Sleep {
+        Sleep((self.0)(duration))
+    }
+}
+
+impl fmt::Debug for CallTimer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CallTimer").finish_non_exhaustive()
+    }
+}
+
+/// Timer, that completes after a call's timeout has elapsed.
+pub(crate) struct Sleep(Timer);
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+impl fmt::Debug for Sleep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sleep").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+type Timer = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Keep-alive state of a client.
+///
+/// A ping is sent to the broker every time the interval elapses. The broker is considered dead if
+/// no message at all was received during an entire interval after a ping was sent.
+pub(crate) struct KeepAlive {
+    interval: Box<dyn FnMut() -> Timer + Send>,
+    timer: Timer,
+    received: bool,
+    ping_sent: bool,
+}
+
+impl KeepAlive {
+    pub fn new<F, Fut>(mut interval: F) -> Self
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let mut interval = move || Box::pin(interval()) as Timer;
+        let timer = interval();
+
+        Self {
+            interval: Box::new(interval),
+            timer,
+            received: false,
+            ping_sent: false,
+        }
+    }
+
+    /// Records that a message was received from the broker.
+    pub fn received(&mut self) {
+        self.received = true;
+    }
+
+    /// Polls the timer and restarts it when it has elapsed.
+    pub fn poll_elapsed(&mut self, cx: &mut Context) -> Poll<()> {
+        if self.timer.as_mut().poll(cx).is_ready() {
+            self.timer = (self.interval)();
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Advances the state after the interval has elapsed.
+    ///
+    /// Returns `false` if the broker is considered dead. Otherwise, the caller must send a ping.
+    pub fn tick(&mut self) -> bool {
+        let alive = !self.ping_sent || self.received;
+        self.received = false;
+        self.ping_sent = true;
+        alive
+    }
+}
+
+impl fmt::Debug for KeepAlive {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeepAlive")
+            .field("received", &self.received)
+            .field("ping_sent", &self.ping_sent)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,6 +1,6 @@
 use crate::handle::Handle;
 use crate::low_level::{Event, Proxy, ProxyId};
-use aldrin_core::{SerializedValue, ServiceCookie, ServiceId, ServiceInfo};
+use aldrin_core::{SerializedValue, ServiceCookie, ServiceId, ServiceInfo, TraceContext};
 use futures_channel::mpsc::{self, UnboundedSender};
 use std::collections::hash_map::{Entry, HashMap};
 use std::collections::HashSet;
@@ -83,6 +83,15 @@ impl Proxies {
         Some(res)
     }
 
+    /// Adds another event stream to a proxy, e.g. when it is cloned.
+    ///
+    /// `send` is dropped if the proxy doesn't exist (anymore), which ends the stream immediately.
+    pub fn add_event_stream(&mut self, proxy: ProxyId, send: UnboundedSender<Event>) {
+        if let Some(entry) = self.entries.get_mut(&proxy) {
+            entry.add_event_stream(send);
+        }
+    }
+
     pub fn remove_service(&mut self, service: ServiceCookie) {
         if let Some(proxies) = self.services.remove(&service) {
             for proxy in proxies {
@@ -183,42 +192,59 @@ impl Proxies {
             .any(|(_, entry)| entry.is_subscribed_to_all())
     }
 
-    pub fn emit(&self, service: ServiceCookie, event: u32, args: SerializedValue) {
+    pub fn emit(
+        &mut self,
+        service: ServiceCookie,
+        event: u32,
+        args: SerializedValue,
+        trace_context: Option<TraceContext>,
+        ack_serial: Option<u32>,
+    ) -> bool {
+        let mut delivered = false;
+
         if let Some(proxies) = self.services.get(&service) {
             let mut proxies = proxies.iter().peekable();
 
             while let Some(proxy) = proxies.next() {
-                let proxy = self.entries.get(proxy).expect("inconsistent state");
+                let proxy = self.entries.get_mut(proxy).expect("inconsistent state");
 
                 if proxy.is_subscribed_to_all() || proxy.is_subscribed_to(event) {
                     // Avoid cloning args for the last proxy.
                     if proxies.peek().is_some() {
-                        proxy.emit(event, args.clone());
+                        delivered |= proxy.emit(event, args.clone(), trace_context, ack_serial);
                     } else {
-                        proxy.emit(event, args);
+                        delivered |= proxy.emit(event, args, trace_context, ack_serial);
                         break;
                     }
                 }
             }
         }
+
+        delivered
     }
 }
 
 #[derive(Debug)]
 struct ProxyEntry {
     service: ServiceCookie,
-    send: UnboundedSender<Event>,
-    events: HashSet<u32>,
-    all_events: bool,
+
+    /// One sender per clone of the proxy.
+    senders: Vec<UnboundedSender<Event>>,
+
+    /// Subscribed events with the number of times they have been subscribed.
+    events: HashMap<u32, usize>,
+
+    /// Number of times all events have been subscribed.
+    all_events: usize,
 }
 
 impl ProxyEntry {
     fn new(service: ServiceCookie, send: UnboundedSender<Event>) -> Self {
         Self {
             service,
-            send,
-            events: HashSet::new(),
-            all_events: false,
+            senders: vec![send],
+            events: HashMap::new(),
+            all_events: 0,
         }
     }
 
@@ -226,51 +252,103 @@ impl ProxyEntry {
         self.service
     }
 
+    fn add_event_stream(&mut self, send: UnboundedSender<Event>) {
+        self.senders.retain(|send| !send.is_closed());
+        self.senders.push(send);
+    }
+
     fn remove(self) -> RemoveProxyResult {
         RemoveProxyResult {
             service: self.service,
             unsubscribe: true,
-            events: self.events,
-            all_events: self.all_events,
+            events: self.events.into_keys().collect(),
+            all_events: self.all_events > 0,
         }
     }
 
+    /// Subscribes to an event and returns `true` if it wasn't subscribed before.
     fn subscribe(&mut self, event: u32) -> bool {
-        self.events.insert(event)
+        let count = self.events.entry(event).or_default();
+        *count += 1;
+        *count == 1
     }
 
+    /// Unsubscribes from an event and returns `true` if this was the last subscription.
     fn unsubscribe(&mut self, event: u32) -> bool {
-        self.events.remove(&event)
+        let Entry::Occupied(mut count) = self.events.entry(event) else {
+            return false;
+        };
+
+        *count.get_mut() -= 1;
+
+        if *count.get() == 0 {
+            count.remove();
+            true
+        } else {
+            false
+        }
     }
 
     fn is_subscribed_to(&self, event: u32) -> bool {
-        self.events.contains(&event)
+        self.events.contains_key(&event)
     }
 
+    /// Subscribes to all events and returns `true` if they weren't subscribed before.
     fn subscribe_all(&mut self) -> bool {
-        if self.all_events {
-            false
-        } else {
-            self.all_events = true;
-            true
-        }
+        self.all_events += 1;
+        self.all_events == 1
     }
 
+    /// Balances a call to `subscribe_all`.
+    ///
+    /// Only if no other calls are outstanding, all subscriptions are removed, including those of
+    /// individual events.
     fn unsubscribe_all(&mut self) -> UnsubscribeAllResult {
-        UnsubscribeAllResult {
-            service: self.service,
-            events: mem::take(&mut self.events),
-            all_events: mem::take(&mut self.all_events),
+        if self.all_events > 1 {
+            self.all_events -= 1;
+
+            UnsubscribeAllResult {
+                service: self.service,
+                events: HashSet::new(),
+                all_events: false,
+            }
+        } else {
+            UnsubscribeAllResult {
+                service: self.service,
+                events: mem::take(&mut self.events).into_keys().collect(),
+                all_events: mem::take(&mut self.all_events) > 0,
+            }
         }
     }
 
     fn is_subscribed_to_all(&self) -> bool {
-        self.all_events
+        self.all_events > 0
     }
 
-    fn emit(&self, event: u32, args: SerializedValue) {
-        debug_assert!(self.all_events || self.events.contains(&event));
-        let _ = self.send.unbounded_send(Event::new(event, args));
+    fn emit(
+        &mut self,
+        event: u32,
+        args: SerializedValue,
+        trace_context: Option<TraceContext>,
+        ack_serial: Option<u32>,
+    ) -> bool {
+        debug_assert!((self.all_events > 0) || self.events.contains_key(&event));
+
+        self.senders.retain(|send| !send.is_closed());
+        let mut senders = self.senders.iter().peekable();
+
+        while let Some(send) = senders.next() {
+            // Avoid cloning args for the last sender.
+            if senders.peek().is_some() {
+                let event = Event::new(event, args.clone(), trace_context, ack_serial);
+                let _ = send.unbounded_send(event);
+            } else {
+                let _ = send.unbounded_send(Event::new(event, args, trace_context, ack_serial));
+                break;
+            }
+        }
+
+        !self.senders.is_empty()
     }
 }
 
@@ -0,0 +1,129 @@
+use crate::error::Error;
+use std::future;
+use std::mem;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Policy for requests, that exceed the capacity of a client's request queue.
+///
+/// See [`ClientBuilder::with_queue_capacity`](super::ClientBuilder::with_queue_capacity).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// Requests are queued anyway.
+    ///
+    /// Asynchronous functions, such as
+    /// [`Service::emit_acked`](crate::low_level::Service::emit_acked), wait until the queue has
+    /// capacity again. Use [`Handle::queue_ready`](crate::Handle::queue_ready) to apply
+    /// backpressure before calling synchronous functions.
+    Wait,
+
+    /// Requests fail with [`Error::QueueFull`].
+    Error,
+
+    /// Events are dropped silently. All other requests are queued anyway.
+    DropEvents,
+}
+
+/// Counts the requests, which a client hasn't processed yet.
+#[derive(Debug)]
+pub(crate) struct RequestQueue {
+    len: AtomicUsize,
+    capacity: usize,
+    policy: OverflowPolicy,
+    closed: AtomicBool,
+    waiters: Mutex<Vec<Waker>>,
+}
+
+impl RequestQueue {
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        Self {
+            len: AtomicUsize::new(0),
+            capacity,
+            policy,
+            closed: AtomicBool::new(false),
+            waiters: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Acquires a slot for a request.
+    ///
+    /// `Ok(false)` is returned if the request must be dropped.
+    pub fn acquire(&self, event: bool) -> Result<bool, Error> {
+        if self.is_full() {
+            match self.policy {
+                OverflowPolicy::Wait => {}
+                OverflowPolicy::Error => return Err(Error::QueueFull),
+                OverflowPolicy::DropEvents if event => return Ok(false),
+                OverflowPolicy::DropEvents => {}
+            }
+        }
+
+        self.len.fetch_add(1, Ordering::AcqRel);
+        Ok(true)
+    }
+
+    /// Releases a slot after the client has received a request.
+    pub fn release(&self) {
+        let prev = self.len.fetch_sub(1, Ordering::AcqRel);
+
+        if prev == self.capacity {
+            self.wake_all();
+        }
+    }
+
+    /// Marks the queue as closed, after the client has shut down.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.wake_all();
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    pub async fn ready(&self) {
+        future::poll_fn(|cx| self.poll_ready(cx)).await
+    }
+
+    fn poll_ready(&self, cx: &mut Context) -> Poll<()> {
+        if !self.is_full() {
+            return Poll::Ready(());
+        }
+
+        self.waiters.lock().unwrap().push(cx.waker().clone());
+
+        // The queue may have been drained in the meantime.
+        if self.is_full() {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        !self.closed.load(Ordering::Acquire) && (self.len() >= self.capacity)
+    }
+
+    fn wake_all(&self) {
+        let waiters = mem::take(&mut *self.waiters.lock().unwrap());
+
+        for waker in waiters {
+            waker.wake();
+        }
+    }
+}
+
+/// Closes a [`RequestQueue`] when the client is dropped.
+#[derive(Debug)]
+pub(crate) struct RequestQueueGuard(pub Arc<RequestQueue>);
+
+impl Drop for RequestQueueGuard {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
@@ -1,3 +1,4 @@
+use super::keep_alive::KeepAlive;
 use crate::core::message::Message;
 use crate::core::transport::{AsyncTransport, AsyncTransportExt};
 use crate::function_call_map::FunctionCallMap;
@@ -14,6 +15,7 @@ pub(crate) enum Select {
     Transport,
     Handle,
     AbortFunctionCall,
+    KeepAlive,
 }
 
 impl Select {
@@ -26,11 +28,13 @@ impl Select {
         transport: &mut T,
         handle: &mut UnboundedReceiver<HandleRequest>,
         function_calls: &mut FunctionCallMap,
+        keep_alive: &mut Option<KeepAlive>,
     ) -> Selected<T>
     where
         T: AsyncTransport + Unpin,
     {
-        future::poll_fn(|cx| self.poll_select(transport, handle, function_calls, cx)).await
+        future::poll_fn(|cx| self.poll_select(transport, handle, function_calls, keep_alive, cx))
+            .await
     }
 
     fn poll_select<T>(
@@ -38,12 +42,13 @@ impl Select {
         transport: &mut T,
         handle: &mut UnboundedReceiver<HandleRequest>,
         function_calls: &mut FunctionCallMap,
+        keep_alive: &mut Option<KeepAlive>,
         cx: &mut Context,
     ) -> Poll<Selected<T>>
     where
         T: AsyncTransport + Unpin,
     {
-        for _ in 0..3 {
+        for _ in 0..4 {
             match self.next() {
                 Self::Transport => {
                     if let Poll::Ready(res) = transport.receive_poll_unpin(cx) {
@@ -63,6 +68,14 @@ impl Select {
                         return Poll::Ready(Selected::AbortFunctionCall(serial));
                     }
                 }
+
+                Self::KeepAlive => {
+                    if let Some(ref mut keep_alive) = keep_alive {
+                        if keep_alive.poll_elapsed(cx).is_ready() {
+                            return Poll::Ready(Selected::KeepAlive);
+                        }
+                    }
+                }
             }
         }
 
@@ -73,7 +86,8 @@ impl Select {
         let next = match self {
             Self::Transport => Self::Handle,
             Self::Handle => Self::AbortFunctionCall,
-            Self::AbortFunctionCall => Self::Transport,
+            Self::AbortFunctionCall => Self::KeepAlive,
+            Self::KeepAlive => Self::Transport,
         };
 
         mem::replace(self, next)
@@ -88,4 +102,5 @@ where
     Transport(Result<Message, T::Error>),
     Handle(HandleRequest),
     AbortFunctionCall(u32),
+    KeepAlive,
 }
@@ -0,0 +1,154 @@
+use crate::core::{ObjectUuid, ServiceId, ServiceUuid};
+use crate::error::Error;
+use crate::handle::Handle;
+use crate::low_level::{Call, Service, ServiceInfo};
+use crate::object::Object;
+use std::time::{Duration, Instant};
+use uuid::uuid;
+
+/// Bus service, that provides a common monotonic clock to clients.
+///
+/// Clients usually can't compare timestamps of their local clocks with each other. The clock
+/// service provides a single time base for the whole bus instead, so that e.g. timestamps in events
+/// from different clients can be correlated. Clients sample it with [`Handle::bus_time`].
+///
+/// The service is typically run by the broker (see `Embedded::spawn_clock_service` in the
+/// `aldrin-broker` crate), but it can run on any client with [`new`](Self::new) and
+/// [`run`](Self::run).
+///
+/// # Protocol
+///
+/// The service has a single function [`NOW`](Self::NOW), which takes no arguments (`()`) and
+/// returns the time, that has elapsed since the service was created, as a `Duration`.
+#[derive(Debug)]
+pub struct ClockService {
+    object: Object,
+    service: Service,
+    start: Instant,
+}
+
+impl ClockService {
+    /// UUID of the object, that owns the clock service.
+    pub const OBJECT_UUID: ObjectUuid = ObjectUuid(uuid!("5e8c2d71-a94f-4b3e-8d06-c7f1a2b93e54"));
+
+    /// UUID of the clock service.
+    pub const SERVICE_UUID: ServiceUuid =
+        ServiceUuid(uuid!("b7d3f0a6-1c5e-4e92-a8b4-3f6d9c0e2a71"));
+
+    /// Version of the clock service.
+    pub const VERSION: u32 = 1;
+
+    /// Function id for querying the current bus time.
+    pub const NOW: u32 = 1;
+
+    /// Creates the clock service on a client.
+    ///
+    /// This creates an object with the UUID [`OBJECT_UUID`](Self::OBJECT_UUID) and fails with
+    /// [`Error::DuplicateObject`], if a clock service already exists on the bus.
+    pub async fn new(client: &Handle) -> Result<Self, Error> {
+        let object = client.create_object(Self::OBJECT_UUID).await?;
+
+        let service = object
+            .create_service(Self::SERVICE_UUID, ServiceInfo::new(Self::VERSION))
+            .await?;
+
+        Ok(Self {
+            object,
+            service,
+            start: Instant::now(),
+        })
+    }
+
+    /// Returns the id of the clock service.
+    pub fn id(&self) -> ServiceId {
+        self.service.id()
+    }
+
+    /// Returns the object, that owns the clock service.
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+
+    /// Runs the clock service.
+    ///
+    /// This function returns when the service has been destroyed, e.g. because the client shut
+    /// down.
+    pub async fn run(mut self) -> Result<(), Error> {
+        while let Some(call) = self.service.next_call().await {
+            self.call(call)?;
+        }
+
+        Ok(())
+    }
+
+    fn call(&self, call: Call) -> Result<(), Error> {
+        match call.id() {
+            Self::NOW => {
+                if call.deserialize::<()>().is_err() {
+                    return call.into_promise().invalid_args();
+                }
+
+                call.into_promise().ok(&self.start.elapsed())
+            }
+
+            _ => call.into_promise().invalid_function(),
+        }
+    }
+}
+
+/// Sample of the bus time.
+///
+/// Samples are taken with [`Handle::bus_time`]. They relate the [`ClockService`]'s time to the
+/// local clock, similar to how NTP works: the bus time is assumed to have been taken half way
+/// through the call's round-trip time. The error of the estimate is thus at most half of
+/// [`rtt`](Self::rtt). When taking multiple samples, the one with the lowest round-trip time is
+/// usually the most accurate.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BusTime {
+    local: Instant,
+    bus: Duration,
+    rtt: Duration,
+}
+
+impl BusTime {
+    pub(crate) fn new(sent: Instant, received: Instant, bus: Duration) -> Self {
+        let rtt = received.saturating_duration_since(sent);
+
+        Self {
+            local: received,
+            bus: bus + rtt / 2,
+            rtt,
+        }
+    }
+
+    /// Returns the local time, at which the sample was taken.
+    pub fn local(self) -> Instant {
+        self.local
+    }
+
+    /// Returns the estimated bus time at [`local`](Self::local).
+    pub fn bus(self) -> Duration {
+        self.bus
+    }
+
+    /// Returns the round-trip time of the sample.
+    pub fn rtt(self) -> Duration {
+        self.rtt
+    }
+
+    /// Converts a local time to the bus time.
+    ///
+    /// Local times before the start of the [`ClockService`] are clamped to zero.
+    pub fn to_bus(self, local: Instant) -> Duration {
+        if local >= self.local {
+            self.bus + (local - self.local)
+        } else {
+            self.bus.saturating_sub(self.local - local)
+        }
+    }
+
+    /// Returns the estimated current bus time.
+    pub fn now(self) -> Duration {
+        self.to_bus(Instant::now())
+    }
+}
@@ -1,7 +1,15 @@
 //! Error types.
 
+#[cfg(feature = "introspection")]
+use crate::core::introspection::ValidationError;
 use crate::core::message::Message;
-use crate::core::{DeserializeError, SerializeError, SerializedValue};
+#[cfg(feature = "introspection")]
+use crate::core::TypeId;
+use crate::core::{AuthRejection, DeserializeError, SerializeError, SerializedValue, ServiceId};
+use std::error::Error as StdError;
+use std::fmt;
+#[cfg(feature = "introspection")]
+use std::io::Error as IoError;
 use thiserror::Error;
 
 /// Error when connecting to a broker.
@@ -27,6 +35,12 @@ pub enum ConnectError<T> {
     #[error("connection rejected")]
     Rejected(Option<SerializedValue>),
 
+    /// The broker rejected the client's credentials.
+    ///
+    /// See [`ClientBuilder::with_auth`](crate::ClientBuilder::with_auth).
+    #[error("authentication failed: {0}")]
+    AuthenticationFailed(AuthRejection),
+
     /// A value failed to serialize.
     #[error(transparent)]
     Serialize(#[from] SerializeError),
@@ -56,10 +70,16 @@ pub enum RunError<T> {
     /// A value failed to deserialize.
     #[error(transparent)]
     Deserialize(DeserializeError),
+
+    /// The broker did not respond in time to a keep-alive.
+    ///
+    /// See [`ClientBuilder::with_keep_alive`](crate::ClientBuilder::with_keep_alive).
+    #[error("keep-alive timed out")]
+    KeepAliveTimeout,
 }
 
 /// Standard error type used for most functions.
-#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
     /// The client has shut down.
     #[error("client shut down")]
@@ -91,6 +111,13 @@ pub enum Error {
     #[error(transparent)]
     InvalidFunction(#[from] InvalidFunction),
 
+    /// A function was called by a name, that could not be resolved.
+    ///
+    /// This happens when either the service's introspection is unavailable or it doesn't contain a
+    /// function with that name.
+    #[error("invalid function name")]
+    InvalidFunctionName,
+
     /// Invalid arguments were supplied to a function or event.
     ///
     /// This can indicate a schema mismatch.
@@ -101,6 +128,18 @@ pub enum Error {
     #[error("call aborted")]
     CallAborted,
 
+    /// A call was rejected, because the service has too many outstanding calls.
+    ///
+    /// See [`ServiceInfo::set_max_calls`](crate::low_level::ServiceInfo::set_max_calls).
+    #[error("service overloaded")]
+    Overloaded,
+
+    /// A call didn't receive a reply in time.
+    ///
+    /// See [`CallOptions::set_timeout`](crate::low_level::CallOptions::set_timeout).
+    #[error("call timed out")]
+    Timeout,
+
     /// A field that is required for some type is missing.
     #[error(transparent)]
     RequiredFieldMissing(#[from] RequiredFieldMissing),
@@ -141,9 +180,31 @@ pub enum Error {
     #[error(transparent)]
     Serialize(#[from] SerializeError),
 
-    /// The negotiated protocol version is too low.
+    /// An operation is not supported.
+    ///
+    /// This is usually the case when the negotiated protocol version is too low.
     #[error("not supported")]
     NotSupported,
+
+    /// A service's version is lower than required.
+    ///
+    /// This is returned by the `new_with_min_version` constructors of generated proxies.
+    #[error(transparent)]
+    UnsupportedVersion(#[from] UnsupportedVersion),
+
+    /// The client's request queue is full.
+    ///
+    /// See [`OverflowPolicy::Error`](crate::OverflowPolicy::Error).
+    #[error("request queue full")]
+    QueueFull,
+
+    /// A function call failed.
+    ///
+    /// Errors, that are caused by a specific call, such as [`CallAborted`](Self::CallAborted) or
+    /// [`InvalidReply`](Self::InvalidReply), are wrapped in this variant together with the context
+    /// of the call. Use [`without_context`](Self::without_context) to get the underlying error.
+    #[error(transparent)]
+    Call(Box<CallError>),
 }
 
 impl Error {
@@ -171,6 +232,116 @@ impl Error {
     pub fn invalid_item(source: DeserializeError) -> Self {
         Self::InvalidItem(InvalidItem::new(source))
     }
+
+    /// Creates a new `UnsupportedVersion` error.
+    pub fn unsupported_version(required: u32, actual: u32) -> Self {
+        Self::UnsupportedVersion(UnsupportedVersion::new(required, actual))
+    }
+
+    /// Creates a new `Call` error.
+    ///
+    /// If `error` is a `Call` error already, then its context is replaced.
+    pub fn call(service: ServiceId, function: u32, serial: Option<u32>, error: Self) -> Self {
+        Self::Call(Box::new(CallError::new(service, function, serial, error)))
+    }
+
+    /// Strips the context of a call from the error.
+    ///
+    /// This returns the underlying error of [`Call`](Self::Call) errors and all other errors
+    /// unchanged. It is useful for matching on specific errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin::Error;
+    /// # use aldrin::core::ServiceId;
+    /// let err = Error::call(ServiceId::NIL, 1, Some(2), Error::CallAborted);
+    /// assert_eq!(err.without_context(), Error::CallAborted);
+    /// ```
+    pub fn without_context(self) -> Self {
+        match self {
+            Self::Call(e) => e.error,
+            e => e,
+        }
+    }
+}
+
+/// A function call failed.
+///
+/// This error carries the context of a failed call, i.e. the service, the function and the serial
+/// of the call, together with the underlying error, which is also available as the error's
+/// [`source`](StdError::source).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallError {
+    service: ServiceId,
+    function: u32,
+    serial: Option<u32>,
+    error: Error,
+}
+
+impl CallError {
+    /// Creates a new `CallError`.
+    ///
+    /// If `error` is a [`Error::Call`] already, then its context is replaced.
+    pub fn new(service: ServiceId, function: u32, serial: Option<u32>, error: Error) -> Self {
+        Self {
+            service,
+            function,
+            serial,
+            error: error.without_context(),
+        }
+    }
+
+    /// Returns the id of the called service.
+    pub fn service(&self) -> ServiceId {
+        self.service
+    }
+
+    /// Returns the id of the called function.
+    pub fn function(&self) -> u32 {
+        self.function
+    }
+
+    /// Returns the serial of the call.
+    ///
+    /// The serial is assigned by the client and is also part of the messages exchanged with the
+    /// broker. It is `None` if the call failed without a reply from the broker, e.g. because it
+    /// timed out.
+    pub fn serial(&self) -> Option<u32> {
+        self.serial
+    }
+
+    /// Returns the underlying error.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Converts the `CallError` into the underlying error.
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "call")?;
+
+        if let Some(serial) = self.serial {
+            write!(f, " {serial}")?;
+        }
+
+        write!(
+            f,
+            " of function {} of service {} failed",
+            self.function, self.service.uuid
+        )
+    }
+}
+
+impl StdError for CallError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.error)
+    }
 }
 
 /// An invalid function was called.
@@ -197,23 +368,66 @@ impl InvalidFunction {
 /// Invalid arguments were supplied to a function or event.
 ///
 /// This can indicate a schema mismatch.
-#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
-#[error("invalid arguments for function or event {}", .id)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InvalidArguments {
     id: u32,
     source: Option<DeserializeError>,
+    #[cfg(feature = "introspection")]
+    validation_error: Option<Box<ValidationError>>,
 }
 
 impl InvalidArguments {
     /// Creates a new `InvalidArguments` error.
     pub fn new(id: u32, source: Option<DeserializeError>) -> Self {
-        Self { id, source }
+        Self {
+            id,
+            source,
+            #[cfg(feature = "introspection")]
+            validation_error: None,
+        }
+    }
+
+    /// Creates a new `InvalidArguments` error from a failed validation.
+    ///
+    /// See [`Service::enable_args_validation`](crate::low_level::Service::enable_args_validation).
+    #[cfg(feature = "introspection")]
+    pub fn with_validation_error(id: u32, validation_error: ValidationError) -> Self {
+        Self {
+            id,
+            source: None,
+            validation_error: Some(Box::new(validation_error)),
+        }
     }
 
     /// Returns the id of the function or event.
-    pub fn id(self) -> u32 {
+    pub fn id(&self) -> u32 {
         self.id
     }
+
+    /// Returns the validation error, if the arguments failed validation.
+    ///
+    /// The error points to the offending part of the arguments.
+    #[cfg(feature = "introspection")]
+    pub fn validation_error(&self) -> Option<&ValidationError> {
+        self.validation_error.as_deref()
+    }
+}
+
+impl fmt::Display for InvalidArguments {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid arguments for function or event {}", self.id)
+    }
+}
+
+impl StdError for InvalidArguments {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        #[cfg(feature = "introspection")]
+        if let Some(ref validation_error) = self.validation_error {
+            return Some(validation_error);
+        }
+
+        self.source.as_ref().map(|source| source as _)
+    }
 }
 
 /// A field that is required for some type is missing.
@@ -268,3 +482,57 @@ impl InvalidItem {
         Self { source }
     }
 }
+
+/// A service's version is lower than required.
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+#[error("service version {} is lower than required version {}", .actual, .required)]
+pub struct UnsupportedVersion {
+    required: u32,
+    actual: u32,
+}
+
+impl UnsupportedVersion {
+    /// Creates a new `UnsupportedVersion` error.
+    pub fn new(required: u32, actual: u32) -> Self {
+        Self { required, actual }
+    }
+
+    /// Returns the version that was required.
+    pub fn required(self) -> u32 {
+        self.required
+    }
+
+    /// Returns the actual version of the service.
+    pub fn actual(self) -> u32 {
+        self.actual
+    }
+}
+
+/// Error when loading or saving an [`IntrospectionCache`](crate::IntrospectionCache).
+#[cfg(feature = "introspection")]
+#[derive(Error, Debug)]
+pub enum IntrospectionCacheError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] IoError),
+
+    /// The data is not an introspection cache.
+    #[error("invalid introspection cache")]
+    InvalidFormat,
+
+    /// The cache has an unsupported version.
+    #[error("unsupported introspection cache version {0}")]
+    UnsupportedVersion(u8),
+
+    /// The cache failed to deserialize.
+    #[error(transparent)]
+    Deserialize(#[from] DeserializeError),
+
+    /// An introspection failed to serialize.
+    #[error(transparent)]
+    Serialize(#[from] SerializeError),
+
+    /// The introspection of a type is invalid or doesn't match its [`TypeId`].
+    #[error("invalid introspection for type {0}")]
+    InvalidIntrospection(TypeId),
+}
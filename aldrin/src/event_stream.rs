@@ -0,0 +1,106 @@
+use crate::core::{Deserialize, ServiceId};
+use crate::error::Error;
+use crate::low_level::Proxy;
+use futures_core::stream::{FusedStream, Stream};
+use std::fmt;
+use std::future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Typed stream of a single event of a service.
+///
+/// `EventStream`s are usually created with the `{event}_events` functions of generated proxies.
+/// Each stream uses its own low-level [`Proxy`], which is subscribed only to the stream's event.
+/// Events are thus demultiplexed by the client and streams are independent of each other and of
+/// the proxy they were created from.
+///
+/// The stream ends when the service is destroyed or the client shuts down.
+pub struct EventStream<T> {
+    inner: Proxy,
+    event: u32,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> EventStream<T> {
+    /// Creates a new stream of the event `event` of `proxy`'s service.
+    ///
+    /// `proxy` itself is not affected by this function.
+    pub async fn new(proxy: &Proxy, event: u32) -> Result<Self, Error> {
+        let inner = Proxy::new(proxy.client(), proxy.id()).await?;
+        inner.subscribe(event).await?;
+
+        Ok(Self {
+            inner,
+            event,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns the id of the service.
+    pub fn id(&self) -> ServiceId {
+        self.inner.id()
+    }
+
+    /// Returns the id of the event.
+    pub fn event(&self) -> u32 {
+        self.event
+    }
+
+    /// Extracts the inner low-level proxy.
+    pub fn into_low_level(self) -> Proxy {
+        self.inner
+    }
+}
+
+impl<T: Deserialize> EventStream<T> {
+    /// Polls for the next event.
+    pub fn poll_next_event(&mut self, cx: &mut Context) -> Poll<Option<Result<T, Error>>> {
+        loop {
+            let ev = match self.inner.poll_next_event(cx) {
+                Poll::Ready(Some(ev)) => ev,
+                Poll::Ready(None) => break Poll::Ready(None),
+                Poll::Pending => break Poll::Pending,
+            };
+
+            if ev.id() != self.event {
+                continue;
+            }
+
+            break match ev.deserialize() {
+                Ok(ev) => Poll::Ready(Some(Ok(ev))),
+                Err(e) => Poll::Ready(Some(Err(Error::invalid_arguments(self.event, Some(e))))),
+            };
+        }
+    }
+
+    /// Returns the next event.
+    ///
+    /// `None` is returned when the service has been destroyed or the client has shut down.
+    pub async fn next_event(&mut self) -> Option<Result<T, Error>> {
+        future::poll_fn(|cx| self.poll_next_event(cx)).await
+    }
+}
+
+impl<T> fmt::Debug for EventStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EventStream")
+            .field("inner", &self.inner)
+            .field("event", &self.event)
+            .finish()
+    }
+}
+
+impl<T: Deserialize> Stream for EventStream<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.poll_next_event(cx)
+    }
+}
+
+impl<T: Deserialize> FusedStream for EventStream<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.events_finished()
+    }
+}
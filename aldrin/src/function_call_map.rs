@@ -4,7 +4,10 @@ use crate::serial_map::SerialMap;
 use futures_channel::oneshot::Sender;
 use std::task::{Context, Poll};
 
-type ResultSender = Sender<Result<CallFunctionResult, Error>>;
+/// Result of a call, together with its serial.
+pub(crate) type CallResult = Result<(u32, CallFunctionResult), Error>;
+
+type ResultSender = Sender<CallResult>;
 
 #[derive(Debug)]
 pub struct FunctionCallMap {
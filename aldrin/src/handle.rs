@@ -1,47 +1,55 @@
 pub(crate) mod request;
 
 use crate::bus_listener::BusListener;
+use crate::bus_tracker::{BusTracker, BusTrackerBuilder};
 use crate::channel::ChannelBuilder;
+use crate::client::{CallTimer, OverflowPolicy, RequestQueue, Sleep};
+use crate::clock::{BusTime, ClockService};
 #[cfg(feature = "introspection")]
 use crate::core::introspection::{DynIntrospectable, Introspectable, Introspection};
 use crate::core::message::{
     AddBusListenerFilter, AddChannelCapacity, CallFunctionResult, ClearBusListenerFilters,
-    DestroyBusListenerResult, DestroyObjectResult, RemoveBusListenerFilter, StartBusListenerResult,
-    StopBusListenerResult,
+    DestroyBusListenerResult, DestroyObjectResult, Message, RemoveBusListenerFilter,
+    StartBusListenerResult, StopBusListenerResult,
 };
 #[cfg(feature = "introspection")]
 use crate::core::TypeId;
 use crate::core::{
-    BusListenerCookie, BusListenerFilter, BusListenerScope, ChannelCookie, ChannelEnd,
-    ObjectCookie, ObjectId, ObjectUuid, ProtocolVersion, Serialize, SerializedValue, ServiceId,
-    ServiceUuid,
+    BusEvent, BusListenerCookie, BusListenerFilter, BusListenerScope, BusListenerServiceFilter,
+    CallPriority, ChannelCookie, ChannelEnd, ObjectCookie, ObjectId, ObjectUuid, ProtocolVersion,
+    SerializedValue, ServiceId, ServiceUuid, TraceContext,
 };
-use crate::discoverer::{Discoverer, DiscovererBuilder};
+use crate::discoverer::{Discoverer, DiscovererBuilder, DiscovererEvent, DiscovererEventKind};
 use crate::error::Error;
+use crate::function_call_map::CallResult;
 use crate::lifetime::{Lifetime, LifetimeId, LifetimeListener, LifetimeScope};
 use crate::low_level::{
-    self, PendingReceiver, PendingSender, Proxy, ProxyId, Reply, Service, ServiceInfo,
-    UnclaimedReceiver, UnclaimedSender,
+    self, ChannelCapacity, Event, PendingReceiver, PendingSender, Proxy, ProxyId, RawMessages,
+    Service, ServiceInfo, UnclaimedReceiver, UnclaimedSender,
 };
 use crate::object::Object;
-use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use futures_channel::oneshot;
+use futures_util::future::{self, Either};
 #[cfg(feature = "introspection")]
 use request::QueryIntrospectionRequest;
 use request::{
-    CallFunctionReplyRequest, CallFunctionRequest, ClaimReceiverRequest, ClaimSenderRequest,
-    CloseChannelEndRequest, CreateClaimedReceiverRequest, CreateObjectRequest, CreateProxyRequest,
-    CreateServiceRequest, DestroyBusListenerRequest, DestroyObjectRequest, DestroyServiceRequest,
-    EmitEventRequest, HandleRequest, SendItemRequest, StartBusListenerRequest,
-    StopBusListenerRequest, SubscribeAllEventsRequest, SubscribeEventRequest,
-    UnsubscribeAllEventsRequest, UnsubscribeEventRequest,
+    AddProxyEventStreamRequest, CallFunctionReplyRequest, CallFunctionRequest,
+    ClaimReceiverRequest, ClaimSenderRequest, CloseChannelEndRequest, CreateClaimedReceiverRequest,
+    CreateObjectRequest, CreateProxyRequest, CreateServiceRequest, CreateServicesRequest,
+    DestroyBusListenerRequest, DestroyObjectRequest, DestroyServiceRequest, DestroyServicesRequest,
+    EmitEventAckedRequest, EmitEventRequest, EmitKind, EventSubscriptionsRequest, HandleRequest,
+    SendItemRequest, StartBusListenerRequest, StopBusListenerRequest, SubscribeAllEventsRequest,
+    SubscribeEventRequest, UnsubscribeAllEventsRequest, UnsubscribeEventRequest,
 };
+use std::convert::Infallible;
 use std::future::Future;
 use std::hash::Hash;
 use std::mem::MaybeUninit;
-use std::num::NonZeroU32;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
 /// Handle to a client.
 ///
@@ -82,11 +90,40 @@ use std::task::{Context, Poll};
 #[derive(Debug)]
 pub struct Handle {
     send: UnboundedSender<HandleRequest>,
+    timer: Option<CallTimer>,
+    queue: Option<Arc<RequestQueue>>,
 }
 
 impl Handle {
     pub(crate) fn new(send: UnboundedSender<HandleRequest>) -> Self {
-        Self { send }
+        Self {
+            send,
+            timer: None,
+            queue: None,
+        }
+    }
+
+    pub(crate) fn set_timer(&mut self, timer: CallTimer) {
+        self.timer = Some(timer);
+    }
+
+    pub(crate) fn set_queue(&mut self, queue: Arc<RequestQueue>) {
+        self.queue = Some(queue);
+    }
+
+    /// Acquires a slot in the client's request queue.
+    ///
+    /// `Ok(false)` is returned if the request must be dropped.
+    fn acquire_queue(&self, event: bool) -> Result<bool, Error> {
+        match self.queue {
+            Some(ref queue) => queue.acquire(event),
+            None => Ok(true),
+        }
+    }
+
+    /// Returns a timer for a call's timeout, if the client has been configured with one.
+    pub(crate) fn sleep(&self, duration: Duration) -> Option<Sleep> {
+        self.timer.as_ref().map(|timer| timer.sleep(duration))
     }
 
     /// Shuts down the client.
@@ -102,6 +139,20 @@ impl Handle {
         let _ = self.send.unbounded_send(HandleRequest::Shutdown);
     }
 
+    /// Waits until the client's request queue has capacity.
+    ///
+    /// This function returns immediately, if the client has not been configured with a bounded
+    /// request queue (see [`ClientBuilder::with_queue_capacity`](crate::ClientBuilder::with_queue_capacity)). It also
+    /// returns when the client has shut down.
+    ///
+    /// Use this function to apply backpressure before e.g. emitting events, when the queue's
+    /// [`OverflowPolicy`] is not [`Wait`](OverflowPolicy::Wait).
+    pub async fn queue_ready(&self) {
+        if let Some(ref queue) = self.queue {
+            queue.ready().await;
+        }
+    }
+
     /// Creates a new object on the bus.
     ///
     /// The `uuid` must not yet exists on the bus, or else [`Error::DuplicateObject`] will be
@@ -216,30 +267,61 @@ impl Handle {
             }));
     }
 
-    pub(crate) fn call<Args>(&self, id: ServiceId, function: u32, args: &Args) -> Reply
-    where
-        Args: Serialize + ?Sized,
-    {
-        let (send, recv) = oneshot::channel();
+    pub(crate) async fn create_services(
+        &self,
+        object_id: ObjectId,
+        services: Vec<(ServiceUuid, ServiceInfo)>,
+    ) -> Result<Vec<Service>, Error> {
+        let (reply, recv) = oneshot::channel();
+        self.send
+            .unbounded_send(HandleRequest::CreateServices(CreateServicesRequest {
+                object_id,
+                services,
+                reply,
+            }))
+            .map_err(|_| Error::Shutdown)?;
 
-        match SerializedValue::serialize(args) {
-            Ok(value) => {
-                let req = HandleRequest::CallFunction(CallFunctionRequest {
-                    service_cookie: id.cookie,
-                    function,
-                    value,
-                    reply: send,
-                });
+        recv.await.map_err(|_| Error::Shutdown)?
+    }
 
-                let _ = self.send.unbounded_send(req);
-            }
+    pub(crate) async fn destroy_services(&self, ids: Vec<ServiceId>) -> Result<(), Error> {
+        let (reply, recv) = oneshot::channel();
+        self.send
+            .unbounded_send(HandleRequest::DestroyServices(DestroyServicesRequest {
+                ids,
+                reply,
+            }))
+            .map_err(|_| Error::Shutdown)?;
 
-            Err(e) => {
-                let _ = send.send(Err(e.into()));
-            }
+        recv.await.map_err(|_| Error::Shutdown)?
+    }
+
+    pub(crate) fn call_serialized(
+        &self,
+        id: ServiceId,
+        function: u32,
+        value: SerializedValue,
+        trace_context: Option<TraceContext>,
+        priority: CallPriority,
+    ) -> oneshot::Receiver<CallResult> {
+        let (send, recv) = oneshot::channel();
+
+        if let Err(e) = self.acquire_queue(false) {
+            let _ = send.send(Err(e));
+            return recv;
         }
 
-        Reply::new(recv, function)
+        let req = HandleRequest::CallFunction(CallFunctionRequest {
+            service_cookie: id.cookie,
+            function,
+            value,
+            trace_context,
+            priority,
+            reply: send,
+        });
+
+        let _ = self.send.unbounded_send(req);
+        recv
     }
 
     pub(crate) fn function_call_reply(
@@ -247,6 +329,8 @@ impl Handle {
         serial: u32,
         result: CallFunctionResult,
     ) -> Result<(), Error> {
+        self.acquire_queue(false)?;
+
         self.send
             .unbounded_send(HandleRequest::CallFunctionReply(CallFunctionReplyRequest {
                 serial,
@@ -255,25 +339,83 @@ impl Handle {
             .map_err(|_| Error::Shutdown)
     }
 
-    pub(crate) fn emit_event<T>(
+    pub(crate) fn emit_event(
         &self,
         service_id: ServiceId,
         event: u32,
-        value: &T,
-    ) -> Result<(), Error>
-    where
-        T: Serialize + ?Sized,
-    {
-        let value = SerializedValue::serialize(value)?;
+        value: SerializedValue,
+        kind: EmitKind,
+        trace_context: Option<TraceContext>,
+    ) -> Result<(), Error> {
+        if !self.acquire_queue(true)? {
+            return Ok(());
+        }
+
         self.send
             .unbounded_send(HandleRequest::EmitEvent(EmitEventRequest {
                 service_cookie: service_id.cookie,
                 event,
                 value,
+                kind,
+                trace_context,
             }))
             .map_err(|_| Error::Shutdown)
     }
 
+    pub(crate) fn ack_event(&self, serial: u32) -> Result<(), Error> {
+        self.send
+            .unbounded_send(HandleRequest::AckEvent(serial))
+            .map_err(|_| Error::Shutdown)
+    }
+
+    pub(crate) async fn emit_event_acked(
+        &self,
+        service_id: ServiceId,
+        event: u32,
+        value: SerializedValue,
+        trace_context: Option<TraceContext>,
+    ) -> Result<usize, Error> {
+        let (reply, recv) = oneshot::channel();
+
+        if let Some(ref queue) = self.queue {
+            if queue.policy() == OverflowPolicy::Wait {
+                queue.ready().await;
+            }
+        }
+
+        self.acquire_queue(false)?;
+
+        self.send
+            .unbounded_send(HandleRequest::EmitEventAcked(EmitEventAckedRequest {
+                service_cookie: service_id.cookie,
+                event,
+                value,
+                trace_context,
+                reply,
+            }))
+            .map_err(|_| Error::Shutdown)?;
+
+        recv.await.map_err(|_| Error::Shutdown)?
+    }
+
+    pub(crate) async fn event_subscriptions(
+        &self,
+        service_id: ServiceId,
+    ) -> Result<low_level::EventSubscriptions, Error> {
+        let (reply, recv) = oneshot::channel();
+
+        self.send
+            .unbounded_send(HandleRequest::EventSubscriptions(
+                EventSubscriptionsRequest {
+                    service_cookie: service_id.cookie,
+                    reply,
+                },
+            ))
+            .map_err(|_| Error::Shutdown)?;
+
+        recv.await.map_err(|_| Error::Shutdown)?
+    }
+
     /// Creates a low-level [`ChannelBuilder`](low_level::ChannelBuilder).
     ///
     /// Alternatively, [`ChannelBuilder::new`](low_level::ChannelBuilder::new) can be used as well.
@@ -302,9 +444,8 @@ impl Handle {
 
     pub(crate) async fn create_claimed_receiver(
         &self,
-        capacity: u32,
+        capacity: ChannelCapacity,
     ) -> Result<(UnclaimedSender, PendingReceiver), Error> {
-        let capacity = NonZeroU32::new(capacity).unwrap_or(NonZeroU32::new(1).unwrap());
         let (reply, recv) = oneshot::channel();
 
         self.send
@@ -313,7 +454,19 @@ impl Handle {
             ))
             .map_err(|_| Error::Shutdown)?;
 
-        recv.await.map_err(|_| Error::Shutdown)
+        recv.await.map_err(|_| Error::Shutdown)?
+    }
+
+    pub(crate) async fn create_broadcast(
+        &self,
+    ) -> Result<(ChannelCookie, UnboundedReceiver<u32>), Error> {
+        let (reply, recv) = oneshot::channel();
+
+        self.send
+            .unbounded_send(HandleRequest::CreateBroadcast(reply))
+            .map_err(|_| Error::Shutdown)?;
+
+        recv.await.map_err(|_| Error::Shutdown)?
     }
 
     pub(crate) fn close_channel_end(
@@ -338,7 +491,7 @@ impl Handle {
     pub(crate) async fn claim_sender(
         &self,
         cookie: ChannelCookie,
-    ) -> Result<(UnboundedReceiver<u32>, u32), Error> {
+    ) -> Result<(UnboundedReceiver<u32>, ChannelCapacity), Error> {
         let (reply, recv) = oneshot::channel();
         self.send
             .unbounded_send(HandleRequest::ClaimSender(ClaimSenderRequest {
@@ -353,10 +506,8 @@ impl Handle {
     pub(crate) async fn claim_receiver(
         &self,
         cookie: ChannelCookie,
-        capacity: u32,
-    ) -> Result<(UnboundedReceiver<SerializedValue>, NonZeroU32), Error> {
-        let capacity = NonZeroU32::new(capacity).unwrap_or(NonZeroU32::new(1).unwrap());
-
+        capacity: ChannelCapacity,
+    ) -> Result<(UnboundedReceiver<SerializedValue>, ChannelCapacity), Error> {
         let (reply, recv) = oneshot::channel();
         self.send
             .unbounded_send(HandleRequest::ClaimReceiver(ClaimReceiverRequest {
@@ -374,6 +525,8 @@ impl Handle {
         cookie: ChannelCookie,
         value: SerializedValue,
     ) -> Result<(), Error> {
+        self.acquire_queue(false)?;
+
         self.send
             .unbounded_send(HandleRequest::SendItem(SendItemRequest { cookie, value }))
             .map_err(|_| Error::Shutdown)
@@ -594,6 +747,11 @@ impl Handle {
         Discoverer::builder(self)
     }
 
+    /// Create a new `BusTrackerBuilder`.
+    pub fn create_bus_tracker(&self) -> BusTrackerBuilder<'_> {
+        BusTracker::builder(self)
+    }
+
     /// Find an object with a specific set of services.
     ///
     /// If `object` is `None`, then any object that has all required services may be
@@ -682,24 +840,7 @@ impl Handle {
             debug_assert_eq!(event.object_id().uuid, object);
         }
 
-        // SAFETY: This creates an array of MaybeUninit, which doesn't require initialization.
-        let mut ids: [MaybeUninit<ServiceId>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-
-        for (&uuid, id) in services.iter().zip(&mut ids) {
-            id.write(event.service_id(&discoverer, uuid));
-        }
-
-        // SAFETY: All N elements have been initialized in the loop above.
-        //
-        // In some future version of Rust, all this can be simplified; see:
-        // https://github.com/rust-lang/rust/issues/96097
-        // https://github.com/rust-lang/rust/issues/61956
-        let ids = unsafe {
-            (*(&MaybeUninit::new(ids) as *const _ as *const MaybeUninit<[ServiceId; N]>))
-                .assume_init_read()
-        };
-
-        Ok(Some((event.object_id(), ids)))
+        Ok(Some(Self::discovered_ids(&discoverer, event, services)))
     }
 
     /// Finds any object implementing a set of services.
@@ -748,24 +889,7 @@ impl Handle {
             debug_assert_eq!(event.object_id().uuid, object);
         }
 
-        // SAFETY: This creates an array of MaybeUninit, which doesn't require initialization.
-        let mut ids: [MaybeUninit<ServiceId>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-
-        for (&uuid, id) in services.iter().zip(&mut ids) {
-            id.write(event.service_id(&discoverer, uuid));
-        }
-
-        // SAFETY: All N elements have been initialized in the loop above.
-        //
-        // In some future version of Rust, all this can be simplified; see:
-        // https://github.com/rust-lang/rust/issues/96097
-        // https://github.com/rust-lang/rust/issues/61956
-        let ids = unsafe {
-            (*(&MaybeUninit::new(ids) as *const _ as *const MaybeUninit<[ServiceId; N]>))
-                .assume_init_read()
-        };
-
-        Ok((event.object_id(), ids))
+        Ok(Self::discovered_ids(&discoverer, event, services))
     }
 
     /// Wait for any object implementing a set of services.
@@ -789,6 +913,209 @@ impl Handle {
         self.wait_for_object(Some(object.into()), services).await
     }
 
+    /// Waits for an object with a specific set of services, but at most for `timeout`.
+    ///
+    /// This function behaves like [`wait_for_object`](Self::wait_for_object), except that
+    /// `Ok(None)` is returned if no matching object appeared before `timeout` elapsed.
+    ///
+    /// Timeouts require a timer, see [`ClientBuilder::with_timer`](crate::ClientBuilder::with_timer).
+    /// [`Error::NotSupported`] is returned if the client has not been configured with one.
+    pub async fn wait_for_object_timeout<const N: usize>(
+        &self,
+        object: Option<ObjectUuid>,
+        services: &[ServiceUuid; N],
+        timeout: Duration,
+    ) -> Result<Option<(ObjectId, [ServiceId; N])>, Error> {
+        let sleep = self.sleep(timeout).ok_or(Error::NotSupported)?;
+        let wait = self.wait_for_object(object, services);
+        futures_util::pin_mut!(wait);
+
+        match future::select(wait, sleep).await {
+            Either::Left((res, _)) => res.map(Some),
+            Either::Right(((), _)) => Ok(None),
+        }
+    }
+
+    /// Finds all objects with a specific set of services.
+    ///
+    /// If `object` is `None`, then all objects that have all required services are returned.
+    /// Otherwise, the result contains at most one element.
+    ///
+    /// This is a convenience function for using a [`Discoverer`] to find all matching objects among
+    /// the current objects on the bus. The order of the returned objects is unspecified.
+    pub async fn find_all_objects<const N: usize>(
+        &self,
+        object: Option<ObjectUuid>,
+        services: &[ServiceUuid; N],
+    ) -> Result<Vec<(ObjectId, [ServiceId; N])>, Error> {
+        let mut discoverer = self
+            .create_discoverer()
+            .object((), object, services.iter().copied())
+            .build_current_only()
+            .await?;
+
+        let mut objects = Vec::new();
+
+        while let Some(event) = discoverer.next_event().await {
+            if event.kind() == DiscovererEventKind::Created {
+                objects.push(Self::discovered_ids(&discoverer, event, services));
+            }
+        }
+
+        Ok(objects)
+    }
+
+    /// Finds the object with a specific set of services, that maximizes some key.
+    ///
+    /// All candidates are first collected with [`find_all_objects`](Self::find_all_objects). Then
+    /// `key` is evaluated for each of them and the candidate with the greatest key is returned. If
+    /// several candidates are equally good, the last one is returned. The key function is
+    /// asynchronous and fallible, so that it can e.g. query the candidates' services.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin::core::{ObjectUuid, ServiceUuid};
+    /// use aldrin::low_level::ServiceInfo;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker = aldrin_test::tokio::TestBroker::new();
+    /// # let handle = broker.add_client().await;
+    /// let uuid = ServiceUuid::new_v4();
+    ///
+    /// let obj1 = handle.create_object(ObjectUuid::new_v4()).await?;
+    /// let svc1 = obj1.create_service(uuid, ServiceInfo::new(1)).await?;
+    ///
+    /// let obj2 = handle.create_object(ObjectUuid::new_v4()).await?;
+    /// let svc2 = obj2.create_service(uuid, ServiceInfo::new(2)).await?;
+    ///
+    /// // Find the object with the highest service version.
+    /// let (object_id, [service_id]) = handle
+    ///     .find_object_by_key(None, &[uuid], |_, [service_id]| {
+    ///         let handle = &handle;
+    ///         async move { Ok(handle.create_proxy(service_id).await?.version()) }
+    ///     })
+    ///     .await?
+    ///     .unwrap();
+    ///
+    /// assert_eq!(object_id, obj2.id());
+    /// assert_eq!(service_id, svc2.id());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn find_object_by_key<const N: usize, F, Fut, K>(
+        &self,
+        object: Option<ObjectUuid>,
+        services: &[ServiceUuid; N],
+        mut key: F,
+    ) -> Result<Option<(ObjectId, [ServiceId; N])>, Error>
+    where
+        F: FnMut(ObjectId, [ServiceId; N]) -> Fut,
+        Fut: Future<Output = Result<K, Error>>,
+        K: Ord,
+    {
+        let mut best = None;
+
+        for (object_id, service_ids) in self.find_all_objects(object, services).await? {
+            let candidate = key(object_id, service_ids).await?;
+
+            match best {
+                Some((ref best_key, _, _)) if candidate < *best_key => {}
+                _ => best = Some((candidate, object_id, service_ids)),
+            }
+        }
+
+        Ok(best.map(|(_, object_id, service_ids)| (object_id, service_ids)))
+    }
+
+    /// Returns a snapshot of all objects currently on the bus.
+    ///
+    /// This is a convenience function for using a [`BusListener`] with the
+    /// [`Current`](BusListenerScope::Current) scope. The order of the returned ids is unspecified.
+    pub async fn list_objects(&self) -> Result<Vec<ObjectId>, Error> {
+        let mut bus_listener = self.create_bus_listener().await?;
+        bus_listener.add_filter(BusListenerFilter::any_object())?;
+        bus_listener.start(BusListenerScope::Current).await?;
+
+        let mut objects = Vec::new();
+
+        while let Some(event) = bus_listener.next_event().await {
+            if let BusEvent::ObjectCreated(object) = event {
+                objects.push(object);
+            }
+        }
+
+        Ok(objects)
+    }
+
+    /// Returns a snapshot of all services currently on the bus, that match `filter`.
+    ///
+    /// This is a convenience function for using a [`BusListener`] with the
+    /// [`Current`](BusListenerScope::Current) scope. The order of the returned ids is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin::core::{BusListenerServiceFilter, ObjectUuid, ServiceUuid};
+    /// use aldrin::low_level::ServiceInfo;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker = aldrin_test::tokio::TestBroker::new();
+    /// # let handle = broker.add_client().await;
+    /// let obj = handle.create_object(ObjectUuid::new_v4()).await?;
+    /// let svc = obj.create_service(ServiceUuid::new_v4(), ServiceInfo::new(0)).await?;
+    ///
+    /// let services = handle.list_services(BusListenerServiceFilter::any()).await?;
+    /// assert_eq!(services, [svc.id()]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_services(
+        &self,
+        filter: BusListenerServiceFilter,
+    ) -> Result<Vec<ServiceId>, Error> {
+        let mut bus_listener = self.create_bus_listener().await?;
+        bus_listener.add_filter(BusListenerFilter::service(filter))?;
+        bus_listener.start(BusListenerScope::Current).await?;
+
+        let mut services = Vec::new();
+
+        while let Some(event) = bus_listener.next_event().await {
+            if let BusEvent::ServiceCreated(service) = event {
+                services.push(service);
+            }
+        }
+
+        Ok(services)
+    }
+
+    fn discovered_ids<const N: usize>(
+        discoverer: &Discoverer<()>,
+        event: DiscovererEvent<()>,
+        services: &[ServiceUuid; N],
+    ) -> (ObjectId, [ServiceId; N]) {
+        // SAFETY: This creates an array of MaybeUninit, which doesn't require initialization.
+        let mut ids: [MaybeUninit<ServiceId>; N] = unsafe { MaybeUninit::uninit().assume_init() };
+
+        for (&uuid, id) in services.iter().zip(&mut ids) {
+            id.write(event.service_id(discoverer, uuid));
+        }
+
+        // SAFETY: All N elements have been initialized in the loop above.
+        //
+        // In some future version of Rust, all this can be simplified; see:
+        // https://github.com/rust-lang/rust/issues/96097
+        // https://github.com/rust-lang/rust/issues/61956
+        let ids = unsafe {
+            (*(&MaybeUninit::new(ids) as *const _ as *const MaybeUninit<[ServiceId; N]>))
+                .assume_init_read()
+        };
+
+        (event.object_id(), ids)
+    }
+
     /// Creates a new lifetime scope.
     pub async fn create_lifetime_scope(&self) -> Result<LifetimeScope, Error> {
         self.create_object(ObjectUuid::new_v4())
@@ -834,10 +1161,43 @@ impl Handle {
         recv.await.map_err(|_| Error::Shutdown)?
     }
 
+    /// Takes a sample of the bus time.
+    ///
+    /// This calls the [`ClockService`] and measures the call's round-trip time. See [`BusTime`] for
+    /// how the sample relates the bus time to the local clock.
+    ///
+    /// If there is no clock service on the bus, then [`Error::InvalidService`] is returned.
+    pub async fn bus_time(&self) -> Result<BusTime, Error> {
+        let Some((_, [service])) = self
+            .find_specific_object(ClockService::OBJECT_UUID, &[ClockService::SERVICE_UUID])
+            .await?
+        else {
+            return Err(Error::InvalidService);
+        };
+
+        let proxy = self.create_proxy(service).await?;
+
+        let sent = Instant::now();
+        let bus = proxy
+            .call(ClockService::NOW, &())
+            .cast::<Duration, Infallible>()
+            .await?
+            .unwrap_or_else(|e| match e {});
+        let received = Instant::now();
+
+        Ok(BusTime::new(sent, received, bus))
+    }
+
     pub(crate) fn destroy_proxy_now(&self, proxy: ProxyId) {
         let _ = self.send.unbounded_send(HandleRequest::DestroyProxy(proxy));
     }
 
+    pub(crate) fn add_proxy_event_stream(&self, proxy: ProxyId, send: UnboundedSender<Event>) {
+        let _ = self.send.unbounded_send(HandleRequest::AddProxyEventStream(
+            AddProxyEventStreamRequest { proxy, send },
+        ));
+    }
+
     pub(crate) async fn subscribe_event(&self, proxy: ProxyId, event: u32) -> Result<(), Error> {
         let (reply, recv) = oneshot::channel();
 
@@ -890,6 +1250,66 @@ impl Handle {
         recv.await.map_err(|_| Error::Shutdown)?
     }
 
+    /// Sends a raw message to the broker.
+    ///
+    /// The message is sent as-is, bypassing all of the client's own bookkeeping. This is an escape
+    /// hatch for building protocol tools (e.g. fuzzers or conformance checkers) and should not be
+    /// needed otherwise. Messages, that alter state managed by the client (e.g. destroying one of
+    /// its objects), can leave the client in an inconsistent state.
+    ///
+    /// Serials of raw requests are entirely up to the caller. They should be chosen from
+    /// [`RAW_SERIAL_MIN`](low_level::RAW_SERIAL_MIN) upwards, because the client never uses those
+    /// for its own requests. Replies can be received with [`raw_messages`](Self::raw_messages).
+    ///
+    /// Once this method or [`raw_messages`](Self::raw_messages) has been used, the client no longer
+    /// treats unexpected messages from the broker as an error and ignores them instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin::core::message::{Message, Sync, SyncReply};
+    /// use aldrin::low_level::RAW_SERIAL_MIN;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker = aldrin_test::tokio::TestBroker::new();
+    /// # let handle = broker.add_client().await;
+    /// let mut messages = handle.raw_messages()?;
+    /// handle.send_raw_message(Sync {
+    ///     serial: RAW_SERIAL_MIN,
+    /// })?;
+    ///
+    /// let reply = messages.next_message().await;
+    /// assert_eq!(
+    ///     reply,
+    ///     Some(Message::SyncReply(SyncReply {
+    ///         serial: RAW_SERIAL_MIN,
+    ///     })),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_raw_message(&self, msg: impl Into<Message>) -> Result<(), Error> {
+        self.send
+            .unbounded_send(HandleRequest::SendRawMessage(msg.into()))
+            .map_err(|_| Error::Shutdown)
+    }
+
+    /// Subscribes to the raw stream of messages received by the client.
+    ///
+    /// The returned [`RawMessages`] receive a copy of every message, that the client receives from
+    /// the broker from now on. See [`send_raw_message`](Self::send_raw_message) for more
+    /// information.
+    pub fn raw_messages(&self) -> Result<RawMessages, Error> {
+        let (send, recv) = mpsc::unbounded();
+
+        self.send
+            .unbounded_send(HandleRequest::RawMessages(send))
+            .map_err(|_| Error::Shutdown)?;
+
+        Ok(RawMessages::new(recv))
+    }
+
     /// Registers an introspectable type with the client.
     ///
     /// Registered types are made available to be queried by other clients.
@@ -909,6 +1329,9 @@ impl Handle {
     }
 
     /// Submits all registered introspectable types to the broker.
+    ///
+    /// Only types, that haven't been submitted before, are sent to the broker. It is thus cheap to
+    /// call this function (and the generated `register_introspection` functions) repeatedly.
     #[cfg(feature = "introspection")]
     pub fn submit_introspection(&self) -> Result<(), Error> {
         self.send
@@ -917,6 +1340,13 @@ impl Handle {
     }
 
     /// Queries the introspection for a type.
+    ///
+    /// Introspections, that were received from the broker, are cached by the client and shared by
+    /// all of its handles (see [`IntrospectionCache`](crate::IntrospectionCache)). Because a
+    /// [`TypeId`] is computed from the introspection itself, cached entries never become outdated.
+    /// Concurrent queries for the same type are combined into a single query to the broker.
+    ///
+    /// Types, that are unavailable, are not cached and will be queried again.
     #[cfg(feature = "introspection")]
     pub async fn query_introspection(
         &self,
@@ -943,6 +1373,8 @@ impl Clone for Handle {
 
         Self {
             send: self.send.clone(),
+            timer: self.timer.clone(),
+            queue: self.queue.clone(),
         }
     }
 }
@@ -3,23 +3,24 @@ use crate::bus_listener::BusListener;
 use crate::core::introspection::DynIntrospectable;
 use crate::core::message::{
     AddBusListenerFilter, AddChannelCapacity, CallFunctionResult, ClearBusListenerFilters,
-    DestroyBusListenerResult, DestroyObjectResult, RemoveBusListenerFilter, StartBusListenerResult,
-    StopBusListenerResult,
+    DestroyBusListenerResult, DestroyObjectResult, Message, RemoveBusListenerFilter,
+    StartBusListenerResult, StopBusListenerResult,
 };
 #[cfg(feature = "introspection")]
 use crate::core::TypeId;
 use crate::core::{
-    BusListenerCookie, BusListenerScope, ChannelCookie, ChannelEnd, ObjectCookie, ObjectId,
-    ObjectUuid, ProtocolVersion, SerializedValue, ServiceCookie, ServiceId, ServiceUuid,
+    BusListenerCookie, BusListenerScope, CallPriority, ChannelCookie, ChannelEnd, ObjectCookie,
+    ObjectId, ObjectUuid, ProtocolVersion, SerializedValue, ServiceCookie, ServiceId, ServiceUuid,
+    TraceContext,
 };
+use crate::function_call_map::CallResult;
 use crate::lifetime::LifetimeListener;
 use crate::low_level::{
-    PendingReceiver, PendingSender, Proxy, ProxyId, Service, ServiceInfo, UnclaimedReceiver,
-    UnclaimedSender,
+    ChannelCapacity, Event, EventSubscriptions, PendingReceiver, PendingSender, Proxy, ProxyId,
+    Service, ServiceInfo, UnclaimedReceiver, UnclaimedSender,
 };
 use crate::{Error, Object};
 use futures_channel::{mpsc, oneshot};
-use std::num::NonZeroU32;
 
 #[derive(Debug)]
 pub(crate) enum HandleRequest {
@@ -30,10 +31,16 @@ pub(crate) enum HandleRequest {
     DestroyObject(DestroyObjectRequest),
     CreateService(CreateServiceRequest),
     DestroyService(DestroyServiceRequest),
+    CreateServices(CreateServicesRequest),
+    DestroyServices(DestroyServicesRequest),
     CallFunction(CallFunctionRequest),
     CallFunctionReply(CallFunctionReplyRequest),
     EmitEvent(EmitEventRequest),
+    EmitEventAcked(EmitEventAckedRequest),
+    AckEvent(u32),
+    EventSubscriptions(EventSubscriptionsRequest),
     CreateClaimedSender(CreateClaimedSenderRequest),
+    CreateBroadcast(CreateBroadcastRequest),
     CreateClaimedReceiver(CreateClaimedReceiverRequest),
     CloseChannelEnd(CloseChannelEndRequest),
     ClaimSender(ClaimSenderRequest),
@@ -53,10 +60,13 @@ pub(crate) enum HandleRequest {
     GetProtocolVersion(GetProtocolVersionRequest),
     CreateProxy(CreateProxyRequest),
     DestroyProxy(ProxyId),
+    AddProxyEventStream(AddProxyEventStreamRequest),
     SubscribeEvent(SubscribeEventRequest),
     UnsubscribeEvent(UnsubscribeEventRequest),
     SubscribeAllEvents(SubscribeAllEventsRequest),
     UnsubscribeAllEvents(UnsubscribeAllEventsRequest),
+    SendRawMessage(Message),
+    RawMessages(mpsc::UnboundedSender<Message>),
     #[cfg(feature = "introspection")]
     RegisterIntrospection(DynIntrospectable),
     #[cfg(feature = "introspection")]
@@ -65,6 +75,20 @@ pub(crate) enum HandleRequest {
     QueryIntrospection(QueryIntrospectionRequest),
 }
 
+impl HandleRequest {
+    /// Checks whether the request occupies a slot of the client's request queue.
+    pub fn is_queued(&self) -> bool {
+        matches!(
+            self,
+            Self::CallFunction(_)
+                | Self::CallFunctionReply(_)
+                | Self::EmitEvent(_)
+                | Self::EmitEventAcked(_)
+                | Self::SendItem(_)
+        )
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct CreateObjectRequest {
     pub uuid: ObjectUuid,
@@ -91,12 +115,27 @@ pub(crate) struct DestroyServiceRequest {
     pub reply: oneshot::Sender<Result<(), Error>>,
 }
 
+#[derive(Debug)]
+pub(crate) struct CreateServicesRequest {
+    pub object_id: ObjectId,
+    pub services: Vec<(ServiceUuid, ServiceInfo)>,
+    pub reply: oneshot::Sender<Result<Vec<Service>, Error>>,
+}
+
+#[derive(Debug)]
+pub(crate) struct DestroyServicesRequest {
+    pub ids: Vec<ServiceId>,
+    pub reply: oneshot::Sender<Result<(), Error>>,
+}
+
 #[derive(Debug)]
 pub(crate) struct CallFunctionRequest {
     pub service_cookie: ServiceCookie,
     pub function: u32,
     pub value: SerializedValue,
-    pub reply: oneshot::Sender<Result<CallFunctionResult, Error>>,
+    pub trace_context: Option<TraceContext>,
+    pub priority: CallPriority,
+    pub reply: oneshot::Sender<CallResult>,
 }
 
 #[derive(Debug)]
@@ -110,14 +149,41 @@ pub(crate) struct EmitEventRequest {
     pub service_cookie: ServiceCookie,
     pub event: u32,
     pub value: SerializedValue,
+    pub kind: EmitKind,
+    pub trace_context: Option<TraceContext>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum EmitKind {
+    Normal,
+    Retained,
+    Reliable,
+}
+
+#[derive(Debug)]
+pub(crate) struct EmitEventAckedRequest {
+    pub service_cookie: ServiceCookie,
+    pub event: u32,
+    pub value: SerializedValue,
+    pub trace_context: Option<TraceContext>,
+    pub reply: oneshot::Sender<Result<usize, Error>>,
+}
+
+#[derive(Debug)]
+pub(crate) struct EventSubscriptionsRequest {
+    pub service_cookie: ServiceCookie,
+    pub reply: oneshot::Sender<Result<EventSubscriptions, Error>>,
 }
 
 pub(crate) type CreateClaimedSenderRequest = oneshot::Sender<(PendingSender, UnclaimedReceiver)>;
 
+pub(crate) type CreateBroadcastRequest =
+    oneshot::Sender<Result<(ChannelCookie, mpsc::UnboundedReceiver<u32>), Error>>;
+
 #[derive(Debug)]
 pub(crate) struct CreateClaimedReceiverRequest {
-    pub capacity: NonZeroU32,
-    pub reply: oneshot::Sender<(UnclaimedSender, PendingReceiver)>,
+    pub capacity: ChannelCapacity,
+    pub reply: oneshot::Sender<Result<(UnclaimedSender, PendingReceiver), Error>>,
 }
 
 #[derive(Debug)]
@@ -131,15 +197,15 @@ pub(crate) struct CloseChannelEndRequest {
 #[derive(Debug)]
 pub(crate) struct ClaimSenderRequest {
     pub cookie: ChannelCookie,
-    pub reply: oneshot::Sender<Result<(mpsc::UnboundedReceiver<u32>, u32), Error>>,
+    pub reply: oneshot::Sender<Result<(mpsc::UnboundedReceiver<u32>, ChannelCapacity), Error>>,
 }
 
 #[derive(Debug)]
 pub(crate) struct ClaimReceiverRequest {
     pub cookie: ChannelCookie,
-    pub capacity: NonZeroU32,
+    pub capacity: ChannelCapacity,
     pub reply:
-        oneshot::Sender<Result<(mpsc::UnboundedReceiver<SerializedValue>, NonZeroU32), Error>>,
+        oneshot::Sender<Result<(mpsc::UnboundedReceiver<SerializedValue>, ChannelCapacity), Error>>,
 }
 
 #[derive(Debug)]
@@ -183,6 +249,12 @@ pub(crate) struct CreateProxyRequest {
     pub reply: oneshot::Sender<Result<Proxy, Error>>,
 }
 
+#[derive(Debug)]
+pub(crate) struct AddProxyEventStreamRequest {
+    pub proxy: ProxyId,
+    pub send: mpsc::UnboundedSender<Event>,
+}
+
 #[derive(Debug)]
 pub(crate) struct SubscribeEventRequest {
     pub proxy: ProxyId,
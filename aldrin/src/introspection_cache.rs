@@ -0,0 +1,170 @@
+use crate::core::introspection::Introspection;
+use crate::core::{SerializedValue, SerializedValueSlice, TypeId};
+use crate::error::IntrospectionCacheError;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+const MAGIC: [u8; 8] = *b"ALDRNINT";
+const VERSION: u8 = 1;
+
+/// Cache of introspections received from the broker.
+///
+/// Every [`Client`](crate::Client) memoizes the results of
+/// [`Handle::query_introspection`](crate::Handle::query_introspection) per [`TypeId`]. By default,
+/// each client uses its own, empty cache. A cache can be shared between clients and persisted
+/// across runs by passing it to
+/// [`ClientBuilder::with_introspection_cache`](crate::ClientBuilder::with_introspection_cache).
+///
+/// Because a `TypeId` is computed from the introspection itself, entries never become outdated and
+/// a cache can be kept indefinitely.
+///
+/// `IntrospectionCache` is a cheap handle to a shared cache. All clones refer to the same entries.
+///
+/// # File format
+///
+/// The file format is the same as the binary format of the broker's introspection dumps. It starts
+/// with the 8 bytes magic `ALDRNINT`, followed by a 1 byte version (currently 1) and a serialized
+/// map from [`TypeId`] to [`Introspection`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use aldrin::{Client, IntrospectionCache};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let (async_transport, _) = aldrin::core::channel::unbounded();
+/// let cache = IntrospectionCache::load("introspection.cache").unwrap_or_default();
+///
+/// let client = Client::builder(async_transport)
+///     .with_introspection_cache(cache.clone())
+///     .connect()
+///     .await?;
+///
+/// // Run the client ...
+///
+/// cache.save("introspection.cache")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IntrospectionCache {
+    entries: Arc<Mutex<BTreeMap<TypeId, SerializedValue>>>,
+}
+
+impl IntrospectionCache {
+    /// Creates a new empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of types in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Indicates whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().unwrap().is_empty()
+    }
+
+    /// Indicates whether the cache contains a type.
+    pub fn contains(&self, type_id: TypeId) -> bool {
+        self.entries.lock().unwrap().contains_key(&type_id)
+    }
+
+    /// Returns all types in the cache.
+    pub fn type_ids(&self) -> Vec<TypeId> {
+        self.entries.lock().unwrap().keys().copied().collect()
+    }
+
+    /// Returns the introspection of a type.
+    pub fn get(&self, type_id: TypeId) -> Option<Introspection> {
+        self.get_serialized(type_id)?.deserialize().ok()
+    }
+
+    /// Adds an introspection to the cache.
+    pub fn insert(&self, introspection: &Introspection) -> Result<(), IntrospectionCacheError> {
+        let serialized = SerializedValue::serialize(introspection)?;
+        self.insert_serialized(introspection.type_id(), serialized);
+        Ok(())
+    }
+
+    /// Removes a type from the cache.
+    ///
+    /// Returns `true` if the type was part of the cache.
+    pub fn remove(&self, type_id: TypeId) -> bool {
+        self.entries.lock().unwrap().remove(&type_id).is_some()
+    }
+
+    /// Removes all types from the cache.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Encodes the cache in the binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let serialized = SerializedValue::serialize(&*self.entries.lock().unwrap()).unwrap();
+
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 1 + serialized.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&serialized);
+        bytes
+    }
+
+    /// Decodes a cache from the binary format.
+    ///
+    /// All introspections are validated against their [`TypeId`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IntrospectionCacheError> {
+        let bytes = bytes
+            .strip_prefix(&MAGIC)
+            .ok_or(IntrospectionCacheError::InvalidFormat)?;
+
+        let (&version, bytes) = bytes
+            .split_first()
+            .ok_or(IntrospectionCacheError::InvalidFormat)?;
+
+        if version != VERSION {
+            return Err(IntrospectionCacheError::UnsupportedVersion(version));
+        }
+
+        let entries: BTreeMap<TypeId, SerializedValue> =
+            SerializedValueSlice::new(bytes).deserialize()?;
+
+        let cache = Self::new();
+
+        for (type_id, introspection) in entries {
+            match introspection.deserialize::<Introspection>() {
+                Ok(introspection) if introspection.type_id() == type_id => {
+                    cache.insert(&introspection)?;
+                }
+
+                _ => return Err(IntrospectionCacheError::InvalidIntrospection(type_id)),
+            }
+        }
+
+        Ok(cache)
+    }
+
+    /// Loads a cache from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, IntrospectionCacheError> {
+        let bytes = fs::read(path)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Saves the cache to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), IntrospectionCacheError> {
+        fs::write(path, self.to_bytes()).map_err(Into::into)
+    }
+
+    pub(crate) fn get_serialized(&self, type_id: TypeId) -> Option<SerializedValue> {
+        self.entries.lock().unwrap().get(&type_id).cloned()
+    }
+
+    pub(crate) fn insert_serialized(&self, type_id: TypeId, introspection: SerializedValue) {
+        self.entries.lock().unwrap().insert(type_id, introspection);
+    }
+}
@@ -59,25 +59,41 @@
 #![deny(missing_debug_implementations)]
 #![deny(missing_docs)]
 
+mod auth;
 mod bus_listener;
+mod bus_tracker;
+mod call_builder;
 mod channel;
 mod client;
+mod clock;
 mod discoverer;
+mod event_stream;
 mod function_call_map;
 mod handle;
+#[cfg(feature = "introspection")]
+mod introspection_cache;
 mod lifetime;
 mod object;
 mod promise;
+mod property;
 mod reply;
 mod serial_map;
+mod service_runtime;
 #[cfg(test)]
 mod test;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod error;
 pub mod low_level;
+pub mod mock;
 #[cfg(feature = "codegen")]
 #[doc(hidden)]
 pub mod private;
+#[cfg(feature = "tower")]
+pub mod tower;
+#[cfg(feature = "tracing")]
+pub mod trace;
 
 pub use aldrin_core as core;
 #[cfg(feature = "codegen")]
@@ -92,19 +108,31 @@ pub use aldrin_macros::{
 pub use aldrin_macros::{
     IntrospectableFromAldrin as Introspectable, KeyTypeOfFromAldrin as KeyTypeOf,
 };
+pub use auth::{AuthProvider, TokenAuth};
 pub use bus_listener::BusListener;
+pub use bus_tracker::{BusTracker, BusTrackerBuilder, BusTrackerIter, TrackedObject};
+pub use call_builder::CallBuilder;
 pub use channel::{
-    ChannelBuilder, PendingReceiver, PendingSender, Receiver, Sender, UnboundReceiver,
-    UnboundSender, UnclaimedReceiver, UnclaimedSender,
+    BroadcastReceiver, BroadcastSender, ChannelBuilder, PendingReceiver, PendingSender, Receiver,
+    Sender, UnboundBroadcastReceiver, UnboundReceiver, UnboundSender, UnclaimedReceiver,
+    UnclaimedSender,
 };
-pub use client::Client;
+pub use client::{Client, ClientBuilder, OverflowPolicy};
+pub use clock::{BusTime, ClockService};
 pub use discoverer::{
     Discoverer, DiscovererBuilder, DiscovererEntry, DiscovererEntryIter, DiscovererEvent,
     DiscovererEventKind, DiscovererIter, DiscovererIterEntry,
 };
 pub use error::Error;
+pub use event_stream::EventStream;
 pub use handle::Handle;
+#[cfg(feature = "introspection")]
+pub use introspection_cache::IntrospectionCache;
 pub use lifetime::{Lifetime, LifetimeId, LifetimeScope};
 pub use object::Object;
 pub use promise::Promise;
+pub use property::{
+    ApplyDelta, NoDelta, PropertyPublisher, PropertySubscriber, PropertyUpdate, PropertyWatch,
+};
 pub use reply::Reply;
+pub use service_runtime::{ServiceEvent, ServiceFunction, ServiceRuntime};
@@ -3,27 +3,61 @@
 //! The types in this module are primarily intended for use by the code generator.
 
 mod call;
+mod call_options;
 mod channel;
 mod event;
+mod event_subscriptions;
+mod interceptor;
 mod promise;
 mod proxy;
+mod proxy_layer;
+mod raw_messages;
 mod reply;
 mod service;
 mod service_info;
+#[cfg(feature = "statistics")]
+mod statistics;
 #[cfg(test)]
 mod test;
 
+pub(crate) use channel::ChannelCapacity;
+pub(crate) use interceptor::Interceptors;
 pub(crate) use proxy::ProxyId;
+pub(crate) use proxy_layer::{LayeredCall, ProxyLayers};
 pub(crate) use service::RawCall;
+#[cfg(feature = "statistics")]
+pub(crate) use statistics::{ProxyCounters, ServiceCounters};
 
+pub use crate::core::CallPriority;
 pub use call::Call;
+pub use call_options::CallOptions;
 pub use channel::{
     ChannelBuilder, PendingReceiver, PendingSender, Receiver, Sender, UnboundReceiver,
     UnboundSender, UnclaimedReceiver, UnclaimedSender,
 };
 pub use event::Event;
+pub use event_subscriptions::{EventSubscriptions, SubscriptionChange};
+pub use interceptor::{Intercept, Interceptor};
 pub use promise::Promise;
 pub use proxy::Proxy;
+pub use proxy_layer::{OutgoingCall, ProxyLayer, ReplyAction};
+pub use raw_messages::{RawMessages, RAW_SERIAL_MIN};
 pub use reply::Reply;
 pub use service::Service;
 pub use service_info::ServiceInfo;
+#[cfg(feature = "statistics")]
+pub use statistics::{ProxyStatistics, ServiceStatistics};
+
+use crate::core::TraceContext;
+
+pub(crate) fn current_trace_context() -> Option<TraceContext> {
+    #[cfg(feature = "tracing")]
+    {
+        crate::trace::current()
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    {
+        None
+    }
+}
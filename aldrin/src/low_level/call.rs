@@ -1,15 +1,30 @@
-use super::Promise;
-use crate::core::{Deserialize, DeserializeError, SerializedValue, SerializedValueSlice};
+#[cfg(feature = "statistics")]
+use super::ServiceCounters;
+use super::{Interceptors, Promise};
+#[cfg(feature = "introspection")]
+use crate::core::introspection::{ValidationError, Validator};
+use crate::core::{
+    Deserialize, DeserializeError, SerializedValue, SerializedValueSlice, TraceContext,
+};
 use crate::error::Error;
+#[cfg(feature = "introspection")]
+use crate::error::InvalidArguments;
 use crate::handle::Handle;
+use futures_channel::mpsc::UnboundedSender;
 use futures_channel::oneshot::Receiver;
+use std::convert::Infallible;
+#[cfg(any(feature = "introspection", feature = "statistics"))]
+use std::sync::Arc;
 
 /// Pending call.
 #[derive(Debug)]
 pub struct Call {
     id: u32,
     args: SerializedValue,
+    trace_context: Option<TraceContext>,
     promise: Promise,
+    #[cfg(feature = "introspection")]
+    validator: Option<Arc<Validator>>,
 }
 
 impl Call {
@@ -19,14 +34,36 @@ impl Call {
         serial: u32,
         id: u32,
         args: SerializedValue,
+        trace_context: Option<TraceContext>,
     ) -> Self {
         Self {
             id,
             args,
-            promise: Promise::new(client, aborted, serial),
+            trace_context,
+            promise: Promise::new(client, aborted, serial, id),
+            #[cfg(feature = "introspection")]
+            validator: None,
         }
     }
 
+    pub(crate) fn set_interceptors(&mut self, interceptors: Interceptors) {
+        self.promise.set_interceptors(interceptors);
+    }
+
+    #[cfg(feature = "statistics")]
+    pub(crate) fn set_statistics(&mut self, statistics: Arc<ServiceCounters>) {
+        self.promise.set_statistics(statistics);
+    }
+
+    pub(crate) fn set_in_flight(&mut self, in_flight: UnboundedSender<Infallible>) {
+        self.promise.set_in_flight(in_flight);
+    }
+
+    #[cfg(feature = "introspection")]
+    pub(crate) fn set_validator(&mut self, validator: Arc<Validator>) {
+        self.validator = Some(validator);
+    }
+
     /// Returns a handle to the client that was used to create the call.
     pub fn client(&self) -> &Handle {
         self.promise.client()
@@ -42,11 +79,35 @@ impl Call {
         &self.args
     }
 
+    /// Replaces the call's serialized arguments.
+    ///
+    /// This is primarily useful for [`Interceptors`](super::Interceptor).
+    pub fn set_args(&mut self, args: SerializedValue) {
+        self.args = args;
+    }
+
     /// Deserializes the call's arguments.
     pub fn deserialize<T: Deserialize>(&self) -> Result<T, DeserializeError> {
         self.args.deserialize()
     }
 
+    /// Validates the call's arguments against the service's introspection.
+    ///
+    /// This always succeeds, unless validation has been enabled with
+    /// [`Service::enable_args_validation`](super::Service::enable_args_validation).
+    #[cfg(feature = "introspection")]
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        match self.validator {
+            Some(ref validator) => validator.validate_args(self.id, &self.args),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the trace context, that the caller attached to the call.
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        self.trace_context
+    }
+
     /// Converts this call into its promise object.
     pub fn into_promise(self) -> Promise {
         self.promise
@@ -60,7 +121,8 @@ impl Call {
     /// Deserializes arguments and casts the promise to a specific set of result types.
     ///
     /// If deserialization fails, then the call will be replied using [`Promise::invalid_args`] and
-    /// [`Error::InvalidArguments`] will be returned.
+    /// [`Error::InvalidArguments`] will be returned. The same applies if argument validation is
+    /// enabled and the arguments fail [validation](Self::validate).
     pub fn deserialize_and_cast<Args, T, E>(
         self,
     ) -> Result<(Args, crate::promise::Promise<T, E>), Error>
@@ -69,7 +131,13 @@ impl Call {
         T: ?Sized,
         E: ?Sized,
     {
-        match self.args.deserialize() {
+        #[cfg(feature = "introspection")]
+        if let Err(e) = self.validate() {
+            let _ = self.promise.invalid_args();
+            return Err(InvalidArguments::with_validation_error(self.id, e).into());
+        }
+
+        match self.args.deserialize_shared() {
             Ok(args) => Ok((args, self.promise.cast())),
 
             Err(e) => {
@@ -0,0 +1,58 @@
+use crate::core::CallPriority;
+use std::time::Duration;
+
+/// Options for a single call.
+///
+/// Options are passed to [`Proxy::call_with_options`](super::Proxy::call_with_options). Generated
+/// proxies provide them with a [`CallBuilder`](crate::CallBuilder).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct CallOptions {
+    timeout: Option<Duration>,
+    priority: CallPriority,
+}
+
+impl CallOptions {
+    /// Creates new `CallOptions` with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the timeout of the call.
+    pub fn timeout(self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Sets the timeout of the call.
+    ///
+    /// The call fails with [`Error::Timeout`](crate::Error::Timeout) (wrapped in
+    /// [`Error::Call`](crate::Error::Call)) and is aborted if no reply arrives in time. The timeout
+    /// covers all attempts of the call, including retries by [`ProxyLayer`s](super::ProxyLayer).
+    ///
+    /// Timeouts require a timer, see [`ClientBuilder::with_timer`](crate::ClientBuilder::with_timer).
+    /// Calls with a timeout fail with [`Error::NotSupported`](crate::Error::NotSupported) if the
+    /// client doesn't have one.
+    #[must_use = "this method follows the builder pattern and returns a new `CallOptions`"]
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the priority of the call.
+    pub fn priority(self) -> CallPriority {
+        self.priority
+    }
+
+    /// Sets the priority of the call.
+    ///
+    /// Brokers forward high-priority calls and their replies ahead of other queued messages. The
+    /// priority is also available to [`ProxyLayer`s](super::ProxyLayer) through
+    /// [`OutgoingCall::options`](super::OutgoingCall::options), which can e.g. throttle or reject
+    /// low-priority calls.
+    ///
+    /// Priorities are not sent to brokers with protocol versions older than 1.19.
+    #[must_use = "this method follows the builder pattern and returns a new `CallOptions`"]
+    pub fn set_priority(mut self, priority: CallPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+}
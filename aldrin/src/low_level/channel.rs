@@ -1,4 +1,5 @@
 mod builder;
+mod capacity;
 mod established;
 mod pending;
 mod raw;
@@ -7,6 +8,8 @@ mod unclaimed;
 
 use raw::RawChannel;
 
+pub(crate) use capacity::ChannelCapacity;
+
 pub use builder::ChannelBuilder;
 pub use established::{Receiver, Sender};
 pub use pending::{PendingReceiver, PendingSender};
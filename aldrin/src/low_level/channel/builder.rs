@@ -1,4 +1,7 @@
-use super::{PendingReceiver, PendingSender, UnclaimedReceiver, UnclaimedSender};
+use super::{
+    ChannelCapacity, PendingReceiver, PendingSender, RawChannel, Sender, UnclaimedReceiver,
+    UnclaimedSender,
+};
 use crate::channel as high_level;
 use crate::error::Error;
 use crate::handle::Handle;
@@ -64,6 +67,50 @@ impl<'a> ChannelBuilder<'a> {
         self,
         capacity: u32,
     ) -> Result<(UnclaimedSender, PendingReceiver), Error> {
-        self.client.create_claimed_receiver(capacity).await
+        self.client
+            .create_claimed_receiver(ChannelCapacity::items(capacity))
+            .await
+    }
+
+    /// Creates a new channel and claims the receiver with a capacity measured in bytes.
+    ///
+    /// Unlike [`claim_receiver`](Self::claim_receiver), the capacity limits the total size of the
+    /// serialized items, which are in flight or queued at the receiver. The sender is allowed to
+    /// send as long as some capacity is left, such that memory usage is bounded by `max_bytes` plus
+    /// the size of one item.
+    ///
+    /// A capacity of 0 will be treated as if 1 was specified instead.
+    ///
+    /// This requires protocol version 1.19. [`Error::NotSupported`] is returned for older brokers.
+    pub async fn claim_receiver_bytes(
+        self,
+        max_bytes: u32,
+    ) -> Result<(UnclaimedSender, PendingReceiver), Error> {
+        self.client
+            .create_claimed_receiver(ChannelCapacity::bytes(max_bytes))
+            .await
+    }
+
+    /// Creates a new broadcast channel and claims the sender.
+    ///
+    /// Broadcast channels have a single sender, but any number of receivers. Every item is
+    /// delivered to all receivers, that have been claimed at the time the item was sent. Each
+    /// client can claim at most one receiver of a broadcast channel.
+    ///
+    /// The sender is granted capacity only up to the smallest capacity of all receivers. It will
+    /// thus have no capacity at all, until at least one receiver has been claimed.
+    ///
+    /// Receivers are claimed with [`UnclaimedReceiver::claim`]. Receivers of broadcast channels
+    /// cannot be created in the pending state.
+    ///
+    /// This requires protocol version 1.19. [`Error::NotSupported`] is returned for older brokers.
+    pub async fn broadcast(self) -> Result<Sender, Error> {
+        let (cookie, capacity_added) = self.client.create_broadcast().await?;
+
+        Ok(Sender::new(
+            RawChannel::claimed(self.client.clone(), cookie),
+            capacity_added,
+            ChannelCapacity::Items(0),
+        ))
     }
 }
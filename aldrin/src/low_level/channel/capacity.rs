@@ -0,0 +1,48 @@
+use crate::core::ChannelEndWithCapacity;
+
+/// Capacity of a channel's receiver.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum ChannelCapacity {
+    /// Number of items.
+    Items(u32),
+
+    /// Number of bytes of serialized items.
+    Bytes(u32),
+}
+
+impl ChannelCapacity {
+    /// Creates a capacity in items, treating 0 as 1.
+    pub fn items(capacity: u32) -> Self {
+        Self::Items(capacity.max(1))
+    }
+
+    /// Creates a capacity in bytes, treating 0 as 1.
+    pub fn bytes(capacity: u32) -> Self {
+        Self::Bytes(capacity.max(1))
+    }
+
+    pub fn get(self) -> u32 {
+        match self {
+            Self::Items(capacity) | Self::Bytes(capacity) => capacity,
+        }
+    }
+
+    pub fn is_bytes(self) -> bool {
+        matches!(self, Self::Bytes(_))
+    }
+
+    pub fn from_core(end: ChannelEndWithCapacity) -> Option<Self> {
+        match end {
+            ChannelEndWithCapacity::Sender | ChannelEndWithCapacity::BroadcastSender => None,
+            ChannelEndWithCapacity::Receiver(capacity) => Some(Self::Items(capacity)),
+            ChannelEndWithCapacity::ReceiverBytes(capacity) => Some(Self::Bytes(capacity)),
+        }
+    }
+
+    pub fn to_core(self) -> ChannelEndWithCapacity {
+        match self {
+            Self::Items(capacity) => ChannelEndWithCapacity::Receiver(capacity),
+            Self::Bytes(capacity) => ChannelEndWithCapacity::ReceiverBytes(capacity),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::RawChannel;
+use super::{ChannelCapacity, RawChannel};
 use crate::channel as high_level;
 use crate::core::{ChannelCookie, Deserialize, Serialize, SerializedValue};
 use crate::error::Error;
@@ -8,7 +8,6 @@ use futures_core::stream::{FusedStream, Stream};
 #[cfg(feature = "sink")]
 use futures_sink::Sink;
 use std::future;
-use std::num::NonZeroU32;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -23,19 +22,24 @@ const LOW_CAPACITY: u32 = 4;
 pub struct Sender {
     inner: RawChannel<true>,
     capacity_added: mpsc::UnboundedReceiver<u32>,
-    capacity: u32,
+
+    /// Remaining capacity, which can become negative in byte mode.
+    capacity: i64,
+
+    bytes: bool,
 }
 
 impl Sender {
     pub(crate) fn new(
         inner: RawChannel<true>,
         capacity_added: mpsc::UnboundedReceiver<u32>,
-        capacity: u32,
+        capacity: ChannelCapacity,
     ) -> Self {
         Self {
             inner,
             capacity_added,
-            capacity,
+            capacity: capacity.get().into(),
+            bytes: capacity.is_bytes(),
         }
     }
 
@@ -106,7 +110,7 @@ impl Sender {
     pub fn poll_receiver_closed(&mut self, cx: &mut Context) -> Poll<()> {
         loop {
             match Pin::new(&mut self.capacity_added).poll_next(cx) {
-                Poll::Ready(Some(added_capacity)) => self.capacity += added_capacity,
+                Poll::Ready(Some(added_capacity)) => self.capacity += i64::from(added_capacity),
                 Poll::Ready(None) => break Poll::Ready(()),
                 Poll::Pending => break Poll::Pending,
             }
@@ -151,7 +155,7 @@ impl Sender {
     pub fn poll_send_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
         loop {
             match Pin::new(&mut self.capacity_added).poll_next(cx) {
-                Poll::Ready(Some(added_capacity)) => self.capacity += added_capacity,
+                Poll::Ready(Some(added_capacity)) => self.capacity += i64::from(added_capacity),
                 Poll::Ready(None) => break Poll::Ready(Err(Error::InvalidChannel)),
                 Poll::Pending if self.capacity > 0 => break Poll::Ready(Ok(())),
                 Poll::Pending => break Poll::Pending,
@@ -162,6 +166,11 @@ impl Sender {
     /// Waits until the channel has capacity to send at least one item.
     ///
     /// Before sending an item, this method must be used to ensure there is capacity.
+    ///
+    /// If the receiver's capacity is measured in bytes (see
+    /// [`ChannelBuilder::claim_receiver_bytes`](super::ChannelBuilder::claim_receiver_bytes)), then
+    /// this waits until at least one byte of capacity is left. The next item may then exceed the
+    /// remaining capacity.
     pub async fn send_ready(&mut self) -> Result<(), Error> {
         future::poll_fn(|cx| self.poll_send_ready(cx)).await
     }
@@ -172,8 +181,11 @@ impl Sender {
     /// prior to sending an item.
     pub fn start_send_serialized(&mut self, item: SerializedValue) -> Result<(), Error> {
         debug_assert!(self.capacity > 0);
+
+        let cost = if self.bytes { item.len() as i64 } else { 1 };
         self.inner.send_item(item)?;
-        self.capacity -= 1;
+        self.capacity -= cost;
+
         Ok(())
     }
 
@@ -239,21 +251,23 @@ impl Sink<SerializedValue> for Sender {
 pub struct Receiver {
     inner: RawChannel<false>,
     items: mpsc::UnboundedReceiver<SerializedValue>,
-    max_capacity: NonZeroU32,
-    cur_capacity: u32,
+    max_capacity: ChannelCapacity,
+
+    /// Remaining capacity, which can become negative in byte mode.
+    cur_capacity: i64,
 }
 
 impl Receiver {
     pub(crate) fn new(
         inner: RawChannel<false>,
         items: mpsc::UnboundedReceiver<SerializedValue>,
-        max_capacity: NonZeroU32,
+        max_capacity: ChannelCapacity,
     ) -> Self {
         Self {
             inner,
             items,
             max_capacity,
-            cur_capacity: max_capacity.get(),
+            cur_capacity: max_capacity.get().into(),
         }
     }
 
@@ -327,8 +341,10 @@ impl Receiver {
 
     /// Polls the channel for the next item.
     pub fn poll_next_serialized(&mut self, cx: &mut Context) -> Poll<Option<SerializedValue>> {
+        let max_capacity = i64::from(self.max_capacity.get());
+
         debug_assert!(self.cur_capacity > 0);
-        debug_assert!(self.cur_capacity <= self.max_capacity.get());
+        debug_assert!(self.cur_capacity <= max_capacity);
 
         let item = match Pin::new(&mut self.items).poll_next(cx) {
             Poll::Ready(Some(item)) => item,
@@ -336,17 +352,23 @@ impl Receiver {
             Poll::Pending => return Poll::Pending,
         };
 
-        self.cur_capacity -= 1;
-        if self.cur_capacity <= LOW_CAPACITY {
-            let diff = self.max_capacity.get() - self.cur_capacity;
+        // Capacity in bytes is added back in larger chunks, because items can be arbitrarily small.
+        let (cost, low_capacity) = match self.max_capacity {
+            ChannelCapacity::Items(_) => (1, i64::from(LOW_CAPACITY)),
+            ChannelCapacity::Bytes(_) => (item.len() as i64, max_capacity / 2),
+        };
+
+        self.cur_capacity -= cost;
+        if self.cur_capacity <= low_capacity {
+            let diff = u32::try_from(max_capacity - self.cur_capacity).unwrap_or(u32::MAX);
             debug_assert!(diff >= 1);
 
             self.inner.add_channel_capacity(diff);
-            self.cur_capacity += diff;
+            self.cur_capacity += i64::from(diff);
         }
 
         debug_assert!(self.cur_capacity > 0);
-        debug_assert!(self.cur_capacity <= self.max_capacity.get());
+        debug_assert!(self.cur_capacity <= max_capacity);
 
         Poll::Ready(Some(item))
     }
@@ -362,9 +384,11 @@ impl Receiver {
         cx: &mut Context,
     ) -> Poll<Result<Option<T>, Error>> {
         match self.poll_next_serialized(cx) {
-            Poll::Ready(Some(item)) => {
-                Poll::Ready(item.deserialize().map(Some).map_err(Error::invalid_item))
-            }
+            Poll::Ready(Some(item)) => Poll::Ready(
+                item.deserialize_shared()
+                    .map(Some)
+                    .map_err(Error::invalid_item),
+            ),
 
             Poll::Ready(None) => Poll::Ready(Ok(None)),
             Poll::Pending => Poll::Pending,
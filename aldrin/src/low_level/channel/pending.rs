@@ -1,4 +1,4 @@
-use super::{RawChannel, Receiver, Sender};
+use super::{ChannelCapacity, RawChannel, Receiver, Sender};
 use crate::channel as high_level;
 use crate::core::{ChannelCookie, SerializedValue};
 use crate::error::Error;
@@ -6,7 +6,6 @@ use crate::handle::Handle;
 use futures_channel::{mpsc, oneshot};
 use std::fmt;
 use std::future::{self, Future};
-use std::num::NonZeroU32;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -22,14 +21,14 @@ use std::task::{Context, Poll};
 #[derive(Debug)]
 pub struct PendingSender {
     inner: RawChannel<true>,
-    recv: OneshotReceiver<Result<(mpsc::UnboundedReceiver<u32>, u32), Error>>,
+    recv: OneshotReceiver<Result<(mpsc::UnboundedReceiver<u32>, ChannelCapacity), Error>>,
 }
 
 impl PendingSender {
     pub(crate) fn new(
         client: Handle,
         cookie: ChannelCookie,
-        recv: oneshot::Receiver<Result<(mpsc::UnboundedReceiver<u32>, u32), Error>>,
+        recv: oneshot::Receiver<Result<(mpsc::UnboundedReceiver<u32>, ChannelCapacity), Error>>,
     ) -> Self {
         Self {
             inner: RawChannel::claimed(client, cookie),
@@ -165,7 +164,7 @@ impl PendingSender {
 pub struct PendingReceiver {
     inner: RawChannel<false>,
     recv: OneshotReceiver<Result<mpsc::UnboundedReceiver<SerializedValue>, Error>>,
-    capacity: NonZeroU32,
+    capacity: ChannelCapacity,
 }
 
 impl PendingReceiver {
@@ -173,7 +172,7 @@ impl PendingReceiver {
         client: Handle,
         cookie: ChannelCookie,
         recv: oneshot::Receiver<Result<mpsc::UnboundedReceiver<SerializedValue>, Error>>,
-        capacity: NonZeroU32,
+        capacity: ChannelCapacity,
     ) -> Self {
         Self {
             inner: RawChannel::claimed(client, cookie),
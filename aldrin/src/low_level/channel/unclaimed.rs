@@ -1,4 +1,4 @@
-use super::{RawChannel, Receiver, Sender, UnboundReceiver, UnboundSender};
+use super::{ChannelCapacity, RawChannel, Receiver, Sender, UnboundReceiver, UnboundSender};
 use crate::channel as high_level;
 use crate::core::ChannelCookie;
 use crate::error::Error;
@@ -234,14 +234,34 @@ impl UnclaimedReceiver {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn claim(mut self, capacity: u32) -> Result<Receiver, Error> {
+    pub async fn claim(self, capacity: u32) -> Result<Receiver, Error> {
+        self.claim_impl(ChannelCapacity::items(capacity)).await
+    }
+
+    /// Claims the receiver with a capacity measured in bytes.
+    ///
+    /// See [`ChannelBuilder::claim_receiver_bytes`](super::ChannelBuilder::claim_receiver_bytes)
+    /// for more information on byte-based capacities.
+    ///
+    /// A capacity of 0 will be treated as if 1 was specified instead.
+    ///
+    /// This requires protocol version 1.19. [`Error::NotSupported`] is returned for older brokers.
+    pub async fn claim_bytes(self, max_bytes: u32) -> Result<Receiver, Error> {
+        self.claim_impl(ChannelCapacity::bytes(max_bytes)).await
+    }
+
+    async fn claim_impl(mut self, capacity: ChannelCapacity) -> Result<Receiver, Error> {
         self.inner.set_claimed();
 
-        let (items, max_capacity) = self
-            .client()
-            .claim_receiver(self.cookie(), capacity)
-            .await?;
+        match self.client().claim_receiver(self.cookie(), capacity).await {
+            Ok((items, max_capacity)) => Ok(Receiver::new(self.inner, items, max_capacity)),
 
-        Ok(Receiver::new(self.inner, items, max_capacity))
+            Err(e) => {
+                // There is nothing to close after a failed claim. Closing anyway would affect a
+                // receiver of a broadcast channel, that this client may have claimed already.
+                self.inner.set_closed();
+                Err(e)
+            }
+        }
     }
 }
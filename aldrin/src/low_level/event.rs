@@ -1,15 +1,29 @@
-use crate::core::{Deserialize, DeserializeError, SerializedValue, SerializedValueSlice};
+use crate::core::{
+    Deserialize, DeserializeError, SerializedValue, SerializedValueSlice, TraceContext,
+};
 
 /// Event emitted by a service.
 #[derive(Debug, Clone)]
 pub struct Event {
     id: u32,
     args: SerializedValue,
+    trace_context: Option<TraceContext>,
+    ack_serial: Option<u32>,
 }
 
 impl Event {
-    pub(crate) fn new(id: u32, args: SerializedValue) -> Self {
-        Self { id, args }
+    pub(crate) fn new(
+        id: u32,
+        args: SerializedValue,
+        trace_context: Option<TraceContext>,
+        ack_serial: Option<u32>,
+    ) -> Self {
+        Self {
+            id,
+            args,
+            trace_context,
+            ack_serial,
+        }
     }
 
     /// Returns the event's id.
@@ -31,4 +45,21 @@ impl Event {
     pub fn deserialize<T: Deserialize>(&self) -> Result<T, DeserializeError> {
         self.args.deserialize()
     }
+
+    /// Returns the trace context, that the service attached to the event.
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        self.trace_context
+    }
+
+    /// Indicates whether the event is reliable.
+    ///
+    /// Reliable events should be acknowledged with [`Proxy::ack_event`](super::Proxy::ack_event).
+    /// The broker otherwise keeps them buffered and delivers them again after a reconnect.
+    pub fn is_reliable(&self) -> bool {
+        self.ack_serial.is_some()
+    }
+
+    pub(crate) fn ack_serial(&self) -> Option<u32> {
+        self.ack_serial
+    }
 }
@@ -0,0 +1,157 @@
+use crate::error::Error;
+use futures_channel::mpsc::UnboundedReceiver;
+use futures_core::stream::{FusedStream, Stream};
+use std::collections::HashSet;
+use std::future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Tracks which events of a [`Service`](super::Service) have subscribers.
+///
+/// `EventSubscriptions` are created with
+/// [`Service::event_subscriptions`](super::Service::event_subscriptions). They allow servers to
+/// start and stop producing events depending on whether anyone is interested in them.
+///
+/// Events are considered to have subscribers, if at least one client has subscribed to them,
+/// either individually or by subscribing to all events of the service.
+///
+/// The stream of [`SubscriptionChange`]s ends when the service is destroyed.
+#[derive(Debug)]
+pub struct EventSubscriptions {
+    events: HashSet<u32>,
+    all_events: bool,
+    changes: UnboundedReceiver<SubscriptionChange>,
+}
+
+impl EventSubscriptions {
+    pub(crate) fn new(
+        events: HashSet<u32>,
+        all_events: bool,
+        changes: UnboundedReceiver<SubscriptionChange>,
+    ) -> Self {
+        Self {
+            events,
+            all_events,
+            changes,
+        }
+    }
+
+    /// Checks whether an event currently has subscribers.
+    pub fn has_subscribers(&mut self, event: u32) -> bool {
+        loop {
+            match self.changes.try_next() {
+                Ok(Some(change)) => self.apply(change),
+                Err(_) => break,
+
+                Ok(None) => {
+                    self.clear();
+                    break;
+                }
+            }
+        }
+
+        self.all_events || self.events.contains(&event)
+    }
+
+    /// Polls for the next change of subscriptions.
+    pub fn poll_next_change(&mut self, cx: &mut Context) -> Poll<Option<SubscriptionChange>> {
+        match Pin::new(&mut self.changes).poll_next(cx) {
+            Poll::Ready(Some(change)) => {
+                self.apply(change);
+                Poll::Ready(Some(change))
+            }
+
+            Poll::Ready(None) => {
+                self.clear();
+                Poll::Ready(None)
+            }
+
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    /// Returns the next change of subscriptions.
+    ///
+    /// `None` is returned when the service has been destroyed.
+    pub async fn next_change(&mut self) -> Option<SubscriptionChange> {
+        future::poll_fn(|cx| self.poll_next_change(cx)).await
+    }
+
+    /// Waits until an event has subscribers.
+    ///
+    /// This function returns immediately, if the event already has subscribers.
+    /// [`Error::InvalidService`] is returned when the service is destroyed.
+    pub async fn subscribed(&mut self, event: u32) -> Result<(), Error> {
+        while !self.has_subscribers(event) {
+            if self.next_change().await.is_none() {
+                return Err(Error::InvalidService);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Waits until an event no longer has subscribers.
+    ///
+    /// This function returns immediately, if the event doesn't have subscribers. It also returns
+    /// when the service is destroyed.
+    pub async fn unsubscribed(&mut self, event: u32) {
+        while self.has_subscribers(event) {
+            self.next_change().await;
+        }
+    }
+
+    fn apply(&mut self, change: SubscriptionChange) {
+        match change {
+            SubscriptionChange::Subscribed(event) => {
+                self.events.insert(event);
+            }
+
+            SubscriptionChange::Unsubscribed(event) => {
+                self.events.remove(&event);
+            }
+
+            SubscriptionChange::AllSubscribed => self.all_events = true,
+            SubscriptionChange::AllUnsubscribed => self.all_events = false,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.events.clear();
+        self.all_events = false;
+    }
+}
+
+impl Stream for EventSubscriptions {
+    type Item = SubscriptionChange;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<SubscriptionChange>> {
+        self.poll_next_change(cx)
+    }
+}
+
+impl FusedStream for EventSubscriptions {
+    fn is_terminated(&self) -> bool {
+        self.changes.is_terminated()
+    }
+}
+
+/// Change of the subscriptions of a service's events.
+///
+/// See [`EventSubscriptions`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SubscriptionChange {
+    /// An event got its first subscriber.
+    Subscribed(u32),
+
+    /// An event lost its last subscriber.
+    Unsubscribed(u32),
+
+    /// All events got subscribed.
+    AllSubscribed,
+
+    /// The subscription to all events was removed.
+    ///
+    /// Individual subscriptions of events may still exist.
+    AllUnsubscribed,
+}
@@ -0,0 +1,122 @@
+use super::Call;
+use crate::core::message::CallFunctionResult;
+use std::fmt;
+use std::sync::Arc;
+
+/// Intercepts the calls and replies of a service.
+///
+/// Interceptors implement cross-cutting concerns like logging, metrics or authorization once for
+/// all functions of a service. They are added to a service with
+/// [`Service::add_interceptor`](super::Service::add_interceptor). Generated services provide an
+/// `add_interceptor` method as well.
+///
+/// If a service has multiple interceptors, then [`on_call`](Self::on_call) is invoked in the
+/// order in which they were added, and [`on_reply`](Self::on_reply) in the reverse order.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin::core::message::CallFunctionResult;
+/// use aldrin::low_level::{Call, Intercept, Interceptor};
+///
+/// struct DenyFunction(u32);
+///
+/// impl Interceptor for DenyFunction {
+///     fn on_call(&self, call: &mut Call) -> Intercept {
+///         if call.id() == self.0 {
+///             Intercept::Reply(CallFunctionResult::InvalidFunction)
+///         } else {
+///             Intercept::Continue
+///         }
+///     }
+/// }
+/// ```
+pub trait Interceptor: Send + Sync + 'static {
+    /// Intercepts an incoming call.
+    ///
+    /// This is invoked before the call is returned from
+    /// [`Service::poll_next_call`](super::Service::poll_next_call). The call's arguments may be
+    /// modified with [`Call::set_args`].
+    ///
+    /// Returning [`Intercept::Reply`] replies to the call immediately and the call is not
+    /// dispatched to the service. Subsequent interceptors are skipped as well. The reply passes
+    /// through [`on_reply`](Self::on_reply) of this and all previous interceptors.
+    ///
+    /// The default implementation returns [`Intercept::Continue`].
+    fn on_call(&self, call: &mut Call) -> Intercept {
+        let _ = call;
+        Intercept::Continue
+    }
+
+    /// Intercepts the reply to a call.
+    ///
+    /// This is invoked with the id of the called function and can modify the `result` before it
+    /// is sent to the caller. It is also invoked when the call is aborted, e.g. because the
+    /// [`Promise`](super::Promise) was dropped.
+    ///
+    /// The default implementation does nothing.
+    fn on_reply(&self, function: u32, result: &mut CallFunctionResult) {
+        let _ = (function, result);
+    }
+}
+
+impl<I: Interceptor + ?Sized> Interceptor for Arc<I> {
+    fn on_call(&self, call: &mut Call) -> Intercept {
+        (**self).on_call(call)
+    }
+
+    fn on_reply(&self, function: u32, result: &mut CallFunctionResult) {
+        (**self).on_reply(function, result)
+    }
+}
+
+/// Decision of an [`Interceptor`] about an incoming call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Intercept {
+    /// Continue with the next interceptor or dispatch the call to the service.
+    Continue,
+
+    /// Reply to the call immediately without dispatching it.
+    Reply(CallFunctionResult),
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors(Arc<Vec<Arc<dyn Interceptor>>>);
+
+impl Interceptors {
+    pub fn push(&mut self, interceptor: Arc<dyn Interceptor>) {
+        Arc::make_mut(&mut self.0).push(interceptor);
+    }
+
+    /// Passes a call through all interceptors.
+    ///
+    /// If an interceptor replies to the call, then that reply is returned after passing it through
+    /// `on_reply` of all interceptors, that have seen the call.
+    pub fn on_call(&self, call: &mut Call) -> Option<CallFunctionResult> {
+        for (i, interceptor) in self.0.iter().enumerate() {
+            if let Intercept::Reply(mut result) = interceptor.on_call(call) {
+                for interceptor in self.0[..=i].iter().rev() {
+                    interceptor.on_reply(call.id(), &mut result);
+                }
+
+                return Some(result);
+            }
+        }
+
+        None
+    }
+
+    pub fn on_reply(&self, function: u32, result: &mut CallFunctionResult) {
+        for interceptor in self.0.iter().rev() {
+            interceptor.on_reply(function, result);
+        }
+    }
+}
+
+impl fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Interceptors")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
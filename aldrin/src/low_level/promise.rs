@@ -1,12 +1,19 @@
+use super::Interceptors;
+#[cfg(feature = "statistics")]
+use super::ServiceCounters;
 use crate::core::message::CallFunctionResult;
 use crate::core::Serialize;
 use crate::error::Error;
 use crate::handle::Handle;
 use crate::Promise as HlPromise;
+use futures_channel::mpsc::UnboundedSender;
 use futures_channel::oneshot::Receiver;
 use futures_core::FusedFuture;
+use std::convert::Infallible;
 use std::future::{self, Future};
 use std::pin::Pin;
+#[cfg(feature = "statistics")]
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 /// Replies to a pending call.
@@ -15,17 +22,42 @@ pub struct Promise {
     client: Option<Handle>,
     aborted: Receiver<()>,
     serial: u32,
+    function: u32,
+    interceptors: Interceptors,
+    #[cfg(feature = "statistics")]
+    statistics: Option<Arc<ServiceCounters>>,
+
+    /// Keeps the service from shutting down as long as the call is pending.
+    in_flight: Option<UnboundedSender<Infallible>>,
 }
 
 impl Promise {
-    pub(crate) fn new(client: Handle, aborted: Receiver<()>, serial: u32) -> Self {
+    pub(crate) fn new(client: Handle, aborted: Receiver<()>, serial: u32, function: u32) -> Self {
         Self {
             client: Some(client),
             aborted,
             serial,
+            function,
+            interceptors: Interceptors::default(),
+            #[cfg(feature = "statistics")]
+            statistics: None,
+            in_flight: None,
         }
     }
 
+    pub(crate) fn set_interceptors(&mut self, interceptors: Interceptors) {
+        self.interceptors = interceptors;
+    }
+
+    #[cfg(feature = "statistics")]
+    pub(crate) fn set_statistics(&mut self, statistics: Arc<ServiceCounters>) {
+        self.statistics = Some(statistics);
+    }
+
+    pub(crate) fn set_in_flight(&mut self, in_flight: UnboundedSender<Infallible>) {
+        self.in_flight = Some(in_flight);
+    }
+
     /// Returns a handle to the client that was used to create the promise.
     pub fn client(&self) -> &Handle {
         self.client.as_ref().unwrap()
@@ -49,65 +81,61 @@ impl Promise {
     }
 
     /// Signals that the call was successful.
-    pub fn ok<T>(mut self, value: &T) -> Result<(), Error>
+    pub fn ok<T>(self, value: &T) -> Result<(), Error>
     where
         T: Serialize + ?Sized,
     {
         let res = CallFunctionResult::ok_with_serialize_value(value)?;
 
-        self.client
-            .take()
-            .unwrap()
-            .function_call_reply(self.serial, res)
+        self.reply(res)
     }
 
     /// Signals that the call was successful without returning a value.
-    pub fn done(mut self) -> Result<(), Error> {
+    pub fn done(self) -> Result<(), Error> {
         let res = CallFunctionResult::ok_with_serialize_value(&())?;
 
-        self.client
-            .take()
-            .unwrap()
-            .function_call_reply(self.serial, res)
+        self.reply(res)
     }
 
     /// Signals that the call failed.
-    pub fn err<E>(mut self, value: &E) -> Result<(), Error>
+    pub fn err<E>(self, value: &E) -> Result<(), Error>
     where
         E: Serialize + ?Sized,
     {
         let res = CallFunctionResult::err_with_serialize_value(value)?;
 
-        self.client
-            .take()
-            .unwrap()
-            .function_call_reply(self.serial, res)
+        self.reply(res)
     }
 
     /// Aborts the call.
     ///
     /// The caller will be notified that the call was aborted.
-    pub fn abort(mut self) -> Result<(), Error> {
-        self.client
-            .take()
-            .unwrap()
-            .function_call_reply(self.serial, CallFunctionResult::Aborted)
+    pub fn abort(self) -> Result<(), Error> {
+        self.reply(CallFunctionResult::Aborted)
     }
 
     /// Signals that an invalid function was called.
-    pub fn invalid_function(mut self) -> Result<(), Error> {
-        self.client
-            .take()
-            .unwrap()
-            .function_call_reply(self.serial, CallFunctionResult::InvalidFunction)
+    pub fn invalid_function(self) -> Result<(), Error> {
+        self.reply(CallFunctionResult::InvalidFunction)
     }
 
     /// Signals that invalid arguments were passed to the function.
-    pub fn invalid_args(mut self) -> Result<(), Error> {
+    pub fn invalid_args(self) -> Result<(), Error> {
+        self.reply(CallFunctionResult::InvalidArgs)
+    }
+
+    pub(crate) fn reply(mut self, mut result: CallFunctionResult) -> Result<(), Error> {
+        self.interceptors.on_reply(self.function, &mut result);
+
+        #[cfg(feature = "statistics")]
+        if let Some(ref statistics) = self.statistics {
+            statistics.reply(&result);
+        }
+
         self.client
             .take()
             .unwrap()
-            .function_call_reply(self.serial, CallFunctionResult::InvalidArgs)
+            .function_call_reply(self.serial, result)
     }
 
     /// Returns whether the call was aborted by the caller.
@@ -137,7 +165,15 @@ impl Promise {
 impl Drop for Promise {
     fn drop(&mut self) {
         if let Some(client) = self.client.take() {
-            let _ = client.function_call_reply(self.serial, CallFunctionResult::Aborted);
+            let mut result = CallFunctionResult::Aborted;
+            self.interceptors.on_reply(self.function, &mut result);
+
+            #[cfg(feature = "statistics")]
+            if let Some(ref statistics) = self.statistics {
+                statistics.reply(&result);
+            }
+
+            let _ = client.function_call_reply(self.serial, result);
         }
     }
 }
@@ -1,17 +1,28 @@
-use super::{Event, Reply};
+use super::current_trace_context;
+use super::{CallOptions, Event, LayeredCall, OutgoingCall, ProxyLayer, ProxyLayers, Reply};
+#[cfg(feature = "statistics")]
+use super::{ProxyCounters, ProxyStatistics};
 #[cfg(feature = "introspection")]
 use crate::core::introspection::Introspection;
-use crate::core::{Serialize, ServiceId, ServiceInfo, TypeId};
+use crate::core::{
+    Serialize, SerializeError, SerializedValue, ServiceId, ServiceInfo, TraceContext, TypeId,
+};
 use crate::error::Error;
 use crate::handle::Handle;
-use futures_channel::mpsc::UnboundedReceiver;
+use futures_channel::mpsc::{self, UnboundedReceiver};
 use futures_core::stream::{FusedStream, Stream};
 use std::future;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use uuid::Uuid;
 
 /// Proxy to a service.
+///
+/// Proxies are cheap to clone. All clones share the same event subscriptions, i.e. subscribing to
+/// an event through one clone makes it available to all of them. Each clone has its own stream of
+/// events though, which receives all events emitted after the clone was created. The proxy is
+/// destroyed when the last clone is dropped.
 #[derive(Debug)]
 pub struct Proxy {
     id: ProxyId,
@@ -19,6 +30,10 @@ pub struct Proxy {
     svc: ServiceId,
     info: ServiceInfo,
     recv: UnboundedReceiver<Event>,
+    layers: ProxyLayers,
+    guard: Arc<ProxyGuard>,
+    #[cfg(feature = "statistics")]
+    statistics: Arc<ProxyCounters>,
 }
 
 impl Proxy {
@@ -36,10 +51,17 @@ impl Proxy {
     ) -> Self {
         Self {
             id,
+            guard: Arc::new(ProxyGuard {
+                id,
+                client: client.clone(),
+            }),
             client,
             svc,
             info,
             recv,
+            layers: ProxyLayers::default(),
+            #[cfg(feature = "statistics")]
+            statistics: Arc::default(),
         }
     }
 
@@ -68,6 +90,17 @@ impl Proxy {
         self.info.subscribe_all().unwrap_or(false)
     }
 
+    /// Returns the proxy's statistics.
+    ///
+    /// The statistics are shared by all clones of the proxy. See [`ProxyStatistics`] for more
+    /// information.
+    ///
+    /// This function requires the `statistics` feature.
+    #[cfg(feature = "statistics")]
+    pub fn statistics(&self) -> ProxyStatistics {
+        self.statistics.get()
+    }
+
     /// Queries the introspection for the proxy's service.
     #[cfg(feature = "introspection")]
     pub async fn query_introspection(&self) -> Result<Option<Introspection>, Error> {
@@ -78,25 +111,171 @@ impl Proxy {
     }
 
     /// Calls a function on the service.
+    ///
+    /// With the `tracing` feature, the trace context of the current span is attached to the call
+    /// (see the `trace` module).
     pub fn call<Args>(&self, function: u32, args: &Args) -> Reply
     where
         Args: Serialize + ?Sized,
     {
-        self.client.call(self.svc, function, args)
+        self.call_with_trace_context(function, args, current_trace_context())
+    }
+
+    /// Resolves the name of a function to its id.
+    ///
+    /// The name is looked up in the service's introspection. `None` is returned if the
+    /// introspection is unavailable or doesn't contain a function with that name.
+    #[cfg(feature = "introspection")]
+    pub async fn function_id(&self, name: &str) -> Result<Option<u32>, Error> {
+        let Some(introspection) = self.query_introspection().await? else {
+            return Ok(None);
+        };
+
+        Ok(introspection
+            .as_service_layout()
+            .and_then(|layout| layout.function_by_name(name))
+            .map(|function| function.id()))
+    }
+
+    /// Calls a function on the service by its name.
+    ///
+    /// This is intended for clients, that know only the introspection of a service, such as
+    /// scripting clients. The name is resolved with [`function_id`](Self::function_id) and the
+    /// function is then called as usual, such that services see no difference. If the name cannot
+    /// be resolved, then [`Error::InvalidFunctionName`] is returned.
+    ///
+    /// Each call queries the introspection anew. Use `function_id` to resolve names only once, when
+    /// calling the same function repeatedly.
+    #[cfg(feature = "introspection")]
+    pub async fn call_by_name<Args>(&self, name: &str, args: &Args) -> Result<Reply, Error>
+    where
+        Args: Serialize + ?Sized,
+    {
+        let function = self
+            .function_id(name)
+            .await?
+            .ok_or(Error::InvalidFunctionName)?;
+
+        Ok(self.call(function, args))
+    }
+
+    /// Calls a function on the service with an explicit trace context.
+    ///
+    /// Trace contexts require protocol version 1.19. On older versions, `trace_context` is
+    /// ignored.
+    pub fn call_with_trace_context<Args>(
+        &self,
+        function: u32,
+        args: &Args,
+        trace_context: Option<TraceContext>,
+    ) -> Reply
+    where
+        Args: Serialize + ?Sized,
+    {
+        self.call_serialized(
+            function,
+            SerializedValue::serialize(args),
+            trace_context,
+            CallOptions::new(),
+        )
+    }
+
+    /// Calls a function on the service with [`CallOptions`].
+    ///
+    /// The trace context is determined the same way as with [`call`](Self::call).
+    pub fn call_with_options<Args>(&self, function: u32, args: &Args, options: CallOptions) -> Reply
+    where
+        Args: Serialize + ?Sized,
+    {
+        self.call_serialized(
+            function,
+            SerializedValue::serialize(args),
+            current_trace_context(),
+            options,
+        )
+    }
+
+    pub(crate) fn call_serialized(
+        &self,
+        function: u32,
+        args: Result<SerializedValue, SerializeError>,
+        trace_context: Option<TraceContext>,
+        options: CallOptions,
+    ) -> Reply {
+        #[cfg(feature = "statistics")]
+        self.statistics.call(args.as_deref().ok());
+
+        let reply = match args {
+            Ok(args) if self.layers.is_empty() => Reply::new(
+                self.client.call_serialized(
+                    self.svc,
+                    function,
+                    args,
+                    trace_context,
+                    options.priority(),
+                ),
+                self.svc,
+                function,
+            ),
+
+            Ok(args) => LayeredCall::start(
+                self.client.clone(),
+                self.svc,
+                self.layers.clone(),
+                OutgoingCall::new(function, args, trace_context, options),
+            ),
+
+            Err(e) => Reply::error(e.into(), self.svc, function),
+        };
+
+        #[cfg(feature = "statistics")]
+        let reply = reply.with_statistics(self.statistics.clone());
+
+        match options.timeout() {
+            Some(timeout) => reply.with_timeout(self.client.sleep(timeout)),
+            None => reply,
+        }
+    }
+
+    /// Adds a layer to the proxy.
+    ///
+    /// All subsequent calls made through this proxy pass through the layer. See [`ProxyLayer`]
+    /// for more information.
+    pub fn add_layer(&mut self, layer: impl ProxyLayer) {
+        self.layers.push(Arc::new(layer));
+    }
+
+    /// Adds a layer to the proxy and returns it.
+    ///
+    /// This is a convenience wrapper around [`add_layer`](Self::add_layer).
+    pub fn with_layer(mut self, layer: impl ProxyLayer) -> Self {
+        self.add_layer(layer);
+        self
     }
 
     /// Subscribes to an event.
+    ///
+    /// Subscriptions are reference-counted and shared by all clones of the proxy. Subscribing
+    /// several times to the same event requires the same number of calls to
+    /// [`unsubscribe`](Self::unsubscribe), before the event is actually unsubscribed. This allows
+    /// independent parts of an application to share a proxy.
     pub async fn subscribe(&self, event: u32) -> Result<(), Error> {
         self.client.subscribe_event(self.id, event).await
     }
 
     /// Unsubscribe from an event.
+    ///
+    /// The event is unsubscribed only when this balances the last call to
+    /// [`subscribe`](Self::subscribe).
     pub async fn unsubscribe(&self, event: u32) -> Result<(), Error> {
         self.client.unsubscribe_event(self.id, event).await
     }
 
     /// Subscribes to all events.
     ///
+    /// Like individual subscriptions, this is reference-counted and shared by all clones of the
+    /// proxy.
+    ///
     /// Note that this function can return [`Error::NotSupported`].
     pub async fn subscribe_all(&self) -> Result<(), Error> {
         if self.can_subscribe_all() {
@@ -107,6 +286,10 @@ impl Proxy {
     }
 
     /// Unsubscribes from all events.
+    ///
+    /// If [`subscribe_all`](Self::subscribe_all) was called more often than this function, then
+    /// only one of these calls is balanced and all subscriptions remain. Otherwise, this removes all
+    /// subscriptions, regardless of how often each event was subscribed.
     pub async fn unsubscribe_all(&self) -> Result<(), Error> {
         self.client.unsubscribe_all_events(self.id).await
     }
@@ -118,7 +301,14 @@ impl Proxy {
     ///
     /// On protocol version 1.18 or later, `None` is also returned if the service was destroyed.
     pub fn poll_next_event(&mut self, cx: &mut Context) -> Poll<Option<Event>> {
-        Pin::new(&mut self.recv).poll_next(cx)
+        let res = Pin::new(&mut self.recv).poll_next(cx);
+
+        #[cfg(feature = "statistics")]
+        if let Poll::Ready(Some(ref event)) = res {
+            self.statistics.event(event.args());
+        }
+
+        res
     }
 
     /// Returns the next event.
@@ -131,6 +321,42 @@ impl Proxy {
         future::poll_fn(|cx| self.poll_next_event(cx)).await
     }
 
+    /// Polls for the next event and acknowledges it.
+    ///
+    /// This behaves like [`poll_next_event`](Self::poll_next_event), except that reliable events
+    /// are acknowledged before they are returned.
+    pub fn poll_next_event_acked(&mut self, cx: &mut Context) -> Poll<Option<Event>> {
+        let res = self.poll_next_event(cx);
+
+        if let Poll::Ready(Some(ref event)) = res {
+            // Errors can be ignored, because the client has shut down and the broker thus
+            // discards the event anyway.
+            let _ = self.ack_event(event);
+        }
+
+        res
+    }
+
+    /// Returns the next event and acknowledges it.
+    ///
+    /// This behaves like [`next_event`](Self::next_event), except that reliable events are
+    /// acknowledged before they are returned.
+    pub async fn next_event_acked(&mut self) -> Option<Event> {
+        future::poll_fn(|cx| self.poll_next_event_acked(cx)).await
+    }
+
+    /// Acknowledges a reliable event.
+    ///
+    /// The broker buffers reliable events for each subscriber until they are acknowledged (see
+    /// [`Service::emit_reliable`](super::Service::emit_reliable)). Acknowledging an event, that
+    /// isn't reliable, does nothing.
+    pub fn ack_event(&self, event: &Event) -> Result<(), Error> {
+        match event.ack_serial() {
+            Some(serial) => self.client.ack_event(serial),
+            None => Ok(()),
+        }
+    }
+
     /// Indicates whether no more events can be expected.
     ///
     /// When `events_finished` returns `true`, then [`next_event`](Self::next_event) is guaranteed
@@ -141,9 +367,22 @@ impl Proxy {
     }
 }
 
-impl Drop for Proxy {
-    fn drop(&mut self) {
-        self.client.destroy_proxy_now(self.id);
+impl Clone for Proxy {
+    fn clone(&self) -> Self {
+        let (send, recv) = mpsc::unbounded();
+        self.client.add_proxy_event_stream(self.id, send);
+
+        Self {
+            id: self.id,
+            client: self.client.clone(),
+            svc: self.svc,
+            info: self.info,
+            recv,
+            layers: self.layers.clone(),
+            guard: self.guard.clone(),
+            #[cfg(feature = "statistics")]
+            statistics: self.statistics.clone(),
+        }
     }
 }
 
@@ -161,6 +400,19 @@ impl FusedStream for Proxy {
     }
 }
 
+/// Destroys the proxy when the last clone is dropped.
+#[derive(Debug)]
+struct ProxyGuard {
+    id: ProxyId,
+    client: Handle,
+}
+
+impl Drop for ProxyGuard {
+    fn drop(&mut self) {
+        self.client.destroy_proxy_now(self.id);
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct ProxyId(Uuid);
 
@@ -0,0 +1,262 @@
+use super::{CallOptions, Reply};
+use crate::core::message::CallFunctionResult;
+use crate::core::{SerializedValue, ServiceId, TraceContext};
+use crate::error::Error;
+use crate::function_call_map::CallResult;
+use crate::handle::Handle;
+use futures_channel::oneshot::{self, Receiver};
+use std::fmt;
+use std::sync::Arc;
+
+/// Middleware for the calls made through a proxy.
+///
+/// Layers implement cross-cutting concerns like logging, metrics or retry policies once for all
+/// functions of a service. They are added to a proxy with
+/// [`Proxy::add_layer`](super::Proxy::add_layer). Generated proxies provide `add_layer` and
+/// `with_layer` methods as well.
+///
+/// If a proxy has multiple layers, then [`on_call`](Self::on_call) is invoked in the order in
+/// which they were added, and [`on_reply`](Self::on_reply) in the reverse order.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin::core::message::CallFunctionResult;
+/// use aldrin::low_level::{OutgoingCall, ProxyLayer, ReplyAction};
+/// use aldrin::Error;
+///
+/// /// Retries aborted calls up to 3 times.
+/// struct RetryAborted;
+///
+/// impl ProxyLayer for RetryAborted {
+///     fn on_reply(
+///         &self,
+///         call: &OutgoingCall,
+///         result: &mut Result<CallFunctionResult, Error>,
+///     ) -> ReplyAction {
+///         if matches!(result, Ok(CallFunctionResult::Aborted)) && (call.attempt() < 3) {
+///             ReplyAction::Retry
+///         } else {
+///             ReplyAction::Done
+///         }
+///     }
+/// }
+/// ```
+pub trait ProxyLayer: Send + Sync + 'static {
+    /// Intercepts an outgoing call.
+    ///
+    /// This is invoked before the call is sent. The call's arguments and trace context may be
+    /// modified.
+    ///
+    /// Returning an error fails the call without sending it. Subsequent layers are skipped as
+    /// well. The error passes through [`on_reply`](Self::on_reply) of this and all previous
+    /// layers.
+    ///
+    /// The default implementation returns `Ok(())`.
+    fn on_call(&self, call: &mut OutgoingCall) -> Result<(), Error> {
+        let _ = call;
+        Ok(())
+    }
+
+    /// Intercepts the reply to a call.
+    ///
+    /// This is invoked with the call as it was sent and can modify the `result` before it is
+    /// returned to the caller.
+    ///
+    /// Returning [`ReplyAction::Retry`] discards the `result` and makes the call again. All layers
+    /// then see the call again, with an incremented [`attempt`](OutgoingCall::attempt). Layers,
+    /// which were added before this one, don't see the discarded `result`. Retries happen
+    /// immediately; there is no delay between attempts.
+    ///
+    /// The default implementation returns [`ReplyAction::Done`].
+    fn on_reply(
+        &self,
+        call: &OutgoingCall,
+        result: &mut Result<CallFunctionResult, Error>,
+    ) -> ReplyAction {
+        let _ = (call, result);
+        ReplyAction::Done
+    }
+}
+
+impl<L: ProxyLayer + ?Sized> ProxyLayer for Arc<L> {
+    fn on_call(&self, call: &mut OutgoingCall) -> Result<(), Error> {
+        (**self).on_call(call)
+    }
+
+    fn on_reply(
+        &self,
+        call: &OutgoingCall,
+        result: &mut Result<CallFunctionResult, Error>,
+    ) -> ReplyAction {
+        (**self).on_reply(call, result)
+    }
+}
+
+/// Decision of a [`ProxyLayer`] about the reply to a call.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReplyAction {
+    /// Continue with the next layer or return the result to the caller.
+    Done,
+
+    /// Discard the result and make the call again.
+    Retry,
+}
+
+/// Outgoing call as seen by a [`ProxyLayer`].
+#[derive(Debug, Clone)]
+pub struct OutgoingCall {
+    function: u32,
+    args: SerializedValue,
+    trace_context: Option<TraceContext>,
+    options: CallOptions,
+    attempt: u32,
+}
+
+impl OutgoingCall {
+    pub(crate) fn new(
+        function: u32,
+        args: SerializedValue,
+        trace_context: Option<TraceContext>,
+        options: CallOptions,
+    ) -> Self {
+        Self {
+            function,
+            args,
+            trace_context,
+            options,
+            attempt: 0,
+        }
+    }
+
+    /// Returns the id of the called function.
+    pub fn function(&self) -> u32 {
+        self.function
+    }
+
+    /// Returns the call's arguments.
+    pub fn args(&self) -> &SerializedValue {
+        &self.args
+    }
+
+    /// Sets the call's arguments.
+    pub fn set_args(&mut self, args: SerializedValue) {
+        self.args = args;
+    }
+
+    /// Returns the call's trace context.
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        self.trace_context
+    }
+
+    /// Sets the call's trace context.
+    pub fn set_trace_context(&mut self, trace_context: Option<TraceContext>) {
+        self.trace_context = trace_context;
+    }
+
+    /// Returns the options, with which the call was made.
+    pub fn options(&self) -> CallOptions {
+        self.options
+    }
+
+    /// Returns the number of previous attempts of this call.
+    ///
+    /// This is 0 for the first attempt and is incremented every time the call is retried.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct ProxyLayers(Arc<Vec<Arc<dyn ProxyLayer>>>);
+
+impl ProxyLayers {
+    pub fn push(&mut self, layer: Arc<dyn ProxyLayer>) {
+        Arc::make_mut(&mut self.0).push(layer);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for ProxyLayers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProxyLayers")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+/// State of a call, that passes through a proxy's layers.
+#[derive(Debug)]
+pub(crate) struct LayeredCall {
+    client: Handle,
+    svc: ServiceId,
+    layers: ProxyLayers,
+    original: OutgoingCall,
+    call: OutgoingCall,
+    seen: usize,
+}
+
+impl LayeredCall {
+    pub fn start(client: Handle, svc: ServiceId, layers: ProxyLayers, call: OutgoingCall) -> Reply {
+        let function = call.function;
+
+        let mut this = Box::new(Self {
+            client,
+            svc,
+            layers,
+            original: call.clone(),
+            call,
+            seen: 0,
+        });
+
+        let recv = this.send();
+        let svc = this.svc;
+        Reply::new_layered(recv, svc, function, this)
+    }
+
+    /// Passes the result of a call through the layers.
+    ///
+    /// If a layer decides to retry the call, then the receiver for the new attempt is returned.
+    pub fn on_reply(
+        &mut self,
+        result: &mut Result<CallFunctionResult, Error>,
+    ) -> Option<Receiver<CallResult>> {
+        let layers = self.layers.clone();
+
+        for layer in layers.0[..self.seen].iter().rev() {
+            if layer.on_reply(&self.call, result) == ReplyAction::Retry {
+                self.original.attempt += 1;
+                return Some(self.send());
+            }
+        }
+
+        None
+    }
+
+    fn send(&mut self) -> Receiver<CallResult> {
+        self.call = self.original.clone();
+
+        for (i, layer) in self.layers.0.iter().enumerate() {
+            if let Err(e) = layer.on_call(&mut self.call) {
+                self.seen = i + 1;
+
+                let (send, recv) = oneshot::channel();
+                let _ = send.send(Err(e));
+                return recv;
+            }
+        }
+
+        self.seen = self.layers.0.len();
+
+        self.client.call_serialized(
+            self.svc,
+            self.call.function,
+            self.call.args.clone(),
+            self.call.trace_context,
+            self.call.options.priority(),
+        )
+    }
+}
@@ -0,0 +1,61 @@
+use crate::core::message::Message;
+use futures_channel::mpsc::UnboundedReceiver;
+use futures_core::stream::{FusedStream, Stream};
+use std::future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Smallest serial reserved for raw messages.
+///
+/// The client allocates serials for its own requests only below this value. Requests sent with
+/// [`Handle::send_raw_message`](crate::Handle::send_raw_message) should use serials from
+/// `RAW_SERIAL_MIN` upwards, such that their replies never collide with those of the client's own
+/// requests.
+pub const RAW_SERIAL_MIN: u32 = 0x8000_0000;
+
+/// Stream of all messages received by a client.
+///
+/// `RawMessages` are created with [`Handle::raw_messages`](crate::Handle::raw_messages). They
+/// receive a copy of every message, that the client receives from the broker, including those that
+/// the client handles itself. This is intended for building protocol tools (e.g. fuzzers or
+/// conformance checkers) on top of a [`Client`](crate::Client).
+///
+/// The stream ends when the client shuts down.
+#[derive(Debug)]
+pub struct RawMessages(UnboundedReceiver<Message>);
+
+impl RawMessages {
+    pub(crate) fn new(recv: UnboundedReceiver<Message>) -> Self {
+        Self(recv)
+    }
+
+    /// Polls for the next message.
+    pub fn poll_next_message(&mut self, cx: &mut Context) -> Poll<Option<Message>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
+
+    /// Returns the next message.
+    ///
+    /// `None` is returned when the client has shut down.
+    pub async fn next_message(&mut self) -> Option<Message> {
+        future::poll_fn(|cx| self.poll_next_message(cx)).await
+    }
+}
+
+impl Stream for RawMessages {
+    type Item = Message;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Message>> {
+        self.poll_next_message(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl FusedStream for RawMessages {
+    fn is_terminated(&self) -> bool {
+        self.0.is_terminated()
+    }
+}
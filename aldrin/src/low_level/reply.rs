@@ -1,23 +1,95 @@
+use super::LayeredCall;
+#[cfg(feature = "statistics")]
+use super::ProxyCounters;
+use crate::client::Sleep;
 use crate::core::message::CallFunctionResult;
-use crate::core::SerializedValue;
+use crate::core::{SerializedValue, ServiceId};
 use crate::error::Error;
+use crate::function_call_map::CallResult;
 use crate::reply::Reply as HlReply;
-use futures_channel::oneshot::Receiver;
+use futures_channel::oneshot::{self, Receiver};
 use std::future::Future;
 use std::pin::Pin;
+#[cfg(feature = "statistics")]
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 /// Future to await the result of a call.
 #[derive(Debug)]
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct Reply {
-    recv: Receiver<Result<CallFunctionResult, Error>>,
+    recv: Receiver<CallResult>,
+    service: ServiceId,
     function: u32,
+    serial: Option<u32>,
+    layered: Option<Box<LayeredCall>>,
+    timeout: Option<Sleep>,
+    #[cfg(feature = "statistics")]
+    statistics: Option<Arc<ProxyCounters>>,
 }
 
 impl Reply {
-    pub(crate) fn new(recv: Receiver<Result<CallFunctionResult, Error>>, function: u32) -> Self {
-        Self { recv, function }
+    pub(crate) fn new(recv: Receiver<CallResult>, service: ServiceId, function: u32) -> Self {
+        Self {
+            recv,
+            service,
+            function,
+            serial: None,
+            layered: None,
+            timeout: None,
+            #[cfg(feature = "statistics")]
+            statistics: None,
+        }
+    }
+
+    pub(crate) fn error(error: Error, service: ServiceId, function: u32) -> Self {
+        let (send, recv) = oneshot::channel();
+        let _ = send.send(Err(error));
+        Self::new(recv, service, function)
+    }
+
+    pub(crate) fn new_layered(
+        recv: Receiver<CallResult>,
+        service: ServiceId,
+        function: u32,
+        layered: Box<LayeredCall>,
+    ) -> Self {
+        Self {
+            recv,
+            service,
+            function,
+            serial: None,
+            layered: Some(layered),
+            timeout: None,
+            #[cfg(feature = "statistics")]
+            statistics: None,
+        }
+    }
+
+    /// Fails the call with [`Error::Timeout`] when `timeout` completes.
+    ///
+    /// If `timeout` is `None`, then the client has no timer and the call fails immediately.
+    pub(crate) fn with_timeout(mut self, timeout: Option<Sleep>) -> Self {
+        match timeout {
+            Some(timeout) => {
+                self.timeout = Some(timeout);
+                self
+            }
+
+            None => Self::error(Error::NotSupported, self.service, self.function),
+        }
+    }
+
+    /// Records the result of the call in `statistics`.
+    #[cfg(feature = "statistics")]
+    pub(crate) fn with_statistics(mut self, statistics: Arc<ProxyCounters>) -> Self {
+        self.statistics = Some(statistics);
+        self
+    }
+
+    /// Wraps an error in an [`Error::Call`] with the context of this call.
+    pub(crate) fn call_error(&self, error: Error) -> Error {
+        Error::call(self.service, self.function, self.serial, error)
     }
 
     /// Cast the reply to a typed [`Reply<T, E>`](HlReply).
@@ -29,35 +101,79 @@ impl Reply {
     ///
     /// This function is equivalent to dropping the `Reply`.
     pub fn abort(self) {}
+
+    fn finish(
+        &self,
+        result: Result<Result<SerializedValue, SerializedValue>, Error>,
+    ) -> Poll<Result<Result<SerializedValue, SerializedValue>, Error>> {
+        #[cfg(feature = "statistics")]
+        if let Some(ref statistics) = self.statistics {
+            statistics.reply(&result);
+        }
+
+        Poll::Ready(result)
+    }
 }
 
 impl Future for Reply {
     type Output = Result<Result<SerializedValue, SerializedValue>, Error>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
-        match Pin::new(&mut self.recv).poll(cx) {
-            Poll::Ready(Ok(Ok(CallFunctionResult::Ok(t)))) => Poll::Ready(Ok(Ok(t))),
-            Poll::Ready(Ok(Ok(CallFunctionResult::Err(e)))) => Poll::Ready(Ok(Err(e))),
+        let this = &mut *self;
 
-            Poll::Ready(Ok(Ok(CallFunctionResult::Aborted))) => {
-                Poll::Ready(Err(Error::CallAborted))
-            }
+        let result = loop {
+            let mut result = match Pin::new(&mut this.recv).poll(cx) {
+                Poll::Ready(Ok(Ok((serial, result)))) => {
+                    this.serial = Some(serial);
+                    Ok(result)
+                }
 
-            Poll::Ready(Ok(Ok(CallFunctionResult::InvalidService))) => {
-                Poll::Ready(Err(Error::InvalidService))
+                Poll::Ready(Ok(Err(e))) => Err(e),
+                Poll::Ready(Err(_)) => Err(Error::Shutdown),
+
+                Poll::Pending => {
+                    if let Some(ref mut timeout) = this.timeout {
+                        if Pin::new(timeout).poll(cx).is_ready() {
+                            this.timeout = None;
+                            this.serial = None;
+                            this.recv.close();
+                            return this.finish(Err(this.call_error(Error::Timeout)));
+                        }
+                    }
+
+                    return Poll::Pending;
+                }
+            };
+
+            if let Some(ref mut layered) = this.layered {
+                if let Some(recv) = layered.on_reply(&mut result) {
+                    this.recv = recv;
+                    this.serial = None;
+                    continue;
+                }
             }
 
-            Poll::Ready(Ok(Ok(CallFunctionResult::InvalidFunction))) => {
-                Poll::Ready(Err(Error::invalid_function(self.function)))
+            break result;
+        };
+
+        let result = match result {
+            Ok(CallFunctionResult::Ok(t)) => Ok(Ok(t)),
+            Ok(CallFunctionResult::Err(e)) => Ok(Err(e)),
+            Ok(CallFunctionResult::Aborted) => Err(this.call_error(Error::CallAborted)),
+            Ok(CallFunctionResult::InvalidService) => Err(this.call_error(Error::InvalidService)),
+            Ok(CallFunctionResult::Overloaded) => Err(this.call_error(Error::Overloaded)),
+
+            Ok(CallFunctionResult::InvalidFunction) => {
+                Err(this.call_error(Error::invalid_function(this.function)))
             }
 
-            Poll::Ready(Ok(Ok(CallFunctionResult::InvalidArgs))) => {
-                Poll::Ready(Err(Error::invalid_arguments(self.function, None)))
+            Ok(CallFunctionResult::InvalidArgs) => {
+                Err(this.call_error(Error::invalid_arguments(this.function, None)))
             }
 
-            Poll::Ready(Ok(Err(e))) => Poll::Ready(Err(e)),
-            Poll::Ready(Err(_)) => Poll::Ready(Err(Error::Shutdown)),
-            Poll::Pending => Poll::Pending,
-        }
+            Err(e) => Err(e),
+        };
+
+        this.finish(result)
     }
 }
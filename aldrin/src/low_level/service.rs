@@ -1,17 +1,27 @@
-use super::Call;
+use super::current_trace_context;
+use super::{Call, EventSubscriptions, Interceptor, Interceptors};
+#[cfg(feature = "statistics")]
+use super::{ServiceCounters, ServiceStatistics};
 #[cfg(feature = "introspection")]
-use crate::core::introspection::Introspection;
-use crate::core::{Serialize, SerializedValue, ServiceId, ServiceUuid, TypeId};
+use crate::core::introspection::{Introspection, Validator};
+use crate::core::message::CallFunctionResult;
+use crate::core::{Serialize, SerializedValue, ServiceId, ServiceUuid, TraceContext, TypeId};
 use crate::error::Error;
+use crate::handle::request::EmitKind;
 use crate::handle::Handle;
 use crate::low_level::ServiceInfo;
 use crate::object::Object;
-use futures_channel::mpsc::UnboundedReceiver;
+use futures_channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use futures_channel::oneshot::Receiver;
 use futures_core::stream::{FusedStream, Stream};
-use std::future;
+use std::convert::Infallible;
+use std::future::{self, Future};
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 /// Owned service.
 #[derive(Debug)]
@@ -20,6 +30,19 @@ pub struct Service {
     info: ServiceInfo,
     client: Handle,
     calls: UnboundedReceiver<RawCall>,
+    interceptors: Interceptors,
+    #[cfg(feature = "introspection")]
+    validator: Option<Arc<Validator>>,
+    #[cfg(feature = "statistics")]
+    statistics: Arc<ServiceCounters>,
+
+    /// Handed to every call and closed once the last pending call has been answered.
+    ///
+    /// The sender is `None` while the service is shutting down.
+    in_flight: (
+        Option<UnboundedSender<Infallible>>,
+        UnboundedReceiver<Infallible>,
+    ),
 }
 
 impl Service {
@@ -34,11 +57,19 @@ impl Service {
         client: Handle,
         calls: UnboundedReceiver<RawCall>,
     ) -> Self {
+        let (in_flight, drained) = mpsc::unbounded();
+
         Self {
             id,
             info,
             client,
             calls,
+            interceptors: Interceptors::default(),
+            #[cfg(feature = "introspection")]
+            validator: None,
+            #[cfg(feature = "statistics")]
+            statistics: Arc::default(),
+            in_flight: (Some(in_flight), drained),
         }
     }
 
@@ -57,6 +88,16 @@ impl Service {
         self.info.type_id()
     }
 
+    /// Returns the service's statistics.
+    ///
+    /// See [`ServiceStatistics`] for more information.
+    ///
+    /// This function requires the `statistics` feature.
+    #[cfg(feature = "statistics")]
+    pub fn statistics(&self) -> ServiceStatistics {
+        self.statistics.get()
+    }
+
     /// Queries the introspection for the service.
     #[cfg(feature = "introspection")]
     pub async fn query_introspection(&self) -> Result<Option<Introspection>, Error> {
@@ -78,19 +119,119 @@ impl Service {
         self.client.destroy_service(self.id).await
     }
 
+    /// Shuts down the service gracefully.
+    ///
+    /// Unlike [`destroy`](Self::destroy), this lets pending calls finish first. The service stops
+    /// accepting calls immediately and replies to all new ones as if it had already been destroyed,
+    /// i.e. callers receive [`Error::InvalidService`]. Calls, that have already been returned from
+    /// [`poll_next_call`](Self::poll_next_call), can still be answered with their promises. The
+    /// service is destroyed once all of them have been answered or dropped.
+    ///
+    /// If `timeout` is given, the service is destroyed after at most that duration, regardless of
+    /// any pending calls. Timeouts require a timer to be configured with
+    /// [`ClientBuilder::with_timer`](crate::ClientBuilder::with_timer). Otherwise,
+    /// [`Error::NotSupported`] is returned and the service is left untouched.
+    ///
+    /// The service stays shut down even if the returned future is dropped before it completes.
+    pub async fn shutdown(&mut self, timeout: Option<Duration>) -> Result<(), Error> {
+        let mut timeout = match timeout {
+            Some(timeout) => Some(self.client.sleep(timeout).ok_or(Error::NotSupported)?),
+            None => None,
+        };
+
+        self.in_flight.0 = None;
+
+        future::poll_fn(|cx| {
+            // New calls are answered by poll_next_call while shutting down.
+            if let Poll::Ready(None) = self.poll_next_call(cx) {
+                return Poll::Ready(());
+            }
+
+            if let Poll::Ready(None) = Pin::new(&mut self.in_flight.1).poll_next(cx) {
+                return Poll::Ready(());
+            }
+
+            match timeout {
+                Some(ref mut timeout) => Pin::new(timeout).poll(cx),
+                None => Poll::Pending,
+            }
+        })
+        .await;
+
+        self.destroy().await
+    }
+
+    /// Adds an interceptor to the service.
+    ///
+    /// Interceptors observe and can modify or deny all calls, before they are returned from
+    /// [`poll_next_call`](Self::poll_next_call), as well as all replies. See [`Interceptor`] for
+    /// more information.
+    pub fn add_interceptor(&mut self, interceptor: impl Interceptor) {
+        self.interceptors.push(Arc::new(interceptor));
+    }
+
+    /// Enables validation of the arguments of incoming calls.
+    ///
+    /// The `validator` must have been created from the service's introspection, e.g. with
+    /// [`Validator::new`]. Calls are then validated before their arguments are deserialized with
+    /// [`Call::deserialize_and_cast`], which replies with [`Promise::invalid_args`] and returns an
+    /// [`InvalidArguments`](crate::error::InvalidArguments) error, that points to the offending
+    /// field. Calls can also be validated explicitly with [`Call::validate`].
+    ///
+    /// [`Promise::invalid_args`]: super::Promise::invalid_args
+    #[cfg(feature = "introspection")]
+    pub fn enable_args_validation(&mut self, validator: Validator) {
+        self.validator = Some(Arc::new(validator));
+    }
+
     /// Polls for the next call.
     pub fn poll_next_call(&mut self, cx: &mut Context) -> Poll<Option<Call>> {
-        match Pin::new(&mut self.calls).poll_next(cx) {
-            Poll::Ready(Some(call)) => Poll::Ready(Some(Call::new(
+        loop {
+            let call = match Pin::new(&mut self.calls).poll_next(cx) {
+                Poll::Ready(Some(call)) => call,
+                Poll::Ready(None) => break Poll::Ready(None),
+                Poll::Pending => break Poll::Pending,
+            };
+
+            let mut call = Call::new(
                 self.client.clone(),
                 call.aborted,
                 call.serial,
                 call.function,
                 call.args,
-            ))),
+                call.trace_context,
+            );
+
+            #[cfg(feature = "statistics")]
+            {
+                self.statistics.call(call.args());
+                call.set_statistics(self.statistics.clone());
+            }
+
+            let Some(ref in_flight) = self.in_flight.0 else {
+                let _ = call
+                    .into_promise()
+                    .reply(CallFunctionResult::InvalidService);
+                continue;
+            };
+
+            match self.interceptors.on_call(&mut call) {
+                None => {
+                    call.set_interceptors(self.interceptors.clone());
+                    call.set_in_flight(in_flight.clone());
+
+                    #[cfg(feature = "introspection")]
+                    if let Some(ref validator) = self.validator {
+                        call.set_validator(validator.clone());
+                    }
+
+                    break Poll::Ready(Some(call));
+                }
 
-            Poll::Ready(None) => Poll::Ready(None),
-            Poll::Pending => Poll::Pending,
+                Some(result) => {
+                    let _ = call.into_promise().reply(result);
+                }
+            }
         }
     }
 
@@ -99,12 +240,160 @@ impl Service {
         future::poll_fn(|cx| self.poll_next_call(cx)).await
     }
 
+    /// Handles calls concurrently until the service is destroyed.
+    ///
+    /// `f` is called for every call. The futures it returns are polled concurrently by the future
+    /// returned from this function, without spawning any tasks. At most `limit` calls are handled at
+    /// the same time and further calls stay queued until a handler completes. A limit of `None` or
+    /// `Some(0)` means that the number of concurrent calls is unbounded.
+    ///
+    /// Panics of handlers are caught. The call is then aborted, as if the promise had been dropped,
+    /// and all other calls continue unaffected.
+    ///
+    /// The returned future completes after the service has been destroyed and all remaining handlers
+    /// have completed.
+    pub async fn for_each_concurrent<F, Fut>(&mut self, limit: impl Into<Option<usize>>, mut f: F)
+    where
+        F: FnMut(Call) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let limit = limit.into().and_then(NonZeroUsize::new);
+        let mut handlers = Vec::new();
+        let mut finished = false;
+
+        future::poll_fn(|cx| loop {
+            while !finished && limit.map_or(true, |limit| handlers.len() < limit.get()) {
+                match self.poll_next_call(cx) {
+                    Poll::Ready(Some(call)) => handlers.push(Box::pin(f(call))),
+                    Poll::Ready(None) => finished = true,
+                    Poll::Pending => break,
+                }
+            }
+
+            let num_handlers = handlers.len();
+
+            handlers.retain_mut(|handler| {
+                panic::catch_unwind(AssertUnwindSafe(|| handler.as_mut().poll(cx)))
+                    .is_ok_and(|res| res.is_pending())
+            });
+
+            if finished && handlers.is_empty() {
+                break Poll::Ready(());
+            }
+
+            // Completed handlers may have made room for more calls.
+            if finished || (handlers.len() == num_handlers) {
+                break Poll::Pending;
+            }
+        })
+        .await
+    }
+
     /// Emits an event.
+    ///
+    /// With the `tracing` feature, the trace context of the current span is attached to the event
+    /// (see the `trace` module).
     pub fn emit<T>(&self, event: u32, args: &T) -> Result<(), Error>
     where
         T: Serialize + ?Sized,
     {
-        self.client.emit_event(self.id, event, args)
+        self.emit_impl(event, args, EmitKind::Normal, current_trace_context())
+    }
+
+    /// Emits an event with an explicit trace context.
+    ///
+    /// Trace contexts require protocol version 1.19. On older versions, `trace_context` is
+    /// ignored.
+    pub fn emit_with_trace_context<T>(
+        &self,
+        event: u32,
+        args: &T,
+        trace_context: Option<TraceContext>,
+    ) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.emit_impl(event, args, EmitKind::Normal, trace_context)
+    }
+
+    /// Emits a retained event.
+    ///
+    /// The broker stores the last value of retained events and delivers it immediately to new
+    /// subscribers. It is dropped when the service is destroyed.
+    ///
+    /// Retained events require protocol version 1.19. On older versions, this function behaves
+    /// like [`emit`](Self::emit).
+    pub fn emit_retained<T>(&self, event: u32, args: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.emit_impl(event, args, EmitKind::Retained, current_trace_context())
+    }
+
+    /// Emits a reliable event.
+    ///
+    /// Reliable events are always sent to the broker, which buffers them for each subscriber until
+    /// the subscriber acknowledges them (see [`Proxy::ack_event`](super::Proxy::ack_event)).
+    /// Subscribers, that reconnect with the same session, receive all unacknowledged events again
+    /// (see [`ClientBuilder::with_session`](crate::ClientBuilder::with_session)). Events are thus
+    /// delivered at least once, as long as the broker's buffer limit isn't exceeded.
+    ///
+    /// Reliable events require protocol version 1.19. On older versions, this function behaves
+    /// like [`emit`](Self::emit).
+    pub fn emit_reliable<T>(&self, event: u32, args: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.emit_impl(event, args, EmitKind::Reliable, current_trace_context())
+    }
+
+    /// Emits an event and waits for the broker to acknowledge it.
+    ///
+    /// Unlike [`emit`](Self::emit), the event is always sent to the broker, which replies with the
+    /// number of clients the event was delivered to. Several subscribers on the same client count
+    /// only once. Clients, that are dropping events because they are too slow, are not counted.
+    ///
+    /// Acknowledged events require protocol version 1.19. On older versions,
+    /// [`Error::NotSupported`] is returned.
+    pub async fn emit_acked<T>(&self, event: u32, args: &T) -> Result<usize, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = SerializedValue::serialize(args)?;
+
+        #[cfg(feature = "statistics")]
+        self.statistics.event(&value);
+
+        self.client
+            .emit_event_acked(self.id, event, value, current_trace_context())
+            .await
+    }
+
+    fn emit_impl<T>(
+        &self,
+        event: u32,
+        args: &T,
+        kind: EmitKind,
+        trace_context: Option<TraceContext>,
+    ) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let value = SerializedValue::serialize(args)?;
+
+        #[cfg(feature = "statistics")]
+        self.statistics.event(&value);
+
+        self.client
+            .emit_event(self.id, event, value, kind, trace_context)
+    }
+
+    /// Creates an [`EventSubscriptions`], which tracks which events have subscribers.
+    ///
+    /// Events emitted without any subscribers are dropped by the client. Servers can use this to
+    /// avoid producing events, that no one is interested in.
+    pub async fn event_subscriptions(&self) -> Result<EventSubscriptions, Error> {
+        self.client.event_subscriptions(self.id).await
     }
 }
 
@@ -133,5 +422,6 @@ pub(crate) struct RawCall {
     pub serial: u32,
     pub function: u32,
     pub args: SerializedValue,
+    pub trace_context: Option<TraceContext>,
     pub aborted: Receiver<()>,
 }
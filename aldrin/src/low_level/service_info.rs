@@ -1,10 +1,12 @@
-use aldrin_core::{ServiceInfo as CoreServiceInfo, TypeId};
+use aldrin_core::{LexicalId, ServiceInfo as CoreServiceInfo, TypeId};
 
 /// Contains extra information about a service.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ServiceInfo {
     version: u32,
     type_id: Option<TypeId>,
+    max_calls: Option<u32>,
+    lexical_id: Option<LexicalId>,
 }
 
 impl ServiceInfo {
@@ -13,6 +15,8 @@ impl ServiceInfo {
         Self {
             version,
             type_id: None,
+            max_calls: None,
+            lexical_id: None,
         }
     }
 
@@ -23,6 +27,14 @@ impl ServiceInfo {
             info = info.set_type_id(type_id);
         }
 
+        if let Some(max_calls) = self.max_calls {
+            info = info.set_max_calls(max_calls);
+        }
+
+        if let Some(lexical_id) = self.lexical_id {
+            info = info.set_lexical_id(lexical_id);
+        }
+
         info
     }
 
@@ -49,4 +61,37 @@ impl ServiceInfo {
         self.type_id = Some(type_id);
         self
     }
+
+    /// Returns the maximum number of outstanding calls of the service.
+    pub fn max_calls(self) -> Option<u32> {
+        self.max_calls
+    }
+
+    /// Sets the maximum number of outstanding calls of the service.
+    ///
+    /// The broker rejects further calls with [`Error::Overloaded`](crate::Error::Overloaded)
+    /// (wrapped in [`Error::Call`](crate::Error::Call)) while the service has `max_calls` calls,
+    /// that it has not yet replied to. This requires protocol version 1.19 on the broker. Older
+    /// brokers ignore the limit.
+    #[must_use = "this method follows the builder pattern and returns a new `ServiceInfo`"]
+    pub fn set_max_calls(mut self, max_calls: u32) -> Self {
+        self.max_calls = Some(max_calls);
+        self
+    }
+
+    /// Returns the lexical id of the service.
+    pub fn lexical_id(self) -> Option<LexicalId> {
+        self.lexical_id
+    }
+
+    /// Sets the lexical id of the service.
+    ///
+    /// The lexical id identifies the layout of the service by its schema and name. It allows bus
+    /// listeners to discover the service with a [layout
+    /// filter](crate::core::BusListenerFilter::Layout).
+    #[must_use = "this method follows the builder pattern and returns a new `ServiceInfo`"]
+    pub fn set_lexical_id(mut self, lexical_id: LexicalId) -> Self {
+        self.lexical_id = Some(lexical_id);
+        self
+    }
 }
@@ -0,0 +1,193 @@
+use crate::core::message::CallFunctionResult;
+use crate::core::{SerializedValue, SerializedValueSlice};
+use crate::error::Error;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Statistics of a [`Proxy`](super::Proxy).
+///
+/// The statistics are shared by all clones of a proxy and cover its whole lifetime. They are
+/// acquired with [`Proxy::statistics`](super::Proxy::statistics).
+///
+/// This type requires the `statistics` feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ProxyStatistics {
+    calls: usize,
+    calls_failed: usize,
+    events_received: usize,
+    bytes_sent: u64,
+    bytes_received: u64,
+}
+
+impl ProxyStatistics {
+    /// Number of function calls made.
+    pub fn calls(&self) -> usize {
+        self.calls
+    }
+
+    /// Number of function calls, that failed.
+    ///
+    /// Calls fail if they resolve to an [`Error`], e.g. because the call was aborted, the
+    /// arguments were invalid or the call timed out. Calls, that the service replied to with an
+    /// error value (`Ok(Err(_))`), are not counted here.
+    pub fn calls_failed(&self) -> usize {
+        self.calls_failed
+    }
+
+    /// Number of events received.
+    ///
+    /// Events are counted when they are returned from [`Proxy::next_event`] and friends. Each
+    /// clone of a proxy receives all events and thus counts them separately.
+    ///
+    /// [`Proxy::next_event`]: super::Proxy::next_event
+    pub fn events_received(&self) -> usize {
+        self.events_received
+    }
+
+    /// Number of bytes sent as arguments of function calls.
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    /// Number of bytes received in replies to function calls and in events.
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received
+    }
+}
+
+/// Statistics of a [`Service`](super::Service).
+///
+/// The statistics cover the whole lifetime of the service. They are acquired with
+/// [`Service::statistics`](super::Service::statistics).
+///
+/// This type requires the `statistics` feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ServiceStatistics {
+    calls_received: usize,
+    calls_failed: usize,
+    events_emitted: usize,
+    bytes_sent: u64,
+    bytes_received: u64,
+}
+
+impl ServiceStatistics {
+    /// Number of function calls received.
+    ///
+    /// This includes calls, that were rejected by an [`Interceptor`](super::Interceptor).
+    pub fn calls_received(&self) -> usize {
+        self.calls_received
+    }
+
+    /// Number of function calls, that failed.
+    ///
+    /// Calls fail if they are replied to with anything other than a value or an error value, e.g.
+    /// if they are aborted or if the function or the arguments are invalid. This includes calls,
+    /// whose [`Promise`](super::Promise) was dropped.
+    pub fn calls_failed(&self) -> usize {
+        self.calls_failed
+    }
+
+    /// Number of events emitted.
+    pub fn events_emitted(&self) -> usize {
+        self.events_emitted
+    }
+
+    /// Number of bytes sent in replies to function calls and in events.
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    /// Number of bytes received as arguments of function calls.
+    pub fn bytes_received(&self) -> u64 {
+        self.bytes_received
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ProxyCounters {
+    calls: AtomicUsize,
+    calls_failed: AtomicUsize,
+    events_received: AtomicUsize,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+}
+
+impl ProxyCounters {
+    pub fn call(&self, args: Option<&SerializedValueSlice>) {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(args) = args {
+            add_bytes(&self.bytes_sent, args);
+        }
+    }
+
+    pub fn reply(&self, result: &Result<Result<SerializedValue, SerializedValue>, Error>) {
+        match result {
+            Ok(Ok(value)) | Ok(Err(value)) => add_bytes(&self.bytes_received, value),
+            Err(_) => {
+                self.calls_failed.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn event(&self, args: &SerializedValueSlice) {
+        self.events_received.fetch_add(1, Ordering::Relaxed);
+        add_bytes(&self.bytes_received, args);
+    }
+
+    pub fn get(&self) -> ProxyStatistics {
+        ProxyStatistics {
+            calls: self.calls.load(Ordering::Relaxed),
+            calls_failed: self.calls_failed.load(Ordering::Relaxed),
+            events_received: self.events_received.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ServiceCounters {
+    calls_received: AtomicUsize,
+    calls_failed: AtomicUsize,
+    events_emitted: AtomicUsize,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+}
+
+impl ServiceCounters {
+    pub fn call(&self, args: &SerializedValueSlice) {
+        self.calls_received.fetch_add(1, Ordering::Relaxed);
+        add_bytes(&self.bytes_received, args);
+    }
+
+    pub fn reply(&self, result: &CallFunctionResult) {
+        match result {
+            CallFunctionResult::Ok(value) | CallFunctionResult::Err(value) => {
+                add_bytes(&self.bytes_sent, value)
+            }
+
+            _ => {
+                self.calls_failed.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn event(&self, args: &SerializedValueSlice) {
+        self.events_emitted.fetch_add(1, Ordering::Relaxed);
+        add_bytes(&self.bytes_sent, args);
+    }
+
+    pub fn get(&self) -> ServiceStatistics {
+        ServiceStatistics {
+            calls_received: self.calls_received.load(Ordering::Relaxed),
+            calls_failed: self.calls_failed.load(Ordering::Relaxed),
+            events_emitted: self.events_emitted.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn add_bytes(counter: &AtomicU64, value: &SerializedValueSlice) {
+    counter.fetch_add(value.len() as u64, Ordering::Relaxed);
+}
@@ -1,10 +1,12 @@
-use crate::core::{ObjectUuid, ServiceUuid, TypeId};
+use crate::core::{ObjectUuid, ServiceUuid, TraceContext, TypeId};
 use aldrin_test::aldrin::low_level::ServiceInfo;
 use aldrin_test::aldrin::Error;
 use aldrin_test::tokio::TestBroker;
 use futures_core::stream::FusedStream;
 use std::mem;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Notify;
 use tokio::time;
 use uuid::uuid;
 
@@ -186,7 +188,43 @@ async fn call_abort_by_callee() {
     assert_eq!(call.deserialize(), Ok(()));
     call.into_promise().abort().unwrap();
 
-    assert_eq!(reply.await, Err(Error::CallAborted));
+    assert_eq!(
+        reply.await.map_err(Error::without_context),
+        Err(Error::CallAborted)
+    );
+}
+
+#[tokio::test]
+async fn call_error_context() {
+    use std::error::Error as _;
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let proxy = client.create_proxy(svc.id()).await.unwrap();
+    let reply = proxy.call(7, &());
+
+    let call = svc.next_call().await.unwrap();
+    call.into_promise().abort().unwrap();
+
+    let Err(Error::Call(err)) = reply.await else {
+        panic!("expected Error::Call");
+    };
+    assert_eq!(err.service(), svc.id());
+    assert_eq!(err.function(), 7);
+    assert!(err.serial().is_some());
+    assert_eq!(*err.error(), Error::CallAborted);
+    assert_eq!(
+        err.source().map(ToString::to_string),
+        Some(Error::CallAborted.to_string())
+    );
 }
 
 #[tokio::test]
@@ -213,6 +251,98 @@ async fn call_abort_by_caller() {
     assert!(promise.is_aborted());
 }
 
+#[tokio::test]
+async fn for_each_concurrent() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let proxy = client.create_proxy(svc.id()).await.unwrap();
+    let notify = Arc::new(Notify::new());
+
+    let join = tokio::spawn(async move {
+        svc.for_each_concurrent(None, |call| {
+            let notify = notify.clone();
+
+            async move {
+                match call.id() {
+                    // Completes only after the third call has been handled.
+                    0 => {
+                        notify.notified().await;
+                        call.into_promise().done().unwrap();
+                    }
+
+                    1 => panic!("handler panicked"),
+
+                    _ => {
+                        notify.notify_one();
+                        call.into_promise().done().unwrap();
+                    }
+                }
+            }
+        })
+        .await
+    });
+
+    let reply0 = proxy.call(0, &());
+
+    assert_eq!(
+        proxy.call(1, &()).await.map_err(Error::without_context),
+        Err(Error::CallAborted)
+    );
+
+    proxy.call(2, &()).await.unwrap().unwrap();
+    reply0.await.unwrap().unwrap();
+
+    client.shutdown();
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn shutdown_drains_pending_calls() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let proxy = client.create_proxy(svc.id()).await.unwrap();
+    let reply = proxy.call(0, &());
+    let call = svc.next_call().await.unwrap();
+
+    // The client has no timer.
+    assert_eq!(
+        svc.shutdown(Some(Duration::from_secs(1))).await,
+        Err(Error::NotSupported)
+    );
+
+    let join = tokio::spawn(async move { svc.shutdown(None).await });
+
+    assert_eq!(
+        proxy.call(1, &()).await.map_err(Error::without_context),
+        Err(Error::InvalidService)
+    );
+
+    call.into_promise().done().unwrap();
+    reply.await.unwrap().unwrap();
+    join.await.unwrap().unwrap();
+
+    assert_eq!(
+        proxy.call(2, &()).await.map_err(Error::without_context),
+        Err(Error::InvalidService)
+    );
+}
+
 #[tokio::test]
 async fn call_invalid_function() {
     let mut broker = TestBroker::new();
@@ -232,7 +362,10 @@ async fn call_invalid_function() {
     assert_eq!(call.deserialize(), Ok(()));
     call.into_promise().invalid_function().unwrap();
 
-    assert_eq!(reply.await, Err(Error::invalid_function(0)));
+    assert_eq!(
+        reply.await.map_err(Error::without_context),
+        Err(Error::invalid_function(0))
+    );
 }
 
 #[tokio::test]
@@ -254,7 +387,10 @@ async fn call_invalid_args() {
     assert_eq!(call.deserialize(), Ok(()));
     call.into_promise().invalid_args().unwrap();
 
-    assert_eq!(reply.await, Err(Error::invalid_arguments(0, None)));
+    assert_eq!(
+        reply.await.map_err(Error::without_context),
+        Err(Error::invalid_arguments(0, None))
+    );
 }
 
 #[tokio::test]
@@ -318,6 +454,86 @@ async fn unsubscribe_event() {
     assert_eq!(ev.deserialize(), Ok(()));
 }
 
+#[tokio::test]
+async fn subscriptions_are_reference_counted() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let mut proxy = client.create_proxy(svc.id()).await.unwrap();
+    proxy.subscribe(0).await.unwrap();
+    proxy.subscribe(0).await.unwrap();
+    proxy.subscribe(1).await.unwrap();
+
+    proxy.unsubscribe(0).await.unwrap();
+    client.sync_broker().await.unwrap();
+
+    svc.emit(0, &()).unwrap();
+    svc.emit(1, &()).unwrap();
+
+    let ev = proxy.next_event().await.unwrap();
+    assert_eq!(ev.id(), 0);
+
+    let ev = proxy.next_event().await.unwrap();
+    assert_eq!(ev.id(), 1);
+
+    proxy.unsubscribe(0).await.unwrap();
+    client.sync_broker().await.unwrap();
+
+    svc.emit(0, &()).unwrap();
+    svc.emit(1, &()).unwrap();
+
+    let ev = proxy.next_event().await.unwrap();
+    assert_eq!(ev.id(), 1);
+}
+
+#[tokio::test]
+async fn subscribe_all_is_reference_counted() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let mut proxy1 = client.create_proxy(svc.id()).await.unwrap();
+    let mut proxy2 = proxy1.clone();
+    proxy1.subscribe_all().await.unwrap();
+    proxy2.subscribe_all().await.unwrap();
+
+    proxy1.unsubscribe_all().await.unwrap();
+    client.sync_broker().await.unwrap();
+
+    svc.emit(0, &()).unwrap();
+
+    let ev = time::timeout(Duration::from_millis(100), proxy1.next_event())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ev.id(), 0);
+
+    let ev = proxy2.next_event().await.unwrap();
+    assert_eq!(ev.id(), 0);
+
+    proxy2.unsubscribe_all().await.unwrap();
+    client.sync_broker().await.unwrap();
+
+    svc.emit(1, &()).unwrap();
+    svc.destroy().await.unwrap();
+
+    assert!(proxy1.next_event().await.is_none());
+    assert!(proxy2.next_event().await.is_none());
+}
+
 #[tokio::test]
 async fn events_mutliple_proxies() {
     let mut broker = TestBroker::new();
@@ -491,6 +707,78 @@ async fn subscribe_multiple_services_same_event_id() {
     );
 }
 
+#[tokio::test]
+async fn retained_event() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    svc.emit_retained(0, &1).unwrap();
+    svc.emit_retained(0, &2).unwrap();
+    client.sync_broker().await.unwrap();
+
+    let mut proxy = client.create_proxy(svc.id()).await.unwrap();
+    proxy.subscribe(0).await.unwrap();
+
+    let event = proxy.next_event().await.unwrap();
+    assert_eq!(event.id(), 0);
+    assert_eq!(event.deserialize(), Ok(2));
+
+    svc.emit_retained(0, &3).unwrap();
+
+    let event = proxy.next_event().await.unwrap();
+    assert_eq!(event.id(), 0);
+    assert_eq!(event.deserialize(), Ok(3));
+
+    let mut proxy = client.create_proxy(svc.id()).await.unwrap();
+    proxy.subscribe_all().await.unwrap();
+
+    let event = proxy.next_event().await.unwrap();
+    assert_eq!(event.id(), 0);
+    assert_eq!(event.deserialize(), Ok(3));
+}
+
+#[tokio::test]
+async fn emit_acked() {
+    let mut broker = TestBroker::new();
+    let client1 = broker.add_client().await;
+    let client2 = broker.add_client().await;
+    let client3 = broker.add_client().await;
+
+    let obj = client1.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    assert_eq!(svc.emit_acked(0, &1).await, Ok(0));
+
+    // Subscribers on the same client count only once.
+    let mut proxy1 = client2.create_proxy(svc.id()).await.unwrap();
+    proxy1.subscribe(0).await.unwrap();
+    let mut proxy2 = client2.create_proxy(svc.id()).await.unwrap();
+    proxy2.subscribe_all().await.unwrap();
+
+    let mut proxy3 = client3.create_proxy(svc.id()).await.unwrap();
+    proxy3.subscribe(0).await.unwrap();
+
+    let proxy4 = client1.create_proxy(svc.id()).await.unwrap();
+    proxy4.subscribe(1).await.unwrap();
+
+    assert_eq!(svc.emit_acked(0, &2).await, Ok(2));
+
+    for proxy in [&mut proxy1, &mut proxy2, &mut proxy3] {
+        let event = proxy.next_event().await.unwrap();
+        assert_eq!(event.id(), 0);
+        assert_eq!(event.deserialize(), Ok(2));
+    }
+}
+
 #[tokio::test]
 async fn can_subscribe_all() {
     let mut broker = TestBroker::new();
@@ -547,6 +835,48 @@ async fn destroy_proxy() {
     assert_eq!(ev.deserialize(), Ok(()));
 }
 
+#[tokio::test]
+async fn clone_proxy() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let mut proxy1 = client.create_proxy(svc.id()).await.unwrap();
+    let mut proxy2 = proxy1.clone();
+    assert_eq!(proxy2.id(), proxy1.id());
+
+    // Subscriptions are shared by all clones.
+    proxy1.subscribe(0).await.unwrap();
+    svc.emit(0, &1).unwrap();
+
+    for proxy in [&mut proxy1, &mut proxy2] {
+        let ev = proxy.next_event().await.unwrap();
+        assert_eq!(ev.id(), 0);
+        assert_eq!(ev.deserialize(), Ok(1));
+    }
+
+    // Dropping a clone doesn't affect the others.
+    mem::drop(proxy1);
+    client.sync_broker().await.unwrap();
+    svc.emit(0, &2).unwrap();
+
+    let ev = time::timeout(Duration::from_millis(100), proxy2.next_event())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ev.id(), 0);
+    assert_eq!(ev.deserialize(), Ok(2));
+
+    svc.destroy().await.unwrap();
+    assert!(proxy2.next_event().await.is_none());
+    assert!(proxy2.clone().next_event().await.is_none());
+}
+
 #[tokio::test]
 async fn subscribe_all() {
     let mut broker = TestBroker::new();
@@ -757,3 +1087,450 @@ async fn unsubscribe_all_without_serial() {
 
     client.sync_broker().await.unwrap();
 }
+
+#[tokio::test]
+async fn explicit_trace_context() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let mut proxy = client.create_proxy(svc.id()).await.unwrap();
+    proxy.subscribe(0).await.unwrap();
+
+    let ctx = TraceContext::new(1, 2, true);
+
+    let reply = proxy.call_with_trace_context(0, &(), Some(ctx));
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.trace_context(), Some(ctx));
+    call.into_promise().ok(&()).unwrap();
+    reply.await.unwrap().unwrap();
+
+    let reply = proxy.call(0, &());
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.trace_context(), None);
+    call.into_promise().ok(&()).unwrap();
+    reply.await.unwrap().unwrap();
+
+    svc.emit_with_trace_context(0, &(), Some(ctx)).unwrap();
+    let event = proxy.next_event().await.unwrap();
+    assert_eq!(event.trace_context(), Some(ctx));
+}
+
+#[tokio::test]
+async fn interceptors() {
+    use aldrin_test::aldrin::core::message::CallFunctionResult;
+    use aldrin_test::aldrin::core::SerializedValue;
+    use aldrin_test::aldrin::low_level::{Call, Intercept, Interceptor};
+    use std::sync::{Arc, Mutex};
+
+    struct Log(Arc<Mutex<Vec<&'static str>>>, &'static str);
+
+    impl Interceptor for Log {
+        fn on_call(&self, _call: &mut Call) -> Intercept {
+            self.0.lock().unwrap().push(self.1);
+            Intercept::Continue
+        }
+
+        fn on_reply(&self, _function: u32, _result: &mut CallFunctionResult) {
+            self.0.lock().unwrap().push(self.1);
+        }
+    }
+
+    struct Double;
+
+    impl Interceptor for Double {
+        fn on_call(&self, call: &mut Call) -> Intercept {
+            match call.id() {
+                0 => {
+                    let arg = call.deserialize::<u32>().unwrap();
+                    call.set_args(SerializedValue::serialize(&(arg * 2)).unwrap());
+                    Intercept::Continue
+                }
+
+                _ => Intercept::Reply(CallFunctionResult::InvalidFunction),
+            }
+        }
+
+        fn on_reply(&self, _function: u32, result: &mut CallFunctionResult) {
+            if let CallFunctionResult::Ok(value) = result {
+                let value = value.deserialize::<u32>().unwrap();
+                *result = CallFunctionResult::ok_with_serialize_value(&(value + 1)).unwrap();
+            }
+        }
+    }
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    svc.add_interceptor(Log(log.clone(), "outer"));
+    svc.add_interceptor(Double);
+    svc.add_interceptor(Log(log.clone(), "inner"));
+
+    let proxy = client.create_proxy(svc.id()).await.unwrap();
+
+    let reply = proxy.call(0, &2u32);
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.deserialize(), Ok(4u32));
+    call.into_promise().ok(&4u32).unwrap();
+    let res = reply.await.unwrap().unwrap();
+    assert_eq!(res.deserialize(), Ok(5u32));
+    assert_eq!(*log.lock().unwrap(), ["outer", "inner", "inner", "outer"]);
+    log.lock().unwrap().clear();
+
+    // Rejected calls are skipped by the service.
+    let reply1 = proxy.call(1, &());
+    let reply2 = proxy.call(0, &3u32);
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.id(), 0);
+    assert_eq!(call.deserialize(), Ok(6u32));
+    assert_eq!(
+        reply1.await.unwrap_err().without_context(),
+        Error::invalid_function(1)
+    );
+    assert_eq!(*log.lock().unwrap(), ["outer", "outer", "outer", "inner"]);
+
+    call.into_promise().ok(&6u32).unwrap();
+    let res = reply2.await.unwrap().unwrap();
+    assert_eq!(res.deserialize(), Ok(7u32));
+}
+
+#[tokio::test]
+async fn proxy_layers() {
+    use aldrin_test::aldrin::core::message::CallFunctionResult;
+    use aldrin_test::aldrin::core::SerializedValue;
+    use aldrin_test::aldrin::low_level::{OutgoingCall, ProxyLayer, ReplyAction};
+    use std::sync::{Arc, Mutex};
+
+    struct Log(Arc<Mutex<Vec<&'static str>>>, &'static str);
+
+    impl ProxyLayer for Log {
+        fn on_call(&self, _call: &mut OutgoingCall) -> Result<(), Error> {
+            self.0.lock().unwrap().push(self.1);
+            Ok(())
+        }
+
+        fn on_reply(
+            &self,
+            _call: &OutgoingCall,
+            _result: &mut Result<CallFunctionResult, Error>,
+        ) -> ReplyAction {
+            self.0.lock().unwrap().push(self.1);
+            ReplyAction::Done
+        }
+    }
+
+    struct DoubleAndRetry;
+
+    impl ProxyLayer for DoubleAndRetry {
+        fn on_call(&self, call: &mut OutgoingCall) -> Result<(), Error> {
+            match call.function() {
+                0 => {
+                    let arg = call.args().deserialize::<u32>().unwrap();
+                    call.set_args(SerializedValue::serialize(&(arg * 2)).unwrap());
+                    Ok(())
+                }
+
+                function => Err(Error::invalid_function(function)),
+            }
+        }
+
+        fn on_reply(
+            &self,
+            call: &OutgoingCall,
+            result: &mut Result<CallFunctionResult, Error>,
+        ) -> ReplyAction {
+            match result {
+                Ok(CallFunctionResult::Aborted) if call.attempt() == 0 => ReplyAction::Retry,
+                _ => ReplyAction::Done,
+            }
+        }
+    }
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let proxy = client
+        .create_proxy(svc.id())
+        .await
+        .unwrap()
+        .with_layer(Log(log.clone(), "outer"))
+        .with_layer(DoubleAndRetry)
+        .with_layer(Log(log.clone(), "inner"));
+
+    let reply = proxy.call(0, &2u32);
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.deserialize(), Ok(4u32));
+    call.into_promise().ok(&5u32).unwrap();
+    let res = reply.await.unwrap().unwrap();
+    assert_eq!(res.deserialize(), Ok(5u32));
+    assert_eq!(*log.lock().unwrap(), ["outer", "inner", "inner", "outer"]);
+    log.lock().unwrap().clear();
+
+    // Aborted calls are retried once with the original arguments.
+    let reply = tokio::spawn(proxy.call(0, &3u32));
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.deserialize(), Ok(6u32));
+    call.into_promise().abort().unwrap();
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.deserialize(), Ok(6u32));
+    call.into_promise().abort().unwrap();
+    assert_eq!(
+        reply.await.unwrap().unwrap_err().without_context(),
+        Error::CallAborted
+    );
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["outer", "inner", "inner", "outer", "inner", "inner", "outer"]
+    );
+    log.lock().unwrap().clear();
+
+    // Rejected calls are not sent.
+    let reply = proxy.call(1, &());
+    assert_eq!(reply.await.unwrap_err(), Error::invalid_function(1));
+    assert_eq!(*log.lock().unwrap(), ["outer", "outer"]);
+}
+
+#[cfg(feature = "introspection")]
+#[tokio::test]
+async fn call_by_name() {
+    use crate::core::introspection::{Introspectable, Layout, LexicalId, References, Service};
+
+    struct Test;
+
+    impl Introspectable for Test {
+        fn layout() -> Layout {
+            let uuid = ServiceUuid(uuid!("2f5ac1f1-0d5b-4e4b-8d7c-4a0e3d3d7b1a"));
+
+            Layout::Service(
+                Service::builder("test", "Test", uuid, 0)
+                    .function(3, "foo", None, None, None)
+                    .finish(),
+            )
+        }
+
+        fn lexical_id() -> LexicalId {
+            LexicalId::service("test", "Test")
+        }
+
+        fn add_references(_references: &mut References) {}
+    }
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    client.register_introspection::<Test>().unwrap();
+    client.submit_introspection().unwrap();
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0).set_type_id(TypeId::compute::<Test>());
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let proxy = client.create_proxy(svc.id()).await.unwrap();
+    assert_eq!(proxy.function_id("foo").await, Ok(Some(3)));
+    assert_eq!(proxy.function_id("bar").await, Ok(None));
+
+    let reply = proxy.call_by_name("foo", &1u32).await.unwrap();
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.id(), 3);
+    assert_eq!(call.deserialize(), Ok(1u32));
+    call.into_promise().ok(&2u32).unwrap();
+    let res = reply.await.unwrap().unwrap();
+    assert_eq!(res.deserialize(), Ok(2u32));
+
+    let res = proxy.call_by_name("bar", &()).await;
+    assert_eq!(res.err(), Some(Error::InvalidFunctionName));
+}
+
+#[cfg(feature = "introspection")]
+#[tokio::test]
+async fn call_args_validation() {
+    use crate::core::introspection::{
+        Introspectable, Layout, LexicalId, References, Service, ValidationErrorKind, Validator,
+    };
+    use crate::core::ValueKind;
+
+    struct Test;
+
+    impl Introspectable for Test {
+        fn layout() -> Layout {
+            let uuid = ServiceUuid(uuid!("6b5e0b8f-3b0e-4c4e-9a0e-7d8c6b1f6c2a"));
+
+            Layout::Service(
+                Service::builder("test", "Test", uuid, 0)
+                    .function(1, "foo", Some(LexicalId::U32), None, None)
+                    .finish(),
+            )
+        }
+
+        fn lexical_id() -> LexicalId {
+            LexicalId::service("test", "Test")
+        }
+
+        fn add_references(references: &mut References) {
+            references.add::<u32>();
+        }
+    }
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+    svc.enable_args_validation(Validator::new::<Test>());
+
+    let proxy = client.create_proxy(svc.id()).await.unwrap();
+
+    let reply = proxy.call(1, &1u32);
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.validate(), Ok(()));
+    let (args, promise) = call.deserialize_and_cast::<u32, u32, ()>().unwrap();
+    assert_eq!(args, 1);
+    promise.ok(2).unwrap();
+    assert_eq!(reply.await.unwrap().unwrap().deserialize(), Ok(2u32));
+
+    let reply = proxy.call(1, "foo");
+    let call = svc.next_call().await.unwrap();
+    let err = call.deserialize_and_cast::<u32, u32, ()>().unwrap_err();
+    let Error::InvalidArguments(err) = err else {
+        panic!("unexpected error {err:?}");
+    };
+    assert_eq!(err.id(), 1);
+    assert_eq!(
+        err.validation_error().map(|e| e.kind()),
+        Some(&ValidationErrorKind::UnexpectedValue(ValueKind::String)),
+    );
+    assert_eq!(
+        reply.await.map_err(Error::without_context),
+        Err(Error::invalid_arguments(1, None))
+    );
+}
+
+#[tokio::test]
+async fn event_subscriptions() {
+    use aldrin_test::aldrin::low_level::SubscriptionChange;
+
+    let mut broker = TestBroker::new();
+    let client1 = broker.add_client().await;
+    let client2 = broker.add_client().await;
+
+    let obj = client1.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let mut subs = svc.event_subscriptions().await.unwrap();
+    assert!(!subs.has_subscribers(1));
+
+    let proxy = client2.create_proxy(svc.id()).await.unwrap();
+    proxy.subscribe(1).await.unwrap();
+
+    time::timeout(Duration::from_millis(100), subs.subscribed(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(subs.has_subscribers(1));
+    assert!(!subs.has_subscribers(2));
+
+    proxy.unsubscribe(1).await.unwrap();
+    time::timeout(Duration::from_millis(100), subs.unsubscribed(1))
+        .await
+        .unwrap();
+
+    proxy.subscribe_all().await.unwrap();
+    let change = time::timeout(Duration::from_millis(100), subs.next_change())
+        .await
+        .unwrap();
+    assert_eq!(change, Some(SubscriptionChange::AllSubscribed));
+    assert!(subs.has_subscribers(2));
+
+    // A new `EventSubscriptions` starts with the current state.
+    let mut subs2 = svc.event_subscriptions().await.unwrap();
+    assert!(subs2.has_subscribers(1));
+
+    svc.destroy().await.unwrap();
+    time::timeout(Duration::from_millis(100), subs.unsubscribed(2))
+        .await
+        .unwrap();
+    assert_eq!(subs.next_change().await, None);
+    assert!(!subs2.has_subscribers(1));
+}
+
+#[cfg(feature = "statistics")]
+#[tokio::test]
+async fn statistics() {
+    use crate::core::SerializedValue;
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+    let mut proxy = client.create_proxy(svc.id()).await.unwrap();
+
+    let reply = proxy.call(0, &1u32);
+    let call = svc.next_call().await.unwrap();
+    call.into_promise().ok(&2u32).unwrap();
+    assert_eq!(reply.await.unwrap().unwrap().deserialize(), Ok(2u32));
+
+    let reply = proxy.call(1, &());
+    let call = svc.next_call().await.unwrap();
+    call.into_promise().invalid_function().unwrap();
+    reply.await.unwrap_err();
+
+    proxy.subscribe(0).await.unwrap();
+    svc.emit(0, "foo").unwrap();
+    proxy.next_event().await.unwrap();
+
+    let len = |value: SerializedValue| value.len() as u64;
+    let sent = len(SerializedValue::serialize(&1u32).unwrap())
+        + len(SerializedValue::serialize(&()).unwrap());
+    let received = len(SerializedValue::serialize(&2u32).unwrap())
+        + len(SerializedValue::serialize("foo").unwrap());
+
+    let stats = proxy.statistics();
+    assert_eq!(stats.calls(), 2);
+    assert_eq!(stats.calls_failed(), 1);
+    assert_eq!(stats.events_received(), 1);
+    assert_eq!(stats.bytes_sent(), sent);
+    assert_eq!(stats.bytes_received(), received);
+    assert_eq!(proxy.clone().statistics(), stats);
+
+    let stats = svc.statistics();
+    assert_eq!(stats.calls_received(), 2);
+    assert_eq!(stats.calls_failed(), 1);
+    assert_eq!(stats.events_emitted(), 1);
+    assert_eq!(stats.bytes_sent(), received);
+    assert_eq!(stats.bytes_received(), sent);
+}
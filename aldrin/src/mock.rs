@@ -0,0 +1,201 @@
+//! Mock services for tests.
+//!
+//! The types in this module are used by mocks, which are generated by the `service!` macro with the
+//! `#[aldrin(mocks)]` attribute, or by the code generator with the `mocks` option. A mock wraps a
+//! regular service and answers function calls according to [`Expectation`s](Expectation), that
+//! have been set up beforehand.
+//!
+//! Calls, for which no expectation matches, cause a panic. Likewise, [`Mock::checkpoint`] panics
+//! if some expectation has not been called the expected number of times.
+
+use crate::core::Serialize;
+use crate::{Error, Promise};
+use std::fmt;
+use std::mem;
+use std::task::{Context, Poll};
+
+type Matcher<A> = Box<dyn Fn(&A) -> bool + Send>;
+type Returning<A, T, E> = Box<dyn FnMut(A) -> Result<T, E> + Send>;
+
+/// Mock of a service.
+///
+/// This trait is implemented by all generated mocks.
+pub trait Mock {
+    /// Polls the service for the next call and answers it.
+    ///
+    /// `None` is returned when the service has been destroyed.
+    fn poll_handle_call(&mut self, cx: &mut Context) -> Poll<Option<Result<(), Error>>>;
+
+    /// Verifies and then clears all expectations.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if some expectation has not been called the expected number of times.
+    fn checkpoint(&mut self);
+}
+
+/// Expected call of a mocked function.
+///
+/// `A` is the type of the function's arguments (`()` if it has none), `T` and `E` are the types of
+/// its result.
+pub struct Expectation<A, T, E> {
+    matcher: Option<Matcher<A>>,
+    returning: Option<Returning<A, T, E>>,
+    times: Option<usize>,
+    calls: usize,
+}
+
+impl<A, T, E> Expectation<A, T, E> {
+    fn new() -> Self {
+        Self {
+            matcher: None,
+            returning: None,
+            times: None,
+            calls: 0,
+        }
+    }
+
+    /// Restricts the expectation to calls whose arguments satisfy `matcher`.
+    pub fn withf<F>(&mut self, matcher: F) -> &mut Self
+    where
+        F: Fn(&A) -> bool + Send + 'static,
+    {
+        self.matcher = Some(Box::new(matcher));
+        self
+    }
+
+    /// Sets the number of times the expectation must be called.
+    ///
+    /// Once the expectation has been called `times` times, it no longer matches further calls.
+    pub fn times(&mut self, times: usize) -> &mut Self {
+        self.times = Some(times);
+        self
+    }
+
+    /// Sets a function that computes the reply to each call.
+    pub fn returning<F>(&mut self, returning: F) -> &mut Self
+    where
+        F: FnMut(A) -> Result<T, E> + Send + 'static,
+    {
+        self.returning = Some(Box::new(returning));
+        self
+    }
+
+    /// Sets a fixed reply to all calls.
+    pub fn return_const(&mut self, res: Result<T, E>) -> &mut Self
+    where
+        T: Clone + Send + 'static,
+        E: Clone + Send + 'static,
+    {
+        self.returning(move |_| res.clone())
+    }
+
+    /// Returns how often the expectation has been called.
+    pub fn calls(&self) -> usize {
+        self.calls
+    }
+
+    fn matches(&self, args: &A) -> bool {
+        let saturated = self.times.is_some_and(|times| self.calls >= times);
+        !saturated && self.matcher.as_ref().map_or(true, |matcher| matcher(args))
+    }
+}
+
+impl<A, T, E> fmt::Debug for Expectation<A, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Expectation")
+            .field("times", &self.times)
+            .field("calls", &self.calls)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Expectations of a single mocked function.
+pub struct Expectations<A, T, E> {
+    name: &'static str,
+    expectations: Vec<Expectation<A, T, E>>,
+    calls: usize,
+}
+
+impl<A, T, E> Expectations<A, T, E> {
+    #[doc(hidden)]
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            expectations: Vec::new(),
+            calls: 0,
+        }
+    }
+
+    /// Adds a new expectation.
+    ///
+    /// Calls are matched against expectations in the order in which they were added.
+    pub fn expect(&mut self) -> &mut Expectation<A, T, E> {
+        self.expectations.push(Expectation::new());
+        self.expectations.last_mut().unwrap()
+    }
+
+    /// Returns how often the function has been called since the last checkpoint.
+    pub fn calls(&self) -> usize {
+        self.calls
+    }
+
+    /// Answers a call with the first matching expectation.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no expectation matches or if the matching expectation has no reply
+    /// set.
+    pub fn handle(&mut self, args: A, promise: Promise<T, E>) -> Result<(), Error>
+    where
+        T: Serialize,
+        E: Serialize,
+    {
+        self.calls += 1;
+
+        let Some(expectation) = self.expectations.iter_mut().find(|exp| exp.matches(&args)) else {
+            panic!("unexpected call to `{}`", self.name);
+        };
+
+        expectation.calls += 1;
+
+        let Some(ref mut returning) = expectation.returning else {
+            panic!("no reply set for call to `{}`", self.name);
+        };
+
+        let res = returning(args);
+        promise.set_ref(res.as_ref())
+    }
+
+    /// Verifies and then clears all expectations.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if some expectation has not been called the expected number of times.
+    pub fn checkpoint(&mut self) {
+        let expectations = mem::take(&mut self.expectations);
+        self.calls = 0;
+
+        for expectation in expectations {
+            if let Some(times) = expectation.times {
+                assert!(
+                    expectation.calls == times,
+                    "expected `{}` to be called {} time(s), but it was called {} time(s)",
+                    self.name,
+                    times,
+                    expectation.calls,
+                );
+            }
+        }
+    }
+}
+
+impl<A, T, E> fmt::Debug for Expectations<A, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Expectations")
+            .field("name", &self.name)
+            .field("expectations", &self.expectations)
+            .field("calls", &self.calls)
+            .finish()
+    }
+}
@@ -112,6 +112,76 @@ impl Object {
     ) -> Result<Service, Error> {
         self.client.create_service(self.id, uuid.into(), info).await
     }
+
+    /// Creates several services on the object atomically.
+    ///
+    /// Either all services are created or none at all. Other clients never observe only some of
+    /// the services, e.g. a [`Discoverer`](crate::Discoverer) will not find a partial
+    /// object. The returned [`Service`s](Service) are in the same order as `services`.
+    ///
+    /// If any `uuid` already exists on this [`Object`] or occurs more than once in `services`,
+    /// then [`Error::DuplicateService`] is returned. This function requires protocol version 1.19
+    /// and returns [`Error::NotSupported`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin::core::{ObjectUuid, ServiceUuid};
+    /// use aldrin::low_level::ServiceInfo;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker = aldrin_test::tokio::TestBroker::new();
+    /// # let handle = broker.add_client().await;
+    /// let object = handle.create_object(ObjectUuid::new_v4()).await?;
+    ///
+    /// let services = object
+    ///     .create_services([
+    ///         (ServiceUuid::new_v4(), ServiceInfo::new(0)),
+    ///         (ServiceUuid::new_v4(), ServiceInfo::new(0)),
+    ///     ])
+    ///     .await?;
+    ///
+    /// assert_eq!(services.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_services<I, U>(&self, services: I) -> Result<Vec<Service>, Error>
+    where
+        I: IntoIterator<Item = (U, ServiceInfo)>,
+        U: Into<ServiceUuid>,
+    {
+        let services = services
+            .into_iter()
+            .map(|(uuid, info)| (uuid.into(), info))
+            .collect();
+
+        self.client.create_services(self.id, services).await
+    }
+
+    /// Destroys several services of the object atomically.
+    ///
+    /// Either all services are destroyed or none at all. If any of the services has already been
+    /// destroyed or doesn't belong to this [`Object`], then [`Error::InvalidService`] is returned.
+    /// This function requires protocol version 1.19 and returns [`Error::NotSupported`] otherwise.
+    pub async fn destroy_services<'a, I>(&self, services: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = &'a Service>,
+    {
+        let mut ids = Vec::new();
+
+        for service in services {
+            let id = service.id();
+
+            if id.object_id != self.id {
+                return Err(Error::InvalidService);
+            }
+
+            ids.push(id);
+        }
+
+        self.client.destroy_services(ids).await
+    }
 }
 
 impl Drop for Object {
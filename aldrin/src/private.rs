@@ -2,5 +2,84 @@
 //!
 //! This module and its contents are not part of the public API.
 
+#[cfg(feature = "serde")]
+pub mod serde_array;
+
+use crate::core::Serialize;
+use crate::error::Error;
+use crate::promise::Promise;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
 pub use futures_core;
+#[cfg(feature = "serde")]
+pub use serde;
 pub use uuid;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Replies to a call with the result of an async handler.
+///
+/// The handler's future is dropped without replying if the caller aborts the call.
+pub fn reply<'a, T, E>(
+    mut promise: Promise<T, E>,
+    mut handler: BoxFuture<'a, Result<T, E>>,
+) -> BoxFuture<'a, ()>
+where
+    T: Serialize + Send + 'a,
+    E: Serialize + Send + 'a,
+{
+    Box::pin(async move {
+        let res = future::poll_fn(|cx| {
+            if promise.poll_aborted(cx).is_ready() {
+                return Poll::Ready(None);
+            }
+
+            handler.as_mut().poll(cx).map(Some)
+        })
+        .await;
+
+        if let Some(res) = res {
+            let _ = promise.set_ref(res.as_ref());
+        }
+    })
+}
+
+/// Dispatches calls concurrently until the service ends.
+///
+/// Invalid calls are skipped, because they have already been answered. Calls, that are still
+/// pending when the service ends, are dropped.
+pub async fn serve_calls<'a, C>(
+    mut poll_next_call: impl FnMut(&mut Context) -> Poll<Option<Result<C, Error>>>,
+    mut dispatch: impl FnMut(C) -> BoxFuture<'a, ()>,
+) {
+    let mut pending: Vec<BoxFuture<'a, ()>> = Vec::new();
+
+    future::poll_fn(|cx| {
+        let mut finished = false;
+
+        loop {
+            match poll_next_call(cx) {
+                Poll::Ready(Some(Ok(call))) => pending.push(dispatch(call)),
+                Poll::Ready(Some(Err(_))) => {}
+
+                Poll::Ready(None) => {
+                    finished = true;
+                    break;
+                }
+
+                Poll::Pending => break,
+            }
+        }
+
+        pending.retain_mut(|call| call.as_mut().poll(cx).is_pending());
+
+        if finished {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
@@ -0,0 +1,109 @@
+//! Serde support for arrays of any length.
+//!
+//! Serde implements its traits only for arrays of up to 32 elements. Generated code uses this
+//! module with `#[serde(with = "...")]` for all fields of array type. Arrays are represented the
+//! same way as by serde itself, i.e. as tuples.
+
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+pub fn serialize<S, T, const N: usize>(value: &[T; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    let mut serializer = serializer.serialize_tuple(N)?;
+
+    for elem in value {
+        serializer.serialize_element(elem)?;
+    }
+
+    serializer.end()
+}
+
+pub fn deserialize<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    deserializer.deserialize_tuple(N, ArrayVisitor(PhantomData))
+}
+
+struct ArrayVisitor<T, const N: usize>(PhantomData<T>);
+
+impl<'de, T, const N: usize> Visitor<'de> for ArrayVisitor<T, N>
+where
+    T: Deserialize<'de>,
+{
+    type Value = [T; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an array of length {N}")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elems = Vec::with_capacity(N);
+
+        while elems.len() < N {
+            match seq.next_element()? {
+                Some(elem) => elems.push(elem),
+                None => return Err(A::Error::invalid_length(elems.len(), &self)),
+            }
+        }
+
+        elems
+            .try_into()
+            .map_err(|_| A::Error::invalid_length(N, &self))
+    }
+}
+
+/// Serde support for optional arrays of any length.
+pub mod option {
+    use serde::de::{Deserialize, Deserializer};
+    use serde::ser::{Serialize, Serializer};
+
+    pub fn serialize<S, T, const N: usize>(
+        value: &Option<[T; N]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        struct Array<'a, T, const N: usize>(&'a [T; N]);
+
+        impl<T: Serialize, const N: usize> Serialize for Array<'_, T, N> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                super::serialize(self.0, serializer)
+            }
+        }
+
+        match value {
+            Some(value) => serializer.serialize_some(&Array(value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D, T, const N: usize>(
+        deserializer: D,
+    ) -> Result<Option<[T; N]>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        struct Array<T, const N: usize>([T; N]);
+
+        impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for Array<T, N> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                super::deserialize(deserializer).map(Self)
+            }
+        }
+
+        Option::<Array<T, N>>::deserialize(deserializer).map(|value| value.map(|value| value.0))
+    }
+}
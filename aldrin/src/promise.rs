@@ -1,3 +1,4 @@
+use crate::channel::{Sender, UnboundReceiver};
 use crate::core::{AsSerializeArg, Serialize, SerializeArg};
 use crate::error::Error;
 use crate::handle::Handle;
@@ -141,6 +142,26 @@ where
     }
 }
 
+impl<T, E: ?Sized> Promise<UnboundReceiver<T>, E> {
+    /// Replies to a streaming function and returns a sender for the stream's items.
+    ///
+    /// This creates a new channel, replies to the call with its receiver and waits until the caller
+    /// has claimed it. Use [`err`](Self::err) instead to fail the call before streaming any items.
+    ///
+    /// If the call has been aborted by the caller in the meantime, then [`Error::CallAborted`] is
+    /// returned.
+    pub async fn stream(mut self) -> Result<Sender<T>, Error> {
+        let (sender, receiver) = self.client().create_channel::<T>().claim_sender().await?;
+
+        if self.is_aborted() {
+            return Err(Error::CallAborted);
+        }
+
+        self.ok_ref(&receiver.unbind())?;
+        sender.establish().await
+    }
+}
+
 impl<T: ?Sized, E: ?Sized> fmt::Debug for Promise<T, E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Promise")
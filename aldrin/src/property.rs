@@ -0,0 +1,621 @@
+#[cfg(test)]
+mod test;
+
+use crate::core::{
+    Deserialize, DeserializeError, Deserializer, Serialize, SerializeError, Serializer,
+};
+use crate::error::Error;
+use crate::low_level::{Event, Promise, Proxy, Reply, Service};
+use std::fmt;
+use std::future::{self, Future};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Applies a delta update to a property value.
+///
+/// Properties, which are synchronized with [`PropertyPublisher`] and [`PropertySubscriber`], can
+/// be updated either with a full value or with a delta of type `D`. Both sides use this trait to
+/// apply deltas to their local copy of the value.
+///
+/// Properties without deltas use [`NoDelta`], for which this trait is implemented for all types.
+pub trait ApplyDelta<D> {
+    /// Applies `delta` to `self`.
+    fn apply_delta(&mut self, delta: D);
+}
+
+impl<T> ApplyDelta<NoDelta> for T {
+    fn apply_delta(&mut self, delta: NoDelta) {
+        match delta {}
+    }
+}
+
+/// Delta type of properties, that are only updated with full values.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NoDelta {}
+
+impl Serialize for NoDelta {
+    fn serialize(&self, _serializer: Serializer) -> Result<(), SerializeError> {
+        match *self {}
+    }
+}
+
+impl Deserialize for NoDelta {
+    fn deserialize(_deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        Err(DeserializeError::UnexpectedValue)
+    }
+}
+
+/// Update of a property, as it is sent over the bus.
+///
+/// Every update carries a sequence number, which is incremented by 1 for every change of the
+/// property. Subscribers use it to detect missed updates and to discard stale ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyUpdate<T, D = NoDelta> {
+    /// The full value of the property.
+    ///
+    /// A value of `None` indicates, that the property has not been set.
+    Full {
+        /// Sequence number of the update.
+        seq: u64,
+
+        /// Value of the property.
+        value: Option<T>,
+    },
+
+    /// A delta, that must be applied to the previous value.
+    Delta {
+        /// Sequence number of the update.
+        seq: u64,
+
+        /// Delta to the previous value.
+        delta: D,
+    },
+}
+
+impl<T, D> PropertyUpdate<T, D> {
+    /// Returns the sequence number of the update.
+    pub fn seq(&self) -> u64 {
+        match *self {
+            Self::Full { seq, .. } | Self::Delta { seq, .. } => seq,
+        }
+    }
+}
+
+impl<T: Serialize, D: Serialize> Serialize for PropertyUpdate<T, D> {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        match self {
+            Self::Full { seq, value } => {
+                UpdateRef::<T, D>::full(*seq, value.as_ref()).serialize(serializer)
+            }
+
+            Self::Delta { seq, delta } => {
+                UpdateRef::<T, D>::delta(*seq, delta).serialize(serializer)
+            }
+        }
+    }
+}
+
+impl<T: Deserialize, D: Deserialize> Deserialize for PropertyUpdate<T, D> {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let mut deserializer = deserializer.deserialize_struct()?;
+
+        let mut seq = None;
+        let mut value = None;
+        let mut delta = None;
+
+        while deserializer.has_more_fields() {
+            let deserializer = deserializer.deserialize_field()?;
+
+            match deserializer.id() {
+                FIELD_SEQ => seq = deserializer.deserialize().map(Some)?,
+                FIELD_VALUE => value = deserializer.deserialize().map(Some)?,
+                FIELD_DELTA => delta = deserializer.deserialize().map(Some)?,
+                _ => deserializer.skip()?,
+            }
+        }
+
+        deserializer.finish_with(|| {
+            let seq = seq.ok_or(DeserializeError::InvalidSerialization)?;
+
+            match (value, delta) {
+                (Some(value), None) => Ok(Self::Full { seq, value }),
+                (None, Some(delta)) => Ok(Self::Delta { seq, delta }),
+                _ => Err(DeserializeError::InvalidSerialization),
+            }
+        })
+    }
+}
+
+const FIELD_SEQ: u32 = 0;
+const FIELD_VALUE: u32 = 1;
+const FIELD_DELTA: u32 = 2;
+
+enum UpdateRef<'a, T, D> {
+    Full { seq: u64, value: Option<&'a T> },
+    Delta { seq: u64, delta: &'a D },
+}
+
+impl<'a, T, D> UpdateRef<'a, T, D> {
+    fn full(seq: u64, value: Option<&'a T>) -> Self {
+        Self::Full { seq, value }
+    }
+
+    fn delta(seq: u64, delta: &'a D) -> Self {
+        Self::Delta { seq, delta }
+    }
+}
+
+impl<T: Serialize, D: Serialize> Serialize for UpdateRef<'_, T, D> {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        let mut serializer = serializer.serialize_struct(2)?;
+
+        match *self {
+            Self::Full { seq, value } => {
+                serializer.serialize_field(FIELD_SEQ, &seq)?;
+                serializer.serialize_field(FIELD_VALUE, &value)?;
+            }
+
+            Self::Delta { seq, delta } => {
+                serializer.serialize_field(FIELD_SEQ, &seq)?;
+                serializer.serialize_field(FIELD_DELTA, delta)?;
+            }
+        }
+
+        serializer.finish()
+    }
+}
+
+/// Server-side half of a synchronized property.
+///
+/// A `PropertyPublisher` holds the current value of a property and publishes changes over an
+/// event of a [`Service`]. Changes can be published either as a full value with
+/// [`set`](Self::set) or as a delta with [`apply`](Self::apply).
+///
+/// Subscribers fetch the initial value with a function call, which must be answered with
+/// [`reply`](Self::reply). The event and the function use the same id.
+///
+/// Properties can also be declared in schemas and in the `service!` macro. Generated services and
+/// proxies then manage publishers and subscribers automatically.
+#[derive(Debug)]
+pub struct PropertyPublisher<T, D = NoDelta> {
+    id: u32,
+    seq: u64,
+    value: Option<T>,
+    phantom: PhantomData<fn(D)>,
+}
+
+impl<T, D> PropertyPublisher<T, D> {
+    /// Creates a new publisher for the event and function `id`, without a value.
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            seq: 0,
+            value: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Creates a new publisher for the event and function `id` with an initial value.
+    pub fn with_value(id: u32, value: T) -> Self {
+        Self {
+            id,
+            seq: 0,
+            value: Some(value),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the id of the event and function, that is used for the property.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the sequence number of the current value.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Returns the current value.
+    pub fn get(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+}
+
+impl<T: Serialize, D: Serialize> PropertyPublisher<T, D> {
+    /// Sets a new value and publishes it.
+    pub fn set(&mut self, service: &Service, value: T) -> Result<(), Error> {
+        self.value = Some(value);
+        self.publish_full(service)
+    }
+
+    /// Clears the value and publishes the change.
+    pub fn clear(&mut self, service: &Service) -> Result<(), Error> {
+        self.value = None;
+        self.publish_full(service)
+    }
+
+    /// Applies a delta to the current value and publishes it.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the property doesn't have a value.
+    pub fn apply(&mut self, service: &Service, delta: D) -> Result<(), Error>
+    where
+        T: ApplyDelta<D>,
+    {
+        let value = self.value.as_mut().expect("property has no value");
+        self.seq += 1;
+
+        let res = service.emit(self.id, &UpdateRef::<T, D>::delta(self.seq, &delta));
+
+        value.apply_delta(delta);
+        res
+    }
+
+    /// Replies to a call, which fetches the current value.
+    pub fn reply(&self, promise: Promise) -> Result<(), Error> {
+        promise.ok(&UpdateRef::<T, D>::full(self.seq, self.value.as_ref()))
+    }
+
+    fn publish_full(&mut self, service: &Service) -> Result<(), Error> {
+        self.seq += 1;
+
+        service.emit(
+            self.id,
+            &UpdateRef::<T, D>::full(self.seq, self.value.as_ref()),
+        )
+    }
+}
+
+/// Client-side half of a synchronized property.
+///
+/// A `PropertySubscriber` maintains a local copy of a property, which is published by a
+/// [`PropertyPublisher`]. It is driven by a [`Proxy`]:
+///
+/// - [`subscribe`](Self::subscribe) subscribes to the property's event and fetches the initial
+///   value.
+/// - [`handle_event`](Self::handle_event) must be called with all events of the proxy.
+/// - [`poll_fetch`](Self::poll_fetch) must be polled to complete fetches of the value.
+///
+/// Updates, which were missed, are detected by their sequence numbers. The value is then fetched
+/// again automatically.
+///
+/// Changes can be observed with [`watch`](Self::watch), in addition to the return values of
+/// [`handle_event`](Self::handle_event) and [`poll_fetch`](Self::poll_fetch).
+#[derive(Debug)]
+pub struct PropertySubscriber<T, D = NoDelta> {
+    id: u32,
+    seq: Option<u64>,
+    value: Option<T>,
+    fetch: Option<Reply>,
+    pending: Vec<PropertyUpdate<T, D>>,
+    watch: Option<Arc<Mutex<WatchState<T>>>>,
+}
+
+impl<T, D> PropertySubscriber<T, D> {
+    /// Creates a new subscriber for the event and function `id`.
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            seq: None,
+            value: None,
+            fetch: None,
+            pending: Vec::new(),
+            watch: None,
+        }
+    }
+
+    /// Returns the id of the event and function, that is used for the property.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the sequence number of the current value.
+    ///
+    /// `None` is returned if the value is not known yet.
+    pub fn seq(&self) -> Option<u64> {
+        self.seq
+    }
+
+    /// Returns the current value.
+    pub fn get(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Indicates whether a fetch of the value is in progress.
+    pub fn is_fetching(&self) -> bool {
+        self.fetch.is_some()
+    }
+
+    /// Starts fetching the value.
+    ///
+    /// Any fetch, that is already in progress, is aborted.
+    pub fn fetch(&mut self, proxy: &Proxy) {
+        self.fetch = Some(proxy.call(self.id, &()));
+    }
+
+    /// Subscribes to the property's event and starts fetching the initial value.
+    pub async fn subscribe(&mut self, proxy: &Proxy) -> Result<(), Error> {
+        proxy.subscribe(self.id).await?;
+        self.fetch(proxy);
+        Ok(())
+    }
+
+    /// Unsubscribes from the property's event.
+    ///
+    /// The current value is kept, but will no longer be updated.
+    pub async fn unsubscribe(&mut self, proxy: &Proxy) -> Result<(), Error> {
+        self.fetch = None;
+        self.pending.clear();
+        proxy.unsubscribe(self.id).await
+    }
+
+    /// Clones the subscriber for use with a clone of its proxy.
+    ///
+    /// The current value and sequence number are copied, but not any watches. If a fetch of the
+    /// value is in progress, then the new subscriber starts its own fetch on `proxy`.
+    pub fn clone_with(&self, proxy: &Proxy) -> Self
+    where
+        T: Clone,
+    {
+        let mut clone = Self::new(self.id);
+        clone.seq = self.seq;
+        clone.value = self.value.clone();
+
+        if self.is_fetching() {
+            clone.fetch(proxy);
+        }
+
+        clone
+    }
+}
+
+impl<T, D> PropertySubscriber<T, D>
+where
+    T: Deserialize + ApplyDelta<D> + Clone,
+    D: Deserialize,
+{
+    /// Handles an event of the proxy.
+    ///
+    /// Events with a different id are ignored. Returns `true` if the value has changed.
+    pub fn handle_event(&mut self, proxy: &Proxy, event: &Event) -> Result<bool, Error> {
+        if event.id() != self.id {
+            return Ok(false);
+        }
+
+        let update = event
+            .deserialize()
+            .map_err(|e| Error::invalid_arguments(self.id, Some(e)))?;
+
+        if self.fetch.is_some() {
+            self.pending.push(update);
+            return Ok(false);
+        }
+
+        match self.apply(update) {
+            Some(changed) => {
+                if changed {
+                    self.notify();
+                }
+
+                Ok(changed)
+            }
+
+            None => {
+                self.fetch(proxy);
+                Ok(false)
+            }
+        }
+    }
+
+    /// Polls a fetch of the value for completion.
+    ///
+    /// Returns `true` if the value has changed. If no fetch is in progress, then
+    /// `Poll::Ready(Ok(false))` is returned immediately.
+    pub fn poll_fetch(&mut self, cx: &mut Context) -> Poll<Result<bool, Error>> {
+        let Some(ref mut fetch) = self.fetch else {
+            return Poll::Ready(Ok(false));
+        };
+
+        let res = match Pin::new(fetch).poll(cx) {
+            Poll::Ready(res) => res,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        self.fetch = None;
+        let pending = std::mem::take(&mut self.pending);
+
+        let update = match res {
+            Ok(Ok(update)) => update,
+            Err(e) => return Poll::Ready(Err(e)),
+
+            Ok(Err(_)) => {
+                return Poll::Ready(Err(Error::invalid_reply(DeserializeError::UnexpectedValue)));
+            }
+        };
+
+        let update = match update.deserialize() {
+            Ok(update @ PropertyUpdate::Full { .. }) => update,
+            Ok(PropertyUpdate::Delta { .. }) => {
+                return Poll::Ready(Err(Error::invalid_reply(DeserializeError::UnexpectedValue)));
+            }
+            Err(e) => return Poll::Ready(Err(Error::invalid_reply(e))),
+        };
+
+        let mut changed = self.apply(update).unwrap_or(false);
+
+        for update in pending {
+            match self.apply(update) {
+                Some(update_changed) => changed |= update_changed,
+
+                // Later updates will detect the gap as well and then fetch the value again.
+                None => break,
+            }
+        }
+
+        if changed {
+            self.notify();
+        }
+
+        Poll::Ready(Ok(changed))
+    }
+
+    /// Awaits the completion of a fetch of the value.
+    ///
+    /// Returns `true` if the value has changed. If no fetch is in progress, then this function
+    /// returns `Ok(false)` immediately.
+    pub async fn fetched(&mut self) -> Result<bool, Error> {
+        future::poll_fn(|cx| self.poll_fetch(cx)).await
+    }
+
+    /// Returns a [`PropertyWatch`], that is notified about changes of the value.
+    pub fn watch(&mut self) -> PropertyWatch<T> {
+        let value = &self.value;
+
+        let state = self.watch.get_or_insert_with(|| {
+            Arc::new(Mutex::new(WatchState {
+                value: value.clone(),
+                version: 0,
+                closed: false,
+                wakers: Vec::new(),
+            }))
+        });
+
+        let version = state.lock().unwrap().version;
+
+        PropertyWatch {
+            state: state.clone(),
+            version,
+        }
+    }
+
+    /// Applies an update. Returns `None` if updates are missing.
+    fn apply(&mut self, update: PropertyUpdate<T, D>) -> Option<bool> {
+        match update {
+            PropertyUpdate::Full { seq, value } => match self.seq {
+                Some(cur) if seq <= cur => Some(false),
+
+                _ => {
+                    self.seq = Some(seq);
+                    self.value = value;
+                    Some(true)
+                }
+            },
+
+            PropertyUpdate::Delta { seq, delta } => match self.seq {
+                Some(cur) if seq <= cur => Some(false),
+
+                Some(cur) if seq == cur + 1 => {
+                    self.value.as_mut()?.apply_delta(delta);
+                    self.seq = Some(seq);
+                    Some(true)
+                }
+
+                _ => None,
+            },
+        }
+    }
+
+    fn notify(&self) {
+        if let Some(ref state) = self.watch {
+            let mut state = state.lock().unwrap();
+            state.value = self.value.clone();
+            state.version += 1;
+
+            for waker in state.wakers.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T, D> Drop for PropertySubscriber<T, D> {
+    fn drop(&mut self) {
+        if let Some(ref state) = self.watch {
+            let mut state = state.lock().unwrap();
+            state.closed = true;
+
+            for waker in state.wakers.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Watches a property for changes.
+///
+/// A `PropertyWatch` is created with [`PropertySubscriber::watch`]. It is notified whenever the
+/// subscriber observes a change of the value. Note that the subscriber must be driven for this to
+/// happen.
+pub struct PropertyWatch<T> {
+    state: Arc<Mutex<WatchState<T>>>,
+    version: u64,
+}
+
+impl<T: Clone> PropertyWatch<T> {
+    /// Returns the current value.
+    pub fn get(&self) -> Option<T> {
+        self.state.lock().unwrap().value.clone()
+    }
+
+    /// Indicates whether the value has changed since it was last marked as seen.
+    pub fn has_changed(&self) -> bool {
+        self.state.lock().unwrap().version != self.version
+    }
+
+    /// Polls for a change of the value.
+    ///
+    /// When a change is returned, the value is marked as seen. Returns `false` if the
+    /// [`PropertySubscriber`] was dropped.
+    pub fn poll_changed(&mut self, cx: &mut Context) -> Poll<bool> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.version != self.version {
+            self.version = state.version;
+            Poll::Ready(true)
+        } else if state.closed {
+            Poll::Ready(false)
+        } else {
+            if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                state.wakers.push(cx.waker().clone());
+            }
+
+            Poll::Pending
+        }
+    }
+
+    /// Awaits a change of the value.
+    ///
+    /// The value is marked as seen. Returns `false` if the [`PropertySubscriber`] was dropped.
+    pub async fn changed(&mut self) -> bool {
+        future::poll_fn(|cx| self.poll_changed(cx)).await
+    }
+}
+
+impl<T> Clone for PropertyWatch<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            version: self.version,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PropertyWatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PropertyWatch")
+            .field("state", &self.state)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+struct WatchState<T> {
+    value: Option<T>,
+    version: u64,
+    closed: bool,
+    wakers: Vec<Waker>,
+}
@@ -0,0 +1,171 @@
+use crate::core::{
+    Deserialize, DeserializeError, Deserializer, ObjectUuid, Serialize, SerializeError,
+    SerializedValue, Serializer, ServiceUuid,
+};
+use aldrin_test::aldrin::low_level::ServiceInfo;
+use aldrin_test::aldrin::{
+    ApplyDelta, NoDelta, PropertyPublisher, PropertySubscriber, PropertyUpdate,
+};
+use aldrin_test::tokio::TestBroker;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Push(u32);
+
+impl Serialize for Push {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl Deserialize for Push {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        u32::deserialize(deserializer).map(Self)
+    }
+}
+
+impl ApplyDelta<Push> for Vec<u32> {
+    fn apply_delta(&mut self, delta: Push) {
+        self.push(delta.0);
+    }
+}
+
+#[test]
+fn serialize_update() {
+    let update = PropertyUpdate::<String, NoDelta>::Full {
+        seq: 1,
+        value: Some("foo".to_owned()),
+    };
+    let value = SerializedValue::serialize(&update).unwrap();
+    assert_eq!(value.deserialize(), Ok(update));
+
+    let update = PropertyUpdate::<String, NoDelta>::Full {
+        seq: 2,
+        value: None,
+    };
+    let value = SerializedValue::serialize(&update).unwrap();
+    assert_eq!(value.deserialize(), Ok(update));
+
+    let update = PropertyUpdate::<Vec<u32>, Push>::Delta {
+        seq: 3,
+        delta: Push(4),
+    };
+    let value = SerializedValue::serialize(&update).unwrap();
+    assert_eq!(value.deserialize(), Ok(update));
+}
+
+#[tokio::test]
+async fn synchronize() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let mut publisher = PropertyPublisher::<Vec<u32>, Push>::with_value(1, vec![1]);
+    publisher.apply(&svc, Push(2)).unwrap();
+
+    let mut proxy = client.create_proxy(svc.id()).await.unwrap();
+    let mut subscriber = PropertySubscriber::<Vec<u32>, Push>::new(1);
+    let mut watch = subscriber.watch();
+    assert_eq!(watch.get(), None);
+
+    subscriber.subscribe(&proxy).await.unwrap();
+    assert!(subscriber.is_fetching());
+
+    // Updates before and after the reply are received while fetching the value.
+    publisher.apply(&svc, Push(3)).unwrap();
+    let call = svc.next_call().await.unwrap();
+    publisher.reply(call.into_promise()).unwrap();
+    publisher.apply(&svc, Push(4)).unwrap();
+
+    for _ in 0..2 {
+        let event = proxy.next_event().await.unwrap();
+        assert!(!subscriber.handle_event(&proxy, &event).unwrap());
+    }
+
+    assert!(subscriber.fetched().await.unwrap());
+    assert_eq!(subscriber.get(), Some(&vec![1, 2, 3, 4]));
+    assert_eq!(subscriber.seq(), Some(3));
+    assert!(watch.changed().await);
+    assert_eq!(watch.get(), Some(vec![1, 2, 3, 4]));
+    assert!(!watch.has_changed());
+
+    publisher.set(&svc, vec![5]).unwrap();
+    let event = proxy.next_event().await.unwrap();
+    assert!(subscriber.handle_event(&proxy, &event).unwrap());
+    assert_eq!(subscriber.get(), Some(&vec![5]));
+    assert!(watch.changed().await);
+
+    // Missed updates cause the value to be fetched again.
+    proxy.unsubscribe(1).await.unwrap();
+    publisher.apply(&svc, Push(6)).unwrap();
+    proxy.subscribe(1).await.unwrap();
+    publisher.apply(&svc, Push(7)).unwrap();
+
+    let event = proxy.next_event().await.unwrap();
+    assert!(!subscriber.handle_event(&proxy, &event).unwrap());
+    assert!(subscriber.is_fetching());
+
+    let call = svc.next_call().await.unwrap();
+    publisher.reply(call.into_promise()).unwrap();
+    assert!(subscriber.fetched().await.unwrap());
+    assert_eq!(subscriber.get(), Some(&vec![5, 6, 7]));
+
+    drop(subscriber);
+    assert!(watch.changed().await);
+    assert!(!watch.changed().await);
+}
+
+#[tokio::test]
+async fn clone_subscriber() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let mut publisher = PropertyPublisher::<Vec<u32>, Push>::with_value(1, vec![1]);
+
+    let mut proxy1 = client.create_proxy(svc.id()).await.unwrap();
+    let mut subscriber1 = PropertySubscriber::<Vec<u32>, Push>::new(1);
+    subscriber1.subscribe(&proxy1).await.unwrap();
+
+    // A clone, that is created while fetching, fetches the value on its own.
+    let mut proxy2 = proxy1.clone();
+    let mut subscriber2 = subscriber1.clone_with(&proxy2);
+    assert!(subscriber2.is_fetching());
+
+    for _ in 0..2 {
+        let call = svc.next_call().await.unwrap();
+        publisher.reply(call.into_promise()).unwrap();
+    }
+
+    assert!(subscriber1.fetched().await.unwrap());
+    assert!(subscriber2.fetched().await.unwrap());
+    assert_eq!(subscriber2.get(), Some(&vec![1]));
+
+    // Otherwise the value is copied and both clones receive updates.
+    let mut proxy3 = proxy2.clone();
+    let mut subscriber3 = subscriber2.clone_with(&proxy3);
+    assert!(!subscriber3.is_fetching());
+    assert_eq!(subscriber3.get(), Some(&vec![1]));
+    assert_eq!(subscriber3.seq(), Some(0));
+
+    publisher.apply(&svc, Push(2)).unwrap();
+
+    for (proxy, subscriber) in [
+        (&mut proxy1, &mut subscriber1),
+        (&mut proxy2, &mut subscriber2),
+        (&mut proxy3, &mut subscriber3),
+    ] {
+        let event = proxy.next_event().await.unwrap();
+        assert!(subscriber.handle_event(proxy, &event).unwrap());
+        assert_eq!(subscriber.get(), Some(&vec![1, 2]));
+    }
+}
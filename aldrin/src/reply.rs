@@ -1,5 +1,7 @@
+use crate::channel::{Receiver, UnboundReceiver};
 use crate::core::Deserialize;
 use crate::error::Error;
+use crate::handle::Handle;
 use crate::low_level;
 use std::fmt;
 use std::future::Future;
@@ -45,14 +47,14 @@ impl<T: Deserialize, E: Deserialize> Future for Reply<T, E> {
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
         match Pin::new(&mut self.inner).poll(cx) {
-            Poll::Ready(Ok(Ok(t))) => match t.deserialize() {
+            Poll::Ready(Ok(Ok(t))) => match t.deserialize_shared() {
                 Ok(t) => Poll::Ready(Ok(Ok(t))),
-                Err(e) => Poll::Ready(Err(Error::invalid_reply(e))),
+                Err(e) => Poll::Ready(Err(self.inner.call_error(Error::invalid_reply(e)))),
             },
 
-            Poll::Ready(Ok(Err(e))) => match e.deserialize() {
+            Poll::Ready(Ok(Err(e))) => match e.deserialize_shared() {
                 Ok(e) => Poll::Ready(Ok(Err(e))),
-                Err(e) => Poll::Ready(Err(Error::invalid_reply(e))),
+                Err(e) => Poll::Ready(Err(self.inner.call_error(Error::invalid_reply(e)))),
             },
 
             Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
@@ -61,6 +63,23 @@ impl<T: Deserialize, E: Deserialize> Future for Reply<T, E> {
     }
 }
 
+impl<T, E: Deserialize> Reply<UnboundReceiver<T>, E> {
+    /// Awaits the reply of a streaming function and claims the receiver.
+    ///
+    /// Streaming functions reply with an [`UnboundReceiver`], which is claimed here with the given
+    /// `capacity`. A capacity of 0 will be treated as if 1 was specified instead.
+    pub async fn claim_stream(
+        self,
+        client: &Handle,
+        capacity: u32,
+    ) -> Result<Result<Receiver<T>, E>, Error> {
+        match self.await? {
+            Ok(receiver) => receiver.claim(client.clone(), capacity).await.map(Ok),
+            Err(e) => Ok(Err(e)),
+        }
+    }
+}
+
 impl<T, E> fmt::Debug for Reply<T, E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Reply").field("inner", &self.inner).finish()
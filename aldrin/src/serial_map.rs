@@ -1,3 +1,4 @@
+use crate::low_level::RAW_SERIAL_MIN;
 use std::collections::hash_map::{Entry, HashMap};
 
 #[derive(Debug)]
@@ -17,7 +18,7 @@ impl<T> SerialMap<T> {
     pub fn insert(&mut self, obj: T) -> u32 {
         loop {
             let serial = self.next;
-            self.next = self.next.wrapping_add(1);
+            self.next = (self.next + 1) % RAW_SERIAL_MIN;
             if let Entry::Vacant(entry) = self.elems.entry(serial) {
                 entry.insert(obj);
                 return serial;
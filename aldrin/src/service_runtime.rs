@@ -0,0 +1,102 @@
+use crate::core::{ServiceId, ServiceUuid, TypeId};
+use crate::Handle;
+use std::fmt;
+
+/// Runtime information about a generated proxy or service.
+///
+/// This trait is implemented by all generated proxies and services. It is object-safe, which allows
+/// managing heterogeneous services generically, e.g. as `Box<dyn ServiceRuntime>` in a registry or
+/// for health checks and metrics.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin::ServiceRuntime;
+///
+/// fn describe(service: &dyn ServiceRuntime) -> String {
+///     format!(
+///         "{} ({}, version {}) with {} function(s) and {} event(s)",
+///         service.name(),
+///         service.uuid(),
+///         service.version(),
+///         service.functions().len(),
+///         service.events().len(),
+///     )
+/// }
+/// ```
+pub trait ServiceRuntime: fmt::Debug {
+    /// Returns the name of the service as it was declared.
+    fn name(&self) -> &'static str;
+
+    /// Returns the UUID of the service.
+    fn uuid(&self) -> ServiceUuid;
+
+    /// Returns the version of the service.
+    ///
+    /// For proxies, this is the version of the remote service, which may differ from the version
+    /// the proxy was generated for.
+    fn version(&self) -> u32;
+
+    /// Returns the type id of the service, if it is known.
+    fn type_id(&self) -> Option<TypeId>;
+
+    /// Returns the id of the service.
+    fn id(&self) -> ServiceId;
+
+    /// Returns a handle to the client the service is bound to.
+    fn client(&self) -> &Handle;
+
+    /// Returns the table of functions of the service.
+    fn functions(&self) -> &'static [ServiceFunction];
+
+    /// Returns the table of events of the service.
+    fn events(&self) -> &'static [ServiceEvent];
+}
+
+/// Entry of a function table of a [`ServiceRuntime`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ServiceFunction {
+    id: u32,
+    name: &'static str,
+}
+
+impl ServiceFunction {
+    /// Creates a new `ServiceFunction`.
+    pub const fn new(id: u32, name: &'static str) -> Self {
+        Self { id, name }
+    }
+
+    /// Returns the id of the function.
+    pub fn id(self) -> u32 {
+        self.id
+    }
+
+    /// Returns the name of the function.
+    pub fn name(self) -> &'static str {
+        self.name
+    }
+}
+
+/// Entry of an event table of a [`ServiceRuntime`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ServiceEvent {
+    id: u32,
+    name: &'static str,
+}
+
+impl ServiceEvent {
+    /// Creates a new `ServiceEvent`.
+    pub const fn new(id: u32, name: &'static str) -> Self {
+        Self { id, name }
+    }
+
+    /// Returns the id of the event.
+    pub fn id(self) -> u32 {
+        self.id
+    }
+
+    /// Returns the name of the event.
+    pub fn name(self) -> &'static str {
+        self.name
+    }
+}
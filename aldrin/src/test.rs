@@ -1,12 +1,19 @@
-use crate::core::{ObjectUuid, ServiceUuid};
-use aldrin_test::aldrin::low_level::{Proxy, ServiceInfo};
+use crate::core::channel;
+use crate::core::message::{CreateObjectReply, CreateObjectResult, Message, Sync, SyncReply};
+use crate::core::{BusListenerServiceFilter, ObjectUuid, ServiceUuid};
+use aldrin_test::aldrin::error::RunError;
+use aldrin_test::aldrin::low_level::{Proxy, ServiceInfo, RAW_SERIAL_MIN};
+use aldrin_test::aldrin::{Client, ClockService, Error, Handle};
+use aldrin_test::aldrin_broker::Broker;
 use aldrin_test::tokio::TestBroker;
 use std::future::Future;
 use std::mem;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::Duration;
+use tokio::task::JoinHandle;
 use tokio::time;
+use uuid::Uuid;
 
 struct PollOnce<Fut>(Fut);
 
@@ -169,3 +176,600 @@ async fn reply_aborted_call() {
     client.join().await;
     broker.join().await;
 }
+
+#[tokio::test]
+async fn create_and_destroy_services() {
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let uuid1 = ServiceUuid::new_v4();
+    let uuid2 = ServiceUuid::new_v4();
+    let uuid3 = ServiceUuid::new_v4();
+
+    let svcs = obj
+        .create_services([(uuid1, info), (uuid2, info)])
+        .await
+        .unwrap();
+    assert_eq!(svcs.len(), 2);
+    assert_eq!(svcs[0].id().uuid, uuid1);
+    assert_eq!(svcs[1].id().uuid, uuid2);
+    Proxy::new(&client, svcs[0].id()).await.unwrap();
+    Proxy::new(&client, svcs[1].id()).await.unwrap();
+
+    // Nothing is created if one of the services already exists.
+    assert_eq!(
+        obj.create_services([(uuid3, info), (uuid1, info)])
+            .await
+            .unwrap_err(),
+        Error::DuplicateService
+    );
+
+    assert_eq!(
+        obj.create_services([(uuid3, info), (uuid3, info)])
+            .await
+            .unwrap_err(),
+        Error::DuplicateService
+    );
+
+    obj.destroy_services(&svcs).await.unwrap();
+    assert_eq!(
+        Proxy::new(&client, svcs[0].id()).await.unwrap_err(),
+        Error::InvalidService
+    );
+    assert_eq!(
+        Proxy::new(&client, svcs[1].id()).await.unwrap_err(),
+        Error::InvalidService
+    );
+    assert_eq!(
+        obj.destroy_services(&svcs).await.unwrap_err(),
+        Error::InvalidService
+    );
+
+    let svcs = obj
+        .create_services([(uuid1, info), (uuid2, info), (uuid3, info)])
+        .await
+        .unwrap();
+    assert_eq!(svcs.len(), 3);
+
+    client.join().await;
+    broker.join().await;
+}
+
+#[tokio::test]
+async fn keep_alive_timeout() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (t1, t2) = channel::unbounded();
+    let client = tokio::spawn(
+        Client::builder(t1)
+            .with_keep_alive(|| time::sleep(Duration::from_millis(10)))
+            .connect(),
+    );
+
+    // The connection is never run, so the client's pings are never answered.
+    let conn = handle.connect(t2).await.unwrap();
+    let client = client.await.unwrap().unwrap();
+    let _handle = client.handle().clone();
+
+    let res = time::timeout(Duration::from_secs(1), client.run())
+        .await
+        .unwrap();
+    assert!(matches!(res, Err(RunError::KeepAliveTimeout)));
+
+    mem::drop(conn);
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn raw_messages() {
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+    let mut messages = client.raw_messages().unwrap();
+
+    client
+        .send_raw_message(Sync {
+            serial: RAW_SERIAL_MIN,
+        })
+        .unwrap();
+
+    assert_eq!(
+        messages.next_message().await,
+        Some(Message::SyncReply(SyncReply {
+            serial: RAW_SERIAL_MIN,
+        }))
+    );
+
+    // Messages handled by the client itself are received as well.
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    assert_eq!(
+        messages.next_message().await,
+        Some(Message::CreateObjectReply(CreateObjectReply {
+            serial: 0,
+            result: CreateObjectResult::Ok(obj.id().cookie),
+        }))
+    );
+
+    client.join().await;
+    broker.join().await;
+
+    assert_eq!(messages.next_message().await, None);
+}
+
+#[tokio::test]
+async fn call_timeout() {
+    use aldrin_test::aldrin::low_level::CallOptions;
+
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (t1, t2) = channel::unbounded();
+    let client = tokio::spawn(Client::builder(t1).with_timer(time::sleep).connect());
+    let conn = handle.connect(t2).await.unwrap();
+    tokio::spawn(conn.run());
+    let client = client.await.unwrap().unwrap();
+    let client_handle = client.handle().clone();
+    let client_join = tokio::spawn(client.run());
+
+    let obj = client_handle
+        .create_object(ObjectUuid::new_v4())
+        .await
+        .unwrap();
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+    let proxy = Proxy::new(&client_handle, svc.id()).await.unwrap();
+
+    let options = CallOptions::new().set_timeout(Duration::from_millis(10));
+    let reply = proxy.call_with_options(0, &(), options);
+    let _call = svc.next_call().await.unwrap();
+    assert_eq!(reply.await.unwrap_err().without_context(), Error::Timeout);
+
+    // Calls, which are answered in time, are unaffected by the timeout.
+    let options = CallOptions::new().set_timeout(Duration::from_secs(10));
+    let reply = proxy.call_with_options(0, &(), options);
+    let call = svc.next_call().await.unwrap();
+    call.into_promise().ok(&1u32).unwrap();
+    let res = reply.await.unwrap().unwrap();
+    assert_eq!(res.deserialize(), Ok(1u32));
+
+    client_handle.shutdown();
+    client_join.await.unwrap().unwrap();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn call_timeout_without_timer() {
+    use aldrin_test::aldrin::low_level::CallOptions;
+
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+    let proxy = Proxy::new(&client, svc.id()).await.unwrap();
+
+    let options = CallOptions::new().set_timeout(Duration::from_millis(10));
+    let reply = proxy.call_with_options(0, &(), options);
+    assert_eq!(reply.await.unwrap_err(), Error::NotSupported);
+
+    client.join().await;
+    broker.join().await;
+}
+
+#[tokio::test]
+async fn bounded_request_queue() {
+    use aldrin_test::aldrin::OverflowPolicy;
+
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (t1, t2) = channel::unbounded();
+    let client = tokio::spawn(
+        Client::builder(t1)
+            .with_queue_capacity(1, OverflowPolicy::Error)
+            .connect(),
+    );
+    let conn = handle.connect(t2).await.unwrap();
+    tokio::spawn(conn.run());
+    let client = client.await.unwrap().unwrap();
+    let client_handle = client.handle().clone();
+    let client_join = tokio::spawn(client.run());
+
+    let obj = client_handle
+        .create_object(ObjectUuid::new_v4())
+        .await
+        .unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    // The client doesn't get to run in between, so the second event exceeds the capacity.
+    svc.emit(0, &()).unwrap();
+    assert_eq!(svc.emit(0, &()), Err(Error::QueueFull));
+
+    client_handle.queue_ready().await;
+    svc.emit(0, &()).unwrap();
+
+    client_handle.shutdown();
+    client_join.await.unwrap().unwrap();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn list_objects_and_services() {
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+
+    assert_eq!(client.list_objects().await.unwrap(), []);
+
+    let info = ServiceInfo::new(0);
+    let obj1 = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc1 = obj1
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+    let obj2 = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc2 = obj2.create_service(svc1.id().uuid, info).await.unwrap();
+
+    let mut objects = client.list_objects().await.unwrap();
+    objects.sort();
+    let mut expected = [obj1.id(), obj2.id()];
+    expected.sort();
+    assert_eq!(objects, expected);
+
+    let mut services = client
+        .list_services(BusListenerServiceFilter::any())
+        .await
+        .unwrap();
+    services.sort();
+    let mut expected = [svc1.id(), svc2.id()];
+    expected.sort();
+    assert_eq!(services, expected);
+
+    let services = client
+        .list_services(BusListenerServiceFilter::with_object(obj2.id().uuid))
+        .await
+        .unwrap();
+    assert_eq!(services, [svc2.id()]);
+
+    svc1.destroy().await.unwrap();
+    obj1.destroy().await.unwrap();
+    assert_eq!(client.list_objects().await.unwrap(), [obj2.id()]);
+
+    client.join().await;
+    broker.join().await;
+}
+
+#[tokio::test]
+async fn find_all_objects() {
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+
+    let uuid = ServiceUuid::new_v4();
+    let info = ServiceInfo::new(0);
+
+    let obj1 = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc1 = obj1.create_service(uuid, info).await.unwrap();
+    let obj2 = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc2 = obj2.create_service(uuid, info).await.unwrap();
+    let obj3 = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    obj3.create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let mut objects = client.find_all_objects(None, &[uuid]).await.unwrap();
+    objects.sort();
+    let mut expected = [(obj1.id(), [svc1.id()]), (obj2.id(), [svc2.id()])];
+    expected.sort();
+    assert_eq!(objects, expected);
+
+    let objects = client
+        .find_all_objects(Some(obj2.id().uuid), &[uuid])
+        .await
+        .unwrap();
+    assert_eq!(objects, [(obj2.id(), [svc2.id()])]);
+
+    let objects = client
+        .find_all_objects(Some(obj3.id().uuid), &[uuid])
+        .await
+        .unwrap();
+    assert_eq!(objects, []);
+
+    client.join().await;
+    broker.join().await;
+}
+
+#[tokio::test]
+async fn wait_for_object_timeout() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (t1, t2) = channel::unbounded();
+    let client = tokio::spawn(Client::builder(t1).with_timer(time::sleep).connect());
+    let conn = handle.connect(t2).await.unwrap();
+    tokio::spawn(conn.run());
+    let client = client.await.unwrap().unwrap();
+    let client_handle = client.handle().clone();
+    let client_join = tokio::spawn(client.run());
+
+    let uuid = ServiceUuid::new_v4();
+    let res = client_handle
+        .wait_for_object_timeout(None, &[uuid], Duration::from_millis(10))
+        .await
+        .unwrap();
+    assert_eq!(res, None);
+
+    let obj = client_handle
+        .create_object(ObjectUuid::new_v4())
+        .await
+        .unwrap();
+    let svc = obj.create_service(uuid, ServiceInfo::new(0)).await.unwrap();
+
+    let res = client_handle
+        .wait_for_object_timeout(None, &[uuid], Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(res, Some((obj.id(), [svc.id()])));
+
+    client_handle.shutdown();
+    client_join.await.unwrap().unwrap();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn wait_for_object_timeout_without_timer() {
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+
+    let res = client
+        .wait_for_object_timeout(None, &[ServiceUuid::new_v4()], Duration::from_secs(1))
+        .await;
+    assert_eq!(res, Err(Error::NotSupported));
+
+    client.join().await;
+    broker.join().await;
+}
+
+#[cfg(feature = "introspection")]
+#[tokio::test]
+async fn query_introspection_is_cached() {
+    use crate::core::TypeId;
+
+    let mut broker = TestBroker::new();
+    let mut client1 = broker.add_client().await;
+    let mut client2 = broker.add_client().await;
+    let mut client3 = broker.add_client().await;
+
+    client1.register_introspection::<Vec<u32>>().unwrap();
+    client1.submit_introspection().unwrap();
+    client1.sync_broker().await.unwrap();
+
+    let type_id = TypeId::compute::<Vec<u32>>();
+    let (res1, res2) = futures_util::future::join(
+        client2.query_introspection(type_id),
+        client2.query_introspection(type_id),
+    )
+    .await;
+    let introspection = res1.unwrap().unwrap();
+    assert_eq!(introspection.type_id(), type_id);
+    assert_eq!(res2.unwrap().unwrap().type_id(), type_id);
+
+    client1.join().await;
+    client3.sync_broker().await.unwrap();
+    assert!(client3
+        .query_introspection(type_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    let cached = client2.query_introspection(type_id).await.unwrap().unwrap();
+    assert_eq!(cached.type_id(), type_id);
+
+    client2.join().await;
+    client3.join().await;
+    broker.join().await;
+}
+
+#[cfg(feature = "introspection")]
+#[tokio::test]
+async fn query_introspection_caches_bundle() {
+    use crate::core::TypeId;
+
+    let mut broker = TestBroker::new();
+    let mut client1 = broker.add_client().await;
+    let mut client2 = broker.add_client().await;
+    let mut client3 = broker.add_client().await;
+
+    client1
+        .register_introspection::<Option<Vec<u32>>>()
+        .unwrap();
+    client1.submit_introspection().unwrap();
+    client1.sync_broker().await.unwrap();
+
+    // Make the broker fetch all introspections from client1.
+    let type_id1 = TypeId::compute::<Option<Vec<u32>>>();
+    let type_id2 = TypeId::compute::<Vec<u32>>();
+    let type_id3 = TypeId::compute::<u32>();
+    for type_id in [type_id1, type_id2, type_id3] {
+        assert!(client2
+            .query_introspection(type_id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    // The reply to client3 bundles all referenced types.
+    let introspection = client3.query_introspection(type_id1).await.unwrap();
+    assert_eq!(introspection.unwrap().type_id(), type_id1);
+
+    client1.join().await;
+    client3.sync_broker().await.unwrap();
+
+    let cached = client3
+        .query_introspection(type_id2)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(cached.type_id(), type_id2);
+    let cached = client3
+        .query_introspection(type_id3)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(cached.type_id(), type_id3);
+
+    client2.join().await;
+    client3.join().await;
+    broker.join().await;
+}
+
+#[cfg(feature = "introspection")]
+#[tokio::test]
+async fn persistent_introspection_cache() {
+    use crate::core::introspection::Introspection;
+    use crate::core::TypeId;
+    use aldrin_test::aldrin::IntrospectionCache;
+
+    let type_id = TypeId::compute::<Vec<u32>>();
+
+    let cache = IntrospectionCache::new();
+    cache.insert(&Introspection::new::<Vec<u32>>()).unwrap();
+    let cache = IntrospectionCache::from_bytes(&cache.to_bytes()).unwrap();
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get(type_id).unwrap().type_id(), type_id);
+
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (t1, t2) = channel::unbounded();
+    let client = tokio::spawn(
+        Client::builder(t1)
+            .with_introspection_cache(cache.clone())
+            .connect(),
+    );
+    let conn = handle.connect(t2).await.unwrap();
+    tokio::spawn(conn.run());
+    let client = client.await.unwrap().unwrap();
+    let client_handle = client.handle().clone();
+    let client_join = tokio::spawn(client.run());
+
+    // The broker doesn't know the type, so it must be answered from the cache.
+    let introspection = client_handle.query_introspection(type_id).await.unwrap();
+    assert_eq!(introspection.unwrap().type_id(), type_id);
+
+    // Unavailable types are not cached.
+    let type_id = TypeId::compute::<u8>();
+    assert!(client_handle
+        .query_introspection(type_id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(!cache.contains(type_id));
+
+    client_handle.shutdown();
+    client_join.await.unwrap().unwrap();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn reliable_events_are_replayed_after_reconnect() {
+    async fn connect(broker: &TestBroker, session: Uuid) -> (Handle, JoinHandle<()>) {
+        let (t1, t2) = channel::unbounded();
+        let client = tokio::spawn(Client::builder(t1).with_session(session).connect());
+        let conn = broker.handle().clone().connect(t2).await.unwrap();
+        let conn = tokio::spawn(conn.run());
+        let client = client.await.unwrap().unwrap();
+        let handle = client.handle().clone();
+
+        // The connection is joined as well, so that the broker has suspended the session
+        // afterwards.
+        let join = tokio::spawn(async move {
+            client.run().await.unwrap();
+            conn.await.unwrap().unwrap();
+        });
+
+        (handle, join)
+    }
+
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+    let session = Uuid::new_v4();
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let (handle, join) = connect(&broker, session).await;
+    let mut proxy = Proxy::new(&handle, svc.id()).await.unwrap();
+    proxy.subscribe(0).await.unwrap();
+
+    svc.emit_reliable(0, &1).unwrap();
+    svc.emit_reliable(0, &2).unwrap();
+
+    // Only the first event is acknowledged.
+    let event = proxy.next_event_acked().await.unwrap();
+    assert!(event.is_reliable());
+    assert_eq!(event.deserialize(), Ok(1));
+    let event = proxy.next_event().await.unwrap();
+    assert_eq!(event.deserialize(), Ok(2));
+
+    // The subscription outlives the connection, because the proxy doesn't unsubscribe.
+    handle.shutdown();
+    join.await.unwrap();
+
+    // Events emitted while the session is suspended are buffered as well.
+    svc.emit_reliable(0, &3).unwrap();
+    client.sync_broker().await.unwrap();
+
+    let (handle, join) = connect(&broker, session).await;
+    let mut proxy = Proxy::new(&handle, svc.id()).await.unwrap();
+    proxy.subscribe(0).await.unwrap();
+
+    let event = proxy.next_event_acked().await.unwrap();
+    assert_eq!(event.deserialize(), Ok(2));
+    let event = proxy.next_event_acked().await.unwrap();
+    assert_eq!(event.deserialize(), Ok(3));
+
+    handle.shutdown();
+    join.await.unwrap();
+    client.join().await;
+    broker.join().await;
+}
+
+#[tokio::test]
+async fn bus_time() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    assert_eq!(client.bus_time().await, Err(Error::InvalidService));
+
+    let clock = ClockService::new(&client).await.unwrap();
+    tokio::spawn(clock.run());
+
+    let first = client.bus_time().await.unwrap();
+    time::sleep(Duration::from_millis(10)).await;
+    let second = client.bus_time().await.unwrap();
+
+    assert!(second.bus() + first.rtt() >= first.bus() + Duration::from_millis(10));
+    assert!(second.bus() <= first.to_bus(second.local()) + first.rtt() + second.rtt());
+}
@@ -0,0 +1,318 @@
+//! Adapters for the `tower` ecosystem
+//!
+//! This module allows using [`tower::Service`](Service) middleware (retry, rate-limiting,
+//! timeouts, etc.) with Aldrin function calls, both on the client and on the server side.
+//!
+//! On the client side, [`ProxyService`] and [`FunctionService`] wrap a
+//! [`low_level::Proxy`](crate::low_level::Proxy). Generated proxies can be converted with their
+//! `into_inner` method:
+//!
+//! ```ignore
+//! let calculator = ProxyService::new(calculator.into_inner());
+//! let add = calculator.function::<CalculatorAddArgs, i32, CalculatorAddError>(1);
+//! let add = ServiceBuilder::new().timeout(Duration::from_secs(1)).service(add);
+//! ```
+//!
+//! On the server side, [`serve`] drives a [`low_level::Service`](crate::low_level::Service) with
+//! a `tower::Service` that handles [`CallRequest`s](CallRequest).
+
+#[cfg(test)]
+mod test;
+
+use crate::core::{
+    Deserialize, DeserializeError, Serialize, SerializeError, SerializedValue, SerializedValueSlice,
+};
+use crate::error::Error;
+use crate::low_level::{self, Promise, Proxy};
+use crate::reply::Reply;
+use futures_core::stream::FusedStream;
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// Request to call a function with serialized arguments.
+#[derive(Debug, Clone)]
+pub struct CallRequest {
+    function: u32,
+    args: SerializedValue,
+}
+
+impl CallRequest {
+    /// Creates a new request by serializing `args`.
+    pub fn new<T: Serialize + ?Sized>(function: u32, args: &T) -> Result<Self, SerializeError> {
+        SerializedValue::serialize(args).map(|args| Self::with_serialized_args(function, args))
+    }
+
+    /// Creates a new request from already serialized arguments.
+    pub fn with_serialized_args(function: u32, args: SerializedValue) -> Self {
+        Self { function, args }
+    }
+
+    /// Returns the id of the function.
+    pub fn function(&self) -> u32 {
+        self.function
+    }
+
+    /// Returns the serialized arguments.
+    pub fn args(&self) -> &SerializedValueSlice {
+        &self.args
+    }
+
+    /// Deserializes the arguments.
+    pub fn deserialize<T: Deserialize>(&self) -> Result<T, DeserializeError> {
+        self.args.deserialize()
+    }
+
+    /// Converts the request into its serialized arguments.
+    pub fn into_args(self) -> SerializedValue {
+        self.args
+    }
+}
+
+/// `tower::Service` for calling arbitrary functions of a service.
+///
+/// The service's responses are the raw serialized results of the calls. Use [`FunctionService`]
+/// for typed calls of a single function.
+///
+/// `ProxyService` is cheap to clone and is always ready.
+#[derive(Debug, Clone)]
+pub struct ProxyService {
+    proxy: Arc<Proxy>,
+}
+
+impl ProxyService {
+    /// Creates a new `ProxyService` from a [`Proxy`].
+    pub fn new(proxy: Proxy) -> Self {
+        Self {
+            proxy: Arc::new(proxy),
+        }
+    }
+
+    /// Returns a reference to the [`Proxy`].
+    pub fn proxy(&self) -> &Proxy {
+        &self.proxy
+    }
+
+    /// Creates a typed [`FunctionService`] for a single function.
+    pub fn function<Args, T, E>(&self, function: u32) -> FunctionService<Args, T, E> {
+        FunctionService {
+            proxy: self.proxy.clone(),
+            function,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl From<Proxy> for ProxyService {
+    fn from(proxy: Proxy) -> Self {
+        Self::new(proxy)
+    }
+}
+
+impl Service<CallRequest> for ProxyService {
+    type Response = Result<SerializedValue, SerializedValue>;
+    type Error = Error;
+    type Future = low_level::Reply;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: CallRequest) -> low_level::Reply {
+        self.proxy.call(req.function, &req.args)
+    }
+}
+
+/// Typed `tower::Service` for calling a single function of a service.
+///
+/// Requests are the function's arguments and responses are the function's result. A
+/// `FunctionService` can be created with [`ProxyService::function`].
+///
+/// `FunctionService` is cheap to clone and is always ready.
+pub struct FunctionService<Args, T, E> {
+    proxy: Arc<Proxy>,
+    function: u32,
+    phantom: PhantomData<fn(Args) -> (T, E)>,
+}
+
+impl<Args, T, E> FunctionService<Args, T, E> {
+    /// Returns a reference to the [`Proxy`].
+    pub fn proxy(&self) -> &Proxy {
+        &self.proxy
+    }
+
+    /// Returns the id of the function.
+    pub fn function(&self) -> u32 {
+        self.function
+    }
+}
+
+impl<Args, T, E> Clone for FunctionService<Args, T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            proxy: self.proxy.clone(),
+            function: self.function,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Args, T, E> fmt::Debug for FunctionService<Args, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FunctionService")
+            .field("proxy", &self.proxy)
+            .field("function", &self.function)
+            .finish()
+    }
+}
+
+impl<Args, T, E> Service<Args> for FunctionService<Args, T, E>
+where
+    Args: Serialize,
+    T: Deserialize,
+    E: Deserialize,
+{
+    type Response = Result<T, E>;
+    type Error = Error;
+    type Future = Reply<T, E>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, args: Args) -> Reply<T, E> {
+        self.proxy.call(self.function, &args).cast()
+    }
+}
+
+/// Serves calls of a [`low_level::Service`] with a `tower::Service`.
+///
+/// Every call is turned into a [`CallRequest`] and passed to `handler`. The handler's response is
+/// then used to reply to the call. If the handler fails with an error, then the call is aborted.
+/// Calls are handled concurrently, limited only by the readiness of `handler`.
+///
+/// When the caller aborts a call, the future returned by the handler is dropped.
+///
+/// The returned future completes once the service's calls end (because it has been destroyed or the
+/// client has shut down) and all pending calls have been handled, or when `handler` fails in
+/// [`poll_ready`](Service::poll_ready).
+pub fn serve<S>(service: low_level::Service, handler: S) -> Serve<S>
+where
+    S: Service<CallRequest, Response = Result<SerializedValue, SerializedValue>>,
+{
+    Serve {
+        service,
+        handler,
+        pending: FuturesUnordered::new(),
+    }
+}
+
+/// Future returned by [`serve`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Serve<S: Service<CallRequest>> {
+    service: low_level::Service,
+    handler: S,
+    pending: FuturesUnordered<PendingCall<S::Future>>,
+}
+
+// No field is structurally pinned. The handler is only ever accessed through `&mut`.
+impl<S: Service<CallRequest>> Unpin for Serve<S> {}
+
+impl<S> Future for Serve<S>
+where
+    S: Service<CallRequest, Response = Result<SerializedValue, SerializedValue>>,
+{
+    type Output = Result<(), S::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        while !this.service.is_terminated() {
+            match this.handler.poll_ready(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => break,
+            }
+
+            let Poll::Ready(call) = this.service.poll_next_call(cx) else {
+                break;
+            };
+
+            let Some(call) = call else {
+                break;
+            };
+
+            let function = call.id();
+            let (args, promise) = call.into_args_and_promise();
+            let fut = this
+                .handler
+                .call(CallRequest::with_serialized_args(function, args));
+
+            this.pending.push(PendingCall {
+                fut: Box::pin(fut),
+                promise: Some(promise),
+            });
+        }
+
+        while let Poll::Ready(Some(())) = this.pending.poll_next_unpin(cx) {}
+
+        if this.service.is_terminated() && this.pending.is_empty() {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<S> fmt::Debug for Serve<S>
+where
+    S: Service<CallRequest> + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Serve")
+            .field("service", &self.service)
+            .field("handler", &self.handler)
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+struct PendingCall<F> {
+    fut: Pin<Box<F>>,
+    promise: Option<Promise>,
+}
+
+impl<F, E> Future for PendingCall<F>
+where
+    F: Future<Output = Result<Result<SerializedValue, SerializedValue>, E>>,
+{
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let promise = self.promise.as_mut().unwrap();
+
+        if promise.poll_aborted(cx).is_ready() {
+            return Poll::Ready(());
+        }
+
+        let res = match self.fut.as_mut().poll(cx) {
+            Poll::Ready(res) => res,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let promise = self.promise.take().unwrap();
+
+        let _ = match res {
+            Ok(Ok(value)) => promise.ok(&value),
+            Ok(Err(value)) => promise.err(&value),
+            Err(_) => promise.abort(),
+        };
+
+        Poll::Ready(())
+    }
+}
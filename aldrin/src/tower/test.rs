@@ -0,0 +1,113 @@
+use aldrin_test::aldrin::core::{ObjectUuid, SerializedValue, ServiceUuid};
+use aldrin_test::aldrin::low_level::{Proxy, ServiceInfo};
+use aldrin_test::aldrin::tower::{self, CallRequest, ProxyService};
+use aldrin_test::aldrin::Error;
+use aldrin_test::tokio::TestBroker;
+use futures_channel::oneshot;
+use futures_util::future::{self, BoxFuture, FutureExt};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time;
+use tower_service::Service;
+
+#[derive(Debug)]
+struct Adder {
+    dropped: Option<oneshot::Sender<()>>,
+}
+
+impl Service<CallRequest> for Adder {
+    type Response = Result<SerializedValue, SerializedValue>;
+    type Error = ();
+    type Future = BoxFuture<'static, Result<Self::Response, ()>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), ()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: CallRequest) -> Self::Future {
+        match req.function() {
+            1 => {
+                let (a, b) = req.deserialize::<(u32, u32)>().unwrap();
+
+                let res = match a.checked_add(b) {
+                    Some(sum) => Ok(SerializedValue::serialize(&sum).unwrap()),
+                    None => Err(SerializedValue::serialize(&()).unwrap()),
+                };
+
+                future::ready(Ok(res)).boxed()
+            }
+
+            2 => future::ready(Err(())).boxed(),
+
+            _ => {
+                let dropped = self.dropped.take().unwrap();
+
+                async move {
+                    let _dropped = dropped;
+                    future::pending().await
+                }
+                .boxed()
+            }
+        }
+    }
+}
+
+async fn call<S, Req>(svc: &mut S, req: Req) -> Result<S::Response, S::Error>
+where
+    S: Service<Req>,
+{
+    future::poll_fn(|cx| svc.poll_ready(cx)).await?;
+    svc.call(req).await
+}
+
+#[tokio::test]
+async fn proxy_and_serve() {
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+    let id = svc.id();
+
+    let (dropped_send, dropped_recv) = oneshot::channel();
+    let serve = tokio::spawn(tower::serve(
+        svc,
+        Adder {
+            dropped: Some(dropped_send),
+        },
+    ));
+
+    let mut proxy = ProxyService::new(Proxy::new(&client, id).await.unwrap());
+    let mut add = proxy.function::<(u32, u32), u32, ()>(1);
+
+    let res = call(&mut add, (1, 2)).await.unwrap();
+    assert_eq!(res, Ok(3));
+
+    let res = call(&mut add, (u32::MAX, 1)).await.unwrap();
+    assert_eq!(res, Err(()));
+
+    let req = CallRequest::new(1, &(2u32, 3u32)).unwrap();
+    let res = call(&mut proxy, req).await.unwrap().unwrap();
+    assert_eq!(res.deserialize::<u32>().unwrap(), 5);
+
+    let req = CallRequest::new(2, &()).unwrap();
+    let res = call(&mut proxy, req).await;
+    assert_eq!(res.map_err(Error::without_context), Err(Error::CallAborted));
+
+    let reply = proxy.call(CallRequest::new(3, &()).unwrap());
+    let _ = time::timeout(Duration::from_millis(50), reply).await;
+    time::timeout(Duration::from_secs(1), dropped_recv)
+        .await
+        .unwrap()
+        .unwrap_err();
+
+    client.join().await;
+    time::timeout(Duration::from_secs(1), serve)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+}
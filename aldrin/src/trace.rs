@@ -0,0 +1,116 @@
+//! Distributed tracing with the `tracing` crate
+//!
+//! Function calls and events can carry a [`TraceContext`], which identifies the trace and the span
+//! on the sending side. This module connects these contexts with `tracing` spans.
+//!
+//! [`TraceContextLayer`] must be added to a `tracing_subscriber::Registry`. It assigns a
+//! [`TraceContext`] to every span. Root spans start a new trace, while all other spans continue
+//! the trace of their parent.
+//!
+//! With the layer in place, [`low_level::Proxy::call`](crate::low_level::Proxy::call) and
+//! [`low_level::Service::emit`](crate::low_level::Service::emit) automatically attach the context
+//! of the current span. This includes all calls and events of generated code.
+//!
+//! On the receiving side, [`set_remote_parent`] lets a span continue the trace of an incoming call
+//! or event:
+//!
+//! ```ignore
+//! let span = tracing::info_span!("call", function = call.id());
+//! if let Some(trace_context) = call.trace_context() {
+//!     aldrin::trace::set_remote_parent(&span, trace_context);
+//! }
+//! ```
+
+#[cfg(test)]
+mod test;
+
+use crate::core::TraceContext;
+use tracing::span::{Attributes, Id};
+use tracing::{Span, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Registry;
+use uuid::Uuid;
+
+/// `tracing` layer, that assigns a [`TraceContext`] to every span.
+///
+/// The layer must be used together with a `tracing_subscriber::Registry`.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TraceContextLayer {
+    _priv: (),
+}
+
+impl TraceContextLayer {
+    /// Creates a new `TraceContextLayer`.
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<S> Layer<S> for TraceContextLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &Attributes, id: &Id, ctx: Context<S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let span_id = new_span_id();
+
+        let trace_context = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<TraceContext>().copied())
+            .map(|parent| parent.child(span_id))
+            .unwrap_or_else(|| TraceContext::new(Uuid::new_v4().as_u128(), span_id, true));
+
+        span.extensions_mut().insert(trace_context);
+    }
+}
+
+/// Returns the trace context of the current span.
+///
+/// `None` is returned if there is no current span or if [`TraceContextLayer`] is not in use.
+pub fn current() -> Option<TraceContext> {
+    context(&Span::current())
+}
+
+/// Returns the trace context of a span.
+///
+/// `None` is returned if `span` is disabled or if [`TraceContextLayer`] is not in use.
+pub fn context(span: &Span) -> Option<TraceContext> {
+    span.with_subscriber(|(id, dispatch)| {
+        let registry = dispatch.downcast_ref::<Registry>()?;
+        let span = registry.span(id)?;
+        let trace_context = span.extensions().get::<TraceContext>().copied();
+        trace_context
+    })
+    .flatten()
+}
+
+/// Makes a span continue a remote trace.
+///
+/// This is typically used with the trace context of an incoming call or event. Spans, which are
+/// created inside `span`, will then also be part of the remote trace.
+///
+/// This function has no effect if `span` is disabled or if [`TraceContextLayer`] is not in use.
+pub fn set_remote_parent(span: &Span, parent: TraceContext) {
+    span.with_subscriber(|(id, dispatch)| {
+        let Some(span) = dispatch
+            .downcast_ref::<Registry>()
+            .and_then(|registry| registry.span(id))
+        else {
+            return;
+        };
+
+        let mut extensions = span.extensions_mut();
+
+        if let Some(trace_context) = extensions.get_mut::<TraceContext>() {
+            *trace_context = parent.child(trace_context.span_id);
+        }
+    });
+}
+
+fn new_span_id() -> u64 {
+    Uuid::new_v4().as_u64_pair().0
+}
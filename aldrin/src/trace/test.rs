@@ -0,0 +1,109 @@
+use aldrin_test::aldrin::core::{ObjectUuid, ServiceUuid, TraceContext};
+use aldrin_test::aldrin::low_level::ServiceInfo;
+use aldrin_test::aldrin::trace::{self, TraceContextLayer};
+use aldrin_test::tokio::TestBroker;
+use tracing::subscriber::{self, DefaultGuard};
+use tracing::{info_span, Span};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Registry;
+
+fn set_subscriber() -> DefaultGuard {
+    subscriber::set_default(Registry::default().with(TraceContextLayer::new()))
+}
+
+#[test]
+fn assign_trace_contexts() {
+    let _guard = set_subscriber();
+
+    assert_eq!(trace::current(), None);
+
+    let root = info_span!("root");
+    let root_ctx = trace::context(&root).unwrap();
+    assert!(root_ctx.sampled);
+
+    let child = info_span!(parent: &root, "child");
+    let child_ctx = trace::context(&child).unwrap();
+    assert_eq!(child_ctx.trace_id, root_ctx.trace_id);
+    assert_ne!(child_ctx.span_id, root_ctx.span_id);
+
+    let _entered = child.enter();
+    assert_eq!(trace::current(), Some(child_ctx));
+
+    let other = info_span!(parent: None, "other");
+    let other_ctx = trace::context(&other).unwrap();
+    assert_ne!(other_ctx.trace_id, root_ctx.trace_id);
+}
+
+#[test]
+fn remote_parent() {
+    let _guard = set_subscriber();
+
+    let remote = TraceContext::new(1, 2, false);
+
+    let span = info_span!("span");
+    trace::set_remote_parent(&span, remote);
+
+    let ctx = trace::context(&span).unwrap();
+    assert_eq!(ctx.trace_id, 1);
+    assert_ne!(ctx.span_id, 2);
+    assert!(!ctx.sampled);
+
+    let child = info_span!(parent: &span, "child");
+    assert_eq!(trace::context(&child).unwrap().trace_id, 1);
+}
+
+#[test]
+fn no_layer() {
+    let _guard = subscriber::set_default(Registry::default());
+
+    let span = info_span!("span");
+    assert_eq!(trace::context(&span), None);
+
+    trace::set_remote_parent(&span, TraceContext::new(1, 2, true));
+    assert_eq!(trace::context(&span), None);
+
+    assert_eq!(trace::context(&Span::none()), None);
+}
+
+#[tokio::test]
+async fn propagate_calls_and_events() {
+    let _guard = set_subscriber();
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let mut proxy = client.create_proxy(svc.id()).await.unwrap();
+    proxy.subscribe(0).await.unwrap();
+
+    let span = info_span!("span");
+    let ctx = trace::context(&span).unwrap();
+
+    let reply = {
+        let _entered = span.enter();
+        proxy.call(0, &())
+    };
+
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.trace_context(), Some(ctx));
+    call.into_promise().ok(&()).unwrap();
+    reply.await.unwrap().unwrap();
+
+    {
+        let _entered = span.enter();
+        svc.emit(0, &()).unwrap();
+    }
+
+    let event = proxy.next_event().await.unwrap();
+    assert_eq!(event.trace_context(), Some(ctx));
+
+    // Without a current span, nothing is attached.
+    svc.emit(0, &()).unwrap();
+    let event = proxy.next_event().await.unwrap();
+    assert_eq!(event.trace_context(), None);
+}
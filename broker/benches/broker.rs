@@ -0,0 +1,238 @@
+//! Broker benchmarks.
+//!
+//! All benchmarks run an in-process broker with clients connected through in-memory channels and
+//! are repeated for different numbers of connections. Each benchmark is run several times and the
+//! median, minimum and maximum are reported.
+//!
+//! Run with `cargo bench -p aldrin-broker`. An optional argument selects only benchmarks, whose
+//! name contains it, e.g. `cargo bench -p aldrin-broker -- events`.
+
+use aldrin::core::{Bytes, ObjectUuid, ServiceUuid};
+use aldrin::low_level::{Proxy, ServiceInfo};
+use aldrin_test::tokio::{TestBroker, TestClient};
+use futures_channel::mpsc;
+use futures_util::stream::StreamExt;
+use std::env;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::runtime::{Builder, Runtime};
+
+const CONNECTIONS: &[usize] = &[1, 4, 16];
+const RUNS: usize = 5;
+const DURATION: Duration = Duration::from_secs(1);
+const ITEM_SIZE: usize = 1024;
+const CHANNEL_CAPACITY: u32 = 16;
+
+fn main() {
+    // Cargo passes `--bench` to the harness.
+    let filter = env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let filter = filter.as_deref().unwrap_or("");
+
+    let rt = Builder::new_multi_thread().enable_all().build().unwrap();
+
+    for &conns in CONNECTIONS {
+        bench(&rt, filter, &format!("calls/{conns}"), "calls/s", || {
+            calls(conns)
+        });
+    }
+
+    for &conns in CONNECTIONS {
+        bench(&rt, filter, &format!("events/{conns}"), "us", || {
+            events(conns)
+        });
+    }
+
+    for &conns in CONNECTIONS {
+        bench(&rt, filter, &format!("channels/{conns}"), "MiB/s", || {
+            channels(conns)
+        });
+    }
+}
+
+fn bench<F, Fut>(rt: &Runtime, filter: &str, name: &str, unit: &str, mut f: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = f64>,
+{
+    if !name.contains(filter) {
+        return;
+    }
+
+    let mut samples: Vec<f64> = (0..RUNS).map(|_| rt.block_on(f())).collect();
+    samples.sort_by(f64::total_cmp);
+
+    println!(
+        "{name:<16} {:>12.1} {unit:<8} (min {:.1}, max {:.1})",
+        samples[RUNS / 2],
+        samples[0],
+        samples[RUNS - 1],
+    );
+}
+
+async fn connect(broker: &mut TestBroker, num: usize) -> Vec<TestClient> {
+    let mut clients = Vec::with_capacity(num);
+
+    for _ in 0..num {
+        clients.push(broker.add_client().await);
+    }
+
+    clients
+}
+
+/// Measures the throughput of function calls made by `conns` connections to a single service.
+async fn calls(conns: usize) -> f64 {
+    let mut broker = TestBroker::new();
+    let server = broker.add_client().await;
+    let callers = connect(&mut broker, conns).await;
+
+    let obj = server.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+    let svc_id = svc.id();
+
+    tokio::spawn(async move {
+        while let Some(call) = svc.next_call().await {
+            let _ = call.into_promise().done();
+        }
+    });
+
+    let start = Instant::now();
+    let deadline = start + DURATION;
+
+    let tasks: Vec<_> = callers
+        .iter()
+        .map(|caller| {
+            let caller = caller.handle().clone();
+
+            tokio::spawn(async move {
+                let proxy = Proxy::new(&caller, svc_id).await.unwrap();
+                let mut num = 0u64;
+
+                while Instant::now() < deadline {
+                    proxy.call(0, &()).await.unwrap().unwrap();
+                    num += 1;
+                }
+
+                num
+            })
+        })
+        .collect();
+
+    let mut num = 0;
+    for task in tasks {
+        num += task.await.unwrap();
+    }
+
+    let elapsed = start.elapsed();
+    broker.join().await;
+
+    num as f64 / elapsed.as_secs_f64()
+}
+
+/// Measures the median latency until an event has reached all of `conns` subscribers.
+async fn events(conns: usize) -> f64 {
+    let mut broker = TestBroker::new();
+    let emitter = broker.add_client().await;
+    let subscribers = connect(&mut broker, conns).await;
+
+    let obj = emitter.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let (send, mut recv) = mpsc::unbounded();
+
+    for subscriber in &subscribers {
+        let mut proxy = Proxy::new(subscriber, svc.id()).await.unwrap();
+        proxy.subscribe(0).await.unwrap();
+
+        let send = send.clone();
+        tokio::spawn(async move {
+            while proxy.next_event().await.is_some() {
+                if send.unbounded_send(Instant::now()).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    let deadline = Instant::now() + DURATION;
+    let mut latencies = Vec::new();
+
+    while Instant::now() < deadline {
+        let start = Instant::now();
+        svc.emit(0, &()).unwrap();
+
+        let mut end = start;
+        for _ in 0..conns {
+            end = end.max(recv.next().await.unwrap());
+        }
+
+        latencies.push(end - start);
+    }
+
+    broker.join().await;
+
+    latencies.sort();
+    latencies[latencies.len() / 2].as_secs_f64() * 1e6
+}
+
+/// Measures the total bandwidth of `conns` channels, each streamed between two connections.
+async fn channels(conns: usize) -> f64 {
+    let mut broker = TestBroker::new();
+    let senders = connect(&mut broker, conns).await;
+    let receivers = connect(&mut broker, conns).await;
+
+    let start = Instant::now();
+    let deadline = start + DURATION;
+    let mut tasks = Vec::with_capacity(conns);
+
+    for (sender, receiver) in senders.iter().zip(&receivers) {
+        let (sender, unclaimed) = sender
+            .create_channel::<Bytes>()
+            .claim_sender()
+            .await
+            .unwrap();
+
+        let mut receiver = unclaimed
+            .unbind()
+            .claim(receiver.handle().clone(), CHANNEL_CAPACITY)
+            .await
+            .unwrap();
+
+        let mut sender = sender.establish().await.unwrap();
+
+        tokio::spawn(async move {
+            let item = Bytes(vec![0; ITEM_SIZE]);
+
+            while Instant::now() < deadline {
+                sender.send_ref(&item).await.unwrap();
+            }
+
+            sender.close().await.unwrap();
+        });
+
+        tasks.push(tokio::spawn(async move {
+            let mut len = 0;
+
+            while let Some(item) = receiver.next_item().await.unwrap() {
+                len += item.0.len();
+            }
+
+            len
+        }));
+    }
+
+    let mut len = 0;
+    for task in tasks {
+        len += task.await.unwrap();
+    }
+
+    let elapsed = start.elapsed();
+    broker.join().await;
+
+    len as f64 / elapsed.as_secs_f64() / (1024.0 * 1024.0)
+}
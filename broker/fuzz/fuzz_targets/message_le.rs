@@ -23,9 +23,10 @@ use aldrin_broker::core::message::{
     UnsubscribeEvent, UnsubscribeService,
 };
 use aldrin_broker::core::{
-    BusEvent, BusListenerCookie, BusListenerFilter, BusListenerScope, BusListenerServiceFilter,
-    ChannelCookie, ChannelEnd, ChannelEndWithCapacity, ObjectCookie, ObjectId, ObjectUuid,
-    SerializedValue, ServiceCookie, ServiceId, ServiceInfo, ServiceUuid, TypeId,
+    BusEvent, BusListenerCookie, BusListenerFilter, BusListenerLayoutFilter, BusListenerScope,
+    BusListenerServiceFilter, ChannelCookie, ChannelEnd, Compression, ChannelEndWithCapacity, LexicalId,
+    ObjectCookie, ObjectId, ObjectUuid, SerializedValue, ServiceCookie, ServiceId, ServiceInfo,
+    ServiceUuid, TypeId,
 };
 use arbitrary::Arbitrary;
 use std::collections::HashSet;
@@ -1256,6 +1257,8 @@ pub enum BusListenerFilterLe {
     SpecificObjectAnyService(UuidLe),
     AnyObjectSpecificService(UuidLe),
     SpecificObjectSpecificService(UuidLe, UuidLe),
+    AnyObjectSpecificLayout(UuidLe),
+    SpecificObjectSpecificLayout(UuidLe, UuidLe),
 }
 
 impl BusListenerFilterLe {
@@ -1279,6 +1282,17 @@ impl BusListenerFilterLe {
                     ServiceUuid(service.get(ctx)),
                 )
             }
+
+            Self::AnyObjectSpecificLayout(layout) => {
+                BusListenerFilter::any_object_layout(LexicalId(layout.get(ctx)))
+            }
+
+            Self::SpecificObjectSpecificLayout(object, layout) => {
+                BusListenerFilter::specific_object_layout(
+                    ObjectUuid(object.get(ctx)),
+                    LexicalId(layout.get(ctx)),
+                )
+            }
         }
     }
 }
@@ -1310,6 +1324,19 @@ impl UpdateContext for BusListenerFilter {
                 ctx.add_uuid(object.0);
                 ctx.add_uuid(service.0);
             }
+
+            BusListenerFilter::Layout(BusListenerLayoutFilter {
+                object: None,
+                layout,
+            }) => ctx.add_uuid(layout.0),
+
+            BusListenerFilter::Layout(BusListenerLayoutFilter {
+                object: Some(object),
+                layout,
+            }) => {
+                ctx.add_uuid(object.0);
+                ctx.add_uuid(layout.0);
+            }
         }
     }
 }
@@ -1773,6 +1800,8 @@ impl UpdateContext for RegisterIntrospection {
 pub struct QueryIntrospectionLe {
     pub serial: SerialLe,
     pub type_id: UuidLe,
+    pub known: Vec<UuidLe>,
+    pub compression: Option<Compression>,
 }
 
 impl QueryIntrospectionLe {
@@ -1780,6 +1809,8 @@ impl QueryIntrospectionLe {
         QueryIntrospection {
             serial: self.serial.get(ctx),
             type_id: TypeId(self.type_id.get(ctx)),
+            known: self.known.iter().map(|k| TypeId(k.get(ctx))).collect(),
+            compression: self.compression,
         }
     }
 }
@@ -1788,6 +1819,10 @@ impl UpdateContext for QueryIntrospection {
     fn update_context(&self, ctx: &mut Context) {
         ctx.add_serial(self.serial);
         ctx.add_uuid(self.type_id.0);
+
+        for type_id in &self.known {
+            ctx.add_uuid(type_id.0);
+        }
     }
 }
 
@@ -1838,6 +1873,7 @@ pub enum ServiceInfoLe {
         version: u8,
         type_id: Option<UuidLe>,
         subscribe_all: Option<bool>,
+        lexical_id: Option<UuidLe>,
     },
 
     Invalid,
@@ -1850,6 +1886,7 @@ impl ServiceInfoLe {
                 version,
                 type_id,
                 subscribe_all,
+                lexical_id,
             } => {
                 let mut info = ServiceInfo::new(*version as u32);
 
@@ -1861,6 +1898,10 @@ impl ServiceInfoLe {
                     info = info.set_subscribe_all(*subscribe_all);
                 }
 
+                if let Some(lexical_id) = lexical_id {
+                    info = info.set_lexical_id(LexicalId(lexical_id.get(ctx)));
+                }
+
                 SerializedValue::serialize(&info).unwrap()
             }
 
@@ -1874,6 +1915,10 @@ impl UpdateContext for ServiceInfo {
         if let Some(type_id) = self.type_id() {
             ctx.add_uuid(type_id.0);
         }
+
+        if let Some(lexical_id) = self.lexical_id() {
+            ctx.add_uuid(lexical_id.0);
+        }
     }
 }
 
@@ -0,0 +1,133 @@
+use crate::core::{AuthRejection, SerializedValue, SerializedValueSlice};
+use std::collections::HashSet;
+
+/// Authenticates clients during the handshake.
+///
+/// Authenticators are used with
+/// [`PendingConnection::authenticate`](crate::PendingConnection::authenticate). They first receive
+/// the credentials, that the client sent along with its initial connection message. From there,
+/// they can either accept or reject the client right away, or send a challenge, to which the
+/// client must respond. Challenges can be repeated as often as necessary.
+///
+/// Clients provide credentials and respond to challenges with an `AuthProvider` (see the `aldrin`
+/// crate).
+pub trait Authenticator {
+    /// Verifies the client's initial credentials.
+    ///
+    /// `credentials` is `None` if the client didn't provide any.
+    fn authenticate(&mut self, credentials: Option<&SerializedValueSlice>) -> AuthStep;
+
+    /// Verifies the client's response to the previous challenge.
+    ///
+    /// The default implementation rejects the client with
+    /// [`AuthRejection::InvalidCredentials`].
+    fn verify_response(&mut self, response: &SerializedValueSlice) -> AuthStep {
+        let _ = response;
+        AuthStep::Reject(AuthRejection::InvalidCredentials)
+    }
+}
+
+impl<A: Authenticator + ?Sized> Authenticator for &mut A {
+    fn authenticate(&mut self, credentials: Option<&SerializedValueSlice>) -> AuthStep {
+        (**self).authenticate(credentials)
+    }
+
+    fn verify_response(&mut self, response: &SerializedValueSlice) -> AuthStep {
+        (**self).verify_response(response)
+    }
+}
+
+/// Outcome of a single step of the authentication of a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthStep {
+    /// Accept the client.
+    Accept,
+
+    /// Send a challenge to the client.
+    ///
+    /// The client's response will be passed to [`Authenticator::verify_response`].
+    Challenge(SerializedValue),
+
+    /// Reject the client.
+    Reject(AuthRejection),
+}
+
+/// Authenticator, that accepts clients with one of a set of tokens.
+///
+/// Tokens are plain strings, which clients send as their credentials. This corresponds to the
+/// `TokenAuth` provider of the `aldrin` crate.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin_broker::TokenAuthenticator;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let mut handle = aldrin_test::tokio::TestBroker::new();
+/// # let (t, t2) = aldrin_broker::core::channel::unbounded();
+/// # let client = aldrin::Client::builder(t2).with_auth(aldrin::TokenAuth::new("secret"));
+/// # let client_join = tokio::spawn(client.connect());
+/// let authenticator = TokenAuthenticator::new(["secret"]);
+///
+/// let conn = handle
+///     .begin_connect(t)
+///     .await?
+///     .authenticate(&authenticator)
+///     .await?
+///     .accept(None)
+///     .await?;
+///
+/// tokio::spawn(conn.run());
+/// # let client = client_join.await??;
+/// # tokio::spawn(client.run());
+/// # handle.join().await;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TokenAuthenticator {
+    tokens: HashSet<String>,
+}
+
+impl TokenAuthenticator {
+    /// Creates a new `TokenAuthenticator` from a set of valid tokens.
+    pub fn new<I>(tokens: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            tokens: tokens.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Adds a valid token.
+    pub fn add_token(&mut self, token: impl Into<String>) {
+        self.tokens.insert(token.into());
+    }
+
+    /// Removes a token.
+    pub fn remove_token(&mut self, token: &str) -> bool {
+        self.tokens.remove(token)
+    }
+}
+
+impl Authenticator for TokenAuthenticator {
+    fn authenticate(&mut self, credentials: Option<&SerializedValueSlice>) -> AuthStep {
+        (&*self).authenticate(credentials)
+    }
+}
+
+impl Authenticator for &TokenAuthenticator {
+    fn authenticate(&mut self, credentials: Option<&SerializedValueSlice>) -> AuthStep {
+        let Some(credentials) = credentials else {
+            return AuthStep::Reject(AuthRejection::MissingCredentials);
+        };
+
+        match credentials.deserialize::<String>() {
+            Ok(token) if self.tokens.contains(&token) => AuthStep::Accept,
+            _ => AuthStep::Reject(AuthRejection::InvalidCredentials),
+        }
+    }
+}
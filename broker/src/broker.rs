@@ -1,77 +1,139 @@
+mod broadcast;
 mod channel;
+mod conn_sender;
 mod conn_state;
+mod connection_info;
+#[cfg(feature = "consistency-check")]
+mod consistency;
+mod dead_letter;
 mod error;
 mod handle;
+#[cfg(feature = "history")]
+mod history;
+mod namespace;
 mod object;
+mod reliable;
+mod routing;
 mod service;
+mod standby;
 mod state;
 #[cfg(feature = "statistics")]
 mod statistics;
 #[cfg(test)]
 mod test;
 
-use crate::bus_listener::BusListener;
-use crate::conn::ConnectionEvent;
+use crate::bus_listener::{BusListener, BusListenerMatch};
+use crate::conn::{ConnectionEvent, ConnectionHandle};
 use crate::conn_id::ConnectionId;
+#[cfg(feature = "tracing")]
+use crate::core::message::MessageOps;
 use crate::core::message::{
-    AbortFunctionCall, AddBusListenerFilter, AddChannelCapacity, BusListenerCurrentFinished,
-    CallFunction, CallFunctionReply, CallFunctionResult, ChannelEndClaimed, ChannelEndClosed,
-    ClaimChannelEnd, ClaimChannelEndReply, ClaimChannelEndResult, ClearBusListenerFilters,
-    CloseChannelEnd, CloseChannelEndReply, CloseChannelEndResult, CreateBusListener,
-    CreateBusListenerReply, CreateChannel, CreateChannelReply, CreateObject, CreateObjectReply,
-    CreateObjectResult, CreateService, CreateService2, CreateServiceReply, CreateServiceResult,
-    DestroyBusListener, DestroyBusListenerReply, DestroyBusListenerResult, DestroyObject,
-    DestroyObjectReply, DestroyObjectResult, DestroyService, DestroyServiceReply,
-    DestroyServiceResult, EmitBusEvent, EmitEvent, ItemReceived, Message, QueryIntrospection,
-    QueryIntrospectionReply, QueryIntrospectionResult, QueryServiceInfo, QueryServiceInfoReply,
-    QueryServiceInfoResult, QueryServiceVersion, QueryServiceVersionReply,
-    QueryServiceVersionResult, RegisterIntrospection, RemoveBusListenerFilter, SendItem,
-    ServiceDestroyed, Shutdown, StartBusListener, StartBusListenerReply, StartBusListenerResult,
-    StopBusListener, StopBusListenerReply, StopBusListenerResult, SubscribeAllEvents,
-    SubscribeAllEventsReply, SubscribeAllEventsResult, SubscribeEvent, SubscribeEventReply,
-    SubscribeEventResult, SubscribeService, SubscribeServiceReply, SubscribeServiceResult, Sync,
-    SyncReply, UnsubscribeAllEvents, UnsubscribeAllEventsReply, UnsubscribeAllEventsResult,
-    UnsubscribeEvent, UnsubscribeService,
+    AbortFunctionCall, AckEvent, AddBusListenerFilter, AddChannelCapacity,
+    BusListenerCurrentFinished, CallFunction, CallFunctionReply, CallFunctionResult,
+    ChannelEndClaimed, ChannelEndClosed, ClaimChannelEnd, ClaimChannelEndReply,
+    ClaimChannelEndResult, ClearBusListenerFilters, CloseChannelEnd, CloseChannelEndReply,
+    CloseChannelEndResult, CreateBusListener, CreateBusListenerReply, CreateChannel,
+    CreateChannelReply, CreateObject, CreateObjectReply, CreateObjectResult, CreateService,
+    CreateService2, CreateServiceReply, CreateServiceResult, CreateServices, CreateServicesReply,
+    CreateServicesResult, DestroyBusListener, DestroyBusListenerReply, DestroyBusListenerResult,
+    DestroyObject, DestroyObjectReply, DestroyObjectResult, DestroyService, DestroyServiceReply,
+    DestroyServiceResult, DestroyServices, DestroyServicesReply, DestroyServicesResult,
+    EmitBusEvent, EmitEvent, EmitEventAcked, EmitEventAckedReply, EmitReliableEvent,
+    EmitRetainedEvent, ItemReceived, Message, QueryIntrospection, QueryIntrospectionReply,
+    QueryIntrospectionResult, QueryServiceInfo, QueryServiceInfoReply, QueryServiceInfoResult,
+    QueryServiceVersion, QueryServiceVersionReply, QueryServiceVersionResult,
+    RegisterIntrospection, RemoveBusListenerFilter, SendItem, ServiceDestroyed, Shutdown,
+    StartBusListener, StartBusListenerReply, StartBusListenerResult, StopBusListener,
+    StopBusListenerReply, StopBusListenerResult, SubscribeAllEvents, SubscribeAllEventsReply,
+    SubscribeAllEventsResult, SubscribeEvent, SubscribeEventReply, SubscribeEventResult,
+    SubscribeService, SubscribeServiceReply, SubscribeServiceResult, Sync, SyncReply,
+    UnsubscribeAllEvents, UnsubscribeAllEventsReply, UnsubscribeAllEventsResult, UnsubscribeEvent,
+    UnsubscribeService,
 };
-#[cfg(feature = "introspection")]
-use crate::core::TypeId;
 use crate::core::{
-    BusEvent, BusListenerCookie, BusListenerScope, ChannelCookie, ChannelEnd,
-    ChannelEndWithCapacity, ObjectCookie, ObjectId, ObjectUuid, ProtocolVersion, ServiceCookie,
-    ServiceId, ServiceInfo, ServiceUuid,
+    BusEvent, BusListenerCookie, BusListenerFilter, BusListenerScope, CallPriority, ChannelCookie,
+    ChannelEnd, ChannelEndWithCapacity, LexicalId, ObjectCookie, ObjectId, ObjectUuid,
+    ProtocolVersion, ServiceCookie, ServiceId, ServiceInfo, ServiceUuid,
 };
 #[cfg(feature = "introspection")]
+use crate::core::{Compression, TypeId};
+#[cfg(feature = "introspection")]
 use crate::introspection_database::{
-    IntrospectionDatabase, IntrospectionQueryResult, RemoveConnResult,
+    IntrospectionDatabase, IntrospectionDump, IntrospectionQueryResult, RemoveConnResult,
 };
 use crate::serial_map::SerialMap;
+use broadcast::BroadcastChannel;
 use channel::{AddCapacityError, Channel, SendItemError};
 use conn_state::ConnectionState;
 use futures_channel::mpsc::{channel, Receiver};
+use futures_util::future;
 use futures_util::stream::StreamExt;
+use futures_util::task;
 use object::Object;
+use reliable::ReliableEvents;
 use service::Service;
 use state::State;
 use std::collections::hash_map::{Entry, HashMap};
 use std::collections::HashSet;
-
+use std::mem;
+use std::task::{Context, Poll};
+
+pub use connection_info::ConnectionInfo;
+#[cfg(feature = "consistency-check")]
+pub(crate) use consistency::ConsistencyChecker;
+#[cfg(feature = "consistency-check")]
+pub use consistency::{ConsistencyReport, ConsistencyReports, Inconsistency};
+pub(crate) use dead_letter::DeadLetterSink;
+pub use dead_letter::{DeadLetter, DeadLetterKind, DeadLetters};
 pub use error::BrokerShutdown;
 pub use handle::{BrokerHandle, PendingConnection};
+#[cfg(feature = "history")]
+pub(crate) use history::History;
+#[cfg(feature = "history")]
+pub use history::{HistoryEntry, HistoryEvent};
+pub(crate) use namespace::NamespaceScope;
+pub(crate) use routing::{EventDelivery, RoutingTable};
+pub(crate) use standby::Standbys;
+pub use standby::{Failover, FailoverEvent, FailoverEvents};
 #[cfg(feature = "statistics")]
 pub use statistics::BrokerStatistics;
 
 const FIFO_SIZE: usize = 32;
 
 macro_rules! send {
-    ($self:expr, $conn:expr, $msg:expr $(,)?) => {{
-        let res = $conn.send($msg.into());
+    ($self:expr, $conn:expr, $msg:expr $(,)?) => {
+        send!($self, $conn, $msg, CallPriority::Normal)
+    };
+
+    ($self:expr, $conn:expr, $msg:expr, $priority:expr $(,)?) => {{
+        let res = $conn.send_with_priority($msg.into(), $priority);
 
         #[cfg(feature = "statistics")]
         {
-            $self.statistics.messages_sent = $self.statistics.messages_sent.saturating_add(1);
+            if res.dropped().is_some() {
+                $self.statistics.events_dropped = $self.statistics.events_dropped.saturating_add(1);
+            } else {
+                $self.statistics.messages_sent = $self.statistics.messages_sent.saturating_add(1);
+            }
+
+            if res.slow_consumer() {
+                $self.statistics.slow_consumers = $self.statistics.slow_consumers.saturating_add(1);
+            }
         }
 
-        res
+        if let Some((service, event)) = res.dropped() {
+            #[cfg(feature = "statistics")]
+            {
+                $self.statistics.dead_letters = $self.statistics.dead_letters.saturating_add(1);
+            }
+
+            DeadLetterSink::send_to(
+                &mut $self.dead_letters,
+                DeadLetter::new(DeadLetterKind::EventDropped, service, event),
+            );
+        }
+
+        res.into_result()
     }};
 }
 
@@ -81,6 +143,12 @@ macro_rules! send {
 /// [`new`](Broker::new), it must be turned into future with [`run`](Broker::run) and then polled to
 /// completion.
 ///
+/// Alternatively, the `Broker` can be driven manually with [`poll`](Broker::poll) or
+/// [`run_until_idle`](Broker::run_until_idle). This allows embedding it into custom event loops
+/// and deterministic simulations, that don't use an async runtime for the broker itself. Messages
+/// can also be handed to the `Broker` synchronously with
+/// [`inject_message`](Broker::inject_message).
+///
 /// [`BrokerHandle`s](BrokerHandle) are used to interact with a running `Broker` and can be acquired
 /// with the [`handle`](Broker::handle) method. Through a `BrokerHandle`, you can add new
 /// connections to the `Broker` as well as shut it down again.
@@ -88,6 +156,17 @@ macro_rules! send {
 /// The `Broker` will automatically shut down, when there are no active connections and the last
 /// `BrokerHandle` has been dropped.
 ///
+/// Events are usually not routed by the `Broker` itself. Instead, each
+/// [`Connection`](crate::Connection) sends events directly to all subscribers, which allows event
+/// throughput to scale with the number of threads the connections run on. Events still arrive in
+/// the same order, relative to all other messages, as if they were routed by the `Broker`.
+///
+/// This applies only to plain events and only while the `Broker` has no earlier messages of the
+/// emitting connection left to handle. The first event of each connection always goes through the
+/// `Broker`, such that connections, which never emit events, don't pay for keeping track of this. All other messages, in particular function calls and their
+/// replies, are always handled by the `Broker` task. It thus remains the limit for their
+/// throughput, regardless of the number of threads.
+///
 /// # Examples
 ///
 /// ```
@@ -119,6 +198,9 @@ macro_rules! send {
 pub struct Broker {
     recv: Receiver<ConnectionEvent>,
     handle: Option<BrokerHandle>,
+    state: State,
+    finished: bool,
+    routes: RoutingTable,
     conns: HashMap<ConnectionId, ConnectionState>,
     obj_uuids: HashMap<ObjectCookie, ObjectUuid>,
     objs: HashMap<ObjectUuid, Object>,
@@ -126,9 +208,19 @@ pub struct Broker {
     svcs: HashMap<(ObjectUuid, ServiceUuid), Service>,
     function_calls: SerialMap<PendingFunctionCall>,
     channels: HashMap<ChannelCookie, Channel>,
+    broadcasts: HashMap<ChannelCookie, BroadcastChannel>,
     bus_listeners: HashMap<BusListenerCookie, BusListener>,
+    dead_letters: Option<DeadLetterSink>,
+    standbys: Standbys,
+    reliable: ReliableEvents,
     #[cfg(feature = "statistics")]
     statistics: BrokerStatistics,
+    #[cfg(feature = "history")]
+    history: History,
+    #[cfg(feature = "consistency-check")]
+    events: u64,
+    #[cfg(feature = "consistency-check")]
+    consistency_checker: Option<ConsistencyChecker>,
     #[cfg(feature = "introspection")]
     introspection: IntrospectionDatabase,
     #[cfg(feature = "introspection")]
@@ -142,10 +234,14 @@ impl Broker {
     /// polled to completion.
     pub fn new() -> Self {
         let (send, recv) = channel(FIFO_SIZE);
+        let routes = RoutingTable::new();
 
         Self {
             recv,
-            handle: Some(BrokerHandle::new(send)),
+            handle: Some(BrokerHandle::new(send, routes.clone())),
+            state: State::new(),
+            finished: false,
+            routes,
             conns: HashMap::new(),
             obj_uuids: HashMap::new(),
             objs: HashMap::new(),
@@ -153,9 +249,19 @@ impl Broker {
             svcs: HashMap::new(),
             function_calls: SerialMap::new(),
             channels: HashMap::new(),
+            broadcasts: HashMap::new(),
             bus_listeners: HashMap::new(),
+            dead_letters: None,
+            standbys: Standbys::new(),
+            reliable: ReliableEvents::new(),
             #[cfg(feature = "statistics")]
             statistics: BrokerStatistics::new(),
+            #[cfg(feature = "history")]
+            history: History::new(),
+            #[cfg(feature = "consistency-check")]
+            events: 0,
+            #[cfg(feature = "consistency-check")]
+            consistency_checker: None,
             #[cfg(feature = "introspection")]
             introspection: IntrospectionDatabase::new(),
             #[cfg(feature = "introspection")]
@@ -174,6 +280,24 @@ impl Broker {
         self.handle.as_ref().unwrap()
     }
 
+    /// Pre-loads types into the introspection database.
+    ///
+    /// Pre-loaded types can be queried by clients, even if no client currently registers them. They
+    /// are also part of all [dumps](BrokerHandle::dump_introspection) of the introspection
+    /// database.
+    ///
+    /// See [`IntrospectionDump`] for an example.
+    #[cfg(feature = "introspection")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "introspection")))]
+    pub fn load_introspection(&mut self, dump: &IntrospectionDump) {
+        self.introspection.load(dump);
+
+        #[cfg(feature = "statistics")]
+        {
+            self.statistics.num_introspections = self.introspection.len();
+        }
+    }
+
     /// Runs the broker.
     ///
     /// This is a long running method, that will only return when explicitly shut down or when there
@@ -181,39 +305,175 @@ impl Broker {
     ///
     /// Make sure to [acquire](Broker::handle) a `BrokerHandle` before running the `Broker`.
     pub async fn run(mut self) {
-        self.handle.take().unwrap();
+        future::poll_fn(|cx| self.poll(cx)).await
+    }
 
-        let mut state = State::new();
+    /// Polls the broker.
+    ///
+    /// This handles all events, that are currently pending, and is the building block of
+    /// [`run`](Self::run). It returns `Poll::Ready(())` when the broker has shut down, under the
+    /// same conditions as `run`. Otherwise, `cx`'s waker is notified when new events arrive.
+    ///
+    /// Make sure to [acquire](Broker::handle) a `BrokerHandle` before polling the `Broker` for the
+    /// first time.
+    pub fn poll(&mut self, cx: &mut Context) -> Poll<()> {
+        if self.finished {
+            return Poll::Ready(());
+        }
+
+        if self.handle.take().is_some() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("broker started");
+        }
 
         loop {
-            if state.shutdown_now() || (state.shutdown_idle() && self.conns.is_empty()) {
+            if self.state.shutdown_now() || (self.state.shutdown_idle() && self.conns.is_empty()) {
                 break;
             }
 
-            let ev = match self.recv.next().await {
-                Some(ev) => ev,
-                None => return,
-            };
+            match self.recv.poll_next_unpin(cx) {
+                Poll::Ready(Some(ev)) => self.step(ev),
+
+                Poll::Ready(None) => {
+                    self.finished = true;
+                    return Poll::Ready(());
+                }
 
-            self.handle_event(&mut state, ev);
-            self.process_loop_result(&mut state);
+                Poll::Pending => return Poll::Pending,
+            }
         }
 
-        debug_assert!(!state.has_work_left());
+        self.finished = true;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!("broker shut down");
+
+        debug_assert!(!self.state.has_work_left());
         debug_assert!(self.conns.is_empty());
         debug_assert!(self.obj_uuids.is_empty());
         debug_assert!(self.objs.is_empty());
         debug_assert!(self.svc_uuids.is_empty());
         debug_assert!(self.svcs.is_empty());
         debug_assert!(self.function_calls.is_empty());
+
+        Poll::Ready(())
+    }
+
+    /// Handles all pending events without waiting for new ones.
+    ///
+    /// This is like [`poll`](Self::poll), but doesn't require a [`Context`]. It is useful for
+    /// driving the broker from custom event loops, e.g. by calling it after each step of all
+    /// connections and clients.
+    ///
+    /// Returns `true` when the broker has shut down.
+    pub fn run_until_idle(&mut self) -> bool {
+        let mut cx = Context::from_waker(task::noop_waker_ref());
+        self.poll(&mut cx).is_ready()
+    }
+
+    /// Indicates whether the broker has shut down.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Shuts down the broker.
+    ///
+    /// This is like [`BrokerHandle::shutdown`], but takes effect immediately. All connections are
+    /// told to shut down and the next call to [`poll`](Self::poll) returns `Poll::Ready(())`.
+    pub fn shutdown(&mut self) {
+        self.step(ConnectionEvent::ShutdownBroker);
+    }
+
+    /// Shuts down the broker when the last client disconnects.
+    ///
+    /// This is like [`BrokerHandle::shutdown_idle`], but takes effect immediately.
+    pub fn shutdown_idle(&mut self) {
+        self.step(ConnectionEvent::ShutdownIdleBroker);
+    }
+
+    /// Shuts down a specific connection.
+    ///
+    /// This is like [`BrokerHandle::shutdown_connection`], but takes effect immediately.
+    pub fn shutdown_connection(&mut self, conn: &ConnectionHandle) {
+        self.step(ConnectionEvent::ShutdownConnection(conn.id().clone()));
+    }
+
+    /// Handles a message as if it had been received from a specific connection.
+    ///
+    /// This is the synchronous counterpart of a [`Connection`](crate::Connection) forwarding a
+    /// message from its client. The message is handled immediately, including all consequences,
+    /// and any replies are queued on the connection. Messages, that are still pending in the
+    /// broker's channel, are not handled first. Call [`run_until_idle`](Self::run_until_idle)
+    /// beforehand, if the order relative to them matters.
+    ///
+    /// Returns `false` and discards the message, if the connection doesn't exist (anymore).
+    pub fn inject_message(&mut self, conn: &ConnectionHandle, msg: impl Into<Message>) -> bool {
+        if !self.conns.contains_key(conn.id()) {
+            return false;
+        }
+
+        self.step(ConnectionEvent::Message(conn.id().clone(), msg.into()));
+        true
+    }
+
+    fn step(&mut self, ev: ConnectionEvent) {
+        let tracked_conn_id = match ev {
+            ConnectionEvent::TrackedMessage(ref id, _) => Some(id.clone()),
+            _ => None,
+        };
+
+        let mut state = mem::replace(&mut self.state, State::new());
+        self.handle_event(&mut state, ev);
+        self.process_loop_result(&mut state);
+        self.state = state;
+
+        // Connections route events directly only when the broker has finished handling all of
+        // their earlier messages, including all consequences.
+        if let Some(conn) = tracked_conn_id.and_then(|id| self.conns.get(&id)) {
+            conn.queue().end_message();
+        }
+
+        #[cfg(feature = "consistency-check")]
+        self.periodic_consistency_check();
     }
 
     fn handle_event(&mut self, state: &mut State, ev: ConnectionEvent) {
         match ev {
-            ConnectionEvent::NewConnection(id, protocol_version, sender) => {
-                let dup = self
-                    .conns
-                    .insert(id, ConnectionState::new(protocol_version, sender));
+            ConnectionEvent::NewConnection(
+                id,
+                protocol_version,
+                sender,
+                priority_sender,
+                queue,
+                limit,
+                name,
+                metadata,
+                session,
+                namespace,
+            ) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    conn = id.number(),
+                    version = %protocol_version,
+                    name = name.as_deref(),
+                    "connection added",
+                );
+
+                self.reliable.add_conn(&id, session);
+
+                let dup = self.conns.insert(
+                    id,
+                    ConnectionState::new(
+                        protocol_version,
+                        sender,
+                        priority_sender,
+                        queue,
+                        limit,
+                        name,
+                        metadata,
+                        namespace,
+                    ),
+                );
                 debug_assert!(dup.is_none());
 
                 #[cfg(feature = "statistics")]
@@ -227,8 +487,15 @@ impl Broker {
                 state.push_remove_conn(id, false);
             }
 
-            ConnectionEvent::Message(id, msg) => {
+            ConnectionEvent::Message(id, msg) | ConnectionEvent::TrackedMessage(id, msg) => {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::trace_span!("message", conn = id.number(), kind = ?msg.kind())
+                    .entered();
+
                 if self.handle_message(state, &id, msg).is_err() {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!("invalid message received");
+
                     state.push_remove_conn(id, false);
                 }
 
@@ -239,6 +506,27 @@ impl Broker {
                 }
             }
 
+            ConnectionEvent::EventUndeliverable(id, svc_cookie, event) => {
+                if self.conns.contains_key(&id) {
+                    state.push_remove_conn(id, false);
+                    self.dead_letter(DeadLetter::new(
+                        DeadLetterKind::EventUndeliverable,
+                        svc_cookie,
+                        event,
+                    ));
+                }
+            }
+
+            ConnectionEvent::EventsDropped(svc_cookie, event, num) => {
+                for _ in 0..num {
+                    self.dead_letter(DeadLetter::new(
+                        DeadLetterKind::EventDropped,
+                        svc_cookie,
+                        event,
+                    ));
+                }
+            }
+
             ConnectionEvent::ShutdownBroker => {
                 state.push_remove_conns(self.conns.keys().cloned().map(|id| (id, true)));
                 state.set_shutdown_now();
@@ -252,10 +540,141 @@ impl Broker {
                 state.push_remove_conn(id, true);
             }
 
+            ConnectionEvent::ShutdownConnectionByNumber(number, sender) => {
+                let id = self.conns.keys().find(|id| id.number() == number).cloned();
+                let found = id.is_some();
+
+                if let Some(id) = id {
+                    state.push_remove_conn(id, true);
+                }
+
+                let _ = sender.send(found);
+            }
+
+            ConnectionEvent::SetDeadLetterSink(sink) => {
+                self.dead_letters = Some(sink);
+            }
+
+            ConnectionEvent::RegisterStandby(object, services, id) => {
+                if self.conns.contains_key(&id) {
+                    self.standbys.register(object, services, id);
+                }
+            }
+
+            ConnectionEvent::UnregisterStandby(object) => {
+                self.standbys.unregister(object);
+            }
+
+            ConnectionEvent::SetReliableEventBufferLimit(buffer_limit) => {
+                self.reliable.set_buffer_limit(buffer_limit);
+            }
+
+            ConnectionEvent::SetReconnectWindow(reconnect_window) => {
+                self.reliable.set_reconnect_window(reconnect_window);
+            }
+
+            ConnectionEvent::AddFailoverListener(listener) => {
+                self.standbys.add_listener(listener);
+            }
+
+            ConnectionEvent::ListConnections(sender) => {
+                let _ = sender.send(self.list_connections());
+            }
+
             #[cfg(feature = "statistics")]
             ConnectionEvent::TakeStatistics(sender) => {
+                self.routes.take_statistics(&mut self.statistics);
                 let _ = sender.send(self.statistics.take());
             }
+
+            #[cfg(feature = "history")]
+            ConnectionEvent::QueryHistory(sender) => {
+                let _ = sender.send(self.history.entries());
+            }
+
+            #[cfg(feature = "history")]
+            ConnectionEvent::SetHistoryCapacity(capacity) => {
+                self.history.set_capacity(capacity);
+            }
+
+            #[cfg(feature = "consistency-check")]
+            ConnectionEvent::CheckConsistency(sender) => {
+                let _ = sender.send(self.check_consistency());
+            }
+
+            #[cfg(feature = "consistency-check")]
+            ConnectionEvent::SetConsistencyChecker(checker) => {
+                self.consistency_checker = Some(checker);
+            }
+
+            #[cfg(feature = "introspection")]
+            ConnectionEvent::DumpIntrospection(sender) => {
+                let _ = sender.send(self.introspection.dump());
+            }
+
+            #[cfg(feature = "introspection")]
+            ConnectionEvent::AddIntrospectionConflictListener(listener) => {
+                self.introspection.add_conflict_listener(listener);
+            }
+        }
+    }
+
+    /// Returns information about all current connections.
+    ///
+    /// This is like [`BrokerHandle::list_connections`], but returns immediately.
+    pub fn list_connections(&self) -> Vec<ConnectionInfo> {
+        let mut conns = self
+            .conns
+            .iter()
+            .map(|(id, conn)| {
+                let mut objects = conn
+                    .objects()
+                    .map(|cookie| ObjectId::new(self.obj_uuids[&cookie], cookie))
+                    .collect::<Vec<_>>();
+
+                objects.sort_unstable();
+
+                let num_services = objects
+                    .iter()
+                    .map(|obj| self.objs[&obj.uuid].services().count())
+                    .sum();
+
+                ConnectionInfo::new(
+                    id.number(),
+                    conn.name().map(ToOwned::to_owned),
+                    conn.metadata().clone(),
+                    conn.namespace().map(ToString::to_string),
+                    objects,
+                    conn.protocol_version(),
+                    num_services,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        conns.sort_unstable_by_key(ConnectionInfo::number);
+        conns
+    }
+
+    #[cfg(feature = "consistency-check")]
+    fn periodic_consistency_check(&mut self) {
+        self.events = self.events.saturating_add(1);
+
+        let Some(ref mut checker) = self.consistency_checker else {
+            return;
+        };
+
+        if !checker.tick() {
+            return;
+        }
+
+        let report = self.check_consistency();
+        if report.is_consistent() {
+            return;
+        }
+
+        let checker = self.consistency_checker.as_mut().unwrap();
+        if !checker.send(report) {
+            self.consistency_checker = None;
         }
     }
 
@@ -268,7 +687,7 @@ impl Broker {
             // objects and services, which have previously been declared destroyed.
 
             if let Some((conn_id, send_shutdown)) = state.pop_remove_conn() {
-                self.shutdown_connection(state, &conn_id, send_shutdown);
+                self.remove_connection(state, &conn_id, send_shutdown);
                 continue;
             }
 
@@ -332,23 +751,27 @@ impl Broker {
                 continue;
             }
 
-            if let Some(object) = state.pop_create_object() {
-                self.emit_bus_event(state, BusEvent::ObjectCreated(object));
+            if let Some((object, namespace)) = state.pop_create_object() {
+                let event = BusEvent::ObjectCreated(object);
+                self.emit_bus_event(state, event, None, namespace.as_deref());
                 continue;
             }
 
-            if let Some(service) = state.pop_create_service() {
-                self.emit_bus_event(state, BusEvent::ServiceCreated(service));
+            if let Some((service, layout, namespace)) = state.pop_create_service() {
+                let event = BusEvent::ServiceCreated(service);
+                self.emit_bus_event(state, event, layout, namespace.as_deref());
                 continue;
             }
 
-            if let Some(service) = state.pop_destroy_service() {
-                self.emit_bus_event(state, BusEvent::ServiceDestroyed(service));
+            if let Some((service, layout, namespace)) = state.pop_destroy_service() {
+                let event = BusEvent::ServiceDestroyed(service);
+                self.emit_bus_event(state, event, layout, namespace.as_deref());
                 continue;
             }
 
-            if let Some(object) = state.pop_destroy_object() {
-                self.emit_bus_event(state, BusEvent::ObjectDestroyed(object));
+            if let Some((object, namespace)) = state.pop_destroy_object() {
+                let event = BusEvent::ObjectDestroyed(object);
+                self.emit_bus_event(state, event, None, namespace.as_deref());
                 continue;
             }
 
@@ -362,11 +785,14 @@ impl Broker {
         }
     }
 
-    fn shutdown_connection(&mut self, state: &mut State, id: &ConnectionId, send_shutdown: bool) {
+    fn remove_connection(&mut self, state: &mut State, id: &ConnectionId, send_shutdown: bool) {
         let Some(conn) = self.conns.remove(id) else {
             return;
         };
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(conn = id.number(), send_shutdown, "connection removed");
+
         if send_shutdown {
             // Ignore errors here.
             let _ = send!(self, conn, Shutdown);
@@ -376,6 +802,12 @@ impl Broker {
             self.remove_bus_listener(bus_listener_cookie);
         }
 
+        self.standbys.connection_removed(
+            id,
+            conn.objects()
+                .filter_map(|obj_cookie| self.obj_uuids.get(&obj_cookie).copied()),
+        );
+
         for obj_cookie in conn.objects() {
             self.remove_object(state, obj_cookie);
         }
@@ -404,6 +836,8 @@ impl Broker {
             state.push_abort_function_call(callee_serial, callee_id.clone());
         }
 
+        self.reliable.remove_conn(id);
+
         #[cfg(feature = "statistics")]
         {
             self.statistics.num_connections = self.statistics.num_connections.saturating_sub(1);
@@ -428,7 +862,9 @@ impl Broker {
             Message::CallFunctionReply(req) => self.call_function_reply(state, id, req),
             Message::SubscribeEvent(req) => self.subscribe_event(id, req)?,
             Message::UnsubscribeEvent(req) => self.unsubscribe_event(state, id, req),
-            Message::EmitEvent(req) => self.emit_event(state, id, req),
+            Message::EmitEvent(req) => {
+                self.emit_event(state, id, req);
+            }
             Message::QueryServiceVersion(req) => self.query_service_version(id, req)?,
             Message::CreateChannel(req) => self.create_channel(id, req)?,
             Message::CloseChannelEnd(req) => self.close_channel_end(state, id, req)?,
@@ -438,7 +874,7 @@ impl Broker {
             Message::Sync(req) => self.sync(id, req)?,
             Message::CreateBusListener(req) => self.create_bus_listener(id, req)?,
             Message::DestroyBusListener(req) => self.destroy_bus_listener(id, req)?,
-            Message::AddBusListenerFilter(req) => self.add_bus_listener_filter(id, req),
+            Message::AddBusListenerFilter(req) => self.add_bus_listener_filter(id, req)?,
             Message::RemoveBusListenerFilter(req) => self.remove_bus_listener_filter(id, req),
             Message::ClearBusListenerFilters(req) => self.clear_bus_listener_filters(id, req),
             Message::StartBusListener(req) => self.start_bus_listener(id, req)?,
@@ -455,6 +891,12 @@ impl Broker {
             Message::UnsubscribeService(req) => self.unsubscribe_service(id, req)?,
             Message::SubscribeAllEvents(req) => self.subscribe_all_events(id, req)?,
             Message::UnsubscribeAllEvents(req) => self.unsubscribe_all_events(id, req)?,
+            Message::EmitRetainedEvent(req) => self.emit_retained_event(state, id, req)?,
+            Message::CreateServices(req) => self.create_services(state, id, req)?,
+            Message::DestroyServices(req) => self.destroy_services(state, id, req)?,
+            Message::EmitEventAcked(req) => self.emit_event_acked(state, id, req)?,
+            Message::EmitReliableEvent(req) => self.emit_reliable_event(state, id, req)?,
+            Message::AckEvent(req) => self.ack_event(id, req)?,
 
             Message::Connect(_)
             | Message::ConnectReply(_)
@@ -483,7 +925,13 @@ impl Broker {
             | Message::QueryServiceInfoReply(_)
             | Message::SubscribeServiceReply(_)
             | Message::SubscribeAllEventsReply(_)
-            | Message::UnsubscribeAllEventsReply(_) => return Err(()),
+            | Message::UnsubscribeAllEventsReply(_)
+            | Message::AuthChallenge(_)
+            | Message::AuthResponse(_)
+            | Message::CreateServicesReply(_)
+            | Message::DestroyServicesReply(_)
+            | Message::EmitEventAckedReply(_)
+            | Message::ReliableEventReceived(_) => return Err(()),
 
             Message::Shutdown(Shutdown) => unreachable!(), // Handled by connection.
         }
@@ -501,6 +949,17 @@ impl Broker {
             return Ok(());
         };
 
+        if !self.standbys.may_create_object(req.uuid, id) {
+            return send!(
+                self,
+                conn,
+                CreateObjectReply {
+                    serial: req.serial,
+                    result: CreateObjectResult::DuplicateObject,
+                },
+            );
+        }
+
         match self.objs.entry(req.uuid) {
             Entry::Occupied(_) => send!(
                 self,
@@ -525,9 +984,15 @@ impl Broker {
 
                 let dup = self.obj_uuids.insert(cookie, req.uuid);
                 debug_assert!(dup.is_none());
-                entry.insert(Object::new(id.clone()));
+                let namespace = conn.namespace().cloned();
+                entry.insert(Object::new(id.clone(), namespace.clone()));
                 conn.add_object(cookie);
-                state.push_create_object(ObjectId::new(req.uuid, cookie));
+                state.push_create_object(ObjectId::new(req.uuid, cookie), namespace);
+                self.standbys.object_created(req.uuid, id);
+
+                #[cfg(feature = "history")]
+                self.history
+                    .object_created(ObjectId::new(req.uuid, cookie), id);
 
                 #[cfg(feature = "statistics")]
                 {
@@ -647,9 +1112,19 @@ impl Broker {
             .svc_uuids
             .insert(svc_cookie, (object_id, req.uuid, info));
         debug_assert!(dup.is_none());
-        entry.insert(Service::new());
+        let namespace = obj.namespace().cloned();
+        entry.insert(Service::new(namespace.clone()));
         obj.add_service(svc_cookie);
-        state.push_create_service(ServiceId::new(object_id, req.uuid, svc_cookie));
+        self.routes.add_service(svc_cookie, id.clone());
+        state.push_create_service(
+            ServiceId::new(object_id, req.uuid, svc_cookie),
+            None,
+            namespace,
+        );
+
+        #[cfg(feature = "history")]
+        self.history
+            .service_created(ServiceId::new(object_id, req.uuid, svc_cookie), id);
 
         #[cfg(feature = "statistics")]
         {
@@ -715,17 +1190,59 @@ impl Broker {
             return Ok(());
         };
 
-        let Some(&(obj_id, svc_uuid, _)) = self.svc_uuids.get(&req.service_cookie) else {
-            return send!(
+        if req.priority != CallPriority::Normal && conn.protocol_version() < ProtocolVersion::V1_19
+        {
+            return Err(());
+        }
+
+        let Some(&(obj_id, svc_uuid, info)) = self.svc_uuids.get(&req.service_cookie) else {
+            let res = send!(
                 self,
                 conn,
                 CallFunctionReply {
                     serial: req.serial,
                     result: CallFunctionResult::InvalidService,
                 },
+                req.priority,
             );
+
+            self.dead_letter(DeadLetter::new(
+                DeadLetterKind::CallInvalidService,
+                req.service_cookie,
+                req.function,
+            ));
+
+            return res;
         };
 
+        let svc = self
+            .svcs
+            .get(&(obj_id.uuid, svc_uuid))
+            .expect("inconsistent state");
+
+        if info
+            .max_calls()
+            .is_some_and(|max_calls| svc.num_function_calls() >= max_calls as usize)
+        {
+            let res = send!(
+                self,
+                conn,
+                CallFunctionReply {
+                    serial: req.serial,
+                    result: overloaded_result(conn.protocol_version()),
+                },
+                req.priority,
+            );
+
+            self.dead_letter(DeadLetter::new(
+                DeadLetterKind::CallOverloaded,
+                req.service_cookie,
+                req.function,
+            ));
+
+            return res;
+        }
+
         let callee_id = self
             .objs
             .get(&obj_id.uuid)
@@ -737,6 +1254,8 @@ impl Broker {
             caller_conn_id: id.clone(),
             callee_obj: obj_id.uuid,
             callee_svc: svc_uuid,
+            function: req.function,
+            priority: req.priority,
             aborted: false,
         });
 
@@ -752,6 +1271,13 @@ impl Broker {
             .expect("inconsistent state")
             .add_function_call(serial);
 
+        let (trace_context, priority) = if callee_conn.protocol_version() >= ProtocolVersion::V1_19
+        {
+            (req.trace_context, req.priority)
+        } else {
+            (None, CallPriority::Normal)
+        };
+
         let res = send!(
             self,
             callee_conn,
@@ -760,7 +1286,21 @@ impl Broker {
                 service_cookie: req.service_cookie,
                 function: req.function,
                 value: req.value,
+                trace_context,
+                priority,
             },
+            req.priority,
+        );
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            caller = id.number(),
+            caller_serial = req.serial,
+            callee = callee_id.number(),
+            callee_serial = serial,
+            service = %req.service_cookie,
+            function = req.function,
+            "function call forwarded",
         );
 
         if res.is_err() {
@@ -794,6 +1334,16 @@ impl Broker {
 
         svc.remove_function_call(req.serial);
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            caller = call.caller_conn_id.number(),
+            caller_serial = call.caller_serial,
+            callee = id.number(),
+            callee_serial = req.serial,
+            aborted = call.aborted,
+            "function call finished",
+        );
+
         if call.aborted {
             return;
         }
@@ -804,13 +1354,19 @@ impl Broker {
 
         conn.remove_call(call.caller_serial);
 
+        let result = match req.result {
+            CallFunctionResult::Overloaded => overloaded_result(conn.protocol_version()),
+            result => result,
+        };
+
         let res = send!(
             self,
             conn,
             CallFunctionReply {
                 serial: call.caller_serial,
-                result: req.result,
+                result,
             },
+            call.priority,
         );
 
         if res.is_err() {
@@ -848,11 +1404,49 @@ impl Broker {
         )?;
 
         conn.subscribe_event(req.service_cookie, req.event);
-        let send_req = self
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            conn = id.number(),
+            service = %req.service_cookie,
+            event = req.event,
+            "event subscribed",
+        );
+
+        let svc = self
             .svcs
             .get_mut(&(obj_id.uuid, svc_uuid))
-            .expect("inconsistent state")
-            .subscribe_event(req.event, id.clone());
+            .expect("inconsistent state");
+        let send_req = svc.subscribe_event(req.event, id.clone());
+
+        if let Some(value) = svc.retained_event(req.event) {
+            send!(
+                self,
+                conn,
+                EmitEvent {
+                    service_cookie: req.service_cookie,
+                    event: req.event,
+                    value: value.clone(),
+                    trace_context: None,
+                },
+            )?;
+        }
+
+        for msg in self
+            .reliable
+            .subscribe(id, req.service_cookie, Some(req.event))
+        {
+            send!(self, conn, msg)?;
+        }
+
+        // The route must be added only after the reply and the retained event have been sent.
+        // Otherwise, events routed directly by the emitting connection could overtake them.
+        self.routes.subscribe_event(
+            req.service_cookie,
+            req.event,
+            id.clone(),
+            conn.sender().clone(),
+        );
 
         if send_req {
             let target_conn_id = self
@@ -891,7 +1485,19 @@ impl Broker {
             return;
         };
 
+        self.routes
+            .unsubscribe_event(req.service_cookie, req.event, id);
         conn.unsubscribe_event(req.service_cookie, req.event);
+        self.reliable
+            .unsubscribe(id, req.service_cookie, Some(req.event));
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            conn = id.number(),
+            service = %req.service_cookie,
+            event = req.event,
+            "event unsubscribed",
+        );
         let send_unsubscribe = svc.unsubscribe_event(req.event, id);
 
         if send_unsubscribe {
@@ -905,27 +1511,213 @@ impl Broker {
         }
     }
 
-    fn emit_event(&mut self, state: &mut State, id: &ConnectionId, req: EmitEvent) {
+    fn emit_event(&mut self, state: &mut State, id: &ConnectionId, req: EmitEvent) -> u32 {
         let Some(obj_uuid) = self
             .svc_uuids
             .get(&req.service_cookie)
             .map(|(object_id, _, _)| object_id.uuid)
         else {
-            return;
+            self.dead_letter(DeadLetter::new(
+                DeadLetterKind::EventInvalidService,
+                req.service_cookie,
+                req.event,
+            ));
+
+            return 0;
         };
 
         let obj = self.objs.get(&obj_uuid).expect("inconsistent state");
         if obj.conn_id() != id {
-            return;
+            return 0;
         }
 
-        for (conn_id, conn) in self.conns.iter() {
-            if conn.is_subscribed_to_event(req.service_cookie, req.event)
-                && send!(self, conn, req.clone()).is_err()
-            {
+        let delivery = self
+            .routes
+            .emit_event(id, &req)
+            .expect("inconsistent state");
+
+        for _ in 0..delivery.dropped {
+            self.dead_letter(DeadLetter::new(
+                DeadLetterKind::EventDropped,
+                req.service_cookie,
+                req.event,
+            ));
+        }
+
+        for conn_id in delivery.undeliverable {
+            state.push_remove_conn(conn_id, false);
+
+            self.dead_letter(DeadLetter::new(
+                DeadLetterKind::EventUndeliverable,
+                req.service_cookie,
+                req.event,
+            ));
+        }
+
+        delivery.delivered
+    }
+
+    fn emit_retained_event(
+        &mut self,
+        state: &mut State,
+        id: &ConnectionId,
+        req: EmitRetainedEvent,
+    ) -> Result<(), ()> {
+        let Some(conn) = self.conns.get(id) else {
+            return Ok(());
+        };
+
+        if conn.protocol_version() < ProtocolVersion::V1_19 {
+            return Err(());
+        }
+
+        if let Some(&(obj_id, svc_uuid, _)) = self.svc_uuids.get(&req.service_cookie) {
+            let obj = self.objs.get(&obj_id.uuid).expect("inconsistent state");
+
+            if obj.conn_id() == id {
+                self.svcs
+                    .get_mut(&(obj_id.uuid, svc_uuid))
+                    .expect("inconsistent state")
+                    .retain_event(req.event, req.value.clone());
+            }
+        }
+
+        self.emit_event(
+            state,
+            id,
+            EmitEvent {
+                service_cookie: req.service_cookie,
+                event: req.event,
+                value: req.value,
+                trace_context: req.trace_context,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn emit_event_acked(
+        &mut self,
+        state: &mut State,
+        id: &ConnectionId,
+        req: EmitEventAcked,
+    ) -> Result<(), ()> {
+        match self.conns.get(id) {
+            Some(conn) if conn.protocol_version() < ProtocolVersion::V1_19 => return Err(()),
+            Some(_) => {}
+            None => return Ok(()),
+        }
+
+        let delivered = self.emit_event(
+            state,
+            id,
+            EmitEvent {
+                service_cookie: req.service_cookie,
+                event: req.event,
+                value: req.value,
+                trace_context: req.trace_context,
+            },
+        );
+
+        let Some(conn) = self.conns.get(id) else {
+            return Ok(());
+        };
+
+        send!(
+            self,
+            conn,
+            EmitEventAckedReply {
+                serial: req.serial,
+                delivered,
+            },
+        )
+    }
+
+    fn emit_reliable_event(
+        &mut self,
+        state: &mut State,
+        id: &ConnectionId,
+        req: EmitReliableEvent,
+    ) -> Result<(), ()> {
+        match self.conns.get(id) {
+            Some(conn) if conn.protocol_version() < ProtocolVersion::V1_19 => return Err(()),
+            Some(_) => {}
+            None => return Ok(()),
+        }
+
+        let Some(&(obj_id, svc_uuid, _)) = self.svc_uuids.get(&req.service_cookie) else {
+            self.dead_letter(DeadLetter::new(
+                DeadLetterKind::EventInvalidService,
+                req.service_cookie,
+                req.event,
+            ));
+
+            return Ok(());
+        };
+
+        let obj = self.objs.get(&obj_id.uuid).expect("inconsistent state");
+        if obj.conn_id() != id {
+            return Ok(());
+        }
+
+        let svc = self
+            .svcs
+            .get(&(obj_id.uuid, svc_uuid))
+            .expect("inconsistent state");
+
+        // Subscribers, that don't support reliable events, receive them as regular events.
+        let (subscribers, legacy): (Vec<_>, Vec<_>) =
+            svc.event_subscribers(req.event).partition(|conn_id| {
+                self.conns
+                    .get(conn_id)
+                    .is_some_and(|conn| conn.protocol_version() >= ProtocolVersion::V1_19)
+            });
+
+        let msgs = self.reliable.emit(
+            subscribers,
+            req.service_cookie,
+            req.event,
+            &req.value,
+            req.trace_context,
+        );
+
+        for (conn_id, msg) in msgs {
+            let conn = self.conns.get(&conn_id).expect("inconsistent state");
+
+            if send!(self, conn, msg).is_err() {
+                state.push_remove_conn(conn_id, false);
+            }
+        }
+
+        for conn_id in legacy {
+            let Some(conn) = self.conns.get(conn_id) else {
+                continue;
+            };
+
+            let msg = EmitEvent {
+                service_cookie: req.service_cookie,
+                event: req.event,
+                value: req.value.clone(),
+                trace_context: None,
+            };
+
+            if send!(self, conn, msg).is_err() {
                 state.push_remove_conn(conn_id.clone(), false);
             }
         }
+
+        Ok(())
+    }
+
+    fn ack_event(&mut self, id: &ConnectionId, req: AckEvent) -> Result<(), ()> {
+        match self.conns.get(id) {
+            Some(conn) if conn.protocol_version() < ProtocolVersion::V1_19 => Err(()),
+            Some(_) => {
+                self.reliable.ack(id, req.serial);
+                Ok(())
+            }
+            None => Ok(()),
+        }
     }
 
     fn query_service_version(
@@ -962,16 +1754,38 @@ impl Broker {
         let channel = match req.end {
             ChannelEndWithCapacity::Sender => {
                 conn.add_sender(cookie);
-                Channel::with_claimed_sender(id.clone())
+                Some(Channel::with_claimed_sender(id.clone()))
             }
 
             ChannelEndWithCapacity::Receiver(capacity) => {
                 conn.add_receiver(cookie);
-                Channel::with_claimed_receiver(id.clone(), capacity)
+                Some(Channel::with_claimed_receiver(id.clone(), capacity, false))
+            }
+
+            ChannelEndWithCapacity::ReceiverBytes(capacity) => {
+                if conn.protocol_version() < ProtocolVersion::V1_19 {
+                    return Err(());
+                }
+
+                conn.add_receiver(cookie);
+                Some(Channel::with_claimed_receiver(id.clone(), capacity, true))
+            }
+
+            ChannelEndWithCapacity::BroadcastSender => {
+                if conn.protocol_version() < ProtocolVersion::V1_19 {
+                    return Err(());
+                }
+
+                conn.add_sender(cookie);
+                self.broadcasts
+                    .insert(cookie, BroadcastChannel::new(id.clone()));
+                None
             }
         };
 
-        self.channels.insert(cookie, channel);
+        if let Some(channel) = channel {
+            self.channels.insert(cookie, channel);
+        }
 
         send!(
             self,
@@ -1000,6 +1814,25 @@ impl Broker {
             return Ok(());
         };
 
+        if let Some(broadcast) = self.broadcasts.get(&req.cookie) {
+            let result = broadcast.check_close(id, req.end);
+
+            send!(
+                self,
+                conn,
+                CloseChannelEndReply {
+                    serial: req.serial,
+                    result,
+                },
+            )?;
+
+            if result == CloseChannelEndResult::Ok {
+                self.remove_broadcast_end(state, req.cookie, req.end, id);
+            }
+
+            return Ok(());
+        }
+
         let Some(channel) = self.channels.get(&req.cookie) else {
             return send!(
                 self,
@@ -1036,10 +1869,27 @@ impl Broker {
         id: &ConnectionId,
         req: ClaimChannelEnd,
     ) -> Result<(), ()> {
+        if self.broadcasts.contains_key(&req.cookie) {
+            return self.claim_broadcast_receiver(state, id, req);
+        }
+
+        // Senders of clients, that don't support byte capacities, can't be connected to receivers,
+        // that use them.
+        let sender_supports_bytes = self
+            .channels
+            .get(&req.cookie)
+            .and_then(|channel| channel.owner(ChannelEnd::Sender))
+            .and_then(|sender_id| self.conns.get(sender_id))
+            .map_or(true, |sender| {
+                sender.protocol_version() >= ProtocolVersion::V1_19
+            });
+
         let Some(conn) = self.conns.get_mut(id) else {
             return Ok(());
         };
 
+        let supports_bytes = conn.protocol_version() >= ProtocolVersion::V1_19;
+
         let Some(channel) = self.channels.get_mut(&req.cookie) else {
             return send!(
                 self,
@@ -1053,18 +1903,44 @@ impl Broker {
 
         let result = match req.end {
             ChannelEndWithCapacity::Sender => {
-                channel.claim_sender(id).map(|(receiver, capacity)| {
-                    conn.add_sender(req.cookie);
-                    (receiver, ClaimChannelEndResult::SenderClaimed(capacity))
-                })
+                channel
+                    .claim_sender(id, supports_bytes)
+                    .map(|(receiver, result)| {
+                        conn.add_sender(req.cookie);
+                        (receiver, result)
+                    })
             }
 
             ChannelEndWithCapacity::Receiver(capacity) => {
-                channel.claim_receiver(id, capacity).map(|sender| {
+                channel.claim_receiver(id, capacity, false).map(|sender| {
                     conn.add_receiver(req.cookie);
                     (sender, ClaimChannelEndResult::ReceiverClaimed)
                 })
             }
+
+            ChannelEndWithCapacity::ReceiverBytes(capacity) => {
+                if !supports_bytes {
+                    return Err(());
+                }
+
+                if sender_supports_bytes {
+                    channel.claim_receiver(id, capacity, true).map(|sender| {
+                        conn.add_receiver(req.cookie);
+                        (sender, ClaimChannelEndResult::ReceiverClaimed)
+                    })
+                } else {
+                    Err(ClaimChannelEndResult::InvalidChannel)
+                }
+            }
+
+            // Broadcast senders can only be created, but never claimed.
+            ChannelEndWithCapacity::BroadcastSender => {
+                if conn.protocol_version() < ProtocolVersion::V1_19 {
+                    return Err(());
+                }
+
+                Err(ClaimChannelEndResult::InvalidChannel)
+            }
         };
 
         match result {
@@ -1107,17 +1983,89 @@ impl Broker {
         }
     }
 
+    fn claim_broadcast_receiver(
+        &mut self,
+        state: &mut State,
+        id: &ConnectionId,
+        req: ClaimChannelEnd,
+    ) -> Result<(), ()> {
+        let Some(conn) = self.conns.get_mut(id) else {
+            return Ok(());
+        };
+
+        let Some(broadcast) = self.broadcasts.get_mut(&req.cookie) else {
+            return Ok(());
+        };
+
+        let res = match req.end {
+            ChannelEndWithCapacity::Receiver(capacity) => broadcast.claim_receiver(id, capacity),
+
+            ChannelEndWithCapacity::ReceiverBytes(_)
+                if conn.protocol_version() < ProtocolVersion::V1_19 =>
+            {
+                return Err(());
+            }
+
+            // Broadcast channels always measure capacities in items and the sender can't be
+            // claimed.
+            ChannelEndWithCapacity::Sender
+            | ChannelEndWithCapacity::ReceiverBytes(_)
+            | ChannelEndWithCapacity::BroadcastSender => Err(ClaimChannelEndResult::InvalidChannel),
+        };
+
+        let (result, add_capacity) = match res {
+            Ok(add_capacity) => {
+                conn.add_receiver(req.cookie);
+                (ClaimChannelEndResult::ReceiverClaimed, add_capacity)
+            }
+
+            Err(result) => (result, None),
+        };
+
+        let res = send!(
+            self,
+            conn,
+            ClaimChannelEndReply {
+                serial: req.serial,
+                result,
+            },
+        );
+
+        if let Some((sender_id, capacity)) = add_capacity {
+            if let Some(sender) = self.conns.get(sender_id) {
+                let add_res = send!(
+                    self,
+                    sender,
+                    AddChannelCapacity {
+                        cookie: req.cookie,
+                        capacity,
+                    },
+                );
+
+                if add_res.is_err() {
+                    state.push_remove_conn(sender_id.clone(), false);
+                }
+            }
+        }
+
+        res
+    }
+
     fn add_channel_capacity(
         &mut self,
         state: &mut State,
         id: &ConnectionId,
         req: AddChannelCapacity,
     ) {
-        let Some(channel) = self.channels.get_mut(&req.cookie) else {
+        let res = if let Some(broadcast) = self.broadcasts.get_mut(&req.cookie) {
+            broadcast.add_capacity(id, req.capacity)
+        } else if let Some(channel) = self.channels.get_mut(&req.cookie) {
+            channel.add_capacity(id, req.capacity)
+        } else {
             return;
         };
 
-        let (sender_id, capacity) = match channel.add_capacity(id, req.capacity) {
+        let (sender_id, capacity) = match res {
             Ok(Some((sender_id, capacity))) => (sender_id, capacity),
             Ok(None) => return,
 
@@ -1150,11 +2098,15 @@ impl Broker {
             return Ok(());
         };
 
+        if self.broadcasts.contains_key(&req.cookie) {
+            return self.send_broadcast_item(state, id, req);
+        }
+
         let Some(channel) = self.channels.get_mut(&req.cookie) else {
             return Ok(());
         };
 
-        let (receiver_id, add_capacity) = match channel.send_item(id) {
+        let (receiver_id, add_capacity) = match channel.send_item(id, req.value.len()) {
             Ok(res) => res,
 
             Err(e) => {
@@ -1184,17 +2136,79 @@ impl Broker {
             return Ok(());
         };
 
-        let res = send!(
-            self,
-            receiver,
-            ItemReceived {
-                cookie: req.cookie,
-                value: req.value,
-            },
-        );
+        let res = send!(
+            self,
+            receiver,
+            ItemReceived {
+                cookie: req.cookie,
+                value: req.value,
+            },
+        );
+
+        if res.is_err() {
+            state.push_remove_conn(receiver_id.clone(), false);
+        }
+
+        if let Some(add_capacity) = add_capacity {
+            send!(
+                self,
+                sender,
+                AddChannelCapacity {
+                    cookie: req.cookie,
+                    capacity: add_capacity,
+                },
+            )
+        } else {
+            Ok(())
+        }
+    }
+
+    fn send_broadcast_item(
+        &mut self,
+        state: &mut State,
+        id: &ConnectionId,
+        req: SendItem,
+    ) -> Result<(), ()> {
+        let Some(sender) = self.conns.get(id) else {
+            return Ok(());
+        };
+
+        let Some(broadcast) = self.broadcasts.get_mut(&req.cookie) else {
+            return Ok(());
+        };
+
+        let add_capacity = match broadcast.send_item(id) {
+            Ok(add_capacity) => add_capacity.map(|(_, capacity)| capacity),
+
+            Err(SendItemError::CapacityExhausted) => {
+                self.remove_broadcast_end(state, req.cookie, ChannelEnd::Sender, id);
+                return Ok(());
+            }
+
+            Err(
+                SendItemError::InvalidSender
+                | SendItemError::ReceiverUnclaimed
+                | SendItemError::ReceiverClosed,
+            ) => return Ok(()),
+        };
+
+        for receiver_id in broadcast.receivers() {
+            let Some(receiver) = self.conns.get(receiver_id) else {
+                continue;
+            };
+
+            let res = send!(
+                self,
+                receiver,
+                ItemReceived {
+                    cookie: req.cookie,
+                    value: req.value.clone(),
+                },
+            );
 
-        if res.is_err() {
-            state.push_remove_conn(receiver_id.clone(), false);
+            if res.is_err() {
+                state.push_remove_conn(receiver_id.clone(), false);
+            }
         }
 
         if let Some(add_capacity) = add_capacity {
@@ -1292,12 +2306,26 @@ impl Broker {
         Ok(())
     }
 
-    fn add_bus_listener_filter(&mut self, id: &ConnectionId, req: AddBusListenerFilter) {
+    fn add_bus_listener_filter(
+        &mut self,
+        id: &ConnectionId,
+        req: AddBusListenerFilter,
+    ) -> Result<(), ()> {
+        if let BusListenerFilter::Layout(_) = req.filter {
+            match self.conns.get(id) {
+                Some(conn) if conn.protocol_version() < ProtocolVersion::V1_19 => return Err(()),
+                Some(_) => {}
+                None => return Ok(()),
+            }
+        }
+
         if let Some(bus_listener) = self.bus_listeners.get_mut(&req.cookie) {
             if bus_listener.conn_id() == id {
                 bus_listener.add_filter(req.filter);
             }
         }
+
+        Ok(())
     }
 
     fn remove_bus_listener_filter(&mut self, id: &ConnectionId, req: RemoveBusListenerFilter) {
@@ -1366,8 +2394,11 @@ impl Broker {
         if req.scope != BusListenerScope::New {
             for (&cookie, &uuid) in &self.obj_uuids {
                 let object = ObjectId::new(uuid, cookie);
+                let namespace = self.objs[&uuid].namespace();
 
-                if bus_listener.matches_object(object) {
+                if conn.is_namespace_visible(namespace.map(AsRef::as_ref))
+                    && bus_listener.matches_object(object)
+                {
                     send!(
                         self,
                         conn,
@@ -1379,10 +2410,13 @@ impl Broker {
                 }
             }
 
-            for (&service_cookie, &(object, service_uuid, _)) in &self.svc_uuids {
+            for (&service_cookie, &(object, service_uuid, info)) in &self.svc_uuids {
                 let service = ServiceId::new(object, service_uuid, service_cookie);
+                let namespace = self.svcs[&(object.uuid, service_uuid)].namespace();
 
-                if bus_listener.matches_service(service) {
+                if conn.is_namespace_visible(namespace.map(AsRef::as_ref))
+                    && bus_listener.matches_service(service, info.lexical_id())
+                {
                     send!(
                         self,
                         conn,
@@ -1545,27 +2579,30 @@ impl Broker {
             );
         };
 
-        if let Some(introspection) = entry.introspection() {
+        if entry.introspection().is_some() {
+            let result = self.introspection_result(
+                conn.protocol_version(),
+                req.type_id,
+                &req.known,
+                req.compression,
+            );
+
             send!(
                 self,
                 conn,
                 QueryIntrospectionReply {
                     serial: req.serial,
-                    result: QueryIntrospectionResult::Ok(introspection.clone()),
+                    result,
                 },
             )
         } else {
-            entry.add_pending(id.clone(), req.serial);
+            entry.add_pending(id.clone(), req.serial, req.known, req.compression);
 
             if entry.queried().is_none() {
                 let serial = self.query_introspection.insert(req.type_id);
                 let conn_id = entry.query_random_conn(serial);
                 let conn = self.conns.get(conn_id).expect("inconsistent state");
-
-                let msg = QueryIntrospection {
-                    serial,
-                    type_id: req.type_id,
-                };
+                let msg = QueryIntrospection::new(serial, req.type_id);
 
                 if send!(self, conn, msg).is_err() {
                     state.push_remove_conn(conn_id.clone(), false);
@@ -1634,19 +2671,23 @@ impl Broker {
         self.query_introspection.remove(serial);
 
         match res {
-            IntrospectionQueryResult::Available {
-                introspection,
-                pending,
-            } => {
+            IntrospectionQueryResult::Available(pending) => {
                 for pending in pending {
                     let conn = self
                         .conns
                         .get(&pending.conn_id)
                         .expect("inconsistent state");
 
+                    let result = self.introspection_result(
+                        conn.protocol_version(),
+                        type_id,
+                        &pending.known,
+                        pending.compression,
+                    );
+
                     let msg = QueryIntrospectionReply {
                         serial: pending.serial,
-                        result: QueryIntrospectionResult::Ok(introspection.clone()),
+                        result,
                     };
 
                     if send!(self, conn, msg).is_err() {
@@ -1677,7 +2718,7 @@ impl Broker {
                 let serial = self.query_introspection.insert(type_id);
                 let conn_id = entry.query_random_conn(serial);
                 let conn = self.conns.get(conn_id).expect("inconsistent state");
-                let msg = QueryIntrospection { serial, type_id };
+                let msg = QueryIntrospection::new(serial, type_id);
 
                 if send!(self, conn, msg).is_err() {
                     state.push_remove_conn(conn_id.clone(), false);
@@ -1774,9 +2815,19 @@ impl Broker {
             .svc_uuids
             .insert(svc_cookie, (object_id, req.uuid, info));
         debug_assert!(dup.is_none());
-        entry.insert(Service::new());
+        let namespace = obj.namespace().cloned();
+        entry.insert(Service::new(namespace.clone()));
         obj.add_service(svc_cookie);
-        state.push_create_service(ServiceId::new(object_id, req.uuid, svc_cookie));
+        self.routes.add_service(svc_cookie, id.clone());
+        state.push_create_service(
+            ServiceId::new(object_id, req.uuid, svc_cookie),
+            info.lexical_id(),
+            namespace,
+        );
+
+        #[cfg(feature = "history")]
+        self.history
+            .service_created(ServiceId::new(object_id, req.uuid, svc_cookie), id);
 
         #[cfg(feature = "statistics")]
         {
@@ -1786,6 +2837,165 @@ impl Broker {
         Ok(())
     }
 
+    fn create_services(
+        &mut self,
+        state: &mut State,
+        id: &ConnectionId,
+        req: CreateServices,
+    ) -> Result<(), ()> {
+        let Some(conn) = self.conns.get(id) else {
+            return Ok(());
+        };
+
+        if conn.protocol_version() < ProtocolVersion::V1_19 {
+            return Err(());
+        }
+
+        let Some(&obj_uuid) = self.obj_uuids.get(&req.object_cookie) else {
+            return send!(
+                self,
+                conn,
+                CreateServicesReply {
+                    serial: req.serial,
+                    result: CreateServicesResult::InvalidObject,
+                },
+            );
+        };
+
+        let Ok(services) = req.deserialize_services() else {
+            return Err(());
+        };
+
+        // Services must be unique among the request itself as well as among the existing services
+        // of the object.
+        let mut uuids = HashSet::with_capacity(services.len());
+        if services
+            .iter()
+            .any(|&(uuid, _)| !uuids.insert(uuid) || self.svcs.contains_key(&(obj_uuid, uuid)))
+        {
+            return send!(
+                self,
+                conn,
+                CreateServicesReply {
+                    serial: req.serial,
+                    result: CreateServicesResult::DuplicateService,
+                },
+            );
+        }
+
+        let obj = self.objs.get(&obj_uuid).expect("inconsistent state");
+        if obj.conn_id() != id {
+            return send!(
+                self,
+                conn,
+                CreateServicesReply {
+                    serial: req.serial,
+                    result: CreateServicesResult::ForeignObject,
+                },
+            );
+        }
+
+        let svc_cookies: Vec<_> = services.iter().map(|_| ServiceCookie::new_v4()).collect();
+        send!(
+            self,
+            conn,
+            CreateServicesReply {
+                serial: req.serial,
+                result: CreateServicesResult::Ok(svc_cookies.clone()),
+            },
+        )?;
+
+        // All services are added within a single step, so that other connections never observe
+        // only some of them.
+        let object_id = ObjectId::new(obj_uuid, req.object_cookie);
+        let obj = self.objs.get_mut(&obj_uuid).expect("inconsistent state");
+
+        for ((svc_uuid, info), svc_cookie) in services.into_iter().zip(svc_cookies) {
+            let dup = self
+                .svc_uuids
+                .insert(svc_cookie, (object_id, svc_uuid, info));
+            debug_assert!(dup.is_none());
+            let namespace = obj.namespace().cloned();
+            let dup = self
+                .svcs
+                .insert((obj_uuid, svc_uuid), Service::new(namespace.clone()));
+            debug_assert!(dup.is_none());
+            obj.add_service(svc_cookie);
+            self.routes.add_service(svc_cookie, id.clone());
+            state.push_create_service(
+                ServiceId::new(object_id, svc_uuid, svc_cookie),
+                info.lexical_id(),
+                namespace,
+            );
+
+            #[cfg(feature = "history")]
+            self.history
+                .service_created(ServiceId::new(object_id, svc_uuid, svc_cookie), id);
+
+            #[cfg(feature = "statistics")]
+            {
+                self.statistics.num_services = self.statistics.num_services.saturating_add(1);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn destroy_services(
+        &mut self,
+        state: &mut State,
+        id: &ConnectionId,
+        req: DestroyServices,
+    ) -> Result<(), ()> {
+        let Some(conn) = self.conns.get(id) else {
+            return Ok(());
+        };
+
+        if conn.protocol_version() < ProtocolVersion::V1_19 {
+            return Err(());
+        }
+
+        for svc_cookie in &req.cookies {
+            let Some(ids) = self.svc_uuids.get(svc_cookie) else {
+                return send!(
+                    self,
+                    conn,
+                    DestroyServicesReply {
+                        serial: req.serial,
+                        result: DestroyServicesResult::InvalidService,
+                    },
+                );
+            };
+
+            let obj = self.objs.get(&ids.0.uuid).expect("inconsistent state");
+            if obj.conn_id() != id {
+                return send!(
+                    self,
+                    conn,
+                    DestroyServicesReply {
+                        serial: req.serial,
+                        result: DestroyServicesResult::ForeignObject,
+                    },
+                );
+            }
+        }
+
+        send!(
+            self,
+            conn,
+            DestroyServicesReply {
+                serial: req.serial,
+                result: DestroyServicesResult::Ok,
+            },
+        )?;
+
+        for svc_cookie in req.cookies {
+            self.remove_service(state, svc_cookie);
+        }
+
+        Ok(())
+    }
+
     fn query_service_info(&mut self, id: &ConnectionId, req: QueryServiceInfo) -> Result<(), ()> {
         let Some(conn) = self.conns.get(id) else {
             return Ok(());
@@ -1946,11 +3156,40 @@ impl Broker {
         let conn = self.conns.get_mut(id).unwrap();
 
         conn.subscribe_all_events(req.service_cookie);
-        let send_req = self
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            conn = id.number(),
+            service = %req.service_cookie,
+            "all events subscribed",
+        );
+
+        let svc = self
             .svcs
             .get_mut(&(obj_id.uuid, svc_uuid))
-            .expect("inconsistent state")
-            .subscribe_all_events(id.clone());
+            .expect("inconsistent state");
+        let send_req = svc.subscribe_all_events(id.clone());
+
+        for (event, value) in svc.retained_events() {
+            send!(
+                self,
+                conn,
+                EmitEvent {
+                    service_cookie: req.service_cookie,
+                    event,
+                    value: value.clone(),
+                    trace_context: None,
+                },
+            )?;
+        }
+
+        for msg in self.reliable.subscribe(id, req.service_cookie, None) {
+            send!(self, conn, msg)?;
+        }
+
+        // See `subscribe_event` for why the route is added last.
+        self.routes
+            .subscribe_all_events(req.service_cookie, id.clone(), conn.sender().clone());
 
         if send_req {
             let _ = send!(
@@ -2017,6 +3256,10 @@ impl Broker {
             }
         }
 
+        // Remove the route first, such that no further events are routed to the connection after
+        // the reply.
+        self.routes.unsubscribe_all_events(req.service_cookie, id);
+
         if let Some(serial) = req.serial {
             send!(
                 self,
@@ -2031,6 +3274,15 @@ impl Broker {
         let conn = self.conns.get_mut(id).unwrap();
 
         conn.unsubscribe_all_events(req.service_cookie);
+        self.reliable.unsubscribe(id, req.service_cookie, None);
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            conn = id.number(),
+            service = %req.service_cookie,
+            "all events unsubscribed",
+        );
+
         let send_req = self
             .svcs
             .get_mut(&(obj_id.uuid, svc_uuid))
@@ -2068,7 +3320,14 @@ impl Broker {
             conn.remove_object(obj_cookie);
         }
 
-        state.push_destroy_object(ObjectId::new(obj_uuid, obj_cookie));
+        state.push_destroy_object(
+            ObjectId::new(obj_uuid, obj_cookie),
+            obj.namespace().cloned(),
+        );
+
+        #[cfg(feature = "history")]
+        self.history
+            .object_destroyed(ObjectId::new(obj_uuid, obj_cookie), obj.conn_id());
 
         for svc_cookie in obj.services() {
             self.remove_service(state, svc_cookie);
@@ -2085,10 +3344,13 @@ impl Broker {
     /// This function will also remove everything related to `svc_cookie`, e.g. pending function
     /// calls. It is safe to call with an invalid `svc_cookie`.
     fn remove_service(&mut self, state: &mut State, svc_cookie: ServiceCookie) {
-        let Some((obj_id, svc_uuid, _)) = self.svc_uuids.remove(&svc_cookie) else {
+        let Some((obj_id, svc_uuid, info)) = self.svc_uuids.remove(&svc_cookie) else {
             return;
         };
 
+        self.routes.remove_service(svc_cookie);
+        self.reliable.remove_service(svc_cookie);
+
         let svc = self
             .svcs
             .remove(&(obj_id.uuid, svc_uuid))
@@ -2099,7 +3361,15 @@ impl Broker {
             obj.remove_service(svc_cookie);
         }
 
-        state.push_destroy_service(ServiceId::new(obj_id, svc_uuid, svc_cookie));
+        state.push_destroy_service(
+            ServiceId::new(obj_id, svc_uuid, svc_cookie),
+            info.lexical_id(),
+            svc.namespace().cloned(),
+        );
+
+        #[cfg(feature = "history")]
+        self.history
+            .service_destroyed(ServiceId::new(obj_id, svc_uuid, svc_cookie));
 
         for serial in svc.function_calls() {
             let call = self
@@ -2113,6 +3383,12 @@ impl Broker {
                     call.caller_conn_id,
                     CallFunctionResult::InvalidService,
                 );
+
+                self.dead_letter(DeadLetter::new(
+                    DeadLetterKind::CallServiceDestroyed,
+                    svc_cookie,
+                    call.function,
+                ));
             }
         }
 
@@ -2144,6 +3420,8 @@ impl Broker {
             return;
         };
 
+        self.routes.unsubscribe_event(svc_cookie, event, conn_id);
+
         // The connection might already have been removed.
         if let Some(conn) = self.conns.get_mut(conn_id) {
             conn.unsubscribe_event(svc_cookie, event);
@@ -2170,6 +3448,8 @@ impl Broker {
             return;
         };
 
+        self.routes.unsubscribe_all_events(svc_cookie, conn_id);
+
         // The connection might already have been removed.
         if let Some(conn) = self.conns.get_mut(conn_id) {
             conn.unsubscribe_all_events(svc_cookie);
@@ -2204,6 +3484,14 @@ impl Broker {
         end: ChannelEnd,
         owner: Option<&ConnectionId>,
     ) {
+        if self.broadcasts.contains_key(&cookie) {
+            if let Some(owner) = owner {
+                self.remove_broadcast_end(state, cookie, end, owner);
+            }
+
+            return;
+        }
+
         let Entry::Occupied(mut channel) = self.channels.entry(cookie) else {
             return;
         };
@@ -2241,6 +3529,58 @@ impl Broker {
         }
     }
 
+    fn remove_broadcast_end(
+        &mut self,
+        state: &mut State,
+        cookie: ChannelCookie,
+        end: ChannelEnd,
+        owner: &ConnectionId,
+    ) {
+        let Entry::Occupied(mut broadcast) = self.broadcasts.entry(cookie) else {
+            return;
+        };
+
+        if let Some(conn) = self.conns.get_mut(owner) {
+            match end {
+                ChannelEnd::Sender => conn.remove_sender(cookie),
+                ChannelEnd::Receiver => conn.remove_receiver(cookie),
+            }
+        }
+
+        match end {
+            ChannelEnd::Sender => {
+                for receiver_id in broadcast.get_mut().close_sender() {
+                    let Some(receiver) = self.conns.get(receiver_id) else {
+                        continue;
+                    };
+
+                    if send!(self, receiver, ChannelEndClosed { cookie, end }).is_err() {
+                        state.push_remove_conn(receiver_id.clone(), false);
+                    }
+                }
+            }
+
+            ChannelEnd::Receiver => {
+                if let Some((sender_id, capacity)) = broadcast.get_mut().close_receiver(owner) {
+                    if let Some(sender) = self.conns.get(sender_id) {
+                        if send!(self, sender, AddChannelCapacity { cookie, capacity }).is_err() {
+                            state.push_remove_conn(sender_id.clone(), false);
+                        }
+                    }
+                }
+            }
+        }
+
+        if broadcast.get().is_closed() {
+            broadcast.remove();
+
+            #[cfg(feature = "statistics")]
+            {
+                self.statistics.num_channels = self.statistics.num_channels.saturating_sub(1);
+            }
+        }
+    }
+
     fn remove_bus_listener(&mut self, cookie: BusListenerCookie) {
         let Some(bus_listener) = self.bus_listeners.remove(&cookie) else {
             return;
@@ -2256,34 +3596,49 @@ impl Broker {
         }
     }
 
-    fn emit_bus_event(&mut self, state: &mut State, event: BusEvent) {
+    fn emit_bus_event(
+        &mut self,
+        state: &mut State,
+        event: BusEvent,
+        layout: Option<LexicalId>,
+        namespace: Option<&str>,
+    ) {
         #[allow(clippy::mutable_key_type)]
         let mut dups = HashSet::new();
 
         #[allow(clippy::mutable_key_type)]
         let mut remove_conns = HashSet::new();
 
-        for bus_listener in self.bus_listeners.values() {
+        for (&cookie, bus_listener) in &self.bus_listeners {
             let conn_id = bus_listener.conn_id();
 
-            if !bus_listener.matches_new_event(event) || dups.contains(conn_id) {
+            let Some(conn) = self.conns.get(conn_id) else {
+                continue;
+            };
+
+            if !conn.is_namespace_visible(namespace) {
                 continue;
             }
 
-            dups.insert(conn_id);
+            let cookie = match bus_listener.matches_new_event(event, layout) {
+                BusListenerMatch::None => continue,
 
-            let Some(conn) = self.conns.get(conn_id) else {
-                continue;
+                // Clients match broadcast events against their own filters. Thus, such events
+                // must be sent at most once per connection.
+                BusListenerMatch::Broadcast => {
+                    if !dups.insert(conn_id) {
+                        continue;
+                    }
+
+                    None
+                }
+
+                // Layout filters cannot be evaluated by clients, because they don't know the
+                // layout of a service. Such events are addressed to the bus listener directly.
+                BusListenerMatch::Layout => Some(cookie),
             };
 
-            let res = send!(
-                self,
-                conn,
-                EmitBusEvent {
-                    cookie: None,
-                    event,
-                },
-            );
+            let res = send!(self, conn, EmitBusEvent { cookie, event });
 
             if res.is_err() {
                 remove_conns.insert(conn_id);
@@ -2293,6 +3648,18 @@ impl Broker {
         state.push_remove_conns(remove_conns.into_iter().map(|id| (id.clone(), false)));
     }
 
+    /// Records a message that could not be delivered.
+    ///
+    /// The dead letter is forwarded to the current dead letter sink, if there is one.
+    fn dead_letter(&mut self, letter: DeadLetter) {
+        #[cfg(feature = "statistics")]
+        {
+            self.statistics.dead_letters = self.statistics.dead_letters.saturating_add(1);
+        }
+
+        DeadLetterSink::send_to(&mut self.dead_letters, letter);
+    }
+
     fn abort_call(&mut self, state: &mut State, callee_serial: u32, callee_id: ConnectionId) {
         let Some(call) = self.function_calls.get_mut(callee_serial) else {
             return;
@@ -2338,6 +3705,35 @@ impl Broker {
         }
     }
 
+    /// Builds the result for a query of an introspection, that is known to be available.
+    ///
+    /// Clients since protocol version 1.19 receive a bundle, which includes the introspections of
+    /// all referenced types, except those in `known`.
+    #[cfg(feature = "introspection")]
+    fn introspection_result(
+        &self,
+        version: ProtocolVersion,
+        type_id: TypeId,
+        known: &[TypeId],
+        compression: Option<Compression>,
+    ) -> QueryIntrospectionResult {
+        let introspection = self
+            .introspection
+            .get(type_id)
+            .and_then(|entry| entry.introspection())
+            .expect("inconsistent state");
+
+        if version >= ProtocolVersion::V1_19 {
+            let bundle = self.introspection.bundle(type_id, known);
+
+            if let Ok(result) = QueryIntrospectionResult::bundle(bundle, compression) {
+                return result;
+            }
+        }
+
+        QueryIntrospectionResult::Ok(introspection.clone())
+    }
+
     #[cfg(feature = "introspection")]
     fn remove_introspection_conn(&mut self, state: &mut State, conn_id: &ConnectionId) {
         let remove_conn = self.introspection.remove_conn(conn_id);
@@ -2382,7 +3778,7 @@ impl Broker {
                     let serial = self.query_introspection.insert(type_id);
                     let conn_id = entry.query_random_conn(serial);
                     let conn = self.conns.get(conn_id).expect("inconsistent state");
-                    let msg = QueryIntrospection { serial, type_id };
+                    let msg = QueryIntrospection::new(serial, type_id);
 
                     if send!(self, conn, msg).is_err() {
                         state.push_remove_conn(conn_id.clone(), false);
@@ -2405,5 +3801,19 @@ struct PendingFunctionCall {
     caller_conn_id: ConnectionId,
     callee_obj: ObjectUuid,
     callee_svc: ServiceUuid,
+    function: u32,
+    priority: CallPriority,
     aborted: bool,
 }
+
+/// Returns the result for calls rejected because of overload.
+///
+/// Clients older than protocol version 1.19 don't know `CallFunctionResult::Overloaded` and get
+/// `CallFunctionResult::Aborted` instead.
+fn overloaded_result(version: ProtocolVersion) -> CallFunctionResult {
+    if version >= ProtocolVersion::V1_19 {
+        CallFunctionResult::Overloaded
+    } else {
+        CallFunctionResult::Aborted
+    }
+}
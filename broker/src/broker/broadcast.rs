@@ -0,0 +1,157 @@
+use super::channel::{AddCapacityError, SendItemError};
+use crate::conn_id::ConnectionId;
+use crate::core::message::{ClaimChannelEndResult, CloseChannelEndResult};
+use crate::core::ChannelEnd;
+use std::collections::HashMap;
+
+const LOW_CAPACITY: i64 = 4;
+
+/// A channel with a single sender and any number of receivers.
+///
+/// Every receiver has its own capacity. The sender is granted capacity only up to the smallest
+/// capacity of all receivers, such that the slowest receiver determines the rate of the channel.
+///
+/// Receivers can join at any time. A receiver, that joins while the sender still has capacity left,
+/// may receive a few more items than its own capacity. Receiver capacities can thus become
+/// negative.
+#[derive(Debug)]
+pub(crate) struct BroadcastChannel {
+    sender: Option<BroadcastSender>,
+    receivers: HashMap<ConnectionId, i64>,
+}
+
+impl BroadcastChannel {
+    pub fn new(owner: ConnectionId) -> Self {
+        Self {
+            sender: Some(BroadcastSender { owner, capacity: 0 }),
+            receivers: HashMap::new(),
+        }
+    }
+
+    #[cfg(feature = "consistency-check")]
+    pub fn sender(&self) -> Option<&ConnectionId> {
+        self.sender.as_ref().map(|sender| &sender.owner)
+    }
+
+    pub fn receivers(&self) -> impl Iterator<Item = &ConnectionId> {
+        self.receivers.keys()
+    }
+
+    /// Indicates whether the channel has neither a sender nor any receivers.
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_none() && self.receivers.is_empty()
+    }
+
+    pub fn check_close(&self, conn_id: &ConnectionId, end: ChannelEnd) -> CloseChannelEndResult {
+        match end {
+            ChannelEnd::Sender => match self.sender {
+                Some(ref sender) if sender.owner == *conn_id => CloseChannelEndResult::Ok,
+                Some(_) => CloseChannelEndResult::ForeignChannel,
+                None => CloseChannelEndResult::InvalidChannel,
+            },
+
+            ChannelEnd::Receiver => {
+                if self.receivers.contains_key(conn_id) {
+                    CloseChannelEndResult::Ok
+                } else {
+                    CloseChannelEndResult::InvalidChannel
+                }
+            }
+        }
+    }
+
+    /// Closes the sender and returns the receivers, that must be notified.
+    pub fn close_sender(&mut self) -> impl Iterator<Item = &ConnectionId> {
+        self.sender = None;
+        self.receivers.keys()
+    }
+
+    /// Closes a receiver and returns capacity, that must be added to the sender.
+    pub fn close_receiver(&mut self, conn_id: &ConnectionId) -> Option<(&ConnectionId, u32)> {
+        self.receivers.remove(conn_id);
+        self.grant_capacity()
+    }
+
+    pub fn claim_receiver(
+        &mut self,
+        conn_id: &ConnectionId,
+        capacity: u32,
+    ) -> Result<Option<(&ConnectionId, u32)>, ClaimChannelEndResult> {
+        if self.sender.is_none() {
+            return Err(ClaimChannelEndResult::InvalidChannel);
+        }
+
+        if self.receivers.contains_key(conn_id) {
+            return Err(ClaimChannelEndResult::AlreadyClaimed);
+        }
+
+        self.receivers.insert(conn_id.clone(), capacity.into());
+        Ok(self.grant_capacity())
+    }
+
+    pub fn send_item(
+        &mut self,
+        conn_id: &ConnectionId,
+    ) -> Result<Option<(&ConnectionId, u32)>, SendItemError> {
+        let Some(ref mut sender) = self.sender else {
+            return Err(SendItemError::InvalidSender);
+        };
+
+        if sender.owner != *conn_id {
+            return Err(SendItemError::InvalidSender);
+        }
+
+        if sender.capacity <= 0 {
+            return Err(SendItemError::CapacityExhausted);
+        }
+
+        sender.capacity -= 1;
+
+        for capacity in self.receivers.values_mut() {
+            *capacity -= 1;
+        }
+
+        Ok(self.grant_capacity())
+    }
+
+    pub fn add_capacity(
+        &mut self,
+        conn_id: &ConnectionId,
+        capacity: u32,
+    ) -> Result<Option<(&ConnectionId, u32)>, AddCapacityError> {
+        if capacity == 0 {
+            return Ok(None);
+        }
+
+        let Some(receiver_capacity) = self.receivers.get_mut(conn_id) else {
+            return Ok(None);
+        };
+
+        let new_receiver_capacity = *receiver_capacity + i64::from(capacity);
+        if new_receiver_capacity > i64::from(u32::MAX) {
+            return Err(AddCapacityError);
+        }
+
+        *receiver_capacity = new_receiver_capacity;
+        Ok(self.grant_capacity())
+    }
+
+    fn grant_capacity(&mut self) -> Option<(&ConnectionId, u32)> {
+        let sender = self.sender.as_mut()?;
+        let min_capacity = *self.receivers.values().min()?;
+
+        if (sender.capacity <= LOW_CAPACITY) && (min_capacity > sender.capacity) {
+            let diff = min_capacity - sender.capacity;
+            sender.capacity = min_capacity;
+            Some((&sender.owner, diff as u32))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BroadcastSender {
+    owner: ConnectionId,
+    capacity: i64,
+}
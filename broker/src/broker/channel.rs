@@ -3,12 +3,18 @@ use crate::core::message::{ClaimChannelEndResult, CloseChannelEndResult};
 use crate::core::ChannelEnd;
 use std::mem;
 
-const LOW_CAPACITY: u32 = 4;
+const LOW_CAPACITY: i64 = 4;
 
 #[derive(Debug)]
 pub(crate) struct Channel {
     sender: ChannelEndState,
     receiver: ChannelEndState,
+
+    /// Whether capacities are measured in bytes instead of items.
+    ///
+    /// In this mode, capacities can become negative, because a sender is allowed to send an item
+    /// as long as at least one byte of capacity is left.
+    bytes: bool,
 }
 
 impl Channel {
@@ -16,13 +22,30 @@ impl Channel {
         Self {
             sender: ChannelEndState::Claimed { owner, capacity: 0 },
             receiver: ChannelEndState::Unclaimed,
+            bytes: false,
         }
     }
 
-    pub fn with_claimed_receiver(owner: ConnectionId, capacity: u32) -> Self {
+    pub fn with_claimed_receiver(owner: ConnectionId, capacity: u32, bytes: bool) -> Self {
         Self {
             sender: ChannelEndState::Unclaimed,
-            receiver: ChannelEndState::Claimed { owner, capacity },
+            receiver: ChannelEndState::Claimed {
+                owner,
+                capacity: capacity.into(),
+            },
+            bytes,
+        }
+    }
+
+    pub fn owner(&self, end: ChannelEnd) -> Option<&ConnectionId> {
+        let state = match end {
+            ChannelEnd::Sender => &self.sender,
+            ChannelEnd::Receiver => &self.receiver,
+        };
+
+        match state {
+            ChannelEndState::Claimed { owner, .. } => Some(owner),
+            ChannelEndState::Unclaimed | ChannelEndState::Closed => None,
         }
     }
 
@@ -78,13 +101,18 @@ impl Channel {
     pub fn claim_sender(
         &mut self,
         conn_id: &ConnectionId,
-    ) -> Result<(&ConnectionId, u32), ClaimChannelEndResult> {
+        supports_bytes: bool,
+    ) -> Result<(&ConnectionId, ClaimChannelEndResult), ClaimChannelEndResult> {
         match self.sender {
             ChannelEndState::Unclaimed => {}
             ChannelEndState::Claimed { .. } => return Err(ClaimChannelEndResult::AlreadyClaimed),
             ChannelEndState::Closed => return Err(ClaimChannelEndResult::InvalidChannel),
         }
 
+        if self.bytes && !supports_bytes {
+            return Err(ClaimChannelEndResult::InvalidChannel);
+        }
+
         let ChannelEndState::Claimed {
             owner: ref receiver,
             capacity,
@@ -99,13 +127,23 @@ impl Channel {
             capacity,
         };
 
-        Ok((receiver, capacity))
+        // No items can have been sent yet, so the capacity is still the initial one.
+        let capacity = u32::try_from(capacity).expect("inconsistent state");
+
+        let result = if self.bytes {
+            ClaimChannelEndResult::SenderClaimedBytes(capacity)
+        } else {
+            ClaimChannelEndResult::SenderClaimed(capacity)
+        };
+
+        Ok((receiver, result))
     }
 
     pub fn claim_receiver(
         &mut self,
         conn_id: &ConnectionId,
         capacity: u32,
+        bytes: bool,
     ) -> Result<&ConnectionId, ClaimChannelEndResult> {
         match self.receiver {
             ChannelEndState::Unclaimed => {}
@@ -124,10 +162,11 @@ impl Channel {
 
         self.receiver = ChannelEndState::Claimed {
             owner: conn_id.clone(),
-            capacity,
+            capacity: capacity.into(),
         };
 
-        *sender_capacity = capacity;
+        *sender_capacity = capacity.into();
+        self.bytes = bytes;
 
         Ok(sender)
     }
@@ -135,6 +174,7 @@ impl Channel {
     pub fn send_item(
         &mut self,
         conn_id: &ConnectionId,
+        len: usize,
     ) -> Result<(&ConnectionId, Option<u32>), SendItemError> {
         let ChannelEndState::Claimed {
             owner: ref sender,
@@ -158,12 +198,22 @@ impl Channel {
             ChannelEndState::Closed => return Err(SendItemError::ReceiverClosed),
         };
 
-        if *sender_capacity == 0 {
-            debug_assert!(*receiver_capacity == 0);
+        if *sender_capacity <= 0 {
+            debug_assert!(*receiver_capacity <= 0);
             return Err(SendItemError::CapacityExhausted);
         }
 
-        debug_assert!(*sender_capacity > 0);
+        if self.bytes {
+            // Byte capacities are never batched, so both ends always agree on the capacity.
+            debug_assert_eq!(*sender_capacity, *receiver_capacity);
+
+            let len = i64::try_from(len).unwrap_or(i64::MAX);
+            *sender_capacity = sender_capacity.saturating_sub(len);
+            *receiver_capacity = receiver_capacity.saturating_sub(len);
+
+            return Ok((receiver, None));
+        }
+
         *sender_capacity -= 1;
         *receiver_capacity -= 1;
 
@@ -171,7 +221,7 @@ impl Channel {
             if (*sender_capacity <= LOW_CAPACITY) && (*receiver_capacity > *sender_capacity) {
                 let diff = *receiver_capacity - *sender_capacity;
                 *sender_capacity = *receiver_capacity;
-                Some(diff)
+                Some(diff as u32)
             } else {
                 None
             };
@@ -200,11 +250,13 @@ impl Channel {
             return Ok(None);
         }
 
-        match receiver_capacity.checked_add(capacity) {
-            Some(new_receiver_capacity) => *receiver_capacity = new_receiver_capacity,
-            None => return Err(AddCapacityError),
+        let new_receiver_capacity = *receiver_capacity + i64::from(capacity);
+        if new_receiver_capacity > i64::from(u32::MAX) {
+            return Err(AddCapacityError);
         }
 
+        *receiver_capacity = new_receiver_capacity;
+
         let ChannelEndState::Claimed {
             owner: ref sender,
             capacity: ref mut sender_capacity,
@@ -213,11 +265,11 @@ impl Channel {
             return Ok(None);
         };
 
-        if *sender_capacity <= LOW_CAPACITY {
+        if self.bytes || (*sender_capacity <= LOW_CAPACITY) {
             debug_assert!(*receiver_capacity > *sender_capacity);
             let diff = *receiver_capacity - *sender_capacity;
             *sender_capacity = *receiver_capacity;
-            Ok(Some((sender, diff)))
+            Ok(Some((sender, diff as u32)))
         } else {
             Ok(None)
         }
@@ -238,6 +290,6 @@ pub(crate) struct AddCapacityError;
 #[derive(Debug)]
 enum ChannelEndState {
     Unclaimed,
-    Claimed { owner: ConnectionId, capacity: u32 },
+    Claimed { owner: ConnectionId, capacity: i64 },
     Closed,
 }
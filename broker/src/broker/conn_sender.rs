@@ -0,0 +1,133 @@
+use crate::conn::{SendQueue, SendQueueLimit, SlowConsumerPolicy};
+use crate::core::message::Message;
+use crate::core::{CallPriority, ProtocolVersion, ServiceCookie};
+use futures_channel::mpsc::UnboundedSender;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Sending half of a connection.
+///
+/// This is shared between the broker and the [`RoutingTable`](super::RoutingTable), such that
+/// connections can route events directly to each other.
+#[derive(Debug)]
+pub(crate) struct ConnectionSender {
+    protocol_version: ProtocolVersion,
+    send: UnboundedSender<Message>,
+
+    /// Messages sent here are written to the transport ahead of those sent to `send`.
+    priority_send: UnboundedSender<Message>,
+
+    queue: Arc<SendQueue>,
+    limit: Option<SendQueueLimit>,
+    slow_consumer: AtomicBool,
+}
+
+impl ConnectionSender {
+    pub fn new(
+        protocol_version: ProtocolVersion,
+        send: UnboundedSender<Message>,
+        priority_send: UnboundedSender<Message>,
+        queue: Arc<SendQueue>,
+        limit: Option<SendQueueLimit>,
+    ) -> Self {
+        Self {
+            protocol_version,
+            send,
+            priority_send,
+            queue,
+            limit,
+            slow_consumer: AtomicBool::new(false),
+        }
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    pub fn queue(&self) -> &SendQueue {
+        &self.queue
+    }
+
+    pub fn send(&self, msg: Message) -> SendResult {
+        self.send_with_priority(msg, CallPriority::Normal)
+    }
+
+    /// Sends a message, that belongs to a function call with the given priority.
+    ///
+    /// Messages of high-priority calls overtake all other queued messages.
+    pub fn send_with_priority(&self, msg: Message, priority: CallPriority) -> SendResult {
+        let mut res = SendResult {
+            res: Ok(()),
+            dropped: None,
+            slow_consumer: false,
+        };
+
+        if let Some(limit) = self.limit {
+            if self.queue.len() >= limit.high_watermark() {
+                // Count only the transition into the slow state.
+                res.slow_consumer = !self.slow_consumer.swap(true, Ordering::Relaxed);
+
+                match limit.policy() {
+                    SlowConsumerPolicy::Alert => {}
+
+                    SlowConsumerPolicy::DropEvents => {
+                        if let Message::EmitEvent(ref msg) = msg {
+                            res.dropped = Some((msg.service_cookie, msg.event));
+                            return res;
+                        }
+                    }
+
+                    SlowConsumerPolicy::Disconnect => {
+                        self.queue.disconnect();
+                        self.send.close_channel();
+                        self.priority_send.close_channel();
+                        res.res = Err(());
+                        return res;
+                    }
+                }
+            } else {
+                self.slow_consumer.store(false, Ordering::Relaxed);
+            }
+        }
+
+        let send = match priority {
+            CallPriority::High => &self.priority_send,
+            CallPriority::Low | CallPriority::Normal => &self.send,
+        };
+
+        res.res = send.unbounded_send(msg).map_err(|_| ());
+
+        if res.res.is_ok() {
+            self.queue.push();
+        }
+
+        res
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+#[must_use]
+pub(crate) struct SendResult {
+    res: Result<(), ()>,
+    dropped: Option<(ServiceCookie, u32)>,
+    #[cfg_attr(not(feature = "statistics"), allow(dead_code))]
+    slow_consumer: bool,
+}
+
+impl SendResult {
+    pub fn into_result(self) -> Result<(), ()> {
+        self.res
+    }
+
+    /// Returns the service cookie and id of an event, that was dropped due to
+    /// [`SlowConsumerPolicy::DropEvents`].
+    pub fn dropped(self) -> Option<(ServiceCookie, u32)> {
+        self.dropped
+    }
+
+    /// Indicates whether the connection has just become a slow consumer.
+    #[cfg(feature = "statistics")]
+    pub fn slow_consumer(self) -> bool {
+        self.slow_consumer
+    }
+}
@@ -1,14 +1,19 @@
+use super::conn_sender::{ConnectionSender, SendResult};
+use super::NamespaceScope;
+use crate::conn::{SendQueue, SendQueueLimit};
 use crate::conn_id::ConnectionId;
 use crate::core::message::Message;
-use crate::core::{BusListenerCookie, ChannelCookie, ObjectCookie, ProtocolVersion, ServiceCookie};
+use crate::core::{
+    BusListenerCookie, CallPriority, ChannelCookie, ObjectCookie, ProtocolVersion, ServiceCookie,
+};
 use futures_channel::mpsc::UnboundedSender;
 use std::collections::hash_map::{Entry, HashMap};
 use std::collections::HashSet;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub(super) struct ConnectionState {
-    protocol_version: ProtocolVersion,
-    send: UnboundedSender<Message>,
+    sender: Arc<ConnectionSender>,
     objects: HashSet<ObjectCookie>,
     events: HashMap<ServiceCookie, HashSet<u32>>,
     all_events: HashSet<ServiceCookie>,
@@ -17,13 +22,31 @@ pub(super) struct ConnectionState {
     receivers: HashSet<ChannelCookie>,
     bus_listeners: HashSet<BusListenerCookie>,
     calls: HashMap<u32, (u32, ConnectionId)>,
+    name: Option<String>,
+    metadata: HashMap<String, String>,
+    namespace: NamespaceScope,
 }
 
 impl ConnectionState {
-    pub fn new(protocol_version: ProtocolVersion, send: UnboundedSender<Message>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        protocol_version: ProtocolVersion,
+        send: UnboundedSender<Message>,
+        priority_send: UnboundedSender<Message>,
+        queue: Arc<SendQueue>,
+        limit: Option<SendQueueLimit>,
+        name: Option<String>,
+        metadata: HashMap<String, String>,
+        namespace: NamespaceScope,
+    ) -> Self {
         Self {
-            protocol_version,
-            send,
+            sender: Arc::new(ConnectionSender::new(
+                protocol_version,
+                send,
+                priority_send,
+                queue,
+                limit,
+            )),
             objects: HashSet::new(),
             events: HashMap::new(),
             all_events: HashSet::new(),
@@ -32,11 +55,30 @@ impl ConnectionState {
             receivers: HashSet::new(),
             bus_listeners: HashSet::new(),
             calls: HashMap::new(),
+            name,
+            metadata,
+            namespace,
         }
     }
 
     pub fn protocol_version(&self) -> ProtocolVersion {
-        self.protocol_version
+        self.sender.protocol_version()
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    pub fn namespace(&self) -> Option<&Arc<str>> {
+        self.namespace.namespace()
+    }
+
+    pub fn is_namespace_visible(&self, namespace: Option<&str>) -> bool {
+        self.namespace.is_visible(namespace)
     }
 
     pub fn add_object(&mut self, cookie: ObjectCookie) {
@@ -53,8 +95,16 @@ impl ConnectionState {
         self.objects.iter().copied()
     }
 
-    pub fn send(&self, msg: Message) -> Result<(), ()> {
-        self.send.unbounded_send(msg).map_err(|_| ())
+    pub fn sender(&self) -> &Arc<ConnectionSender> {
+        &self.sender
+    }
+
+    pub fn queue(&self) -> &SendQueue {
+        self.sender.queue()
+    }
+
+    pub fn send_with_priority(&self, msg: Message, priority: CallPriority) -> SendResult {
+        self.sender.send_with_priority(msg, priority)
     }
 
     pub fn subscribe_event(&mut self, svc_cookie: ServiceCookie, event: u32) {
@@ -76,15 +126,6 @@ impl ConnectionState {
             .flat_map(|(&c, ids)| ids.iter().map(move |&event| (c, event)))
     }
 
-    pub fn is_subscribed_to_event(&self, svc_cookie: ServiceCookie, event: u32) -> bool {
-        self.all_events.contains(&svc_cookie)
-            || self
-                .events
-                .get(&svc_cookie)
-                .map(|s| s.contains(&event))
-                .unwrap_or(false)
-    }
-
     pub fn subscribe_all_events(&mut self, svc_cookie: ServiceCookie) {
         self.all_events.insert(svc_cookie);
     }
@@ -0,0 +1,159 @@
+use crate::core::{
+    Deserialize, DeserializeError, Deserializer, ObjectId, ProtocolVersion, Serialize,
+    SerializeError, Serializer,
+};
+use std::collections::HashMap;
+
+/// Information about a connection of the broker.
+///
+/// The list of all connections is acquired with
+/// [`BrokerHandle::list_connections`](crate::BrokerHandle::list_connections). The name and metadata
+/// are provided by clients when they connect (see e.g. `ClientBuilder::with_name` and
+/// `ClientBuilder::with_metadata` in the `aldrin` crate).
+///
+/// `ConnectionInfo` can be serialized as a struct with the fields `number` (0, `u64`), `name` (1,
+/// `Option<String>`), `metadata` (2, `HashMap<String, String>`), `objects` (3, `Vec<ObjectId>`),
+/// `protocol_version` (4, `String`, e.g. `"1.19"`), `num_services` (5, `u64`) and `namespace` (6,
+/// `Option<String>`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    number: u64,
+    name: Option<String>,
+    metadata: HashMap<String, String>,
+    namespace: Option<String>,
+    objects: Vec<ObjectId>,
+    protocol_version: ProtocolVersion,
+    num_services: usize,
+}
+
+impl ConnectionInfo {
+    pub(crate) fn new(
+        number: u64,
+        name: Option<String>,
+        metadata: HashMap<String, String>,
+        namespace: Option<String>,
+        objects: Vec<ObjectId>,
+        protocol_version: ProtocolVersion,
+        num_services: usize,
+    ) -> Self {
+        Self {
+            number,
+            name,
+            metadata,
+            namespace,
+            objects,
+            protocol_version,
+            num_services,
+        }
+    }
+
+    /// Number of the connection.
+    ///
+    /// See [`ConnectionHandle::number`](crate::ConnectionHandle::number).
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// Human-readable name, that the client provided.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Metadata, that the client provided.
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    /// Namespace of the connection.
+    ///
+    /// `None` is the default namespace.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Objects, that are owned by the connection.
+    pub fn objects(&self) -> &[ObjectId] {
+        &self.objects
+    }
+
+    /// Protocol version, that was negotiated with the client.
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    /// Number of services, that are owned by the connection.
+    pub fn num_services(&self) -> usize {
+        self.num_services
+    }
+}
+
+impl Serialize for ConnectionInfo {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        let mut serializer = serializer.serialize_struct(7)?;
+
+        serializer.serialize_field(0u32, &self.number)?;
+        serializer.serialize_field(1u32, &self.name)?;
+        serializer.serialize_field(2u32, &self.metadata)?;
+        serializer.serialize_field(3u32, &self.objects)?;
+        serializer.serialize_field(4u32, &self.protocol_version.to_string())?;
+        serializer.serialize_field(5u32, &(self.num_services as u64))?;
+        serializer.serialize_field(6u32, &self.namespace)?;
+
+        serializer.finish()
+    }
+}
+
+impl Deserialize for ConnectionInfo {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let mut deserializer = deserializer.deserialize_struct()?;
+
+        let mut number = None;
+        let mut name = None;
+        let mut metadata = HashMap::new();
+        let mut objects = Vec::new();
+        let mut protocol_version = None;
+        let mut num_services = 0;
+        let mut namespace = None;
+
+        while deserializer.has_more_fields() {
+            let deserializer = deserializer.deserialize_field()?;
+
+            match deserializer.id() {
+                0 => number = deserializer.deserialize().map(Some)?,
+                1 => name = deserializer.deserialize()?,
+                2 => metadata = deserializer.deserialize()?,
+                3 => objects = deserializer.deserialize()?,
+
+                4 => {
+                    protocol_version = deserializer
+                        .deserialize::<String>()?
+                        .parse()
+                        .map(Some)
+                        .map_err(|_| DeserializeError::InvalidSerialization)?;
+                }
+
+                5 => {
+                    num_services = deserializer
+                        .deserialize::<u64>()?
+                        .try_into()
+                        .map_err(|_| DeserializeError::InvalidSerialization)?;
+                }
+
+                6 => namespace = deserializer.deserialize()?,
+                _ => deserializer.skip()?,
+            }
+        }
+
+        deserializer.finish_with(|| {
+            Ok(Self {
+                number: number.ok_or(DeserializeError::InvalidSerialization)?,
+                name,
+                metadata,
+                namespace,
+                objects,
+                protocol_version: protocol_version.ok_or(DeserializeError::InvalidSerialization)?,
+                num_services,
+            })
+        })
+    }
+}
@@ -0,0 +1,599 @@
+use super::Broker;
+use crate::core::{
+    BusListenerCookie, ChannelCookie, ChannelEnd, ObjectCookie, ObjectId, ObjectUuid,
+    ServiceCookie, ServiceId, ServiceUuid,
+};
+use futures_channel::mpsc;
+use futures_core::stream::{FusedStream, Stream};
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+/// Result of a consistency check of the broker's internal state.
+///
+/// Reports are acquired on demand with
+/// [`BrokerHandle::check_consistency`](crate::BrokerHandle::check_consistency) or periodically with
+/// [`BrokerHandle::consistency_reports`](crate::BrokerHandle::consistency_reports).
+#[derive(Debug, Clone)]
+pub struct ConsistencyReport {
+    timestamp: Instant,
+    events: u64,
+    inconsistencies: Vec<Inconsistency>,
+}
+
+impl ConsistencyReport {
+    /// The [`Instant`] when the check was performed.
+    pub fn timestamp(&self) -> Instant {
+        self.timestamp
+    }
+
+    /// Number of events the broker has processed before the check was performed.
+    pub fn events(&self) -> u64 {
+        self.events
+    }
+
+    /// Indicates whether no inconsistencies were found.
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+
+    /// Returns all inconsistencies that were found.
+    pub fn inconsistencies(&self) -> &[Inconsistency] {
+        &self.inconsistencies
+    }
+
+    /// Converts the report into the inconsistencies that were found.
+    pub fn into_inconsistencies(self) -> Vec<Inconsistency> {
+        self.inconsistencies
+    }
+}
+
+/// Inconsistency in the broker's internal state.
+///
+/// Each variant describes a broken cross-reference between the broker's objects, services, function
+/// calls, channels, bus listeners and connections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Inconsistency {
+    /// The number of object cookies and objects differ.
+    ObjectCountMismatch {
+        /// Number of object cookies.
+        cookies: usize,
+
+        /// Number of objects.
+        objects: usize,
+    },
+
+    /// An object cookie refers to an object that doesn't exist.
+    ObjectMissing(ObjectId),
+
+    /// An object is owned by a connection that doesn't exist.
+    ObjectOwnerMissing(ObjectId),
+
+    /// An object is not known to its owning connection.
+    ObjectNotOwned(ObjectId),
+
+    /// A connection owns an object that doesn't exist.
+    OwnedObjectMissing(ObjectCookie),
+
+    /// The number of service cookies and services differ.
+    ServiceCountMismatch {
+        /// Number of service cookies.
+        cookies: usize,
+
+        /// Number of services.
+        services: usize,
+    },
+
+    /// A service cookie refers to a service that doesn't exist.
+    ServiceMissing(ServiceId),
+
+    /// A service belongs to an object that doesn't exist.
+    ServiceObjectMissing(ServiceId),
+
+    /// A service is not known to its object.
+    ServiceNotInObject(ServiceId),
+
+    /// An object contains a service that doesn't exist.
+    ObjectServiceMissing {
+        /// Id of the object.
+        object: ObjectId,
+
+        /// Cookie of the missing service.
+        service: ServiceCookie,
+    },
+
+    /// A service refers to a function call that doesn't exist.
+    ServiceCallMissing {
+        /// Id of the service.
+        service: ServiceId,
+
+        /// Serial of the missing function call.
+        serial: u32,
+    },
+
+    /// A service has a subscriber, whose connection doesn't exist.
+    SubscriberMissing(ServiceId),
+
+    /// A function call targets a service that doesn't exist.
+    CallServiceMissing {
+        /// Serial of the function call.
+        serial: u32,
+
+        /// UUID of the object of the missing service.
+        object: ObjectUuid,
+
+        /// UUID of the missing service.
+        service: ServiceUuid,
+    },
+
+    /// A function call is not known to its service.
+    CallNotInService {
+        /// Serial of the function call.
+        serial: u32,
+
+        /// Id of the service.
+        service: ServiceId,
+    },
+
+    /// A function call was made by a connection that doesn't exist.
+    CallerMissing {
+        /// Serial of the function call.
+        serial: u32,
+    },
+
+    /// A connection refers to a function call that doesn't exist.
+    ConnectionCallMissing {
+        /// Serial of the missing function call.
+        serial: u32,
+    },
+
+    /// A channel end is claimed by a connection that doesn't exist.
+    ChannelOwnerMissing {
+        /// Cookie of the channel.
+        channel: ChannelCookie,
+
+        /// End of the channel.
+        end: ChannelEnd,
+    },
+
+    /// A channel end is not known to its owning connection.
+    ChannelEndNotOwned {
+        /// Cookie of the channel.
+        channel: ChannelCookie,
+
+        /// End of the channel.
+        end: ChannelEnd,
+    },
+
+    /// A connection owns a channel end that doesn't exist.
+    OwnedChannelEndMissing {
+        /// Cookie of the channel.
+        channel: ChannelCookie,
+
+        /// End of the channel.
+        end: ChannelEnd,
+    },
+
+    /// A bus listener is owned by a connection that doesn't exist.
+    BusListenerOwnerMissing(BusListenerCookie),
+
+    /// A bus listener is not known to its owning connection.
+    BusListenerNotOwned(BusListenerCookie),
+
+    /// A connection owns a bus listener that doesn't exist.
+    OwnedBusListenerMissing(BusListenerCookie),
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ObjectCountMismatch { cookies, objects } => write!(
+                f,
+                "number of object cookies ({cookies}) and objects ({objects}) differ"
+            ),
+
+            Self::ObjectMissing(id) => write!(f, "object {} is missing", id.uuid),
+            Self::ObjectOwnerMissing(id) => write!(f, "owner of object {} is missing", id.uuid),
+
+            Self::ObjectNotOwned(id) => {
+                write!(f, "object {} is unknown to its owner", id.uuid)
+            }
+
+            Self::OwnedObjectMissing(cookie) => {
+                write!(
+                    f,
+                    "object with cookie {cookie} owned by a connection is missing"
+                )
+            }
+
+            Self::ServiceCountMismatch { cookies, services } => write!(
+                f,
+                "number of service cookies ({cookies}) and services ({services}) differ"
+            ),
+
+            Self::ServiceMissing(id) => write!(f, "service {} is missing", id.uuid),
+
+            Self::ServiceObjectMissing(id) => {
+                write!(f, "object of service {} is missing", id.uuid)
+            }
+
+            Self::ServiceNotInObject(id) => {
+                write!(f, "service {} is unknown to its object", id.uuid)
+            }
+
+            Self::ObjectServiceMissing { object, service } => write!(
+                f,
+                "service with cookie {service} of object {} is missing",
+                object.uuid
+            ),
+
+            Self::ServiceCallMissing { service, serial } => write!(
+                f,
+                "function call {serial} of service {} is missing",
+                service.uuid
+            ),
+
+            Self::SubscriberMissing(id) => {
+                write!(f, "subscriber of service {} is missing", id.uuid)
+            }
+
+            Self::CallServiceMissing {
+                serial, service, ..
+            } => {
+                write!(f, "service {service} of function call {serial} is missing")
+            }
+
+            Self::CallNotInService { serial, service } => write!(
+                f,
+                "function call {serial} is unknown to service {}",
+                service.uuid
+            ),
+
+            Self::CallerMissing { serial } => {
+                write!(f, "caller of function call {serial} is missing")
+            }
+
+            Self::ConnectionCallMissing { serial } => {
+                write!(f, "function call {serial} of a connection is missing")
+            }
+
+            Self::ChannelOwnerMissing { channel, end } => {
+                write!(f, "owner of {end:?} of channel {channel} is missing")
+            }
+
+            Self::ChannelEndNotOwned { channel, end } => {
+                write!(f, "{end:?} of channel {channel} is unknown to its owner")
+            }
+
+            Self::OwnedChannelEndMissing { channel, end } => write!(
+                f,
+                "{end:?} of channel {channel} owned by a connection is missing"
+            ),
+
+            Self::BusListenerOwnerMissing(cookie) => {
+                write!(f, "owner of bus listener {cookie} is missing")
+            }
+
+            Self::BusListenerNotOwned(cookie) => {
+                write!(f, "bus listener {cookie} is unknown to its owner")
+            }
+
+            Self::OwnedBusListenerMissing(cookie) => {
+                write!(f, "bus listener {cookie} owned by a connection is missing")
+            }
+        }
+    }
+}
+
+/// Stream of failed [`ConsistencyReport`s](ConsistencyReport).
+///
+/// This type is acquired by
+/// [`BrokerHandle::consistency_reports`](crate::BrokerHandle::consistency_reports). Only reports
+/// with at least one inconsistency are delivered. The stream is bounded. If it is full, then
+/// further reports are dropped.
+///
+/// The stream ends when the broker shuts down or when another `ConsistencyReports` stream is
+/// installed.
+#[derive(Debug)]
+pub struct ConsistencyReports(mpsc::Receiver<ConsistencyReport>);
+
+impl ConsistencyReports {
+    pub(crate) fn new(interval: NonZeroUsize, capacity: usize) -> (ConsistencyChecker, Self) {
+        let (send, recv) = mpsc::channel(capacity);
+
+        let checker = ConsistencyChecker {
+            send,
+            interval,
+            countdown: interval.get(),
+        };
+
+        (checker, Self(recv))
+    }
+}
+
+impl Stream for ConsistencyReports {
+    type Item = ConsistencyReport;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<ConsistencyReport>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl FusedStream for ConsistencyReports {
+    fn is_terminated(&self) -> bool {
+        self.0.is_terminated()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ConsistencyChecker {
+    send: mpsc::Sender<ConsistencyReport>,
+    interval: NonZeroUsize,
+    countdown: usize,
+}
+
+impl ConsistencyChecker {
+    /// Counts an event and returns `true` if a check is due.
+    pub fn tick(&mut self) -> bool {
+        self.countdown -= 1;
+
+        if self.countdown == 0 {
+            self.countdown = self.interval.get();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Sends a report and returns `false` if the stream has been closed.
+    pub fn send(&mut self, report: ConsistencyReport) -> bool {
+        match self.send.try_send(report) {
+            Ok(()) => true,
+            Err(e) => e.is_full(),
+        }
+    }
+}
+
+impl Broker {
+    pub(super) fn check_consistency(&self) -> ConsistencyReport {
+        let mut inconsistencies = Vec::new();
+
+        self.check_objects(&mut inconsistencies);
+        self.check_services(&mut inconsistencies);
+        self.check_function_calls(&mut inconsistencies);
+        self.check_channels(&mut inconsistencies);
+        self.check_bus_listeners(&mut inconsistencies);
+        self.check_connections(&mut inconsistencies);
+
+        ConsistencyReport {
+            timestamp: Instant::now(),
+            events: self.events,
+            inconsistencies,
+        }
+    }
+
+    fn check_objects(&self, res: &mut Vec<Inconsistency>) {
+        if self.obj_uuids.len() != self.objs.len() {
+            res.push(Inconsistency::ObjectCountMismatch {
+                cookies: self.obj_uuids.len(),
+                objects: self.objs.len(),
+            });
+        }
+
+        for (&cookie, &uuid) in &self.obj_uuids {
+            let id = ObjectId::new(uuid, cookie);
+
+            let Some(obj) = self.objs.get(&uuid) else {
+                res.push(Inconsistency::ObjectMissing(id));
+                continue;
+            };
+
+            match self.conns.get(obj.conn_id()) {
+                Some(conn) => {
+                    if !conn.objects().any(|obj| obj == cookie) {
+                        res.push(Inconsistency::ObjectNotOwned(id));
+                    }
+                }
+
+                None => res.push(Inconsistency::ObjectOwnerMissing(id)),
+            }
+
+            for service in obj.services() {
+                if !self.svc_uuids.contains_key(&service) {
+                    res.push(Inconsistency::ObjectServiceMissing {
+                        object: id,
+                        service,
+                    });
+                }
+            }
+        }
+    }
+
+    fn check_services(&self, res: &mut Vec<Inconsistency>) {
+        if self.svc_uuids.len() != self.svcs.len() {
+            res.push(Inconsistency::ServiceCountMismatch {
+                cookies: self.svc_uuids.len(),
+                services: self.svcs.len(),
+            });
+        }
+
+        for (&cookie, &(object_id, uuid, _)) in &self.svc_uuids {
+            let id = ServiceId::new(object_id, uuid, cookie);
+
+            let Some(svc) = self.svcs.get(&(object_id.uuid, uuid)) else {
+                res.push(Inconsistency::ServiceMissing(id));
+                continue;
+            };
+
+            match self.objs.get(&object_id.uuid) {
+                Some(obj) if self.obj_uuids.get(&object_id.cookie) == Some(&object_id.uuid) => {
+                    if !obj.services().any(|svc| svc == cookie) {
+                        res.push(Inconsistency::ServiceNotInObject(id));
+                    }
+                }
+
+                _ => res.push(Inconsistency::ServiceObjectMissing(id)),
+            }
+
+            for serial in svc.function_calls() {
+                match self.function_calls.get(serial) {
+                    Some(call)
+                        if (call.callee_obj == object_id.uuid) && (call.callee_svc == uuid) => {}
+
+                    _ => res.push(Inconsistency::ServiceCallMissing {
+                        service: id,
+                        serial,
+                    }),
+                }
+            }
+
+            if svc
+                .subscribed_conn_ids()
+                .chain(svc.all_events_conn_ids())
+                .any(|conn_id| !self.conns.contains_key(conn_id))
+            {
+                res.push(Inconsistency::SubscriberMissing(id));
+            }
+        }
+    }
+
+    fn check_function_calls(&self, res: &mut Vec<Inconsistency>) {
+        for (serial, call) in self.function_calls.iter() {
+            match self.svcs.get(&(call.callee_obj, call.callee_svc)) {
+                Some(svc) => {
+                    if !svc.function_calls().any(|call| call == serial) {
+                        let service = self
+                            .svc_uuids
+                            .iter()
+                            .find(|(_, (obj, svc, _))| {
+                                (obj.uuid == call.callee_obj) && (*svc == call.callee_svc)
+                            })
+                            .map(|(&cookie, &(obj, svc, _))| ServiceId::new(obj, svc, cookie));
+
+                        if let Some(service) = service {
+                            res.push(Inconsistency::CallNotInService { serial, service });
+                        }
+                    }
+                }
+
+                None => res.push(Inconsistency::CallServiceMissing {
+                    serial,
+                    object: call.callee_obj,
+                    service: call.callee_svc,
+                }),
+            }
+
+            if !self.conns.contains_key(&call.caller_conn_id) {
+                res.push(Inconsistency::CallerMissing { serial });
+            }
+        }
+    }
+
+    fn check_channels(&self, res: &mut Vec<Inconsistency>) {
+        for (&channel, chann) in &self.channels {
+            for end in [ChannelEnd::Sender, ChannelEnd::Receiver] {
+                let Some(owner) = chann.owner(end) else {
+                    continue;
+                };
+
+                let Some(conn) = self.conns.get(owner) else {
+                    res.push(Inconsistency::ChannelOwnerMissing { channel, end });
+                    continue;
+                };
+
+                let owned = match end {
+                    ChannelEnd::Sender => conn.senders().any(|c| c == channel),
+                    ChannelEnd::Receiver => conn.receivers().any(|c| c == channel),
+                };
+
+                if !owned {
+                    res.push(Inconsistency::ChannelEndNotOwned { channel, end });
+                }
+            }
+        }
+
+        for (&channel, broadcast) in &self.broadcasts {
+            let ends = broadcast
+                .sender()
+                .map(|owner| (ChannelEnd::Sender, owner))
+                .into_iter()
+                .chain(
+                    broadcast
+                        .receivers()
+                        .map(|owner| (ChannelEnd::Receiver, owner)),
+                );
+
+            for (end, owner) in ends {
+                let Some(conn) = self.conns.get(owner) else {
+                    res.push(Inconsistency::ChannelOwnerMissing { channel, end });
+                    continue;
+                };
+
+                let owned = match end {
+                    ChannelEnd::Sender => conn.senders().any(|c| c == channel),
+                    ChannelEnd::Receiver => conn.receivers().any(|c| c == channel),
+                };
+
+                if !owned {
+                    res.push(Inconsistency::ChannelEndNotOwned { channel, end });
+                }
+            }
+        }
+    }
+
+    fn check_bus_listeners(&self, res: &mut Vec<Inconsistency>) {
+        for (&cookie, bus_listener) in &self.bus_listeners {
+            match self.conns.get(bus_listener.conn_id()) {
+                Some(conn) => {
+                    if !conn.bus_listeners().any(|c| c == cookie) {
+                        res.push(Inconsistency::BusListenerNotOwned(cookie));
+                    }
+                }
+
+                None => res.push(Inconsistency::BusListenerOwnerMissing(cookie)),
+            }
+        }
+    }
+
+    fn check_connections(&self, res: &mut Vec<Inconsistency>) {
+        for conn in self.conns.values() {
+            for cookie in conn.objects() {
+                if !self.obj_uuids.contains_key(&cookie) {
+                    res.push(Inconsistency::OwnedObjectMissing(cookie));
+                }
+            }
+
+            for (serial, _) in conn.calls() {
+                if self.function_calls.get(serial).is_none() {
+                    res.push(Inconsistency::ConnectionCallMissing { serial });
+                }
+            }
+
+            for (end, channels) in [
+                (ChannelEnd::Sender, conn.senders().collect::<Vec<_>>()),
+                (ChannelEnd::Receiver, conn.receivers().collect()),
+            ] {
+                for channel in channels {
+                    if !self.channels.contains_key(&channel)
+                        && !self.broadcasts.contains_key(&channel)
+                    {
+                        res.push(Inconsistency::OwnedChannelEndMissing { channel, end });
+                    }
+                }
+            }
+
+            for cookie in conn.bus_listeners() {
+                if !self.bus_listeners.contains_key(&cookie) {
+                    res.push(Inconsistency::OwnedBusListenerMissing(cookie));
+                }
+            }
+        }
+    }
+}
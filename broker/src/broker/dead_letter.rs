@@ -0,0 +1,158 @@
+use crate::core::ServiceCookie;
+use futures_channel::mpsc;
+use futures_core::stream::{FusedStream, Stream};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+/// A function call or event that the broker could not deliver.
+///
+/// Dead letters are reported on a [`DeadLetters`] stream, which can be acquired with
+/// [`BrokerHandle::dead_letters`](crate::BrokerHandle::dead_letters).
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    timestamp: Instant,
+    kind: DeadLetterKind,
+    service: ServiceCookie,
+    id: u32,
+    num_lost: usize,
+}
+
+impl DeadLetter {
+    pub(super) fn new(kind: DeadLetterKind, service: ServiceCookie, id: u32) -> Self {
+        Self {
+            timestamp: Instant::now(),
+            kind,
+            service,
+            id,
+            num_lost: 0,
+        }
+    }
+
+    /// The [`Instant`] when the broker dropped the message.
+    pub fn timestamp(&self) -> Instant {
+        self.timestamp
+    }
+
+    /// The reason why the message was dropped.
+    pub fn kind(&self) -> DeadLetterKind {
+        self.kind
+    }
+
+    /// Cookie of the service targeted by the function call or that emitted the event.
+    pub fn service(&self) -> ServiceCookie {
+        self.service
+    }
+
+    /// Id of the function or event.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Number of dead letters that were lost immediately before this one.
+    ///
+    /// Dead letters are lost when the [`DeadLetters`] stream is full.
+    pub fn num_lost(&self) -> usize {
+        self.num_lost
+    }
+}
+
+/// Reason why a [`DeadLetter`] was dropped.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DeadLetterKind {
+    /// A function was called on a service that doesn't exist (anymore).
+    CallInvalidService,
+
+    /// A pending function call was aborted because the service was destroyed.
+    CallServiceDestroyed,
+
+    /// A function call was rejected, because the service has too many outstanding calls.
+    CallOverloaded,
+
+    /// An event was emitted on a service that doesn't exist (anymore).
+    EventInvalidService,
+
+    /// An event could not be delivered to a subscriber, because its connection is shutting down.
+    EventUndeliverable,
+
+    /// An event was dropped for a slow subscriber.
+    ///
+    /// See [`SlowConsumerPolicy::DropEvents`](crate::SlowConsumerPolicy::DropEvents).
+    EventDropped,
+}
+
+/// Stream of [`DeadLetter`s](DeadLetter).
+///
+/// This type is acquired by [`BrokerHandle::dead_letters`](crate::BrokerHandle::dead_letters). The
+/// stream is bounded. If it is full, the broker drops further dead letters and reports the number
+/// of lost ones with the next dead letter (see [`DeadLetter::num_lost`]).
+///
+/// The stream ends when the broker shuts down or when another `DeadLetters` stream is installed.
+#[derive(Debug)]
+pub struct DeadLetters(mpsc::Receiver<DeadLetter>);
+
+impl DeadLetters {
+    pub(crate) fn new(capacity: usize) -> (DeadLetterSink, Self) {
+        let (send, recv) = mpsc::channel(capacity);
+        (DeadLetterSink::new(send), Self(recv))
+    }
+}
+
+impl Stream for DeadLetters {
+    type Item = DeadLetter;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<DeadLetter>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl FusedStream for DeadLetters {
+    fn is_terminated(&self) -> bool {
+        self.0.is_terminated()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DeadLetterSink {
+    send: mpsc::Sender<DeadLetter>,
+    num_lost: usize,
+}
+
+impl DeadLetterSink {
+    fn new(send: mpsc::Sender<DeadLetter>) -> Self {
+        Self { send, num_lost: 0 }
+    }
+
+    /// Sends a dead letter to an optional sink and removes the sink if it has been closed.
+    pub fn send_to(sink: &mut Option<Self>, letter: DeadLetter) {
+        if let Some(ref mut send) = sink {
+            if !send.send(letter) {
+                *sink = None;
+            }
+        }
+    }
+
+    /// Sends a dead letter and returns `false` if the sink has been closed.
+    pub fn send(&mut self, mut letter: DeadLetter) -> bool {
+        letter.num_lost = self.num_lost;
+
+        match self.send.try_send(letter) {
+            Ok(()) => {
+                self.num_lost = 0;
+                true
+            }
+
+            Err(e) if e.is_full() => {
+                self.num_lost = self.num_lost.saturating_add(1);
+                true
+            }
+
+            Err(_) => false,
+        }
+    }
+}
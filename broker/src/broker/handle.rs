@@ -1,21 +1,42 @@
-use super::BrokerShutdown;
 #[cfg(feature = "statistics")]
 use super::BrokerStatistics;
-use crate::conn::{Connection, ConnectionEvent, ConnectionHandle, EstablishError};
+#[cfg(feature = "history")]
+use super::HistoryEntry;
+use super::{
+    BrokerShutdown, ConnectionInfo, DeadLetters, FailoverEvents, NamespaceScope, RoutingTable,
+};
+#[cfg(feature = "consistency-check")]
+use super::{ConsistencyReport, ConsistencyReports};
+use crate::auth::{AuthStep, Authenticator};
+use crate::conn::{
+    Connection, ConnectionEvent, ConnectionHandle, EstablishError, SendQueue, SendQueueLimit,
+};
 use crate::conn_id::ConnectionIdManager;
-use crate::core::message::{ConnectData, ConnectReply, ConnectReply2, ConnectReplyData, Message};
+use crate::core::message::{
+    AuthChallenge, ConnectData, ConnectReply, ConnectReply2, ConnectReplyData, Message,
+};
 use crate::core::transport::{AsyncTransport, AsyncTransportExt};
 use crate::core::{
-    Deserialize, DeserializeError, ProtocolVersion, Serialize, SerializedValue,
-    SerializedValueSlice,
+    AuthRejection, Deserialize, DeserializeError, ObjectUuid, ProtocolVersion, Serialize,
+    SerializedValue, SerializedValueSlice, ServiceUuid,
 };
+#[cfg(feature = "introspection")]
+use crate::{IntrospectionConflicts, IntrospectionDump};
 use futures_channel::mpsc;
-#[cfg(feature = "statistics")]
 use futures_channel::oneshot;
+use futures_util::future::{self, Either};
 use futures_util::sink::SinkExt;
+use std::collections::HashMap;
+use std::future::Future;
+#[cfg(feature = "consistency-check")]
+use std::num::NonZeroUsize;
+use std::pin::{pin, Pin};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
 
 const PROTOCOL_VERSION_MIN: ProtocolVersion = ProtocolVersion::V1_14;
-const PROTOCOL_VERSION_MAX: ProtocolVersion = ProtocolVersion::V1_18;
+const PROTOCOL_VERSION_MAX: ProtocolVersion = ProtocolVersion::V1_19;
 
 /// Handle of an active broker.
 ///
@@ -29,13 +50,15 @@ const PROTOCOL_VERSION_MAX: ProtocolVersion = ProtocolVersion::V1_18;
 pub struct BrokerHandle {
     send: mpsc::Sender<ConnectionEvent>,
     ids: ConnectionIdManager,
+    routes: RoutingTable,
 }
 
 impl BrokerHandle {
-    pub(crate) fn new(send: mpsc::Sender<ConnectionEvent>) -> Self {
+    pub(crate) fn new(send: mpsc::Sender<ConnectionEvent>, routes: RoutingTable) -> Self {
         Self {
             send,
             ids: ConnectionIdManager::new(),
+            routes,
         }
     }
 
@@ -98,6 +121,16 @@ impl BrokerHandle {
                 Message::Connect(msg) => {
                     let data = ConnectData {
                         user: Some(msg.value),
+                        auth: None,
+                        compression: Vec::new(),
+                        fd_passing: false,
+                        wire_formats: Vec::new(),
+                        max_message_size: None,
+                        name: None,
+                        metadata: HashMap::new(),
+                        session: None,
+                        namespace: None,
+                        visible_namespaces: Vec::new(),
                     };
 
                     (false, data, ProtocolVersion::MAJOR, msg.version)
@@ -248,6 +281,219 @@ impl BrokerHandle {
             .map_err(|_| BrokerShutdown)
     }
 
+    /// Shuts down a specific connection by its number.
+    ///
+    /// This is like [`shutdown_connection`](Self::shutdown_connection), but doesn't require a
+    /// [`ConnectionHandle`]. It allows e.g. administrative services to disconnect misbehaving
+    /// clients, which were found with [`list_connections`](Self::list_connections).
+    ///
+    /// Returns `false` if there is no connection with the number `number`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin_test::tokio::TestBroker;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker_handle = TestBroker::new();
+    /// // Disconnect all clients, that own more than 100 services.
+    /// for conn in broker_handle.list_connections().await? {
+    ///     if conn.num_services() > 100 {
+    ///         broker_handle.shutdown_connection_by_number(conn.number()).await?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown_connection_by_number(
+        &mut self,
+        number: u64,
+    ) -> Result<bool, BrokerShutdown> {
+        let (send, recv) = oneshot::channel();
+        self.send
+            .send(ConnectionEvent::ShutdownConnectionByNumber(number, send))
+            .await
+            .map_err(|_| BrokerShutdown)?;
+        recv.await.map_err(|_| BrokerShutdown)
+    }
+
+    /// Installs a sink for dead letters and returns a stream of them.
+    ///
+    /// Dead letters are function calls and events that the broker could not deliver, e.g. because
+    /// the targeted service has just been destroyed. See [`DeadLetterKind`](crate::DeadLetterKind)
+    /// for all possible reasons.
+    ///
+    /// The returned stream buffers up to roughly `capacity` dead letters. If it is full, further
+    /// dead letters are lost, and their number is reported with the next one that fits (see
+    /// [`DeadLetter::num_lost`](crate::DeadLetter::num_lost)).
+    ///
+    /// There can only be one dead letter sink at a time. Calling this function again ends any
+    /// previously returned stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin_test::tokio::TestBroker;
+    /// use futures_util::stream::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker_handle = TestBroker::new();
+    /// let mut dead_letters = broker_handle.dead_letters(16).await?;
+    ///
+    /// # broker_handle.shutdown().await;
+    /// while let Some(letter) = dead_letters.next().await {
+    ///     println!("Dropped {:?} for service {}.", letter.kind(), letter.service());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn dead_letters(&mut self, capacity: usize) -> Result<DeadLetters, BrokerShutdown> {
+        let (sink, dead_letters) = DeadLetters::new(capacity);
+
+        self.send
+            .send(ConnectionEvent::SetDeadLetterSink(sink))
+            .await
+            .map_err(|_| BrokerShutdown)?;
+
+        Ok(dead_letters)
+    }
+
+    /// Registers a connection as standby for an object and its services.
+    ///
+    /// When the connection, that owns the object `object` (the primary), dies, then the broker
+    /// promotes the standby. From then on, only the standby can create the object, until it has
+    /// done so. This allows the standby to take over the object and its `services` without racing
+    /// against other connections, e.g. a restarted primary.
+    ///
+    /// The standby must still notice the primary's death itself, e.g. with a discoverer. The broker
+    /// reports the progress of each failover on the [`FailoverEvents`] stream returned by
+    /// [`failover_events`](Self::failover_events).
+    ///
+    /// There can only be one standby per object. Registering another one replaces the previous
+    /// standby. Registrations end when the standby's connection shuts down, when the failover has
+    /// completed, or with [`unregister_standby`](Self::unregister_standby). Registrations of
+    /// connections, which have already shut down, are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin_test::tokio::TestBroker;
+    /// use aldrin_broker::core::{ObjectUuid, ServiceUuid};
+    /// use aldrin_broker::FailoverEvent;
+    /// use futures_util::stream::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker_handle = TestBroker::new();
+    /// # let (t, t2) = aldrin_broker::core::channel::unbounded();
+    /// # let client_join = tokio::spawn(aldrin::Client::connect(t2));
+    /// let mut failover_events = broker_handle.failover_events().await?;
+    ///
+    /// let standby = broker_handle.connect(t).await?;
+    /// let object = ObjectUuid::new_v4();
+    /// let service = ServiceUuid::new_v4();
+    ///
+    /// broker_handle
+    ///     .register_standby(standby.handle(), object, vec![service])
+    ///     .await?;
+    /// # let standby_handle = standby.handle().clone();
+    /// # tokio::spawn(standby.run());
+    /// # tokio::spawn(client_join.await??.run());
+    /// # broker_handle.shutdown_connection(&standby_handle).await?;
+    ///
+    /// while let Some(event) = failover_events.next().await {
+    ///     match event {
+    ///         FailoverEvent::Promoted(failover) => {
+    ///             println!("Connection {} takes over.", failover.standby());
+    ///         }
+    ///
+    ///         FailoverEvent::Completed(failover) => {
+    ///             println!("Connection {} took over.", failover.standby());
+    ///         }
+    ///
+    ///         FailoverEvent::StandbyLost(failover) => {
+    ///             println!("Connection {} is gone.", failover.standby());
+    ///             # break;
+    ///         }
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn register_standby(
+        &mut self,
+        standby: &ConnectionHandle,
+        object: ObjectUuid,
+        services: Vec<ServiceUuid>,
+    ) -> Result<(), BrokerShutdown> {
+        self.send
+            .send(ConnectionEvent::RegisterStandby(
+                object,
+                services,
+                standby.id().clone(),
+            ))
+            .await
+            .map_err(|_| BrokerShutdown)
+    }
+
+    /// Unregisters the standby of an object.
+    ///
+    /// See [`register_standby`](Self::register_standby).
+    pub async fn unregister_standby(&mut self, object: ObjectUuid) -> Result<(), BrokerShutdown> {
+        self.send
+            .send(ConnectionEvent::UnregisterStandby(object))
+            .await
+            .map_err(|_| BrokerShutdown)
+    }
+
+    /// Returns a stream of failover events.
+    ///
+    /// Only events, that occur after this function returns, are reported on the stream. See
+    /// [`register_standby`](Self::register_standby).
+    pub async fn failover_events(&mut self) -> Result<FailoverEvents, BrokerShutdown> {
+        let (send, failover_events) = FailoverEvents::new();
+
+        self.send
+            .send(ConnectionEvent::AddFailoverListener(send))
+            .await
+            .map_err(|_| BrokerShutdown)?;
+
+        Ok(failover_events)
+    }
+
+    /// Lists all connections of the broker.
+    ///
+    /// Connections are sorted by their [number](ConnectionInfo::number). Each
+    /// [`ConnectionInfo`] contains the name and metadata, that the client provided when it
+    /// connected, as well as the objects it currently owns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin_test::tokio::TestBroker;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker_handle = TestBroker::new();
+    /// for conn in broker_handle.list_connections().await? {
+    ///     let name = conn.name().unwrap_or("<unnamed>");
+    ///
+    ///     for obj in conn.objects() {
+    ///         println!("Object {} is owned by {name} ({}).", obj.uuid, conn.number());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_connections(&mut self) -> Result<Vec<ConnectionInfo>, BrokerShutdown> {
+        let (send, recv) = oneshot::channel();
+        self.send
+            .send(ConnectionEvent::ListConnections(send))
+            .await
+            .map_err(|_| BrokerShutdown)?;
+        recv.await.map_err(|_| BrokerShutdown)
+    }
+
     /// Gets the current broker statistics.
     ///
     /// Some statistics are measured over the time interval between two calls to this function. Such
@@ -279,6 +525,238 @@ impl BrokerHandle {
             .map_err(|_| BrokerShutdown)?;
         recv.await.map_err(|_| BrokerShutdown)
     }
+
+    /// Gets the history of objects and services.
+    ///
+    /// The broker records the creation and destruction of all objects and services, together with
+    /// a timestamp and the owning connection. Only the most recent entries are kept, 1024 by
+    /// default (see [`set_history_capacity`](Self::set_history_capacity)). Entries are returned in
+    /// the order they were recorded, oldest first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin_test::tokio::TestBroker;
+    /// use aldrin_broker::HistoryEvent;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker_handle = TestBroker::new();
+    /// for entry in broker_handle.history().await? {
+    ///     if let HistoryEvent::ObjectDestroyed(id) = entry.event() {
+    ///         println!("Object {} destroyed by connection {}.", id.uuid, entry.connection());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "history")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "history")))]
+    pub async fn history(&mut self) -> Result<Vec<HistoryEntry>, BrokerShutdown> {
+        let (send, recv) = oneshot::channel();
+        self.send
+            .send(ConnectionEvent::QueryHistory(send))
+            .await
+            .map_err(|_| BrokerShutdown)?;
+        recv.await.map_err(|_| BrokerShutdown)
+    }
+
+    /// Sets the maximum number of entries in the history.
+    ///
+    /// If the history currently holds more entries, the oldest ones are discarded. A capacity of 0
+    /// disables the history.
+    ///
+    /// See [`history`](Self::history).
+    #[cfg(feature = "history")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "history")))]
+    pub async fn set_history_capacity(&mut self, capacity: usize) -> Result<(), BrokerShutdown> {
+        self.send
+            .send(ConnectionEvent::SetHistoryCapacity(capacity))
+            .await
+            .map_err(|_| BrokerShutdown)
+    }
+
+    /// Sets the maximum number of reliable events, that are buffered per subscriber.
+    ///
+    /// Reliable events are buffered until the subscriber acknowledges them. If a subscriber falls
+    /// behind by more than `limit` events, then the oldest ones are discarded. The default limit is
+    /// 1024.
+    pub async fn set_reliable_event_buffer_limit(
+        &mut self,
+        limit: usize,
+    ) -> Result<(), BrokerShutdown> {
+        self.send
+            .send(ConnectionEvent::SetReliableEventBufferLimit(limit))
+            .await
+            .map_err(|_| BrokerShutdown)
+    }
+
+    /// Sets the time, for which the sessions of disconnected clients are kept.
+    ///
+    /// Clients, that reconnect with the same session within this window, receive all reliable
+    /// events again, that they haven't acknowledged. This includes events, that were emitted while
+    /// the client was disconnected. The default window is 60 seconds.
+    pub async fn set_reconnect_window(&mut self, window: Duration) -> Result<(), BrokerShutdown> {
+        self.send
+            .send(ConnectionEvent::SetReconnectWindow(window))
+            .await
+            .map_err(|_| BrokerShutdown)
+    }
+
+    /// Checks the consistency of the broker's internal state.
+    ///
+    /// The check verifies all cross-references between objects, services, function calls,
+    /// channels, bus listeners and connections. Inconsistencies are reported and never cause a
+    /// panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin_test::tokio::TestBroker;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker_handle = TestBroker::new();
+    /// let report = broker_handle.check_consistency().await?;
+    ///
+    /// for inconsistency in report.inconsistencies() {
+    ///     println!("Broker state is inconsistent: {inconsistency}.");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "consistency-check")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "consistency-check")))]
+    pub async fn check_consistency(&mut self) -> Result<ConsistencyReport, BrokerShutdown> {
+        let (send, recv) = oneshot::channel();
+        self.send
+            .send(ConnectionEvent::CheckConsistency(send))
+            .await
+            .map_err(|_| BrokerShutdown)?;
+        recv.await.map_err(|_| BrokerShutdown)
+    }
+
+    /// Periodically checks the consistency of the broker's internal state.
+    ///
+    /// The broker performs the same check as [`check_consistency`](Self::check_consistency) after
+    /// every `interval` events it has processed. Reports of failed checks are delivered on the
+    /// returned stream, which buffers at most `capacity` reports.
+    ///
+    /// Only one [`ConsistencyReports`] stream can be active at a time. Calling this function again
+    /// replaces the previous stream, which will then end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin_test::tokio::TestBroker;
+    /// # use futures_util::stream::StreamExt;
+    /// # use std::num::NonZeroUsize;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker_handle = TestBroker::new();
+    /// let interval = NonZeroUsize::new(1000).unwrap();
+    /// let mut reports = broker_handle.consistency_reports(interval, 4).await?;
+    ///
+    /// # broker_handle.shutdown().await;
+    /// while let Some(report) = reports.next().await {
+    ///     for inconsistency in report.inconsistencies() {
+    ///         eprintln!("Broker state is inconsistent: {inconsistency}.");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "consistency-check")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "consistency-check")))]
+    pub async fn consistency_reports(
+        &mut self,
+        interval: NonZeroUsize,
+        capacity: usize,
+    ) -> Result<ConsistencyReports, BrokerShutdown> {
+        let (checker, reports) = ConsistencyReports::new(interval, capacity);
+
+        self.send
+            .send(ConnectionEvent::SetConsistencyChecker(checker))
+            .await
+            .map_err(|_| BrokerShutdown)?;
+
+        Ok(reports)
+    }
+
+    /// Dumps the introspection database.
+    ///
+    /// The dump contains all types, whose introspection is currently known to the broker. This
+    /// includes types [pre-loaded](super::Broker::load_introspection) at startup, as well as types
+    /// registered by clients, which have been queried at least once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin_test::tokio::TestBroker;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker_handle = TestBroker::new();
+    /// let dump = broker_handle.dump_introspection().await?;
+    ///
+    /// for (type_id, _) in dump.iter() {
+    ///     println!("The introspection of type {type_id} is known.");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "introspection")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "introspection")))]
+    pub async fn dump_introspection(&mut self) -> Result<IntrospectionDump, BrokerShutdown> {
+        let (send, recv) = oneshot::channel();
+        self.send
+            .send(ConnectionEvent::DumpIntrospection(send))
+            .await
+            .map_err(|_| BrokerShutdown)?;
+        recv.await.map_err(|_| BrokerShutdown)
+    }
+
+    /// Returns a stream of introspection conflicts.
+    ///
+    /// The broker checks every introspection, that it queries from a client, against the queried
+    /// [`TypeId`](crate::core::TypeId). Mismatches are reported on the returned stream, but
+    /// otherwise don't affect the broker. Only conflicts, that occur after this function returns,
+    /// are reported. See [`IntrospectionConflict`](crate::IntrospectionConflict) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin_test::tokio::TestBroker;
+    /// # use futures_util::stream::StreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut broker_handle = TestBroker::new();
+    /// let mut conflicts = broker_handle.introspection_conflicts().await?;
+    ///
+    /// # broker_handle.shutdown().await;
+    /// while let Some(conflict) = conflicts.next().await {
+    ///     eprintln!(
+    ///         "Connection {} provided a conflicting introspection for type {}: {:?}.",
+    ///         conflict.connection(),
+    ///         conflict.type_id(),
+    ///         conflict.kind(),
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "introspection")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "introspection")))]
+    pub async fn introspection_conflicts(
+        &mut self,
+    ) -> Result<IntrospectionConflicts, BrokerShutdown> {
+        let (send, conflicts) = IntrospectionConflicts::new();
+
+        self.send
+            .send(ConnectionEvent::AddIntrospectionConflictListener(send))
+            .await
+            .map_err(|_| BrokerShutdown)?;
+
+        Ok(conflicts)
+    }
 }
 
 /// A pending client connection, that hasn't been accepted or rejected yet.
@@ -295,6 +773,7 @@ pub struct PendingConnection<T: AsyncTransport + Unpin> {
     connect2: bool,
     data: ConnectData,
     version: ProtocolVersion,
+    send_queue_limit: Option<SendQueueLimit>,
 }
 
 impl<T: AsyncTransport + Unpin> PendingConnection<T> {
@@ -311,6 +790,7 @@ impl<T: AsyncTransport + Unpin> PendingConnection<T> {
             connect2,
             data,
             version,
+            send_queue_limit: None,
         }
     }
 
@@ -324,11 +804,222 @@ impl<T: AsyncTransport + Unpin> PendingConnection<T> {
         self.data.deserialize_user()
     }
 
+    /// Returns the client's human-readable name.
+    pub fn name(&self) -> Option<&str> {
+        self.data.name.as_deref()
+    }
+
+    /// Returns the client's metadata.
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.data.metadata
+    }
+
+    /// Returns the namespace, that the client requested.
+    ///
+    /// Objects and services of the client are created in this namespace. `None` is the default
+    /// namespace, which is visible to all connections.
+    pub fn namespace(&self) -> Option<&str> {
+        self.data.namespace.as_deref()
+    }
+
+    /// Sets the namespace of the connection.
+    ///
+    /// This overrides the namespace, that the client requested.
+    pub fn set_namespace(&mut self, namespace: Option<String>) {
+        self.data.namespace = namespace;
+    }
+
+    /// Returns the patterns of other namespaces, that are visible to the client.
+    ///
+    /// A pattern is either the exact name of a namespace or a prefix followed by `*`. The
+    /// connection's own namespace and the default namespace are always visible.
+    pub fn visible_namespaces(&self) -> &[String] {
+        &self.data.visible_namespaces
+    }
+
+    /// Sets the patterns of other namespaces, that are visible to the client.
+    ///
+    /// This overrides the patterns, that the client requested. See
+    /// [`visible_namespaces`](Self::visible_namespaces) for details.
+    pub fn set_visible_namespaces(&mut self, patterns: Vec<String>) {
+        self.data.visible_namespaces = patterns;
+    }
+
+    /// Returns the client's session.
+    ///
+    /// Sessions are used to resume reliable events after a reconnect. They are ignored on protocol
+    /// versions older than 1.19.
+    pub fn session(&self) -> Option<Uuid> {
+        self.data.session
+    }
+
+    /// Returns the credentials, that the client provided for authentication.
+    ///
+    /// See [`authenticate`](Self::authenticate) for verifying them.
+    pub fn credentials(&self) -> Option<&SerializedValueSlice> {
+        self.data.auth.as_deref()
+    }
+
     /// Returns the selected protocol version for this connection.
     pub fn protocol_version(&self) -> ProtocolVersion {
         self.version
     }
 
+    /// Returns a reference to the connection's transport.
+    ///
+    /// This can be used to authenticate clients based on properties of the transport, such as the
+    /// peer credentials of Unix domain sockets (see `TokioTransport::peer_credentials`).
+    pub fn transport(&self) -> &T {
+        &self.t
+    }
+
+    /// Returns the limit of the connection's send queue.
+    pub fn send_queue_limit(&self) -> Option<SendQueueLimit> {
+        self.send_queue_limit
+    }
+
+    /// Sets the limit of the connection's send queue.
+    ///
+    /// By default, there is no limit. See [`SendQueueLimit`] for more information.
+    pub fn set_send_queue_limit(&mut self, limit: SendQueueLimit) {
+        self.send_queue_limit = Some(limit);
+    }
+
+    /// Authenticates the client.
+    ///
+    /// The [`Authenticator`] first verifies the client's [credentials](Self::credentials) and may
+    /// then exchange an arbitrary number of challenges and responses with the client.
+    ///
+    /// On success, the `PendingConnection` is returned, such that the client can then be
+    /// [accepted](Self::accept) (or still be [rejected](Self::reject)). Otherwise, the client is
+    /// informed about the [`AuthRejection`] and
+    /// [`EstablishError::AuthenticationFailed`] is returned.
+    ///
+    /// This function will wait indefinitely for the client to respond to challenges. Use
+    /// [`authenticate_with_timeout`](Self::authenticate_with_timeout) to limit the duration.
+    pub async fn authenticate<A: Authenticator>(
+        self,
+        authenticator: A,
+    ) -> Result<Self, EstablishError<T::Error>> {
+        self.authenticate_with_timeout(authenticator, future::pending())
+            .await
+    }
+
+    /// Authenticates the client with a timeout.
+    ///
+    /// This function behaves like [`authenticate`](Self::authenticate), except that the client is
+    /// rejected with [`AuthRejection::Timeout`] if `timeout` completes before the authentication
+    /// has finished.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin_broker::TokenAuthenticator;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut handle = aldrin_test::tokio::TestBroker::new();
+    /// # let (t, t2) = aldrin_broker::core::channel::unbounded();
+    /// # let client = aldrin::Client::builder(t2).with_auth(aldrin::TokenAuth::new("secret"));
+    /// # let client_join = tokio::spawn(client.connect());
+    /// let authenticator = TokenAuthenticator::new(["secret"]);
+    ///
+    /// let conn = handle
+    ///     .begin_connect(t)
+    ///     .await?
+    ///     .authenticate_with_timeout(authenticator, tokio::time::sleep(Duration::from_secs(10)))
+    ///     .await?
+    ///     .accept(None)
+    ///     .await?;
+    /// # tokio::spawn(conn.run());
+    /// # let client = client_join.await??;
+    /// # tokio::spawn(client.run());
+    /// # handle.join().await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn authenticate_with_timeout<A, F>(
+        mut self,
+        mut authenticator: A,
+        timeout: F,
+    ) -> Result<Self, EstablishError<T::Error>>
+    where
+        A: Authenticator,
+        F: Future<Output = ()>,
+    {
+        let mut timeout = pin!(timeout);
+        let mut step = authenticator.authenticate(self.data.auth.as_deref());
+
+        loop {
+            let challenge = match step {
+                AuthStep::Accept => return Ok(self),
+                AuthStep::Challenge(challenge) => challenge,
+                AuthStep::Reject(reason) => return self.reject_auth(reason).await,
+            };
+
+            // Clients, that didn't send any credentials, may not know how to handle challenges.
+            if !self.connect2 || self.data.auth.is_none() {
+                return self.reject_auth(AuthRejection::MissingCredentials).await;
+            }
+
+            let res = match future::select(
+                pin!(async {
+                    self.t
+                        .send_and_flush(AuthChallenge { value: challenge })
+                        .await?;
+
+                    self.t.receive().await
+                }),
+                timeout.as_mut(),
+            )
+            .await
+            {
+                Either::Left((res, _)) => Some(res),
+                Either::Right(((), _)) => None,
+            };
+
+            let Some(res) = res else {
+                return self.reject_auth(AuthRejection::Timeout).await;
+            };
+
+            match res.map_err(EstablishError::Transport)? {
+                Message::AuthResponse(msg) => step = authenticator.verify_response(&msg.value),
+                msg => return Err(EstablishError::UnexpectedMessageReceived(msg)),
+            }
+        }
+    }
+
+    async fn reject_auth(
+        mut self,
+        reason: AuthRejection,
+    ) -> Result<Self, EstablishError<T::Error>> {
+        if self.connect2 {
+            let _ = self
+                .t
+                .send_and_flush(Message::ConnectReply2(
+                    ConnectReply2::rejected_with_serialize_data(&ConnectReplyData {
+                        user: None,
+                        auth_rejection: Some(reason),
+                        compression: None,
+                        fd_passing: false,
+                        wire_format: None,
+                        max_message_size: None,
+                    })?,
+                ))
+                .await;
+        } else {
+            let _ = self
+                .t
+                .send_and_flush(Message::ConnectReply(ConnectReply::Rejected(
+                    SerializedValue::serialize(&())?,
+                )))
+                .await;
+        }
+
+        Err(EstablishError::AuthenticationFailed(reason))
+    }
+
     /// Accepts a client with optional user data.
     ///
     /// The resulting [`Connection`] must be [`run`](Connection::run) and polled to completion, much
@@ -338,15 +1029,59 @@ impl<T: AsyncTransport + Unpin> PendingConnection<T> {
         user_data: Option<SerializedValue>,
     ) -> Result<Connection<T>, EstablishError<T::Error>> {
         if self.connect2 {
+            let compression = self
+                .data
+                .compression
+                .iter()
+                .copied()
+                .find(|c| self.t.supported_compression().contains(c));
+
+            let fd_passing = self.data.fd_passing && self.t.supports_fd_passing();
+
+            let wire_format = self
+                .data
+                .wire_formats
+                .iter()
+                .copied()
+                .find(|f| self.t.supported_wire_formats().contains(f));
+
+            let max_message_size = self
+                .t
+                .max_message_size()
+                .and_then(|size| u32::try_from(size).ok());
+
             self.t
                 .send_and_flush(Message::ConnectReply2(
                     ConnectReply2::ok_with_serialize_data(
                         self.version.minor(),
-                        &ConnectReplyData { user: user_data },
+                        &ConnectReplyData {
+                            user: user_data,
+                            auth_rejection: None,
+                            compression,
+                            fd_passing,
+                            wire_format,
+                            max_message_size,
+                        },
                     )?,
                 ))
                 .await
                 .map_err(EstablishError::Transport)?;
+
+            if let Some(compression) = compression {
+                Pin::new(&mut self.t).enable_compression(compression);
+            }
+
+            if fd_passing {
+                Pin::new(&mut self.t).enable_fd_passing();
+            }
+
+            if let Some(wire_format) = wire_format {
+                Pin::new(&mut self.t).enable_wire_format(wire_format);
+            }
+
+            if let Some(max_message_size) = self.data.max_message_size {
+                Pin::new(&mut self.t).set_peer_max_message_size(max_message_size as usize);
+            }
         } else {
             let user_data = user_data
                 .map(Ok)
@@ -360,6 +1095,8 @@ impl<T: AsyncTransport + Unpin> PendingConnection<T> {
 
         let id = self.handle.ids.acquire();
         let (send, recv) = mpsc::unbounded();
+        let (priority_send, priority_recv) = mpsc::unbounded();
+        let queue = Arc::new(SendQueue::new());
 
         self.handle
             .send
@@ -367,11 +1104,28 @@ impl<T: AsyncTransport + Unpin> PendingConnection<T> {
                 id.clone(),
                 self.version,
                 send,
+                priority_send,
+                queue.clone(),
+                self.send_queue_limit,
+                self.data.name,
+                self.data.metadata,
+                self.data
+                    .session
+                    .filter(|_| self.version >= ProtocolVersion::V1_19),
+                NamespaceScope::new(self.data.namespace, self.data.visible_namespaces),
             ))
             .await
             .map_err(|_| EstablishError::Shutdown)?;
 
-        let conn = Connection::new(self.t, id, self.handle.send, recv);
+        let conn = Connection::new(
+            self.t,
+            id,
+            self.handle.send,
+            recv,
+            priority_recv,
+            queue,
+            self.handle.routes,
+        );
 
         Ok(conn)
     }
@@ -398,6 +1152,11 @@ impl<T: AsyncTransport + Unpin> PendingConnection<T> {
                 .send_and_flush(Message::ConnectReply2(
                     ConnectReply2::rejected_with_serialize_data(&ConnectReplyData {
                         user: user_data,
+                        auth_rejection: None,
+                        compression: None,
+                        fd_passing: false,
+                        wire_format: None,
+                        max_message_size: None,
                     })?,
                 ))
                 .await
@@ -471,7 +1230,11 @@ mod test {
         );
         assert_eq!(
             select_protocol_version(1, 19, true),
-            Some(ProtocolVersion::V1_18)
+            Some(ProtocolVersion::V1_19)
+        );
+        assert_eq!(
+            select_protocol_version(1, 20, true),
+            Some(ProtocolVersion::V1_19)
         );
         assert_eq!(select_protocol_version(1, 13, true), None);
         assert_eq!(select_protocol_version(2, 0, true), None);
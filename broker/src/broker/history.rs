@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod test;
+
+use crate::conn_id::ConnectionId;
+use crate::core::{ObjectId, ServiceCookie, ServiceId};
+use std::collections::{HashMap, VecDeque};
+use std::time::SystemTime;
+
+/// Number of entries the history keeps by default.
+pub(crate) const DEFAULT_CAPACITY: usize = 1024;
+
+/// An entry of the broker's object and service history.
+///
+/// The history is acquired with [`BrokerHandle::history`](crate::BrokerHandle::history).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    timestamp: SystemTime,
+    event: HistoryEvent,
+    conn: u64,
+}
+
+impl HistoryEntry {
+    /// The time when the broker processed the event.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// The event that was recorded.
+    pub fn event(&self) -> HistoryEvent {
+        self.event
+    }
+
+    /// Number of the connection, that owns the object or service.
+    ///
+    /// See [`ConnectionHandle::number`](crate::ConnectionHandle::number).
+    pub fn connection(&self) -> u64 {
+        self.conn
+    }
+}
+
+/// Event recorded in a [`HistoryEntry`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HistoryEvent {
+    /// An object was created.
+    ObjectCreated(ObjectId),
+
+    /// An object was destroyed.
+    ObjectDestroyed(ObjectId),
+
+    /// A service was created.
+    ServiceCreated(ServiceId),
+
+    /// A service was destroyed.
+    ServiceDestroyed(ServiceId),
+}
+
+#[derive(Debug)]
+pub(crate) struct History {
+    entries: VecDeque<HistoryEntry>,
+    capacity: usize,
+
+    /// Owning connection of each service.
+    svc_conns: HashMap<ServiceCookie, u64>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: DEFAULT_CAPACITY,
+            svc_conns: HashMap::new(),
+        }
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+
+        let excess = self.entries.len().saturating_sub(capacity);
+        self.entries.drain(..excess);
+    }
+
+    pub fn entries(&self) -> Vec<HistoryEntry> {
+        self.entries.iter().copied().collect()
+    }
+
+    pub fn object_created(&mut self, id: ObjectId, conn_id: &ConnectionId) {
+        self.push(HistoryEvent::ObjectCreated(id), conn_id.number());
+    }
+
+    pub fn object_destroyed(&mut self, id: ObjectId, conn_id: &ConnectionId) {
+        self.push(HistoryEvent::ObjectDestroyed(id), conn_id.number());
+    }
+
+    pub fn service_created(&mut self, id: ServiceId, conn_id: &ConnectionId) {
+        let conn = conn_id.number();
+        self.svc_conns.insert(id.cookie, conn);
+        self.push(HistoryEvent::ServiceCreated(id), conn);
+    }
+
+    pub fn service_destroyed(&mut self, id: ServiceId) {
+        let conn = self
+            .svc_conns
+            .remove(&id.cookie)
+            .expect("inconsistent state");
+
+        self.push(HistoryEvent::ServiceDestroyed(id), conn);
+    }
+
+    fn push(&mut self, event: HistoryEvent, conn: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(HistoryEntry {
+            timestamp: SystemTime::now(),
+            event,
+            conn,
+        });
+    }
+}
@@ -0,0 +1,73 @@
+use crate::core::{ObjectUuid, ServiceUuid};
+use aldrin::low_level::ServiceInfo;
+use aldrin_test::aldrin_broker::HistoryEvent;
+use aldrin_test::tokio::TestBroker;
+
+#[tokio::test]
+async fn objects_and_services() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+    let conn = client.connection().number();
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+    obj.destroy().await.unwrap();
+
+    let history = broker.history().await.unwrap();
+    let events = history.iter().map(|e| e.event()).collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        [
+            HistoryEvent::ObjectCreated(obj.id()),
+            HistoryEvent::ServiceCreated(svc.id()),
+            HistoryEvent::ObjectDestroyed(obj.id()),
+            HistoryEvent::ServiceDestroyed(svc.id()),
+        ]
+    );
+
+    assert!(history.iter().all(|e| e.connection() == conn));
+    assert!(history
+        .windows(2)
+        .all(|e| e[0].timestamp() <= e[1].timestamp()));
+}
+
+#[tokio::test]
+async fn connection_shutdown() {
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+    let conn = client.connection().number();
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    client.join().await;
+
+    let history = broker.history().await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].event(), HistoryEvent::ObjectDestroyed(obj.id()));
+    assert_eq!(history[1].connection(), conn);
+}
+
+#[tokio::test]
+async fn capacity() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj1 = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let obj2 = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+
+    broker.set_history_capacity(1).await.unwrap();
+    let history = broker.history().await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].event(), HistoryEvent::ObjectCreated(obj2.id()));
+
+    obj1.destroy().await.unwrap();
+    let history = broker.history().await.unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].event(), HistoryEvent::ObjectDestroyed(obj1.id()));
+
+    broker.set_history_capacity(0).await.unwrap();
+    obj2.destroy().await.unwrap();
+    assert!(broker.history().await.unwrap().is_empty());
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+/// Namespace of a connection and the namespaces, that are visible to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct NamespaceScope {
+    namespace: Option<Arc<str>>,
+    visible: Vec<String>,
+}
+
+impl NamespaceScope {
+    pub fn new(namespace: Option<String>, visible: Vec<String>) -> Self {
+        Self {
+            namespace: namespace.map(Into::into),
+            visible,
+        }
+    }
+
+    pub fn namespace(&self) -> Option<&Arc<str>> {
+        self.namespace.as_ref()
+    }
+
+    /// Checks whether objects and services in `namespace` are visible.
+    ///
+    /// The default namespace (`None`) is always visible. Other namespaces must either be the
+    /// connection's own namespace or match one of its patterns.
+    pub fn is_visible(&self, namespace: Option<&str>) -> bool {
+        let Some(namespace) = namespace else {
+            return true;
+        };
+
+        if self.namespace.as_deref() == Some(namespace) {
+            return true;
+        }
+
+        self.visible
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => namespace.starts_with(prefix),
+                None => pattern == namespace,
+            })
+    }
+}
@@ -1,17 +1,20 @@
 use crate::conn_id::ConnectionId;
 use crate::core::ServiceCookie;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub(crate) struct Object {
     conn_id: ConnectionId,
+    namespace: Option<Arc<str>>,
     svcs: HashSet<ServiceCookie>,
 }
 
 impl Object {
-    pub fn new(conn_id: ConnectionId) -> Self {
+    pub fn new(conn_id: ConnectionId, namespace: Option<Arc<str>>) -> Self {
         Self {
             conn_id,
+            namespace,
             svcs: HashSet::new(),
         }
     }
@@ -20,6 +23,10 @@ impl Object {
         &self.conn_id
     }
 
+    pub fn namespace(&self) -> Option<&Arc<str>> {
+        self.namespace.as_ref()
+    }
+
     pub fn add_service(&mut self, cookie: ServiceCookie) {
         let unique = self.svcs.insert(cookie);
         debug_assert!(unique);
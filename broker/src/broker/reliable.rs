@@ -0,0 +1,291 @@
+use crate::conn_id::ConnectionId;
+use crate::core::message::ReliableEventReceived;
+use crate::core::{SerializedValue, ServiceCookie, TraceContext};
+use std::collections::hash_map::{Entry, HashMap};
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const DEFAULT_BUFFER_LIMIT: usize = 1024;
+const DEFAULT_RECONNECT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Buffers of unacknowledged reliable events.
+///
+/// Every subscriber has its own buffer, which is keyed by the connection while it is alive. The
+/// buffers of connections with a session outlive the connection for the reconnect window and
+/// continue to record events for the session's subscriptions in the meantime.
+#[derive(Debug)]
+pub(crate) struct ReliableEvents {
+    buffer_limit: usize,
+    reconnect_window: Duration,
+    conns: HashMap<ConnectionId, Backlog>,
+    conn_sessions: HashMap<ConnectionId, Uuid>,
+    sessions: HashMap<Uuid, Backlog>,
+}
+
+impl ReliableEvents {
+    pub fn new() -> Self {
+        Self {
+            buffer_limit: DEFAULT_BUFFER_LIMIT,
+            reconnect_window: DEFAULT_RECONNECT_WINDOW,
+            conns: HashMap::new(),
+            conn_sessions: HashMap::new(),
+            sessions: HashMap::new(),
+        }
+    }
+
+    pub fn set_buffer_limit(&mut self, buffer_limit: usize) {
+        self.buffer_limit = buffer_limit;
+
+        for backlog in self.conns.values_mut().chain(self.sessions.values_mut()) {
+            backlog.truncate(buffer_limit);
+        }
+    }
+
+    pub fn set_reconnect_window(&mut self, reconnect_window: Duration) {
+        self.reconnect_window = reconnect_window;
+        self.remove_expired_sessions();
+    }
+
+    /// Registers a new connection and resumes its session.
+    ///
+    /// Sessions can be used by only one connection at a time. Connections, whose session is already
+    /// in use, are treated as if they had none.
+    pub fn add_conn(&mut self, conn_id: &ConnectionId, session: Option<Uuid>) {
+        let Some(session) = session else {
+            return;
+        };
+
+        if self.conn_sessions.values().any(|&other| other == session) {
+            return;
+        }
+
+        self.remove_expired_sessions();
+        self.conn_sessions.insert(conn_id.clone(), session);
+
+        if let Some(mut backlog) = self.sessions.remove(&session) {
+            backlog.resume();
+            self.conns.insert(conn_id.clone(), backlog);
+        }
+    }
+
+    /// Removes a connection and suspends its session.
+    pub fn remove_conn(&mut self, conn_id: &ConnectionId) {
+        let backlog = self.conns.remove(conn_id);
+
+        if let Some(session) = self.conn_sessions.remove(conn_id) {
+            let mut backlog = backlog.unwrap_or_default();
+            backlog.suspend();
+            self.sessions.insert(session, backlog);
+        }
+    }
+
+    pub fn remove_service(&mut self, service_cookie: ServiceCookie) {
+        for backlog in self.conns.values_mut().chain(self.sessions.values_mut()) {
+            backlog.remove_service(service_cookie);
+        }
+    }
+
+    /// Records a subscription and returns all events, that must be replayed.
+    ///
+    /// `event` is `None` for subscriptions of all events.
+    pub fn subscribe(
+        &mut self,
+        conn_id: &ConnectionId,
+        service_cookie: ServiceCookie,
+        event: Option<u32>,
+    ) -> Vec<ReliableEventReceived> {
+        let has_session = self.conn_sessions.contains_key(conn_id);
+
+        let backlog = match self.conns.entry(conn_id.clone()) {
+            Entry::Occupied(backlog) => backlog.into_mut(),
+            Entry::Vacant(_) if !has_session => return Vec::new(),
+            Entry::Vacant(backlog) => backlog.insert(Backlog::default()),
+        };
+
+        if has_session {
+            backlog.subscriptions.insert((service_cookie, event));
+        }
+
+        backlog.replay(service_cookie, event)
+    }
+
+    pub fn unsubscribe(
+        &mut self,
+        conn_id: &ConnectionId,
+        service_cookie: ServiceCookie,
+        event: Option<u32>,
+    ) {
+        if let Some(backlog) = self.conns.get_mut(conn_id) {
+            backlog.subscriptions.remove(&(service_cookie, event));
+        }
+    }
+
+    /// Buffers an event for a set of subscribers and returns the messages to send to them.
+    ///
+    /// The event is also buffered for all suspended sessions, that are subscribed to it.
+    pub fn emit<'a>(
+        &mut self,
+        subscribers: impl IntoIterator<Item = &'a ConnectionId>,
+        service_cookie: ServiceCookie,
+        event: u32,
+        value: &SerializedValue,
+        trace_context: Option<TraceContext>,
+    ) -> Vec<(ConnectionId, ReliableEventReceived)> {
+        let mut msgs = Vec::new();
+
+        for conn_id in subscribers {
+            let backlog = self.conns.entry(conn_id.clone()).or_default();
+
+            let msg = backlog.push(
+                service_cookie,
+                event,
+                value,
+                trace_context,
+                true,
+                self.buffer_limit,
+            );
+
+            msgs.push((conn_id.clone(), msg));
+        }
+
+        self.remove_expired_sessions();
+
+        for backlog in self.sessions.values_mut() {
+            if backlog.is_subscribed(service_cookie, event) {
+                backlog.push(
+                    service_cookie,
+                    event,
+                    value,
+                    trace_context,
+                    false,
+                    self.buffer_limit,
+                );
+            }
+        }
+
+        msgs
+    }
+
+    pub fn ack(&mut self, conn_id: &ConnectionId, serial: u32) {
+        if let Some(backlog) = self.conns.get_mut(conn_id) {
+            backlog.ack(serial);
+        }
+    }
+
+    fn remove_expired_sessions(&mut self) {
+        let reconnect_window = self.reconnect_window;
+
+        self.sessions
+            .retain(|_, backlog| !backlog.is_expired(reconnect_window));
+    }
+}
+
+#[derive(Debug, Default)]
+struct Backlog {
+    next_serial: u32,
+    events: VecDeque<BufferedEvent>,
+
+    /// Subscriptions of a session, which are kept while it is suspended.
+    subscriptions: HashSet<(ServiceCookie, Option<u32>)>,
+
+    suspended: Option<Instant>,
+}
+
+impl Backlog {
+    fn push(
+        &mut self,
+        service_cookie: ServiceCookie,
+        event: u32,
+        value: &SerializedValue,
+        trace_context: Option<TraceContext>,
+        delivered: bool,
+        buffer_limit: usize,
+    ) -> ReliableEventReceived {
+        let serial = self.next_serial;
+        self.next_serial = self.next_serial.wrapping_add(1);
+
+        let msg = ReliableEventReceived {
+            serial,
+            service_cookie,
+            event,
+            value: value.clone(),
+            trace_context,
+        };
+
+        self.events.push_back(BufferedEvent {
+            msg: msg.clone(),
+            delivered,
+        });
+
+        self.truncate(buffer_limit);
+        msg
+    }
+
+    fn truncate(&mut self, buffer_limit: usize) {
+        while self.events.len() > buffer_limit {
+            self.events.pop_front();
+        }
+    }
+
+    fn ack(&mut self, serial: u32) {
+        if let Some(idx) = self.events.iter().position(|ev| ev.msg.serial == serial) {
+            self.events.remove(idx);
+        }
+    }
+
+    fn replay(
+        &mut self,
+        service_cookie: ServiceCookie,
+        event: Option<u32>,
+    ) -> Vec<ReliableEventReceived> {
+        self.events
+            .iter_mut()
+            .filter(|ev| {
+                !ev.delivered
+                    && (ev.msg.service_cookie == service_cookie)
+                    && event.map_or(true, |event| ev.msg.event == event)
+            })
+            .map(|ev| {
+                ev.delivered = true;
+                ev.msg.clone()
+            })
+            .collect()
+    }
+
+    fn remove_service(&mut self, service_cookie: ServiceCookie) {
+        self.events
+            .retain(|ev| ev.msg.service_cookie != service_cookie);
+
+        self.subscriptions
+            .retain(|&(cookie, _)| cookie != service_cookie);
+    }
+
+    fn is_subscribed(&self, service_cookie: ServiceCookie, event: u32) -> bool {
+        self.subscriptions.contains(&(service_cookie, Some(event)))
+            || self.subscriptions.contains(&(service_cookie, None))
+    }
+
+    fn suspend(&mut self) {
+        self.suspended = Some(Instant::now());
+
+        for ev in &mut self.events {
+            ev.delivered = false;
+        }
+    }
+
+    fn resume(&mut self) {
+        self.suspended = None;
+    }
+
+    fn is_expired(&self, reconnect_window: Duration) -> bool {
+        self.suspended
+            .is_some_and(|suspended| suspended.elapsed() >= reconnect_window)
+    }
+}
+
+#[derive(Debug)]
+struct BufferedEvent {
+    msg: ReliableEventReceived,
+    delivered: bool,
+}
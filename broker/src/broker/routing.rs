@@ -0,0 +1,277 @@
+use super::conn_sender::ConnectionSender;
+#[cfg(feature = "statistics")]
+use super::conn_sender::SendResult;
+#[cfg(feature = "statistics")]
+use super::BrokerStatistics;
+use crate::conn_id::ConnectionId;
+use crate::core::message::EmitEvent;
+use crate::core::{ProtocolVersion, ServiceCookie};
+use std::collections::HashMap;
+#[cfg(feature = "statistics")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+const SHARDS: usize = 16;
+
+/// Routing table for events.
+///
+/// The table is shared by the broker and all connections and split into shards by service cookie.
+/// Connections use it to route events directly to all subscribers, without involving the broker.
+/// Only the broker modifies the table, such that it always mirrors the broker's own state.
+#[derive(Debug, Clone)]
+pub(crate) struct RoutingTable(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    shards: Box<[RwLock<HashMap<ServiceCookie, ServiceRoutes>>]>,
+
+    #[cfg(feature = "statistics")]
+    statistics: RoutingStatistics,
+}
+
+impl RoutingTable {
+    pub fn new() -> Self {
+        Self(Arc::new(Inner {
+            shards: (0..SHARDS).map(|_| RwLock::default()).collect(),
+
+            #[cfg(feature = "statistics")]
+            statistics: RoutingStatistics::default(),
+        }))
+    }
+
+    fn shard(
+        &self,
+        service_cookie: ServiceCookie,
+    ) -> &RwLock<HashMap<ServiceCookie, ServiceRoutes>> {
+        &self.0.shards[service_cookie.0.as_u128() as usize % SHARDS]
+    }
+
+    pub fn add_service(&self, service_cookie: ServiceCookie, owner: ConnectionId) {
+        let dup = self
+            .shard(service_cookie)
+            .write()
+            .expect("lock poisoned")
+            .insert(service_cookie, ServiceRoutes::new(owner));
+
+        debug_assert!(dup.is_none());
+    }
+
+    pub fn remove_service(&self, service_cookie: ServiceCookie) {
+        self.shard(service_cookie)
+            .write()
+            .expect("lock poisoned")
+            .remove(&service_cookie);
+    }
+
+    pub fn subscribe_event(
+        &self,
+        service_cookie: ServiceCookie,
+        event: u32,
+        conn_id: ConnectionId,
+        sender: Arc<ConnectionSender>,
+    ) {
+        let mut shard = self.shard(service_cookie).write().expect("lock poisoned");
+
+        if let Some(routes) = shard.get_mut(&service_cookie) {
+            routes
+                .events
+                .entry(event)
+                .or_default()
+                .insert(conn_id, sender);
+        }
+    }
+
+    pub fn unsubscribe_event(
+        &self,
+        service_cookie: ServiceCookie,
+        event: u32,
+        conn_id: &ConnectionId,
+    ) {
+        let mut shard = self.shard(service_cookie).write().expect("lock poisoned");
+
+        let Some(routes) = shard.get_mut(&service_cookie) else {
+            return;
+        };
+
+        if let Some(subs) = routes.events.get_mut(&event) {
+            subs.remove(conn_id);
+
+            if subs.is_empty() {
+                routes.events.remove(&event);
+            }
+        }
+    }
+
+    pub fn subscribe_all_events(
+        &self,
+        service_cookie: ServiceCookie,
+        conn_id: ConnectionId,
+        sender: Arc<ConnectionSender>,
+    ) {
+        let mut shard = self.shard(service_cookie).write().expect("lock poisoned");
+
+        if let Some(routes) = shard.get_mut(&service_cookie) {
+            routes.all_events.insert(conn_id, sender);
+        }
+    }
+
+    pub fn unsubscribe_all_events(&self, service_cookie: ServiceCookie, conn_id: &ConnectionId) {
+        let mut shard = self.shard(service_cookie).write().expect("lock poisoned");
+
+        if let Some(routes) = shard.get_mut(&service_cookie) {
+            routes.all_events.remove(conn_id);
+        }
+    }
+
+    /// Sends an event to all subscribers.
+    ///
+    /// Returns `None` if the service doesn't exist or isn't owned by `conn_id`. Otherwise, the
+    /// number of subscribers, that the event was sent to or dropped for, and the connections, to
+    /// which the event could not be sent, are returned.
+    ///
+    /// The shard is locked while the event is sent. The broker can thus rely on the event being
+    /// queued either entirely before or entirely after any change it makes to the table.
+    pub fn emit_event(&self, conn_id: &ConnectionId, req: &EmitEvent) -> Option<EventDelivery> {
+        let shard = self
+            .shard(req.service_cookie)
+            .read()
+            .expect("lock poisoned");
+
+        let routes = shard.get(&req.service_cookie)?;
+        if routes.owner != *conn_id {
+            return None;
+        }
+
+        #[cfg(feature = "statistics")]
+        self.0
+            .statistics
+            .events_emitted
+            .fetch_add(1, Ordering::Relaxed);
+
+        let mut delivery = EventDelivery {
+            delivered: 0,
+            dropped: 0,
+            undeliverable: Vec::new(),
+        };
+
+        for (conn_id, sender) in routes.subscribers(req.event) {
+            let mut msg = req.clone();
+            if sender.protocol_version() < ProtocolVersion::V1_19 {
+                msg.trace_context = None;
+            }
+
+            let res = sender.send(msg.into());
+
+            #[cfg(feature = "statistics")]
+            self.0.statistics.record_sent(res);
+
+            if res.into_result().is_err() {
+                delivery.undeliverable.push(conn_id.clone());
+            } else if res.dropped().is_some() {
+                delivery.dropped += 1;
+            } else {
+                delivery.delivered += 1;
+            }
+        }
+
+        Some(delivery)
+    }
+
+    /// Records a message, that was received and routed by a connection.
+    #[cfg(feature = "statistics")]
+    pub fn record_received(&self) {
+        self.0
+            .statistics
+            .messages_received
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Adds all statistics recorded by the table to `statistics` and resets them.
+    #[cfg(feature = "statistics")]
+    pub fn take_statistics(&self, statistics: &mut BrokerStatistics) {
+        self.0.statistics.take(statistics);
+    }
+}
+
+/// Outcome of sending an event to all subscribers.
+#[derive(Debug)]
+pub(crate) struct EventDelivery {
+    pub delivered: u32,
+    pub dropped: u32,
+    pub undeliverable: Vec<ConnectionId>,
+}
+
+#[derive(Debug)]
+struct ServiceRoutes {
+    owner: ConnectionId,
+    events: HashMap<u32, HashMap<ConnectionId, Arc<ConnectionSender>>>,
+    all_events: HashMap<ConnectionId, Arc<ConnectionSender>>,
+}
+
+impl ServiceRoutes {
+    fn new(owner: ConnectionId) -> Self {
+        Self {
+            owner,
+            events: HashMap::new(),
+            all_events: HashMap::new(),
+        }
+    }
+
+    /// Returns all subscribers of `event`, each exactly once.
+    fn subscribers(&self, event: u32) -> impl Iterator<Item = (&ConnectionId, &ConnectionSender)> {
+        let events = self
+            .events
+            .get(&event)
+            .into_iter()
+            .flatten()
+            .filter(|(conn_id, _)| !self.all_events.contains_key(conn_id));
+
+        self.all_events
+            .iter()
+            .chain(events)
+            .map(|(conn_id, sender)| (conn_id, &**sender))
+    }
+}
+
+#[cfg(feature = "statistics")]
+#[derive(Debug, Default)]
+struct RoutingStatistics {
+    messages_sent: AtomicUsize,
+    messages_received: AtomicUsize,
+    slow_consumers: AtomicUsize,
+    events_dropped: AtomicUsize,
+    events_emitted: AtomicUsize,
+    events_delivered: AtomicUsize,
+}
+
+#[cfg(feature = "statistics")]
+impl RoutingStatistics {
+    fn record_sent(&self, res: SendResult) {
+        if res.dropped().is_some() {
+            self.events_dropped.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.messages_sent.fetch_add(1, Ordering::Relaxed);
+
+            if res.into_result().is_ok() {
+                self.events_delivered.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        if res.slow_consumer() {
+            self.slow_consumers.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn take(&self, statistics: &mut BrokerStatistics) {
+        fn take(counter: &AtomicUsize, total: &mut usize) {
+            *total = total.saturating_add(counter.swap(0, Ordering::Relaxed));
+        }
+
+        take(&self.messages_sent, &mut statistics.messages_sent);
+        take(&self.messages_received, &mut statistics.messages_received);
+        take(&self.slow_consumers, &mut statistics.slow_consumers);
+        take(&self.events_dropped, &mut statistics.events_dropped);
+        take(&self.events_emitted, &mut statistics.events_emitted);
+        take(&self.events_delivered, &mut statistics.events_delivered);
+    }
+}
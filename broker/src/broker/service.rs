@@ -1,9 +1,14 @@
 use super::ConnectionId;
+use crate::core::SerializedValue;
 use std::collections::hash_map::{Entry, HashMap};
 use std::collections::HashSet;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub(crate) struct Service {
+    /// Namespace of the service's object.
+    namespace: Option<Arc<str>>,
+
     function_calls: HashSet<u32>,
 
     /// Map of events subscribed by a set of connections.
@@ -14,18 +19,27 @@ pub(crate) struct Service {
 
     /// Set of connections subscribed to this service.
     subscriptions: HashSet<ConnectionId>,
+
+    /// Last value of each retained event.
+    retained_events: HashMap<u32, SerializedValue>,
 }
 
 impl Service {
-    pub fn new() -> Self {
+    pub fn new(namespace: Option<Arc<str>>) -> Self {
         Self {
+            namespace,
             function_calls: HashSet::new(),
             events: HashMap::new(),
             all_events: HashSet::new(),
             subscriptions: HashSet::new(),
+            retained_events: HashMap::new(),
         }
     }
 
+    pub fn namespace(&self) -> Option<&Arc<str>> {
+        self.namespace.as_ref()
+    }
+
     pub fn add_function_call(&mut self, serial: u32) {
         let unique = self.function_calls.insert(serial);
         debug_assert!(unique);
@@ -40,6 +54,10 @@ impl Service {
         self.function_calls.iter().copied()
     }
 
+    pub fn num_function_calls(&self) -> usize {
+        self.function_calls.len()
+    }
+
     pub fn subscribe_event(&mut self, event: u32, conn_id: ConnectionId) -> bool {
         match self.events.entry(event) {
             Entry::Occupied(mut subs) => {
@@ -94,6 +112,33 @@ impl Service {
         self.subscriptions.remove(conn_id);
     }
 
+    pub fn retain_event(&mut self, event: u32, value: SerializedValue) {
+        self.retained_events.insert(event, value);
+    }
+
+    pub fn retained_event(&self, event: u32) -> Option<&SerializedValue> {
+        self.retained_events.get(&event)
+    }
+
+    pub fn retained_events(&self) -> impl Iterator<Item = (u32, &SerializedValue)> {
+        self.retained_events
+            .iter()
+            .map(|(&event, value)| (event, value))
+    }
+
+    /// Returns all connections, that are subscribed to `event`.
+    ///
+    /// This includes connections, that are subscribed to all events.
+    pub fn event_subscribers(&self, event: u32) -> impl Iterator<Item = &ConnectionId> {
+        #[allow(clippy::mutable_key_type)]
+        let mut res = HashSet::new();
+
+        res.extend(self.events.get(&event).into_iter().flatten());
+        res.extend(self.all_events.iter());
+
+        res.into_iter()
+    }
+
     pub fn subscribed_conn_ids(&self) -> impl Iterator<Item = &ConnectionId> {
         #[allow(clippy::mutable_key_type)]
         let mut res = HashSet::new();
@@ -103,4 +148,9 @@ impl Service {
 
         res.into_iter()
     }
+
+    #[cfg(feature = "consistency-check")]
+    pub fn all_events_conn_ids(&self) -> impl Iterator<Item = &ConnectionId> {
+        self.all_events.iter()
+    }
 }
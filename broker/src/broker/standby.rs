@@ -0,0 +1,218 @@
+use crate::conn_id::ConnectionId;
+use crate::core::{ObjectUuid, ServiceUuid};
+use futures_channel::mpsc;
+use futures_core::stream::{FusedStream, Stream};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Description of a failover from a primary to a standby connection.
+///
+/// See [`BrokerHandle::register_standby`](crate::BrokerHandle::register_standby).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failover {
+    object: ObjectUuid,
+    services: Vec<ServiceUuid>,
+    primary: Option<u64>,
+    standby: u64,
+}
+
+impl Failover {
+    /// UUID of the object, that the standby was registered for.
+    pub fn object(&self) -> ObjectUuid {
+        self.object
+    }
+
+    /// UUIDs of the services, that the standby was registered for.
+    pub fn services(&self) -> &[ServiceUuid] {
+        &self.services
+    }
+
+    /// Number of the primary's connection.
+    ///
+    /// This is `None` if the standby was lost before it was promoted. See
+    /// [`ConnectionHandle::number`](crate::ConnectionHandle::number).
+    pub fn primary(&self) -> Option<u64> {
+        self.primary
+    }
+
+    /// Number of the standby's connection.
+    ///
+    /// See [`ConnectionHandle::number`](crate::ConnectionHandle::number).
+    pub fn standby(&self) -> u64 {
+        self.standby
+    }
+}
+
+/// Event emitted by the broker during a failover.
+///
+/// Failover events are reported on a [`FailoverEvents`] stream, which can be acquired with
+/// [`BrokerHandle::failover_events`](crate::BrokerHandle::failover_events).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailoverEvent {
+    /// The primary's connection died and the standby was promoted.
+    ///
+    /// From now on, only the standby can create the object.
+    Promoted(Failover),
+
+    /// The promoted standby has created the object.
+    ///
+    /// The standby's registration ends with this event.
+    Completed(Failover),
+
+    /// The standby's connection died or was unregistered.
+    ///
+    /// If the standby had already been promoted, then the object is no longer reserved for it.
+    StandbyLost(Failover),
+}
+
+/// Stream of [`FailoverEvent`s](FailoverEvent).
+///
+/// This type is acquired by [`BrokerHandle::failover_events`](crate::BrokerHandle::failover_events).
+/// The stream ends when the broker shuts down.
+#[derive(Debug)]
+pub struct FailoverEvents(mpsc::UnboundedReceiver<FailoverEvent>);
+
+impl FailoverEvents {
+    pub(crate) fn new() -> (mpsc::UnboundedSender<FailoverEvent>, Self) {
+        let (send, recv) = mpsc::unbounded();
+        (send, Self(recv))
+    }
+}
+
+impl Stream for FailoverEvents {
+    type Item = FailoverEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<FailoverEvent>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl FusedStream for FailoverEvents {
+    fn is_terminated(&self) -> bool {
+        self.0.is_terminated()
+    }
+}
+
+#[derive(Debug)]
+struct Standby {
+    conn_id: ConnectionId,
+    services: Vec<ServiceUuid>,
+    primary: Option<u64>,
+}
+
+impl Standby {
+    fn failover(&self, object: ObjectUuid) -> Failover {
+        Failover {
+            object,
+            services: self.services.clone(),
+            primary: self.primary,
+            standby: self.conn_id.number(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Standbys {
+    standbys: HashMap<ObjectUuid, Standby>,
+    listeners: Vec<mpsc::UnboundedSender<FailoverEvent>>,
+}
+
+impl Standbys {
+    pub fn new() -> Self {
+        Self {
+            standbys: HashMap::new(),
+            listeners: Vec::new(),
+        }
+    }
+
+    pub fn add_listener(&mut self, listener: mpsc::UnboundedSender<FailoverEvent>) {
+        self.listeners.push(listener);
+    }
+
+    pub fn register(
+        &mut self,
+        object: ObjectUuid,
+        services: Vec<ServiceUuid>,
+        conn_id: ConnectionId,
+    ) {
+        let standby = Standby {
+            conn_id,
+            services,
+            primary: None,
+        };
+
+        if let Some(prev) = self.standbys.insert(object, standby) {
+            self.emit(FailoverEvent::StandbyLost(prev.failover(object)));
+        }
+    }
+
+    pub fn unregister(&mut self, object: ObjectUuid) {
+        if let Some(standby) = self.standbys.remove(&object) {
+            self.emit(FailoverEvent::StandbyLost(standby.failover(object)));
+        }
+    }
+
+    /// Checks whether a connection may create an object.
+    ///
+    /// Objects of promoted standbys are reserved for them.
+    pub fn may_create_object(&self, object: ObjectUuid, conn_id: &ConnectionId) -> bool {
+        match self.standbys.get(&object) {
+            Some(standby) if standby.primary.is_some() => standby.conn_id == *conn_id,
+            _ => true,
+        }
+    }
+
+    pub fn object_created(&mut self, object: ObjectUuid, conn_id: &ConnectionId) {
+        let completed = match self.standbys.get(&object) {
+            Some(standby) => (standby.primary.is_some()) && (standby.conn_id == *conn_id),
+            None => false,
+        };
+
+        if completed {
+            let standby = self.standbys.remove(&object).unwrap();
+            self.emit(FailoverEvent::Completed(standby.failover(object)));
+        }
+    }
+
+    /// Handles the shutdown of a connection.
+    ///
+    /// `objects` are the UUIDs of all objects owned by the connection.
+    pub fn connection_removed(
+        &mut self,
+        conn_id: &ConnectionId,
+        objects: impl IntoIterator<Item = ObjectUuid>,
+    ) {
+        let lost = self
+            .standbys
+            .iter()
+            .filter(|(_, standby)| standby.conn_id == *conn_id)
+            .map(|(object, _)| *object)
+            .collect::<Vec<_>>();
+
+        for object in lost {
+            self.unregister(object);
+        }
+
+        for object in objects {
+            let Some(standby) = self.standbys.get_mut(&object) else {
+                continue;
+            };
+
+            if standby.primary.is_none() {
+                standby.primary = Some(conn_id.number());
+                let failover = standby.failover(object);
+                self.emit(FailoverEvent::Promoted(failover));
+            }
+        }
+    }
+
+    fn emit(&mut self, event: FailoverEvent) {
+        self.listeners
+            .retain(|listener| listener.unbounded_send(event.clone()).is_ok());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::conn_id::ConnectionId;
 use crate::core::message::CallFunctionResult;
-use crate::core::{ObjectId, ServiceCookie, ServiceId};
+use crate::core::{LexicalId, ObjectId, ServiceCookie, ServiceId};
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub(super) struct State {
@@ -11,10 +12,10 @@ pub(super) struct State {
     services_destroyed: Vec<(ConnectionId, ServiceCookie)>,
     unsubscribe_event: Vec<(ConnectionId, ServiceCookie, u32)>,
     unsubscribe_all_events: Vec<(ConnectionId, ServiceCookie)>,
-    create_object: Vec<ObjectId>,
-    destroy_object: Vec<ObjectId>,
-    create_service: Vec<ServiceId>,
-    destroy_service: Vec<ServiceId>,
+    create_object: Vec<(ObjectId, Option<Arc<str>>)>,
+    destroy_object: Vec<(ObjectId, Option<Arc<str>>)>,
+    create_service: Vec<(ServiceId, Option<LexicalId>, Option<Arc<str>>)>,
+    destroy_service: Vec<(ServiceId, Option<LexicalId>, Option<Arc<str>>)>,
     abort_function_calls: Vec<(u32, ConnectionId)>,
 }
 
@@ -126,35 +127,49 @@ impl State {
         self.unsubscribe_all_events.pop()
     }
 
-    pub fn push_create_object(&mut self, object: ObjectId) {
-        self.create_object.push(object);
+    pub fn push_create_object(&mut self, object: ObjectId, namespace: Option<Arc<str>>) {
+        self.create_object.push((object, namespace));
     }
 
-    pub fn pop_create_object(&mut self) -> Option<ObjectId> {
+    pub fn pop_create_object(&mut self) -> Option<(ObjectId, Option<Arc<str>>)> {
         self.create_object.pop()
     }
 
-    pub fn push_destroy_object(&mut self, object: ObjectId) {
-        self.destroy_object.push(object);
+    pub fn push_destroy_object(&mut self, object: ObjectId, namespace: Option<Arc<str>>) {
+        self.destroy_object.push((object, namespace));
     }
 
-    pub fn pop_destroy_object(&mut self) -> Option<ObjectId> {
+    pub fn pop_destroy_object(&mut self) -> Option<(ObjectId, Option<Arc<str>>)> {
         self.destroy_object.pop()
     }
 
-    pub fn push_create_service(&mut self, service: ServiceId) {
-        self.create_service.push(service);
+    pub fn push_create_service(
+        &mut self,
+        service: ServiceId,
+        layout: Option<LexicalId>,
+        namespace: Option<Arc<str>>,
+    ) {
+        self.create_service.push((service, layout, namespace));
     }
 
-    pub fn pop_create_service(&mut self) -> Option<ServiceId> {
+    pub fn pop_create_service(
+        &mut self,
+    ) -> Option<(ServiceId, Option<LexicalId>, Option<Arc<str>>)> {
         self.create_service.pop()
     }
 
-    pub fn push_destroy_service(&mut self, service: ServiceId) {
-        self.destroy_service.push(service);
+    pub fn push_destroy_service(
+        &mut self,
+        service: ServiceId,
+        layout: Option<LexicalId>,
+        namespace: Option<Arc<str>>,
+    ) {
+        self.destroy_service.push((service, layout, namespace));
     }
 
-    pub fn pop_destroy_service(&mut self) -> Option<ServiceId> {
+    pub fn pop_destroy_service(
+        &mut self,
+    ) -> Option<(ServiceId, Option<LexicalId>, Option<Arc<str>>)> {
         self.destroy_service.pop()
     }
 
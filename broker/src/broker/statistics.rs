@@ -14,6 +14,11 @@ pub struct BrokerStatistics {
     pub(super) end: Instant,
     pub(super) messages_sent: usize,
     pub(super) messages_received: usize,
+    pub(super) dead_letters: usize,
+    pub(super) slow_consumers: usize,
+    pub(super) events_dropped: usize,
+    pub(super) events_emitted: usize,
+    pub(super) events_delivered: usize,
     pub(super) num_connections: usize,
     pub(super) num_objects: usize,
     pub(super) num_services: usize,
@@ -38,6 +43,11 @@ impl BrokerStatistics {
             end: now,
             messages_sent: 0,
             messages_received: 0,
+            dead_letters: 0,
+            slow_consumers: 0,
+            events_dropped: 0,
+            events_emitted: 0,
+            events_delivered: 0,
             num_connections: 0,
             num_objects: 0,
             num_services: 0,
@@ -60,6 +70,11 @@ impl BrokerStatistics {
         // Reset statistics to 0.
         self.messages_sent = 0;
         self.messages_received = 0;
+        self.dead_letters = 0;
+        self.slow_consumers = 0;
+        self.events_dropped = 0;
+        self.events_emitted = 0;
+        self.events_delivered = 0;
 
         res
     }
@@ -90,6 +105,41 @@ impl BrokerStatistics {
         self.messages_received
     }
 
+    /// Number of function calls and events that could not be delivered.
+    ///
+    /// See [`DeadLetterKind`](crate::DeadLetterKind) for which messages are counted here.
+    pub fn dead_letters(&self) -> usize {
+        self.dead_letters
+    }
+
+    /// Number of times a connection has become a slow consumer.
+    ///
+    /// See [`SendQueueLimit`](crate::SendQueueLimit) for more information.
+    pub fn slow_consumers(&self) -> usize {
+        self.slow_consumers
+    }
+
+    /// Number of events that were dropped, because the receiving connection was a slow consumer.
+    ///
+    /// See [`SlowConsumerPolicy::DropEvents`](crate::SlowConsumerPolicy::DropEvents).
+    pub fn events_dropped(&self) -> usize {
+        self.events_dropped
+    }
+
+    /// Number of events emitted by the owners of services.
+    pub fn events_emitted(&self) -> usize {
+        self.events_emitted
+    }
+
+    /// Number of events delivered to subscribers.
+    ///
+    /// Events are delivered only to the connections, that are subscribed to them. The ratio of
+    /// `events_delivered` to [`events_emitted`](Self::events_emitted) is the average fan-out and
+    /// the broker's work per event is proportional to it, rather than to the number of connections.
+    pub fn events_delivered(&self) -> usize {
+        self.events_delivered
+    }
+
     /// The number of current connections.
     pub fn num_connections(&self) -> usize {
         self.num_connections
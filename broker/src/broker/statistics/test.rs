@@ -237,6 +237,8 @@ async fn events() {
     let stats = broker.take_statistics().await.unwrap();
     assert_eq!(stats.messages_sent(), 9);
     assert_eq!(stats.messages_received(), 8);
+    assert_eq!(stats.events_emitted(), 0);
+    assert_eq!(stats.events_delivered(), 0);
 
     // Emit 3 events on 0.
     svc.emit(0, &()).unwrap();
@@ -246,6 +248,8 @@ async fn events() {
     let stats = broker.take_statistics().await.unwrap();
     assert_eq!(stats.messages_sent(), 7);
     assert_eq!(stats.messages_received(), 4);
+    assert_eq!(stats.events_emitted(), 3);
+    assert_eq!(stats.events_delivered(), 6);
 
     // Emit 2 events on 0.
     // Emit 1 event on 1.
@@ -256,6 +260,8 @@ async fn events() {
     let stats = broker.take_statistics().await.unwrap();
     assert_eq!(stats.messages_sent(), 5);
     assert_eq!(stats.messages_received(), 3);
+    assert_eq!(stats.events_emitted(), 2); // Clients don't emit events without subscribers.
+    assert_eq!(stats.events_delivered(), 4);
 
     // Final state.
     let stats = broker.take_statistics().await.unwrap();
@@ -1,24 +1,54 @@
-use crate::core::channel::{self, Disconnected, Unbounded};
+use crate::core::channel::{self, Bounded, Disconnected, Unbounded};
 use crate::core::message::{
     CallFunction, CallFunctionReply, CallFunctionResult, ChannelEndClaimed, ChannelEndClosed,
     ClaimChannelEnd, ClaimChannelEndReply, ClaimChannelEndResult, CloseChannelEnd,
     CloseChannelEndReply, CloseChannelEndResult, Connect, Connect2, ConnectData, ConnectReply,
     ConnectResult, CreateChannel, CreateChannelReply, CreateObject, CreateObjectReply,
-    CreateObjectResult, CreateService, CreateServiceReply, CreateServiceResult, Message, SendItem,
-    Sync, SyncReply,
+    CreateObjectResult, CreateService, CreateServiceReply, CreateServiceResult, DestroyService,
+    EmitEvent, Message, SendItem, Shutdown, SubscribeEvent, SubscribeEventResult, Sync, SyncReply,
 };
+#[cfg(unix)]
+use crate::core::tokio::{TokioTransport, TokioTransportError, TokioUnixTransport};
+#[cfg(unix)]
+use crate::core::transport::AsyncTransport;
 use crate::core::transport::AsyncTransportExt;
+#[cfg(all(unix, feature = "lz4"))]
+use crate::core::Compression;
+#[cfg(unix)]
+use crate::core::Fd;
+#[cfg(all(unix, feature = "json"))]
+use crate::core::WireFormat;
 use crate::core::{
-    ChannelEnd, ChannelEndWithCapacity, ObjectUuid, ProtocolVersion, SerializedValue, ServiceUuid,
+    AuthRejection, BusEvent, BusListenerFilter, BusListenerScope, CallPriority, ChannelEnd,
+    ChannelEndWithCapacity, ObjectId, ObjectUuid, ProtocolVersion, SerializeError, SerializedValue,
+    SerializedValueSlice, ServiceCookie, ServiceId, ServiceUuid, TraceContext,
 };
-use crate::{Broker, BrokerHandle};
-use aldrin::low_level::{Proxy, ServiceInfo};
-use aldrin::Client;
+use crate::{
+    AuthStep, Authenticator, Broker, BrokerHandle, Connection, ConnectionError, ConnectionHandle,
+    EstablishError, FailoverEvent, SendQueueLimit, SlowConsumerPolicy, TokenAuthenticator,
+};
+use aldrin::error::ConnectError;
+use aldrin::low_level::{CallOptions, Proxy, ServiceInfo};
+use aldrin::{AuthProvider, Client, Error, Handle, TokenAuth};
+use aldrin_test::aldrin_broker::DeadLetterKind;
 use aldrin_test::tokio::TestBroker;
 use futures_util::future::{self, Either};
+use futures_util::stream::StreamExt;
+use std::fmt;
 use std::future::Future;
+#[cfg(unix)]
+use std::io::{Read, Write};
 use std::mem;
+#[cfg(unix)]
+use std::os::fd::OwnedFd;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream as StdUnixStream;
+use std::pin::pin;
+#[cfg(unix)]
+use std::pin::Pin;
 use std::time::Duration;
+#[cfg(unix)]
+use tokio::net::UnixStream;
 use tokio::time;
 
 #[tokio::test]
@@ -116,6 +146,35 @@ async fn begin_connect_accept() {
     join.await.unwrap();
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn begin_connect_unix_peer_credentials() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (s1, s2) = UnixStream::pair().unwrap();
+    let mut t1 = TokioTransport::new(s1);
+    let t2 = TokioTransport::new(s2);
+
+    t1.send_and_flush(Connect::with_serialize_value(14, &0u32).unwrap())
+        .await
+        .unwrap();
+
+    let conn = handle.begin_connect(t2).await.unwrap();
+    let creds = conn.transport().peer_credentials().unwrap();
+    assert_eq!(creds.pid(), Some(std::process::id() as i32));
+
+    conn.reject(None).await.unwrap();
+    assert!(matches!(
+        t1.receive().await.unwrap(),
+        Message::ConnectReply(ConnectReply::Rejected(_))
+    ));
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
 #[tokio::test]
 async fn begin_connect_2_accept() {
     let broker = Broker::new();
@@ -156,46 +215,74 @@ async fn begin_connect_2_accept() {
     join.await.unwrap();
 }
 
+#[cfg(all(unix, feature = "lz4"))]
 #[tokio::test]
-async fn begin_connect_reject() {
+async fn begin_connect_2_compression() {
     let broker = Broker::new();
     let mut handle = broker.handle().clone();
     let join = tokio::spawn(broker.run());
 
-    let (mut t1, t2) = channel::unbounded();
+    let (s1, s2) = UnixStream::pair().unwrap();
+    let mut t1 = TokioTransport::new(s1);
+    t1.set_compression_threshold(Some(0));
+    let mut t2 = TokioTransport::new(s2);
+    t2.set_compression_threshold(Some(0));
 
-    t1.send_and_flush(Connect::with_serialize_value(14, &0u32).unwrap())
-        .await
-        .unwrap();
+    let mut data = ConnectData::new();
+    data.compression.push(Compression::Lz4);
+    t1.send_and_flush(
+        Connect2::with_serialize_data(
+            ProtocolVersion::V1_14.major(),
+            ProtocolVersion::V1_15.minor(),
+            &data,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
 
     let conn = handle.begin_connect(t2).await.unwrap();
-    assert_eq!(conn.deserialize_client_data(), Some(Ok(0u32)));
+    tokio::spawn(conn.accept(None).await.unwrap().run());
 
-    conn.reject_serialize(Some(&1u32)).await.unwrap();
-    let value = match t1.receive().await.unwrap() {
-        Message::ConnectReply(ConnectReply::Rejected(value)) => value,
+    let msg = match t1.receive().await.unwrap() {
+        Message::ConnectReply2(msg) => msg,
         msg => panic!("invalid msg received {msg:?}"),
     };
-    assert_eq!(value.deserialize(), Ok(1u32));
+    let data = msg.deserialize_connect_data().unwrap();
+    assert_eq!(data.compression, Some(Compression::Lz4));
+
+    Pin::new(&mut t1).enable_compression(Compression::Lz4);
+    assert_eq!(t1.compression(), Some(Compression::Lz4));
+
+    t1.send_and_flush(Sync { serial: 0 }).await.unwrap();
+    assert_eq!(
+        t1.receive().await.unwrap(),
+        Message::SyncReply(SyncReply { serial: 0 })
+    );
 
     handle.shutdown().await;
     join.await.unwrap();
 }
 
+#[cfg(all(unix, feature = "json"))]
 #[tokio::test]
-async fn begin_connect_2_reject() {
+async fn begin_connect_2_json() {
     let broker = Broker::new();
     let mut handle = broker.handle().clone();
     let join = tokio::spawn(broker.run());
 
-    let (mut t1, t2) = channel::unbounded();
+    let (s1, s2) = UnixStream::pair().unwrap();
+    let mut t1 = TokioTransport::new(s1);
+    t1.set_wire_formats([WireFormat::Json]);
+    let mut t2 = TokioTransport::new(s2);
+    t2.set_wire_formats([WireFormat::Json]);
 
     let mut data = ConnectData::new();
-    data.serialize_user(&0u32).unwrap();
+    data.wire_formats.push(WireFormat::Json);
     t1.send_and_flush(
         Connect2::with_serialize_data(
             ProtocolVersion::V1_14.major(),
-            ProtocolVersion::V1_15.minor(),
+            ProtocolVersion::V1_19.minor(),
             &data,
         )
         .unwrap(),
@@ -204,210 +291,178 @@ async fn begin_connect_2_reject() {
     .unwrap();
 
     let conn = handle.begin_connect(t2).await.unwrap();
-    assert_eq!(conn.deserialize_client_data(), Some(Ok(0u32)));
+    tokio::spawn(conn.accept(None).await.unwrap().run());
 
-    conn.reject_serialize(Some(&1u32)).await.unwrap();
     let msg = match t1.receive().await.unwrap() {
         Message::ConnectReply2(msg) => msg,
         msg => panic!("invalid msg received {msg:?}"),
     };
-    assert_eq!(msg.result, ConnectResult::Rejected);
     let data = msg.deserialize_connect_data().unwrap();
-    assert_eq!(data.deserialize_user(), Some(Ok(1u32)));
+    assert_eq!(data.wire_format, Some(WireFormat::Json));
+
+    Pin::new(&mut t1).enable_wire_format(WireFormat::Json);
+    assert_eq!(t1.wire_format(), Some(WireFormat::Json));
+
+    t1.send_and_flush(Sync { serial: 0 }).await.unwrap();
+    assert_eq!(
+        t1.receive().await.unwrap(),
+        Message::SyncReply(SyncReply { serial: 0 })
+    );
 
     handle.shutdown().await;
     join.await.unwrap();
 }
 
+#[cfg(unix)]
 #[tokio::test]
-async fn wrong_client_replies_function_call() {
+async fn begin_connect_2_max_message_size() {
     let broker = Broker::new();
     let mut handle = broker.handle().clone();
-    tokio::spawn(broker.run());
+    let join = tokio::spawn(broker.run());
 
-    let mut client1 = connect_client(&mut handle).await;
-    let mut client2 = connect_client(&mut handle).await;
+    let (s1, s2) = UnixStream::pair().unwrap();
+    let mut t1 = TokioTransport::new(s1);
+    let mut t2 = TokioTransport::new(s2);
+    t2.set_max_message_size(Some(1024));
 
-    let object_uuid = ObjectUuid::new_v4();
+    let mut data = ConnectData::new();
+    data.max_message_size = Some(4096);
+    t1.send_and_flush(
+        Connect2::with_serialize_data(
+            ProtocolVersion::V1_14.major(),
+            ProtocolVersion::V1_19.minor(),
+            &data,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
 
-    client1
-        .send(Message::CreateObject(CreateObject {
-            serial: 0,
-            uuid: object_uuid,
-        }))
-        .await
-        .unwrap();
+    let conn = handle.begin_connect(t2).await.unwrap();
+    tokio::spawn(conn.accept(None).await.unwrap().run());
 
-    let Message::CreateObjectReply(CreateObjectReply {
-        result: CreateObjectResult::Ok(object_cookie),
-        ..
-    }) = client1.receive().await.unwrap()
-    else {
-        panic!();
+    let msg = match t1.receive().await.unwrap() {
+        Message::ConnectReply2(msg) => msg,
+        msg => panic!("invalid msg received {msg:?}"),
     };
+    let data = msg.deserialize_connect_data().unwrap();
+    assert_eq!(data.max_message_size, Some(1024));
 
-    let service_uuid = ServiceUuid::new_v4();
+    Pin::new(&mut t1).set_peer_max_message_size(1024);
+    assert_eq!(t1.peer_max_message_size(), Some(1024));
 
-    client1
-        .send(Message::CreateService(CreateService {
-            serial: 0,
-            object_cookie,
-            uuid: service_uuid,
-            version: 0,
-        }))
-        .await
-        .unwrap();
+    t1.send_and_flush(Sync { serial: 0 }).await.unwrap();
+    assert_eq!(
+        t1.receive().await.unwrap(),
+        Message::SyncReply(SyncReply { serial: 0 })
+    );
 
-    let Message::CreateServiceReply(CreateServiceReply {
-        result: CreateServiceResult::Ok(service_cookie),
-        ..
-    }) = client1.receive().await.unwrap()
-    else {
-        panic!();
-    };
+    // Oversized messages are rejected before sending them.
+    let value = SerializedValue::serialize(&vec![0u8; 2048]).unwrap();
+    let res = t1
+        .send_and_flush(EmitEvent {
+            service_cookie: ServiceCookie::new_v4(),
+            event: 0,
+            value,
+            trace_context: None,
+        })
+        .await;
+    assert!(matches!(res, Err(TokioTransportError::MessageTooLarge(_))));
 
-    client1
-        .send(Message::CallFunction(
-            CallFunction::with_serialize_value(0, service_cookie, 0, &()).unwrap(),
-        ))
-        .await
-        .unwrap();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
 
-    let Message::CallFunction(CallFunction { serial, .. }) = client1.receive().await.unwrap()
-    else {
-        panic!();
-    };
+#[cfg(unix)]
+#[tokio::test]
+async fn max_message_size_exceeded() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
 
-    // Here, client2 replies to the function call that client1 received.
-    client2
-        .send(Message::CallFunctionReply(
-            CallFunctionReply::err_with_serialize_value(serial, &()).unwrap(),
-        ))
-        .await
-        .unwrap();
+    let (s1, s2) = UnixStream::pair().unwrap();
+    let mut t1 = TokioTransport::new(s1);
+    let mut t2 = TokioTransport::new(s2);
+    t2.set_max_message_size(Some(1024));
 
-    // Sync client2 to make sure the CallFunctionReply has been processed by the broker.
-    client2
-        .send(Message::Sync(Sync { serial: 0 }))
-        .await
-        .unwrap();
+    // The length prefix alone is enough to reject the message.
+    let mut data = ConnectData::new();
+    data.serialize_user(&vec![0u8; 1024 * 1024]).unwrap();
+    let connect = Connect2::with_serialize_data(
+        ProtocolVersion::V1_14.major(),
+        ProtocolVersion::V1_19.minor(),
+        &data,
+    )
+    .unwrap();
 
-    let Message::SyncReply(SyncReply { .. }) = client2.receive().await.unwrap() else {
-        panic!();
-    };
+    let send = tokio::spawn(async move { t1.send_and_flush(connect).await });
 
-    client1
-        .send(Message::CallFunctionReply(
-            CallFunctionReply::ok_with_serialize_value(serial, &()).unwrap(),
+    let res = handle.begin_connect(t2).await;
+    assert!(matches!(
+        res,
+        Err(EstablishError::Transport(
+            TokioTransportError::MessageTooLarge(_)
         ))
-        .await
-        .unwrap();
+    ));
 
-    let Message::CallFunctionReply(CallFunctionReply {
-        result: CallFunctionResult::Ok(_),
-        ..
-    }) = client1.receive().await.unwrap()
-    else {
-        panic!();
-    };
+    drop(send);
+    handle.shutdown().await;
+    join.await.unwrap();
 }
 
+#[cfg(all(unix, feature = "json"))]
 #[tokio::test]
-async fn send_item_without_capacity() {
+async fn json_client() {
     let broker = Broker::new();
     let mut handle = broker.handle().clone();
-    tokio::spawn(broker.run());
-
-    let mut client1 = connect_client(&mut handle).await;
-    let mut client2 = connect_client(&mut handle).await;
-
-    client1
-        .send(Message::CreateChannel(CreateChannel {
-            serial: 0,
-            end: ChannelEndWithCapacity::Sender,
-        }))
-        .await
-        .unwrap();
-
-    let Message::CreateChannelReply(CreateChannelReply { serial: 0, cookie }) =
-        client1.receive().await.unwrap()
-    else {
-        panic!();
-    };
+    let join = tokio::spawn(broker.run());
 
-    client2
-        .send(Message::ClaimChannelEnd(ClaimChannelEnd {
-            serial: 0,
-            cookie,
-            end: ChannelEndWithCapacity::Receiver(0),
-        }))
+    let (s1, s2) = UnixStream::pair().unwrap();
+    let mut t1 = TokioTransport::new(s1);
+    t1.set_wire_formats([WireFormat::Json]);
+    let mut t2 = TokioTransport::new(s2);
+    t2.set_wire_formats([WireFormat::Json]);
+
+    let conn = async { handle.begin_connect(t2).await?.accept(None).await };
+    let (client, conn) = tokio::join!(Client::connect(t1), conn);
+    tokio::spawn(conn.unwrap().run());
+    let client = client.unwrap();
+    let client_handle = client.handle().clone();
+    tokio::spawn(client.run());
+
+    let obj = client_handle
+        .create_object(ObjectUuid::new_v4())
         .await
         .unwrap();
-
-    if !matches!(
-        client2.receive().await,
-        Ok(Message::ClaimChannelEndReply(ClaimChannelEndReply {
-            serial: 0,
-            result: ClaimChannelEndResult::ReceiverClaimed,
-        })),
-    ) {
-        panic!();
-    };
-
-    if !matches!(
-        client1.receive().await,
-        Ok(Message::ChannelEndClaimed(ChannelEndClaimed {
-            cookie: cookie2,
-            end: ChannelEndWithCapacity::Receiver(0),
-        }))
-        if cookie == cookie2
-    ) {
-        panic!()
-    }
-
-    client1
-        .send(Message::SendItem(
-            SendItem::with_serialize_value(cookie, &()).unwrap(),
-        ))
+    let info = ServiceInfo::new(0);
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
         .await
         .unwrap();
 
-    if !matches!(
-        client2.receive().await,
-        Ok(Message::ChannelEndClosed(ChannelEndClosed {
-            cookie: cookie2,
-            end: ChannelEnd::Sender,
-        }))
-        if cookie == cookie2
-    ) {
-        panic!();
-    };
+    let proxy = Proxy::new(&client_handle, svc.id()).await.unwrap();
+    let reply = proxy.call(0, &(1u32, "foo"));
 
-    client2
-        .send(Message::CloseChannelEnd(CloseChannelEnd {
-            serial: 0,
-            cookie,
-            end: ChannelEnd::Receiver,
-        }))
-        .await
-        .unwrap();
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.deserialize(), Ok((1u32, "foo".to_owned())));
+    call.into_promise().ok(&2u32).unwrap();
+    assert_eq!(reply.await.unwrap().unwrap().deserialize(), Ok(2u32));
 
-    if !matches!(
-        client2.receive().await,
-        Ok(Message::CloseChannelEndReply(CloseChannelEndReply {
-            serial: 0,
-            result: CloseChannelEndResult::Ok,
-        }))
-    ) {
-        panic!();
-    };
+    client_handle.shutdown();
+    handle.shutdown().await;
+    join.await.unwrap();
 }
 
+#[cfg(unix)]
 #[tokio::test]
-async fn calls_from_multiple_clients() {
-    let mut broker = TestBroker::new();
+async fn fd_passing() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (client1, _) = TestClient::new(&mut handle).fd_passing().connect().await;
+    let (client2, _) = TestClient::new(&mut handle).fd_passing().connect().await;
 
-    let mut client1 = broker.add_client().await;
     let obj = client1.create_object(ObjectUuid::new_v4()).await.unwrap();
     let info = ServiceInfo::new(0);
     let mut svc = obj
@@ -415,45 +470,295 @@ async fn calls_from_multiple_clients() {
         .await
         .unwrap();
 
-    let mut client2 = broker.add_client().await;
-    let proxy = client2.create_proxy(svc.id()).await.unwrap();
-    let reply = proxy.call(0, &());
-    svc.next_call()
-        .await
-        .unwrap()
-        .into_promise()
-        .done()
-        .unwrap();
+    let (mut sock1, sock2) = StdUnixStream::pair().unwrap();
+    let proxy = Proxy::new(&client2, svc.id()).await.unwrap();
+    let reply = proxy.call(0, &Fd::new(OwnedFd::from(sock2)));
+
+    let call = svc.next_call().await.unwrap();
+    let fd = call.deserialize::<Fd>().unwrap();
+    let mut sock2 = StdUnixStream::from(fd.into_owned().unwrap());
+    sock2.write_all(b"aldrin").unwrap();
+    call.into_promise().ok(&()).unwrap();
     reply.await.unwrap().unwrap();
 
-    let mut client3 = broker.add_client().await;
-    let proxy = client3.create_proxy(svc.id()).await.unwrap();
-    let reply = proxy.call(0, &());
-    svc.next_call()
+    let mut buf = [0; 6];
+    sock1.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, *b"aldrin");
+
+    client1.shutdown();
+    client2.shutdown();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn begin_connect_reject() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (mut t1, t2) = channel::unbounded();
+
+    t1.send_and_flush(Connect::with_serialize_value(14, &0u32).unwrap())
         .await
-        .unwrap()
-        .into_promise()
-        .done()
         .unwrap();
-    reply.await.unwrap().unwrap();
 
-    client1.join().await;
-    client2.join().await;
-    client3.join().await;
-    broker.join().await;
+    let conn = handle.begin_connect(t2).await.unwrap();
+    assert_eq!(conn.deserialize_client_data(), Some(Ok(0u32)));
+
+    conn.reject_serialize(Some(&1u32)).await.unwrap();
+    let value = match t1.receive().await.unwrap() {
+        Message::ConnectReply(ConnectReply::Rejected(value)) => value,
+        msg => panic!("invalid msg received {msg:?}"),
+    };
+    assert_eq!(value.deserialize(), Ok(1u32));
+
+    handle.shutdown().await;
+    join.await.unwrap();
 }
 
 #[tokio::test]
-async fn duplicate_call_serial() {
+async fn begin_connect_2_reject() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (mut t1, t2) = channel::unbounded();
+
+    let mut data = ConnectData::new();
+    data.serialize_user(&0u32).unwrap();
+    t1.send_and_flush(
+        Connect2::with_serialize_data(
+            ProtocolVersion::V1_14.major(),
+            ProtocolVersion::V1_15.minor(),
+            &data,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let conn = handle.begin_connect(t2).await.unwrap();
+    assert_eq!(conn.deserialize_client_data(), Some(Ok(0u32)));
+
+    conn.reject_serialize(Some(&1u32)).await.unwrap();
+    let msg = match t1.receive().await.unwrap() {
+        Message::ConnectReply2(msg) => msg,
+        msg => panic!("invalid msg received {msg:?}"),
+    };
+    assert_eq!(msg.result, ConnectResult::Rejected);
+    let data = msg.deserialize_connect_data().unwrap();
+    assert_eq!(data.deserialize_user(), Some(Ok(1u32)));
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn authenticate_token() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let authenticator = TokenAuthenticator::new(["secret"]);
+
+    let (t1, t2) = channel::unbounded();
+    let client = Client::builder(t1).with_auth(TokenAuth::new("secret"));
+    let conn = async {
+        handle
+            .begin_connect(t2)
+            .await?
+            .authenticate(&authenticator)
+            .await?
+            .accept(None)
+            .await
+    };
+    let (client, conn) = tokio::join!(client.connect(), conn);
+    let client = client.unwrap();
+    let conn = tokio::spawn(conn.unwrap().run());
+    let client_handle = client.handle().clone();
+    let client = tokio::spawn(client.run());
+    client_handle.shutdown();
+    client.await.unwrap().unwrap();
+    conn.await.unwrap().unwrap();
+
+    let (t1, t2) = channel::unbounded();
+    let client = Client::builder(t1).with_auth(TokenAuth::new("wrong"));
+    let conn = async {
+        handle
+            .begin_connect(t2)
+            .await?
+            .authenticate(&authenticator)
+            .await
+    };
+    let (client, conn) = tokio::join!(client.connect(), conn);
+    assert!(matches!(
+        client,
+        Err(ConnectError::AuthenticationFailed(
+            AuthRejection::InvalidCredentials
+        ))
+    ));
+    assert!(matches!(
+        conn,
+        Err(EstablishError::AuthenticationFailed(
+            AuthRejection::InvalidCredentials
+        ))
+    ));
+
+    let (t1, t2) = channel::unbounded();
+    let conn = async {
+        handle
+            .begin_connect(t2)
+            .await?
+            .authenticate(&authenticator)
+            .await
+    };
+    let (client, conn) = tokio::join!(Client::connect(t1), conn);
+    assert!(matches!(
+        client,
+        Err(ConnectError::AuthenticationFailed(
+            AuthRejection::MissingCredentials
+        ))
+    ));
+    assert!(matches!(
+        conn,
+        Err(EstablishError::AuthenticationFailed(
+            AuthRejection::MissingCredentials
+        ))
+    ));
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+struct CounterAuthenticator {
+    rounds: u32,
+}
+
+impl Authenticator for CounterAuthenticator {
+    fn authenticate(&mut self, credentials: Option<&SerializedValueSlice>) -> AuthStep {
+        assert_eq!(credentials.unwrap().deserialize(), Ok(0u32));
+        AuthStep::Challenge(SerializedValue::serialize(&self.rounds).unwrap())
+    }
+
+    fn verify_response(&mut self, response: &SerializedValueSlice) -> AuthStep {
+        assert_eq!(response.deserialize(), Ok(self.rounds + 1));
+        self.rounds -= 1;
+
+        if self.rounds > 0 {
+            AuthStep::Challenge(SerializedValue::serialize(&self.rounds).unwrap())
+        } else {
+            AuthStep::Accept
+        }
+    }
+}
+
+struct CounterAuthProvider;
+
+impl AuthProvider for CounterAuthProvider {
+    fn credentials(&mut self) -> Result<SerializedValue, SerializeError> {
+        SerializedValue::serialize(&0u32)
+    }
+
+    fn respond(
+        &mut self,
+        challenge: &SerializedValueSlice,
+    ) -> Result<SerializedValue, SerializeError> {
+        let challenge: u32 = challenge.deserialize().unwrap();
+        SerializedValue::serialize(&(challenge + 1))
+    }
+}
+
+#[tokio::test]
+async fn authenticate_challenge() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (t1, t2) = channel::unbounded();
+    let client = Client::builder(t1).with_auth(CounterAuthProvider);
+    let conn = async {
+        handle
+            .begin_connect(t2)
+            .await?
+            .authenticate(CounterAuthenticator { rounds: 3 })
+            .await?
+            .accept(None)
+            .await
+    };
+    let (client, conn) = tokio::join!(client.connect(), conn);
+    let client = client.unwrap();
+    let conn = tokio::spawn(conn.unwrap().run());
+    let client_handle = client.handle().clone();
+    let client = tokio::spawn(client.run());
+    client_handle.shutdown();
+    client.await.unwrap().unwrap();
+    conn.await.unwrap().unwrap();
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn authenticate_timeout() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (mut t1, t2) = channel::unbounded();
+
+    let mut data = ConnectData::new();
+    data.serialize_auth(&0u32).unwrap();
+    t1.send_and_flush(
+        Connect2::with_serialize_data(
+            ProtocolVersion::V1_14.major(),
+            ProtocolVersion::V1_15.minor(),
+            &data,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let conn = handle.begin_connect(t2).await.unwrap();
+    let res = conn
+        .authenticate_with_timeout(CounterAuthenticator { rounds: 1 }, future::ready(()))
+        .await;
+    assert!(matches!(
+        res,
+        Err(EstablishError::AuthenticationFailed(AuthRejection::Timeout))
+    ));
+
+    let msg = match t1.receive().await.unwrap() {
+        Message::AuthChallenge(msg) => msg,
+        msg => panic!("invalid msg received {msg:?}"),
+    };
+    assert_eq!(msg.value.deserialize(), Ok(1u32));
+
+    let msg = match t1.receive().await.unwrap() {
+        Message::ConnectReply2(msg) => msg,
+        msg => panic!("invalid msg received {msg:?}"),
+    };
+    assert_eq!(msg.result, ConnectResult::Rejected);
+    let data = msg.deserialize_connect_data().unwrap();
+    assert_eq!(data.auth_rejection, Some(AuthRejection::Timeout));
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn wrong_client_replies_function_call() {
     let broker = Broker::new();
     let mut handle = broker.handle().clone();
     tokio::spawn(broker.run());
 
-    let mut client = connect_client(&mut handle).await;
+    let mut client1 = TestClient::new(&mut handle).connect_raw().await;
+    let mut client2 = TestClient::new(&mut handle).connect_raw().await;
 
     let object_uuid = ObjectUuid::new_v4();
 
-    client
+    client1
         .send(Message::CreateObject(CreateObject {
             serial: 0,
             uuid: object_uuid,
@@ -464,14 +769,14 @@ async fn duplicate_call_serial() {
     let Message::CreateObjectReply(CreateObjectReply {
         result: CreateObjectResult::Ok(object_cookie),
         ..
-    }) = client.receive().await.unwrap()
+    }) = client1.receive().await.unwrap()
     else {
         panic!();
     };
 
     let service_uuid = ServiceUuid::new_v4();
 
-    client
+    client1
         .send(Message::CreateService(CreateService {
             serial: 0,
             object_cookie,
@@ -484,71 +789,156 @@ async fn duplicate_call_serial() {
     let Message::CreateServiceReply(CreateServiceReply {
         result: CreateServiceResult::Ok(service_cookie),
         ..
-    }) = client.receive().await.unwrap()
+    }) = client1.receive().await.unwrap()
     else {
         panic!();
     };
 
-    client
-        .send(Message::CallFunction(CallFunction {
-            serial: 0,
-            service_cookie,
-            function: 0,
-            value: SerializedValue::serialize(&()).unwrap(),
-        }))
+    client1
+        .send(Message::CallFunction(
+            CallFunction::with_serialize_value(0, service_cookie, 0, &()).unwrap(),
+        ))
         .await
         .unwrap();
 
-    assert!(matches!(
-        client.receive().await.unwrap(),
-        Message::CallFunction(_)
-    ));
+    let Message::CallFunction(CallFunction { serial, .. }) = client1.receive().await.unwrap()
+    else {
+        panic!();
+    };
 
-    client
-        .send(Message::CallFunction(CallFunction {
-            serial: 0,
-            service_cookie,
-            function: 0,
-            value: SerializedValue::serialize(&()).unwrap(),
-        }))
+    // Here, client2 replies to the function call that client1 received.
+    client2
+        .send(Message::CallFunctionReply(
+            CallFunctionReply::err_with_serialize_value(serial, &()).unwrap(),
+        ))
         .await
         .unwrap();
 
-    assert_eq!(client.receive().await, Err(Disconnected));
-}
-
-async fn connect_client(broker: &mut BrokerHandle) -> Unbounded {
-    const VERSION: ProtocolVersion = ProtocolVersion::V1_16;
-
-    let (mut t1, t2) = channel::unbounded();
-
-    t1.send(
-        Connect2::with_serialize_data(VERSION.major(), VERSION.minor(), &ConnectData::new())
-            .unwrap(),
-    )
-    .await
-    .unwrap();
-
-    let conn = broker.connect(t2).await.unwrap();
+    // Sync client2 to make sure the CallFunctionReply has been processed by the broker.
+    client2
+        .send(Message::Sync(Sync { serial: 0 }))
+        .await
+        .unwrap();
 
-    let Message::ConnectReply2(reply) = t1.receive().await.unwrap() else {
-        panic!("expected connect-reply2");
+    let Message::SyncReply(SyncReply { .. }) = client2.receive().await.unwrap() else {
+        panic!();
     };
 
-    assert_eq!(reply.result, ConnectResult::Ok(VERSION.minor()));
+    client1
+        .send(Message::CallFunctionReply(
+            CallFunctionReply::ok_with_serialize_value(serial, &()).unwrap(),
+        ))
+        .await
+        .unwrap();
 
-    tokio::spawn(conn.run());
-    t1
+    let Message::CallFunctionReply(CallFunctionReply {
+        result: CallFunctionResult::Ok(_),
+        ..
+    }) = client1.receive().await.unwrap()
+    else {
+        panic!();
+    };
 }
 
 #[tokio::test]
-async fn destroy_service_after_abort() {
+async fn send_item_without_capacity() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    tokio::spawn(broker.run());
+
+    let mut client1 = TestClient::new(&mut handle).connect_raw().await;
+    let mut client2 = TestClient::new(&mut handle).connect_raw().await;
+
+    client1
+        .send(Message::CreateChannel(CreateChannel {
+            serial: 0,
+            end: ChannelEndWithCapacity::Sender,
+        }))
+        .await
+        .unwrap();
+
+    let Message::CreateChannelReply(CreateChannelReply { serial: 0, cookie }) =
+        client1.receive().await.unwrap()
+    else {
+        panic!();
+    };
+
+    client2
+        .send(Message::ClaimChannelEnd(ClaimChannelEnd {
+            serial: 0,
+            cookie,
+            end: ChannelEndWithCapacity::Receiver(0),
+        }))
+        .await
+        .unwrap();
+
+    if !matches!(
+        client2.receive().await,
+        Ok(Message::ClaimChannelEndReply(ClaimChannelEndReply {
+            serial: 0,
+            result: ClaimChannelEndResult::ReceiverClaimed,
+        })),
+    ) {
+        panic!();
+    };
+
+    if !matches!(
+        client1.receive().await,
+        Ok(Message::ChannelEndClaimed(ChannelEndClaimed {
+            cookie: cookie2,
+            end: ChannelEndWithCapacity::Receiver(0),
+        }))
+        if cookie == cookie2
+    ) {
+        panic!()
+    }
+
+    client1
+        .send(Message::SendItem(
+            SendItem::with_serialize_value(cookie, &()).unwrap(),
+        ))
+        .await
+        .unwrap();
+
+    if !matches!(
+        client2.receive().await,
+        Ok(Message::ChannelEndClosed(ChannelEndClosed {
+            cookie: cookie2,
+            end: ChannelEnd::Sender,
+        }))
+        if cookie == cookie2
+    ) {
+        panic!();
+    };
+
+    client2
+        .send(Message::CloseChannelEnd(CloseChannelEnd {
+            serial: 0,
+            cookie,
+            end: ChannelEnd::Receiver,
+        }))
+        .await
+        .unwrap();
+
+    if !matches!(
+        client2.receive().await,
+        Ok(Message::CloseChannelEndReply(CloseChannelEndReply {
+            serial: 0,
+            result: CloseChannelEndResult::Ok,
+        }))
+    ) {
+        panic!();
+    };
+}
+
+#[tokio::test]
+async fn calls_from_multiple_clients() {
     let mut broker = TestBroker::new();
 
     let mut client1 = broker.add_client().await;
     let obj = client1.create_object(ObjectUuid::new_v4()).await.unwrap();
     let info = ServiceInfo::new(0);
-    let svc = obj
+    let mut svc = obj
         .create_service(ServiceUuid::new_v4(), info)
         .await
         .unwrap();
@@ -556,11 +946,1497 @@ async fn destroy_service_after_abort() {
     let mut client2 = broker.add_client().await;
     let proxy = client2.create_proxy(svc.id()).await.unwrap();
     let reply = proxy.call(0, &());
+    svc.next_call()
+        .await
+        .unwrap()
+        .into_promise()
+        .done()
+        .unwrap();
+    reply.await.unwrap().unwrap();
 
-    reply.abort();
-    svc.destroy().await.unwrap();
+    let mut client3 = broker.add_client().await;
+    let proxy = client3.create_proxy(svc.id()).await.unwrap();
+    let reply = proxy.call(0, &());
+    svc.next_call()
+        .await
+        .unwrap()
+        .into_promise()
+        .done()
+        .unwrap();
+    reply.await.unwrap().unwrap();
 
     client1.join().await;
     client2.join().await;
+    client3.join().await;
     broker.join().await;
 }
+
+#[tokio::test]
+async fn duplicate_call_serial() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    tokio::spawn(broker.run());
+
+    let mut client = TestClient::new(&mut handle).connect_raw().await;
+
+    let object_uuid = ObjectUuid::new_v4();
+
+    client
+        .send(Message::CreateObject(CreateObject {
+            serial: 0,
+            uuid: object_uuid,
+        }))
+        .await
+        .unwrap();
+
+    let Message::CreateObjectReply(CreateObjectReply {
+        result: CreateObjectResult::Ok(object_cookie),
+        ..
+    }) = client.receive().await.unwrap()
+    else {
+        panic!();
+    };
+
+    let service_uuid = ServiceUuid::new_v4();
+
+    client
+        .send(Message::CreateService(CreateService {
+            serial: 0,
+            object_cookie,
+            uuid: service_uuid,
+            version: 0,
+        }))
+        .await
+        .unwrap();
+
+    let Message::CreateServiceReply(CreateServiceReply {
+        result: CreateServiceResult::Ok(service_cookie),
+        ..
+    }) = client.receive().await.unwrap()
+    else {
+        panic!();
+    };
+
+    client
+        .send(Message::CallFunction(CallFunction {
+            serial: 0,
+            service_cookie,
+            function: 0,
+            value: SerializedValue::serialize(&()).unwrap(),
+            trace_context: None,
+            priority: CallPriority::Normal,
+        }))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        client.receive().await.unwrap(),
+        Message::CallFunction(_)
+    ));
+
+    client
+        .send(Message::CallFunction(CallFunction {
+            serial: 0,
+            service_cookie,
+            function: 0,
+            value: SerializedValue::serialize(&()).unwrap(),
+            trace_context: None,
+            priority: CallPriority::Normal,
+        }))
+        .await
+        .unwrap();
+
+    assert_eq!(client.receive().await, Err(Disconnected));
+}
+
+#[tokio::test]
+async fn strip_trace_context_for_old_clients() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    tokio::spawn(broker.run());
+
+    let mut client = TestClient::new(&mut handle).connect_raw().await;
+
+    let object_uuid = ObjectUuid::new_v4();
+
+    client
+        .send(Message::CreateObject(CreateObject {
+            serial: 0,
+            uuid: object_uuid,
+        }))
+        .await
+        .unwrap();
+
+    let Message::CreateObjectReply(CreateObjectReply {
+        result: CreateObjectResult::Ok(object_cookie),
+        ..
+    }) = client.receive().await.unwrap()
+    else {
+        panic!();
+    };
+
+    let service_uuid = ServiceUuid::new_v4();
+
+    client
+        .send(Message::CreateService(CreateService {
+            serial: 0,
+            object_cookie,
+            uuid: service_uuid,
+            version: 0,
+        }))
+        .await
+        .unwrap();
+
+    let Message::CreateServiceReply(CreateServiceReply {
+        result: CreateServiceResult::Ok(service_cookie),
+        ..
+    }) = client.receive().await.unwrap()
+    else {
+        panic!();
+    };
+
+    let (caller, _) = TestClient::new(&mut handle).connect().await;
+    let service_id = ServiceId::new(
+        ObjectId::new(object_uuid, object_cookie),
+        service_uuid,
+        service_cookie,
+    );
+    let proxy = Proxy::new(&caller, service_id).await.unwrap();
+    let _reply = proxy.call_with_trace_context(0, &(), Some(TraceContext::new(1, 2, true)));
+
+    let Message::CallFunction(msg) = client.receive().await.unwrap() else {
+        panic!();
+    };
+
+    assert_eq!(msg.trace_context, None);
+}
+
+#[tokio::test]
+async fn events_routed_directly_in_order() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    tokio::spawn(broker.run());
+
+    let mut owner = TestClient::new(&mut handle).connect_raw().await;
+    let mut subscriber = TestClient::new(&mut handle).connect_raw().await;
+
+    owner
+        .send(Message::CreateObject(CreateObject {
+            serial: 0,
+            uuid: ObjectUuid::new_v4(),
+        }))
+        .await
+        .unwrap();
+
+    let Message::CreateObjectReply(CreateObjectReply {
+        result: CreateObjectResult::Ok(object_cookie),
+        ..
+    }) = owner.receive().await.unwrap()
+    else {
+        panic!();
+    };
+
+    owner
+        .send(Message::CreateService(CreateService {
+            serial: 0,
+            object_cookie,
+            uuid: ServiceUuid::new_v4(),
+            version: 0,
+        }))
+        .await
+        .unwrap();
+
+    let Message::CreateServiceReply(CreateServiceReply {
+        result: CreateServiceResult::Ok(service_cookie),
+        ..
+    }) = owner.receive().await.unwrap()
+    else {
+        panic!();
+    };
+
+    subscriber
+        .send(Message::SubscribeEvent(SubscribeEvent {
+            serial: Some(0),
+            service_cookie,
+            event: 0,
+        }))
+        .await
+        .unwrap();
+
+    let Message::SubscribeEventReply(reply) = subscriber.receive().await.unwrap() else {
+        panic!();
+    };
+    assert_eq!(reply.result, SubscribeEventResult::Ok);
+
+    let Message::SubscribeEvent(_) = owner.receive().await.unwrap() else {
+        panic!();
+    };
+
+    // Interleave events with a message, that must be handled by the broker.
+    for i in 0..100u32 {
+        if i == 50 {
+            owner.send(Message::Sync(Sync { serial: 0 })).await.unwrap();
+        }
+
+        owner
+            .send(Message::EmitEvent(EmitEvent {
+                service_cookie,
+                event: 0,
+                value: SerializedValue::serialize(&i).unwrap(),
+                trace_context: None,
+            }))
+            .await
+            .unwrap();
+    }
+
+    owner
+        .send(Message::DestroyService(DestroyService {
+            serial: 1,
+            cookie: service_cookie,
+        }))
+        .await
+        .unwrap();
+
+    for i in 0..100u32 {
+        let Message::EmitEvent(msg) = subscriber.receive().await.unwrap() else {
+            panic!();
+        };
+
+        assert_eq!(msg.value.deserialize::<u32>().unwrap(), i);
+    }
+
+    let Message::ServiceDestroyed(msg) = subscriber.receive().await.unwrap() else {
+        panic!();
+    };
+    assert_eq!(msg.service_cookie, service_cookie);
+}
+
+#[tokio::test]
+async fn destroy_service_after_abort() {
+    let mut broker = TestBroker::new();
+
+    let mut client1 = broker.add_client().await;
+    let obj = client1.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let mut client2 = broker.add_client().await;
+    let proxy = client2.create_proxy(svc.id()).await.unwrap();
+    let reply = proxy.call(0, &());
+
+    reply.abort();
+    svc.destroy().await.unwrap();
+
+    client1.join().await;
+    client2.join().await;
+    broker.join().await;
+}
+
+#[tokio::test]
+async fn dead_letters_for_function_calls() {
+    let mut broker = TestBroker::new();
+    let mut dead_letters = broker.dead_letters(4).await.unwrap();
+
+    let mut client1 = broker.add_client().await;
+    let obj = client1.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+    let cookie = svc.id().cookie;
+
+    let mut client2 = broker.add_client().await;
+    let proxy = client2.create_proxy(svc.id()).await.unwrap();
+
+    // Destroy the service while a call is pending.
+    let reply = proxy.call(1, &());
+    let _call = svc.next_call().await.unwrap();
+    svc.destroy().await.unwrap();
+    assert!(reply.await.is_err());
+
+    let letter = dead_letters.next().await.unwrap();
+    assert_eq!(letter.kind(), DeadLetterKind::CallServiceDestroyed);
+    assert_eq!(letter.service(), cookie);
+    assert_eq!(letter.id(), 1);
+    assert_eq!(letter.num_lost(), 0);
+
+    // Call the service after it has been destroyed.
+    assert!(proxy.call(2, &()).await.is_err());
+
+    let letter = dead_letters.next().await.unwrap();
+    assert_eq!(letter.kind(), DeadLetterKind::CallInvalidService);
+    assert_eq!(letter.service(), cookie);
+    assert_eq!(letter.id(), 2);
+    assert_eq!(letter.num_lost(), 0);
+
+    client1.join().await;
+    client2.join().await;
+    broker.join().await;
+
+    assert!(dead_letters.next().await.is_none());
+}
+
+#[tokio::test]
+async fn max_calls() {
+    let mut broker = TestBroker::new();
+    let mut dead_letters = broker.dead_letters(4).await.unwrap();
+
+    let mut client = broker.add_client().await;
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0).set_max_calls(1);
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+    let cookie = svc.id().cookie;
+    let proxy = client.create_proxy(svc.id()).await.unwrap();
+
+    // The second call is rejected while the first one is still pending.
+    let reply = proxy.call(1, &());
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(
+        proxy.call(2, &()).await.unwrap_err().without_context(),
+        Error::Overloaded
+    );
+
+    let letter = dead_letters.next().await.unwrap();
+    assert_eq!(letter.kind(), DeadLetterKind::CallOverloaded);
+    assert_eq!(letter.service(), cookie);
+    assert_eq!(letter.id(), 2);
+
+    call.into_promise().done().unwrap();
+    reply.await.unwrap().unwrap();
+
+    // The service accepts calls again.
+    let reply = proxy.call(3, &());
+    let call = svc.next_call().await.unwrap();
+    call.into_promise().done().unwrap();
+    reply.await.unwrap().unwrap();
+
+    client.join().await;
+    broker.join().await;
+}
+
+#[tokio::test]
+async fn dead_letters_lost() {
+    let mut broker = TestBroker::new();
+    let mut dead_letters = broker.dead_letters(0).await.unwrap();
+
+    let mut client = broker.add_client().await;
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+    let proxy = client.create_proxy(svc.id()).await.unwrap();
+    svc.destroy().await.unwrap();
+
+    for function in 0..3 {
+        assert!(proxy.call(function, &()).await.is_err());
+    }
+
+    // The stream has room for only 1 dead letter.
+    let letter = dead_letters.next().await.unwrap();
+    assert_eq!(letter.id(), 0);
+    assert_eq!(letter.num_lost(), 0);
+
+    assert!(proxy.call(3, &()).await.is_err());
+
+    let letter = dead_letters.next().await.unwrap();
+    assert_eq!(letter.id(), 3);
+    assert_eq!(letter.num_lost(), 2);
+
+    client.join().await;
+    broker.join().await;
+}
+
+#[cfg(feature = "consistency-check")]
+#[tokio::test]
+async fn consistency_check() {
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+    let proxy = client.create_proxy(svc.id()).await.unwrap();
+    proxy.subscribe_all().await.unwrap();
+    let _reply = proxy.call(0, &());
+    let (_sender, _receiver) = client
+        .create_channel::<()>()
+        .claim_receiver(1)
+        .await
+        .unwrap();
+    client.sync_broker().await.unwrap();
+
+    let report = broker.check_consistency().await.unwrap();
+    assert!(report.is_consistent(), "{:?}", report.inconsistencies());
+    assert!(report.events() > 0);
+
+    client.join().await;
+    broker.join().await;
+}
+
+#[cfg(feature = "consistency-check")]
+#[test]
+fn consistency_check_reports_inconsistencies() {
+    use super::Inconsistency;
+    use crate::core::{ObjectCookie, ObjectId};
+
+    let mut broker = Broker::new();
+    assert!(broker.check_consistency().is_consistent());
+
+    let id = ObjectId::new(ObjectUuid::new_v4(), ObjectCookie::new_v4());
+    broker.obj_uuids.insert(id.cookie, id.uuid);
+
+    let report = broker.check_consistency();
+    assert_eq!(
+        report.inconsistencies(),
+        [
+            Inconsistency::ObjectCountMismatch {
+                cookies: 1,
+                objects: 0,
+            },
+            Inconsistency::ObjectMissing(id),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn slow_consumer_disconnect() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (client, _) = TestClient::new(&mut handle).connect().await;
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let limit = SendQueueLimit::new(4, SlowConsumerPolicy::Disconnect);
+    // A capacity of 1 makes sure that the connection blocks as soon as the client stops reading.
+    let mut slow = TestClient::new(&mut handle)
+        .send_queue_limit(limit)
+        .connect_raw_bounded(1)
+        .await;
+    subscribe_event(&mut slow, svc.id().cookie, 0).await;
+
+    for _ in 0..10 {
+        svc.emit(0, &()).unwrap();
+    }
+    client.sync_broker().await.unwrap();
+
+    let mut events = 0;
+    loop {
+        match slow.receive().await {
+            Ok(Message::EmitEvent(_)) => events += 1,
+            Ok(msg) => panic!("unexpected message {msg:?}"),
+            Err(Disconnected) => break,
+        }
+    }
+    assert!(events < 10);
+
+    #[cfg(feature = "statistics")]
+    {
+        let stats = handle.take_statistics().await.unwrap();
+        assert_eq!(stats.slow_consumers(), 1);
+        assert_eq!(stats.num_connections(), 1);
+    }
+
+    client.shutdown();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn slow_consumer_drop_events() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+    let mut dead_letters = handle.dead_letters(16).await.unwrap();
+
+    let (client, _) = TestClient::new(&mut handle).connect().await;
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let limit = SendQueueLimit::new(2, SlowConsumerPolicy::DropEvents);
+    // A capacity of 1 makes sure that the connection blocks as soon as the client stops reading.
+    let mut slow = TestClient::new(&mut handle)
+        .send_queue_limit(limit)
+        .connect_raw_bounded(1)
+        .await;
+    subscribe_event(&mut slow, svc.id().cookie, 0).await;
+
+    for _ in 0..10 {
+        svc.emit(0, &()).unwrap();
+    }
+    client.sync_broker().await.unwrap();
+
+    // Other messages are still delivered.
+    slow.send(Message::Sync(Sync { serial: 0 })).await.unwrap();
+
+    let mut events = 0;
+    loop {
+        match slow.receive().await.unwrap() {
+            Message::EmitEvent(_) => events += 1,
+            Message::SyncReply(_) => break,
+            msg => panic!("unexpected message {msg:?}"),
+        }
+    }
+    assert!(events < 10);
+
+    for _ in events..10 {
+        let letter = dead_letters.next().await.unwrap();
+        assert_eq!(letter.kind(), crate::DeadLetterKind::EventDropped);
+        assert_eq!(letter.service(), svc.id().cookie);
+        assert_eq!(letter.id(), 0);
+        assert_eq!(letter.num_lost(), 0);
+    }
+
+    #[cfg(feature = "statistics")]
+    {
+        let stats = handle.take_statistics().await.unwrap();
+        assert_eq!(stats.slow_consumers(), 1);
+        assert_eq!(stats.events_dropped(), 10 - events);
+        assert_eq!(stats.dead_letters(), 10 - events);
+        assert_eq!(stats.num_connections(), 2);
+    }
+
+    client.shutdown();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[cfg(feature = "statistics")]
+#[tokio::test]
+async fn event_statistics_count_only_subscribers() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (client, _) = TestClient::new(&mut handle).connect().await;
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let (subscriber, _) = TestClient::new(&mut handle).connect().await;
+    let mut proxy = Proxy::new(&subscriber, svc.id()).await.unwrap();
+    proxy.subscribe(1).await.unwrap();
+
+    // Neither of these connections receives event 1.
+    let (_idle, _) = TestClient::new(&mut handle).connect().await;
+    let (other, _) = TestClient::new(&mut handle).connect().await;
+    let other_proxy = Proxy::new(&other, svc.id()).await.unwrap();
+    other_proxy.subscribe(2).await.unwrap();
+
+    handle.take_statistics().await.unwrap();
+
+    for _ in 0..3 {
+        svc.emit(1, &()).unwrap();
+    }
+
+    for _ in 0..3 {
+        assert_eq!(proxy.next_event().await.unwrap().id(), 1);
+    }
+
+    let stats = handle.take_statistics().await.unwrap();
+    assert_eq!(stats.num_connections(), 4);
+    assert_eq!(stats.events_emitted(), 3);
+    assert_eq!(stats.events_delivered(), 3);
+    assert_eq!(stats.events_dropped(), 0);
+
+    client.shutdown();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn high_priority_call_overtakes_events() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (client, _) = TestClient::new(&mut handle).connect().await;
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    // A capacity of 1 makes sure that the connection blocks as soon as the client stops reading.
+    let mut slow = TestClient::new(&mut handle).connect_raw_bounded(1).await;
+    subscribe_event(&mut slow, svc.id().cookie, 0).await;
+
+    let object_uuid = ObjectUuid::new_v4();
+
+    slow.send(Message::CreateObject(CreateObject {
+        serial: 0,
+        uuid: object_uuid,
+    }))
+    .await
+    .unwrap();
+
+    let Message::CreateObjectReply(CreateObjectReply {
+        result: CreateObjectResult::Ok(object_cookie),
+        ..
+    }) = slow.receive().await.unwrap()
+    else {
+        panic!();
+    };
+
+    let service_uuid = ServiceUuid::new_v4();
+
+    slow.send(Message::CreateService(CreateService {
+        serial: 0,
+        object_cookie,
+        uuid: service_uuid,
+        version: 0,
+    }))
+    .await
+    .unwrap();
+
+    let Message::CreateServiceReply(CreateServiceReply {
+        result: CreateServiceResult::Ok(service_cookie),
+        ..
+    }) = slow.receive().await.unwrap()
+    else {
+        panic!();
+    };
+
+    let service_id = ServiceId::new(
+        ObjectId::new(object_uuid, object_cookie),
+        service_uuid,
+        service_cookie,
+    );
+    let proxy = Proxy::new(&client, service_id).await.unwrap();
+
+    for _ in 0..10 {
+        svc.emit(0, &()).unwrap();
+    }
+
+    let options = CallOptions::new().set_priority(CallPriority::High);
+    let _reply = proxy.call_with_options(0, &(), options);
+    client.sync_broker().await.unwrap();
+
+    let mut events = 0;
+    let call = loop {
+        match slow.receive().await.unwrap() {
+            Message::EmitEvent(_) => events += 1,
+            Message::CallFunction(call) => break call,
+            msg => panic!("unexpected message {msg:?}"),
+        }
+    };
+    assert!(events < 10);
+
+    // The priority itself isn't forwarded to clients with older protocol versions.
+    assert_eq!(call.priority, CallPriority::Normal);
+
+    client.shutdown();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn send_timeout() {
+    const VERSION: ProtocolVersion = ProtocolVersion::V1_16;
+
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (mut t1, t2) = channel::bounded(1);
+
+    t1.send(
+        Connect2::with_serialize_data(VERSION.major(), VERSION.minor(), &ConnectData::new())
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let conn = handle.connect(t2).await.unwrap();
+    let conn = tokio::spawn(conn.run_with_send_timeout(|| time::sleep(Duration::from_millis(10))));
+
+    // Neither of the replies is received, so the connection will be stuck sending the second one.
+    for serial in 0..2 {
+        t1.send(Message::Sync(Sync { serial })).await.unwrap();
+    }
+
+    assert_eq!(conn.await.unwrap(), Err(ConnectionError::SendTimeout));
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn keep_alive_timeout() {
+    const VERSION: ProtocolVersion = ProtocolVersion::V1_16;
+
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (mut t1, t2) = channel::unbounded();
+
+    t1.send(
+        Connect2::with_serialize_data(VERSION.major(), VERSION.minor(), &ConnectData::new())
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let conn = handle.connect(t2).await.unwrap();
+    let conn =
+        tokio::spawn(conn.run_with_keep_alive_timeout(|| time::sleep(Duration::from_millis(10))));
+
+    assert_eq!(conn.await.unwrap(), Err(ConnectionError::KeepAliveTimeout));
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn keep_alive() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (t1, t2) = channel::unbounded();
+    let client = tokio::spawn(
+        Client::builder(t1)
+            .with_keep_alive(|| time::sleep(Duration::from_millis(10)))
+            .connect(),
+    );
+
+    let conn = handle.connect(t2).await.unwrap();
+    let conn =
+        tokio::spawn(conn.run_with_keep_alive_timeout(|| time::sleep(Duration::from_millis(50))));
+
+    let client = client.await.unwrap().unwrap();
+    let client_handle = client.handle().clone();
+    let client = tokio::spawn(client.run());
+
+    // Neither side times out, even though the client is otherwise idle.
+    time::sleep(Duration::from_millis(200)).await;
+
+    client_handle.shutdown();
+    client.await.unwrap().unwrap();
+    conn.await.unwrap().unwrap();
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn standby_failover() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let mut failover_events = handle.failover_events().await.unwrap();
+    let (primary, primary_conn) = TestClient::new(&mut handle).connect().await;
+    let (standby, standby_conn) = TestClient::new(&mut handle).connect().await;
+    let (other, _) = TestClient::new(&mut handle).connect().await;
+
+    let uuid = ObjectUuid::new_v4();
+    let svc_uuid = ServiceUuid::new_v4();
+    let obj = primary.create_object(uuid).await.unwrap();
+
+    handle
+        .register_standby(&standby_conn, uuid, vec![svc_uuid])
+        .await
+        .unwrap();
+
+    handle.shutdown_connection(&primary_conn).await.unwrap();
+
+    let FailoverEvent::Promoted(failover) = failover_events.next().await.unwrap() else {
+        panic!("failover not promoted");
+    };
+    assert_eq!(failover.object(), uuid);
+    assert_eq!(failover.services(), [svc_uuid]);
+    assert_eq!(failover.primary(), Some(primary_conn.number()));
+    assert_eq!(failover.standby(), standby_conn.number());
+    mem::drop(obj);
+
+    // The object is reserved for the standby.
+    assert_eq!(
+        other.create_object(uuid).await.unwrap_err(),
+        Error::DuplicateObject
+    );
+
+    let _obj = standby.create_object(uuid).await.unwrap();
+    assert_eq!(
+        failover_events.next().await,
+        Some(FailoverEvent::Completed(failover))
+    );
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn standby_lost() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let mut failover_events = handle.failover_events().await.unwrap();
+    let (_standby, standby_conn) = TestClient::new(&mut handle).connect().await;
+
+    let uuid = ObjectUuid::new_v4();
+    handle
+        .register_standby(&standby_conn, uuid, Vec::new())
+        .await
+        .unwrap();
+
+    handle.shutdown_connection(&standby_conn).await.unwrap();
+
+    let Some(FailoverEvent::StandbyLost(failover)) = failover_events.next().await else {
+        panic!("standby not lost");
+    };
+    assert_eq!(failover.object(), uuid);
+    assert_eq!(failover.primary(), None);
+    assert_eq!(failover.standby(), standby_conn.number());
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[cfg(feature = "introspection")]
+#[tokio::test]
+async fn preloaded_introspection() {
+    use crate::core::introspection::Introspection;
+    use crate::core::message::{QueryIntrospection, QueryIntrospectionResult};
+    use crate::core::TypeId;
+    use crate::IntrospectionDump;
+
+    let type_id = TypeId::compute::<u32>();
+    let mut dump = IntrospectionDump::new();
+    dump.insert(&Introspection::new::<u32>()).unwrap();
+
+    let mut broker = Broker::new();
+    broker.load_introspection(&dump);
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let mut client = TestClient::new(&mut handle)
+        .version(ProtocolVersion::V1_17)
+        .connect_raw()
+        .await;
+
+    client
+        .send(Message::QueryIntrospection(QueryIntrospection::new(
+            0, type_id,
+        )))
+        .await
+        .unwrap();
+
+    let Message::QueryIntrospectionReply(reply) = client.receive().await.unwrap() else {
+        panic!("expected query-introspection-reply");
+    };
+    assert_eq!(reply.serial, 0);
+    assert_eq!(
+        reply.result,
+        QueryIntrospectionResult::Ok(dump.get(type_id).unwrap().clone())
+    );
+
+    assert_eq!(handle.dump_introspection().await.unwrap(), dump);
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[cfg(feature = "introspection")]
+#[tokio::test]
+async fn introspection_bundle() {
+    use crate::core::introspection::Introspection;
+    use crate::core::message::{QueryIntrospection, QueryIntrospectionResult};
+    use crate::core::TypeId;
+    use crate::IntrospectionDump;
+    use std::collections::HashMap;
+
+    let type_id1 = TypeId::compute::<Option<Vec<u32>>>();
+    let type_id2 = TypeId::compute::<Vec<u32>>();
+    let type_id3 = TypeId::compute::<u32>();
+
+    let mut dump = IntrospectionDump::new();
+    dump.insert(&Introspection::new::<Option<Vec<u32>>>())
+        .unwrap();
+    dump.insert(&Introspection::new::<Vec<u32>>()).unwrap();
+    dump.insert(&Introspection::new::<u32>()).unwrap();
+
+    let mut broker = Broker::new();
+    broker.load_introspection(&dump);
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let mut client = TestClient::new(&mut handle)
+        .version(ProtocolVersion::V1_19)
+        .connect_raw()
+        .await;
+
+    let mut query = QueryIntrospection::new(0, type_id1);
+    query.known.push(type_id3);
+    client
+        .send(Message::QueryIntrospection(query))
+        .await
+        .unwrap();
+
+    let Message::QueryIntrospectionReply(reply) = client.receive().await.unwrap() else {
+        panic!("expected query-introspection-reply");
+    };
+    assert_eq!(reply.serial, 0);
+    assert!(matches!(reply.result, QueryIntrospectionResult::Bundle(_)));
+
+    let introspections = reply.result.into_introspections(type_id1).unwrap();
+    let expected = HashMap::from([
+        (type_id1, dump.get(type_id1).unwrap().clone()),
+        (type_id2, dump.get(type_id2).unwrap().clone()),
+    ]);
+    assert_eq!(introspections, expected);
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[cfg(feature = "introspection")]
+#[tokio::test]
+async fn introspection_conflict() {
+    use crate::core::introspection::Introspection;
+    use crate::core::message::{
+        QueryIntrospection, QueryIntrospectionReply, QueryIntrospectionResult,
+        RegisterIntrospection,
+    };
+    use crate::core::{SerializedValue, TypeId};
+    use crate::IntrospectionConflictKind;
+
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let mut conflicts = handle.introspection_conflicts().await.unwrap();
+    let mut client1 = TestClient::new(&mut handle)
+        .version(ProtocolVersion::V1_17)
+        .connect_raw()
+        .await;
+    let mut client2 = TestClient::new(&mut handle)
+        .version(ProtocolVersion::V1_17)
+        .connect_raw()
+        .await;
+
+    let type_id = TypeId::compute::<u32>();
+    client1
+        .send(Message::RegisterIntrospection(
+            RegisterIntrospection::with_serialize_type_ids(&[type_id].into()).unwrap(),
+        ))
+        .await
+        .unwrap();
+
+    client2
+        .send(Message::QueryIntrospection(QueryIntrospection::new(
+            0, type_id,
+        )))
+        .await
+        .unwrap();
+
+    let Message::QueryIntrospection(query) = client1.receive().await.unwrap() else {
+        panic!("expected query-introspection");
+    };
+    assert_eq!(query.type_id, type_id);
+
+    let introspection = SerializedValue::serialize(&Introspection::new::<u64>()).unwrap();
+    client1
+        .send(Message::QueryIntrospectionReply(QueryIntrospectionReply {
+            serial: query.serial,
+            result: QueryIntrospectionResult::Ok(introspection),
+        }))
+        .await
+        .unwrap();
+
+    let Message::QueryIntrospectionReply(reply) = client2.receive().await.unwrap() else {
+        panic!("expected query-introspection-reply");
+    };
+    assert_eq!(reply.serial, 0);
+
+    let conflict = conflicts.next().await.unwrap();
+    assert_eq!(conflict.type_id(), type_id);
+    assert_eq!(
+        conflict.kind(),
+        IntrospectionConflictKind::TypeIdMismatch(TypeId::compute::<u64>())
+    );
+
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn list_connections() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let join = tokio::spawn(broker.run());
+
+    let (t1, t2) = channel::unbounded();
+
+    let client = Client::builder(t1)
+        .with_name("foo")
+        .with_metadata("bar", "baz")
+        .connect();
+
+    let conn = async {
+        let conn = handle.begin_connect(t2).await?;
+        assert_eq!(conn.name(), Some("foo"));
+        assert_eq!(conn.metadata().get("bar").map(String::as_str), Some("baz"));
+        conn.accept(None).await
+    };
+
+    let (client, conn) = tokio::join!(client, conn);
+    let conn = conn.unwrap();
+    let number = conn.handle().number();
+    tokio::spawn(conn.run());
+
+    let client = client.unwrap();
+    let client_handle = client.handle().clone();
+    let client_join = tokio::spawn(client.run());
+
+    let obj = client_handle
+        .create_object(ObjectUuid::new_v4())
+        .await
+        .unwrap();
+    let _svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await
+        .unwrap();
+
+    let conns = handle.list_connections().await.unwrap();
+    assert_eq!(conns.len(), 1);
+    assert_eq!(conns[0].number(), number);
+    assert_eq!(conns[0].name(), Some("foo"));
+    assert_eq!(conns[0].metadata().len(), 1);
+    assert_eq!(conns[0].objects(), [obj.id()]);
+    assert_eq!(conns[0].num_services(), 1);
+    assert_eq!(conns[0].protocol_version(), ProtocolVersion::V1_19);
+
+    obj.destroy().await.unwrap();
+    let conns = handle.list_connections().await.unwrap();
+    assert!(conns[0].objects().is_empty());
+
+    client_handle.shutdown();
+    client_join.await.unwrap().unwrap();
+    handle.shutdown().await;
+    join.await.unwrap();
+}
+
+#[tokio::test]
+async fn shutdown_connection_by_number() {
+    let mut broker = TestBroker::new();
+    let mut client1 = broker.add_client().await;
+    let mut client2 = broker.add_client().await;
+
+    let conns = broker.list_connections().await.unwrap();
+    assert_eq!(conns.len(), 2);
+
+    assert!(broker
+        .shutdown_connection_by_number(conns[0].number())
+        .await
+        .unwrap());
+    client1.join().await;
+
+    let conns2 = broker.list_connections().await.unwrap();
+    assert_eq!(conns2.len(), 1);
+    assert_eq!(conns2[0].number(), conns[1].number());
+
+    assert!(!broker
+        .shutdown_connection_by_number(conns[0].number())
+        .await
+        .unwrap());
+
+    client2.join().await;
+    broker.join().await;
+}
+
+#[test]
+fn run_until_idle() {
+    let mut broker = Broker::new();
+    let handle = broker.handle().clone();
+
+    assert!(!broker.run_until_idle());
+    assert!(!broker.is_finished());
+
+    mem::drop(handle);
+    assert!(broker.run_until_idle());
+    assert!(broker.is_finished());
+}
+
+#[test]
+fn shutdown_idle_without_runtime() {
+    let mut broker = Broker::new();
+    let _handle = broker.handle().clone();
+
+    broker.shutdown_idle();
+    assert!(broker.run_until_idle());
+}
+
+#[tokio::test]
+async fn poll_manually() {
+    let mut broker = Broker::new();
+    let mut handle = broker.handle().clone();
+
+    let (t1, t2) = channel::unbounded();
+    let client = tokio::spawn(Client::connect(t1));
+    let conn = handle.connect(t2).await.unwrap();
+    let conn_join = tokio::spawn(conn.run());
+
+    let client = client.await.unwrap().unwrap();
+    let client_handle = client.handle().clone();
+    let client_join = tokio::spawn(client.run());
+
+    let obj_uuid = ObjectUuid::new_v4();
+    let create = pin!(client_handle.create_object(obj_uuid));
+
+    let obj = match future::select(create, future::poll_fn(|cx| broker.poll(cx))).await {
+        Either::Left((obj, _)) => obj.unwrap(),
+        Either::Right(((), _)) => panic!("broker shut down unexpectedly"),
+    };
+
+    let conns = broker.list_connections();
+    assert_eq!(conns.len(), 1);
+    assert_eq!(conns[0].objects(), [obj.id()]);
+
+    broker.shutdown();
+    assert!(broker.run_until_idle());
+
+    client_join.await.unwrap().unwrap();
+    conn_join.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn inject_message() {
+    let mut broker = Broker::new();
+    let mut handle = broker.handle().clone();
+
+    let (mut t1, t2) = channel::unbounded();
+    t1.send_and_flush(Connect::with_serialize_value(14, &()).unwrap())
+        .await
+        .unwrap();
+
+    let conn = handle.connect(t2).await.unwrap();
+    let conn_handle = conn.handle().clone();
+    assert!(matches!(
+        t1.receive().await.unwrap(),
+        Message::ConnectReply(ConnectReply::Ok(_))
+    ));
+
+    let conn_join = tokio::spawn(conn.run());
+    assert!(!broker.run_until_idle());
+
+    let object_uuid = ObjectUuid::new_v4();
+    assert!(broker.inject_message(
+        &conn_handle,
+        CreateObject {
+            serial: 0,
+            uuid: object_uuid,
+        }
+    ));
+
+    let conns = broker.list_connections();
+    assert_eq!(conns.len(), 1);
+    assert_eq!(conns[0].objects().len(), 1);
+    assert_eq!(conns[0].objects()[0].uuid, object_uuid);
+
+    let reply = match t1.receive().await.unwrap() {
+        Message::CreateObjectReply(reply) => reply,
+        msg => panic!("invalid msg received {msg:?}"),
+    };
+    assert_eq!(reply.serial, 0);
+    assert!(matches!(reply.result, CreateObjectResult::Ok(_)));
+
+    broker.shutdown_connection(&conn_handle);
+    assert!(!broker.inject_message(&conn_handle, Sync { serial: 1 }));
+
+    assert_eq!(t1.receive().await.unwrap(), Message::Shutdown(Shutdown));
+    t1.send_and_flush(Shutdown).await.unwrap();
+    conn_join.await.unwrap().unwrap();
+
+    broker.shutdown();
+    assert!(broker.run_until_idle());
+}
+
+async fn visible_objects(client: &Handle) -> Vec<ObjectId> {
+    let mut bus_listener = client.create_bus_listener().await.unwrap();
+    bus_listener
+        .add_filter(BusListenerFilter::any_object())
+        .unwrap();
+    bus_listener.start(BusListenerScope::Current).await.unwrap();
+
+    let mut objects = Vec::new();
+    while let Some(event) = bus_listener.next_event().await {
+        let BusEvent::ObjectCreated(object) = event else {
+            panic!("unexpected bus event {event:?}");
+        };
+
+        objects.push(object);
+    }
+
+    objects.sort_by_key(|object| object.uuid);
+    objects
+}
+
+#[tokio::test]
+async fn namespaces() {
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    tokio::spawn(broker.run());
+
+    let (default, _) = TestClient::new(&mut handle).connect().await;
+    let (a, _) = TestClient::new(&mut handle)
+        .namespace("test.a")
+        .connect()
+        .await;
+    let (b, _) = TestClient::new(&mut handle)
+        .namespace("test.b")
+        .visible(&["test.a"])
+        .connect()
+        .await;
+    let (all, _) = TestClient::new(&mut handle)
+        .visible(&["test.*"])
+        .connect()
+        .await;
+
+    let obj_default = default.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let obj_a = a.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let obj_b = b.create_object(ObjectUuid::new_v4()).await.unwrap();
+
+    let sorted = |mut objects: Vec<ObjectId>| {
+        objects.sort_by_key(|object| object.uuid);
+        objects
+    };
+
+    assert_eq!(
+        visible_objects(&default).await,
+        sorted(vec![obj_default.id()])
+    );
+
+    assert_eq!(
+        visible_objects(&a).await,
+        sorted(vec![obj_default.id(), obj_a.id()])
+    );
+
+    assert_eq!(
+        visible_objects(&b).await,
+        sorted(vec![obj_default.id(), obj_a.id(), obj_b.id()])
+    );
+
+    assert_eq!(
+        visible_objects(&all).await,
+        sorted(vec![obj_default.id(), obj_a.id(), obj_b.id()])
+    );
+
+    // New objects are filtered as well.
+    let mut bus_listener = a.create_bus_listener().await.unwrap();
+    bus_listener
+        .add_filter(BusListenerFilter::any_object())
+        .unwrap();
+    bus_listener.start(BusListenerScope::New).await.unwrap();
+
+    let obj_b2 = b.create_object(ObjectUuid::new_v4()).await.unwrap();
+    obj_b2.destroy().await.unwrap();
+    let obj_default2 = default.create_object(ObjectUuid::new_v4()).await.unwrap();
+
+    let event = bus_listener.next_event().await.unwrap();
+    assert_eq!(event, BusEvent::ObjectCreated(obj_default2.id()));
+
+    let conns = handle.list_connections().await.unwrap();
+    let mut namespaces = conns
+        .iter()
+        .map(|conn| conn.namespace())
+        .collect::<Vec<_>>();
+    namespaces.sort();
+    assert_eq!(namespaces, [None, None, Some("test.a"), Some("test.b")]);
+
+    handle.shutdown().await;
+}
+
+/// Connects clients to a broker in tests.
+///
+/// [`connect`](Self::connect) creates an Aldrin client, while [`connect_raw`](Self::connect_raw)
+/// and [`connect_raw_bounded`](Self::connect_raw_bounded) return the client's end of the
+/// transport.
+struct TestClient<'a> {
+    broker: &'a mut BrokerHandle,
+    version: ProtocolVersion,
+    namespace: Option<&'a str>,
+    visible: &'a [&'a str],
+    #[cfg(unix)]
+    fd_passing: bool,
+    send_queue_limit: Option<SendQueueLimit>,
+}
+
+impl<'a> TestClient<'a> {
+    fn new(broker: &'a mut BrokerHandle) -> Self {
+        Self {
+            broker,
+            version: ProtocolVersion::V1_16,
+            namespace: None,
+            visible: &[],
+            #[cfg(unix)]
+            fd_passing: false,
+            send_queue_limit: None,
+        }
+    }
+
+    /// Sets the protocol version of raw clients.
+    #[cfg_attr(not(feature = "introspection"), allow(dead_code))]
+    fn version(mut self, version: ProtocolVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    fn namespace(mut self, namespace: &'a str) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
+    fn visible(mut self, patterns: &'a [&'a str]) -> Self {
+        self.visible = patterns;
+        self
+    }
+
+    /// Connects over a Unix socket pair instead of a channel.
+    #[cfg(unix)]
+    fn fd_passing(mut self) -> Self {
+        self.fd_passing = true;
+        self
+    }
+
+    fn send_queue_limit(mut self, limit: SendQueueLimit) -> Self {
+        self.send_queue_limit = Some(limit);
+        self
+    }
+
+    async fn connect(self) -> (Handle, ConnectionHandle) {
+        #[cfg(unix)]
+        if self.fd_passing {
+            let (s1, s2) = UnixStream::pair().unwrap();
+            return self
+                .connect_over(TokioUnixTransport::new(s1), TokioUnixTransport::new(s2))
+                .await;
+        }
+
+        let (t1, t2) = channel::unbounded();
+        self.connect_over(t1, t2).await
+    }
+
+    async fn connect_over<T>(self, t1: T, t2: T) -> (Handle, ConnectionHandle)
+    where
+        T: AsyncTransport + Unpin + Send + 'static,
+        T::Error: fmt::Debug + Send,
+    {
+        let mut builder = Client::builder(t1);
+        if let Some(namespace) = self.namespace {
+            builder = builder.with_namespace(namespace);
+        }
+        for pattern in self.visible {
+            builder = builder.with_visible_namespace(*pattern);
+        }
+
+        let conn = accept(self.broker, t2, self.send_queue_limit);
+        let (client, conn) = future::join(builder.connect(), conn).await;
+
+        let client = client.unwrap();
+        let handle = client.handle().clone();
+        tokio::spawn(client.run());
+
+        let conn_handle = conn.handle().clone();
+        tokio::spawn(conn.run());
+
+        (handle, conn_handle)
+    }
+
+    async fn connect_raw(self) -> Unbounded {
+        let (t1, t2) = channel::unbounded();
+        self.connect_raw_over(t1, t2).await
+    }
+
+    async fn connect_raw_bounded(self, capacity: usize) -> Bounded {
+        let (t1, t2) = channel::bounded(capacity);
+        self.connect_raw_over(t1, t2).await
+    }
+
+    async fn connect_raw_over<T>(self, mut t1: T, t2: T) -> T
+    where
+        T: AsyncTransport + Unpin + Send + 'static,
+        T::Error: fmt::Debug + Send,
+    {
+        let mut data = ConnectData::new();
+        data.namespace = self.namespace.map(ToOwned::to_owned);
+        data.visible_namespaces = self.visible.iter().map(|&p| p.to_owned()).collect();
+
+        #[cfg(unix)]
+        assert!(!self.fd_passing, "raw clients don't support fd passing");
+
+        let major = self.version.major();
+        let minor = self.version.minor();
+        t1.send_and_flush(Connect2::with_serialize_data(major, minor, &data).unwrap())
+            .await
+            .unwrap();
+
+        let conn = accept(self.broker, t2, self.send_queue_limit).await;
+
+        let Message::ConnectReply2(reply) = t1.receive().await.unwrap() else {
+            panic!("expected connect-reply2");
+        };
+        assert_eq!(reply.result, ConnectResult::Ok(minor));
+
+        tokio::spawn(conn.run());
+        t1
+    }
+}
+
+async fn accept<T>(broker: &mut BrokerHandle, t: T, limit: Option<SendQueueLimit>) -> Connection<T>
+where
+    T: AsyncTransport + Unpin,
+    T::Error: fmt::Debug,
+{
+    let mut conn = broker.begin_connect(t).await.unwrap();
+    if let Some(limit) = limit {
+        conn.set_send_queue_limit(limit);
+    }
+    conn.accept(None).await.unwrap()
+}
+
+/// Subscribes a raw client to an event.
+async fn subscribe_event<T>(t: &mut T, service_cookie: ServiceCookie, event: u32)
+where
+    T: AsyncTransport + Unpin,
+    T::Error: fmt::Debug,
+{
+    t.send_and_flush(Message::SubscribeEvent(SubscribeEvent {
+        serial: Some(0),
+        service_cookie,
+        event,
+    }))
+    .await
+    .unwrap();
+
+    let Message::SubscribeEventReply(reply) = t.receive().await.unwrap() else {
+        panic!("expected subscribe-event-reply");
+    };
+    assert_eq!(reply.result, SubscribeEventResult::Ok);
+}
@@ -1,5 +1,5 @@
 use crate::conn_id::ConnectionId;
-use crate::core::{BusEvent, BusListenerFilter, BusListenerScope, ObjectId, ServiceId};
+use crate::core::{BusEvent, BusListenerFilter, BusListenerScope, LexicalId, ObjectId, ServiceId};
 use std::collections::HashSet;
 
 #[derive(Debug)]
@@ -54,21 +54,60 @@ impl BusListener {
             .any(|filter| filter.matches_object(object))
     }
 
-    pub fn matches_service(&self, service: ServiceId) -> bool {
+    pub fn matches_service(&self, service: ServiceId, layout: Option<LexicalId>) -> bool {
         self.filters
             .iter()
             .copied()
-            .any(|filter| filter.matches_service(service))
+            .any(|filter| filter.matches_service_layout(service, layout))
     }
 
-    pub fn matches_new_event(&self, event: BusEvent) -> bool {
-        self.scope
+    pub fn matches_new_event(
+        &self,
+        event: BusEvent,
+        layout: Option<LexicalId>,
+    ) -> BusListenerMatch {
+        if !self
+            .scope
             .map(BusListenerScope::includes_new)
             .unwrap_or(false)
-            && self
-                .filters
-                .iter()
-                .copied()
-                .any(|filter| filter.matches_event(event))
+        {
+            return BusListenerMatch::None;
+        }
+
+        let mut res = BusListenerMatch::None;
+
+        for filter in &self.filters {
+            match (filter, event) {
+                (BusListenerFilter::Layout(filter), BusEvent::ServiceCreated(service))
+                | (BusListenerFilter::Layout(filter), BusEvent::ServiceDestroyed(service)) => {
+                    if filter.matches(service, layout) {
+                        res = BusListenerMatch::Layout;
+                    }
+                }
+
+                (BusListenerFilter::Layout(_), _) => {}
+
+                (filter, event) => {
+                    if filter.matches_event(event) {
+                        return BusListenerMatch::Broadcast;
+                    }
+                }
+            }
+        }
+
+        res
     }
 }
+
+/// Describes how a new bus event matches a bus listener.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum BusListenerMatch {
+    /// The event doesn't match.
+    None,
+
+    /// The event matches a filter, which clients can evaluate on their own.
+    Broadcast,
+
+    /// The event matches only a layout filter.
+    Layout,
+}
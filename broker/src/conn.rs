@@ -1,20 +1,28 @@
 mod error;
 mod event;
 mod handle;
+mod send_queue;
 
+use crate::broker::{EventDelivery, RoutingTable};
 use crate::conn_id::ConnectionId;
-use crate::core::message::{Message, Shutdown};
+use crate::core::message::{EmitEvent, Message, Shutdown};
 use crate::core::transport::{AsyncTransport, AsyncTransportExt};
 use futures_channel::mpsc::{Sender, UnboundedReceiver};
 use futures_core::stream::FusedStream;
-use futures_util::future::{select, Either};
+use futures_util::future::{self, select, Either};
 use futures_util::sink::SinkExt;
 use futures_util::stream::StreamExt;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
 
 pub(crate) use event::ConnectionEvent;
+pub(crate) use send_queue::SendQueue;
 
 pub use error::{ConnectionError, EstablishError};
 pub use handle::ConnectionHandle;
+pub use send_queue::{SendQueueLimit, SlowConsumerPolicy};
 
 /// Connection between a broker and a client.
 ///
@@ -32,6 +40,10 @@ where
     t: T,
     send: Sender<ConnectionEvent>,
     recv: UnboundedReceiver<Message>,
+    priority_recv: UnboundedReceiver<Message>,
+    queue: Arc<SendQueue>,
+    routes: RoutingTable,
+    track_messages: bool,
     handle: Option<ConnectionHandle>,
 }
 
@@ -44,11 +56,18 @@ where
         id: ConnectionId,
         send: Sender<ConnectionEvent>,
         recv: UnboundedReceiver<Message>,
+        priority_recv: UnboundedReceiver<Message>,
+        queue: Arc<SendQueue>,
+        routes: RoutingTable,
     ) -> Self {
         Self {
             t,
             send,
             recv,
+            priority_recv,
+            queue,
+            routes,
+            track_messages: false,
             handle: Some(ConnectionHandle::new(id)),
         }
     }
@@ -68,26 +87,194 @@ where
     ///
     /// After [establishing](crate::BrokerHandle::connect) a new `Connection`, this method must be
     /// called and polled to completion to run the `Connection`.
-    pub async fn run(mut self) -> Result<(), ConnectionError<T::Error>> {
+    ///
+    /// Sending messages to the client can block indefinitely, if the client stops reading from the
+    /// transport. Use [`run_with_send_timeout`](Self::run_with_send_timeout) to detect this.
+    pub async fn run(self) -> Result<(), ConnectionError<T::Error>> {
+        self.run_impl(
+            None::<fn() -> future::Pending<()>>,
+            None::<fn() -> future::Pending<()>>,
+        )
+        .await
+    }
+
+    /// Runs the connection with a timeout for sending messages to the client.
+    ///
+    /// This is the same as [`run`](Self::run), except that sending every message is raced against
+    /// a timeout future created by `timeout`. If the timeout elapses first, then the connection is
+    /// shut down and [`ConnectionError::SendTimeout`] is returned. This allows detecting clients
+    /// that have stopped reading from the transport without this crate depending on any specific
+    /// async runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin_broker::Broker;
+    /// use aldrin_broker::core::channel;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let broker = Broker::new();
+    /// let mut handle = broker.handle().clone();
+    /// tokio::spawn(broker.run());
+    ///
+    /// let (t1, t2) = channel::unbounded();
+    /// let client = tokio::spawn(aldrin::Client::connect(t2));
+    ///
+    /// let conn = handle.connect(t1).await?;
+    /// tokio::spawn(conn.run_with_send_timeout(|| tokio::time::sleep(Duration::from_secs(10))));
+    /// # let client = client.await??;
+    /// # tokio::spawn(client.run());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run_with_send_timeout<F, Fut>(
+        self,
+        timeout: F,
+    ) -> Result<(), ConnectionError<T::Error>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        self.run_impl(Some(timeout), None::<fn() -> future::Pending<()>>)
+            .await
+    }
+
+    /// Runs the connection with a timeout for the liveness of the client.
+    ///
+    /// This is the same as [`run`](Self::run), except that a timeout future is created by
+    /// `timeout` and restarted whenever a message is received from the client. If the timeout
+    /// elapses first, then the connection is shut down and [`ConnectionError::KeepAliveTimeout`] is
+    /// returned. This detects connections, that have died silently.
+    ///
+    /// The timeout should be longer than the keep-alive interval of the clients, which is
+    /// configured with `ClientBuilder::with_keep_alive` in the `aldrin` crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin_broker::Broker;
+    /// use aldrin_broker::core::channel;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let broker = Broker::new();
+    /// let mut handle = broker.handle().clone();
+    /// tokio::spawn(broker.run());
+    ///
+    /// let (t1, t2) = channel::unbounded();
+    /// let client = tokio::spawn(async move {
+    ///     aldrin::Client::builder(t2)
+    ///         .with_keep_alive(|| tokio::time::sleep(Duration::from_secs(10)))
+    ///         .connect()
+    ///         .await
+    /// });
+    ///
+    /// let conn = handle.connect(t1).await?;
+    /// tokio::spawn(
+    ///     conn.run_with_keep_alive_timeout(|| tokio::time::sleep(Duration::from_secs(30))),
+    /// );
+    /// # let client = client.await??;
+    /// # tokio::spawn(client.run());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run_with_keep_alive_timeout<F, Fut>(
+        self,
+        timeout: F,
+    ) -> Result<(), ConnectionError<T::Error>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        self.run_impl(None::<fn() -> future::Pending<()>>, Some(timeout))
+            .await
+    }
+
+    async fn run_impl<F1, Fut1, F2, Fut2>(
+        mut self,
+        mut timeout: Option<F1>,
+        mut keep_alive_timeout: Option<F2>,
+    ) -> Result<(), ConnectionError<T::Error>>
+    where
+        F1: FnMut() -> Fut1,
+        Fut1: Future<Output = ()>,
+        F2: FnMut() -> Fut2,
+        Fut2: Future<Output = ()>,
+    {
         let id = self.handle.take().unwrap().into_id();
+        let mut keep_alive = keep_alive_timeout
+            .as_mut()
+            .map(|timeout| Box::pin(timeout()));
 
         loop {
-            match select(self.recv.next(), self.t.receive()).await {
+            let next = select(
+                future::poll_fn(|cx| poll_broker_msg(&mut self.priority_recv, &mut self.recv, cx)),
+                self.t.receive(),
+            );
+
+            let next = match keep_alive {
+                Some(ref mut keep_alive) => match select(keep_alive, next).await {
+                    Either::Left(((), _)) => None,
+                    Either::Right((next, _)) => Some(next),
+                },
+
+                None => Some(next.await),
+            };
+
+            let Some(next) = next else {
+                self.send_broker_shutdown(id).await?;
+                self.drain_broker_recv().await;
+                return Err(ConnectionError::KeepAliveTimeout);
+            };
+
+            match next {
                 Either::Left((Some(Message::Shutdown(Shutdown)), _)) => {
+                    self.queue.pop();
                     self.t.send_and_flush(Shutdown).await?;
                     self.drain_client_recv().await?;
                     return Ok(());
                 }
 
                 Either::Left((Some(msg), _)) => {
-                    if let Err(e) = self.t.send_and_flush(msg).await {
+                    self.queue.pop();
+
+                    if self.queue.is_disconnected() {
+                        self.send_broker_shutdown(id).await?;
+                        return Err(ConnectionError::SlowConsumer);
+                    }
+
+                    let res = match timeout {
+                        Some(ref mut timeout) => {
+                            match select(pin!(self.t.send_and_flush(msg)), pin!(timeout())).await {
+                                Either::Left((res, _)) => res.map_err(ConnectionError::Transport),
+                                Either::Right(((), _)) => Err(ConnectionError::SendTimeout),
+                            }
+                        }
+
+                        None => self
+                            .t
+                            .send_and_flush(msg)
+                            .await
+                            .map_err(ConnectionError::Transport),
+                    };
+
+                    if let Err(e) = res {
                         self.send_broker_shutdown(id).await?;
                         self.drain_broker_recv().await;
-                        return Err(e.into());
+                        return Err(e);
                     }
                 }
 
-                Either::Left((None, _)) => return Err(ConnectionError::UnexpectedShutdown),
+                Either::Left((None, _)) => {
+                    if self.queue.is_disconnected() {
+                        return Err(ConnectionError::SlowConsumer);
+                    } else {
+                        return Err(ConnectionError::UnexpectedShutdown);
+                    }
+                }
 
                 Either::Right((Ok(Message::Shutdown(Shutdown)), _)) => {
                     self.send_broker_shutdown(id).await?;
@@ -96,7 +283,15 @@ where
                     return Ok(());
                 }
 
-                Either::Right((Ok(msg), _)) => self.send_broker_msg(id.clone(), msg).await?,
+                Either::Right((Ok(msg), _)) => {
+                    if let (Some(keep_alive), Some(timeout)) =
+                        (keep_alive.as_mut(), keep_alive_timeout.as_mut())
+                    {
+                        keep_alive.set(timeout());
+                    }
+
+                    self.handle_client_msg(&id, msg).await?;
+                }
 
                 Either::Right((Err(e), _)) => {
                     self.send_broker_shutdown(id).await?;
@@ -107,13 +302,84 @@ where
         }
     }
 
+    async fn handle_client_msg(
+        &mut self,
+        id: &ConnectionId,
+        msg: Message,
+    ) -> Result<(), ConnectionError<T::Error>> {
+        // Events are routed directly to the subscribers, unless the broker is still handling
+        // earlier messages from this connection. This preserves the order of all messages.
+        //
+        // Keeping track of which messages the broker has handled is only necessary for connections
+        // that emit events. It starts with the first event, which is always sent to the broker.
+        // Since the broker handles all messages of a connection in order, it has also handled all
+        // earlier untracked messages once it has handled that event.
+        if let Message::EmitEvent(ref req) = msg {
+            if self.track_messages && self.queue.is_idle() {
+                if let Some(delivery) = self.routes.emit_event(id, req) {
+                    #[cfg(feature = "statistics")]
+                    self.routes.record_received();
+
+                    if delivery.dropped > 0 || !delivery.undeliverable.is_empty() {
+                        // Boxed, because this is rare and would otherwise grow the future of
+                        // `run`, which slows down all other messages as well.
+                        Box::pin(self.report_event_delivery(req, delivery)).await?;
+                    }
+
+                    return Ok(());
+                }
+            }
+
+            self.track_messages = true;
+        }
+
+        self.send_broker_msg(id.clone(), msg).await
+    }
+
+    async fn report_event_delivery(
+        &mut self,
+        req: &EmitEvent,
+        delivery: EventDelivery,
+    ) -> Result<(), ConnectionError<T::Error>> {
+        if delivery.dropped > 0 {
+            self.send
+                .send(ConnectionEvent::EventsDropped(
+                    req.service_cookie,
+                    req.event,
+                    delivery.dropped,
+                ))
+                .await
+                .map_err(|_| ConnectionError::UnexpectedShutdown)?;
+        }
+
+        for conn_id in delivery.undeliverable {
+            self.send
+                .send(ConnectionEvent::EventUndeliverable(
+                    conn_id,
+                    req.service_cookie,
+                    req.event,
+                ))
+                .await
+                .map_err(|_| ConnectionError::UnexpectedShutdown)?;
+        }
+
+        Ok(())
+    }
+
     async fn send_broker_msg(
         &mut self,
         id: ConnectionId,
         msg: Message,
     ) -> Result<(), ConnectionError<T::Error>> {
+        let ev = if self.track_messages {
+            self.queue.begin_message();
+            ConnectionEvent::TrackedMessage(id, msg)
+        } else {
+            ConnectionEvent::Message(id, msg)
+        };
+
         self.send
-            .send(ConnectionEvent::Message(id, msg))
+            .send(ev)
             .await
             .map_err(|_| ConnectionError::UnexpectedShutdown)
     }
@@ -129,7 +395,13 @@ where
     }
 
     async fn drain_broker_recv(&mut self) {
-        while !self.recv.is_terminated() && self.recv.next().await.is_some() {}
+        while !self.priority_recv.is_terminated() && self.priority_recv.next().await.is_some() {
+            self.queue.pop();
+        }
+
+        while !self.recv.is_terminated() && self.recv.next().await.is_some() {
+            self.queue.pop();
+        }
     }
 
     async fn drain_client_recv(&mut self) -> Result<(), ConnectionError<T::Error>> {
@@ -140,3 +412,21 @@ where
         }
     }
 }
+
+/// Polls for the next message from the broker.
+///
+/// Messages of high-priority function calls are preferred over all others. The broker closes both
+/// channels at the same time, so the end of `recv` is also the end of `priority_recv`.
+fn poll_broker_msg(
+    priority_recv: &mut UnboundedReceiver<Message>,
+    recv: &mut UnboundedReceiver<Message>,
+    cx: &mut Context,
+) -> Poll<Option<Message>> {
+    if !priority_recv.is_terminated() {
+        if let Poll::Ready(Some(msg)) = priority_recv.poll_next_unpin(cx) {
+            return Poll::Ready(Some(msg));
+        }
+    }
+
+    recv.poll_next_unpin(cx)
+}
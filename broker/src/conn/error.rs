@@ -1,5 +1,5 @@
 use crate::core::message::Message;
-use crate::core::{DeserializeError, SerializeError};
+use crate::core::{AuthRejection, DeserializeError, SerializeError};
 use thiserror::Error;
 
 /// Error of an active connection.
@@ -9,6 +9,25 @@ pub enum ConnectionError<T> {
     #[error("broker shut down unexpectedly")]
     UnexpectedShutdown,
 
+    /// Sending a message to the client timed out.
+    ///
+    /// See [`Connection::run_with_send_timeout`](crate::Connection::run_with_send_timeout).
+    #[error("sending a message to the client timed out")]
+    SendTimeout,
+
+    /// No message was received from the client in time.
+    ///
+    /// See
+    /// [`Connection::run_with_keep_alive_timeout`](crate::Connection::run_with_keep_alive_timeout).
+    #[error("client keep-alive timed out")]
+    KeepAliveTimeout,
+
+    /// The client was disconnected, because it didn't keep up with the messages sent to it.
+    ///
+    /// See [`SlowConsumerPolicy::Disconnect`](crate::SlowConsumerPolicy::Disconnect).
+    #[error("client disconnected as a slow consumer")]
+    SlowConsumer,
+
     /// The transport encountered an error.
     #[error(transparent)]
     Transport(#[from] T),
@@ -29,6 +48,12 @@ pub enum EstablishError<T> {
     #[error("broker shut down")]
     Shutdown,
 
+    /// The client failed to authenticate.
+    ///
+    /// See [`PendingConnection::authenticate`](crate::PendingConnection::authenticate).
+    #[error("authentication failed: {0}")]
+    AuthenticationFailed(AuthRejection),
+
     /// The transport encountered an error.
     #[error(transparent)]
     Transport(T),
@@ -1,11 +1,22 @@
+use super::{SendQueue, SendQueueLimit};
+#[cfg(feature = "history")]
+use crate::broker::HistoryEntry;
+use crate::broker::{ConnectionInfo, DeadLetterSink, FailoverEvent, NamespaceScope};
+#[cfg(feature = "consistency-check")]
+use crate::broker::{ConsistencyChecker, ConsistencyReport};
 use crate::conn_id::ConnectionId;
 use crate::core::message::Message;
-use crate::core::ProtocolVersion;
+use crate::core::{ObjectUuid, ProtocolVersion, ServiceCookie, ServiceUuid};
 #[cfg(feature = "statistics")]
 use crate::BrokerStatistics;
+#[cfg(feature = "introspection")]
+use crate::{IntrospectionConflict, IntrospectionDump};
 use futures_channel::mpsc;
-#[cfg(feature = "statistics")]
 use futures_channel::oneshot;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
 
 #[derive(Debug)]
 pub(crate) enum ConnectionEvent {
@@ -14,16 +25,56 @@ pub(crate) enum ConnectionEvent {
         ConnectionId,
         ProtocolVersion,
         mpsc::UnboundedSender<Message>,
+        mpsc::UnboundedSender<Message>,
+        Arc<SendQueue>,
+        Option<SendQueueLimit>,
+        Option<String>,
+        HashMap<String, String>,
+        Option<Uuid>,
+        NamespaceScope,
     ),
 
     ConnectionShutdown(ConnectionId),
     Message(ConnectionId, Message),
 
+    /// Like `Message`, but the broker ends the message on the connection's `SendQueue` after
+    /// handling it.
+    TrackedMessage(ConnectionId, Message),
+
+    EventUndeliverable(ConnectionId, ServiceCookie, u32),
+    EventsDropped(ServiceCookie, u32, u32),
+
     // Sent by broker handles
     ShutdownBroker,
     ShutdownIdleBroker,
     ShutdownConnection(ConnectionId),
+    ShutdownConnectionByNumber(u64, oneshot::Sender<bool>),
+    SetDeadLetterSink(DeadLetterSink),
+    RegisterStandby(ObjectUuid, Vec<ServiceUuid>, ConnectionId),
+    UnregisterStandby(ObjectUuid),
+    SetReliableEventBufferLimit(usize),
+    SetReconnectWindow(Duration),
+    AddFailoverListener(mpsc::UnboundedSender<FailoverEvent>),
+    ListConnections(oneshot::Sender<Vec<ConnectionInfo>>),
 
     #[cfg(feature = "statistics")]
     TakeStatistics(oneshot::Sender<BrokerStatistics>),
+
+    #[cfg(feature = "history")]
+    QueryHistory(oneshot::Sender<Vec<HistoryEntry>>),
+
+    #[cfg(feature = "history")]
+    SetHistoryCapacity(usize),
+
+    #[cfg(feature = "consistency-check")]
+    CheckConsistency(oneshot::Sender<ConsistencyReport>),
+
+    #[cfg(feature = "consistency-check")]
+    SetConsistencyChecker(ConsistencyChecker),
+
+    #[cfg(feature = "introspection")]
+    DumpIntrospection(oneshot::Sender<IntrospectionDump>),
+
+    #[cfg(feature = "introspection")]
+    AddIntrospectionConflictListener(mpsc::UnboundedSender<IntrospectionConflict>),
 }
@@ -15,6 +15,14 @@ impl ConnectionHandle {
         Self { id }
     }
 
+    /// Returns the number of the connection.
+    ///
+    /// Connections are numbered in the order in which they are established. Numbers are unique
+    /// among all connections of a broker and never reused.
+    pub fn number(&self) -> u64 {
+        self.id.number()
+    }
+
     pub(crate) fn id(&self) -> &ConnectionId {
         &self.id
     }
@@ -0,0 +1,115 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Limit of the queue of messages, that the broker sends to a connection.
+///
+/// The broker doesn't block when it sends messages to a [`Connection`](crate::Connection). Instead,
+/// messages are queued until the `Connection` has written them to its transport. A client, that
+/// stops reading from its transport, would thus cause the queue to grow without bounds.
+///
+/// When the queue reaches its high-watermark, the connection is considered a slow consumer and the
+/// [`SlowConsumerPolicy`] is applied.
+///
+/// Limits are set per connection with
+/// [`PendingConnection::set_send_queue_limit`](crate::PendingConnection::set_send_queue_limit).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SendQueueLimit {
+    high_watermark: usize,
+    policy: SlowConsumerPolicy,
+}
+
+impl SendQueueLimit {
+    /// Creates a new `SendQueueLimit`.
+    pub fn new(high_watermark: usize, policy: SlowConsumerPolicy) -> Self {
+        Self {
+            high_watermark,
+            policy,
+        }
+    }
+
+    /// Returns the number of queued messages at which a connection is considered a slow consumer.
+    pub fn high_watermark(self) -> usize {
+        self.high_watermark
+    }
+
+    /// Returns the policy, that is applied to slow consumers.
+    pub fn policy(self) -> SlowConsumerPolicy {
+        self.policy
+    }
+}
+
+/// Policy applied to slow consumers.
+///
+/// See [`SendQueueLimit`] for more information.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum SlowConsumerPolicy {
+    /// Keep sending all messages.
+    ///
+    /// Slow consumers are only counted in the broker's statistics.
+    #[default]
+    Alert,
+
+    /// Drop events while the queue is at or above its high-watermark.
+    ///
+    /// All other messages are still queued.
+    DropEvents,
+
+    /// Disconnect the client.
+    ///
+    /// The [`Connection`](crate::Connection) will then fail with
+    /// [`ConnectionError::SlowConsumer`](crate::ConnectionError::SlowConsumer).
+    Disconnect,
+}
+
+/// State shared between a connection and the broker.
+#[derive(Debug, Default)]
+pub(crate) struct SendQueue {
+    len: AtomicUsize,
+    disconnected: AtomicBool,
+
+    /// Number of tracked messages sent by the connection, that the broker hasn't finished handling
+    /// yet.
+    in_flight: AtomicUsize,
+}
+
+impl SendQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    pub fn push(&self) {
+        self.len.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn pop(&self) {
+        self.len.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub fn disconnect(&self) {
+        self.disconnected.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected.load(Ordering::Relaxed)
+    }
+
+    pub fn begin_message(&self) {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn end_message(&self) {
+        self.in_flight.fetch_sub(1, Ordering::Release);
+    }
+
+    /// Indicates whether the broker has finished handling all tracked messages sent by the
+    /// connection.
+    ///
+    /// Everything the broker sent in response to these messages has then already been queued.
+    pub fn is_idle(&self) -> bool {
+        self.in_flight.load(Ordering::Acquire) == 0
+    }
+}
@@ -10,12 +10,12 @@ impl ConnectionIdManager {
     }
 
     pub fn acquire(&self) -> ConnectionId {
-        let id = {
+        let (id, number) = {
             let mut this = self.0.lock().expect("mutex poisoned");
             this.acquire()
         };
 
-        ConnectionId::new(id, self.clone())
+        ConnectionId::new(id, number, self.clone())
     }
 
     fn release(&self, id: usize) {
@@ -28,6 +28,7 @@ impl ConnectionIdManager {
 struct Inner {
     next: usize,
     free: Vec<usize>,
+    next_number: u64,
 }
 
 impl Inner {
@@ -35,17 +36,21 @@ impl Inner {
         Self {
             next: 0,
             free: Vec::new(),
+            next_number: 0,
         }
     }
 
-    fn acquire(&mut self) -> usize {
+    fn acquire(&mut self) -> (usize, u64) {
+        let number = self.next_number;
+        self.next_number += 1;
+
         match self.free.pop() {
-            Some(id) => id,
+            Some(id) => (id, number),
 
             None => {
                 let id = self.next;
                 self.next += 1;
-                id
+                (id, number)
             }
         }
     }
@@ -66,8 +71,15 @@ impl Inner {
 pub(crate) struct ConnectionId(Arc<ConnectionIdInner>);
 
 impl ConnectionId {
-    fn new(id: usize, ids: ConnectionIdManager) -> Self {
-        Self(Arc::new(ConnectionIdInner::new(id, ids)))
+    fn new(id: usize, number: u64, ids: ConnectionIdManager) -> Self {
+        Self(Arc::new(ConnectionIdInner::new(id, number, ids)))
+    }
+
+    /// Returns a number, that is unique among all connections of a broker.
+    ///
+    /// Unlike the id itself, numbers are never reused.
+    pub fn number(&self) -> u64 {
+        self.0.number
     }
 }
 
@@ -91,12 +103,13 @@ impl Hash for ConnectionId {
 #[derive(Debug)]
 struct ConnectionIdInner {
     id: usize,
+    number: u64,
     ids: ConnectionIdManager,
 }
 
 impl ConnectionIdInner {
-    fn new(id: usize, ids: ConnectionIdManager) -> Self {
-        Self { id, ids }
+    fn new(id: usize, number: u64, ids: ConnectionIdManager) -> Self {
+        Self { id, number, ids }
     }
 
     fn id(&self) -> usize {
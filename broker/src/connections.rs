@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod test;
+
+use crate::core::{ObjectUuid, ServiceId, ServiceUuid};
+use crate::{BrokerHandle, ConnectionInfo};
+use aldrin::low_level::{Call, Service, ServiceInfo};
+use aldrin::{Error, Handle, Object};
+use uuid::uuid;
+
+/// Bus service, that provides information about the broker's connections to clients.
+///
+/// Clients can attach a name and metadata when they connect (see e.g. `ClientBuilder::with_name`
+/// in the `aldrin` crate). This service makes that information, together with the objects owned by
+/// each connection, available to all clients on the bus. It answers questions such as which
+/// process owns a particular object.
+///
+/// The service is usually spawned with
+/// [`Embedded::spawn_connection_service`](crate::Embedded::spawn_connection_service), but it can
+/// run on any client with [`new`](Self::new) and [`run`](Self::run).
+///
+/// # Protocol
+///
+/// The service has the following functions:
+///
+/// - [`LIST`](Self::LIST) takes no arguments (`()`) and returns all connections as a
+///   `Vec<ConnectionInfo>`.
+/// - [`FIND_OWNER`](Self::FIND_OWNER) takes an `ObjectUuid` and returns the connection, that
+///   owns the object, as an `Option<ConnectionInfo>`.
+///
+/// See [`ConnectionInfo`] for how it is serialized.
+///
+/// This type requires the `connections` feature.
+#[derive(Debug)]
+pub struct ConnectionService {
+    object: Object,
+    service: Service,
+    broker: BrokerHandle,
+}
+
+impl ConnectionService {
+    /// UUID of the object, that owns the connection service.
+    pub const OBJECT_UUID: ObjectUuid = ObjectUuid(uuid!("8b2f5e14-7c3a-4d96-a0e1-5f9c2b7d4e63"));
+
+    /// UUID of the connection service.
+    pub const SERVICE_UUID: ServiceUuid =
+        ServiceUuid(uuid!("d4a61c8e-3f52-4b07-9e8d-2c6b1a0f7e95"));
+
+    /// Version of the connection service.
+    pub const VERSION: u32 = 1;
+
+    /// Function id for listing all connections.
+    pub const LIST: u32 = 1;
+
+    /// Function id for finding the connection, that owns an object.
+    pub const FIND_OWNER: u32 = 2;
+
+    /// Creates the connection service on a client.
+    ///
+    /// The service queries `broker` whenever it is called. This creates an object with the UUID
+    /// [`OBJECT_UUID`](Self::OBJECT_UUID) and fails with [`Error::DuplicateObject`], if a
+    /// connection service already exists on the bus.
+    pub async fn new(client: &Handle, broker: BrokerHandle) -> Result<Self, Error> {
+        let object = client.create_object(Self::OBJECT_UUID).await?;
+
+        let service = object
+            .create_service(Self::SERVICE_UUID, ServiceInfo::new(Self::VERSION))
+            .await?;
+
+        Ok(Self {
+            object,
+            service,
+            broker,
+        })
+    }
+
+    /// Returns the id of the connection service.
+    pub fn id(&self) -> ServiceId {
+        self.service.id()
+    }
+
+    /// Returns the object, that owns the connection service.
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+
+    /// Runs the connection service.
+    ///
+    /// This function returns when the service has been destroyed or when the broker has shut
+    /// down.
+    pub async fn run(mut self) -> Result<(), Error> {
+        while let Some(call) = self.service.next_call().await {
+            if !self.call(call).await? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handles a call and returns `false` if the broker has shut down.
+    async fn call(&mut self, call: Call) -> Result<bool, Error> {
+        match call.id() {
+            Self::LIST => {
+                if call.deserialize::<()>().is_err() {
+                    call.into_promise().invalid_args()?;
+                    return Ok(true);
+                }
+
+                let Ok(conns) = self.broker.list_connections().await else {
+                    return Ok(false);
+                };
+
+                call.into_promise().ok(&conns)?;
+            }
+
+            Self::FIND_OWNER => {
+                let Ok(uuid) = call.deserialize::<ObjectUuid>() else {
+                    call.into_promise().invalid_args()?;
+                    return Ok(true);
+                };
+
+                let Ok(conns) = self.broker.list_connections().await else {
+                    return Ok(false);
+                };
+
+                let owner = conns
+                    .into_iter()
+                    .find(|conn| conn.objects().iter().any(|obj| obj.uuid == uuid));
+
+                call.into_promise().ok::<Option<ConnectionInfo>>(&owner)?;
+            }
+
+            _ => call.into_promise().invalid_function()?,
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,91 @@
+use super::ConnectionService;
+use crate::core::ObjectUuid;
+use crate::{ConnectionInfo, Embedded};
+use aldrin::low_level::Proxy;
+use aldrin::{Client, Error};
+
+#[tokio::test]
+async fn list() {
+    let mut bus = Embedded::new();
+    let id = bus.spawn_connection_service().await.unwrap();
+    let client = bus.connect().await.unwrap();
+
+    let proxy = Proxy::new(&client, id).await.unwrap();
+    let conns: Vec<ConnectionInfo> = proxy
+        .call(ConnectionService::LIST, &())
+        .await
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+
+    // The connection service's own client and `client`.
+    assert_eq!(conns.len(), 2);
+    assert_eq!(conns[0].objects(), [proxy.id().object_id]);
+    assert!(conns[1].objects().is_empty());
+
+    bus.shutdown().await;
+}
+
+#[tokio::test]
+async fn find_owner() {
+    let mut bus = Embedded::new();
+    let id = bus.spawn_connection_service().await.unwrap();
+
+    let (t1, t2) = crate::core::channel::unbounded();
+    let client = Client::builder(t1)
+        .with_name("owner")
+        .with_metadata("pid", "42")
+        .connect();
+    let mut broker = bus.handle().clone();
+    let (client, conn) = tokio::join!(client, broker.connect(t2));
+    tokio::spawn(conn.unwrap().run());
+    let client = client.unwrap();
+    let handle = client.handle().clone();
+    let join = tokio::spawn(client.run());
+
+    let obj = handle.create_object(ObjectUuid::new_v4()).await.unwrap();
+
+    let proxy = Proxy::new(&handle, id).await.unwrap();
+    let owner: Option<ConnectionInfo> = proxy
+        .call(ConnectionService::FIND_OWNER, &obj.id().uuid)
+        .await
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    let owner = owner.unwrap();
+    assert_eq!(owner.name(), Some("owner"));
+    assert_eq!(owner.metadata().get("pid").map(String::as_str), Some("42"));
+    assert_eq!(owner.objects(), [obj.id()]);
+
+    let owner: Option<ConnectionInfo> = proxy
+        .call(ConnectionService::FIND_OWNER, &ObjectUuid::new_v4())
+        .await
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    assert_eq!(owner, None);
+
+    handle.shutdown();
+    join.await.unwrap().unwrap();
+    bus.shutdown().await;
+}
+
+#[tokio::test]
+async fn invalid_args() {
+    let mut bus = Embedded::new();
+    let id = bus.spawn_connection_service().await.unwrap();
+    let client = bus.connect().await.unwrap();
+
+    let proxy = Proxy::new(&client, id).await.unwrap();
+
+    let res = proxy.call(ConnectionService::FIND_OWNER, "foo").await;
+    assert!(matches!(
+        res.map_err(Error::without_context),
+        Err(Error::InvalidArguments(_))
+    ));
+
+    bus.shutdown().await;
+}
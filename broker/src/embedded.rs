@@ -0,0 +1,217 @@
+#[cfg(test)]
+mod test;
+
+use crate::conn::EstablishError;
+use crate::core::channel::{self, Disconnected};
+use crate::core::ServiceId;
+#[cfg(feature = "connections")]
+use crate::ConnectionService;
+#[cfg(feature = "timers")]
+use crate::TimerService;
+use crate::{Broker, BrokerHandle};
+use aldrin::error::ConnectError;
+use aldrin::{Client, ClockService, Handle};
+use futures_util::future;
+use std::panic;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+
+/// In-process bus with a broker and directly connected clients.
+///
+/// `Embedded` is a convenience for applications, that run a broker together with some of its
+/// clients in the same process. It spawns the [`Broker`] on creation and hands out connected
+/// [`Handle`s](Handle) from [`connect`](Self::connect). Clients are connected with an unbounded
+/// [channel transport](aldrin_core::channel). All tasks, i.e. the broker, the clients and their
+/// connections, are spawned on the current Tokio runtime and joined by
+/// [`shutdown`](Self::shutdown).
+///
+/// Other clients can still connect through the [`BrokerHandle`] returned by
+/// [`handle`](Self::handle).
+///
+/// Dropping an `Embedded` without calling [`shutdown`](Self::shutdown) detaches all tasks. They
+/// keep running until the broker is shut down by other means.
+///
+/// This type requires the `embedded` feature.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin_broker::Embedded;
+/// use aldrin_broker::core::ObjectUuid;
+///
+/// # #[tokio::main]
+/// # async fn main() -> anyhow::Result<()> {
+/// let mut bus = Embedded::new();
+///
+/// let client1 = bus.connect().await?;
+/// let client2 = bus.connect().await?;
+///
+/// let obj = client1.create_object(ObjectUuid::new_v4()).await?;
+/// assert!(client2.find_object(Some(obj.id().uuid), &[]).await?.is_some());
+///
+/// // Shuts down the broker and joins all tasks, including those of the clients.
+/// bus.shutdown().await;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Embedded {
+    handle: BrokerHandle,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Embedded {
+    /// Creates a new `Embedded` with a default [`Broker`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside of a Tokio runtime.
+    pub fn new() -> Self {
+        Self::with_broker(Broker::new())
+    }
+
+    /// Creates a new `Embedded` with a custom [`Broker`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside of a Tokio runtime.
+    pub fn with_broker(broker: Broker) -> Self {
+        let handle = broker.handle().clone();
+        let join = tokio::spawn(broker.run());
+
+        Self {
+            handle,
+            tasks: vec![join],
+        }
+    }
+
+    /// Returns a handle to the broker.
+    pub fn handle(&self) -> &BrokerHandle {
+        &self.handle
+    }
+
+    /// Connects a new client.
+    ///
+    /// The client and its connection are spawned automatically. The client can be shut down
+    /// individually with [`Handle::shutdown`]; otherwise it runs until the broker shuts down.
+    pub async fn connect(&mut self) -> Result<Handle, EmbeddedConnectError> {
+        let (t1, t2) = channel::unbounded();
+
+        let (client, conn) = future::join(Client::connect(t1), self.handle.connect(t2)).await;
+        let conn = conn?;
+        let client = client?;
+
+        let handle = client.handle().clone();
+
+        self.tasks.push(tokio::spawn(async {
+            let _ = conn.run().await;
+        }));
+
+        self.tasks.push(tokio::spawn(async {
+            let _ = client.run().await;
+        }));
+
+        Ok(handle)
+    }
+
+    /// Connects a new client and spawns a [`TimerService`] on it.
+    ///
+    /// The service runs until the broker shuts down. Its id is returned.
+    ///
+    /// This function requires the `timers` feature.
+    #[cfg(feature = "timers")]
+    pub async fn spawn_timer_service(&mut self) -> Result<ServiceId, SpawnServiceError> {
+        let client = self.connect().await?;
+        let timers = TimerService::new(&client).await?;
+        let id = timers.id();
+
+        self.tasks.push(tokio::spawn(async {
+            let _ = timers.run().await;
+        }));
+
+        Ok(id)
+    }
+
+    /// Connects a new client and spawns a [`ClockService`] on it.
+    ///
+    /// The service runs until the broker shuts down. Its id is returned. Because the service runs
+    /// in the broker's process, the bus time is the broker's monotonic time.
+    pub async fn spawn_clock_service(&mut self) -> Result<ServiceId, SpawnServiceError> {
+        let client = self.connect().await?;
+        let clock = ClockService::new(&client).await?;
+        let id = clock.id();
+
+        self.tasks.push(tokio::spawn(async {
+            let _ = clock.run().await;
+        }));
+
+        Ok(id)
+    }
+
+    /// Connects a new client and spawns a [`ConnectionService`] on it.
+    ///
+    /// The service runs until the broker shuts down. Its id is returned.
+    ///
+    /// This function requires the `connections` feature.
+    #[cfg(feature = "connections")]
+    pub async fn spawn_connection_service(&mut self) -> Result<ServiceId, SpawnServiceError> {
+        let client = self.connect().await?;
+        let conns = ConnectionService::new(&client, self.handle.clone()).await?;
+        let id = conns.id();
+
+        self.tasks.push(tokio::spawn(async {
+            let _ = conns.run().await;
+        }));
+
+        Ok(id)
+    }
+
+    /// Shuts down the broker and joins all tasks.
+    ///
+    /// Shutting down the broker closes all connections, which in turn causes all clients to shut
+    /// down as well. Panics of any of the tasks are propagated.
+    pub async fn shutdown(mut self) {
+        self.handle.shutdown().await;
+
+        for task in self.tasks {
+            if let Err(e) = task.await {
+                if let Ok(panic) = e.try_into_panic() {
+                    panic::resume_unwind(panic);
+                }
+            }
+        }
+    }
+}
+
+impl Default for Embedded {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error when connecting a client with [`Embedded::connect`].
+#[derive(Error, Debug)]
+pub enum EmbeddedConnectError {
+    /// The broker failed to establish the connection.
+    #[error(transparent)]
+    Establish(#[from] EstablishError<Disconnected>),
+
+    /// The client failed to connect.
+    #[error(transparent)]
+    Connect(#[from] ConnectError<Disconnected>),
+}
+
+/// Error when spawning one of the built-in services with [`Embedded`].
+///
+/// This type is returned by `Embedded::spawn_timer_service`, `Embedded::spawn_clock_service` and
+/// `Embedded::spawn_connection_service`.
+#[derive(Error, Debug)]
+pub enum SpawnServiceError {
+    /// The client of the service failed to connect.
+    #[error(transparent)]
+    Connect(#[from] EmbeddedConnectError),
+
+    /// The service could not be created.
+    #[error(transparent)]
+    Create(#[from] aldrin::Error),
+}
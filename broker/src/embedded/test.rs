@@ -0,0 +1,49 @@
+use super::Embedded;
+use crate::core::{ObjectUuid, ServiceUuid};
+use aldrin::low_level::{Proxy, ServiceInfo};
+
+#[tokio::test]
+async fn connect_clients() {
+    let mut bus = Embedded::new();
+    let client1 = bus.connect().await.unwrap();
+    let client2 = bus.connect().await.unwrap();
+
+    let obj = client1.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let info = ServiceInfo::new(0);
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), info)
+        .await
+        .unwrap();
+
+    let proxy = Proxy::new(&client2, svc.id()).await.unwrap();
+    let reply = proxy.call(0, &1u32);
+
+    let call = svc.next_call().await.unwrap();
+    assert_eq!(call.deserialize(), Ok(1u32));
+    call.into_promise().ok(&2u32).unwrap();
+    assert_eq!(reply.await.unwrap().unwrap().deserialize(), Ok(2u32));
+
+    bus.shutdown().await;
+}
+
+#[tokio::test]
+async fn shutdown_disconnects_clients() {
+    let mut bus = Embedded::new();
+    let client = bus.connect().await.unwrap();
+    client.sync_broker().await.unwrap();
+
+    bus.shutdown().await;
+    assert!(client.sync_broker().await.is_err());
+}
+
+#[tokio::test]
+async fn shutdown_client_individually() {
+    let mut bus = Embedded::new();
+    let client1 = bus.connect().await.unwrap();
+    let client2 = bus.connect().await.unwrap();
+
+    client1.shutdown();
+    client2.sync_broker().await.unwrap();
+
+    bus.shutdown().await;
+}
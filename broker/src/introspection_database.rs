@@ -1,23 +1,42 @@
+mod conflict;
+mod dump;
+#[cfg(test)]
+mod test;
+
 use crate::conn_id::ConnectionId;
-use crate::core::message::{QueryIntrospectionReply, QueryIntrospectionResult};
-use crate::core::{SerializedValue, TypeId};
+use crate::core::introspection::Introspection;
+use crate::core::message::QueryIntrospectionReply;
+use crate::core::{Compression, SerializedValue, TypeId};
+use futures_channel::mpsc;
 use rand::Rng;
 use std::collections::hash_map::{Entry, HashMap};
 use std::collections::HashSet;
 use std::mem;
 
+pub use conflict::{IntrospectionConflict, IntrospectionConflictKind, IntrospectionConflicts};
+pub use dump::{IntrospectionDump, IntrospectionDumpError};
+
 #[derive(Debug)]
 pub(crate) struct IntrospectionDatabase {
     entries: HashMap<TypeId, IntrospectionEntry>,
+    conflict_listeners: Vec<mpsc::UnboundedSender<IntrospectionConflict>>,
 }
 
 impl IntrospectionDatabase {
     pub fn new() -> Self {
         Self {
             entries: HashMap::new(),
+            conflict_listeners: Vec::new(),
         }
     }
 
+    pub fn add_conflict_listener(
+        &mut self,
+        listener: mpsc::UnboundedSender<IntrospectionConflict>,
+    ) {
+        self.conflict_listeners.push(listener);
+    }
+
     #[cfg(feature = "statistics")]
     pub fn len(&self) -> usize {
         self.entries.len()
@@ -32,12 +51,40 @@ impl IntrospectionDatabase {
         }
     }
 
+    /// Adds all types of `dump` as persistent entries.
+    ///
+    /// Persistent entries are kept even when no connection registers them.
+    pub fn load(&mut self, dump: &IntrospectionDump) {
+        for (type_id, introspection) in dump.iter() {
+            let entry = self.entries.entry(type_id).or_default();
+            entry.persistent = true;
+
+            if entry.introspection.is_none() {
+                entry.introspection = Some(introspection.clone());
+                entry.references = references(introspection);
+            }
+        }
+    }
+
+    /// Returns all types, whose introspection is known.
+    pub fn dump(&self) -> IntrospectionDump {
+        let mut dump = IntrospectionDump::new();
+
+        for (&type_id, entry) in &self.entries {
+            if let Some(ref introspection) = entry.introspection {
+                dump.insert_serialized(type_id, introspection.clone());
+            }
+        }
+
+        dump
+    }
+
     pub fn remove_conn(&mut self, conn_id: &ConnectionId) -> Vec<RemoveConn> {
         let mut result = Vec::new();
 
         self.entries.retain(|&type_id, entry| {
             let was_queried = entry.queried();
-            let retain = entry.remove_conn(conn_id);
+            let retain = entry.remove_conn(conn_id) || entry.persistent;
             let is_queried = entry.queried();
 
             if let (Some(serial), None) = (was_queried, is_queried) {
@@ -54,16 +101,52 @@ impl IntrospectionDatabase {
         result
     }
 
+    pub fn get(&self, type_id: TypeId) -> Option<&IntrospectionEntry> {
+        self.entries.get(&type_id)
+    }
+
     pub fn get_mut(&mut self, type_id: TypeId) -> Option<&mut IntrospectionEntry> {
         self.entries.get_mut(&type_id)
     }
 
+    /// Collects the introspection of `type_id` and those of all types it references.
+    ///
+    /// References are followed transitively, but only through types, whose introspection is known
+    /// and which are not in `known`.
+    pub fn bundle(&self, type_id: TypeId, known: &[TypeId]) -> Vec<(TypeId, &SerializedValue)> {
+        let mut visited = known.iter().copied().collect::<HashSet<_>>();
+        let mut queue = vec![type_id];
+        let mut bundle = Vec::new();
+
+        visited.insert(type_id);
+
+        while let Some(type_id) = queue.pop() {
+            let Some(entry) = self.entries.get(&type_id) else {
+                continue;
+            };
+
+            let Some(ref introspection) = entry.introspection else {
+                continue;
+            };
+
+            bundle.push((type_id, introspection));
+
+            for &reference in &entry.references {
+                if visited.insert(reference) {
+                    queue.push(reference);
+                }
+            }
+        }
+
+        bundle
+    }
+
     pub fn query_replied(
         &mut self,
         type_id: TypeId,
         conn_id: &ConnectionId,
         reply: QueryIntrospectionReply,
-    ) -> Option<IntrospectionQueryResult> {
+    ) -> Option<IntrospectionQueryResult<'_>> {
         let Entry::Occupied(mut entry) = self.entries.entry(type_id) else {
             panic!("inconsistent state");
         };
@@ -72,19 +155,35 @@ impl IntrospectionDatabase {
             return None;
         }
 
-        match reply.result {
-            QueryIntrospectionResult::Ok(introspection) => {
+        // Providers may reply with a bundle as well. Only the queried type is taken from it.
+        let introspection = reply
+            .result
+            .into_introspections(type_id)
+            .ok()
+            .and_then(|mut introspections| introspections.remove(&type_id));
+
+        match introspection {
+            Some(introspection) => {
+                let references = match check_introspection(type_id, &introspection) {
+                    Ok(references) => references,
+
+                    Err(kind) => {
+                        let conflict = IntrospectionConflict::new(type_id, conn_id.number(), kind);
+
+                        self.conflict_listeners
+                            .retain(|listener| listener.unbounded_send(conflict.clone()).is_ok());
+
+                        Vec::new()
+                    }
+                };
+
                 let entry = entry.into_mut();
-                let pending = entry.take_pending();
-                let introspection = entry.set_introspection(introspection);
+                entry.set_introspection(introspection, references);
 
-                Some(IntrospectionQueryResult::Available {
-                    introspection,
-                    pending,
-                })
+                Some(IntrospectionQueryResult::Available(entry.take_pending()))
             }
 
-            QueryIntrospectionResult::Unavailable => {
+            None => {
                 if entry.get_mut().remove_conn(conn_id) {
                     Some(IntrospectionQueryResult::Continue(entry.into_mut()))
                 } else {
@@ -96,13 +195,40 @@ impl IntrospectionDatabase {
     }
 }
 
+/// Checks an introspection and returns the types it references.
+fn check_introspection(
+    type_id: TypeId,
+    introspection: &SerializedValue,
+) -> Result<Vec<TypeId>, IntrospectionConflictKind> {
+    match introspection.deserialize::<Introspection>() {
+        Ok(introspection) if introspection.type_id() == type_id => {
+            Ok(introspection.references().values().copied().collect())
+        }
+
+        Ok(introspection) => Err(IntrospectionConflictKind::TypeIdMismatch(
+            introspection.type_id(),
+        )),
+
+        Err(_) => Err(IntrospectionConflictKind::Invalid),
+    }
+}
+
+fn references(introspection: &SerializedValue) -> Vec<TypeId> {
+    introspection
+        .deserialize::<Introspection>()
+        .map(|introspection| introspection.references().values().copied().collect())
+        .unwrap_or_default()
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct IntrospectionEntry {
     conn_id_idxs: HashMap<ConnectionId, usize>,
     conn_ids: Vec<ConnectionId>,
     introspection: Option<SerializedValue>,
+    references: Vec<TypeId>,
     queried: Option<IntrospectionQuery>,
     pending: Vec<IntrospectionQuery>,
+    persistent: bool,
 }
 
 impl IntrospectionEntry {
@@ -125,6 +251,7 @@ impl IntrospectionEntry {
 
         if let Some(idx) = self.conn_id_idxs.remove(conn_id) {
             if self.conn_id_idxs.is_empty() {
+                self.conn_ids.clear();
                 false
             } else {
                 self.conn_ids.swap_remove(idx);
@@ -141,10 +268,11 @@ impl IntrospectionEntry {
                 true
             }
         } else {
-            debug_assert!(!self.conn_id_idxs.is_empty());
-            debug_assert!(!self.conn_ids.is_empty());
+            // Only persistent entries may exist without any connections.
+            debug_assert!(self.persistent || !self.conn_id_idxs.is_empty());
+            debug_assert_eq!(self.conn_id_idxs.len(), self.conn_ids.len());
 
-            true
+            !self.conn_id_idxs.is_empty()
         }
     }
 
@@ -156,9 +284,21 @@ impl IntrospectionEntry {
         self.queried.as_ref().map(|queried| queried.serial)
     }
 
-    pub fn add_pending(&mut self, conn_id: ConnectionId, serial: u32) {
+    pub fn add_pending(
+        &mut self,
+        conn_id: ConnectionId,
+        serial: u32,
+        known: Vec<TypeId>,
+        compression: Option<Compression>,
+    ) {
         debug_assert!(self.introspection.is_none());
-        self.pending.push(IntrospectionQuery::new(conn_id, serial));
+
+        self.pending.push(IntrospectionQuery {
+            conn_id,
+            serial,
+            known,
+            compression,
+        });
     }
 
     pub fn query_random_conn(&mut self, serial: u32) -> &ConnectionId {
@@ -189,9 +329,10 @@ impl IntrospectionEntry {
         }
     }
 
-    fn set_introspection(&mut self, introspection: SerializedValue) -> &SerializedValue {
+    fn set_introspection(&mut self, introspection: SerializedValue, references: Vec<TypeId>) {
         debug_assert!(self.introspection.is_none());
-        self.introspection.insert(introspection)
+        self.introspection = Some(introspection);
+        self.references = references;
     }
 
     fn take_pending(&mut self) -> Vec<IntrospectionQuery> {
@@ -203,20 +344,24 @@ impl IntrospectionEntry {
 pub(crate) struct IntrospectionQuery {
     pub conn_id: ConnectionId,
     pub serial: u32,
+    pub known: Vec<TypeId>,
+    pub compression: Option<Compression>,
 }
 
 impl IntrospectionQuery {
     fn new(conn_id: ConnectionId, serial: u32) -> Self {
-        Self { conn_id, serial }
+        Self {
+            conn_id,
+            serial,
+            known: Vec::new(),
+            compression: None,
+        }
     }
 }
 
 #[derive(Debug)]
 pub(crate) enum IntrospectionQueryResult<'a> {
-    Available {
-        introspection: &'a SerializedValue,
-        pending: Vec<IntrospectionQuery>,
-    },
+    Available(Vec<IntrospectionQuery>),
 
     Unavailable(Vec<IntrospectionQuery>),
     Continue(&'a mut IntrospectionEntry),
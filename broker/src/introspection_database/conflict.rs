@@ -0,0 +1,99 @@
+use crate::core::TypeId;
+use futures_channel::mpsc;
+use futures_core::stream::{FusedStream, Stream};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Conflicting introspection provided by a client.
+///
+/// Clients register only the [`TypeId`s](TypeId) of their types with the broker. The introspection
+/// itself is queried from one of the registering clients when it is first needed. If that client
+/// replies with an introspection, that doesn't match the queried `TypeId`, then the client and
+/// the broker disagree about the type's definition. This usually indicates that a type has changed
+/// without the client being rebuilt, or that the client computes `TypeId`s differently.
+///
+/// [`TypeId::explain`](crate::core::TypeId::explain) can be used on both sides to find the cause of
+/// such a mismatch.
+///
+/// Conflicts are reported on an [`IntrospectionConflicts`] stream, which can be acquired with
+/// [`BrokerHandle::introspection_conflicts`](crate::BrokerHandle::introspection_conflicts).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntrospectionConflict {
+    type_id: TypeId,
+    conn: u64,
+    kind: IntrospectionConflictKind,
+}
+
+impl IntrospectionConflict {
+    pub(crate) fn new(type_id: TypeId, conn: u64, kind: IntrospectionConflictKind) -> Self {
+        Self {
+            type_id,
+            conn,
+            kind,
+        }
+    }
+
+    /// `TypeId`, that was queried.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Number of the connection, that provided the introspection.
+    ///
+    /// See [`ConnectionHandle::number`](crate::ConnectionHandle::number).
+    pub fn connection(&self) -> u64 {
+        self.conn
+    }
+
+    /// Kind of the conflict.
+    pub fn kind(&self) -> IntrospectionConflictKind {
+        self.kind
+    }
+}
+
+/// Kind of an [`IntrospectionConflict`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IntrospectionConflictKind {
+    /// The introspection could not be deserialized.
+    Invalid,
+
+    /// The introspection describes a type with a different `TypeId`.
+    TypeIdMismatch(TypeId),
+}
+
+/// Stream of [`IntrospectionConflict`s](IntrospectionConflict).
+///
+/// This type is acquired by
+/// [`BrokerHandle::introspection_conflicts`](crate::BrokerHandle::introspection_conflicts). The
+/// stream ends when the broker shuts down.
+#[derive(Debug)]
+pub struct IntrospectionConflicts(mpsc::UnboundedReceiver<IntrospectionConflict>);
+
+impl IntrospectionConflicts {
+    pub(crate) fn new() -> (mpsc::UnboundedSender<IntrospectionConflict>, Self) {
+        let (send, recv) = mpsc::unbounded();
+        (send, Self(recv))
+    }
+}
+
+impl Stream for IntrospectionConflicts {
+    type Item = IntrospectionConflict;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<IntrospectionConflict>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl FusedStream for IntrospectionConflicts {
+    fn is_terminated(&self) -> bool {
+        self.0.is_terminated()
+    }
+}
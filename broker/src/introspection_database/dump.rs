@@ -0,0 +1,212 @@
+use crate::core::introspection::Introspection;
+use crate::core::{
+    DeserializeError, SerializeError, SerializedValue, SerializedValueSlice, TypeId,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Error as IoError;
+use std::path::Path;
+use thiserror::Error;
+
+const MAGIC: [u8; 8] = *b"ALDRNINT";
+const VERSION: u8 = 1;
+
+/// Snapshot of a broker's introspection database.
+///
+/// A dump maps [`TypeId`]s to their serialized [`Introspection`]. It is acquired with
+/// [`BrokerHandle::dump_introspection`](crate::BrokerHandle::dump_introspection) and can be
+/// pre-loaded into a new broker with
+/// [`Broker::load_introspection`](crate::Broker::load_introspection). Pre-loaded types can be
+/// queried even if no client currently registers them.
+///
+/// # File formats
+///
+/// Dumps can be stored in a compact binary format (see [`to_bytes`](Self::to_bytes)) and, with the
+/// `json` feature, as JSON (see [`to_json`](Self::to_json)), which is useful for diffing.
+///
+/// The binary format starts with the 8 bytes magic `ALDRNINT`, followed by a 1 byte version
+/// (currently 1) and a serialized map from [`TypeId`] to [`Introspection`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use aldrin_broker::{Broker, IntrospectionDump};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut broker = Broker::new();
+/// broker.load_introspection(&IntrospectionDump::load("introspection.db")?);
+///
+/// let mut handle = broker.handle().clone();
+/// let join = tokio::spawn(broker.run());
+///
+/// // Run the broker ...
+///
+/// handle.dump_introspection().await?.save("introspection.db")?;
+/// handle.shutdown().await;
+/// join.await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntrospectionDump {
+    entries: BTreeMap<TypeId, SerializedValue>,
+}
+
+impl IntrospectionDump {
+    /// Creates an empty dump.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of types in the dump.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Indicates whether the dump is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds an introspection to the dump.
+    pub fn insert(&mut self, introspection: &Introspection) -> Result<(), SerializeError> {
+        let serialized = SerializedValue::serialize(introspection)?;
+        self.entries.insert(introspection.type_id(), serialized);
+        Ok(())
+    }
+
+    /// Removes a type from the dump.
+    ///
+    /// Returns `true` if the type was part of the dump.
+    pub fn remove(&mut self, type_id: TypeId) -> bool {
+        self.entries.remove(&type_id).is_some()
+    }
+
+    /// Returns the serialized introspection of a type.
+    pub fn get(&self, type_id: TypeId) -> Option<&SerializedValue> {
+        self.entries.get(&type_id)
+    }
+
+    /// Returns an iterator over all types and their serialized introspection.
+    ///
+    /// Types are ordered by their [`TypeId`].
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (TypeId, &SerializedValue)> + '_ {
+        self.entries
+            .iter()
+            .map(|(&type_id, introspection)| (type_id, introspection))
+    }
+
+    /// Encodes the dump in the binary format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let serialized = SerializedValue::serialize(&self.entries).unwrap();
+
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 1 + serialized.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&serialized);
+        bytes
+    }
+
+    /// Decodes a dump from the binary format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IntrospectionDumpError> {
+        let bytes = bytes
+            .strip_prefix(&MAGIC)
+            .ok_or(IntrospectionDumpError::InvalidFormat)?;
+
+        let (&version, bytes) = bytes
+            .split_first()
+            .ok_or(IntrospectionDumpError::InvalidFormat)?;
+
+        if version != VERSION {
+            return Err(IntrospectionDumpError::UnsupportedVersion(version));
+        }
+
+        let entries = SerializedValueSlice::new(bytes).deserialize()?;
+        Self::validate(entries)
+    }
+
+    /// Encodes the dump as pretty-printed JSON.
+    ///
+    /// Types are represented as an object, which maps each [`TypeId`] to the generic form of its
+    /// introspection.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn to_json(&self) -> Result<String, IntrospectionDumpError> {
+        serde_json::to_string_pretty(&self.entries).map_err(Into::into)
+    }
+
+    /// Decodes a dump from JSON.
+    ///
+    /// See [`to_json`](Self::to_json) for a description of the format.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn from_json(json: &str) -> Result<Self, IntrospectionDumpError> {
+        let entries: BTreeMap<TypeId, SerializedValue> = serde_json::from_str(json)?;
+        Self::validate(entries)
+    }
+
+    /// Loads a dump in the binary format from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, IntrospectionDumpError> {
+        let bytes = fs::read(path)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Saves the dump in the binary format to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), IntrospectionDumpError> {
+        fs::write(path, self.to_bytes()).map_err(Into::into)
+    }
+
+    pub(crate) fn insert_serialized(&mut self, type_id: TypeId, introspection: SerializedValue) {
+        self.entries.insert(type_id, introspection);
+    }
+
+    /// Validates all entries and brings them into their canonical serialized form.
+    fn validate(
+        entries: BTreeMap<TypeId, SerializedValue>,
+    ) -> Result<Self, IntrospectionDumpError> {
+        let mut dump = Self::new();
+
+        for (type_id, introspection) in entries {
+            match introspection.deserialize::<Introspection>() {
+                Ok(introspection) if introspection.type_id() == type_id => {
+                    dump.insert(&introspection)
+                        .map_err(|_| IntrospectionDumpError::InvalidIntrospection(type_id))?;
+                }
+
+                _ => return Err(IntrospectionDumpError::InvalidIntrospection(type_id)),
+            }
+        }
+
+        Ok(dump)
+    }
+}
+
+/// Error when loading an [`IntrospectionDump`].
+#[derive(Error, Debug)]
+pub enum IntrospectionDumpError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] IoError),
+
+    /// The data is not an introspection dump.
+    #[error("invalid introspection dump")]
+    InvalidFormat,
+
+    /// The dump has an unsupported version.
+    #[error("unsupported introspection dump version {0}")]
+    UnsupportedVersion(u8),
+
+    /// The dump failed to deserialize.
+    #[error(transparent)]
+    Deserialize(#[from] DeserializeError),
+
+    /// The introspection of a type is invalid or doesn't match its [`TypeId`].
+    #[error("invalid introspection for type {0}")]
+    InvalidIntrospection(TypeId),
+
+    /// The dump failed to encode or decode as JSON.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
@@ -0,0 +1,81 @@
+use super::{IntrospectionDatabase, IntrospectionDump, IntrospectionDumpError};
+use crate::conn_id::ConnectionIdManager;
+use crate::core::introspection::Introspection;
+use crate::core::{SerializedValue, TypeId};
+use std::collections::HashSet;
+
+fn dump() -> IntrospectionDump {
+    let mut dump = IntrospectionDump::new();
+    dump.insert(&Introspection::new::<u32>()).unwrap();
+    dump.insert(&Introspection::new::<Vec<String>>()).unwrap();
+    dump
+}
+
+#[test]
+fn dump_bytes() {
+    let dump = dump();
+    assert_eq!(dump.len(), 2);
+
+    let bytes = dump.to_bytes();
+    assert!(bytes.starts_with(b"ALDRNINT\x01"));
+    assert_eq!(IntrospectionDump::from_bytes(&bytes).unwrap(), dump);
+}
+
+#[test]
+fn dump_invalid_bytes() {
+    let mut bytes = dump().to_bytes();
+
+    assert!(matches!(
+        IntrospectionDump::from_bytes(&bytes[..4]),
+        Err(IntrospectionDumpError::InvalidFormat)
+    ));
+
+    bytes[8] = 2;
+    assert!(matches!(
+        IntrospectionDump::from_bytes(&bytes),
+        Err(IntrospectionDumpError::UnsupportedVersion(2))
+    ));
+}
+
+#[test]
+fn dump_mismatched_type_id() {
+    let type_id = TypeId::compute::<u32>();
+    let mut dump = IntrospectionDump::new();
+    dump.insert_serialized(
+        type_id,
+        SerializedValue::serialize(&Introspection::new::<u64>()).unwrap(),
+    );
+
+    assert!(matches!(
+        IntrospectionDump::from_bytes(&dump.to_bytes()),
+        Err(IntrospectionDumpError::InvalidIntrospection(id)) if id == type_id
+    ));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn dump_json() {
+    let dump = dump();
+    let json = dump.to_json().unwrap();
+
+    assert!(json.contains(&TypeId::compute::<u32>().to_string()));
+    assert_eq!(IntrospectionDump::from_json(&json).unwrap(), dump);
+}
+
+#[test]
+fn loaded_entries_are_persistent() {
+    let dump = dump();
+    let type_id = TypeId::compute::<u32>();
+
+    let mut db = IntrospectionDatabase::new();
+    db.load(&dump);
+    assert_eq!(db.dump(), dump);
+
+    let conn_id = ConnectionIdManager::new().acquire();
+    db.register(&HashSet::from([type_id]), &conn_id);
+    assert!(db.remove_conn(&conn_id).is_empty());
+
+    let entry = db.get_mut(type_id).unwrap();
+    assert_eq!(entry.introspection(), dump.get(type_id));
+    assert_eq!(db.dump(), dump);
+}
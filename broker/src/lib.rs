@@ -11,7 +11,10 @@
 //! Furthermore, this crate does not depend on any async runtime, such as e.g. Tokio. Neither the
 //! `Broker` nor `Connection` need to spawn additional tasks, nor perform any I/O on their
 //! own. Users of this crate have full control over what runtime to use (if any at all) and how to
-//! arrange the various parts into tasks.
+//! arrange the various parts into tasks. The only exceptions are `Embedded`, which is available
+//! with the optional `embedded` feature and runs a broker together with in-process clients on Tokio,
+//! as well as the built-in `TimerService` and `ConnectionService`, which are available with the
+//! optional `timers` and `connections` features.
 //!
 //! # Examples
 //!
@@ -52,16 +55,45 @@
 #![deny(missing_debug_implementations)]
 #![deny(missing_docs)]
 
+mod auth;
 mod broker;
 mod bus_listener;
 mod conn;
 mod conn_id;
+#[cfg(feature = "connections")]
+mod connections;
+#[cfg(feature = "embedded")]
+mod embedded;
 #[cfg(feature = "introspection")]
 mod introspection_database;
 mod serial_map;
+#[cfg(feature = "timers")]
+mod timers;
 
 pub use aldrin_core as core;
+pub use auth::{AuthStep, Authenticator, TokenAuthenticator};
 #[cfg(feature = "statistics")]
 pub use broker::BrokerStatistics;
-pub use broker::{Broker, BrokerHandle, BrokerShutdown, PendingConnection};
-pub use conn::{Connection, ConnectionError, ConnectionHandle, EstablishError};
+pub use broker::{
+    Broker, BrokerHandle, BrokerShutdown, ConnectionInfo, DeadLetter, DeadLetterKind, DeadLetters,
+    Failover, FailoverEvent, FailoverEvents, PendingConnection,
+};
+#[cfg(feature = "consistency-check")]
+pub use broker::{ConsistencyReport, ConsistencyReports, Inconsistency};
+#[cfg(feature = "history")]
+pub use broker::{HistoryEntry, HistoryEvent};
+pub use conn::{
+    Connection, ConnectionError, ConnectionHandle, EstablishError, SendQueueLimit,
+    SlowConsumerPolicy,
+};
+#[cfg(feature = "connections")]
+pub use connections::ConnectionService;
+#[cfg(feature = "embedded")]
+pub use embedded::{Embedded, EmbeddedConnectError, SpawnServiceError};
+#[cfg(feature = "introspection")]
+pub use introspection_database::{
+    IntrospectionConflict, IntrospectionConflictKind, IntrospectionConflicts, IntrospectionDump,
+    IntrospectionDumpError,
+};
+#[cfg(feature = "timers")]
+pub use timers::TimerService;
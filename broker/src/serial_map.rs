@@ -40,4 +40,9 @@ impl<T> SerialMap<T> {
     pub fn is_empty(&self) -> bool {
         self.elems.is_empty()
     }
+
+    #[cfg(feature = "consistency-check")]
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &T)> {
+        self.elems.iter().map(|(&serial, obj)| (serial, obj))
+    }
 }
@@ -0,0 +1,290 @@
+#[cfg(test)]
+mod test;
+
+use crate::core::{ObjectUuid, ServiceId, ServiceUuid};
+use aldrin::low_level::{Call, EventSubscriptions, Service, ServiceInfo, SubscriptionChange};
+use aldrin::{Error, Handle, Object};
+use std::collections::{HashMap, HashSet};
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::{self, Instant, Sleep};
+use uuid::uuid;
+
+/// Bus service, that provides timers to clients.
+///
+/// The timer service allows clients to request one-shot or periodic wakeups, which are delivered
+/// as events. This is useful for clients without reliable local timers and provides a single
+/// source of truth for timing on the bus.
+///
+/// The service is usually spawned with
+/// [`Embedded::spawn_timer_service`](crate::Embedded::spawn_timer_service), but it can run on any
+/// client with [`new`](Self::new) and [`run`](Self::run).
+///
+/// # Protocol
+///
+/// The service has the following functions:
+///
+/// - [`ONCE`](Self::ONCE) takes a `Duration` and creates a one-shot timer.
+/// - [`PERIODIC`](Self::PERIODIC) takes a `Duration` and creates a periodic timer.
+/// - [`CANCEL`](Self::CANCEL) takes a timer id and cancels the timer. It replies with an error
+///   (`()`), if the timer doesn't exist.
+///
+/// Timers are identified by a `u32`, which both `ONCE` and `PERIODIC` return. It is also the id
+/// of the event, which is emitted whenever the timer expires. The event's value is a `u64`, which
+/// counts the expirations, starting at 1.
+///
+/// A timer starts only when its event gets its first subscriber. Thus, clients must first create
+/// a timer and then subscribe to its event, without the risk of missing an expiration. Timers are
+/// removed when they are cancelled, when they lose all subscribers and, for one-shot timers, after
+/// they have expired.
+///
+/// This type requires the `timers` feature.
+#[derive(Debug)]
+pub struct TimerService {
+    object: Object,
+    service: Service,
+    subscriptions: EventSubscriptions,
+    subscribed: HashSet<u32>,
+    timers: HashMap<u32, Timer>,
+    next_id: u32,
+    sleep: Pin<Box<Sleep>>,
+}
+
+impl TimerService {
+    /// UUID of the object, that owns the timer service.
+    pub const OBJECT_UUID: ObjectUuid = ObjectUuid(uuid!("f1a0d3c6-2b7e-4c58-9e14-6d2b8a5f0c37"));
+
+    /// UUID of the timer service.
+    pub const SERVICE_UUID: ServiceUuid =
+        ServiceUuid(uuid!("3c9e71b4-8d25-4f0a-b6e3-1a7c5d9f2e48"));
+
+    /// Version of the timer service.
+    pub const VERSION: u32 = 1;
+
+    /// Function id for creating a one-shot timer.
+    pub const ONCE: u32 = 1;
+
+    /// Function id for creating a periodic timer.
+    pub const PERIODIC: u32 = 2;
+
+    /// Function id for cancelling a timer.
+    pub const CANCEL: u32 = 3;
+
+    /// Creates the timer service on a client.
+    ///
+    /// This creates an object with the UUID [`OBJECT_UUID`](Self::OBJECT_UUID) and fails with
+    /// [`Error::DuplicateObject`], if a timer service already exists on the bus.
+    pub async fn new(client: &Handle) -> Result<Self, Error> {
+        let object = client.create_object(Self::OBJECT_UUID).await?;
+
+        let service = object
+            .create_service(Self::SERVICE_UUID, ServiceInfo::new(Self::VERSION))
+            .await?;
+
+        let subscriptions = service.event_subscriptions().await?;
+
+        Ok(Self {
+            object,
+            service,
+            subscriptions,
+            subscribed: HashSet::new(),
+            timers: HashMap::new(),
+            next_id: 0,
+            sleep: Box::pin(time::sleep(Duration::ZERO)),
+        })
+    }
+
+    /// Returns the id of the timer service.
+    pub fn id(&self) -> ServiceId {
+        self.service.id()
+    }
+
+    /// Returns the object, that owns the timer service.
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+
+    /// Runs the timer service.
+    ///
+    /// This function returns when the service has been destroyed, e.g. because the client shut
+    /// down.
+    pub async fn run(mut self) -> Result<(), Error> {
+        future::poll_fn(|cx| self.poll_run(cx)).await
+    }
+
+    fn poll_run(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
+        loop {
+            if let Poll::Ready(change) = self.subscriptions.poll_next_change(cx) {
+                match change {
+                    Some(SubscriptionChange::Subscribed(id)) => self.subscribed(id),
+                    Some(SubscriptionChange::Unsubscribed(id)) => self.unsubscribed(id),
+                    Some(
+                        SubscriptionChange::AllSubscribed | SubscriptionChange::AllUnsubscribed,
+                    ) => {}
+                    None => return Poll::Ready(Ok(())),
+                }
+
+                continue;
+            }
+
+            if let Poll::Ready(call) = self.service.poll_next_call(cx) {
+                match call {
+                    Some(call) => self.call(call)?,
+                    None => return Poll::Ready(Ok(())),
+                }
+
+                continue;
+            }
+
+            if let Some(deadline) = self.next_deadline() {
+                if self.sleep.deadline() != deadline {
+                    self.sleep.as_mut().reset(deadline);
+                }
+
+                if self.sleep.as_mut().poll(cx).is_ready() {
+                    self.expire(Instant::now())?;
+                    continue;
+                }
+            }
+
+            return Poll::Pending;
+        }
+    }
+
+    fn call(&mut self, call: Call) -> Result<(), Error> {
+        match call.id() {
+            Self::ONCE | Self::PERIODIC => {
+                let Ok(duration) = call.deserialize::<Duration>() else {
+                    return call.into_promise().invalid_args();
+                };
+
+                let periodic = call.id() == Self::PERIODIC;
+                if periodic && duration.is_zero() {
+                    return call.into_promise().invalid_args();
+                }
+
+                let id = self.create(duration, periodic);
+                call.into_promise().ok(&id)
+            }
+
+            Self::CANCEL => {
+                let Ok(id) = call.deserialize::<u32>() else {
+                    return call.into_promise().invalid_args();
+                };
+
+                if self.timers.remove(&id).is_some() {
+                    call.into_promise().done()
+                } else {
+                    call.into_promise().err(&())
+                }
+            }
+
+            _ => call.into_promise().invalid_function(),
+        }
+    }
+
+    fn create(&mut self, duration: Duration, periodic: bool) -> u32 {
+        loop {
+            self.next_id = self.next_id.wrapping_add(1);
+
+            if !self.timers.contains_key(&self.next_id) {
+                break;
+            }
+        }
+
+        let mut timer = Timer::new(duration, periodic);
+
+        // Events can be subscribed before the timer is created.
+        if self.subscribed.contains(&self.next_id) {
+            timer.start(Instant::now());
+        }
+
+        self.timers.insert(self.next_id, timer);
+        self.next_id
+    }
+
+    fn subscribed(&mut self, id: u32) {
+        self.subscribed.insert(id);
+
+        if let Some(timer) = self.timers.get_mut(&id) {
+            timer.start(Instant::now());
+        }
+    }
+
+    fn unsubscribed(&mut self, id: u32) {
+        self.subscribed.remove(&id);
+        self.timers.remove(&id);
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        self.timers
+            .values()
+            .filter_map(|timer| timer.deadline)
+            .min()
+    }
+
+    fn expire(&mut self, now: Instant) -> Result<(), Error> {
+        let mut res = Ok(());
+
+        self.timers.retain(|&id, timer| {
+            let Some(count) = timer.expire(now) else {
+                return true;
+            };
+
+            if res.is_ok() {
+                res = self.service.emit(id, &count);
+            }
+
+            timer.periodic
+        });
+
+        res
+    }
+}
+
+#[derive(Debug)]
+struct Timer {
+    duration: Duration,
+    periodic: bool,
+    deadline: Option<Instant>,
+    count: u64,
+}
+
+impl Timer {
+    fn new(duration: Duration, periodic: bool) -> Self {
+        Self {
+            duration,
+            periodic,
+            deadline: None,
+            count: 0,
+        }
+    }
+
+    fn start(&mut self, now: Instant) {
+        if self.deadline.is_none() && (self.count == 0) {
+            self.deadline = Some(now + self.duration);
+        }
+    }
+
+    /// Returns the expiration count, if the timer has expired.
+    fn expire(&mut self, now: Instant) -> Option<u64> {
+        let deadline = self.deadline.filter(|&deadline| deadline <= now)?;
+        self.count += 1;
+
+        if self.periodic {
+            // Skip missed expirations instead of emitting them in a burst.
+            let mut next = deadline + self.duration;
+            while next <= now {
+                next += self.duration;
+            }
+
+            self.deadline = Some(next);
+        } else {
+            self.deadline = None;
+        }
+
+        Some(self.count)
+    }
+}
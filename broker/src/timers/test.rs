@@ -0,0 +1,123 @@
+use super::TimerService;
+use crate::Embedded;
+use aldrin::low_level::Proxy;
+use aldrin::Error;
+use std::time::Duration;
+use tokio::time;
+
+#[tokio::test]
+async fn one_shot() {
+    let mut bus = Embedded::new();
+    let id = bus.spawn_timer_service().await.unwrap();
+    let client = bus.connect().await.unwrap();
+
+    let mut proxy = Proxy::new(&client, id).await.unwrap();
+    let timer: u32 = proxy
+        .call(TimerService::ONCE, &Duration::from_millis(10))
+        .await
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    proxy.subscribe(timer).await.unwrap();
+
+    let ev = time::timeout(Duration::from_secs(1), proxy.next_event())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ev.id(), timer);
+    assert_eq!(ev.deserialize(), Ok(1u64));
+
+    // One-shot timers are removed after they have expired.
+    let reply = proxy.call(TimerService::CANCEL, &timer).await.unwrap();
+    assert!(reply.is_err());
+
+    bus.shutdown().await;
+}
+
+#[tokio::test]
+async fn periodic() {
+    let mut bus = Embedded::new();
+    let id = bus.spawn_timer_service().await.unwrap();
+    let client = bus.connect().await.unwrap();
+
+    let mut proxy = Proxy::new(&client, id).await.unwrap();
+    let timer: u32 = proxy
+        .call(TimerService::PERIODIC, &Duration::from_millis(10))
+        .await
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    proxy.subscribe(timer).await.unwrap();
+
+    for count in 1..=3u64 {
+        let ev = time::timeout(Duration::from_secs(1), proxy.next_event())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(ev.id(), timer);
+        assert_eq!(ev.deserialize(), Ok(count));
+    }
+
+    let reply = proxy.call(TimerService::CANCEL, &timer).await.unwrap();
+    assert!(reply.is_ok());
+
+    bus.shutdown().await;
+}
+
+#[tokio::test]
+async fn unsubscribe_removes_timer() {
+    let mut bus = Embedded::new();
+    let id = bus.spawn_timer_service().await.unwrap();
+    let client = bus.connect().await.unwrap();
+
+    let proxy = Proxy::new(&client, id).await.unwrap();
+    let timer: u32 = proxy
+        .call(TimerService::PERIODIC, &Duration::from_secs(60))
+        .await
+        .unwrap()
+        .unwrap()
+        .deserialize()
+        .unwrap();
+
+    proxy.subscribe(timer).await.unwrap();
+    proxy.unsubscribe(timer).await.unwrap();
+    client.sync_broker().await.unwrap();
+
+    let reply = proxy.call(TimerService::CANCEL, &timer).await.unwrap();
+    assert!(reply.is_err());
+
+    bus.shutdown().await;
+}
+
+#[tokio::test]
+async fn invalid_args() {
+    let mut bus = Embedded::new();
+    let id = bus.spawn_timer_service().await.unwrap();
+    let client = bus.connect().await.unwrap();
+
+    let proxy = Proxy::new(&client, id).await.unwrap();
+
+    let res = proxy.call(TimerService::PERIODIC, &Duration::ZERO).await;
+    assert!(matches!(
+        res.map_err(Error::without_context),
+        Err(Error::InvalidArguments(_))
+    ));
+
+    let res = proxy.call(TimerService::ONCE, "foo").await;
+    assert!(matches!(
+        res.map_err(Error::without_context),
+        Err(Error::InvalidArguments(_))
+    ));
+
+    bus.shutdown().await;
+}
+
+#[tokio::test]
+async fn duplicate_timer_service() {
+    let mut bus = Embedded::new();
+    bus.spawn_timer_service().await.unwrap();
+    assert!(bus.spawn_timer_service().await.is_err());
+    bus.shutdown().await;
+}
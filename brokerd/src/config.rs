@@ -0,0 +1,317 @@
+#[cfg(test)]
+mod test;
+
+use aldrin_broker::{SendQueueLimit, SlowConsumerPolicy};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::de::{Deserializer, Error};
+use serde::Deserialize;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::Level;
+
+const TCP_DEFAULT: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 24940);
+
+/// Configuration of the broker.
+///
+/// See `README.md` for a description of the file format.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    #[serde(default = "default_log_level", deserialize_with = "log_level")]
+    pub log_level: Level,
+
+    #[serde(default)]
+    pub listen: Listen,
+
+    #[serde(default)]
+    pub limits: Limits,
+
+    #[serde(default)]
+    pub acl: Acl,
+
+    pub auth: Option<Auth>,
+    pub statistics: Option<Statistics>,
+    pub introspection: Option<Introspection>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let src = fs::read_to_string(path)
+            .with_context(|| anyhow!("failed to read {}", path.display()))?;
+
+        Self::parse(&src).with_context(|| anyhow!("invalid configuration {}", path.display()))
+    }
+
+    pub fn parse(src: &str) -> Result<Self> {
+        let config: Self = toml::from_str(src)?;
+
+        #[cfg(not(unix))]
+        if !config.listen.unix.is_empty() {
+            bail!("unix sockets are not supported on this platform");
+        }
+
+        if config.listen.tls.is_some() {
+            bail!(
+                "`listen.tls` is not supported, because Aldrin has no TLS transport; terminate TLS \
+                 in a proxy in front of a `listen.tcp` or `listen.websocket` address instead"
+            );
+        }
+
+        #[cfg(not(feature = "websocket"))]
+        if !config.listen.websocket.is_empty() {
+            bail!("`listen.websocket` requires the `websocket` feature");
+        }
+
+        if config
+            .auth
+            .as_ref()
+            .is_some_and(|auth| auth.tokens.is_empty())
+        {
+            bail!("`auth.tokens` must not be empty");
+        }
+
+        #[cfg(not(feature = "statistics"))]
+        if config.statistics.is_some() {
+            bail!("`statistics` requires the `statistics` feature");
+        }
+
+        #[cfg(not(feature = "introspection"))]
+        if config.introspection.is_some() {
+            bail!("`introspection` requires the `introspection` feature");
+        }
+
+        Ok(config)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            log_level: Level::INFO,
+            listen: Listen::default(),
+            limits: Limits::default(),
+            acl: Acl::default(),
+            auth: None,
+            statistics: None,
+            introspection: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Listen {
+    #[serde(default)]
+    pub tcp: Vec<SocketAddr>,
+
+    #[serde(default)]
+    #[cfg_attr(not(unix), allow(dead_code))]
+    pub unix: Vec<PathBuf>,
+
+    #[serde(default)]
+    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    pub websocket: Vec<SocketAddr>,
+
+    /// Only present to reject it with a helpful error.
+    tls: Option<toml::Value>,
+}
+
+impl Default for Listen {
+    fn default() -> Self {
+        Self {
+            tcp: vec![TCP_DEFAULT],
+            unix: Vec::new(),
+            websocket: Vec::new(),
+            tls: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(try_from = "LimitsFile")]
+pub struct Limits {
+    pub max_message_size: Option<usize>,
+    pub send_queue_limit: Option<SendQueueLimit>,
+    pub keep_alive_timeout: Option<Duration>,
+    pub auth_timeout: Option<Duration>,
+}
+
+/// The `limits` table as it appears in the file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct LimitsFile {
+    max_message_size: Option<usize>,
+    send_queue: Option<usize>,
+    slow_consumer_policy: Option<Policy>,
+    keep_alive_timeout: Option<u64>,
+    auth_timeout: Option<u64>,
+}
+
+impl TryFrom<LimitsFile> for Limits {
+    type Error = &'static str;
+
+    fn try_from(file: LimitsFile) -> Result<Self, Self::Error> {
+        if file.send_queue.is_none() && file.slow_consumer_policy.is_some() {
+            return Err("`slow-consumer-policy` requires `send-queue`");
+        }
+
+        let policy = match file.slow_consumer_policy.unwrap_or_default() {
+            Policy::Alert => SlowConsumerPolicy::Alert,
+            Policy::DropEvents => SlowConsumerPolicy::DropEvents,
+            Policy::Disconnect => SlowConsumerPolicy::Disconnect,
+        };
+
+        Ok(Self {
+            max_message_size: file.max_message_size,
+            send_queue_limit: file
+                .send_queue
+                .map(|limit| SendQueueLimit::new(limit, policy)),
+            keep_alive_timeout: file.keep_alive_timeout.map(Duration::from_secs),
+            auth_timeout: file.auth_timeout.map(Duration::from_secs),
+        })
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Policy {
+    #[default]
+    Alert,
+    DropEvents,
+    Disconnect,
+}
+
+/// Access control rules for clients connecting over TCP or WebSocket.
+///
+/// Rules are checked in order and the first one matching a client's address decides. If there are
+/// rules, clients that match none of them are denied.
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Acl {
+    rules: Vec<AclRule>,
+}
+
+impl Acl {
+    pub fn allows(&self, addr: IpAddr) -> bool {
+        self.rules
+            .iter()
+            .find(|rule| rule.from.contains(addr))
+            .map_or(self.rules.is_empty(), |rule| {
+                rule.action == AclAction::Allow
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AclRule {
+    pub action: AclAction,
+    pub from: Network,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AclAction {
+    Allow,
+    Deny,
+}
+
+/// An IP network such as `192.168.0.0/16`, or a single address.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Network {
+    addr: IpAddr,
+    prefix: u32,
+}
+
+impl Network {
+    pub fn contains(self, addr: IpAddr) -> bool {
+        // Dual-stack sockets report IPv4 clients as IPv4-mapped IPv6 addresses.
+        let addr = match addr {
+            IpAddr::V6(addr) => addr.to_ipv4_mapped().map_or(IpAddr::V6(addr), IpAddr::V4),
+            IpAddr::V4(_) => addr,
+        };
+
+        match (self.addr, addr) {
+            (IpAddr::V4(net), IpAddr::V4(addr)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix).unwrap_or(0);
+                u32::from(net) & mask == u32::from(addr) & mask
+            }
+
+            (IpAddr::V6(net), IpAddr::V6(addr)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix).unwrap_or(0);
+                u128::from(net) & mask == u128::from(addr) & mask
+            }
+
+            _ => false,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Network {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let network = String::deserialize(deserializer)?;
+        let invalid = || D::Error::custom(format!("invalid network `{network}`"));
+
+        let (addr, prefix) = match network.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (network.as_str(), None),
+        };
+
+        let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse().map_err(|_| invalid())?,
+            None => max,
+        };
+
+        if prefix > max {
+            return Err(invalid());
+        }
+
+        Ok(Self { addr, prefix })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Auth {
+    pub tokens: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(not(feature = "statistics"), allow(dead_code))]
+pub struct Statistics {
+    #[serde(default = "default_interval", deserialize_with = "secs")]
+    pub interval: Duration,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(not(feature = "introspection"), allow(dead_code))]
+pub struct Introspection {
+    pub database: Option<PathBuf>,
+}
+
+fn default_log_level() -> Level {
+    Level::INFO
+}
+
+fn log_level<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Level, D::Error> {
+    let level = String::deserialize(deserializer)?;
+
+    level
+        .parse()
+        .map_err(|_| D::Error::custom(format!("invalid log level `{level}`")))
+}
+
+fn default_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn secs<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    u64::deserialize(deserializer).map(Duration::from_secs)
+}
@@ -0,0 +1,246 @@
+use super::Config;
+use aldrin_broker::{SendQueueLimit, SlowConsumerPolicy};
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::Level;
+
+#[test]
+fn empty() {
+    let config = Config::parse("").unwrap();
+    let default = Config::default();
+
+    assert_eq!(config.log_level, default.log_level);
+    assert_eq!(config.listen.tcp, default.listen.tcp);
+    assert_eq!(config.listen.tcp, ["127.0.0.1:24940".parse().unwrap()]);
+    assert!(config.listen.unix.is_empty());
+    assert!(config.listen.websocket.is_empty());
+    assert_eq!(config.limits.max_message_size, None);
+    assert_eq!(config.limits.send_queue_limit, None);
+    assert!(config.auth.is_none());
+    assert!(config.statistics.is_none());
+    assert!(config.introspection.is_none());
+}
+
+#[test]
+fn full() {
+    let config = Config::parse(
+        r#"
+        # Comments are allowed.
+        log-level = "debug"
+
+        [listen]
+        tcp = [
+            "127.0.0.1:1234",
+            "[::1]:1234",
+        ]
+        unix = ["/run/aldrin.sock"]
+
+        [limits]
+        max-message-size = 16_777_216
+        send-queue = 1024
+        slow-consumer-policy = "drop-events"
+        keep-alive-timeout = 30
+        auth-timeout = 10
+
+        [auth]
+        tokens = ["foo", 'bar']
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.log_level, Level::DEBUG);
+
+    assert_eq!(
+        config.listen.tcp,
+        [
+            "127.0.0.1:1234".parse::<SocketAddr>().unwrap(),
+            "[::1]:1234".parse().unwrap(),
+        ]
+    );
+    assert_eq!(config.listen.unix, [PathBuf::from("/run/aldrin.sock")]);
+
+    assert_eq!(config.limits.max_message_size, Some(16 * 1024 * 1024));
+    assert_eq!(
+        config.limits.send_queue_limit,
+        Some(SendQueueLimit::new(1024, SlowConsumerPolicy::DropEvents))
+    );
+    assert_eq!(
+        config.limits.keep_alive_timeout,
+        Some(Duration::from_secs(30))
+    );
+    assert_eq!(config.limits.auth_timeout, Some(Duration::from_secs(10)));
+
+    assert_eq!(config.auth.unwrap().tokens, ["foo", "bar"]);
+}
+
+#[test]
+fn listen_without_tcp() {
+    let config = Config::parse("[listen]\nunix = [\"/run/aldrin.sock\"]\n").unwrap();
+    assert!(config.listen.tcp.is_empty());
+}
+
+#[test]
+fn default_slow_consumer_policy() {
+    let config = Config::parse("[limits]\nsend-queue = 8\n").unwrap();
+
+    assert_eq!(
+        config.limits.send_queue_limit,
+        Some(SendQueueLimit::new(8, SlowConsumerPolicy::Alert))
+    );
+}
+
+#[test]
+fn slow_consumer_policy_requires_send_queue() {
+    let err = Config::parse("[limits]\nslow-consumer-policy = \"disconnect\"\n").unwrap_err();
+    assert!(format!("{err:#}").contains("`slow-consumer-policy` requires `send-queue`"));
+}
+
+#[cfg(feature = "websocket")]
+#[test]
+fn websocket() {
+    let config = Config::parse("[listen]\nwebsocket = [\"127.0.0.1:1234\"]\n").unwrap();
+
+    assert!(config.listen.tcp.is_empty());
+    assert_eq!(
+        config.listen.websocket,
+        ["127.0.0.1:1234".parse::<SocketAddr>().unwrap()]
+    );
+}
+
+#[cfg(not(feature = "websocket"))]
+#[test]
+fn websocket_requires_feature() {
+    assert!(Config::parse("[listen]\nwebsocket = [\"127.0.0.1:1234\"]\n").is_err());
+}
+
+#[test]
+fn reject_tls() {
+    let err = Config::parse("[listen]\ntls = []\n").unwrap_err();
+    assert!(err.to_string().contains("`listen.tls` is not supported"));
+}
+
+#[test]
+fn acl() {
+    let config = Config::parse(
+        r#"
+        [[acl]]
+        action = "deny"
+        from = "192.168.1.13"
+
+        [[acl]]
+        action = "allow"
+        from = "192.168.0.0/16"
+
+        [[acl]]
+        action = "allow"
+        from = "::1/128"
+        "#,
+    )
+    .unwrap();
+
+    let allows = |addr: &str| config.acl.allows(addr.parse::<IpAddr>().unwrap());
+
+    assert!(allows("192.168.1.1"));
+    assert!(allows("192.168.255.255"));
+    assert!(allows("::ffff:192.168.1.1"));
+    assert!(allows("::1"));
+    assert!(!allows("192.168.1.13"));
+    assert!(!allows("::ffff:192.168.1.13"));
+    assert!(!allows("192.169.0.1"));
+    assert!(!allows("::2"));
+}
+
+#[test]
+fn empty_acl_allows_all() {
+    let config = Config::parse("").unwrap();
+
+    assert!(config.acl.allows("10.0.0.1".parse().unwrap()));
+    assert!(config.acl.allows("::1".parse().unwrap()));
+}
+
+#[test]
+fn catch_all_acl() {
+    let config = Config::parse(
+        "[[acl]]\naction = \"allow\"\nfrom = \"0.0.0.0/0\"\n\n\
+         [[acl]]\naction = \"deny\"\nfrom = \"::/0\"\n",
+    )
+    .unwrap();
+
+    assert!(config.acl.allows("10.0.0.1".parse().unwrap()));
+    assert!(!config.acl.allows("fe80::1".parse().unwrap()));
+}
+
+#[cfg(feature = "statistics")]
+#[test]
+fn statistics() {
+    let config = Config::parse("[statistics]\n").unwrap();
+    assert_eq!(config.statistics.unwrap().interval, Duration::from_secs(60));
+
+    let config = Config::parse("[statistics]\ninterval = 5\n").unwrap();
+    assert_eq!(config.statistics.unwrap().interval, Duration::from_secs(5));
+}
+
+#[cfg(not(feature = "statistics"))]
+#[test]
+fn statistics_requires_feature() {
+    assert!(Config::parse("[statistics]\n").is_err());
+}
+
+#[cfg(feature = "introspection")]
+#[test]
+fn introspection() {
+    let config = Config::parse("[introspection]\ndatabase = \"db.json\"\n").unwrap();
+
+    assert_eq!(
+        config.introspection.unwrap().database,
+        Some(PathBuf::from("db.json"))
+    );
+}
+
+#[cfg(not(feature = "introspection"))]
+#[test]
+fn introspection_requires_feature() {
+    assert!(Config::parse("[introspection]\n").is_err());
+}
+
+#[test]
+fn reject_invalid_syntax() {
+    assert!(Config::parse("log-level = ").is_err());
+    assert!(Config::parse("[limits\n").is_err());
+}
+
+#[test]
+fn reject_unknown_fields() {
+    assert!(Config::parse("foo = 1\n").is_err());
+    assert!(Config::parse("[foo]\n").is_err());
+    assert!(Config::parse("[limits]\nfoo = 1\n").is_err());
+}
+
+#[test]
+fn reject_wrong_types() {
+    assert!(Config::parse("log-level = 1\n").is_err());
+    assert!(Config::parse("listen = 1\n").is_err());
+    assert!(Config::parse("[listen]\ntcp = \"127.0.0.1:1234\"\n").is_err());
+    assert!(Config::parse("[listen]\ntcp = [1]\n").is_err());
+    assert!(Config::parse("[limits]\nsend-queue = \"1\"\n").is_err());
+    assert!(Config::parse("[auth]\ntokens = [true]\n").is_err());
+}
+
+#[test]
+fn reject_invalid_values() {
+    assert!(Config::parse("log-level = \"verbose\"\n").is_err());
+    assert!(Config::parse("[listen]\ntcp = [\"localhost\"]\n").is_err());
+    assert!(Config::parse("[limits]\nmax-message-size = -1\n").is_err());
+    assert!(Config::parse("[limits]\nkeep-alive-timeout = -1\n").is_err());
+    assert!(Config::parse("[limits]\nslow-consumer-policy = \"ignore\"\n").is_err());
+    assert!(Config::parse("[[acl]]\naction = \"allow\"\nfrom = \"10.0.0.0/33\"\n").is_err());
+    assert!(Config::parse("[[acl]]\naction = \"allow\"\nfrom = \"localhost\"\n").is_err());
+    assert!(Config::parse("[[acl]]\naction = \"ignore\"\nfrom = \"::1\"\n").is_err());
+}
+
+#[test]
+fn reject_empty_tokens() {
+    assert!(Config::parse("[auth]\n").is_err());
+    assert!(Config::parse("[auth]\ntokens = []\n").is_err());
+}
@@ -0,0 +1,362 @@
+mod config;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+use aldrin_broker::core::tokio::TokioTransport;
+#[cfg(feature = "introspection")]
+use aldrin_broker::IntrospectionDump;
+use aldrin_broker::{Broker, BrokerHandle, TokenAuthenticator};
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use config::{Acl, Config, Limits};
+use std::fmt::Display;
+#[cfg(unix)]
+use std::fs;
+use std::future;
+#[cfg(feature = "websocket")]
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+#[cfg(feature = "websocket")]
+use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixListener;
+use tokio::task::JoinSet;
+use tokio::{signal, time};
+use tracing::{error, info, warn};
+#[cfg(feature = "websocket")]
+use websocket::WebSocket;
+
+/// Aldrin broker.
+///
+/// The broker is configured with a TOML file. Without a configuration file, it listens on
+/// 127.0.0.1:24940 without any limits.
+#[derive(Parser)]
+#[clap(version)]
+struct Args {
+    /// Path to the configuration file.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+
+    /// Only check the configuration file and exit.
+    #[clap(long, requires = "config")]
+    check_config: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let config = match args.config {
+        Some(ref path) => Config::load(path)?,
+        None => Config::default(),
+    };
+
+    if args.check_config {
+        println!("Configuration is valid.");
+        return Ok(());
+    }
+
+    tracing_subscriber::fmt()
+        .with_max_level(config.log_level)
+        .with_target(false)
+        .init();
+
+    run(config).await
+}
+
+async fn run(config: Config) -> Result<()> {
+    #[allow(unused_mut)]
+    let mut broker = Broker::new();
+
+    #[cfg(feature = "introspection")]
+    if let Some(path) = config
+        .introspection
+        .as_ref()
+        .and_then(|introspection| introspection.database.as_ref())
+    {
+        if path.exists() {
+            let dump = IntrospectionDump::load(path)
+                .with_context(|| anyhow!("failed to load {}", path.display()))?;
+
+            info!("Loaded {} types from {}.", dump.len(), path.display());
+            broker.load_introspection(&dump);
+        }
+    }
+
+    let mut handle = broker.handle().clone();
+    let broker = tokio::spawn(broker.run());
+
+    let conn = Arc::new(ConnectionConfig {
+        limits: config.limits,
+        acl: config.acl,
+        authenticator: config.auth.map(|auth| TokenAuthenticator::new(auth.tokens)),
+    });
+
+    let mut listeners = JoinSet::new();
+
+    for addr in config.listen.tcp {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| anyhow!("failed to bind to {addr}"))?;
+
+        info!("Listening on {addr}.");
+        let handle = handle.clone();
+        let conn = conn.clone();
+
+        listeners.spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, addr)) => {
+                        if !conn.acl.allows(addr.ip()) {
+                            info!("Denied connection from {addr}.");
+                            continue;
+                        }
+
+                        if let Err(e) = stream.set_nodelay(true) {
+                            warn!("Failed to set TCP_NODELAY for {addr}: {e}.");
+                        }
+
+                        spawn_connection(handle.clone(), conn.clone(), stream, addr);
+                    }
+
+                    Err(e) => warn!("Failed to accept new connection: {e}."),
+                }
+            }
+        });
+    }
+
+    #[cfg(feature = "websocket")]
+    for addr in config.listen.websocket {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| anyhow!("failed to bind to {addr}"))?;
+
+        info!("Listening for WebSocket connections on {addr}.");
+        let handle = handle.clone();
+        let conn = conn.clone();
+
+        listeners.spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, addr)) => {
+                        if !conn.acl.allows(addr.ip()) {
+                            info!("Denied WebSocket connection from {addr}.");
+                            continue;
+                        }
+
+                        if let Err(e) = stream.set_nodelay(true) {
+                            warn!("Failed to set TCP_NODELAY for {addr}: {e}.");
+                        }
+
+                        spawn_websocket_connection(handle.clone(), conn.clone(), stream, addr);
+                    }
+
+                    Err(e) => warn!("Failed to accept new WebSocket connection: {e}."),
+                }
+            }
+        });
+    }
+
+    #[cfg(unix)]
+    for path in &config.listen.unix {
+        let listener = UnixListener::bind(path)
+            .with_context(|| anyhow!("failed to bind to {}", path.display()))?;
+
+        let name = path.display().to_string();
+        info!("Listening on {name}.");
+        let handle = handle.clone();
+        let conn = conn.clone();
+
+        listeners.spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        spawn_connection(handle.clone(), conn.clone(), stream, name.clone());
+                    }
+
+                    Err(e) => warn!("Failed to accept new connection: {e}."),
+                }
+            }
+        });
+    }
+
+    #[cfg(feature = "statistics")]
+    if let Some(statistics) = config.statistics {
+        let mut handle = handle.clone();
+
+        listeners.spawn(async move {
+            let mut interval = time::interval(statistics.interval);
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+
+                let Ok(statistics) = handle.take_statistics().await else {
+                    break;
+                };
+
+                info!(
+                    messages_sent = statistics.messages_sent(),
+                    messages_received = statistics.messages_received(),
+                    events_emitted = statistics.events_emitted(),
+                    events_delivered = statistics.events_delivered(),
+                    connections = statistics.num_connections(),
+                    objects = statistics.num_objects(),
+                    services = statistics.num_services(),
+                    channels = statistics.num_channels(),
+                    bus_listeners = statistics.num_bus_listeners(),
+                    "Statistics."
+                );
+            }
+        });
+    }
+
+    shutdown_signal().await?;
+    info!("Shutting down broker.");
+    listeners.shutdown().await;
+
+    #[cfg(unix)]
+    for path in &config.listen.unix {
+        if let Err(e) = fs::remove_file(path) {
+            warn!("Failed to remove {}: {e}.", path.display());
+        }
+    }
+
+    #[cfg(feature = "introspection")]
+    if let Some(path) = config
+        .introspection
+        .as_ref()
+        .and_then(|introspection| introspection.database.as_ref())
+    {
+        let dump = handle.dump_introspection().await?;
+
+        dump.save(path)
+            .with_context(|| anyhow!("failed to save {}", path.display()))?;
+
+        info!("Saved {} types to {}.", dump.len(), path.display());
+    }
+
+    handle.shutdown().await;
+    broker
+        .await
+        .with_context(|| anyhow!("failed to join broker task"))?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn shutdown_signal() -> Result<()> {
+    use tokio::signal::unix::{self, SignalKind};
+
+    let mut terminate = unix::signal(SignalKind::terminate())
+        .with_context(|| anyhow!("failed to listen for SIGTERM"))?;
+
+    tokio::select! {
+        res = signal::ctrl_c() => res.with_context(|| anyhow!("failed to listen for CTRL-C")),
+        _ = terminate.recv() => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+async fn shutdown_signal() -> Result<()> {
+    signal::ctrl_c()
+        .await
+        .with_context(|| anyhow!("failed to listen for CTRL-C"))
+}
+
+struct ConnectionConfig {
+    limits: Limits,
+    acl: Acl,
+    authenticator: Option<TokenAuthenticator>,
+}
+
+fn spawn_connection<S, A>(handle: BrokerHandle, conn: Arc<ConnectionConfig>, stream: S, addr: A)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    A: Display + Send + 'static,
+{
+    tokio::spawn(async move {
+        info!("New connection from {addr}.");
+
+        match handle_connection(handle, &conn, stream).await {
+            Ok(()) => info!("Connection from {addr} shut down."),
+            Err(e) => error!("Error on connection from {addr}: {e:#}"),
+        }
+    });
+}
+
+#[cfg(feature = "websocket")]
+fn spawn_websocket_connection(
+    handle: BrokerHandle,
+    conn: Arc<ConnectionConfig>,
+    stream: TcpStream,
+    addr: SocketAddr,
+) {
+    tokio::spawn(async move {
+        info!("New WebSocket connection from {addr}.");
+
+        let res = match WebSocket::accept(stream).await {
+            Ok(stream) => handle_connection(handle, &conn, stream).await,
+            Err(e) => Err(e).with_context(|| anyhow!("WebSocket handshake failed")),
+        };
+
+        match res {
+            Ok(()) => info!("WebSocket connection from {addr} shut down."),
+            Err(e) => error!("Error on WebSocket connection from {addr}: {e:#}"),
+        }
+    });
+}
+
+async fn handle_connection<S>(
+    mut handle: BrokerHandle,
+    conn: &ConnectionConfig,
+    stream: S,
+) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut transport = TokioTransport::new(stream);
+    transport.set_max_message_size(conn.limits.max_message_size);
+
+    let mut pending = handle
+        .begin_connect(transport)
+        .await
+        .with_context(|| anyhow!("failed to begin connection"))?;
+
+    if let Some(limit) = conn.limits.send_queue_limit {
+        pending.set_send_queue_limit(limit);
+    }
+
+    if let Some(ref authenticator) = conn.authenticator {
+        let timeout = async {
+            match conn.limits.auth_timeout {
+                Some(timeout) => time::sleep(timeout).await,
+                None => future::pending().await,
+            }
+        };
+
+        pending = pending
+            .authenticate_with_timeout(authenticator, timeout)
+            .await
+            .with_context(|| anyhow!("failed to authenticate client"))?;
+    }
+
+    let conn_res = pending
+        .accept(None)
+        .await
+        .with_context(|| anyhow!("failed to accept client"))?;
+
+    match conn.limits.keep_alive_timeout {
+        Some(timeout) => {
+            conn_res
+                .run_with_keep_alive_timeout(|| time::sleep(timeout))
+                .await
+        }
+
+        None => conn_res.run().await,
+    }
+    .with_context(|| anyhow!("failed to run connection"))
+}
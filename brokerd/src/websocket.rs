@@ -0,0 +1,88 @@
+use futures_util::{Sink, Stream};
+use std::io::{Error, ErrorKind, Result};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::WebSocketStream;
+
+/// Byte stream on top of a WebSocket connection.
+///
+/// Both directions carry the same byte stream as a TCP connection would. Bytes are sent as binary
+/// messages, and message boundaries are irrelevant. All other messages from the client are
+/// ignored.
+pub struct WebSocket {
+    inner: WebSocketStream<TcpStream>,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl WebSocket {
+    /// Performs the server side of the WebSocket handshake.
+    pub async fn accept(stream: TcpStream) -> Result<Self> {
+        let inner = tokio_tungstenite::accept_async(stream)
+            .await
+            .map_err(io_error)?;
+
+        Ok(Self {
+            inner,
+            buf: Vec::new(),
+            pos: 0,
+        })
+    }
+}
+
+impl AsyncRead for WebSocket {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut ReadBuf,
+    ) -> Poll<Result<()>> {
+        while self.pos >= self.buf.len() {
+            match ready!(Pin::new(&mut self.inner).poll_next(cx)) {
+                Some(Ok(Message::Binary(data))) => {
+                    self.buf = data;
+                    self.pos = 0;
+                }
+
+                Some(Ok(Message::Close(_))) | None => return Poll::Ready(Ok(())),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Poll::Ready(Err(io_error(e))),
+            }
+        }
+
+        let len = buf.remaining().min(self.buf.len() - self.pos);
+        buf.put_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for WebSocket {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<Result<usize>> {
+        ready!(Pin::new(&mut self.inner).poll_ready(cx)).map_err(io_error)?;
+
+        Pin::new(&mut self.inner)
+            .start_send(Message::Binary(buf.to_vec()))
+            .map_err(io_error)?;
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx).map_err(io_error)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner).poll_close(cx).map_err(io_error)
+    }
+}
+
+fn io_error(e: tungstenite::Error) -> Error {
+    match e {
+        tungstenite::Error::Io(e) => e,
+        e => Error::new(ErrorKind::Other, e),
+    }
+}
@@ -0,0 +1,402 @@
+//! Aldrin code generation from Cargo build scripts.
+//!
+//! This crate is an alternative to the `generate!` macro of the `aldrin` crate. Instead of
+//! generating code during macro expansion, code is generated by a build script and written into
+//! Cargo's `OUT_DIR`. All schemas, including imported ones, lockfiles and patches are reported to
+//! Cargo with `cargo:rerun-if-changed`, such that the code is regenerated whenever any of them
+//! change.
+//!
+//! Add `aldrin-build` to the `[build-dependencies]` of your `Cargo.toml` and call it from
+//! `build.rs`:
+//!
+//! ```no_run
+//! aldrin_build::compile("schemas/example.aldrin")
+//!     .with_includes(["schemas/common"])
+//!     .run()
+//!     .unwrap();
+//! ```
+//!
+//! For every schema, a file named after the schema is written into `OUT_DIR`. It contains a single
+//! module of the same name, just like the one `generate!` would create:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/example.rs"));
+//!
+//! fn main() {
+//!     let _ = example::MyStruct::builder().build();
+//! }
+//! ```
+//!
+//! Errors in schemas are printed to stderr, which Cargo shows when the build script fails.
+//! Warnings are forwarded as `cargo:warning`.
+
+#![deny(missing_debug_implementations)]
+#![deny(missing_docs)]
+
+use aldrin_codegen::{Generator, Options, RustOptions};
+use aldrin_parser::resolver::Lockfile;
+use aldrin_parser::{Diagnostic, Parsed, Parser};
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io::Error as IoError;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Creates a new [`Builder`] for the schema `schema`.
+///
+/// This is a shorthand for [`Builder::new`].
+pub fn compile(schema: impl Into<PathBuf>) -> Builder {
+    Builder::new(schema)
+}
+
+/// Generates Rust code from one or more schemas.
+///
+/// All options correspond to those of the `generate!` macro and have the same defaults.
+#[derive(Debug, Clone)]
+#[must_use = "builders do nothing unless `run` is invoked"]
+pub struct Builder {
+    schemas: Vec<PathBuf>,
+    includes: Vec<PathBuf>,
+    lockfiles: Vec<PathBuf>,
+    options: Options,
+    warnings_as_errors: bool,
+    patches: Vec<PathBuf>,
+    struct_builders: bool,
+    struct_non_exhaustive: bool,
+    enum_non_exhaustive: bool,
+    event_non_exhaustive: bool,
+    function_non_exhaustive: bool,
+    introspection_if: Option<String>,
+    mocks: bool,
+    serde: bool,
+    krate: Option<String>,
+    out_dir: Option<PathBuf>,
+}
+
+impl Builder {
+    /// Creates a new [`Builder`] for the schema `schema`.
+    ///
+    /// Relative paths are interpreted relative to the current working directory, which is the
+    /// package's root directory when running build scripts.
+    pub fn new(schema: impl Into<PathBuf>) -> Self {
+        Self {
+            schemas: vec![schema.into()],
+            includes: Vec::new(),
+            lockfiles: Vec::new(),
+            options: Options::default(),
+            warnings_as_errors: false,
+            patches: Vec::new(),
+            struct_builders: true,
+            struct_non_exhaustive: true,
+            enum_non_exhaustive: true,
+            event_non_exhaustive: true,
+            function_non_exhaustive: true,
+            introspection_if: None,
+            mocks: false,
+            serde: false,
+            krate: None,
+            out_dir: None,
+        }
+    }
+
+    /// Adds another schema.
+    ///
+    /// Patches cannot be combined with multiple schemas.
+    pub fn schema(mut self, schema: impl Into<PathBuf>) -> Self {
+        self.schemas.push(schema.into());
+        self
+    }
+
+    /// Adds an include directory, in which imported schemas are searched.
+    pub fn include(mut self, include: impl Into<PathBuf>) -> Self {
+        self.includes.push(include.into());
+        self
+    }
+
+    /// Adds multiple include directories, in which imported schemas are searched.
+    pub fn with_includes<I>(mut self, includes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<PathBuf>,
+    {
+        self.includes.extend(includes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a lockfile, whose resolvers are used to locate imported schemas.
+    pub fn lockfile(mut self, lockfile: impl Into<PathBuf>) -> Self {
+        self.lockfiles.push(lockfile.into());
+        self
+    }
+
+    /// Sets whether client code is generated.
+    pub fn client(mut self, client: bool) -> Self {
+        self.options.client = client;
+        self
+    }
+
+    /// Sets whether server code is generated.
+    pub fn server(mut self, server: bool) -> Self {
+        self.options.server = server;
+        self
+    }
+
+    /// Sets whether warnings are treated as errors.
+    pub fn warnings_as_errors(mut self, warnings_as_errors: bool) -> Self {
+        self.warnings_as_errors = warnings_as_errors;
+        self
+    }
+
+    /// Adds a patch, which is applied to the generated code.
+    pub fn patch(mut self, patch: impl Into<PathBuf>) -> Self {
+        self.patches.push(patch.into());
+        self
+    }
+
+    /// Sets whether builders are generated for structs.
+    pub fn struct_builders(mut self, struct_builders: bool) -> Self {
+        self.struct_builders = struct_builders;
+        self
+    }
+
+    /// Sets whether structs are marked `#[non_exhaustive]`.
+    pub fn struct_non_exhaustive(mut self, struct_non_exhaustive: bool) -> Self {
+        self.struct_non_exhaustive = struct_non_exhaustive;
+        self
+    }
+
+    /// Sets whether enums are marked `#[non_exhaustive]`.
+    pub fn enum_non_exhaustive(mut self, enum_non_exhaustive: bool) -> Self {
+        self.enum_non_exhaustive = enum_non_exhaustive;
+        self
+    }
+
+    /// Sets whether event enums of services are marked `#[non_exhaustive]`.
+    pub fn event_non_exhaustive(mut self, event_non_exhaustive: bool) -> Self {
+        self.event_non_exhaustive = event_non_exhaustive;
+        self
+    }
+
+    /// Sets whether function enums of services are marked `#[non_exhaustive]`.
+    pub fn function_non_exhaustive(mut self, function_non_exhaustive: bool) -> Self {
+        self.function_non_exhaustive = function_non_exhaustive;
+        self
+    }
+
+    /// Sets whether introspection code is generated.
+    pub fn introspection(mut self, introspection: bool) -> Self {
+        self.options.introspection = introspection;
+        self
+    }
+
+    /// Generates introspection code, conditional on the Cargo feature `feature`.
+    pub fn introspection_if(mut self, feature: impl Into<String>) -> Self {
+        self.introspection_if = Some(feature.into());
+        self.options.introspection = true;
+        self
+    }
+
+    /// Sets whether mocks are generated.
+    pub fn mocks(mut self, mocks: bool) -> Self {
+        self.mocks = mocks;
+        self
+    }
+
+    /// Sets whether serde traits are derived.
+    pub fn serde(mut self, serde: bool) -> Self {
+        self.serde = serde;
+        self
+    }
+
+    /// Overrides the path of the `aldrin` crate.
+    ///
+    /// Path resolution starts inside the generated module.
+    pub fn krate(mut self, krate: impl Into<String>) -> Self {
+        self.krate = Some(krate.into());
+        self
+    }
+
+    /// Overrides the output directory.
+    ///
+    /// Per default, the environment variable `OUT_DIR` is used.
+    pub fn out_dir(mut self, out_dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(out_dir.into());
+        self
+    }
+
+    /// Generates code for all schemas and writes it into the output directory.
+    ///
+    /// Returns the paths of all generated files.
+    pub fn run(self) -> Result<Vec<PathBuf>, Error> {
+        if (self.schemas.len() > 1) && !self.patches.is_empty() {
+            return Err(Error::PatchesWithMultipleSchemas);
+        }
+
+        let out_dir = match self.out_dir {
+            Some(ref out_dir) => out_dir.clone(),
+            None => env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or(Error::OutDirNotSet)?,
+        };
+
+        let mut parser = Parser::new();
+
+        for lockfile in &self.lockfiles {
+            rerun_if_changed(lockfile);
+
+            let lockfile = Lockfile::load(lockfile).map_err(|e| Error::Lockfile {
+                path: lockfile.clone(),
+                source: e,
+            })?;
+
+            for resolver in lockfile.resolvers(None) {
+                parser.add_resolver(resolver);
+            }
+        }
+
+        for include in &self.includes {
+            parser.add_schema_path(include);
+        }
+
+        for patch in &self.patches {
+            rerun_if_changed(patch);
+        }
+
+        let mut rust_options = RustOptions::new();
+        rust_options.patches = self.patches.iter().map(PathBuf::as_path).collect();
+        rust_options.struct_builders = self.struct_builders;
+        rust_options.struct_non_exhaustive = self.struct_non_exhaustive;
+        rust_options.enum_non_exhaustive = self.enum_non_exhaustive;
+        rust_options.event_non_exhaustive = self.event_non_exhaustive;
+        rust_options.function_non_exhaustive = self.function_non_exhaustive;
+        rust_options.introspection_if = self.introspection_if.as_deref();
+        rust_options.mocks = self.mocks;
+        rust_options.serde = self.serde;
+
+        if let Some(ref krate) = self.krate {
+            rust_options.krate = krate;
+        }
+
+        let mut tracked = BTreeSet::new();
+        let mut outputs = Vec::with_capacity(self.schemas.len());
+
+        for schema in &self.schemas {
+            rerun_if_changed(schema);
+
+            let parsed = parser.parse(schema);
+
+            // Imported schemas must be tracked as well, even if there are errors. Otherwise, fixing
+            // an error in an imported schema wouldn't trigger a rerun.
+            for imported in parsed.schemas() {
+                if tracked.insert(imported.path().to_owned()) {
+                    rerun_if_changed(imported.path());
+                }
+            }
+
+            for error in parsed.errors() {
+                eprintln!("{}", format_diagnostic(error, &parsed));
+            }
+
+            for warning in parsed.warnings() {
+                let formatted = format_diagnostic(warning, &parsed);
+
+                if self.warnings_as_errors {
+                    eprintln!("{formatted}");
+                } else {
+                    for line in formatted.lines() {
+                        println!("cargo:warning={line}");
+                    }
+                }
+            }
+
+            if !parsed.errors().is_empty()
+                || (self.warnings_as_errors && !parsed.warnings().is_empty())
+            {
+                return Err(Error::InvalidSchema {
+                    path: schema.clone(),
+                });
+            }
+
+            let output = Generator::new(&self.options, &parsed).generate_rust(&rust_options)?;
+
+            let path = out_dir.join(format!("{}.rs", output.module_name));
+            let content = format!(
+                "pub mod r#{} {{\n{}}}\n",
+                output.module_name, output.module_content
+            );
+
+            fs::write(&path, content).map_err(|e| Error::Write {
+                path: path.clone(),
+                source: e,
+            })?;
+
+            outputs.push(path);
+        }
+
+        Ok(outputs)
+    }
+}
+
+/// Error of [`Builder::run`].
+#[derive(Error, Debug)]
+pub enum Error {
+    /// The environment variable `OUT_DIR` is not set.
+    ///
+    /// This usually means, that [`Builder::run`] was called outside of a build script. Use
+    /// [`Builder::out_dir`] in that case.
+    #[error("the environment variable `OUT_DIR` is not set")]
+    OutDirNotSet,
+
+    /// Patches were combined with multiple schemas.
+    #[error("patches cannot be applied to multiple schemas")]
+    PatchesWithMultipleSchemas,
+
+    /// A lockfile failed to load.
+    #[error("failed to load lockfile `{}`", path.display())]
+    Lockfile {
+        /// Path of the lockfile.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: IoError,
+    },
+
+    /// A schema contains errors.
+    ///
+    /// The diagnostics have been printed to stderr.
+    #[error("schema `{}` contains errors", path.display())]
+    InvalidSchema {
+        /// Path of the schema.
+        path: PathBuf,
+    },
+
+    /// Code generation failed.
+    #[error(transparent)]
+    Codegen(#[from] aldrin_codegen::Error),
+
+    /// The generated code could not be written.
+    #[error("failed to write `{}`", path.display())]
+    Write {
+        /// Path of the output file.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: IoError,
+    },
+}
+
+fn rerun_if_changed(path: &Path) {
+    println!("cargo:rerun-if-changed={}", path.display());
+}
+
+fn format_diagnostic(diag: &impl Diagnostic, parsed: &Parsed) -> String {
+    let formatted = diag.format(parsed);
+
+    let mut msg = format!("{}\n", formatted.summary());
+    for line in formatted.lines().skip(1) {
+        msg.push_str(&line.to_string());
+    }
+
+    msg
+}
@@ -0,0 +1,123 @@
+//! Rust side of the tests, that run generated Python and TypeScript code against a broker.
+
+use aldrin::core::{
+    Bytes, ObjectCookie, ObjectId, ObjectUuid, SerializedValue, ServiceCookie, ServiceId,
+    ServiceUuid,
+};
+use aldrin::Handle;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use uuid::{uuid, Uuid};
+
+pub(crate) use interop::{
+    Interop, InteropEvent, InteropFunction, InteropProxy, Point, Record, Shape,
+};
+
+aldrin::generate!("test/interop.aldrin");
+
+const RECORD_ID: Uuid = uuid!("5c368dc9-e6d3-4545-86d1-435fe3e771cc");
+pub(crate) const OBJECT_UUID: ObjectUuid =
+    ObjectUuid(uuid!("1f6c4e7a-3b2d-4c8e-9a5f-0d7e6b4c3a21"));
+
+// Must match `record()` in `test_interop.py` and `test_interop.mjs`.
+pub(crate) fn record() -> Record {
+    let obj = ObjectId::new(
+        ObjectUuid(uuid!("b5d1e8a7-8c3f-4e62-9f1a-2d4c6b8e0a13")),
+        ObjectCookie(uuid!("c6e2f9b8-9d40-4f73-a02b-3e5d7c9f1b24")),
+    );
+
+    Record {
+        name: "record".to_owned(),
+        id: RECORD_ID,
+        flag: Some(true),
+        small: Some(200),
+        signed: Some(-5),
+        short: Some(-300),
+        count: Some(u64::MAX),
+        delta: Some(i64::MIN),
+        ratio: Some(1.5),
+        precise: Some(-2.25),
+        data: Some(Bytes::new([0, 1, 255])),
+        tags: Some(vec!["x".to_owned(), "y".to_owned()]),
+        scores: Some(HashMap::from([("a".to_owned(), 1)])),
+        keys: Some(HashSet::from([-7])),
+        shape: Some(Shape::Polygon(vec![
+            Point { x: 1, y: -1 },
+            Point {
+                x: i32::MAX,
+                y: i32::MIN,
+            },
+        ])),
+        origin: Some(Point { x: 0, y: 0 }),
+        maybe: Some(Some(3)),
+        outcome: Some(Err("bad".to_owned())),
+        pair: Some([-1, 1]),
+        obj: Some(obj),
+        svc: Some(ServiceId::new(
+            obj,
+            ServiceUuid(uuid!("d7f30ac9-ae51-4084-b13c-4f6e8d0a2c35")),
+            ServiceCookie(uuid!("e8041bda-bf62-4195-824d-507f9e1b3d46")),
+        )),
+        any: Some(SerializedValue::serialize("hi").unwrap()),
+        nested: Some(Box::new(
+            Record::builder()
+                .name("inner".to_owned())
+                .id(RECORD_ID)
+                .build()
+                .unwrap(),
+        )),
+    }
+}
+
+/// Returns `record()` in serialized form as a hex string.
+pub(crate) fn record_hex() -> String {
+    SerializedValue::serialize(&record())
+        .unwrap()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Creates a new directory for the files of a test.
+pub(crate) fn test_dir(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aldrin-codegen-{test}-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Provides the `Interop` service on a new object.
+///
+/// The `level` property starts at 1. `ping` emits the `changed` event and sets `level` to 2 before
+/// replying.
+pub(crate) async fn spawn_service(client: &Handle) {
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = Interop::new(&obj).await.unwrap();
+    svc.set_level(1).unwrap();
+
+    tokio::spawn(async move {
+        // Keep the object alive as long as the service.
+        let _obj = obj;
+
+        while let Some(Ok(call)) = svc.next_call().await {
+            match call {
+                InteropFunction::Echo(args, promise) => promise.ok(&args).unwrap(),
+
+                InteropFunction::Area(Shape::Circle(r), promise) => {
+                    promise.ok(3 * u64::from(r) * u64::from(r)).unwrap()
+                }
+
+                InteropFunction::Area(_, promise) => promise.err("empty").unwrap(),
+
+                InteropFunction::Ping(promise) => {
+                    svc.changed(&Point { x: 1, y: 2 }).unwrap();
+                    svc.set_level(2).unwrap();
+                    promise.done().unwrap();
+                }
+            }
+        }
+    });
+}
@@ -1,15 +1,25 @@
 #![deny(missing_debug_implementations)]
 
+#[cfg(all(test, any(feature = "python", feature = "typescript")))]
+mod interop_test;
+#[cfg(feature = "python")]
+mod python;
 #[cfg(feature = "rust")]
 mod rust;
+#[cfg(feature = "typescript")]
+mod typescript;
 
 pub mod error;
 
 use aldrin_parser::Parsed;
 
 pub use error::Error;
+#[cfg(feature = "python")]
+pub use python::{PythonOptions, PythonOutput};
 #[cfg(feature = "rust")]
 pub use rust::{RustOptions, RustOutput};
+#[cfg(feature = "typescript")]
+pub use typescript::{TypeScriptOptions, TypeScriptOutput};
 
 #[derive(Debug)]
 pub struct Generator<'a> {
@@ -33,7 +43,44 @@ impl<'a> Generator<'a> {
 
     #[cfg(feature = "rust")]
     pub fn generate_rust(&self, rust_options: &RustOptions) -> Result<RustOutput, Error> {
-        rust::generate(self.parsed, self.options, rust_options)
+        rust::generate(
+            self.parsed,
+            self.parsed.main_schema(),
+            self.options,
+            rust_options,
+        )
+    }
+
+    /// Generates Rust code for one of the imported schemas.
+    ///
+    /// Types from other schemas are referred to as `super::{schema}::{type}`. Generating all
+    /// schemas as sibling modules thus defines every type exactly once.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `schema_name` is neither the main schema nor one of the imported
+    /// schemas.
+    #[cfg(feature = "rust")]
+    pub fn generate_rust_schema(
+        &self,
+        schema_name: &str,
+        rust_options: &RustOptions,
+    ) -> Result<RustOutput, Error> {
+        let schema = self.parsed.get_schema(schema_name).unwrap();
+        rust::generate(self.parsed, schema, self.options, rust_options)
+    }
+
+    #[cfg(feature = "python")]
+    pub fn generate_python(&self, python_options: &PythonOptions) -> Result<PythonOutput, Error> {
+        python::generate(self.parsed, self.options, python_options)
+    }
+
+    #[cfg(feature = "typescript")]
+    pub fn generate_typescript(
+        &self,
+        ts_options: &TypeScriptOptions,
+    ) -> Result<TypeScriptOutput, Error> {
+        typescript::generate(self.parsed, self.options, ts_options)
     }
 }
 
@@ -0,0 +1,1130 @@
+#[cfg(test)]
+mod test;
+
+use crate::error::Error;
+use crate::Options;
+use aldrin_parser::{ast, Parsed, Schema};
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use std::fmt::Write;
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PythonOptions<'a> {
+    pub package: &'a str,
+}
+
+impl PythonOptions<'_> {
+    pub fn new() -> Self {
+        PythonOptions { package: "aldrin" }
+    }
+}
+
+impl Default for PythonOptions<'_> {
+    fn default() -> Self {
+        PythonOptions::new()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PythonOutput {
+    pub module_name: String,
+    pub module_content: String,
+}
+
+pub(crate) fn generate(
+    parsed: &Parsed,
+    options: &Options,
+    python_options: &PythonOptions,
+) -> Result<PythonOutput, Error> {
+    let schema = parsed.main_schema();
+
+    let generator = PythonGenerator {
+        parsed,
+        schema,
+        options,
+        python_options,
+        output: PythonOutput {
+            module_name: schema.name().to_owned(),
+            module_content: String::new(),
+        },
+    };
+
+    generator.generate()
+}
+
+struct PythonGenerator<'a> {
+    parsed: &'a Parsed,
+    schema: &'a Schema,
+    options: &'a Options,
+    python_options: &'a PythonOptions<'a>,
+    output: PythonOutput,
+}
+
+macro_rules! code {
+    ($this:expr, $arg:literal) => {
+        write!($this.output.module_content, $arg).unwrap()
+    };
+}
+
+macro_rules! codeln {
+    ($this:expr) => {
+        writeln!($this.output.module_content).unwrap()
+    };
+
+    ($this:expr, $arg:literal) => {
+        writeln!($this.output.module_content, $arg).unwrap()
+    };
+}
+
+#[rustfmt::skip::macros(code, codeln)]
+impl PythonGenerator<'_> {
+    fn generate(mut self) -> Result<PythonOutput, Error> {
+        let pkg = self.python_options.package;
+        let schema_name = self.schema.name();
+        let has_services = self
+            .schema
+            .definitions()
+            .iter()
+            .any(|def| matches!(def, ast::Definition::Service(_)));
+
+        codeln!(self, "# Generated by aldrin-codegen from schema `{schema_name}`. Do not edit.");
+        codeln!(self);
+        codeln!(self, "from __future__ import annotations");
+        codeln!(self);
+
+        if has_services && self.options.server {
+            codeln!(self, "import abc");
+        }
+
+        codeln!(self, "import dataclasses");
+        codeln!(self, "import typing");
+        codeln!(self, "import uuid");
+        codeln!(self);
+        codeln!(self, "import {pkg}");
+
+        for import in self.schema.imports() {
+            let module = import.schema_name().value();
+            codeln!(self, "import {module}");
+        }
+
+        // Constants come first, because they may be used as array lengths in type descriptors,
+        // which are evaluated eagerly. Composite constants are generated as functions in
+        // definition().
+        for def in self.schema.definitions() {
+            if let ast::Definition::Const(c) = def {
+                if let ast::ConstValue::Typed(v) = c.value() {
+                    if !is_const_type(v.type_name()) {
+                        continue;
+                    }
+                }
+
+                codeln!(self);
+                codeln!(self);
+                self.const_def(c);
+            }
+        }
+
+        // Newtypes come next, because their type descriptors are referenced eagerly by structs,
+        // enums and services.
+        let mut newtypes = Vec::new();
+        for def in self.schema.definitions() {
+            if let ast::Definition::Newtype(n) = def {
+                self.newtype_def(n, &mut newtypes);
+            }
+        }
+
+        for def in self.schema.definitions() {
+            self.definition(def);
+        }
+
+        Ok(self.output)
+    }
+
+    fn definition(&mut self, def: &ast::Definition) {
+        match def {
+            ast::Definition::Struct(d) => self.struct_def(d.name().value(), d.fields()),
+            ast::Definition::Enum(e) => self.enum_def(e.name().value(), e.variants()),
+            ast::Definition::Service(s) => self.service_def(s),
+
+            ast::Definition::Const(c) => match c.value() {
+                ast::ConstValue::Typed(v) if !is_const_type(v.type_name()) => {
+                    self.const_fn(c.name().value(), v)
+                }
+
+                _ => {}
+            },
+
+            ast::Definition::Newtype(_) => {}
+        }
+    }
+
+    fn newtype_def(&mut self, newtype_def: &ast::NewtypeDef, done: &mut Vec<String>) {
+        let name = newtype_def.name().value();
+        if done.iter().any(|n| n == name) {
+            return;
+        }
+        done.push(name.to_owned());
+
+        // Descriptors of other newtypes of this schema must be defined first.
+        let schema = self.schema;
+        let mut deps = Vec::new();
+        newtype_deps(newtype_def.target_type(), &mut deps);
+        for dep in deps {
+            if let Some(dep) = schema
+                .definitions()
+                .iter()
+                .filter_map(ast::Definition::as_newtype)
+                .find(|d| d.name().value() == dep)
+            {
+                self.newtype_def(dep, done);
+            }
+        }
+
+        let newtype_ident = ident(name);
+        let hint = self.type_hint(newtype_def.target_type());
+        let ty = self.type_desc(newtype_def.target_type());
+        let type_name = newtype_type_name(name);
+
+        codeln!(self);
+        codeln!(self);
+        codeln!(self, "{newtype_ident} = typing.NewType(\"{name}\", {hint})");
+        codeln!(self, "{type_name}: typing.Final = {ty}");
+    }
+
+    fn struct_def(&mut self, name: &str, fields: &[ast::StructField]) {
+        let pkg = self.python_options.package;
+        let schema_name = self.schema.name();
+        let struct_ident = ident(name);
+
+        codeln!(self);
+        codeln!(self);
+        codeln!(self, "@{pkg}.struct(schema=\"{schema_name}\")");
+        codeln!(self, "@dataclasses.dataclass(kw_only=True)");
+        codeln!(self, "class {struct_ident}:");
+
+        if fields.is_empty() {
+            codeln!(self, "    pass");
+        }
+
+        for field in fields {
+            let id = field.id().value();
+            let field_ident = ident(field.name().value());
+            let hint = self.type_hint(field.field_type());
+            let ty = self.type_desc(field.field_type());
+
+            if field.required() {
+                codeln!(self, "    {field_ident}: {hint} = {pkg}.field({id}, {ty})");
+            } else {
+                codeln!(self, "    {field_ident}: typing.Optional[{hint}] = {pkg}.field({id}, {ty}, optional=True)");
+            }
+        }
+    }
+
+    fn enum_def(&mut self, name: &str, vars: &[ast::EnumVariant]) {
+        let pkg = self.python_options.package;
+        let schema_name = self.schema.name();
+        let enum_ident = ident(name);
+
+        codeln!(self);
+        codeln!(self);
+        codeln!(self, "@{pkg}.enum(schema=\"{schema_name}\")");
+        codeln!(self, "class {enum_ident}({pkg}.Enum):");
+
+        if vars.is_empty() {
+            codeln!(self, "    pass");
+        }
+
+        for var in vars {
+            let id = var.id().value();
+            let var_ident = ident(var.name().value());
+
+            if let Some(ty) = var.variant_type() {
+                let ty = self.type_desc(ty);
+                codeln!(self, "    {var_ident} = {pkg}.variant({id}, {ty})");
+            } else {
+                codeln!(self, "    {var_ident} = {pkg}.variant({id})");
+            }
+        }
+    }
+
+    fn service_def(&mut self, svc: &ast::ServiceDef) {
+        if !self.options.client && !self.options.server {
+            return;
+        }
+
+        let svc_name = svc.name().value();
+
+        for item in svc.items() {
+            match item {
+                ast::ServiceItem::Function(func) => {
+                    let func_name = func.name().value();
+
+                    if let Some(args) = func.args() {
+                        let name = function_part_name(svc_name, func_name, "Args");
+                        self.inline_type_def(&name, args.part_type());
+                    }
+
+                    if let Some(ok) = func.ok() {
+                        let name = function_part_name(svc_name, func_name, "Ok");
+                        self.inline_type_def(&name, ok.part_type());
+                    }
+
+                    if let Some(stream) = func.stream() {
+                        let name = function_part_name(svc_name, func_name, "Item");
+                        self.inline_type_def(&name, stream.part_type());
+                    }
+
+                    if let Some(err) = func.err() {
+                        let name = function_part_name(svc_name, func_name, "Error");
+                        self.inline_type_def(&name, err.part_type());
+                    }
+                }
+
+                ast::ServiceItem::Event(ev) => {
+                    if let Some(ty) = ev.event_type() {
+                        let name = event_type_name(svc_name, ev.name().value());
+                        self.inline_type_def(&name, ty);
+                    }
+                }
+
+                // Property types are always named.
+                ast::ServiceItem::Property(_) => {}
+            }
+        }
+
+        if self.options.client {
+            self.service_proxy(svc);
+        }
+
+        if self.options.server {
+            self.service_server(svc);
+            self.service_handler(svc);
+        }
+    }
+
+    fn inline_type_def(&mut self, name: &str, ty: &ast::TypeNameOrInline) {
+        match ty {
+            ast::TypeNameOrInline::Struct(s) => self.struct_def(name, s.fields()),
+            ast::TypeNameOrInline::Enum(e) => self.enum_def(name, e.variants()),
+            ast::TypeNameOrInline::TypeName(_) => {}
+        }
+    }
+
+    fn service_header(&mut self, svc: &ast::ServiceDef, class_name: &str, base: &str) {
+        let pkg = self.python_options.package;
+        let svc_name = svc.name().value();
+        let class_ident = ident(class_name);
+        let uuid = svc.uuid().value();
+        let version = svc.version().value();
+
+        codeln!(self, "class {class_ident}({pkg}.{base}):");
+        codeln!(self, "    UUID: typing.Final = uuid.UUID(\"{uuid}\")");
+        codeln!(self, "    VERSION: typing.Final = {version}");
+        codeln!(self);
+
+        codeln!(self, "    FUNCTIONS: typing.Final = {{");
+        for item in svc.items() {
+            let ast::ServiceItem::Function(func) = item else {
+                continue;
+            };
+
+            let func_name = func.name().value();
+            let id = func.id().value();
+
+            code!(self, "        {id}: {pkg}.function(\"{func_name}\"");
+
+            if let Some(args) = func.args() {
+                let ty = self.part_type_desc(svc_name, func_name, "Args", args);
+                code!(self, ", args={ty}");
+            }
+
+            if let Some(ok) = func.ok() {
+                let ty = self.part_type_desc(svc_name, func_name, "Ok", ok);
+                code!(self, ", ok={ty}");
+            }
+
+            if let Some(stream) = func.stream() {
+                let ty = self.part_type_desc(svc_name, func_name, "Item", stream);
+                code!(self, ", stream={ty}");
+            }
+
+            if let Some(err) = func.err() {
+                let ty = self.part_type_desc(svc_name, func_name, "Error", err);
+                code!(self, ", err={ty}");
+            }
+
+            codeln!(self, "),");
+        }
+        codeln!(self, "    }}");
+        codeln!(self);
+
+        codeln!(self, "    EVENTS: typing.Final = {{");
+        for item in svc.items() {
+            let ast::ServiceItem::Event(ev) = item else {
+                continue;
+            };
+
+            let ev_name = ev.name().value();
+            let id = ev.id().value();
+
+            if let Some(ty) = ev.event_type() {
+                let ty = self.event_type_desc(svc_name, ev_name, ty);
+                codeln!(self, "        {id}: {pkg}.event(\"{ev_name}\", {ty}),");
+            } else {
+                codeln!(self, "        {id}: {pkg}.event(\"{ev_name}\"),");
+            }
+        }
+        codeln!(self, "    }}");
+        codeln!(self);
+
+        codeln!(self, "    PROPERTIES: typing.Final = {{");
+        for item in svc.items() {
+            let ast::ServiceItem::Property(prop) = item else {
+                continue;
+            };
+
+            let prop_name = prop.name().value();
+            let id = prop.id().value();
+            let ty = self.type_desc(prop.property_type());
+            codeln!(self, "        {id}: {pkg}.prop(\"{prop_name}\", {ty}),");
+        }
+        codeln!(self, "    }}");
+    }
+
+    fn service_proxy(&mut self, svc: &ast::ServiceDef) {
+        let pkg = self.python_options.package;
+        let svc_name = svc.name().value();
+
+        codeln!(self);
+        codeln!(self);
+        self.service_header(svc, &format!("{svc_name}Proxy"), "Proxy");
+
+        for item in svc.items() {
+            codeln!(self);
+
+            match item {
+                ast::ServiceItem::Function(func) => {
+                    let func_name = func.name().value();
+                    let func_ident = ident(func_name);
+                    let id = func.id().value();
+
+                    let ret = match (func.ok(), func.stream()) {
+                        (Some(ok), _) => self.part_type_hint(svc_name, func_name, "Ok", ok),
+
+                        (None, Some(stream)) => {
+                            let item = self.part_type_hint(svc_name, func_name, "Item", stream);
+                            format!("{pkg}.Receiver[{item}]")
+                        }
+
+                        (None, None) => "None".to_owned(),
+                    };
+
+                    if let Some(args) = func.args() {
+                        let hint = self.part_type_hint(svc_name, func_name, "Args", args);
+                        codeln!(self, "    async def {func_ident}(self, args: {hint}) -> {ret}:");
+                        codeln!(self, "        return await self._call({id}, args)");
+                    } else {
+                        codeln!(self, "    async def {func_ident}(self) -> {ret}:");
+                        codeln!(self, "        return await self._call({id}, None)");
+                    }
+                }
+
+                ast::ServiceItem::Event(ev) => {
+                    let ev_name = ev.name().value();
+                    let id = ev.id().value();
+
+                    codeln!(self, "    async def subscribe_{ev_name}(self) -> None:");
+                    codeln!(self, "        await self._subscribe({id})");
+                    codeln!(self);
+                    codeln!(self, "    async def unsubscribe_{ev_name}(self) -> None:");
+                    codeln!(self, "        await self._unsubscribe({id})");
+                }
+
+                ast::ServiceItem::Property(prop) => {
+                    let prop_name = prop.name().value();
+                    let prop_ident = ident(prop_name);
+                    let id = prop.id().value();
+                    let hint = self.type_hint(prop.property_type());
+
+                    codeln!(self, "    def {prop_ident}(self) -> typing.Optional[{hint}]:");
+                    codeln!(self, "        return self._property({id})");
+                    codeln!(self);
+                    codeln!(self, "    async def subscribe_{prop_name}(self) -> None:");
+                    codeln!(self, "        await self._subscribe_property({id})");
+                    codeln!(self);
+                    codeln!(self, "    async def unsubscribe_{prop_name}(self) -> None:");
+                    codeln!(self, "        await self._unsubscribe({id})");
+                }
+            }
+        }
+    }
+
+    fn service_server(&mut self, svc: &ast::ServiceDef) {
+        let svc_name = svc.name().value();
+
+        codeln!(self);
+        codeln!(self);
+        self.service_header(svc, svc_name, "Service");
+
+        for item in svc.items() {
+            match item {
+                ast::ServiceItem::Function(_) => {}
+
+                ast::ServiceItem::Event(ev) => {
+                    let ev_name = ev.name().value();
+                    let id = ev.id().value();
+
+                    codeln!(self);
+
+                    if let Some(ty) = ev.event_type() {
+                        let hint = self.event_type_hint(svc_name, ev_name, ty);
+                        codeln!(self, "    def emit_{ev_name}(self, value: {hint}) -> None:");
+                        codeln!(self, "        self._emit({id}, value)");
+                    } else {
+                        codeln!(self, "    def emit_{ev_name}(self) -> None:");
+                        codeln!(self, "        self._emit({id}, None)");
+                    }
+                }
+
+                ast::ServiceItem::Property(prop) => {
+                    let prop_name = prop.name().value();
+                    let prop_ident = ident(prop_name);
+                    let id = prop.id().value();
+                    let hint = self.type_hint(prop.property_type());
+
+                    codeln!(self);
+                    codeln!(self, "    def {prop_ident}(self) -> typing.Optional[{hint}]:");
+                    codeln!(self, "        return self._property({id})");
+                    codeln!(self);
+                    codeln!(self, "    def set_{prop_name}(self, value: {hint}) -> None:");
+                    codeln!(self, "        self._set_property({id}, value)");
+                    codeln!(self);
+                    codeln!(self, "    def clear_{prop_name}(self) -> None:");
+                    codeln!(self, "        self._set_property({id}, None)");
+                }
+            }
+        }
+    }
+
+    fn service_handler(&mut self, svc: &ast::ServiceDef) {
+        let pkg = self.python_options.package;
+        let svc_name = svc.name().value();
+        let handler_ident = ident(&format!("{svc_name}Handler"));
+
+        codeln!(self);
+        codeln!(self);
+        codeln!(self, "class {handler_ident}(abc.ABC):");
+
+        let mut empty = true;
+        for item in svc.items() {
+            let ast::ServiceItem::Function(func) = item else {
+                continue;
+            };
+
+            let func_name = func.name().value();
+            let func_ident = ident(func_name);
+
+            if empty {
+                empty = false;
+            } else {
+                codeln!(self);
+            }
+
+            let args = match func.args() {
+                Some(args) => {
+                    let hint = self.part_type_hint(svc_name, func_name, "Args", args);
+                    format!(", args: {hint}")
+                }
+
+                None => String::new(),
+            };
+
+            let ret = match (func.ok(), func.stream()) {
+                (Some(ok), _) => self.part_type_hint(svc_name, func_name, "Ok", ok),
+
+                (None, Some(stream)) => {
+                    let item = self.part_type_hint(svc_name, func_name, "Item", stream);
+                    format!("typing.AsyncIterator[{item}]")
+                }
+
+                (None, None) => "None".to_owned(),
+            };
+
+            codeln!(self, "    @abc.abstractmethod");
+            codeln!(self, "    async def {func_ident}(self, call: {pkg}.Call{args}) -> {ret}:");
+
+            if let Some(err) = func.err() {
+                let err = self.part_type_hint(svc_name, func_name, "Error", err);
+                codeln!(self, "        \"\"\"Raise {pkg}.CallError with a value of type `{err}` to reply with an error.\"\"\"");
+            }
+
+            codeln!(self, "        ...");
+        }
+
+        if empty {
+            codeln!(self, "    pass");
+        }
+    }
+
+    fn const_def(&mut self, const_def: &ast::ConstDef) {
+        let name = ident(const_def.name().value());
+
+        let val = match const_def.value() {
+            ast::ConstValue::U8(v) => v.value().to_owned(),
+            ast::ConstValue::I8(v) => v.value().to_owned(),
+            ast::ConstValue::U16(v) => v.value().to_owned(),
+            ast::ConstValue::I16(v) => v.value().to_owned(),
+            ast::ConstValue::U32(v) => v.value().to_owned(),
+            ast::ConstValue::I32(v) => v.value().to_owned(),
+            ast::ConstValue::U64(v) => v.value().to_owned(),
+            ast::ConstValue::I64(v) => v.value().to_owned(),
+            ast::ConstValue::String(v) => format!("\"{}\"", v.value()),
+            ast::ConstValue::Uuid(v) => format!("uuid.UUID(\"{}\")", v.value()),
+            ast::ConstValue::Typed(v) => self.const_expr(self.schema, v.type_name(), v.expr()),
+        };
+
+        codeln!(self, "{name}: typing.Final = {val}");
+    }
+
+    fn const_fn(&mut self, name: &str, value: &ast::TypedConst) {
+        let fn_ident = ident(&name.to_snake_case());
+        let hint = self.type_hint(value.type_name());
+        let val = self.const_expr(self.schema, value.type_name(), value.expr());
+
+        codeln!(self);
+        codeln!(self);
+        codeln!(self, "def {fn_ident}() -> {hint}:");
+        codeln!(self, "    return {val}");
+    }
+
+    /// Returns a Python expression for a constant of type `ty`, which is defined in `schema`.
+    fn const_expr(&self, schema: &Schema, ty: &ast::TypeName, expr: &ast::ConstExpr) -> String {
+        let pkg = self.python_options.package;
+
+        match (ty.kind(), expr.kind()) {
+            (ast::TypeNameKind::Ref(named_ref), _) if self.is_newtype(schema, named_ref) => {
+                self.newtype_const_expr(schema, named_ref, expr)
+            }
+
+            (ast::TypeNameKind::F32 | ast::TypeNameKind::F64, ast::ConstExprKind::Int(lit)) => {
+                format!("{}.0", lit.value())
+            }
+
+            (_, ast::ConstExprKind::Int(lit)) => lit.value().to_owned(),
+            (_, ast::ConstExprKind::Float(lit)) => lit.value().to_owned(),
+            (_, ast::ConstExprKind::String(lit)) => format!("\"{}\"", lit.value()),
+            (_, ast::ConstExprKind::Uuid(lit)) => format!("uuid.UUID(\"{}\")", lit.value()),
+
+            (ast::TypeNameKind::Bool, ast::ConstExprKind::Ident(ident)) => {
+                if ident.value() == "true" {
+                    "True".to_owned()
+                } else {
+                    "False".to_owned()
+                }
+            }
+
+            (ast::TypeNameKind::Option(_), ast::ConstExprKind::Ident(ident))
+                if ident.value() == "none" =>
+            {
+                "None".to_owned()
+            }
+
+            (ast::TypeNameKind::Option(ty) | ast::TypeNameKind::Box(ty), _) => {
+                self.const_expr(schema, ty, expr)
+            }
+
+            (ast::TypeNameKind::Bytes, ast::ConstExprKind::List(elems)) => {
+                let elems = elems.iter().map(|e| self.const_expr(schema, ty, e));
+                format!("bytes([{}])", join(elems))
+            }
+
+            (ast::TypeNameKind::Vec(ty), ast::ConstExprKind::List(elems)) => {
+                let elems = join(elems.iter().map(|e| self.const_expr(schema, ty, e)));
+
+                match ty.kind() {
+                    ast::TypeNameKind::U8 => format!("bytes([{elems}])"),
+                    _ => format!("[{elems}]"),
+                }
+            }
+
+            (ast::TypeNameKind::Array(ty, _), ast::ConstExprKind::List(elems)) => {
+                let elems = elems.iter().map(|e| self.const_expr(schema, ty, e));
+                format!("[{}]", join(elems))
+            }
+
+            (ast::TypeNameKind::Set(key), ast::ConstExprKind::List(elems)) => {
+                if elems.is_empty() {
+                    "set()".to_owned()
+                } else {
+                    let elems = elems.iter().map(|e| self.key_const_expr(schema, key, e));
+                    format!("{{{}}}", join(elems))
+                }
+            }
+
+            (ast::TypeNameKind::Map(key, ty), ast::ConstExprKind::Map(entries)) => {
+                let entries = entries.iter().map(|entry| {
+                    let key = self.key_const_expr(schema, key, entry.key());
+                    let value = self.const_expr(schema, ty, entry.value());
+                    format!("{key}: {value}")
+                });
+
+                format!("{{{}}}", join(entries))
+            }
+
+            (ast::TypeNameKind::Map(_, _), ast::ConstExprKind::Struct(_)) => "{}".to_owned(),
+
+            (ast::TypeNameKind::Result(ok, err), ast::ConstExprKind::Variant(name, value)) => {
+                if name.value() == "ok" {
+                    format!("{pkg}.Ok({})", self.const_expr(schema, ok, value))
+                } else {
+                    format!("{pkg}.Err({})", self.const_expr(schema, err, value))
+                }
+            }
+
+            (ast::TypeNameKind::Ref(named_ref), _) => {
+                let (def_schema, def) = self.resolve(schema, named_ref);
+                let path = self.const_type_path(schema, named_ref);
+
+                match (def, expr.kind()) {
+                    (ast::Definition::Struct(struct_def), ast::ConstExprKind::Struct(fields)) => {
+                        let fields = fields.iter().map(|field| {
+                            let field_ty = struct_def
+                                .fields()
+                                .iter()
+                                .find(|f| f.name().value() == field.name().value())
+                                .unwrap()
+                                .field_type();
+
+                            let field_ident = ident(field.name().value());
+                            let value = self.const_expr(def_schema, field_ty, field.value());
+                            format!("{field_ident}={value}")
+                        });
+
+                        format!("{path}({})", join(fields))
+                    }
+
+                    (ast::Definition::Enum(_), ast::ConstExprKind::Ident(var)) => {
+                        format!("{path}.{}", ident(var.value()))
+                    }
+
+                    (ast::Definition::Enum(enum_def), ast::ConstExprKind::Variant(var, value)) => {
+                        let var_ty = enum_def
+                            .variants()
+                            .iter()
+                            .find(|v| v.name().value() == var.value())
+                            .and_then(ast::EnumVariant::variant_type)
+                            .unwrap();
+
+                        let value = self.const_expr(def_schema, var_ty, value);
+                        format!("{path}.{}({value})", ident(var.value()))
+                    }
+
+                    _ => unreachable!(),
+                }
+            }
+
+            _ => unreachable!(),
+        }
+    }
+
+    fn key_const_expr(
+        &self,
+        schema: &Schema,
+        key: &ast::KeyTypeName,
+        expr: &ast::ConstExpr,
+    ) -> String {
+        match (key.kind(), expr.kind()) {
+            (ast::KeyTypeNameKind::Ref(named_ref), _) => {
+                self.newtype_const_expr(schema, named_ref, expr)
+            }
+
+            (_, ast::ConstExprKind::Int(lit)) => lit.value().to_owned(),
+            (_, ast::ConstExprKind::String(lit)) => format!("\"{}\"", lit.value()),
+            (_, ast::ConstExprKind::Uuid(lit)) => format!("uuid.UUID(\"{}\")", lit.value()),
+            _ => unreachable!(),
+        }
+    }
+
+    fn newtype_const_expr(
+        &self,
+        schema: &Schema,
+        named_ref: &ast::NamedRef,
+        expr: &ast::ConstExpr,
+    ) -> String {
+        let (def_schema, def) = self.resolve(schema, named_ref);
+        let path = self.const_type_path(schema, named_ref);
+
+        let ast::Definition::Newtype(newtype_def) = def else {
+            unreachable!();
+        };
+
+        let value = self.const_expr(def_schema, newtype_def.target_type(), expr);
+        format!("{path}({value})")
+    }
+
+    fn is_newtype(&self, schema: &Schema, named_ref: &ast::NamedRef) -> bool {
+        let (_, def) = self.resolve(schema, named_ref);
+        matches!(def, ast::Definition::Newtype(_))
+    }
+
+    fn resolve<'b>(
+        &'b self,
+        schema: &'b Schema,
+        named_ref: &ast::NamedRef,
+    ) -> (&'b Schema, &'b ast::Definition) {
+        let schema = match named_ref.kind() {
+            ast::NamedRefKind::Intern(_) => schema,
+            ast::NamedRefKind::Extern(m, _) => self.parsed.get_schema(m.value()).unwrap(),
+        };
+
+        let def = schema
+            .definitions()
+            .iter()
+            .find(|def| def.name().value() == named_ref.ident().value())
+            .unwrap();
+
+        (schema, def)
+    }
+
+    fn const_type_path(&self, schema: &Schema, named_ref: &ast::NamedRef) -> String {
+        match named_ref.kind() {
+            ast::NamedRefKind::Intern(ty) if schema.name() != self.schema.name() => {
+                format!("{}.{}", schema.name(), ident(ty.value()))
+            }
+
+            _ => self.named_ref_name(named_ref),
+        }
+    }
+
+    fn part_type_desc(
+        &self,
+        svc_name: &str,
+        func_name: &str,
+        suffix: &str,
+        part: &ast::FunctionPart,
+    ) -> String {
+        match part.part_type() {
+            ast::TypeNameOrInline::TypeName(ty) => self.type_desc(ty),
+
+            ast::TypeNameOrInline::Struct(_) | ast::TypeNameOrInline::Enum(_) => {
+                let pkg = self.python_options.package;
+                let name = ident(&function_part_name(svc_name, func_name, suffix));
+                format!("{pkg}.types.Ref(lambda: {name})")
+            }
+        }
+    }
+
+    fn part_type_hint(
+        &self,
+        svc_name: &str,
+        func_name: &str,
+        suffix: &str,
+        part: &ast::FunctionPart,
+    ) -> String {
+        match part.part_type() {
+            ast::TypeNameOrInline::TypeName(ty) => self.type_hint(ty),
+
+            ast::TypeNameOrInline::Struct(_) | ast::TypeNameOrInline::Enum(_) => {
+                ident(&function_part_name(svc_name, func_name, suffix))
+            }
+        }
+    }
+
+    fn event_type_desc(&self, svc_name: &str, ev_name: &str, ty: &ast::TypeNameOrInline) -> String {
+        match ty {
+            ast::TypeNameOrInline::TypeName(ty) => self.type_desc(ty),
+
+            ast::TypeNameOrInline::Struct(_) | ast::TypeNameOrInline::Enum(_) => {
+                let pkg = self.python_options.package;
+                let name = ident(&event_type_name(svc_name, ev_name));
+                format!("{pkg}.types.Ref(lambda: {name})")
+            }
+        }
+    }
+
+    fn event_type_hint(&self, svc_name: &str, ev_name: &str, ty: &ast::TypeNameOrInline) -> String {
+        match ty {
+            ast::TypeNameOrInline::TypeName(ty) => self.type_hint(ty),
+
+            ast::TypeNameOrInline::Struct(_) | ast::TypeNameOrInline::Enum(_) => {
+                ident(&event_type_name(svc_name, ev_name))
+            }
+        }
+    }
+
+    /// Returns an expression describing the wire format of a type.
+    fn type_desc(&self, ty: &ast::TypeName) -> String {
+        let pkg = self.python_options.package;
+
+        match ty.kind() {
+            ast::TypeNameKind::Bool => format!("{pkg}.types.BOOL"),
+            ast::TypeNameKind::U8 => format!("{pkg}.types.U8"),
+            ast::TypeNameKind::I8 => format!("{pkg}.types.I8"),
+            ast::TypeNameKind::U16 => format!("{pkg}.types.U16"),
+            ast::TypeNameKind::I16 => format!("{pkg}.types.I16"),
+            ast::TypeNameKind::U32 => format!("{pkg}.types.U32"),
+            ast::TypeNameKind::I32 => format!("{pkg}.types.I32"),
+            ast::TypeNameKind::U64 => format!("{pkg}.types.U64"),
+            ast::TypeNameKind::I64 => format!("{pkg}.types.I64"),
+            ast::TypeNameKind::F32 => format!("{pkg}.types.F32"),
+            ast::TypeNameKind::F64 => format!("{pkg}.types.F64"),
+            ast::TypeNameKind::String => format!("{pkg}.types.STRING"),
+            ast::TypeNameKind::Uuid => format!("{pkg}.types.UUID"),
+            ast::TypeNameKind::ObjectId => format!("{pkg}.types.OBJECT_ID"),
+            ast::TypeNameKind::ServiceId => format!("{pkg}.types.SERVICE_ID"),
+            ast::TypeNameKind::Value => format!("{pkg}.types.VALUE"),
+            ast::TypeNameKind::Option(ty) => format!("{pkg}.types.Option({})", self.type_desc(ty)),
+            ast::TypeNameKind::Box(ty) => self.type_desc(ty),
+
+            ast::TypeNameKind::Vec(ty) => match ty.kind() {
+                ast::TypeNameKind::U8 => format!("{pkg}.types.BYTES"),
+                _ => format!("{pkg}.types.Vec({})", self.type_desc(ty)),
+            },
+
+            ast::TypeNameKind::Bytes => format!("{pkg}.types.BYTES"),
+
+            ast::TypeNameKind::Map(k, v) => format!(
+                "{pkg}.types.Map({}, {})",
+                self.key_type_desc(k),
+                self.type_desc(v)
+            ),
+
+            ast::TypeNameKind::Set(ty) => format!("{pkg}.types.Set({})", self.key_type_desc(ty)),
+            ast::TypeNameKind::Sender(ty) => format!("{pkg}.types.Sender({})", self.type_desc(ty)),
+
+            ast::TypeNameKind::Receiver(ty) | ast::TypeNameKind::Broadcast(ty) => {
+                format!("{pkg}.types.Receiver({})", self.type_desc(ty))
+            }
+
+            ast::TypeNameKind::Lifetime => format!("{pkg}.types.LIFETIME"),
+            ast::TypeNameKind::Unit => format!("{pkg}.types.UNIT"),
+
+            ast::TypeNameKind::Result(ok, err) => format!(
+                "{pkg}.types.Result({}, {})",
+                self.type_desc(ok),
+                self.type_desc(err)
+            ),
+
+            ast::TypeNameKind::Array(ty, len) => {
+                let len = match len.value() {
+                    ast::ArrayLenValue::Literal(len) => len.value().to_owned(),
+                    ast::ArrayLenValue::Ref(named_ref) => self.named_ref_name(named_ref),
+                };
+
+                format!("{pkg}.types.Array({}, {len})", self.type_desc(ty))
+            }
+
+            ast::TypeNameKind::Ref(ty) => {
+                if self.is_newtype(self.schema, ty) {
+                    self.newtype_type_desc(ty)
+                } else {
+                    format!("{pkg}.types.Ref(lambda: {})", self.named_ref_name(ty))
+                }
+            }
+        }
+    }
+
+    fn key_type_desc(&self, ty: &ast::KeyTypeName) -> String {
+        let pkg = self.python_options.package;
+
+        match ty.kind() {
+            ast::KeyTypeNameKind::U8 => format!("{pkg}.types.U8"),
+            ast::KeyTypeNameKind::I8 => format!("{pkg}.types.I8"),
+            ast::KeyTypeNameKind::U16 => format!("{pkg}.types.U16"),
+            ast::KeyTypeNameKind::I16 => format!("{pkg}.types.I16"),
+            ast::KeyTypeNameKind::U32 => format!("{pkg}.types.U32"),
+            ast::KeyTypeNameKind::I32 => format!("{pkg}.types.I32"),
+            ast::KeyTypeNameKind::U64 => format!("{pkg}.types.U64"),
+            ast::KeyTypeNameKind::I64 => format!("{pkg}.types.I64"),
+            ast::KeyTypeNameKind::String => format!("{pkg}.types.STRING"),
+            ast::KeyTypeNameKind::Uuid => format!("{pkg}.types.UUID"),
+            ast::KeyTypeNameKind::Ref(ty) => self.newtype_type_desc(ty),
+        }
+    }
+
+    fn newtype_type_desc(&self, ty: &ast::NamedRef) -> String {
+        match ty.kind() {
+            ast::NamedRefKind::Intern(ty) => newtype_type_name(ty.value()),
+
+            ast::NamedRefKind::Extern(m, ty) => {
+                format!("{}.{}", m.value(), newtype_type_name(ty.value()))
+            }
+        }
+    }
+
+    /// Returns a Python type hint for a type.
+    fn type_hint(&self, ty: &ast::TypeName) -> String {
+        let pkg = self.python_options.package;
+
+        match ty.kind() {
+            ast::TypeNameKind::Bool => "bool".to_owned(),
+
+            ast::TypeNameKind::U8
+            | ast::TypeNameKind::I8
+            | ast::TypeNameKind::U16
+            | ast::TypeNameKind::I16
+            | ast::TypeNameKind::U32
+            | ast::TypeNameKind::I32
+            | ast::TypeNameKind::U64
+            | ast::TypeNameKind::I64 => "int".to_owned(),
+
+            ast::TypeNameKind::F32 | ast::TypeNameKind::F64 => "float".to_owned(),
+            ast::TypeNameKind::String => "str".to_owned(),
+            ast::TypeNameKind::Uuid => "uuid.UUID".to_owned(),
+            ast::TypeNameKind::ObjectId => format!("{pkg}.ObjectId"),
+            ast::TypeNameKind::ServiceId => format!("{pkg}.ServiceId"),
+            ast::TypeNameKind::Value => format!("{pkg}.SerializedValue"),
+            ast::TypeNameKind::Option(ty) => format!("typing.Optional[{}]", self.type_hint(ty)),
+            ast::TypeNameKind::Box(ty) => self.type_hint(ty),
+
+            ast::TypeNameKind::Vec(ty) => match ty.kind() {
+                ast::TypeNameKind::U8 => "bytes".to_owned(),
+                _ => format!("typing.List[{}]", self.type_hint(ty)),
+            },
+
+            ast::TypeNameKind::Bytes => "bytes".to_owned(),
+
+            ast::TypeNameKind::Map(k, v) => format!(
+                "typing.Dict[{}, {}]",
+                self.key_type_hint(k),
+                self.type_hint(v)
+            ),
+
+            ast::TypeNameKind::Set(ty) => format!("typing.Set[{}]", self.key_type_hint(ty)),
+
+            ast::TypeNameKind::Sender(ty) => {
+                format!("{pkg}.UnboundSender[{}]", self.type_hint(ty))
+            }
+
+            ast::TypeNameKind::Receiver(ty) | ast::TypeNameKind::Broadcast(ty) => {
+                format!("{pkg}.UnboundReceiver[{}]", self.type_hint(ty))
+            }
+
+            ast::TypeNameKind::Lifetime => format!("{pkg}.LifetimeId"),
+            ast::TypeNameKind::Unit => "None".to_owned(),
+
+            ast::TypeNameKind::Result(ok, err) => format!(
+                "{pkg}.Result[{}, {}]",
+                self.type_hint(ok),
+                self.type_hint(err)
+            ),
+
+            ast::TypeNameKind::Array(ty, _) => format!("typing.List[{}]", self.type_hint(ty)),
+            ast::TypeNameKind::Ref(ty) => self.named_ref_name(ty),
+        }
+    }
+
+    fn key_type_hint(&self, ty: &ast::KeyTypeName) -> String {
+        match ty.kind() {
+            ast::KeyTypeNameKind::U8
+            | ast::KeyTypeNameKind::I8
+            | ast::KeyTypeNameKind::U16
+            | ast::KeyTypeNameKind::I16
+            | ast::KeyTypeNameKind::U32
+            | ast::KeyTypeNameKind::I32
+            | ast::KeyTypeNameKind::U64
+            | ast::KeyTypeNameKind::I64 => "int".to_owned(),
+
+            ast::KeyTypeNameKind::String => "str".to_owned(),
+            ast::KeyTypeNameKind::Uuid => "uuid.UUID".to_owned(),
+            ast::KeyTypeNameKind::Ref(ty) => self.named_ref_name(ty),
+        }
+    }
+
+    fn named_ref_name(&self, ty: &ast::NamedRef) -> String {
+        match ty.kind() {
+            ast::NamedRefKind::Intern(ty) => ident(ty.value()),
+            ast::NamedRefKind::Extern(m, ty) => format!("{}.{}", m.value(), ident(ty.value())),
+        }
+    }
+}
+
+fn ident(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
+
+fn is_const_type(ty: &ast::TypeName) -> bool {
+    matches!(
+        ty.kind(),
+        ast::TypeNameKind::Bool
+            | ast::TypeNameKind::U8
+            | ast::TypeNameKind::I8
+            | ast::TypeNameKind::U16
+            | ast::TypeNameKind::I16
+            | ast::TypeNameKind::U32
+            | ast::TypeNameKind::I32
+            | ast::TypeNameKind::U64
+            | ast::TypeNameKind::I64
+            | ast::TypeNameKind::F32
+            | ast::TypeNameKind::F64
+            | ast::TypeNameKind::String
+            | ast::TypeNameKind::Uuid
+    )
+}
+
+fn newtype_deps<'a>(ty: &'a ast::TypeName, deps: &mut Vec<&'a str>) {
+    match ty.kind() {
+        ast::TypeNameKind::Option(ty)
+        | ast::TypeNameKind::Box(ty)
+        | ast::TypeNameKind::Vec(ty)
+        | ast::TypeNameKind::Sender(ty)
+        | ast::TypeNameKind::Receiver(ty)
+        | ast::TypeNameKind::Broadcast(ty)
+        | ast::TypeNameKind::Array(ty, _) => newtype_deps(ty, deps),
+
+        ast::TypeNameKind::Map(key, ty) => {
+            newtype_key_deps(key, deps);
+            newtype_deps(ty, deps);
+        }
+
+        ast::TypeNameKind::Set(key) => newtype_key_deps(key, deps),
+
+        ast::TypeNameKind::Result(ok, err) => {
+            newtype_deps(ok, deps);
+            newtype_deps(err, deps);
+        }
+
+        ast::TypeNameKind::Ref(named_ref) => {
+            if let ast::NamedRefKind::Intern(ident) = named_ref.kind() {
+                deps.push(ident.value());
+            }
+        }
+
+        _ => {}
+    }
+}
+
+fn newtype_key_deps<'a>(key: &'a ast::KeyTypeName, deps: &mut Vec<&'a str>) {
+    if let ast::KeyTypeNameKind::Ref(named_ref) = key.kind() {
+        if let ast::NamedRefKind::Intern(ident) = named_ref.kind() {
+            deps.push(ident.value());
+        }
+    }
+}
+
+fn join<I: Iterator<Item = String>>(iter: I) -> String {
+    iter.collect::<Vec<_>>().join(", ")
+}
+
+fn function_part_name(svc_name: &str, func_name: &str, suffix: &str) -> String {
+    format!("{svc_name}{}{suffix}", func_name.to_upper_camel_case())
+}
+
+fn event_type_name(svc_name: &str, ev_name: &str) -> String {
+    format!("{svc_name}{}Event", ev_name.to_upper_camel_case())
+}
+
+fn newtype_type_name(name: &str) -> String {
+    format!("{name}Type")
+}
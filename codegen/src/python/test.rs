@@ -0,0 +1,282 @@
+use crate::interop_test::{self, Interop, InteropEvent, InteropProxy, Shape, OBJECT_UUID};
+use crate::{Generator, Options, PythonOptions, PythonOutput};
+use aldrin::core::tokio::TokioTransport;
+use aldrin::core::SerializedValue;
+use aldrin_parser::Parser;
+use aldrin_test::tokio::TestBroker;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task;
+use tokio::time;
+
+fn generate(schema: &str, options: &Options, python_options: &PythonOptions) -> PythonOutput {
+    let mut parser = Parser::new();
+    parser.add_schema_path("test");
+
+    let parsed = parser.parse(format!("test/{schema}.aldrin"));
+    assert!(parsed.errors().is_empty());
+
+    Generator::new(options, &parsed)
+        .generate_python(python_options)
+        .unwrap()
+}
+
+#[test]
+fn all_schemas() {
+    let options = Options::new();
+    let python_options = PythonOptions::new();
+
+    for schema in [
+        "all_types",
+        "before_derive_compat",
+        "constants",
+        "extern",
+        "generic_struct",
+        "interop",
+        "introspection",
+        "newtypes",
+        "old_new",
+        "options",
+        "raw_identifiers",
+        "reliable",
+        "result",
+        "retained",
+        "server",
+        "streaming",
+        "subscribe_all",
+        "test1",
+        "typed_constants",
+        "unit",
+    ] {
+        let output = generate(schema, &options, &python_options);
+        assert_eq!(output.module_name, schema);
+        assert!(output
+            .module_content
+            .contains("from __future__ import annotations"));
+    }
+}
+
+#[test]
+fn constants() {
+    let output = generate("constants", &Options::new(), &PythonOptions::new());
+    let content = output.module_content;
+
+    assert!(content.contains("CONST_U8: typing.Final = 1\n"));
+    assert!(content.contains("CONST_STRING: typing.Final = \"string\"\n"));
+    assert!(content.contains(
+        "CONST_UUID: typing.Final = uuid.UUID(\"5c368dc9-e6d3-4545-86d1-435fe3e771cc\")\n"
+    ));
+}
+
+#[test]
+fn services() {
+    let output = generate("streaming", &Options::new(), &PythonOptions::new());
+    let content = output.module_content;
+
+    assert!(content.contains("import abc\n"));
+    assert!(content.contains("class StreamingProxy(aldrin.Proxy):\n"));
+    assert!(content.contains("class Streaming(aldrin.Service):\n"));
+    assert!(content.contains("class StreamingHandler(abc.ABC):\n"));
+}
+
+#[test]
+fn properties() {
+    let output = generate("property", &Options::new(), &PythonOptions::new());
+    let content = output.module_content;
+
+    assert!(content.contains("        2: aldrin.prop(\"volume\", aldrin.types.U32),\n"));
+    assert!(content.contains("    def volume(self) -> typing.Optional[int]:\n"));
+    assert!(content.contains("    async def subscribe_volume(self) -> None:\n"));
+    assert!(content.contains("    def set_volume(self, value: int) -> None:\n"));
+    assert!(content.contains("    def clear_name(self) -> None:\n"));
+}
+
+#[test]
+fn client_only() {
+    let mut options = Options::new();
+    options.server = false;
+
+    let output = generate("streaming", &options, &PythonOptions::new());
+    let content = output.module_content;
+
+    assert!(!content.contains("import abc\n"));
+    assert!(content.contains("class StreamingProxy(aldrin.Proxy):\n"));
+    assert!(!content.contains("class Streaming(aldrin.Service):\n"));
+    assert!(!content.contains("class StreamingHandler(abc.ABC):\n"));
+}
+
+#[test]
+fn custom_package() {
+    let mut python_options = PythonOptions::new();
+    python_options.package = "aldrin_py";
+
+    let output = generate("test1", &Options::new(), &python_options);
+    let content = output.module_content;
+
+    assert!(content.contains("import aldrin_py\n"));
+    assert!(!content.contains("import aldrin\n"));
+}
+
+#[test]
+fn keywords() {
+    let output = generate("raw_identifiers", &Options::new(), &PythonOptions::new());
+    let content = output.module_content;
+
+    assert!(content.contains("    if_: extern.pub = aldrin.field(1, "));
+    assert!(content.contains("    break_ = aldrin.variant(1)\n"));
+}
+
+#[test]
+fn typed_constants() {
+    let output = generate("typed_constants", &Options::new(), &PythonOptions::new());
+    let content = output.module_content;
+
+    assert!(content.contains("ENABLED: typing.Final = True\n"));
+    assert!(content.contains("GREETING: typing.Final = \"hello\"\n"));
+    assert!(content.contains("CODE_LEN: typing.Final = 4\n"));
+    assert!(content.contains("aldrin.types.Array(aldrin.types.U8, CODE_LEN)"));
+    assert!(content.contains("def default_config() -> Config:\n"));
+    assert!(content.contains("mode=Mode.On(5)"));
+    assert!(content.contains("ext=extern.pub()"));
+}
+
+#[test]
+fn newtypes() {
+    let output = generate("newtypes", &Options::new(), &PythonOptions::new());
+    let content = output.module_content;
+
+    assert!(content.contains("PlayerId = typing.NewType(\"PlayerId\", uuid.UUID)\n"));
+    assert!(content.contains("PlayerIdType: typing.Final = aldrin.types.UUID\n"));
+    assert!(content.contains("AliasType: typing.Final = PlayerIdType\n"));
+    assert!(content.contains("aldrin.types.Map(PlayerIdType, aldrin.types.U32)"));
+    assert!(content.contains("aldrin.types.Set(extern.HandleType)"));
+    assert!(content.contains("    return Level(1)\n"));
+}
+
+/// Writes the generated `interop` module and the test script into a new directory.
+///
+/// Returns `None` if Python 3.10 or newer is not available.
+fn setup_python(test: &str) -> Option<PathBuf> {
+    let status = Command::new("python3")
+        .args(["-c", "import sys; sys.exit(sys.version_info < (3, 10))"])
+        .status();
+
+    if !status.is_ok_and(|status| status.success()) {
+        eprintln!("skipping test, because Python 3.10 or newer is not available");
+        return None;
+    }
+
+    let dir = interop_test::test_dir(test);
+    let output = generate("interop", &Options::new(), &PythonOptions::new());
+    fs::write(dir.join("interop.py"), output.module_content).unwrap();
+    fs::write(dir.join("main.py"), include_str!("test_interop.py")).unwrap();
+
+    Some(dir)
+}
+
+fn python(dir: &Path, args: &[&str]) -> Command {
+    let runtime = Path::new(env!("CARGO_MANIFEST_DIR")).join("../python");
+    let path = env::join_paths([runtime.as_path(), dir]).unwrap();
+
+    let mut cmd = Command::new("python3");
+    cmd.arg(dir.join("main.py"))
+        .args(args)
+        .env("PYTHONPATH", path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    cmd
+}
+
+fn assert_python_ok(dir: PathBuf, output: Output) {
+    fs::remove_dir_all(dir).unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert_eq!(output.stdout, b"ok\n");
+}
+
+#[test]
+fn runtime_round_trip() {
+    let Some(dir) = setup_python("round-trip") else {
+        return;
+    };
+
+    let hex = interop_test::record_hex();
+    let output = python(&dir, &["round-trip", &hex]).output().unwrap();
+    assert_python_ok(dir, output);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn runtime_client() {
+    let Some(dir) = setup_python("client") else {
+        return;
+    };
+
+    let mut broker = TestBroker::new();
+    let mut handle = broker.handle().clone();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port().to_string();
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let conn = handle.connect(TokioTransport::new(stream)).await.unwrap();
+            tokio::spawn(conn.run());
+        }
+    });
+
+    let client = broker.add_client().await;
+    interop_test::spawn_service(&client).await;
+
+    // The Python side first calls the Rust service and then provides its own.
+    let child = python(&dir, &["client", &port]).spawn().unwrap();
+
+    time::timeout(Duration::from_secs(30), async {
+        let (_, [id]) = client
+            .wait_for_specific_object(OBJECT_UUID, &[Interop::UUID])
+            .await
+            .unwrap();
+        let mut proxy = InteropProxy::new(&client, id).await.unwrap();
+        proxy.subscribe_reset().await.unwrap();
+
+        // The property is answered by the Python service, which hasn't set it yet.
+        proxy.subscribe_level().await.unwrap();
+        assert!(matches!(
+            proxy.next_event().await,
+            Some(Ok(InteropEvent::Level(None)))
+        ));
+
+        let echo = proxy.echo(&interop_test::record()).await.unwrap().unwrap();
+        assert_eq!(
+            SerializedValue::serialize(&echo).unwrap(),
+            SerializedValue::serialize(&interop_test::record()).unwrap()
+        );
+
+        assert_eq!(proxy.area(&Shape::Circle(2)).await.unwrap(), Ok(12));
+        assert_eq!(
+            proxy.area(&Shape::Empty).await.unwrap(),
+            Err("empty".to_owned())
+        );
+
+        proxy.ping().await.unwrap().unwrap();
+        assert!(matches!(
+            proxy.next_event().await,
+            Some(Ok(InteropEvent::Level(Some(3))))
+        ));
+        assert_eq!(proxy.level(), Some(&3));
+        assert!(matches!(
+            proxy.next_event().await,
+            Some(Ok(InteropEvent::Reset))
+        ));
+    })
+    .await
+    .unwrap();
+
+    let output = task::spawn_blocking(|| child.wait_with_output())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_python_ok(dir, output);
+}
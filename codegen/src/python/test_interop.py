@@ -0,0 +1,133 @@
+# Driven by the tests in `test.rs`, which generate the `interop` module from
+# `test/interop.aldrin` and provide the counterpart in Rust.
+
+import asyncio
+import sys
+import uuid
+
+import aldrin
+import interop
+
+RECORD_ID = uuid.UUID("5c368dc9-e6d3-4545-86d1-435fe3e771cc")
+OBJECT_UUID = uuid.UUID("1f6c4e7a-3b2d-4c8e-9a5f-0d7e6b4c3a21")
+
+
+def record() -> interop.Record:
+    object_id = aldrin.ObjectId(
+        uuid.UUID("b5d1e8a7-8c3f-4e62-9f1a-2d4c6b8e0a13"),
+        uuid.UUID("c6e2f9b8-9d40-4f73-a02b-3e5d7c9f1b24"),
+    )
+
+    return interop.Record(
+        name="record",
+        id=RECORD_ID,
+        flag=True,
+        small=200,
+        signed=-5,
+        short=-300,
+        count=2**64 - 1,
+        delta=-(2**63),
+        ratio=1.5,
+        precise=-2.25,
+        data=bytes([0, 1, 255]),
+        tags=["x", "y"],
+        scores={"a": 1},
+        keys={-7},
+        shape=interop.Shape.Polygon(
+            [interop.Point(x=1, y=-1), interop.Point(x=2**31 - 1, y=-(2**31))]
+        ),
+        origin=interop.Point(x=0, y=0),
+        maybe=3,
+        outcome=aldrin.Err("bad"),
+        pair=[-1, 1],
+        obj=object_id,
+        svc=aldrin.ServiceId(
+            object_id,
+            uuid.UUID("d7f30ac9-ae51-4084-b13c-4f6e8d0a2c35"),
+            uuid.UUID("e8041bda-bf62-4195-824d-507f9e1b3d46"),
+        ),
+        any=aldrin.SerializedValue.serialize(aldrin.types.STRING, "hi"),
+        nested=interop.Record(name="inner", id=RECORD_ID),
+    )
+
+
+def round_trip(data: bytes) -> None:
+    decoded = aldrin.types.deserialize(interop.Record, data)
+    assert decoded == record(), decoded
+    assert aldrin.types.serialize(interop.Record, decoded) == data
+    assert aldrin.types.serialize(interop.Record, record()) == data
+
+
+def area(shape: interop.Shape) -> int:
+    if shape == interop.Shape.Empty:
+        raise aldrin.CallError("empty")
+    elif shape.name == "Circle":
+        return 3 * shape.value * shape.value
+    else:
+        return len(shape.value)
+
+
+class Handler(interop.InteropHandler):
+    def __init__(self) -> None:
+        self.done = asyncio.Event()
+
+    async def echo(self, call: aldrin.Call, args: interop.Record) -> interop.Record:
+        return args
+
+    async def area(self, call: aldrin.Call, args: interop.Shape) -> int:
+        return area(args)
+
+    async def ping(self, call: aldrin.Call) -> None:
+        call.service.set_level(3)
+        call.service.emit_reset()
+        self.done.set()
+
+
+async def client(port: int) -> None:
+    async with await aldrin.Client.connect("127.0.0.1", port, name="python") as client:
+        # Call the service provided by Rust.
+        proxy = await interop.InteropProxy.find(client)
+        await proxy.subscribe_changed()
+
+        # Subscribing to a property fetches its current value.
+        await proxy.subscribe_level()
+        ev = await proxy.next_event()
+        assert ev == aldrin.Event(4, "level", 1), ev
+        assert proxy.level() == 1
+
+        assert await proxy.echo(record()) == record()
+        assert await proxy.area(interop.Shape.Circle(2)) == 12
+
+        try:
+            await proxy.area(interop.Shape.Empty)
+            raise AssertionError("expected an error")
+        except aldrin.CallError as e:
+            assert e.value == "empty"
+
+        await proxy.ping()
+        ev = await proxy.next_event()
+        assert ev == aldrin.Event(1, "changed", interop.Point(x=1, y=2)), ev
+        ev = await proxy.next_event()
+        assert ev == aldrin.Event(4, "level", 2), ev
+        assert proxy.level() == 2
+
+        await proxy.unsubscribe_changed()
+        await proxy.unsubscribe_level()
+
+        # Provide the same service to Rust, which stops when it calls `ping`.
+        handler = Handler()
+        obj = await client.create_object(OBJECT_UUID)
+        await interop.Interop.create(obj, handler)
+        await handler.done.wait()
+
+
+def main() -> None:
+    if sys.argv[1] == "round-trip":
+        round_trip(bytes.fromhex(sys.argv[2]))
+    else:
+        asyncio.run(asyncio.wait_for(client(int(sys.argv[2])), 30))
+
+    print("ok")
+
+
+main()
@@ -5,7 +5,7 @@ use crate::error::Error;
 use crate::Options;
 use aldrin_parser::{ast, Parsed, Schema};
 use diffy::Patch;
-use heck::ToUpperCamelCase;
+use heck::{ToSnakeCase, ToUpperCamelCase};
 use std::fmt::Write;
 use std::fs;
 use std::path::Path;
@@ -15,14 +15,17 @@ const BOX: &str = "::std::boxed::Box";
 const CLONE: &str = "::std::clone::Clone";
 const DEBUG: &str = "::std::fmt::Debug";
 const DEFAULT: &str = "::std::default::Default";
+const ERR: &str = "::std::result::Result::Err";
 const F32: &str = "::std::primitive::f32";
 const F64: &str = "::std::primitive::f64";
+const FROM: &str = "::std::convert::From";
 const HASH_MAP: &str = "::std::collections::HashMap";
 const HASH_SET: &str = "::std::collections::HashSet";
 const I16: &str = "::std::primitive::i16";
 const I32: &str = "::std::primitive::i32";
 const I64: &str = "::std::primitive::i64";
 const I8: &str = "::std::primitive::i8";
+const NONE: &str = "::std::option::Option::None";
 const OK: &str = "::std::result::Result::Ok";
 const OPTION: &str = "::std::option::Option";
 const RESULT: &str = "::std::result::Result";
@@ -45,6 +48,8 @@ pub struct RustOptions<'a> {
     pub event_non_exhaustive: bool,
     pub function_non_exhaustive: bool,
     pub introspection_if: Option<&'a str>,
+    pub mocks: bool,
+    pub serde: bool,
     pub krate: &'a str,
 }
 
@@ -58,6 +63,8 @@ impl RustOptions<'_> {
             event_non_exhaustive: true,
             function_non_exhaustive: true,
             introspection_if: None,
+            mocks: false,
+            serde: false,
             krate: "::aldrin",
         }
     }
@@ -77,12 +84,12 @@ pub struct RustOutput {
 
 pub(crate) fn generate(
     parsed: &Parsed,
+    schema: &Schema,
     options: &Options,
     rust_options: &RustOptions,
 ) -> Result<RustOutput, Error> {
-    let schema = parsed.main_schema();
-
     let generator = RustGenerator {
+        parsed,
         schema,
         options,
         rust_options,
@@ -96,6 +103,7 @@ pub(crate) fn generate(
 }
 
 struct RustGenerator<'a> {
+    parsed: &'a Parsed,
     schema: &'a Schema,
     options: &'a Options,
     rust_options: &'a RustOptions<'a>,
@@ -138,6 +146,10 @@ impl RustGenerator<'_> {
                 self.register_introspection(def);
             }
 
+            if self.has_error_enums() {
+                codeln!(self, "    client.register_introspection::<{krate}::core::ErrorEnvelope>()?;");
+            }
+
             codeln!(self, "    {OK}(())");
             codeln!(self, "}}");
         }
@@ -159,15 +171,36 @@ impl RustGenerator<'_> {
     fn definition(&mut self, def: &ast::Definition) {
         match def {
             ast::Definition::Struct(d) => {
-                self.struct_def(d.name().value(), Some(d.attributes()), d.fields())
+                self.doc_string(d.doc(), "");
+                self.struct_def(
+                    d.name().value(),
+                    Some(d.attributes()),
+                    d.fields(),
+                    d.fallback(),
+                )
             }
 
             ast::Definition::Enum(e) => {
-                self.enum_def(e.name().value(), Some(e.attributes()), e.variants())
+                self.doc_string(e.doc(), "");
+                self.enum_def(
+                    e.name().value(),
+                    Some(e.attributes()),
+                    e.variants(),
+                    e.fallback(),
+                );
+
+                if self.is_error_enum(e.name().value()) {
+                    self.error_envelope_from(e.name().value(), e.variants(), e.fallback());
+                }
             }
 
             ast::Definition::Service(s) => self.service_def(s),
             ast::Definition::Const(c) => self.const_def(c),
+
+            ast::Definition::Newtype(n) => {
+                self.doc_string(n.doc(), "");
+                self.newtype_def(n);
+            }
         }
     }
 
@@ -176,6 +209,7 @@ impl RustGenerator<'_> {
         name: &str,
         attrs: Option<&[ast::Attribute]>,
         fields: &[ast::StructField],
+        fallback: Option<&ast::StructFallback>,
     ) {
         let krate = self.rust_options.krate;
         let ident = format!("r#{name}");
@@ -210,6 +244,8 @@ impl RustGenerator<'_> {
         }
 
         codeln!(self, "#[aldrin(crate = \"{krate}::core\", schema = \"{schema_name}\")]");
+        self.serde_attrs(false);
+        self.additional_attrs(&attrs);
 
         if self.rust_options.struct_non_exhaustive {
             codeln!(self, "#[non_exhaustive]");
@@ -227,14 +263,48 @@ impl RustGenerator<'_> {
                 codeln!(self);
             }
 
+            self.doc_string(field.doc(), "    ");
+
             if field.required() {
                 codeln!(self, "    #[aldrin(id = {id})]");
+
+                if let Some(with) = self.serde_with(field.field_type(), false) {
+                    codeln!(self, "    #[serde({with})]");
+                }
+
                 codeln!(self, "    pub {ident}: {ty},");
             } else {
                 codeln!(self, "    #[aldrin(id = {id}, optional)]");
+
+                if self.rust_options.serde {
+                    let with = self
+                        .serde_with(field.field_type(), true)
+                        .map(|with| format!(", {with}"))
+                        .unwrap_or_default();
+
+                    codeln!(self, "    #[serde(default, skip_serializing_if = \"{OPTION}::is_none\"{with})]");
+                }
+
                 codeln!(self, "    pub {ident}: {OPTION}<{ty}>,");
             }
         }
+
+        if let Some(fallback) = fallback {
+            let ident = format!("r#{}", fallback.name().value());
+
+            if !first {
+                codeln!(self);
+            }
+
+            self.doc_string(fallback.doc(), "    ");
+            codeln!(self, "    #[aldrin(fallback)]");
+
+            if self.rust_options.serde {
+                codeln!(self, "    #[serde(skip)]");
+            }
+
+            codeln!(self, "    pub {ident}: {krate}::core::UnknownFields,");
+        }
         codeln!(self, "}}");
         codeln!(self);
 
@@ -291,6 +361,10 @@ impl RustGenerator<'_> {
                     let ident = format!("r#{}", field.name().value());
                     codeln!(self, "            {ident}: self.{ident},");
                 }
+                if let Some(fallback) = fallback {
+                    let ident = format!("r#{}", fallback.name().value());
+                    codeln!(self, "            {ident}: {DEFAULT}::default(),");
+                }
                 codeln!(self, "        }}");
             } else {
                 codeln!(self, "    pub fn build(self) -> {RESULT}<{ident}, {krate}::Error> {{");
@@ -305,6 +379,10 @@ impl RustGenerator<'_> {
                         codeln!(self, "            {ident}: self.{ident},");
                     }
                 }
+                if let Some(fallback) = fallback {
+                    let ident = format!("r#{}", fallback.name().value());
+                    codeln!(self, "            {ident}: {DEFAULT}::default(),");
+                }
                 codeln!(self, "        }})");
             }
             codeln!(self, "    }}");
@@ -318,6 +396,7 @@ impl RustGenerator<'_> {
         name: &str,
         attrs: Option<&[ast::Attribute]>,
         vars: &[ast::EnumVariant],
+        fallback: Option<&ast::EnumFallback>,
     ) {
         let ident = format!("r#{name}");
         let krate = &self.rust_options.krate;
@@ -344,6 +423,8 @@ impl RustGenerator<'_> {
         }
 
         codeln!(self, "#[aldrin(crate = \"{krate}::core\", schema = \"{schema_name}\")]");
+        self.serde_attrs(false);
+        self.additional_attrs(&attrs);
 
         if self.rust_options.enum_non_exhaustive {
             codeln!(self, "#[non_exhaustive]");
@@ -360,14 +441,164 @@ impl RustGenerator<'_> {
                 codeln!(self);
             }
 
+            self.doc_string(var.doc(), "    ");
             codeln!(self, "    #[aldrin(id = {id})]");
             if let Some(ty) = var.variant_type() {
+                let with = self
+                    .serde_with(ty, false)
+                    .map(|with| format!("#[serde({with})] "))
+                    .unwrap_or_default();
                 let ty = self.type_name(ty);
-                codeln!(self, "    {ident}({ty}),");
+                codeln!(self, "    {ident}({with}{ty}),");
             } else {
                 codeln!(self, "    {ident},");
             }
         }
+
+        if let Some(fallback) = fallback {
+            let ident = format!("r#{}", fallback.name().value());
+
+            if !first {
+                codeln!(self);
+            }
+
+            self.doc_string(fallback.doc(), "    ");
+            codeln!(self, "    #[aldrin(fallback)]");
+
+            if self.rust_options.serde {
+                codeln!(self, "    #[serde(skip)]");
+            }
+
+            codeln!(self, "    {ident}({krate}::core::UnknownVariant),");
+        }
+        codeln!(self, "}}");
+        codeln!(self);
+    }
+
+    fn newtype_def(&mut self, newtype_def: &ast::NewtypeDef) {
+        let krate = self.rust_options.krate;
+        let ident = format!("r#{}", newtype_def.name().value());
+        let ty = self.type_name(newtype_def.target_type());
+        let is_key_type = self.is_key_type(self.schema, newtype_def.target_type());
+
+        let mut attrs = RustAttributes::parse(newtype_def.attributes());
+        if is_key_type {
+            attrs.impl_partial_eq = true;
+            attrs.impl_eq = true;
+            attrs.impl_partial_ord = true;
+            attrs.impl_ord = true;
+            attrs.impl_hash = true;
+        }
+        let additional_derives = attrs.additional_derives();
+
+        let derive_key = if is_key_type {
+            format!(", {krate}::SerializeKey, {krate}::DeserializeKey")
+        } else {
+            String::new()
+        };
+
+        let introspectable = if is_key_type {
+            format!("{krate}::Introspectable, {krate}::KeyTypeOf")
+        } else {
+            format!("{krate}::Introspectable")
+        };
+
+        let derive_introspectable =
+            if self.options.introspection && self.rust_options.introspection_if.is_none() {
+                format!(", {introspectable}")
+            } else {
+                String::new()
+            };
+
+        codeln!(self, "#[derive({DEBUG}, {CLONE}, {krate}::Serialize, {krate}::Deserialize, {krate}::AsSerializeArg{derive_key}{derive_introspectable}{additional_derives})]");
+
+        if self.options.introspection {
+            if let Some(feature) = self.rust_options.introspection_if {
+                codeln!(self, "#[cfg_attr(feature = \"{feature}\", derive({introspectable}))]");
+            }
+        }
+
+        let with = self
+            .serde_with(newtype_def.target_type(), false)
+            .map(|with| format!("#[serde({with})] "))
+            .unwrap_or_default();
+
+        codeln!(self, "#[aldrin(crate = \"{krate}::core\", newtype)]");
+        self.serde_attrs(true);
+        self.additional_attrs(&attrs);
+        codeln!(self, "pub struct {ident}({with}pub {ty});");
+        codeln!(self);
+    }
+
+    fn serde_attrs(&mut self, transparent: bool) {
+        if !self.rust_options.serde {
+            return;
+        }
+
+        let krate = self.rust_options.krate;
+        let transparent = if transparent { ", transparent" } else { "" };
+
+        codeln!(self, "#[derive({krate}::private::serde::Serialize, {krate}::private::serde::Deserialize)]");
+        codeln!(self, "#[serde(crate = \"{krate}::private::serde\"{transparent})]");
+    }
+
+    /// Returns the `with` attribute for serde, if `ty` is an array.
+    ///
+    /// Serde supports only arrays of up to 32 elements on its own.
+    fn serde_with(&self, ty: &ast::TypeName, optional: bool) -> Option<String> {
+        if !self.rust_options.serde || !matches!(ty.kind(), ast::TypeNameKind::Array(_, _)) {
+            return None;
+        }
+
+        let krate = self.rust_options.krate;
+        let option = if optional { "::option" } else { "" };
+
+        Some(format!("with = \"{krate}::private::serde_array{option}\""))
+    }
+
+    fn additional_attrs(&mut self, attrs: &RustAttributes) {
+        for attr in &attrs.attrs {
+            codeln!(self, "#[{attr}]");
+        }
+    }
+
+    fn error_envelope_from(
+        &mut self,
+        name: &str,
+        vars: &[ast::EnumVariant],
+        fallback: Option<&ast::EnumFallback>,
+    ) {
+        let ident = format!("r#{name}");
+        let krate = self.rust_options.krate;
+
+        codeln!(self, "impl {FROM}<{ident}> for {krate}::core::ErrorEnvelope {{");
+        codeln!(self, "    fn from(err: {ident}) -> Self {{");
+        codeln!(self, "        match err {{");
+        for var in vars {
+            let id = var.id().value();
+            let name = var.name().value();
+            let var_ident = format!("r#{name}");
+
+            if var.variant_type().is_some() {
+                codeln!(self, "            {ident}::{var_ident}(details) => match {krate}::core::SerializedValue::serialize(&details) {{");
+                codeln!(self, "                {OK}(details) => Self::with_details({id}, \"{name}\", details),");
+                codeln!(self, "                {ERR}(_) => Self::new({id}, \"{name}\"),");
+                codeln!(self, "            }},");
+            } else {
+                codeln!(self, "            {ident}::{var_ident} => Self::new({id}, \"{name}\"),");
+            }
+        }
+        if let Some(fallback) = fallback {
+            let name = fallback.name().value();
+            let var_ident = format!("r#{name}");
+
+            codeln!(self, "            {ident}::{var_ident}(unknown) => {{");
+            codeln!(self, "                let (id, details) = unknown.into_parts();");
+            codeln!(self, "                Self::with_details(id, \"{name}\", details)");
+            codeln!(self, "            }}");
+        }
+        codeln!(self, "        }}");
+        codeln!(self, "    }}");
         codeln!(self, "}}");
         codeln!(self);
     }
@@ -385,6 +616,7 @@ impl RustGenerator<'_> {
         let version = svc.version().value();
 
         codeln!(self, "{krate}::service! {{");
+        self.doc_string(svc.doc(), "    ");
 
         code!(self, "    #[aldrin(crate = \"{krate}\", schema = \"{schema}\"");
 
@@ -412,6 +644,10 @@ impl RustGenerator<'_> {
             code!(self, ", introspection_if = \"{feature}\"");
         }
 
+        if self.rust_options.mocks {
+            code!(self, ", mocks");
+        }
+
         codeln!(self, ")]");
 
         codeln!(self, "    pub service {ident} {{");
@@ -427,9 +663,19 @@ impl RustGenerator<'_> {
                     let ident = format!("r#{name}");
                     let id = func.id().value();
 
+                    self.doc_string(func.doc(), "        ");
                     code!(self, "        fn {ident} @ {id}");
 
-                    if func.args().is_some() || func.ok().is_some() || func.err().is_some() {
+                    if let Some(introduced_in) = func.introduced_in() {
+                        let introduced_in = introduced_in.value();
+                        code!(self, " introduced_in {introduced_in}");
+                    }
+
+                    if func.args().is_some()
+                        || func.ok().is_some()
+                        || func.stream().is_some()
+                        || func.err().is_some()
+                    {
                         codeln!(self, " {{");
 
                         if let Some(args) = func.args() {
@@ -442,6 +688,11 @@ impl RustGenerator<'_> {
                             codeln!(self, "            ok = {ty};");
                         }
 
+                        if let Some(stream) = func.stream() {
+                            let ty = self.function_stream_type_name(svc_name, name, stream, true);
+                            codeln!(self, "            stream = {ty};");
+                        }
+
                         if let Some(err) = func.err() {
                             let ty = self.function_err_type_name(svc_name, name, err, true);
                             codeln!(self, "            err = {ty};");
@@ -458,7 +709,19 @@ impl RustGenerator<'_> {
                     let ident = format!("r#{name}");
                     let id = ev.id().value();
 
-                    code!(self, "        event {ident} @ {id}");
+                    self.doc_string(ev.doc(), "        ");
+                    if ev.retained() {
+                        code!(self, "        retained event {ident} @ {id}");
+                    } else if ev.reliable() {
+                        code!(self, "        reliable event {ident} @ {id}");
+                    } else {
+                        code!(self, "        event {ident} @ {id}");
+                    }
+
+                    if let Some(introduced_in) = ev.introduced_in() {
+                        let introduced_in = introduced_in.value();
+                        code!(self, " introduced_in {introduced_in}");
+                    }
 
                     if let Some(ty) = ev.event_type() {
                         let ty = self.event_variant_type(svc_name, name, ty, true);
@@ -467,6 +730,15 @@ impl RustGenerator<'_> {
 
                     codeln!(self, ";");
                 }
+
+                ast::ServiceItem::Property(prop) => {
+                    let ident = format!("r#{}", prop.name().value());
+                    let id = prop.id().value();
+                    let ty = self.type_name(prop.property_type());
+
+                    self.doc_string(prop.doc(), "        ");
+                    codeln!(self, "        property {ident} @ {id} = {ty};");
+                }
             }
         }
 
@@ -485,12 +757,14 @@ impl RustGenerator<'_> {
                                 &self.function_args_type_name(svc_name, func_name, args, false),
                                 None,
                                 s.fields(),
+                                s.fallback(),
                             ),
 
                             ast::TypeNameOrInline::Enum(e) => self.enum_def(
                                 &self.function_args_type_name(svc_name, func_name, args, false),
                                 None,
                                 e.variants(),
+                                e.fallback(),
                             ),
 
                             ast::TypeNameOrInline::TypeName(_) => {}
@@ -503,32 +777,57 @@ impl RustGenerator<'_> {
                                 &self.function_ok_type_name(svc_name, func_name, ok, false),
                                 None,
                                 s.fields(),
+                                s.fallback(),
                             ),
 
                             ast::TypeNameOrInline::Enum(e) => self.enum_def(
                                 &self.function_ok_type_name(svc_name, func_name, ok, false),
                                 None,
                                 e.variants(),
+                                e.fallback(),
                             ),
 
                             ast::TypeNameOrInline::TypeName(_) => {}
                         }
                     }
 
-                    if let Some(err) = func.err() {
-                        match err.part_type() {
+                    if let Some(stream) = func.stream() {
+                        match stream.part_type() {
                             ast::TypeNameOrInline::Struct(s) => self.struct_def(
-                                &self.function_err_type_name(svc_name, func_name, err, false),
+                                &self.function_stream_type_name(svc_name, func_name, stream, false),
                                 None,
                                 s.fields(),
+                                s.fallback(),
                             ),
 
                             ast::TypeNameOrInline::Enum(e) => self.enum_def(
-                                &self.function_err_type_name(svc_name, func_name, err, false),
+                                &self.function_stream_type_name(svc_name, func_name, stream, false),
                                 None,
                                 e.variants(),
+                                e.fallback(),
+                            ),
+
+                            ast::TypeNameOrInline::TypeName(_) => {}
+                        }
+                    }
+
+                    if let Some(err) = func.err() {
+                        match err.part_type() {
+                            ast::TypeNameOrInline::Struct(s) => self.struct_def(
+                                &self.function_err_type_name(svc_name, func_name, err, false),
+                                None,
+                                s.fields(),
+                                s.fallback(),
                             ),
 
+                            ast::TypeNameOrInline::Enum(e) => {
+                                let name =
+                                    self.function_err_type_name(svc_name, func_name, err, false);
+
+                                self.enum_def(&name, None, e.variants(), e.fallback());
+                                self.error_envelope_from(&name, e.variants(), e.fallback());
+                            }
+
                             ast::TypeNameOrInline::TypeName(_) => {}
                         }
                     }
@@ -543,18 +842,22 @@ impl RustGenerator<'_> {
                                 &self.event_variant_type(svc_name, ev_name, ty, false),
                                 None,
                                 s.fields(),
+                                s.fallback(),
                             ),
 
                             ast::TypeNameOrInline::Enum(e) => self.enum_def(
                                 &self.event_variant_type(svc_name, ev_name, ty, false),
                                 None,
                                 e.variants(),
+                                e.fallback(),
                             ),
 
                             ast::TypeNameOrInline::TypeName(_) => {}
                         }
                     }
                 }
+
+                ast::ServiceItem::Property(_) => {}
             }
         }
     }
@@ -563,6 +866,8 @@ impl RustGenerator<'_> {
         let krate = self.rust_options.krate;
         let name = const_def.name().value();
 
+        self.doc_string(const_def.doc(), "");
+
         match const_def.value() {
             ast::ConstValue::U8(v) => {
                 let val = v.value();
@@ -613,11 +918,39 @@ impl RustGenerator<'_> {
                 let val = v.value();
                 codeln!(self, "pub const {name}: {krate}::private::uuid::Uuid = {krate}::private::uuid::uuid!(\"{val}\");");
             }
+
+            ast::ConstValue::Typed(v) => {
+                let ty = self.type_name(v.type_name());
+                let val = self.const_expr(self.schema, v.type_name(), v.expr());
+
+                if is_const_type(v.type_name()) {
+                    codeln!(self, "pub const {name}: {ty} = {val};");
+                } else {
+                    let fn_name = name.to_snake_case();
+                    codeln!(self, "pub fn r#{fn_name}() -> {ty} {{");
+                    codeln!(self, "    {val}");
+                    codeln!(self, "}}");
+                }
+            }
         };
 
         codeln!(self);
     }
 
+    fn doc_string(&mut self, doc: Option<&str>, indent: &str) {
+        let Some(doc) = doc else {
+            return;
+        };
+
+        for line in doc.lines() {
+            if line.is_empty() {
+                codeln!(self, "{indent}///");
+            } else {
+                codeln!(self, "{indent}/// {line}");
+            }
+        }
+    }
+
     fn register_introspection(&mut self, def: &ast::Definition) {
         match def {
             ast::Definition::Struct(d) => {
@@ -637,10 +970,53 @@ impl RustGenerator<'_> {
                 }
             }
 
-            ast::Definition::Const(_) => {}
+            ast::Definition::Const(_) | ast::Definition::Newtype(_) => {}
         }
     }
 
+    fn is_error_enum(&self, name: &str) -> bool {
+        self.error_types().any(|err| match err.part_type() {
+            ast::TypeNameOrInline::TypeName(ty) => match ty.kind() {
+                ast::TypeNameKind::Ref(named_ref) => match named_ref.kind() {
+                    ast::NamedRefKind::Intern(ty) => ty.value() == name,
+                    ast::NamedRefKind::Extern(_, _) => false,
+                },
+
+                _ => false,
+            },
+
+            ast::TypeNameOrInline::Struct(_) | ast::TypeNameOrInline::Enum(_) => false,
+        })
+    }
+
+    fn has_error_enums(&self) -> bool {
+        let inline = (self.options.client || self.options.server)
+            && self
+                .error_types()
+                .any(|err| matches!(err.part_type(), ast::TypeNameOrInline::Enum(_)));
+
+        inline
+            || self.schema.definitions().iter().any(|def| match def {
+                ast::Definition::Enum(e) => self.is_error_enum(e.name().value()),
+                _ => false,
+            })
+    }
+
+    fn error_types(&self) -> impl Iterator<Item = &ast::FunctionPart> {
+        self.schema
+            .definitions()
+            .iter()
+            .filter_map(|def| match def {
+                ast::Definition::Service(svc) => Some(svc.items()),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|item| match item {
+                ast::ServiceItem::Function(func) => func.err(),
+                ast::ServiceItem::Event(_) | ast::ServiceItem::Property(_) => None,
+            })
+    }
+
     fn type_name(&self, ty: &ast::TypeName) -> String {
         let krate = self.rust_options.krate;
 
@@ -687,6 +1063,10 @@ impl RustGenerator<'_> {
                 format!("{krate}::UnboundReceiver<{}>", self.type_name(ty))
             }
 
+            ast::TypeNameKind::Broadcast(ty) => {
+                format!("{krate}::UnboundBroadcastReceiver<{}>", self.type_name(ty))
+            }
+
             ast::TypeNameKind::Lifetime => format!("{krate}::LifetimeId"),
             ast::TypeNameKind::Unit => "()".to_owned(),
 
@@ -762,6 +1142,26 @@ impl RustGenerator<'_> {
         }
     }
 
+    fn function_stream_type_name(
+        &self,
+        svc_name: &str,
+        func_name: &str,
+        part: &ast::FunctionPart,
+        raw: bool,
+    ) -> String {
+        match part.part_type() {
+            ast::TypeNameOrInline::TypeName(ty) => self.type_name(ty),
+
+            ast::TypeNameOrInline::Struct(_) | ast::TypeNameOrInline::Enum(_) => {
+                if raw {
+                    format!("r#{svc_name}{}Item", func_name.to_upper_camel_case())
+                } else {
+                    format!("{svc_name}{}Item", func_name.to_upper_camel_case())
+                }
+            }
+        }
+    }
+
     fn function_err_type_name(
         &self,
         svc_name: &str,
@@ -802,6 +1202,245 @@ impl RustGenerator<'_> {
         }
     }
 
+    /// Generates a Rust expression for a constant of type `ty`, which is defined in `schema`.
+    fn const_expr(&self, schema: &Schema, ty: &ast::TypeName, expr: &ast::ConstExpr) -> String {
+        let krate = self.rust_options.krate;
+
+        match (ty.kind(), expr.kind()) {
+            (ast::TypeNameKind::Ref(named_ref), _) if self.is_newtype(schema, named_ref) => {
+                self.newtype_const_expr(schema, named_ref, expr)
+            }
+
+            (ast::TypeNameKind::F32 | ast::TypeNameKind::F64, ast::ConstExprKind::Int(lit)) => {
+                format!("{}.0", lit.value())
+            }
+
+            (_, ast::ConstExprKind::Int(lit)) => lit.value().to_owned(),
+            (_, ast::ConstExprKind::Float(lit)) => lit.value().to_owned(),
+            (_, ast::ConstExprKind::String(lit)) => format!("{STRING}::from(\"{}\")", lit.value()),
+
+            (_, ast::ConstExprKind::Uuid(lit)) => {
+                format!("{krate}::private::uuid::uuid!(\"{}\")", lit.value())
+            }
+
+            (ast::TypeNameKind::Bool, ast::ConstExprKind::Ident(ident)) => ident.value().to_owned(),
+
+            (ast::TypeNameKind::Option(_), ast::ConstExprKind::Ident(ident))
+                if ident.value() == "none" =>
+            {
+                NONE.to_owned()
+            }
+
+            (ast::TypeNameKind::Option(ty), _) => {
+                format!("{SOME}({})", self.const_expr(schema, ty, expr))
+            }
+
+            (ast::TypeNameKind::Box(ty), _) => {
+                format!("{BOX}::new({})", self.const_expr(schema, ty, expr))
+            }
+
+            (ast::TypeNameKind::Bytes, ast::ConstExprKind::List(elems)) => {
+                let elems = elems.iter().map(|e| self.const_expr(schema, ty, e));
+                format!("{krate}::core::Bytes(::std::vec![{}])", join(elems))
+            }
+
+            (ast::TypeNameKind::Vec(ty), ast::ConstExprKind::List(elems)) => {
+                let elems = join(elems.iter().map(|e| self.const_expr(schema, ty, e)));
+
+                match ty.kind() {
+                    ast::TypeNameKind::U8 => format!("{krate}::core::Bytes(::std::vec![{elems}])"),
+                    _ => format!("::std::vec![{elems}]"),
+                }
+            }
+
+            (ast::TypeNameKind::Array(ty, _), ast::ConstExprKind::List(elems)) => {
+                let elems = elems.iter().map(|e| self.const_expr(schema, ty, e));
+                format!("[{}]", join(elems))
+            }
+
+            (ast::TypeNameKind::Set(key), ast::ConstExprKind::List(elems)) => {
+                let elems = elems.iter().map(|e| self.key_const_expr(schema, key, e));
+                format!("{HASH_SET}::from([{}])", join(elems))
+            }
+
+            (ast::TypeNameKind::Map(key, ty), ast::ConstExprKind::Map(entries)) => {
+                let entries = entries.iter().map(|entry| {
+                    let key = self.key_const_expr(schema, key, entry.key());
+                    let value = self.const_expr(schema, ty, entry.value());
+                    format!("({key}, {value})")
+                });
+
+                format!("{HASH_MAP}::from([{}])", join(entries))
+            }
+
+            (ast::TypeNameKind::Map(_, _), ast::ConstExprKind::Struct(_)) => {
+                format!("{HASH_MAP}::new()")
+            }
+
+            (ast::TypeNameKind::Result(ok, err), ast::ConstExprKind::Variant(name, value)) => {
+                if name.value() == "ok" {
+                    format!("{OK}({})", self.const_expr(schema, ok, value))
+                } else {
+                    format!("{ERR}({})", self.const_expr(schema, err, value))
+                }
+            }
+
+            (ast::TypeNameKind::Ref(named_ref), _) => {
+                let (def_schema, def) = self.resolve(schema, named_ref);
+                let path = self.const_type_path(schema, named_ref);
+
+                match (def, expr.kind()) {
+                    (ast::Definition::Struct(struct_def), ast::ConstExprKind::Struct(fields)) => {
+                        let fallback = struct_def.fallback().map(|fallback| {
+                            let ident = format!("r#{}", fallback.name().value());
+                            format!("{ident}: {krate}::core::UnknownFields::new()")
+                        });
+
+                        let fields = struct_def.fields().iter().map(|def_field| {
+                            let ident = format!("r#{}", def_field.name().value());
+
+                            let value = fields
+                                .iter()
+                                .find(|f| f.name().value() == def_field.name().value())
+                                .map(|f| {
+                                    self.const_expr(def_schema, def_field.field_type(), f.value())
+                                });
+
+                            match value {
+                                Some(value) if def_field.required() => format!("{ident}: {value}"),
+                                Some(value) => format!("{ident}: {SOME}({value})"),
+                                None => format!("{ident}: {NONE}"),
+                            }
+                        });
+
+                        format!("{path} {{ {} }}", join(fields.chain(fallback)))
+                    }
+
+                    (ast::Definition::Enum(_), ast::ConstExprKind::Ident(var)) => {
+                        format!("{path}::r#{}", var.value())
+                    }
+
+                    (ast::Definition::Enum(enum_def), ast::ConstExprKind::Variant(var, value)) => {
+                        let var_ty = enum_def
+                            .variants()
+                            .iter()
+                            .find(|v| v.name().value() == var.value())
+                            .and_then(ast::EnumVariant::variant_type)
+                            .unwrap();
+
+                        let value = self.const_expr(def_schema, var_ty, value);
+                        format!("{path}::r#{}({value})", var.value())
+                    }
+
+                    _ => unreachable!(),
+                }
+            }
+
+            _ => unreachable!(),
+        }
+    }
+
+    fn key_const_expr(
+        &self,
+        schema: &Schema,
+        key: &ast::KeyTypeName,
+        expr: &ast::ConstExpr,
+    ) -> String {
+        let krate = self.rust_options.krate;
+
+        if let ast::KeyTypeNameKind::Ref(named_ref) = key.kind() {
+            return self.newtype_const_expr(schema, named_ref, expr);
+        }
+
+        match expr.kind() {
+            ast::ConstExprKind::Int(lit) => lit.value().to_owned(),
+            ast::ConstExprKind::String(lit) => format!("{STRING}::from(\"{}\")", lit.value()),
+
+            ast::ConstExprKind::Uuid(lit) => {
+                format!("{krate}::private::uuid::uuid!(\"{}\")", lit.value())
+            }
+
+            _ => unreachable!(),
+        }
+    }
+
+    fn newtype_const_expr(
+        &self,
+        schema: &Schema,
+        named_ref: &ast::NamedRef,
+        expr: &ast::ConstExpr,
+    ) -> String {
+        let (def_schema, def) = self.resolve(schema, named_ref);
+        let path = self.const_type_path(schema, named_ref);
+
+        let ast::Definition::Newtype(newtype_def) = def else {
+            unreachable!();
+        };
+
+        let value = self.const_expr(def_schema, newtype_def.target_type(), expr);
+        format!("{path}({value})")
+    }
+
+    fn is_newtype(&self, schema: &Schema, named_ref: &ast::NamedRef) -> bool {
+        let (_, def) = self.resolve(schema, named_ref);
+        matches!(def, ast::Definition::Newtype(_))
+    }
+
+    /// Checks whether `ty`, which is used in `schema`, can be used as the key of maps and sets.
+    fn is_key_type(&self, schema: &Schema, ty: &ast::TypeName) -> bool {
+        match ty.kind() {
+            ast::TypeNameKind::U8
+            | ast::TypeNameKind::I8
+            | ast::TypeNameKind::U16
+            | ast::TypeNameKind::I16
+            | ast::TypeNameKind::U32
+            | ast::TypeNameKind::I32
+            | ast::TypeNameKind::U64
+            | ast::TypeNameKind::I64
+            | ast::TypeNameKind::String
+            | ast::TypeNameKind::Uuid => true,
+
+            ast::TypeNameKind::Ref(named_ref) => match self.resolve(schema, named_ref) {
+                (schema, ast::Definition::Newtype(newtype_def)) => {
+                    self.is_key_type(schema, newtype_def.target_type())
+                }
+
+                _ => false,
+            },
+
+            _ => false,
+        }
+    }
+
+    fn resolve<'b>(
+        &'b self,
+        schema: &'b Schema,
+        named_ref: &ast::NamedRef,
+    ) -> (&'b Schema, &'b ast::Definition) {
+        let schema = match named_ref.kind() {
+            ast::NamedRefKind::Intern(_) => schema,
+            ast::NamedRefKind::Extern(m, _) => self.parsed.get_schema(m.value()).unwrap(),
+        };
+
+        let def = schema
+            .definitions()
+            .iter()
+            .find(|def| def.name().value() == named_ref.ident().value())
+            .unwrap();
+
+        (schema, def)
+    }
+
+    fn const_type_path(&self, schema: &Schema, named_ref: &ast::NamedRef) -> String {
+        match named_ref.kind() {
+            ast::NamedRefKind::Intern(ty) if schema.name() != self.schema.name() => {
+                format!("super::r#{}::r#{}", schema.name(), ty.value())
+            }
+
+            _ => self.named_ref_name(named_ref),
+        }
+    }
+
     fn key_type_name(&self, ty: &ast::KeyTypeName) -> String {
         let krate = self.rust_options.krate;
 
@@ -816,10 +1455,33 @@ impl RustGenerator<'_> {
             ast::KeyTypeNameKind::I64 => I64.to_owned(),
             ast::KeyTypeNameKind::String => STRING.to_owned(),
             ast::KeyTypeNameKind::Uuid => format!("{krate}::private::uuid::Uuid"),
+            ast::KeyTypeNameKind::Ref(ty) => self.named_ref_name(ty),
         }
     }
 }
 
+fn is_const_type(ty: &ast::TypeName) -> bool {
+    matches!(
+        ty.kind(),
+        ast::TypeNameKind::Bool
+            | ast::TypeNameKind::U8
+            | ast::TypeNameKind::I8
+            | ast::TypeNameKind::U16
+            | ast::TypeNameKind::I16
+            | ast::TypeNameKind::U32
+            | ast::TypeNameKind::I32
+            | ast::TypeNameKind::U64
+            | ast::TypeNameKind::I64
+            | ast::TypeNameKind::F32
+            | ast::TypeNameKind::F64
+            | ast::TypeNameKind::Uuid
+    )
+}
+
+fn join<I: Iterator<Item = String>>(iter: I) -> String {
+    iter.collect::<Vec<_>>().join(", ")
+}
+
 fn struct_builder_name(base: &str) -> String {
     format!("{base}Builder")
 }
@@ -835,6 +1497,8 @@ struct RustAttributes {
     impl_partial_ord: bool,
     impl_ord: bool,
     impl_hash: bool,
+    derives: Vec<String>,
+    attrs: Vec<String>,
 }
 
 impl RustAttributes {
@@ -846,6 +1510,8 @@ impl RustAttributes {
             impl_partial_ord: false,
             impl_ord: false,
             impl_hash: false,
+            derives: Vec::new(),
+            attrs: Vec::new(),
         }
     }
 
@@ -853,24 +1519,45 @@ impl RustAttributes {
         let mut res = Self::new();
 
         for attr in attrs {
-            if attr.name().value() != "rust" {
-                continue;
-            }
-
-            for opt in attr.options() {
-                match opt.value() {
-                    "impl_copy" => res.impl_copy = true,
-                    "impl_partial_eq" => res.impl_partial_eq = true,
-                    "impl_eq" => res.impl_eq = true,
-                    "impl_partial_ord" => res.impl_partial_ord = true,
-                    "impl_ord" => res.impl_ord = true,
-                    "impl_hash" => res.impl_hash = true,
+            match attr.name().value() {
+                "rust" => res.parse_rust(attr),
+                "rust_attrs" => res.parse_rust_attrs(attr),
+                _ => {}
+            }
+        }
+
+        res
+    }
+
+    fn parse_rust(&mut self, attr: &ast::Attribute) {
+        for opt in attr.options() {
+            match opt.kind() {
+                ast::AttributeOptionKind::Ident(ident) => match ident.value() {
+                    "impl_copy" => self.impl_copy = true,
+                    "impl_partial_eq" => self.impl_partial_eq = true,
+                    "impl_eq" => self.impl_eq = true,
+                    "impl_partial_ord" => self.impl_partial_ord = true,
+                    "impl_ord" => self.impl_ord = true,
+                    "impl_hash" => self.impl_hash = true,
                     _ => {}
+                },
+
+                ast::AttributeOptionKind::List(ident, args) if ident.value() == "derive" => {
+                    self.derives
+                        .extend(args.iter().map(|arg| arg.value().to_owned()));
                 }
+
+                ast::AttributeOptionKind::List(_, _) | ast::AttributeOptionKind::String(_) => {}
             }
         }
+    }
 
-        res
+    fn parse_rust_attrs(&mut self, attr: &ast::Attribute) {
+        for opt in attr.options() {
+            if let ast::AttributeOptionKind::String(lit) = opt.kind() {
+                self.attrs.push(lit.value().replace("\\\"", "\""));
+            }
+        }
     }
 
     fn additional_derives(&self) -> String {
@@ -900,6 +1587,11 @@ impl RustAttributes {
             derives.push_str(", ::std::hash::Hash");
         }
 
+        for derive in &self.derives {
+            derives.push_str(", ");
+            derives.push_str(derive);
+        }
+
         derives
     }
 }
@@ -1,26 +1,57 @@
 use aldrin::core::{
-    Deserialize, DeserializeError, Deserializer, ObjectUuid, Serialize, SerializeError,
-    SerializedValue, Serializer,
+    Deserialize, DeserializeError, Deserializer, ErrorEnvelope, ObjectUuid, Serialize,
+    SerializeError, SerializedValue, Serializer,
 };
-use aldrin::low_level::Proxy;
-use aldrin::Error;
+use aldrin::low_level::{Proxy, ServiceInfo};
+use aldrin::{Error, Promise, ServiceEvent, ServiceFunction, ServiceRuntime};
 use aldrin_test::tokio::TestBroker;
 use futures_util::stream::StreamExt;
+use property::{PropertyEvent, PropertyFunction};
+use reliable::ReliableEvent;
+use retained::RetainedEvent;
+use server::{Counter, CounterAsyncHandler, CounterHandler, CounterProxy, CounterServer};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, Ordering};
+use streaming::{StreamingFunction, StreamingItemsItem, StreamingNumbersError};
 use subscribe_all::SubscribeAllEvent;
 use uuid::uuid;
 
 aldrin::generate!("test/all_types.aldrin");
+aldrin::generate!("test/arrays.aldrin", introspection = true, serde = true);
 aldrin::generate!("test/before_derive_compat.aldrin");
 aldrin::generate!("test/constants.aldrin");
+aldrin::generate!("test/doc_strings.aldrin");
+aldrin::generate!("test/error_envelope.aldrin", introspection = true);
 aldrin::generate!("test/extern.aldrin", introspection = true);
+
+aldrin::generate!("test/fallback.aldrin", introspection = true, serde = true);
+
 aldrin::generate!("test/generic_struct.aldrin");
 aldrin::generate!("test/introspection.aldrin", introspection = true);
+
+aldrin::generate!(
+    "test/newtypes.aldrin",
+    include = "test",
+    introspection = true
+);
+
 aldrin::generate!("test/old_new.aldrin");
 aldrin::generate!("test/options.aldrin");
+aldrin::generate!("test/property.aldrin");
+aldrin::generate!("test/reliable.aldrin");
 aldrin::generate!("test/result.aldrin");
+aldrin::generate!("test/retained.aldrin");
+aldrin::generate!("test/rust_attrs.aldrin");
+aldrin::generate!("test/serde.aldrin", serde = true);
+aldrin::generate!("test/server.aldrin");
+aldrin::generate!("test/streaming.aldrin");
 aldrin::generate!("test/subscribe_all.aldrin");
 aldrin::generate!("test/test1.aldrin");
+aldrin::generate!("test/typed_constants.aldrin", include = "test");
 aldrin::generate!("test/unit.aldrin");
+aldrin::generate!("test/versions.aldrin");
 
 aldrin::generate!(
     "test/raw_identifiers.aldrin",
@@ -58,10 +89,10 @@ async fn auto_reply_with_invalid_args() {
     let proxy = Proxy::new(&client, svc.id()).await.unwrap();
     tokio::spawn(async move { while svc.next().await.is_some() {} });
 
-    let res = proxy.call(1, &0).await;
+    let res = proxy.call(1, &0).await.map_err(Error::without_context);
     assert_eq!(res, Err(Error::invalid_arguments(1, None)));
 
-    let res = proxy.call(2, &()).await;
+    let res = proxy.call(2, &()).await.map_err(Error::without_context);
     assert_eq!(res, Err(Error::invalid_arguments(2, None)));
 }
 
@@ -75,7 +106,7 @@ async fn auto_reply_with_invalid_function() {
     let proxy = Proxy::new(&client, svc.id()).await.unwrap();
     tokio::spawn(async move { while svc.next().await.is_some() {} });
 
-    let res = proxy.call(3, &()).await;
+    let res = proxy.call(3, &()).await.map_err(Error::without_context);
     assert_eq!(res, Err(Error::invalid_function(3)));
 }
 
@@ -96,6 +127,72 @@ fn constants() {
     );
 }
 
+#[test]
+fn newtypes() {
+    use newtypes::{Alias, Game, Level, Names, PlayerId};
+
+    let id = PlayerId(uuid!("5c368dc9-e6d3-4545-86d1-435fe3e771cc"));
+    assert_eq!(newtypes::leader(), id);
+    assert_eq!(newtypes::start(), Level(1));
+    assert_eq!(newtypes::initial()[&id], 0);
+
+    let game = Game::builder()
+        .scores([(id.clone(), 7)].into())
+        .players([id.clone()].into())
+        .aliases([(Alias(id.clone()), Names(vec!["alice".to_owned()]))].into())
+        .level(Level(3))
+        .handles([r#extern::Handle(1)].into())
+        .build();
+
+    let serialized = SerializedValue::serialize(&game).unwrap();
+    let game = serialized.deserialize::<Game>().unwrap();
+    assert_eq!(game.scores.unwrap()[&id], 7);
+    assert!(game.players.unwrap().contains(&id));
+    assert_eq!(game.aliases.unwrap()[&Alias(id.clone())].0, ["alice"]);
+    assert_eq!(game.level, Some(Level(3)));
+    assert!(game.handles.unwrap().contains(&r#extern::Handle(1)));
+
+    // Newtypes are transparent on the wire.
+    assert_eq!(
+        SerializedValue::serialize(&id).unwrap(),
+        SerializedValue::serialize(&id.0).unwrap()
+    );
+}
+
+#[test]
+fn typed_constants() {
+    use typed_constants::{Config, Mode};
+
+    const _: () = assert!(typed_constants::ENABLED);
+    const _: [u8; 4] = [0; typed_constants::CODE_LEN as usize];
+    assert_eq!(typed_constants::RATIO, 1.0);
+    assert_eq!(
+        typed_constants::ID,
+        uuid!("5c368dc9-e6d3-4545-86d1-435fe3e771cc")
+    );
+    assert_eq!(typed_constants::greeting(), "hello");
+    assert_eq!(typed_constants::result(), Err("failed".to_owned()));
+
+    let config: Config = typed_constants::default_config();
+    assert_eq!(config.name, "default");
+    assert_eq!(config.retries, 3);
+    assert_eq!(config.timeout, Some(1.5));
+    assert_eq!(config.tags, Some(vec!["a".to_owned(), "b".to_owned()]));
+    assert_eq!(config.limits.as_ref().unwrap()["y"], 2);
+    assert!(matches!(config.mode, Some(Mode::On(5))));
+    assert_eq!(config.ids, Some([1, 2].into()));
+    assert_eq!(config.data.as_deref().map(|d| &d[..]), Some(&[0, 255][..]));
+    assert_eq!(config.pair, Some([-1, 1]));
+    assert!(config.ext.is_some());
+    assert_eq!(config.code, None);
+
+    let next = config.next.unwrap().unwrap();
+    assert_eq!(next.name, "next");
+    assert!(matches!(next.mode, Some(Mode::Off)));
+    assert!(next.limits.unwrap().is_empty());
+    assert!(next.next.is_none());
+}
+
 #[test]
 fn generic_struct() {
     let s1 = generic_struct::Struct {
@@ -169,6 +266,281 @@ async fn unsubscribe_all() {
     assert!(proxy.next_event().await.is_none());
 }
 
+#[tokio::test]
+async fn retained_event() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = retained::Retained::new(&obj).await.unwrap();
+    let mut proxy = retained::RetainedProxy::new(&client, svc.id())
+        .await
+        .unwrap();
+
+    svc.state(1).unwrap();
+    svc.changed().unwrap();
+    client.sync_broker().await.unwrap();
+
+    proxy.subscribe_all().await.unwrap();
+
+    assert!(matches!(
+        proxy.next_event().await,
+        Some(Ok(RetainedEvent::State(1)))
+    ));
+
+    svc.changed().unwrap();
+
+    assert!(matches!(
+        proxy.next_event().await,
+        Some(Ok(RetainedEvent::Changed))
+    ));
+}
+
+#[tokio::test]
+async fn reliable_event() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = reliable::Reliable::new(&obj).await.unwrap();
+    let mut proxy = reliable::ReliableProxy::new(&client, svc.id())
+        .await
+        .unwrap();
+
+    proxy.subscribe_all().await.unwrap();
+
+    svc.booked(1).unwrap();
+    svc.changed().unwrap();
+
+    assert!(matches!(
+        proxy.next_event_acked().await,
+        Some(Ok(ReliableEvent::Booked(1)))
+    ));
+
+    assert!(matches!(
+        proxy.next_event_acked().await,
+        Some(Ok(ReliableEvent::Changed))
+    ));
+}
+
+#[tokio::test]
+async fn server() {
+    struct Handler(u32);
+
+    impl CounterHandler for Handler {
+        fn get(&mut self, promise: Promise<u32, Infallible>) {
+            promise.ok(self.0).unwrap();
+        }
+
+        fn add(&mut self, args: u32, promise: Promise<(), Infallible>) {
+            self.0 += args;
+            promise.done().unwrap();
+        }
+    }
+
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+    let uuid = ObjectUuid::new_v4();
+
+    let mut server = CounterServer::new(&client, uuid, Handler(0));
+    let join = tokio::spawn(async move {
+        server.serve().await.unwrap();
+        server.into_handler()
+    });
+
+    let (_, [id]) = client
+        .wait_for_specific_object(uuid, &[Counter::UUID])
+        .await
+        .unwrap();
+    let proxy = CounterProxy::new(&client, id).await.unwrap();
+
+    proxy.add(2).await.unwrap().unwrap();
+    proxy.add(3).await.unwrap().unwrap();
+    assert_eq!(proxy.get().await.unwrap(), Ok(5));
+
+    // The server stops when the client shuts down.
+    client.join().await;
+    assert_eq!(join.await.unwrap().0, 5);
+}
+
+#[tokio::test]
+async fn async_server() {
+    struct Handler(AtomicU32);
+
+    impl CounterAsyncHandler for Handler {
+        fn get(&self) -> Pin<Box<dyn Future<Output = Result<u32, Infallible>> + Send + '_>> {
+            Box::pin(async { Ok(self.0.load(Ordering::Relaxed)) })
+        }
+
+        fn add(
+            &self,
+            args: u32,
+        ) -> Pin<Box<dyn Future<Output = Result<(), Infallible>> + Send + '_>> {
+            Box::pin(async move {
+                self.0.fetch_add(args, Ordering::Relaxed);
+                Ok(())
+            })
+        }
+    }
+
+    let mut broker = TestBroker::new();
+    let mut client = broker.add_client().await;
+    let uuid = ObjectUuid::new_v4();
+
+    let mut server = CounterServer::new(&client, uuid, Handler(AtomicU32::new(0)));
+    let join = tokio::spawn(async move {
+        server.serve_async().await.unwrap();
+        server.into_handler()
+    });
+
+    let (_, [id]) = client
+        .wait_for_specific_object(uuid, &[Counter::UUID])
+        .await
+        .unwrap();
+    let proxy = CounterProxy::new(&client, id).await.unwrap();
+
+    proxy.add(2).await.unwrap().unwrap();
+    proxy.add(3).await.unwrap().unwrap();
+    assert_eq!(proxy.get().await.unwrap(), Ok(5));
+
+    client.join().await;
+    assert_eq!(join.await.unwrap().0.into_inner(), 5);
+}
+
+#[tokio::test]
+async fn property() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = property::Property::new(&obj).await.unwrap();
+    svc.set_volume(10).unwrap();
+    assert_eq!(svc.volume(), Some(&10));
+    assert_eq!(svc.name(), None);
+
+    let mut proxy = property::PropertyProxy::new(&client, svc.id())
+        .await
+        .unwrap();
+    let mut watch = proxy.watch_volume();
+
+    tokio::spawn(async move {
+        while let Some(Ok(call)) = svc.next_call().await {
+            match call {
+                PropertyFunction::SetVolume(volume, promise) => {
+                    svc.set_volume(volume).unwrap();
+                    promise.done().unwrap();
+                }
+            }
+        }
+    });
+
+    // Subscribing fetches the current value. The fetch call is answered by the generated service.
+    proxy.subscribe_volume().await.unwrap();
+    assert!(matches!(
+        proxy.next_event().await,
+        Some(Ok(PropertyEvent::Volume(Some(10))))
+    ));
+    assert_eq!(proxy.volume(), Some(&10));
+    assert_eq!(proxy.name(), None);
+    assert!(watch.changed().await);
+    assert_eq!(watch.get(), Some(10));
+
+    proxy.set_volume(20).await.unwrap().unwrap();
+    assert!(matches!(
+        proxy.next_event().await,
+        Some(Ok(PropertyEvent::Volume(Some(20))))
+    ));
+    assert_eq!(proxy.volume(), Some(&20));
+    assert_eq!(watch.get(), Some(20));
+
+    assert_eq!(
+        proxy.functions(),
+        [
+            ServiceFunction::new(1, "set_volume"),
+            ServiceFunction::new(2, "volume"),
+            ServiceFunction::new(3, "name"),
+        ]
+    );
+    assert_eq!(
+        proxy.events(),
+        [ServiceEvent::new(2, "volume"), ServiceEvent::new(3, "name")]
+    );
+}
+
+#[tokio::test]
+async fn acked_event() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = retained::Retained::new(&obj).await.unwrap();
+    let mut proxy = retained::RetainedProxy::new(&client, svc.id())
+        .await
+        .unwrap();
+
+    assert_eq!(svc.changed_acked().await, Ok(0));
+
+    proxy.subscribe_changed().await.unwrap();
+    assert_eq!(svc.changed_acked().await, Ok(1));
+
+    assert!(matches!(
+        proxy.next_event().await,
+        Some(Ok(RetainedEvent::Changed))
+    ));
+}
+
+#[tokio::test]
+async fn event_subscriptions() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = subscribe_all::SubscribeAll::new(&obj).await.unwrap();
+    let proxy = subscribe_all::SubscribeAllProxy::new(&client, svc.id())
+        .await
+        .unwrap();
+
+    let mut subs = svc.event_subscriptions().await.unwrap();
+    assert!(!subs.has_subscribers_ev1());
+    assert!(!subs.has_subscribers_ev2());
+
+    proxy.subscribe_ev1().await.unwrap();
+    subs.subscribed_ev1().await.unwrap();
+    assert!(subs.has_subscribers_ev1());
+    assert!(!subs.has_subscribers_ev2());
+
+    proxy.unsubscribe_ev1().await.unwrap();
+    subs.unsubscribed_ev1().await;
+    assert!(!subs.has_subscribers_ev1());
+}
+
+#[tokio::test]
+async fn event_streams() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = retained::Retained::new(&obj).await.unwrap();
+    let proxy = retained::RetainedProxy::new(&client, svc.id())
+        .await
+        .unwrap();
+
+    let mut state = proxy.state_events().await.unwrap();
+    let mut changed = proxy.changed_events().await.unwrap();
+
+    svc.state(1).unwrap();
+    svc.changed().unwrap();
+    svc.state(2).unwrap();
+
+    assert_eq!(state.next_event().await, Some(Ok(1)));
+    assert_eq!(changed.next_event().await, Some(Ok(())));
+    assert_eq!(state.next_event().await, Some(Ok(2)));
+
+    svc.destroy().await.unwrap();
+    assert_eq!(state.next_event().await, None);
+    assert_eq!(changed.next_event().await, None);
+}
+
 #[tokio::test]
 async fn before_derive_compat_struct() {
     use before_derive_compat::NewStruct;
@@ -394,3 +766,382 @@ async fn before_derive_compat_enum() {
     let old = serialized.deserialize::<OldEnum>().unwrap();
     assert_eq!(new, old);
 }
+
+#[tokio::test]
+async fn streaming_function() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = streaming::Streaming::new(&obj).await.unwrap();
+    let proxy = streaming::StreamingProxy::new(&client, svc.id())
+        .await
+        .unwrap();
+
+    tokio::spawn(async move {
+        while let Some(Ok(call)) = svc.next_call().await {
+            match call {
+                StreamingFunction::Numbers(n, promise) if n > 3 => {
+                    promise.err(&StreamingNumbersError::TooMany).unwrap();
+                }
+
+                StreamingFunction::Numbers(n, promise) => {
+                    let mut sender = promise.stream().await.unwrap();
+
+                    for i in 0..n {
+                        sender.send_item(i).await.unwrap();
+                    }
+                }
+
+                StreamingFunction::Items(promise) => {
+                    let mut sender = promise.stream().await.unwrap();
+
+                    let item = StreamingItemsItem {
+                        name: "foo".to_owned(),
+                    };
+
+                    sender.send_item(&item).await.unwrap();
+                }
+            }
+        }
+    });
+
+    let stream = proxy.numbers(3).await.unwrap().unwrap();
+    let items = stream.map(Result::unwrap).collect::<Vec<_>>().await;
+    assert_eq!(items, [0, 1, 2]);
+
+    let res = proxy.numbers(4).await.unwrap();
+    assert!(matches!(res, Err(StreamingNumbersError::TooMany)));
+
+    let mut stream = proxy.items().await.unwrap().unwrap();
+    let item = stream.next().await.unwrap().unwrap();
+    assert_eq!(item.name, "foo");
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn function_aborted() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = test1::Test1::new(&obj).await.unwrap();
+    let proxy = test1::Test1Proxy::new(&client, svc.id()).await.unwrap();
+
+    let reply = proxy.with_args(1);
+    let mut call = svc.next_call().await.unwrap().unwrap();
+    assert!(!call.is_aborted());
+
+    reply.abort();
+    call.aborted().await;
+    assert!(call.is_aborted());
+}
+
+#[tokio::test]
+async fn call_builder() {
+    use aldrin::low_level::CallPriority;
+    use std::time::Duration;
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = test1::Test1::new(&obj).await.unwrap();
+    let proxy = test1::Test1Proxy::new(&client, svc.id()).await.unwrap();
+
+    let reply = proxy.with_args_with(1).priority(CallPriority::High).call();
+    let Some(Ok(test1::Test1Function::WithArgs(1, promise))) = svc.next_call().await else {
+        panic!("unexpected call");
+    };
+    promise.done().unwrap();
+    assert_eq!(reply.await, Ok(Ok(())));
+
+    // The test client has no timer.
+    let reply = proxy.no_args_with().timeout(Duration::from_secs(1)).call();
+    assert_eq!(reply.await, Err(Error::NotSupported));
+}
+
+#[tokio::test]
+async fn service_runtime() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc1 = subscribe_all::SubscribeAll::new(&obj).await.unwrap();
+    let svc2 = streaming::Streaming::new(&obj).await.unwrap();
+    let proxy = streaming::StreamingProxy::new(&client, svc2.id())
+        .await
+        .unwrap();
+
+    let runtimes: [&dyn ServiceRuntime; 2] = [&svc1, &proxy];
+
+    assert_eq!(runtimes[0].name(), "SubscribeAll");
+    assert_eq!(runtimes[0].uuid(), subscribe_all::SubscribeAll::UUID);
+    assert_eq!(runtimes[0].version(), 1);
+    assert_eq!(runtimes[0].id(), svc1.id());
+    assert!(runtimes[0].functions().is_empty());
+    assert_eq!(
+        runtimes[0].events(),
+        [ServiceEvent::new(1, "ev1"), ServiceEvent::new(2, "ev2")]
+    );
+
+    assert_eq!(runtimes[1].name(), "Streaming");
+    assert_eq!(runtimes[1].uuid(), streaming::StreamingProxy::UUID);
+    assert_eq!(
+        runtimes[1].functions(),
+        [
+            ServiceFunction::new(1, "numbers"),
+            ServiceFunction::new(2, "items")
+        ]
+    );
+    assert!(runtimes[1].events().is_empty());
+}
+
+#[test]
+fn error_envelope() {
+    let err = ErrorEnvelope::from(error_envelope::Error::NotFound("foo".to_owned()));
+    assert_eq!(err.code(), 1);
+    assert_eq!(err.message(), "NotFound");
+    assert_eq!(
+        err.deserialize_details::<String>(),
+        Some(Ok("foo".to_owned()))
+    );
+
+    let err = ErrorEnvelope::from(error_envelope::Error::Busy);
+    assert_eq!(err.code(), 2);
+    assert_eq!(err.message(), "Busy");
+    assert_eq!(err.details(), None);
+
+    let err = ErrorEnvelope::from(error_envelope::ErrorEnvelopePutError::Invalid(7));
+    assert_eq!(err.code(), 2);
+    assert_eq!(err.message(), "Invalid");
+    assert_eq!(err.deserialize_details::<u32>(), Some(Ok(7)));
+}
+
+#[test]
+fn fallback_field_preserves_unknown_fields() {
+    let new = fallback::New {
+        f1: 1,
+        f2: Some("foo".to_owned()),
+    };
+    let serialized = SerializedValue::serialize(&new).unwrap();
+
+    let old: fallback::Old = serialized.deserialize().unwrap();
+    assert_eq!(old.f1, 1);
+    assert_eq!(old.unknown_fields.len(), 1);
+
+    let serialized = SerializedValue::serialize(&old).unwrap();
+    let new: fallback::New = serialized.deserialize().unwrap();
+    assert_eq!(new.f1, 1);
+    assert_eq!(new.f2.as_deref(), Some("foo"));
+
+    let old = fallback::Old::builder().f1(2).build().unwrap();
+    assert!(old.unknown_fields.is_empty());
+    assert!(fallback::default_old().unknown_fields.is_empty());
+}
+
+#[test]
+fn fallback_variant_preserves_unknown_variants() {
+    let new = fallback::NewEnum::Var2("foo".to_owned());
+    let serialized = SerializedValue::serialize(&new).unwrap();
+
+    let old: fallback::OldEnum = serialized.deserialize().unwrap();
+    let fallback::OldEnum::Unknown(ref unknown) = old else {
+        panic!("expected the fallback variant");
+    };
+    assert_eq!(unknown.id(), 2);
+
+    let serialized = SerializedValue::serialize(&old).unwrap();
+    let new: fallback::NewEnum = serialized.deserialize().unwrap();
+    assert!(matches!(new, fallback::NewEnum::Var2(ref s) if s == "foo"));
+}
+
+#[test]
+fn fallback_error_envelope() {
+    let unknown =
+        aldrin::core::UnknownVariant::from_parts(3, SerializedValue::serialize("foo").unwrap());
+
+    let err = ErrorEnvelope::from(fallback::FallbackForwardError::Unknown(unknown));
+    assert_eq!(err.code(), 3);
+    assert_eq!(err.message(), "Unknown");
+    assert_eq!(
+        err.deserialize_details::<String>(),
+        Some(Ok("foo".to_owned()))
+    );
+}
+
+#[tokio::test]
+async fn service_versions() {
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = versions::Versions::new(&obj).await.unwrap();
+    let proxy = versions::VersionsProxy::new(&client, svc.id())
+        .await
+        .unwrap();
+
+    assert!(proxy.supports_old());
+    assert!(proxy.supports_added());
+    assert!(proxy.supports_old_event());
+    assert!(proxy.supports_added_event());
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let svc = obj
+        .create_service(versions::Versions::UUID, ServiceInfo::new(1))
+        .await
+        .unwrap();
+    let proxy = versions::VersionsProxy::new(&client, svc.id())
+        .await
+        .unwrap();
+
+    assert!(proxy.supports_old());
+    assert!(!proxy.supports_added());
+    assert!(proxy.supports_old_event());
+    assert!(!proxy.supports_added_event());
+
+    let res = versions::VersionsProxy::new_with_min_version(&client, svc.id(), 1).await;
+    assert!(res.is_ok());
+
+    let res = versions::VersionsProxy::new_with_min_version(&client, svc.id(), 2).await;
+    assert_eq!(res.unwrap_err(), Error::unsupported_version(2, 1));
+}
+
+#[test]
+fn serde() {
+    let item = serde::Item::builder()
+        .id(serde::Id(uuid!("a4c1b0e2-5d3f-4a8b-9c6e-7f1d2e3b4a50")))
+        .name("foo".to_owned())
+        .data(aldrin::core::Bytes::new([1, 2, 3]))
+        .tags(vec![
+            serde::Tag::None,
+            serde::Tag::Label("bar".to_owned()),
+            serde::Tag::Value(4),
+        ])
+        .build()
+        .unwrap();
+
+    let json = serde_json::to_value(&item).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "id": "a4c1b0e2-5d3f-4a8b-9c6e-7f1d2e3b4a50",
+            "name": "foo",
+            "data": [1, 2, 3],
+            "tags": ["None", { "Label": "bar" }, { "Value": 4 }],
+        })
+    );
+
+    let item2: serde::Item = serde_json::from_value(json).unwrap();
+    assert_eq!(item2.id, item.id);
+    assert_eq!(item2.name, item.name);
+    assert_eq!(item2.data, item.data);
+
+    let args = serde::StorePutArgs::builder().item(item).build().unwrap();
+    let json = serde_json::to_value(&args).unwrap();
+    assert!(json.get("replace").is_none());
+
+    let args: serde::StorePutArgs = serde_json::from_value(json).unwrap();
+    assert_eq!(args.replace, None);
+}
+
+#[test]
+fn arrays() {
+    use aldrin::core::introspection::{Introspectable, LexicalId};
+
+    let value = arrays::Arrays {
+        small: [1, 2, 3, 4],
+        large: std::array::from_fn(|i| i as u32),
+        sized: std::array::from_fn(|i| i.to_string()),
+        nested: std::array::from_fn(|i| [i as i16, -(i as i16)]),
+        items: [
+            arrays::Item { id: Some(1) },
+            arrays::Item { id: None },
+            arrays::Item { id: Some(3) },
+        ],
+        optional: Some([5; 48]),
+    };
+
+    let serialized = SerializedValue::serialize(&value).unwrap();
+    let value2 = serialized.deserialize::<arrays::Arrays>().unwrap();
+    assert_eq!(value2.small, value.small);
+    assert_eq!(value2.large, value.large);
+    assert_eq!(value2.sized, value.sized);
+    assert_eq!(value2.nested, value.nested);
+    assert_eq!(value2.items[2].id, Some(3));
+    assert_eq!(value2.optional, value.optional);
+
+    let serialized = SerializedValue::serialize(&vec![0u8; 4]).unwrap();
+    assert!(serialized.deserialize::<arrays::Key>().is_err());
+
+    let layout = arrays::Arrays::layout();
+    let fields = layout.as_struct().unwrap().fields();
+    assert_eq!(
+        fields[&2].field_type(),
+        LexicalId::array(LexicalId::U32, 64)
+    );
+    assert_eq!(
+        fields[&3].field_type(),
+        LexicalId::array(LexicalId::STRING, 33)
+    );
+
+    let json = serde_json::to_value(&value).unwrap();
+    assert_eq!(json["large"].as_array().unwrap().len(), 64);
+    assert_eq!(json["nested"][39], serde_json::json!([39, -39]));
+    let value2: arrays::Arrays = serde_json::from_value(json).unwrap();
+    assert_eq!(value2.large, value.large);
+    assert_eq!(value2.optional, value.optional);
+
+    let json = serde_json::to_value(arrays::Payload::Block([7; 512])).unwrap();
+    let arrays::Payload::Block(block) = serde_json::from_value(json).unwrap() else {
+        panic!("unexpected variant");
+    };
+    assert_eq!(block, [7; 512]);
+
+    let json = serde_json::to_value(arrays::Key([1; 64])).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 64);
+    assert!(serde_json::from_value::<arrays::Key>(serde_json::json!([1, 2])).is_err());
+}
+
+#[test]
+fn rust_attrs() {
+    use std::collections::HashSet;
+
+    let p1 = rust_attrs::Point { x: 1, y: 2 };
+    let p2 = rust_attrs::Point { x: 1, y: 3 };
+    assert!(p1 < p2);
+
+    let set = HashSet::from([p1.clone(), p2, p1]);
+    assert_eq!(set.len(), 2);
+
+    assert_eq!(rust_attrs::Color::Red as u8, 0);
+    assert_eq!(rust_attrs::Color::Green, rust_attrs::Color::Green);
+
+    assert_eq!(rust_attrs::Count::default().0, 0);
+}
+
+#[test]
+fn imported_schema() {
+    use crate::{Generator, Options, RustOptions};
+    use aldrin_parser::Parser;
+
+    let mut parser = Parser::new();
+    parser.add_schema_path("test");
+    let options = Options::new();
+    let rust_options = RustOptions::new();
+
+    let parsed = parser.parse("test/newtypes.aldrin");
+    assert!(parsed.errors().is_empty());
+    let imported = Generator::new(&options, &parsed)
+        .generate_rust_schema("extern", &rust_options)
+        .unwrap();
+
+    let parsed = parser.parse("test/extern.aldrin");
+    assert!(parsed.errors().is_empty());
+    let main = Generator::new(&options, &parsed)
+        .generate_rust(&rust_options)
+        .unwrap();
+
+    assert_eq!(imported.module_name, "extern");
+    assert_eq!(imported.module_content, main.module_content);
+}
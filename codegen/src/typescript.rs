@@ -0,0 +1,1083 @@
+#[cfg(test)]
+mod test;
+
+use crate::error::Error;
+use crate::Options;
+use aldrin_parser::{ast, Parsed, Schema};
+use heck::{ToLowerCamelCase, ToUpperCamelCase};
+use std::fmt::Write;
+
+const KEYWORDS: &[&str] = &[
+    "any",
+    "await",
+    "bigint",
+    "boolean",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "never",
+    "new",
+    "null",
+    "number",
+    "object",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "string",
+    "super",
+    "switch",
+    "symbol",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "unknown",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct TypeScriptOptions<'a> {
+    pub package: &'a str,
+}
+
+impl TypeScriptOptions<'_> {
+    pub fn new() -> Self {
+        TypeScriptOptions { package: "aldrin" }
+    }
+}
+
+impl Default for TypeScriptOptions<'_> {
+    fn default() -> Self {
+        TypeScriptOptions::new()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeScriptOutput {
+    pub module_name: String,
+    pub module_content: String,
+}
+
+pub(crate) fn generate(
+    parsed: &Parsed,
+    options: &Options,
+    ts_options: &TypeScriptOptions,
+) -> Result<TypeScriptOutput, Error> {
+    let schema = parsed.main_schema();
+
+    let generator = TypeScriptGenerator {
+        parsed,
+        schema,
+        options,
+        ts_options,
+        output: TypeScriptOutput {
+            module_name: schema.name().to_owned(),
+            module_content: String::new(),
+        },
+    };
+
+    generator.generate()
+}
+
+struct TypeScriptGenerator<'a> {
+    parsed: &'a Parsed,
+    schema: &'a Schema,
+    options: &'a Options,
+    ts_options: &'a TypeScriptOptions<'a>,
+    output: TypeScriptOutput,
+}
+
+macro_rules! code {
+    ($this:expr, $arg:literal) => {
+        write!($this.output.module_content, $arg).unwrap()
+    };
+}
+
+macro_rules! codeln {
+    ($this:expr) => {
+        writeln!($this.output.module_content).unwrap()
+    };
+
+    ($this:expr, $arg:literal) => {
+        writeln!($this.output.module_content, $arg).unwrap()
+    };
+}
+
+#[rustfmt::skip::macros(code, codeln)]
+impl TypeScriptGenerator<'_> {
+    fn generate(mut self) -> Result<TypeScriptOutput, Error> {
+        let pkg = self.ts_options.package;
+        let schema_name = self.schema.name();
+
+        codeln!(self, "// Generated by aldrin-codegen from schema `{schema_name}`. Do not edit.");
+        codeln!(self);
+        codeln!(self, "import * as aldrin from \"{pkg}\";");
+
+        for import in self.schema.imports() {
+            let module = import.schema_name().value();
+            codeln!(self, "import * as {module} from \"./{module}.js\";");
+        }
+
+        for def in self.schema.definitions() {
+            self.definition(def);
+        }
+
+        Ok(self.output)
+    }
+
+    fn definition(&mut self, def: &ast::Definition) {
+        match def {
+            ast::Definition::Struct(d) => self.struct_def(d.name().value(), d.fields()),
+            ast::Definition::Enum(e) => self.enum_def(e.name().value(), e.variants()),
+            ast::Definition::Service(s) => self.service_def(s),
+            ast::Definition::Const(c) => self.const_def(c),
+            ast::Definition::Newtype(n) => self.newtype_def(n),
+        }
+    }
+
+    fn newtype_def(&mut self, newtype_def: &ast::NewtypeDef) {
+        let pkg = self.ts_options.package;
+        let name = newtype_def.name().value();
+        let newtype_ident = ident(name);
+        let hint = self.type_hint(newtype_def.target_type());
+        let codec = self.codec(newtype_def.target_type());
+        let is_key = self.is_key_type(self.schema, newtype_def.target_type());
+        let codec_ty = if is_key { "KeyCodec" } else { "Codec" };
+
+        codeln!(self);
+        codeln!(self, "export type {newtype_ident} = {hint};");
+        codeln!(self);
+        codeln!(self, "export const {name}Codec: {pkg}.{codec_ty}<{newtype_ident}> = {{");
+
+        // The target's codec is looked up lazily, because it may be defined further below.
+        if is_key {
+            codeln!(self, "    get key(): {pkg}.KeyOps<{newtype_ident}> {{");
+            codeln!(self, "        return {codec}.key;");
+            codeln!(self, "    }},");
+            codeln!(self);
+        }
+
+        codeln!(self, "    serialize(serializer: {pkg}.Serializer, value: {newtype_ident}): void {{");
+        codeln!(self, "        {codec}.serialize(serializer, value);");
+        codeln!(self, "    }},");
+        codeln!(self);
+        codeln!(self, "    deserialize(deserializer: {pkg}.Deserializer): {newtype_ident} {{");
+        codeln!(self, "        return {codec}.deserialize(deserializer);");
+        codeln!(self, "    }},");
+        codeln!(self, "}};");
+    }
+
+    fn struct_def(&mut self, name: &str, fields: &[ast::StructField]) {
+        let pkg = self.ts_options.package;
+        let struct_ident = ident(name);
+
+        codeln!(self);
+        codeln!(self, "export interface {struct_ident} {{");
+
+        for field in fields {
+            let field_name = field.name().value();
+            let hint = self.type_hint(field.field_type());
+
+            if field.required() {
+                codeln!(self, "    {field_name}: {hint};");
+            } else {
+                codeln!(self, "    {field_name}?: {hint};");
+            }
+        }
+
+        codeln!(self, "}}");
+        codeln!(self);
+
+        let num_required = fields.iter().filter(|f| f.required()).count();
+        let value = if fields.is_empty() { "_value" } else { "value" };
+
+        codeln!(self, "export const {name}Codec: {pkg}.Codec<{struct_ident}> = {{");
+        codeln!(self, "    serialize(serializer: {pkg}.Serializer, {value}: {struct_ident}): void {{");
+
+        if num_required == fields.len() {
+            codeln!(self, "        const struct = serializer.serializeStruct({num_required});");
+        } else {
+            codeln!(self, "        let numFields = {num_required};");
+
+            for field in fields.iter().filter(|f| !f.required()) {
+                let field_name = field.name().value();
+                codeln!(self, "        if (value.{field_name} !== undefined) numFields += 1;");
+            }
+
+            codeln!(self, "        const struct = serializer.serializeStruct(numFields);");
+        }
+
+        for field in fields {
+            let id = field.id().value();
+            let field_name = field.name().value();
+            let codec = self.codec(field.field_type());
+
+            if field.required() {
+                codeln!(self, "        struct.serializeField({id}, {codec}, value.{field_name});");
+            } else {
+                // Optional fields are serialized as options, when they are present.
+                codeln!(self, "        if (value.{field_name} !== undefined) {{");
+                codeln!(self, "            struct.serializeField({id}, {pkg}.optional({codec}), value.{field_name});");
+                codeln!(self, "        }}");
+            }
+        }
+
+        codeln!(self, "        struct.finish();");
+        codeln!(self, "    }},");
+        codeln!(self);
+        codeln!(self, "    deserialize(deserializer: {pkg}.Deserializer): {struct_ident} {{");
+        codeln!(self, "        const struct = deserializer.deserializeStruct();");
+
+        for field in fields {
+            let id = field.id().value();
+            let hint = self.type_hint(field.field_type());
+            codeln!(self, "        let field{id}: {hint} | undefined;");
+        }
+
+        codeln!(self);
+        codeln!(self, "        while (struct.hasMoreFields()) {{");
+        codeln!(self, "            const field = struct.deserializeField();");
+        codeln!(self);
+        codeln!(self, "            switch (field.id) {{");
+
+        for field in fields {
+            let id = field.id().value();
+            let mut codec = self.codec(field.field_type());
+
+            if !field.required() {
+                codec = format!("{pkg}.optional({codec})");
+            }
+
+            codeln!(self, "                case {id}:");
+            codeln!(self, "                    field{id} = field.deserialize({codec});");
+            codeln!(self, "                    break;");
+        }
+
+        codeln!(self, "                default:");
+        codeln!(self, "                    field.skip();");
+        codeln!(self, "                    break;");
+        codeln!(self, "            }}");
+        codeln!(self, "        }}");
+        codeln!(self);
+        codeln!(self, "        struct.finish();");
+        codeln!(self);
+        codeln!(self, "        return {{");
+
+        for field in fields {
+            let id = field.id().value();
+            let field_name = field.name().value();
+
+            if field.required() {
+                codeln!(self, "            {field_name}: {pkg}.required(field{id}, {id}),");
+            } else {
+                codeln!(self, "            {field_name}: field{id},");
+            }
+        }
+
+        codeln!(self, "        }};");
+        codeln!(self, "    }},");
+        codeln!(self, "}};");
+    }
+
+    fn enum_def(&mut self, name: &str, vars: &[ast::EnumVariant]) {
+        let pkg = self.ts_options.package;
+        let enum_ident = ident(name);
+
+        codeln!(self);
+
+        if vars.is_empty() {
+            codeln!(self, "export type {enum_ident} = never;");
+        } else {
+            codeln!(self, "export type {enum_ident} =");
+
+            for (i, var) in vars.iter().enumerate() {
+                let var_name = var.name().value();
+
+                if let Some(ty) = var.variant_type() {
+                    let hint = self.type_hint(ty);
+                    code!(self, "    | {{ kind: \"{var_name}\"; value: {hint} }}");
+                } else {
+                    code!(self, "    | {{ kind: \"{var_name}\" }}");
+                }
+
+                if i + 1 == vars.len() {
+                    codeln!(self, ";");
+                } else {
+                    codeln!(self);
+                }
+            }
+        }
+
+        codeln!(self);
+        codeln!(self, "export const {name}Codec: {pkg}.Codec<{enum_ident}> = {{");
+        codeln!(self, "    serialize(serializer: {pkg}.Serializer, value: {enum_ident}): void {{");
+        codeln!(self, "        switch (value.kind) {{");
+
+        for var in vars {
+            let id = var.id().value();
+            let var_name = var.name().value();
+
+            codeln!(self, "            case \"{var_name}\":");
+
+            if let Some(ty) = var.variant_type() {
+                let codec = self.codec(ty);
+                codeln!(self, "                serializer.serializeEnum({id}, {codec}, value.value);");
+            } else {
+                codeln!(self, "                serializer.serializeEnum({id}, {pkg}.UNIT, null);");
+            }
+
+            codeln!(self, "                break;");
+        }
+
+        codeln!(self, "        }}");
+        codeln!(self, "    }},");
+        codeln!(self);
+        codeln!(self, "    deserialize(deserializer: {pkg}.Deserializer): {enum_ident} {{");
+        codeln!(self, "        const variant = deserializer.deserializeEnum();");
+        codeln!(self);
+        codeln!(self, "        switch (variant.id) {{");
+
+        for var in vars {
+            let id = var.id().value();
+            let var_name = var.name().value();
+
+            codeln!(self, "            case {id}:");
+
+            if let Some(ty) = var.variant_type() {
+                let codec = self.codec(ty);
+                codeln!(self, "                return {{ kind: \"{var_name}\", value: variant.deserialize({codec}) }};");
+            } else {
+                codeln!(self, "                variant.deserialize({pkg}.UNIT);");
+                codeln!(self, "                return {{ kind: \"{var_name}\" }};");
+            }
+        }
+
+        codeln!(self, "            default:");
+        codeln!(self, "                throw new {pkg}.DeserializeError(`unknown variant ${{variant.id}}`);");
+        codeln!(self, "        }}");
+        codeln!(self, "    }},");
+        codeln!(self, "}};");
+    }
+
+    fn service_def(&mut self, svc: &ast::ServiceDef) {
+        // Only client-side code is generated for TypeScript.
+        if !self.options.client {
+            return;
+        }
+
+        let svc_name = svc.name().value();
+
+        for item in svc.items() {
+            match item {
+                ast::ServiceItem::Function(func) => {
+                    let func_name = func.name().value();
+
+                    if let Some(args) = func.args() {
+                        let name = function_part_name(svc_name, func_name, "Args");
+                        self.inline_type_def(&name, args.part_type());
+                    }
+
+                    if let Some(ok) = func.ok() {
+                        let name = function_part_name(svc_name, func_name, "Ok");
+                        self.inline_type_def(&name, ok.part_type());
+                    }
+
+                    if let Some(stream) = func.stream() {
+                        let name = function_part_name(svc_name, func_name, "Item");
+                        self.inline_type_def(&name, stream.part_type());
+                    }
+
+                    if let Some(err) = func.err() {
+                        let name = function_part_name(svc_name, func_name, "Error");
+                        self.inline_type_def(&name, err.part_type());
+                    }
+                }
+
+                ast::ServiceItem::Event(ev) => {
+                    if let Some(ty) = ev.event_type() {
+                        let name = event_type_name(svc_name, ev.name().value());
+                        self.inline_type_def(&name, ty);
+                    }
+                }
+
+                // Property types are always named.
+                ast::ServiceItem::Property(_) => {}
+            }
+        }
+
+        self.service_proxy(svc);
+    }
+
+    fn inline_type_def(&mut self, name: &str, ty: &ast::TypeNameOrInline) {
+        match ty {
+            ast::TypeNameOrInline::Struct(s) => self.struct_def(name, s.fields()),
+            ast::TypeNameOrInline::Enum(e) => self.enum_def(name, e.variants()),
+            ast::TypeNameOrInline::TypeName(_) => {}
+        }
+    }
+
+    fn service_proxy(&mut self, svc: &ast::ServiceDef) {
+        let pkg = self.ts_options.package;
+        let svc_name = svc.name().value();
+        let uuid = svc.uuid().value();
+        let version = svc.version().value();
+
+        codeln!(self);
+        codeln!(self, "export class {svc_name}Proxy extends {pkg}.Proxy {{");
+        codeln!(self, "    static readonly UUID = \"{uuid}\";");
+        codeln!(self, "    static readonly VERSION = {version};");
+
+        for item in svc.items() {
+            codeln!(self);
+
+            match item {
+                ast::ServiceItem::Function(func) => {
+                    let func_name = func.name().value();
+                    let method = func_name.to_lower_camel_case();
+                    let id = func.id().value();
+
+                    let (param, args_codec, args) = match func.args() {
+                        Some(args) => {
+                            let hint = self.part_type_hint(svc_name, func_name, "Args", args);
+                            let codec = self.part_codec(svc_name, func_name, "Args", args);
+                            (format!("args: {hint}"), codec, "args")
+                        }
+
+                        None => (String::new(), format!("{pkg}.UNIT"), "null"),
+                    };
+
+                    let err_codec = match func.err() {
+                        Some(err) => self.part_codec(svc_name, func_name, "Error", err),
+                        None => format!("{pkg}.UNIT"),
+                    };
+
+                    match (func.ok(), func.stream()) {
+                        (None, Some(stream)) => {
+                            let item = self.part_type_hint(svc_name, func_name, "Item", stream);
+                            let item_codec = self.part_codec(svc_name, func_name, "Item", stream);
+
+                            codeln!(self, "    {method}({param}): Promise<{pkg}.Receiver<{item}>> {{");
+                            codeln!(self, "        return this._callStream({id}, {args_codec}, {args}, {item_codec}, {err_codec});");
+                        }
+
+                        (ok, _) => {
+                            let (ret, ok_codec) = match ok {
+                                Some(ok) => (
+                                    self.part_type_hint(svc_name, func_name, "Ok", ok),
+                                    self.part_codec(svc_name, func_name, "Ok", ok),
+                                ),
+
+                                None => ("null".to_owned(), format!("{pkg}.UNIT")),
+                            };
+
+                            codeln!(self, "    {method}({param}): Promise<{ret}> {{");
+                            codeln!(self, "        return this._call({id}, {args_codec}, {args}, {ok_codec}, {err_codec});");
+                        }
+                    }
+
+                    codeln!(self, "    }}");
+                }
+
+                ast::ServiceItem::Event(ev) => {
+                    let ev_name = ev.name().value();
+                    let upper = ev_name.to_upper_camel_case();
+                    let id = ev.id().value();
+
+                    let (hint, codec) = match ev.event_type() {
+                        Some(ty) => (
+                            self.event_type_hint(svc_name, ev_name, ty),
+                            self.event_codec(svc_name, ev_name, ty),
+                        ),
+
+                        None => ("null".to_owned(), format!("{pkg}.UNIT")),
+                    };
+
+                    codeln!(self, "    subscribe{upper}(): Promise<void> {{");
+                    codeln!(self, "        return this._subscribe({id});");
+                    codeln!(self, "    }}");
+                    codeln!(self);
+                    codeln!(self, "    unsubscribe{upper}(): Promise<void> {{");
+                    codeln!(self, "        return this._unsubscribe({id});");
+                    codeln!(self, "    }}");
+                    codeln!(self);
+                    codeln!(self, "    on{upper}(listener: (value: {hint}) => void): () => void {{");
+                    codeln!(self, "        return this._onEvent({id}, {codec}, listener);");
+                    codeln!(self, "    }}");
+                }
+
+                ast::ServiceItem::Property(prop) => {
+                    let prop_name = prop.name().value();
+                    let method = prop_name.to_lower_camel_case();
+                    let upper = prop_name.to_upper_camel_case();
+                    let id = prop.id().value();
+                    let codec = self.codec(prop.property_type());
+
+                    // Unset properties are `null`, just like optional values.
+                    let mut hint = self.type_hint(prop.property_type());
+                    if !hint.ends_with(" | null") {
+                        hint.push_str(" | null");
+                    }
+
+                    codeln!(self, "    {method}(): {hint} {{");
+                    codeln!(self, "        return this._property({id});");
+                    codeln!(self, "    }}");
+                    codeln!(self);
+                    codeln!(self, "    subscribe{upper}(): Promise<void> {{");
+                    codeln!(self, "        return this._subscribeProperty({id}, {codec});");
+                    codeln!(self, "    }}");
+                    codeln!(self);
+                    codeln!(self, "    unsubscribe{upper}(): Promise<void> {{");
+                    codeln!(self, "        return this._unsubscribe({id});");
+                    codeln!(self, "    }}");
+                    codeln!(self);
+                    codeln!(self, "    on{upper}(listener: (value: {hint}) => void): () => void {{");
+                    codeln!(self, "        return this._onProperty({id}, listener);");
+                    codeln!(self, "    }}");
+                }
+            }
+        }
+
+        codeln!(self, "}}");
+    }
+
+    fn const_def(&mut self, const_def: &ast::ConstDef) {
+        let name = const_def.name().value();
+
+        let val = match const_def.value() {
+            ast::ConstValue::U8(v) => v.value().to_owned(),
+            ast::ConstValue::I8(v) => v.value().to_owned(),
+            ast::ConstValue::U16(v) => v.value().to_owned(),
+            ast::ConstValue::I16(v) => v.value().to_owned(),
+            ast::ConstValue::U32(v) => v.value().to_owned(),
+            ast::ConstValue::I32(v) => v.value().to_owned(),
+            ast::ConstValue::U64(v) => format!("{}n", v.value()),
+            ast::ConstValue::I64(v) => format!("{}n", v.value()),
+            ast::ConstValue::String(v) => format!("\"{}\"", v.value()),
+            ast::ConstValue::Uuid(v) => format!("\"{}\"", v.value()),
+
+            ast::ConstValue::Typed(v) if !is_const_type(v.type_name()) => {
+                self.const_fn(name, v);
+                return;
+            }
+
+            ast::ConstValue::Typed(v) => self.const_expr(self.schema, v.type_name(), v.expr()),
+        };
+
+        codeln!(self);
+        codeln!(self, "export const {name} = {val};");
+    }
+
+    fn const_fn(&mut self, name: &str, value: &ast::TypedConst) {
+        let fn_ident = ident(&name.to_lower_camel_case());
+        let hint = self.type_hint(value.type_name());
+        let val = self.const_expr(self.schema, value.type_name(), value.expr());
+
+        codeln!(self);
+        codeln!(self, "export function {fn_ident}(): {hint} {{");
+        codeln!(self, "    return {val};");
+        codeln!(self, "}}");
+    }
+
+    /// Returns a TypeScript expression for a constant of type `ty`, which is defined in `schema`.
+    fn const_expr(&self, schema: &Schema, ty: &ast::TypeName, expr: &ast::ConstExpr) -> String {
+        match (ty.kind(), expr.kind()) {
+            (ast::TypeNameKind::Ref(named_ref), _) if self.is_newtype(schema, named_ref) => {
+                self.newtype_const_expr(schema, named_ref, expr)
+            }
+
+            (ast::TypeNameKind::U64 | ast::TypeNameKind::I64, ast::ConstExprKind::Int(lit)) => {
+                format!("{}n", lit.value())
+            }
+
+            (_, ast::ConstExprKind::Int(lit)) => lit.value().to_owned(),
+            (_, ast::ConstExprKind::Float(lit)) => lit.value().to_owned(),
+            (_, ast::ConstExprKind::String(lit)) => format!("\"{}\"", lit.value()),
+            (_, ast::ConstExprKind::Uuid(lit)) => format!("\"{}\"", lit.value()),
+            (ast::TypeNameKind::Bool, ast::ConstExprKind::Ident(ident)) => ident.value().to_owned(),
+
+            (ast::TypeNameKind::Option(_), ast::ConstExprKind::Ident(ident))
+                if ident.value() == "none" =>
+            {
+                "null".to_owned()
+            }
+
+            (ast::TypeNameKind::Option(ty) | ast::TypeNameKind::Box(ty), _) => {
+                self.const_expr(schema, ty, expr)
+            }
+
+            (ast::TypeNameKind::Bytes, ast::ConstExprKind::List(elems)) => {
+                let elems = elems.iter().map(|e| self.const_expr(schema, ty, e));
+                format!("new Uint8Array([{}])", join(elems))
+            }
+
+            (ast::TypeNameKind::Vec(ty), ast::ConstExprKind::List(elems)) => {
+                let elems = join(elems.iter().map(|e| self.const_expr(schema, ty, e)));
+
+                match ty.kind() {
+                    ast::TypeNameKind::U8 => format!("new Uint8Array([{elems}])"),
+                    _ => format!("[{elems}]"),
+                }
+            }
+
+            (ast::TypeNameKind::Array(ty, _), ast::ConstExprKind::List(elems)) => {
+                let elems = elems.iter().map(|e| self.const_expr(schema, ty, e));
+                format!("[{}]", join(elems))
+            }
+
+            (ast::TypeNameKind::Set(key), ast::ConstExprKind::List(elems)) => {
+                let elems = elems.iter().map(|e| self.key_const_expr(schema, key, e));
+                format!("new Set([{}])", join(elems))
+            }
+
+            (ast::TypeNameKind::Map(key, ty), ast::ConstExprKind::Map(entries)) => {
+                let entries = entries.iter().map(|entry| {
+                    let key = self.key_const_expr(schema, key, entry.key());
+                    let value = self.const_expr(schema, ty, entry.value());
+                    format!("[{key}, {value}]")
+                });
+
+                format!("new Map([{}])", join(entries))
+            }
+
+            (ast::TypeNameKind::Map(_, _), ast::ConstExprKind::Struct(_)) => "new Map()".to_owned(),
+
+            (ast::TypeNameKind::Result(ok, err), ast::ConstExprKind::Variant(name, value)) => {
+                if name.value() == "ok" {
+                    let value = self.const_expr(schema, ok, value);
+                    format!("{{ ok: true, value: {value} }}")
+                } else {
+                    let value = self.const_expr(schema, err, value);
+                    format!("{{ ok: false, error: {value} }}")
+                }
+            }
+
+            (ast::TypeNameKind::Ref(named_ref), _) => {
+                let (def_schema, def) = self.resolve(schema, named_ref);
+
+                match (def, expr.kind()) {
+                    (ast::Definition::Struct(_), ast::ConstExprKind::Struct(fields))
+                        if fields.is_empty() =>
+                    {
+                        "{}".to_owned()
+                    }
+
+                    (ast::Definition::Struct(struct_def), ast::ConstExprKind::Struct(fields)) => {
+                        let fields = fields.iter().map(|field| {
+                            let field_ty = struct_def
+                                .fields()
+                                .iter()
+                                .find(|f| f.name().value() == field.name().value())
+                                .unwrap()
+                                .field_type();
+
+                            let field_name = field.name().value();
+                            let value = self.const_expr(def_schema, field_ty, field.value());
+                            format!("{field_name}: {value}")
+                        });
+
+                        format!("{{ {} }}", join(fields))
+                    }
+
+                    (ast::Definition::Enum(_), ast::ConstExprKind::Ident(var)) => {
+                        format!("{{ kind: \"{}\" }}", var.value())
+                    }
+
+                    (ast::Definition::Enum(enum_def), ast::ConstExprKind::Variant(var, value)) => {
+                        let var_ty = enum_def
+                            .variants()
+                            .iter()
+                            .find(|v| v.name().value() == var.value())
+                            .and_then(ast::EnumVariant::variant_type)
+                            .unwrap();
+
+                        let value = self.const_expr(def_schema, var_ty, value);
+                        format!("{{ kind: \"{}\", value: {value} }}", var.value())
+                    }
+
+                    _ => unreachable!(),
+                }
+            }
+
+            _ => unreachable!(),
+        }
+    }
+
+    fn key_const_expr(
+        &self,
+        schema: &Schema,
+        key: &ast::KeyTypeName,
+        expr: &ast::ConstExpr,
+    ) -> String {
+        match (key.kind(), expr.kind()) {
+            (ast::KeyTypeNameKind::Ref(named_ref), _) => {
+                self.newtype_const_expr(schema, named_ref, expr)
+            }
+
+            (
+                ast::KeyTypeNameKind::U64 | ast::KeyTypeNameKind::I64,
+                ast::ConstExprKind::Int(lit),
+            ) => format!("{}n", lit.value()),
+
+            (_, ast::ConstExprKind::Int(lit)) => lit.value().to_owned(),
+            (_, ast::ConstExprKind::String(lit)) => format!("\"{}\"", lit.value()),
+            (_, ast::ConstExprKind::Uuid(lit)) => format!("\"{}\"", lit.value()),
+            _ => unreachable!(),
+        }
+    }
+
+    fn newtype_const_expr(
+        &self,
+        schema: &Schema,
+        named_ref: &ast::NamedRef,
+        expr: &ast::ConstExpr,
+    ) -> String {
+        let (def_schema, def) = self.resolve(schema, named_ref);
+
+        let ast::Definition::Newtype(newtype_def) = def else {
+            unreachable!();
+        };
+
+        self.const_expr(def_schema, newtype_def.target_type(), expr)
+    }
+
+    /// Checks whether `ty` can be used as the key of maps and sets.
+    fn is_key_type(&self, schema: &Schema, ty: &ast::TypeName) -> bool {
+        match ty.kind() {
+            ast::TypeNameKind::U8
+            | ast::TypeNameKind::I8
+            | ast::TypeNameKind::U16
+            | ast::TypeNameKind::I16
+            | ast::TypeNameKind::U32
+            | ast::TypeNameKind::I32
+            | ast::TypeNameKind::U64
+            | ast::TypeNameKind::I64
+            | ast::TypeNameKind::String
+            | ast::TypeNameKind::Uuid => true,
+
+            ast::TypeNameKind::Ref(named_ref) => match self.resolve(schema, named_ref) {
+                (def_schema, ast::Definition::Newtype(newtype_def)) => {
+                    self.is_key_type(def_schema, newtype_def.target_type())
+                }
+
+                _ => false,
+            },
+
+            _ => false,
+        }
+    }
+
+    fn is_newtype(&self, schema: &Schema, named_ref: &ast::NamedRef) -> bool {
+        let (_, def) = self.resolve(schema, named_ref);
+        matches!(def, ast::Definition::Newtype(_))
+    }
+
+    fn resolve<'b>(
+        &'b self,
+        schema: &'b Schema,
+        named_ref: &ast::NamedRef,
+    ) -> (&'b Schema, &'b ast::Definition) {
+        let schema = match named_ref.kind() {
+            ast::NamedRefKind::Intern(_) => schema,
+            ast::NamedRefKind::Extern(m, _) => self.parsed.get_schema(m.value()).unwrap(),
+        };
+
+        let def = schema
+            .definitions()
+            .iter()
+            .find(|def| def.name().value() == named_ref.ident().value())
+            .unwrap();
+
+        (schema, def)
+    }
+
+    fn part_codec(
+        &self,
+        svc_name: &str,
+        func_name: &str,
+        suffix: &str,
+        part: &ast::FunctionPart,
+    ) -> String {
+        match part.part_type() {
+            ast::TypeNameOrInline::TypeName(ty) => self.codec(ty),
+
+            ast::TypeNameOrInline::Struct(_) | ast::TypeNameOrInline::Enum(_) => {
+                format!("{}Codec", function_part_name(svc_name, func_name, suffix))
+            }
+        }
+    }
+
+    fn part_type_hint(
+        &self,
+        svc_name: &str,
+        func_name: &str,
+        suffix: &str,
+        part: &ast::FunctionPart,
+    ) -> String {
+        match part.part_type() {
+            ast::TypeNameOrInline::TypeName(ty) => self.type_hint(ty),
+
+            ast::TypeNameOrInline::Struct(_) | ast::TypeNameOrInline::Enum(_) => {
+                ident(&function_part_name(svc_name, func_name, suffix))
+            }
+        }
+    }
+
+    fn event_codec(&self, svc_name: &str, ev_name: &str, ty: &ast::TypeNameOrInline) -> String {
+        match ty {
+            ast::TypeNameOrInline::TypeName(ty) => self.codec(ty),
+
+            ast::TypeNameOrInline::Struct(_) | ast::TypeNameOrInline::Enum(_) => {
+                format!("{}Codec", event_type_name(svc_name, ev_name))
+            }
+        }
+    }
+
+    fn event_type_hint(&self, svc_name: &str, ev_name: &str, ty: &ast::TypeNameOrInline) -> String {
+        match ty {
+            ast::TypeNameOrInline::TypeName(ty) => self.type_hint(ty),
+
+            ast::TypeNameOrInline::Struct(_) | ast::TypeNameOrInline::Enum(_) => {
+                ident(&event_type_name(svc_name, ev_name))
+            }
+        }
+    }
+
+    /// Returns an expression for the codec, which (de)serializes a type.
+    fn codec(&self, ty: &ast::TypeName) -> String {
+        let pkg = self.ts_options.package;
+
+        match ty.kind() {
+            ast::TypeNameKind::Bool => format!("{pkg}.BOOL"),
+            ast::TypeNameKind::U8 => format!("{pkg}.U8"),
+            ast::TypeNameKind::I8 => format!("{pkg}.I8"),
+            ast::TypeNameKind::U16 => format!("{pkg}.U16"),
+            ast::TypeNameKind::I16 => format!("{pkg}.I16"),
+            ast::TypeNameKind::U32 => format!("{pkg}.U32"),
+            ast::TypeNameKind::I32 => format!("{pkg}.I32"),
+            ast::TypeNameKind::U64 => format!("{pkg}.U64"),
+            ast::TypeNameKind::I64 => format!("{pkg}.I64"),
+            ast::TypeNameKind::F32 => format!("{pkg}.F32"),
+            ast::TypeNameKind::F64 => format!("{pkg}.F64"),
+            ast::TypeNameKind::String => format!("{pkg}.STRING"),
+            ast::TypeNameKind::Uuid => format!("{pkg}.UUID"),
+            ast::TypeNameKind::ObjectId => format!("{pkg}.OBJECT_ID"),
+            ast::TypeNameKind::ServiceId => format!("{pkg}.SERVICE_ID"),
+            ast::TypeNameKind::Value => format!("{pkg}.VALUE"),
+            ast::TypeNameKind::Option(ty) => format!("{pkg}.option({})", self.codec(ty)),
+            ast::TypeNameKind::Box(ty) => self.codec(ty),
+
+            ast::TypeNameKind::Vec(ty) => match ty.kind() {
+                ast::TypeNameKind::U8 => format!("{pkg}.BYTES"),
+                _ => format!("{pkg}.vec({})", self.codec(ty)),
+            },
+
+            ast::TypeNameKind::Bytes => format!("{pkg}.BYTES"),
+
+            ast::TypeNameKind::Map(k, v) => {
+                format!("{pkg}.map({}, {})", self.key_codec(k), self.codec(v))
+            }
+
+            ast::TypeNameKind::Set(ty) => format!("{pkg}.set({})", self.key_codec(ty)),
+            ast::TypeNameKind::Sender(ty) => format!("{pkg}.sender({})", self.codec(ty)),
+            ast::TypeNameKind::Receiver(ty) | ast::TypeNameKind::Broadcast(ty) => {
+                format!("{pkg}.receiver({})", self.codec(ty))
+            }
+
+            ast::TypeNameKind::Lifetime => format!("{pkg}.LIFETIME"),
+            ast::TypeNameKind::Unit => format!("{pkg}.UNIT"),
+
+            ast::TypeNameKind::Result(ok, err) => {
+                format!("{pkg}.result({}, {})", self.codec(ok), self.codec(err))
+            }
+
+            ast::TypeNameKind::Array(ty, len) => {
+                let len = match len.value() {
+                    ast::ArrayLenValue::Literal(len) => len.value().to_owned(),
+                    ast::ArrayLenValue::Ref(named_ref) => match named_ref.kind() {
+                        ast::NamedRefKind::Intern(c) => c.value().to_owned(),
+                        ast::NamedRefKind::Extern(m, c) => format!("{}.{}", m.value(), c.value()),
+                    },
+                };
+
+                format!("{pkg}.array({}, {len})", self.codec(ty))
+            }
+
+            ast::TypeNameKind::Ref(ty) => match ty.kind() {
+                ast::NamedRefKind::Intern(ty) => format!("{}Codec", ty.value()),
+                ast::NamedRefKind::Extern(m, ty) => format!("{}.{}Codec", m.value(), ty.value()),
+            },
+        }
+    }
+
+    fn key_codec(&self, ty: &ast::KeyTypeName) -> String {
+        let pkg = self.ts_options.package;
+
+        match ty.kind() {
+            ast::KeyTypeNameKind::U8 => format!("{pkg}.U8"),
+            ast::KeyTypeNameKind::I8 => format!("{pkg}.I8"),
+            ast::KeyTypeNameKind::U16 => format!("{pkg}.U16"),
+            ast::KeyTypeNameKind::I16 => format!("{pkg}.I16"),
+            ast::KeyTypeNameKind::U32 => format!("{pkg}.U32"),
+            ast::KeyTypeNameKind::I32 => format!("{pkg}.I32"),
+            ast::KeyTypeNameKind::U64 => format!("{pkg}.U64"),
+            ast::KeyTypeNameKind::I64 => format!("{pkg}.I64"),
+            ast::KeyTypeNameKind::String => format!("{pkg}.STRING"),
+            ast::KeyTypeNameKind::Uuid => format!("{pkg}.UUID"),
+
+            ast::KeyTypeNameKind::Ref(ty) => match ty.kind() {
+                ast::NamedRefKind::Intern(ty) => format!("{}Codec", ty.value()),
+                ast::NamedRefKind::Extern(m, ty) => format!("{}.{}Codec", m.value(), ty.value()),
+            },
+        }
+    }
+
+    /// Returns a TypeScript type for a type.
+    fn type_hint(&self, ty: &ast::TypeName) -> String {
+        let pkg = self.ts_options.package;
+
+        match ty.kind() {
+            ast::TypeNameKind::Bool => "boolean".to_owned(),
+
+            ast::TypeNameKind::U8
+            | ast::TypeNameKind::I8
+            | ast::TypeNameKind::U16
+            | ast::TypeNameKind::I16
+            | ast::TypeNameKind::U32
+            | ast::TypeNameKind::I32
+            | ast::TypeNameKind::F32
+            | ast::TypeNameKind::F64 => "number".to_owned(),
+
+            ast::TypeNameKind::U64 | ast::TypeNameKind::I64 => "bigint".to_owned(),
+            ast::TypeNameKind::String | ast::TypeNameKind::Uuid => "string".to_owned(),
+            ast::TypeNameKind::ObjectId => format!("{pkg}.ObjectId"),
+            ast::TypeNameKind::ServiceId => format!("{pkg}.ServiceId"),
+            ast::TypeNameKind::Value => format!("{pkg}.SerializedValue"),
+            ast::TypeNameKind::Option(ty) => format!("{} | null", self.type_hint(ty)),
+            ast::TypeNameKind::Box(ty) => self.type_hint(ty),
+
+            ast::TypeNameKind::Vec(ty) => match ty.kind() {
+                ast::TypeNameKind::U8 => "Uint8Array".to_owned(),
+                _ => format!("Array<{}>", self.type_hint(ty)),
+            },
+
+            ast::TypeNameKind::Bytes => "Uint8Array".to_owned(),
+
+            ast::TypeNameKind::Map(k, v) => {
+                format!("Map<{}, {}>", key_type_hint(k), self.type_hint(v))
+            }
+
+            ast::TypeNameKind::Set(ty) => format!("Set<{}>", key_type_hint(ty)),
+            ast::TypeNameKind::Sender(ty) => format!("{pkg}.UnboundSender<{}>", self.type_hint(ty)),
+
+            ast::TypeNameKind::Receiver(ty) | ast::TypeNameKind::Broadcast(ty) => {
+                format!("{pkg}.UnboundReceiver<{}>", self.type_hint(ty))
+            }
+
+            ast::TypeNameKind::Lifetime => format!("{pkg}.LifetimeId"),
+            ast::TypeNameKind::Unit => "null".to_owned(),
+
+            ast::TypeNameKind::Result(ok, err) => format!(
+                "{pkg}.Result<{}, {}>",
+                self.type_hint(ok),
+                self.type_hint(err)
+            ),
+
+            ast::TypeNameKind::Array(ty, _) => format!("Array<{}>", self.type_hint(ty)),
+
+            ast::TypeNameKind::Ref(ty) => match ty.kind() {
+                ast::NamedRefKind::Intern(ty) => ident(ty.value()),
+                ast::NamedRefKind::Extern(m, ty) => format!("{}.{}", m.value(), ident(ty.value())),
+            },
+        }
+    }
+}
+
+fn ident(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
+
+fn key_type_hint(ty: &ast::KeyTypeName) -> String {
+    match ty.kind() {
+        ast::KeyTypeNameKind::U8
+        | ast::KeyTypeNameKind::I8
+        | ast::KeyTypeNameKind::U16
+        | ast::KeyTypeNameKind::I16
+        | ast::KeyTypeNameKind::U32
+        | ast::KeyTypeNameKind::I32 => "number".to_owned(),
+
+        ast::KeyTypeNameKind::U64 | ast::KeyTypeNameKind::I64 => "bigint".to_owned(),
+        ast::KeyTypeNameKind::String | ast::KeyTypeNameKind::Uuid => "string".to_owned(),
+
+        ast::KeyTypeNameKind::Ref(ty) => match ty.kind() {
+            ast::NamedRefKind::Intern(ty) => ident(ty.value()),
+            ast::NamedRefKind::Extern(m, ty) => format!("{}.{}", m.value(), ident(ty.value())),
+        },
+    }
+}
+
+fn is_const_type(ty: &ast::TypeName) -> bool {
+    matches!(
+        ty.kind(),
+        ast::TypeNameKind::Bool
+            | ast::TypeNameKind::U8
+            | ast::TypeNameKind::I8
+            | ast::TypeNameKind::U16
+            | ast::TypeNameKind::I16
+            | ast::TypeNameKind::U32
+            | ast::TypeNameKind::I32
+            | ast::TypeNameKind::U64
+            | ast::TypeNameKind::I64
+            | ast::TypeNameKind::F32
+            | ast::TypeNameKind::F64
+            | ast::TypeNameKind::String
+            | ast::TypeNameKind::Uuid
+    )
+}
+
+fn join<I: Iterator<Item = String>>(iter: I) -> String {
+    iter.collect::<Vec<_>>().join(", ")
+}
+
+fn function_part_name(svc_name: &str, func_name: &str, suffix: &str) -> String {
+    format!("{svc_name}{}{suffix}", func_name.to_upper_camel_case())
+}
+
+fn event_type_name(svc_name: &str, ev_name: &str) -> String {
+    format!("{svc_name}{}Event", ev_name.to_upper_camel_case())
+}
@@ -0,0 +1,450 @@
+use crate::interop_test;
+use crate::{Generator, Options, TypeScriptOptions, TypeScriptOutput};
+use aldrin::core::tokio::TokioTransport;
+use aldrin_parser::Parser;
+use aldrin_test::tokio::TestBroker;
+use futures_util::{SinkExt, StreamExt};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::Duration;
+use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task;
+use tokio::time;
+use tokio_tungstenite::tungstenite::Message;
+
+fn generate(schema: &str, options: &Options, ts_options: &TypeScriptOptions) -> TypeScriptOutput {
+    let mut parser = Parser::new();
+    parser.add_schema_path("test");
+
+    let parsed = parser.parse(format!("test/{schema}.aldrin"));
+    assert!(parsed.errors().is_empty());
+
+    Generator::new(options, &parsed)
+        .generate_typescript(ts_options)
+        .unwrap()
+}
+
+#[test]
+fn all_schemas() {
+    let options = Options::new();
+    let ts_options = TypeScriptOptions::new();
+
+    for schema in [
+        "all_types",
+        "before_derive_compat",
+        "constants",
+        "extern",
+        "generic_struct",
+        "introspection",
+        "newtypes",
+        "old_new",
+        "options",
+        "raw_identifiers",
+        "reliable",
+        "result",
+        "retained",
+        "server",
+        "streaming",
+        "subscribe_all",
+        "test1",
+        "typed_constants",
+        "unit",
+    ] {
+        let output = generate(schema, &options, &ts_options);
+        assert_eq!(output.module_name, schema);
+        assert!(output
+            .module_content
+            .contains("import * as aldrin from \"aldrin\";\n"));
+    }
+}
+
+#[test]
+fn constants() {
+    let output = generate("constants", &Options::new(), &TypeScriptOptions::new());
+    let content = output.module_content;
+
+    assert!(content.contains("export const CONST_U8 = 1;\n"));
+    assert!(content.contains("export const CONST_U64 = 7n;\n"));
+    assert!(content.contains("export const CONST_STRING = \"string\";\n"));
+    assert!(
+        content.contains("export const CONST_UUID = \"5c368dc9-e6d3-4545-86d1-435fe3e771cc\";\n")
+    );
+}
+
+#[test]
+fn codecs() {
+    let output = generate(
+        "typed_constants",
+        &Options::new(),
+        &TypeScriptOptions::new(),
+    );
+    let content = output.module_content;
+
+    assert!(content.contains("export interface Config {\n"));
+    assert!(content.contains("    timeout?: number;\n"));
+    assert!(content.contains("export const ConfigCodec: aldrin.Codec<Config> = {\n"));
+    assert!(content.contains("struct.serializeField(1, aldrin.STRING, value.name);\n"));
+    assert!(
+        content.contains("struct.serializeField(3, aldrin.optional(aldrin.F64), value.timeout);\n")
+    );
+    assert!(content.contains("            name: aldrin.required(field1, 1),\n"));
+    assert!(content.contains("    | { kind: \"On\"; value: number };\n"));
+    assert!(content.contains("serializer.serializeEnum(2, aldrin.U32, value.value);\n"));
+}
+
+#[test]
+fn proxies() {
+    let output = generate("streaming", &Options::new(), &TypeScriptOptions::new());
+    let content = output.module_content;
+
+    assert!(content.contains("export class StreamingProxy extends aldrin.Proxy {\n"));
+    assert!(content.contains("Promise<aldrin.Receiver<"));
+    assert!(content.contains("return this._callStream("));
+}
+
+#[test]
+fn property_proxies() {
+    let output = generate("property", &Options::new(), &TypeScriptOptions::new());
+    let content = output.module_content;
+
+    assert!(content.contains("    volume(): number | null {\n"));
+    assert!(content.contains("        return this._subscribeProperty(2, aldrin.U32);\n"));
+    assert!(content.contains("    onName(listener: (value: string | null) => void)"));
+}
+
+#[test]
+fn no_client() {
+    let mut options = Options::new();
+    options.client = false;
+
+    let output = generate("streaming", &options, &TypeScriptOptions::new());
+    assert!(!output.module_content.contains("class StreamingProxy"));
+}
+
+#[test]
+fn custom_package() {
+    let mut ts_options = TypeScriptOptions::new();
+    ts_options.package = "@aldrin/client";
+
+    let output = generate("test1", &Options::new(), &ts_options);
+    let content = output.module_content;
+
+    assert!(content.contains("import * as aldrin from \"@aldrin/client\";\n"));
+}
+
+#[test]
+fn keywords() {
+    let output = generate(
+        "raw_identifiers",
+        &Options::new(),
+        &TypeScriptOptions::new(),
+    );
+    let content = output.module_content;
+
+    assert!(content.contains("export interface true_ {\n"));
+    assert!(content.contains("    if: extern.pub;\n"));
+    assert!(content.contains("export type false_ =\n"));
+}
+
+#[test]
+fn typed_constants() {
+    let output = generate(
+        "typed_constants",
+        &Options::new(),
+        &TypeScriptOptions::new(),
+    );
+    let content = output.module_content;
+
+    assert!(content.contains("export const ENABLED = true;\n"));
+    assert!(content.contains("export const CODE_LEN = 4;\n"));
+    assert!(content.contains("aldrin.array(aldrin.U8, CODE_LEN)"));
+    assert!(content.contains("export function defaultConfig(): Config {\n"));
+    assert!(content.contains("mode: { kind: \"On\", value: 5 }"));
+    assert!(content.contains("limits: new Map([[\"x\", 1], [\"y\", 2]])"));
+    assert!(content.contains("{ ok: false, error: \"failed\" }"));
+}
+
+#[test]
+fn newtypes() {
+    let output = generate("newtypes", &Options::new(), &TypeScriptOptions::new());
+    let content = output.module_content;
+
+    assert!(content.contains("export type PlayerId = string;\n"));
+    assert!(content.contains("export const PlayerIdCodec: aldrin.KeyCodec<PlayerId> = {\n"));
+    assert!(content.contains("    get key(): aldrin.KeyOps<PlayerId> {\n"));
+    assert!(content.contains("export const NamesCodec: aldrin.Codec<Names> = {\n"));
+    assert!(content.contains("    scores?: Map<PlayerId, number>;\n"));
+    assert!(content.contains("aldrin.map(PlayerIdCodec, aldrin.U32)"));
+    assert!(content.contains("aldrin.set(extern.HandleCodec)"));
+    assert!(content.contains("    return 1;\n"));
+}
+
+/// Removes the type annotations from generated code, so that it can be run by Node.js directly.
+///
+/// This handles only the constructs emitted by the generator.
+fn strip_types(ts: &str) -> String {
+    let mut js = String::new();
+    let mut lines = ts.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+
+        if trimmed.starts_with("export interface ") {
+            lines.by_ref().find(|line| *line == "}");
+            continue;
+        }
+
+        if trimmed.starts_with("export type ") {
+            let mut line = line;
+            while !line.ends_with(';') {
+                line = lines.next().unwrap();
+            }
+            continue;
+        }
+
+        let stripped = if let Some(decl) = trimmed.strip_prefix("let ") {
+            match decl.split_once(": ") {
+                Some((name, _)) => format!("{indent}let {name};"),
+                None => line.to_owned(),
+            }
+        } else if let Some(decl) = trimmed.strip_prefix("export const ") {
+            match decl.split_once(" = ") {
+                Some((name, value)) => {
+                    let name = name.split(':').next().unwrap();
+                    format!("{indent}export const {name} = {value}")
+                }
+
+                None => line.to_owned(),
+            }
+        } else if let Some(decl) = trimmed.strip_prefix("static readonly ") {
+            format!("{indent}static {decl}")
+        } else if let Some(signature) = strip_signature(trimmed) {
+            format!("{indent}{signature}")
+        } else {
+            line.to_owned()
+        };
+
+        js.push_str(&stripped);
+        js.push('\n');
+    }
+
+    js
+}
+
+/// Strips the types from function and method signatures, e.g. `name(arg: T): U {`.
+fn strip_signature(line: &str) -> Option<String> {
+    let (prefix, rest) = ["export function ", "get ", ""]
+        .into_iter()
+        .find_map(|prefix| Some((prefix, line.strip_prefix(prefix)?)))?;
+
+    let open = rest.find('(')?;
+    let name = &rest[..open];
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    if !line.ends_with(" {") {
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut params = Vec::new();
+    let mut param = String::new();
+    let mut prev = ' ';
+
+    for c in rest[open + 1..].chars() {
+        match c {
+            '(' | '<' | '{' | '[' => depth += 1,
+            '>' if prev == '=' => {}
+            ')' if depth == 0 => break,
+            ')' | '>' | '}' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                params.push(param);
+                param = String::new();
+                continue;
+            }
+            _ => {}
+        }
+
+        param.push(c);
+        prev = c;
+    }
+
+    params.push(param);
+
+    let params = params
+        .iter()
+        .filter_map(|param| param.split(':').next())
+        .map(str::trim)
+        .filter(|param| !param.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Some(format!("{prefix}{name}({params}) {{"))
+}
+
+/// Checks whether Node.js is available and returns the arguments needed for `WebSocket`.
+///
+/// Returns `None` if Node.js or its `WebSocket` client is not available.
+fn node_args() -> Option<&'static [&'static str]> {
+    let check = "process.exit(typeof WebSocket === 'function' ? 0 : 1)";
+
+    for args in [&[][..], &["--experimental-websocket"]] {
+        let status = Command::new("node")
+            .args(args)
+            .args(["-e", check])
+            .stderr(Stdio::null())
+            .status();
+
+        if status.is_ok_and(|status| status.success()) {
+            return Some(args);
+        }
+    }
+
+    eprintln!("skipping test, because Node.js with WebSocket support is not available");
+    None
+}
+
+/// Writes the runtime, the generated `interop` module and the test script into a new directory.
+fn setup_node(test: &str) -> PathBuf {
+    let dir = interop_test::test_dir(test);
+    let runtime = Path::new(env!("CARGO_MANIFEST_DIR")).join("../typescript");
+    let package = dir.join("node_modules/aldrin");
+    fs::create_dir_all(&package).unwrap();
+
+    for file in ["client.js", "index.js", "package.json", "serialization.js"] {
+        fs::copy(runtime.join(file), package.join(file)).unwrap();
+    }
+
+    let output = generate("interop", &Options::new(), &TypeScriptOptions::new());
+    let js = strip_types(&output.module_content);
+    fs::write(dir.join("interop.mjs"), js).unwrap();
+    fs::write(dir.join("main.mjs"), include_str!("test_interop.mjs")).unwrap();
+
+    dir
+}
+
+fn node(dir: &Path, node_args: &[&str], args: &[&str]) -> Command {
+    let mut cmd = Command::new("node");
+    cmd.args(node_args)
+        .arg(dir.join("main.mjs"))
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    cmd
+}
+
+fn assert_node_ok(dir: PathBuf, output: Output) {
+    fs::remove_dir_all(dir).unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert_eq!(output.stdout, b"ok\n");
+}
+
+#[test]
+fn strip_signatures() {
+    assert_eq!(
+        strip_signature("serialize(serializer: aldrin.Serializer, value: Map<K, V>): void {"),
+        Some("serialize(serializer, value) {".to_owned())
+    );
+    assert_eq!(
+        strip_signature("onReset(listener: (value: null) => void): () => void {"),
+        Some("onReset(listener) {".to_owned())
+    );
+    assert_eq!(
+        strip_signature("export function defaultConfig(): Config {"),
+        Some("export function defaultConfig() {".to_owned())
+    );
+    assert_eq!(strip_signature("switch (value.kind) {"), None);
+    assert_eq!(strip_signature("while (struct.hasMoreFields()) {"), None);
+}
+
+#[test]
+fn runtime_round_trip() {
+    let Some(node_args) = node_args() else {
+        return;
+    };
+
+    let dir = setup_node("ts-round-trip");
+    let hex = interop_test::record_hex();
+    let output = node(&dir, node_args, &["round-trip", &hex])
+        .output()
+        .unwrap();
+    assert_node_ok(dir, output);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn runtime_client() {
+    let Some(node_args) = node_args() else {
+        return;
+    };
+
+    let dir = setup_node("ts-client");
+    let mut broker = TestBroker::new();
+    let handle = broker.handle().clone();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port().to_string();
+
+    // Bridges WebSocket connections to the broker. Both sides carry the same byte stream.
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let (broker_side, mut bridge) = io::duplex(1 << 16);
+            let mut handle = handle.clone();
+
+            // The handshake needs the bridge below to be running already.
+            tokio::spawn(async move {
+                let conn = handle
+                    .connect(TokioTransport::new(broker_side))
+                    .await
+                    .unwrap();
+                conn.run().await
+            });
+
+            tokio::spawn(async move {
+                let mut buf = vec![0; 1 << 16];
+
+                loop {
+                    tokio::select! {
+                        msg = ws.next() => match msg {
+                            Some(Ok(Message::Binary(data))) => {
+                                bridge.write_all(&data).await.unwrap()
+                            }
+
+                            Some(Ok(_)) => {}
+                            _ => break,
+                        },
+
+                        len = bridge.read(&mut buf) => match len {
+                            Ok(len) if len > 0 => {
+                                let msg = Message::Binary(buf[..len].to_vec());
+                                if ws.send(msg).await.is_err() {
+                                    break;
+                                }
+                            }
+
+                            _ => break,
+                        },
+                    }
+                }
+            });
+        }
+    });
+
+    let client = broker.add_client().await;
+    interop_test::spawn_service(&client).await;
+
+    let child = node(&dir, node_args, &["client", &port]).spawn().unwrap();
+    let output = time::timeout(
+        Duration::from_secs(30),
+        task::spawn_blocking(|| child.wait_with_output()),
+    )
+    .await
+    .unwrap()
+    .unwrap()
+    .unwrap();
+
+    assert_node_ok(dir, output);
+}
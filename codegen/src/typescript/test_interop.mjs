@@ -0,0 +1,112 @@
+// Driven by the tests in `test.rs`, which generate the `interop` module from
+// `test/interop.aldrin`, strip its type annotations and provide the service in Rust.
+
+import assert from "node:assert/strict";
+import * as aldrin from "aldrin";
+import * as interop from "./interop.mjs";
+
+const RECORD_ID = "5c368dc9-e6d3-4545-86d1-435fe3e771cc";
+
+function record() {
+    const obj = {
+        uuid: "b5d1e8a7-8c3f-4e62-9f1a-2d4c6b8e0a13",
+        cookie: "c6e2f9b8-9d40-4f73-a02b-3e5d7c9f1b24",
+    };
+
+    return {
+        name: "record",
+        id: RECORD_ID,
+        flag: true,
+        small: 200,
+        signed: -5,
+        short: -300,
+        count: 2n ** 64n - 1n,
+        delta: -(2n ** 63n),
+        ratio: 1.5,
+        precise: -2.25,
+        data: new Uint8Array([0, 1, 255]),
+        tags: ["x", "y"],
+        scores: new Map([["a", 1]]),
+        keys: new Set([-7n]),
+        shape: {
+            kind: "Polygon",
+            value: [
+                { x: 1, y: -1 },
+                { x: 2 ** 31 - 1, y: -(2 ** 31) },
+            ],
+        },
+        origin: { x: 0, y: 0 },
+        maybe: 3,
+        outcome: { ok: false, error: "bad" },
+        pair: [-1, 1],
+        obj,
+        svc: {
+            object: obj,
+            uuid: "d7f30ac9-ae51-4084-b13c-4f6e8d0a2c35",
+            cookie: "e8041bda-bf62-4195-824d-507f9e1b3d46",
+        },
+        any: aldrin.SerializedValue.serialize(aldrin.STRING, "hi"),
+        nested: { name: "inner", id: RECORD_ID },
+    };
+}
+
+// Deserialized structs contain absent optional fields as `undefined`.
+function assertRecord(actual) {
+    const pruned = Object.fromEntries(Object.entries(actual).filter(([, v]) => v !== undefined));
+    pruned.nested = Object.fromEntries(
+        Object.entries(pruned.nested).filter(([, v]) => v !== undefined),
+    );
+
+    assert.deepEqual(pruned, record());
+}
+
+function roundTrip(hex) {
+    const bytes = Buffer.from(hex, "hex");
+    const data = new Uint8Array(bytes.buffer, bytes.byteOffset, bytes.length);
+
+    assertRecord(aldrin.deserialize(interop.RecordCodec, data));
+    assert.deepEqual(aldrin.serialize(interop.RecordCodec, record()), data);
+}
+
+async function client(port) {
+    const client = await aldrin.Client.connect(`ws://127.0.0.1:${port}`, { name: "typescript" });
+    const proxy = await interop.InteropProxy.find(client);
+
+    const changed = new Promise((resolve) => proxy.onChanged(resolve));
+    await proxy.subscribeChanged();
+
+    // Subscribing to a property fetches its current value.
+    const level = new Promise((resolve) => proxy.onLevel((value) => value === 2 && resolve()));
+    await proxy.subscribeLevel();
+    assert.equal(proxy.level(), 1);
+
+    assertRecord(await proxy.echo(record()));
+    assert.equal(await proxy.area({ kind: "Circle", value: 2 }), 12n);
+
+    await assert.rejects(proxy.area({ kind: "Empty" }), (e) => {
+        assert.ok(e instanceof aldrin.CallError);
+        assert.equal(e.value, "empty");
+        return true;
+    });
+
+    assert.equal(await proxy.ping(), null);
+    assert.deepEqual(await changed, { x: 1, y: 2 });
+    await level;
+    assert.equal(proxy.level(), 2);
+
+    await proxy.unsubscribeChanged();
+    await proxy.unsubscribeLevel();
+    await client.close();
+}
+
+async function main() {
+    if (process.argv[2] === "round-trip") {
+        roundTrip(process.argv[3]);
+    } else {
+        await client(process.argv[3]);
+    }
+
+    console.log("ok");
+}
+
+await main();
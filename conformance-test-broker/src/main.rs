@@ -1,22 +1,42 @@
 use aldrin_broker::core::tokio::TokioTransport;
+use aldrin_broker::core::transport::AsyncTransport;
 use aldrin_broker::{Broker, BrokerHandle};
 use anyhow::{anyhow, Context, Error, Result};
+use std::env;
 use std::io::{self, Read};
 use std::net::Ipv4Addr;
+#[cfg(unix)]
+use std::path::PathBuf;
 use std::thread;
-use tokio::net::{TcpListener, TcpStream};
+use tokio::net::TcpListener;
+#[cfg(unix)]
+use tokio::net::UnixListener;
 use tokio::sync::oneshot::{self, Receiver, Sender};
 use tokio::task::JoinHandle;
 
-struct BrokerUnderTest {
-    broker: BrokerHandle,
-    join: JoinHandle<()>,
-    listener: TcpListener,
-    stdin_closed: Receiver<Result<()>>,
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
 }
 
-impl BrokerUnderTest {
-    async fn new() -> Result<Self> {
+impl Listener {
+    async fn bind() -> Result<Self> {
+        let transport = match env::var("ALDRIN_CONFORMANCE_TRANSPORT") {
+            Ok(transport) => transport,
+            Err(env::VarError::NotPresent) => "tcp".to_owned(),
+            Err(e) => return Err(Error::new(e).context(anyhow!("invalid transport"))),
+        };
+
+        match transport.as_str() {
+            "tcp" => Self::bind_tcp().await,
+            #[cfg(unix)]
+            "unix" => Self::bind_unix(),
+            _ => Err(anyhow!("unsupported transport `{transport}`")),
+        }
+    }
+
+    async fn bind_tcp() -> Result<Self> {
         let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
             .await
             .with_context(|| anyhow!("failed to bind tcp listener"))?;
@@ -27,6 +47,79 @@ impl BrokerUnderTest {
             .port();
 
         println!("{port}");
+        Ok(Self::Tcp(listener))
+    }
+
+    #[cfg(unix)]
+    fn bind_unix() -> Result<Self> {
+        let path = env::temp_dir().join(format!(
+            "aldrin-conformance-test-broker-{}.sock",
+            std::process::id()
+        ));
+
+        let listener = UnixListener::bind(&path)
+            .with_context(|| anyhow!("failed to bind unix listener at {}", path.display()))?;
+
+        println!("{}", path.display());
+        Ok(Self::Unix(listener, path))
+    }
+
+    async fn accept(&self, broker: &BrokerHandle) -> Result<()> {
+        match self {
+            Self::Tcp(listener) => {
+                let stream = listener
+                    .accept()
+                    .await
+                    .with_context(|| anyhow!("failed to accept new connection"))?
+                    .0;
+
+                stream.set_nodelay(true)?;
+                let transport = TokioTransport::new(stream);
+                tokio::spawn(BrokerUnderTest::handle_new_connection(
+                    broker.clone(),
+                    transport,
+                ));
+            }
+
+            #[cfg(unix)]
+            Self::Unix(listener, _) => {
+                let stream = listener
+                    .accept()
+                    .await
+                    .with_context(|| anyhow!("failed to accept new connection"))?
+                    .0;
+
+                let transport = TokioTransport::new(stream);
+                tokio::spawn(BrokerUnderTest::handle_new_connection(
+                    broker.clone(),
+                    transport,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Self::Unix(_, path) = self {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+struct BrokerUnderTest {
+    broker: BrokerHandle,
+    join: JoinHandle<()>,
+    listener: Listener,
+    stdin_closed: Receiver<Result<()>>,
+}
+
+impl BrokerUnderTest {
+    async fn new() -> Result<Self> {
+        let listener = Listener::bind().await?;
 
         let broker = Broker::new();
         let handle = broker.handle().clone();
@@ -57,12 +150,7 @@ impl BrokerUnderTest {
                     }
                 }
 
-                stream = self.listener.accept() => {
-                    let stream = stream
-                        .with_context(|| anyhow!("failed to accept new connection"))?
-                        .0;
-                    tokio::spawn(Self::handle_new_connection(self.broker.clone(), stream));
-                }
+                res = self.listener.accept(&self.broker) => res?,
 
                 res = &mut self.stdin_closed => {
                     let res = match res {
@@ -81,10 +169,11 @@ impl BrokerUnderTest {
         }
     }
 
-    async fn handle_new_connection(mut broker: BrokerHandle, stream: TcpStream) -> Result<()> {
-        stream.set_nodelay(true)?;
-        let transport = TokioTransport::new(stream);
-
+    async fn handle_new_connection<T>(mut broker: BrokerHandle, transport: T) -> Result<()>
+    where
+        T: AsyncTransport + Unpin,
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
         let conn = broker
             .connect(transport)
             .await
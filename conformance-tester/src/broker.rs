@@ -1,6 +1,9 @@
 use crate::util::FutureExt;
 use anyhow::{anyhow, Context, Error, Result};
+use clap::ValueEnum;
 use std::ffi::OsStr;
+#[cfg(unix)]
+use std::path::PathBuf;
 use std::process::Stdio;
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
@@ -8,16 +11,51 @@ use tokio::process::{Child, Command};
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
 
+/// Environment variable, which tells the broker which transport to use.
+const TRANSPORT_ENV: &str = "ALDRIN_CONFORMANCE_TRANSPORT";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Transport {
+    /// TCP on localhost.
+    Tcp,
+
+    /// Unix domain sockets.
+    #[cfg(unix)]
+    Unix,
+}
+
+impl Transport {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Tcp => "tcp",
+            #[cfg(unix)]
+            Self::Unix => "unix",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Endpoint {
+    Tcp(u16),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
 #[derive(Debug)]
 pub struct Broker {
     child: Child,
     stderr: JoinHandle<Vec<u8>>,
-    port: u16,
+    endpoint: Endpoint,
 }
 
 impl Broker {
-    pub async fn new(broker: &OsStr, startup_timeout: Duration) -> Result<Self> {
+    pub async fn new(
+        broker: &OsStr,
+        transport: Transport,
+        startup_timeout: Duration,
+    ) -> Result<Self> {
         let mut child = Command::new(broker)
+            .env(TRANSPORT_ENV, transport.as_str())
             .kill_on_drop(true)
             .stdout(Stdio::piped())
             .stdin(Stdio::piped())
@@ -25,17 +63,30 @@ impl Broker {
             .spawn()
             .with_context(|| anyhow!("failed to spawn broker process"))?;
 
-        let mut port = String::new();
+        let mut endpoint = String::new();
         BufReader::new(child.stdout.take().unwrap())
-            .read_line(&mut port)
+            .read_line(&mut endpoint)
             .timeout(startup_timeout)
             .await
-            .map_err(|_| anyhow!("timeout while reading the port from broker's stdout"))?
-            .with_context(|| anyhow!("failed to read the port from broker's stdout"))?;
-        let port = port
-            .trim_end()
-            .parse()
-            .with_context(|| anyhow!("failed to parse the port from `{port}`"))?;
+            .map_err(|_| anyhow!("timeout while reading the endpoint from broker's stdout"))?
+            .with_context(|| anyhow!("failed to read the endpoint from broker's stdout"))?;
+        let endpoint = endpoint.trim_end();
+
+        let endpoint = match transport {
+            Transport::Tcp => endpoint
+                .parse()
+                .map(Endpoint::Tcp)
+                .with_context(|| anyhow!("failed to parse the port from `{endpoint}`"))?,
+
+            #[cfg(unix)]
+            Transport::Unix => {
+                if endpoint.is_empty() {
+                    return Err(anyhow!("broker printed an empty socket path"));
+                }
+
+                Endpoint::Unix(PathBuf::from(endpoint))
+            }
+        };
 
         let mut stderr = child.stderr.take().unwrap();
         let stderr = tokio::spawn(async move {
@@ -47,12 +98,12 @@ impl Broker {
         Ok(Self {
             child,
             stderr,
-            port,
+            endpoint,
         })
     }
 
-    pub fn port(&self) -> u16 {
-        self.port
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
     }
 
     pub async fn shut_down(&mut self, timeout: Instant) -> Result<()> {
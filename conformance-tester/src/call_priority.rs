@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CallPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl CallPriority {
+    pub fn is_normal(&self) -> bool {
+        *self == Self::Normal
+    }
+}
+
+impl From<aldrin_core::CallPriority> for CallPriority {
+    fn from(priority: aldrin_core::CallPriority) -> Self {
+        match priority {
+            aldrin_core::CallPriority::Low => Self::Low,
+            aldrin_core::CallPriority::Normal => Self::Normal,
+            aldrin_core::CallPriority::High => Self::High,
+        }
+    }
+}
+
+impl From<CallPriority> for aldrin_core::CallPriority {
+    fn from(priority: CallPriority) -> Self {
+        match priority {
+            CallPriority::Low => Self::Low,
+            CallPriority::Normal => Self::Normal,
+            CallPriority::High => Self::High,
+        }
+    }
+}
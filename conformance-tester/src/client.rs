@@ -1,3 +1,4 @@
+use crate::broker::Endpoint;
 use crate::util::FutureExt;
 use aldrin_core::message::Message;
 use aldrin_core::tokio::{TokioTransport, TokioTransportError};
@@ -6,6 +7,8 @@ use anyhow::{anyhow, Context, Error, Result};
 use std::io::ErrorKind;
 use std::net::{Ipv4Addr, SocketAddrV4};
 use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixStream;
 use tokio::time::Instant;
 
 type TransportBox = Box<dyn AsyncTransport<Error = TokioTransportError> + Unpin + Send + Sync>;
@@ -17,16 +20,41 @@ pub struct Client {
 }
 
 impl Client {
-    pub async fn connect(port: u16, timeout: Instant, sync: bool, shutdown: bool) -> Result<Self> {
-        let addr = SocketAddrV4::new(Ipv4Addr::LOCALHOST, port);
+    pub async fn connect(
+        endpoint: &Endpoint,
+        timeout: Instant,
+        sync: bool,
+        shutdown: bool,
+    ) -> Result<Self> {
+        let transport: TransportBox = match endpoint {
+            Endpoint::Tcp(port) => {
+                let addr = SocketAddrV4::new(Ipv4Addr::LOCALHOST, *port);
 
-        let stream = TcpStream::connect(addr)
-            .timeout_at(timeout)
-            .await
-            .map_err(|_| anyhow!("timeout while connecting to broker at {}", addr))?
-            .with_context(|| anyhow!("failed to connect to broker at {}", addr))?;
+                let stream = TcpStream::connect(addr)
+                    .timeout_at(timeout)
+                    .await
+                    .map_err(|_| anyhow!("timeout while connecting to broker at {}", addr))?
+                    .with_context(|| anyhow!("failed to connect to broker at {}", addr))?;
+
+                Box::new(TokioTransport::new(stream))
+            }
+
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                let stream = UnixStream::connect(path)
+                    .timeout_at(timeout)
+                    .await
+                    .map_err(|_| {
+                        anyhow!("timeout while connecting to broker at {}", path.display())
+                    })?
+                    .with_context(|| {
+                        anyhow!("failed to connect to broker at {}", path.display())
+                    })?;
+
+                Box::new(TokioTransport::new(stream))
+            }
+        };
 
-        let transport = Box::new(TokioTransport::new(stream));
         Ok(Self {
             transport,
             sync,
@@ -1,5 +1,6 @@
 mod broker;
 mod bus_listener;
+mod call_priority;
 mod client;
 mod client_id;
 mod context;
@@ -11,12 +12,14 @@ mod run;
 mod run_error;
 mod serial;
 mod test;
+mod trace_context;
 mod util;
 mod uuid_ref;
 mod value;
 
 use aldrin_core::ProtocolVersion;
 use anyhow::{anyhow, Result};
+use broker::Transport;
 use clap::Parser;
 use colorchoice_clap::Color;
 use message_type::MessageType;
@@ -51,7 +54,7 @@ pub struct FilterArgs {
     message: Vec<MessageType>,
 
     /// Select only tests that require at most the specified protocol version.
-    #[clap(short = 'p', long, default_value_t = ProtocolVersion::V1_18)]
+    #[clap(short = 'p', long, default_value_t = ProtocolVersion::V1_19)]
     version: ProtocolVersion,
 }
 
@@ -78,10 +81,18 @@ pub struct DescribeArgs {
 pub struct BrokerRunArgs {
     /// Timeout in milliseconds until the broker is ready to accept connections.
     ///
-    /// The broker must print the port to stdout and then accept connections within this time.
+    /// The broker must print its endpoint to stdout and then accept connections within this time.
+    /// The endpoint is the port for TCP and the path of the socket for Unix sockets.
     #[clap(long, default_value_t = 1000)]
     startup_timeout: u64,
 
+    /// Transport used to connect to the broker.
+    ///
+    /// The transport is passed to the broker in the `ALDRIN_CONFORMANCE_TRANSPORT` environment
+    /// variable.
+    #[clap(long, value_enum, default_value_t = Transport::Tcp)]
+    transport: Transport,
+
     /// Timeout in milliseconds until the broker terminates when closing stdin.
     ///
     /// Stdin will be closed to signal the broker process that it is supposed to shut down.
@@ -1,6 +1,9 @@
 mod abort_function_call;
+mod ack_event;
 mod add_bus_listener_filter;
 mod add_channel_capacity;
+mod auth_challenge;
+mod auth_response;
 mod bus_listener_current_finished;
 mod bus_listener_filter;
 mod call_function;
@@ -25,14 +28,22 @@ mod create_object_reply;
 mod create_service;
 mod create_service2;
 mod create_service_reply;
+mod create_services;
+mod create_services_reply;
 mod destroy_bus_listener;
 mod destroy_bus_listener_reply;
 mod destroy_object;
 mod destroy_object_reply;
 mod destroy_service;
 mod destroy_service_reply;
+mod destroy_services;
+mod destroy_services_reply;
 mod emit_bus_event;
 mod emit_event;
+mod emit_event_acked;
+mod emit_event_acked_reply;
+mod emit_reliable_event;
+mod emit_retained_event;
 mod item_received;
 mod query_introspection;
 mod query_introspection_reply;
@@ -41,6 +52,7 @@ mod query_service_info_reply;
 mod query_service_version;
 mod query_service_version_reply;
 mod register_introspection;
+mod reliable_event_received;
 mod remove_bus_listener_filter;
 mod send_item;
 mod service_destroyed;
@@ -65,14 +77,17 @@ mod unsubscribe_service;
 use crate::context::Context;
 use crate::uuid_ref::UuidRef;
 use aldrin_core::message::Message as ProtoMessage;
-use aldrin_core::{ServiceInfo as CoreServiceInfo, TypeId};
+use aldrin_core::{LexicalId, ServiceInfo as CoreServiceInfo, TypeId};
 use anyhow::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 pub use abort_function_call::AbortFunctionCall;
+pub use ack_event::AckEvent;
 pub use add_bus_listener_filter::AddBusListenerFilter;
 pub use add_channel_capacity::AddChannelCapacity;
+pub use auth_challenge::AuthChallenge;
+pub use auth_response::AuthResponse;
 pub use bus_listener_current_finished::BusListenerCurrentFinished;
 pub use call_function::CallFunction;
 pub use call_function_reply::CallFunctionReply;
@@ -96,14 +111,22 @@ pub use create_object_reply::{CreateObjectReply, CreateObjectResult};
 pub use create_service::CreateService;
 pub use create_service2::CreateService2;
 pub use create_service_reply::{CreateServiceReply, CreateServiceResult};
+pub use create_services::CreateServices;
+pub use create_services_reply::CreateServicesReply;
 pub use destroy_bus_listener::DestroyBusListener;
 pub use destroy_bus_listener_reply::{DestroyBusListenerReply, DestroyBusListenerResult};
 pub use destroy_object::DestroyObject;
 pub use destroy_object_reply::{DestroyObjectReply, DestroyObjectResult};
 pub use destroy_service::DestroyService;
 pub use destroy_service_reply::{DestroyServiceReply, DestroyServiceResult};
+pub use destroy_services::DestroyServices;
+pub use destroy_services_reply::DestroyServicesReply;
 pub use emit_bus_event::EmitBusEvent;
 pub use emit_event::EmitEvent;
+pub use emit_event_acked::EmitEventAcked;
+pub use emit_event_acked_reply::EmitEventAckedReply;
+pub use emit_reliable_event::EmitReliableEvent;
+pub use emit_retained_event::EmitRetainedEvent;
 pub use item_received::ItemReceived;
 pub use query_introspection::QueryIntrospection;
 pub use query_introspection_reply::QueryIntrospectionReply;
@@ -112,6 +135,7 @@ pub use query_service_info_reply::QueryServiceInfoReply;
 pub use query_service_version::QueryServiceVersion;
 pub use query_service_version_reply::QueryServiceVersionReply;
 pub use register_introspection::RegisterIntrospection;
+pub use reliable_event_received::ReliableEventReceived;
 pub use remove_bus_listener_filter::RemoveBusListenerFilter;
 pub use send_item::SendItem;
 pub use service_destroyed::ServiceDestroyed;
@@ -198,6 +222,18 @@ pub enum Message {
     SubscribeAllEventsReply(SubscribeAllEventsReply),
     UnsubscribeAllEvents(UnsubscribeAllEvents),
     UnsubscribeAllEventsReply(UnsubscribeAllEventsReply),
+    AuthChallenge(AuthChallenge),
+    AuthResponse(AuthResponse),
+    EmitRetainedEvent(EmitRetainedEvent),
+    CreateServices(CreateServices),
+    CreateServicesReply(CreateServicesReply),
+    DestroyServices(DestroyServices),
+    DestroyServicesReply(DestroyServicesReply),
+    EmitEventAcked(EmitEventAcked),
+    EmitEventAckedReply(EmitEventAckedReply),
+    EmitReliableEvent(EmitReliableEvent),
+    ReliableEventReceived(ReliableEventReceived),
+    AckEvent(AckEvent),
 }
 
 impl Message {
@@ -307,6 +343,26 @@ impl Message {
             Self::UnsubscribeAllEventsReply(msg) => msg
                 .to_core(ctx)
                 .map(ProtoMessage::UnsubscribeAllEventsReply),
+            Self::AuthChallenge(msg) => msg.to_core(ctx).map(ProtoMessage::AuthChallenge),
+            Self::AuthResponse(msg) => msg.to_core(ctx).map(ProtoMessage::AuthResponse),
+            Self::EmitRetainedEvent(msg) => msg.to_core(ctx).map(ProtoMessage::EmitRetainedEvent),
+            Self::CreateServices(msg) => msg.to_core(ctx).map(ProtoMessage::CreateServices),
+            Self::CreateServicesReply(msg) => {
+                msg.to_core(ctx).map(ProtoMessage::CreateServicesReply)
+            }
+            Self::DestroyServices(msg) => msg.to_core(ctx).map(ProtoMessage::DestroyServices),
+            Self::DestroyServicesReply(msg) => {
+                msg.to_core(ctx).map(ProtoMessage::DestroyServicesReply)
+            }
+            Self::EmitEventAcked(msg) => msg.to_core(ctx).map(ProtoMessage::EmitEventAcked),
+            Self::EmitEventAckedReply(msg) => {
+                msg.to_core(ctx).map(ProtoMessage::EmitEventAckedReply)
+            }
+            Self::EmitReliableEvent(msg) => msg.to_core(ctx).map(ProtoMessage::EmitReliableEvent),
+            Self::ReliableEventReceived(msg) => {
+                msg.to_core(ctx).map(ProtoMessage::ReliableEventReceived)
+            }
+            Self::AckEvent(msg) => msg.to_core(ctx).map(ProtoMessage::AckEvent),
         }
     }
 
@@ -442,6 +498,30 @@ impl Message {
             (Self::UnsubscribeAllEventsReply(msg), Self::UnsubscribeAllEventsReply(other)) => {
                 msg.matches(other, ctx)
             }
+            (Self::AuthChallenge(msg), Self::AuthChallenge(other)) => msg.matches(other, ctx),
+            (Self::AuthResponse(msg), Self::AuthResponse(other)) => msg.matches(other, ctx),
+            (Self::EmitRetainedEvent(msg), Self::EmitRetainedEvent(other)) => {
+                msg.matches(other, ctx)
+            }
+            (Self::CreateServices(msg), Self::CreateServices(other)) => msg.matches(other, ctx),
+            (Self::CreateServicesReply(msg), Self::CreateServicesReply(other)) => {
+                msg.matches(other, ctx)
+            }
+            (Self::DestroyServices(msg), Self::DestroyServices(other)) => msg.matches(other, ctx),
+            (Self::DestroyServicesReply(msg), Self::DestroyServicesReply(other)) => {
+                msg.matches(other, ctx)
+            }
+            (Self::EmitEventAcked(msg), Self::EmitEventAcked(other)) => msg.matches(other, ctx),
+            (Self::EmitEventAckedReply(msg), Self::EmitEventAckedReply(other)) => {
+                msg.matches(other, ctx)
+            }
+            (Self::EmitReliableEvent(msg), Self::EmitReliableEvent(other)) => {
+                msg.matches(other, ctx)
+            }
+            (Self::ReliableEventReceived(msg), Self::ReliableEventReceived(other)) => {
+                msg.matches(other, ctx)
+            }
+            (Self::AckEvent(msg), Self::AckEvent(other)) => msg.matches(other, ctx),
             _ => Ok(false),
         }
     }
@@ -610,6 +690,38 @@ impl Message {
             (Self::UnsubscribeAllEventsReply(msg), Self::UnsubscribeAllEventsReply(other)) => {
                 msg.update_context(other, ctx)
             }
+            (Self::AuthChallenge(msg), Self::AuthChallenge(other)) => {
+                msg.update_context(other, ctx)
+            }
+            (Self::AuthResponse(msg), Self::AuthResponse(other)) => msg.update_context(other, ctx),
+            (Self::EmitRetainedEvent(msg), Self::EmitRetainedEvent(other)) => {
+                msg.update_context(other, ctx)
+            }
+            (Self::CreateServices(msg), Self::CreateServices(other)) => {
+                msg.update_context(other, ctx)
+            }
+            (Self::CreateServicesReply(msg), Self::CreateServicesReply(other)) => {
+                msg.update_context(other, ctx)
+            }
+            (Self::DestroyServices(msg), Self::DestroyServices(other)) => {
+                msg.update_context(other, ctx)
+            }
+            (Self::DestroyServicesReply(msg), Self::DestroyServicesReply(other)) => {
+                msg.update_context(other, ctx)
+            }
+            (Self::EmitEventAcked(msg), Self::EmitEventAcked(other)) => {
+                msg.update_context(other, ctx)
+            }
+            (Self::EmitEventAckedReply(msg), Self::EmitEventAckedReply(other)) => {
+                msg.update_context(other, ctx)
+            }
+            (Self::EmitReliableEvent(msg), Self::EmitReliableEvent(other)) => {
+                msg.update_context(other, ctx)
+            }
+            (Self::ReliableEventReceived(msg), Self::ReliableEventReceived(other)) => {
+                msg.update_context(other, ctx)
+            }
+            (Self::AckEvent(msg), Self::AckEvent(other)) => msg.update_context(other, ctx),
             _ => unreachable!(),
         }
     }
@@ -714,6 +826,22 @@ impl Message {
             Self::UnsubscribeAllEventsReply(msg) => {
                 msg.apply_context(ctx).map(Self::UnsubscribeAllEventsReply)
             }
+            Self::AuthChallenge(msg) => msg.apply_context(ctx).map(Self::AuthChallenge),
+            Self::AuthResponse(msg) => msg.apply_context(ctx).map(Self::AuthResponse),
+            Self::EmitRetainedEvent(msg) => msg.apply_context(ctx).map(Self::EmitRetainedEvent),
+            Self::CreateServices(msg) => msg.apply_context(ctx).map(Self::CreateServices),
+            Self::CreateServicesReply(msg) => msg.apply_context(ctx).map(Self::CreateServicesReply),
+            Self::DestroyServices(msg) => msg.apply_context(ctx).map(Self::DestroyServices),
+            Self::DestroyServicesReply(msg) => {
+                msg.apply_context(ctx).map(Self::DestroyServicesReply)
+            }
+            Self::EmitEventAcked(msg) => msg.apply_context(ctx).map(Self::EmitEventAcked),
+            Self::EmitEventAckedReply(msg) => msg.apply_context(ctx).map(Self::EmitEventAckedReply),
+            Self::EmitReliableEvent(msg) => msg.apply_context(ctx).map(Self::EmitReliableEvent),
+            Self::ReliableEventReceived(msg) => {
+                msg.apply_context(ctx).map(Self::ReliableEventReceived)
+            }
+            Self::AckEvent(msg) => msg.apply_context(ctx).map(Self::AckEvent),
         }
     }
 }
@@ -821,6 +949,22 @@ impl TryFrom<ProtoMessage> for Message {
             ProtoMessage::UnsubscribeAllEventsReply(msg) => {
                 msg.try_into().map(Self::UnsubscribeAllEventsReply)
             }
+            ProtoMessage::AuthChallenge(msg) => msg.try_into().map(Self::AuthChallenge),
+            ProtoMessage::AuthResponse(msg) => msg.try_into().map(Self::AuthResponse),
+            ProtoMessage::EmitRetainedEvent(msg) => msg.try_into().map(Self::EmitRetainedEvent),
+            ProtoMessage::CreateServices(msg) => msg.try_into().map(Self::CreateServices),
+            ProtoMessage::CreateServicesReply(msg) => msg.try_into().map(Self::CreateServicesReply),
+            ProtoMessage::DestroyServices(msg) => msg.try_into().map(Self::DestroyServices),
+            ProtoMessage::DestroyServicesReply(msg) => {
+                msg.try_into().map(Self::DestroyServicesReply)
+            }
+            ProtoMessage::EmitEventAcked(msg) => msg.try_into().map(Self::EmitEventAcked),
+            ProtoMessage::EmitEventAckedReply(msg) => msg.try_into().map(Self::EmitEventAckedReply),
+            ProtoMessage::EmitReliableEvent(msg) => msg.try_into().map(Self::EmitReliableEvent),
+            ProtoMessage::ReliableEventReceived(msg) => {
+                msg.try_into().map(Self::ReliableEventReceived)
+            }
+            ProtoMessage::AckEvent(msg) => msg.try_into().map(Self::AckEvent),
         }
     }
 }
@@ -864,6 +1008,8 @@ impl fmt::Display for ChannelEnd {
 pub enum ChannelEndWithCapacity {
     Sender,
     Receiver { capacity: u32 },
+    ReceiverBytes { capacity: u32 },
+    BroadcastSender,
 }
 
 impl From<aldrin_core::ChannelEndWithCapacity> for ChannelEndWithCapacity {
@@ -872,6 +1018,12 @@ impl From<aldrin_core::ChannelEndWithCapacity> for ChannelEndWithCapacity {
             aldrin_core::ChannelEndWithCapacity::Sender => Self::Sender,
 
             aldrin_core::ChannelEndWithCapacity::Receiver(capacity) => Self::Receiver { capacity },
+
+            aldrin_core::ChannelEndWithCapacity::ReceiverBytes(capacity) => {
+                Self::ReceiverBytes { capacity }
+            }
+
+            aldrin_core::ChannelEndWithCapacity::BroadcastSender => Self::BroadcastSender,
         }
     }
 }
@@ -881,6 +1033,8 @@ impl From<ChannelEndWithCapacity> for aldrin_core::ChannelEndWithCapacity {
         match end {
             ChannelEndWithCapacity::Sender => Self::Sender,
             ChannelEndWithCapacity::Receiver { capacity } => Self::Receiver(capacity),
+            ChannelEndWithCapacity::ReceiverBytes { capacity } => Self::ReceiverBytes(capacity),
+            ChannelEndWithCapacity::BroadcastSender => Self::BroadcastSender,
         }
     }
 }
@@ -888,8 +1042,8 @@ impl From<ChannelEndWithCapacity> for aldrin_core::ChannelEndWithCapacity {
 impl fmt::Display for ChannelEndWithCapacity {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::Sender => f.pad("sender"),
-            Self::Receiver { .. } => f.pad("receiver"),
+            Self::Sender | Self::BroadcastSender => f.pad("sender"),
+            Self::Receiver { .. } | Self::ReceiverBytes { .. } => f.pad("receiver"),
         }
     }
 }
@@ -900,6 +1054,8 @@ pub struct ServiceInfo {
     pub version: u32,
     pub type_id: Option<UuidRef>,
     pub subscribe_all: Option<bool>,
+    pub max_calls: Option<u32>,
+    pub lexical_id: Option<UuidRef>,
 }
 
 impl ServiceInfo {
@@ -915,6 +1071,15 @@ impl ServiceInfo {
             info = info.set_subscribe_all(subscribe_all);
         }
 
+        if let Some(max_calls) = self.max_calls {
+            info = info.set_max_calls(max_calls);
+        }
+
+        if let Some(ref lexical_id) = self.lexical_id {
+            let lexical_id = lexical_id.get(ctx).map(LexicalId)?;
+            info = info.set_lexical_id(lexical_id);
+        }
+
         Ok(info)
     }
 
@@ -925,15 +1090,31 @@ impl ServiceInfo {
             _ => false,
         };
 
-        Ok(res && (self.version == other.version) && (self.subscribe_all == other.subscribe_all))
+        let res = res
+            && match (self.lexical_id.as_ref(), other.lexical_id.as_ref()) {
+                (Some(lexical_id), Some(other)) => lexical_id.matches(other, ctx)?,
+                (None, None) => true,
+                _ => false,
+            };
+
+        Ok(res
+            && (self.version == other.version)
+            && (self.subscribe_all == other.subscribe_all)
+            && (self.max_calls == other.max_calls))
     }
 
     pub fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
         if let (Some(type_id), Some(other)) = (self.type_id.as_ref(), other.type_id.as_ref()) {
-            type_id.update_context(other, ctx)
-        } else {
-            Ok(())
+            type_id.update_context(other, ctx)?;
+        }
+
+        if let (Some(lexical_id), Some(other)) =
+            (self.lexical_id.as_ref(), other.lexical_id.as_ref())
+        {
+            lexical_id.update_context(other, ctx)?;
         }
+
+        Ok(())
     }
 
     pub fn apply_context(&self, ctx: &Context) -> Result<Self> {
@@ -943,10 +1124,18 @@ impl ServiceInfo {
             .map(|id| id.apply_context(ctx))
             .transpose()?;
 
+        let lexical_id = self
+            .lexical_id
+            .as_ref()
+            .map(|id| id.apply_context(ctx))
+            .transpose()?;
+
         Ok(Self {
             version: self.version,
             type_id,
             subscribe_all: self.subscribe_all,
+            max_calls: self.max_calls,
+            lexical_id,
         })
     }
 }
@@ -957,6 +1146,8 @@ impl From<CoreServiceInfo> for ServiceInfo {
             version: info.version(),
             type_id: info.type_id().map(Into::into),
             subscribe_all: info.subscribe_all(),
+            max_calls: info.max_calls(),
+            lexical_id: info.lexical_id().map(Into::into),
         }
     }
 }
@@ -0,0 +1,44 @@
+use crate::context::Context;
+use crate::value::Value;
+use aldrin_core::message;
+use anyhow::{anyhow, Context as _, Error, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthResponse {
+    #[serde(flatten)]
+    pub value: Value,
+}
+
+impl AuthResponse {
+    pub fn to_core(&self, _ctx: &Context) -> Result<message::AuthResponse> {
+        message::AuthResponse::with_serialize_value(&self.value)
+            .with_context(|| anyhow!("failed to serialize value"))
+    }
+
+    pub fn matches(&self, _other: &Self, _ctx: &Context) -> Result<bool> {
+        Ok(true)
+    }
+
+    pub fn update_context(&self, _other: &Self, _ctx: &mut Context) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn apply_context(&self, _ctx: &Context) -> Result<Self> {
+        Ok(self.clone())
+    }
+}
+
+impl TryFrom<message::AuthResponse> for AuthResponse {
+    type Error = Error;
+
+    fn try_from(msg: message::AuthResponse) -> Result<Self> {
+        let value = msg
+            .value
+            .deserialize()
+            .with_context(|| anyhow!("failed to deserialize value `{:?}`", msg.value))?;
+
+        Ok(Self { value })
+    }
+}
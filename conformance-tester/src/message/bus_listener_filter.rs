@@ -12,6 +12,8 @@ pub enum BusListenerFilter {
     SpecificObjectAnyService { object: UuidRef },
     AnyObjectSpecificService { service: UuidRef },
     SpecificObjectSpecificService { object: UuidRef, service: UuidRef },
+    AnyObjectSpecificLayout { layout: UuidRef },
+    SpecificObjectSpecificLayout { object: UuidRef, layout: UuidRef },
 }
 
 impl BusListenerFilter {
@@ -52,6 +54,20 @@ impl BusListenerFilter {
                     object, service,
                 ))
             }
+
+            Self::AnyObjectSpecificLayout { layout } => {
+                let layout = layout.get(ctx)?.into();
+                Ok(aldrin_core::BusListenerFilter::any_object_layout(layout))
+            }
+
+            Self::SpecificObjectSpecificLayout { object, layout } => {
+                let object = object.get(ctx)?.into();
+                let layout = layout.get(ctx)?.into();
+
+                Ok(aldrin_core::BusListenerFilter::specific_object_layout(
+                    object, layout,
+                ))
+            }
         }
     }
 
@@ -89,6 +105,25 @@ impl BusListenerFilter {
                 Ok(res)
             }
 
+            (
+                Self::AnyObjectSpecificLayout { layout: layout1 },
+                Self::AnyObjectSpecificLayout { layout: layout2 },
+            ) => layout1.matches(layout2, ctx),
+
+            (
+                Self::SpecificObjectSpecificLayout {
+                    object: object1,
+                    layout: layout1,
+                },
+                Self::SpecificObjectSpecificLayout {
+                    object: object2,
+                    layout: layout2,
+                },
+            ) => {
+                let res = object1.matches(object2, ctx)? && layout1.matches(layout2, ctx)?;
+                Ok(res)
+            }
+
             _ => Ok(false),
         }
     }
@@ -129,6 +164,27 @@ impl BusListenerFilter {
                 Ok(())
             }
 
+            (
+                Self::AnyObjectSpecificLayout { layout: layout1 },
+                Self::AnyObjectSpecificLayout { layout: layout2 },
+            ) => layout1.update_context(layout2, ctx),
+
+            (
+                Self::SpecificObjectSpecificLayout {
+                    object: object1,
+                    layout: layout1,
+                },
+                Self::SpecificObjectSpecificLayout {
+                    object: object2,
+                    layout: layout2,
+                },
+            ) => {
+                object1.update_context(object2, ctx)?;
+                layout1.update_context(layout2, ctx)?;
+
+                Ok(())
+            }
+
             _ => unreachable!(),
         }
     }
@@ -159,6 +215,17 @@ impl BusListenerFilter {
                 let service = service.apply_context(ctx)?;
                 Ok(Self::SpecificObjectSpecificService { object, service })
             }
+
+            Self::AnyObjectSpecificLayout { layout } => {
+                let layout = layout.apply_context(ctx)?;
+                Ok(Self::AnyObjectSpecificLayout { layout })
+            }
+
+            Self::SpecificObjectSpecificLayout { object, layout } => {
+                let object = object.apply_context(ctx)?;
+                let layout = layout.apply_context(ctx)?;
+                Ok(Self::SpecificObjectSpecificLayout { object, layout })
+            }
         }
     }
 }
@@ -198,6 +265,21 @@ impl From<aldrin_core::BusListenerFilter> for BusListenerFilter {
                 object: object.into(),
                 service: service.into(),
             },
+
+            aldrin_core::BusListenerFilter::Layout(aldrin_core::BusListenerLayoutFilter {
+                object: None,
+                layout,
+            }) => Self::AnyObjectSpecificLayout {
+                layout: layout.into(),
+            },
+
+            aldrin_core::BusListenerFilter::Layout(aldrin_core::BusListenerLayoutFilter {
+                object: Some(object),
+                layout,
+            }) => Self::SpecificObjectSpecificLayout {
+                object: object.into(),
+                layout: layout.into(),
+            },
         }
     }
 }
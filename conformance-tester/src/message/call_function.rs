@@ -1,5 +1,7 @@
+use crate::call_priority::CallPriority;
 use crate::context::Context;
 use crate::serial::Serial;
+use crate::trace_context::TraceContext;
 use crate::uuid_ref::UuidRef;
 use crate::value::Value;
 use aldrin_core::message;
@@ -13,6 +15,12 @@ pub struct CallFunction {
     pub function: u32,
     pub service_cookie: UuidRef,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
+
+    #[serde(default, skip_serializing_if = "CallPriority::is_normal")]
+    pub priority: CallPriority,
+
     #[serde(flatten)]
     pub value: Value,
 }
@@ -22,20 +30,26 @@ impl CallFunction {
         let serial = self.serial.get(ctx)?;
         let service_cookie = self.service_cookie.get(ctx)?.into();
 
-        message::CallFunction::with_serialize_value(
+        let mut msg = message::CallFunction::with_serialize_value(
             serial,
             service_cookie,
             self.function,
             &self.value,
         )
-        .with_context(|| anyhow!("failed to serialize value"))
+        .with_context(|| anyhow!("failed to serialize value"))?;
+
+        msg.trace_context = self.trace_context.map(Into::into);
+        msg.priority = self.priority.into();
+        Ok(msg)
     }
 
     pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
         let res = self.serial.matches(&other.serial, ctx)?
             && (self.function == other.function)
             && self.service_cookie.matches(&other.service_cookie, ctx)?
-            && self.value.matches(&other.value);
+            && self.value.matches(&other.value)
+            && (self.trace_context == other.trace_context)
+            && (self.priority == other.priority);
 
         Ok(res)
     }
@@ -56,6 +70,8 @@ impl CallFunction {
             serial,
             function: self.function,
             service_cookie,
+            trace_context: self.trace_context,
+            priority: self.priority,
             value: self.value.clone(),
         })
     }
@@ -74,6 +90,8 @@ impl TryFrom<message::CallFunction> for CallFunction {
             serial: msg.serial.into(),
             function: msg.function,
             service_cookie: msg.service_cookie.into(),
+            trace_context: msg.trace_context.map(Into::into),
+            priority: msg.priority.into(),
             value,
         })
     }
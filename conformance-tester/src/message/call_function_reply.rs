@@ -74,6 +74,7 @@ pub enum CallFunctionResult {
     InvalidService,
     InvalidFunction,
     InvalidArgs,
+    Overloaded,
 }
 
 impl CallFunctionResult {
@@ -89,6 +90,7 @@ impl CallFunctionResult {
             Self::InvalidService => Ok(message::CallFunctionResult::InvalidService),
             Self::InvalidFunction => Ok(message::CallFunctionResult::InvalidFunction),
             Self::InvalidArgs => Ok(message::CallFunctionResult::InvalidArgs),
+            Self::Overloaded => Ok(message::CallFunctionResult::Overloaded),
         }
     }
 
@@ -99,7 +101,8 @@ impl CallFunctionResult {
             (Self::Aborted, Self::Aborted)
             | (Self::InvalidService, Self::InvalidService)
             | (Self::InvalidFunction, Self::InvalidFunction)
-            | (Self::InvalidArgs, Self::InvalidArgs) => Ok(true),
+            | (Self::InvalidArgs, Self::InvalidArgs)
+            | (Self::Overloaded, Self::Overloaded) => Ok(true),
             _ => Ok(false),
         }
     }
@@ -138,6 +141,7 @@ impl TryFrom<message::CallFunctionResult> for CallFunctionResult {
             message::CallFunctionResult::InvalidService => Ok(Self::InvalidService),
             message::CallFunctionResult::InvalidFunction => Ok(Self::InvalidFunction),
             message::CallFunctionResult::InvalidArgs => Ok(Self::InvalidArgs),
+            message::CallFunctionResult::Overloaded => Ok(Self::Overloaded),
         }
     }
 }
@@ -59,6 +59,7 @@ impl TryFrom<message::ClaimChannelEndReply> for ClaimChannelEndReply {
 #[serde(rename_all = "kebab-case", tag = "result")]
 pub enum ClaimChannelEndResult {
     SenderClaimed { capacity: u32 },
+    SenderClaimedBytes { capacity: u32 },
     ReceiverClaimed,
     InvalidChannel,
     AlreadyClaimed,
@@ -71,6 +72,10 @@ impl ClaimChannelEndResult {
                 Ok(message::ClaimChannelEndResult::SenderClaimed(capacity))
             }
 
+            Self::SenderClaimedBytes { capacity } => {
+                Ok(message::ClaimChannelEndResult::SenderClaimedBytes(capacity))
+            }
+
             Self::ReceiverClaimed => Ok(message::ClaimChannelEndResult::ReceiverClaimed),
             Self::InvalidChannel => Ok(message::ClaimChannelEndResult::InvalidChannel),
             Self::AlreadyClaimed => Ok(message::ClaimChannelEndResult::AlreadyClaimed),
@@ -89,6 +94,10 @@ impl From<message::ClaimChannelEndResult> for ClaimChannelEndResult {
                 Self::SenderClaimed { capacity }
             }
 
+            message::ClaimChannelEndResult::SenderClaimedBytes(capacity) => {
+                Self::SenderClaimedBytes { capacity }
+            }
+
             message::ClaimChannelEndResult::ReceiverClaimed => Self::ReceiverClaimed,
             message::ClaimChannelEndResult::InvalidChannel => Self::InvalidChannel,
             message::ClaimChannelEndResult::AlreadyClaimed => Self::AlreadyClaimed,
@@ -0,0 +1,155 @@
+use super::ServiceInfo;
+use crate::context::Context;
+use crate::serial::Serial;
+use crate::uuid_ref::UuidRef;
+use aldrin_core::{message, SerializedValue, ServiceUuid};
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateServices {
+    pub serial: Serial,
+    pub object_cookie: UuidRef,
+    pub services: Option<Vec<CreateServicesItem>>,
+}
+
+impl CreateServices {
+    pub fn to_core(&self, ctx: &Context) -> Result<message::CreateServices> {
+        let serial = self.serial.get(ctx)?;
+        let object_cookie = self.object_cookie.get(ctx)?.into();
+
+        let value = match self.services {
+            Some(ref services) => {
+                let services = services
+                    .iter()
+                    .map(|svc| svc.to_core(ctx))
+                    .collect::<Result<Vec<_>>>()?;
+
+                SerializedValue::serialize(&services)?
+            }
+
+            None => SerializedValue::serialize(&())?,
+        };
+
+        Ok(message::CreateServices {
+            serial,
+            object_cookie,
+            value,
+        })
+    }
+
+    pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
+        if !self.serial.matches(&other.serial, ctx)?
+            || !self.object_cookie.matches(&other.object_cookie, ctx)?
+        {
+            return Ok(false);
+        }
+
+        match (&self.services, &other.services) {
+            (Some(svcs1), Some(svcs2)) if svcs1.len() == svcs2.len() => {
+                for (svc1, svc2) in svcs1.iter().zip(svcs2) {
+                    if !svc1.matches(svc2, ctx)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+
+            (None, None) => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    pub fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
+        self.serial.update_context(&other.serial, ctx)?;
+        self.object_cookie
+            .update_context(&other.object_cookie, ctx)?;
+
+        if let (Some(svcs1), Some(svcs2)) = (&self.services, &other.services) {
+            for (svc1, svc2) in svcs1.iter().zip(svcs2) {
+                svc1.update_context(svc2, ctx)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn apply_context(&self, ctx: &Context) -> Result<Self> {
+        let serial = self.serial.apply_context(ctx)?;
+        let object_cookie = self.object_cookie.apply_context(ctx)?;
+
+        let services = self
+            .services
+            .as_ref()
+            .map(|svcs| {
+                svcs.iter()
+                    .map(|svc| svc.apply_context(ctx))
+                    .collect::<Result<_>>()
+            })
+            .transpose()?;
+
+        Ok(Self {
+            serial,
+            object_cookie,
+            services,
+        })
+    }
+}
+
+impl TryFrom<message::CreateServices> for CreateServices {
+    type Error = Error;
+
+    fn try_from(msg: message::CreateServices) -> Result<Self> {
+        let services = msg.deserialize_services().ok().map(|svcs| {
+            svcs.into_iter()
+                .map(|(uuid, info)| CreateServicesItem {
+                    uuid: uuid.into(),
+                    info: info.into(),
+                })
+                .collect()
+        });
+
+        Ok(Self {
+            serial: msg.serial.into(),
+            object_cookie: msg.object_cookie.into(),
+            services,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateServicesItem {
+    pub uuid: UuidRef,
+    pub info: ServiceInfo,
+}
+
+impl CreateServicesItem {
+    fn to_core(&self, ctx: &Context) -> Result<(ServiceUuid, aldrin_core::ServiceInfo)> {
+        let uuid = self.uuid.get(ctx)?.into();
+        let info = self.info.to_core(ctx)?;
+
+        Ok((uuid, info))
+    }
+
+    fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
+        let res = self.uuid.matches(&other.uuid, ctx)? && self.info.matches(&other.info, ctx)?;
+        Ok(res)
+    }
+
+    fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
+        self.uuid.update_context(&other.uuid, ctx)?;
+        self.info.update_context(&other.info, ctx)?;
+
+        Ok(())
+    }
+
+    fn apply_context(&self, ctx: &Context) -> Result<Self> {
+        let uuid = self.uuid.apply_context(ctx)?;
+        let info = self.info.apply_context(ctx)?;
+
+        Ok(Self { uuid, info })
+    }
+}
@@ -0,0 +1,150 @@
+use crate::context::Context;
+use crate::serial::Serial;
+use crate::uuid_ref::UuidRef;
+use aldrin_core::message;
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateServicesReply {
+    pub serial: Serial,
+
+    #[serde(flatten)]
+    pub result: CreateServicesResult,
+}
+
+impl CreateServicesReply {
+    pub fn to_core(&self, ctx: &Context) -> Result<message::CreateServicesReply> {
+        let serial = self.serial.get(ctx)?;
+        let result = self.result.to_core(ctx)?;
+
+        Ok(message::CreateServicesReply { serial, result })
+    }
+
+    pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
+        let res =
+            self.serial.matches(&other.serial, ctx)? && self.result.matches(&other.result, ctx)?;
+        Ok(res)
+    }
+
+    pub fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
+        self.serial.update_context(&other.serial, ctx)?;
+        self.result.update_context(&other.result, ctx)?;
+
+        Ok(())
+    }
+
+    pub fn apply_context(&self, ctx: &Context) -> Result<Self> {
+        let serial = self.serial.apply_context(ctx)?;
+        let result = self.result.apply_context(ctx)?;
+
+        Ok(Self { serial, result })
+    }
+}
+
+impl TryFrom<message::CreateServicesReply> for CreateServicesReply {
+    type Error = Error;
+
+    fn try_from(msg: message::CreateServicesReply) -> Result<Self> {
+        Ok(Self {
+            serial: msg.serial.into(),
+            result: msg.result.into(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "result")]
+pub enum CreateServicesResult {
+    Ok { cookies: Vec<UuidRef> },
+    DuplicateService,
+    InvalidObject,
+    ForeignObject,
+}
+
+impl CreateServicesResult {
+    pub fn to_core(&self, ctx: &Context) -> Result<message::CreateServicesResult> {
+        match self {
+            Self::Ok { cookies } => {
+                let cookies = cookies
+                    .iter()
+                    .map(|cookie| cookie.get(ctx).map(Into::into))
+                    .collect::<Result<_>>()?;
+
+                Ok(message::CreateServicesResult::Ok(cookies))
+            }
+
+            Self::DuplicateService => Ok(message::CreateServicesResult::DuplicateService),
+            Self::InvalidObject => Ok(message::CreateServicesResult::InvalidObject),
+            Self::ForeignObject => Ok(message::CreateServicesResult::ForeignObject),
+        }
+    }
+
+    pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
+        match (self, other) {
+            (Self::Ok { cookies: c1 }, Self::Ok { cookies: c2 }) if c1.len() == c2.len() => {
+                for (c1, c2) in c1.iter().zip(c2) {
+                    if !c1.matches(c2, ctx)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+
+            (Self::DuplicateService, Self::DuplicateService)
+            | (Self::InvalidObject, Self::InvalidObject)
+            | (Self::ForeignObject, Self::ForeignObject) => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    pub fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
+        match (self, other) {
+            (Self::Ok { cookies: c1 }, Self::Ok { cookies: c2 }) => {
+                for (c1, c2) in c1.iter().zip(c2) {
+                    c1.update_context(c2, ctx)?;
+                }
+
+                Ok(())
+            }
+
+            (Self::DuplicateService, Self::DuplicateService)
+            | (Self::InvalidObject, Self::InvalidObject)
+            | (Self::ForeignObject, Self::ForeignObject) => Ok(()),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn apply_context(&self, ctx: &Context) -> Result<Self> {
+        match self {
+            Self::Ok { cookies } => {
+                let cookies = cookies
+                    .iter()
+                    .map(|cookie| cookie.apply_context(ctx))
+                    .collect::<Result<_>>()?;
+
+                Ok(Self::Ok { cookies })
+            }
+
+            Self::DuplicateService => Ok(Self::DuplicateService),
+            Self::InvalidObject => Ok(Self::InvalidObject),
+            Self::ForeignObject => Ok(Self::ForeignObject),
+        }
+    }
+}
+
+impl From<message::CreateServicesResult> for CreateServicesResult {
+    fn from(res: message::CreateServicesResult) -> Self {
+        match res {
+            message::CreateServicesResult::Ok(cookies) => Self::Ok {
+                cookies: cookies.into_iter().map(Into::into).collect(),
+            },
+
+            message::CreateServicesResult::DuplicateService => Self::DuplicateService,
+            message::CreateServicesResult::InvalidObject => Self::InvalidObject,
+            message::CreateServicesResult::ForeignObject => Self::ForeignObject,
+        }
+    }
+}
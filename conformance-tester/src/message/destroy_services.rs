@@ -0,0 +1,75 @@
+use crate::context::Context;
+use crate::serial::Serial;
+use crate::uuid_ref::UuidRef;
+use aldrin_core::message;
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DestroyServices {
+    pub serial: Serial,
+    pub cookies: Vec<UuidRef>,
+}
+
+impl DestroyServices {
+    pub fn to_core(&self, ctx: &Context) -> Result<message::DestroyServices> {
+        let serial = self.serial.get(ctx)?;
+
+        let cookies = self
+            .cookies
+            .iter()
+            .map(|cookie| cookie.get(ctx).map(Into::into))
+            .collect::<Result<_>>()?;
+
+        Ok(message::DestroyServices { serial, cookies })
+    }
+
+    pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
+        if !self.serial.matches(&other.serial, ctx)? || (self.cookies.len() != other.cookies.len())
+        {
+            return Ok(false);
+        }
+
+        for (c1, c2) in self.cookies.iter().zip(&other.cookies) {
+            if !c1.matches(c2, ctx)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    pub fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
+        self.serial.update_context(&other.serial, ctx)?;
+
+        for (c1, c2) in self.cookies.iter().zip(&other.cookies) {
+            c1.update_context(c2, ctx)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn apply_context(&self, ctx: &Context) -> Result<Self> {
+        let serial = self.serial.apply_context(ctx)?;
+
+        let cookies = self
+            .cookies
+            .iter()
+            .map(|cookie| cookie.apply_context(ctx))
+            .collect::<Result<_>>()?;
+
+        Ok(Self { serial, cookies })
+    }
+}
+
+impl TryFrom<message::DestroyServices> for DestroyServices {
+    type Error = Error;
+
+    fn try_from(msg: message::DestroyServices) -> Result<Self> {
+        Ok(Self {
+            serial: msg.serial.into(),
+            cookies: msg.cookies.into_iter().map(Into::into).collect(),
+        })
+    }
+}
@@ -0,0 +1,93 @@
+use crate::context::Context;
+use crate::serial::Serial;
+use aldrin_core::message;
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DestroyServicesReply {
+    pub serial: Serial,
+
+    #[serde(flatten)]
+    pub result: DestroyServicesResult,
+}
+
+impl DestroyServicesReply {
+    pub fn to_core(&self, ctx: &Context) -> Result<message::DestroyServicesReply> {
+        let serial = self.serial.get(ctx)?;
+        let result = self.result.to_core(ctx)?;
+
+        Ok(message::DestroyServicesReply { serial, result })
+    }
+
+    pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
+        let res =
+            self.serial.matches(&other.serial, ctx)? && self.result.matches(&other.result, ctx)?;
+        Ok(res)
+    }
+
+    pub fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
+        self.serial.update_context(&other.serial, ctx)?;
+        self.result.update_context(&other.result, ctx)?;
+        Ok(())
+    }
+
+    pub fn apply_context(&self, ctx: &Context) -> Result<Self> {
+        let serial = self.serial.apply_context(ctx)?;
+        let result = self.result.apply_context(ctx)?;
+
+        Ok(Self { serial, result })
+    }
+}
+
+impl TryFrom<message::DestroyServicesReply> for DestroyServicesReply {
+    type Error = Error;
+
+    fn try_from(msg: message::DestroyServicesReply) -> Result<Self> {
+        Ok(Self {
+            serial: msg.serial.into(),
+            result: msg.result.into(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "result")]
+pub enum DestroyServicesResult {
+    Ok,
+    InvalidService,
+    ForeignObject,
+}
+
+impl DestroyServicesResult {
+    pub fn to_core(&self, _ctx: &Context) -> Result<message::DestroyServicesResult> {
+        match self {
+            Self::Ok => Ok(message::DestroyServicesResult::Ok),
+            Self::InvalidService => Ok(message::DestroyServicesResult::InvalidService),
+            Self::ForeignObject => Ok(message::DestroyServicesResult::ForeignObject),
+        }
+    }
+
+    pub fn matches(&self, other: &Self, _ctx: &Context) -> Result<bool> {
+        Ok(self == other)
+    }
+
+    pub fn update_context(&self, _other: &Self, _ctx: &mut Context) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn apply_context(&self, _ctx: &Context) -> Result<Self> {
+        Ok(self.clone())
+    }
+}
+
+impl From<message::DestroyServicesResult> for DestroyServicesResult {
+    fn from(res: message::DestroyServicesResult) -> Self {
+        match res {
+            message::DestroyServicesResult::Ok => Self::Ok,
+            message::DestroyServicesResult::InvalidService => Self::InvalidService,
+            message::DestroyServicesResult::ForeignObject => Self::ForeignObject,
+        }
+    }
+}
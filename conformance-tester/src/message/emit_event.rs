@@ -1,4 +1,5 @@
 use crate::context::Context;
+use crate::trace_context::TraceContext;
 use crate::uuid_ref::UuidRef;
 use crate::value::Value;
 use aldrin_core::message;
@@ -11,6 +12,9 @@ pub struct EmitEvent {
     pub service_cookie: UuidRef,
     pub event: u32,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
+
     #[serde(flatten)]
     pub value: Value,
 }
@@ -19,13 +23,18 @@ impl EmitEvent {
     pub fn to_core(&self, ctx: &Context) -> Result<message::EmitEvent> {
         let service_cookie = self.service_cookie.get(ctx)?.into();
 
-        message::EmitEvent::with_serialize_value(service_cookie, self.event, &self.value)
-            .with_context(|| anyhow!("failed to serialize value"))
+        let mut msg =
+            message::EmitEvent::with_serialize_value(service_cookie, self.event, &self.value)
+                .with_context(|| anyhow!("failed to serialize value"))?;
+
+        msg.trace_context = self.trace_context.map(Into::into);
+        Ok(msg)
     }
 
     pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
-        let res =
-            self.service_cookie.matches(&other.service_cookie, ctx)? && (self.event == other.event);
+        let res = self.service_cookie.matches(&other.service_cookie, ctx)?
+            && (self.event == other.event)
+            && (self.trace_context == other.trace_context);
 
         Ok(res)
     }
@@ -42,6 +51,7 @@ impl EmitEvent {
         Ok(Self {
             service_cookie,
             event: self.event,
+            trace_context: self.trace_context,
             value: self.value.clone(),
         })
     }
@@ -59,6 +69,7 @@ impl TryFrom<message::EmitEvent> for EmitEvent {
         Ok(Self {
             service_cookie: msg.service_cookie.into(),
             event: msg.event,
+            trace_context: msg.trace_context.map(Into::into),
             value,
         })
     }
@@ -0,0 +1,52 @@
+use crate::context::Context;
+use crate::serial::Serial;
+use aldrin_core::message;
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EmitEventAckedReply {
+    pub serial: Serial,
+    pub delivered: u32,
+}
+
+impl EmitEventAckedReply {
+    pub fn to_core(&self, ctx: &Context) -> Result<message::EmitEventAckedReply> {
+        let serial = self.serial.get(ctx)?;
+
+        Ok(message::EmitEventAckedReply {
+            serial,
+            delivered: self.delivered,
+        })
+    }
+
+    pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
+        let res = self.serial.matches(&other.serial, ctx)? && (self.delivered == other.delivered);
+        Ok(res)
+    }
+
+    pub fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
+        self.serial.update_context(&other.serial, ctx)
+    }
+
+    pub fn apply_context(&self, ctx: &Context) -> Result<Self> {
+        let serial = self.serial.apply_context(ctx)?;
+
+        Ok(Self {
+            serial,
+            delivered: self.delivered,
+        })
+    }
+}
+
+impl TryFrom<message::EmitEventAckedReply> for EmitEventAckedReply {
+    type Error = Error;
+
+    fn try_from(msg: message::EmitEventAckedReply) -> Result<Self> {
+        Ok(Self {
+            serial: msg.serial.into(),
+            delivered: msg.delivered,
+        })
+    }
+}
@@ -0,0 +1,79 @@
+use crate::context::Context;
+use crate::trace_context::TraceContext;
+use crate::uuid_ref::UuidRef;
+use crate::value::Value;
+use aldrin_core::message;
+use anyhow::{anyhow, Context as _, Error, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EmitRetainedEvent {
+    pub service_cookie: UuidRef,
+    pub event: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
+
+    #[serde(flatten)]
+    pub value: Value,
+}
+
+impl EmitRetainedEvent {
+    pub fn to_core(&self, ctx: &Context) -> Result<message::EmitRetainedEvent> {
+        let service_cookie = self.service_cookie.get(ctx)?.into();
+
+        let mut msg = message::EmitRetainedEvent::with_serialize_value(
+            service_cookie,
+            self.event,
+            &self.value,
+        )
+        .with_context(|| anyhow!("failed to serialize value"))?;
+
+        msg.trace_context = self.trace_context.map(Into::into);
+        Ok(msg)
+    }
+
+    pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
+        let res = self.service_cookie.matches(&other.service_cookie, ctx)?
+            && (self.event == other.event)
+            && (self.trace_context == other.trace_context);
+
+        Ok(res)
+    }
+
+    pub fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
+        self.service_cookie
+            .update_context(&other.service_cookie, ctx)?;
+        Ok(())
+    }
+
+    pub fn apply_context(&self, ctx: &Context) -> Result<Self> {
+        let service_cookie = self.service_cookie.apply_context(ctx)?;
+
+        Ok(Self {
+            service_cookie,
+            event: self.event,
+            trace_context: self.trace_context,
+            value: self.value.clone(),
+        })
+    }
+}
+
+impl TryFrom<message::EmitRetainedEvent> for EmitRetainedEvent {
+    type Error = Error;
+
+    fn try_from(msg: message::EmitRetainedEvent) -> Result<Self> {
+        let value = msg
+            .value
+            .deserialize()
+            .with_context(|| anyhow!("failed to deserialize value `{:?}`", msg.value))?;
+
+        Ok(Self {
+            service_cookie: msg.service_cookie.into(),
+            event: msg.event,
+            trace_context: msg.trace_context.map(Into::into),
+            value,
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use crate::context::Context;
 use crate::serial::Serial;
 use crate::uuid_ref::UuidRef;
-use aldrin_core::message;
+use aldrin_core::{message, Compression};
 use anyhow::{Error, Result};
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +10,12 @@ use serde::{Deserialize, Serialize};
 pub struct QueryIntrospection {
     pub serial: Serial,
     pub type_id: UuidRef,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub known: Vec<UuidRef>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<QueryIntrospectionCompression>,
 }
 
 impl QueryIntrospection {
@@ -17,20 +23,46 @@ impl QueryIntrospection {
         let serial = self.serial.get(ctx)?;
         let type_id = self.type_id.get(ctx)?.into();
 
-        Ok(message::QueryIntrospection { serial, type_id })
+        let known = self
+            .known
+            .iter()
+            .map(|type_id| type_id.get(ctx).map(Into::into))
+            .collect::<Result<_>>()?;
+
+        Ok(message::QueryIntrospection {
+            serial,
+            type_id,
+            known,
+            compression: self.compression.map(Into::into),
+        })
     }
 
     pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
-        let res = self.serial.matches(&other.serial, ctx)?
-            && self.type_id.matches(&other.type_id, ctx)?;
+        if !self.serial.matches(&other.serial, ctx)?
+            || !self.type_id.matches(&other.type_id, ctx)?
+            || (self.known.len() != other.known.len())
+            || (self.compression != other.compression)
+        {
+            return Ok(false);
+        }
+
+        for (t1, t2) in self.known.iter().zip(&other.known) {
+            if !t1.matches(t2, ctx)? {
+                return Ok(false);
+            }
+        }
 
-        Ok(res)
+        Ok(true)
     }
 
     pub fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
         self.serial.update_context(&other.serial, ctx)?;
         self.type_id.update_context(&other.type_id, ctx)?;
 
+        for (t1, t2) in self.known.iter().zip(&other.known) {
+            t1.update_context(t2, ctx)?;
+        }
+
         Ok(())
     }
 
@@ -38,7 +70,18 @@ impl QueryIntrospection {
         let serial = self.serial.apply_context(ctx)?;
         let type_id = self.type_id.apply_context(ctx)?;
 
-        Ok(Self { serial, type_id })
+        let known = self
+            .known
+            .iter()
+            .map(|type_id| type_id.apply_context(ctx))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            serial,
+            type_id,
+            known,
+            compression: self.compression,
+        })
     }
 }
 
@@ -49,6 +92,30 @@ impl TryFrom<message::QueryIntrospection> for QueryIntrospection {
         Ok(Self {
             serial: msg.serial.into(),
             type_id: msg.type_id.into(),
+            known: msg.known.into_iter().map(Into::into).collect(),
+            compression: msg.compression.map(Into::into),
         })
     }
 }
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QueryIntrospectionCompression {
+    Lz4,
+}
+
+impl From<QueryIntrospectionCompression> for Compression {
+    fn from(compression: QueryIntrospectionCompression) -> Self {
+        match compression {
+            QueryIntrospectionCompression::Lz4 => Self::Lz4,
+        }
+    }
+}
+
+impl From<Compression> for QueryIntrospectionCompression {
+    fn from(compression: Compression) -> Self {
+        match compression {
+            Compression::Lz4 => Self::Lz4,
+        }
+    }
+}
@@ -66,6 +66,16 @@ pub enum QueryIntrospectionResult {
     },
 
     Unavailable,
+
+    Bundle {
+        #[serde(flatten)]
+        value: Value,
+    },
+
+    CompressedBundle {
+        #[serde(flatten)]
+        value: Value,
+    },
 }
 
 impl QueryIntrospectionResult {
@@ -76,6 +86,14 @@ impl QueryIntrospectionResult {
                 .with_context(|| anyhow!("failed to serialize value")),
 
             Self::Unavailable => Ok(message::QueryIntrospectionResult::Unavailable),
+
+            Self::Bundle { value } => SerializedValue::serialize(value)
+                .map(message::QueryIntrospectionResult::Bundle)
+                .with_context(|| anyhow!("failed to serialize value")),
+
+            Self::CompressedBundle { value } => SerializedValue::serialize(value)
+                .map(message::QueryIntrospectionResult::CompressedBundle)
+                .with_context(|| anyhow!("failed to serialize value")),
         }
     }
 
@@ -83,6 +101,12 @@ impl QueryIntrospectionResult {
         match (self, other) {
             (Self::Ok { value: v1 }, Self::Ok { value: v2 }) => Ok(v1.matches(v2)),
             (Self::Unavailable, Self::Unavailable) => Ok(true),
+            (Self::Bundle { value: v1 }, Self::Bundle { value: v2 }) => Ok(v1.matches(v2)),
+
+            (Self::CompressedBundle { value: v1 }, Self::CompressedBundle { value: v2 }) => {
+                Ok(v1.matches(v2))
+            }
+
             _ => Ok(false),
         }
     }
@@ -110,6 +134,22 @@ impl TryFrom<message::QueryIntrospectionResult> for QueryIntrospectionResult {
             }
 
             message::QueryIntrospectionResult::Unavailable => Ok(Self::Unavailable),
+
+            message::QueryIntrospectionResult::Bundle(value) => {
+                let value = value
+                    .deserialize()
+                    .with_context(|| anyhow!("failed to deserialize value `{:?}`", value))?;
+
+                Ok(Self::Bundle { value })
+            }
+
+            message::QueryIntrospectionResult::CompressedBundle(value) => {
+                let value = value
+                    .deserialize()
+                    .with_context(|| anyhow!("failed to deserialize value `{:?}`", value))?;
+
+                Ok(Self::CompressedBundle { value })
+            }
         }
     }
 }
@@ -0,0 +1,88 @@
+use crate::context::Context;
+use crate::serial::Serial;
+use crate::trace_context::TraceContext;
+use crate::uuid_ref::UuidRef;
+use crate::value::Value;
+use aldrin_core::message;
+use anyhow::{anyhow, Context as _, Error, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReliableEventReceived {
+    pub serial: Serial,
+    pub service_cookie: UuidRef,
+    pub event: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
+
+    #[serde(flatten)]
+    pub value: Value,
+}
+
+impl ReliableEventReceived {
+    pub fn to_core(&self, ctx: &Context) -> Result<message::ReliableEventReceived> {
+        let serial = self.serial.get(ctx)?;
+        let service_cookie = self.service_cookie.get(ctx)?.into();
+
+        let mut msg = message::ReliableEventReceived::with_serialize_value(
+            serial,
+            service_cookie,
+            self.event,
+            &self.value,
+        )
+        .with_context(|| anyhow!("failed to serialize value"))?;
+
+        msg.trace_context = self.trace_context.map(Into::into);
+        Ok(msg)
+    }
+
+    pub fn matches(&self, other: &Self, ctx: &Context) -> Result<bool> {
+        let res = self.serial.matches(&other.serial, ctx)?
+            && self.service_cookie.matches(&other.service_cookie, ctx)?
+            && (self.event == other.event)
+            && (self.trace_context == other.trace_context);
+
+        Ok(res)
+    }
+
+    pub fn update_context(&self, other: &Self, ctx: &mut Context) -> Result<()> {
+        self.serial.update_context(&other.serial, ctx)?;
+        self.service_cookie
+            .update_context(&other.service_cookie, ctx)?;
+        Ok(())
+    }
+
+    pub fn apply_context(&self, ctx: &Context) -> Result<Self> {
+        let serial = self.serial.apply_context(ctx)?;
+        let service_cookie = self.service_cookie.apply_context(ctx)?;
+
+        Ok(Self {
+            serial,
+            service_cookie,
+            event: self.event,
+            trace_context: self.trace_context,
+            value: self.value.clone(),
+        })
+    }
+}
+
+impl TryFrom<message::ReliableEventReceived> for ReliableEventReceived {
+    type Error = Error;
+
+    fn try_from(msg: message::ReliableEventReceived) -> Result<Self> {
+        let value = msg
+            .value
+            .deserialize()
+            .with_context(|| anyhow!("failed to deserialize value `{:?}`", msg.value))?;
+
+        Ok(Self {
+            serial: msg.serial.into(),
+            service_cookie: msg.service_cookie.into(),
+            event: msg.event,
+            trace_context: msg.trace_context.map(Into::into),
+            value,
+        })
+    }
+}
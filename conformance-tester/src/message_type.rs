@@ -6,8 +6,11 @@ use std::fmt;
 #[serde(rename_all = "kebab-case")]
 pub enum MessageType {
     AbortFunctionCall,
+    AckEvent,
     AddBusListenerFilter,
     AddChannelCapacity,
+    AuthChallenge,
+    AuthResponse,
     BusListenerCurrentFinished,
     CallFunction,
     CallFunctionReply,
@@ -31,14 +34,22 @@ pub enum MessageType {
     CreateService,
     CreateService2,
     CreateServiceReply,
+    CreateServices,
+    CreateServicesReply,
     DestroyBusListener,
     DestroyBusListenerReply,
     DestroyObject,
     DestroyObjectReply,
     DestroyService,
     DestroyServiceReply,
+    DestroyServices,
+    DestroyServicesReply,
     EmitBusEvent,
     EmitEvent,
+    EmitEventAcked,
+    EmitEventAckedReply,
+    EmitReliableEvent,
+    EmitRetainedEvent,
     ItemReceived,
     QueryIntrospection,
     QueryIntrospectionReply,
@@ -47,6 +58,7 @@ pub enum MessageType {
     QueryServiceVersion,
     QueryServiceVersionReply,
     RegisterIntrospection,
+    ReliableEventReceived,
     RemoveBusListenerFilter,
     SendItem,
     ServiceDestroyed,
@@ -73,8 +85,11 @@ impl fmt::Display for MessageType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Self::AbortFunctionCall => f.pad("abort-function-call"),
+            Self::AckEvent => f.pad("ack-event"),
             Self::AddBusListenerFilter => f.pad("add-bus-listener-filter"),
             Self::AddChannelCapacity => f.pad("add-channel-capacity"),
+            Self::AuthChallenge => f.pad("auth-challenge"),
+            Self::AuthResponse => f.pad("auth-response"),
             Self::BusListenerCurrentFinished => f.pad("bus-listener-current-finished"),
             Self::CallFunction => f.pad("call-function"),
             Self::CallFunctionReply => f.pad("call-function-reply"),
@@ -98,14 +113,22 @@ impl fmt::Display for MessageType {
             Self::CreateService => f.pad("create-service"),
             Self::CreateService2 => f.pad("create-service2"),
             Self::CreateServiceReply => f.pad("create-service-reply"),
+            Self::CreateServices => f.pad("create-services"),
+            Self::CreateServicesReply => f.pad("create-services-reply"),
             Self::DestroyBusListener => f.pad("destroy-bus-listener"),
             Self::DestroyBusListenerReply => f.pad("destroy-bus-listener-reply"),
             Self::DestroyObject => f.pad("destroy-object"),
             Self::DestroyObjectReply => f.pad("destroy-object-reply"),
             Self::DestroyService => f.pad("destroy-service"),
             Self::DestroyServiceReply => f.pad("destroy-service-reply"),
+            Self::DestroyServices => f.pad("destroy-services"),
+            Self::DestroyServicesReply => f.pad("destroy-services-reply"),
             Self::EmitBusEvent => f.pad("emit-bus-event"),
             Self::EmitEvent => f.pad("emit-event"),
+            Self::EmitEventAcked => f.pad("emit-event-acked"),
+            Self::EmitEventAckedReply => f.pad("emit-event-acked-reply"),
+            Self::EmitReliableEvent => f.pad("emit-reliable-event"),
+            Self::EmitRetainedEvent => f.pad("emit-retained-event"),
             Self::ItemReceived => f.pad("item-received"),
             Self::QueryIntrospection => f.pad("query-introspection"),
             Self::QueryIntrospectionReply => f.pad("query-introspection-reply"),
@@ -114,6 +137,7 @@ impl fmt::Display for MessageType {
             Self::QueryServiceVersion => f.pad("query-service-version"),
             Self::QueryServiceVersionReply => f.pad("query-service-version-reply"),
             Self::RegisterIntrospection => f.pad("register-introspection"),
+            Self::ReliableEventReceived => f.pad("reliable-event-received"),
             Self::RemoveBusListenerFilter => f.pad("remove-bus-listener-filter"),
             Self::SendItem => f.pad("send-item"),
             Self::ServiceDestroyed => f.pad("service-destroyed"),
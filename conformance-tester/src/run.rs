@@ -72,6 +72,7 @@ async fn run_test(
 ) -> Result<Duration, RunError> {
     let mut broker = Broker::new(
         args.broker.as_os_str(),
+        args.transport,
         Duration::from_millis(args.startup_timeout),
     )
     .await
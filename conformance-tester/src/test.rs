@@ -77,13 +77,22 @@ pub static BUILT_IN_TESTS: Lazy<Vec<Test>> = Lazy::new(|| {
         include_str!("../tests/abort-call-old-callee.json"),
         include_str!("../tests/abort-call-old-version.json"),
         include_str!("../tests/abort-invalid-call.json"),
+        include_str!("../tests/broadcast-channel.json"),
+        include_str!("../tests/broadcast-channel-old-version.json"),
+        include_str!("../tests/bus-listener-layout-filter.json"),
         include_str!("../tests/call-function-aborted.json"),
         include_str!("../tests/call-function-err.json"),
         include_str!("../tests/call-function-invalid-args.json"),
         include_str!("../tests/call-function-invalid-service.json"),
         include_str!("../tests/call-function-ok.json"),
+        include_str!("../tests/call-function-overloaded.json"),
+        include_str!("../tests/call-function-priority-old-version.json"),
+        include_str!("../tests/call-function-priority.json"),
+        include_str!("../tests/call-function-trace-context.json"),
         include_str!("../tests/call-function.json"),
         include_str!("../tests/call-invalid-function.json"),
+        include_str!("../tests/channel-bytes-capacity.json"),
+        include_str!("../tests/channel-bytes-capacity-old-sender.json"),
         include_str!("../tests/channel-capacity-overflow-1.json"),
         include_str!("../tests/channel-capacity-overflow-2.json"),
         include_str!("../tests/claim-invalid-receiver.json"),
@@ -122,6 +131,8 @@ pub static BUILT_IN_TESTS: Lazy<Vec<Test>> = Lazy::new(|| {
         include_str!("../tests/create-service-ok.json"),
         include_str!("../tests/create-service2-invalid-info.json"),
         include_str!("../tests/create-service2-ok.json"),
+        include_str!("../tests/create-services-duplicate.json"),
+        include_str!("../tests/create-services-ok.json"),
         include_str!("../tests/destroy-bus-listener.json"),
         include_str!("../tests/destroy-foreign-bus-listener.json"),
         include_str!("../tests/destroy-foreign-object.json"),
@@ -134,7 +145,11 @@ pub static BUILT_IN_TESTS: Lazy<Vec<Test>> = Lazy::new(|| {
         include_str!("../tests/emit-event-0-subscribers.json"),
         include_str!("../tests/emit-event-1-subscriber.json"),
         include_str!("../tests/emit-event-2-subscribers.json"),
+        include_str!("../tests/emit-event-acked.json"),
         include_str!("../tests/emit-event-foreign-service.json"),
+        include_str!("../tests/emit-event-trace-context.json"),
+        include_str!("../tests/emit-reliable-event.json"),
+        include_str!("../tests/emit-retained-event.json"),
         include_str!("../tests/invalid-message.json"),
         include_str!("../tests/one-bus-event-per-client.json"),
         include_str!("../tests/query-invalid-service-version.json"),
@@ -44,7 +44,7 @@ impl ConnectClient {
     }
 
     async fn run_impl(&self, broker: &Broker, ctx: &mut Context, timeout: Instant) -> Result<()> {
-        let client = Client::connect(broker.port(), timeout, self.sync, self.shutdown).await?;
+        let client = Client::connect(broker.endpoint(), timeout, self.sync, self.shutdown).await?;
         ctx.set_client(self.client.clone(), client)?;
 
         if self.handshake {
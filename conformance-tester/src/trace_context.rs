@@ -0,0 +1,87 @@
+use aldrin_core::TraceContext as CoreTraceContext;
+use serde::de::{Error, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TraceContext(CoreTraceContext);
+
+impl TraceContext {
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.split('-');
+
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        if (version != "00")
+            || (trace_id.len() != 32)
+            || (span_id.len() != 16)
+            || (flags.len() != 2)
+            || parts.next().is_some()
+        {
+            return None;
+        }
+
+        let trace_id = u128::from_str_radix(trace_id, 16).ok()?;
+        let span_id = u64::from_str_radix(span_id, 16).ok()?;
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+
+        Some(Self(CoreTraceContext::new(
+            trace_id,
+            span_id,
+            (flags & 0x01) != 0,
+        )))
+    }
+}
+
+impl From<CoreTraceContext> for TraceContext {
+    fn from(ctx: CoreTraceContext) -> Self {
+        Self(ctx)
+    }
+}
+
+impl From<TraceContext> for CoreTraceContext {
+    fn from(ctx: TraceContext) -> Self {
+        ctx.0
+    }
+}
+
+impl Serialize for TraceContext {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for TraceContext {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TraceContextVisitor;
+
+        impl Visitor<'_> for TraceContextVisitor {
+            type Value = TraceContext;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    formatter,
+                    "a W3C traceparent of the form `00-{{trace-id}}-{{span-id}}-{{flags}}`"
+                )
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                TraceContext::parse(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(TraceContextVisitor)
+    }
+}
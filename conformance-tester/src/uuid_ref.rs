@@ -1,6 +1,7 @@
 use crate::context::Context;
 use aldrin_core::{
-    BusListenerCookie, ChannelCookie, ObjectCookie, ObjectUuid, ServiceCookie, ServiceUuid, TypeId,
+    BusListenerCookie, ChannelCookie, LexicalId, ObjectCookie, ObjectUuid, ServiceCookie,
+    ServiceUuid, TypeId,
 };
 use anyhow::{anyhow, Error, Result};
 use serde::{Deserialize, Serialize};
@@ -169,3 +170,9 @@ impl From<TypeId> for UuidRef {
         value.0.into()
     }
 }
+
+impl From<LexicalId> for UuidRef {
+    fn from(value: LexicalId) -> Self {
+        value.0.into()
+    }
+}
@@ -0,0 +1,76 @@
+use crate::error::{DeserializeError, SerializeError};
+use crate::value_deserializer::{Deserialize, Deserializer};
+use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+use core::fmt;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// Reason why a broker rejected the authentication of a client.
+///
+/// Brokers send this to clients as part of the handshake, when authentication fails. Unknown
+/// reasons, e.g. from newer brokers, are mapped to [`Other`](Self::Other).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[repr(u32)]
+pub enum AuthRejection {
+    /// The client didn't provide any credentials.
+    MissingCredentials = 0,
+
+    /// The client's credentials are invalid.
+    InvalidCredentials = 1,
+
+    /// The client's credentials have expired.
+    Expired = 2,
+
+    /// The client was authenticated, but is not allowed to connect.
+    Forbidden = 3,
+
+    /// The client didn't complete the authentication in time.
+    Timeout = 4,
+
+    /// Authentication failed for some other reason.
+    Other = 5,
+}
+
+impl fmt::Display for AuthRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingCredentials => f.write_str("missing credentials"),
+            Self::InvalidCredentials => f.write_str("invalid credentials"),
+            Self::Expired => f.write_str("credentials expired"),
+            Self::Forbidden => f.write_str("forbidden"),
+            Self::Timeout => f.write_str("authentication timed out"),
+            Self::Other => f.write_str("authentication failed"),
+        }
+    }
+}
+
+impl Serialize for AuthRejection {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        serializer.serialize_u32((*self).into());
+        Ok(())
+    }
+}
+
+impl Deserialize for AuthRejection {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer
+            .deserialize_u32()
+            .map(|reason| Self::try_from(reason).unwrap_or(Self::Other))
+    }
+}
+
+impl AsSerializeArg for AuthRejection {
+    type SerializeArg<'a> = Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        *self
+    }
+}
@@ -2,6 +2,7 @@
 mod test;
 
 use crate::error::DeserializeError;
+#[cfg(feature = "std")]
 use crate::message_deserializer::MessageDeserializeError;
 use bytes::{Buf, BufMut, Bytes};
 
@@ -191,6 +192,7 @@ pub(crate) trait ValueBufExt: Buf {
 
 impl<T: Buf + ?Sized> ValueBufExt for T {}
 
+#[cfg(feature = "std")]
 pub(crate) trait MessageBufExt: Buf {
     fn try_get_discriminant_u8<T: TryFrom<u8>>(&mut self) -> Result<T, MessageDeserializeError> {
         self.try_get_u8()?
@@ -245,6 +247,7 @@ pub(crate) trait MessageBufExt: Buf {
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Buf + ?Sized> MessageBufExt for T {}
 
 fn zigzag_encode_i16(n: i16) -> u16 {
@@ -9,11 +9,11 @@ use bytes::BytesMut;
 fn put_discriminant_u8() {
     let mut buf = BytesMut::new();
     buf.put_discriminant_u8(ValueKind::U32);
-    assert_eq!(*buf, [ValueKind::U32.into()]);
+    assert_eq!(*buf, [u8::from(ValueKind::U32)]);
 
     let mut buf = BytesMut::new();
     buf.put_discriminant_u8(MessageKind::CallFunction);
-    assert_eq!(*buf, [MessageKind::CallFunction.into()]);
+    assert_eq!(*buf, [u8::from(MessageKind::CallFunction)]);
 }
 
 #[test]
@@ -230,13 +230,13 @@ fn try_put_varint_i64_le() {
 fn value_try_get_discriminant_u8() {
     use super::ValueBufExt;
 
-    let mut buf = &[ValueKind::U32.into()][..];
+    let mut buf = &[u8::from(ValueKind::U32)][..];
     assert_eq!(buf.try_get_discriminant_u8(), Ok(ValueKind::U32));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
-    let mut buf = &[MessageKind::CallFunction.into()][..];
+    let mut buf = &[u8::from(MessageKind::CallFunction)][..];
     assert_eq!(buf.try_get_discriminant_u8(), Ok(MessageKind::CallFunction));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255][..];
     assert_eq!(
@@ -283,11 +283,11 @@ fn value_try_get_u8() {
 
     let mut buf = &[0][..];
     assert_eq!(buf.try_get_u8(), Ok(0));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255][..];
     assert_eq!(buf.try_get_u8(), Ok(255));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 }
 
 #[test]
@@ -296,19 +296,19 @@ fn value_try_get_i8() {
 
     let mut buf = &[0][..];
     assert_eq!(buf.try_get_i8(), Ok(0));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255][..];
     assert_eq!(buf.try_get_i8(), Ok(-1));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[127][..];
     assert_eq!(buf.try_get_i8(), Ok(127));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[128][..];
     assert_eq!(buf.try_get_i8(), Ok(-128));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 }
 
 #[test]
@@ -317,7 +317,7 @@ fn value_try_get_u32_le() {
 
     let mut buf = &[0x78, 0x56, 0x34, 0x12][..];
     assert_eq!(buf.try_get_u32_le(), Ok(0x12345678));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[0, 0, 0][..];
     assert_eq!(buf.try_get_u32_le(), Err(DeserializeError::UnexpectedEoi));
@@ -329,7 +329,7 @@ fn value_try_get_u64_le() {
 
     let mut buf = &[0xf0, 0xde, 0xbc, 0x9a, 0x78, 0x56, 0x34, 0x12][..];
     assert_eq!(buf.try_get_u64_le(), Ok(0x123456789abcdef0));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[0, 0, 0, 0, 0, 0, 0][..];
     assert_eq!(buf.try_get_u64_le(), Err(DeserializeError::UnexpectedEoi));
@@ -341,27 +341,27 @@ fn value_try_get_varint_u16_le() {
 
     let mut buf = &[0x00][..];
     assert_eq!(buf.try_get_varint_u16_le(), Ok(0x0000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[0xfd][..];
     assert_eq!(buf.try_get_varint_u16_le(), Ok(0x00fd));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[254, 0xfe][..];
     assert_eq!(buf.try_get_varint_u16_le(), Ok(0x00fe));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[254, 0xff][..];
     assert_eq!(buf.try_get_varint_u16_le(), Ok(0x00ff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u16_le(), Ok(0x0100));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u16_le(), Ok(0xffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 }
 
 #[test]
@@ -370,19 +370,19 @@ fn value_try_get_varint_i16_le() {
 
     let mut buf = &[0][..];
     assert_eq!(buf.try_get_varint_i16_le(), Ok(0));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[2][..];
     assert_eq!(buf.try_get_varint_i16_le(), Ok(1));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 254, 255][..];
     assert_eq!(buf.try_get_varint_i16_le(), Ok(i16::MAX));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 255, 255][..];
     assert_eq!(buf.try_get_varint_i16_le(), Ok(i16::MIN));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 }
 
 #[test]
@@ -391,43 +391,43 @@ fn value_try_get_varint_u32_le() {
 
     let mut buf = &[0x00][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x00000000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[0xfb][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x000000fb));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[252, 0xfc][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x000000fc));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[252, 0xff][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x000000ff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[253, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x00000100));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[253, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x0000ffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[254, 0x00, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x00010000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[254, 0xff, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x00ffffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 0x00, 0x00, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x01000000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 0xff, 0xff, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0xffffffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 }
 
 #[test]
@@ -436,23 +436,23 @@ fn value_try_get_varint_i32_le() {
 
     let mut buf = &[0][..];
     assert_eq!(buf.try_get_varint_i32_le(), Ok(0));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[2][..];
     assert_eq!(buf.try_get_varint_i32_le(), Ok(1));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[1][..];
     assert_eq!(buf.try_get_varint_i32_le(), Ok(-1));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 254, 255, 255, 255][..];
     assert_eq!(buf.try_get_varint_i32_le(), Ok(i32::MAX));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 255, 255, 255, 255][..];
     assert_eq!(buf.try_get_varint_i32_le(), Ok(i32::MIN));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 }
 
 #[test]
@@ -461,75 +461,75 @@ fn value_try_get_varint_u64_le() {
 
     let mut buf = &[0x00][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x0000000000000000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[0xf7][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x00000000000000f7));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[248, 0xf8][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x00000000000000f8));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[248, 0xff][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x00000000000000ff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[249, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x0000000000000100));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[249, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x000000000000ffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[250, 0x00, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x0000000000010000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[250, 0xff, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x0000000000ffffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[251, 0x00, 0x00, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x0000000001000000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[251, 0xff, 0xff, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x00000000ffffffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[252, 0x00, 0x00, 0x00, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x0000000100000000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[252, 0xff, 0xff, 0xff, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x000000ffffffffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[253, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x0000010000000000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[253, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x0000ffffffffffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[254, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x0001000000000000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[254, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x00ffffffffffffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0x0100000000000000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u64_le(), Ok(0xffffffffffffffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 }
 
 #[test]
@@ -538,23 +538,23 @@ fn value_try_get_varint_i64_le() {
 
     let mut buf = &[0][..];
     assert_eq!(buf.try_get_varint_i64_le(), Ok(0));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[2][..];
     assert_eq!(buf.try_get_varint_i64_le(), Ok(1));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[1][..];
     assert_eq!(buf.try_get_varint_i64_le(), Ok(-1));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 254, 255, 255, 255, 255, 255, 255, 255][..];
     assert_eq!(buf.try_get_varint_i64_le(), Ok(i64::MAX));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 255, 255, 255, 255, 255, 255, 255, 255][..];
     assert_eq!(buf.try_get_varint_i64_le(), Ok(i64::MIN));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 }
 
 #[test]
@@ -563,7 +563,7 @@ fn value_try_copy_to_bytes() {
 
     let mut buf = &[1, 2, 3][..];
     assert_eq!(*buf.try_copy_to_bytes(3).unwrap(), [1, 2, 3]);
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[1, 2, 3][..];
     assert_eq!(*buf.try_copy_to_bytes(2).unwrap(), [1, 2]);
@@ -584,7 +584,7 @@ fn value_try_copy_to_slice() {
     let mut dst = [0, 0, 0];
     src.try_copy_to_slice(&mut dst).unwrap();
     assert_eq!(dst, [1, 2, 3]);
-    assert_eq!(*src, []);
+    assert!(src.is_empty());
 
     let mut src = &[1, 2, 3][..];
     let mut dst = [0, 0, 0];
@@ -620,7 +620,7 @@ fn value_try_skip() {
 
     let mut buf = &[1, 2][..];
     buf.try_skip(2).unwrap();
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[1, 2][..];
     assert_eq!(buf.try_skip(3), Err(DeserializeError::UnexpectedEoi));
@@ -630,13 +630,13 @@ fn value_try_skip() {
 fn message_try_get_discriminant_u8() {
     use super::MessageBufExt;
 
-    let mut buf = &[ValueKind::U32.into()][..];
+    let mut buf = &[u8::from(ValueKind::U32)][..];
     assert_eq!(buf.try_get_discriminant_u8(), Ok(ValueKind::U32));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
-    let mut buf = &[MessageKind::CallFunction.into()][..];
+    let mut buf = &[u8::from(MessageKind::CallFunction)][..];
     assert_eq!(buf.try_get_discriminant_u8(), Ok(MessageKind::CallFunction));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255][..];
     assert_eq!(
@@ -651,11 +651,11 @@ fn message_try_get_u8() {
 
     let mut buf = &[0][..];
     assert_eq!(buf.try_get_u8(), Ok(0));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255][..];
     assert_eq!(buf.try_get_u8(), Ok(255));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 }
 
 #[test]
@@ -664,43 +664,43 @@ fn message_try_get_varint_u32_le() {
 
     let mut buf = &[0x00][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x00000000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[0xfb][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x000000fb));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[252, 0xfc][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x000000fc));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[252, 0xff][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x000000ff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[253, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x00000100));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[253, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x0000ffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[254, 0x00, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x00010000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[254, 0xff, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x00ffffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 0x00, 0x00, 0x00, 0x01][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0x01000000));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 
     let mut buf = &[255, 0xff, 0xff, 0xff, 0xff][..];
     assert_eq!(buf.try_get_varint_u32_le(), Ok(0xffffffff));
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
 }
 
 #[test]
@@ -711,7 +711,7 @@ fn message_try_copy_to_slice() {
     let mut dst = [0, 0, 0];
     src.try_copy_to_slice(&mut dst).unwrap();
     assert_eq!(dst, [1, 2, 3]);
-    assert_eq!(*src, []);
+    assert!(src.is_empty());
 
     let mut src = &[1, 2, 3][..];
     let mut dst = [0, 0, 0];
@@ -1,10 +1,15 @@
-use crate::ids::{ObjectId, ObjectUuid, ServiceId, ServiceUuid};
+use crate::ids::{LexicalId, ObjectId, ObjectUuid, ServiceId, ServiceUuid};
 use crate::message_deserializer::{MessageDeserializeError, MessageWithoutValueDeserializer};
 use crate::message_serializer::MessageSerializer;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum BusEvent {
     ObjectCreated(ObjectId),
     ObjectDestroyed(ObjectId),
@@ -14,6 +19,11 @@ pub enum BusEvent {
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 #[repr(u8)]
 pub enum BusListenerScope {
     Current = 0,
@@ -33,9 +43,15 @@ impl BusListenerScope {
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum BusListenerFilter {
     Object(Option<ObjectUuid>),
     Service(BusListenerServiceFilter),
+    Layout(BusListenerLayoutFilter),
 }
 
 impl BusListenerFilter {
@@ -69,18 +85,44 @@ impl BusListenerFilter {
         ))
     }
 
+    pub fn layout(filter: BusListenerLayoutFilter) -> Self {
+        Self::Layout(filter)
+    }
+
+    pub fn any_object_layout(layout: LexicalId) -> Self {
+        Self::layout(BusListenerLayoutFilter::new(layout))
+    }
+
+    pub fn specific_object_layout(object: ObjectUuid, layout: LexicalId) -> Self {
+        Self::layout(BusListenerLayoutFilter::with_object(object, layout))
+    }
+
     pub fn matches_object(self, object: ObjectId) -> bool {
         match self {
             Self::Object(None) => true,
             Self::Object(Some(filter)) => object.uuid == filter,
-            Self::Service(_) => false,
+            Self::Service(_) | Self::Layout(_) => false,
         }
     }
 
+    /// Checks whether the filter matches a service without considering its layout.
+    ///
+    /// [`Layout`](Self::Layout) filters never match here. Use
+    /// [`matches_service_layout`](Self::matches_service_layout) if the layout of the service is
+    /// known.
     pub fn matches_service(self, service: ServiceId) -> bool {
+        match self {
+            Self::Object(_) | Self::Layout(_) => false,
+            Self::Service(filter) => filter.matches(service),
+        }
+    }
+
+    /// Checks whether the filter matches a service with an optional layout.
+    pub fn matches_service_layout(self, service: ServiceId, layout: Option<LexicalId>) -> bool {
         match self {
             Self::Object(_) => false,
             Self::Service(filter) => filter.matches(service),
+            Self::Layout(filter) => filter.matches(service, layout),
         }
     }
 
@@ -135,6 +177,23 @@ impl BusListenerFilter {
                 serializer.put_uuid(object.0);
                 serializer.put_uuid(service.0);
             }
+
+            Self::Layout(BusListenerLayoutFilter {
+                object: None,
+                layout,
+            }) => {
+                serializer.put_discriminant_u8(BusListenerFilterKind::AnyObjectSpecificLayout);
+                serializer.put_uuid(layout.0);
+            }
+
+            Self::Layout(BusListenerLayoutFilter {
+                object: Some(object),
+                layout,
+            }) => {
+                serializer.put_discriminant_u8(BusListenerFilterKind::SpecificObjectSpecificLayout);
+                serializer.put_uuid(object.0);
+                serializer.put_uuid(layout.0);
+            }
         }
     }
 
@@ -166,6 +225,17 @@ impl BusListenerFilter {
                 let service = deserializer.try_get_uuid().map(ServiceUuid)?;
                 Ok(Self::specific_object_and_service(object, service))
             }
+
+            BusListenerFilterKind::AnyObjectSpecificLayout => {
+                let layout = deserializer.try_get_uuid().map(LexicalId)?;
+                Ok(Self::any_object_layout(layout))
+            }
+
+            BusListenerFilterKind::SpecificObjectSpecificLayout => {
+                let object = deserializer.try_get_uuid().map(ObjectUuid)?;
+                let layout = deserializer.try_get_uuid().map(LexicalId)?;
+                Ok(Self::specific_object_layout(object, layout))
+            }
         }
     }
 }
@@ -188,8 +258,19 @@ impl From<BusListenerServiceFilter> for BusListenerFilter {
     }
 }
 
+impl From<BusListenerLayoutFilter> for BusListenerFilter {
+    fn from(filter: BusListenerLayoutFilter) -> Self {
+        Self::Layout(filter)
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct BusListenerServiceFilter {
     pub object: Option<ObjectUuid>,
     pub service: Option<ServiceUuid>,
@@ -234,6 +315,50 @@ impl BusListenerServiceFilter {
     }
 }
 
+/// Matches services by their layout.
+///
+/// The layout of a service is identified by the [`LexicalId`] in its
+/// [`ServiceInfo`](crate::ServiceInfo), i.e. by the schema and name of the service. Services
+/// without a [`LexicalId`] never match.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct BusListenerLayoutFilter {
+    pub object: Option<ObjectUuid>,
+    pub layout: LexicalId,
+}
+
+impl BusListenerLayoutFilter {
+    pub fn new(layout: LexicalId) -> Self {
+        Self {
+            object: None,
+            layout,
+        }
+    }
+
+    pub fn with_object(object: ObjectUuid, layout: LexicalId) -> Self {
+        Self {
+            object: Some(object),
+            layout,
+        }
+    }
+
+    pub fn matches(self, id: ServiceId, layout: Option<LexicalId>) -> bool {
+        if layout != Some(self.layout) {
+            return false;
+        }
+
+        match self.object {
+            Some(object) => id.object_id.uuid == object,
+            None => true,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[repr(u8)]
 enum BusListenerFilterKind {
@@ -243,4 +368,6 @@ enum BusListenerFilterKind {
     SpecificObjectAnyService = 3,
     AnyObjectSpecificService = 4,
     SpecificObjectSpecificService = 5,
+    AnyObjectSpecificLayout = 6,
+    SpecificObjectSpecificLayout = 7,
 }
@@ -0,0 +1,42 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// Priority of a function call.
+///
+/// Brokers forward high-priority calls and their replies ahead of other queued messages. This keeps
+/// control-plane calls responsive on connections, that are congested by bulk traffic such as
+/// events or channel items. Low-priority calls are currently scheduled like normal ones.
+///
+/// Priorities other than [`Normal`](Self::Normal) require protocol version 1.19.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    IntoPrimitive,
+    TryFromPrimitive,
+)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[repr(u8)]
+pub enum CallPriority {
+    /// Low priority.
+    Low = 0,
+
+    /// Normal priority.
+    ///
+    /// This is the default.
+    #[default]
+    Normal = 1,
+
+    /// High priority.
+    High = 2,
+}
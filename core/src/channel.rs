@@ -55,7 +55,10 @@ use crate::message::Message;
 use crate::transport::AsyncTransport;
 use futures_channel::mpsc;
 use futures_core::stream::Stream;
+use std::fmt;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::task::{Context, Poll};
 use thiserror::Error;
 
@@ -66,10 +69,12 @@ use thiserror::Error;
 pub fn bounded(fifo_size: usize) -> (Bounded, Bounded) {
     let (sender1, receiver1) = mpsc::channel(fifo_size);
     let (sender2, receiver2) = mpsc::channel(fifo_size);
+    let metrics1 = BoundedMetrics::new();
+    let metrics2 = BoundedMetrics::new();
 
     (
-        Bounded::new(receiver1, sender2),
-        Bounded::new(receiver2, sender1),
+        Bounded::new(receiver1, metrics1.clone(), sender2, metrics2.clone()),
+        Bounded::new(receiver2, metrics2, sender1, metrics1),
     )
 }
 
@@ -77,15 +82,136 @@ pub fn bounded(fifo_size: usize) -> (Bounded, Bounded) {
 ///
 /// Bounded transports have an internal fifo for receiving [`Message`s](Message). If this runs full,
 /// backpressure will be applied to the sender.
-#[derive(Debug)]
+///
+/// The messages sent by a transport are tracked in [`BoundedMetrics`], which can be used to detect
+/// slow receivers. Additionally, a callback can be invoked whenever the number of queued messages
+/// reaches a high-water mark (see [`set_watermark_callback`](Self::set_watermark_callback)).
 pub struct Bounded {
     receiver: mpsc::Receiver<Message>,
+    recv_metrics: BoundedMetrics,
     sender: mpsc::Sender<Message>,
+    send_metrics: BoundedMetrics,
+    blocked: bool,
+    watermark: Option<Watermark>,
 }
 
 impl Bounded {
-    fn new(receiver: mpsc::Receiver<Message>, sender: mpsc::Sender<Message>) -> Self {
-        Self { receiver, sender }
+    fn new(
+        receiver: mpsc::Receiver<Message>,
+        recv_metrics: BoundedMetrics,
+        sender: mpsc::Sender<Message>,
+        send_metrics: BoundedMetrics,
+    ) -> Self {
+        Self {
+            receiver,
+            recv_metrics,
+            sender,
+            send_metrics,
+            blocked: false,
+            watermark: None,
+        }
+    }
+
+    /// Returns the metrics of the messages sent by this transport.
+    ///
+    /// The returned [`BoundedMetrics`] remain valid after the transport has been moved, e.g. into a
+    /// client or connection.
+    pub fn metrics(&self) -> BoundedMetrics {
+        self.send_metrics.clone()
+    }
+
+    /// Sets a callback, that is invoked when the number of queued messages reaches `watermark`.
+    ///
+    /// Queued messages are those, that were sent by this transport, but not yet received by the
+    /// other end. The callback is passed the current number of queued messages. It is invoked again
+    /// only after the number of queued messages has dropped below `watermark` in the meantime.
+    ///
+    /// Any previously set callback is replaced.
+    pub fn set_watermark_callback<F>(&mut self, watermark: usize, callback: F)
+    where
+        F: FnMut(usize) + Send + 'static,
+    {
+        self.watermark = Some(Watermark {
+            level: watermark,
+            callback: Box::new(callback),
+            reached: false,
+        });
+    }
+
+    /// Removes the callback set with [`set_watermark_callback`](Self::set_watermark_callback).
+    pub fn clear_watermark_callback(&mut self) {
+        self.watermark = None;
+    }
+}
+
+impl fmt::Debug for Bounded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Bounded")
+            .field("receiver", &self.receiver)
+            .field("recv_metrics", &self.recv_metrics)
+            .field("sender", &self.sender)
+            .field("send_metrics", &self.send_metrics)
+            .field("blocked", &self.blocked)
+            .field("watermark", &self.watermark.as_ref().map(|w| w.level))
+            .finish()
+    }
+}
+
+struct Watermark {
+    level: usize,
+    callback: Box<dyn FnMut(usize) + Send>,
+    reached: bool,
+}
+
+/// Metrics of the messages sent by a [`Bounded`] transport.
+///
+/// `BoundedMetrics` can be cloned cheaply. All clones refer to the same transport.
+#[derive(Debug, Clone)]
+pub struct BoundedMetrics {
+    inner: Arc<MetricsInner>,
+}
+
+#[derive(Debug, Default)]
+struct MetricsInner {
+    queued: AtomicUsize,
+    max_queued: AtomicUsize,
+    blocked_sends: AtomicU64,
+}
+
+impl BoundedMetrics {
+    fn new() -> Self {
+        Self {
+            inner: Arc::new(MetricsInner::default()),
+        }
+    }
+
+    /// Returns the number of messages, that were sent but not yet received by the other end.
+    pub fn queued(&self) -> usize {
+        self.inner.queued.load(Ordering::Relaxed)
+    }
+
+    /// Returns the highest number of messages, that were queued at the same time.
+    pub fn max_queued(&self) -> usize {
+        self.inner.max_queued.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of sends, that were blocked because the fifo was full.
+    pub fn blocked_sends(&self) -> u64 {
+        self.inner.blocked_sends.load(Ordering::Relaxed)
+    }
+
+    fn push(&self) -> usize {
+        let queued = self.inner.queued.fetch_add(1, Ordering::Relaxed) + 1;
+        self.inner.max_queued.fetch_max(queued, Ordering::Relaxed);
+        queued
+    }
+
+    fn pop(&self) {
+        self.inner.queued.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    fn blocked(&self) {
+        self.inner.blocked_sends.fetch_add(1, Ordering::Relaxed);
     }
 }
 
@@ -102,7 +228,11 @@ impl AsyncTransport for Bounded {
         cx: &mut Context,
     ) -> Poll<Result<Message, Disconnected>> {
         match Pin::new(&mut self.receiver).poll_next(cx) {
-            Poll::Ready(Some(msg)) => Poll::Ready(Ok(msg)),
+            Poll::Ready(Some(msg)) => {
+                self.recv_metrics.pop();
+                Poll::Ready(Ok(msg))
+            }
+
             Poll::Ready(None) => Poll::Ready(Err(Disconnected)),
             Poll::Pending => Poll::Pending,
         }
@@ -113,14 +243,41 @@ impl AsyncTransport for Bounded {
         cx: &mut Context,
     ) -> Poll<Result<(), Disconnected>> {
         match self.sender.poll_ready(cx) {
-            Poll::Ready(Ok(())) => Poll::Ready(Ok(())),
+            Poll::Ready(Ok(())) => {
+                self.blocked = false;
+                Poll::Ready(Ok(()))
+            }
+
             Poll::Ready(Err(_)) => Poll::Ready(Err(Disconnected)),
-            Poll::Pending => Poll::Pending,
+
+            Poll::Pending => {
+                if !self.blocked {
+                    self.blocked = true;
+                    self.send_metrics.blocked();
+                }
+
+                Poll::Pending
+            }
         }
     }
 
     fn send_start(mut self: Pin<&mut Self>, msg: Message) -> Result<(), Disconnected> {
-        self.sender.start_send(msg).map_err(|_| Disconnected)
+        self.sender.start_send(msg).map_err(|_| Disconnected)?;
+
+        let queued = self.send_metrics.push();
+
+        if let Some(ref mut watermark) = self.watermark {
+            if queued <= watermark.level {
+                watermark.reached = false;
+            }
+
+            if (queued >= watermark.level) && !watermark.reached {
+                watermark.reached = true;
+                (watermark.callback)(queued);
+            }
+        }
+
+        Ok(())
     }
 
     fn send_poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Result<(), Disconnected>> {
@@ -185,3 +342,53 @@ impl AsyncTransport for Unbounded {
         Poll::Ready(Ok(()))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::bounded;
+    use crate::message::Shutdown;
+    use crate::transport::AsyncTransportExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn bounded_metrics() {
+        let (mut t1, mut t2) = bounded(1);
+        let metrics = t1.metrics();
+
+        let reached = Arc::new(AtomicUsize::new(0));
+        t1.set_watermark_callback(2, {
+            let reached = reached.clone();
+            move |queued| {
+                assert_eq!(queued, 2);
+                reached.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        t1.send(Shutdown).await.unwrap();
+        assert_eq!(metrics.queued(), 1);
+        assert_eq!(reached.load(Ordering::Relaxed), 0);
+
+        t1.send(Shutdown).await.unwrap();
+        assert_eq!(metrics.queued(), 2);
+        assert_eq!(reached.load(Ordering::Relaxed), 1);
+
+        tokio::select! {
+            biased;
+            _ = t1.send(Shutdown) => panic!("send should block"),
+            _ = async {} => {}
+        }
+        assert_eq!(metrics.blocked_sends(), 1);
+
+        t2.receive().await.unwrap();
+        t2.receive().await.unwrap();
+        assert_eq!(metrics.queued(), 0);
+
+        t1.send(Shutdown).await.unwrap();
+        t1.send(Shutdown).await.unwrap();
+        assert_eq!(metrics.queued(), 2);
+        assert_eq!(metrics.max_queued(), 2);
+        assert_eq!(metrics.blocked_sends(), 1);
+        assert_eq!(reached.load(Ordering::Relaxed), 2);
+    }
+}
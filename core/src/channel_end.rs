@@ -3,6 +3,11 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 /// Sending or receiving end of a channel.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 #[repr(u8)]
 pub enum ChannelEnd {
     /// Sending end of a channel.
@@ -35,8 +40,12 @@ impl ChannelEnd {
 impl From<ChannelEndWithCapacity> for ChannelEnd {
     fn from(value: ChannelEndWithCapacity) -> Self {
         match value {
-            ChannelEndWithCapacity::Sender => Self::Sender,
-            ChannelEndWithCapacity::Receiver(_) => Self::Receiver,
+            ChannelEndWithCapacity::Sender | ChannelEndWithCapacity::BroadcastSender => {
+                Self::Sender
+            }
+            ChannelEndWithCapacity::Receiver(_) | ChannelEndWithCapacity::ReceiverBytes(_) => {
+                Self::Receiver
+            }
         }
     }
 }
@@ -44,10 +53,58 @@ impl From<ChannelEndWithCapacity> for ChannelEnd {
 /// Sending or receiving end and capacity of a channel.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum ChannelEndWithCapacity {
     /// Sending end of a channel.
     Sender,
 
     /// Receiving end of a channel and capacity.
     Receiver(u32),
+
+    /// Receiving end of a channel and capacity in bytes.
+    ///
+    /// The capacity is the number of bytes of serialized items, that the sender may have in flight.
+    /// Items can be sent as long as some capacity is left, so a single item may exceed it.
+    ///
+    /// This requires protocol version 1.19.
+    ReceiverBytes(u32),
+
+    /// Sending end of a broadcast channel.
+    ///
+    /// Broadcast channels have a single sender and any number of receivers, which can be claimed
+    /// independently by different clients. This is only valid when creating a channel.
+    ///
+    /// This requires protocol version 1.19.
+    BroadcastSender,
+}
+
+impl ChannelEndWithCapacity {
+    /// Returns the capacity, if this is a receiving end.
+    pub fn capacity(self) -> Option<u32> {
+        match self {
+            Self::Sender | Self::BroadcastSender => None,
+            Self::Receiver(capacity) | Self::ReceiverBytes(capacity) => Some(capacity),
+        }
+    }
+
+    /// Indicates whether the capacity is measured in bytes.
+    pub fn is_bytes(self) -> bool {
+        matches!(self, Self::ReceiverBytes(_))
+    }
+}
+
+/// Wire representation of [`ChannelEndWithCapacity`].
+///
+/// The first two values are the same as [`ChannelEnd`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub(crate) enum ChannelEndWithCapacityKind {
+    Sender = 0,
+    Receiver = 1,
+    ReceiverBytes = 2,
+    BroadcastSender = 3,
 }
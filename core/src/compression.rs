@@ -0,0 +1,180 @@
+// The framing helpers are only used by transports, that support compression.
+#![cfg_attr(not(any(feature = "futures-io", feature = "tokio")), allow(dead_code))]
+
+#[cfg(test)]
+mod test;
+
+use crate::error::{DeserializeError, SerializeError};
+use crate::message_deserializer::MessageDeserializeError;
+use crate::value_deserializer::{Deserialize, Deserializer};
+use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+use bytes::{Buf, BytesMut};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use std::fmt;
+
+/// Marker in place of the message kind, which identifies compressed frames.
+const COMPRESSED_FRAME: u8 = 0xff;
+
+/// 4 bytes frame length + 1 byte marker + 1 byte algorithm + 4 bytes uncompressed length.
+const HEADER_LEN: usize = 10;
+
+/// Compression algorithm for messages.
+///
+/// Compression is negotiated between clients and the broker during the connection handshake. It is
+/// then applied by transports, that support it (see
+/// [`AsyncTransport::supported_compression`](crate::transport::AsyncTransport::supported_compression)).
+/// Support for the individual algorithms must be enabled with the respective Cargo feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[repr(u8)]
+pub enum Compression {
+    /// LZ4 block compression.
+    ///
+    /// This requires the `lz4` feature.
+    Lz4 = 0,
+}
+
+impl Compression {
+    /// All algorithms, that are supported with the enabled Cargo features.
+    pub const SUPPORTED: &'static [Self] = &[
+        #[cfg(feature = "lz4")]
+        Self::Lz4,
+    ];
+
+    /// Indicates whether the algorithm is supported with the enabled Cargo features.
+    pub fn is_supported(self) -> bool {
+        Self::SUPPORTED.contains(&self)
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Lz4 => f.write_str("lz4"),
+        }
+    }
+}
+
+impl Serialize for Compression {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        serializer.serialize_u8((*self).into());
+        Ok(())
+    }
+}
+
+impl Deserialize for Compression {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer
+            .deserialize_u8()?
+            .try_into()
+            .map_err(|_| DeserializeError::InvalidSerialization)
+    }
+}
+
+impl AsSerializeArg for Compression {
+    type SerializeArg<'a> = Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        *self
+    }
+}
+
+/// Indicates whether a frame is compressed.
+pub(crate) fn is_compressed(frame: &[u8]) -> bool {
+    frame.get(4) == Some(&COMPRESSED_FRAME)
+}
+
+/// Compresses a serialized message into a frame.
+///
+/// `None` is returned if the compressed frame would not be smaller than the message.
+/// Returns the length of the decompressed message of a compressed frame.
+pub(crate) fn decompressed_len(frame: &[u8]) -> Option<usize> {
+    frame
+        .get(6..HEADER_LEN)
+        .map(|mut len| len.get_u32_le() as usize)
+}
+
+pub(crate) fn compress(compression: Compression, msg: &[u8]) -> Option<BytesMut> {
+    let mut frame = match compression {
+        Compression::Lz4 => compress_lz4(msg)?,
+    };
+
+    if frame.len() >= msg.len() {
+        return None;
+    }
+
+    let frame_len = frame.len() as u32;
+    frame[0..4].copy_from_slice(&frame_len.to_le_bytes());
+    frame[4] = COMPRESSED_FRAME;
+    frame[5] = compression.into();
+    frame[6..10].copy_from_slice(&(msg.len() as u32).to_le_bytes());
+
+    Some(frame)
+}
+
+/// Decompresses a frame into a serialized message.
+pub(crate) fn decompress(frame: BytesMut) -> Result<BytesMut, MessageDeserializeError> {
+    if frame.len() < HEADER_LEN {
+        return Err(MessageDeserializeError::UnexpectedEoi);
+    }
+
+    let frame_len = (&frame[0..4]).get_u32_le() as usize;
+    if (frame_len != frame.len()) || (frame[4] != COMPRESSED_FRAME) {
+        return Err(MessageDeserializeError::InvalidSerialization);
+    }
+
+    let compression = Compression::try_from(frame[5])
+        .map_err(|_| MessageDeserializeError::InvalidSerialization)?;
+    let msg_len = (&frame[6..10]).get_u32_le() as usize;
+    let data = &frame[HEADER_LEN..];
+
+    match compression {
+        Compression::Lz4 => decompress_lz4(data, msg_len),
+    }
+}
+
+#[cfg(feature = "lz4")]
+fn compress_lz4(msg: &[u8]) -> Option<BytesMut> {
+    let max_len = lz4_flex::block::get_maximum_output_size(msg.len());
+    let mut frame = BytesMut::zeroed(HEADER_LEN + max_len);
+    let len = lz4_flex::block::compress_into(msg, &mut frame[HEADER_LEN..]).ok()?;
+    frame.truncate(HEADER_LEN + len);
+    Some(frame)
+}
+
+#[cfg(not(feature = "lz4"))]
+fn compress_lz4(_msg: &[u8]) -> Option<BytesMut> {
+    None
+}
+
+#[cfg(feature = "lz4")]
+fn decompress_lz4(data: &[u8], msg_len: usize) -> Result<BytesMut, MessageDeserializeError> {
+    // LZ4 cannot compress at a better ratio than 255:1. Checking this avoids huge allocations for
+    // bogus lengths.
+    if msg_len > data.len().saturating_mul(255) {
+        return Err(MessageDeserializeError::InvalidSerialization);
+    }
+
+    let mut msg = BytesMut::zeroed(msg_len);
+    let len = lz4_flex::block::decompress_into(data, &mut msg)
+        .map_err(|_| MessageDeserializeError::InvalidSerialization)?;
+
+    if len == msg_len {
+        Ok(msg)
+    } else {
+        Err(MessageDeserializeError::InvalidSerialization)
+    }
+}
+
+#[cfg(not(feature = "lz4"))]
+fn decompress_lz4(_data: &[u8], _msg_len: usize) -> Result<BytesMut, MessageDeserializeError> {
+    Err(MessageDeserializeError::InvalidSerialization)
+}
@@ -0,0 +1,58 @@
+use super::{compress, decompress, is_compressed, Compression};
+use crate::message_deserializer::MessageDeserializeError;
+use bytes::BytesMut;
+
+#[cfg(feature = "lz4")]
+#[test]
+fn compress_and_decompress() {
+    let msg = vec![0x42; 1024];
+
+    let frame = compress(Compression::Lz4, &msg).unwrap();
+    assert!(frame.len() < msg.len());
+    assert!(is_compressed(&frame));
+
+    let decompressed = decompress(frame).unwrap();
+    assert_eq!(*decompressed, *msg);
+}
+
+#[test]
+fn incompressible() {
+    let msg = [0, 1, 2, 3, 4, 5, 6, 7];
+    assert_eq!(compress(Compression::Lz4, &msg), None);
+}
+
+#[test]
+fn uncompressed_frame() {
+    let msg = [5, 0, 0, 0, 0];
+    assert!(!is_compressed(&msg));
+}
+
+#[test]
+fn invalid_frames() {
+    let frame = BytesMut::from(&[10, 0, 0, 0, 0xff][..]);
+    assert_eq!(
+        decompress(frame),
+        Err(MessageDeserializeError::UnexpectedEoi)
+    );
+
+    // Invalid frame length.
+    let frame = BytesMut::from(&[11, 0, 0, 0, 0xff, 0, 1, 0, 0, 0][..]);
+    assert_eq!(
+        decompress(frame),
+        Err(MessageDeserializeError::InvalidSerialization)
+    );
+
+    // Unknown algorithm.
+    let frame = BytesMut::from(&[11, 0, 0, 0, 0xff, 0xff, 1, 0, 0, 0, 0][..]);
+    assert_eq!(
+        decompress(frame),
+        Err(MessageDeserializeError::InvalidSerialization)
+    );
+
+    // Uncompressed length exceeds the maximum ratio.
+    let frame = BytesMut::from(&[11, 0, 0, 0, 0xff, 0, 0xff, 0xff, 0xff, 0xff, 0][..]);
+    assert_eq!(
+        decompress(frame),
+        Err(MessageDeserializeError::InvalidSerialization)
+    );
+}
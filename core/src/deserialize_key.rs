@@ -1,8 +1,10 @@
 use crate::buf_ext::ValueBufExt;
 use crate::error::DeserializeError;
 use crate::value::ValueKind;
+use alloc::boxed::Box;
+use alloc::string::String;
 use bytes::Buf;
-use std::mem;
+use core::mem;
 use uuid::Uuid;
 
 pub trait Sealed: Sized {
@@ -1,59 +1,92 @@
-use thiserror::Error;
+use core::fmt;
 
-#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum SerializeError {
-    #[error("serialized value overflowed")]
     Overflow,
-
-    #[error("more elements serialized than expected")]
     TooManyElements,
-
-    #[error("fewer elements serialized than expected")]
     TooFewElements,
-
-    #[error("too deeply nested")]
     TooDeeplyNested,
+    InvalidValue,
 }
 
-#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Overflow => f.write_str("serialized value overflowed"),
+            Self::TooManyElements => f.write_str("more elements serialized than expected"),
+            Self::TooFewElements => f.write_str("fewer elements serialized than expected"),
+            Self::TooDeeplyNested => f.write_str("too deeply nested"),
+            Self::InvalidValue => f.write_str("invalid value"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SerializeError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum DeserializeError {
-    #[error("invalid serialization")]
     InvalidSerialization,
-
-    #[error("unexpected end of input")]
     UnexpectedEoi,
-
-    #[error("unexpected value type")]
     UnexpectedValue,
-
-    #[error("no more elements")]
     NoMoreElements,
-
-    #[error("more elements remain")]
     MoreElementsRemain,
-
-    #[error("too deeply nested")]
     TooDeeplyNested,
-
-    #[error("serialization contains trailing data")]
     TrailingData,
+    MissingFd,
 }
 
-#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
-#[error(transparent)]
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidSerialization => f.write_str("invalid serialization"),
+            Self::UnexpectedEoi => f.write_str("unexpected end of input"),
+            Self::UnexpectedValue => f.write_str("unexpected value type"),
+            Self::NoMoreElements => f.write_str("no more elements"),
+            Self::MoreElementsRemain => f.write_str("more elements remain"),
+            Self::TooDeeplyNested => f.write_str("too deeply nested"),
+            Self::TrailingData => f.write_str("serialization contains trailing data"),
+            Self::MissingFd => f.write_str("file descriptor is missing"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DeserializeError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolVersionError {
-    #[from]
     pub(crate) kind: ProtocolVersionErrorKind,
 }
 
-#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+impl From<ProtocolVersionErrorKind> for ProtocolVersionError {
+    fn from(kind: ProtocolVersionErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for ProtocolVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProtocolVersionError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) enum ProtocolVersionErrorKind {
-    #[error("invalid major version component")]
     InvalidMajor,
-
-    #[error("invalid minor version component")]
     InvalidMinor,
-
-    #[error("version failed to parse")]
     Parse,
 }
+
+impl fmt::Display for ProtocolVersionErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidMajor => f.write_str("invalid major version component"),
+            Self::InvalidMinor => f.write_str("invalid minor version component"),
+            Self::Parse => f.write_str("version failed to parse"),
+        }
+    }
+}
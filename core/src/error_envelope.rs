@@ -0,0 +1,215 @@
+use crate::error::{DeserializeError, SerializeError};
+#[cfg(feature = "introspection")]
+use crate::introspection::{Introspectable, Layout, LexicalId, References, Struct};
+use crate::serialized_value::{SerializedValue, SerializedValueSlice};
+use crate::value_deserializer::{Deserialize, Deserializer};
+use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+use alloc::string::String;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+#[derive(IntoPrimitive, TryFromPrimitive)]
+#[repr(u32)]
+enum ErrorEnvelopeField {
+    Code = 0,
+    Message = 1,
+    Details = 2,
+}
+
+/// Standard envelope for errors on the bus.
+///
+/// An `ErrorEnvelope` consists of a numeric code, a human-readable message and optionally a value
+/// with further details. It allows handling and logging errors uniformly across services, without
+/// knowing the concrete error types of each schema.
+///
+/// The Rust code generator implements `From<E> for ErrorEnvelope` for all enums that are used as
+/// the error type of a function. The code is then the id of the variant, the message its name and
+/// the details its value, if it has one.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin_core::{ErrorEnvelope, SerializedValue};
+///
+/// let details = SerializedValue::serialize("/tmp/foo").unwrap();
+/// let err = ErrorEnvelope::new(1, "NotFound").set_details(details);
+///
+/// assert_eq!(err.code(), 1);
+/// assert_eq!(err.message(), "NotFound");
+/// assert_eq!(err.deserialize_details::<String>().unwrap().unwrap(), "/tmp/foo");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorEnvelope {
+    code: u32,
+    message: String,
+    details: Option<SerializedValue>,
+}
+
+impl ErrorEnvelope {
+    /// Creates a new `ErrorEnvelope` without details.
+    pub fn new(code: u32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Creates a new `ErrorEnvelope` with details.
+    pub fn with_details(code: u32, message: impl Into<String>, details: SerializedValue) -> Self {
+        Self::new(code, message).set_details(details)
+    }
+
+    /// Returns the error code.
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// Returns the error message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the serialized details of the error, if any.
+    pub fn details(&self) -> Option<&SerializedValueSlice> {
+        self.details.as_deref()
+    }
+
+    /// Deserializes the details of the error, if any.
+    pub fn deserialize_details<T: Deserialize>(&self) -> Option<Result<T, DeserializeError>> {
+        self.details
+            .as_deref()
+            .map(SerializedValueSlice::deserialize)
+    }
+
+    /// Sets the details of the error.
+    #[must_use = "this method follows the builder pattern and returns a new `ErrorEnvelope`"]
+    pub fn set_details(mut self, details: SerializedValue) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    /// Converts the `ErrorEnvelope` into its details, if any.
+    pub fn into_details(self) -> Option<SerializedValue> {
+        self.details
+    }
+}
+
+impl Serialize for ErrorEnvelope {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        let num_fields = if self.details.is_some() { 3 } else { 2 };
+        let mut serializer = serializer.serialize_struct(num_fields)?;
+
+        serializer.serialize_field(ErrorEnvelopeField::Code, &self.code)?;
+        serializer.serialize_field(ErrorEnvelopeField::Message, &self.message)?;
+
+        if self.details.is_some() {
+            serializer.serialize_field(ErrorEnvelopeField::Details, &self.details)?;
+        }
+
+        serializer.finish()
+    }
+}
+
+impl Deserialize for ErrorEnvelope {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let mut deserializer = deserializer.deserialize_struct()?;
+
+        let mut code = None;
+        let mut message = None;
+        let mut details = None;
+
+        while deserializer.has_more_fields() {
+            let deserializer = deserializer.deserialize_field()?;
+
+            match deserializer.try_id() {
+                Ok(ErrorEnvelopeField::Code) => code = deserializer.deserialize().map(Some)?,
+                Ok(ErrorEnvelopeField::Message) => {
+                    message = deserializer.deserialize().map(Some)?
+                }
+                Ok(ErrorEnvelopeField::Details) => details = deserializer.deserialize()?,
+                Err(_) => deserializer.skip()?,
+            }
+        }
+
+        deserializer.finish_with(|| {
+            Ok(Self {
+                code: code.ok_or(DeserializeError::InvalidSerialization)?,
+                message: message.ok_or(DeserializeError::InvalidSerialization)?,
+                details,
+            })
+        })
+    }
+}
+
+impl AsSerializeArg for ErrorEnvelope {
+    type SerializeArg<'a> = &'a Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        self
+    }
+}
+
+#[cfg(feature = "introspection")]
+impl Introspectable for ErrorEnvelope {
+    fn layout() -> Layout {
+        Struct::builder("aldrin", "ErrorEnvelope")
+            .field(
+                ErrorEnvelopeField::Code.into(),
+                "code",
+                true,
+                LexicalId::U32,
+            )
+            .field(
+                ErrorEnvelopeField::Message.into(),
+                "message",
+                true,
+                LexicalId::STRING,
+            )
+            .field(
+                ErrorEnvelopeField::Details.into(),
+                "details",
+                false,
+                LexicalId::VALUE,
+            )
+            .finish()
+            .into()
+    }
+
+    fn lexical_id() -> LexicalId {
+        LexicalId::custom("aldrin", "ErrorEnvelope")
+    }
+
+    fn add_references(references: &mut References) {
+        references.add::<u32>();
+        references.add::<String>();
+        references.add::<SerializedValue>();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ErrorEnvelope;
+    use crate::serialized_value::SerializedValue;
+
+    fn serde(err: &ErrorEnvelope) -> ErrorEnvelope {
+        SerializedValue::serialize(err)
+            .unwrap()
+            .deserialize()
+            .unwrap()
+    }
+
+    #[test]
+    fn serialize() {
+        let err = ErrorEnvelope::new(1, "foo");
+        assert_eq!(err, serde(&err));
+        assert_eq!(err.details(), None);
+
+        let details = SerializedValue::serialize(&[1u32, 2, 3]).unwrap();
+        let err = ErrorEnvelope::with_details(2, "bar", details);
+        assert_eq!(err, serde(&err));
+        assert_eq!(err.deserialize_details(), Some(Ok(vec![1u32, 2, 3])));
+    }
+}
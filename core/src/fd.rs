@@ -0,0 +1,199 @@
+#[cfg(all(test, unix))]
+mod test;
+
+#[cfg(all(unix, feature = "std"))]
+use crate::error::{DeserializeError, SerializeError};
+#[cfg(all(unix, feature = "introspection"))]
+use crate::introspection::{BuiltInType, Introspectable, Layout, LexicalId, References};
+#[cfg(all(unix, feature = "std"))]
+use crate::value_deserializer::{Deserialize, Deserializer};
+#[cfg(all(unix, feature = "std"))]
+use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+#[cfg(all(unix, feature = "std"))]
+use std::io::Error as IoError;
+#[cfg(all(unix, feature = "std"))]
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd};
+#[cfg(all(unix, feature = "std"))]
+use std::sync::Arc;
+
+/// Maximum number of file descriptors, that can be attached to a single value.
+///
+/// This is the limit of a single `SCM_RIGHTS` message on Linux.
+#[cfg(all(unix, feature = "std"))]
+pub(crate) const MAX_FDS: usize = 253;
+
+/// File descriptor, that can be passed to other clients on the same host.
+///
+/// [`Fd`s](Self) can be part of any value. When serializing, they are attached to the resulting
+/// [`SerializedValue`](crate::SerializedValue) and the value itself contains only their index
+/// (as a `u32`).
+///
+/// Passing file descriptors to other clients requires that both the sending and the receiving
+/// client use a transport, that supports it (see
+/// [`AsyncTransport::supports_fd_passing`](crate::transport::AsyncTransport::supports_fd_passing)).
+/// Otherwise, the file descriptors are dropped and deserializing them fails with
+/// [`DeserializeError::MissingFd`].
+///
+/// Cloning an [`Fd`] is cheap. All clones refer to the same file descriptor, which is closed when
+/// the last clone is dropped.
+#[cfg(all(unix, feature = "std"))]
+#[derive(Debug, Clone)]
+pub struct Fd(Arc<OwnedFd>);
+
+#[cfg(all(unix, feature = "std"))]
+impl Fd {
+    /// Creates a new [`Fd`] from an [`OwnedFd`].
+    pub fn new(fd: OwnedFd) -> Self {
+        Self(Arc::new(fd))
+    }
+
+    /// Converts the [`Fd`] into an [`OwnedFd`].
+    ///
+    /// If there are other clones of this [`Fd`], then the file descriptor is duplicated.
+    pub fn into_owned(self) -> Result<OwnedFd, IoError> {
+        match Arc::try_unwrap(self.0) {
+            Ok(fd) => Ok(fd),
+            Err(fd) => fd.try_clone(),
+        }
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl From<OwnedFd> for Fd {
+    fn from(fd: OwnedFd) -> Self {
+        Self::new(fd)
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl AsFd for Fd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl AsRawFd for Fd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl PartialEq for Fd {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_raw_fd() == other.as_raw_fd()
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl Eq for Fd {}
+
+#[cfg(all(unix, feature = "std"))]
+impl Serialize for Fd {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        serializer.serialize_fd(self)
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl Deserialize for Fd {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_fd()
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl AsSerializeArg for Fd {
+    type SerializeArg<'a> = &'a Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        self
+    }
+}
+
+#[cfg(all(unix, feature = "introspection"))]
+impl Introspectable for Fd {
+    fn layout() -> Layout {
+        BuiltInType::U32.into()
+    }
+
+    fn lexical_id() -> LexicalId {
+        LexicalId::U32
+    }
+
+    fn add_references(_references: &mut References) {}
+}
+
+/// File descriptors attached to a serialized value.
+///
+/// This type exists on all platforms to avoid `cfg`s throughout the (de)serializers, but it is
+/// always empty on non-Unix platforms and without the `std` feature.
+///
+/// Almost all values carry no file descriptors. The list is therefore boxed and only allocated
+/// when needed, such that it adds just a single pointer to every
+/// [`SerializedValue`](crate::SerializedValue). It is `None` whenever it is empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct FdList {
+    #[cfg(all(unix, feature = "std"))]
+    #[allow(clippy::box_collection)]
+    fds: Option<Box<Vec<Fd>>>,
+}
+
+impl FdList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn is_empty(&self) -> bool {
+        #[cfg(all(unix, feature = "std"))]
+        {
+            self.fds.is_none()
+        }
+
+        #[cfg(not(all(unix, feature = "std")))]
+        {
+            true
+        }
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+impl FdList {
+    pub fn from_vec(fds: Vec<Fd>) -> Self {
+        if fds.is_empty() {
+            Self::new()
+        } else {
+            Self {
+                fds: Some(Box::new(fds)),
+            }
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<Fd> {
+        self.fds.map(|fds| *fds).unwrap_or_default()
+    }
+
+    pub fn as_slice(&self) -> &[Fd] {
+        self.fds.as_deref().map_or(&[], Vec::as_slice)
+    }
+
+    pub fn push(&mut self, fd: Fd) -> Result<u32, SerializeError> {
+        let fds = self.fds.get_or_insert_with(Box::default);
+
+        if fds.len() < MAX_FDS {
+            fds.push(fd);
+            Ok(fds.len() as u32 - 1)
+        } else {
+            Err(SerializeError::Overflow)
+        }
+    }
+
+    pub fn get(&self, index: u32) -> Option<&Fd> {
+        self.as_slice().get(index as usize)
+    }
+}
@@ -0,0 +1,53 @@
+use super::Fd;
+use crate::error::DeserializeError;
+use crate::serialized_value::SerializedValue;
+use std::fs::File;
+use std::os::fd::{AsRawFd, OwnedFd};
+
+fn dev_null() -> Fd {
+    Fd::new(OwnedFd::from(File::open("/dev/null").unwrap()))
+}
+
+#[test]
+fn serialize_and_deserialize() {
+    let fd1 = dev_null();
+    let fd2 = dev_null();
+
+    let value = SerializedValue::serialize(&(fd1.clone(), fd2.clone(), fd1.clone())).unwrap();
+    assert_eq!(value.fds(), [fd1.clone(), fd2.clone(), fd1.clone()]);
+
+    // Fds are serialized as indices.
+    assert_eq!(value.deserialize(), Ok((0u32, 1u32, 2u32)));
+
+    let (fd3, fd4, fd5): (Fd, Fd, Fd) = value.deserialize().unwrap();
+    assert_eq!(fd3.as_raw_fd(), fd1.as_raw_fd());
+    assert_eq!(fd4.as_raw_fd(), fd2.as_raw_fd());
+    assert_eq!(fd5.as_raw_fd(), fd1.as_raw_fd());
+}
+
+#[test]
+fn missing_fd() {
+    let mut value = SerializedValue::serialize(&dev_null()).unwrap();
+
+    // Slices don't have access to the attached fds.
+    assert_eq!(
+        (*value).deserialize::<Fd>(),
+        Err(DeserializeError::MissingFd)
+    );
+
+    value.take_fds();
+    assert_eq!(value.deserialize::<Fd>(), Err(DeserializeError::MissingFd));
+}
+
+#[test]
+fn into_owned() {
+    let fd1 = dev_null();
+    let raw = fd1.as_raw_fd();
+
+    let fd2 = fd1.clone();
+    let owned = fd2.into_owned().unwrap();
+    assert_ne!(owned.as_raw_fd(), raw);
+
+    let owned = fd1.into_owned().unwrap();
+    assert_eq!(owned.as_raw_fd(), raw);
+}
@@ -0,0 +1,451 @@
+//! Implementations for common foreign types.
+//!
+//! Durations and points in time are represented as structs, which correspond to the following
+//! schema types:
+//!
+//! ```aldrin
+//! struct Duration {
+//!     required secs @ 0 = u64;
+//!     required nanos @ 1 = u32;
+//! }
+//!
+//! // Time since the Unix epoch. `nanos` is always in the range 0 to 999,999,999, even for points
+//! // in time before the epoch.
+//! struct Timestamp {
+//!     required secs @ 0 = i64;
+//!     required nanos @ 1 = u32;
+//! }
+//! ```
+//!
+//! [`Duration`] maps to `Duration`. [`SystemTime`], as well as `chrono::DateTime` and
+//! `time::OffsetDateTime` (with the respective features), map to `Timestamp`.
+//!
+//! IP and socket addresses and paths are represented as strings. Paths must be valid UTF-8.
+
+#[cfg(test)]
+mod test;
+
+use crate::error::{DeserializeError, SerializeError};
+#[cfg(feature = "introspection")]
+use crate::introspection::{
+    BuiltInType, Introspectable, Layout, LexicalId, References, Struct as StructLayout,
+};
+use crate::value_deserializer::{Deserialize, Deserializer};
+use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+use core::time::Duration;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+#[cfg(feature = "std")]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "std")]
+use std::str::FromStr;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+#[derive(IntoPrimitive, TryFromPrimitive)]
+#[repr(u32)]
+enum DurationField {
+    Secs = 0,
+    Nanos = 1,
+}
+
+fn serialize_secs_nanos<S: Serialize>(
+    serializer: Serializer,
+    secs: &S,
+    nanos: u32,
+) -> Result<(), SerializeError> {
+    let mut serializer = serializer.serialize_struct(2)?;
+
+    serializer.serialize_field(DurationField::Secs, secs)?;
+    serializer.serialize_field(DurationField::Nanos, &nanos)?;
+
+    serializer.finish()
+}
+
+fn deserialize_secs_nanos<S: Deserialize>(
+    deserializer: Deserializer,
+) -> Result<(S, u32), DeserializeError> {
+    let mut deserializer = deserializer.deserialize_struct()?;
+
+    let mut secs = None;
+    let mut nanos = None;
+
+    while deserializer.has_more_fields() {
+        let deserializer = deserializer.deserialize_field()?;
+
+        match deserializer.try_id() {
+            Ok(DurationField::Secs) => secs = deserializer.deserialize().map(Some)?,
+            Ok(DurationField::Nanos) => nanos = deserializer.deserialize().map(Some)?,
+            Err(_) => deserializer.skip()?,
+        }
+    }
+
+    deserializer.finish_with(|| {
+        let secs = secs.ok_or(DeserializeError::InvalidSerialization)?;
+        let nanos = nanos.ok_or(DeserializeError::InvalidSerialization)?;
+
+        if nanos < NANOS_PER_SEC {
+            Ok((secs, nanos))
+        } else {
+            Err(DeserializeError::InvalidSerialization)
+        }
+    })
+}
+
+#[cfg(feature = "introspection")]
+fn secs_nanos_layout(name: &str, secs: LexicalId) -> Layout {
+    StructLayout::builder("std", name)
+        .field(DurationField::Secs.into(), "secs", true, secs)
+        .field(DurationField::Nanos.into(), "nanos", true, LexicalId::U32)
+        .finish()
+        .into()
+}
+
+impl Serialize for Duration {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        serialize_secs_nanos(serializer, &self.as_secs(), self.subsec_nanos())
+    }
+}
+
+impl Deserialize for Duration {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let (secs, nanos) = deserialize_secs_nanos(deserializer)?;
+        Ok(Self::new(secs, nanos))
+    }
+}
+
+impl AsSerializeArg for Duration {
+    type SerializeArg<'a> = Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        *self
+    }
+}
+
+#[cfg(feature = "introspection")]
+impl Introspectable for Duration {
+    fn layout() -> Layout {
+        secs_nanos_layout("Duration", LexicalId::U64)
+    }
+
+    fn lexical_id() -> LexicalId {
+        LexicalId::custom("std", "Duration")
+    }
+
+    fn add_references(references: &mut References) {
+        references.add::<u64>();
+        references.add::<u32>();
+    }
+}
+
+#[cfg(feature = "std")]
+/// Splits a point in time relative to the Unix epoch into seconds and non-negative nanoseconds.
+fn to_timestamp(time: SystemTime) -> Result<(i64, u32), SerializeError> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => {
+            let secs = i64::try_from(after.as_secs()).map_err(|_| SerializeError::InvalidValue)?;
+            Ok((secs, after.subsec_nanos()))
+        }
+
+        Err(e) => {
+            let before = e.duration();
+            let secs = i64::try_from(before.as_secs()).map_err(|_| SerializeError::InvalidValue)?;
+
+            if before.subsec_nanos() == 0 {
+                Ok((-secs, 0))
+            } else {
+                Ok((-secs - 1, NANOS_PER_SEC - before.subsec_nanos()))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn from_timestamp(secs: i64, nanos: u32) -> Option<SystemTime> {
+    let time = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))?
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+    };
+
+    time.checked_add(Duration::from_nanos(nanos.into()))
+}
+
+#[cfg(feature = "introspection")]
+fn timestamp_layout() -> Layout {
+    secs_nanos_layout("Timestamp", LexicalId::I64)
+}
+
+#[cfg(feature = "introspection")]
+fn timestamp_lexical_id() -> LexicalId {
+    LexicalId::custom("std", "Timestamp")
+}
+
+#[cfg(feature = "introspection")]
+fn timestamp_references(references: &mut References) {
+    references.add::<i64>();
+    references.add::<u32>();
+}
+
+#[cfg(feature = "std")]
+impl Serialize for SystemTime {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        let (secs, nanos) = to_timestamp(*self)?;
+        serialize_secs_nanos(serializer, &secs, nanos)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Deserialize for SystemTime {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let (secs, nanos) = deserialize_secs_nanos(deserializer)?;
+        from_timestamp(secs, nanos).ok_or(DeserializeError::InvalidSerialization)
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsSerializeArg for SystemTime {
+    type SerializeArg<'a> = Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        *self
+    }
+}
+
+#[cfg(feature = "introspection")]
+impl Introspectable for SystemTime {
+    fn layout() -> Layout {
+        timestamp_layout()
+    }
+
+    fn lexical_id() -> LexicalId {
+        timestamp_lexical_id()
+    }
+
+    fn add_references(references: &mut References) {
+        timestamp_references(references);
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> Serialize for chrono::DateTime<Tz> {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        // Leap seconds are represented by chrono with nanoseconds beyond 1 second.
+        let nanos = self.timestamp_subsec_nanos().min(NANOS_PER_SEC - 1);
+        serialize_secs_nanos(serializer, &self.timestamp(), nanos)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Deserialize for chrono::DateTime<chrono::Utc> {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let (secs, nanos) = deserialize_secs_nanos(deserializer)?;
+        Self::from_timestamp(secs, nanos).ok_or(DeserializeError::InvalidSerialization)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> AsSerializeArg for chrono::DateTime<Tz> {
+    type SerializeArg<'a>
+        = &'a Self
+    where
+        Self: 'a;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        self
+    }
+}
+
+#[cfg(all(feature = "chrono", feature = "introspection"))]
+impl<Tz: chrono::TimeZone> Introspectable for chrono::DateTime<Tz> {
+    fn layout() -> Layout {
+        timestamp_layout()
+    }
+
+    fn lexical_id() -> LexicalId {
+        timestamp_lexical_id()
+    }
+
+    fn add_references(references: &mut References) {
+        timestamp_references(references);
+    }
+}
+
+#[cfg(feature = "time")]
+impl Serialize for time::OffsetDateTime {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        serialize_secs_nanos(serializer, &self.unix_timestamp(), self.nanosecond())
+    }
+}
+
+#[cfg(feature = "time")]
+impl Deserialize for time::OffsetDateTime {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let (secs, nanos) = deserialize_secs_nanos::<i64>(deserializer)?;
+
+        let nanos = i128::from(secs) * i128::from(NANOS_PER_SEC) + i128::from(nanos);
+        Self::from_unix_timestamp_nanos(nanos).map_err(|_| DeserializeError::InvalidSerialization)
+    }
+}
+
+#[cfg(feature = "time")]
+impl AsSerializeArg for time::OffsetDateTime {
+    type SerializeArg<'a> = Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        *self
+    }
+}
+
+#[cfg(all(feature = "time", feature = "introspection"))]
+impl Introspectable for time::OffsetDateTime {
+    fn layout() -> Layout {
+        timestamp_layout()
+    }
+
+    fn lexical_id() -> LexicalId {
+        timestamp_lexical_id()
+    }
+
+    fn add_references(references: &mut References) {
+        timestamp_references(references);
+    }
+}
+
+#[cfg(feature = "std")]
+macro_rules! string_repr {
+    ($ty:ty) => {
+        impl Serialize for $ty {
+            fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+                serializer.serialize_string(&self.to_string())
+            }
+        }
+
+        impl Deserialize for $ty {
+            fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+                let value = deserializer.deserialize_string()?;
+                Self::from_str(&value).map_err(|_| DeserializeError::InvalidSerialization)
+            }
+        }
+
+        impl AsSerializeArg for $ty {
+            type SerializeArg<'a> = Self;
+
+            fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+            where
+                Self: 'a,
+            {
+                *self
+            }
+        }
+
+        #[cfg(feature = "introspection")]
+        impl Introspectable for $ty {
+            fn layout() -> Layout {
+                BuiltInType::String.into()
+            }
+
+            fn lexical_id() -> LexicalId {
+                LexicalId::STRING
+            }
+
+            fn add_references(_references: &mut References) {}
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+string_repr!(IpAddr);
+#[cfg(feature = "std")]
+string_repr!(Ipv4Addr);
+#[cfg(feature = "std")]
+string_repr!(Ipv6Addr);
+#[cfg(feature = "std")]
+string_repr!(SocketAddr);
+
+#[cfg(feature = "std")]
+impl Serialize for Path {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        let path = self.to_str().ok_or(SerializeError::InvalidValue)?;
+        serializer.serialize_string(path)
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsSerializeArg for Path {
+    type SerializeArg<'a> = &'a Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        self
+    }
+}
+
+#[cfg(feature = "introspection")]
+impl Introspectable for Path {
+    fn layout() -> Layout {
+        BuiltInType::String.into()
+    }
+
+    fn lexical_id() -> LexicalId {
+        LexicalId::STRING
+    }
+
+    fn add_references(_references: &mut References) {}
+}
+
+#[cfg(feature = "std")]
+impl Serialize for PathBuf {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        self.as_path().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Deserialize for PathBuf {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_string().map(Self::from)
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsSerializeArg for PathBuf {
+    type SerializeArg<'a> = &'a Path;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        self
+    }
+}
+
+#[cfg(feature = "introspection")]
+impl Introspectable for PathBuf {
+    fn layout() -> Layout {
+        BuiltInType::String.into()
+    }
+
+    fn lexical_id() -> LexicalId {
+        LexicalId::STRING
+    }
+
+    fn add_references(_references: &mut References) {}
+}
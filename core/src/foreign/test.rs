@@ -0,0 +1,148 @@
+use crate::error::{DeserializeError, SerializeError};
+use crate::generic_value::{Struct, Value};
+use crate::serialized_value::SerializedValue;
+use crate::value_deserializer::Deserialize;
+use crate::value_serializer::Serialize;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn assert_roundtrip<T>(value: &T, expected: &Value)
+where
+    T: Serialize + Deserialize + PartialEq + Debug,
+{
+    let serialized = SerializedValue::serialize(value).unwrap();
+    assert_eq!(serialized.deserialize::<Value>().unwrap(), *expected);
+    assert_eq!(serialized.deserialize::<T>().unwrap(), *value);
+}
+
+fn secs_nanos(secs: Value, nanos: u32) -> Value {
+    Value::Struct(Struct(HashMap::from([(0, secs), (1, Value::U32(nanos))])))
+}
+
+#[test]
+fn duration() {
+    assert_roundtrip(&Duration::new(12, 345), &secs_nanos(Value::U64(12), 345));
+}
+
+#[test]
+fn duration_invalid_nanos() {
+    let serialized = SerializedValue::serialize(&secs_nanos(Value::U64(1), 1_000_000_000)).unwrap();
+
+    assert_eq!(
+        serialized.deserialize::<Duration>(),
+        Err(DeserializeError::InvalidSerialization)
+    );
+}
+
+#[test]
+fn system_time() {
+    assert_roundtrip(
+        &(UNIX_EPOCH + Duration::new(12, 345)),
+        &secs_nanos(Value::I64(12), 345),
+    );
+
+    assert_roundtrip(
+        &(UNIX_EPOCH - Duration::new(12, 0)),
+        &secs_nanos(Value::I64(-12), 0),
+    );
+
+    assert_roundtrip(
+        &(UNIX_EPOCH - Duration::new(12, 1)),
+        &secs_nanos(Value::I64(-13), 999_999_999),
+    );
+
+    let now = SystemTime::now();
+    let serialized = SerializedValue::serialize(&now).unwrap();
+    assert_eq!(serialized.deserialize::<SystemTime>().unwrap(), now);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_date_time() {
+    use chrono::{DateTime, Utc};
+
+    let time = DateTime::<Utc>::from_timestamp(-12, 345).unwrap();
+    assert_roundtrip(&time, &secs_nanos(Value::I64(-12), 345));
+
+    // chrono and std timestamps are interchangeable.
+    let serialized = SerializedValue::serialize(&time).unwrap();
+    let std_time = serialized.deserialize::<SystemTime>().unwrap();
+    assert_eq!(std_time, UNIX_EPOCH - Duration::new(11, 999_999_655));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_offset_date_time() {
+    use time::OffsetDateTime;
+
+    let time = OffsetDateTime::from_unix_timestamp_nanos(-11_999_999_655).unwrap();
+    assert_roundtrip(&time, &secs_nanos(Value::I64(-12), 345));
+}
+
+#[test]
+fn ip_addr() {
+    let ipv4 = Ipv4Addr::new(127, 0, 0, 1);
+    assert_roundtrip(&ipv4, &Value::String("127.0.0.1".to_owned()));
+    assert_roundtrip(&IpAddr::V4(ipv4), &Value::String("127.0.0.1".to_owned()));
+
+    let ipv6 = Ipv6Addr::LOCALHOST;
+    assert_roundtrip(&ipv6, &Value::String("::1".to_owned()));
+    assert_roundtrip(&IpAddr::V6(ipv6), &Value::String("::1".to_owned()));
+
+    let addr = SocketAddr::new(IpAddr::V6(ipv6), 24940);
+    assert_roundtrip(&addr, &Value::String("[::1]:24940".to_owned()));
+
+    let serialized = SerializedValue::serialize("localhost").unwrap();
+    assert_eq!(
+        serialized.deserialize::<IpAddr>(),
+        Err(DeserializeError::InvalidSerialization)
+    );
+}
+
+#[test]
+fn path() {
+    let path = PathBuf::from("schemas/example.aldrin");
+    assert_roundtrip(&path, &Value::String("schemas/example.aldrin".to_owned()));
+
+    let serialized = SerializedValue::serialize(Path::new("schemas/example.aldrin")).unwrap();
+    assert_eq!(serialized.deserialize::<PathBuf>().unwrap(), path);
+}
+
+#[cfg(unix)]
+#[test]
+fn path_invalid_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = Path::new(OsStr::from_bytes(b"\xff"));
+    assert_eq!(
+        SerializedValue::serialize(path).unwrap_err(),
+        SerializeError::InvalidValue
+    );
+}
+
+#[cfg(feature = "introspection")]
+#[test]
+fn introspection() {
+    use crate::introspection::{Introspection, LexicalId};
+
+    assert_eq!(
+        Introspection::new::<Duration>().lexical_id(),
+        LexicalId::custom("std", "Duration")
+    );
+
+    assert_eq!(
+        Introspection::new::<SystemTime>().type_id(),
+        Introspection::new::<Option<SystemTime>>()
+            .resolve(LexicalId::custom("std", "Timestamp"))
+            .unwrap()
+    );
+
+    assert_eq!(
+        Introspection::new::<PathBuf>().lexical_id(),
+        LexicalId::STRING
+    );
+}
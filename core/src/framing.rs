@@ -0,0 +1,135 @@
+//! Framing of messages on byte streams, shared by the stream-based transports.
+
+use crate::compression::{self, Compression};
+use crate::message::{Message, MessageOps, Packetizer};
+use crate::message_deserializer::MessageDeserializeError;
+use crate::message_serializer::MessageSerializeError;
+#[cfg(feature = "json")]
+use crate::wire_format;
+use crate::wire_format::WireFormat;
+use bytes::BytesMut;
+
+pub(crate) const INITIAL_CAPACITY: usize = 8 * 1024;
+pub(crate) const BACKPRESSURE_BOUNDARY: usize = INITIAL_CAPACITY;
+
+/// Errors, that occur while framing messages.
+///
+/// Every transport converts these into its own error type.
+#[derive(Debug)]
+pub(crate) enum FramingError {
+    Serialize(MessageSerializeError),
+    Deserialize(MessageDeserializeError),
+    MessageTooLarge(usize),
+}
+
+impl From<MessageSerializeError> for FramingError {
+    fn from(e: MessageSerializeError) -> Self {
+        Self::Serialize(e)
+    }
+}
+
+impl From<MessageDeserializeError> for FramingError {
+    fn from(e: MessageDeserializeError) -> Self {
+        Self::Deserialize(e)
+    }
+}
+
+/// Fails if the incomplete message in `packetizer` is already known to exceed `max`.
+#[cfg_attr(not(feature = "json"), allow(unused_variables))]
+pub(crate) fn check_incomplete_size(
+    packetizer: &Packetizer,
+    wire_format: Option<WireFormat>,
+    max: Option<usize>,
+) -> Result<(), FramingError> {
+    let Some(max) = max else {
+        return Ok(());
+    };
+
+    // Text-based wire formats have no length prefix, so the buffered bytes of the incomplete line
+    // are checked instead.
+    #[cfg(feature = "json")]
+    let len = match wire_format {
+        Some(_) => Some(packetizer.len()),
+        None => packetizer.next_message_len(),
+    };
+
+    #[cfg(not(feature = "json"))]
+    let len = packetizer.next_message_len();
+
+    check_size(len.unwrap_or(0), Some(max))
+}
+
+pub(crate) fn check_size(len: usize, max: Option<usize>) -> Result<(), FramingError> {
+    match max {
+        Some(max) if len > max => Err(FramingError::MessageTooLarge(len)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg_attr(not(feature = "json"), allow(unused_variables))]
+pub(crate) fn next_message(
+    packetizer: &mut Packetizer,
+    wire_format: Option<WireFormat>,
+    max_message_size: Option<usize>,
+) -> Option<Result<Message, FramingError>> {
+    #[cfg(feature = "json")]
+    if let Some(format) = wire_format {
+        let frame = packetizer.next_line()?;
+
+        return Some(
+            check_size(frame.len(), max_message_size)
+                .and_then(|()| wire_format::decode(format, &frame).map_err(Into::into)),
+        );
+    }
+
+    let buf = packetizer.next_message()?;
+    Some(unpack_message(buf, max_message_size))
+}
+
+/// Decompresses a message, if necessary, and deserializes it.
+fn unpack_message(
+    mut buf: BytesMut,
+    max_message_size: Option<usize>,
+) -> Result<Message, FramingError> {
+    check_size(buf.len(), max_message_size)?;
+
+    if compression::is_compressed(&buf) {
+        if let Some(len) = compression::decompressed_len(&buf) {
+            check_size(len, max_message_size)?;
+        }
+
+        buf = compression::decompress(buf)?;
+    }
+
+    Message::deserialize_message(buf).map_err(Into::into)
+}
+
+/// Serializes a message in the negotiated wire format and compresses it, if necessary.
+#[cfg_attr(not(feature = "json"), allow(unused_variables))]
+pub(crate) fn pack_message(
+    msg: Message,
+    wire_format: Option<WireFormat>,
+    compression: Option<Compression>,
+    compression_threshold: Option<usize>,
+    peer_max_message_size: Option<usize>,
+) -> Result<BytesMut, FramingError> {
+    #[cfg(feature = "json")]
+    if let Some(format) = wire_format {
+        let msg = wire_format::encode(format, &msg)?;
+        check_size(msg.len(), peer_max_message_size)?;
+        return Ok(msg);
+    }
+
+    let mut msg = msg.serialize_message()?;
+
+    if let (Some(compression), Some(threshold)) = (compression, compression_threshold) {
+        if msg.len() >= threshold {
+            if let Some(compressed) = compression::compress(compression, &msg) {
+                msg = compressed;
+            }
+        }
+    }
+
+    check_size(msg.len(), peer_max_message_size)?;
+    Ok(msg)
+}
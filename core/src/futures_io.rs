@@ -0,0 +1,293 @@
+//! Transport for runtime-agnostic byte streams.
+//!
+//! [`FuturesIoTransport`] works with any type, that implements [`AsyncRead`] and [`AsyncWrite`]
+//! from the `futures-io` crate. This makes it usable with runtimes like smol or async-std, without
+//! depending on Tokio.
+
+#[cfg(test)]
+mod test;
+
+use crate::compression::Compression;
+use crate::framing::{self, FramingError, BACKPRESSURE_BOUNDARY, INITIAL_CAPACITY};
+use crate::message::{Message, Packetizer};
+use crate::message_deserializer::MessageDeserializeError;
+use crate::message_serializer::MessageSerializeError;
+use crate::transport::AsyncTransport;
+use crate::wire_format::WireFormat;
+use bytes::{Buf, BytesMut};
+use futures_io::{AsyncRead, AsyncWrite};
+use pin_project_lite::pin_project;
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use thiserror::Error;
+
+pin_project! {
+    /// Transport over a `futures-io` byte stream.
+    ///
+    /// Messages are framed in exactly the same way as by `TokioTransport`, so both can be used on
+    /// opposite ends of the same connection. Compression, alternative wire formats and message
+    /// size limits are supported as well.
+    #[derive(Debug)]
+    pub struct FuturesIoTransport<T> {
+        #[pin]
+        io: T,
+        packetizer: Packetizer,
+        read_buf: Box<[u8]>,
+        write_buf: BytesMut,
+        compression_threshold: Option<usize>,
+        compression: Option<Compression>,
+        wire_formats: Vec<WireFormat>,
+        wire_format: Option<WireFormat>,
+        max_message_size: Option<usize>,
+        peer_max_message_size: Option<usize>,
+    }
+}
+
+impl<T> FuturesIoTransport<T> {
+    /// Creates a new transport from a byte stream.
+    pub fn new(io: T) -> Self {
+        Self {
+            io,
+            packetizer: Packetizer::new(),
+            read_buf: vec![0; INITIAL_CAPACITY].into_boxed_slice(),
+            write_buf: BytesMut::with_capacity(INITIAL_CAPACITY),
+            compression_threshold: None,
+            compression: None,
+            wire_formats: Vec::new(),
+            wire_format: None,
+            max_message_size: None,
+            peer_max_message_size: None,
+        }
+    }
+
+    /// Returns a reference to the underlying IO object.
+    pub fn get_ref(&self) -> &T {
+        &self.io
+    }
+
+    /// Consumes the transport and returns the underlying IO object.
+    ///
+    /// Buffered data, that has neither been received nor sent yet, is lost.
+    pub fn into_inner(self) -> T {
+        self.io
+    }
+
+    /// Sets the size threshold, at which messages are compressed.
+    ///
+    /// Compression is disabled by default. Setting a threshold makes the transport offer all
+    /// [supported algorithms](Compression::SUPPORTED) during the connection handshake. If one is
+    /// negotiated, then all messages of at least `threshold` bytes are compressed. Smaller messages
+    /// are always sent uncompressed, as are messages, that don't get smaller by compressing them.
+    ///
+    /// This must be set before connecting. `None` disables compression again.
+    pub fn set_compression_threshold(&mut self, threshold: Option<usize>) {
+        self.compression_threshold = threshold;
+    }
+
+    /// Returns the size threshold, at which messages are compressed.
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+
+    /// Returns the negotiated compression algorithm.
+    pub fn compression(&self) -> Option<Compression> {
+        self.compression
+    }
+
+    /// Sets the alternative wire formats, that the transport supports.
+    ///
+    /// Messages are encoded in the binary format by default. Setting alternative formats makes
+    /// clients offer them during the connection handshake, ordered by preference. On the broker's
+    /// side, these are the formats, that clients are allowed to choose from. Formats, that are not
+    /// [supported](WireFormat::SUPPORTED) with the enabled Cargo features, are ignored.
+    ///
+    /// Compression is never applied to messages in an alternative wire format.
+    ///
+    /// This must be set before connecting.
+    pub fn set_wire_formats(&mut self, formats: impl IntoIterator<Item = WireFormat>) {
+        self.wire_formats = formats
+            .into_iter()
+            .filter(|format| format.is_supported())
+            .collect();
+    }
+
+    /// Returns the alternative wire formats, that the transport supports.
+    pub fn wire_formats(&self) -> &[WireFormat] {
+        &self.wire_formats
+    }
+
+    /// Returns the negotiated wire format.
+    ///
+    /// `None` indicates the binary format.
+    pub fn wire_format(&self) -> Option<WireFormat> {
+        self.wire_format
+    }
+
+    /// Sets the maximum size of received messages.
+    ///
+    /// There is no limit by default. The limit is advertised to the peer during the connection
+    /// handshake. Receiving a larger message fails with
+    /// [`FuturesIoTransportError::MessageTooLarge`] as soon as its length is known, without
+    /// buffering it. For compressed messages, the limit applies to the decompressed size as well.
+    ///
+    /// Limits larger than [`u32::MAX`] are equivalent to no limit. This must be set before
+    /// connecting.
+    pub fn set_max_message_size(&mut self, size: Option<usize>) {
+        self.max_message_size = size;
+    }
+
+    /// Returns the maximum size of received messages.
+    pub fn max_message_size(&self) -> Option<usize> {
+        self.max_message_size
+    }
+
+    /// Returns the maximum size of messages, that the peer accepts.
+    ///
+    /// This is known only after the connection handshake. Sending a larger message fails with
+    /// [`FuturesIoTransportError::MessageTooLarge`].
+    pub fn peer_max_message_size(&self) -> Option<usize> {
+        self.peer_max_message_size
+    }
+}
+
+impl<T> AsyncTransport for FuturesIoTransport<T>
+where
+    T: AsyncRead + AsyncWrite,
+{
+    type Error = FuturesIoTransportError;
+
+    fn receive_poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Message, Self::Error>> {
+        let mut this = self.project();
+
+        loop {
+            if let Some(msg) =
+                framing::next_message(this.packetizer, *this.wire_format, *this.max_message_size)
+            {
+                return Poll::Ready(msg.map_err(Into::into));
+            }
+
+            framing::check_incomplete_size(
+                this.packetizer,
+                *this.wire_format,
+                *this.max_message_size,
+            )?;
+
+            match this.io.as_mut().poll_read(cx, this.read_buf) {
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(FuturesIoTransportError::Io(
+                        IoErrorKind::UnexpectedEof.into(),
+                    )))
+                }
+
+                Poll::Ready(Ok(len)) => this.packetizer.extend_from_slice(&this.read_buf[..len]),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(FuturesIoTransportError::Io(e))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    fn send_poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        if self.write_buf.len() >= BACKPRESSURE_BOUNDARY {
+            self.send_poll_flush(cx)
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn send_start(self: Pin<&mut Self>, msg: Message) -> Result<(), Self::Error> {
+        let this = self.project();
+
+        let msg = framing::pack_message(
+            msg,
+            *this.wire_format,
+            *this.compression,
+            *this.compression_threshold,
+            *this.peer_max_message_size,
+        )?;
+
+        if this.write_buf.is_empty() {
+            *this.write_buf = msg;
+        } else {
+            this.write_buf.extend_from_slice(&msg);
+        }
+
+        Ok(())
+    }
+
+    fn send_poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        let mut this = self.project();
+
+        while !this.write_buf.is_empty() {
+            match this.io.as_mut().poll_write(cx, this.write_buf) {
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(FuturesIoTransportError::Io(
+                        IoErrorKind::WriteZero.into(),
+                    )));
+                }
+                Poll::Ready(Ok(n)) => {
+                    this.write_buf.advance(n);
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(FuturesIoTransportError::Io(e))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        this.io.poll_flush(cx).map_err(FuturesIoTransportError::Io)
+    }
+
+    fn supported_compression(&self) -> &[Compression] {
+        if self.compression_threshold.is_some() {
+            Compression::SUPPORTED
+        } else {
+            &[]
+        }
+    }
+
+    fn enable_compression(self: Pin<&mut Self>, compression: Compression) {
+        *self.project().compression = Some(compression);
+    }
+
+    fn supported_wire_formats(&self) -> &[WireFormat] {
+        &self.wire_formats
+    }
+
+    fn enable_wire_format(self: Pin<&mut Self>, format: WireFormat) {
+        *self.project().wire_format = Some(format);
+    }
+
+    fn max_message_size(&self) -> Option<usize> {
+        self.max_message_size
+    }
+
+    fn set_peer_max_message_size(self: Pin<&mut Self>, size: usize) {
+        *self.project().peer_max_message_size = Some(size);
+    }
+}
+
+/// Error type of [`FuturesIoTransport`].
+#[derive(Error, Debug)]
+pub enum FuturesIoTransportError {
+    #[error(transparent)]
+    Io(#[from] IoError),
+
+    #[error(transparent)]
+    Serialize(#[from] MessageSerializeError),
+
+    #[error(transparent)]
+    Deserialize(#[from] MessageDeserializeError),
+
+    /// A message exceeded the maximum size.
+    #[error("message of {0} bytes exceeds the maximum size")]
+    MessageTooLarge(usize),
+}
+
+impl From<FramingError> for FuturesIoTransportError {
+    fn from(e: FramingError) -> Self {
+        match e {
+            FramingError::Serialize(e) => Self::Serialize(e),
+            FramingError::Deserialize(e) => Self::Deserialize(e),
+            FramingError::MessageTooLarge(len) => Self::MessageTooLarge(len),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use super::{FuturesIoTransport, FuturesIoTransportError};
+use crate::message::{Message, Sync};
+use crate::transport::AsyncTransportExt;
+use futures_io::{AsyncRead, AsyncWrite};
+use std::io::{ErrorKind as IoErrorKind, Result as IoResult};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Byte stream, that reads from a slice and writes into a vector.
+struct Io<'a> {
+    read: &'a [u8],
+    write: Vec<u8>,
+}
+
+impl<'a> Io<'a> {
+    fn new(read: &'a [u8]) -> Self {
+        Self {
+            read,
+            write: Vec::new(),
+        }
+    }
+}
+
+impl AsyncRead for Io<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.read).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Io<'_> {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.write).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.write).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.write).poll_close(cx)
+    }
+}
+
+#[tokio::test]
+async fn send_and_receive() {
+    let mut sender = FuturesIoTransport::new(Io::new(&[]));
+    for serial in 0..3 {
+        sender.send_and_flush(Sync { serial }).await.unwrap();
+    }
+
+    let written = sender.into_inner().write;
+    let mut receiver = FuturesIoTransport::new(Io::new(&written));
+
+    for serial in 0..3 {
+        let msg = receiver.receive().await.unwrap();
+        assert_eq!(msg, Message::Sync(Sync { serial }));
+    }
+
+    let err = receiver.receive().await.unwrap_err();
+    assert!(
+        matches!(err, FuturesIoTransportError::Io(e) if e.kind() == IoErrorKind::UnexpectedEof)
+    );
+}
+
+#[tokio::test]
+async fn max_message_size() {
+    let mut sender = FuturesIoTransport::new(Io::new(&[]));
+    sender.send_and_flush(Sync { serial: 0 }).await.unwrap();
+
+    let written = sender.into_inner().write;
+    let mut receiver = FuturesIoTransport::new(Io::new(&written));
+    receiver.set_max_message_size(Some(written.len() - 1));
+
+    let err = receiver.receive().await.unwrap_err();
+    assert!(matches!(err, FuturesIoTransportError::MessageTooLarge(len) if len == written.len()));
+}
@@ -269,18 +269,24 @@ impl Introspectable for Value {
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Serialize, serde::Deserialize),
-    serde(transparent)
-)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(transparent))]
 pub struct Struct(pub HashMap<u32, Value>);
 
+impl Struct {
+    fn sorted_fields(&self) -> Vec<(&u32, &Value)> {
+        let mut fields = self.0.iter().collect::<Vec<_>>();
+        fields.sort_unstable_by_key(|&(&id, _)| id);
+        fields
+    }
+}
+
 impl Serialize for Struct {
     fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
         let mut serializer = serializer.serialize_struct(self.0.len())?;
 
-        for (&id, field) in &self.0 {
+        // Fields are serialized in ascending order of their ids, because some types require them
+        // in the order in which they are declared.
+        for (&id, field) in self.sorted_fields() {
             serializer.serialize_field(id, field)?;
         }
 
@@ -288,6 +294,14 @@ impl Serialize for Struct {
     }
 }
 
+/// Fields are serialized in ascending order of their ids, such that the output is deterministic.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Struct {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.sorted_fields())
+    }
+}
+
 impl Deserialize for Struct {
     fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
         let mut deserializer = deserializer.deserialize_struct()?;
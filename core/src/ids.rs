@@ -1,14 +1,12 @@
 use crate::deserialize_key::DeserializeKey;
 use crate::error::{DeserializeError, SerializeError};
 #[cfg(feature = "introspection")]
-use crate::introspection::{
-    BuiltInType, Introspectable, KeyType, KeyTypeOf, Layout, LexicalId, References,
-};
+use crate::introspection::{BuiltInType, Introspectable, KeyType, KeyTypeOf, Layout, References};
 use crate::serialize_key::SerializeKey;
 use crate::value_deserializer::{Deserialize, Deserializer};
 use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
-use std::fmt;
-use std::str::FromStr;
+use core::fmt;
+use core::str::FromStr;
 use uuid::{Error as UuidError, Uuid};
 
 /// Id of an object.
@@ -911,3 +909,81 @@ impl FromStr for TypeId {
         s.parse().map(Self)
     }
 }
+
+/// Introspection lexical id of a type or service.
+///
+/// Unlike a [`TypeId`], the lexical id depends only on the schema and name, not on the layout.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[repr(transparent)]
+pub struct LexicalId(pub Uuid);
+
+impl LexicalId {
+    /// Nil `LexicalId` (all zeros).
+    pub const NIL: Self = Self(Uuid::nil());
+
+    /// Checks if the id is nil (all zeros).
+    pub const fn is_nil(self) -> bool {
+        self.0.is_nil()
+    }
+}
+
+impl Serialize for LexicalId {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        serializer.serialize_uuid(self.0);
+        Ok(())
+    }
+}
+
+impl Deserialize for LexicalId {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_uuid().map(Self)
+    }
+}
+
+impl SerializeKey for LexicalId {
+    type Impl<'a> = Uuid;
+
+    fn as_impl(&self) -> Self::Impl<'_> {
+        self.0
+    }
+}
+
+impl DeserializeKey for LexicalId {
+    type Impl = Uuid;
+
+    fn try_from_impl(key: Self::Impl) -> Result<Self, DeserializeError> {
+        Ok(Self(key))
+    }
+}
+
+impl From<Uuid> for LexicalId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+impl From<LexicalId> for Uuid {
+    fn from(id: LexicalId) -> Self {
+        id.0
+    }
+}
+
+impl fmt::Display for LexicalId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for LexicalId {
+    type Err = UuidError;
+
+    fn from_str(s: &str) -> Result<Self, UuidError> {
+        s.parse().map(Self)
+    }
+}
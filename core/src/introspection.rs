@@ -14,6 +14,7 @@ mod struct_ty;
 #[cfg(test)]
 mod test;
 mod type_id;
+mod validator;
 mod variant;
 
 #[doc(hidden)]
@@ -26,6 +27,7 @@ use crate::value_serializer::{Serialize, Serializer};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use std::collections::BTreeMap;
 
+pub use crate::ids::LexicalId;
 pub use array_type::ArrayType;
 pub use built_in_type::BuiltInType;
 pub use enum_ty::{Enum, EnumBuilder};
@@ -34,11 +36,12 @@ pub use field::Field;
 pub use function::Function;
 pub use key_type::{KeyType, KeyTypeOf};
 pub use layout::Layout;
-pub use lexical_id::LexicalId;
 pub use map_type::MapType;
 pub use result_type::ResultType;
 pub use service::{Service, ServiceBuilder};
 pub use struct_ty::{Struct, StructBuilder};
+pub use type_id::TypeIdExplanation;
+pub use validator::{PathSegment, ValidationError, ValidationErrorKind, Validator};
 pub use variant::Variant;
 
 pub const VERSION: u32 = 1;
@@ -11,6 +11,7 @@ pub struct Enum {
     schema: String,
     name: String,
     variants: BTreeMap<u32, Variant>,
+    type_args: Vec<LexicalId>,
 }
 
 impl Enum {
@@ -21,7 +22,7 @@ impl Enum {
     }
 
     pub fn lexical_id(&self) -> LexicalId {
-        LexicalId::custom(&self.schema, &self.name)
+        LexicalId::custom_generic(&self.schema, &self.name, &self.type_args)
     }
 
     pub fn schema(&self) -> &str {
@@ -35,6 +36,10 @@ impl Enum {
     pub fn variants(&self) -> &BTreeMap<u32, Variant> {
         &self.variants
     }
+
+    pub fn type_args(&self) -> &[LexicalId] {
+        &self.type_args
+    }
 }
 
 #[derive(IntoPrimitive, TryFromPrimitive)]
@@ -43,16 +48,22 @@ enum EnumField {
     Schema = 0,
     Name = 1,
     Variants = 2,
+    TypeArgs = 3,
 }
 
 impl Serialize for Enum {
     fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
-        let mut serializer = serializer.serialize_struct(3)?;
+        let num = if self.type_args.is_empty() { 3 } else { 4 };
+        let mut serializer = serializer.serialize_struct(num)?;
 
         serializer.serialize_field(EnumField::Schema, &self.schema)?;
         serializer.serialize_field(EnumField::Name, &self.name)?;
         serializer.serialize_field(EnumField::Variants, &self.variants)?;
 
+        if !self.type_args.is_empty() {
+            serializer.serialize_field(EnumField::TypeArgs, &self.type_args)?;
+        }
+
         serializer.finish()
     }
 }
@@ -65,10 +76,17 @@ impl Deserialize for Enum {
         let name = deserializer.deserialize_specific_field(EnumField::Name)?;
         let variants = deserializer.deserialize_specific_field(EnumField::Variants)?;
 
+        let type_args = if deserializer.has_more_fields() {
+            deserializer.deserialize_specific_field(EnumField::TypeArgs)?
+        } else {
+            Vec::new()
+        };
+
         deserializer.finish(Self {
             schema,
             name,
             variants,
+            type_args,
         })
     }
 }
@@ -78,6 +96,7 @@ pub struct EnumBuilder {
     schema: String,
     name: String,
     variants: BTreeMap<u32, Variant>,
+    type_args: Vec<LexicalId>,
 }
 
 impl EnumBuilder {
@@ -86,6 +105,7 @@ impl EnumBuilder {
             schema: schema.into(),
             name: name.into(),
             variants: BTreeMap::new(),
+            type_args: Vec::new(),
         }
     }
 
@@ -113,11 +133,17 @@ impl EnumBuilder {
         self.variant(id, name, None)
     }
 
+    pub fn type_arg(mut self, ty: LexicalId) -> Self {
+        self.type_args.push(ty);
+        self
+    }
+
     pub fn finish(self) -> Enum {
         Enum {
             schema: self.schema,
             name: self.name,
             variants: self.variants,
+            type_args: self.type_args,
         }
     }
 }
@@ -1,21 +1,8 @@
 use super::{Introspection, KeyType, KeyTypeOf};
-use crate::deserialize_key::DeserializeKey;
-use crate::error::{DeserializeError, SerializeError};
-use crate::ids::TypeId;
-use crate::serialize_key::SerializeKey;
-use crate::value_deserializer::{Deserialize, Deserializer};
-use crate::value_serializer::{Serialize, Serializer};
-use std::fmt;
-use std::str::FromStr;
-use uuid::{uuid, Error as UuidError, Uuid};
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
-#[repr(transparent)]
-pub struct LexicalId(pub Uuid);
+use crate::ids::{LexicalId, TypeId};
+use uuid::{uuid, Uuid};
 
 impl LexicalId {
-    pub const NIL: Self = Self(Uuid::nil());
-
     pub const BOOL: Self = Self(uuid!("f00cbfc4-cf2f-457a-aa56-35923c8b2571"));
     pub const U8: Self = Self(uuid!("0ff2b764-1666-46e1-82ff-aa59264ba7f0"));
     pub const I8: Self = Self(uuid!("055a9029-e3ff-4b38-922f-6a5eee338138"));
@@ -91,11 +78,7 @@ impl LexicalId {
         Self::fully_qualified(Self::NAMESPACE_CUSTOM, schema, name, &[])
     }
 
-    pub fn custom_generic<const N: usize>(
-        schema: impl AsRef<str>,
-        name: impl AsRef<str>,
-        types: &[Self; N],
-    ) -> Self {
+    pub fn custom_generic(schema: impl AsRef<str>, name: impl AsRef<str>, types: &[Self]) -> Self {
         Self::fully_qualified(Self::NAMESPACE_CUSTOM, schema, name, types)
     }
 
@@ -103,10 +86,6 @@ impl LexicalId {
         Self::fully_qualified(Self::NAMESPACE_SERVICE, schema, name, &[])
     }
 
-    pub const fn is_nil(self) -> bool {
-        self.0.is_nil()
-    }
-
     pub fn resolve(self, introspection: &Introspection) -> Option<TypeId> {
         introspection.resolve(self)
     }
@@ -122,15 +101,15 @@ impl LexicalId {
         Self(Uuid::new_v5(&ns, &name))
     }
 
-    fn fully_qualified<const N: usize>(
+    fn fully_qualified(
         ns: Uuid,
         schema: impl AsRef<str>,
         name: impl AsRef<str>,
-        types: &[Self; N],
+        types: &[Self],
     ) -> Self {
         let mut fully_qualified = format!("{}::{}", schema.as_ref(), name.as_ref());
 
-        if N > 0 {
+        if !types.is_empty() {
             fully_qualified.push('<');
         }
 
@@ -142,7 +121,7 @@ impl LexicalId {
             fully_qualified.push_str(&ty.to_string());
         }
 
-        if N > 0 {
+        if !types.is_empty() {
             fully_qualified.push('>');
         }
 
@@ -150,61 +129,6 @@ impl LexicalId {
     }
 }
 
-impl Serialize for LexicalId {
-    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
-        serializer.serialize_uuid(self.0);
-        Ok(())
-    }
-}
-
-impl Deserialize for LexicalId {
-    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
-        deserializer.deserialize_uuid().map(Self)
-    }
-}
-
-impl SerializeKey for LexicalId {
-    type Impl<'a> = Uuid;
-
-    fn as_impl(&self) -> Self::Impl<'_> {
-        self.0
-    }
-}
-
-impl DeserializeKey for LexicalId {
-    type Impl = Uuid;
-
-    fn try_from_impl(key: Self::Impl) -> Result<Self, DeserializeError> {
-        Ok(Self(key))
-    }
-}
-
 impl KeyTypeOf for LexicalId {
     const KEY_TYPE: KeyType = KeyType::Uuid;
 }
-
-impl From<Uuid> for LexicalId {
-    fn from(uuid: Uuid) -> Self {
-        Self(uuid)
-    }
-}
-
-impl From<LexicalId> for Uuid {
-    fn from(id: LexicalId) -> Self {
-        id.0
-    }
-}
-
-impl fmt::Display for LexicalId {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.fmt(f)
-    }
-}
-
-impl FromStr for LexicalId {
-    type Err = UuidError;
-
-    fn from_str(s: &str) -> Result<Self, UuidError> {
-        s.parse().map(Self)
-    }
-}
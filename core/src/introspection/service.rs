@@ -56,6 +56,16 @@ impl Service {
     pub fn events(&self) -> &BTreeMap<u32, Event> {
         &self.events
     }
+
+    pub fn function_by_name(&self, name: &str) -> Option<&Function> {
+        self.functions
+            .values()
+            .find(|function| function.name() == name)
+    }
+
+    pub fn event_by_name(&self, name: &str) -> Option<&Event> {
+        self.events.values().find(|event| event.name() == name)
+    }
 }
 
 #[derive(IntoPrimitive, TryFromPrimitive)]
@@ -11,6 +11,7 @@ pub struct Struct {
     schema: String,
     name: String,
     fields: BTreeMap<u32, Field>,
+    type_args: Vec<LexicalId>,
 }
 
 impl Struct {
@@ -21,7 +22,7 @@ impl Struct {
     }
 
     pub fn lexical_id(&self) -> LexicalId {
-        LexicalId::custom(&self.schema, &self.name)
+        LexicalId::custom_generic(&self.schema, &self.name, &self.type_args)
     }
 
     pub fn schema(&self) -> &str {
@@ -35,6 +36,10 @@ impl Struct {
     pub fn fields(&self) -> &BTreeMap<u32, Field> {
         &self.fields
     }
+
+    pub fn type_args(&self) -> &[LexicalId] {
+        &self.type_args
+    }
 }
 
 #[derive(IntoPrimitive, TryFromPrimitive)]
@@ -43,16 +48,22 @@ enum StructField {
     Schema = 0,
     Name = 1,
     Fields = 2,
+    TypeArgs = 3,
 }
 
 impl Serialize for Struct {
     fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
-        let mut serializer = serializer.serialize_struct(3)?;
+        let num = if self.type_args.is_empty() { 3 } else { 4 };
+        let mut serializer = serializer.serialize_struct(num)?;
 
         serializer.serialize_field(StructField::Schema, &self.schema)?;
         serializer.serialize_field(StructField::Name, &self.name)?;
         serializer.serialize_field(StructField::Fields, &self.fields)?;
 
+        if !self.type_args.is_empty() {
+            serializer.serialize_field(StructField::TypeArgs, &self.type_args)?;
+        }
+
         serializer.finish()
     }
 }
@@ -65,10 +76,17 @@ impl Deserialize for Struct {
         let name = deserializer.deserialize_specific_field(StructField::Name)?;
         let fields = deserializer.deserialize_specific_field(StructField::Fields)?;
 
+        let type_args = if deserializer.has_more_fields() {
+            deserializer.deserialize_specific_field(StructField::TypeArgs)?
+        } else {
+            Vec::new()
+        };
+
         deserializer.finish(Self {
             schema,
             name,
             fields,
+            type_args,
         })
     }
 }
@@ -78,6 +96,7 @@ pub struct StructBuilder {
     schema: String,
     name: String,
     fields: BTreeMap<u32, Field>,
+    type_args: Vec<LexicalId>,
 }
 
 impl StructBuilder {
@@ -86,6 +105,7 @@ impl StructBuilder {
             schema: schema.into(),
             name: name.into(),
             fields: BTreeMap::new(),
+            type_args: Vec::new(),
         }
     }
 
@@ -101,11 +121,17 @@ impl StructBuilder {
         self
     }
 
+    pub fn type_arg(mut self, ty: LexicalId) -> Self {
+        self.type_args.push(ty);
+        self
+    }
+
     pub fn finish(self) -> Struct {
         Struct {
             schema: self.schema,
             name: self.name,
             fields: self.fields,
+            type_args: self.type_args,
         }
     }
 }
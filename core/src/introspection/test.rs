@@ -1,4 +1,12 @@
-use super::{Introspectable, Introspection, Layout, LexicalId, References, Struct};
+use super::{
+    Enum, Introspectable, Introspection, Layout, LexicalId, PathSegment, References, Service,
+    Struct, ValidationErrorKind, Validator,
+};
+use crate::generic_value::{Enum as EnumValue, Struct as StructValue, Value};
+use crate::value::ValueKind;
+use crate::{SerializedValue, ServiceUuid, TypeId};
+use std::collections::HashMap;
+use uuid::{uuid, Uuid};
 
 #[test]
 fn duplicate_lexical_id_good() {
@@ -72,3 +80,199 @@ fn duplicate_lexical_id_bad() {
 
     Introspection::new::<Dup>();
 }
+
+struct Args;
+
+impl Introspectable for Args {
+    fn layout() -> Layout {
+        Struct::builder("test", "Args")
+            .field(1, "id", true, LexicalId::U32)
+            .field(2, "modes", false, <Vec<Mode>>::lexical_id())
+            .finish()
+            .into()
+    }
+
+    fn lexical_id() -> LexicalId {
+        LexicalId::custom("test", "Args")
+    }
+
+    fn add_references(references: &mut References) {
+        references.add::<u32>();
+        references.add::<Vec<Mode>>();
+    }
+}
+
+struct Mode;
+
+impl Introspectable for Mode {
+    fn layout() -> Layout {
+        Enum::builder("test", "Mode")
+            .variant_with_type(0, "A", LexicalId::U8)
+            .unit_variant(1, "B")
+            .finish()
+            .into()
+    }
+
+    fn lexical_id() -> LexicalId {
+        LexicalId::custom("test", "Mode")
+    }
+
+    fn add_references(references: &mut References) {
+        references.add::<u8>();
+    }
+}
+
+struct Svc;
+
+impl Introspectable for Svc {
+    fn layout() -> Layout {
+        Service::builder(
+            "test",
+            "Svc",
+            ServiceUuid(uuid!("6a7c0c8e-3bc5-4a5d-9ef5-4b0a8c3f4a8e")),
+            1,
+        )
+        .function(0, "args", Some(Args::lexical_id()), None, None)
+        .function(1, "no_args", None, None, None)
+        .finish()
+        .into()
+    }
+
+    fn lexical_id() -> LexicalId {
+        LexicalId::custom("test", "Svc")
+    }
+
+    fn add_references(references: &mut References) {
+        references.add::<Args>();
+    }
+}
+
+fn args(fields: impl IntoIterator<Item = (u32, Value)>) -> SerializedValue {
+    let value = Value::Struct(StructValue(fields.into_iter().collect::<HashMap<_, _>>()));
+    SerializedValue::serialize(&value).unwrap()
+}
+
+fn mode(variant: u32, value: Value) -> Value {
+    Value::Enum(Box::new(EnumValue::new(variant, value)))
+}
+
+#[test]
+fn validate_valid_args() {
+    let validator = Validator::new::<Svc>();
+
+    let value = args([
+        (1, Value::U32(1)),
+        (
+            2,
+            Value::Vec(vec![mode(0, Value::U8(2)), mode(1, Value::None)]),
+        ),
+        (3, Value::String("unknown fields are ignored".to_owned())),
+    ]);
+    assert_eq!(validator.validate_args(0, &value), Ok(()));
+
+    let value = args([(1, Value::U32(1))]);
+    assert_eq!(validator.validate_args(0, &value), Ok(()));
+
+    let value = SerializedValue::serialize(&()).unwrap();
+    assert_eq!(validator.validate_args(1, &value), Ok(()));
+
+    // Unknown functions can't be validated.
+    let value = SerializedValue::serialize(&0u8).unwrap();
+    assert_eq!(validator.validate_args(2, &value), Ok(()));
+}
+
+#[test]
+fn validate_required_field_missing() {
+    let validator = Validator::new::<Svc>();
+
+    let value = args([(2, Value::Vec(Vec::new()))]);
+    let err = validator.validate_args(0, &value).unwrap_err();
+    assert_eq!(*err.kind(), ValidationErrorKind::RequiredFieldMissing(1));
+    assert_eq!(err.path(), []);
+}
+
+#[test]
+fn validate_nested_unexpected_value() {
+    let validator = Validator::new::<Svc>();
+
+    let value = args([
+        (1, Value::U32(1)),
+        (
+            2,
+            Value::Vec(vec![mode(0, Value::U8(2)), mode(0, Value::U16(2))]),
+        ),
+    ]);
+    let err = validator.validate_args(0, &value).unwrap_err();
+    assert_eq!(
+        *err.kind(),
+        ValidationErrorKind::UnexpectedValue(ValueKind::U16)
+    );
+    assert_eq!(
+        err.path(),
+        [
+            PathSegment::Field(2),
+            PathSegment::Index(1),
+            PathSegment::Variant(0)
+        ]
+    );
+    assert_eq!(
+        err.to_string(),
+        "unexpected value of kind U16 at field 2 / index 1 / variant 0"
+    );
+}
+
+#[test]
+fn validate_invalid_variant() {
+    let validator = Validator::new::<Svc>();
+
+    let value = args([
+        (1, Value::U32(1)),
+        (2, Value::Vec(vec![mode(2, Value::None)])),
+    ]);
+    let err = validator.validate_args(0, &value).unwrap_err();
+    assert_eq!(*err.kind(), ValidationErrorKind::InvalidVariant(2));
+    assert_eq!(err.path(), [PathSegment::Field(2), PathSegment::Index(0)]);
+}
+
+#[test]
+fn validate_unit_args() {
+    let validator = Validator::new::<Svc>();
+
+    let value = SerializedValue::serialize(&0u32).unwrap();
+    let err = validator.validate_args(1, &value).unwrap_err();
+    assert_eq!(
+        *err.kind(),
+        ValidationErrorKind::UnexpectedValue(ValueKind::U32)
+    );
+}
+
+#[test]
+fn explain_type_id() {
+    let explanation = TypeId::explain::<Args>();
+
+    assert_eq!(explanation.type_id(), TypeId::compute::<Args>());
+    assert_eq!(
+        explanation.type_id(),
+        Introspection::new::<Args>().type_id()
+    );
+    assert_eq!(explanation.namespace(), Struct::NAMESPACE);
+    assert_eq!(*explanation.layout(), Args::layout());
+
+    let referenced = explanation
+        .referenced()
+        .map(Layout::lexical_id)
+        .collect::<Vec<_>>();
+    assert!(referenced.contains(&LexicalId::U32));
+    assert!(referenced.contains(&LexicalId::custom("test", "Mode")));
+    assert!(referenced.contains(&LexicalId::U8));
+
+    let type_id = Uuid::new_v5(&explanation.namespace(), explanation.canonical_form());
+    assert_eq!(TypeId(type_id), explanation.type_id());
+
+    let display = explanation.to_string();
+    assert!(display.starts_with(&format!("type id: {}\n", explanation.type_id())));
+    assert!(display.contains(&format!(
+        "canonical form: {} bytes\n",
+        explanation.canonical_form().len()
+    )));
+}
@@ -1,10 +1,11 @@
 use super::{DynIntrospectable, Introspectable, Layout, References, VERSION};
 use crate::error::SerializeError;
 use crate::ids::TypeId;
-use crate::serialized_value::SerializedValue;
+use crate::serialized_value::{SerializedValue, SerializedValueSlice};
 use crate::value_serializer::{Serialize, Serializer};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use std::collections::BTreeSet;
+use std::fmt;
 use uuid::Uuid;
 
 impl TypeId {
@@ -13,6 +14,44 @@ impl TypeId {
     }
 
     pub fn compute_from_dyn(ty: DynIntrospectable) -> Self {
+        TypeIdExplanation::new(ty).type_id
+    }
+
+    /// Explains how the `TypeId` of `T` is computed.
+    ///
+    /// This is useful to diagnose mismatching `TypeId`s, e.g. when a type has changed in a way
+    /// that was not expected to affect its `TypeId`. See [`TypeIdExplanation`] for details.
+    pub fn explain<T: Introspectable + ?Sized>() -> TypeIdExplanation {
+        Self::explain_dyn(DynIntrospectable::new::<T>())
+    }
+
+    /// Explains how the `TypeId` of a [`DynIntrospectable`] is computed.
+    pub fn explain_dyn(ty: DynIntrospectable) -> TypeIdExplanation {
+        TypeIdExplanation::new(ty)
+    }
+}
+
+/// Canonical form of a type, from which its [`TypeId`] is computed.
+///
+/// A `TypeId` is a version 5 UUID. Its namespace depends on the kind of the type (built-in type,
+/// struct, enum or service) and its name is the serialized canonical form. The canonical form
+/// consists of the introspection [`VERSION`], the type's [`Layout`] and the layouts of all types
+/// it references, directly or indirectly. Two types thus have the same `TypeId` if and only if
+/// all of these are equal.
+///
+/// The [`Display`](fmt::Display) implementation dumps everything in a human-readable form,
+/// which can be compared between two programs to find the cause of mismatching `TypeId`s.
+///
+/// This type is acquired by [`TypeId::explain`].
+#[derive(Debug, Clone)]
+pub struct TypeIdExplanation {
+    type_id: TypeId,
+    compute: Compute,
+    canonical_form: SerializedValue,
+}
+
+impl TypeIdExplanation {
+    fn new(ty: DynIntrospectable) -> Self {
         let mut compute = Compute::new(ty.layout());
 
         let mut references = Vec::new();
@@ -24,11 +63,76 @@ impl TypeId {
             }
         }
 
-        let serialized = SerializedValue::serialize(&compute).unwrap();
-        Self(Uuid::new_v5(&compute.namespace(), &serialized))
+        let canonical_form = SerializedValue::serialize(&compute).unwrap();
+        let type_id = TypeId(Uuid::new_v5(&compute.namespace(), &canonical_form));
+
+        Self {
+            type_id,
+            compute,
+            canonical_form,
+        }
+    }
+
+    /// Returns the computed `TypeId`.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the UUID namespace, that was used to compute the `TypeId`.
+    pub fn namespace(&self) -> Uuid {
+        self.compute.namespace()
+    }
+
+    /// Returns the layout of the type.
+    pub fn layout(&self) -> &Layout {
+        &self.compute.layout
+    }
+
+    /// Returns the layouts of all types, that are referenced by the type.
+    ///
+    /// The layouts are returned in the order in which they are part of the canonical form.
+    pub fn referenced(&self) -> impl ExactSizeIterator<Item = &Layout> {
+        self.compute.referenced.iter()
+    }
+
+    /// Returns the serialized canonical form, that was hashed to compute the `TypeId`.
+    pub fn canonical_form(&self) -> &SerializedValueSlice {
+        &self.canonical_form
+    }
+}
+
+impl fmt::Display for TypeIdExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const BYTES_PER_LINE: usize = 32;
+
+        writeln!(f, "type id: {}", self.type_id)?;
+        writeln!(f, "namespace: {}", self.namespace())?;
+        writeln!(f, "version: {VERSION}")?;
+        writeln!(f, "layout: {:#?}", self.compute.layout)?;
+
+        writeln!(f, "referenced layouts: {}", self.compute.referenced.len())?;
+        for layout in &self.compute.referenced {
+            writeln!(f, "{layout:#?}")?;
+        }
+
+        write!(f, "canonical form: {} bytes", self.canonical_form.len())?;
+        for line in self.canonical_form.chunks(BYTES_PER_LINE) {
+            writeln!(f)?;
+
+            for (i, byte) in line.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+
+                write!(f, "{byte:02x}")?;
+            }
+        }
+
+        Ok(())
     }
 }
 
+#[derive(Debug, Clone)]
 struct Compute {
     layout: Layout,
     referenced: BTreeSet<Layout>,
@@ -0,0 +1,386 @@
+use super::{
+    ArrayType, BuiltInType, DynIntrospectable, Enum, Introspectable, KeyType, Layout, LexicalId,
+    MapType, References, ResultType, Struct,
+};
+use crate::error::DeserializeError;
+use crate::generic_value::{Struct as StructValue, Value};
+use crate::serialized_value::SerializedValueSlice;
+use crate::value::ValueKind;
+use std::collections::hash_map::{Entry, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use thiserror::Error;
+
+/// Validates serialized values against introspection layouts.
+///
+/// A validator is created from a root type and collects the layouts of all types, that are
+/// transitively referenced by it. Values can then be checked against any of these types. Unlike
+/// plain deserialization errors, validation errors point to the offending part of a value (see
+/// [`ValidationError::path`]).
+///
+/// Validation follows the same rules as derived [`Deserialize`](crate::Deserialize)
+/// implementations: unknown struct fields are ignored, whereas missing required fields and unknown
+/// enum variants are errors.
+#[derive(Debug, Clone)]
+pub struct Validator {
+    root: LexicalId,
+    layouts: HashMap<LexicalId, Layout>,
+}
+
+impl Validator {
+    /// Creates a new validator with `T` as its root type.
+    pub fn new<T: Introspectable + ?Sized>() -> Self {
+        Self::from_dyn(DynIntrospectable::new::<T>())
+    }
+
+    /// Creates a new validator with `ty` as its root type.
+    pub fn from_dyn(ty: DynIntrospectable) -> Self {
+        let root = ty.lexical_id();
+        let mut layouts = HashMap::new();
+        let mut types = vec![ty];
+
+        while let Some(ty) = types.pop() {
+            if let Entry::Vacant(entry) = layouts.entry(ty.lexical_id()) {
+                entry.insert(ty.layout());
+                ty.add_references(&mut References::new(&mut types));
+            }
+        }
+
+        Self { root, layouts }
+    }
+
+    /// Returns the lexical id of the root type.
+    pub fn root(&self) -> LexicalId {
+        self.root
+    }
+
+    /// Returns the layout of a type, if it is known to the validator.
+    pub fn layout(&self, ty: LexicalId) -> Option<&Layout> {
+        self.layouts.get(&ty)
+    }
+
+    /// Validates a value against a type.
+    pub fn validate(
+        &self,
+        ty: LexicalId,
+        value: &SerializedValueSlice,
+    ) -> Result<(), ValidationError> {
+        let value = value.deserialize().map_err(ValidationError::from)?;
+
+        self.validate_value(ty, &value).map_err(|mut e| {
+            e.path.reverse();
+            e
+        })
+    }
+
+    /// Validates the arguments of a function of the root service.
+    ///
+    /// If the root type is not a service or the service has no function `function`, then there is
+    /// nothing to validate against and `Ok(())` is returned.
+    pub fn validate_args(
+        &self,
+        function: u32,
+        args: &SerializedValueSlice,
+    ) -> Result<(), ValidationError> {
+        let Some(Layout::Service(service)) = self.layouts.get(&self.root) else {
+            return Ok(());
+        };
+
+        let Some(function) = service.functions().get(&function) else {
+            return Ok(());
+        };
+
+        match function.args() {
+            Some(args_ty) => self.validate(args_ty, args),
+
+            None => {
+                let value = args.deserialize().map_err(ValidationError::from)?;
+                self.validate_built_in(BuiltInType::Unit, &value)
+            }
+        }
+    }
+
+    fn validate_value(&self, ty: LexicalId, value: &Value) -> Result<(), ValidationError> {
+        match self.layouts.get(&ty) {
+            Some(Layout::BuiltIn(ty)) => self.validate_built_in(*ty, value),
+            Some(Layout::Struct(ty)) => self.validate_struct(ty, value),
+            Some(Layout::Enum(ty)) => self.validate_enum(ty, value),
+            Some(Layout::Service(_)) => {
+                Err(ValidationErrorKind::UnexpectedValue(value.kind()).into())
+            }
+            None => Err(ValidationErrorKind::UnknownType(ty).into()),
+        }
+    }
+
+    fn validate_built_in(&self, ty: BuiltInType, value: &Value) -> Result<(), ValidationError> {
+        match (ty, value) {
+            (BuiltInType::Bool, Value::Bool(_))
+            | (BuiltInType::U8, Value::U8(_))
+            | (BuiltInType::I8, Value::I8(_))
+            | (BuiltInType::U16, Value::U16(_))
+            | (BuiltInType::I16, Value::I16(_))
+            | (BuiltInType::U32, Value::U32(_))
+            | (BuiltInType::I32, Value::I32(_))
+            | (BuiltInType::U64, Value::U64(_))
+            | (BuiltInType::I64, Value::I64(_))
+            | (BuiltInType::F32, Value::F32(_))
+            | (BuiltInType::F64, Value::F64(_))
+            | (BuiltInType::String, Value::String(_))
+            | (BuiltInType::Uuid, Value::Uuid(_))
+            | (BuiltInType::ObjectId, Value::ObjectId(_))
+            | (BuiltInType::ServiceId, Value::ServiceId(_))
+            | (BuiltInType::Value, _)
+            | (BuiltInType::Option(_), Value::None)
+            | (BuiltInType::Bytes, Value::Bytes(_))
+            | (BuiltInType::Sender(_), Value::Sender(_))
+            | (BuiltInType::Receiver(_), Value::Receiver(_))
+            | (BuiltInType::Lifetime, Value::ObjectId(_))
+            | (BuiltInType::Unit, Value::None) => Ok(()),
+
+            (BuiltInType::Option(ty), Value::Some(value)) => self.validate_value(ty, value),
+            (BuiltInType::Box(ty), value) => self.validate_value(ty, value),
+
+            (BuiltInType::Vec(ty), Value::Vec(elems)) => self.validate_elems(ty, elems),
+            (BuiltInType::Map(ty), value) => self.validate_map(ty, value),
+            (BuiltInType::Set(ty), value) => Self::validate_set(ty, value),
+            (BuiltInType::Result(ty), Value::Enum(value)) => {
+                self.validate_result(ty, value.variant, &value.value)
+            }
+            (BuiltInType::Array(ty), Value::Vec(elems)) => self.validate_array(ty, elems),
+
+            _ => Err(ValidationErrorKind::UnexpectedValue(value.kind()).into()),
+        }
+    }
+
+    fn validate_elems(&self, ty: LexicalId, elems: &[Value]) -> Result<(), ValidationError> {
+        for (i, elem) in elems.iter().enumerate() {
+            self.validate_value(ty, elem)
+                .map_err(|e| e.within(PathSegment::Index(i as u32)))?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_array(&self, ty: ArrayType, elems: &[Value]) -> Result<(), ValidationError> {
+        if elems.len() == ty.len() as usize {
+            self.validate_elems(ty.elem_type(), elems)
+        } else {
+            Err(ValidationErrorKind::InvalidArrayLength(elems.len()).into())
+        }
+    }
+
+    fn validate_map(&self, ty: MapType, value: &Value) -> Result<(), ValidationError> {
+        match (ty.key(), value) {
+            (KeyType::U8, Value::U8Map(map)) => self.validate_map_values(ty.value(), map),
+            (KeyType::I8, Value::I8Map(map)) => self.validate_map_values(ty.value(), map),
+            (KeyType::U16, Value::U16Map(map)) => self.validate_map_values(ty.value(), map),
+            (KeyType::I16, Value::I16Map(map)) => self.validate_map_values(ty.value(), map),
+            (KeyType::U32, Value::U32Map(map)) => self.validate_map_values(ty.value(), map),
+            (KeyType::I32, Value::I32Map(map)) => self.validate_map_values(ty.value(), map),
+            (KeyType::U64, Value::U64Map(map)) => self.validate_map_values(ty.value(), map),
+            (KeyType::I64, Value::I64Map(map)) => self.validate_map_values(ty.value(), map),
+            (KeyType::String, Value::StringMap(map)) => self.validate_map_values(ty.value(), map),
+            (KeyType::Uuid, Value::UuidMap(map)) => self.validate_map_values(ty.value(), map),
+            _ => Err(ValidationErrorKind::UnexpectedValue(value.kind()).into()),
+        }
+    }
+
+    fn validate_map_values<K: Eq + Hash + fmt::Display>(
+        &self,
+        ty: LexicalId,
+        map: &HashMap<K, Value>,
+    ) -> Result<(), ValidationError> {
+        for (key, value) in map {
+            self.validate_value(ty, value)
+                .map_err(|e| e.within(PathSegment::Key(key.to_string())))?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_set(ty: KeyType, value: &Value) -> Result<(), ValidationError> {
+        match (ty, value) {
+            (KeyType::U8, Value::U8Set(_))
+            | (KeyType::I8, Value::I8Set(_))
+            | (KeyType::U16, Value::U16Set(_))
+            | (KeyType::I16, Value::I16Set(_))
+            | (KeyType::U32, Value::U32Set(_))
+            | (KeyType::I32, Value::I32Set(_))
+            | (KeyType::U64, Value::U64Set(_))
+            | (KeyType::I64, Value::I64Set(_))
+            | (KeyType::String, Value::StringSet(_))
+            | (KeyType::Uuid, Value::UuidSet(_)) => Ok(()),
+            _ => Err(ValidationErrorKind::UnexpectedValue(value.kind()).into()),
+        }
+    }
+
+    fn validate_result(
+        &self,
+        ty: ResultType,
+        variant: u32,
+        value: &Value,
+    ) -> Result<(), ValidationError> {
+        let ty = match variant {
+            0 => ty.ok(),
+            1 => ty.err(),
+            _ => return Err(ValidationErrorKind::InvalidVariant(variant).into()),
+        };
+
+        self.validate_value(ty, value)
+            .map_err(|e| e.within(PathSegment::Variant(variant)))
+    }
+
+    fn validate_struct(&self, ty: &Struct, value: &Value) -> Result<(), ValidationError> {
+        let Value::Struct(StructValue(fields)) = value else {
+            return Err(ValidationErrorKind::UnexpectedValue(value.kind()).into());
+        };
+
+        for (&id, field) in ty.fields() {
+            match fields.get(&id) {
+                Some(value) => self
+                    .validate_value(field.field_type(), value)
+                    .map_err(|e| e.within(PathSegment::Field(id)))?,
+
+                None if field.is_required() => {
+                    return Err(ValidationErrorKind::RequiredFieldMissing(id).into())
+                }
+
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_enum(&self, ty: &Enum, value: &Value) -> Result<(), ValidationError> {
+        let Value::Enum(value) = value else {
+            return Err(ValidationErrorKind::UnexpectedValue(value.kind()).into());
+        };
+
+        let Some(variant) = ty.variants().get(&value.variant) else {
+            return Err(ValidationErrorKind::InvalidVariant(value.variant).into());
+        };
+
+        let res = match variant.variant_type() {
+            Some(ty) => self.validate_value(ty, &value.value),
+            None => self.validate_built_in(BuiltInType::Unit, &value.value),
+        };
+
+        res.map_err(|e| e.within(PathSegment::Variant(value.variant)))
+    }
+}
+
+/// Error when validating a value with a [`Validator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    path: Vec<PathSegment>,
+    kind: ValidationErrorKind,
+}
+
+impl ValidationError {
+    /// Returns the path from the root of the value to the offending part.
+    ///
+    /// The path is empty if the root of the value itself is invalid.
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    /// Returns the kind of error.
+    pub fn kind(&self) -> &ValidationErrorKind {
+        &self.kind
+    }
+
+    fn within(mut self, segment: PathSegment) -> Self {
+        self.path.push(segment);
+        self
+    }
+}
+
+impl From<ValidationErrorKind> for ValidationError {
+    fn from(kind: ValidationErrorKind) -> Self {
+        Self {
+            path: Vec::new(),
+            kind,
+        }
+    }
+}
+
+impl From<DeserializeError> for ValidationError {
+    fn from(e: DeserializeError) -> Self {
+        ValidationErrorKind::Deserialize(e).into()
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        if let Some((first, rest)) = self.path.split_first() {
+            write!(f, " at {first}")?;
+
+            for segment in rest {
+                write!(f, " / {segment}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Kind of a [`ValidationError`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationErrorKind {
+    /// The value could not be deserialized at all.
+    #[error(transparent)]
+    Deserialize(DeserializeError),
+
+    /// A value is of an unexpected kind.
+    #[error("unexpected value of kind {0:?}")]
+    UnexpectedValue(ValueKind),
+
+    /// A required field of a struct is missing.
+    #[error("required field {0} missing")]
+    RequiredFieldMissing(u32),
+
+    /// An enum or result has an unknown variant.
+    #[error("invalid variant {0}")]
+    InvalidVariant(u32),
+
+    /// An array has the wrong number of elements.
+    #[error("invalid array length {0}")]
+    InvalidArrayLength(usize),
+
+    /// A type is not known to the validator.
+    #[error("unknown type {0}")]
+    UnknownType(LexicalId),
+}
+
+/// Segment of the path to an invalid part of a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// Field of a struct.
+    Field(u32),
+
+    /// Variant of an enum or result.
+    Variant(u32),
+
+    /// Element of a vec or array.
+    Index(u32),
+
+    /// Value of a map entry with the given key.
+    Key(String),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Field(id) => write!(f, "field {id}"),
+            Self::Variant(id) => write!(f, "variant {id}"),
+            Self::Index(index) => write!(f, "index {index}"),
+            Self::Key(key) => write!(f, "key {key}"),
+        }
+    }
+}
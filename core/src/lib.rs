@@ -1,48 +1,89 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_debug_implementations)]
 
+extern crate alloc;
+
+mod auth_rejection;
 mod buf_ext;
+#[cfg(feature = "std")]
 mod bus_listener;
+mod call_priority;
 mod channel_end;
+#[cfg(feature = "std")]
+mod compression;
 mod deserialize_key;
 mod error;
+mod error_envelope;
+mod fd;
+mod foreign;
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+mod framing;
+#[cfg(feature = "std")]
 mod generic_value;
 mod ids;
+#[cfg(feature = "std")]
 mod message_deserializer;
+#[cfg(feature = "std")]
 mod message_serializer;
 mod protocol_version;
 mod serialize_key;
 mod serialized_value;
 mod service_info;
+mod trace_context;
+mod unknown_fields;
+mod unknown_variant;
 mod value;
 mod value_deserializer;
 mod value_serializer;
+mod wire_format;
 
 #[cfg(feature = "channel")]
 pub mod channel;
+#[cfg(feature = "futures-io")]
+pub mod futures_io;
 #[cfg(feature = "introspection")]
 pub mod introspection;
+#[cfg(feature = "std")]
 pub mod message;
+#[cfg(feature = "std")]
+pub mod recording;
 #[cfg(feature = "tokio")]
 pub mod tokio;
+#[cfg(feature = "std")]
 pub mod transport;
 
 #[cfg(feature = "derive")]
 pub use aldrin_macros::{AsSerializeArg, Deserialize, DeserializeKey, Serialize, SerializeKey};
 #[cfg(all(feature = "derive", feature = "introspection"))]
 pub use aldrin_macros::{Introspectable, KeyTypeOf};
-pub use bus_listener::{BusEvent, BusListenerFilter, BusListenerScope, BusListenerServiceFilter};
+pub use auth_rejection::AuthRejection;
+#[cfg(feature = "std")]
+pub use bus_listener::{
+    BusEvent, BusListenerFilter, BusListenerLayoutFilter, BusListenerScope,
+    BusListenerServiceFilter,
+};
+pub use call_priority::CallPriority;
 pub use channel_end::{ChannelEnd, ChannelEndWithCapacity};
+#[cfg(feature = "std")]
+pub use compression::Compression;
 pub use deserialize_key::{DeserializeKey, DeserializeKeyImpl};
 pub use error::{DeserializeError, ProtocolVersionError, SerializeError};
+pub use error_envelope::ErrorEnvelope;
+#[cfg(all(unix, feature = "std"))]
+pub use fd::Fd;
+#[cfg(feature = "std")]
 pub use generic_value::{Enum, Struct, Value};
 pub use ids::{
-    BusListenerCookie, ChannelCookie, ObjectCookie, ObjectId, ObjectUuid, ServiceCookie, ServiceId,
-    ServiceUuid, TypeId,
+    BusListenerCookie, ChannelCookie, LexicalId, ObjectCookie, ObjectId, ObjectUuid, ServiceCookie,
+    ServiceId, ServiceUuid, TypeId,
 };
 pub use protocol_version::ProtocolVersion;
 pub use serialize_key::{SerializeKey, SerializeKeyImpl};
 pub use serialized_value::{SerializedValue, SerializedValueSlice};
 pub use service_info::ServiceInfo;
+pub use trace_context::TraceContext;
+pub use unknown_fields::UnknownFields;
+pub use unknown_variant::UnknownVariant;
 pub use value::{ByteSlice, Bytes, Skip, ValueKind};
 pub use value_deserializer::{
     BytesDeserializer, Deserialize, Deserializer, ElementDeserializer, EnumDeserializer,
@@ -52,5 +93,6 @@ pub use value_serializer::{
     AsSerializeArg, BytesSerializer, MapSerializer, Serialize, SerializeArg, Serializer,
     SetSerializer, StructSerializer, VecSerializer,
 };
+pub use wire_format::WireFormat;
 
 const MAX_VALUE_DEPTH: u8 = 32;
@@ -1,6 +1,9 @@
 mod abort_function_call;
+mod ack_event;
 mod add_bus_listener_filter;
 mod add_channel_capacity;
+mod auth_challenge;
+mod auth_response;
 mod bus_listener_current_finished;
 mod call_function;
 mod call_function_reply;
@@ -24,14 +27,22 @@ mod create_object_reply;
 mod create_service;
 mod create_service2;
 mod create_service_reply;
+mod create_services;
+mod create_services_reply;
 mod destroy_bus_listener;
 mod destroy_bus_listener_reply;
 mod destroy_object;
 mod destroy_object_reply;
 mod destroy_service;
 mod destroy_service_reply;
+mod destroy_services;
+mod destroy_services_reply;
 mod emit_bus_event;
 mod emit_event;
+mod emit_event_acked;
+mod emit_event_acked_reply;
+mod emit_reliable_event;
+mod emit_retained_event;
 mod item_received;
 mod packetizer;
 mod query_introspection;
@@ -41,6 +52,7 @@ mod query_service_info_reply;
 mod query_service_version;
 mod query_service_version_reply;
 mod register_introspection;
+mod reliable_event_received;
 mod remove_bus_listener_filter;
 mod send_item;
 mod service_destroyed;
@@ -64,6 +76,8 @@ mod unsubscribe_all_events_reply;
 mod unsubscribe_event;
 mod unsubscribe_service;
 
+#[cfg(all(unix, feature = "tokio"))]
+use crate::serialized_value::SerializedValue;
 use crate::serialized_value::SerializedValueSlice;
 use bytes::BytesMut;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -71,8 +85,11 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 pub use crate::message_deserializer::MessageDeserializeError;
 pub use crate::message_serializer::MessageSerializeError;
 pub use abort_function_call::AbortFunctionCall;
+pub use ack_event::AckEvent;
 pub use add_bus_listener_filter::AddBusListenerFilter;
 pub use add_channel_capacity::AddChannelCapacity;
+pub use auth_challenge::AuthChallenge;
+pub use auth_response::AuthResponse;
 pub use bus_listener_current_finished::BusListenerCurrentFinished;
 pub use call_function::CallFunction;
 pub use call_function_reply::{CallFunctionReply, CallFunctionResult};
@@ -96,14 +113,22 @@ pub use create_object_reply::{CreateObjectReply, CreateObjectResult};
 pub use create_service::CreateService;
 pub use create_service2::CreateService2;
 pub use create_service_reply::{CreateServiceReply, CreateServiceResult};
+pub use create_services::CreateServices;
+pub use create_services_reply::{CreateServicesReply, CreateServicesResult};
 pub use destroy_bus_listener::DestroyBusListener;
 pub use destroy_bus_listener_reply::{DestroyBusListenerReply, DestroyBusListenerResult};
 pub use destroy_object::DestroyObject;
 pub use destroy_object_reply::{DestroyObjectReply, DestroyObjectResult};
 pub use destroy_service::DestroyService;
 pub use destroy_service_reply::{DestroyServiceReply, DestroyServiceResult};
+pub use destroy_services::DestroyServices;
+pub use destroy_services_reply::{DestroyServicesReply, DestroyServicesResult};
 pub use emit_bus_event::EmitBusEvent;
 pub use emit_event::EmitEvent;
+pub use emit_event_acked::EmitEventAcked;
+pub use emit_event_acked_reply::EmitEventAckedReply;
+pub use emit_reliable_event::EmitReliableEvent;
+pub use emit_retained_event::EmitRetainedEvent;
 pub use item_received::ItemReceived;
 pub use packetizer::Packetizer;
 pub use query_introspection::QueryIntrospection;
@@ -113,6 +138,7 @@ pub use query_service_info_reply::{QueryServiceInfoReply, QueryServiceInfoResult
 pub use query_service_version::QueryServiceVersion;
 pub use query_service_version_reply::{QueryServiceVersionReply, QueryServiceVersionResult};
 pub use register_introspection::RegisterIntrospection;
+pub use reliable_event_received::ReliableEventReceived;
 pub use remove_bus_listener_filter::RemoveBusListenerFilter;
 pub use send_item::SendItem;
 pub use service_destroyed::ServiceDestroyed;
@@ -199,6 +225,18 @@ pub enum MessageKind {
     SubscribeAllEventsReply = 59,
     UnsubscribeAllEvents = 60,
     UnsubscribeAllEventsReply = 61,
+    AuthChallenge = 62,
+    AuthResponse = 63,
+    EmitRetainedEvent = 64,
+    CreateServices = 65,
+    CreateServicesReply = 66,
+    DestroyServices = 67,
+    DestroyServicesReply = 68,
+    EmitEventAcked = 69,
+    EmitEventAckedReply = 70,
+    EmitReliableEvent = 71,
+    ReliableEventReceived = 72,
+    AckEvent = 73,
 }
 
 impl MessageKind {
@@ -216,7 +254,14 @@ impl MessageKind {
             | Self::RegisterIntrospection
             | Self::QueryIntrospectionReply
             | Self::CreateService2
-            | Self::QueryServiceInfoReply => true,
+            | Self::QueryServiceInfoReply
+            | Self::AuthChallenge
+            | Self::AuthResponse
+            | Self::EmitRetainedEvent
+            | Self::CreateServices
+            | Self::EmitEventAcked
+            | Self::EmitReliableEvent
+            | Self::ReliableEventReceived => true,
 
             Self::Shutdown
             | Self::CreateObject
@@ -266,7 +311,12 @@ impl MessageKind {
             | Self::SubscribeAllEvents
             | Self::SubscribeAllEventsReply
             | Self::UnsubscribeAllEvents
-            | Self::UnsubscribeAllEventsReply => false,
+            | Self::UnsubscribeAllEventsReply
+            | Self::CreateServicesReply
+            | Self::DestroyServices
+            | Self::DestroyServicesReply
+            | Self::EmitEventAckedReply
+            | Self::AckEvent => false,
         }
     }
 }
@@ -284,6 +334,11 @@ pub trait MessageOps: Sized + message_ops::Sealed {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "kind", content = "data", rename_all = "kebab-case")
+)]
 pub enum Message {
     Connect(Connect),
     ConnectReply(ConnectReply),
@@ -347,6 +402,18 @@ pub enum Message {
     SubscribeAllEventsReply(SubscribeAllEventsReply),
     UnsubscribeAllEvents(UnsubscribeAllEvents),
     UnsubscribeAllEventsReply(UnsubscribeAllEventsReply),
+    AuthChallenge(AuthChallenge),
+    AuthResponse(AuthResponse),
+    EmitRetainedEvent(EmitRetainedEvent),
+    CreateServices(CreateServices),
+    CreateServicesReply(CreateServicesReply),
+    DestroyServices(DestroyServices),
+    DestroyServicesReply(DestroyServicesReply),
+    EmitEventAcked(EmitEventAcked),
+    EmitEventAckedReply(EmitEventAckedReply),
+    EmitReliableEvent(EmitReliableEvent),
+    ReliableEventReceived(ReliableEventReceived),
+    AckEvent(AckEvent),
 }
 
 impl MessageOps for Message {
@@ -414,6 +481,18 @@ impl MessageOps for Message {
             Self::SubscribeAllEventsReply(_) => MessageKind::SubscribeAllEventsReply,
             Self::UnsubscribeAllEvents(_) => MessageKind::UnsubscribeAllEvents,
             Self::UnsubscribeAllEventsReply(_) => MessageKind::UnsubscribeAllEventsReply,
+            Self::AuthChallenge(_) => MessageKind::AuthChallenge,
+            Self::AuthResponse(_) => MessageKind::AuthResponse,
+            Self::EmitRetainedEvent(_) => MessageKind::EmitRetainedEvent,
+            Self::CreateServices(_) => MessageKind::CreateServices,
+            Self::CreateServicesReply(_) => MessageKind::CreateServicesReply,
+            Self::DestroyServices(_) => MessageKind::DestroyServices,
+            Self::DestroyServicesReply(_) => MessageKind::DestroyServicesReply,
+            Self::EmitEventAcked(_) => MessageKind::EmitEventAcked,
+            Self::EmitEventAckedReply(_) => MessageKind::EmitEventAckedReply,
+            Self::EmitReliableEvent(_) => MessageKind::EmitReliableEvent,
+            Self::ReliableEventReceived(_) => MessageKind::ReliableEventReceived,
+            Self::AckEvent(_) => MessageKind::AckEvent,
         }
     }
 
@@ -481,6 +560,18 @@ impl MessageOps for Message {
             Self::SubscribeAllEventsReply(msg) => msg.serialize_message(),
             Self::UnsubscribeAllEvents(msg) => msg.serialize_message(),
             Self::UnsubscribeAllEventsReply(msg) => msg.serialize_message(),
+            Self::AuthChallenge(msg) => msg.serialize_message(),
+            Self::AuthResponse(msg) => msg.serialize_message(),
+            Self::EmitRetainedEvent(msg) => msg.serialize_message(),
+            Self::CreateServices(msg) => msg.serialize_message(),
+            Self::CreateServicesReply(msg) => msg.serialize_message(),
+            Self::DestroyServices(msg) => msg.serialize_message(),
+            Self::DestroyServicesReply(msg) => msg.serialize_message(),
+            Self::EmitEventAcked(msg) => msg.serialize_message(),
+            Self::EmitEventAckedReply(msg) => msg.serialize_message(),
+            Self::EmitReliableEvent(msg) => msg.serialize_message(),
+            Self::ReliableEventReceived(msg) => msg.serialize_message(),
+            Self::AckEvent(msg) => msg.serialize_message(),
         }
     }
 
@@ -668,6 +759,40 @@ impl MessageOps for Message {
                 UnsubscribeAllEventsReply::deserialize_message(buf)
                     .map(Self::UnsubscribeAllEventsReply)
             }
+            MessageKind::AuthChallenge => {
+                AuthChallenge::deserialize_message(buf).map(Self::AuthChallenge)
+            }
+            MessageKind::AuthResponse => {
+                AuthResponse::deserialize_message(buf).map(Self::AuthResponse)
+            }
+            MessageKind::EmitRetainedEvent => {
+                EmitRetainedEvent::deserialize_message(buf).map(Self::EmitRetainedEvent)
+            }
+            MessageKind::CreateServices => {
+                CreateServices::deserialize_message(buf).map(Self::CreateServices)
+            }
+            MessageKind::CreateServicesReply => {
+                CreateServicesReply::deserialize_message(buf).map(Self::CreateServicesReply)
+            }
+            MessageKind::DestroyServices => {
+                DestroyServices::deserialize_message(buf).map(Self::DestroyServices)
+            }
+            MessageKind::DestroyServicesReply => {
+                DestroyServicesReply::deserialize_message(buf).map(Self::DestroyServicesReply)
+            }
+            MessageKind::EmitEventAcked => {
+                EmitEventAcked::deserialize_message(buf).map(Self::EmitEventAcked)
+            }
+            MessageKind::EmitEventAckedReply => {
+                EmitEventAckedReply::deserialize_message(buf).map(Self::EmitEventAckedReply)
+            }
+            MessageKind::EmitReliableEvent => {
+                EmitReliableEvent::deserialize_message(buf).map(Self::EmitReliableEvent)
+            }
+            MessageKind::ReliableEventReceived => {
+                ReliableEventReceived::deserialize_message(buf).map(Self::ReliableEventReceived)
+            }
+            MessageKind::AckEvent => AckEvent::deserialize_message(buf).map(Self::AckEvent),
         }
     }
 
@@ -735,6 +860,42 @@ impl MessageOps for Message {
             Self::SubscribeAllEventsReply(msg) => msg.value(),
             Self::UnsubscribeAllEvents(msg) => msg.value(),
             Self::UnsubscribeAllEventsReply(msg) => msg.value(),
+            Self::AuthChallenge(msg) => msg.value(),
+            Self::AuthResponse(msg) => msg.value(),
+            Self::EmitRetainedEvent(msg) => msg.value(),
+            Self::CreateServices(msg) => msg.value(),
+            Self::CreateServicesReply(msg) => msg.value(),
+            Self::DestroyServices(msg) => msg.value(),
+            Self::DestroyServicesReply(msg) => msg.value(),
+            Self::EmitEventAcked(msg) => msg.value(),
+            Self::EmitEventAckedReply(msg) => msg.value(),
+            Self::EmitReliableEvent(msg) => msg.value(),
+            Self::ReliableEventReceived(msg) => msg.value(),
+            Self::AckEvent(msg) => msg.value(),
+        }
+    }
+}
+
+#[cfg(all(unix, feature = "tokio"))]
+impl Message {
+    /// Returns the value of messages, that can carry file descriptors.
+    pub(crate) fn fd_value_mut(&mut self) -> Option<&mut SerializedValue> {
+        match self {
+            Self::CallFunction(msg) => Some(&mut msg.value),
+            Self::CallFunctionReply(msg) => match msg.result {
+                CallFunctionResult::Ok(ref mut value) | CallFunctionResult::Err(ref mut value) => {
+                    Some(value)
+                }
+                _ => None,
+            },
+            Self::EmitEvent(msg) => Some(&mut msg.value),
+            Self::EmitEventAcked(msg) => Some(&mut msg.value),
+            Self::EmitRetainedEvent(msg) => Some(&mut msg.value),
+            Self::EmitReliableEvent(msg) => Some(&mut msg.value),
+            Self::ReliableEventReceived(msg) => Some(&mut msg.value),
+            Self::SendItem(msg) => Some(&mut msg.value),
+            Self::ItemReceived(msg) => Some(&mut msg.value),
+            _ => None,
         }
     }
 }
@@ -0,0 +1,72 @@
+use super::message_ops::Sealed;
+use super::{Message, MessageKind, MessageOps};
+use crate::message_deserializer::{MessageDeserializeError, MessageWithoutValueDeserializer};
+use crate::message_serializer::{MessageSerializeError, MessageSerializer};
+use crate::serialized_value::SerializedValueSlice;
+use bytes::BytesMut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct AckEvent {
+    pub serial: u32,
+}
+
+impl MessageOps for AckEvent {
+    fn kind(&self) -> MessageKind {
+        MessageKind::AckEvent
+    }
+
+    fn serialize_message(self) -> Result<BytesMut, MessageSerializeError> {
+        let mut serializer = MessageSerializer::without_value(MessageKind::AckEvent);
+
+        serializer.put_varint_u32_le(self.serial);
+
+        serializer.finish()
+    }
+
+    fn deserialize_message(buf: BytesMut) -> Result<Self, MessageDeserializeError> {
+        let mut deserializer = MessageWithoutValueDeserializer::new(buf, MessageKind::AckEvent)?;
+
+        let serial = deserializer.try_get_varint_u32_le()?;
+
+        deserializer.finish()?;
+        Ok(Self { serial })
+    }
+
+    fn value(&self) -> Option<&SerializedValueSlice> {
+        None
+    }
+}
+
+impl Sealed for AckEvent {}
+
+impl From<AckEvent> for Message {
+    fn from(msg: AckEvent) -> Self {
+        Self::AckEvent(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::{assert_deserialize_eq, assert_serialize_eq};
+    use super::super::Message;
+    use super::AckEvent;
+
+    #[test]
+    fn ack_event() {
+        let serialized = [6, 0, 0, 0, 73, 1];
+
+        let msg = AckEvent { serial: 1 };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::AckEvent(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+}
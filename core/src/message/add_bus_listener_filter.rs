@@ -9,6 +9,11 @@ use bytes::BytesMut;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct AddBusListenerFilter {
     pub cookie: BusListenerCookie,
     pub filter: BusListenerFilter,
@@ -58,7 +63,7 @@ mod test {
     use super::super::Message;
     use super::AddBusListenerFilter;
     use crate::bus_listener::BusListenerFilter;
-    use crate::ids::{BusListenerCookie, ObjectUuid, ServiceUuid};
+    use crate::ids::{BusListenerCookie, LexicalId, ObjectUuid, ServiceUuid};
     use uuid::uuid;
 
     #[test]
@@ -188,4 +193,50 @@ mod test {
         assert_serialize_eq(&msg, serialized);
         assert_deserialize_eq(&msg, serialized);
     }
+
+    #[test]
+    fn any_object_specific_layout() {
+        let serialized = [
+            38, 0, 0, 0, 37, 0x89, 0xe6, 0x24, 0x38, 0x29, 0x91, 0x48, 0xf8, 0xae, 0x1d, 0x7a,
+            0xd9, 0xdd, 0xcd, 0x7e, 0x72, 6, 0xb7, 0xf4, 0x93, 0x0e, 0xa6, 0x73, 0x4b, 0x3d, 0x95,
+            0x45, 0x78, 0x87, 0xfa, 0x8b, 0xde, 0x3f,
+        ];
+
+        let msg = AddBusListenerFilter {
+            cookie: BusListenerCookie(uuid!("89e62438-2991-48f8-ae1d-7ad9ddcd7e72")),
+            filter: BusListenerFilter::any_object_layout(LexicalId(uuid!(
+                "b7f4930e-a673-4b3d-9545-7887fa8bde3f"
+            ))),
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::AddBusListenerFilter(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+
+    #[test]
+    fn specific_object_specific_layout() {
+        let serialized = [
+            54, 0, 0, 0, 37, 0x89, 0xe6, 0x24, 0x38, 0x29, 0x91, 0x48, 0xf8, 0xae, 0x1d, 0x7a,
+            0xd9, 0xdd, 0xcd, 0x7e, 0x72, 7, 0x8a, 0x88, 0xcf, 0xe6, 0x26, 0xae, 0x4c, 0x5a, 0x8f,
+            0x70, 0x5e, 0x11, 0xbe, 0x41, 0xd2, 0x5a, 0xb7, 0xf4, 0x93, 0x0e, 0xa6, 0x73, 0x4b,
+            0x3d, 0x95, 0x45, 0x78, 0x87, 0xfa, 0x8b, 0xde, 0x3f,
+        ];
+
+        let msg = AddBusListenerFilter {
+            cookie: BusListenerCookie(uuid!("89e62438-2991-48f8-ae1d-7ad9ddcd7e72")),
+            filter: BusListenerFilter::specific_object_layout(
+                ObjectUuid(uuid!("8a88cfe6-26ae-4c5a-8f70-5e11be41d25a")),
+                LexicalId(uuid!("b7f4930e-a673-4b3d-9545-7887fa8bde3f")),
+            ),
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::AddBusListenerFilter(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
 }
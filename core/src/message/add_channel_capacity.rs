@@ -8,6 +8,11 @@ use bytes::BytesMut;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct AddChannelCapacity {
     pub cookie: ChannelCookie,
     pub capacity: u32,
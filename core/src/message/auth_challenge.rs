@@ -0,0 +1,77 @@
+use super::message_ops::Sealed;
+use super::{Message, MessageKind, MessageOps};
+use crate::error::SerializeError;
+use crate::message_deserializer::{MessageDeserializeError, MessageWithValueDeserializer};
+use crate::message_serializer::{MessageSerializeError, MessageSerializer};
+use crate::serialized_value::{SerializedValue, SerializedValueSlice};
+use crate::value_serializer::Serialize;
+use bytes::BytesMut;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct AuthChallenge {
+    pub value: SerializedValue,
+}
+
+impl AuthChallenge {
+    pub fn with_serialize_value<T: Serialize + ?Sized>(value: &T) -> Result<Self, SerializeError> {
+        let value = SerializedValue::serialize(value)?;
+        Ok(Self { value })
+    }
+}
+
+impl MessageOps for AuthChallenge {
+    fn kind(&self) -> MessageKind {
+        MessageKind::AuthChallenge
+    }
+
+    fn serialize_message(self) -> Result<BytesMut, MessageSerializeError> {
+        let serializer = MessageSerializer::with_value(self.value, MessageKind::AuthChallenge)?;
+        serializer.finish()
+    }
+
+    fn deserialize_message(buf: BytesMut) -> Result<Self, MessageDeserializeError> {
+        let deserializer = MessageWithValueDeserializer::new(buf, MessageKind::AuthChallenge)?;
+        let value = deserializer.finish()?;
+
+        Ok(Self { value })
+    }
+
+    fn value(&self) -> Option<&SerializedValueSlice> {
+        Some(&self.value)
+    }
+}
+
+impl Sealed for AuthChallenge {}
+
+impl From<AuthChallenge> for Message {
+    fn from(msg: AuthChallenge) -> Self {
+        Self::AuthChallenge(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::{assert_deserialize_eq_with_value, assert_serialize_eq};
+    use super::super::Message;
+    use super::AuthChallenge;
+
+    #[test]
+    fn auth_challenge() {
+        let serialized = [11, 0, 0, 0, 62, 2, 0, 0, 0, 3, 4];
+        let value = 4u8;
+
+        let msg = AuthChallenge::with_serialize_value(&value).unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::AuthChallenge(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+}
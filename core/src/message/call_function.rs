@@ -1,20 +1,29 @@
 use super::message_ops::Sealed;
 use super::{Message, MessageKind, MessageOps};
+use crate::call_priority::CallPriority;
 use crate::error::SerializeError;
 use crate::ids::ServiceCookie;
 use crate::message_deserializer::{MessageDeserializeError, MessageWithValueDeserializer};
 use crate::message_serializer::{MessageSerializeError, MessageSerializer};
 use crate::serialized_value::{SerializedValue, SerializedValueSlice};
+use crate::trace_context::TraceContext;
 use crate::value_serializer::Serialize;
 use bytes::BytesMut;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct CallFunction {
     pub serial: u32,
     pub service_cookie: ServiceCookie,
     pub function: u32,
     pub value: SerializedValue,
+    pub trace_context: Option<TraceContext>,
+    pub priority: CallPriority,
 }
 
 impl CallFunction {
@@ -30,6 +39,8 @@ impl CallFunction {
             service_cookie,
             function,
             value,
+            trace_context: None,
+            priority: CallPriority::Normal,
         })
     }
 }
@@ -46,6 +57,9 @@ impl MessageOps for CallFunction {
         serializer.put_uuid(self.service_cookie.0);
         serializer.put_varint_u32_le(self.function);
 
+        serializer.put_trace_context(self.trace_context);
+        serializer.put_call_priority(self.priority);
+
         serializer.finish()
     }
 
@@ -55,6 +69,7 @@ impl MessageOps for CallFunction {
         let serial = deserializer.try_get_varint_u32_le()?;
         let service_cookie = deserializer.try_get_uuid().map(ServiceCookie)?;
         let function = deserializer.try_get_varint_u32_le()?;
+        let (trace_context, priority) = deserializer.try_get_trace_context_and_priority()?;
         let value = deserializer.finish()?;
 
         Ok(Self {
@@ -62,6 +77,8 @@ impl MessageOps for CallFunction {
             service_cookie,
             function,
             value,
+            trace_context,
+            priority,
         })
     }
 
@@ -83,7 +100,9 @@ mod test {
     use super::super::test::{assert_deserialize_eq_with_value, assert_serialize_eq};
     use super::super::Message;
     use super::CallFunction;
+    use crate::call_priority::CallPriority;
     use crate::ids::ServiceCookie;
+    use crate::trace_context::TraceContext;
     use uuid::uuid;
 
     #[test]
@@ -108,4 +127,89 @@ mod test {
         assert_serialize_eq(&msg, serialized);
         assert_deserialize_eq_with_value(&msg, serialized, &value);
     }
+
+    #[test]
+    fn call_function_with_trace_context() {
+        let serialized = [
+            54, 0, 0, 0, 11, 2, 0, 0, 0, 3, 4, 1, 0x02, 0x6c, 0x31, 0x42, 0x53, 0x0b, 0x4d, 0x65,
+            0x85, 0x0d, 0xa2, 0x97, 0xdc, 0xc2, 0xfe, 0xcb, 2, 0x4b, 0xf9, 0x2f, 0x35, 0x77, 0xb3,
+            0x4d, 0xa6, 0xa3, 0xce, 0x92, 0x9d, 0x0e, 0x0e, 0x47, 0x36, 0x00, 0xf0, 0x67, 0xaa,
+            0x0b, 0xa9, 0x02, 0xb7, 1,
+        ];
+        let value = 4u8;
+
+        let mut msg = CallFunction::with_serialize_value(
+            1,
+            ServiceCookie(uuid!("026c3142-530b-4d65-850d-a297dcc2fecb")),
+            2,
+            &value,
+        )
+        .unwrap();
+        msg.trace_context = Some(TraceContext::new(
+            0x4bf92f3577b34da6a3ce929d0e0e4736,
+            0x00f067aa0ba902b7,
+            true,
+        ));
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::CallFunction(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn call_function_with_priority() {
+        let serialized = [
+            30, 0, 0, 0, 11, 2, 0, 0, 0, 3, 4, 1, 0x02, 0x6c, 0x31, 0x42, 0x53, 0x0b, 0x4d, 0x65,
+            0x85, 0x0d, 0xa2, 0x97, 0xdc, 0xc2, 0xfe, 0xcb, 2, 2,
+        ];
+        let value = 4u8;
+
+        let mut msg = CallFunction::with_serialize_value(
+            1,
+            ServiceCookie(uuid!("026c3142-530b-4d65-850d-a297dcc2fecb")),
+            2,
+            &value,
+        )
+        .unwrap();
+        msg.priority = CallPriority::High;
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::CallFunction(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn call_function_with_trace_context_and_priority() {
+        let serialized = [
+            55, 0, 0, 0, 11, 2, 0, 0, 0, 3, 4, 1, 0x02, 0x6c, 0x31, 0x42, 0x53, 0x0b, 0x4d, 0x65,
+            0x85, 0x0d, 0xa2, 0x97, 0xdc, 0xc2, 0xfe, 0xcb, 2, 0x4b, 0xf9, 0x2f, 0x35, 0x77, 0xb3,
+            0x4d, 0xa6, 0xa3, 0xce, 0x92, 0x9d, 0x0e, 0x0e, 0x47, 0x36, 0x00, 0xf0, 0x67, 0xaa,
+            0x0b, 0xa9, 0x02, 0xb7, 1, 0,
+        ];
+        let value = 4u8;
+
+        let mut msg = CallFunction::with_serialize_value(
+            1,
+            ServiceCookie(uuid!("026c3142-530b-4d65-850d-a297dcc2fecb")),
+            2,
+            &value,
+        )
+        .unwrap();
+        msg.trace_context = Some(TraceContext::new(
+            0x4bf92f3577b34da6a3ce929d0e0e4736,
+            0x00f067aa0ba902b7,
+            true,
+        ));
+        msg.priority = CallPriority::Low;
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::CallFunction(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
 }
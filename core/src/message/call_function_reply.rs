@@ -17,10 +17,16 @@ enum CallFunctionReplyKind {
     InvalidService = 3,
     InvalidFunction = 4,
     InvalidArgs = 5,
+    Overloaded = 6,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum CallFunctionResult {
     Ok(SerializedValue),
     Err(SerializedValue),
@@ -28,6 +34,7 @@ pub enum CallFunctionResult {
     InvalidService,
     InvalidFunction,
     InvalidArgs,
+    Overloaded,
 }
 
 impl CallFunctionResult {
@@ -46,6 +53,11 @@ impl CallFunctionResult {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct CallFunctionReply {
     pub serial: u32,
     pub result: CallFunctionResult,
@@ -123,6 +135,14 @@ impl MessageOps for CallFunctionReply {
                 serializer.put_discriminant_u8(CallFunctionReplyKind::InvalidArgs);
                 serializer
             }
+
+            CallFunctionResult::Overloaded => {
+                let mut serializer =
+                    MessageSerializer::with_none_value(MessageKind::CallFunctionReply);
+                serializer.put_varint_u32_le(self.serial);
+                serializer.put_discriminant_u8(CallFunctionReplyKind::Overloaded);
+                serializer
+            }
         };
 
         serializer.finish()
@@ -182,6 +202,14 @@ impl MessageOps for CallFunctionReply {
                     result: CallFunctionResult::InvalidArgs,
                 })
             }
+
+            CallFunctionReplyKind::Overloaded => {
+                deserializer.finish_discard_value()?;
+                Ok(Self {
+                    serial,
+                    result: CallFunctionResult::Overloaded,
+                })
+            }
         }
     }
 
@@ -192,7 +220,8 @@ impl MessageOps for CallFunctionReply {
             CallFunctionResult::Aborted
             | CallFunctionResult::InvalidService
             | CallFunctionResult::InvalidFunction
-            | CallFunctionResult::InvalidArgs => None,
+            | CallFunctionResult::InvalidArgs
+            | CallFunctionResult::Overloaded => None,
         }
     }
 }
@@ -304,4 +333,20 @@ mod test {
         assert_serialize_eq(&msg, serialized);
         assert_deserialize_eq(&msg, serialized);
     }
+
+    #[test]
+    fn overloaded() {
+        let serialized = [12, 0, 0, 0, 12, 1, 0, 0, 0, 0, 1, 6];
+
+        let msg = CallFunctionReply {
+            serial: 1,
+            result: CallFunctionResult::Overloaded,
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::CallFunctionReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
 }
@@ -1,6 +1,6 @@
 use super::message_ops::Sealed;
 use super::{Message, MessageKind, MessageOps};
-use crate::channel_end::{ChannelEnd, ChannelEndWithCapacity};
+use crate::channel_end::ChannelEndWithCapacity;
 use crate::ids::ChannelCookie;
 use crate::message_deserializer::{MessageDeserializeError, MessageWithoutValueDeserializer};
 use crate::message_serializer::{MessageSerializeError, MessageSerializer};
@@ -9,6 +9,11 @@ use bytes::BytesMut;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct ClaimChannelEnd {
     pub serial: u32,
     pub cookie: ChannelCookie,
@@ -26,13 +31,7 @@ impl MessageOps for ClaimChannelEnd {
         serializer.put_varint_u32_le(self.serial);
         serializer.put_uuid(self.cookie.0);
 
-        match self.end {
-            ChannelEndWithCapacity::Sender => serializer.put_discriminant_u8(ChannelEnd::Sender),
-            ChannelEndWithCapacity::Receiver(capacity) => {
-                serializer.put_discriminant_u8(ChannelEnd::Receiver);
-                serializer.put_varint_u32_le(capacity);
-            }
-        }
+        serializer.put_channel_end_with_capacity(self.end);
 
         serializer.finish()
     }
@@ -44,13 +43,7 @@ impl MessageOps for ClaimChannelEnd {
         let serial = deserializer.try_get_varint_u32_le()?;
         let cookie = deserializer.try_get_uuid().map(ChannelCookie)?;
 
-        let end = match deserializer.try_get_discriminant_u8()? {
-            ChannelEnd::Sender => ChannelEndWithCapacity::Sender,
-            ChannelEnd::Receiver => {
-                let capacity = deserializer.try_get_varint_u32_le()?;
-                ChannelEndWithCapacity::Receiver(capacity)
-            }
-        };
+        let end = deserializer.try_get_channel_end_with_capacity()?;
 
         deserializer.finish()?;
 
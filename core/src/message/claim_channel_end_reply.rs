@@ -13,19 +13,31 @@ enum ClaimChannelEndReplyKind {
     ReceiverClaimed = 1,
     InvalidChannel = 2,
     AlreadyClaimed = 3,
+    SenderClaimedBytes = 4,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum ClaimChannelEndResult {
     SenderClaimed(u32),
     ReceiverClaimed,
     InvalidChannel,
     AlreadyClaimed,
+    SenderClaimedBytes(u32),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct ClaimChannelEndReply {
     pub serial: u32,
     pub result: ClaimChannelEndResult,
@@ -55,6 +67,10 @@ impl MessageOps for ClaimChannelEndReply {
             ClaimChannelEndResult::AlreadyClaimed => {
                 serializer.put_discriminant_u8(ClaimChannelEndReplyKind::AlreadyClaimed)
             }
+            ClaimChannelEndResult::SenderClaimedBytes(capacity) => {
+                serializer.put_discriminant_u8(ClaimChannelEndReplyKind::SenderClaimedBytes);
+                serializer.put_varint_u32_le(capacity);
+            }
         }
 
         serializer.finish()
@@ -74,6 +90,10 @@ impl MessageOps for ClaimChannelEndReply {
             ClaimChannelEndReplyKind::ReceiverClaimed => ClaimChannelEndResult::ReceiverClaimed,
             ClaimChannelEndReplyKind::InvalidChannel => ClaimChannelEndResult::InvalidChannel,
             ClaimChannelEndReplyKind::AlreadyClaimed => ClaimChannelEndResult::AlreadyClaimed,
+            ClaimChannelEndReplyKind::SenderClaimedBytes => {
+                let capacity = deserializer.try_get_varint_u32_le()?;
+                ClaimChannelEndResult::SenderClaimedBytes(capacity)
+            }
         };
 
         deserializer.finish()?;
@@ -162,4 +182,20 @@ mod test {
         assert_serialize_eq(&msg, serialized);
         assert_deserialize_eq(&msg, serialized);
     }
+
+    #[test]
+    fn sender_claimed_bytes() {
+        let serialized = [8, 0, 0, 0, 25, 1, 4, 2];
+
+        let msg = ClaimChannelEndReply {
+            serial: 1,
+            result: ClaimChannelEndResult::SenderClaimedBytes(2),
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::ClaimChannelEndReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
 }
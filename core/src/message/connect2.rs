@@ -1,18 +1,37 @@
 use super::message_ops::Sealed;
 use super::{Message, MessageKind, MessageOps};
+use crate::compression::Compression;
 use crate::error::{DeserializeError, SerializeError};
 use crate::message_deserializer::{MessageDeserializeError, MessageWithValueDeserializer};
 use crate::message_serializer::{MessageSerializeError, MessageSerializer};
 use crate::serialized_value::{SerializedValue, SerializedValueSlice};
 use crate::value_deserializer::{Deserialize, Deserializer};
 use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+use crate::wire_format::WireFormat;
 use bytes::BytesMut;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
+use std::collections::HashMap;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct ConnectData {
     pub user: Option<SerializedValue>,
+    pub auth: Option<SerializedValue>,
+    pub compression: Vec<Compression>,
+    pub fd_passing: bool,
+    pub wire_formats: Vec<WireFormat>,
+    pub max_message_size: Option<u32>,
+    pub name: Option<String>,
+    pub metadata: HashMap<String, String>,
+    pub session: Option<Uuid>,
+    pub namespace: Option<String>,
+    pub visible_namespaces: Vec<String>,
 }
 
 impl ConnectData {
@@ -31,18 +50,96 @@ impl ConnectData {
     pub fn deserialize_user<T: Deserialize>(&self) -> Option<Result<T, DeserializeError>> {
         self.user.as_deref().map(SerializedValueSlice::deserialize)
     }
+
+    pub fn serialize_auth<T: Serialize + ?Sized>(
+        &mut self,
+        auth: &T,
+    ) -> Result<&mut Self, SerializeError> {
+        self.auth = SerializedValue::serialize(auth).map(Some)?;
+        Ok(self)
+    }
+
+    pub fn deserialize_auth<T: Deserialize>(&self) -> Option<Result<T, DeserializeError>> {
+        self.auth.as_deref().map(SerializedValueSlice::deserialize)
+    }
 }
 
 #[derive(IntoPrimitive, TryFromPrimitive)]
 #[repr(u32)]
 enum ConnectDataField {
     User = 0,
+    Auth = 1,
+    Compression = 2,
+    FdPassing = 3,
+    WireFormats = 4,
+    MaxMessageSize = 5,
+    Name = 6,
+    Metadata = 7,
+    Session = 8,
+    Namespace = 9,
+    VisibleNamespaces = 10,
 }
 
 impl Serialize for ConnectData {
     fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
-        let mut serializer = serializer.serialize_struct(1)?;
+        let num_fields = 1
+            + self.auth.is_some() as usize
+            + !self.compression.is_empty() as usize
+            + self.fd_passing as usize
+            + !self.wire_formats.is_empty() as usize
+            + self.max_message_size.is_some() as usize
+            + self.name.is_some() as usize
+            + !self.metadata.is_empty() as usize
+            + self.session.is_some() as usize
+            + self.namespace.is_some() as usize
+            + !self.visible_namespaces.is_empty() as usize;
+        let mut serializer = serializer.serialize_struct(num_fields)?;
+
         serializer.serialize_field(ConnectDataField::User, &self.user)?;
+
+        if self.auth.is_some() {
+            serializer.serialize_field(ConnectDataField::Auth, &self.auth)?;
+        }
+
+        if !self.compression.is_empty() {
+            serializer.serialize_field(ConnectDataField::Compression, &self.compression)?;
+        }
+
+        if self.fd_passing {
+            serializer.serialize_field(ConnectDataField::FdPassing, &self.fd_passing)?;
+        }
+
+        if !self.wire_formats.is_empty() {
+            serializer.serialize_field(ConnectDataField::WireFormats, &self.wire_formats)?;
+        }
+
+        if self.max_message_size.is_some() {
+            serializer.serialize_field(ConnectDataField::MaxMessageSize, &self.max_message_size)?;
+        }
+
+        if self.name.is_some() {
+            serializer.serialize_field(ConnectDataField::Name, &self.name)?;
+        }
+
+        if !self.metadata.is_empty() {
+            serializer.serialize_field(ConnectDataField::Metadata, &self.metadata)?;
+        }
+
+        if self.session.is_some() {
+            serializer.serialize_field(ConnectDataField::Session, &self.session)?;
+        }
+
+        if self.namespace.is_some() {
+            serializer.serialize_field(ConnectDataField::Namespace, &self.namespace)?;
+        }
+
+        if !self.visible_namespaces.is_empty() {
+            serializer.serialize_field(
+                ConnectDataField::VisibleNamespaces,
+                &self.visible_namespaces,
+            )?;
+        }
+
         serializer.finish()
     }
 }
@@ -52,6 +149,16 @@ impl Deserialize for ConnectData {
         let mut deserializer = deserializer.deserialize_struct()?;
 
         let mut user = None;
+        let mut auth = None;
+        let mut compression = Vec::new();
+        let mut fd_passing = false;
+        let mut wire_formats = Vec::new();
+        let mut max_message_size = None;
+        let mut name = None;
+        let mut metadata = HashMap::new();
+        let mut session = None;
+        let mut namespace = None;
+        let mut visible_namespaces = Vec::new();
 
         while deserializer.has_more_fields() {
             let deserializer = deserializer.deserialize_field()?;
@@ -63,10 +170,56 @@ impl Deserialize for ConnectData {
 
             match field {
                 ConnectDataField::User => user = deserializer.deserialize()?,
+                ConnectDataField::Auth => auth = deserializer.deserialize()?,
+
+                // Unknown algorithms are ignored, so that newer clients can offer them.
+                ConnectDataField::Compression => {
+                    compression = deserializer
+                        .deserialize::<Vec<u8>>()?
+                        .into_iter()
+                        .filter_map(|algorithm| algorithm.try_into().ok())
+                        .collect();
+                }
+
+                ConnectDataField::FdPassing => fd_passing = deserializer.deserialize()?,
+
+                // Unknown formats are ignored as well.
+                ConnectDataField::WireFormats => {
+                    wire_formats = deserializer
+                        .deserialize::<Vec<u8>>()?
+                        .into_iter()
+                        .filter_map(|format| format.try_into().ok())
+                        .collect();
+                }
+
+                ConnectDataField::MaxMessageSize => {
+                    max_message_size = deserializer.deserialize()?
+                }
+
+                ConnectDataField::Name => name = deserializer.deserialize()?,
+                ConnectDataField::Metadata => metadata = deserializer.deserialize()?,
+                ConnectDataField::Session => session = deserializer.deserialize()?,
+                ConnectDataField::Namespace => namespace = deserializer.deserialize()?,
+
+                ConnectDataField::VisibleNamespaces => {
+                    visible_namespaces = deserializer.deserialize()?
+                }
             }
         }
 
-        deserializer.finish(Self { user })
+        deserializer.finish(Self {
+            user,
+            auth,
+            compression,
+            fd_passing,
+            wire_formats,
+            max_message_size,
+            name,
+            metadata,
+            session,
+            namespace,
+            visible_namespaces,
+        })
     }
 }
 
@@ -83,6 +236,11 @@ impl AsSerializeArg for ConnectData {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct Connect2 {
     pub major_version: u32,
     pub minor_version: u32,
@@ -154,7 +312,8 @@ impl From<Connect2> for Message {
 mod test {
     use super::super::test::{assert_deserialize_eq_with_value, assert_serialize_eq};
     use super::super::Message;
-    use super::{Connect2, ConnectData};
+    use super::{Compression, Connect2, ConnectData, WireFormat};
+    use uuid::uuid;
 
     #[test]
     fn connect() {
@@ -169,4 +328,122 @@ mod test {
         assert_serialize_eq(&msg, serialized);
         assert_deserialize_eq_with_value(&msg, serialized, &value);
     }
+
+    #[test]
+    fn connect_with_auth() {
+        let serialized = [19, 0, 0, 0, 46, 8, 0, 0, 0, 39, 2, 0, 0, 1, 1, 3, 4, 1, 2];
+        let mut value = ConnectData::new();
+        value.serialize_auth(&4u8).unwrap();
+
+        let msg = Connect2::with_serialize_data(1, 2, &value).unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::Connect2(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn connect_with_compression() {
+        let serialized = [
+            20, 0, 0, 0, 46, 9, 0, 0, 0, 39, 2, 0, 0, 2, 17, 1, 3, 0, 1, 2,
+        ];
+        let mut value = ConnectData::new();
+        value.compression.push(Compression::Lz4);
+
+        let msg = Connect2::with_serialize_data(1, 2, &value).unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::Connect2(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn connect_with_fd_passing() {
+        let serialized = [18, 0, 0, 0, 46, 7, 0, 0, 0, 39, 2, 0, 0, 3, 2, 1, 1, 2];
+        let mut value = ConnectData::new();
+        value.fd_passing = true;
+
+        let msg = Connect2::with_serialize_data(1, 2, &value).unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::Connect2(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn connect_with_wire_formats() {
+        let serialized = [
+            20, 0, 0, 0, 46, 9, 0, 0, 0, 39, 2, 0, 0, 4, 17, 1, 3, 0, 1, 2,
+        ];
+        let mut value = ConnectData::new();
+        value.wire_formats.push(WireFormat::Json);
+
+        let msg = Connect2::with_serialize_data(1, 2, &value).unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::Connect2(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn connect_with_max_message_size() {
+        let serialized = [
+            21, 0, 0, 0, 46, 10, 0, 0, 0, 39, 2, 0, 0, 5, 1, 7, 253, 0, 4, 1, 2,
+        ];
+        let mut value = ConnectData::new();
+        value.max_message_size = Some(1024);
+
+        let msg = Connect2::with_serialize_data(1, 2, &value).unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::Connect2(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn connect_with_session() {
+        let serialized = [
+            34, 0, 0, 0, 46, 23, 0, 0, 0, 39, 2, 0, 0, 8, 1, 14, 2, 108, 49, 66, 83, 11, 77, 101,
+            133, 13, 162, 151, 220, 194, 254, 203, 1, 2,
+        ];
+        let mut value = ConnectData::new();
+        value.session = Some(uuid!("026c3142-530b-4d65-850d-a297dcc2fecb"));
+
+        let msg = Connect2::with_serialize_data(1, 2, &value).unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::Connect2(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn connect_with_namespace() {
+        let serialized = [
+            31, 0, 0, 0, 46, 20, 0, 0, 0, 39, 3, 0, 0, 9, 1, 13, 3, 102, 111, 111, 10, 17, 1, 13,
+            4, 98, 97, 114, 42, 1, 2,
+        ];
+        let mut value = ConnectData::new();
+        value.namespace = Some("foo".to_owned());
+        value.visible_namespaces = vec!["bar*".to_owned()];
+
+        let msg = Connect2::with_serialize_data(1, 2, &value).unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::Connect2(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
 }
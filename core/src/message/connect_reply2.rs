@@ -1,18 +1,31 @@
 use super::message_ops::Sealed;
 use super::{Message, MessageKind, MessageOps};
+use crate::auth_rejection::AuthRejection;
+use crate::compression::Compression;
 use crate::error::{DeserializeError, SerializeError};
 use crate::message_deserializer::{MessageDeserializeError, MessageWithValueDeserializer};
 use crate::message_serializer::{MessageSerializeError, MessageSerializer};
 use crate::serialized_value::{SerializedValue, SerializedValueSlice};
 use crate::value_deserializer::{Deserialize, Deserializer};
 use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+use crate::wire_format::WireFormat;
 use bytes::BytesMut;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct ConnectReplyData {
     pub user: Option<SerializedValue>,
+    pub auth_rejection: Option<AuthRejection>,
+    pub compression: Option<Compression>,
+    pub fd_passing: bool,
+    pub wire_format: Option<WireFormat>,
+    pub max_message_size: Option<u32>,
 }
 
 impl ConnectReplyData {
@@ -37,12 +50,49 @@ impl ConnectReplyData {
 #[repr(u32)]
 enum ConnectReplyDataField {
     User = 0,
+    AuthRejection = 1,
+    Compression = 2,
+    FdPassing = 3,
+    WireFormat = 4,
+    MaxMessageSize = 5,
 }
 
 impl Serialize for ConnectReplyData {
     fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
-        let mut serializer = serializer.serialize_struct(1)?;
+        let num_fields = 1
+            + self.auth_rejection.is_some() as usize
+            + self.compression.is_some() as usize
+            + self.fd_passing as usize
+            + self.wire_format.is_some() as usize
+            + self.max_message_size.is_some() as usize;
+        let mut serializer = serializer.serialize_struct(num_fields)?;
+
         serializer.serialize_field(ConnectReplyDataField::User, &self.user)?;
+
+        if self.auth_rejection.is_some() {
+            serializer
+                .serialize_field(ConnectReplyDataField::AuthRejection, &self.auth_rejection)?;
+        }
+
+        if self.compression.is_some() {
+            serializer.serialize_field(ConnectReplyDataField::Compression, &self.compression)?;
+        }
+
+        if self.fd_passing {
+            serializer.serialize_field(ConnectReplyDataField::FdPassing, &self.fd_passing)?;
+        }
+
+        if self.wire_format.is_some() {
+            serializer.serialize_field(ConnectReplyDataField::WireFormat, &self.wire_format)?;
+        }
+
+        if self.max_message_size.is_some() {
+            serializer.serialize_field(
+                ConnectReplyDataField::MaxMessageSize,
+                &self.max_message_size,
+            )?;
+        }
+
         serializer.finish()
     }
 }
@@ -52,6 +102,11 @@ impl Deserialize for ConnectReplyData {
         let mut deserializer = deserializer.deserialize_struct()?;
 
         let mut user = None;
+        let mut auth_rejection = None;
+        let mut compression = None;
+        let mut fd_passing = false;
+        let mut wire_format = None;
+        let mut max_message_size = None;
 
         while deserializer.has_more_fields() {
             let deserializer = deserializer.deserialize_field()?;
@@ -63,10 +118,26 @@ impl Deserialize for ConnectReplyData {
 
             match field {
                 ConnectReplyDataField::User => user = deserializer.deserialize()?,
+                ConnectReplyDataField::AuthRejection => {
+                    auth_rejection = deserializer.deserialize()?
+                }
+                ConnectReplyDataField::Compression => compression = deserializer.deserialize()?,
+                ConnectReplyDataField::FdPassing => fd_passing = deserializer.deserialize()?,
+                ConnectReplyDataField::WireFormat => wire_format = deserializer.deserialize()?,
+                ConnectReplyDataField::MaxMessageSize => {
+                    max_message_size = deserializer.deserialize()?
+                }
             }
         }
 
-        deserializer.finish(Self { user })
+        deserializer.finish(Self {
+            user,
+            auth_rejection,
+            compression,
+            fd_passing,
+            wire_format,
+            max_message_size,
+        })
     }
 }
 
@@ -91,6 +162,11 @@ enum ConnectReplyKind {
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum ConnectResult {
     Ok(u32),
     Rejected,
@@ -99,6 +175,11 @@ pub enum ConnectResult {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct ConnectReply2 {
     pub result: ConnectResult,
     pub value: SerializedValue,
@@ -221,6 +302,7 @@ mod test {
     use super::super::test::{assert_deserialize_eq_with_value, assert_serialize_eq};
     use super::super::Message;
     use super::{ConnectReply2, ConnectReplyData};
+    use crate::AuthRejection;
 
     #[test]
     fn ok() {
@@ -263,4 +345,36 @@ mod test {
         assert_serialize_eq(&msg, serialized);
         assert_deserialize_eq_with_value(&msg, serialized, &value);
     }
+
+    #[test]
+    fn rejected_with_auth_rejection() {
+        let serialized = [18, 0, 0, 0, 47, 8, 0, 0, 0, 39, 2, 0, 0, 1, 1, 7, 4, 1];
+        let mut value = ConnectReplyData::new();
+        value.auth_rejection = Some(AuthRejection::Timeout);
+
+        let msg = ConnectReply2::rejected_with_serialize_data(&value).unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::ConnectReply2(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn ok_with_max_message_size() {
+        let serialized = [
+            21, 0, 0, 0, 47, 10, 0, 0, 0, 39, 2, 0, 0, 5, 1, 7, 253, 0, 4, 0, 2,
+        ];
+        let mut value = ConnectReplyData::new();
+        value.max_message_size = Some(1024);
+
+        let msg = ConnectReply2::ok_with_serialize_data(2, &value).unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::ConnectReply2(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
 }
@@ -1,6 +1,6 @@
 use super::message_ops::Sealed;
 use super::{Message, MessageKind, MessageOps};
-use crate::channel_end::{ChannelEnd, ChannelEndWithCapacity};
+use crate::channel_end::ChannelEndWithCapacity;
 use crate::message_deserializer::{MessageDeserializeError, MessageWithoutValueDeserializer};
 use crate::message_serializer::{MessageSerializeError, MessageSerializer};
 use crate::serialized_value::SerializedValueSlice;
@@ -8,6 +8,11 @@ use bytes::BytesMut;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct CreateChannel {
     pub serial: u32,
     pub end: ChannelEndWithCapacity,
@@ -23,13 +28,7 @@ impl MessageOps for CreateChannel {
 
         serializer.put_varint_u32_le(self.serial);
 
-        match self.end {
-            ChannelEndWithCapacity::Sender => serializer.put_discriminant_u8(ChannelEnd::Sender),
-            ChannelEndWithCapacity::Receiver(capacity) => {
-                serializer.put_discriminant_u8(ChannelEnd::Receiver);
-                serializer.put_varint_u32_le(capacity);
-            }
-        }
+        serializer.put_channel_end_with_capacity(self.end);
 
         serializer.finish()
     }
@@ -40,13 +39,7 @@ impl MessageOps for CreateChannel {
 
         let serial = deserializer.try_get_varint_u32_le()?;
 
-        let end = match deserializer.try_get_discriminant_u8()? {
-            ChannelEnd::Sender => ChannelEndWithCapacity::Sender,
-            ChannelEnd::Receiver => {
-                let capacity = deserializer.try_get_varint_u32_le()?;
-                ChannelEndWithCapacity::Receiver(capacity)
-            }
-        };
+        let end = deserializer.try_get_channel_end_with_capacity()?;
 
         deserializer.finish()?;
         Ok(Self { serial, end })
@@ -103,4 +96,36 @@ mod test {
         assert_serialize_eq(&msg, serialized);
         assert_deserialize_eq(&msg, serialized);
     }
+
+    #[test]
+    fn receiver_bytes() {
+        let serialized = [8, 0, 0, 0, 19, 1, 2, 16];
+
+        let msg = CreateChannel {
+            serial: 1,
+            end: ChannelEndWithCapacity::ReceiverBytes(16),
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::CreateChannel(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+
+    #[test]
+    fn broadcast_sender() {
+        let serialized = [7, 0, 0, 0, 19, 1, 3];
+
+        let msg = CreateChannel {
+            serial: 1,
+            end: ChannelEndWithCapacity::BroadcastSender,
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::CreateChannel(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
 }
@@ -10,6 +10,11 @@ use bytes::BytesMut;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct CreateService2 {
     pub serial: u32,
     pub object_cookie: ObjectCookie,
@@ -96,9 +101,10 @@ mod test {
     #[test]
     fn create_service2() {
         let serialized = [
-            51, 0, 0, 0, 52, 9, 0, 0, 0, 39, 3, 0, 7, 2, 1, 0, 2, 0, 1, 0xb7, 0xc3, 0xbe, 0x13,
-            0x53, 0x77, 0x46, 0x6e, 0xb4, 0xbf, 0x37, 0x38, 0x76, 0x52, 0x3d, 0x1b, 0xd3, 0xef,
-            0xd0, 0x0b, 0x7a, 0x7b, 0x4b, 0xf7, 0xbd, 0xd3, 0x3c, 0x66, 0x32, 0x47, 0x33, 0x47,
+            55, 0, 0, 0, 52, 13, 0, 0, 0, 39, 5, 0, 7, 2, 1, 0, 2, 0, 3, 0, 4, 0, 1, 0xb7, 0xc3,
+            0xbe, 0x13, 0x53, 0x77, 0x46, 0x6e, 0xb4, 0xbf, 0x37, 0x38, 0x76, 0x52, 0x3d, 0x1b,
+            0xd3, 0xef, 0xd0, 0x0b, 0x7a, 0x7b, 0x4b, 0xf7, 0xbd, 0xd3, 0x3c, 0x66, 0x32, 0x47,
+            0x33, 0x47,
         ];
 
         let msg = CreateService2::with_serialize_info(
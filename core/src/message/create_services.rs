@@ -0,0 +1,131 @@
+use super::message_ops::Sealed;
+use super::{Message, MessageKind, MessageOps};
+use crate::error::{DeserializeError, SerializeError};
+use crate::ids::{ObjectCookie, ServiceUuid};
+use crate::message_deserializer::{MessageDeserializeError, MessageWithValueDeserializer};
+use crate::message_serializer::{MessageSerializeError, MessageSerializer};
+use crate::serialized_value::{SerializedValue, SerializedValueSlice};
+use crate::service_info::ServiceInfo;
+use bytes::BytesMut;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct CreateServices {
+    pub serial: u32,
+    pub object_cookie: ObjectCookie,
+    pub value: SerializedValue,
+}
+
+impl CreateServices {
+    pub fn with_serialize_services(
+        serial: u32,
+        object_cookie: ObjectCookie,
+        services: &[(ServiceUuid, ServiceInfo)],
+    ) -> Result<Self, SerializeError> {
+        let value = SerializedValue::serialize(services)?;
+
+        Ok(Self {
+            serial,
+            object_cookie,
+            value,
+        })
+    }
+
+    pub fn deserialize_services(
+        &self,
+    ) -> Result<Vec<(ServiceUuid, ServiceInfo)>, DeserializeError> {
+        self.value.deserialize()
+    }
+}
+
+impl MessageOps for CreateServices {
+    fn kind(&self) -> MessageKind {
+        MessageKind::CreateServices
+    }
+
+    fn serialize_message(self) -> Result<BytesMut, MessageSerializeError> {
+        let mut serializer =
+            MessageSerializer::with_value(self.value, MessageKind::CreateServices)?;
+
+        serializer.put_varint_u32_le(self.serial);
+        serializer.put_uuid(self.object_cookie.0);
+
+        serializer.finish()
+    }
+
+    fn deserialize_message(buf: BytesMut) -> Result<Self, MessageDeserializeError> {
+        let mut deserializer = MessageWithValueDeserializer::new(buf, MessageKind::CreateServices)?;
+
+        let serial = deserializer.try_get_varint_u32_le()?;
+        let object_cookie = deserializer.try_get_uuid().map(ObjectCookie)?;
+        let value = deserializer.finish()?;
+
+        Ok(Self {
+            serial,
+            object_cookie,
+            value,
+        })
+    }
+
+    fn value(&self) -> Option<&SerializedValueSlice> {
+        Some(&self.value)
+    }
+}
+
+impl Sealed for CreateServices {}
+
+impl From<CreateServices> for Message {
+    fn from(msg: CreateServices) -> Self {
+        Self::CreateServices(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::{assert_deserialize_eq, assert_serialize_eq};
+    use super::super::Message;
+    use super::CreateServices;
+    use crate::ids::{ObjectCookie, ServiceUuid};
+    use crate::service_info::ServiceInfo;
+    use uuid::uuid;
+
+    #[test]
+    fn create_services() {
+        let serialized = [
+            96, 0, 0, 0, 65, 70, 0, 0, 0, 17, 2, 39, 2, 0, 14, 0xd3, 0xef, 0xd0, 0x0b, 0x7a, 0x7b,
+            0x4b, 0xf7, 0xbd, 0xd3, 0x3c, 0x66, 0x32, 0x47, 0x33, 0x47, 1, 39, 5, 0, 7, 2, 1, 0, 2,
+            0, 3, 0, 4, 0, 39, 2, 0, 14, 0x02, 0x6c, 0x31, 0x42, 0x53, 0x0b, 0x4d, 0x65, 0x85,
+            0x0d, 0xa2, 0x97, 0xdc, 0xc2, 0xfe, 0xcb, 1, 39, 5, 0, 7, 3, 1, 0, 2, 0, 3, 0, 4, 0, 1,
+            0xb7, 0xc3, 0xbe, 0x13, 0x53, 0x77, 0x46, 0x6e, 0xb4, 0xbf, 0x37, 0x38, 0x76, 0x52,
+            0x3d, 0x1b,
+        ];
+
+        let msg = CreateServices::with_serialize_services(
+            1,
+            ObjectCookie(uuid!("b7c3be13-5377-466e-b4bf-373876523d1b")),
+            &[
+                (
+                    ServiceUuid(uuid!("d3efd00b-7a7b-4bf7-bdd3-3c6632473347")),
+                    ServiceInfo::new(2),
+                ),
+                (
+                    ServiceUuid(uuid!("026c3142-530b-4d65-850d-a297dcc2fecb")),
+                    ServiceInfo::new(3),
+                ),
+            ],
+        )
+        .unwrap();
+
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::CreateServices(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+}
@@ -0,0 +1,203 @@
+use super::message_ops::Sealed;
+use super::{Message, MessageKind, MessageOps};
+use crate::ids::ServiceCookie;
+use crate::message_deserializer::{MessageDeserializeError, MessageWithoutValueDeserializer};
+use crate::message_serializer::{MessageSerializeError, MessageSerializer};
+use crate::serialized_value::SerializedValueSlice;
+use bytes::BytesMut;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+enum CreateServicesReplyKind {
+    Ok = 0,
+    DuplicateService = 1,
+    InvalidObject = 2,
+    ForeignObject = 3,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum CreateServicesResult {
+    Ok(Vec<ServiceCookie>),
+    DuplicateService,
+    InvalidObject,
+    ForeignObject,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct CreateServicesReply {
+    pub serial: u32,
+    pub result: CreateServicesResult,
+}
+
+impl MessageOps for CreateServicesReply {
+    fn kind(&self) -> MessageKind {
+        MessageKind::CreateServicesReply
+    }
+
+    fn serialize_message(self) -> Result<BytesMut, MessageSerializeError> {
+        let mut serializer = MessageSerializer::without_value(MessageKind::CreateServicesReply);
+
+        serializer.put_varint_u32_le(self.serial);
+
+        match self.result {
+            CreateServicesResult::Ok(cookies) => {
+                serializer.put_discriminant_u8(CreateServicesReplyKind::Ok);
+
+                let len = cookies
+                    .len()
+                    .try_into()
+                    .map_err(|_| MessageSerializeError::Overflow)?;
+                serializer.put_varint_u32_le(len);
+
+                for cookie in cookies {
+                    serializer.put_uuid(cookie.0);
+                }
+            }
+
+            CreateServicesResult::DuplicateService => {
+                serializer.put_discriminant_u8(CreateServicesReplyKind::DuplicateService);
+            }
+
+            CreateServicesResult::InvalidObject => {
+                serializer.put_discriminant_u8(CreateServicesReplyKind::InvalidObject);
+            }
+
+            CreateServicesResult::ForeignObject => {
+                serializer.put_discriminant_u8(CreateServicesReplyKind::ForeignObject);
+            }
+        }
+
+        serializer.finish()
+    }
+
+    fn deserialize_message(buf: BytesMut) -> Result<Self, MessageDeserializeError> {
+        let mut deserializer =
+            MessageWithoutValueDeserializer::new(buf, MessageKind::CreateServicesReply)?;
+
+        let serial = deserializer.try_get_varint_u32_le()?;
+
+        let result = match deserializer.try_get_discriminant_u8()? {
+            CreateServicesReplyKind::Ok => {
+                let len = deserializer.try_get_varint_u32_le()?;
+
+                let cookies = (0..len)
+                    .map(|_| deserializer.try_get_uuid().map(ServiceCookie))
+                    .collect::<Result<_, _>>()?;
+
+                CreateServicesResult::Ok(cookies)
+            }
+
+            CreateServicesReplyKind::DuplicateService => CreateServicesResult::DuplicateService,
+            CreateServicesReplyKind::InvalidObject => CreateServicesResult::InvalidObject,
+            CreateServicesReplyKind::ForeignObject => CreateServicesResult::ForeignObject,
+        };
+
+        deserializer.finish()?;
+        Ok(Self { serial, result })
+    }
+
+    fn value(&self) -> Option<&SerializedValueSlice> {
+        None
+    }
+}
+
+impl Sealed for CreateServicesReply {}
+
+impl From<CreateServicesReply> for Message {
+    fn from(msg: CreateServicesReply) -> Self {
+        Self::CreateServicesReply(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::{assert_deserialize_eq, assert_serialize_eq};
+    use super::super::Message;
+    use super::{CreateServicesReply, CreateServicesResult};
+    use crate::ids::ServiceCookie;
+    use uuid::uuid;
+
+    #[test]
+    fn ok() {
+        let serialized = [
+            40, 0, 0, 0, 66, 1, 0, 2, 0xb7, 0xc3, 0xbe, 0x13, 0x53, 0x77, 0x46, 0x6e, 0xb4, 0xbf,
+            0x37, 0x38, 0x76, 0x52, 0x3d, 0x1b, 0xd3, 0xef, 0xd0, 0x0b, 0x7a, 0x7b, 0x4b, 0xf7,
+            0xbd, 0xd3, 0x3c, 0x66, 0x32, 0x47, 0x33, 0x47,
+        ];
+
+        let msg = CreateServicesReply {
+            serial: 1,
+            result: CreateServicesResult::Ok(vec![
+                ServiceCookie(uuid!("b7c3be13-5377-466e-b4bf-373876523d1b")),
+                ServiceCookie(uuid!("d3efd00b-7a7b-4bf7-bdd3-3c6632473347")),
+            ]),
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::CreateServicesReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+
+    #[test]
+    fn duplicate_service() {
+        let serialized = [7, 0, 0, 0, 66, 1, 1];
+
+        let msg = CreateServicesReply {
+            serial: 1,
+            result: CreateServicesResult::DuplicateService,
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::CreateServicesReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+
+    #[test]
+    fn invalid_object() {
+        let serialized = [7, 0, 0, 0, 66, 1, 2];
+
+        let msg = CreateServicesReply {
+            serial: 1,
+            result: CreateServicesResult::InvalidObject,
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::CreateServicesReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+
+    #[test]
+    fn foreign_object() {
+        let serialized = [7, 0, 0, 0, 66, 1, 3];
+
+        let msg = CreateServicesReply {
+            serial: 1,
+            result: CreateServicesResult::ForeignObject,
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::CreateServicesReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+}
@@ -0,0 +1,103 @@
+use super::message_ops::Sealed;
+use super::{Message, MessageKind, MessageOps};
+use crate::ids::ServiceCookie;
+use crate::message_deserializer::{MessageDeserializeError, MessageWithoutValueDeserializer};
+use crate::message_serializer::{MessageSerializeError, MessageSerializer};
+use crate::serialized_value::SerializedValueSlice;
+use bytes::BytesMut;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct DestroyServices {
+    pub serial: u32,
+    pub cookies: Vec<ServiceCookie>,
+}
+
+impl MessageOps for DestroyServices {
+    fn kind(&self) -> MessageKind {
+        MessageKind::DestroyServices
+    }
+
+    fn serialize_message(self) -> Result<BytesMut, MessageSerializeError> {
+        let mut serializer = MessageSerializer::without_value(MessageKind::DestroyServices);
+
+        serializer.put_varint_u32_le(self.serial);
+
+        let len = self
+            .cookies
+            .len()
+            .try_into()
+            .map_err(|_| MessageSerializeError::Overflow)?;
+        serializer.put_varint_u32_le(len);
+
+        for cookie in self.cookies {
+            serializer.put_uuid(cookie.0);
+        }
+
+        serializer.finish()
+    }
+
+    fn deserialize_message(buf: BytesMut) -> Result<Self, MessageDeserializeError> {
+        let mut deserializer =
+            MessageWithoutValueDeserializer::new(buf, MessageKind::DestroyServices)?;
+
+        let serial = deserializer.try_get_varint_u32_le()?;
+        let len = deserializer.try_get_varint_u32_le()?;
+
+        let cookies = (0..len)
+            .map(|_| deserializer.try_get_uuid().map(ServiceCookie))
+            .collect::<Result<_, _>>()?;
+
+        deserializer.finish()?;
+        Ok(Self { serial, cookies })
+    }
+
+    fn value(&self) -> Option<&SerializedValueSlice> {
+        None
+    }
+}
+
+impl Sealed for DestroyServices {}
+
+impl From<DestroyServices> for Message {
+    fn from(msg: DestroyServices) -> Self {
+        Self::DestroyServices(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::{assert_deserialize_eq, assert_serialize_eq};
+    use super::super::Message;
+    use super::DestroyServices;
+    use crate::ids::ServiceCookie;
+    use uuid::uuid;
+
+    #[test]
+    fn destroy_services() {
+        let serialized = [
+            39, 0, 0, 0, 67, 1, 2, 0xb7, 0xc3, 0xbe, 0x13, 0x53, 0x77, 0x46, 0x6e, 0xb4, 0xbf,
+            0x37, 0x38, 0x76, 0x52, 0x3d, 0x1b, 0xd3, 0xef, 0xd0, 0x0b, 0x7a, 0x7b, 0x4b, 0xf7,
+            0xbd, 0xd3, 0x3c, 0x66, 0x32, 0x47, 0x33, 0x47,
+        ];
+
+        let msg = DestroyServices {
+            serial: 1,
+            cookies: vec![
+                ServiceCookie(uuid!("b7c3be13-5377-466e-b4bf-373876523d1b")),
+                ServiceCookie(uuid!("d3efd00b-7a7b-4bf7-bdd3-3c6632473347")),
+            ],
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::DestroyServices(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+}
@@ -0,0 +1,126 @@
+use super::message_ops::Sealed;
+use super::{Message, MessageKind, MessageOps};
+use crate::message_deserializer::{MessageDeserializeError, MessageWithoutValueDeserializer};
+use crate::message_serializer::{MessageSerializeError, MessageSerializer};
+use crate::serialized_value::SerializedValueSlice;
+use bytes::BytesMut;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[repr(u8)]
+pub enum DestroyServicesResult {
+    Ok = 0,
+    InvalidService = 1,
+    ForeignObject = 2,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct DestroyServicesReply {
+    pub serial: u32,
+    pub result: DestroyServicesResult,
+}
+
+impl MessageOps for DestroyServicesReply {
+    fn kind(&self) -> MessageKind {
+        MessageKind::DestroyServicesReply
+    }
+
+    fn serialize_message(self) -> Result<BytesMut, MessageSerializeError> {
+        let mut serializer = MessageSerializer::without_value(MessageKind::DestroyServicesReply);
+
+        serializer.put_varint_u32_le(self.serial);
+        serializer.put_discriminant_u8(self.result);
+
+        serializer.finish()
+    }
+
+    fn deserialize_message(buf: BytesMut) -> Result<Self, MessageDeserializeError> {
+        let mut deserializer =
+            MessageWithoutValueDeserializer::new(buf, MessageKind::DestroyServicesReply)?;
+
+        let serial = deserializer.try_get_varint_u32_le()?;
+        let result = deserializer.try_get_discriminant_u8()?;
+
+        deserializer.finish()?;
+        Ok(Self { serial, result })
+    }
+
+    fn value(&self) -> Option<&SerializedValueSlice> {
+        None
+    }
+}
+
+impl Sealed for DestroyServicesReply {}
+
+impl From<DestroyServicesReply> for Message {
+    fn from(msg: DestroyServicesReply) -> Self {
+        Self::DestroyServicesReply(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::{assert_deserialize_eq, assert_serialize_eq};
+    use super::super::Message;
+    use super::{DestroyServicesReply, DestroyServicesResult};
+
+    #[test]
+    fn ok() {
+        let serialized = [7, 0, 0, 0, 68, 1, 0];
+
+        let msg = DestroyServicesReply {
+            serial: 1,
+            result: DestroyServicesResult::Ok,
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::DestroyServicesReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+
+    #[test]
+    fn invalid_service() {
+        let serialized = [7, 0, 0, 0, 68, 1, 1];
+
+        let msg = DestroyServicesReply {
+            serial: 1,
+            result: DestroyServicesResult::InvalidService,
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::DestroyServicesReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+
+    #[test]
+    fn foreign_object() {
+        let serialized = [7, 0, 0, 0, 68, 1, 2];
+
+        let msg = DestroyServicesReply {
+            serial: 1,
+            result: DestroyServicesResult::ForeignObject,
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::DestroyServicesReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+}
@@ -5,15 +5,22 @@ use crate::ids::ServiceCookie;
 use crate::message_deserializer::{MessageDeserializeError, MessageWithValueDeserializer};
 use crate::message_serializer::{MessageSerializeError, MessageSerializer};
 use crate::serialized_value::{SerializedValue, SerializedValueSlice};
+use crate::trace_context::TraceContext;
 use crate::value_serializer::Serialize;
 use bytes::BytesMut;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct EmitEvent {
     pub service_cookie: ServiceCookie,
     pub event: u32,
     pub value: SerializedValue,
+    pub trace_context: Option<TraceContext>,
 }
 
 impl EmitEvent {
@@ -27,6 +34,7 @@ impl EmitEvent {
             service_cookie,
             event,
             value,
+            trace_context: None,
         })
     }
 }
@@ -42,6 +50,8 @@ impl MessageOps for EmitEvent {
         serializer.put_uuid(self.service_cookie.0);
         serializer.put_varint_u32_le(self.event);
 
+        serializer.put_trace_context(self.trace_context);
+
         serializer.finish()
     }
 
@@ -50,12 +60,14 @@ impl MessageOps for EmitEvent {
 
         let service_cookie = deserializer.try_get_uuid().map(ServiceCookie)?;
         let event = deserializer.try_get_varint_u32_le()?;
+        let trace_context = deserializer.try_get_trace_context()?;
         let value = deserializer.finish()?;
 
         Ok(Self {
             service_cookie,
             event,
             value,
+            trace_context,
         })
     }
 
@@ -78,6 +90,7 @@ mod test {
     use super::super::Message;
     use super::EmitEvent;
     use crate::ids::ServiceCookie;
+    use crate::trace_context::TraceContext;
     use uuid::uuid;
 
     #[test]
@@ -101,4 +114,33 @@ mod test {
         assert_serialize_eq(&msg, serialized);
         assert_deserialize_eq_with_value(&msg, serialized, &value);
     }
+
+    #[test]
+    fn emit_event_with_trace_context() {
+        let serialized = [
+            53, 0, 0, 0, 16, 2, 0, 0, 0, 3, 4, 0x02, 0x6c, 0x31, 0x42, 0x53, 0x0b, 0x4d, 0x65,
+            0x85, 0x0d, 0xa2, 0x97, 0xdc, 0xc2, 0xfe, 0xcb, 1, 0x4b, 0xf9, 0x2f, 0x35, 0x77, 0xb3,
+            0x4d, 0xa6, 0xa3, 0xce, 0x92, 0x9d, 0x0e, 0x0e, 0x47, 0x36, 0x00, 0xf0, 0x67, 0xaa,
+            0x0b, 0xa9, 0x02, 0xb7, 0,
+        ];
+        let value = 4u8;
+
+        let mut msg = EmitEvent::with_serialize_value(
+            ServiceCookie(uuid!("026c3142-530b-4d65-850d-a297dcc2fecb")),
+            1,
+            &value,
+        )
+        .unwrap();
+        msg.trace_context = Some(TraceContext::new(
+            0x4bf92f3577b34da6a3ce929d0e0e4736,
+            0x00f067aa0ba902b7,
+            false,
+        ));
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::EmitEvent(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
 }
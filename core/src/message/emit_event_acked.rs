@@ -0,0 +1,124 @@
+use super::message_ops::Sealed;
+use super::{Message, MessageKind, MessageOps};
+use crate::error::SerializeError;
+use crate::ids::ServiceCookie;
+use crate::message_deserializer::{MessageDeserializeError, MessageWithValueDeserializer};
+use crate::message_serializer::{MessageSerializeError, MessageSerializer};
+use crate::serialized_value::{SerializedValue, SerializedValueSlice};
+use crate::trace_context::TraceContext;
+use crate::value_serializer::Serialize;
+use bytes::BytesMut;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct EmitEventAcked {
+    pub serial: u32,
+    pub service_cookie: ServiceCookie,
+    pub event: u32,
+    pub value: SerializedValue,
+    pub trace_context: Option<TraceContext>,
+}
+
+impl EmitEventAcked {
+    pub fn with_serialize_value<T: Serialize + ?Sized>(
+        serial: u32,
+        service_cookie: ServiceCookie,
+        event: u32,
+        value: &T,
+    ) -> Result<Self, SerializeError> {
+        let value = SerializedValue::serialize(value)?;
+        Ok(Self {
+            serial,
+            service_cookie,
+            event,
+            value,
+            trace_context: None,
+        })
+    }
+}
+
+impl MessageOps for EmitEventAcked {
+    fn kind(&self) -> MessageKind {
+        MessageKind::EmitEventAcked
+    }
+
+    fn serialize_message(self) -> Result<BytesMut, MessageSerializeError> {
+        let mut serializer =
+            MessageSerializer::with_value(self.value, MessageKind::EmitEventAcked)?;
+
+        serializer.put_varint_u32_le(self.serial);
+        serializer.put_uuid(self.service_cookie.0);
+        serializer.put_varint_u32_le(self.event);
+
+        serializer.put_trace_context(self.trace_context);
+
+        serializer.finish()
+    }
+
+    fn deserialize_message(buf: BytesMut) -> Result<Self, MessageDeserializeError> {
+        let mut deserializer = MessageWithValueDeserializer::new(buf, MessageKind::EmitEventAcked)?;
+
+        let serial = deserializer.try_get_varint_u32_le()?;
+        let service_cookie = deserializer.try_get_uuid().map(ServiceCookie)?;
+        let event = deserializer.try_get_varint_u32_le()?;
+        let trace_context = deserializer.try_get_trace_context()?;
+        let value = deserializer.finish()?;
+
+        Ok(Self {
+            serial,
+            service_cookie,
+            event,
+            value,
+            trace_context,
+        })
+    }
+
+    fn value(&self) -> Option<&SerializedValueSlice> {
+        Some(&self.value)
+    }
+}
+
+impl Sealed for EmitEventAcked {}
+
+impl From<EmitEventAcked> for Message {
+    fn from(msg: EmitEventAcked) -> Self {
+        Self::EmitEventAcked(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::{assert_deserialize_eq_with_value, assert_serialize_eq};
+    use super::super::Message;
+    use super::EmitEventAcked;
+    use crate::ids::ServiceCookie;
+    use uuid::uuid;
+
+    #[test]
+    fn emit_event_acked() {
+        let serialized = [
+            29, 0, 0, 0, 69, 2, 0, 0, 0, 3, 4, 2, 0x02, 0x6c, 0x31, 0x42, 0x53, 0x0b, 0x4d, 0x65,
+            0x85, 0x0d, 0xa2, 0x97, 0xdc, 0xc2, 0xfe, 0xcb, 1,
+        ];
+        let value = 4u8;
+
+        let msg = EmitEventAcked::with_serialize_value(
+            2,
+            ServiceCookie(uuid!("026c3142-530b-4d65-850d-a297dcc2fecb")),
+            1,
+            &value,
+        )
+        .unwrap();
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::EmitEventAcked(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+}
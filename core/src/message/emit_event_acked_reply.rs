@@ -0,0 +1,79 @@
+use super::message_ops::Sealed;
+use super::{Message, MessageKind, MessageOps};
+use crate::message_deserializer::{MessageDeserializeError, MessageWithoutValueDeserializer};
+use crate::message_serializer::{MessageSerializeError, MessageSerializer};
+use crate::serialized_value::SerializedValueSlice;
+use bytes::BytesMut;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct EmitEventAckedReply {
+    pub serial: u32,
+    pub delivered: u32,
+}
+
+impl MessageOps for EmitEventAckedReply {
+    fn kind(&self) -> MessageKind {
+        MessageKind::EmitEventAckedReply
+    }
+
+    fn serialize_message(self) -> Result<BytesMut, MessageSerializeError> {
+        let mut serializer = MessageSerializer::without_value(MessageKind::EmitEventAckedReply);
+
+        serializer.put_varint_u32_le(self.serial);
+        serializer.put_varint_u32_le(self.delivered);
+
+        serializer.finish()
+    }
+
+    fn deserialize_message(buf: BytesMut) -> Result<Self, MessageDeserializeError> {
+        let mut deserializer =
+            MessageWithoutValueDeserializer::new(buf, MessageKind::EmitEventAckedReply)?;
+
+        let serial = deserializer.try_get_varint_u32_le()?;
+        let delivered = deserializer.try_get_varint_u32_le()?;
+
+        deserializer.finish()?;
+        Ok(Self { serial, delivered })
+    }
+
+    fn value(&self) -> Option<&SerializedValueSlice> {
+        None
+    }
+}
+
+impl Sealed for EmitEventAckedReply {}
+
+impl From<EmitEventAckedReply> for Message {
+    fn from(msg: EmitEventAckedReply) -> Self {
+        Self::EmitEventAckedReply(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::{assert_deserialize_eq, assert_serialize_eq};
+    use super::super::Message;
+    use super::EmitEventAckedReply;
+
+    #[test]
+    fn emit_event_acked_reply() {
+        let serialized = [7, 0, 0, 0, 70, 1, 3];
+
+        let msg = EmitEventAckedReply {
+            serial: 1,
+            delivered: 3,
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::EmitEventAckedReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+}
@@ -10,6 +10,11 @@ use bytes::BytesMut;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct ItemReceived {
     pub cookie: ChannelCookie,
     pub value: SerializedValue,
@@ -57,6 +57,27 @@ impl Packetizer {
         }
     }
 
+    /// Returns the number of buffered bytes.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Indicates whether there are no buffered bytes.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns the length of the next message, if its length prefix has been received.
+    ///
+    /// This allows rejecting oversized messages before they have been received completely.
+    pub fn next_message_len(&self) -> Option<usize> {
+        match self.len {
+            Some(len) => Some(len),
+            None if self.buf.len() >= 4 => Some((&self.buf[..4]).get_u32_le() as usize),
+            None => None,
+        }
+    }
+
     pub fn next_message(&mut self) -> Option<BytesMut> {
         if self.buf.len() < 4 {
             return None;
@@ -80,6 +101,17 @@ impl Packetizer {
             None
         }
     }
+
+    /// Returns the next newline-terminated line, including the newline.
+    ///
+    /// This is used instead of [`next_message`](Self::next_message) by text-based wire formats.
+    #[cfg(feature = "json")]
+    pub fn next_line(&mut self) -> Option<BytesMut> {
+        debug_assert!(self.len.is_none());
+
+        let pos = self.buf.iter().position(|&b| b == b'\n')?;
+        Some(self.buf.split_to(pos + 1))
+    }
 }
 
 impl Default for Packetizer {
@@ -144,7 +176,7 @@ mod test {
         assert_eq!(Message::deserialize_message(msg3_serialized), Ok(msg3));
         assert_eq!(packetizer.next_message(), None);
 
-        assert_eq!(serialized[..], []);
+        assert!(serialized.is_empty());
     }
 
     #[test]
@@ -208,6 +240,6 @@ mod test {
         assert_eq!(Message::deserialize_message(msg3_serialized), Ok(msg3));
         assert_eq!(packetizer.next_message(), None);
 
-        assert_eq!(serialized[..], []);
+        assert!(serialized.is_empty());
     }
 }
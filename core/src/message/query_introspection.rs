@@ -1,16 +1,53 @@
 use super::message_ops::Sealed;
 use super::{Message, MessageKind, MessageOps};
+use crate::compression::Compression;
 use crate::ids::TypeId;
 use crate::message_deserializer::{MessageDeserializeError, MessageWithoutValueDeserializer};
 use crate::message_serializer::{MessageSerializeError, MessageSerializer};
 use crate::serialized_value::SerializedValueSlice;
 use bytes::BytesMut;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct QueryIntrospection {
     pub serial: u32,
     pub type_id: TypeId,
+
+    /// Types, whose introspection the client already knows.
+    ///
+    /// The broker omits these from a
+    /// [`Bundle`](super::QueryIntrospectionResult::Bundle). This requires protocol version 1.19.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub known: Vec<TypeId>,
+
+    /// Compression, that the client accepts for a
+    /// [`CompressedBundle`](super::QueryIntrospectionResult::CompressedBundle).
+    ///
+    /// This requires protocol version 1.19.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub compression: Option<Compression>,
+}
+
+impl QueryIntrospection {
+    pub fn new(serial: u32, type_id: TypeId) -> Self {
+        Self {
+            serial,
+            type_id,
+            known: Vec::new(),
+            compression: None,
+        }
+    }
 }
 
 impl MessageOps for QueryIntrospection {
@@ -24,6 +61,19 @@ impl MessageOps for QueryIntrospection {
         serializer.put_varint_u32_le(self.serial);
         serializer.put_uuid(self.type_id.0);
 
+        // Both fields are optional and omitted entirely, when they have their default values.
+        if !self.known.is_empty() || self.compression.is_some() {
+            serializer.put_varint_u32_le(self.known.len() as u32);
+
+            for type_id in self.known {
+                serializer.put_uuid(type_id.0);
+            }
+
+            if let Some(compression) = self.compression {
+                serializer.put_discriminant_u8(compression);
+            }
+        }
+
         serializer.finish()
     }
 
@@ -34,8 +84,36 @@ impl MessageOps for QueryIntrospection {
         let serial = deserializer.try_get_varint_u32_le()?;
         let type_id = deserializer.try_get_uuid().map(TypeId)?;
 
+        let mut known = Vec::new();
+        let mut compression = None;
+
+        if !deserializer.is_empty() {
+            let len = deserializer.try_get_varint_u32_le()? as usize;
+
+            // Each type id is 16 bytes long. Checking this avoids huge allocations for bogus
+            // lengths.
+            if len > deserializer.remaining() / 16 {
+                return Err(MessageDeserializeError::UnexpectedEoi);
+            }
+
+            known.reserve_exact(len);
+            for _ in 0..len {
+                known.push(deserializer.try_get_uuid().map(TypeId)?);
+            }
+
+            if !deserializer.is_empty() {
+                compression = Some(deserializer.try_get_discriminant_u8()?);
+            }
+        }
+
         deserializer.finish()?;
-        Ok(Self { serial, type_id })
+
+        Ok(Self {
+            serial,
+            type_id,
+            known,
+            compression,
+        })
     }
 
     fn value(&self) -> Option<&SerializedValueSlice> {
@@ -56,6 +134,7 @@ mod test {
     use super::super::test::{assert_deserialize_eq, assert_serialize_eq};
     use super::super::Message;
     use super::QueryIntrospection;
+    use crate::compression::Compression;
     use crate::ids::TypeId;
     use uuid::uuid;
 
@@ -66,9 +145,28 @@ mod test {
             0x38, 0x76, 0x52, 0x3d, 0x1b,
         ];
 
+        let msg = QueryIntrospection::new(1, TypeId(uuid!("b7c3be13-5377-466e-b4bf-373876523d1b")));
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+
+        let msg = Message::QueryIntrospection(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
+
+    #[test]
+    fn query_introspection_with_known_and_compression() {
+        let serialized = [
+            40, 0, 0, 0, 50, 1, 0xb7, 0xc3, 0xbe, 0x13, 0x53, 0x77, 0x46, 0x6e, 0xb4, 0xbf, 0x37,
+            0x38, 0x76, 0x52, 0x3d, 0x1b, 1, 0x9d, 0x7a, 0x5e, 0x1c, 0x0f, 0x8f, 0x4b, 0x3a, 0x8f,
+            0x36, 0x62, 0x57, 0x55, 0xb5, 0x29, 0x4e, 0,
+        ];
+
         let msg = QueryIntrospection {
             serial: 1,
             type_id: TypeId(uuid!("b7c3be13-5377-466e-b4bf-373876523d1b")),
+            known: vec![TypeId(uuid!("9d7a5e1c-0f8f-4b3a-8f36-625755b5294e"))],
+            compression: Some(Compression::Lz4),
         };
         assert_serialize_eq(&msg, serialized);
         assert_deserialize_eq(&msg, serialized);
@@ -77,4 +175,19 @@ mod test {
         assert_serialize_eq(&msg, serialized);
         assert_deserialize_eq(&msg, serialized);
     }
+
+    #[test]
+    fn query_introspection_with_compression_only() {
+        let serialized = [
+            24, 0, 0, 0, 50, 1, 0xb7, 0xc3, 0xbe, 0x13, 0x53, 0x77, 0x46, 0x6e, 0xb4, 0xbf, 0x37,
+            0x38, 0x76, 0x52, 0x3d, 0x1b, 0, 0,
+        ];
+
+        let mut msg =
+            QueryIntrospection::new(1, TypeId(uuid!("b7c3be13-5377-466e-b4bf-373876523d1b")));
+        msg.compression = Some(Compression::Lz4);
+
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq(&msg, serialized);
+    }
 }
@@ -1,27 +1,51 @@
 use super::message_ops::Sealed;
 use super::{Message, MessageKind, MessageOps};
-#[cfg(feature = "introspection")]
-use crate::error::SerializeError;
+use crate::compression::{self, Compression};
+use crate::error::{DeserializeError, SerializeError};
+use crate::ids::TypeId;
 #[cfg(feature = "introspection")]
 use crate::introspection::Introspection;
 use crate::message_deserializer::{MessageDeserializeError, MessageWithValueDeserializer};
 use crate::message_serializer::{MessageSerializeError, MessageSerializer};
 use crate::serialized_value::{SerializedValue, SerializedValueSlice};
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
+use std::collections::HashMap;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[repr(u8)]
 enum QueryIntrospectionReplyKind {
     Ok = 0,
     Unavailable = 1,
+    Bundle = 2,
+    CompressedBundle = 3,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum QueryIntrospectionResult {
     Ok(SerializedValue),
     Unavailable,
+
+    /// Introspections of the queried type and of the types it references.
+    ///
+    /// The value is a serialized `HashMap<TypeId, SerializedValue>`, which always contains the
+    /// queried type. Referenced types are included only if the broker knows them and they are not
+    /// in [`QueryIntrospection::known`](super::QueryIntrospection::known).
+    ///
+    /// This requires protocol version 1.19.
+    Bundle(SerializedValue),
+
+    /// Like [`Bundle`](Self::Bundle), but compressed.
+    ///
+    /// The value is a serialized `Bytes` of the compressed bundle. This requires protocol version
+    /// 1.19.
+    CompressedBundle(SerializedValue),
 }
 
 impl QueryIntrospectionResult {
@@ -31,10 +55,71 @@ impl QueryIntrospectionResult {
     ) -> Result<Self, SerializeError> {
         SerializedValue::serialize(introspection).map(Self::Ok)
     }
+
+    /// Creates a bundle of introspections.
+    ///
+    /// The bundle is compressed with `compression`, if the algorithm is supported and compressing
+    /// makes it smaller. Otherwise, a plain [`Bundle`](Self::Bundle) is returned.
+    pub fn bundle<'a>(
+        introspections: impl IntoIterator<Item = (TypeId, &'a SerializedValue)>,
+        compression: Option<Compression>,
+    ) -> Result<Self, SerializeError> {
+        let introspections = introspections.into_iter().collect::<HashMap<_, _>>();
+        let bundle = SerializedValue::serialize(&introspections)?;
+
+        let Some(compression) = compression.filter(|compression| compression.is_supported()) else {
+            return Ok(Self::Bundle(bundle));
+        };
+
+        let bundle = bundle.into_bytes_mut();
+
+        match compression::compress(compression, &bundle) {
+            Some(compressed) => {
+                SerializedValue::serialize(&compressed.freeze()).map(Self::CompressedBundle)
+            }
+
+            None => Ok(Self::Bundle(SerializedValue::from_bytes_mut(bundle))),
+        }
+    }
+
+    /// Returns all introspections of the result.
+    ///
+    /// The introspection of an [`Ok`](Self::Ok) result is returned for `type_id`, which must be the
+    /// queried type. [`Unavailable`](Self::Unavailable) results in an empty map.
+    pub fn into_introspections(
+        self,
+        type_id: TypeId,
+    ) -> Result<HashMap<TypeId, SerializedValue>, DeserializeError> {
+        match self {
+            Self::Ok(introspection) => Ok(HashMap::from([(type_id, introspection)])),
+            Self::Unavailable => Ok(HashMap::new()),
+            Self::Bundle(bundle) => bundle.deserialize(),
+
+            Self::CompressedBundle(compressed) => {
+                let compressed = compressed.deserialize::<Bytes>()?;
+
+                let bundle = compression::decompress(BytesMut::from(&*compressed))
+                    .map_err(|_| DeserializeError::InvalidSerialization)?;
+
+                // 4 bytes message length + 1 byte message kind + 4 bytes value length + at least 1
+                // byte value.
+                if bundle.len() < 10 {
+                    return Err(DeserializeError::InvalidSerialization);
+                }
+
+                SerializedValue::from_bytes_mut(bundle).deserialize()
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct QueryIntrospectionReply {
     pub serial: u32,
     pub result: QueryIntrospectionResult,
@@ -77,6 +162,26 @@ impl MessageOps for QueryIntrospectionReply {
 
                 serializer
             }
+
+            QueryIntrospectionResult::Bundle(value) => {
+                let mut serializer =
+                    MessageSerializer::with_value(value, MessageKind::QueryIntrospectionReply)?;
+
+                serializer.put_varint_u32_le(self.serial);
+                serializer.put_discriminant_u8(QueryIntrospectionReplyKind::Bundle);
+
+                serializer
+            }
+
+            QueryIntrospectionResult::CompressedBundle(value) => {
+                let mut serializer =
+                    MessageSerializer::with_value(value, MessageKind::QueryIntrospectionReply)?;
+
+                serializer.put_varint_u32_le(self.serial);
+                serializer.put_discriminant_u8(QueryIntrospectionReplyKind::CompressedBundle);
+
+                serializer
+            }
         };
 
         serializer.finish()
@@ -106,12 +211,33 @@ impl MessageOps for QueryIntrospectionReply {
                     result: QueryIntrospectionResult::Unavailable,
                 })
             }
+
+            QueryIntrospectionReplyKind::Bundle => {
+                let value = deserializer.finish()?;
+
+                Ok(Self {
+                    serial,
+                    result: QueryIntrospectionResult::Bundle(value),
+                })
+            }
+
+            QueryIntrospectionReplyKind::CompressedBundle => {
+                let value = deserializer.finish()?;
+
+                Ok(Self {
+                    serial,
+                    result: QueryIntrospectionResult::CompressedBundle(value),
+                })
+            }
         }
     }
 
     fn value(&self) -> Option<&SerializedValueSlice> {
         match self.result {
-            QueryIntrospectionResult::Ok(ref value) => Some(value),
+            QueryIntrospectionResult::Ok(ref value)
+            | QueryIntrospectionResult::Bundle(ref value)
+            | QueryIntrospectionResult::CompressedBundle(ref value) => Some(value),
+
             QueryIntrospectionResult::Unavailable => None,
         }
     }
@@ -132,7 +258,11 @@ mod test {
     };
     use super::super::Message;
     use super::{QueryIntrospectionReply, QueryIntrospectionResult};
+    use crate::compression::Compression;
+    use crate::ids::TypeId;
     use crate::serialized_value::SerializedValue;
+    use std::collections::HashMap;
+    use uuid::uuid;
 
     #[test]
     fn ok() {
@@ -151,6 +281,81 @@ mod test {
         assert_deserialize_eq_with_value(&msg, serialized, &value);
     }
 
+    #[test]
+    fn bundle() {
+        let serialized = [13, 0, 0, 0, 51, 2, 0, 0, 0, 3, 4, 1, 2];
+        let value = 4u8;
+
+        let msg = QueryIntrospectionReply {
+            serial: 1,
+            result: QueryIntrospectionResult::Bundle(SerializedValue::serialize(&value).unwrap()),
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::QueryIntrospectionReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn compressed_bundle() {
+        let serialized = [13, 0, 0, 0, 51, 2, 0, 0, 0, 3, 4, 1, 3];
+        let value = 4u8;
+
+        let msg = QueryIntrospectionReply {
+            serial: 1,
+            result: QueryIntrospectionResult::CompressedBundle(
+                SerializedValue::serialize(&value).unwrap(),
+            ),
+        };
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+
+        let msg = Message::QueryIntrospectionReply(msg);
+        assert_serialize_eq(&msg, serialized);
+        assert_deserialize_eq_with_value(&msg, serialized, &value);
+    }
+
+    #[test]
+    fn into_introspections() {
+        let type_id1 = TypeId(uuid!("b7c3be13-5377-466e-b4bf-373876523d1b"));
+        let type_id2 = TypeId(uuid!("9d7a5e1c-0f8f-4b3a-8f36-625755b5294e"));
+        let value1 = SerializedValue::serialize("foo").unwrap();
+        let value2 = SerializedValue::serialize(&[0u8; 256][..]).unwrap();
+
+        let res = QueryIntrospectionResult::Ok(value1.clone());
+        assert_eq!(
+            res.into_introspections(type_id1).unwrap(),
+            HashMap::from([(type_id1, value1.clone())])
+        );
+
+        let res = QueryIntrospectionResult::Unavailable;
+        assert_eq!(res.into_introspections(type_id1).unwrap(), HashMap::new());
+
+        let expected = HashMap::from([(type_id1, value1.clone()), (type_id2, value2.clone())]);
+
+        let res =
+            QueryIntrospectionResult::bundle([(type_id1, &value1), (type_id2, &value2)], None)
+                .unwrap();
+        assert!(matches!(res, QueryIntrospectionResult::Bundle(_)));
+        assert_eq!(res.into_introspections(type_id1).unwrap(), expected);
+
+        let res = QueryIntrospectionResult::bundle(
+            [(type_id1, &value1), (type_id2, &value2)],
+            Some(Compression::Lz4),
+        )
+        .unwrap();
+
+        if Compression::Lz4.is_supported() {
+            assert!(matches!(res, QueryIntrospectionResult::CompressedBundle(_)));
+        } else {
+            assert!(matches!(res, QueryIntrospectionResult::Bundle(_)));
+        }
+
+        assert_eq!(res.into_introspections(type_id1).unwrap(), expected);
+    }
+
     #[test]
     fn unavailable() {
         let serialized = [12, 0, 0, 0, 51, 1, 0, 0, 0, 0, 1, 1];
@@ -17,6 +17,11 @@ enum QueryServiceInfoReplyKind {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum QueryServiceInfoResult {
     Ok(SerializedValue),
     InvalidService,
@@ -30,6 +35,11 @@ impl QueryServiceInfoResult {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct QueryServiceInfoReply {
     pub serial: u32,
     pub result: QueryServiceInfoResult,
@@ -130,8 +140,9 @@ mod test {
     #[test]
     fn ok() {
         let serialized = [
-            39, 0, 0, 0, 54, 28, 0, 0, 0, 39, 3, 0, 7, 2, 1, 1, 14, 0xcf, 0x41, 0xc6, 0x88, 0x49,
-            0x76, 0x46, 0xa5, 0x8e, 0x2d, 0x48, 0x71, 0x02, 0x58, 0xbc, 0x2c, 2, 1, 2, 1, 1, 0,
+            43, 0, 0, 0, 54, 32, 0, 0, 0, 39, 5, 0, 7, 2, 1, 1, 14, 0xcf, 0x41, 0xc6, 0x88, 0x49,
+            0x76, 0x46, 0xa5, 0x8e, 0x2d, 0x48, 0x71, 0x02, 0x58, 0xbc, 0x2c, 2, 1, 2, 1, 3, 0, 4,
+            0, 1, 0,
         ];
         let info = ServiceInfo::new(2)
             .set_type_id(TypeId(uuid!("cf41c688-4976-46a5-8e2d-48710258bc2c")))
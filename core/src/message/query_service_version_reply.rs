@@ -15,6 +15,11 @@ enum QueryServiceVersionReplyKind {
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum QueryServiceVersionResult {
     Ok(u32),
     InvalidService,
@@ -22,6 +27,11 @@ pub enum QueryServiceVersionResult {
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
 pub struct QueryServiceVersionReply {
     pub serial: u32,
     pub result: QueryServiceVersionResult,
@@ -1,6 +1,9 @@
 use crate::buf_ext::MessageBufExt;
+use crate::call_priority::CallPriority;
+use crate::channel_end::{ChannelEndWithCapacity, ChannelEndWithCapacityKind};
 use crate::message::MessageKind;
 use crate::serialized_value::SerializedValue;
+use crate::trace_context::TraceContext;
 use bytes::{Buf, BytesMut};
 use thiserror::Error;
 use uuid::Uuid;
@@ -44,6 +47,34 @@ impl MessageWithoutValueDeserializer {
         Ok(Uuid::from_bytes(bytes))
     }
 
+    pub fn try_get_channel_end_with_capacity(
+        &mut self,
+    ) -> Result<ChannelEndWithCapacity, MessageDeserializeError> {
+        match self.try_get_discriminant_u8()? {
+            ChannelEndWithCapacityKind::Sender => Ok(ChannelEndWithCapacity::Sender),
+
+            ChannelEndWithCapacityKind::Receiver => self
+                .try_get_varint_u32_le()
+                .map(ChannelEndWithCapacity::Receiver),
+
+            ChannelEndWithCapacityKind::ReceiverBytes => self
+                .try_get_varint_u32_le()
+                .map(ChannelEndWithCapacity::ReceiverBytes),
+
+            ChannelEndWithCapacityKind::BroadcastSender => {
+                Ok(ChannelEndWithCapacity::BroadcastSender)
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.len()
+    }
+
     pub fn finish(self) -> Result<(), MessageDeserializeError> {
         if self.buf.is_empty() {
             Ok(())
@@ -73,7 +104,7 @@ impl MessageWithValueDeserializer {
             return Err(MessageDeserializeError::InvalidSerialization);
         }
 
-        if buf[4] != kind.into() {
+        if buf[4] != u8::from(kind) {
             return Err(MessageDeserializeError::UnexpectedMessage);
         }
 
@@ -109,6 +140,38 @@ impl MessageWithValueDeserializer {
         Ok(Uuid::from_bytes(bytes))
     }
 
+    pub fn try_get_trace_context(
+        &mut self,
+    ) -> Result<Option<TraceContext>, MessageDeserializeError> {
+        if self.msg.is_empty() {
+            return Ok(None);
+        }
+
+        let mut bytes = [0; TraceContext::SERIALIZED_LEN];
+        self.msg.try_copy_to_slice(&mut bytes)?;
+        Ok(Some(TraceContext::from_bytes(bytes)))
+    }
+
+    pub fn try_get_trace_context_and_priority(
+        &mut self,
+    ) -> Result<(Option<TraceContext>, CallPriority), MessageDeserializeError> {
+        // Both fields are optional and identified by the number of remaining bytes. A trace context
+        // is never just a single byte long.
+        let trace_context = if self.msg.len() == 1 {
+            None
+        } else {
+            self.try_get_trace_context()?
+        };
+
+        let priority = if self.msg.is_empty() {
+            CallPriority::Normal
+        } else {
+            self.msg.try_get_discriminant_u8()?
+        };
+
+        Ok((trace_context, priority))
+    }
+
     pub fn finish(mut self) -> Result<SerializedValue, MessageDeserializeError> {
         if self.msg.is_empty() {
             self.header_and_value.unsplit(self.msg);
@@ -1,6 +1,9 @@
 use crate::buf_ext::BufMutExt;
+use crate::call_priority::CallPriority;
+use crate::channel_end::{ChannelEndWithCapacity, ChannelEndWithCapacityKind};
 use crate::message::MessageKind;
 use crate::serialized_value::SerializedValue;
+use crate::trace_context::TraceContext;
 use bytes::{BufMut, BytesMut};
 use thiserror::Error;
 use uuid::Uuid;
@@ -60,6 +63,40 @@ impl MessageSerializer {
         self.buf.put_slice(uuid.as_ref());
     }
 
+    pub fn put_channel_end_with_capacity(&mut self, end: ChannelEndWithCapacity) {
+        match end {
+            ChannelEndWithCapacity::Sender => {
+                self.put_discriminant_u8(ChannelEndWithCapacityKind::Sender)
+            }
+
+            ChannelEndWithCapacity::Receiver(capacity) => {
+                self.put_discriminant_u8(ChannelEndWithCapacityKind::Receiver);
+                self.put_varint_u32_le(capacity);
+            }
+
+            ChannelEndWithCapacity::ReceiverBytes(capacity) => {
+                self.put_discriminant_u8(ChannelEndWithCapacityKind::ReceiverBytes);
+                self.put_varint_u32_le(capacity);
+            }
+
+            ChannelEndWithCapacity::BroadcastSender => {
+                self.put_discriminant_u8(ChannelEndWithCapacityKind::BroadcastSender)
+            }
+        }
+    }
+
+    pub fn put_trace_context(&mut self, trace_context: Option<TraceContext>) {
+        if let Some(trace_context) = trace_context {
+            self.buf.put_slice(&trace_context.to_bytes());
+        }
+    }
+
+    pub fn put_call_priority(&mut self, priority: CallPriority) {
+        if priority != CallPriority::Normal {
+            self.buf.put_discriminant_u8(priority);
+        }
+    }
+
     pub fn finish(mut self) -> Result<BytesMut, MessageSerializeError> {
         let len = self.buf.len();
         if len <= u32::MAX as usize {
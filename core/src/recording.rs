@@ -0,0 +1,742 @@
+//! Recording and replaying of message streams.
+//!
+//! A [`Recorder`] captures all messages, that pass through one or more transports, together with
+//! timestamps. Each transport is wrapped with [`Recorder::wrap`] and is assigned a unique
+//! connection id. To record an entire broker, wrap every transport with the same recorder before
+//! handing it to the broker.
+//!
+//! Recordings can be read back with a [`RecordingReader`] and fed back through a
+//! [`ReplayTransport`], either as fast as possible or with their original timing.
+//!
+//! [`Recorder`] is built on [`TapTransport`], which shows all messages of a transport to a
+//! [`Tap`]. Implement [`Tap`] to observe messages in other ways.
+//!
+//! # File format
+//!
+//! Recordings start with the 8 bytes magic `ALDRNREC`, followed by a 1 byte version (currently
+//! 1). Each message is then stored as a record of:
+//!
+//! - the connection id as a 4 bytes little endian integer,
+//! - the direction as 1 byte (0: sent, 1: received),
+//! - the timestamp as an 8 bytes little endian integer in microseconds since the start of the
+//!   recording,
+//! - the serialized message, which begins with its own length.
+//!
+//! # Examples
+//!
+//! Record all messages of a transport to a file:
+//!
+//! ```no_run
+//! # use aldrin_core::recording::Recorder;
+//! # use aldrin_core::transport::AsyncTransport;
+//! # fn example(transport: impl AsyncTransport) -> std::io::Result<()> {
+//! let recorder = Recorder::create("session.rec")?;
+//! let transport = recorder.wrap(transport);
+//!
+//! // Use the transport as usual ...
+//!
+//! recorder.flush()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Replay all messages, that were received on connection 0:
+//!
+//! ```no_run
+//! # use aldrin_core::recording::{Direction, RecordingReader, ReplayTransport};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let messages = RecordingReader::open("session.rec")?
+//!     .collect::<Result<Vec<_>, _>>()?
+//!     .into_iter()
+//!     .filter(|msg| (msg.connection() == 0) && (msg.direction() == Direction::Received));
+//!
+//! let transport = ReplayTransport::new(messages);
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(test)]
+mod test;
+
+use crate::message::{Message, MessageDeserializeError, MessageOps};
+use crate::transport::AsyncTransport;
+use crate::{Compression, WireFormat};
+use bytes::BytesMut;
+use pin_project_lite::pin_project;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::future::Future;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+const MAGIC: [u8; 8] = *b"ALDRNREC";
+const VERSION: u8 = 1;
+
+type Timer = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Direction of a recorded message, as seen from the recorded transport.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// The message was sent on the transport.
+    Sent,
+
+    /// The message was received from the transport.
+    Received,
+}
+
+impl Direction {
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::Sent => 0,
+            Self::Received => 1,
+        }
+    }
+
+    fn from_u8(direction: u8) -> Option<Self> {
+        match direction {
+            0 => Some(Self::Sent),
+            1 => Some(Self::Received),
+            _ => None,
+        }
+    }
+}
+
+/// A single message of a recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedMessage {
+    timestamp: Duration,
+    connection: u32,
+    direction: Direction,
+    message: Message,
+}
+
+impl RecordedMessage {
+    /// Creates a new `RecordedMessage`.
+    pub fn new(
+        timestamp: Duration,
+        connection: u32,
+        direction: Direction,
+        message: Message,
+    ) -> Self {
+        Self {
+            timestamp,
+            connection,
+            direction,
+            message,
+        }
+    }
+
+    /// Returns the time since the start of the recording.
+    pub fn timestamp(&self) -> Duration {
+        self.timestamp
+    }
+
+    /// Returns the id of the connection, on which the message was recorded.
+    pub fn connection(&self) -> u32 {
+        self.connection
+    }
+
+    /// Returns the direction of the message.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns a reference to the message.
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// Converts the `RecordedMessage` into the message.
+    pub fn into_message(self) -> Message {
+        self.message
+    }
+}
+
+/// Records messages of one or more transports.
+///
+/// `Recorder` can be cheaply cloned. All clones write to the same underlying writer.
+///
+/// Errors, that occur while writing, never affect the recorded transports. Instead, recording
+/// stops and the first error is returned from [`flush`](Self::flush).
+#[derive(Clone)]
+pub struct Recorder {
+    inner: Arc<Mutex<RecorderInner>>,
+}
+
+impl Recorder {
+    /// Creates a new `Recorder`, that writes to `writer`.
+    ///
+    /// The header is written immediately.
+    pub fn new<W>(mut writer: W) -> io::Result<Self>
+    where
+        W: Write + Send + 'static,
+    {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION])?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(RecorderInner {
+                writer: Box::new(writer),
+                start: Instant::now(),
+                next_connection: 0,
+                error: None,
+            })),
+        })
+    }
+
+    /// Creates a new `Recorder`, that writes to the file at `path`.
+    ///
+    /// The file is created if it doesn't exist and truncated otherwise.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::create(path)?;
+        Self::new(BufWriter::new(file))
+    }
+
+    /// Wraps a transport such that all messages sent and received on it are recorded.
+    ///
+    /// Each call assigns a new connection id, starting at 0.
+    pub fn wrap<T: AsyncTransport>(&self, transport: T) -> RecordingTransport<T> {
+        let connection = {
+            let mut inner = self.inner.lock().unwrap();
+            let connection = inner.next_connection;
+            inner.next_connection += 1;
+            connection
+        };
+
+        TapTransport::new(
+            transport,
+            RecorderTap {
+                recorder: self.clone(),
+                connection,
+            },
+        )
+    }
+
+    /// Records a single message.
+    pub fn record(&self, connection: u32, direction: Direction, msg: &Message) {
+        self.inner
+            .lock()
+            .unwrap()
+            .record(connection, direction, msg);
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// If an error occurred previously while recording, then that error is returned instead.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(ref err) = inner.error {
+            return Err(io::Error::new(err.kind(), err.to_string()));
+        }
+
+        inner.writer.flush()
+    }
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inner = self.inner.lock().unwrap();
+
+        f.debug_struct("Recorder")
+            .field("start", &inner.start)
+            .field("next_connection", &inner.next_connection)
+            .field("error", &inner.error)
+            .finish_non_exhaustive()
+    }
+}
+
+struct RecorderInner {
+    writer: Box<dyn Write + Send>,
+    start: Instant,
+    next_connection: u32,
+    error: Option<io::Error>,
+}
+
+impl RecorderInner {
+    fn record(&mut self, connection: u32, direction: Direction, msg: &Message) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let timestamp = self.start.elapsed().as_micros() as u64;
+
+        if let Err(e) = self.write(connection, direction, timestamp, msg) {
+            self.error = Some(e);
+        }
+    }
+
+    fn write(
+        &mut self,
+        connection: u32,
+        direction: Direction,
+        timestamp: u64,
+        msg: &Message,
+    ) -> io::Result<()> {
+        let buf = msg
+            .clone()
+            .serialize_message()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        self.writer.write_all(&connection.to_le_bytes())?;
+        self.writer.write_all(&[direction.to_u8()])?;
+        self.writer.write_all(&timestamp.to_le_bytes())?;
+        self.writer.write_all(&buf)?;
+
+        Ok(())
+    }
+}
+
+/// Observer of all messages passing through a [`TapTransport`].
+pub trait Tap {
+    /// Called with every message before it is sent on or after it has been received from the
+    /// transport.
+    fn tap(&mut self, direction: Direction, msg: &Message);
+}
+
+pin_project! {
+    /// Transport, that passes all messages through unchanged, but shows them to a [`Tap`] first.
+    #[derive(Debug)]
+    pub struct TapTransport<T, P> {
+        #[pin]
+        transport: T,
+        tap: P,
+    }
+}
+
+impl<T, P> TapTransport<T, P> {
+    /// Creates a new `TapTransport`.
+    pub fn new(transport: T, tap: P) -> Self {
+        Self { transport, tap }
+    }
+
+    /// Returns a reference to the tap.
+    pub fn tap(&self) -> &P {
+        &self.tap
+    }
+
+    /// Returns a mutable reference to the tap.
+    pub fn tap_mut(&mut self) -> &mut P {
+        &mut self.tap
+    }
+
+    /// Returns a reference to the inner transport.
+    pub fn inner(&self) -> &T {
+        &self.transport
+    }
+
+    /// Converts the `TapTransport` into the inner transport.
+    pub fn into_inner(self) -> T {
+        self.transport
+    }
+}
+
+impl<T: AsyncTransport, P: Tap> AsyncTransport for TapTransport<T, P> {
+    type Error = T::Error;
+
+    fn receive_poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Message, Self::Error>> {
+        let this = self.project();
+        let msg = ready!(this.transport.receive_poll(cx))?;
+
+        this.tap.tap(Direction::Received, &msg);
+        Poll::Ready(Ok(msg))
+    }
+
+    fn send_poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.project().transport.send_poll_ready(cx)
+    }
+
+    fn send_start(self: Pin<&mut Self>, msg: Message) -> Result<(), Self::Error> {
+        let this = self.project();
+
+        this.tap.tap(Direction::Sent, &msg);
+        this.transport.send_start(msg)
+    }
+
+    fn send_poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.project().transport.send_poll_flush(cx)
+    }
+
+    fn supported_compression(&self) -> &[Compression] {
+        self.transport.supported_compression()
+    }
+
+    fn enable_compression(self: Pin<&mut Self>, compression: Compression) {
+        self.project().transport.enable_compression(compression)
+    }
+
+    fn supports_fd_passing(&self) -> bool {
+        self.transport.supports_fd_passing()
+    }
+
+    fn enable_fd_passing(self: Pin<&mut Self>) {
+        self.project().transport.enable_fd_passing()
+    }
+
+    fn supported_wire_formats(&self) -> &[WireFormat] {
+        self.transport.supported_wire_formats()
+    }
+
+    fn enable_wire_format(self: Pin<&mut Self>, format: WireFormat) {
+        self.project().transport.enable_wire_format(format)
+    }
+
+    fn max_message_size(&self) -> Option<usize> {
+        self.transport.max_message_size()
+    }
+
+    fn set_peer_max_message_size(self: Pin<&mut Self>, size: usize) {
+        self.project().transport.set_peer_max_message_size(size)
+    }
+}
+
+/// [`Tap`], that records the messages of one connection with a [`Recorder`].
+///
+/// This type is created by [`Recorder::wrap`].
+#[derive(Debug, Clone)]
+pub struct RecorderTap {
+    recorder: Recorder,
+    connection: u32,
+}
+
+impl RecorderTap {
+    /// Returns the connection id assigned to the recorded transport.
+    pub fn connection(&self) -> u32 {
+        self.connection
+    }
+}
+
+impl Tap for RecorderTap {
+    fn tap(&mut self, direction: Direction, msg: &Message) {
+        self.recorder.record(self.connection, direction, msg);
+    }
+}
+
+/// Transport, that records all messages passing through it.
+///
+/// This type is created with [`Recorder::wrap`].
+pub type RecordingTransport<T> = TapTransport<T, RecorderTap>;
+
+/// Reads messages from a recording.
+///
+/// `RecordingReader` is an iterator over all [`RecordedMessage`s](RecordedMessage) in the
+/// recording. Iteration stops after the first error.
+#[derive(Debug)]
+pub struct RecordingReader<R> {
+    reader: R,
+    done: bool,
+}
+
+impl<R: Read> RecordingReader<R> {
+    /// Creates a new `RecordingReader` and verifies the header of the recording.
+    pub fn new(mut reader: R) -> Result<Self, RecordingError> {
+        let mut header = [0; 9];
+        reader.read_exact(&mut header).map_err(|e| {
+            if e.kind() == io::ErrorKind::UnexpectedEof {
+                RecordingError::InvalidHeader
+            } else {
+                e.into()
+            }
+        })?;
+
+        if header[..8] != MAGIC {
+            return Err(RecordingError::InvalidHeader);
+        }
+
+        if header[8] != VERSION {
+            return Err(RecordingError::UnsupportedVersion(header[8]));
+        }
+
+        Ok(Self {
+            reader,
+            done: false,
+        })
+    }
+
+    /// Converts the `RecordingReader` into the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_record(&mut self) -> Result<Option<RecordedMessage>, RecordingError> {
+        let mut header = [0; 13];
+
+        // A recording may end only between two records.
+        if !read_exact_or_eof(&mut self.reader, &mut header)? {
+            return Ok(None);
+        }
+
+        let connection = u32::from_le_bytes(header[..4].try_into().unwrap());
+        let direction = Direction::from_u8(header[4]).ok_or(RecordingError::InvalidRecord)?;
+        let timestamp = u64::from_le_bytes(header[5..].try_into().unwrap());
+
+        let mut len_bytes = [0; 4];
+        read_exact(&mut self.reader, &mut len_bytes)?;
+        let len = u32::from_le_bytes(len_bytes) as usize;
+
+        if len < 4 {
+            return Err(RecordingError::InvalidRecord);
+        }
+
+        let mut buf = BytesMut::zeroed(len);
+        buf[..4].copy_from_slice(&len_bytes);
+        read_exact(&mut self.reader, &mut buf[4..])?;
+
+        let message = Message::deserialize_message(buf)?;
+
+        Ok(Some(RecordedMessage::new(
+            Duration::from_micros(timestamp),
+            connection,
+            direction,
+            message,
+        )))
+    }
+}
+
+impl RecordingReader<BufReader<File>> {
+    /// Opens the recording at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        let file = File::open(path)?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read> Iterator for RecordingReader<R> {
+    type Item = Result<RecordedMessage, RecordingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let res = self.read_record().transpose();
+        self.done = !matches!(res, Some(Ok(_)));
+        res
+    }
+}
+
+/// Fills `buf` completely, or returns `false` if the reader is at EOF.
+fn read_exact_or_eof(reader: &mut impl Read, buf: &mut [u8]) -> Result<bool, RecordingError> {
+    let mut read = 0;
+
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => return Err(RecordingError::Truncated),
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(true)
+}
+
+fn read_exact(reader: &mut impl Read, buf: &mut [u8]) -> Result<(), RecordingError> {
+    reader.read_exact(buf).map_err(|e| {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            RecordingError::Truncated
+        } else {
+            e.into()
+        }
+    })
+}
+
+/// Error while reading a recording.
+#[derive(Error, Debug)]
+pub enum RecordingError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// The recording doesn't start with a valid header.
+    #[error("invalid recording header")]
+    InvalidHeader,
+
+    /// The recording was made with an unsupported version of the file format.
+    #[error("unsupported recording version {0}")]
+    UnsupportedVersion(u8),
+
+    /// A record is malformed.
+    #[error("invalid record")]
+    InvalidRecord,
+
+    /// The recording ends in the middle of a record.
+    #[error("recording is truncated")]
+    Truncated,
+
+    /// A recorded message failed to deserialize.
+    #[error(transparent)]
+    Message(#[from] MessageDeserializeError),
+}
+
+/// Transport, that replays recorded messages.
+///
+/// All messages passed to [`new`](Self::new) are returned in order from
+/// [`receive_poll`](AsyncTransport::receive_poll), regardless of their connection id and
+/// direction. Filter them beforehand to replay e.g. only the messages received on a single
+/// connection.
+///
+/// By default, messages are replayed as fast as possible. Use [`with_timing`](Self::with_timing) to
+/// replay them with their original timing, or a multiple thereof.
+///
+/// Messages sent on this transport are stored and can be inspected with [`sent`](Self::sent).
+/// After all messages have been replayed, receiving fails with [`ReplayError::EndOfRecording`].
+pub struct ReplayTransport {
+    messages: VecDeque<(Duration, Message)>,
+    timing: Option<Timing>,
+    sent: Vec<Message>,
+}
+
+impl ReplayTransport {
+    /// Creates a new `ReplayTransport`, that replays `messages` as fast as possible.
+    pub fn new<I>(messages: I) -> Self
+    where
+        I: IntoIterator<Item = RecordedMessage>,
+    {
+        Self {
+            messages: messages
+                .into_iter()
+                .map(|msg| (msg.timestamp, msg.message))
+                .collect(),
+            timing: None,
+            sent: Vec::new(),
+        }
+    }
+
+    /// Replays messages with their original timing, accelerated by `speed`.
+    ///
+    /// A `speed` of 1 replays messages at their original timing, 2 at twice the speed and so on.
+    /// The first message is replayed immediately.
+    ///
+    /// The function `sleep` must return a future, that completes after the given duration. This
+    /// keeps `ReplayTransport` independent of any particular async runtime. With Tokio, you would
+    /// pass `tokio::time::sleep`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `speed` is not positive.
+    pub fn with_timing<F, Fut>(mut self, speed: f64, mut sleep: F) -> Self
+    where
+        F: FnMut(Duration) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        assert!(speed > 0.0, "speed must be positive");
+
+        self.timing = Some(Timing {
+            speed,
+            sleep: Box::new(move |delay| Box::pin(sleep(delay))),
+            timer: None,
+            last: None,
+        });
+
+        self
+    }
+
+    /// Returns the number of messages, that have not yet been replayed.
+    pub fn remaining(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns all messages, that were sent on this transport.
+    pub fn sent(&self) -> &[Message] {
+        &self.sent
+    }
+
+    /// Takes all messages, that were sent on this transport.
+    pub fn take_sent(&mut self) -> Vec<Message> {
+        std::mem::take(&mut self.sent)
+    }
+}
+
+impl AsyncTransport for ReplayTransport {
+    type Error = ReplayError;
+
+    fn receive_poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Message, Self::Error>> {
+        let this = self.get_mut();
+
+        let Some(&(timestamp, _)) = this.messages.front() else {
+            return Poll::Ready(Err(ReplayError::EndOfRecording));
+        };
+
+        if let Some(ref mut timing) = this.timing {
+            ready!(timing.poll_delay(timestamp, cx));
+        }
+
+        let (_, msg) = this.messages.pop_front().unwrap();
+        Poll::Ready(Ok(msg))
+    }
+
+    fn send_poll_ready(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn send_start(self: Pin<&mut Self>, msg: Message) -> Result<(), Self::Error> {
+        self.get_mut().sent.push(msg);
+        Ok(())
+    }
+
+    fn send_poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl fmt::Debug for ReplayTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReplayTransport")
+            .field("messages", &self.messages)
+            .field("timing", &self.timing)
+            .field("sent", &self.sent)
+            .finish()
+    }
+}
+
+struct Timing {
+    speed: f64,
+    sleep: Box<dyn FnMut(Duration) -> Timer + Send>,
+    timer: Option<Timer>,
+    last: Option<Duration>,
+}
+
+impl Timing {
+    /// Waits until the message at `timestamp` is due.
+    fn poll_delay(&mut self, timestamp: Duration, cx: &mut Context) -> Poll<()> {
+        if self.timer.is_none() {
+            if let Some(last) = self.last {
+                let delay = timestamp.saturating_sub(last).div_f64(self.speed);
+                self.timer = Some((self.sleep)(delay));
+            }
+        }
+
+        if let Some(ref mut timer) = self.timer {
+            ready!(timer.as_mut().poll(cx));
+            self.timer = None;
+        }
+
+        self.last = Some(timestamp);
+        Poll::Ready(())
+    }
+}
+
+impl fmt::Debug for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Timing")
+            .field("speed", &self.speed)
+            .field("last", &self.last)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error of a [`ReplayTransport`].
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// All recorded messages have been replayed.
+    #[error("end of recording")]
+    EndOfRecording,
+}
@@ -0,0 +1,157 @@
+use super::{
+    Direction, RecordedMessage, Recorder, RecordingError, RecordingReader, ReplayError,
+    ReplayTransport,
+};
+use crate::message::{CreateObject, Message, Shutdown};
+use crate::transport::AsyncTransportExt;
+use crate::ObjectUuid;
+use std::future;
+use std::io::{self, Cursor, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::uuid;
+
+#[derive(Debug, Clone, Default)]
+struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuf {
+    fn get(&self) -> Vec<u8> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn create_object(serial: u32) -> Message {
+    Message::CreateObject(CreateObject {
+        serial,
+        uuid: ObjectUuid(uuid!("b7c3be13-5377-466e-b4bf-373876523d1b")),
+    })
+}
+
+fn recorded(millis: u64, msg: Message) -> RecordedMessage {
+    RecordedMessage::new(Duration::from_millis(millis), 0, Direction::Received, msg)
+}
+
+#[tokio::test]
+async fn record_and_read() {
+    let buf = SharedBuf::default();
+    let recorder = Recorder::new(buf.clone()).unwrap();
+
+    let mut t1 = recorder.wrap(ReplayTransport::new([
+        recorded(0, create_object(0)),
+        recorded(0, create_object(1)),
+    ]));
+    let mut t2 = recorder.wrap(ReplayTransport::new([recorded(0, Shutdown.into())]));
+    assert_eq!(t1.tap().connection(), 0);
+    assert_eq!(t2.tap().connection(), 1);
+
+    assert_eq!(t1.receive().await, Ok(create_object(0)));
+    t2.send_and_flush(Shutdown).await.unwrap();
+    assert_eq!(t1.receive().await, Ok(create_object(1)));
+    assert_eq!(t2.receive().await, Ok(Shutdown.into()));
+    assert_eq!(t1.receive().await, Err(ReplayError::EndOfRecording));
+    recorder.flush().unwrap();
+
+    assert_eq!(t2.inner().sent(), [Shutdown.into()]);
+
+    let msgs = RecordingReader::new(Cursor::new(buf.get()))
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let msgs = msgs
+        .iter()
+        .map(|msg| (msg.connection(), msg.direction(), msg.message().clone()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        msgs,
+        [
+            (0, Direction::Received, create_object(0)),
+            (1, Direction::Sent, Shutdown.into()),
+            (0, Direction::Received, create_object(1)),
+            (1, Direction::Received, Shutdown.into()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn replay_with_timing() {
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let delays2 = delays.clone();
+
+    let mut transport = ReplayTransport::new([
+        recorded(100, create_object(0)),
+        recorded(110, create_object(1)),
+        recorded(130, create_object(2)),
+    ])
+    .with_timing(2.0, move |delay| {
+        delays2.lock().unwrap().push(delay);
+        future::ready(())
+    });
+
+    assert_eq!(transport.remaining(), 3);
+    assert_eq!(transport.receive().await, Ok(create_object(0)));
+    assert_eq!(transport.receive().await, Ok(create_object(1)));
+    assert_eq!(transport.receive().await, Ok(create_object(2)));
+    assert_eq!(transport.remaining(), 0);
+
+    assert_eq!(
+        *delays.lock().unwrap(),
+        [Duration::from_millis(5), Duration::from_millis(10)]
+    );
+}
+
+#[test]
+fn invalid_header() {
+    let res = RecordingReader::new(Cursor::new(b"ALDRNRE"));
+    assert!(matches!(res, Err(RecordingError::InvalidHeader)));
+
+    let res = RecordingReader::new(Cursor::new(b"ALDRNREX\x01"));
+    assert!(matches!(res, Err(RecordingError::InvalidHeader)));
+
+    let res = RecordingReader::new(Cursor::new(b"ALDRNREC\x02"));
+    assert!(matches!(res, Err(RecordingError::UnsupportedVersion(2))));
+}
+
+#[test]
+fn truncated_recording() {
+    let buf = SharedBuf::default();
+    let recorder = Recorder::new(buf.clone()).unwrap();
+    recorder.record(0, Direction::Sent, &create_object(0));
+
+    let mut buf = buf.get();
+    buf.pop();
+
+    let mut reader = RecordingReader::new(Cursor::new(buf)).unwrap();
+    assert!(matches!(
+        reader.next(),
+        Some(Err(RecordingError::Truncated))
+    ));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn invalid_direction() {
+    let buf = SharedBuf::default();
+    let recorder = Recorder::new(buf.clone()).unwrap();
+    recorder.record(0, Direction::Sent, &create_object(0));
+
+    let mut buf = buf.get();
+    buf[13] = 2;
+
+    let mut reader = RecordingReader::new(Cursor::new(buf)).unwrap();
+    assert!(matches!(
+        reader.next(),
+        Some(Err(RecordingError::InvalidRecord))
+    ));
+}
@@ -1,6 +1,8 @@
 use crate::buf_ext::BufMutExt;
 use crate::error::SerializeError;
 use crate::value::ValueKind;
+use alloc::boxed::Box;
+use alloc::string::String;
 use bytes::BufMut;
 use uuid::Uuid;
 
@@ -2,19 +2,26 @@
 mod test;
 
 use crate::error::{DeserializeError, SerializeError};
+#[cfg(all(unix, feature = "std"))]
+use crate::fd::Fd;
+use crate::fd::FdList;
 #[cfg(feature = "introspection")]
 use crate::introspection::{BuiltInType, Introspectable, Layout, LexicalId, References};
 use crate::value::ValueKind;
-use crate::value_deserializer::{Deserialize, Deserializer};
+use crate::value_deserializer::{Deserialize, Deserializer, Owner};
 use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+use alloc::borrow::ToOwned;
 use bytes::BytesMut;
-use std::borrow::Borrow;
-use std::fmt;
-use std::ops::Deref;
+use core::borrow::Borrow;
+use core::fmt;
+#[cfg(all(unix, feature = "std"))]
+use core::mem;
+use core::ops::Deref;
 
 #[derive(Clone, Eq)]
 pub struct SerializedValue {
     buf: BytesMut,
+    fds: FdList,
 }
 
 impl SerializedValue {
@@ -22,25 +29,95 @@ impl SerializedValue {
     pub fn empty() -> Self {
         Self {
             buf: BytesMut::new(),
+            fds: FdList::new(),
         }
     }
 
     pub fn serialize<T: Serialize + ?Sized>(value: &T) -> Result<Self, SerializeError> {
         // 4 bytes message length + 1 byte message kind + 4 bytes value length.
         let mut buf = BytesMut::zeroed(9);
-        let serializer = Serializer::new(&mut buf, 0)?;
+        let mut fds = FdList::new();
+        let serializer = Serializer::new(&mut buf, &mut fds, 0)?;
         value.serialize(serializer)?;
-        Ok(Self { buf })
+        Ok(Self { buf, fds })
     }
 
+    /// Deserializes the value.
+    ///
+    /// This behaves like [`SerializedValueSlice::deserialize`], except that
+    /// [file descriptors](Fd) attached to this value can be deserialized as well.
+    pub fn deserialize<T: Deserialize>(&self) -> Result<T, DeserializeError> {
+        let owner = Owner {
+            bytes: None,
+            fds: Some(&self.fds),
+        };
+
+        (**self).deserialize_with_owner(owner)
+    }
+
+    /// Deserializes the value and shares its buffer with [`bytes::Bytes`].
+    ///
+    /// This behaves like [`SerializedValueSlice::deserialize`], except that all [`bytes::Bytes`]
+    /// inside `T` refer to the buffer of this `SerializedValue` instead of being copied from it.
+    /// This is useful for large byte payloads, which can then be received without any copies.
+    ///
+    /// Note that the entire buffer stays alive as long as any of these [`bytes::Bytes`] exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use aldrin_core::SerializedValue;
+    /// let value = SerializedValue::serialize(&bytes::Bytes::from(vec![0; 1024])).unwrap();
+    /// let bytes: bytes::Bytes = value.deserialize_shared().unwrap();
+    /// assert_eq!(bytes, [0; 1024][..]);
+    /// ```
+    pub fn deserialize_shared<T: Deserialize>(self) -> Result<T, DeserializeError> {
+        let buf = self.buf.freeze();
+
+        let owner = Owner {
+            bytes: Some(&buf),
+            fds: Some(&self.fds),
+        };
+
+        // 4 bytes message length + 1 byte message kind + 4 bytes value length.
+        SerializedValueSlice::new(&buf[9..]).deserialize_with_owner(owner)
+    }
+
+    /// Returns the [file descriptors](Fd) attached to this value.
+    #[cfg(all(unix, feature = "std"))]
+    pub fn fds(&self) -> &[Fd] {
+        self.fds.as_slice()
+    }
+
+    /// Removes all [file descriptors](Fd) from this value and returns them.
+    ///
+    /// This is primarily useful for transports, that pass file descriptors out of band.
+    #[cfg(all(unix, feature = "std"))]
+    pub fn take_fds(&mut self) -> Vec<Fd> {
+        mem::take(&mut self.fds).into_vec()
+    }
+
+    /// Attaches [file descriptors](Fd) to this value, replacing all previously attached ones.
+    ///
+    /// This is primarily useful for transports, that pass file descriptors out of band.
+    #[cfg(all(unix, feature = "std"))]
+    pub fn set_fds(&mut self, fds: Vec<Fd>) {
+        self.fds = FdList::from_vec(fds);
+    }
+
+    #[cfg(feature = "std")]
     pub(crate) fn from_bytes_mut(buf: BytesMut) -> Self {
         // 4 bytes message length + 1 byte message kind + 4 bytes value length + at least 1 byte
         // value.
         debug_assert!(buf.len() >= 10);
 
-        Self { buf }
+        Self {
+            buf,
+            fds: FdList::new(),
+        }
     }
 
+    #[cfg(feature = "std")]
     pub(crate) fn into_bytes_mut(self) -> BytesMut {
         self.buf
     }
@@ -61,6 +138,7 @@ impl fmt::Debug for SerializedValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("SerializedValue")
             .field("buf", &&*self.buf)
+            .field("fds", &self.fds)
             .finish()
     }
 }
@@ -145,6 +223,36 @@ impl Introspectable for SerializedValue {
     fn add_references(_references: &mut References) {}
 }
 
+/// Serializes the value in its generic form, i.e. as a [`Value`](crate::Value).
+///
+/// Values with attached [file descriptors](Fd) cannot be serialized this way and fail with an error.
+/// The generic form contains only the indices of the file descriptors, which would be meaningless
+/// without them.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SerializedValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !self.fds.is_empty() {
+            return Err(serde::ser::Error::custom(
+                "values with file descriptors cannot be serialized with serde",
+            ));
+        }
+
+        let value = self
+            .deserialize::<crate::generic_value::Value>()
+            .map_err(serde::ser::Error::custom)?;
+
+        serde::Serialize::serialize(&value, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SerializedValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <crate::generic_value::Value as serde::Deserialize>::deserialize(deserializer)?;
+        Self::serialize(&value).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(feature = "fuzzing")]
 impl<'a> arbitrary::Arbitrary<'a> for SerializedValue {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
@@ -163,7 +271,10 @@ impl<'a> arbitrary::Arbitrary<'a> for SerializedValue {
 pub struct SerializedValueSlice([u8]);
 
 impl SerializedValueSlice {
-    pub(crate) fn new<T: AsRef<[u8]> + ?Sized>(buf: &T) -> &Self {
+    /// Interprets `buf` as a serialized value.
+    ///
+    /// `buf` is not validated. Invalid values cause errors when they are deserialized.
+    pub fn new<T: AsRef<[u8]> + ?Sized>(buf: &T) -> &Self {
         let self_ptr = buf.as_ref() as *const [u8] as *const Self;
         // Safe because of repr(transparent).
         unsafe { &*self_ptr }
@@ -176,8 +287,15 @@ impl SerializedValueSlice {
     }
 
     pub fn deserialize<T: Deserialize>(&self) -> Result<T, DeserializeError> {
+        self.deserialize_with_owner(Owner::default())
+    }
+
+    fn deserialize_with_owner<'a, T: Deserialize>(
+        &'a self,
+        owner: Owner<'a>,
+    ) -> Result<T, DeserializeError> {
         let mut buf = &self.0;
-        let deserializer = Deserializer::new(&mut buf, 0)?;
+        let deserializer = Deserializer::with_owner(&mut buf, owner, 0)?;
 
         let res = T::deserialize(deserializer);
 
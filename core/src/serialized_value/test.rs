@@ -2,6 +2,8 @@ use crate::error::{DeserializeError, SerializeError};
 use crate::serialized_value::SerializedValue;
 use crate::value_deserializer::{Deserialize, Deserializer};
 use crate::value_serializer::{Serialize, Serializer};
+use bytes::{Bytes, BytesMut};
+use std::mem;
 
 #[test]
 fn concrete_vs_vague() {
@@ -83,3 +85,44 @@ fn concrete_vs_vague() {
             .unwrap()
     );
 }
+
+#[test]
+fn deserialize_shared_bytes() {
+    let value = SerializedValue::serialize(&vec![Bytes::from(vec![1; 64]), Bytes::new()]).unwrap();
+    let range = value.as_ptr_range();
+
+    let copied: Vec<Bytes> = value.deserialize().unwrap();
+    assert_eq!(copied, [Bytes::from(vec![1; 64]), Bytes::new()]);
+    assert!(!range.contains(&copied[0].as_ptr()));
+
+    let shared: Vec<Bytes> = value.deserialize_shared().unwrap();
+    assert_eq!(shared, [Bytes::from(vec![1; 64]), Bytes::new()]);
+    assert!(range.contains(&shared[0].as_ptr()));
+}
+
+#[test]
+fn deserialize_partial_bytes_to_vec() {
+    struct Partial(Vec<u8>, Vec<u8>);
+
+    impl Deserialize for Partial {
+        fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+            let mut deserializer = deserializer.deserialize_bytes()?;
+            let first = deserializer.deserialize_to_vec(2)?;
+            let second = deserializer.deserialize_all_to_vec()?;
+            Ok(Self(first, second))
+        }
+    }
+
+    let value = SerializedValue::serialize(&Bytes::from_static(&[1, 2, 3, 4, 5])).unwrap();
+    let Partial(first, second) = value.deserialize().unwrap();
+    assert_eq!(first, [1, 2]);
+    assert_eq!(second, [3, 4, 5]);
+}
+
+#[test]
+fn size() {
+    // File descriptors must cost at most one pointer, because almost no value carries any.
+    assert!(
+        mem::size_of::<SerializedValue>() <= mem::size_of::<BytesMut>() + mem::size_of::<usize>()
+    );
+}
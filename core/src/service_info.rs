@@ -2,7 +2,7 @@
 mod test_old1;
 
 use crate::error::{DeserializeError, SerializeError};
-use crate::ids::TypeId;
+use crate::ids::{LexicalId, TypeId};
 use crate::value_deserializer::{Deserialize, Deserializer};
 use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -13,6 +13,8 @@ enum ServiceInfoField {
     Version = 0,
     TypeId = 1,
     SubscribeAll = 2,
+    MaxCalls = 3,
+    LexicalId = 4,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -20,6 +22,8 @@ pub struct ServiceInfo {
     version: u32,
     type_id: Option<TypeId>,
     subscribe_all: Option<bool>,
+    max_calls: Option<u32>,
+    lexical_id: Option<LexicalId>,
 }
 
 impl ServiceInfo {
@@ -28,6 +32,8 @@ impl ServiceInfo {
             version,
             type_id: None,
             subscribe_all: None,
+            max_calls: None,
+            lexical_id: None,
         }
     }
 
@@ -60,15 +66,37 @@ impl ServiceInfo {
         self.subscribe_all = Some(subscribe_all);
         self
     }
+
+    pub fn max_calls(self) -> Option<u32> {
+        self.max_calls
+    }
+
+    #[must_use = "this method follows the builder pattern and returns a new `ServiceInfo`"]
+    pub fn set_max_calls(mut self, max_calls: u32) -> Self {
+        self.max_calls = Some(max_calls);
+        self
+    }
+
+    pub fn lexical_id(self) -> Option<LexicalId> {
+        self.lexical_id
+    }
+
+    #[must_use = "this method follows the builder pattern and returns a new `ServiceInfo`"]
+    pub fn set_lexical_id(mut self, lexical_id: LexicalId) -> Self {
+        self.lexical_id = Some(lexical_id);
+        self
+    }
 }
 
 impl Serialize for ServiceInfo {
     fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
-        let mut serializer = serializer.serialize_struct(3)?;
+        let mut serializer = serializer.serialize_struct(5)?;
 
         serializer.serialize_field(ServiceInfoField::Version, &self.version)?;
         serializer.serialize_field(ServiceInfoField::TypeId, &self.type_id)?;
         serializer.serialize_field(ServiceInfoField::SubscribeAll, &self.subscribe_all)?;
+        serializer.serialize_field(ServiceInfoField::MaxCalls, &self.max_calls)?;
+        serializer.serialize_field(ServiceInfoField::LexicalId, &self.lexical_id)?;
 
         serializer.finish()
     }
@@ -81,6 +109,8 @@ impl Deserialize for ServiceInfo {
         let mut version = None;
         let mut type_id = None;
         let mut subscribe_all = None;
+        let mut max_calls = None;
+        let mut lexical_id = None;
 
         while deserializer.has_more_fields() {
             let deserializer = deserializer.deserialize_field()?;
@@ -89,6 +119,8 @@ impl Deserialize for ServiceInfo {
                 Ok(ServiceInfoField::Version) => version = deserializer.deserialize().map(Some)?,
                 Ok(ServiceInfoField::TypeId) => type_id = deserializer.deserialize()?,
                 Ok(ServiceInfoField::SubscribeAll) => subscribe_all = deserializer.deserialize()?,
+                Ok(ServiceInfoField::MaxCalls) => max_calls = deserializer.deserialize()?,
+                Ok(ServiceInfoField::LexicalId) => lexical_id = deserializer.deserialize()?,
                 Err(_) => deserializer.skip()?,
             }
         }
@@ -98,6 +130,8 @@ impl Deserialize for ServiceInfo {
                 version: version.ok_or(DeserializeError::InvalidSerialization)?,
                 type_id,
                 subscribe_all,
+                max_calls,
+                lexical_id,
             })
         })
     }
@@ -117,7 +151,7 @@ impl AsSerializeArg for ServiceInfo {
 #[cfg(test)]
 mod test {
     use super::ServiceInfo;
-    use crate::ids::TypeId;
+    use crate::ids::{LexicalId, TypeId};
     use crate::serialized_value::SerializedValue;
     use uuid::uuid;
 
@@ -144,5 +178,12 @@ mod test {
             .set_type_id(TypeId(uuid!("88e82fb9-03b2-4f51-94d8-4702cfacc90c")))
             .set_subscribe_all(true);
         assert_eq!(info, serde(info));
+
+        let info = ServiceInfo::new(1).set_max_calls(4);
+        assert_eq!(info, serde(info));
+
+        let info = ServiceInfo::new(1)
+            .set_lexical_id(LexicalId(uuid!("3b8d5a4e-2d0c-4d6f-8c1e-7e6a9b2f4d10")));
+        assert_eq!(info, serde(info));
     }
 }
@@ -1,17 +1,29 @@
-use crate::message::{Message, MessageOps, Packetizer};
+#[cfg(unix)]
+mod unix;
+
+use crate::compression::Compression;
+use crate::framing::{self, FramingError, BACKPRESSURE_BOUNDARY, INITIAL_CAPACITY};
+use crate::message::{Message, Packetizer};
 use crate::message_deserializer::MessageDeserializeError;
 use crate::message_serializer::MessageSerializeError;
 use crate::transport::AsyncTransport;
+use crate::wire_format::WireFormat;
 use bytes::{Buf, BytesMut};
 use pin_project_lite::pin_project;
 use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+#[cfg(unix)]
+use std::path::Path;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use thiserror::Error;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+#[cfg(unix)]
+use tokio::net::unix::UCred;
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
 
-const INITIAL_CAPACITY: usize = 8 * 1024;
-const BACKPRESSURE_BOUNDARY: usize = INITIAL_CAPACITY;
+#[cfg(unix)]
+pub use unix::TokioUnixTransport;
 
 pin_project! {
     #[derive(Debug)]
@@ -20,6 +32,12 @@ pin_project! {
         io: T,
         packetizer: Packetizer,
         write_buf: BytesMut,
+        compression_threshold: Option<usize>,
+        compression: Option<Compression>,
+        wire_formats: Vec<WireFormat>,
+        wire_format: Option<WireFormat>,
+        max_message_size: Option<usize>,
+        peer_max_message_size: Option<usize>,
     }
 }
 
@@ -29,8 +47,111 @@ impl<T> TokioTransport<T> {
             io,
             packetizer: Packetizer::new(),
             write_buf: BytesMut::with_capacity(INITIAL_CAPACITY),
+            compression_threshold: None,
+            compression: None,
+            wire_formats: Vec::new(),
+            wire_format: None,
+            max_message_size: None,
+            peer_max_message_size: None,
         }
     }
+
+    /// Returns a reference to the underlying IO object.
+    pub fn get_ref(&self) -> &T {
+        &self.io
+    }
+
+    /// Sets the size threshold, at which messages are compressed.
+    ///
+    /// Compression is disabled by default. Setting a threshold makes the transport offer all
+    /// [supported algorithms](Compression::SUPPORTED) during the connection handshake. If one is
+    /// negotiated, then all messages of at least `threshold` bytes are compressed. Smaller messages
+    /// are always sent uncompressed, as are messages, that don't get smaller by compressing them.
+    ///
+    /// This must be set before connecting. `None` disables compression again.
+    pub fn set_compression_threshold(&mut self, threshold: Option<usize>) {
+        self.compression_threshold = threshold;
+    }
+
+    /// Returns the size threshold, at which messages are compressed.
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+
+    /// Returns the negotiated compression algorithm.
+    pub fn compression(&self) -> Option<Compression> {
+        self.compression
+    }
+
+    /// Sets the alternative wire formats, that the transport supports.
+    ///
+    /// Messages are encoded in the binary format by default. Setting alternative formats makes
+    /// clients offer them during the connection handshake, ordered by preference. On the broker's
+    /// side, these are the formats, that clients are allowed to choose from. Formats, that are not
+    /// [supported](WireFormat::SUPPORTED) with the enabled Cargo features, are ignored.
+    ///
+    /// Compression is never applied to messages in an alternative wire format.
+    ///
+    /// This must be set before connecting.
+    pub fn set_wire_formats(&mut self, formats: impl IntoIterator<Item = WireFormat>) {
+        self.wire_formats = formats
+            .into_iter()
+            .filter(|format| format.is_supported())
+            .collect();
+    }
+
+    /// Returns the alternative wire formats, that the transport supports.
+    pub fn wire_formats(&self) -> &[WireFormat] {
+        &self.wire_formats
+    }
+
+    /// Returns the negotiated wire format.
+    ///
+    /// `None` indicates the binary format.
+    pub fn wire_format(&self) -> Option<WireFormat> {
+        self.wire_format
+    }
+
+    /// Sets the maximum size of received messages.
+    ///
+    /// There is no limit by default. The limit is advertised to the peer during the connection
+    /// handshake. Receiving a larger message fails with [`TokioTransportError::MessageTooLarge`]
+    /// as soon as its length is known, without buffering it. For compressed messages, the limit
+    /// applies to the decompressed size as well.
+    ///
+    /// Limits larger than [`u32::MAX`] are equivalent to no limit. This must be set before
+    /// connecting.
+    pub fn set_max_message_size(&mut self, size: Option<usize>) {
+        self.max_message_size = size;
+    }
+
+    /// Returns the maximum size of received messages.
+    pub fn max_message_size(&self) -> Option<usize> {
+        self.max_message_size
+    }
+
+    /// Returns the maximum size of messages, that the peer accepts.
+    ///
+    /// This is known only after the connection handshake. Sending a larger message fails with
+    /// [`TokioTransportError::MessageTooLarge`].
+    pub fn peer_max_message_size(&self) -> Option<usize> {
+        self.peer_max_message_size
+    }
+}
+
+#[cfg(unix)]
+impl TokioTransport<UnixStream> {
+    /// Connects to a Unix domain socket.
+    pub async fn connect_unix(path: impl AsRef<Path>) -> Result<Self, IoError> {
+        UnixStream::connect(path).await.map(Self::new)
+    }
+
+    /// Returns the credentials of the process on the other end of the socket.
+    ///
+    /// The credentials are those, that were in effect when the socket was connected.
+    pub fn peer_credentials(&self) -> Result<PeerCredentials, IoError> {
+        self.io.peer_cred().map(PeerCredentials::from_ucred)
+    }
 }
 
 impl<T> AsyncTransport for TokioTransport<T>
@@ -43,12 +164,18 @@ where
         let mut this = self.project();
 
         loop {
-            if let Some(buf) = this.packetizer.next_message() {
-                return Poll::Ready(
-                    Message::deserialize_message(buf).map_err(TokioTransportError::Deserialize),
-                );
+            if let Some(msg) =
+                framing::next_message(this.packetizer, *this.wire_format, *this.max_message_size)
+            {
+                return Poll::Ready(msg.map_err(Into::into));
             }
 
+            framing::check_incomplete_size(
+                this.packetizer,
+                *this.wire_format,
+                *this.max_message_size,
+            )?;
+
             let mut read_buf = ReadBuf::uninit(this.packetizer.spare_capacity_mut());
             match this.io.as_mut().poll_read(cx, &mut read_buf) {
                 Poll::Ready(Ok(())) if read_buf.filled().is_empty() => {
@@ -82,9 +209,13 @@ where
     fn send_start(self: Pin<&mut Self>, msg: Message) -> Result<(), Self::Error> {
         let this = self.project();
 
-        let msg = msg
-            .serialize_message()
-            .map_err(TokioTransportError::Serialize)?;
+        let msg = framing::pack_message(
+            msg,
+            *this.wire_format,
+            *this.compression,
+            *this.compression_threshold,
+            *this.peer_max_message_size,
+        )?;
 
         if this.write_buf.is_empty() {
             *this.write_buf = msg;
@@ -115,6 +246,34 @@ where
 
         this.io.poll_flush(cx).map_err(TokioTransportError::Io)
     }
+
+    fn supported_compression(&self) -> &[Compression] {
+        if self.compression_threshold.is_some() {
+            Compression::SUPPORTED
+        } else {
+            &[]
+        }
+    }
+
+    fn enable_compression(self: Pin<&mut Self>, compression: Compression) {
+        *self.project().compression = Some(compression);
+    }
+
+    fn supported_wire_formats(&self) -> &[WireFormat] {
+        &self.wire_formats
+    }
+
+    fn enable_wire_format(self: Pin<&mut Self>, format: WireFormat) {
+        *self.project().wire_format = Some(format);
+    }
+
+    fn max_message_size(&self) -> Option<usize> {
+        self.max_message_size
+    }
+
+    fn set_peer_max_message_size(self: Pin<&mut Self>, size: usize) {
+        *self.project().peer_max_message_size = Some(size);
+    }
 }
 
 #[derive(Error, Debug)]
@@ -127,4 +286,102 @@ pub enum TokioTransportError {
 
     #[error(transparent)]
     Deserialize(#[from] MessageDeserializeError),
+
+    /// A message exceeded the maximum size.
+    #[error("message of {0} bytes exceeds the maximum size")]
+    MessageTooLarge(usize),
+}
+
+impl From<FramingError> for TokioTransportError {
+    fn from(e: FramingError) -> Self {
+        match e {
+            FramingError::Serialize(e) => Self::Serialize(e),
+            FramingError::Deserialize(e) => Self::Deserialize(e),
+            FramingError::MessageTooLarge(len) => Self::MessageTooLarge(len),
+        }
+    }
+}
+
+/// Credentials of the peer of a Unix domain socket.
+///
+/// See [`TokioTransport::peer_credentials`].
+#[cfg(unix)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PeerCredentials {
+    uid: u32,
+    gid: u32,
+    pid: Option<i32>,
+}
+
+#[cfg(unix)]
+impl PeerCredentials {
+    fn from_ucred(cred: UCred) -> Self {
+        Self {
+            uid: cred.uid(),
+            gid: cred.gid(),
+            pid: cred.pid(),
+        }
+    }
+
+    /// Returns the user id of the peer.
+    pub fn uid(self) -> u32 {
+        self.uid
+    }
+
+    /// Returns the group id of the peer.
+    pub fn gid(self) -> u32 {
+        self.gid
+    }
+
+    /// Returns the process id of the peer.
+    ///
+    /// Not all platforms provide the process id.
+    pub fn pid(self) -> Option<i32> {
+        self.pid
+    }
+}
+
+/// Listener for Unix domain socket connections.
+///
+/// This is a thin wrapper around Tokio's [`UnixListener`], that returns connections as
+/// [`TokioTransport`s](TokioTransport). Brokers can use
+/// [`peer_credentials`](TokioTransport::peer_credentials) to authenticate local clients.
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct TokioUnixListener {
+    listener: UnixListener,
+}
+
+#[cfg(unix)]
+impl TokioUnixListener {
+    /// Binds a new listener to a path.
+    pub fn bind(path: impl AsRef<Path>) -> Result<Self, IoError> {
+        UnixListener::bind(path).map(Self::from_listener)
+    }
+
+    /// Creates a listener from a bound Unix domain socket.
+    pub fn from_listener(listener: UnixListener) -> Self {
+        Self { listener }
+    }
+
+    /// Accepts a new connection.
+    pub async fn accept(&self) -> Result<TokioTransport<UnixStream>, IoError> {
+        self.listener
+            .accept()
+            .await
+            .map(|(stream, _)| TokioTransport::new(stream))
+    }
+
+    /// Accepts a new connection, that supports passing file descriptors.
+    pub async fn accept_fd_passing(&self) -> Result<TokioUnixTransport, IoError> {
+        self.listener
+            .accept()
+            .await
+            .map(|(stream, _)| TokioUnixTransport::new(stream))
+    }
+
+    /// Returns the underlying Unix domain socket.
+    pub fn listener(&self) -> &UnixListener {
+        &self.listener
+    }
 }
@@ -0,0 +1,452 @@
+use super::{PeerCredentials, TokioTransportError};
+use crate::compression::{self, Compression};
+use crate::fd::{Fd, MAX_FDS};
+use crate::framing::{check_incomplete_size, check_size, BACKPRESSURE_BOUNDARY, INITIAL_CAPACITY};
+use crate::message::{Message, MessageDeserializeError, MessageOps, Packetizer};
+use crate::transport::AsyncTransport;
+use bytes::{Buf, BufMut, BytesMut};
+use std::collections::VecDeque;
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
+use std::mem::{self, MaybeUninit};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::path::Path;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{ready, Context, Poll};
+use tokio::io::Interest;
+use tokio::net::UnixStream;
+
+/// Marker in place of the message kind, which identifies frames with file descriptors.
+const FD_FRAME: u8 = 0xfe;
+
+/// 4 bytes frame length + 1 byte marker + 1 byte number of file descriptors.
+const FD_HEADER_LEN: usize = 6;
+
+/// Size of the buffer for control messages.
+///
+/// This is large enough for [`MAX_FDS`] file descriptors.
+const CMSG_BUF_LEN: usize = 1536;
+
+/// Buffer for control messages, that is suitably aligned for `cmsghdr`.
+#[repr(C, align(8))]
+struct CmsgBuf([u8; CMSG_BUF_LEN]);
+
+/// Unix domain socket transport, that supports passing file descriptors.
+///
+/// This transport behaves like [`TokioTransport<UnixStream>`](super::TokioTransport), but
+/// additionally passes the [`Fd`s](Fd) attached to values to the peer with `SCM_RIGHTS`. File
+/// descriptors are only sent after fd passing has been negotiated during the connection handshake.
+/// Otherwise, they are silently dropped.
+#[derive(Debug)]
+pub struct TokioUnixTransport {
+    io: UnixStream,
+    packetizer: Packetizer,
+    recv_fds: VecDeque<Fd>,
+    write_buf: BytesMut,
+    write_pos: u64,
+    send_fds: VecDeque<(u64, Vec<Fd>)>,
+    compression_threshold: Option<usize>,
+    compression: Option<Compression>,
+    fd_passing: bool,
+    max_message_size: Option<usize>,
+    peer_max_message_size: Option<usize>,
+}
+
+impl TokioUnixTransport {
+    /// Creates a new transport from a connected Unix domain socket.
+    pub fn new(io: UnixStream) -> Self {
+        Self {
+            io,
+            packetizer: Packetizer::new(),
+            recv_fds: VecDeque::new(),
+            write_buf: BytesMut::with_capacity(INITIAL_CAPACITY),
+            write_pos: 0,
+            send_fds: VecDeque::new(),
+            compression_threshold: None,
+            compression: None,
+            fd_passing: false,
+            max_message_size: None,
+            peer_max_message_size: None,
+        }
+    }
+
+    /// Connects to a Unix domain socket.
+    pub async fn connect(path: impl AsRef<Path>) -> Result<Self, IoError> {
+        UnixStream::connect(path).await.map(Self::new)
+    }
+
+    /// Returns a reference to the underlying socket.
+    pub fn get_ref(&self) -> &UnixStream {
+        &self.io
+    }
+
+    /// Returns the credentials of the process on the other end of the socket.
+    ///
+    /// See [`TokioTransport::peer_credentials`](super::TokioTransport::peer_credentials).
+    pub fn peer_credentials(&self) -> Result<PeerCredentials, IoError> {
+        self.io.peer_cred().map(PeerCredentials::from_ucred)
+    }
+
+    /// Sets the size threshold, at which messages are compressed.
+    ///
+    /// See [`TokioTransport::set_compression_threshold`](super::TokioTransport::set_compression_threshold).
+    pub fn set_compression_threshold(&mut self, threshold: Option<usize>) {
+        self.compression_threshold = threshold;
+    }
+
+    /// Returns the size threshold, at which messages are compressed.
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+
+    /// Returns the negotiated compression algorithm.
+    pub fn compression(&self) -> Option<Compression> {
+        self.compression
+    }
+
+    /// Indicates whether passing file descriptors has been negotiated.
+    pub fn fd_passing(&self) -> bool {
+        self.fd_passing
+    }
+
+    /// Sets the maximum size of received messages.
+    ///
+    /// See [`TokioTransport::set_max_message_size`](super::TokioTransport::set_max_message_size).
+    pub fn set_max_message_size(&mut self, size: Option<usize>) {
+        self.max_message_size = size;
+    }
+
+    /// Returns the maximum size of received messages.
+    pub fn max_message_size(&self) -> Option<usize> {
+        self.max_message_size
+    }
+
+    /// Returns the maximum size of messages, that the peer accepts.
+    pub fn peer_max_message_size(&self) -> Option<usize> {
+        self.peer_max_message_size
+    }
+
+    fn unpack_message(&mut self, mut buf: BytesMut) -> Result<Message, TokioTransportError> {
+        check_size(buf.len(), self.max_message_size)?;
+
+        let mut num_fds = 0;
+
+        if buf.get(4) == Some(&FD_FRAME) {
+            if buf.len() < FD_HEADER_LEN {
+                return Err(MessageDeserializeError::UnexpectedEoi.into());
+            }
+
+            num_fds = buf[5] as usize;
+            buf.advance(FD_HEADER_LEN);
+
+            if (buf.len() < 4) || ((&buf[0..4]).get_u32_le() as usize != buf.len()) {
+                return Err(MessageDeserializeError::InvalidSerialization.into());
+            }
+        }
+
+        if compression::is_compressed(&buf) {
+            if let Some(len) = compression::decompressed_len(&buf) {
+                check_size(len, self.max_message_size)?;
+            }
+
+            buf = compression::decompress(buf)?;
+        }
+
+        let mut msg = Message::deserialize_message(buf)?;
+
+        if num_fds > 0 {
+            if num_fds > self.recv_fds.len() {
+                return Err(MessageDeserializeError::InvalidSerialization.into());
+            }
+
+            let fds = self.recv_fds.drain(..num_fds).collect();
+
+            msg.fd_value_mut()
+                .ok_or(MessageDeserializeError::InvalidSerialization)?
+                .set_fds(fds);
+        }
+
+        Ok(msg)
+    }
+
+    /// Returns the number of leading file descriptor lists and the number of bytes, that can be
+    /// sent with the next `sendmsg` call.
+    fn next_send(&self) -> (usize, usize) {
+        let mut num_fds = 0;
+
+        for (i, (start, fds)) in self.send_fds.iter().enumerate() {
+            if num_fds + fds.len() > MAX_FDS {
+                return (i, (start - self.write_pos) as usize);
+            }
+
+            num_fds += fds.len();
+        }
+
+        (self.send_fds.len(), self.write_buf.len())
+    }
+}
+
+impl AsyncTransport for TokioUnixTransport {
+    type Error = TokioTransportError;
+
+    fn receive_poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Message, Self::Error>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(buf) = this.packetizer.next_message() {
+                return Poll::Ready(this.unpack_message(buf));
+            }
+
+            check_incomplete_size(&this.packetizer, None, this.max_message_size)?;
+
+            ready!(this.io.poll_read_ready(cx))?;
+
+            let sock = this.io.as_raw_fd();
+            let buf = this.packetizer.spare_capacity_mut();
+
+            match this.io.try_io(Interest::READABLE, || {
+                recv_with_fds(sock, buf, &mut this.recv_fds)
+            }) {
+                Ok(0) => {
+                    return Poll::Ready(Err(TokioTransportError::Io(
+                        IoErrorKind::UnexpectedEof.into(),
+                    )))
+                }
+
+                Ok(len) => {
+                    // SAFETY: The first len bytes have been initialized.
+                    unsafe {
+                        this.packetizer.bytes_written(len);
+                    }
+                }
+
+                Err(e) if e.kind() == IoErrorKind::WouldBlock => {}
+                Err(e) => return Poll::Ready(Err(TokioTransportError::Io(e))),
+            }
+        }
+    }
+
+    fn send_poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        if self.write_buf.len() >= BACKPRESSURE_BOUNDARY {
+            self.send_poll_flush(cx)
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn send_start(self: Pin<&mut Self>, mut msg: Message) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+
+        let fds = if this.fd_passing {
+            msg.fd_value_mut()
+                .map(|value| value.take_fds())
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        let mut msg = msg.serialize_message()?;
+
+        if let (Some(compression), Some(threshold)) = (this.compression, this.compression_threshold)
+        {
+            if msg.len() >= threshold {
+                if let Some(compressed) = compression::compress(compression, &msg) {
+                    msg = compressed;
+                }
+            }
+        }
+
+        if fds.is_empty() {
+            check_size(msg.len(), this.peer_max_message_size)?;
+        } else {
+            check_size(FD_HEADER_LEN + msg.len(), this.peer_max_message_size)?;
+        }
+
+        if !fds.is_empty() {
+            let start = this.write_pos + this.write_buf.len() as u64;
+            let frame_len = (FD_HEADER_LEN + msg.len()) as u32;
+
+            this.write_buf.reserve(FD_HEADER_LEN + msg.len());
+            this.write_buf.put_u32_le(frame_len);
+            this.write_buf.put_u8(FD_FRAME);
+            this.write_buf.put_u8(fds.len() as u8);
+            this.write_buf.extend_from_slice(&msg);
+            this.send_fds.push_back((start, fds));
+        } else if this.write_buf.is_empty() {
+            this.write_buf = msg;
+        } else {
+            this.write_buf.extend_from_slice(&msg);
+        }
+
+        Ok(())
+    }
+
+    fn send_poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+
+        while !this.write_buf.is_empty() {
+            ready!(this.io.poll_write_ready(cx))?;
+
+            let (num_lists, len) = this.next_send();
+            let fds = this
+                .send_fds
+                .iter()
+                .take(num_lists)
+                .flat_map(|(_, fds)| fds)
+                .map(AsRawFd::as_raw_fd)
+                .collect::<Vec<_>>();
+
+            let sock = this.io.as_raw_fd();
+            let buf = &this.write_buf[..len];
+
+            match this
+                .io
+                .try_io(Interest::WRITABLE, || send_with_fds(sock, buf, &fds))
+            {
+                Ok(0) => {
+                    return Poll::Ready(Err(TokioTransportError::Io(
+                        IoErrorKind::WriteZero.into(),
+                    )));
+                }
+
+                Ok(n) => {
+                    this.write_buf.advance(n);
+                    this.write_pos += n as u64;
+                    this.send_fds.drain(..num_lists);
+                }
+
+                Err(e) if e.kind() == IoErrorKind::WouldBlock => {}
+                Err(e) => return Poll::Ready(Err(TokioTransportError::Io(e))),
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn supported_compression(&self) -> &[Compression] {
+        if self.compression_threshold.is_some() {
+            Compression::SUPPORTED
+        } else {
+            &[]
+        }
+    }
+
+    fn enable_compression(self: Pin<&mut Self>, compression: Compression) {
+        self.get_mut().compression = Some(compression);
+    }
+
+    fn supports_fd_passing(&self) -> bool {
+        true
+    }
+
+    fn enable_fd_passing(self: Pin<&mut Self>) {
+        self.get_mut().fd_passing = true;
+    }
+
+    fn max_message_size(&self) -> Option<usize> {
+        self.max_message_size
+    }
+
+    fn set_peer_max_message_size(self: Pin<&mut Self>, size: usize) {
+        self.get_mut().peer_max_message_size = Some(size);
+    }
+}
+
+fn send_with_fds(sock: RawFd, buf: &[u8], fds: &[RawFd]) -> IoResult<usize> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_ptr().cast_mut().cast(),
+        iov_len: buf.len(),
+    };
+
+    let mut cmsg_buf = CmsgBuf([0; CMSG_BUF_LEN]);
+
+    // SAFETY: All-zero is a valid msghdr.
+    let mut msg = unsafe { mem::zeroed::<libc::msghdr>() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+
+    if !fds.is_empty() {
+        let fds_len = mem::size_of_val(fds) as u32;
+
+        // SAFETY: CMSG_SPACE has no safety requirements.
+        let cmsg_space = unsafe { libc::CMSG_SPACE(fds_len) } as usize;
+        assert!(cmsg_space <= cmsg_buf.0.len());
+
+        msg.msg_control = cmsg_buf.0.as_mut_ptr().cast();
+        msg.msg_controllen = cmsg_space as _;
+
+        // SAFETY: msg_control points to a buffer large enough for one control message with fds.
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = libc::SOL_SOCKET;
+            (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(fds_len) as _;
+            ptr::copy_nonoverlapping(fds.as_ptr(), libc::CMSG_DATA(cmsg).cast(), fds.len());
+        }
+    }
+
+    // SAFETY: msg and all buffers it points to are valid. The kernel does not write to iov_base.
+    let res = unsafe { libc::sendmsg(sock, &msg, libc::MSG_NOSIGNAL) };
+
+    if res >= 0 {
+        Ok(res as usize)
+    } else {
+        Err(IoError::last_os_error())
+    }
+}
+
+fn recv_with_fds(
+    sock: RawFd,
+    buf: &mut [MaybeUninit<u8>],
+    recv_fds: &mut VecDeque<Fd>,
+) -> IoResult<usize> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+
+    let mut cmsg_buf = CmsgBuf([0; CMSG_BUF_LEN]);
+
+    // SAFETY: All-zero is a valid msghdr.
+    let mut msg = unsafe { mem::zeroed::<libc::msghdr>() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = cmsg_buf.0.as_mut_ptr().cast();
+    msg.msg_controllen = cmsg_buf.0.len() as _;
+
+    // SAFETY: msg and all buffers it points to are valid.
+    let res = unsafe { libc::recvmsg(sock, &mut msg, libc::MSG_CMSG_CLOEXEC) };
+
+    if res < 0 {
+        return Err(IoError::last_os_error());
+    }
+
+    // SAFETY: The kernel has filled in the control messages in msg_control.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+
+        while !cmsg.is_null() {
+            if ((*cmsg).cmsg_level == libc::SOL_SOCKET) && ((*cmsg).cmsg_type == libc::SCM_RIGHTS) {
+                let data = libc::CMSG_DATA(cmsg);
+                let len = (*cmsg).cmsg_len as usize - (data as usize - cmsg as usize);
+
+                for i in 0..(len / mem::size_of::<RawFd>()) {
+                    let fd = data.cast::<RawFd>().add(i).read_unaligned();
+                    recv_fds.push_back(Fd::new(OwnedFd::from_raw_fd(fd)));
+                }
+            }
+
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+
+    // File descriptors have been lost. Later messages would be matched with the wrong ones.
+    if (msg.msg_flags & libc::MSG_CTRUNC) != 0 {
+        return Err(IoError::new(
+            IoErrorKind::InvalidData,
+            "file descriptors have been truncated",
+        ));
+    }
+
+    Ok(res as usize)
+}
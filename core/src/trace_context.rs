@@ -0,0 +1,114 @@
+use core::fmt;
+
+/// Distributed tracing context of a function call or event.
+///
+/// A trace context can optionally accompany function calls and events. It identifies the trace and
+/// the span on the sending side, such that the receiver can continue the trace. The layout follows
+/// the [W3C Trace Context](https://www.w3.org/TR/trace-context/) specification.
+///
+/// Trace contexts require protocol version 1.19. Brokers don't forward them to clients with older
+/// protocol versions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub struct TraceContext {
+    /// Id of the trace.
+    pub trace_id: u128,
+
+    /// Id of the span on the sending side.
+    pub span_id: u64,
+
+    /// Indicates whether the sender records the trace.
+    pub sampled: bool,
+}
+
+impl TraceContext {
+    #[cfg(feature = "std")]
+    pub(crate) const SERIALIZED_LEN: usize = 25;
+
+    const FLAG_SAMPLED: u8 = 0x01;
+
+    /// Creates a new trace context.
+    pub fn new(trace_id: u128, span_id: u64, sampled: bool) -> Self {
+        Self {
+            trace_id,
+            span_id,
+            sampled,
+        }
+    }
+
+    /// Creates a child context, which is part of the same trace.
+    pub fn child(self, span_id: u64) -> Self {
+        Self { span_id, ..self }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn to_bytes(self) -> [u8; Self::SERIALIZED_LEN] {
+        let mut bytes = [0; Self::SERIALIZED_LEN];
+
+        bytes[0..16].copy_from_slice(&self.trace_id.to_be_bytes());
+        bytes[16..24].copy_from_slice(&self.span_id.to_be_bytes());
+
+        if self.sampled {
+            bytes[24] = Self::FLAG_SAMPLED;
+        }
+
+        bytes
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn from_bytes(bytes: [u8; Self::SERIALIZED_LEN]) -> Self {
+        // Unknown flags are ignored, as required by the specification.
+        Self {
+            trace_id: u128::from_be_bytes(bytes[0..16].try_into().unwrap()),
+            span_id: u64::from_be_bytes(bytes[16..24].try_into().unwrap()),
+            sampled: (bytes[24] & Self::FLAG_SAMPLED) != 0,
+        }
+    }
+}
+
+/// Formats the context as a W3C `traceparent` header value.
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let flags = if self.sampled { Self::FLAG_SAMPLED } else { 0 };
+        write!(
+            f,
+            "00-{:032x}-{:016x}-{flags:02x}",
+            self.trace_id, self.span_id
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TraceContext;
+
+    #[test]
+    fn traceparent() {
+        let ctx = TraceContext::new(0x4bf92f3577b34da6a3ce929d0e0e4736, 0x00f067aa0ba902b7, true);
+        assert_eq!(
+            ctx.to_string(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+
+        let ctx = ctx.child(1);
+        assert_eq!(
+            ctx.to_string(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000001-01"
+        );
+    }
+
+    #[test]
+    fn ignore_unknown_flags() {
+        let mut bytes = TraceContext::new(1, 2, false).to_bytes();
+        bytes[24] = 0xfe;
+        assert_eq!(
+            TraceContext::from_bytes(bytes),
+            TraceContext::new(1, 2, false)
+        );
+    }
+}
@@ -1,4 +1,6 @@
+use crate::compression::Compression;
 use crate::message::Message;
+use crate::wire_format::WireFormat;
 use pin_project_lite::pin_project;
 use std::fmt;
 use std::future::Future;
@@ -81,6 +83,93 @@ pub trait AsyncTransport {
     ///
     /// Flushing must deliver _all_ prior [`Message`s](Message) to the remote end of the transport.
     fn send_poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>>;
+
+    /// Returns the compression algorithms, that this transport supports.
+    ///
+    /// The algorithms should be ordered by preference. Clients offer them to the broker during the
+    /// connection handshake and the broker then selects the first one, that its transport
+    /// supports as well.
+    ///
+    /// The default implementation returns an empty slice, i.e. compression is not supported.
+    fn supported_compression(&self) -> &[Compression] {
+        &[]
+    }
+
+    /// Enables compression of sent messages.
+    ///
+    /// This method is called after the connection handshake, if a compression algorithm was
+    /// negotiated. `compression` is always one of the algorithms returned by
+    /// [`supported_compression`](AsyncTransport::supported_compression). Received messages may be
+    /// compressed from this point on as well.
+    ///
+    /// The default implementation does nothing.
+    fn enable_compression(self: Pin<&mut Self>, compression: Compression) {
+        let _ = compression;
+    }
+
+    /// Indicates whether the transport supports passing file descriptors.
+    ///
+    /// Clients and the broker negotiate file descriptor passing during the connection handshake.
+    /// It is used only if the transports on both ends of a connection support it.
+    ///
+    /// The default implementation returns `false`.
+    fn supports_fd_passing(&self) -> bool {
+        false
+    }
+
+    /// Enables passing file descriptors.
+    ///
+    /// This method is called after the connection handshake, if file descriptor passing was
+    /// negotiated. Before that, file descriptors attached to values of sent messages must be
+    /// dropped.
+    ///
+    /// The default implementation does nothing.
+    fn enable_fd_passing(self: Pin<&mut Self>) {}
+
+    /// Returns the alternative wire formats, that this transport supports.
+    ///
+    /// The formats should be ordered by preference. Clients offer them to the broker during the
+    /// connection handshake and the broker then selects the first one, that its transport
+    /// supports as well. The binary format is always supported and used, if no alternative format
+    /// is negotiated.
+    ///
+    /// The default implementation returns an empty slice.
+    fn supported_wire_formats(&self) -> &[WireFormat] {
+        &[]
+    }
+
+    /// Switches to an alternative wire format.
+    ///
+    /// This method is called after the connection handshake, if an alternative wire format was
+    /// negotiated. `format` is always one of the formats returned by
+    /// [`supported_wire_formats`](AsyncTransport::supported_wire_formats). All messages must be
+    /// sent and received in this format from this point on.
+    ///
+    /// The default implementation does nothing.
+    fn enable_wire_format(self: Pin<&mut Self>, format: WireFormat) {
+        let _ = format;
+    }
+
+    /// Returns the maximum size of messages, that this transport accepts.
+    ///
+    /// Clients and the broker advertise their limits to each other during the connection
+    /// handshake. Larger messages should be rejected with an error as early as possible, without
+    /// allocating memory for them.
+    ///
+    /// The default implementation returns `None`, i.e. messages of any size are accepted.
+    fn max_message_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Sets the maximum size of messages, that the peer accepts.
+    ///
+    /// This method is called after the connection handshake, if the peer advertised a limit.
+    /// Sending larger messages should fail with an error from this point on.
+    ///
+    /// The default implementation does nothing.
+    fn set_peer_max_message_size(self: Pin<&mut Self>, size: usize) {
+        let _ = size;
+    }
 }
 
 impl<T> AsyncTransport for Pin<T>
@@ -105,6 +194,38 @@ where
     fn send_poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
         self.get_mut().as_mut().send_poll_flush(cx)
     }
+
+    fn supported_compression(&self) -> &[Compression] {
+        (**self).supported_compression()
+    }
+
+    fn enable_compression(self: Pin<&mut Self>, compression: Compression) {
+        self.get_mut().as_mut().enable_compression(compression)
+    }
+
+    fn supports_fd_passing(&self) -> bool {
+        (**self).supports_fd_passing()
+    }
+
+    fn enable_fd_passing(self: Pin<&mut Self>) {
+        self.get_mut().as_mut().enable_fd_passing()
+    }
+
+    fn supported_wire_formats(&self) -> &[WireFormat] {
+        (**self).supported_wire_formats()
+    }
+
+    fn enable_wire_format(self: Pin<&mut Self>, format: WireFormat) {
+        self.get_mut().as_mut().enable_wire_format(format)
+    }
+
+    fn max_message_size(&self) -> Option<usize> {
+        (**self).max_message_size()
+    }
+
+    fn set_peer_max_message_size(self: Pin<&mut Self>, size: usize) {
+        self.get_mut().as_mut().set_peer_max_message_size(size)
+    }
 }
 
 impl<T> AsyncTransport for Box<T>
@@ -137,6 +258,38 @@ where
     ) -> Poll<Result<(), Self::Error>> {
         Pin::new(&mut **self).send_poll_flush(cx)
     }
+
+    fn supported_compression(&self) -> &[Compression] {
+        (**self).supported_compression()
+    }
+
+    fn enable_compression(mut self: Pin<&mut Self>, compression: Compression) {
+        Pin::new(&mut **self).enable_compression(compression)
+    }
+
+    fn supports_fd_passing(&self) -> bool {
+        (**self).supports_fd_passing()
+    }
+
+    fn enable_fd_passing(mut self: Pin<&mut Self>) {
+        Pin::new(&mut **self).enable_fd_passing()
+    }
+
+    fn supported_wire_formats(&self) -> &[WireFormat] {
+        (**self).supported_wire_formats()
+    }
+
+    fn enable_wire_format(mut self: Pin<&mut Self>, format: WireFormat) {
+        Pin::new(&mut **self).enable_wire_format(format)
+    }
+
+    fn max_message_size(&self) -> Option<usize> {
+        (**self).max_message_size()
+    }
+
+    fn set_peer_max_message_size(mut self: Pin<&mut Self>, size: usize) {
+        Pin::new(&mut **self).set_peer_max_message_size(size)
+    }
 }
 
 impl<T> AsyncTransport for &mut T
@@ -169,6 +322,38 @@ where
     ) -> Poll<Result<(), Self::Error>> {
         T::send_poll_flush(Pin::new(&mut **self), cx)
     }
+
+    fn supported_compression(&self) -> &[Compression] {
+        (**self).supported_compression()
+    }
+
+    fn enable_compression(mut self: Pin<&mut Self>, compression: Compression) {
+        T::enable_compression(Pin::new(&mut **self), compression)
+    }
+
+    fn supports_fd_passing(&self) -> bool {
+        (**self).supports_fd_passing()
+    }
+
+    fn enable_fd_passing(mut self: Pin<&mut Self>) {
+        T::enable_fd_passing(Pin::new(&mut **self))
+    }
+
+    fn supported_wire_formats(&self) -> &[WireFormat] {
+        (**self).supported_wire_formats()
+    }
+
+    fn enable_wire_format(mut self: Pin<&mut Self>, format: WireFormat) {
+        T::enable_wire_format(Pin::new(&mut **self), format)
+    }
+
+    fn max_message_size(&self) -> Option<usize> {
+        (**self).max_message_size()
+    }
+
+    fn set_peer_max_message_size(mut self: Pin<&mut Self>, size: usize) {
+        T::set_peer_max_message_size(Pin::new(&mut **self), size)
+    }
 }
 
 pub trait AsyncTransportExt: AsyncTransport {
@@ -402,4 +587,36 @@ where
         let this = self.project();
         this.transport.send_poll_flush(cx).map_err(this.map_err)
     }
+
+    fn supported_compression(&self) -> &[Compression] {
+        self.transport.supported_compression()
+    }
+
+    fn enable_compression(self: Pin<&mut Self>, compression: Compression) {
+        self.project().transport.enable_compression(compression)
+    }
+
+    fn supports_fd_passing(&self) -> bool {
+        self.transport.supports_fd_passing()
+    }
+
+    fn enable_fd_passing(self: Pin<&mut Self>) {
+        self.project().transport.enable_fd_passing()
+    }
+
+    fn supported_wire_formats(&self) -> &[WireFormat] {
+        self.transport.supported_wire_formats()
+    }
+
+    fn enable_wire_format(self: Pin<&mut Self>, format: WireFormat) {
+        self.project().transport.enable_wire_format(format)
+    }
+
+    fn max_message_size(&self) -> Option<usize> {
+        self.transport.max_message_size()
+    }
+
+    fn set_peer_max_message_size(self: Pin<&mut Self>, size: usize) {
+        self.project().transport.set_peer_max_message_size(size)
+    }
 }
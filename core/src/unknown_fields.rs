@@ -0,0 +1,172 @@
+use crate::error::{DeserializeError, SerializeError};
+use crate::serialized_value::{SerializedValue, SerializedValueSlice};
+use crate::value_deserializer::{Deserialize, Deserializer};
+use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+use alloc::collections::btree_map::{self, BTreeMap};
+
+/// Struct fields, that are unknown to the receiver.
+///
+/// `UnknownFields` maps field ids to their still serialized values. It is the type of fallback
+/// fields, which are marked with `#[aldrin(fallback)]`. When a struct with a fallback field is
+/// serialized, all unknown fields are written back bit-for-bit, which allows intermediaries to
+/// forward values of newer schema versions without losing data. Fields, whose id collides with a
+/// known field of the struct, are skipped.
+///
+/// Fields are kept ordered by their id.
+///
+/// On its own, `UnknownFields` serializes and deserializes as a struct, whose fields are all
+/// unknown.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin_core::{SerializedValue, UnknownFields};
+///
+/// let mut unknown = UnknownFields::new();
+/// unknown.insert(1, SerializedValue::serialize(&42u32).unwrap());
+/// assert_eq!(unknown.deserialize::<u32>(1).unwrap().unwrap(), 42);
+///
+/// let serialized = SerializedValue::serialize(&unknown).unwrap();
+/// let deserialized = serialized.deserialize::<UnknownFields>().unwrap();
+/// assert_eq!(deserialized, unknown);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnknownFields {
+    fields: BTreeMap<u32, SerializedValue>,
+}
+
+impl UnknownFields {
+    /// Creates an empty set of unknown fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates `UnknownFields` from a map of ids to serialized values.
+    pub fn from_parts(fields: BTreeMap<u32, SerializedValue>) -> Self {
+        Self { fields }
+    }
+
+    /// Returns the map of ids to serialized values.
+    pub fn into_parts(self) -> BTreeMap<u32, SerializedValue> {
+        self.fields
+    }
+
+    /// Returns the number of fields.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns the number of fields, whose id is not in `known_ids`.
+    pub fn len_except(&self, known_ids: &[u32]) -> usize {
+        self.fields
+            .keys()
+            .filter(|id| !known_ids.contains(id))
+            .count()
+    }
+
+    /// Indicates whether there are no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Indicates whether a field with the id `id` exists.
+    pub fn contains(&self, id: u32) -> bool {
+        self.fields.contains_key(&id)
+    }
+
+    /// Returns the serialized value of the field `id`.
+    pub fn get(&self, id: u32) -> Option<&SerializedValueSlice> {
+        self.fields.get(&id).map(|value| &**value)
+    }
+
+    /// Deserializes the value of the field `id`.
+    pub fn deserialize<T: Deserialize>(&self, id: u32) -> Option<Result<T, DeserializeError>> {
+        self.fields.get(&id).map(|value| value.deserialize())
+    }
+
+    /// Inserts a field.
+    ///
+    /// Returns the previous value of the field, if any.
+    pub fn insert(&mut self, id: u32, value: SerializedValue) -> Option<SerializedValue> {
+        self.fields.insert(id, value)
+    }
+
+    /// Removes a field.
+    pub fn remove(&mut self, id: u32) -> Option<SerializedValue> {
+        self.fields.remove(&id)
+    }
+
+    /// Returns an iterator over all fields, ordered by their id.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (u32, &SerializedValueSlice)> + '_ {
+        self.fields.iter().map(|(&id, value)| (id, &**value))
+    }
+}
+
+impl Serialize for UnknownFields {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        let mut serializer = serializer.serialize_struct(self.fields.len())?;
+        serializer.serialize_unknown_fields(self, &[])?;
+        serializer.finish()
+    }
+}
+
+impl Deserialize for UnknownFields {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        let mut deserializer = deserializer.deserialize_struct()?;
+        let mut unknown_fields = Self::new();
+
+        while deserializer.has_more_fields() {
+            deserializer
+                .deserialize_field()?
+                .add_to_unknown_fields(&mut unknown_fields)?;
+        }
+
+        deserializer.finish(unknown_fields)
+    }
+}
+
+impl AsSerializeArg for UnknownFields {
+    type SerializeArg<'a> = &'a Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        self
+    }
+}
+
+impl From<BTreeMap<u32, SerializedValue>> for UnknownFields {
+    fn from(fields: BTreeMap<u32, SerializedValue>) -> Self {
+        Self::from_parts(fields)
+    }
+}
+
+impl FromIterator<(u32, SerializedValue)> for UnknownFields {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (u32, SerializedValue)>,
+    {
+        Self {
+            fields: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<(u32, SerializedValue)> for UnknownFields {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (u32, SerializedValue)>,
+    {
+        self.fields.extend(iter);
+    }
+}
+
+impl IntoIterator for UnknownFields {
+    type Item = (u32, SerializedValue);
+    type IntoIter = btree_map::IntoIter<u32, SerializedValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.into_iter()
+    }
+}
@@ -0,0 +1,84 @@
+use crate::error::{DeserializeError, SerializeError};
+use crate::serialized_value::{SerializedValue, SerializedValueSlice};
+use crate::value_deserializer::{Deserialize, Deserializer};
+use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+
+/// Enum variant, that is unknown to the receiver.
+///
+/// `UnknownVariant` holds the id and the still serialized value of an enum variant. It is the type
+/// of fallback variants, which are marked with `#[aldrin(fallback)]`. Serializing an
+/// `UnknownVariant` reproduces the original variant bit-for-bit, which allows intermediaries to
+/// forward values of newer schema versions without losing data.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin_core::{SerializedValue, UnknownVariant};
+///
+/// let value = SerializedValue::serialize("bar").unwrap();
+/// let unknown = UnknownVariant::from_parts(1, value);
+/// assert_eq!(unknown.deserialize::<String>().unwrap(), "bar");
+///
+/// let serialized = SerializedValue::serialize(&unknown).unwrap();
+/// let deserialized = serialized.deserialize::<UnknownVariant>().unwrap();
+/// assert_eq!(deserialized, unknown);
+///
+/// let (id, value) = deserialized.into_parts();
+/// assert_eq!(id, 1);
+/// assert_eq!(value.deserialize::<String>().unwrap(), "bar");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVariant {
+    id: u32,
+    value: SerializedValue,
+}
+
+impl UnknownVariant {
+    /// Creates an `UnknownVariant` from an id and a serialized value.
+    pub fn from_parts(id: u32, value: SerializedValue) -> Self {
+        Self { id, value }
+    }
+
+    /// Splits the `UnknownVariant` into its id and serialized value.
+    pub fn into_parts(self) -> (u32, SerializedValue) {
+        (self.id, self.value)
+    }
+
+    /// Returns the id of the variant.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the serialized value of the variant.
+    pub fn value(&self) -> &SerializedValueSlice {
+        &self.value
+    }
+
+    /// Deserializes the value of the variant.
+    pub fn deserialize<T: Deserialize>(&self) -> Result<T, DeserializeError> {
+        self.value.deserialize()
+    }
+}
+
+impl Serialize for UnknownVariant {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        serializer.serialize_enum(self.id, &self.value)
+    }
+}
+
+impl Deserialize for UnknownVariant {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer.deserialize_enum()?.into_unknown_variant()
+    }
+}
+
+impl AsSerializeArg for UnknownVariant {
+    type SerializeArg<'a> = &'a Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        self
+    }
+}
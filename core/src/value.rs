@@ -11,13 +11,20 @@ use crate::introspection::{
 use crate::serialize_key::SerializeKey;
 use crate::value_deserializer::{Deserialize, Deserializer};
 use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+use alloc::borrow::{Cow, ToOwned};
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, LinkedList, VecDeque};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::convert::Infallible;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+use core::mem::MaybeUninit;
+use core::ops::Deref;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use std::borrow::{Borrow, Cow};
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
-use std::convert::Infallible;
-use std::hash::{BuildHasher, Hash};
-use std::mem::MaybeUninit;
-use std::ops::Deref;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 #[derive(
@@ -72,6 +79,11 @@ pub enum ValueKind {
 
 /// Wrapper for `Vec<u8>` to enable `Serialize` and `Deserialize` specializations.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Bytes(pub Vec<u8>);
 
 impl Bytes {
@@ -1231,7 +1243,7 @@ impl Serialize for bytes::Bytes {
 
 impl Deserialize for bytes::Bytes {
     fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
-        deserializer.deserialize_bytes_to_vec().map(Self::from)
+        deserializer.deserialize_bytes_to_bytes()
     }
 }
 
@@ -1267,9 +1279,10 @@ impl Serialize for bytes::BytesMut {
 
 impl Deserialize for bytes::BytesMut {
     fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
-        // This is inefficient, but bytes doesn't currently offer a better alternative.
-        let vec = deserializer.deserialize_bytes_to_vec()?;
-        Ok(Self::from(vec.as_slice()))
+        let mut deserializer = deserializer.deserialize_bytes()?;
+        let mut bytes = Self::zeroed(deserializer.len());
+        deserializer.deserialize(&mut bytes)?;
+        deserializer.finish(bytes)
     }
 }
 
@@ -1297,12 +1310,14 @@ impl Introspectable for bytes::BytesMut {
     fn add_references(_references: &mut References) {}
 }
 
+#[cfg(feature = "std")]
 impl<K: SerializeKey, V: Serialize, S> Serialize for HashMap<K, V, S> {
     fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
         serializer.serialize_map_iter(self)
     }
 }
 
+#[cfg(feature = "std")]
 impl<K, V, S> Deserialize for HashMap<K, V, S>
 where
     K: DeserializeKey + Eq + Hash,
@@ -1314,6 +1329,7 @@ where
     }
 }
 
+#[cfg(feature = "std")]
 impl<K: SerializeKey, V: Serialize, S> AsSerializeArg for HashMap<K, V, S> {
     type SerializeArg<'a>
         = &'a Self
@@ -1388,12 +1404,14 @@ impl<K: KeyTypeOf, V: Introspectable> Introspectable for BTreeMap<K, V> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: SerializeKey, S> Serialize for HashSet<T, S> {
     fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
         serializer.serialize_set_iter(self)
     }
 }
 
+#[cfg(feature = "std")]
 impl<T, S> Deserialize for HashSet<T, S>
 where
     T: DeserializeKey + Eq + Hash,
@@ -1404,6 +1422,7 @@ where
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: SerializeKey, S> AsSerializeArg for HashSet<T, S> {
     type SerializeArg<'a>
         = &'a Self
@@ -1,5 +1,6 @@
 use super::{ByteSlice, Bytes, Skip};
 use crate::error::{DeserializeError, SerializeError};
+use crate::fd::FdList;
 use crate::generic_value::{Enum, Struct, Value};
 use crate::ids::{
     ChannelCookie, ObjectCookie, ObjectId, ObjectUuid, ServiceCookie, ServiceId, ServiceUuid,
@@ -31,7 +32,7 @@ fn assert_deserialize_eq<T: Deserialize + PartialEq + Debug, B: AsRef<[u8]>>(
     // skip
     let mut buf = serialized.as_ref();
     Deserializer::new(&mut buf, 0).unwrap().skip().unwrap();
-    assert_eq!(*buf, []);
+    assert!(buf.is_empty());
     assert_eq!(serialized_value.deserialize(), Ok(Skip));
 
     // len
@@ -714,7 +715,7 @@ fn test_u8_hash_map() {
     let value = HashMap::<u8, u8>::from_iter([(0, 1), (2, 3)]);
     let mut buf = bytes::BytesMut::new();
     value
-        .serialize(Serializer::new(&mut buf, 0).unwrap())
+        .serialize(Serializer::new(&mut buf, &mut FdList::new(), 0).unwrap())
         .unwrap();
     assert!((buf[..] == serialized1) || (buf[..] == serialized2));
     assert_deserialize_eq(&value, serialized1);
@@ -723,7 +724,7 @@ fn test_u8_hash_map() {
     let value = Value::U8Map(HashMap::from_iter([(0, Value::U8(1)), (2, Value::U8(3))]));
     let mut buf = bytes::BytesMut::new();
     value
-        .serialize(Serializer::new(&mut buf, 0).unwrap())
+        .serialize(Serializer::new(&mut buf, &mut FdList::new(), 0).unwrap())
         .unwrap();
     assert!((buf[..] == serialized1) || (buf[..] == serialized2));
     assert_deserialize_eq(&value, serialized1);
@@ -958,7 +959,7 @@ fn test_u8_hash_set() {
     let value = HashSet::<_>::from_iter([3u8, 4]);
     let mut buf = bytes::BytesMut::new();
     value
-        .serialize(Serializer::new(&mut buf, 0).unwrap())
+        .serialize(Serializer::new(&mut buf, &mut FdList::new(), 0).unwrap())
         .unwrap();
     assert!((buf[..] == serialized1) || (buf[..] == serialized2));
     assert_deserialize_eq(&value, serialized1);
@@ -967,7 +968,7 @@ fn test_u8_hash_set() {
     let value = Value::U8Set(value);
     let mut buf = bytes::BytesMut::new();
     value
-        .serialize(Serializer::new(&mut buf, 0).unwrap())
+        .serialize(Serializer::new(&mut buf, &mut FdList::new(), 0).unwrap())
         .unwrap();
     assert!((buf[..] == serialized1) || (buf[..] == serialized2));
     assert_deserialize_eq(&value, serialized1);
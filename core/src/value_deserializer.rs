@@ -1,30 +1,66 @@
 use crate::buf_ext::ValueBufExt;
 use crate::deserialize_key::{DeserializeKey, Sealed as _};
 use crate::error::DeserializeError;
+#[cfg(all(unix, feature = "std"))]
+use crate::fd::Fd;
+use crate::fd::FdList;
 use crate::ids::{
     ChannelCookie, ObjectCookie, ObjectId, ObjectUuid, ServiceCookie, ServiceId, ServiceUuid,
 };
 use crate::serialized_value::SerializedValueSlice;
+use crate::unknown_fields::UnknownFields;
+use crate::unknown_variant::UnknownVariant;
 use crate::value::ValueKind;
 use crate::MAX_VALUE_DEPTH;
-use bytes::Buf;
-use std::iter;
-use std::marker::PhantomData;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use bytes::{Buf, Bytes};
+use core::iter;
+use core::marker::PhantomData;
 use uuid::Uuid;
 
 pub trait Deserialize: Sized {
     fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError>;
 }
 
+/// Owner of the buffer, from which a value is deserialized.
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct Owner<'a> {
+    /// Buffer, that [`Bytes`] can refer to instead of copying from it.
+    pub bytes: Option<&'a Bytes>,
+
+    /// File descriptors attached to the value.
+    #[cfg_attr(not(all(unix, feature = "std")), allow(dead_code))]
+    pub fds: Option<&'a FdList>,
+}
+
 #[derive(Debug)]
 pub struct Deserializer<'a, 'b> {
     buf: &'a mut &'b [u8],
+    owner: Owner<'b>,
     depth: u8,
 }
 
 impl<'a, 'b> Deserializer<'a, 'b> {
     pub(crate) fn new(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
-        let mut this = Self { buf, depth };
+        Self::with_owner(buf, Owner::default(), depth)
+    }
+
+    /// Creates a deserializer, whose `buf` is a part of `owner`.
+    ///
+    /// Bytes can then be deserialized into [`Bytes`] without copying them.
+    pub(crate) fn with_owner(
+        buf: &'a mut &'b [u8],
+        owner: Owner<'b>,
+        depth: u8,
+    ) -> Result<Self, DeserializeError> {
+        debug_assert!(owner.bytes.map_or(true, |owner| {
+            let range = owner.as_ptr_range();
+            range.contains(&buf.as_ptr()) || (buf.is_empty() && (buf.as_ptr() == range.end))
+        }));
+
+        let mut this = Self { buf, owner, depth };
         this.increment_depth()?;
         Ok(this)
     }
@@ -79,37 +115,51 @@ impl<'a, 'b> Deserializer<'a, 'b> {
             ValueKind::Uuid | ValueKind::Sender | ValueKind::Receiver => self.buf.try_skip(16),
             ValueKind::ObjectId => self.buf.try_skip(32),
             ValueKind::ServiceId => self.buf.try_skip(64),
-            ValueKind::Vec => VecDeserializer::new_without_value_kind(self.buf, self.depth)?.skip(),
-            ValueKind::Bytes => BytesDeserializer::new_without_value_kind(self.buf)?.skip_all(),
+            ValueKind::Vec => {
+                VecDeserializer::new_without_value_kind(self.buf, self.owner, self.depth)?.skip()
+            }
+            ValueKind::Bytes => {
+                BytesDeserializer::new_without_value_kind(self.buf, self.owner)?.skip_all()
+            }
             ValueKind::U8Map => {
-                MapDeserializer::<u8>::new_without_value_kind(self.buf, self.depth)?.skip()
+                MapDeserializer::<u8>::new_without_value_kind(self.buf, self.owner, self.depth)?
+                    .skip()
             }
             ValueKind::I8Map => {
-                MapDeserializer::<i8>::new_without_value_kind(self.buf, self.depth)?.skip()
+                MapDeserializer::<i8>::new_without_value_kind(self.buf, self.owner, self.depth)?
+                    .skip()
             }
             ValueKind::U16Map => {
-                MapDeserializer::<u16>::new_without_value_kind(self.buf, self.depth)?.skip()
+                MapDeserializer::<u16>::new_without_value_kind(self.buf, self.owner, self.depth)?
+                    .skip()
             }
             ValueKind::I16Map => {
-                MapDeserializer::<i16>::new_without_value_kind(self.buf, self.depth)?.skip()
+                MapDeserializer::<i16>::new_without_value_kind(self.buf, self.owner, self.depth)?
+                    .skip()
             }
             ValueKind::U32Map => {
-                MapDeserializer::<u32>::new_without_value_kind(self.buf, self.depth)?.skip()
+                MapDeserializer::<u32>::new_without_value_kind(self.buf, self.owner, self.depth)?
+                    .skip()
             }
             ValueKind::I32Map => {
-                MapDeserializer::<i32>::new_without_value_kind(self.buf, self.depth)?.skip()
+                MapDeserializer::<i32>::new_without_value_kind(self.buf, self.owner, self.depth)?
+                    .skip()
             }
             ValueKind::U64Map => {
-                MapDeserializer::<u64>::new_without_value_kind(self.buf, self.depth)?.skip()
+                MapDeserializer::<u64>::new_without_value_kind(self.buf, self.owner, self.depth)?
+                    .skip()
             }
             ValueKind::I64Map => {
-                MapDeserializer::<i64>::new_without_value_kind(self.buf, self.depth)?.skip()
+                MapDeserializer::<i64>::new_without_value_kind(self.buf, self.owner, self.depth)?
+                    .skip()
             }
             ValueKind::StringMap => {
-                MapDeserializer::<String>::new_without_value_kind(self.buf, self.depth)?.skip()
+                MapDeserializer::<String>::new_without_value_kind(self.buf, self.owner, self.depth)?
+                    .skip()
             }
             ValueKind::UuidMap => {
-                MapDeserializer::<Uuid>::new_without_value_kind(self.buf, self.depth)?.skip()
+                MapDeserializer::<Uuid>::new_without_value_kind(self.buf, self.owner, self.depth)?
+                    .skip()
             }
             ValueKind::U8Set => SetDeserializer::<u8>::new_without_value_kind(self.buf)?.skip(),
             ValueKind::I8Set => SetDeserializer::<i8>::new_without_value_kind(self.buf)?.skip(),
@@ -124,10 +174,10 @@ impl<'a, 'b> Deserializer<'a, 'b> {
             }
             ValueKind::UuidSet => SetDeserializer::<Uuid>::new_without_value_kind(self.buf)?.skip(),
             ValueKind::Struct => {
-                StructDeserializer::new_without_value_kind(self.buf, self.depth)?.skip()
+                StructDeserializer::new_without_value_kind(self.buf, self.owner, self.depth)?.skip()
             }
             ValueKind::Enum => {
-                EnumDeserializer::new_without_value_kind(self.buf, self.depth)?.skip()
+                EnumDeserializer::new_without_value_kind(self.buf, self.owner, self.depth)?.skip()
             }
         }
     }
@@ -257,7 +307,7 @@ impl<'a, 'b> Deserializer<'a, 'b> {
     }
 
     pub fn deserialize_vec(self) -> Result<VecDeserializer<'a, 'b>, DeserializeError> {
-        VecDeserializer::new(self.buf, self.depth)
+        VecDeserializer::new(self.buf, self.owner, self.depth)
     }
 
     pub fn deserialize_vec_extend<V, T>(self, vec: &mut V) -> Result<(), DeserializeError>
@@ -279,17 +329,21 @@ impl<'a, 'b> Deserializer<'a, 'b> {
     }
 
     pub fn deserialize_bytes(self) -> Result<BytesDeserializer<'a, 'b>, DeserializeError> {
-        BytesDeserializer::new(self.buf)
+        BytesDeserializer::new(self.buf, self.owner)
     }
 
     pub fn deserialize_bytes_to_vec(self) -> Result<Vec<u8>, DeserializeError> {
-        BytesDeserializer::new(self.buf)?.deserialize_all_to_vec()
+        BytesDeserializer::new(self.buf, self.owner)?.deserialize_all_to_vec()
+    }
+
+    pub fn deserialize_bytes_to_bytes(self) -> Result<Bytes, DeserializeError> {
+        BytesDeserializer::new(self.buf, self.owner)?.deserialize_all_to_bytes()
     }
 
     pub fn deserialize_map<K: DeserializeKey>(
         self,
     ) -> Result<MapDeserializer<'a, 'b, K>, DeserializeError> {
-        MapDeserializer::new(self.buf, self.depth)
+        MapDeserializer::new(self.buf, self.owner, self.depth)
     }
 
     pub fn deserialize_map_extend<T, K, V>(self, map: &mut T) -> Result<(), DeserializeError>
@@ -298,7 +352,7 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         K: DeserializeKey,
         V: Deserialize,
     {
-        MapDeserializer::new(self.buf, self.depth)?.deserialize_extend(map)
+        MapDeserializer::new(self.buf, self.owner, self.depth)?.deserialize_extend(map)
     }
 
     pub fn deserialize_map_extend_new<T, K, V>(self) -> Result<T, DeserializeError>
@@ -308,7 +362,7 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         V: Deserialize,
     {
         let mut map = T::default();
-        MapDeserializer::new(self.buf, self.depth)?.deserialize_extend(&mut map)?;
+        MapDeserializer::new(self.buf, self.owner, self.depth)?.deserialize_extend(&mut map)?;
         Ok(map)
     }
 
@@ -337,11 +391,11 @@ impl<'a, 'b> Deserializer<'a, 'b> {
     }
 
     pub fn deserialize_struct(self) -> Result<StructDeserializer<'a, 'b>, DeserializeError> {
-        StructDeserializer::new(self.buf, self.depth)
+        StructDeserializer::new(self.buf, self.owner, self.depth)
     }
 
     pub fn deserialize_enum(self) -> Result<EnumDeserializer<'a, 'b>, DeserializeError> {
-        EnumDeserializer::new(self.buf, self.depth)
+        EnumDeserializer::new(self.buf, self.owner, self.depth)
     }
 
     pub fn deserialize_sender(self) -> Result<ChannelCookie, DeserializeError> {
@@ -357,25 +411,49 @@ impl<'a, 'b> Deserializer<'a, 'b> {
         self.buf.try_copy_to_slice(&mut bytes)?;
         Ok(ChannelCookie(Uuid::from_bytes(bytes)))
     }
+
+    /// Deserializes a file descriptor.
+    ///
+    /// This fails with [`DeserializeError::MissingFd`] if the file descriptor isn't attached to the
+    /// value.
+    #[cfg(all(unix, feature = "std"))]
+    pub fn deserialize_fd(self) -> Result<Fd, DeserializeError> {
+        let fds = self.owner.fds;
+        let index = self.deserialize_u32()?;
+
+        fds.and_then(|fds| fds.get(index))
+            .cloned()
+            .ok_or(DeserializeError::MissingFd)
+    }
 }
 
 #[derive(Debug)]
 pub struct VecDeserializer<'a, 'b> {
     buf: &'a mut &'b [u8],
+    owner: Owner<'b>,
     len: u32,
     depth: u8,
 }
 
 impl<'a, 'b> VecDeserializer<'a, 'b> {
-    fn new(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
+    fn new(buf: &'a mut &'b [u8], owner: Owner<'b>, depth: u8) -> Result<Self, DeserializeError> {
         buf.ensure_discriminant_u8(ValueKind::Vec)?;
-        Self::new_without_value_kind(buf, depth)
+        Self::new_without_value_kind(buf, owner, depth)
     }
 
-    fn new_without_value_kind(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
+    fn new_without_value_kind(
+        buf: &'a mut &'b [u8],
+        owner: Owner<'b>,
+        depth: u8,
+    ) -> Result<Self, DeserializeError> {
         let len = buf.try_get_varint_u32_le()?;
 
-        Ok(Self { buf, len, depth })
+        Ok(Self {
+            buf,
+            owner,
+            len,
+            depth,
+        })
     }
 
     pub fn len(&self) -> usize {
@@ -394,7 +472,7 @@ impl<'a, 'b> VecDeserializer<'a, 'b> {
             Err(DeserializeError::NoMoreElements)
         } else {
             self.len -= 1;
-            T::deserialize(Deserializer::new(self.buf, self.depth)?)
+            T::deserialize(Deserializer::with_owner(self.buf, self.owner, self.depth)?)
         }
     }
 
@@ -416,7 +494,7 @@ impl<'a, 'b> VecDeserializer<'a, 'b> {
             Err(DeserializeError::NoMoreElements)
         } else {
             self.len -= 1;
-            Deserializer::new(self.buf, self.depth)?.skip()
+            Deserializer::with_owner(self.buf, self.owner, self.depth)?.skip()
         }
     }
 
@@ -464,18 +542,22 @@ impl<'a, 'b> VecDeserializer<'a, 'b> {
 #[derive(Debug)]
 pub struct BytesDeserializer<'a, 'b> {
     buf: &'a mut &'b [u8],
+    owner: Owner<'b>,
     len: u32,
 }
 
 impl<'a, 'b> BytesDeserializer<'a, 'b> {
-    fn new(buf: &'a mut &'b [u8]) -> Result<Self, DeserializeError> {
+    fn new(buf: &'a mut &'b [u8], owner: Owner<'b>) -> Result<Self, DeserializeError> {
         buf.ensure_discriminant_u8(ValueKind::Bytes)?;
-        Self::new_without_value_kind(buf)
+        Self::new_without_value_kind(buf, owner)
     }
 
-    fn new_without_value_kind(buf: &'a mut &'b [u8]) -> Result<Self, DeserializeError> {
+    fn new_without_value_kind(
+        buf: &'a mut &'b [u8],
+        owner: Owner<'b>,
+    ) -> Result<Self, DeserializeError> {
         let len = buf.try_get_varint_u32_le()?;
-        Ok(Self { buf, len })
+        Ok(Self { buf, owner, len })
     }
 
     pub fn len(&self) -> usize {
@@ -497,19 +579,47 @@ impl<'a, 'b> BytesDeserializer<'a, 'b> {
     }
 
     pub fn deserialize_to_vec(&mut self, len: usize) -> Result<Vec<u8>, DeserializeError> {
+        self.split_to(len).map(ToOwned::to_owned)
+    }
+
+    pub fn deserialize_all_to_vec(mut self) -> Result<Vec<u8>, DeserializeError> {
+        self.deserialize_to_vec(self.len as usize)
+    }
+
+    /// Deserializes `len` bytes into [`Bytes`].
+    ///
+    /// If the value was deserialized with
+    /// [`SerializedValue::deserialize_shared`](crate::SerializedValue::deserialize_shared), then
+    /// the returned [`Bytes`] refer to the same memory and no copy is made. Otherwise, the bytes
+    /// are copied.
+    pub fn deserialize_to_bytes(&mut self, len: usize) -> Result<Bytes, DeserializeError> {
+        let owner = self.owner.bytes;
+        let bytes = self.split_to(len)?;
+
+        match owner {
+            Some(owner) => Ok(owner.slice_ref(bytes)),
+            None => Ok(Bytes::copy_from_slice(bytes)),
+        }
+    }
+
+    /// Deserializes all remaining bytes into [`Bytes`].
+    ///
+    /// See [`deserialize_to_bytes`](Self::deserialize_to_bytes) for when the bytes are copied.
+    pub fn deserialize_all_to_bytes(mut self) -> Result<Bytes, DeserializeError> {
+        self.deserialize_to_bytes(self.len as usize)
+    }
+
+    fn split_to(&mut self, len: usize) -> Result<&'b [u8], DeserializeError> {
         if self.len as usize >= len {
-            let bytes = self.buf.try_copy_to_bytes(self.len as usize)?;
+            let bytes = self.buf.get(..len).ok_or(DeserializeError::UnexpectedEoi)?;
+            self.buf.advance(len);
             self.len -= len as u32;
-            Ok(Vec::from(bytes))
+            Ok(bytes)
         } else {
             Err(DeserializeError::NoMoreElements)
         }
     }
 
-    pub fn deserialize_all_to_vec(mut self) -> Result<Vec<u8>, DeserializeError> {
-        self.deserialize_to_vec(self.len as usize)
-    }
-
     pub fn skip(&mut self, len: usize) -> Result<(), DeserializeError> {
         if self.len as usize >= len {
             self.buf.try_skip(len)?;
@@ -560,22 +670,28 @@ impl<'a, 'b> BytesDeserializer<'a, 'b> {
 #[derive(Debug)]
 pub struct MapDeserializer<'a, 'b, K: DeserializeKey> {
     buf: &'a mut &'b [u8],
+    owner: Owner<'b>,
     len: u32,
     depth: u8,
     _key: PhantomData<K>,
 }
 
 impl<'a, 'b, K: DeserializeKey> MapDeserializer<'a, 'b, K> {
-    fn new(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
+    fn new(buf: &'a mut &'b [u8], owner: Owner<'b>, depth: u8) -> Result<Self, DeserializeError> {
         K::Impl::deserialize_map_value_kind(buf)?;
-        Self::new_without_value_kind(buf, depth)
+        Self::new_without_value_kind(buf, owner, depth)
     }
 
-    fn new_without_value_kind(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
+    fn new_without_value_kind(
+        buf: &'a mut &'b [u8],
+        owner: Owner<'b>,
+        depth: u8,
+    ) -> Result<Self, DeserializeError> {
         let len = buf.try_get_varint_u32_le()?;
 
         Ok(Self {
             buf,
+            owner,
             len,
             depth,
             _key: PhantomData,
@@ -597,7 +713,7 @@ impl<'a, 'b, K: DeserializeKey> MapDeserializer<'a, 'b, K> {
             Err(DeserializeError::NoMoreElements)
         } else {
             self.len -= 1;
-            ElementDeserializer::new(self.buf, self.depth)
+            ElementDeserializer::new(self.buf, self.owner, self.depth)
         }
     }
 
@@ -620,7 +736,7 @@ impl<'a, 'b, K: DeserializeKey> MapDeserializer<'a, 'b, K> {
         } else {
             self.len -= 1;
             K::Impl::skip(self.buf)?;
-            Deserializer::new(self.buf, self.depth)?.skip()
+            Deserializer::with_owner(self.buf, self.owner, self.depth)?.skip()
         }
     }
 
@@ -668,15 +784,21 @@ impl<'a, 'b, K: DeserializeKey> MapDeserializer<'a, 'b, K> {
 #[derive(Debug)]
 pub struct ElementDeserializer<'a, 'b, K: DeserializeKey> {
     buf: &'a mut &'b [u8],
+    owner: Owner<'b>,
     key: K,
     depth: u8,
 }
 
 impl<'a, 'b, K: DeserializeKey> ElementDeserializer<'a, 'b, K> {
-    fn new(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
+    fn new(buf: &'a mut &'b [u8], owner: Owner<'b>, depth: u8) -> Result<Self, DeserializeError> {
         let key = K::Impl::deserialize_key(buf)?;
         let key = K::try_from_impl(key)?;
-        Ok(Self { buf, key, depth })
+        Ok(Self {
+            buf,
+            owner,
+            key,
+            depth,
+        })
     }
 
     pub fn key(&self) -> &K {
@@ -684,12 +806,12 @@ impl<'a, 'b, K: DeserializeKey> ElementDeserializer<'a, 'b, K> {
     }
 
     pub fn deserialize<T: Deserialize>(self) -> Result<(K, T), DeserializeError> {
-        let value = T::deserialize(Deserializer::new(self.buf, self.depth)?)?;
+        let value = T::deserialize(Deserializer::with_owner(self.buf, self.owner, self.depth)?)?;
         Ok((self.key, value))
     }
 
     pub fn skip(self) -> Result<(), DeserializeError> {
-        Deserializer::new(self.buf, self.depth)?.skip()
+        Deserializer::with_owner(self.buf, self.owner, self.depth)?.skip()
     }
 }
 
@@ -799,20 +921,26 @@ impl<'a, 'b, T: DeserializeKey> SetDeserializer<'a, 'b, T> {
 #[derive(Debug)]
 pub struct StructDeserializer<'a, 'b> {
     buf: &'a mut &'b [u8],
+    owner: Owner<'b>,
     num_fields: u32,
     depth: u8,
 }
 
 impl<'a, 'b> StructDeserializer<'a, 'b> {
-    fn new(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
+    fn new(buf: &'a mut &'b [u8], owner: Owner<'b>, depth: u8) -> Result<Self, DeserializeError> {
         buf.ensure_discriminant_u8(ValueKind::Struct)?;
-        Self::new_without_value_kind(buf, depth)
+        Self::new_without_value_kind(buf, owner, depth)
     }
 
-    fn new_without_value_kind(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
+    fn new_without_value_kind(
+        buf: &'a mut &'b [u8],
+        owner: Owner<'b>,
+        depth: u8,
+    ) -> Result<Self, DeserializeError> {
         let num_fields = buf.try_get_varint_u32_le()?;
         Ok(Self {
             buf,
+            owner,
             num_fields,
             depth,
         })
@@ -829,7 +957,7 @@ impl<'a, 'b> StructDeserializer<'a, 'b> {
     pub fn deserialize_field(&mut self) -> Result<FieldDeserializer<'_, 'b>, DeserializeError> {
         if self.has_more_fields() {
             self.num_fields -= 1;
-            FieldDeserializer::new(self.buf, self.depth)
+            FieldDeserializer::new(self.buf, self.owner, self.depth)
         } else {
             Err(DeserializeError::NoMoreElements)
         }
@@ -892,14 +1020,20 @@ impl<'a, 'b> StructDeserializer<'a, 'b> {
 #[derive(Debug)]
 pub struct FieldDeserializer<'a, 'b> {
     buf: &'a mut &'b [u8],
+    owner: Owner<'b>,
     id: u32,
     depth: u8,
 }
 
 impl<'a, 'b> FieldDeserializer<'a, 'b> {
-    fn new(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
+    fn new(buf: &'a mut &'b [u8], owner: Owner<'b>, depth: u8) -> Result<Self, DeserializeError> {
         let id = buf.try_get_varint_u32_le()?;
-        Ok(Self { buf, id, depth })
+        Ok(Self {
+            buf,
+            owner,
+            id,
+            depth,
+        })
     }
 
     pub fn id(&self) -> u32 {
@@ -913,31 +1047,47 @@ impl<'a, 'b> FieldDeserializer<'a, 'b> {
     }
 
     pub fn deserialize<T: Deserialize>(self) -> Result<T, DeserializeError> {
-        T::deserialize(Deserializer::new(self.buf, self.depth)?)
+        T::deserialize(Deserializer::with_owner(self.buf, self.owner, self.depth)?)
+    }
+
+    pub fn add_to_unknown_fields(
+        self,
+        unknown_fields: &mut UnknownFields,
+    ) -> Result<(), DeserializeError> {
+        let id = self.id;
+        let value = self.deserialize()?;
+        unknown_fields.insert(id, value);
+        Ok(())
     }
 
     pub fn skip(self) -> Result<(), DeserializeError> {
-        Deserializer::new(self.buf, self.depth)?.skip()
+        Deserializer::with_owner(self.buf, self.owner, self.depth)?.skip()
     }
 }
 
 #[derive(Debug)]
 pub struct EnumDeserializer<'a, 'b> {
     buf: &'a mut &'b [u8],
+    owner: Owner<'b>,
     variant: u32,
     depth: u8,
 }
 
 impl<'a, 'b> EnumDeserializer<'a, 'b> {
-    fn new(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
+    fn new(buf: &'a mut &'b [u8], owner: Owner<'b>, depth: u8) -> Result<Self, DeserializeError> {
         buf.ensure_discriminant_u8(ValueKind::Enum)?;
-        Self::new_without_value_kind(buf, depth)
+        Self::new_without_value_kind(buf, owner, depth)
     }
 
-    fn new_without_value_kind(buf: &'a mut &'b [u8], depth: u8) -> Result<Self, DeserializeError> {
+    fn new_without_value_kind(
+        buf: &'a mut &'b [u8],
+        owner: Owner<'b>,
+        depth: u8,
+    ) -> Result<Self, DeserializeError> {
         let variant = buf.try_get_varint_u32_le()?;
         Ok(Self {
             buf,
+            owner,
             variant,
             depth,
         })
@@ -954,10 +1104,16 @@ impl<'a, 'b> EnumDeserializer<'a, 'b> {
     }
 
     pub fn deserialize<T: Deserialize>(self) -> Result<T, DeserializeError> {
-        T::deserialize(Deserializer::new(self.buf, self.depth)?)
+        T::deserialize(Deserializer::with_owner(self.buf, self.owner, self.depth)?)
+    }
+
+    pub fn into_unknown_variant(self) -> Result<UnknownVariant, DeserializeError> {
+        let id = self.variant;
+        let value = self.deserialize()?;
+        Ok(UnknownVariant::from_parts(id, value))
     }
 
     pub fn skip(self) -> Result<(), DeserializeError> {
-        Deserializer::new(self.buf, self.depth)?.skip()
+        Deserializer::with_owner(self.buf, self.owner, self.depth)?.skip()
     }
 }
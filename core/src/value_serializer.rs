@@ -1,13 +1,17 @@
 use crate::buf_ext::BufMutExt;
 use crate::error::SerializeError;
+#[cfg(all(unix, feature = "std"))]
+use crate::fd::Fd;
+use crate::fd::FdList;
 use crate::ids::{ChannelCookie, ObjectId, ServiceId};
 use crate::serialize_key::{Sealed as _, SerializeKey};
 use crate::serialized_value::SerializedValueSlice;
+use crate::unknown_fields::UnknownFields;
 use crate::value::ValueKind;
 use crate::MAX_VALUE_DEPTH;
 use bytes::{BufMut, BytesMut};
-use std::fmt;
-use std::marker::PhantomData;
+use core::fmt;
+use core::marker::PhantomData;
 use uuid::Uuid;
 
 pub trait Serialize {
@@ -29,12 +33,17 @@ pub type SerializeArg<'a, T> = <T as AsSerializeArg>::SerializeArg<'a>;
 #[derive(Debug)]
 pub struct Serializer<'a> {
     buf: &'a mut BytesMut,
+    fds: &'a mut FdList,
     depth: u8,
 }
 
 impl<'a> Serializer<'a> {
-    pub(crate) fn new(buf: &'a mut BytesMut, depth: u8) -> Result<Self, SerializeError> {
-        let mut this = Self { buf, depth };
+    pub(crate) fn new(
+        buf: &'a mut BytesMut,
+        fds: &'a mut FdList,
+        depth: u8,
+    ) -> Result<Self, SerializeError> {
+        let mut this = Self { buf, fds, depth };
         this.increment_depth()?;
         Ok(this)
     }
@@ -151,7 +160,7 @@ impl<'a> Serializer<'a> {
     }
 
     pub fn serialize_vec(self, num_elems: usize) -> Result<VecSerializer<'a>, SerializeError> {
-        VecSerializer::new(self.buf, num_elems, self.depth)
+        VecSerializer::new(self.buf, self.fds, num_elems, self.depth)
     }
 
     pub fn serialize_vec_iter<T>(self, vec: T) -> Result<(), SerializeError>
@@ -184,7 +193,7 @@ impl<'a> Serializer<'a> {
         self,
         num_elems: usize,
     ) -> Result<MapSerializer<'a, K>, SerializeError> {
-        MapSerializer::new(self.buf, num_elems, self.depth)
+        MapSerializer::new(self.buf, self.fds, num_elems, self.depth)
     }
 
     pub fn serialize_map_iter<T, K, V>(self, map: T) -> Result<(), SerializeError>
@@ -231,7 +240,7 @@ impl<'a> Serializer<'a> {
         self,
         num_fields: usize,
     ) -> Result<StructSerializer<'a>, SerializeError> {
-        StructSerializer::new(self.buf, num_fields, self.depth)
+        StructSerializer::new(self.buf, self.fds, num_fields, self.depth)
     }
 
     pub fn serialize_enum<T: Serialize + ?Sized>(
@@ -254,22 +263,39 @@ impl<'a> Serializer<'a> {
         self.buf.put_discriminant_u8(ValueKind::Receiver);
         self.buf.put_slice(value.0.as_bytes());
     }
+
+    /// Serializes a file descriptor.
+    ///
+    /// The file descriptor is attached to the value and only its index is serialized as a `u32`.
+    #[cfg(all(unix, feature = "std"))]
+    pub fn serialize_fd(self, fd: &Fd) -> Result<(), SerializeError> {
+        let index = self.fds.push(fd.clone())?;
+        self.serialize_u32(index);
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
 pub struct VecSerializer<'a> {
     buf: &'a mut BytesMut,
+    fds: &'a mut FdList,
     num_elems: u32,
     depth: u8,
 }
 
 impl<'a> VecSerializer<'a> {
-    fn new(buf: &'a mut BytesMut, num_elems: usize, depth: u8) -> Result<Self, SerializeError> {
+    fn new(
+        buf: &'a mut BytesMut,
+        fds: &'a mut FdList,
+        num_elems: usize,
+        depth: u8,
+    ) -> Result<Self, SerializeError> {
         if num_elems <= u32::MAX as usize {
             buf.put_discriminant_u8(ValueKind::Vec);
             buf.put_varint_u32_le(num_elems as u32);
             Ok(Self {
                 buf,
+                fds,
                 num_elems: num_elems as u32,
                 depth,
             })
@@ -292,7 +318,7 @@ impl<'a> VecSerializer<'a> {
     ) -> Result<&mut Self, SerializeError> {
         if self.num_elems > 0 {
             self.num_elems -= 1;
-            value.serialize(Serializer::new(self.buf, self.depth)?)?;
+            value.serialize(Serializer::new(self.buf, self.fds, self.depth)?)?;
             Ok(self)
         } else {
             Err(SerializeError::TooManyElements)
@@ -357,19 +383,26 @@ impl<'a> BytesSerializer<'a> {
 
 pub struct MapSerializer<'a, K: SerializeKey + ?Sized> {
     buf: &'a mut BytesMut,
+    fds: &'a mut FdList,
     num_elems: u32,
     depth: u8,
     _key: PhantomData<K>,
 }
 
 impl<'a, K: SerializeKey + ?Sized> MapSerializer<'a, K> {
-    fn new(mut buf: &'a mut BytesMut, num_elems: usize, depth: u8) -> Result<Self, SerializeError> {
+    fn new(
+        mut buf: &'a mut BytesMut,
+        fds: &'a mut FdList,
+        num_elems: usize,
+        depth: u8,
+    ) -> Result<Self, SerializeError> {
         if num_elems <= u32::MAX as usize {
             K::Impl::serialize_map_value_kind(&mut buf);
             buf.put_varint_u32_le(num_elems as u32);
 
             Ok(Self {
                 buf,
+                fds,
                 num_elems: num_elems as u32,
                 depth,
                 _key: PhantomData,
@@ -395,7 +428,7 @@ impl<'a, K: SerializeKey + ?Sized> MapSerializer<'a, K> {
         if self.num_elems > 0 {
             self.num_elems -= 1;
             key.as_impl().serialize_key(self.buf)?;
-            value.serialize(Serializer::new(self.buf, self.depth)?)?;
+            value.serialize(Serializer::new(self.buf, self.fds, self.depth)?)?;
             Ok(self)
         } else {
             Err(SerializeError::TooManyElements)
@@ -416,6 +449,7 @@ impl<K: SerializeKey + ?Sized> fmt::Debug for MapSerializer<'_, K> {
         let mut f = f.debug_struct("MapSerializer");
 
         f.field("buf", &self.buf);
+        f.field("fds", &self.fds);
         f.field("num_elems", &self.num_elems);
 
         f.finish()
@@ -485,17 +519,24 @@ impl<T: SerializeKey + ?Sized> fmt::Debug for SetSerializer<'_, T> {
 #[derive(Debug)]
 pub struct StructSerializer<'a> {
     buf: &'a mut BytesMut,
+    fds: &'a mut FdList,
     num_fields: u32,
     depth: u8,
 }
 
 impl<'a> StructSerializer<'a> {
-    fn new(buf: &'a mut BytesMut, num_fields: usize, depth: u8) -> Result<Self, SerializeError> {
+    fn new(
+        buf: &'a mut BytesMut,
+        fds: &'a mut FdList,
+        num_fields: usize,
+        depth: u8,
+    ) -> Result<Self, SerializeError> {
         if num_fields <= u32::MAX as usize {
             buf.put_discriminant_u8(ValueKind::Struct);
             buf.put_varint_u32_le(num_fields as u32);
             Ok(Self {
                 buf,
+                fds,
                 num_fields: num_fields as u32,
                 depth,
             })
@@ -520,13 +561,32 @@ impl<'a> StructSerializer<'a> {
         if self.num_fields > 0 {
             self.num_fields -= 1;
             self.buf.put_varint_u32_le(id.into());
-            value.serialize(Serializer::new(self.buf, self.depth)?)?;
+            value.serialize(Serializer::new(self.buf, self.fds, self.depth)?)?;
             Ok(self)
         } else {
             Err(SerializeError::TooManyElements)
         }
     }
 
+    /// Serializes all unknown fields, except those in `known_ids`.
+    ///
+    /// Fields, whose id is in `known_ids`, are skipped, because they would otherwise collide with
+    /// the known fields of the struct. This should include alias ids as well. The number of serialized fields is
+    /// [`UnknownFields::len_except`].
+    pub fn serialize_unknown_fields(
+        &mut self,
+        unknown_fields: &UnknownFields,
+        known_ids: &[u32],
+    ) -> Result<&mut Self, SerializeError> {
+        for (id, value) in unknown_fields.iter() {
+            if !known_ids.contains(&id) {
+                self.serialize_field(id, value)?;
+            }
+        }
+
+        Ok(self)
+    }
+
     pub fn finish(self) -> Result<(), SerializeError> {
         if self.num_fields == 0 {
             Ok(())
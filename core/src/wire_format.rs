@@ -0,0 +1,126 @@
+// The codec helpers are only used by transports, that support alternative wire formats.
+#![cfg_attr(
+    not(all(feature = "json", any(feature = "futures-io", feature = "tokio"))),
+    allow(dead_code)
+)]
+
+#[cfg(all(test, feature = "json"))]
+mod test;
+
+use crate::error::{DeserializeError, SerializeError};
+#[cfg(feature = "json")]
+use crate::message::Message;
+#[cfg(feature = "json")]
+use crate::message_deserializer::MessageDeserializeError;
+#[cfg(feature = "json")]
+use crate::message_serializer::MessageSerializeError;
+use crate::value_deserializer::{Deserialize, Deserializer};
+use crate::value_serializer::{AsSerializeArg, Serialize, Serializer};
+#[cfg(feature = "json")]
+use bytes::BytesMut;
+use core::fmt;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// Alternative wire format for messages.
+///
+/// Messages are encoded in Aldrin's binary format by default. Clients can offer alternative
+/// formats during the connection handshake, which the broker may then pick from. The handshake
+/// itself always uses the binary format; the negotiated format is used for all messages
+/// afterwards. It is applied by transports, that support it (see
+/// [`AsyncTransport::supported_wire_formats`](crate::transport::AsyncTransport::supported_wire_formats)).
+///
+/// Alternative formats are meant for debugging and are considerably less efficient. Support for
+/// the individual formats must be enabled with the respective Cargo feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[repr(u8)]
+pub enum WireFormat {
+    /// Newline-delimited JSON.
+    ///
+    /// Every message is encoded as a single line of JSON, using the `serde` representation of
+    /// [`Message`](crate::message::Message). Values are encoded in their generic form, i.e. as
+    /// [`Value`](crate::Value). Struct fields are encoded in ascending order of their ids.
+    ///
+    /// Values with attached file descriptors cannot be encoded, even if the transport could pass
+    /// them. Sending such a message fails.
+    ///
+    /// This requires the `json` feature.
+    Json = 0,
+}
+
+impl WireFormat {
+    /// All formats, that are supported with the enabled Cargo features.
+    pub const SUPPORTED: &'static [Self] = &[
+        #[cfg(feature = "json")]
+        Self::Json,
+    ];
+
+    /// Indicates whether the format is supported with the enabled Cargo features.
+    pub fn is_supported(self) -> bool {
+        Self::SUPPORTED.contains(&self)
+    }
+}
+
+impl fmt::Display for WireFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+impl Serialize for WireFormat {
+    fn serialize(&self, serializer: Serializer) -> Result<(), SerializeError> {
+        serializer.serialize_u8((*self).into());
+        Ok(())
+    }
+}
+
+impl Deserialize for WireFormat {
+    fn deserialize(deserializer: Deserializer) -> Result<Self, DeserializeError> {
+        deserializer
+            .deserialize_u8()?
+            .try_into()
+            .map_err(|_| DeserializeError::InvalidSerialization)
+    }
+}
+
+impl AsSerializeArg for WireFormat {
+    type SerializeArg<'a> = Self;
+
+    fn as_serialize_arg<'a>(&'a self) -> Self::SerializeArg<'a>
+    where
+        Self: 'a,
+    {
+        *self
+    }
+}
+
+/// Encodes a message into a frame.
+#[cfg(feature = "json")]
+pub(crate) fn encode(format: WireFormat, msg: &Message) -> Result<BytesMut, MessageSerializeError> {
+    match format {
+        WireFormat::Json => {
+            let mut frame =
+                serde_json::to_vec(msg).map_err(|_| MessageSerializeError::InvalidValue)?;
+
+            frame.push(b'\n');
+            Ok(BytesMut::from(&*frame))
+        }
+    }
+}
+
+/// Decodes a frame into a message.
+#[cfg(feature = "json")]
+pub(crate) fn decode(format: WireFormat, frame: &[u8]) -> Result<Message, MessageDeserializeError> {
+    match format {
+        WireFormat::Json => {
+            serde_json::from_slice(frame).map_err(|_| MessageDeserializeError::InvalidSerialization)
+        }
+    }
+}
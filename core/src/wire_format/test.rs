@@ -0,0 +1,84 @@
+use super::{decode, encode, WireFormat};
+#[cfg(unix)]
+use crate::fd::Fd;
+use crate::generic_value::{Struct, Value};
+use crate::ids::ServiceCookie;
+use crate::message::{CallFunction, Message, Shutdown};
+use crate::message_deserializer::MessageDeserializeError;
+#[cfg(unix)]
+use crate::message_serializer::MessageSerializeError;
+#[cfg(unix)]
+use std::fs::File;
+#[cfg(unix)]
+use std::os::fd::OwnedFd;
+use uuid::uuid;
+
+fn call_function(value: &impl crate::Serialize) -> Message {
+    Message::CallFunction(
+        CallFunction::with_serialize_value(
+            1,
+            ServiceCookie(uuid!("f5a3e6c8-3d1b-4b0e-9a55-7c2f0d6e8b14")),
+            2,
+            value,
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+fn json_round_trip() {
+    let msg = call_function(&(3u32, "foo"));
+
+    let frame = encode(WireFormat::Json, &msg).unwrap();
+    assert_eq!(frame.last(), Some(&b'\n'));
+    assert_eq!(frame.iter().filter(|&&b| b == b'\n').count(), 1);
+    assert_eq!(decode(WireFormat::Json, &frame), Ok(msg));
+
+    let msg = Message::Shutdown(Shutdown);
+    let frame = encode(WireFormat::Json, &msg).unwrap();
+    assert_eq!(decode(WireFormat::Json, &frame), Ok(msg));
+}
+
+#[test]
+fn json_is_human_readable() {
+    let frame = br#"{"kind":"sync","data":{"serial":7}}"#;
+    let msg = decode(WireFormat::Json, frame).unwrap();
+    assert_eq!(msg, Message::Sync(crate::message::Sync { serial: 7 }));
+}
+
+#[test]
+fn invalid_json() {
+    assert_eq!(
+        decode(WireFormat::Json, b"{\"kind\":\"foo\"}\n"),
+        Err(MessageDeserializeError::InvalidSerialization)
+    );
+}
+
+#[test]
+fn json_struct_fields_are_sorted() {
+    let ascending = Struct((0..32).map(|id| (id, Value::U32(id))).collect());
+    let descending = Struct((0..32).rev().map(|id| (id, Value::U32(id))).collect());
+
+    let frame = encode(WireFormat::Json, &call_function(&ascending)).unwrap();
+    assert_eq!(
+        encode(WireFormat::Json, &call_function(&descending)).unwrap(),
+        frame
+    );
+
+    let json = serde_json::to_string(&ascending).unwrap();
+    let positions = (0..32)
+        .map(|id| json.find(&format!("\"{id}\":")).unwrap())
+        .collect::<Vec<_>>();
+    assert!(positions.windows(2).all(|pos| pos[0] < pos[1]));
+}
+
+#[cfg(unix)]
+#[test]
+fn json_rejects_fds() {
+    let fd = Fd::new(OwnedFd::from(File::open("/dev/null").unwrap()));
+
+    assert_eq!(
+        encode(WireFormat::Json, &call_function(&fd)),
+        Err(MessageSerializeError::InvalidValue)
+    );
+}
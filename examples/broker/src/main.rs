@@ -1,8 +1,12 @@
 use aldrin_broker::core::tokio::TokioTransport;
+#[cfg(feature = "introspection")]
+use aldrin_broker::IntrospectionDump;
 use aldrin_broker::{Broker, BrokerHandle};
 use anyhow::{anyhow, Context, Result};
 use clap::Parser;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+#[cfg(feature = "introspection")]
+use std::path::PathBuf;
 use std::time::Duration;
 use tokio::net::{TcpListener, TcpStream};
 use tokio::signal;
@@ -17,6 +21,11 @@ struct Args {
     /// Address to bind the broker's TCP socket to.
     #[clap(default_value_t = BIND_DEFAULT)]
     bind: SocketAddr,
+
+    /// File to load the introspection database from and to save it to on shutdown.
+    #[cfg(feature = "introspection")]
+    #[clap(long)]
+    introspection_db: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -34,7 +43,22 @@ async fn main() -> Result<()> {
 
     // Create a broker and save a handle to it. The handle is later used to add connections and to
     // shut down the broker. The broker itself must be run explicitly.
-    let broker = Broker::new();
+    #[allow(unused_mut)]
+    let mut broker = Broker::new();
+
+    // The introspection database can be persisted across restarts. Types loaded here can be
+    // queried, even if no client registers them.
+    #[cfg(feature = "introspection")]
+    if let Some(ref path) = args.introspection_db {
+        if path.exists() {
+            let dump = IntrospectionDump::load(path)
+                .with_context(|| anyhow!("failed to load {}", path.display()))?;
+
+            println!("Loaded {} types from {}.", dump.len(), path.display());
+            broker.load_introspection(&dump);
+        }
+    }
+
     let mut handle = broker.handle().clone();
     let join = tokio::spawn(broker.run());
     let mut statistics = time::interval(STATISTICS_INTERVAL);
@@ -58,6 +82,8 @@ async fn main() -> Result<()> {
                     println!("Statistics for the last {} seconds:", STATISTICS_INTERVAL.as_secs());
                     println!(" - Messages sent: {}", statistics.messages_sent());
                     println!(" - Messages received: {}", statistics.messages_received());
+                    println!(" - Events emitted: {}", statistics.events_emitted());
+                    println!(" - Events delivered: {}", statistics.events_delivered());
                     println!(" - Connections: {}", statistics.num_connections());
                     println!(" - Objects: {}", statistics.num_objects());
                     println!(" - Services: {}", statistics.num_services());
@@ -101,6 +127,17 @@ async fn main() -> Result<()> {
     // The broker can be shut down cleanly. This will notify all clients as well. The broker's task
     // will join once all connections have been shut down.
     println!("Shutting down broker.");
+
+    #[cfg(feature = "introspection")]
+    if let Some(ref path) = args.introspection_db {
+        let dump = handle.dump_introspection().await?;
+
+        dump.save(path)
+            .with_context(|| anyhow!("failed to save {}", path.display()))?;
+
+        println!("Saved {} types to {}.", dump.len(), path.display());
+    }
+
     handle.shutdown().await;
     join.await
         .with_context(|| anyhow!("failed to join broker task"))?;
@@ -150,16 +150,20 @@ async fn download(
 
 async fn download_impl(
     expected_size: u64,
-    promise: Promise<UnboundSender<Chunk>, Infallible>,
+    mut promise: Promise<UnboundSender<Chunk>, Infallible>,
 ) -> Result<()> {
+    let client = promise.client().clone();
+
     // When creating a channel, one of the channel ends must be immediately claimed by the local
     // client and only the other end can be sent to another client. Here, we claim the receiver,
     // which also requires specifying a capacity.
-    let (sender, receiver) = promise
-        .client()
-        .create_channel()
-        .claim_receiver(CAPACITY)
-        .await?;
+    //
+    // The caller may abort the call at any time before it has been replied to. Promises can notify
+    // about this, which allows us to stop early.
+    let (sender, receiver) = tokio::select! {
+        channel = client.create_channel().claim_receiver(CAPACITY) => channel?,
+        () = promise.aborted() => return Err(anyhow!("call aborted by the caller")),
+    };
 
     // Fulfill the function call and send back the sender. Channel ends must be unbound from the
     // client they were created from first.
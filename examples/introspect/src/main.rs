@@ -3,7 +3,7 @@ use aldrin::core::introspection::{
     BuiltInType, Enum, Event, Function, Introspection, Layout, LexicalId, Service, Struct,
 };
 use aldrin::core::tokio::TokioTransport;
-use aldrin::core::{BusEvent, BusListenerFilter, BusListenerScope, TypeId};
+use aldrin::core::{BusListenerServiceFilter, TypeId};
 use aldrin::low_level::Proxy;
 use aldrin::{Client, Handle};
 use anyhow::{anyhow, Context, Result};
@@ -82,28 +82,18 @@ async fn main() -> Result<()> {
 }
 
 async fn list(bus: &Handle) -> Result<()> {
-    let mut bus_listener = bus.create_bus_listener().await?;
-    bus_listener.add_filter(BusListenerFilter::any_object())?;
-    bus_listener.add_filter(BusListenerFilter::any_object_any_service())?;
-    bus_listener.start(BusListenerScope::Current).await?;
-
-    let mut objects: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
-
-    while let Some(event) = bus_listener.next_event().await {
-        match event {
-            BusEvent::ObjectCreated(object_id) => {
-                objects.entry(object_id).or_default();
-            }
-
-            BusEvent::ServiceCreated(service_id) => {
-                objects
-                    .entry(service_id.object_id)
-                    .or_default()
-                    .insert(service_id);
-            }
-
-            BusEvent::ObjectDestroyed(_) | BusEvent::ServiceDestroyed(_) => unreachable!(),
-        }
+    let mut objects: BTreeMap<_, BTreeSet<_>> = bus
+        .list_objects()
+        .await?
+        .into_iter()
+        .map(|object_id| (object_id, BTreeSet::new()))
+        .collect();
+
+    for service_id in bus.list_services(BusListenerServiceFilter::any()).await? {
+        objects
+            .entry(service_id.object_id)
+            .or_default()
+            .insert(service_id);
     }
 
     if objects.is_empty() {
@@ -0,0 +1,408 @@
+use crate::types::Types;
+use aldrin::core::introspection::{self, BuiltInType, Introspection, KeyType, Layout, LexicalId};
+use aldrin::core::{
+    ChannelCookie, Enum, ObjectCookie, ObjectId, ObjectUuid, ServiceCookie, ServiceId, ServiceUuid,
+    Struct, Value,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use uuid::Uuid;
+
+/// Depth, beyond which optional parts of values are no longer generated.
+const MAX_DEPTH: usize = 4;
+
+/// Depth, beyond which generation is aborted, in case of types that are infinitely recursive.
+const ABORT_DEPTH: usize = 32;
+
+/// Maximum number of elements of generated containers.
+const MAX_LEN: usize = 4;
+
+/// Generator of random values based on introspection.
+#[derive(Debug)]
+pub struct Generator<'a> {
+    types: &'a Types,
+    rng: StdRng,
+}
+
+impl<'a> Generator<'a> {
+    pub fn new(types: &'a Types, seed: u64) -> Self {
+        Self {
+            types,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns `true` with a probability of `percent` percent.
+    pub fn chance(&mut self, percent: u8) -> bool {
+        self.rng.gen_range(0..100) < percent
+    }
+
+    /// Generates a value that is valid for the type `lexical_id`, as referenced by `from`.
+    ///
+    /// If `lexical_id` is `None`, then the unit value is generated.
+    pub fn valid(&mut self, from: &Introspection, lexical_id: Option<LexicalId>) -> Value {
+        match lexical_id {
+            Some(lexical_id) => self.resolve(from, lexical_id, 0),
+            None => Value::None,
+        }
+    }
+
+    /// Generates a value that is most likely invalid for the type `lexical_id`.
+    ///
+    /// Structs are generated with a required field missing and enums with an unknown variant. Any
+    /// other type is replaced by a random value of a different kind.
+    pub fn invalid(&mut self, from: &Introspection, lexical_id: Option<LexicalId>) -> Value {
+        let layout = lexical_id
+            .and_then(|lexical_id| self.types.resolve(from, lexical_id))
+            .map(|introspection| (introspection, introspection.layout()));
+
+        match layout {
+            Some((introspection, Layout::Struct(ty))) if self.chance(50) => {
+                if let Some(value) = self.struct_missing_field(introspection, ty) {
+                    return value;
+                }
+            }
+
+            Some((_, Layout::Enum(ty))) if self.chance(50) => {
+                let variant = ty.variants().keys().last().map_or(0, |id| id + 1);
+                let variant = variant.saturating_add(self.rng.gen_range(0..4));
+                let value = self.random(MAX_DEPTH);
+                return Value::Enum(Box::new(Enum::new(variant, value)));
+            }
+
+            _ => {}
+        }
+
+        let valid = self.valid(from, lexical_id);
+
+        loop {
+            let value = self.random(0);
+
+            if value.kind() != valid.kind() {
+                break value;
+            }
+        }
+    }
+
+    fn resolve(&mut self, from: &Introspection, lexical_id: LexicalId, depth: usize) -> Value {
+        if depth > ABORT_DEPTH {
+            return Value::None;
+        }
+
+        match self.types.resolve(from, lexical_id) {
+            Some(introspection) => self.layout(introspection, depth),
+            None => self.random(depth),
+        }
+    }
+
+    fn layout(&mut self, from: &Introspection, depth: usize) -> Value {
+        match from.layout() {
+            Layout::BuiltIn(ty) => self.built_in(from, *ty, depth),
+            Layout::Struct(ty) => self.struct_ty(from, ty, depth),
+            Layout::Enum(ty) => self.enum_ty(from, ty, depth),
+            Layout::Service(_) => Value::None,
+        }
+    }
+
+    fn built_in(&mut self, from: &Introspection, ty: BuiltInType, depth: usize) -> Value {
+        match ty {
+            BuiltInType::Bool => Value::Bool(self.rng.gen()),
+            BuiltInType::U8 => Value::U8(self.int(u8::MIN, u8::MAX)),
+            BuiltInType::I8 => Value::I8(self.int(i8::MIN, i8::MAX)),
+            BuiltInType::U16 => Value::U16(self.int(u16::MIN, u16::MAX)),
+            BuiltInType::I16 => Value::I16(self.int(i16::MIN, i16::MAX)),
+            BuiltInType::U32 => Value::U32(self.int(u32::MIN, u32::MAX)),
+            BuiltInType::I32 => Value::I32(self.int(i32::MIN, i32::MAX)),
+            BuiltInType::U64 => Value::U64(self.int(u64::MIN, u64::MAX)),
+            BuiltInType::I64 => Value::I64(self.int(i64::MIN, i64::MAX)),
+            BuiltInType::F32 => Value::F32(self.f32()),
+            BuiltInType::F64 => Value::F64(self.f64()),
+            BuiltInType::String => Value::String(self.string()),
+            BuiltInType::Uuid => Value::Uuid(self.uuid()),
+            BuiltInType::ObjectId | BuiltInType::Lifetime => Value::ObjectId(self.object_id()),
+            BuiltInType::ServiceId => Value::ServiceId(self.service_id()),
+            BuiltInType::Value => self.random(depth),
+
+            BuiltInType::Option(ty) => {
+                if (depth < MAX_DEPTH) && self.rng.gen() {
+                    Value::Some(Box::new(self.resolve(from, ty, depth + 1)))
+                } else {
+                    Value::None
+                }
+            }
+
+            BuiltInType::Box(ty) => self.resolve(from, ty, depth + 1),
+
+            BuiltInType::Vec(ty) => {
+                let len = self.len(depth);
+                Value::Vec(
+                    (0..len)
+                        .map(|_| self.resolve(from, ty, depth + 1))
+                        .collect(),
+                )
+            }
+
+            BuiltInType::Bytes => Value::Bytes(self.bytes()),
+
+            BuiltInType::Map(ty) => {
+                let len = self.len(depth);
+                let value = ty.value();
+                let mut gen = |this: &mut Self| this.resolve(from, value, depth + 1);
+
+                match ty.key() {
+                    KeyType::U8 => Value::U8Map(self.map(len, Self::any, &mut gen)),
+                    KeyType::I8 => Value::I8Map(self.map(len, Self::any, &mut gen)),
+                    KeyType::U16 => Value::U16Map(self.map(len, Self::any, &mut gen)),
+                    KeyType::I16 => Value::I16Map(self.map(len, Self::any, &mut gen)),
+                    KeyType::U32 => Value::U32Map(self.map(len, Self::any, &mut gen)),
+                    KeyType::I32 => Value::I32Map(self.map(len, Self::any, &mut gen)),
+                    KeyType::U64 => Value::U64Map(self.map(len, Self::any, &mut gen)),
+                    KeyType::I64 => Value::I64Map(self.map(len, Self::any, &mut gen)),
+                    KeyType::String => Value::StringMap(self.map(len, Self::string, &mut gen)),
+                    KeyType::Uuid => Value::UuidMap(self.map(len, Self::uuid, &mut gen)),
+                }
+            }
+
+            BuiltInType::Set(ty) => {
+                let len = self.len(depth);
+
+                match ty {
+                    KeyType::U8 => Value::U8Set(self.set(len, Self::any)),
+                    KeyType::I8 => Value::I8Set(self.set(len, Self::any)),
+                    KeyType::U16 => Value::U16Set(self.set(len, Self::any)),
+                    KeyType::I16 => Value::I16Set(self.set(len, Self::any)),
+                    KeyType::U32 => Value::U32Set(self.set(len, Self::any)),
+                    KeyType::I32 => Value::I32Set(self.set(len, Self::any)),
+                    KeyType::U64 => Value::U64Set(self.set(len, Self::any)),
+                    KeyType::I64 => Value::I64Set(self.set(len, Self::any)),
+                    KeyType::String => Value::StringSet(self.set(len, Self::string)),
+                    KeyType::Uuid => Value::UuidSet(self.set(len, Self::uuid)),
+                }
+            }
+
+            // Channel ends can't be generated meaningfully. A random cookie at least exercises the
+            // error handling of claiming a channel.
+            BuiltInType::Sender(_) => Value::Sender(ChannelCookie(self.uuid())),
+            BuiltInType::Receiver(_) => Value::Receiver(ChannelCookie(self.uuid())),
+
+            BuiltInType::Unit => Value::None,
+
+            BuiltInType::Result(ty) => {
+                let (variant, ty) = if self.rng.gen() {
+                    (0, ty.ok())
+                } else {
+                    (1, ty.err())
+                };
+
+                let value = self.resolve(from, ty, depth + 1);
+                Value::Enum(Box::new(Enum::new(variant, value)))
+            }
+
+            BuiltInType::Array(ty) => Value::Vec(
+                (0..ty.len())
+                    .map(|_| self.resolve(from, ty.elem_type(), depth + 1))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn struct_ty(
+        &mut self,
+        from: &Introspection,
+        ty: &introspection::Struct,
+        depth: usize,
+    ) -> Value {
+        let mut fields = HashMap::new();
+
+        // Optional fields are serialized as `Option`s, if they are present.
+        for field in ty.fields().values() {
+            if field.is_required() {
+                let value = self.resolve(from, field.field_type(), depth + 1);
+                fields.insert(field.id(), value);
+            } else if (depth < MAX_DEPTH) && self.rng.gen() {
+                let value = self.resolve(from, field.field_type(), depth + 1);
+                fields.insert(field.id(), Value::Some(Box::new(value)));
+            }
+        }
+
+        Value::Struct(Struct(fields))
+    }
+
+    fn struct_missing_field(
+        &mut self,
+        from: &Introspection,
+        ty: &introspection::Struct,
+    ) -> Option<Value> {
+        let required = ty
+            .fields()
+            .values()
+            .filter(|field| field.is_required())
+            .map(|field| field.id())
+            .collect::<Vec<_>>();
+
+        if required.is_empty() {
+            return None;
+        }
+
+        let missing = required[self.rng.gen_range(0..required.len())];
+
+        match self.struct_ty(from, ty, 0) {
+            Value::Struct(mut value) => {
+                value.0.remove(&missing);
+                Some(Value::Struct(value))
+            }
+
+            _ => unreachable!(),
+        }
+    }
+
+    fn enum_ty(&mut self, from: &Introspection, ty: &introspection::Enum, depth: usize) -> Value {
+        let variants = ty.variants().values().collect::<Vec<_>>();
+
+        if variants.is_empty() {
+            return Value::None;
+        }
+
+        let variant = variants[self.rng.gen_range(0..variants.len())];
+
+        let value = match variant.variant_type() {
+            Some(ty) => self.resolve(from, ty, depth + 1),
+            None => Value::None,
+        };
+
+        Value::Enum(Box::new(Enum::new(variant.id(), value)))
+    }
+
+    /// Generates a random value without knowledge of its type.
+    fn random(&mut self, depth: usize) -> Value {
+        let kind = if depth < MAX_DEPTH {
+            self.rng.gen_range(0..12)
+        } else {
+            self.rng.gen_range(0..9)
+        };
+
+        match kind {
+            0 => Value::None,
+            1 => Value::Bool(self.rng.gen()),
+            2 => Value::U8(self.int(u8::MIN, u8::MAX)),
+            3 => Value::I32(self.int(i32::MIN, i32::MAX)),
+            4 => Value::U64(self.int(u64::MIN, u64::MAX)),
+            5 => Value::F64(self.f64()),
+            6 => Value::String(self.string()),
+            7 => Value::Uuid(self.uuid()),
+            8 => Value::Bytes(self.bytes()),
+
+            9 => {
+                let len = self.len(depth);
+                Value::Vec((0..len).map(|_| self.random(depth + 1)).collect())
+            }
+
+            10 => {
+                let len = self.len(depth);
+                let fields = (0..len)
+                    .map(|_| (self.rng.gen_range(0..8), self.random(depth + 1)))
+                    .collect();
+                Value::Struct(Struct(fields))
+            }
+
+            _ => {
+                let value = self.random(depth + 1);
+                Value::Enum(Box::new(Enum::new(self.rng.gen_range(0..8), value)))
+            }
+        }
+    }
+
+    /// Generates a random integer, preferring the boundaries of its range.
+    fn int<T>(&mut self, min: T, max: T) -> T
+    where
+        T: Default,
+        rand::distributions::Standard: rand::distributions::Distribution<T>,
+    {
+        match self.rng.gen_range(0..8) {
+            0 => min,
+            1 => max,
+            2 => T::default(),
+            _ => self.rng.gen(),
+        }
+    }
+
+    fn any<T>(&mut self) -> T
+    where
+        rand::distributions::Standard: rand::distributions::Distribution<T>,
+    {
+        self.rng.gen()
+    }
+
+    fn f32(&mut self) -> f32 {
+        match self.rng.gen_range(0..8) {
+            0 => f32::NAN,
+            1 => f32::INFINITY,
+            2 => f32::NEG_INFINITY,
+            3 => -0.0,
+            _ => self.rng.gen::<f32>() * f32::MAX,
+        }
+    }
+
+    fn f64(&mut self) -> f64 {
+        match self.rng.gen_range(0..8) {
+            0 => f64::NAN,
+            1 => f64::INFINITY,
+            2 => f64::NEG_INFINITY,
+            3 => -0.0,
+            _ => self.rng.gen::<f64>() * f64::MAX,
+        }
+    }
+
+    fn string(&mut self) -> String {
+        let len = self.rng.gen_range(0..16);
+        (0..len).map(|_| self.rng.gen::<char>()).collect()
+    }
+
+    fn bytes(&mut self) -> Vec<u8> {
+        let len = self.rng.gen_range(0..32);
+        (0..len).map(|_| self.rng.gen()).collect()
+    }
+
+    fn uuid(&mut self) -> Uuid {
+        Uuid::from_u128(self.rng.gen())
+    }
+
+    fn object_id(&mut self) -> ObjectId {
+        ObjectId::new(ObjectUuid(self.uuid()), ObjectCookie(self.uuid()))
+    }
+
+    fn service_id(&mut self) -> ServiceId {
+        ServiceId::new(
+            self.object_id(),
+            ServiceUuid(self.uuid()),
+            ServiceCookie(self.uuid()),
+        )
+    }
+
+    fn len(&mut self, depth: usize) -> usize {
+        if depth < MAX_DEPTH {
+            self.rng.gen_range(0..=MAX_LEN)
+        } else {
+            0
+        }
+    }
+
+    fn map<K, F, G>(&mut self, len: usize, mut key: F, value: &mut G) -> HashMap<K, Value>
+    where
+        K: Eq + Hash,
+        F: FnMut(&mut Self) -> K,
+        G: FnMut(&mut Self) -> Value,
+    {
+        (0..len).map(|_| (key(self), value(self))).collect()
+    }
+
+    fn set<K, F>(&mut self, len: usize, mut key: F) -> HashSet<K>
+    where
+        K: Eq + Hash,
+        F: FnMut(&mut Self) -> K,
+    {
+        (0..len).map(|_| key(self)).collect()
+    }
+}
@@ -0,0 +1,290 @@
+mod generator;
+mod report;
+#[cfg(test)]
+mod test;
+mod types;
+
+use aldrin::core::tokio::TokioTransport;
+use aldrin::core::{BusEvent, BusListenerFilter, BusListenerScope, ServiceId, ServiceUuid, Value};
+use aldrin::low_level::Proxy;
+use aldrin::{Client, Error, Handle};
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use generator::Generator;
+use report::{EventStats, Report};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::process::ExitCode;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time;
+use types::Types;
+
+const BUS_DEFAULT: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 24940);
+
+/// Introspection-driven fuzzer for Aldrin services.
+///
+/// All services on the bus, that provide introspection, are called with randomized valid and
+/// invalid arguments. A service fails if it doesn't reply to a call in time, aborts a call, or is
+/// destroyed while being fuzzed (which usually indicates a panic). The exit code is non-zero if any
+/// service failed.
+#[derive(Parser)]
+struct Args {
+    /// Address of the broker to connect to.
+    #[clap(short, long, default_value_t = BUS_DEFAULT)]
+    bus: SocketAddr,
+
+    /// Fuzz only services with this UUID.
+    ///
+    /// This argument can be specified multiple times to fuzz more than one kind of service.
+    #[clap(
+        short = 'S',
+        long = "service",
+        value_name = "UUID",
+        number_of_values = 1
+    )]
+    services: Vec<ServiceUuid>,
+
+    /// Number of calls per function.
+    #[clap(short = 'n', long, default_value_t = 100)]
+    iterations: usize,
+
+    /// Percentage of calls made with invalid arguments.
+    #[clap(short, long, default_value_t = 25, value_parser = clap::value_parser!(u8).range(0..=100))]
+    invalid: u8,
+
+    /// Seed of the random number generator.
+    ///
+    /// A random seed is chosen if none is specified. The seed is always printed, such that
+    /// failures can be reproduced.
+    #[clap(short, long)]
+    seed: Option<u64>,
+
+    /// Time in milliseconds to wait for each reply.
+    #[clap(short, long, default_value_t = 1000)]
+    timeout: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    let seed = args.seed.unwrap_or_else(rand::random);
+
+    let stream = TcpStream::connect(&args.bus)
+        .await
+        .with_context(|| anyhow!("failed to connect to broker at {}", args.bus))?;
+
+    stream.set_nodelay(true)?;
+
+    let client = Client::connect(TokioTransport::new(stream))
+        .await
+        .with_context(|| anyhow!("failed to connect to broker at {}", args.bus))?;
+    let handle = client.handle().clone();
+    let join = tokio::spawn(client.run());
+
+    println!("Fuzzing services at {} with seed {seed}.", args.bus);
+    println!();
+
+    let services = find_services(&handle, &args.services).await?;
+    let mut failed = false;
+
+    if services.is_empty() {
+        println!("No services found.");
+    }
+
+    for (i, id) in services.into_iter().enumerate() {
+        let seed = seed.wrapping_add(i as u64);
+
+        if let Some(report) = fuzz(&handle, id, &args, seed).await? {
+            report.print();
+            failed |= report.failed();
+        }
+    }
+
+    handle.shutdown();
+    join.await
+        .with_context(|| anyhow!("failed to shut down client"))?
+        .with_context(|| anyhow!("failed to shut down client"))?;
+
+    if failed {
+        println!("Some service(s) failed.");
+        Ok(ExitCode::FAILURE)
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+async fn find_services(bus: &Handle, uuids: &[ServiceUuid]) -> Result<Vec<ServiceId>> {
+    let mut bus_listener = bus.create_bus_listener().await?;
+
+    if uuids.is_empty() {
+        bus_listener.add_filter(BusListenerFilter::any_object_any_service())?;
+    } else {
+        for &uuid in uuids {
+            bus_listener.add_filter(BusListenerFilter::any_object_specific_service(uuid))?;
+        }
+    }
+
+    bus_listener.start(BusListenerScope::Current).await?;
+
+    let mut services = Vec::new();
+    while let Some(event) = bus_listener.next_event().await {
+        if let BusEvent::ServiceCreated(id) = event {
+            services.push(id);
+        }
+    }
+
+    services.sort();
+    Ok(services)
+}
+
+async fn fuzz(bus: &Handle, id: ServiceId, args: &Args, seed: u64) -> Result<Option<Report>> {
+    let proxy = match Proxy::new(bus, id).await {
+        Ok(proxy) => proxy,
+        Err(Error::InvalidService) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let Some(introspection) = proxy.query_introspection().await? else {
+        println!("Service {}: no introspection available, skipped.", id.uuid);
+        println!();
+        return Ok(None);
+    };
+
+    let Some(layout) = introspection.as_service_layout() else {
+        println!("Service {}: invalid introspection, skipped.", id.uuid);
+        println!();
+        return Ok(None);
+    };
+
+    let types = Types::query(bus, &introspection).await?;
+    let mut gen = Generator::new(&types, seed);
+    let mut report = Report::new(id, layout);
+    let events = EventWatcher::start(bus, id, report.events().clone()).await?;
+    let timeout = Duration::from_millis(args.timeout);
+
+    'functions: for func in layout.functions().values() {
+        for _ in 0..args.iterations {
+            let value = if gen.chance(args.invalid) {
+                gen.invalid(&introspection, func.args())
+            } else {
+                gen.valid(&introspection, func.args())
+            };
+
+            let reply = time::timeout(timeout, proxy.call(func.id(), &value)).await;
+            let stats = report.function(func.id());
+            stats.calls += 1;
+
+            let failure = match reply.map(|res| res.map_err(Error::without_context)) {
+                Ok(Ok(Ok(value))) => {
+                    stats.ok += 1;
+                    value
+                        .deserialize::<Value>()
+                        .err()
+                        .map(|_| "malformed reply")
+                }
+
+                Ok(Ok(Err(value))) => {
+                    stats.err += 1;
+                    value
+                        .deserialize::<Value>()
+                        .err()
+                        .map(|_| "malformed reply")
+                }
+
+                Ok(Err(Error::InvalidArguments(_) | Error::Overloaded)) => {
+                    stats.rejected += 1;
+                    None
+                }
+
+                Ok(Err(Error::CallAborted)) => Some("call aborted"),
+                Ok(Err(Error::InvalidFunction(_))) => Some("function unknown to the service"),
+
+                Ok(Err(Error::InvalidService)) => {
+                    let failure = format!("fn {} @ {}: service destroyed", func.name(), func.id());
+                    report.fail(failure);
+                    break 'functions;
+                }
+
+                Ok(Err(e)) => return Err(e.into()),
+                Err(_) => Some("no reply in time"),
+            };
+
+            if let Some(failure) = failure {
+                let failure = format!("fn {} @ {}: {failure}", func.name(), func.id());
+                report.fail(failure);
+            }
+        }
+    }
+
+    let (events, failures) = events.stop().await?;
+    report.set_events(events);
+
+    for failure in failures {
+        report.fail(failure);
+    }
+
+    Ok(Some(report))
+}
+
+/// Task that subscribes to all events of a service and checks that they are well-formed.
+struct EventWatcher {
+    stop: oneshot::Sender<()>,
+    join: JoinHandle<(BTreeMap<u32, EventStats>, Vec<String>)>,
+}
+
+impl EventWatcher {
+    async fn start(
+        bus: &Handle,
+        id: ServiceId,
+        mut stats: BTreeMap<u32, EventStats>,
+    ) -> Result<Self> {
+        let mut proxy = Proxy::new(bus, id).await?;
+
+        for &event in stats.keys() {
+            proxy.subscribe(event).await?;
+        }
+
+        let (stop, mut stopped) = oneshot::channel();
+
+        let join = tokio::spawn(async move {
+            let mut failures = Vec::new();
+
+            loop {
+                let event = tokio::select! {
+                    _ = &mut stopped => break,
+                    event = proxy.next_event() => event,
+                };
+
+                let Some(event) = event else {
+                    break;
+                };
+
+                let Some(stats) = stats.get_mut(&event.id()) else {
+                    continue;
+                };
+
+                stats.received += 1;
+
+                if event.deserialize::<Value>().is_err() {
+                    failures.push(format!(
+                        "event {} @ {}: malformed",
+                        stats.name(),
+                        event.id()
+                    ));
+                }
+            }
+
+            (stats, failures)
+        });
+
+        Ok(Self { stop, join })
+    }
+
+    async fn stop(self) -> Result<(BTreeMap<u32, EventStats>, Vec<String>)> {
+        let _ = self.stop.send(());
+        self.join.await.map_err(Into::into)
+    }
+}
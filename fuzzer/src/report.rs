@@ -0,0 +1,124 @@
+use aldrin::core::introspection::Service;
+use aldrin::core::ServiceId;
+use std::collections::BTreeMap;
+
+/// Results of fuzzing a single service.
+#[derive(Debug)]
+pub struct Report {
+    name: String,
+    id: ServiceId,
+    functions: BTreeMap<u32, FunctionStats>,
+    events: BTreeMap<u32, EventStats>,
+    failures: BTreeMap<String, usize>,
+}
+
+impl Report {
+    pub fn new(id: ServiceId, layout: &Service) -> Self {
+        Self {
+            name: format!("{}::{}", layout.schema(), layout.name()),
+            id,
+            functions: layout
+                .functions()
+                .values()
+                .map(|func| (func.id(), FunctionStats::new(func.name())))
+                .collect(),
+            events: layout
+                .events()
+                .values()
+                .map(|ev| (ev.id(), EventStats::new(ev.name())))
+                .collect(),
+            failures: BTreeMap::new(),
+        }
+    }
+
+    pub fn function(&mut self, id: u32) -> &mut FunctionStats {
+        self.functions.get_mut(&id).unwrap()
+    }
+
+    pub fn events(&self) -> &BTreeMap<u32, EventStats> {
+        &self.events
+    }
+
+    pub fn set_events(&mut self, events: BTreeMap<u32, EventStats>) {
+        self.events = events;
+    }
+
+    /// Records a failure.
+    ///
+    /// Identical failures are recorded only once, together with the number of occurrences.
+    pub fn fail(&mut self, failure: String) {
+        *self.failures.entry(failure).or_default() += 1;
+    }
+
+    pub fn failed(&self) -> bool {
+        !self.failures.is_empty()
+    }
+
+    pub fn print(&self) {
+        println!("Service {} ({}):", self.name, self.id.uuid);
+
+        for (id, stats) in &self.functions {
+            println!(
+                "    fn {} @ {id}: {} call(s), {} ok, {} err, {} rejected",
+                stats.name, stats.calls, stats.ok, stats.err, stats.rejected,
+            );
+        }
+
+        for (id, stats) in &self.events {
+            println!(
+                "    event {} @ {id}: {} received",
+                stats.name, stats.received
+            );
+        }
+
+        if self.failures.is_empty() {
+            println!("    Passed.");
+        } else {
+            for (failure, count) in &self.failures {
+                println!("    FAILED: {failure} ({count}x)");
+            }
+        }
+
+        println!();
+    }
+}
+
+#[derive(Debug)]
+pub struct FunctionStats {
+    name: String,
+    pub calls: usize,
+    pub ok: usize,
+    pub err: usize,
+    pub rejected: usize,
+}
+
+impl FunctionStats {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            calls: 0,
+            ok: 0,
+            err: 0,
+            rejected: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EventStats {
+    name: String,
+    pub received: usize,
+}
+
+impl EventStats {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            received: 0,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
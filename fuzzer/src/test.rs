@@ -0,0 +1,93 @@
+use crate::generator::Generator;
+use crate::types::Types;
+use aldrin::core::introspection::{DynIntrospectable, Introspectable, Introspection, References};
+use aldrin::core::{SerializedValue, Value};
+use aldrin::{Deserialize, Introspectable, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize, Introspectable)]
+#[aldrin(schema = "test")]
+struct Outer {
+    required: u32,
+
+    #[aldrin(optional)]
+    optional: Option<String>,
+
+    inner: Inner,
+    kinds: Vec<Kind>,
+    map: HashMap<String, i64>,
+    set: HashSet<u8>,
+    result: Result<f64, bool>,
+    boxed: Box<Option<Inner>>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize, Introspectable)]
+#[aldrin(schema = "test")]
+struct Inner {
+    value: i8,
+    values: Vec<u16>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Serialize, Deserialize, Introspectable)]
+#[aldrin(schema = "test")]
+enum Kind {
+    Unit,
+    Value(u64),
+    Inner(Inner),
+}
+
+/// Builds a type database from local types instead of querying a broker.
+fn types<T: Introspectable + ?Sized>() -> Types {
+    let mut types = Types::new();
+    let mut pending = vec![DynIntrospectable::new::<T>()];
+
+    while let Some(ty) = pending.pop() {
+        let introspection = Introspection::from_dyn(ty);
+
+        if types.get(introspection.type_id()).is_some() {
+            continue;
+        }
+
+        ty.add_references(&mut References::new(&mut pending));
+        types.insert(introspection.type_id(), introspection);
+    }
+
+    types
+}
+
+#[test]
+fn valid_values_deserialize() {
+    // Values are generated for types referenced by some introspection, so start at `Vec<Outer>`.
+    let from = Introspection::new::<Vec<Outer>>();
+    let types = types::<Vec<Outer>>();
+    let mut gen = Generator::new(&types, 0);
+
+    for _ in 0..1000 {
+        let value = gen.valid(&from, Some(Outer::lexical_id()));
+        let serialized = SerializedValue::serialize(&value).unwrap();
+        serialized.deserialize::<Outer>().unwrap();
+    }
+}
+
+#[test]
+fn invalid_values_serialize() {
+    let from = Introspection::new::<Vec<Outer>>();
+    let types = types::<Vec<Outer>>();
+    let mut gen = Generator::new(&types, 0);
+    let mut rejected = 0;
+
+    for _ in 0..1000 {
+        let value = gen.invalid(&from, Some(Outer::lexical_id()));
+        let serialized = SerializedValue::serialize(&value).unwrap();
+        serialized.deserialize::<Value>().unwrap();
+
+        if serialized.deserialize::<Outer>().is_err() {
+            rejected += 1;
+        }
+    }
+
+    assert_eq!(rejected, 1000);
+}
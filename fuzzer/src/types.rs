@@ -0,0 +1,55 @@
+use aldrin::core::introspection::{Introspection, LexicalId};
+use aldrin::core::TypeId;
+use aldrin::Handle;
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Introspections of a service and all types it references.
+#[derive(Debug, Default)]
+pub struct Types {
+    db: HashMap<TypeId, Introspection>,
+}
+
+impl Types {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queries the introspection of all types referenced by `root`, directly or indirectly.
+    ///
+    /// Types, for which no introspection is available, are silently skipped. Values of these
+    /// types are generated without knowledge of their layout.
+    pub async fn query(bus: &Handle, root: &Introspection) -> Result<Self> {
+        let mut types = Self::new();
+        let mut pending = root.references().values().copied().collect::<Vec<_>>();
+
+        while let Some(type_id) = pending.pop() {
+            if types.db.contains_key(&type_id) {
+                continue;
+            }
+
+            let Some(introspection) = bus.query_introspection(type_id).await? else {
+                continue;
+            };
+
+            pending.extend(introspection.references().values().copied());
+            types.insert(type_id, introspection);
+        }
+
+        Ok(types)
+    }
+
+    pub fn insert(&mut self, type_id: TypeId, introspection: Introspection) {
+        self.db.insert(type_id, introspection);
+    }
+
+    pub fn get(&self, type_id: TypeId) -> Option<&Introspection> {
+        self.db.get(&type_id)
+    }
+
+    /// Resolves a lexical id, that is referenced by the introspection `from`.
+    pub fn resolve(&self, from: &Introspection, lexical_id: LexicalId) -> Option<&Introspection> {
+        from.resolve(lexical_id)
+            .and_then(|type_id| self.get(type_id))
+    }
+}
@@ -1,5 +1,4 @@
 use crate::{diag, CommonReadArgs};
-use aldrin_parser::Parser;
 use anyhow::Result;
 use std::path::PathBuf;
 
@@ -15,11 +14,7 @@ pub struct CheckArgs {
 }
 
 pub fn run(args: CheckArgs) -> Result<bool> {
-    let mut parser = Parser::new();
-
-    for include in args.common_read_args.include {
-        parser.add_schema_path(include);
-    }
+    let parser = args.common_read_args.parser()?;
 
     let mut res = true;
     let mut first = true;
@@ -0,0 +1,52 @@
+use crate::{diag, CommonReadArgs};
+use aldrin_parser::compat;
+use anyhow::Result;
+use std::path::PathBuf;
+
+#[derive(clap::Parser)]
+#[clap(arg_required_else_help = true)]
+pub struct CompatArgs {
+    #[clap(flatten)]
+    common_read_args: CommonReadArgs,
+
+    /// Path to the old version of the Aldrin schema.
+    old: PathBuf,
+
+    /// Path to the new version of the Aldrin schema.
+    new: PathBuf,
+}
+
+pub fn run(args: CompatArgs) -> Result<bool> {
+    let parser = args.common_read_args.parser()?;
+
+    let old = parser.parse(&args.old);
+    let new = parser.parse(&args.new);
+
+    if !old.errors().is_empty() {
+        diag::print_diagnostics(&old);
+    }
+
+    if !new.errors().is_empty() {
+        diag::print_diagnostics(&new);
+    }
+
+    if !old.errors().is_empty() || !new.errors().is_empty() {
+        println!("Some error(s) found.");
+        return Ok(false);
+    }
+
+    let changes = compat::check(old.main_schema(), new.main_schema());
+
+    for change in &changes {
+        diag::print_breaking_change(change);
+    }
+
+    if changes.is_empty() {
+        println!("No breaking changes found.");
+        Ok(true)
+    } else {
+        eprintln!();
+        println!("{} breaking change(s) found.", changes.len());
+        Ok(false)
+    }
+}
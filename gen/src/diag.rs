@@ -1,3 +1,4 @@
+use aldrin_parser::compat::BreakingChange;
 use aldrin_parser::diag::{Diagnostic, Formatted, Style};
 use aldrin_parser::Parsed;
 use anstream::{eprint, eprintln};
@@ -45,6 +46,10 @@ pub fn print_diagnostics(parsed: &Parsed) {
     }
 }
 
+pub fn print_breaking_change(change: &BreakingChange) {
+    eprintln!("{STYLE_ERROR}breaking change{STYLE_ERROR:#}{STYLE_EMPHASIZED}: {change}{STYLE_EMPHASIZED:#}");
+}
+
 fn print_formatted(formatted: &Formatted) {
     for line in formatted {
         for (chunk, style) in line {
@@ -0,0 +1,66 @@
+use crate::{diag, CommonReadArgs};
+use anyhow::{anyhow, Context, Result};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+#[derive(clap::Parser)]
+#[clap(arg_required_else_help = true)]
+pub struct ExportIrArgs {
+    #[clap(flatten)]
+    common_read_args: CommonReadArgs,
+
+    /// Output file.
+    ///
+    /// The JSON is written to stdout if this is not specified.
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+
+    /// Pretty-print the JSON.
+    #[clap(short, long)]
+    pretty: bool,
+
+    /// Path to an Aldrin schema file.
+    schema: PathBuf,
+}
+
+pub fn run(args: ExportIrArgs) -> Result<bool> {
+    let parser = args.common_read_args.parser()?;
+
+    let parsed = parser.parse(args.schema);
+    diag::print_diagnostics(&parsed);
+
+    let Some(ir) = parsed.to_ir() else {
+        eprintln!("Some error(s) found.");
+        return Ok(false);
+    };
+
+    if !parsed.warnings().is_empty() || !parsed.other_warnings().is_empty() {
+        eprintln!("Some warning(s) found.");
+    }
+
+    let mut writer: Box<dyn Write> = match args.output {
+        Some(ref output) => {
+            let file = File::create(output)
+                .with_context(|| anyhow!("failed to open `{}`", output.display()))?;
+            Box::new(BufWriter::new(file))
+        }
+
+        None => Box::new(io::stdout().lock()),
+    };
+
+    if args.pretty {
+        serde_json::to_writer_pretty(&mut writer, &ir)?;
+    } else {
+        serde_json::to_writer(&mut writer, &ir)?;
+    }
+
+    writeln!(writer)?;
+    writer.flush()?;
+
+    if let Some(output) = args.output {
+        eprintln!("File `{}` written.", output.display());
+    }
+
+    Ok(true)
+}
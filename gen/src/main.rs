@@ -1,8 +1,13 @@
 mod check;
+mod compat;
 mod diag;
+mod export_ir;
+mod python;
 mod rust;
+mod typescript;
 
-use anyhow::Result;
+use aldrin_parser::resolver::Lockfile;
+use anyhow::{anyhow, Context, Result};
 use clap::Parser;
 use colorchoice_clap::Color;
 use std::path::PathBuf;
@@ -23,8 +28,24 @@ enum Command {
     /// Checks an Aldrin schema for errors.
     Check(check::CheckArgs),
 
+    /// Checks a new version of an Aldrin schema for breaking changes.
+    Compat(compat::CompatArgs),
+
+    /// Exports the parsed schema as JSON.
+    ///
+    /// The JSON contains the main schema and all imported schemas in a resolved, machine-readable
+    /// form, including ids, doc comments and spans.
+    ExportIr(export_ir::ExportIrArgs),
+
+    /// Generates code for Python.
+    Python(python::PythonArgs),
+
     /// Generates code for Rust.
     Rust(rust::RustArgs),
+
+    /// Generates code for TypeScript.
+    #[clap(name = "typescript")]
+    TypeScript(typescript::TypeScriptArgs),
 }
 
 #[derive(Parser)]
@@ -34,6 +55,39 @@ struct CommonReadArgs {
     /// Can be specified multiple times.
     #[clap(short = 'I', long)]
     include: Vec<PathBuf>,
+
+    /// Lockfile describing additional sources of schemas, e.g. git repositories.
+    ///
+    /// Can be specified multiple times.
+    #[clap(short = 'L', long)]
+    lockfile: Vec<PathBuf>,
+
+    /// Directory, in which git repositories are cached.
+    ///
+    /// A directory in the system's temporary directory is used if this is not specified.
+    #[clap(long, value_name = "DIR")]
+    schema_cache: Option<PathBuf>,
+}
+
+impl CommonReadArgs {
+    fn parser(self) -> Result<aldrin_parser::Parser> {
+        let mut parser = aldrin_parser::Parser::new();
+
+        for lockfile in self.lockfile {
+            let lockfile = Lockfile::load(&lockfile)
+                .with_context(|| anyhow!("failed to load lockfile `{}`", lockfile.display()))?;
+
+            for resolver in lockfile.resolvers(self.schema_cache.as_deref()) {
+                parser.add_resolver(resolver);
+            }
+        }
+
+        for include in self.include {
+            parser.add_schema_path(include);
+        }
+
+        Ok(parser)
+    }
 }
 
 #[derive(Parser)]
@@ -68,7 +122,11 @@ fn main() -> Result<()> {
 
     let res = match args.cmd {
         Command::Check(args) => check::run(args)?,
+        Command::Compat(args) => compat::run(args)?,
+        Command::ExportIr(args) => export_ir::run(args)?,
+        Command::Python(args) => python::run(args)?,
         Command::Rust(args) => rust::run(args)?,
+        Command::TypeScript(args) => typescript::run(args)?,
     };
 
     if !res {
@@ -1,11 +1,13 @@
 use crate::{diag, CommonGenArgs, CommonReadArgs};
 use aldrin_codegen::{Generator, Options, RustOptions};
-use aldrin_parser::Parser;
+use aldrin_parser::Schema;
 use anyhow::{anyhow, Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
+use std::fmt::Write as _;
 use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(clap::Parser)]
 #[clap(arg_required_else_help = true)]
@@ -46,12 +48,39 @@ pub struct RustArgs {
     #[clap(long, value_name = "FEATURE")]
     introspection_if: Option<String>,
 
+    /// Generate mock implementations of services.
+    #[clap(long)]
+    mocks: bool,
+
+    /// Derive serde's `Serialize` and `Deserialize` traits on generated types.
+    ///
+    /// The generated code requires the `serde` Cargo feature of the `aldrin` crate.
+    #[clap(long)]
+    serde: bool,
+
     /// Path of the aldrin crate
     #[clap(long = "crate", value_name = "PATH")]
     krate: Option<String>,
 
-    /// Path to an Aldrin schema file.
-    schema: PathBuf,
+    /// Also generate modules for all imported schemas.
+    ///
+    /// Every schema is generated exactly once into its own module, even if it is imported by
+    /// several other schemas. Additionally, a `mod.rs` is written, which declares all modules and
+    /// describes which schemas import each other. Generated code refers to types of other schemas
+    /// via `super::`, so all modules must be siblings, e.g. by including `mod.rs` as a module.
+    ///
+    /// Patches are applied only to the modules of the schemas specified on the command line.
+    #[clap(long)]
+    imports: bool,
+
+    /// Paths to Aldrin schema files.
+    #[clap(required = true)]
+    schemas: Vec<PathBuf>,
+}
+
+struct Module {
+    imports: BTreeSet<String>,
+    content: String,
 }
 
 pub fn run(args: RustArgs) -> Result<bool> {
@@ -62,23 +91,7 @@ pub fn run(args: RustArgs) -> Result<bool> {
         }
     };
 
-    let mut parser = Parser::new();
-
-    for include in args.common_read_args.include {
-        parser.add_schema_path(include);
-    }
-
-    let parsed = parser.parse(args.schema);
-    diag::print_diagnostics(&parsed);
-
-    if parsed.errors().is_empty() {
-        if !parsed.warnings().is_empty() || !parsed.other_warnings().is_empty() {
-            println!("Some warning(s) found.");
-        }
-    } else {
-        println!("Some error(s) found.");
-        return Ok(false);
-    }
+    let parser = args.common_read_args.parser()?;
 
     let mut options = Options::new();
     options.client = !args.common_gen_args.no_client;
@@ -95,30 +108,127 @@ pub fn run(args: RustArgs) -> Result<bool> {
     rust_options.event_non_exhaustive = !args.no_event_non_exhaustive;
     rust_options.function_non_exhaustive = !args.no_function_non_exhaustive;
     rust_options.introspection_if = args.introspection_if.as_deref();
+    rust_options.mocks = args.mocks;
+    rust_options.serde = args.serde;
 
     if let Some(ref krate) = args.krate {
         rust_options.krate = krate;
     }
 
-    let generator = Generator::new(&options, &parsed);
-    let output = generator.generate_rust(&rust_options)?;
+    let mut imported_options = rust_options.clone();
+    imported_options.patches.clear();
+
+    let mut parsed_schemas = Vec::with_capacity(args.schemas.len());
+    let mut warnings = false;
+    let mut errors = false;
+
+    for schema in &args.schemas {
+        let parsed = parser.parse(schema);
+        diag::print_diagnostics(&parsed);
+
+        warnings |= !parsed.warnings().is_empty() || !parsed.other_warnings().is_empty();
+        errors |= !parsed.errors().is_empty();
+
+        parsed_schemas.push(parsed);
+    }
+
+    if errors {
+        println!("Some error(s) found.");
+        return Ok(false);
+    } else if warnings {
+        println!("Some warning(s) found.");
+    }
+
+    let mut modules = BTreeMap::new();
 
-    let module_path = output_dir.join(format!("{}.rs", output.module_name));
-    let file = if args.common_gen_args.overwrite {
+    for parsed in &parsed_schemas {
+        let generator = Generator::new(&options, parsed);
+        let output = generator.generate_rust(&rust_options)?;
+        let module = Module::new(parsed.main_schema(), output.module_content);
+        modules.insert(output.module_name, module);
+    }
+
+    if args.imports {
+        for parsed in &parsed_schemas {
+            let generator = Generator::new(&options, parsed);
+
+            for schema in parsed.schemas() {
+                if modules.contains_key(schema.name()) {
+                    continue;
+                }
+
+                let output = generator.generate_rust_schema(schema.name(), &imported_options)?;
+                let module = Module::new(schema, output.module_content);
+                modules.insert(output.module_name, module);
+            }
+        }
+    }
+
+    for (name, module) in &modules {
+        let path = output_dir.join(format!("{name}.rs"));
+        write_file(&path, &module.content, args.common_gen_args.overwrite)?;
+    }
+
+    if args.imports {
+        let path = output_dir.join("mod.rs");
+        write_file(&path, &manifest(&modules), args.common_gen_args.overwrite)?;
+    }
+
+    Ok(true)
+}
+
+impl Module {
+    fn new(schema: &Schema, content: String) -> Self {
+        let imports = schema
+            .imports()
+            .iter()
+            .map(|import| import.schema_name().value().to_owned())
+            .collect();
+
+        Self { imports, content }
+    }
+}
+
+fn manifest(modules: &BTreeMap<String, Module>) -> String {
+    let mut manifest = String::new();
+
+    for (name, module) in modules {
+        if !manifest.is_empty() {
+            manifest.push('\n');
+        }
+
+        if module.imports.is_empty() {
+            writeln!(manifest, "/// Schema `{name}`.").unwrap();
+        } else {
+            let imports = module
+                .imports
+                .iter()
+                .map(|import| format!("`{import}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            writeln!(manifest, "/// Schema `{name}`, which imports {imports}.").unwrap();
+        }
+
+        writeln!(manifest, "pub mod r#{name};").unwrap();
+    }
+
+    manifest
+}
+
+fn write_file(path: &Path, content: &str, overwrite: bool) -> Result<()> {
+    let file = if overwrite {
         File::options()
             .create(true)
             .truncate(true)
             .write(true)
-            .open(&module_path)
+            .open(path)
     } else {
-        File::options()
-            .create_new(true)
-            .write(true)
-            .open(&module_path)
+        File::options().create_new(true).write(true).open(path)
     };
-    let mut file = file.with_context(|| anyhow!("failed to open `{}`", module_path.display()))?;
+    let mut file = file.with_context(|| anyhow!("failed to open `{}`", path.display()))?;
 
-    file.write_all(output.module_content.as_bytes())?;
-    println!("File `{}` written.", module_path.display());
-    Ok(true)
+    file.write_all(content.as_bytes())?;
+    println!("File `{}` written.", path.display());
+    Ok(())
 }
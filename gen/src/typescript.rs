@@ -0,0 +1,79 @@
+use crate::{diag, CommonGenArgs, CommonReadArgs};
+use aldrin_codegen::{Generator, Options, TypeScriptOptions};
+use anyhow::{anyhow, Context, Result};
+use std::env;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
+#[derive(clap::Parser)]
+#[clap(arg_required_else_help = true)]
+pub struct TypeScriptArgs {
+    #[clap(flatten)]
+    common_read_args: CommonReadArgs,
+
+    #[clap(flatten)]
+    common_gen_args: CommonGenArgs,
+
+    /// Name of the aldrin TypeScript package
+    #[clap(long, value_name = "NAME")]
+    package: Option<String>,
+
+    /// Path to an Aldrin schema file.
+    schema: PathBuf,
+}
+
+pub fn run(args: TypeScriptArgs) -> Result<bool> {
+    let output_dir = match args.common_gen_args.output_dir {
+        Some(output_dir) => output_dir,
+        None => {
+            env::current_dir().with_context(|| anyhow!("failed to determine current directory"))?
+        }
+    };
+
+    let parser = args.common_read_args.parser()?;
+
+    let parsed = parser.parse(args.schema);
+    diag::print_diagnostics(&parsed);
+
+    if parsed.errors().is_empty() {
+        if !parsed.warnings().is_empty() || !parsed.other_warnings().is_empty() {
+            println!("Some warning(s) found.");
+        }
+    } else {
+        println!("Some error(s) found.");
+        return Ok(false);
+    }
+
+    let mut options = Options::new();
+    options.client = !args.common_gen_args.no_client;
+    options.server = !args.common_gen_args.no_server;
+    options.introspection = args.common_gen_args.introspection;
+
+    let mut ts_options = TypeScriptOptions::new();
+    if let Some(ref package) = args.package {
+        ts_options.package = package;
+    }
+
+    let generator = Generator::new(&options, &parsed);
+    let output = generator.generate_typescript(&ts_options)?;
+
+    let module_path = output_dir.join(format!("{}.ts", output.module_name));
+    let file = if args.common_gen_args.overwrite {
+        File::options()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(&module_path)
+    } else {
+        File::options()
+            .create_new(true)
+            .write(true)
+            .open(&module_path)
+    };
+    let mut file = file.with_context(|| anyhow!("failed to open `{}`", module_path.display()))?;
+
+    file.write_all(output.module_content.as_bytes())?;
+    println!("File `{}` written.", module_path.display());
+    Ok(true)
+}
@@ -0,0 +1,318 @@
+use aldrin::core::tokio::{TokioTransport, TokioTransportError};
+use aldrin::core::{Bytes, ObjectUuid, ServiceUuid};
+use aldrin::error::RunError;
+use aldrin::low_level::{Proxy, ServiceInfo};
+use aldrin::{Client, Handle};
+use anyhow::{anyhow, Context, Error, Result};
+use clap::Parser;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+const BUS_DEFAULT: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 24940);
+
+/// Load generator for Aldrin brokers.
+///
+/// All workloads connect a number of clients to a broker via TCP, run for a fixed duration and
+/// then print a summary.
+#[derive(Parser)]
+struct Args {
+    /// Address of the broker to connect to.
+    #[clap(short, long, default_value_t = BUS_DEFAULT)]
+    bus: SocketAddr,
+
+    /// Number of connections used by the workload.
+    #[clap(short, long, default_value_t = 4)]
+    connections: usize,
+
+    /// Duration of the workload in seconds.
+    #[clap(short, long, default_value_t = 10)]
+    duration: u64,
+
+    #[clap(subcommand)]
+    cmd: Command,
+}
+
+#[derive(Parser)]
+enum Command {
+    /// Measure the throughput of function calls from all connections to a single service.
+    Calls,
+
+    /// Measure the latency until an event has reached all connections.
+    Events,
+
+    /// Measure the bandwidth of channels, with one channel between each pair of connections.
+    Channels(ChannelsArgs),
+}
+
+#[derive(Parser)]
+struct ChannelsArgs {
+    /// Size of each item in bytes.
+    #[clap(short, long, default_value_t = 1024)]
+    item_size: usize,
+
+    /// Capacity of each channel.
+    #[clap(short = 'C', long, default_value_t = 16)]
+    capacity: u32,
+}
+
+struct Bus {
+    handles: Vec<Handle>,
+    joins: Vec<JoinHandle<Result<(), RunError<TokioTransportError>>>>,
+}
+
+impl Bus {
+    async fn connect(addr: SocketAddr, num: usize) -> Result<Self> {
+        let mut handles = Vec::with_capacity(num);
+        let mut joins = Vec::with_capacity(num);
+
+        for _ in 0..num {
+            let stream = TcpStream::connect(&addr)
+                .await
+                .with_context(|| anyhow!("failed to connect to broker at {addr}"))?;
+
+            stream.set_nodelay(true)?;
+
+            let client = Client::connect(TokioTransport::new(stream))
+                .await
+                .with_context(|| anyhow!("failed to connect to broker at {addr}"))?;
+
+            handles.push(client.handle().clone());
+            joins.push(tokio::spawn(client.run()));
+        }
+
+        Ok(Self { handles, joins })
+    }
+
+    async fn shutdown(self) -> Result<()> {
+        for handle in &self.handles {
+            handle.shutdown();
+        }
+
+        for join in self.joins {
+            join.await
+                .with_context(|| anyhow!("failed to shut down client"))?
+                .with_context(|| anyhow!("failed to shut down client"))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    if args.connections == 0 {
+        return Err(anyhow!("at least 1 connection is required"));
+    }
+
+    let duration = Duration::from_secs(args.duration);
+
+    println!(
+        "Running workload with {} connection(s) against broker at {} for {}s.",
+        args.connections,
+        args.bus,
+        duration.as_secs(),
+    );
+
+    match args.cmd {
+        Command::Calls => calls(args.bus, args.connections, duration).await,
+        Command::Events => events(args.bus, args.connections, duration).await,
+        Command::Channels(cmd) => channels(args.bus, args.connections, duration, cmd).await,
+    }
+}
+
+async fn calls(addr: SocketAddr, conns: usize, duration: Duration) -> Result<()> {
+    let server = Bus::connect(addr, 1).await?;
+    let callers = Bus::connect(addr, conns).await?;
+
+    let obj = server.handles[0]
+        .create_object(ObjectUuid::new_v4())
+        .await?;
+    let mut svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await?;
+    let svc_id = svc.id();
+
+    tokio::spawn(async move {
+        while let Some(call) = svc.next_call().await {
+            let _ = call.into_promise().done();
+        }
+    });
+
+    let start = Instant::now();
+    let deadline = start + duration;
+
+    let tasks: Vec<_> = callers
+        .handles
+        .iter()
+        .map(|caller| {
+            let caller = caller.clone();
+
+            tokio::spawn(async move {
+                let proxy = Proxy::new(&caller, svc_id).await?;
+                let mut num = 0u64;
+
+                while Instant::now() < deadline {
+                    proxy
+                        .call(0, &())
+                        .await?
+                        .map_err(|_| anyhow!("call returned an error"))?;
+                    num += 1;
+                }
+
+                Ok::<_, Error>(num)
+            })
+        })
+        .collect();
+
+    let mut num = 0;
+    for task in tasks {
+        num += task.await??;
+    }
+
+    let elapsed = start.elapsed();
+
+    println!("Calls: {num}");
+    println!(
+        "Throughput: {:.1} calls/s",
+        num as f64 / elapsed.as_secs_f64()
+    );
+
+    callers.shutdown().await?;
+    server.shutdown().await
+}
+
+async fn events(addr: SocketAddr, conns: usize, duration: Duration) -> Result<()> {
+    let emitter = Bus::connect(addr, 1).await?;
+    let subscribers = Bus::connect(addr, conns).await?;
+
+    let obj = emitter.handles[0]
+        .create_object(ObjectUuid::new_v4())
+        .await?;
+    let svc = obj
+        .create_service(ServiceUuid::new_v4(), ServiceInfo::new(0))
+        .await?;
+
+    let (send, mut recv) = mpsc::unbounded_channel();
+
+    for subscriber in &subscribers.handles {
+        let mut proxy = Proxy::new(subscriber, svc.id()).await?;
+        proxy.subscribe(0).await?;
+
+        let send = send.clone();
+        tokio::spawn(async move {
+            while proxy.next_event().await.is_some() {
+                if send.send(Instant::now()).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    let start = Instant::now();
+    let deadline = start + duration;
+    let mut latencies = Vec::new();
+
+    while Instant::now() < deadline {
+        let emitted = Instant::now();
+        svc.emit(0, &())?;
+
+        let mut received = emitted;
+        for _ in 0..conns {
+            let instant = recv
+                .recv()
+                .await
+                .ok_or_else(|| anyhow!("subscriber shut down"))?;
+
+            received = received.max(instant);
+        }
+
+        latencies.push(received - emitted);
+    }
+
+    let elapsed = start.elapsed();
+    latencies.sort();
+
+    let percentile = |p: usize| latencies[(latencies.len() - 1) * p / 100].as_secs_f64() * 1e6;
+
+    println!("Events: {}", latencies.len());
+    println!(
+        "Throughput: {:.1} events/s",
+        latencies.len() as f64 / elapsed.as_secs_f64(),
+    );
+    println!(
+        "Fan-out latency: median {:.1}us, p99 {:.1}us, max {:.1}us",
+        percentile(50),
+        percentile(99),
+        percentile(100),
+    );
+
+    subscribers.shutdown().await?;
+    emitter.shutdown().await
+}
+
+async fn channels(
+    addr: SocketAddr,
+    conns: usize,
+    duration: Duration,
+    args: ChannelsArgs,
+) -> Result<()> {
+    let senders = Bus::connect(addr, conns).await?;
+    let receivers = Bus::connect(addr, conns).await?;
+
+    let start = Instant::now();
+    let deadline = start + duration;
+    let mut tasks = Vec::with_capacity(conns);
+
+    for (sender, receiver) in senders.handles.iter().zip(&receivers.handles) {
+        let (sender, unclaimed) = sender.create_channel::<Bytes>().claim_sender().await?;
+
+        let mut receiver = unclaimed
+            .unbind()
+            .claim(receiver.clone(), args.capacity)
+            .await?;
+
+        let mut sender = sender.establish().await?;
+
+        tasks.push(tokio::spawn(async move {
+            let item = Bytes(vec![0; args.item_size]);
+
+            while Instant::now() < deadline {
+                sender.send_ref(&item).await?;
+            }
+
+            sender.close().await?;
+            Ok::<_, Error>(0)
+        }));
+
+        tasks.push(tokio::spawn(async move {
+            let mut len = 0;
+
+            while let Some(item) = receiver.next_item().await? {
+                len += item.0.len();
+            }
+
+            Ok::<_, Error>(len)
+        }));
+    }
+
+    let mut len = 0;
+    for task in tasks {
+        len += task.await??;
+    }
+
+    let elapsed = start.elapsed();
+
+    println!("Bytes: {len}");
+    println!(
+        "Bandwidth: {:.1} MiB/s",
+        len as f64 / elapsed.as_secs_f64() / (1024.0 * 1024.0),
+    );
+
+    receivers.shutdown().await?;
+    senders.shutdown().await
+}
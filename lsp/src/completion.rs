@@ -0,0 +1,226 @@
+use aldrin_parser::ast::Definition;
+use aldrin_parser::document::{Document, SymbolKind, Token, TokenKind};
+use aldrin_parser::Parsed;
+use serde_json::{json, Value};
+
+const BUILTIN_TYPES: &[&str] = &[
+    "bool",
+    "box",
+    "broadcast",
+    "bytes",
+    "f32",
+    "f64",
+    "i16",
+    "i32",
+    "i64",
+    "i8",
+    "lifetime",
+    "map",
+    "object_id",
+    "option",
+    "receiver",
+    "result",
+    "sender",
+    "service_id",
+    "set",
+    "string",
+    "u16",
+    "u32",
+    "u64",
+    "u8",
+    "unit",
+    "uuid",
+    "value",
+    "vec",
+];
+
+// See the LSP specification of `CompletionItemKind`.
+const KIND_CLASS: u32 = 7;
+const KIND_INTERFACE: u32 = 8;
+const KIND_MODULE: u32 = 9;
+const KIND_VALUE: u32 = 12;
+const KIND_ENUM: u32 = 13;
+const KIND_KEYWORD: u32 = 14;
+const KIND_CONSTANT: u32 = 21;
+const KIND_STRUCT: u32 = 22;
+
+/// Computes completion items at `index`.
+///
+/// After `@`, the next free id is suggested. After `schema::`, the definitions of the imported
+/// schema are suggested. Otherwise, all type names are suggested.
+pub fn complete(doc: &Document, parsed: Option<&Parsed>, index: usize) -> Vec<Value> {
+    let tokens = doc
+        .tokens()
+        .iter()
+        .filter(|t| !matches!(t.kind, TokenKind::Comment | TokenKind::DocComment))
+        .collect::<Vec<_>>();
+
+    let mut prev = tokens.partition_point(|t| t.span.to.index <= index);
+
+    // Skip the word, that is currently being typed.
+    if (prev > 0)
+        && (tokens[prev - 1].span.to.index == index)
+        && matches!(
+            tokens[prev - 1].kind,
+            TokenKind::Ident | TokenKind::Keyword | TokenKind::LitInt
+        )
+    {
+        prev -= 1;
+    }
+
+    let Some(ctx) = prev.checked_sub(1) else {
+        return type_names(doc);
+    };
+
+    match text(doc, tokens[ctx]) {
+        "@" => {
+            let id = next_id(doc, &tokens, ctx);
+            vec![item(&id.to_string(), KIND_VALUE, None)]
+        }
+
+        "::" => match ctx.checked_sub(1) {
+            Some(schema) => schema_definitions(parsed, text(doc, tokens[schema])),
+            None => Vec::new(),
+        },
+
+        _ => type_names(doc),
+    }
+}
+
+fn type_names(doc: &Document) -> Vec<Value> {
+    let builtins = BUILTIN_TYPES
+        .iter()
+        .map(|&name| item(name, KIND_KEYWORD, None));
+
+    let symbols = doc.symbols().iter().map(|symbol| {
+        let kind = match symbol.kind {
+            SymbolKind::Import => KIND_MODULE,
+            SymbolKind::Struct => KIND_STRUCT,
+            SymbolKind::Enum => KIND_ENUM,
+            SymbolKind::Service => KIND_INTERFACE,
+            SymbolKind::Const => KIND_CONSTANT,
+            _ => KIND_CLASS,
+        };
+
+        item(&symbol.name, kind, None)
+    });
+
+    builtins.chain(symbols).collect()
+}
+
+fn schema_definitions(parsed: Option<&Parsed>, schema: &str) -> Vec<Value> {
+    let Some(schema) = parsed.and_then(|parsed| parsed.get_schema(schema)) else {
+        return Vec::new();
+    };
+
+    schema
+        .definitions()
+        .iter()
+        .map(|def| {
+            let (kind, doc) = match def {
+                Definition::Struct(d) => (KIND_STRUCT, d.doc()),
+                Definition::Enum(d) => (KIND_ENUM, d.doc()),
+                Definition::Service(d) => (KIND_INTERFACE, d.doc()),
+                Definition::Const(d) => (KIND_CONSTANT, d.doc()),
+                Definition::Newtype(d) => (KIND_CLASS, d.doc()),
+            };
+
+            item(def.name().value(), kind, doc)
+        })
+        .collect()
+}
+
+/// Finds the next free id for the `@` token at `at`.
+///
+/// All ids of the enclosing block are considered. In services, functions, events and properties
+/// are considered separately.
+fn next_id(doc: &Document, tokens: &[&Token], at: usize) -> u64 {
+    let mut depth = 0;
+    let mut open = None;
+
+    for i in (0..at).rev() {
+        match text(doc, tokens[i]) {
+            "}" => depth += 1,
+            "{" if depth == 0 => {
+                open = Some(i);
+                break;
+            }
+            "{" => depth -= 1,
+            _ => {}
+        }
+    }
+
+    let Some(open) = open else {
+        return 1;
+    };
+
+    let is_enum = tokens[..open]
+        .iter()
+        .rev()
+        .take(2)
+        .any(|t| text(doc, t) == "enum");
+
+    let mut depth = 0;
+    let mut kind = None;
+    let mut at_kind = None;
+    let mut max = None;
+    let mut ids = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate().skip(open + 1) {
+        match text(doc, token) {
+            "{" | "(" | "[" => depth += 1,
+            "}" | ")" | "]" if depth == 0 => break,
+            "}" | ")" | "]" => depth -= 1,
+            "fn" | "event" | "property" if depth == 0 => kind = Some(text(doc, token)),
+
+            "@" if depth == 0 => {
+                if i == at {
+                    at_kind = kind;
+                    continue;
+                }
+
+                let Some(id) = tokens.get(i + 1) else {
+                    continue;
+                };
+
+                if let Ok(id) = text(doc, id).parse::<u64>() {
+                    ids.push((kind, id));
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    for (kind, id) in ids {
+        if kind == at_kind {
+            max = max.max(Some(id));
+        }
+    }
+
+    match max {
+        Some(max) => max + 1,
+        None if is_enum => 0,
+        None => 1,
+    }
+}
+
+fn text<'a>(doc: &'a Document, token: &Token) -> &'a str {
+    &doc.source()[token.span.from.index..token.span.to.index]
+}
+
+fn item(label: &str, kind: u32, doc: Option<&str>) -> Value {
+    let mut item = json!({
+        "label": label,
+        "kind": kind,
+    });
+
+    if let Some(doc) = doc {
+        item["documentation"] = json!({
+            "kind": "markdown",
+            "value": doc,
+        });
+    }
+
+    item
+}
@@ -0,0 +1,111 @@
+use aldrin_parser::Span;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// Converts an LSP position into a byte index into `source`.
+///
+/// LSP positions use zero-based lines and UTF-16 code units. Positions past the end of a line are
+/// clamped to the end of the line.
+pub fn index(source: &str, pos: &Value) -> Option<usize> {
+    let line = pos["line"].as_u64()? as usize;
+    let character = pos["character"].as_u64()? as usize;
+
+    let mut start = 0;
+    for _ in 0..line {
+        match source[start..].find('\n') {
+            Some(i) => start += i + 1,
+            None => return Some(source.len()),
+        }
+    }
+
+    let text = source[start..].split('\n').next().unwrap();
+    let text = text.strip_suffix('\r').unwrap_or(text);
+
+    let mut utf16 = 0;
+    for (i, c) in text.char_indices() {
+        if utf16 >= character {
+            return Some(start + i);
+        }
+
+        utf16 += c.len_utf16();
+    }
+
+    Some(start + text.len())
+}
+
+/// Converts a byte index into `source` into an LSP position.
+pub fn position(source: &str, index: usize) -> Value {
+    let before = &source[..index];
+    let line = before.matches('\n').count();
+    let start = before.rfind('\n').map_or(0, |i| i + 1);
+    let character = before[start..].chars().map(char::len_utf16).sum::<usize>();
+
+    json!({
+        "line": line,
+        "character": character,
+    })
+}
+
+/// Converts a span into an LSP range.
+pub fn range(source: &str, span: Span) -> Value {
+    json!({
+        "start": position(source, span.from.index),
+        "end": position(source, span.to.index),
+    })
+}
+
+/// Creates an LSP location.
+pub fn location(uri: &str, range: Value) -> Value {
+    json!({
+        "uri": uri,
+        "range": range,
+    })
+}
+
+/// Converts a `file` URI into a path.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let bytes = path.as_bytes();
+
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = path.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    let path = String::from_utf8(decoded).ok()?;
+
+    // Windows paths look like `/C:/foo`.
+    if cfg!(windows) && (path.as_bytes().get(2) == Some(&b':')) {
+        Some(PathBuf::from(&path[1..]))
+    } else {
+        Some(PathBuf::from(path))
+    }
+}
+
+/// Converts a path into a `file` URI.
+pub fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"/-._~:".contains(&b) {
+            uri.push(b as char);
+        } else {
+            uri.push_str(&format!("%{b:02X}"));
+        }
+    }
+
+    uri
+}
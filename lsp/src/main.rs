@@ -0,0 +1,66 @@
+mod completion;
+mod convert;
+mod server;
+mod transport;
+
+#[cfg(test)]
+mod test;
+
+use aldrin_parser::resolver::Lockfile;
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use server::Server;
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+
+/// Language server for Aldrin schemas.
+///
+/// The server communicates over stdin and stdout. It supports diagnostics, go-to-definition,
+/// references, document symbols and completion of type names and ids.
+#[derive(Parser)]
+#[clap(version)]
+struct Args {
+    /// Additional include directories.
+    ///
+    /// Can be specified multiple times. The directory of each schema and all workspace folders are
+    /// always searched as well.
+    #[clap(short = 'I', long)]
+    include: Vec<PathBuf>,
+
+    /// Lockfile describing additional sources of schemas, e.g. git repositories.
+    ///
+    /// Can be specified multiple times.
+    #[clap(short = 'L', long)]
+    lockfile: Vec<PathBuf>,
+
+    /// Directory, in which git repositories are cached.
+    ///
+    /// A directory in the system's temporary directory is used if this is not specified.
+    #[clap(long, value_name = "DIR")]
+    schema_cache: Option<PathBuf>,
+
+    /// Communicate over stdin and stdout.
+    ///
+    /// This is the default and only supported mode. The flag exists only for compatibility with
+    /// editors, that always pass it.
+    #[clap(long)]
+    stdio: bool,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let lockfiles = args
+        .lockfile
+        .iter()
+        .map(|lockfile| {
+            Lockfile::load(lockfile)
+                .with_context(|| anyhow!("failed to load lockfile `{}`", lockfile.display()))
+        })
+        .collect::<Result<_>>()?;
+
+    let stdin = BufReader::new(io::stdin());
+    let stdout = io::stdout();
+
+    Server::new(stdout, args.include, lockfiles, args.schema_cache).run(stdin)
+}
@@ -0,0 +1,507 @@
+use crate::completion;
+use crate::convert;
+use crate::transport;
+use aldrin_parser::diag::DiagnosticKind;
+use aldrin_parser::document::{Document, Symbol, SymbolKind};
+use aldrin_parser::resolver::Lockfile;
+use aldrin_parser::{Diagnostic, Parsed, Parser};
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+// See the LSP specification of `DiagnosticSeverity`.
+const SEVERITY_ERROR: u32 = 1;
+const SEVERITY_WARNING: u32 = 2;
+
+// See the LSP specification of `SymbolKind`.
+const SYMBOL_MODULE: u32 = 2;
+const SYMBOL_CLASS: u32 = 5;
+const SYMBOL_METHOD: u32 = 6;
+const SYMBOL_PROPERTY: u32 = 7;
+const SYMBOL_FIELD: u32 = 8;
+const SYMBOL_ENUM: u32 = 10;
+const SYMBOL_INTERFACE: u32 = 11;
+const SYMBOL_CONSTANT: u32 = 14;
+const SYMBOL_ENUM_MEMBER: u32 = 22;
+const SYMBOL_STRUCT: u32 = 23;
+const SYMBOL_EVENT: u32 = 24;
+
+type RequestResult = Result<Value, (i64, String)>;
+
+pub struct Server<W> {
+    writer: W,
+    includes: Vec<PathBuf>,
+    lockfiles: Vec<Lockfile>,
+    schema_cache: Option<PathBuf>,
+    roots: Vec<PathBuf>,
+    documents: HashMap<String, Open>,
+    shutdown: bool,
+}
+
+struct Open {
+    path: PathBuf,
+    doc: Document,
+    parsed: Option<Parsed>,
+}
+
+impl<W: Write> Server<W> {
+    pub fn new(
+        writer: W,
+        includes: Vec<PathBuf>,
+        lockfiles: Vec<Lockfile>,
+        schema_cache: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            writer,
+            includes,
+            lockfiles,
+            schema_cache,
+            roots: Vec::new(),
+            documents: HashMap::new(),
+            shutdown: false,
+        }
+    }
+
+    /// Runs the server until the client sends `exit` or closes the connection.
+    pub fn run(mut self, mut reader: impl BufRead) -> Result<()> {
+        while let Some(msg) = transport::read_message(&mut reader)? {
+            let Some(method) = msg["method"].as_str() else {
+                // Responses are ignored, because the server never sends requests.
+                continue;
+            };
+
+            let params = &msg["params"];
+
+            match msg.get("id") {
+                Some(id) => {
+                    let res = if self.shutdown {
+                        Err((INVALID_REQUEST, "server is shutting down".to_owned()))
+                    } else {
+                        self.request(method, params)
+                    };
+
+                    let response = match res {
+                        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+
+                        Err((code, message)) => json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": { "code": code, "message": message },
+                        }),
+                    };
+
+                    transport::write_message(&mut self.writer, &response)?;
+                }
+
+                None if method == "exit" => break,
+                None => self.notification(method, params)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn request(&mut self, method: &str, params: &Value) -> RequestResult {
+        match method {
+            "initialize" => Ok(self.initialize(params)),
+
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+
+            "textDocument/completion" => {
+                let (open, index) = self.document_position(params)?;
+                let items = completion::complete(&open.doc, open.parsed.as_ref(), index);
+                Ok(Value::Array(items))
+            }
+
+            "textDocument/definition" => {
+                let uri = uri(params)?;
+                let (open, index) = self.document_position(params)?;
+                Ok(definition(uri, open, index).unwrap_or(Value::Null))
+            }
+
+            "textDocument/documentSymbol" => {
+                let open = self.document(uri(params)?)?;
+                let source = open.doc.source();
+
+                let symbols = open
+                    .doc
+                    .symbols()
+                    .iter()
+                    .map(|symbol| document_symbol(source, symbol))
+                    .collect();
+
+                Ok(Value::Array(symbols))
+            }
+
+            "textDocument/references" => {
+                let uri = uri(params)?;
+                let (open, index) = self.document_position(params)?;
+                let source = open.doc.source();
+
+                let mut locations = Vec::new();
+                let target = open.doc.definition_at(index);
+
+                if params["context"]["includeDeclaration"].as_bool() == Some(true) {
+                    if let Some(target) = target {
+                        let range = convert::range(source, target.name_span);
+                        locations.push(convert::location(uri, range));
+                    }
+                }
+
+                // References to imports point at the schema name.
+                let is_import = target.is_some_and(|target| target.kind == SymbolKind::Import);
+
+                for reference in open.doc.references_at(index) {
+                    let span = match reference.schema_span {
+                        Some(schema_span) if is_import => schema_span,
+                        _ => reference.name_span,
+                    };
+
+                    let range = convert::range(source, span);
+                    locations.push(convert::location(uri, range));
+                }
+
+                Ok(Value::Array(locations))
+            }
+
+            _ => Err((METHOD_NOT_FOUND, format!("unknown method `{method}`"))),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Result<()> {
+        match method {
+            "textDocument/didOpen" => {
+                let doc = &params["textDocument"];
+
+                let (Some(uri), Some(text)) = (doc["uri"].as_str(), doc["text"].as_str()) else {
+                    return Ok(());
+                };
+
+                let Some(path) = convert::uri_to_path(uri) else {
+                    return Ok(());
+                };
+
+                let open = Open {
+                    path,
+                    doc: Document::new(text),
+                    parsed: None,
+                };
+
+                self.documents.insert(uri.to_owned(), open);
+                self.check(uri)?;
+            }
+
+            "textDocument/didChange" => {
+                let Some(uri) = params["textDocument"]["uri"].as_str() else {
+                    return Ok(());
+                };
+
+                let Some(open) = self.documents.get_mut(uri) else {
+                    return Ok(());
+                };
+
+                let changes = params["contentChanges"].as_array().into_iter().flatten();
+                for change in changes {
+                    let Some(text) = change["text"].as_str() else {
+                        continue;
+                    };
+
+                    let range = &change["range"];
+                    if range.is_null() {
+                        open.doc.set_source(text);
+                        continue;
+                    }
+
+                    let source = open.doc.source();
+                    let start = convert::index(source, &range["start"]);
+                    let end = convert::index(source, &range["end"]);
+
+                    if let (Some(start), Some(end)) = (start, end) {
+                        open.doc.edit(start..end.max(start), text);
+                    }
+                }
+
+                self.check(uri)?;
+            }
+
+            "textDocument/didSave" => {
+                // Saving a schema may affect all other schemas, that import it.
+                let uris = self.documents.keys().cloned().collect::<Vec<_>>();
+
+                for uri in uris {
+                    self.check(&uri)?;
+                }
+            }
+
+            "textDocument/didClose" => {
+                if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                    self.documents.remove(uri);
+                    self.publish_diagnostics(uri, Vec::new())?;
+                }
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn initialize(&mut self, params: &Value) -> Value {
+        let folders = params["workspaceFolders"].as_array().into_iter().flatten();
+
+        for folder in folders {
+            if let Some(path) = folder["uri"].as_str().and_then(convert::uri_to_path) {
+                self.roots.push(path);
+            }
+        }
+
+        if self.roots.is_empty() {
+            if let Some(path) = params["rootUri"].as_str().and_then(convert::uri_to_path) {
+                self.roots.push(path);
+            }
+        }
+
+        json!({
+            "capabilities": {
+                "positionEncoding": "utf-16",
+                "textDocumentSync": {
+                    "openClose": true,
+                    "change": 2,
+                    "save": true,
+                },
+                "completionProvider": {
+                    "triggerCharacters": ["@", ":"],
+                },
+                "definitionProvider": true,
+                "documentSymbolProvider": true,
+                "referencesProvider": true,
+            },
+            "serverInfo": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        })
+    }
+
+    /// Checks a document and publishes its diagnostics.
+    ///
+    /// Syntax errors are taken from the document itself, because it can report more than one.
+    /// Only if there are none, the schema is fully parsed and validated, including its imports.
+    fn check(&mut self, uri: &str) -> Result<()> {
+        let Some(open) = self.documents.get(uri) else {
+            return Ok(());
+        };
+
+        let source = open.doc.source();
+        let mut diagnostics = Vec::new();
+
+        if !open.doc.errors().is_empty() {
+            for err in open.doc.errors() {
+                let to = source[err.position.index..]
+                    .chars()
+                    .next()
+                    .map_or(err.position.index, |c| err.position.index + c.len_utf8());
+
+                diagnostics.push(json!({
+                    "range": {
+                        "start": convert::position(source, err.position.index),
+                        "end": convert::position(source, to),
+                    },
+                    "severity": SEVERITY_ERROR,
+                    "source": "aldrin",
+                    "message": err.to_string(),
+                }));
+            }
+
+            return self.publish_diagnostics(uri, diagnostics);
+        }
+
+        let parsed = self.parser(open).parse_source(&open.path, source);
+        let main_schema = parsed.main_schema().name();
+
+        let errors = parsed.errors().iter().map(|d| d as &dyn Diagnostic);
+        let warnings = parsed.warnings().iter().map(|d| d as &dyn Diagnostic);
+        let others = parsed.other_warnings().iter().map(|d| d as &dyn Diagnostic);
+
+        for diag in errors.chain(warnings).chain(others) {
+            let formatted = diag.format(&parsed);
+
+            let severity = match diag.kind() {
+                DiagnosticKind::Error => SEVERITY_ERROR,
+                DiagnosticKind::Warning => SEVERITY_WARNING,
+            };
+
+            // Diagnostics of imported schemas are reported at the import.
+            let (span, message) = if diag.schema_name() == main_schema {
+                let Some(span) = formatted.span() else {
+                    continue;
+                };
+
+                (span, formatted.summary().to_owned())
+            } else {
+                let import = open
+                    .doc
+                    .symbols()
+                    .iter()
+                    .find(|s| (s.kind == SymbolKind::Import) && (s.name == diag.schema_name()));
+
+                let Some(import) = import else {
+                    continue;
+                };
+
+                let message = format!(
+                    "imported schema `{}`: {}",
+                    diag.schema_name(),
+                    formatted.summary()
+                );
+
+                (import.name_span, message)
+            };
+
+            diagnostics.push(json!({
+                "range": convert::range(source, span),
+                "severity": severity,
+                "source": "aldrin",
+                "message": message,
+            }));
+        }
+
+        self.documents.get_mut(uri).unwrap().parsed = Some(parsed);
+        self.publish_diagnostics(uri, diagnostics)
+    }
+
+    fn parser(&self, open: &Open) -> Parser {
+        let mut parser = Parser::new();
+
+        if let Some(dir) = open.path.parent() {
+            parser.add_schema_path(dir);
+        }
+
+        for root in &self.roots {
+            parser.add_schema_path(root);
+        }
+
+        for lockfile in &self.lockfiles {
+            for resolver in lockfile.resolvers(self.schema_cache.as_deref()) {
+                parser.add_resolver(resolver);
+            }
+        }
+
+        for include in &self.includes {
+            parser.add_schema_path(include);
+        }
+
+        parser
+    }
+
+    fn publish_diagnostics(&mut self, uri: &str, diagnostics: Vec<Value>) -> Result<()> {
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": {
+                "uri": uri,
+                "diagnostics": diagnostics,
+            },
+        });
+
+        transport::write_message(&mut self.writer, &notification)
+    }
+
+    fn document(&self, uri: &str) -> Result<&Open, (i64, String)> {
+        self.documents
+            .get(uri)
+            .ok_or_else(|| (INVALID_PARAMS, format!("unknown document `{uri}`")))
+    }
+
+    fn document_position(&self, params: &Value) -> Result<(&Open, usize), (i64, String)> {
+        let open = self.document(uri(params)?)?;
+
+        let index = convert::index(open.doc.source(), &params["position"])
+            .ok_or_else(|| (INVALID_PARAMS, "invalid position".to_owned()))?;
+
+        Ok((open, index))
+    }
+}
+
+fn uri(params: &Value) -> Result<&str, (i64, String)> {
+    params["textDocument"]["uri"]
+        .as_str()
+        .ok_or_else(|| (INVALID_PARAMS, "missing document uri".to_owned()))
+}
+
+/// Finds the definition at `index`.
+///
+/// References to other schemas are resolved with the last successfully parsed state of the
+/// document. Imports resolve to the imported schema's file.
+fn definition(uri: &str, open: &Open, index: usize) -> Option<Value> {
+    let doc = &open.doc;
+
+    if let Some(reference) = doc.reference_at(index) {
+        if let (Some(schema), Some(schema_span)) = (&reference.schema, reference.schema_span) {
+            if index > schema_span.to.index {
+                let schema = open.parsed.as_ref()?.get_schema(schema)?;
+
+                let def = schema
+                    .definitions()
+                    .iter()
+                    .find(|def| def.name().value() == reference.name)?;
+
+                let range = convert::range(schema.source()?, def.name().span());
+                let uri = convert::path_to_uri(schema.path());
+                return Some(convert::location(&uri, range));
+            }
+        }
+    }
+
+    let symbol = doc.definition_at(index)?;
+
+    if symbol.kind == SymbolKind::Import {
+        let schema = open.parsed.as_ref()?.get_schema(&symbol.name)?;
+        let start = convert::position("", 0);
+        let range = json!({ "start": start, "end": start });
+        let uri = convert::path_to_uri(schema.path());
+        Some(convert::location(&uri, range))
+    } else {
+        let range = convert::range(doc.source(), symbol.name_span);
+        Some(convert::location(uri, range))
+    }
+}
+
+fn document_symbol(source: &str, symbol: &Symbol) -> Value {
+    let kind = match symbol.kind {
+        SymbolKind::Import => SYMBOL_MODULE,
+        SymbolKind::Struct => SYMBOL_STRUCT,
+        SymbolKind::Enum => SYMBOL_ENUM,
+        SymbolKind::Service => SYMBOL_INTERFACE,
+        SymbolKind::Const => SYMBOL_CONSTANT,
+        SymbolKind::Newtype => SYMBOL_CLASS,
+        SymbolKind::Field => SYMBOL_FIELD,
+        SymbolKind::Variant => SYMBOL_ENUM_MEMBER,
+        SymbolKind::Function => SYMBOL_METHOD,
+        SymbolKind::Event => SYMBOL_EVENT,
+        SymbolKind::Property => SYMBOL_PROPERTY,
+    };
+
+    let children = symbol
+        .children
+        .iter()
+        .map(|child| document_symbol(source, child))
+        .collect::<Vec<_>>();
+
+    json!({
+        "name": symbol.name,
+        "kind": kind,
+        "range": convert::range(source, symbol.span),
+        "selectionRange": convert::range(source, symbol.name_span),
+        "children": children,
+    })
+}
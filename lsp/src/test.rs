@@ -0,0 +1,175 @@
+use crate::server::Server;
+use crate::transport;
+use serde_json::{json, Value};
+use std::io::BufReader;
+
+const URI: &str = "file:///aldrin-lsp-test/test.aldrin";
+
+fn encode(messages: &[Value]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for msg in messages {
+        transport::write_message(&mut buf, msg).unwrap();
+    }
+    buf
+}
+
+/// Runs the server on `messages` and returns everything it sent back.
+fn run(messages: &[Value]) -> Vec<Value> {
+    let input = encode(messages);
+    let mut output = Vec::new();
+
+    Server::new(&mut output, Vec::new(), Vec::new(), None)
+        .run(BufReader::new(input.as_slice()))
+        .unwrap();
+
+    let mut reader = output.as_slice();
+    let mut responses = Vec::new();
+    while let Some(msg) = transport::read_message(&mut reader).unwrap() {
+        responses.push(msg);
+    }
+    responses
+}
+
+fn did_open(text: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {
+            "textDocument": {
+                "uri": URI,
+                "languageId": "aldrin",
+                "version": 1,
+                "text": text,
+            },
+        },
+    })
+}
+
+fn request(id: u32, method: &str, line: u32, character: u32) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": method,
+        "params": {
+            "textDocument": { "uri": URI },
+            "position": { "line": line, "character": character },
+        },
+    })
+}
+
+fn response(responses: &[Value], id: u32) -> &Value {
+    let response = responses
+        .iter()
+        .find(|msg| msg["id"] == id)
+        .expect("no response");
+
+    &response["result"]
+}
+
+fn diagnostics(responses: &[Value]) -> &Vec<Value> {
+    let notification = responses
+        .iter()
+        .find(|msg| msg["method"] == "textDocument/publishDiagnostics")
+        .expect("no diagnostics");
+
+    assert_eq!(notification["params"]["uri"], URI);
+    notification["params"]["diagnostics"].as_array().unwrap()
+}
+
+#[test]
+fn diagnostics_valid_schema() {
+    let responses = run(&[did_open("struct Foo {\n    bar @0 = u32;\n}\n")]);
+    assert_eq!(diagnostics(&responses), &Vec::<Value>::new());
+}
+
+#[test]
+fn diagnostics_syntax_error() {
+    let responses = run(&[did_open("struct Foo {\n    bar @0 = ;\n}\n")]);
+    let diagnostics = diagnostics(&responses);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["severity"], 1);
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], 1);
+}
+
+#[test]
+fn diagnostics_validation_error() {
+    let responses = run(&[did_open(
+        "struct Foo {\n    bar @0 = u32;\n    baz @0 = u32;\n}\n",
+    )]);
+    let diagnostics = diagnostics(&responses);
+
+    assert!(!diagnostics.is_empty());
+    assert!(diagnostics.iter().all(|diag| diag["severity"] == 1));
+}
+
+#[test]
+fn go_to_definition() {
+    let responses = run(&[
+        did_open("struct Foo {}\n\nstruct Bar {\n    foo @0 = Foo;\n}\n"),
+        request(1, "textDocument/definition", 3, 14),
+    ]);
+
+    assert_eq!(
+        *response(&responses, 1),
+        json!({
+            "uri": URI,
+            "range": {
+                "start": { "line": 0, "character": 7 },
+                "end": { "line": 0, "character": 10 },
+            },
+        })
+    );
+}
+
+#[test]
+fn go_to_definition_nothing() {
+    let responses = run(&[
+        did_open("struct Foo {}\n"),
+        request(1, "textDocument/definition", 0, 0),
+    ]);
+
+    assert_eq!(*response(&responses, 1), Value::Null);
+}
+
+#[test]
+fn completion_next_id() {
+    let responses = run(&[
+        did_open("struct Foo {\n    bar @0 = u32;\n    baz @\n}\n"),
+        request(1, "textDocument/completion", 2, 9),
+    ]);
+
+    let items = response(&responses, 1).as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["label"], "1");
+}
+
+#[test]
+fn completion_type_names() {
+    let responses = run(&[
+        did_open("struct Foo {}\n\nstruct Bar {\n    foo @0 = \n}\n"),
+        request(1, "textDocument/completion", 3, 13),
+    ]);
+
+    let items = response(&responses, 1).as_array().unwrap();
+    let labels = items
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect::<Vec<_>>();
+
+    assert!(labels.contains(&"Foo"));
+    assert!(labels.contains(&"u32"));
+}
+
+#[test]
+fn unknown_document() {
+    let responses = run(&[request(1, "textDocument/completion", 0, 0)]);
+    let response = responses.iter().find(|msg| msg["id"] == 1).unwrap();
+    assert_eq!(response["error"]["code"], -32602);
+}
+
+#[test]
+fn content_length_too_large() {
+    let input = b"Content-Length: 18446744073709551615\r\n\r\n{}";
+    assert!(transport::read_message(&mut input.as_slice()).is_err());
+}
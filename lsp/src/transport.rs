@@ -0,0 +1,66 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+/// Maximum accepted value of the `Content-Length` header.
+///
+/// Larger messages are rejected before any memory is allocated for them.
+const MAX_CONTENT_LENGTH: usize = 64 * 1024 * 1024;
+
+/// Reads a single message.
+///
+/// Returns `Ok(None)` when the input has been closed.
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut len = None;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            if len.is_some() {
+                break;
+            } else {
+                continue;
+            }
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                let value = value
+                    .trim()
+                    .parse()
+                    .context("invalid Content-Length header")?;
+                len = Some(value);
+            }
+        }
+    }
+
+    let len = len.ok_or_else(|| anyhow!("missing Content-Length header"))?;
+    if len > MAX_CONTENT_LENGTH {
+        return Err(anyhow!(
+            "Content-Length {len} exceeds the maximum of {MAX_CONTENT_LENGTH}"
+        ));
+    }
+
+    let mut buf = vec![0; len];
+    reader.read_exact(&mut buf)?;
+
+    serde_json::from_slice(&buf)
+        .map(Some)
+        .context("failed to parse message")
+}
+
+/// Writes a single message.
+pub fn write_message(writer: &mut impl Write, msg: &Value) -> Result<()> {
+    let body = serde_json::to_vec(msg)?;
+
+    write!(writer, "Content-Length: {}\r\n\r\n", body.len())?;
+    writer.write_all(&body)?;
+    writer.flush()?;
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use aldrin_codegen::{Generator, Options, RustOptions};
+use aldrin_parser::resolver::Lockfile;
 use aldrin_parser::{Diagnostic, Parsed, Parser};
 use manyhow::{emit, Emitter};
 use proc_macro2::Span;
@@ -11,6 +12,20 @@ use syn::{Error, Ident, LitBool, LitStr, Result, Token};
 
 pub fn generate(args: Args, emitter: &mut Emitter) -> manyhow::Result {
     let mut parser = Parser::new();
+
+    for lockfile in &args.lockfiles {
+        let lockfile = Lockfile::load(lockfile).map_err(|e| {
+            Error::new(
+                Span::call_site(),
+                format!("failed to load lockfile `{}`: {e}", lockfile.display()),
+            )
+        })?;
+
+        for resolver in lockfile.resolvers(None) {
+            parser.add_resolver(resolver);
+        }
+    }
+
     for include in args.includes {
         parser.add_schema_path(include);
     }
@@ -47,6 +62,8 @@ pub fn generate(args: Args, emitter: &mut Emitter) -> manyhow::Result {
         rust_options.event_non_exhaustive = args.event_non_exhaustive;
         rust_options.function_non_exhaustive = args.function_non_exhaustive;
         rust_options.introspection_if = args.introspection_if.as_deref();
+        rust_options.mocks = args.mocks;
+        rust_options.serde = args.serde;
 
         if let Some(ref krate) = args.krate {
             rust_options.krate = krate;
@@ -70,6 +87,27 @@ pub fn generate(args: Args, emitter: &mut Emitter) -> manyhow::Result {
         )
         .unwrap();
 
+        let main_schema = parsed.main_schema().name();
+        for imported in parsed.schemas() {
+            if imported.name() != main_schema {
+                write!(
+                    &mut modules,
+                    "const _: &[u8] = include_bytes!(\"{}\"); ",
+                    imported.path().display()
+                )
+                .unwrap();
+            }
+        }
+
+        for lockfile in &args.lockfiles {
+            write!(
+                &mut modules,
+                "const _: &[u8] = include_bytes!(\"{}\"); ",
+                lockfile.display()
+            )
+            .unwrap();
+        }
+
         for patch in &args.patches {
             write!(
                 &mut modules,
@@ -93,6 +131,7 @@ pub fn generate(args: Args, emitter: &mut Emitter) -> manyhow::Result {
 pub struct Args {
     schemas: Vec<PathBuf>,
     includes: Vec<PathBuf>,
+    lockfiles: Vec<PathBuf>,
     options: Options,
     warnings_as_errors: bool,
     patches: Vec<PathBuf>,
@@ -102,6 +141,8 @@ pub struct Args {
     event_non_exhaustive: bool,
     function_non_exhaustive: bool,
     introspection_if: Option<String>,
+    mocks: bool,
+    serde: bool,
     krate: Option<String>,
 }
 
@@ -112,6 +153,7 @@ impl Parse for Args {
         let mut args = Self {
             schemas: vec![first_schema],
             includes: Vec::new(),
+            lockfiles: Vec::new(),
             options: Options::default(),
             warnings_as_errors: false,
             patches: Vec::new(),
@@ -121,6 +163,8 @@ impl Parse for Args {
             event_non_exhaustive: true,
             function_non_exhaustive: true,
             introspection_if: None,
+            mocks: false,
+            serde: false,
             krate: None,
         };
 
@@ -143,6 +187,9 @@ impl Parse for Args {
             if opt == "include" {
                 let lit_str = input.parse::<LitStr>()?;
                 args.includes.push(lit_str_to_path(&lit_str)?);
+            } else if opt == "lockfile" {
+                let lit_str = input.parse::<LitStr>()?;
+                args.lockfiles.push(lit_str_to_path(&lit_str)?);
             } else if opt == "client" {
                 args.options.client = input.parse::<LitBool>()?.value;
             } else if opt == "server" {
@@ -168,6 +215,10 @@ impl Parse for Args {
                 let lit_str = input.parse::<LitStr>()?;
                 args.introspection_if = Some(lit_str.value());
                 args.options.introspection = true;
+            } else if opt == "mocks" {
+                args.mocks = input.parse::<LitBool>()?.value;
+            } else if opt == "serde" {
+                args.serde = input.parse::<LitBool>()?.value;
             } else if opt == "crate" {
                 let lit_str = input.parse::<LitStr>()?;
                 args.krate = Some(lit_str.value());
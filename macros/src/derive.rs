@@ -9,9 +9,14 @@ mod serialize_key;
 #[cfg(test)]
 mod test;
 
-use options::{ItemOptions, Options};
+use options::{check_alias_ids, ItemOptions, Options};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
 use syn::punctuated::Punctuated;
-use syn::{parse_quote, GenericParam, Generics, Path, Token, WherePredicate};
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Field, Fields, GenericParam, Generics, Index, Path,
+    Result, Token, Variant, WherePredicate,
+};
 
 pub use as_serialize_arg::{gen_as_serialize_arg_from_aldrin, gen_as_serialize_arg_from_core};
 pub use deserialize::{gen_deserialize_from_aldrin, gen_deserialize_from_core};
@@ -44,3 +49,95 @@ fn add_trait_bounds(
 
     generics
 }
+
+/// Returns the single field of a struct with the `newtype` attribute and the expression to access
+/// it.
+fn newtype_field(input: &DeriveInput) -> Result<(&Field, TokenStream)> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => Some(&fields.named),
+            Fields::Unnamed(ref fields) => Some(&fields.unnamed),
+            Fields::Unit => None,
+        },
+
+        Data::Enum(_) | Data::Union(_) => None,
+    };
+
+    match fields {
+        Some(fields) if fields.len() == 1 => {
+            let field = &fields[0];
+
+            let acc = if let Some(ref ident) = field.ident {
+                ident.to_token_stream()
+            } else {
+                Index::from(0).into_token_stream()
+            };
+
+            Ok((field, acc))
+        }
+
+        _ => Err(Error::new_spanned(
+            &input.ident,
+            "newtype can only be used on structs with exactly one field",
+        )),
+    }
+}
+
+/// Returns the index of the field with the `fallback` attribute and the expression to access it.
+fn fallback_field(fields: &Punctuated<Field, Token![,]>) -> Result<Option<(usize, TokenStream)>> {
+    let mut fallback = None;
+
+    for (index, field) in fields.iter().enumerate() {
+        if !ItemOptions::new(&field.attrs, 0)?.is_fallback() {
+            continue;
+        }
+
+        if fallback.is_some() {
+            return Err(Error::new_spanned(
+                field,
+                "only one field can be marked as fallback",
+            ));
+        }
+
+        let acc = if let Some(ref ident) = field.ident {
+            ident.to_token_stream()
+        } else {
+            Index::from(index).into_token_stream()
+        };
+
+        fallback = Some((index, acc));
+    }
+
+    Ok(fallback)
+}
+
+/// Returns the variant with the `fallback` attribute.
+fn fallback_variant(variants: &Punctuated<Variant, Token![,]>) -> Result<Option<&Variant>> {
+    let mut fallback = None;
+
+    for variant in variants {
+        if !ItemOptions::new(&variant.attrs, 0)?.is_fallback() {
+            continue;
+        }
+
+        if fallback.is_some() {
+            return Err(Error::new_spanned(
+                variant,
+                "only one variant can be marked as fallback",
+            ));
+        }
+
+        match variant.fields {
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => fallback = Some(variant),
+
+            _ => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "fallback variants must have exactly 1 element",
+                ))
+            }
+        }
+    }
+
+    Ok(fallback)
+}
@@ -1,4 +1,7 @@
-use super::{add_trait_bounds, ItemOptions, Options};
+use super::{
+    add_trait_bounds, check_alias_ids, fallback_field, fallback_variant, newtype_field,
+    ItemOptions, Options,
+};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use syn::punctuated::Punctuated;
@@ -18,24 +21,34 @@ fn gen_deserialize(input: DeriveInput, options: Options) -> Result<TokenStream>
     let name = &input.ident;
     let krate = options.krate();
 
-    let body = match input.data {
-        Data::Struct(data) => match data.fields {
-            Fields::Named(fields) => gen_struct(&fields.named, &options, true)?,
-            Fields::Unnamed(fields) => gen_struct(&fields.unnamed, &options, false)?,
+    let body = if options.newtype() {
+        let (_, acc) = newtype_field(&input)?;
 
-            Fields::Unit => quote! {
-                deserializer.deserialize_struct()?.skip()?;
-                ::std::result::Result::Ok(Self)
+        quote! {
+            ::core::result::Result::Ok(Self {
+                #acc: #krate::Deserialize::deserialize(deserializer)?,
+            })
+        }
+    } else {
+        match input.data {
+            Data::Struct(data) => match data.fields {
+                Fields::Named(fields) => gen_struct(&fields.named, &options, true)?,
+                Fields::Unnamed(fields) => gen_struct(&fields.unnamed, &options, false)?,
+
+                Fields::Unit => quote! {
+                    deserializer.deserialize_struct()?.skip()?;
+                    ::core::result::Result::Ok(Self)
+                },
             },
-        },
 
-        Data::Enum(data) => gen_enum(&data.variants, &options)?,
+            Data::Enum(data) => gen_enum(&data.variants, &options)?,
 
-        Data::Union(_) => {
-            return Err(Error::new_spanned(
-                input.ident,
-                "unions are not supported by Aldrin",
-            ))
+            Data::Union(_) => {
+                return Err(Error::new_spanned(
+                    input.ident,
+                    "unions are not supported by Aldrin",
+                ))
+            }
         }
     };
 
@@ -51,7 +64,7 @@ fn gen_deserialize(input: DeriveInput, options: Options) -> Result<TokenStream>
         impl #impl_generics #krate::Deserialize for #name #ty_generics #where_clause {
             fn deserialize(
                 deserializer: #krate::Deserializer,
-            ) -> ::std::result::Result<Self, #krate::DeserializeError> {
+            ) -> ::core::result::Result<Self, #krate::DeserializeError> {
                 #body
             }
         }
@@ -64,6 +77,7 @@ fn gen_struct(
     named: bool,
 ) -> Result<TokenStream> {
     let krate = options.krate();
+    let fallback = fallback_field(fields)?.map(|(index, _)| index);
 
     let fields = {
         let mut next_id = 0;
@@ -74,24 +88,56 @@ fn gen_struct(
             .map(|(index, field)| {
                 let item_options = ItemOptions::new(&field.attrs, next_id)?;
                 let field_ident = format_ident!("field{index}");
-                next_id = item_options.id() + 1;
+
+                if Some(index) != fallback {
+                    next_id = item_options.id() + 1;
+                }
+
                 Ok((field, item_options, field_ident))
             })
             .collect::<Result<Vec<_>>>()?
     };
 
-    let field_vars = fields.iter().map(|(_, _, field_ident)| {
-        quote! { let mut #field_ident = ::std::option::Option::None; }
+    check_alias_ids(
+        fields
+            .iter()
+            .filter(|(_, item_options, _)| !item_options.is_fallback())
+            .map(|(_, item_options, _)| item_options),
+    )?;
+
+    let field_vars = fields.iter().map(|(_, item_options, field_ident)| {
+        if item_options.is_fallback() {
+            quote! { let mut #field_ident = #krate::UnknownFields::new(); }
+        } else {
+            quote! { let mut #field_ident = ::core::option::Option::None; }
+        }
     });
 
+    // Fields are deserialized from their alias ids only if the current id hasn't been encountered
+    // (yet). A value for the current id always takes precedence.
     let match_arms = fields.iter().map(|(_, item_options, field_ident)| {
         let id = item_options.id();
+        let alias_ids = item_options.alias_ids();
 
-        if item_options.is_optional() {
-            quote! { #id => #field_ident = deserializer.deserialize()?, }
+        if item_options.is_fallback() {
+            TokenStream::new()
+        } else if item_options.is_optional() {
+            quote! {
+                #id => #field_ident = deserializer.deserialize()?,
+                #(#alias_ids => if #field_ident.is_none() {
+                    #field_ident = deserializer.deserialize()?;
+                } else {
+                    deserializer.skip()?;
+                })*
+            }
         } else {
             quote! {
-                #id => #field_ident = deserializer.deserialize().map(::std::option::Option::Some)?,
+                #id => #field_ident = deserializer.deserialize().map(::core::option::Option::Some)?,
+                #(#alias_ids => if #field_ident.is_none() {
+                    #field_ident = deserializer.deserialize().map(::core::option::Option::Some)?;
+                } else {
+                    deserializer.skip()?;
+                })*
             }
         }
     });
@@ -100,7 +146,7 @@ fn gen_struct(
         let field_inits = fields.iter().map(|(field, item_options, field_ident)| {
             let ident = field.ident.as_ref().unwrap();
 
-            if item_options.is_optional() {
+            if item_options.is_optional() || item_options.is_fallback() {
                 quote! { #ident: #field_ident }
             } else {
                 quote! {
@@ -112,7 +158,7 @@ fn gen_struct(
         quote! { Self { #(#field_inits),* } }
     } else {
         let field_inits = fields.iter().map(|(_, item_options, field_ident)| {
-            if item_options.is_optional() {
+            if item_options.is_optional() || item_options.is_fallback() {
                 quote! { #field_ident }
             } else {
                 quote! { #field_ident.ok_or(#krate::DeserializeError::InvalidSerialization)? }
@@ -122,6 +168,13 @@ fn gen_struct(
         quote! { Self(#(#field_inits),*) }
     };
 
+    let fallback_arm = if let Some(index) = fallback {
+        let field_ident = format_ident!("field{index}");
+        quote! { _ => deserializer.add_to_unknown_fields(&mut #field_ident)?, }
+    } else {
+        quote! { _ => deserializer.skip()?, }
+    };
+
     Ok(quote! {
         let mut deserializer = deserializer.deserialize_struct()?;
         #(#field_vars)*
@@ -131,22 +184,24 @@ fn gen_struct(
 
             match deserializer.id() {
                 #(#match_arms)*
-                _ => deserializer.skip()?,
+                #fallback_arm
             }
         }
 
-        deserializer.finish_with(|| ::std::result::Result::Ok(#ok_expr))
+        deserializer.finish_with(|| ::core::result::Result::Ok(#ok_expr))
     })
 }
 
 fn gen_enum(variants: &Punctuated<Variant, Token![,]>, options: &Options) -> Result<TokenStream> {
     let krate = options.krate();
+    let fallback = fallback_variant(variants)?.map(|variant| &variant.ident);
 
     let variants = {
         let mut next_id = 0;
 
         variants
             .into_iter()
+            .filter(|variant| Some(&variant.ident) != fallback)
             .map(|variant| {
                 let item_options = ItemOptions::new(&variant.attrs, next_id)?;
 
@@ -174,10 +229,13 @@ fn gen_enum(variants: &Punctuated<Variant, Token![,]>, options: &Options) -> Res
             .collect::<Result<Vec<_>>>()?
     };
 
+    check_alias_ids(variants.iter().map(|(_, item_options, _, _)| item_options))?;
+
     let match_arms = variants
         .iter()
         .map(|(ident, item_options, has_field, is_unit)| {
             let id = item_options.id();
+            let alias_ids = item_options.alias_ids();
 
             let rhs = match (has_field, is_unit) {
                 (true, _) => quote! { deserializer.deserialize().map(Self::#ident) },
@@ -185,15 +243,21 @@ fn gen_enum(variants: &Punctuated<Variant, Token![,]>, options: &Options) -> Res
                 (false, false) => quote! { deserializer.deserialize().map(|()| Self::#ident()) },
             };
 
-            quote! { #id => #rhs, }
+            quote! { #id #(| #alias_ids)* => #rhs, }
         });
 
+    let fallback_arm = if let Some(ident) = fallback {
+        quote! { _ => deserializer.into_unknown_variant().map(Self::#ident), }
+    } else {
+        quote! { _ => ::core::result::Result::Err(#krate::DeserializeError::InvalidSerialization), }
+    };
+
     Ok(quote! {
         let deserializer = deserializer.deserialize_enum()?;
 
         match deserializer.variant() {
             #(#match_arms)*
-            _ => ::std::result::Result::Err(#krate::DeserializeError::InvalidSerialization),
+            #fallback_arm
         }
     })
 }
@@ -76,9 +76,9 @@ fn gen_struct(fields: &Punctuated<Field, Token![,]>, options: &Options) -> Resul
     Ok(quote! {
         type Impl = <#ty as #krate::DeserializeKey>::Impl;
 
-        fn try_from_impl(key: Self::Impl) -> ::std::result::Result<Self, #krate::DeserializeError> {
+        fn try_from_impl(key: Self::Impl) -> ::core::result::Result<Self, #krate::DeserializeError> {
             let key = <#ty as #krate::DeserializeKey>::try_from_impl(key)?;
-            ::std::result::Result::Ok(Self #ctor)
+            ::core::result::Result::Ok(Self #ctor)
         }
     })
 }
@@ -1,4 +1,6 @@
-use super::{add_trait_bounds, ItemOptions, Options};
+use super::{
+    add_trait_bounds, fallback_field, fallback_variant, newtype_field, ItemOptions, Options,
+};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::ext::IdentExt;
@@ -20,6 +22,10 @@ fn gen_introspectable(input: DeriveInput, options: Options) -> Result<TokenStrea
     let name = ident.unraw().to_string();
     let krate = options.krate();
 
+    if options.newtype() {
+        return gen_newtype(input, &options);
+    }
+
     let schema = options.schema().ok_or_else(|| {
         Error::new_spanned(
             &input,
@@ -27,14 +33,24 @@ fn gen_introspectable(input: DeriveInput, options: Options) -> Result<TokenStrea
         )
     })?;
 
+    // Layouts of generic types are parameterized by the lexical ids of their type arguments.
+    let type_args: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|ty| {
+            let ty = &ty.ident;
+            quote! { <#ty as #krate::introspection::Introspectable>::lexical_id() }
+        })
+        .collect();
+
     let (layout, add_references) = match input.data {
         Data::Struct(data) => match data.fields {
-            Fields::Named(fields) => gen_struct(&fields.named, &name, &options)?,
-            Fields::Unnamed(fields) => gen_struct(&fields.unnamed, &name, &options)?,
-            Fields::Unit => gen_struct(&Punctuated::new(), &name, &options)?,
+            Fields::Named(fields) => gen_struct(&fields.named, &name, &type_args, &options)?,
+            Fields::Unnamed(fields) => gen_struct(&fields.unnamed, &name, &type_args, &options)?,
+            Fields::Unit => gen_struct(&Punctuated::new(), &name, &type_args, &options)?,
         },
 
-        Data::Enum(data) => gen_enum(&data.variants, &name, &options)?,
+        Data::Enum(data) => gen_enum(&data.variants, &name, &type_args, &options)?,
 
         Data::Union(_) => {
             return Err(Error::new_spanned(
@@ -46,15 +62,10 @@ fn gen_introspectable(input: DeriveInput, options: Options) -> Result<TokenStrea
 
     let generics = add_trait_bounds(
         input.generics,
-        &parse_quote!(#krate::Introspectable),
+        &parse_quote!(#krate::introspection::Introspectable),
         options.intro_bounds(),
     );
 
-    let generic_lexical_ids = generics.type_params().map(|ty| {
-        let ty = &ty.ident;
-        quote! { <#ty as #krate::introspection::Introspectable>::lexical_id() }
-    });
-
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     Ok(quote! {
@@ -68,7 +79,7 @@ fn gen_introspectable(input: DeriveInput, options: Options) -> Result<TokenStrea
                 #krate::introspection::LexicalId::custom_generic(
                     #schema,
                     #name,
-                    &[#( #generic_lexical_ids ),*],
+                    &[#( #type_args ),*],
                 )
             }
 
@@ -79,9 +90,41 @@ fn gen_introspectable(input: DeriveInput, options: Options) -> Result<TokenStrea
     })
 }
 
+fn gen_newtype(input: DeriveInput, options: &Options) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let krate = options.krate();
+    let (field, _) = newtype_field(&input)?;
+    let ty = &field.ty;
+
+    let generics = add_trait_bounds(
+        input.generics.clone(),
+        &parse_quote!(#krate::introspection::Introspectable),
+        options.intro_bounds(),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #krate::introspection::Introspectable for #ident #ty_generics #where_clause {
+            fn layout() -> #krate::introspection::Layout {
+                <#ty as #krate::introspection::Introspectable>::layout()
+            }
+
+            fn lexical_id() -> #krate::introspection::LexicalId {
+                <#ty as #krate::introspection::Introspectable>::lexical_id()
+            }
+
+            fn add_references(references: &mut #krate::introspection::References) {
+                <#ty as #krate::introspection::Introspectable>::add_references(references)
+            }
+        }
+    })
+}
+
 fn gen_struct(
     fields: &Punctuated<Field, Token![,]>,
     name: &str,
+    type_args: &[TokenStream],
     options: &Options,
 ) -> Result<(TokenStream, TokenStream)> {
     let krate = options.krate();
@@ -90,8 +133,14 @@ fn gen_struct(
     let mut layout: Vec<TokenStream> = Vec::new();
     let mut references: Vec<TokenStream> = Vec::new();
     let mut next_id = 0;
+    let fallback = fallback_field(fields)?;
 
     for (index, field) in fields.into_iter().enumerate() {
+        // Unknown fields are not part of the layout.
+        if fallback.as_ref().map(|(fallback, _)| *fallback) == Some(index) {
+            continue;
+        }
+
         let (field_layout, field_references, id) = gen_field(field, index, next_id, options)?;
 
         layout.push(field_layout);
@@ -102,15 +151,16 @@ fn gen_struct(
 
     let layout = quote! {
         #krate::introspection::Struct::builder(#schema, #name)
+            #(.type_arg(#type_args))*
             #(#layout)*
             .finish()
             .into()
     };
 
-    let add_references = if fields.is_empty() {
+    let add_references = if references.is_empty() {
         TokenStream::new()
     } else {
-        let len = fields.len();
+        let len = references.len();
 
         quote! {
             let types: [#krate::introspection::DynIntrospectable; #len] = [
@@ -163,6 +213,7 @@ fn gen_field(
 fn gen_enum(
     variants: &Punctuated<Variant, Token![,]>,
     name: &str,
+    type_args: &[TokenStream],
     options: &Options,
 ) -> Result<(TokenStream, TokenStream)> {
     let krate = options.krate();
@@ -171,8 +222,14 @@ fn gen_enum(
     let mut layout: Vec<TokenStream> = Vec::new();
     let mut references: Vec<TokenStream> = Vec::new();
     let mut next_id = 0;
+    let fallback = fallback_variant(variants)?;
 
     for variant in variants.into_iter() {
+        // Unknown variants are not part of the layout.
+        if fallback.map(|fallback| &fallback.ident) == Some(&variant.ident) {
+            continue;
+        }
+
         let (var_layout, var_references, id) = gen_variant(variant, next_id, options)?;
 
         next_id = id + 1;
@@ -185,6 +242,7 @@ fn gen_enum(
 
     let layout = quote! {
         #krate::introspection::Enum::builder(#schema, #name)
+            #(.type_arg(#type_args))*
             #(#layout)*
             .finish()
             .into()
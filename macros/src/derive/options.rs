@@ -1,3 +1,5 @@
+use proc_macro2::Span;
+use std::collections::HashSet;
 use syn::punctuated::Punctuated;
 use syn::{Attribute, Error, LitInt, LitStr, Path, Result, Token, WherePredicate};
 
@@ -10,6 +12,7 @@ pub struct Options {
     de_key_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
     key_ty_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
     schema: Option<LitStr>,
+    newtype: bool,
 }
 
 impl Options {
@@ -21,6 +24,7 @@ impl Options {
         let mut de_key_bounds = None;
         let mut key_ty_bounds = None;
         let mut schema = None;
+        let mut newtype = false;
 
         for attr in attrs {
             if !attr.path().is_ident("aldrin") {
@@ -59,6 +63,9 @@ impl Options {
                 } else if meta.path.is_ident("schema") {
                     schema = meta.value()?.parse().map(Some)?;
                     Ok(())
+                } else if meta.path.is_ident("newtype") {
+                    newtype = true;
+                    Ok(())
                 } else {
                     Err(meta.error("unknown attribute"))
                 }
@@ -74,6 +81,7 @@ impl Options {
             de_key_bounds,
             key_ty_bounds,
             schema,
+            newtype,
         })
     }
 
@@ -108,17 +116,25 @@ impl Options {
     pub fn schema(&self) -> Option<&LitStr> {
         self.schema.as_ref()
     }
+
+    pub fn newtype(&self) -> bool {
+        self.newtype
+    }
 }
 
 pub struct ItemOptions {
     id: u32,
+    alias_ids: Vec<(u32, Span)>,
     optional: bool,
+    fallback: bool,
 }
 
 impl ItemOptions {
     pub fn new(attrs: &[Attribute], default_id: u32) -> Result<Self> {
-        let mut id = default_id;
+        let mut id = None;
+        let mut alias_ids = Vec::new();
         let mut optional = false;
+        let mut fallback = false;
 
         for attr in attrs {
             if !attr.path().is_ident("aldrin") {
@@ -128,27 +144,75 @@ impl ItemOptions {
             attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("id") {
                     let value: LitInt = meta.value()?.parse()?;
-                    id = value.base10_parse()?;
+                    id = value.base10_parse().map(Some)?;
+                    Ok(())
+                } else if meta.path.is_ident("alias_id") {
+                    let value: LitInt = meta.value()?.parse()?;
+                    alias_ids.push((value.base10_parse()?, value.span()));
                     Ok(())
                 } else if meta.path.is_ident("optional") {
                     optional = true;
                     Ok(())
+                } else if meta.path.is_ident("fallback") {
+                    fallback = true;
+                    Ok(())
                 } else {
                     Err(meta.error("unknown attribute"))
                 }
             })?;
+
+            if fallback && (optional || id.is_some() || !alias_ids.is_empty()) {
+                return Err(Error::new_spanned(
+                    attr,
+                    "`fallback` cannot be combined with `id`, `alias_id` or `optional`",
+                ));
+            }
         }
 
-        Ok(Self { id, optional })
+        Ok(Self {
+            id: id.unwrap_or(default_id),
+            alias_ids,
+            optional,
+            fallback,
+        })
     }
 
     pub fn id(&self) -> u32 {
         self.id
     }
 
+    pub fn alias_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.alias_ids.iter().map(|&(id, _)| id)
+    }
+
     pub fn is_optional(&self) -> bool {
         self.optional
     }
+
+    pub fn is_fallback(&self) -> bool {
+        self.fallback
+    }
+}
+
+/// Ensures that alias ids don't collide with each other or with the ids of other items.
+///
+/// Fallback items are expected to be filtered out already.
+pub fn check_alias_ids<'a>(items: impl IntoIterator<Item = &'a ItemOptions> + Clone) -> Result<()> {
+    let ids = items
+        .clone()
+        .into_iter()
+        .map(ItemOptions::id)
+        .collect::<HashSet<_>>();
+
+    let mut alias_ids = HashSet::new();
+
+    for &(alias_id, span) in items.into_iter().flat_map(|item| &item.alias_ids) {
+        if ids.contains(&alias_id) || !alias_ids.insert(alias_id) {
+            return Err(Error::new(span, format!("id {alias_id} is already in use")));
+        }
+    }
+
+    Ok(())
 }
 
 fn parse_lit_str_into_where_predicates(
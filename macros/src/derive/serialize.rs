@@ -1,8 +1,12 @@
-use super::{add_trait_bounds, ItemOptions, Options};
+use super::{
+    add_trait_bounds, fallback_field, fallback_variant, newtype_field, ItemOptions, Options,
+};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::punctuated::Punctuated;
-use syn::{parse_quote, Data, DeriveInput, Error, Field, Fields, Index, Result, Token, Variant};
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Field, Fields, Index, Path, Result, Token, Variant,
+};
 
 pub fn gen_serialize_from_core(input: DeriveInput) -> Result<TokenStream> {
     let options = Options::new(&input.attrs, parse_quote!(::aldrin_core))?;
@@ -18,20 +22,25 @@ fn gen_serialize(input: DeriveInput, options: Options) -> Result<TokenStream> {
     let name = &input.ident;
     let krate = options.krate();
 
-    let body = match input.data {
-        Data::Struct(data) => match data.fields {
-            Fields::Named(fields) => gen_struct(&fields.named)?,
-            Fields::Unnamed(fields) => gen_struct(&fields.unnamed)?,
-            Fields::Unit => gen_struct(&Punctuated::new())?,
-        },
-
-        Data::Enum(data) => gen_enum(&data.variants)?,
-
-        Data::Union(_) => {
-            return Err(Error::new_spanned(
-                input.ident,
-                "unions are not supported by Aldrin",
-            ))
+    let body = if options.newtype() {
+        let (_, acc) = newtype_field(&input)?;
+        quote! { #krate::Serialize::serialize(&self.#acc, serializer) }
+    } else {
+        match input.data {
+            Data::Struct(data) => match data.fields {
+                Fields::Named(fields) => gen_struct(&fields.named)?,
+                Fields::Unnamed(fields) => gen_struct(&fields.unnamed)?,
+                Fields::Unit => gen_struct(&Punctuated::new())?,
+            },
+
+            Data::Enum(data) => gen_enum(&data.variants, krate)?,
+
+            Data::Union(_) => {
+                return Err(Error::new_spanned(
+                    input.ident,
+                    "unions are not supported by Aldrin",
+                ))
+            }
         }
     };
 
@@ -48,7 +57,7 @@ fn gen_serialize(input: DeriveInput, options: Options) -> Result<TokenStream> {
             fn serialize(
                 &self,
                 serializer: #krate::Serializer
-            ) -> ::std::result::Result<(), #krate::SerializeError> {
+            ) -> ::core::result::Result<(), #krate::SerializeError> {
                 #body
             }
         }
@@ -60,12 +69,20 @@ fn gen_struct(fields: &Punctuated<Field, Token![,]>) -> Result<TokenStream> {
     let mut num_optional_fields = Vec::new();
     let mut body = Vec::new();
     let mut next_id = 0;
+    let mut known_ids = Vec::new();
+    let fallback = fallback_field(fields)?;
 
     for (index, field) in fields.into_iter().enumerate() {
-        let (serialize, id, optional) = gen_field(field, index, next_id)?;
+        if fallback.as_ref().map(|(fallback, _)| *fallback) == Some(index) {
+            continue;
+        }
+
+        let (serialize, item_options, optional) = gen_field(field, index, next_id)?;
 
         body.push(serialize);
-        next_id = id + 1;
+        known_ids.push(item_options.id());
+        known_ids.extend(item_options.alias_ids());
+        next_id = item_options.id() + 1;
 
         if let Some(optional) = optional {
             num_optional_fields.push(optional);
@@ -74,6 +91,11 @@ fn gen_struct(fields: &Punctuated<Field, Token![,]>) -> Result<TokenStream> {
         }
     }
 
+    if let Some((_, acc)) = fallback {
+        num_optional_fields.push(quote! { self.#acc.len_except(&[#(#known_ids),*]) });
+        body.push(quote! { serializer.serialize_unknown_fields(&self.#acc, &[#(#known_ids),*])?; });
+    }
+
     match (num_required_fields, num_optional_fields.is_empty()) {
         (0, true) => Ok(quote! { serializer.serialize_struct(0)?.finish() }),
 
@@ -103,20 +125,20 @@ fn gen_field(
     field: &Field,
     index: usize,
     default_id: u32,
-) -> Result<(TokenStream, u32, Option<TokenStream>)> {
+) -> Result<(TokenStream, ItemOptions, Option<TokenStream>)> {
     let item_options = ItemOptions::new(&field.attrs, default_id)?;
     let id = item_options.id();
 
     let (serialize, optional) = match (field.ident.as_ref(), item_options.is_optional()) {
         (Some(ident), true) => {
             let serialize = quote! {
-                if ::std::option::Option::is_some(&self.#ident) {
+                if ::core::option::Option::is_some(&self.#ident) {
                     serializer.serialize_field(#id, &self.#ident)?;
                 }
             };
 
             let optional = Some(quote! {
-                if ::std::option::Option::is_some(&self.#ident) { 1 } else { 0 }
+                if ::core::option::Option::is_some(&self.#ident) { 1 } else { 0 }
             });
 
             (serialize, optional)
@@ -131,13 +153,13 @@ fn gen_field(
             let index = Index::from(index);
 
             let serialize = quote! {
-                if ::std::option::Option::is_some(&self.#index) {
+                if ::core::option::Option::is_some(&self.#index) {
                     serializer.serialize_field(#id, &self.#index)?;
                 }
             };
 
             let optional = Some(quote! {
-                if ::std::option::Option::is_some(&self.#index) { 1 } else { 0 }
+                if ::core::option::Option::is_some(&self.#index) { 1 } else { 0 }
             });
 
             (serialize, optional)
@@ -150,16 +172,22 @@ fn gen_field(
         }
     };
 
-    Ok((serialize, id, optional))
+    Ok((serialize, item_options, optional))
 }
 
-fn gen_enum(variants: &Punctuated<Variant, Token![,]>) -> Result<TokenStream> {
+fn gen_enum(variants: &Punctuated<Variant, Token![,]>, krate: &Path) -> Result<TokenStream> {
+    let fallback = fallback_variant(variants)?;
+
     let body = {
         let mut next_id = 0;
 
         variants
             .into_iter()
             .map(|variant| {
+                if fallback.map(|fallback| &fallback.ident) == Some(&variant.ident) {
+                    return Ok(gen_fallback_variant(variant, krate));
+                }
+
                 let (tokens, id) = gen_variant(variant, next_id)?;
                 next_id = id + 1;
                 Ok(tokens)
@@ -215,3 +243,14 @@ fn gen_variant(variant: &Variant, default_id: u32) -> Result<(TokenStream, u32)>
 
     Ok((tokens, id))
 }
+
+fn gen_fallback_variant(variant: &Variant, krate: &Path) -> TokenStream {
+    let ident = &variant.ident;
+
+    quote! {
+        Self::#ident(ref val) => <#krate::UnknownVariant as #krate::Serialize>::serialize(
+            val,
+            serializer,
+        )
+    }
+}
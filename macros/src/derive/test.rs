@@ -85,3 +85,374 @@ fn raw_identifiers_enum() {
     assert_eq!(var.name(), "else");
     assert_eq!(var.variant_type(), Some(LexicalId::U32));
 }
+
+#[test]
+fn newtype() {
+    use aldrin_core::{
+        Deserialize, DeserializeKey, KeyTypeOf, Serialize, SerializeKey, SerializedValue,
+    };
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    #[derive(
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        Serialize,
+        Deserialize,
+        Introspectable,
+        SerializeKey,
+        DeserializeKey,
+        KeyTypeOf,
+    )]
+    #[aldrin(newtype)]
+    struct PlayerId(Uuid);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[aldrin(newtype)]
+    struct Name {
+        name: String,
+    }
+
+    const UUID: Uuid = uuid!("5d6d3f6e-6a8c-4b3a-9a3f-0c1e6f2a7b4d");
+
+    let serialized = SerializedValue::serialize(&PlayerId(UUID)).unwrap();
+    assert_eq!(serialized.deserialize::<Uuid>().unwrap(), UUID);
+    assert_eq!(
+        serialized.deserialize::<PlayerId>().unwrap(),
+        PlayerId(UUID)
+    );
+
+    let serialized = SerializedValue::serialize("foo").unwrap();
+    assert_eq!(
+        serialized.deserialize::<Name>().unwrap(),
+        Name {
+            name: "foo".to_owned()
+        }
+    );
+
+    let map = HashMap::from([(PlayerId(UUID), 1u32)]);
+    let serialized = SerializedValue::serialize(&map).unwrap();
+    let map = serialized.deserialize::<HashMap<Uuid, u32>>().unwrap();
+    assert_eq!(map[&UUID], 1);
+
+    assert_eq!(PlayerId::lexical_id(), LexicalId::UUID);
+}
+
+#[test]
+fn fallback() {
+    use aldrin_core::{Deserialize, Serialize, SerializedValue, UnknownFields, UnknownVariant};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct StructV1 {
+        foo: u32,
+
+        #[aldrin(fallback)]
+        unknown: UnknownFields,
+
+        #[aldrin(optional)]
+        bar: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct StructV2 {
+        foo: u32,
+        bar: Option<String>,
+        baz: Vec<u8>,
+
+        #[aldrin(id = 5)]
+        qux: EnumV2,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, Introspectable)]
+    #[aldrin(schema = "test")]
+    enum EnumV1 {
+        Foo,
+
+        #[aldrin(fallback)]
+        Unknown(UnknownVariant),
+
+        Bar(u32),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum EnumV2 {
+        Foo,
+        Bar(u32),
+        Baz(String),
+    }
+
+    let v2 = StructV2 {
+        foo: 1,
+        bar: Some("bar".to_owned()),
+        baz: vec![2, 3],
+        qux: EnumV2::Baz("baz".to_owned()),
+    };
+
+    let serialized = SerializedValue::serialize(&v2).unwrap();
+
+    let v1 = serialized.deserialize::<StructV1>().unwrap();
+    assert_eq!(v1.foo, 1);
+    assert_eq!(v1.bar.as_deref(), Some("bar"));
+    assert_eq!(v1.unknown.len(), 2);
+    assert_eq!(
+        v1.unknown.deserialize::<Vec<u8>>(2).unwrap().unwrap(),
+        [2, 3]
+    );
+
+    let qux = v1.unknown.deserialize::<EnumV1>(5).unwrap().unwrap();
+    let EnumV1::Unknown(ref qux) = qux else {
+        panic!("unexpected variant {qux:?}");
+    };
+    assert_eq!(qux.id(), 2);
+    assert_eq!(qux.deserialize::<String>().unwrap(), "baz");
+
+    // Forwarding the value reproduces it exactly.
+    let forwarded = SerializedValue::serialize(&v1).unwrap();
+    assert_eq!(forwarded, serialized);
+    assert_eq!(forwarded.deserialize::<StructV2>().unwrap(), v2);
+
+    let forwarded = SerializedValue::serialize(&EnumV1::Unknown(qux.clone())).unwrap();
+    assert_eq!(
+        forwarded.deserialize::<EnumV2>().unwrap(),
+        EnumV2::Baz("baz".to_owned())
+    );
+
+    // Known variants keep their ids.
+    let serialized = SerializedValue::serialize(&EnumV2::Bar(4)).unwrap();
+    assert_eq!(serialized.deserialize::<EnumV1>().unwrap(), EnumV1::Bar(4));
+
+    // Fallback variants are not part of the layout.
+    let introspection = Introspection::new::<EnumV1>();
+    let variants = introspection.as_enum_layout().unwrap().variants();
+    assert_eq!(variants.len(), 2);
+    assert_eq!(variants[&0].name(), "Foo");
+    assert_eq!(variants[&1].name(), "Bar");
+}
+
+#[test]
+fn fallback_skips_known_ids() {
+    use aldrin_core::{Serialize, SerializedValue, UnknownFields};
+
+    #[derive(Debug, PartialEq, Serialize)]
+    struct Fallback {
+        foo: u32,
+
+        #[aldrin(id = 2, alias_id = 1, optional)]
+        bar: Option<u32>,
+
+        #[aldrin(fallback)]
+        unknown: UnknownFields,
+    }
+
+    let mut unknown = UnknownFields::new();
+    unknown.insert(0, SerializedValue::serialize(&3u32).unwrap());
+    unknown.insert(1, SerializedValue::serialize(&4u32).unwrap());
+    unknown.insert(2, SerializedValue::serialize(&5u32).unwrap());
+    unknown.insert(3, SerializedValue::serialize(&6u32).unwrap());
+
+    let value = Fallback {
+        foo: 1,
+        bar: None,
+        unknown,
+    };
+
+    let serialized = SerializedValue::serialize(&value).unwrap();
+    let fields = serialized.deserialize::<UnknownFields>().unwrap();
+
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields.deserialize::<u32>(0).unwrap().unwrap(), 1);
+    assert_eq!(fields.deserialize::<u32>(3).unwrap().unwrap(), 6);
+}
+
+#[test]
+fn alias_id() {
+    use aldrin_core::{Deserialize, Serialize, SerializedValue};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct StructV1 {
+        name: String,
+        count: u32,
+
+        #[aldrin(optional)]
+        comment: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct StructV2 {
+        #[aldrin(id = 3, alias_id = 0)]
+        name: String,
+
+        #[aldrin(id = 1)]
+        count: u32,
+
+        #[aldrin(id = 4, alias_id = 2, optional)]
+        comment: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct OldFirst {
+        #[aldrin(id = 0)]
+        old: String,
+
+        #[aldrin(id = 3)]
+        new: String,
+
+        #[aldrin(id = 1)]
+        count: u32,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct NewFirst {
+        #[aldrin(id = 3)]
+        new: String,
+
+        #[aldrin(id = 0)]
+        old: String,
+
+        #[aldrin(id = 1)]
+        count: u32,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum EnumV1 {
+        Foo,
+        Bar(u32),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum EnumV2 {
+        Foo,
+
+        #[aldrin(id = 5, alias_id = 1)]
+        Bar(u32),
+    }
+
+    let v1 = StructV1 {
+        name: "foo".to_owned(),
+        count: 1,
+        comment: Some("bar".to_owned()),
+    };
+
+    let serialized = SerializedValue::serialize(&v1).unwrap();
+    assert_eq!(
+        serialized.deserialize::<StructV2>().unwrap(),
+        StructV2 {
+            name: "foo".to_owned(),
+            count: 1,
+            comment: Some("bar".to_owned()),
+        }
+    );
+
+    // The current id takes precedence over the alias, regardless of the order.
+    let old_first = OldFirst {
+        old: "old".to_owned(),
+        new: "new".to_owned(),
+        count: 2,
+    };
+
+    let serialized = SerializedValue::serialize(&old_first).unwrap();
+    let v2 = serialized.deserialize::<StructV2>().unwrap();
+    assert_eq!(v2.name, "new");
+    assert_eq!(v2.count, 2);
+
+    let new_first = NewFirst {
+        new: "new".to_owned(),
+        old: "old".to_owned(),
+        count: 3,
+    };
+
+    let serialized = SerializedValue::serialize(&new_first).unwrap();
+    let v2 = serialized.deserialize::<StructV2>().unwrap();
+    assert_eq!(v2.name, "new");
+    assert_eq!(v2.count, 3);
+
+    // Values are always serialized with the current id.
+    let serialized = SerializedValue::serialize(&v2).unwrap();
+    assert!(serialized.deserialize::<StructV1>().is_err());
+
+    let serialized = SerializedValue::serialize(&EnumV1::Bar(4)).unwrap();
+    assert_eq!(serialized.deserialize::<EnumV2>().unwrap(), EnumV2::Bar(4));
+
+    let serialized = SerializedValue::serialize(&EnumV2::Bar(4)).unwrap();
+    assert!(serialized.deserialize::<EnumV1>().is_err());
+}
+
+#[test]
+fn generic() {
+    #[derive(Introspectable)]
+    #[aldrin(schema = "test")]
+    #[allow(dead_code)]
+    struct Inner {
+        foo: u32,
+    }
+
+    #[derive(Introspectable)]
+    #[aldrin(schema = "test")]
+    #[allow(dead_code)]
+    struct Wrapper<T> {
+        value: T,
+
+        #[aldrin(optional)]
+        other: Option<T>,
+    }
+
+    #[derive(Introspectable)]
+    #[aldrin(schema = "test")]
+    #[allow(dead_code)]
+    enum Either<L, R> {
+        Left(L),
+        Right(R),
+    }
+
+    let introspection = Introspection::new::<Wrapper<Inner>>();
+    assert_eq!(
+        introspection.lexical_id(),
+        LexicalId::custom_generic("test", "Wrapper", &[LexicalId::custom("test", "Inner")])
+    );
+    assert_eq!(Wrapper::<Inner>::lexical_id(), introspection.lexical_id());
+
+    let layout = introspection.as_struct_layout().unwrap();
+    assert_eq!(layout.schema(), "test");
+    assert_eq!(layout.name(), "Wrapper");
+
+    let fields = layout.fields();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[&0].field_type(), Inner::lexical_id());
+    assert!(!fields[&1].is_required());
+    assert_eq!(fields[&1].field_type(), Inner::lexical_id());
+
+    let inner = Introspection::new::<Inner>();
+    assert_eq!(
+        introspection.resolve(Inner::lexical_id()),
+        Some(inner.type_id())
+    );
+
+    // Each instantiation is a distinct type.
+    let other = Introspection::new::<Wrapper<u32>>();
+    assert_ne!(other.lexical_id(), introspection.lexical_id());
+    assert_ne!(other.type_id(), introspection.type_id());
+
+    let introspection = Introspection::new::<Either<u32, Wrapper<Inner>>>();
+    assert_eq!(
+        introspection.lexical_id(),
+        LexicalId::custom_generic(
+            "test",
+            "Either",
+            &[LexicalId::U32, Wrapper::<Inner>::lexical_id()],
+        )
+    );
+
+    let variants = introspection.as_enum_layout().unwrap().variants();
+    assert_eq!(variants.len(), 2);
+    assert_eq!(variants[&0].variant_type(), Some(LexicalId::U32));
+    assert_eq!(
+        variants[&1].variant_type(),
+        Some(Wrapper::<Inner>::lexical_id())
+    );
+    assert!(introspection.resolve(Inner::lexical_id()).is_none());
+    assert!(introspection
+        .resolve(Wrapper::<Inner>::lexical_id())
+        .is_some());
+}
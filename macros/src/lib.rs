@@ -92,6 +92,44 @@
 //! }
 //! ```
 //!
+//! Generic types are introspectable as well. Their layouts are parameterized by the type arguments,
+//! such that each instantiation becomes a distinct type, e.g. `Wrapper<u32>` and `Wrapper<String>`
+//! below.
+//!
+//! ```
+//! # use aldrin_core::introspection::{Introspectable, LexicalId};
+//! # use aldrin_core::Introspectable;
+//! #[derive(Introspectable)]
+//! #[aldrin(schema = "contacts")]
+//! struct Wrapper<T> {
+//!     value: T,
+//! }
+//!
+//! assert_eq!(
+//!     Wrapper::<u32>::lexical_id(),
+//!     LexicalId::custom_generic("contacts", "Wrapper", &[LexicalId::U32]),
+//! );
+//! ```
+//!
+//! ##### `newtype`
+//!
+//! - Applies to: `Serialize`, `Deserialize` and `Introspectable`
+//!
+//! Structs with exactly one field can be marked with `#[aldrin(newtype)]`. They are then serialized
+//! and deserialized as just that field and `Introspectable` forwards to the field's type. Together
+//! with [`SerializeKey`], [`DeserializeKey`] and [`KeyTypeOf`], this allows using domain-specific
+//! types as keys of maps and sets.
+//!
+//! ```
+//! # use aldrin_core::{Deserialize, DeserializeKey, KeyTypeOf, Serialize, SerializeKey};
+//! # use uuid::Uuid;
+//! #[derive(
+//!     PartialEq, Eq, Hash, Serialize, Deserialize, SerializeKey, DeserializeKey, KeyTypeOf,
+//! )]
+//! #[aldrin(newtype)]
+//! struct PlayerId(Uuid);
+//! ```
+//!
 //! #### Field and variant attributes
 //!
 //! ##### `id`
@@ -131,6 +169,29 @@
 //! }
 //! ```
 //!
+//! ##### `alias_id`
+//!
+//! - Applies to: `Deserialize`
+//!
+//! Use `#[aldrin(alias_id = ...)]` to additionally deserialize a field or variant from a legacy id,
+//! e.g. after its id had to be changed. The attribute can be specified multiple times.
+//!
+//! Values are always serialized with the current id. If a struct contains both the current id and
+//! an alias, then the current id takes precedence. Alias ids must not be used by any other field or
+//! variant.
+//!
+//! ```
+//! # use aldrin_core::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct Person {
+//!     #[aldrin(id = 2, alias_id = 0)]
+//!     name: String, // id = 2, previously 0
+//!
+//!     #[aldrin(id = 1)]
+//!     age: u8, // id = 1
+//! }
+//! ```
+//!
 //! ##### `optional`
 //!
 //! - Applies to: `Serialize`, `Deserialize` and `Introspectable`
@@ -156,6 +217,49 @@
 //! Both fields `required_field_1` and `required_field_2` will always be serialized and
 //! deserialization will fail if either is missing. Serialization of `optional_field` is skipped if
 //! it is `None`. If it's missing during deserialization, then it will be set to `None`.
+//!
+//! ##### `fallback`
+//!
+//! - Applies to: `Serialize`, `Deserialize` and `Introspectable`
+//!
+//! Use `#[aldrin(fallback)]` to preserve data that is unknown to the receiver, e.g. when a value
+//! was serialized from a newer version of a schema. At most one field or variant can be marked as
+//! fallback. It doesn't consume an id and it is not part of the introspection layout.
+//!
+//! A fallback field must be of type [`UnknownFields`](aldrin_core::UnknownFields). It collects
+//! all fields with unknown ids during deserialization and they are written back, after all known
+//! fields, during serialization. Entries, whose id collides with the id or an alias id of a known
+//! field, are skipped.
+//!
+//! A fallback variant must have exactly one field of type
+//! [`UnknownVariant`](aldrin_core::UnknownVariant). It is selected for all unknown variant ids,
+//! instead of failing deserialization.
+//!
+//! Values are thus forwarded without losing data, which allows e.g. gateway services to proxy
+//! values of newer schema versions.
+//!
+//! ```
+//! # use aldrin_core::{Deserialize, Serialize, UnknownFields, UnknownVariant};
+//! #[derive(Serialize, Deserialize)]
+//! struct Contact {
+//!     name: String, // id = 0
+//!
+//!     #[aldrin(fallback)]
+//!     unknown_fields: UnknownFields,
+//!
+//!     #[aldrin(optional)]
+//!     email: Option<String>, // id = 1
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! enum Status {
+//!     Online, // id = 0
+//!     Offline, // id = 1
+//!
+//!     #[aldrin(fallback)]
+//!     Unknown(UnknownVariant),
+//! }
+//! ```
 
 #![deny(missing_docs)]
 
@@ -175,6 +279,9 @@ use syn::{DeriveInput, Result};
 /// This macro provides a front-end to the Aldrin code generator. It is an alternative to running
 /// the standalone `aldrin-gen` tool.
 ///
+/// If you prefer generating code from a build script, e.g. to inspect the generated code in
+/// `OUT_DIR`, use the `aldrin-build` crate instead. It supports the same options.
+///
 /// # Basic usage
 ///
 /// The [`generate!`] macro takes one required argument, the path to the schema file. Paths can be
@@ -266,6 +373,24 @@ use syn::{DeriveInput, Result};
 ///
 /// The `include` option can be repeated multiple times.
 ///
+/// # Lockfiles
+///
+/// Imported schemas can also be resolved with a lockfile, e.g. to fetch them from a git repository
+/// at a fixed revision (see `aldrin_parser::resolver::Lockfile` for the format):
+///
+/// ```
+/// # use aldrin_macros::generate;
+/// generate! {
+///     "schemas/example3.aldrin",
+///     "schemas/example4.aldrin",
+///     lockfile = "schemas/example.lock",
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The `lockfile` option can be repeated multiple times. Git repositories are cached in the system's
+/// temporary directory.
+///
 /// # Skipping server or client code
 ///
 /// You can skip generating server or client code for services by setting `server = false` or
@@ -382,6 +507,37 @@ use syn::{DeriveInput, Result};
 /// }
 /// ```
 ///
+/// # Generating mocks
+///
+/// Setting `mocks = true` generates an additional type `Mock{Service}` for every service, which
+/// can be used in tests in place of a real service. See the `service!` macro for more information.
+///
+/// ```
+/// # use aldrin_macros::generate;
+/// generate! {
+///     "schemas/example1.aldrin",
+///     mocks = true,
+/// }
+/// ```
+///
+/// # Deriving serde traits
+///
+/// Setting `serde = true` additionally derives `serde::Serialize` and `serde::Deserialize` for all
+/// generated structs, enums and newtypes. This requires enabling the `serde` Cargo feature of the
+/// `aldrin` crate. Optional struct fields are skipped when they are `None`, `bytes` are serialized
+/// as a sequence of `u8` and `uuid`s use the representation of the `uuid` crate.
+///
+/// Types containing channels (`sender` and `receiver`) cannot be serialized with serde and will
+/// fail to compile with this option.
+///
+/// ```
+/// # use aldrin_macros::generate;
+/// generate! {
+///     "schemas/example1.aldrin",
+///     serde = true,
+/// }
+/// ```
+///
 /// # Errors and warnings
 ///
 /// Any errors from the schemas will be shown as part of the regular compiler output and no code
@@ -459,6 +615,402 @@ pub fn generate(args: codegen::Args, emitter: &mut manyhow::Emitter) -> manyhow:
 /// }
 /// ```
 ///
+/// # Streaming functions
+///
+/// Functions can reply with a stream of values instead of a single one by specifying `stream`
+/// instead of `ok`. Under the hood, such functions reply with an `UnboundReceiver` of the item
+/// type.
+///
+/// On the client side, the proxy function resolves to a `Receiver`, which implements `Stream`. On
+/// the server side, the function's `Promise` can be turned into a `Sender` with `Promise::stream`.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// service! {
+///     pub service Counter {
+///         uuid = ServiceUuid(uuid!("3b8d5a4e-2d0c-4d6f-8c1e-7e6a9b2f4d10"));
+///         version = 1;
+///
+///         fn count @ 1 {
+///             args = u32;
+///             stream = u32;
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Retained events
+///
+/// Events can be marked as `retained`. The broker then stores the last value of such events and
+/// delivers it immediately to new subscribers. Retained events require protocol version 1.19.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// service! {
+///     pub service Thermometer {
+///         uuid = ServiceUuid(uuid!("5f0c7e4b-8a2d-4c1e-9b3f-2d6e8a1c4b7d"));
+///         version = 1;
+///
+///         retained event temperature @ 1 = f32;
+///     }
+/// }
+/// ```
+///
+/// # Acknowledged events
+///
+/// For every regular event, the service additionally gets an `async` emitter with an `_acked`
+/// suffix. It waits for the broker to reply with the number of clients the event was delivered
+/// to. Acknowledged events require protocol version 1.19. Retained events don't have such an
+/// emitter.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// service! {
+///     pub service Alarm {
+///         uuid = ServiceUuid(uuid!("c4e1a7d2-6b3f-4e8a-9d5c-0f2b7a1e6c38"));
+///         version = 1;
+///
+///         event triggered @ 1 = String;
+///     }
+/// }
+///
+/// async fn trigger(alarm: &Alarm) -> Result<(), aldrin::Error> {
+///     let delivered = alarm.triggered_acked("fire").await?;
+///     println!("Alarm delivered to {delivered} clients.");
+///     Ok(())
+/// }
+/// ```
+///
+/// # Reliable events
+///
+/// Events can be marked as `reliable`. The broker then buffers such events for every subscriber
+/// until they are acknowledged and replays them after a client reconnects with the same session.
+/// Proxies acknowledge events with `next_event_acked()` and `poll_next_event_acked()`. Reliable
+/// events require protocol version 1.19.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// service! {
+///     pub service Ledger {
+///         uuid = ServiceUuid(uuid!("9a2e4c7f-1d3b-4f6a-8e5c-3b7d1f9a2c64"));
+///         version = 1;
+///
+///         reliable event booked @ 1 = u64;
+///     }
+/// }
+///
+/// async fn run(proxy: &mut LedgerProxy) -> Result<(), aldrin::Error> {
+///     while let Some(event) = proxy.next_event_acked().await {
+///         let LedgerEvent::Booked(amount) = event?;
+///         println!("Booked {amount}.");
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// # Event subscriptions
+///
+/// Events without subscribers are dropped by the client. Servers, that produce events at some
+/// cost, can track subscriptions instead with `{Service}::event_subscriptions()`. It returns a
+/// `{Service}Subscriptions`, which has the following methods for every event:
+///
+/// - `has_subscribers_{event}()` checks whether the event currently has subscribers.
+/// - `subscribed_{event}()` waits until the event has subscribers.
+/// - `unsubscribed_{event}()` waits until the event no longer has subscribers.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// service! {
+///     pub service Sensor {
+///         uuid = ServiceUuid(uuid!("8d3b6f1a-2e4c-4a7d-b9e0-5c1f7a3d2e69"));
+///         version = 1;
+///
+///         event value @ 1 = u32;
+///     }
+/// }
+///
+/// async fn run(sensor: &Sensor) -> Result<(), aldrin::Error> {
+///     let mut subscriptions = sensor.event_subscriptions().await?;
+///
+///     loop {
+///         // Wait until someone is interested in the sensor's values.
+///         subscriptions.subscribed_value().await?;
+///
+///         while subscriptions.has_subscribers_value() {
+///             sensor.value(measure())?;
+///         }
+///     }
+/// }
+/// # fn measure() -> u32 { 0 }
+/// ```
+///
+/// # Serving services
+///
+/// For every service, a trait `{Service}Handler` with one method per function and a type
+/// `{Service}Server` are generated. `{Service}Server::serve()` creates an object with the
+/// service, and feeds all calls to the handler until the client shuts down. Should the object
+/// vanish from the broker, then it is created again. Handler methods receive the arguments and a
+/// [`Promise`](aldrin::Promise), which they can also move elsewhere to reply later.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// use aldrin::Promise;
+/// use aldrin::core::ObjectUuid;
+/// use std::convert::Infallible;
+///
+/// service! {
+///     pub service Greeter {
+///         uuid = ServiceUuid(uuid!("7c4e2a9d-1f3b-4d6a-8e5c-0b9f7d3a1e62"));
+///         version = 1;
+///
+///         fn greet @ 1 {
+///             args = String;
+///             ok = String;
+///         }
+///     }
+/// }
+///
+/// struct Handler;
+///
+/// impl GreeterHandler for Handler {
+///     fn greet(&mut self, name: String, promise: Promise<String, Infallible>) {
+///         let _ = promise.ok(&format!("Hello, {name}!"));
+///     }
+/// }
+///
+/// async fn run(client: &aldrin::Handle) -> Result<(), aldrin::Error> {
+///     GreeterServer::new(client, ObjectUuid::new_v4(), Handler)
+///         .serve()
+///         .await
+/// }
+/// ```
+///
+/// Alternatively, a `{Service}AsyncHandler` trait can be implemented and served with
+/// `{Service}Server::serve_async()`. Its methods take `&self` and return boxed futures of the
+/// functions' results, which are used to reply to the calls. Calls are handled concurrently and the
+/// futures are dropped when the caller aborts a call. Only streaming functions still receive a
+/// [`Promise`](aldrin::Promise) instead, so that they can reply before they finish.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// use aldrin::core::ObjectUuid;
+/// use std::future::Future;
+/// use std::pin::Pin;
+///
+/// service! {
+///     pub service Calculator {
+///         uuid = ServiceUuid(uuid!("4d2b8e1a-6c3f-4a9d-b7e0-1f5c9a3d2e84"));
+///         version = 1;
+///
+///         fn div @ 1 {
+///             args = (i32, i32);
+///             ok = i32;
+///             err = String;
+///         }
+///     }
+/// }
+///
+/// struct Handler;
+///
+/// impl CalculatorAsyncHandler for Handler {
+///     fn div(
+///         &self,
+///         (a, b): (i32, i32),
+///     ) -> Pin<Box<dyn Future<Output = Result<i32, String>> + Send + '_>> {
+///         Box::pin(async move { a.checked_div(b).ok_or_else(|| "division by zero".to_owned()) })
+///     }
+/// }
+///
+/// async fn run(client: &aldrin::Handle) -> Result<(), aldrin::Error> {
+///     CalculatorServer::new(client, ObjectUuid::new_v4(), Handler)
+///         .serve_async()
+///         .await
+/// }
+/// ```
+///
+/// # Event streams
+///
+/// Besides the merged `{Service}Event` stream, proxies have an `async` method with an `_events`
+/// suffix for every event. It returns an [`EventStream`](aldrin::EventStream) of only that event's
+/// values. Each stream is subscribed to its event independently, so that it can be moved into a
+/// separate task without having to dispatch events by hand.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// service! {
+///     pub service Doorbell {
+///         uuid = ServiceUuid(uuid!("2a7e4c1d-9b3f-4d8e-a6c0-7f1e3b5d9a24"));
+///         version = 1;
+///
+///         event rang @ 1 = String;
+///         event battery_low @ 2;
+///     }
+/// }
+///
+/// async fn watch(doorbell: &DoorbellProxy) -> Result<(), aldrin::Error> {
+///     let mut rang = doorbell.rang_events().await?;
+///
+///     while let Some(visitor) = rang.next_event().await {
+///         println!("{} is at the door.", visitor?);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// # Sharing proxies
+///
+/// Proxies implement `Clone`, which is cheap and doesn't involve the broker. All clones share the
+/// same event subscriptions, but each clone has its own stream of events. Clones can thus be moved
+/// into separate tasks, without the need to wrap a proxy in an `Arc<Mutex<_>>`. Cached property
+/// values are copied to the new clone.
+///
+/// # Call options
+///
+/// For every function, that doesn't return a stream, the proxy additionally gets a method with a
+/// `_with` suffix. It returns a [`CallBuilder`](aldrin::CallBuilder), which sets options like a
+/// timeout or a priority before making the call. Timeouts require a timer, see
+/// [`ClientBuilder::with_timer`](aldrin::ClientBuilder::with_timer).
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// use aldrin::low_level::CallPriority;
+/// use std::time::Duration;
+///
+/// service! {
+///     pub service Calculator {
+///         uuid = ServiceUuid(uuid!("5b1e9c3a-7d2f-4c6e-8a0b-3f9d1e7c2a54"));
+///         version = 1;
+///
+///         fn add @ 1 {
+///             args = (u32, u32);
+///             ok = u32;
+///         }
+///     }
+/// }
+///
+/// async fn add(calc: &CalculatorProxy) -> Result<(), aldrin::Error> {
+///     let sum = calc
+///         .add_with((1, 2))
+///         .timeout(Duration::from_secs(1))
+///         .priority(CallPriority::High)
+///         .call()
+///         .await?;
+///
+///     println!("1 + 2 = {sum:?}");
+///     Ok(())
+/// }
+/// ```
+///
+/// # Properties
+///
+/// A `property` is a value owned by the service, that proxies can keep synchronized. It occupies
+/// both a function id and an event id. The function is used to fetch the current value and the
+/// event publishes every change. Both carry a `PropertyUpdate` with a sequence number, such that
+/// proxies can detect missed updates and fetch the value again.
+///
+/// The service gets a getter, `set_*` and `clear_*` for each property and answers fetch calls
+/// automatically in `poll_next_call`. The proxy gets a getter, `subscribe_*`, `unsubscribe_*` and
+/// `watch_*`, and reports changes as an event variant carrying the new value.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// service! {
+///     pub service Speaker {
+///         uuid = ServiceUuid(uuid!("9d2e6b1a-3c4f-4a8e-b7d5-1f0c8e2a6b93"));
+///         version = 1;
+///
+///         property volume @ 1 = u32;
+///     }
+/// }
+/// ```
+///
+/// # Versioning
+///
+/// Functions and events can declare the service version in which they were introduced with
+/// `introduced_in`. The proxy gets a `supports_*` method for every function and event, that checks
+/// the version of the remote service. Items without `introduced_in` are always supported.
+///
+/// Proxies can also be created with `new_with_min_version`, which fails with
+/// [`Error::UnsupportedVersion`](aldrin::Error::UnsupportedVersion) if the service's version is
+/// lower than required.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// service! {
+///     pub service Storage {
+///         uuid = ServiceUuid(uuid!("7e3a1c5d-9b2f-4d8e-a6c0-4b1f9e2d7a35"));
+///         version = 2;
+///
+///         fn get @ 1 {
+///             args = String;
+///             ok = Option<String>;
+///         }
+///
+///         fn remove @ 2 introduced_in 2 {
+///             args = String;
+///         }
+///
+///         event removed @ 1 introduced_in 2 = String;
+///     }
+/// }
+///
+/// async fn remove(storage: &StorageProxy, key: &str) -> Result<(), aldrin::Error> {
+///     if storage.supports_remove() {
+///         storage.remove(key).await?;
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// # Doc comments
+///
+/// Doc comments on the service are added to the generated proxy and service types. Doc comments on
+/// functions, events and properties are added to the generated methods and enum variants.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::service;
+/// # use uuid::uuid;
+/// service! {
+///     /// Plays music.
+///     pub service Player {
+///         uuid = ServiceUuid(uuid!("2c7f0b3e-6d1a-4e8b-9f25-8a4d1c6e3b70"));
+///         version = 1;
+///
+///         /// Starts playback.
+///         fn play @ 1;
+///
+///         /// Emitted when playback stops.
+///         event stopped @ 2;
+///     }
+/// }
+/// ```
+///
 /// # Overriding the path to the `aldrin` crate
 ///
 /// Use the `#[aldrin(crate = "...")]` attribute to override the path to the `aldrin` crate.
@@ -556,6 +1108,48 @@ pub fn generate(args: codegen::Args, emitter: &mut manyhow::Emitter) -> manyhow:
 ///     }
 /// }
 /// ```
+///
+/// # Mocks
+///
+/// The attribute `#[aldrin(mocks)]` generates an additional type `Mock{Service}`, which wraps the
+/// service and answers function calls according to expectations set up beforehand. Each function
+/// `foo` gets a method `expect_foo`, that returns an `aldrin::mock::Expectation`. Calls, for which
+/// no expectation matches, cause a panic. This requires the server-side code to be generated.
+///
+/// ```
+/// # use aldrin::core::ServiceUuid;
+/// # use aldrin_macros::{service, AsSerializeArg, Deserialize, Serialize};
+/// # use uuid::uuid;
+/// service! {
+///     #[aldrin(mocks)]
+///     pub service Calculator {
+///         uuid = ServiceUuid(uuid!("b6633b9f-c26d-4987-8ec0-5c8e526290f9"));
+///         version = 1;
+///
+///         fn add @ 1 {
+///             args = (i32, i32);
+///             ok = i32;
+///         }
+///     }
+/// }
+///
+/// # async fn example(object: &aldrin::Object) -> Result<(), aldrin::Error> {
+/// let mut mock = MockCalculator::new(object).await?;
+///
+/// mock.expect_add()
+///     .withf(|&(a, b)| (a == 1) && (b == 2))
+///     .times(1)
+///     .returning(|(a, b)| Ok(a + b));
+///
+/// // Answer a single call.
+/// mock.handle_call().await.transpose()?;
+///
+/// // Panic, if the expectations haven't been met.
+/// mock.checkpoint();
+/// # Ok(())
+/// # }
+/// ```
+/// ```
 #[manyhow::manyhow]
 #[proc_macro]
 pub fn service(svc: service::Service) -> TokenStream {
@@ -570,6 +1164,7 @@ pub fn service(svc: service::Service) -> TokenStream {
 /// Relevant attributes:
 /// - [`crate`](crate#crate)
 /// - [`ser_bounds`](crate#serdeintroser_keyde_keykey_ty_bounds)
+/// - [`newtype`](crate#newtype)
 /// - [`id`](crate#id)
 /// - [`optional`](crate#optional)
 #[manyhow::manyhow]
@@ -586,6 +1181,7 @@ pub fn serialize_from_core(input: DeriveInput) -> Result<TokenStream> {
 /// Relevant attributes:
 /// - [`crate`](crate#crate)
 /// - [`ser_bounds`](crate#serdeintroser_keyde_keykey_ty_bounds)
+/// - [`newtype`](crate#newtype)
 /// - [`id`](crate#id)
 /// - [`optional`](crate#optional)
 #[doc(hidden)]
@@ -603,7 +1199,9 @@ pub fn serialize_from_aldrin(input: DeriveInput) -> Result<TokenStream> {
 /// Relevant attributes:
 /// - [`crate`](crate#crate)
 /// - [`de_bounds`](crate#serdeintroser_keyde_keykey_ty_bounds)
+/// - [`newtype`](crate#newtype)
 /// - [`id`](crate#id)
+/// - [`alias_id`](crate#alias_id)
 /// - [`optional`](crate#optional)
 #[manyhow::manyhow]
 #[proc_macro_derive(Deserialize, attributes(aldrin))]
@@ -619,7 +1217,9 @@ pub fn deserialize_from_core(input: DeriveInput) -> Result<TokenStream> {
 /// Relevant attributes:
 /// - [`crate`](crate#crate)
 /// - [`de_bounds`](crate#serdeintroser_keyde_keykey_ty_bounds)
+/// - [`newtype`](crate#newtype)
 /// - [`id`](crate#id)
+/// - [`alias_id`](crate#alias_id)
 /// - [`optional`](crate#optional)
 #[doc(hidden)]
 #[manyhow::manyhow]
@@ -637,6 +1237,7 @@ pub fn deserialize_from_aldrin(input: DeriveInput) -> Result<TokenStream> {
 /// - [`crate`](crate#crate)
 /// - [`schema`](crate#crate)
 /// - [`intro_bounds`](crate#serdeintroser_keyde_keykey_ty_bounds)
+/// - [`newtype`](crate#newtype)
 /// - [`id`](crate#id)
 /// - [`optional`](crate#optional)
 #[manyhow::manyhow]
@@ -654,6 +1255,7 @@ pub fn introspectable_from_core(input: DeriveInput) -> Result<TokenStream> {
 /// - [`crate`](crate#crate)
 /// - [`schema`](crate#crate)
 /// - [`intro_bounds`](crate#serdeintroser_keyde_keykey_ty_bounds)
+/// - [`newtype`](crate#newtype)
 /// - [`id`](crate#id)
 /// - [`optional`](crate#optional)
 #[doc(hidden)]
@@ -4,6 +4,7 @@ mod fn_body;
 mod fn_item;
 mod item;
 mod options;
+mod prop_item;
 #[cfg(test)]
 mod test;
 
@@ -14,10 +15,11 @@ use fn_item::FnItem;
 use item::ServiceItem;
 use options::Options;
 use proc_macro2::TokenStream;
+use prop_item::PropItem;
 use quote::quote;
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
-use syn::{braced, Ident, Result, Token, Visibility};
+use syn::{braced, Attribute, Error, Ident, LitInt, Result, Token, Visibility};
 
 mod kw {
     use syn::custom_keyword;
@@ -25,8 +27,13 @@ mod kw {
     custom_keyword!(args);
     custom_keyword!(err);
     custom_keyword!(event);
+    custom_keyword!(introduced_in);
     custom_keyword!(ok);
+    custom_keyword!(property);
+    custom_keyword!(reliable);
+    custom_keyword!(retained);
     custom_keyword!(service);
+    custom_keyword!(stream);
     custom_keyword!(uuid);
     custom_keyword!(version);
 }
@@ -39,6 +46,11 @@ pub struct Service {
     event: Ident,
     function: Ident,
     introspection: Ident,
+    mock: Ident,
+    subscriptions: Ident,
+    handler: Ident,
+    async_handler: Ident,
+    server: Ident,
     body: Body,
 }
 
@@ -56,6 +68,12 @@ impl Service {
             None
         };
 
+        let mock = if self.options.mocks() && self.options.server() {
+            Some(self.gen_mock())
+        } else {
+            None
+        };
+
         let introspection =
             if self.options.introspection() && (self.options.client() || self.options.server()) {
                 Some(self.gen_introspection())
@@ -66,6 +84,7 @@ impl Service {
         quote! {
             #client
             #server
+            #mock
             #introspection
         }
     }
@@ -83,19 +102,27 @@ impl Service {
     fn gen_server(&self) -> TokenStream {
         let service = self.gen_service();
         let function = self.gen_function();
+        let subscriptions = self.gen_subscriptions();
+        let handler = self.gen_handler();
 
         quote! {
             #service
             #function
+            #subscriptions
+            #handler
         }
     }
 
     fn gen_proxy(&self) -> TokenStream {
         let krate = self.options.krate();
+        let doc = self.options.doc();
         let vis = &self.vis;
         let proxy = &self.proxy;
         let event = &self.event;
         let body_impl = self.body.gen_proxy(&self.event, &self.options);
+        let fields = self.body.gen_proxy_fields(&self.options);
+        let field_clones = self.body.gen_proxy_field_clones();
+        let runtime = self.body.gen_runtime(&self.ident, proxy, &self.options);
 
         let introspection_if = self.options.introspection_if().map(|feature| {
             quote! { #[cfg(feature = #feature)] }
@@ -127,16 +154,26 @@ impl Service {
         };
 
         quote! {
+            #(#doc)*
             #[derive(::std::fmt::Debug)]
             #vis struct #proxy {
                 #[doc(hidden)]
                 inner: #krate::low_level::Proxy,
+                #fields
             }
 
             impl #proxy {
                 #body_impl
             }
 
+            #[automatically_derived]
+            impl ::std::clone::Clone for #proxy {
+                fn clone(&self) -> Self {
+                    let inner = ::std::clone::Clone::clone(&self.inner);
+                    Self { #field_clones inner }
+                }
+            }
+
             #[automatically_derived]
             impl #krate::private::futures_core::stream::Stream for #proxy {
                 type Item = ::std::result::Result<#event, #krate::Error>;
@@ -156,6 +193,7 @@ impl Service {
                 }
             }
 
+            #runtime
             #introspection
         }
     }
@@ -181,11 +219,16 @@ impl Service {
     }
 
     fn gen_service(&self) -> TokenStream {
+        let doc = self.options.doc();
         let vis = &self.vis;
         let ident = &self.ident;
         let function = &self.function;
         let krate = self.options.krate();
-        let body_impl = self.body.gen_service(&self.function, &self.options);
+        let body_impl = self
+            .body
+            .gen_service(&self.function, &self.subscriptions, &self.options);
+        let fields = self.body.gen_service_fields(&self.options);
+        let runtime = self.body.gen_runtime(ident, ident, &self.options);
 
         let introspection_if = self.options.introspection_if().map(|feature| {
             quote! { #[cfg(feature = #feature)] }
@@ -217,10 +260,12 @@ impl Service {
         };
 
         quote! {
+            #(#doc)*
             #[derive(::std::fmt::Debug)]
             #vis struct #ident {
                 #[doc(hidden)]
                 inner: #krate::low_level::Service,
+                #fields
             }
 
             impl #ident {
@@ -246,14 +291,57 @@ impl Service {
                 }
             }
 
+            #runtime
             #introspection
         }
     }
 
+    fn gen_subscriptions(&self) -> TokenStream {
+        let vis = &self.vis;
+        let subscriptions = &self.subscriptions;
+        let krate = self.options.krate();
+        let body_impl = self.body.gen_subscriptions(&self.options);
+
+        quote! {
+            #[derive(::std::fmt::Debug)]
+            #vis struct #subscriptions {
+                #[doc(hidden)]
+                inner: #krate::low_level::EventSubscriptions,
+            }
+
+            impl #subscriptions {
+                pub fn from_inner(inner: #krate::low_level::EventSubscriptions) -> Self {
+                    Self { inner }
+                }
+
+                pub fn inner(&self) -> &#krate::low_level::EventSubscriptions {
+                    &self.inner
+                }
+
+                pub fn inner_mut(&mut self) -> &mut #krate::low_level::EventSubscriptions {
+                    &mut self.inner
+                }
+
+                pub fn into_inner(self) -> #krate::low_level::EventSubscriptions {
+                    self.inner
+                }
+
+                pub async fn next_change(
+                    &mut self,
+                ) -> ::std::option::Option<#krate::low_level::SubscriptionChange> {
+                    self.inner.next_change().await
+                }
+
+                #body_impl
+            }
+        }
+    }
+
     fn gen_function(&self) -> TokenStream {
         let vis = &self.vis;
         let vars = self.body.gen_function(&self.options);
         let function = &self.function;
+        let body_impl = self.body.gen_function_impl(function);
 
         let non_exhaustive = if self.options.function_non_exhaustive() {
             Some(quote! { #[non_exhaustive] })
@@ -267,6 +355,166 @@ impl Service {
             #vis enum #function {
                 #vars
             }
+
+            impl #function {
+                #body_impl
+            }
+        }
+    }
+
+    fn gen_handler(&self) -> TokenStream {
+        let krate = self.options.krate();
+        let vis = &self.vis;
+        let ident = &self.ident;
+        let function = &self.function;
+        let handler = &self.handler;
+        let async_handler = &self.async_handler;
+        let server = &self.server;
+        let handler_fns = self.body.gen_handler_fns(&self.options);
+        let handle_call = self.body.gen_handler_call(function);
+        let async_handler_fns = self.body.gen_async_handler_fns(&self.options);
+        let handle_async_call = self.body.gen_async_handler_call(function, &self.options);
+
+        let create_service = quote! {
+            let object = match self.client.create_object(self.uuid).await {
+                ::std::result::Result::Ok(object) => object,
+
+                ::std::result::Result::Err(#krate::Error::Shutdown) => {
+                    break ::std::result::Result::Ok(());
+                }
+
+                ::std::result::Result::Err(e) => break ::std::result::Result::Err(e),
+            };
+
+            let mut service = match #ident::new(&object).await {
+                ::std::result::Result::Ok(service) => service,
+
+                ::std::result::Result::Err(#krate::Error::Shutdown) => {
+                    break ::std::result::Result::Ok(());
+                }
+
+                ::std::result::Result::Err(e) => break ::std::result::Result::Err(e),
+            };
+        };
+
+        quote! {
+            #vis trait #handler {
+                #handler_fns
+            }
+
+            #vis trait #async_handler {
+                #async_handler_fns
+            }
+
+            #[derive(::std::fmt::Debug)]
+            #vis struct #server<H> {
+                #[doc(hidden)]
+                client: #krate::Handle,
+
+                #[doc(hidden)]
+                uuid: #krate::core::ObjectUuid,
+
+                #[doc(hidden)]
+                handler: H,
+            }
+
+            impl<H> #server<H> {
+                pub fn new(
+                    client: &#krate::Handle,
+                    uuid: #krate::core::ObjectUuid,
+                    handler: H,
+                ) -> Self {
+                    Self {
+                        client: ::std::clone::Clone::clone(client),
+                        uuid,
+                        handler,
+                    }
+                }
+
+                pub fn handler(&self) -> &H {
+                    &self.handler
+                }
+
+                pub fn handler_mut(&mut self) -> &mut H {
+                    &mut self.handler
+                }
+
+                pub fn into_handler(self) -> H {
+                    self.handler
+                }
+            }
+
+            impl<H: #handler> #server<H> {
+                pub async fn serve(&mut self) -> ::std::result::Result<(), #krate::Error> {
+                    loop {
+                        #create_service
+
+                        // The service ends either when the client shuts down or when it vanishes
+                        // from the broker, in which case it is created again.
+                        while let ::std::option::Option::Some(call) = service.next_call().await {
+                            // Invalid calls have already been answered.
+                            if let ::std::result::Result::Ok(call) = call {
+                                #handle_call
+                            }
+                        }
+                    }
+                }
+            }
+
+            impl<H: #async_handler> #server<H> {
+                pub async fn serve_async(&mut self) -> ::std::result::Result<(), #krate::Error> {
+                    loop {
+                        #create_service
+
+                        let handler = &self.handler;
+
+                        // Calls are handled concurrently and dropped when they are aborted.
+                        #krate::private::serve_calls(
+                            |cx| service.poll_next_call(cx),
+                            |call| #handle_async_call,
+                        )
+                        .await;
+                    }
+                }
+            }
+        }
+    }
+
+    fn gen_mock(&self) -> TokenStream {
+        let krate = self.options.krate();
+        let vis = &self.vis;
+        let ident = &self.ident;
+        let mock = &self.mock;
+        let fields = self.body.gen_mock_fields(&self.options);
+        let body_impl = self.body.gen_mock(ident, &self.function, &self.options);
+
+        quote! {
+            #[derive(::std::fmt::Debug)]
+            #vis struct #mock {
+                #[doc(hidden)]
+                service: #ident,
+                #fields
+            }
+
+            impl #mock {
+                #body_impl
+            }
+
+            #[automatically_derived]
+            impl #krate::mock::Mock for #mock {
+                fn poll_handle_call(
+                    &mut self,
+                    cx: &mut ::std::task::Context,
+                ) -> ::std::task::Poll<
+                    ::std::option::Option<::std::result::Result<(), #krate::Error>>,
+                > {
+                    self.poll_handle_call(cx)
+                }
+
+                fn checkpoint(&mut self) {
+                    self.checkpoint();
+                }
+            }
         }
     }
 
@@ -311,6 +559,12 @@ impl Parse for Service {
         let function = Ident::new_raw(&format!("{}Function", ident.unraw()), ident.span());
         let introspection =
             Ident::new_raw(&format!("{}Introspection", ident.unraw()), ident.span());
+        let mock = Ident::new_raw(&format!("Mock{}", ident.unraw()), ident.span());
+        let subscriptions =
+            Ident::new_raw(&format!("{}Subscriptions", ident.unraw()), ident.span());
+        let handler = Ident::new_raw(&format!("{}Handler", ident.unraw()), ident.span());
+        let async_handler = Ident::new_raw(&format!("{}AsyncHandler", ident.unraw()), ident.span());
+        let server = Ident::new_raw(&format!("{}Server", ident.unraw()), ident.span());
 
         Ok(Self {
             options,
@@ -320,7 +574,56 @@ impl Parse for Service {
             event,
             function,
             introspection,
+            mock,
+            subscriptions,
+            handler,
+            async_handler,
+            server,
             body,
         })
     }
 }
+
+/// Parses the doc comments of a service item.
+///
+/// Doc comments are the only attributes allowed on items.
+fn parse_doc(input: ParseStream) -> Result<Vec<Attribute>> {
+    let attrs = input.call(Attribute::parse_outer)?;
+
+    for attr in &attrs {
+        if !attr.path().is_ident("doc") {
+            return Err(Error::new_spanned(
+                attr,
+                "only doc comments are allowed here",
+            ));
+        }
+    }
+
+    Ok(attrs)
+}
+
+/// Parses the optional `introduced_in` version of a function or event.
+fn parse_introduced_in(input: ParseStream) -> Result<Option<LitInt>> {
+    if input.parse::<Option<kw::introduced_in>>()?.is_some() {
+        input.parse().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Generates a proxy function, that checks if a function or event is supported by the service.
+fn gen_supports_fn(supports: &Ident, introduced_in: Option<&LitInt>) -> TokenStream {
+    match introduced_in {
+        Some(introduced_in) => quote! {
+            pub fn #supports(&self) -> ::std::primitive::bool {
+                self.inner.version() >= #introduced_in
+            }
+        },
+
+        None => quote! {
+            pub fn #supports(&self) -> ::std::primitive::bool {
+                true
+            }
+        },
+    }
+}
@@ -1,4 +1,4 @@
-use super::{kw, EvItem, Options, ServiceItem};
+use super::{kw, FnItem, Options, PropItem, ServiceItem};
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::collections::HashSet;
@@ -48,11 +48,38 @@ impl Body {
             .map(|func| func.gen_calls(options))
             .collect::<TokenStream>();
 
+        let field_inits = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_property)
+            .map(|prop| prop.gen_proxy_field_init(options))
+            .collect::<TokenStream>();
+
+        let prop_fns = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_property)
+            .map(|prop| prop.gen_proxy_fns(options))
+            .collect::<TokenStream>();
+
         let subscribe_all_body = self
             .items
             .iter()
-            .filter_map(ServiceItem::as_event)
-            .map(EvItem::gen_subscribe_call)
+            .filter_map(|item| match item {
+                ServiceItem::Event(ev) => Some(ev.gen_subscribe_call()),
+                ServiceItem::Property(prop) => Some(prop.gen_subscribe_call()),
+                ServiceItem::Function(_) => None,
+            })
+            .collect::<TokenStream>();
+
+        let supports_fns = self
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ServiceItem::Function(func) => Some(func.gen_supports_fn()),
+                ServiceItem::Event(ev) => Some(ev.gen_supports_fn()),
+                ServiceItem::Property(_) => None,
+            })
             .collect::<TokenStream>();
 
         let subscribe_fns = self
@@ -69,11 +96,31 @@ impl Body {
             .map(|ev| ev.gen_unsubscribe_fn(options))
             .collect::<TokenStream>();
 
+        let events_fns = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_event)
+            .map(|ev| ev.gen_events_fn(options))
+            .collect::<TokenStream>();
+
         let next_event_match_arms = self
             .items
             .iter()
             .filter_map(ServiceItem::as_event)
             .map(|ev| ev.gen_next_event_match_arm(event, options))
+            .chain(
+                self.items
+                    .iter()
+                    .filter_map(ServiceItem::as_property)
+                    .map(|prop| prop.gen_next_event_match_arm(event)),
+            )
+            .collect::<TokenStream>();
+
+        let poll_fetches = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_property)
+            .map(|prop| prop.gen_poll_fetch(event))
             .collect::<TokenStream>();
 
         quote! {
@@ -90,7 +137,25 @@ impl Body {
                 }
 
                 let inner = #krate::low_level::Proxy::new(client, id).await?;
-                Ok(Self { inner })
+                Ok(Self { inner, #field_inits })
+            }
+
+            pub async fn new_with_min_version(
+                client: &#krate::Handle,
+                id: #krate::core::ServiceId,
+                min_version: ::std::primitive::u32,
+            ) -> ::std::result::Result<Self, #krate::Error>
+            {
+                let proxy = Self::new(client, id).await?;
+
+                let version = proxy.version();
+                if version < min_version {
+                    return ::std::result::Result::Err(
+                        #krate::Error::unsupported_version(min_version, version),
+                    );
+                }
+
+                Ok(proxy)
             }
 
             pub fn inner(&self) -> &#krate::low_level::Proxy {
@@ -121,6 +186,17 @@ impl Body {
                 self.inner.type_id()
             }
 
+            #supports_fns
+
+            pub fn add_layer(&mut self, layer: impl #krate::low_level::ProxyLayer) {
+                self.inner.add_layer(layer);
+            }
+
+            pub fn with_layer(mut self, layer: impl #krate::low_level::ProxyLayer) -> Self {
+                self.inner.add_layer(layer);
+                self
+            }
+
             #introspection_fns
             #fn_calls
 
@@ -135,15 +211,56 @@ impl Body {
 
             #subscribe_fns
             #unsubscribe_fns
+            #events_fns
+            #prop_fns
 
             pub fn poll_next_event(
                 &mut self,
                 cx: &mut ::std::task::Context,
             ) -> ::std::task::Poll<
                 ::std::option::Option<::std::result::Result<#event, #krate::Error>>,
+            > {
+                self.poll_next_event_impl(cx, false)
+            }
+
+            pub async fn next_event(
+                &mut self,
+            ) -> ::std::option::Option<::std::result::Result<#event, #krate::Error>> {
+                ::std::future::poll_fn(|cx| self.poll_next_event(cx)).await
+            }
+
+            pub fn poll_next_event_acked(
+                &mut self,
+                cx: &mut ::std::task::Context,
+            ) -> ::std::task::Poll<
+                ::std::option::Option<::std::result::Result<#event, #krate::Error>>,
+            > {
+                self.poll_next_event_impl(cx, true)
+            }
+
+            pub async fn next_event_acked(
+                &mut self,
+            ) -> ::std::option::Option<::std::result::Result<#event, #krate::Error>> {
+                ::std::future::poll_fn(|cx| self.poll_next_event_acked(cx)).await
+            }
+
+            fn poll_next_event_impl(
+                &mut self,
+                cx: &mut ::std::task::Context,
+                ack: ::std::primitive::bool,
+            ) -> ::std::task::Poll<
+                ::std::option::Option<::std::result::Result<#event, #krate::Error>>,
             > {
                 loop {
-                    let ev = match self.inner.poll_next_event(cx) {
+                    #poll_fetches
+
+                    let ev = if ack {
+                        self.inner.poll_next_event_acked(cx)
+                    } else {
+                        self.inner.poll_next_event(cx)
+                    };
+
+                    let ev = match ev {
                         ::std::task::Poll::Ready(::std::option::Option::Some(ev)) => ev,
 
                         ::std::task::Poll::Ready(::std::option::Option::None) => {
@@ -159,24 +276,50 @@ impl Body {
                     }
                 }
             }
-
-            pub async fn next_event(
-                &mut self,
-            ) -> ::std::option::Option<::std::result::Result<#event, #krate::Error>> {
-                ::std::future::poll_fn(|cx| self.poll_next_event(cx)).await
-            }
         }
     }
 
+    pub fn gen_proxy_fields(&self, options: &Options) -> TokenStream {
+        self.items
+            .iter()
+            .filter_map(ServiceItem::as_property)
+            .map(|prop| prop.gen_proxy_field(options))
+            .collect()
+    }
+
+    pub fn gen_proxy_field_clones(&self) -> TokenStream {
+        self.items
+            .iter()
+            .filter_map(ServiceItem::as_property)
+            .map(PropItem::gen_proxy_field_clone)
+            .collect()
+    }
+
+    pub fn gen_service_fields(&self, options: &Options) -> TokenStream {
+        self.items
+            .iter()
+            .filter_map(ServiceItem::as_property)
+            .map(|prop| prop.gen_service_field(options))
+            .collect()
+    }
+
     pub fn gen_event(&self) -> TokenStream {
         self.items
             .iter()
-            .filter_map(ServiceItem::as_event)
-            .map(EvItem::gen_variant)
+            .filter_map(|item| match item {
+                ServiceItem::Event(ev) => Some(ev.gen_variant()),
+                ServiceItem::Property(prop) => Some(prop.gen_variant()),
+                ServiceItem::Function(_) => None,
+            })
             .collect::<TokenStream>()
     }
 
-    pub fn gen_service(&self, function: &Ident, options: &Options) -> TokenStream {
+    pub fn gen_service(
+        &self,
+        function: &Ident,
+        subscriptions: &Ident,
+        options: &Options,
+    ) -> TokenStream {
         let uuid = &self.uuid;
         let version = &self.version;
         let krate = options.krate();
@@ -188,7 +331,11 @@ impl Body {
         let info_type_id = options.introspection().then(|| {
             quote! {
                 #introspection_if
-                let info = info.set_type_id(#krate::core::TypeId::compute::<Self>());
+                let info = info
+                    .set_type_id(#krate::core::TypeId::compute::<Self>())
+                    .set_lexical_id(
+                        <Self as #krate::core::introspection::Introspectable>::lexical_id(),
+                    );
             }
         });
 
@@ -208,6 +355,13 @@ impl Body {
                 > {
                     self.inner.query_introspection().await
                 }
+
+                #introspection_if
+                pub fn enable_args_validation(&mut self) {
+                    self.inner.enable_args_validation(
+                        #krate::core::introspection::Validator::new::<Self>(),
+                    );
+                }
             }
         });
 
@@ -218,6 +372,20 @@ impl Body {
             .map(|ev| ev.gen_emitters(options))
             .collect::<TokenStream>();
 
+        let field_inits = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_property)
+            .map(|prop| prop.gen_service_field_init(options))
+            .collect::<TokenStream>();
+
+        let prop_fns = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_property)
+            .map(|prop| prop.gen_service_fns(options))
+            .collect::<TokenStream>();
+
         let next_call_match_arms = self
             .items
             .iter()
@@ -225,16 +393,82 @@ impl Body {
             .map(|func| func.gen_next_call_match_arm(function))
             .collect::<TokenStream>();
 
+        let prop_call_match_arms = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_property)
+            .map(PropItem::gen_next_call_match_arm)
+            .collect::<TokenStream>();
+
+        let recv_call = quote! {
+            let call = match self.inner.poll_next_call(cx) {
+                ::std::task::Poll::Ready(::std::option::Option::Some(call)) => call,
+
+                ::std::task::Poll::Ready(::std::option::Option::None) => {
+                    return ::std::task::Poll::Ready(::std::option::Option::None);
+                }
+
+                ::std::task::Poll::Pending => return ::std::task::Poll::Pending,
+            };
+        };
+
+        let dispatch_call = quote! {
+            match call.id() {
+                #prop_call_match_arms
+                #next_call_match_arms
+
+                id => {
+                    let _ = call.into_promise().invalid_function();
+
+                    ::std::task::Poll::Ready(
+                        ::std::option::Option::Some(
+                            ::std::result::Result::Err(
+                                #krate::Error::invalid_function(id),
+                            ),
+                        ),
+                    )
+                }
+            }
+        };
+
+        // Fetch calls of properties are answered here, so polling must continue afterwards.
+        let next_call = if prop_call_match_arms.is_empty() {
+            quote! {
+                #recv_call
+                #dispatch_call
+            }
+        } else {
+            quote! {
+                loop {
+                    #recv_call
+                    break #dispatch_call;
+                }
+            }
+        };
+
         quote! {
             pub const UUID: #krate::core::ServiceUuid = #uuid;
             pub const VERSION: ::std::primitive::u32 = #version;
 
             pub async fn new(object: &#krate::Object) -> ::std::result::Result<Self, #krate::Error> {
+                let inner = object.create_service(Self::UUID, Self::service_info()).await?;
+                Self::from_inner(inner)
+            }
+
+            pub fn service_info() -> #krate::low_level::ServiceInfo {
                 let info = #krate::low_level::ServiceInfo::new(Self::VERSION);
                 #info_type_id
+                info
+            }
+
+            pub fn from_inner(
+                inner: #krate::low_level::Service,
+            ) -> ::std::result::Result<Self, #krate::Error> {
+                if inner.id().uuid != Self::UUID {
+                    return ::std::result::Result::Err(#krate::Error::InvalidService);
+                }
 
-                let inner = object.create_service(Self::UUID, info).await?;
-                ::std::result::Result::Ok(Self { inner })
+                ::std::result::Result::Ok(Self { inner, #field_inits })
             }
 
             pub fn inner(&self) -> &#krate::low_level::Service {
@@ -271,7 +505,28 @@ impl Body {
                 self.inner.destroy().await
             }
 
+            pub async fn shutdown(
+                &mut self,
+                timeout: ::std::option::Option<::std::time::Duration>,
+            ) -> ::std::result::Result<(), #krate::Error> {
+                self.inner.shutdown(timeout).await
+            }
+
+            pub fn add_interceptor(&mut self, interceptor: impl #krate::low_level::Interceptor) {
+                self.inner.add_interceptor(interceptor);
+            }
+
+            pub async fn event_subscriptions(
+                &self,
+            ) -> ::std::result::Result<#subscriptions, #krate::Error> {
+                self.inner
+                    .event_subscriptions()
+                    .await
+                    .map(#subscriptions::from_inner)
+            }
+
             #ev_emitters
+            #prop_fns
 
             pub fn poll_next_call(
                 &mut self,
@@ -279,8 +534,204 @@ impl Body {
             ) -> ::std::task::Poll<
                 ::std::option::Option<::std::result::Result<#function, #krate::Error>>,
             > {
-                let call = match self.inner.poll_next_call(cx) {
-                    ::std::task::Poll::Ready(::std::option::Option::Some(call)) => call,
+                #next_call
+            }
+
+            pub async fn next_call(
+                &mut self,
+            ) -> ::std::option::Option<::std::result::Result<#function, #krate::Error>> {
+                ::std::future::poll_fn(|cx| self.poll_next_call(cx)).await
+            }
+        }
+    }
+
+    pub fn gen_subscriptions(&self, options: &Options) -> TokenStream {
+        self.items
+            .iter()
+            .filter_map(ServiceItem::as_event)
+            .map(|ev| ev.gen_subscriptions_fns(options))
+            .collect()
+    }
+
+    pub fn gen_function(&self, options: &Options) -> TokenStream {
+        self.items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(|func| func.gen_variant(options))
+            .collect::<TokenStream>()
+    }
+
+    pub fn gen_function_impl(&self, function: &Ident) -> TokenStream {
+        let patterns = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(|func| func.gen_promise_pattern(function))
+            .collect::<Vec<_>>();
+
+        quote! {
+            pub fn is_aborted(&mut self) -> ::std::primitive::bool {
+                match *self {
+                    #( #patterns => promise.is_aborted(), )*
+                }
+            }
+
+            pub fn poll_aborted(&mut self, cx: &mut ::std::task::Context) -> ::std::task::Poll<()> {
+                match *self {
+                    #( #patterns => promise.poll_aborted(cx), )*
+                }
+            }
+
+            pub async fn aborted(&mut self) {
+                ::std::future::poll_fn(|cx| self.poll_aborted(cx)).await
+            }
+        }
+    }
+
+    pub fn gen_handler_fns(&self, options: &Options) -> TokenStream {
+        self.items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(|func| func.gen_handler_fn(options))
+            .collect()
+    }
+
+    pub fn gen_handler_call(&self, function: &Ident) -> TokenStream {
+        let match_arms = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(|func| func.gen_handler_match_arm(function))
+            .collect::<TokenStream>();
+
+        quote! {
+            match call {
+                #match_arms
+            }
+        }
+    }
+
+    pub fn gen_async_handler_fns(&self, options: &Options) -> TokenStream {
+        self.items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(|func| func.gen_async_handler_fn(options))
+            .collect()
+    }
+
+    pub fn gen_async_handler_call(&self, function: &Ident, options: &Options) -> TokenStream {
+        let match_arms = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(|func| func.gen_async_handler_match_arm(function, options))
+            .collect::<TokenStream>();
+
+        quote! {
+            match call {
+                #match_arms
+            }
+        }
+    }
+
+    pub fn gen_mock_fields(&self, options: &Options) -> TokenStream {
+        self.items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(|func| func.gen_mock_field(options))
+            .collect()
+    }
+
+    pub fn gen_mock(&self, service: &Ident, function: &Ident, options: &Options) -> TokenStream {
+        let krate = options.krate();
+
+        let field_inits = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(|func| func.gen_mock_field_init(service, options))
+            .collect::<TokenStream>();
+
+        let expect_fns = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(|func| func.gen_mock_expect_fn(options))
+            .collect::<TokenStream>();
+
+        let match_arms = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(|func| func.gen_mock_match_arm(function))
+            .collect::<TokenStream>();
+
+        let fields = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_function)
+            .map(FnItem::mock_field)
+            .collect::<Vec<_>>();
+
+        // A service without functions has an empty function enum, which can't be matched on
+        // without making the remaining code unreachable.
+        let handle_call = if match_arms.is_empty() {
+            quote! { match call {} }
+        } else {
+            quote! {
+                let res = match call {
+                    #match_arms
+                };
+
+                ::std::task::Poll::Ready(::std::option::Option::Some(res))
+            }
+        };
+
+        quote! {
+            pub async fn new(object: &#krate::Object) -> ::std::result::Result<Self, #krate::Error> {
+                #service::new(object).await.map(Self::from_service)
+            }
+
+            pub fn from_service(service: #service) -> Self {
+                Self { service, #field_inits }
+            }
+
+            pub fn service(&self) -> &#service {
+                &self.service
+            }
+
+            pub fn service_mut(&mut self) -> &mut #service {
+                &mut self.service
+            }
+
+            pub fn into_service(self) -> #service {
+                self.service
+            }
+
+            pub fn id(&self) -> #krate::core::ServiceId {
+                self.service.id()
+            }
+
+            #expect_fns
+
+            pub fn poll_handle_call(
+                &mut self,
+                cx: &mut ::std::task::Context,
+            ) -> ::std::task::Poll<
+                ::std::option::Option<::std::result::Result<(), #krate::Error>>,
+            > {
+                let call = match self.service.poll_next_call(cx) {
+                    ::std::task::Poll::Ready(
+                        ::std::option::Option::Some(::std::result::Result::Ok(call)),
+                    ) => call,
+
+                    ::std::task::Poll::Ready(
+                        ::std::option::Option::Some(::std::result::Result::Err(e)),
+                    ) => {
+                        return ::std::task::Poll::Ready(
+                            ::std::option::Option::Some(::std::result::Result::Err(e)),
+                        );
+                    }
 
                     ::std::task::Poll::Ready(::std::option::Option::None) => {
                         return ::std::task::Poll::Ready(::std::option::Option::None);
@@ -289,37 +740,89 @@ impl Body {
                     ::std::task::Poll::Pending => return ::std::task::Poll::Pending,
                 };
 
-                match call.id() {
-                    #next_call_match_arms
-
-                    id => {
-                        let _ = call.into_promise().invalid_function();
-
-                        ::std::task::Poll::Ready(
-                            ::std::option::Option::Some(
-                                ::std::result::Result::Err(
-                                    #krate::Error::invalid_function(id),
-                                ),
-                            ),
-                        )
-                    }
-                }
+                #handle_call
             }
 
-            pub async fn next_call(
+            pub async fn handle_call(
                 &mut self,
-            ) -> ::std::option::Option<::std::result::Result<#function, #krate::Error>> {
-                ::std::future::poll_fn(|cx| self.poll_next_call(cx)).await
+            ) -> ::std::option::Option<::std::result::Result<(), #krate::Error>> {
+                ::std::future::poll_fn(|cx| self.poll_handle_call(cx)).await
+            }
+
+            pub fn checkpoint(&mut self) {
+                #( self.#fields.checkpoint(); )*
             }
         }
     }
 
-    pub fn gen_function(&self, options: &Options) -> TokenStream {
-        self.items
+    pub fn gen_runtime(&self, service: &Ident, ty: &Ident, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let service = service.unraw().to_string();
+
+        let functions = self
+            .items
             .iter()
             .filter_map(ServiceItem::as_function)
-            .map(|func| func.gen_variant(options))
-            .collect::<TokenStream>()
+            .map(|func| func.gen_runtime_entry(options))
+            .chain(
+                self.items
+                    .iter()
+                    .filter_map(ServiceItem::as_property)
+                    .map(|prop| prop.gen_runtime_entries(options).0),
+            )
+            .collect::<TokenStream>();
+
+        let events = self
+            .items
+            .iter()
+            .filter_map(ServiceItem::as_event)
+            .map(|ev| ev.gen_runtime_entry(options))
+            .chain(
+                self.items
+                    .iter()
+                    .filter_map(ServiceItem::as_property)
+                    .map(|prop| prop.gen_runtime_entries(options).1),
+            )
+            .collect::<TokenStream>();
+
+        quote! {
+            #[automatically_derived]
+            impl #krate::ServiceRuntime for #ty {
+                fn name(&self) -> &'static ::std::primitive::str {
+                    #service
+                }
+
+                fn uuid(&self) -> #krate::core::ServiceUuid {
+                    Self::UUID
+                }
+
+                fn version(&self) -> ::std::primitive::u32 {
+                    self.inner.version()
+                }
+
+                fn type_id(&self) -> ::std::option::Option<#krate::core::TypeId> {
+                    self.inner.type_id()
+                }
+
+                fn id(&self) -> #krate::core::ServiceId {
+                    self.inner.id()
+                }
+
+                fn client(&self) -> &#krate::Handle {
+                    self.inner.client()
+                }
+
+                fn functions(&self) -> &'static [#krate::ServiceFunction] {
+                    const FUNCTIONS: &[#krate::ServiceFunction] = &[#functions];
+                    FUNCTIONS
+                }
+
+                fn events(&self) -> &'static [#krate::ServiceEvent] {
+                    const EVENTS: &[#krate::ServiceEvent] = &[#events];
+                    EVENTS
+                }
+            }
+        }
     }
 
     pub fn gen_introspection(&self, service: &Ident, options: &Options) -> TokenStream {
@@ -5,16 +5,26 @@ use quote::quote;
 use std::collections::HashSet;
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
-use syn::{Ident, LitInt, Result, Token, Type};
+use syn::{Attribute, Ident, LitInt, Result, Token, Type};
 
 pub(super) struct EvItem {
+    doc: Vec<Attribute>,
     ident: Ident,
     ident_ref: Ident,
+    ident_acked: Ident,
     subscribe: Ident,
     unsubscribe: Ident,
+    events: Ident,
+    supports: Ident,
+    has_subscribers: Ident,
+    subscribed: Ident,
+    unsubscribed: Ident,
     variant: Ident,
     id: LitInt,
+    introduced_in: Option<LitInt>,
     ty: Option<Type>,
+    retained: bool,
+    reliable: bool,
 }
 
 impl EvItem {
@@ -47,6 +57,29 @@ impl EvItem {
         }
     }
 
+    pub fn gen_events_fn(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let events = &self.events;
+        let id = &self.id;
+
+        let ty = match self.ty {
+            Some(ref ty) => quote! { #ty },
+            None => quote! { () },
+        };
+
+        quote! {
+            pub async fn #events(
+                &self,
+            ) -> ::std::result::Result<#krate::EventStream<#ty>, #krate::Error> {
+                #krate::EventStream::new(&self.inner, #id).await
+            }
+        }
+    }
+
+    pub fn gen_supports_fn(&self) -> TokenStream {
+        super::gen_supports_fn(&self.supports, self.introduced_in.as_ref())
+    }
+
     pub fn gen_next_event_match_arm(&self, event: &Ident, options: &Options) -> TokenStream {
         let krate = options.krate();
         let id = &self.id;
@@ -95,21 +128,32 @@ impl EvItem {
     }
 
     pub fn gen_variant(&self) -> TokenStream {
+        let doc = &self.doc;
         let variant = &self.variant;
 
         if let Some(ref ty) = self.ty {
-            quote! { #variant(#ty), }
+            quote! { #(#doc)* #variant(#ty), }
         } else {
-            quote! { #variant, }
+            quote! { #(#doc)* #variant, }
         }
     }
 
     pub fn gen_emitters(&self, options: &Options) -> TokenStream {
         let krate = options.krate();
+        let doc = &self.doc;
         let ident = &self.ident;
         let ident_ref = &self.ident_ref;
+        let ident_acked = &self.ident_acked;
         let id = &self.id;
 
+        let emit = if self.retained {
+            quote! { emit_retained }
+        } else if self.reliable {
+            quote! { emit_reliable }
+        } else {
+            quote! { emit }
+        };
+
         let (args, args_ref, val) = match self.ty {
             Some(ref ty) => (
                 Some(quote! { , args: #krate::core::SerializeArg<'_, #ty> }),
@@ -120,14 +164,65 @@ impl EvItem {
             None => (None, None, quote! { &() }),
         };
 
+        // Acknowledged events are neither retained nor buffered by the broker. They are thus
+        // generated only for regular events.
+        let acked = if self.retained || self.reliable {
+            None
+        } else {
+            Some(quote! {
+                #(#doc)*
+                pub async fn #ident_acked(
+                    &self #args
+                ) -> ::std::result::Result<::std::primitive::usize, #krate::Error> {
+                    self.inner.emit_acked(#id, #val).await
+                }
+            })
+        };
+
         quote! {
+            #(#doc)*
             pub fn #ident(&self #args) -> ::std::result::Result<(), #krate::Error> {
-                self.inner.emit(#id, #val)
+                self.inner.#emit(#id, #val)
             }
 
+            #(#doc)*
             pub fn #ident_ref(&self #args_ref) -> ::std::result::Result<(), #krate::Error> {
-                self.inner.emit(#id, #val)
+                self.inner.#emit(#id, #val)
             }
+
+            #acked
+        }
+    }
+
+    pub fn gen_subscriptions_fns(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let has_subscribers = &self.has_subscribers;
+        let subscribed = &self.subscribed;
+        let unsubscribed = &self.unsubscribed;
+        let id = &self.id;
+
+        quote! {
+            pub fn #has_subscribers(&mut self) -> ::std::primitive::bool {
+                self.inner.has_subscribers(#id)
+            }
+
+            pub async fn #subscribed(&mut self) -> ::std::result::Result<(), #krate::Error> {
+                self.inner.subscribed(#id).await
+            }
+
+            pub async fn #unsubscribed(&mut self) {
+                self.inner.unsubscribed(#id).await
+            }
+        }
+    }
+
+    pub fn gen_runtime_entry(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let id = &self.id;
+        let name = self.ident.unraw().to_string();
+
+        quote! {
+            #krate::ServiceEvent::new(#id, #name),
         }
     }
 
@@ -160,10 +255,14 @@ impl EvItem {
 
 impl Parse for EvItem {
     fn parse(input: ParseStream) -> Result<Self> {
+        let doc = super::parse_doc(input)?;
+        let retained = input.parse::<Option<kw::retained>>()?.is_some();
+        let reliable = !retained && input.parse::<Option<kw::reliable>>()?.is_some();
         input.parse::<kw::event>()?;
         let ident = input.parse::<Ident>()?;
         input.parse::<Token![@]>()?;
         let id = input.parse()?;
+        let introduced_in = super::parse_introduced_in(input)?;
 
         let ty = if input.parse::<Token![=]>().is_ok() {
             input.parse().map(Some)?
@@ -174,8 +273,15 @@ impl Parse for EvItem {
         input.parse::<Token![;]>()?;
 
         let ident_ref = Ident::new_raw(&format!("{}_ref", ident.unraw()), ident.span());
+        let ident_acked = Ident::new_raw(&format!("{}_acked", ident.unraw()), ident.span());
         let subscribe = Ident::new_raw(&format!("subscribe_{}", ident.unraw()), ident.span());
         let unsubscribe = Ident::new_raw(&format!("unsubscribe_{}", ident.unraw()), ident.span());
+        let events = Ident::new_raw(&format!("{}_events", ident.unraw()), ident.span());
+        let supports = Ident::new_raw(&format!("supports_{}", ident.unraw()), ident.span());
+        let has_subscribers =
+            Ident::new_raw(&format!("has_subscribers_{}", ident.unraw()), ident.span());
+        let subscribed = Ident::new_raw(&format!("subscribed_{}", ident.unraw()), ident.span());
+        let unsubscribed = Ident::new_raw(&format!("unsubscribed_{}", ident.unraw()), ident.span());
 
         let variant = Ident::new_raw(
             &ident.unraw().to_string().to_upper_camel_case(),
@@ -183,13 +289,23 @@ impl Parse for EvItem {
         );
 
         Ok(Self {
+            doc,
             ident,
             ident_ref,
+            ident_acked,
             subscribe,
             unsubscribe,
+            events,
+            supports,
+            has_subscribers,
+            subscribed,
+            unsubscribed,
             variant,
             id,
+            introduced_in,
             ty,
+            retained,
+            reliable,
         })
     }
 }
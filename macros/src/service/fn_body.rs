@@ -5,6 +5,7 @@ use syn::{Result, Token, Type};
 pub(super) struct FnBody {
     args: Option<Type>,
     ok: Option<Type>,
+    stream: Option<Type>,
     err: Option<Type>,
 }
 
@@ -13,6 +14,7 @@ impl FnBody {
         Self {
             args: None,
             ok: None,
+            stream: None,
             err: None,
         }
     }
@@ -25,6 +27,10 @@ impl FnBody {
         self.ok.as_ref()
     }
 
+    pub fn stream(&self) -> Option<&Type> {
+        self.stream.as_ref()
+    }
+
     pub fn err(&self) -> Option<&Type> {
         self.err.as_ref()
     }
@@ -50,6 +56,15 @@ impl Parse for FnBody {
             None
         };
 
+        let stream = if ok.is_none() && input.parse::<kw::stream>().is_ok() {
+            input.parse::<Token![=]>()?;
+            let stream = input.parse()?;
+            input.parse::<Token![;]>()?;
+            Some(stream)
+        } else {
+            None
+        };
+
         let err = if input.parse::<kw::err>().is_ok() {
             input.parse::<Token![=]>()?;
             let err = input.parse()?;
@@ -59,6 +74,11 @@ impl Parse for FnBody {
             None
         };
 
-        Ok(Self { args, ok, err })
+        Ok(Self {
+            args,
+            ok,
+            stream,
+            err,
+        })
     }
 }
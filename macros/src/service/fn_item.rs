@@ -6,21 +6,30 @@ use std::collections::HashSet;
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::token::Brace;
-use syn::{braced, Ident, LitInt, Result, Token, Type};
+use syn::{braced, Attribute, Ident, LitInt, Result, Token, Type};
+
+/// Capacity of the receivers of streaming functions.
+const STREAM_CAPACITY: u32 = 16;
 
 pub(super) struct FnItem {
+    doc: Vec<Attribute>,
     ident: Ident,
     ident_ref: Ident,
+    ident_with: Ident,
+    supports: Ident,
     variant: Ident,
     id: LitInt,
+    introduced_in: Option<LitInt>,
     body: FnBody,
 }
 
 impl FnItem {
     pub fn gen_calls(&self, options: &Options) -> TokenStream {
         let krate = options.krate();
+        let doc = &self.doc;
         let ident = &self.ident;
         let ident_ref = &self.ident_ref;
+        let ident_with = &self.ident_with;
         let id = &self.id;
 
         let (args, args_ref, val) = match self.body.args() {
@@ -33,48 +42,99 @@ impl FnItem {
             None => (None, None, quote! { &() }),
         };
 
-        let ok = match self.body.ok() {
-            Some(ok) => quote! { #ok },
-            None => quote! { () },
-        };
+        let err = self.err();
+
+        if let Some(stream) = self.body.stream() {
+            return quote! {
+                #(#doc)*
+                pub fn #ident(
+                    &self
+                    #args
+                ) -> impl ::std::future::Future<
+                    Output = ::std::result::Result<
+                        ::std::result::Result<#krate::Receiver<#stream>, #err>,
+                        #krate::Error,
+                    >,
+                > {
+                    let client = self.inner.client().clone();
+                    let reply = self
+                        .inner
+                        .call(#id, #val)
+                        .cast::<#krate::UnboundReceiver<#stream>, #err>();
+
+                    async move { reply.claim_stream(&client, #STREAM_CAPACITY).await }
+                }
 
-        let err = match self.body.err() {
-            Some(err) => quote! { #err },
-            None => quote! { ::std::convert::Infallible },
-        };
+                #(#doc)*
+                pub fn #ident_ref(
+                    &self
+                    #args_ref
+                ) -> impl ::std::future::Future<
+                    Output = ::std::result::Result<
+                        ::std::result::Result<#krate::Receiver<#stream>, #err>,
+                        #krate::Error,
+                    >,
+                > {
+                    let client = self.inner.client().clone();
+                    let reply = self
+                        .inner
+                        .call(#id, #val)
+                        .cast::<#krate::UnboundReceiver<#stream>, #err>();
+
+                    async move { reply.claim_stream(&client, #STREAM_CAPACITY).await }
+                }
+            };
+        }
+
+        let ok = self.ok(options);
 
         quote! {
+            #(#doc)*
             pub fn #ident(&self #args) -> #krate::Reply<#ok, #err> {
                 self.inner.call(#id, #val).cast()
             }
 
+            #(#doc)*
             pub fn #ident_ref(&self #args_ref) -> #krate::Reply<#ok, #err> {
                 self.inner.call(#id, #val).cast()
             }
+
+            #(#doc)*
+            pub fn #ident_with(&self #args) -> #krate::CallBuilder<'_, #ok, #err> {
+                #krate::CallBuilder::new(&self.inner, #id, #val)
+            }
         }
     }
 
+    pub fn gen_supports_fn(&self) -> TokenStream {
+        super::gen_supports_fn(&self.supports, self.introduced_in.as_ref())
+    }
+
     pub fn gen_variant(&self, options: &Options) -> TokenStream {
         let krate = options.krate();
+        let doc = &self.doc;
         let variant = &self.variant;
 
         let args = self.body.args().map(|args| quote! { #args, });
-
-        let ok = match self.body.ok() {
-            Some(ok) => quote! { #ok },
-            None => quote! { () },
-        };
-
-        let err = match self.body.err() {
-            Some(err) => quote! { #err },
-            None => quote! { ::std::convert::Infallible },
-        };
+        let ok = self.ok(options);
+        let err = self.err();
 
         quote! {
+            #(#doc)*
             #variant(#args #krate::Promise<#ok, #err>),
         }
     }
 
+    pub fn gen_promise_pattern(&self, function: &Ident) -> TokenStream {
+        let variant = &self.variant;
+
+        if self.body.args().is_some() {
+            quote! { #function::#variant(_, ref mut promise) }
+        } else {
+            quote! { #function::#variant(ref mut promise) }
+        }
+    }
+
     pub fn gen_next_call_match_arm(&self, function: &Ident) -> TokenStream {
         let id = &self.id;
         let variant = &self.variant;
@@ -104,6 +164,151 @@ impl FnItem {
         }
     }
 
+    pub fn gen_mock_field(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let field = self.mock_field();
+        let args = self.mock_args();
+        let ok = self.ok(options);
+        let err = self.err();
+
+        quote! {
+            #[doc(hidden)]
+            #field: #krate::mock::Expectations<#args, #ok, #err>,
+        }
+    }
+
+    pub fn gen_mock_field_init(&self, service: &Ident, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let field = self.mock_field();
+        let name = format!("{}::{}", service.unraw(), self.ident.unraw());
+
+        quote! {
+            #field: #krate::mock::Expectations::new(#name),
+        }
+    }
+
+    pub fn gen_mock_expect_fn(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let field = self.mock_field();
+        let args = self.mock_args();
+        let ok = self.ok(options);
+        let err = self.err();
+
+        let calls = Ident::new(&format!("{}_calls", self.ident.unraw()), self.ident.span());
+
+        quote! {
+            pub fn #field(&mut self) -> &mut #krate::mock::Expectation<#args, #ok, #err> {
+                self.#field.expect()
+            }
+
+            pub fn #calls(&self) -> ::std::primitive::usize {
+                self.#field.calls()
+            }
+        }
+    }
+
+    pub fn gen_mock_match_arm(&self, function: &Ident) -> TokenStream {
+        let variant = &self.variant;
+        let field = self.mock_field();
+
+        if self.body.args().is_some() {
+            quote! { #function::#variant(args, promise) => self.#field.handle(args, promise), }
+        } else {
+            quote! { #function::#variant(promise) => self.#field.handle((), promise), }
+        }
+    }
+
+    pub fn gen_handler_fn(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let doc = &self.doc;
+        let ident = &self.ident;
+        let args = self.body.args().map(|args| quote! { args: #args, });
+        let ok = self.ok(options);
+        let err = self.err();
+
+        quote! {
+            #(#doc)*
+            fn #ident(&mut self, #args promise: #krate::Promise<#ok, #err>);
+        }
+    }
+
+    pub fn gen_handler_match_arm(&self, function: &Ident) -> TokenStream {
+        let ident = &self.ident;
+        let variant = &self.variant;
+
+        if self.body.args().is_some() {
+            quote! { #function::#variant(args, promise) => self.handler.#ident(args, promise), }
+        } else {
+            quote! { #function::#variant(promise) => self.handler.#ident(promise), }
+        }
+    }
+
+    pub fn gen_async_handler_fn(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let doc = &self.doc;
+        let ident = &self.ident;
+        let args = self.body.args().map(|args| quote! { args: #args, });
+        let ok = self.ok(options);
+        let err = self.err();
+
+        // Streaming functions must be able to reply before they finish, so they get the promise.
+        if self.body.stream().is_some() {
+            quote! {
+                #(#doc)*
+                fn #ident(
+                    &self,
+                    #args
+                    promise: #krate::Promise<#ok, #err>,
+                ) -> ::std::pin::Pin<::std::boxed::Box<
+                    dyn ::std::future::Future<Output = ()> + ::std::marker::Send + '_,
+                >>;
+            }
+        } else {
+            quote! {
+                #(#doc)*
+                fn #ident(
+                    &self,
+                    #args
+                ) -> ::std::pin::Pin<::std::boxed::Box<
+                    dyn ::std::future::Future<Output = ::std::result::Result<#ok, #err>>
+                        + ::std::marker::Send
+                        + '_,
+                >>;
+            }
+        }
+    }
+
+    pub fn gen_async_handler_match_arm(&self, function: &Ident, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let ident = &self.ident;
+        let variant = &self.variant;
+
+        let (pattern, args) = if self.body.args().is_some() {
+            (
+                quote! { #function::#variant(args, promise) },
+                quote! { args, },
+            )
+        } else {
+            (quote! { #function::#variant(promise) }, quote! {})
+        };
+
+        if self.body.stream().is_some() {
+            quote! { #pattern => handler.#ident(#args promise), }
+        } else {
+            quote! { #pattern => #krate::private::reply(promise, handler.#ident(#args)), }
+        }
+    }
+
+    pub fn gen_runtime_entry(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let id = &self.id;
+        let name = self.ident.unraw().to_string();
+
+        quote! {
+            #krate::ServiceFunction::new(#id, #name),
+        }
+    }
+
     pub fn layout(&self, options: &Options) -> TokenStream {
         let id = &self.id;
         let name = self.ident.unraw().to_string();
@@ -119,14 +324,16 @@ impl FnItem {
             None => quote! { ::std::option::Option::None },
         };
 
-        let ok = match self.body.ok() {
-            Some(ok) => quote! {
+        let ok = if self.body.ok().is_some() || self.body.stream().is_some() {
+            let ok = self.ok(options);
+
+            quote! {
                 ::std::option::Option::Some(
                     <#ok as #krate::core::introspection::Introspectable>::lexical_id(),
                 )
-            },
-
-            None => quote! { ::std::option::Option::None },
+            }
+        } else {
+            quote! { ::std::option::Option::None }
         };
 
         let err = match self.body.err() {
@@ -153,18 +360,52 @@ impl FnItem {
             references.insert(ok);
         }
 
+        if let Some(stream) = self.body.stream() {
+            references.insert(stream);
+        }
+
         if let Some(err) = self.body.err() {
             references.insert(err);
         }
     }
+
+    pub fn mock_field(&self) -> Ident {
+        Ident::new(&format!("expect_{}", self.ident.unraw()), self.ident.span())
+    }
+
+    fn mock_args(&self) -> TokenStream {
+        match self.body.args() {
+            Some(args) => quote! { #args },
+            None => quote! { () },
+        }
+    }
+
+    fn ok(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+
+        match (self.body.ok(), self.body.stream()) {
+            (Some(ok), _) => quote! { #ok },
+            (None, Some(stream)) => quote! { #krate::UnboundReceiver<#stream> },
+            (None, None) => quote! { () },
+        }
+    }
+
+    fn err(&self) -> TokenStream {
+        match self.body.err() {
+            Some(err) => quote! { #err },
+            None => quote! { ::std::convert::Infallible },
+        }
+    }
 }
 
 impl Parse for FnItem {
     fn parse(input: ParseStream) -> Result<Self> {
+        let doc = super::parse_doc(input)?;
         input.parse::<Token![fn]>()?;
         let ident = input.parse::<Ident>()?;
         input.parse::<Token![@]>()?;
         let id = input.parse()?;
+        let introduced_in = super::parse_introduced_in(input)?;
 
         let body = if input.peek(Brace) {
             let content;
@@ -176,6 +417,8 @@ impl Parse for FnItem {
         };
 
         let ident_ref = Ident::new_raw(&format!("{}_ref", &ident.unraw()), ident.span());
+        let ident_with = Ident::new_raw(&format!("{}_with", &ident.unraw()), ident.span());
+        let supports = Ident::new_raw(&format!("supports_{}", &ident.unraw()), ident.span());
 
         let variant = Ident::new_raw(
             &ident.unraw().to_string().to_upper_camel_case(),
@@ -183,10 +426,14 @@ impl Parse for FnItem {
         );
 
         Ok(Self {
+            doc,
             ident,
             ident_ref,
+            ident_with,
+            supports,
             variant,
             id,
+            introduced_in,
             body,
         })
     }
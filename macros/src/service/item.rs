@@ -1,4 +1,4 @@
-use super::{kw, EvItem, FnItem, Options};
+use super::{kw, EvItem, FnItem, Options, PropItem};
 use proc_macro2::TokenStream;
 use std::collections::HashSet;
 use syn::parse::{Parse, ParseStream};
@@ -7,27 +7,36 @@ use syn::{Attribute, Result, Token, Type};
 pub(super) enum ServiceItem {
     Event(EvItem),
     Function(FnItem),
+    Property(PropItem),
 }
 
 impl ServiceItem {
     pub fn as_event(&self) -> Option<&EvItem> {
         match self {
             Self::Event(ev) => Some(ev),
-            Self::Function(_) => None,
+            Self::Function(_) | Self::Property(_) => None,
         }
     }
 
     pub fn as_function(&self) -> Option<&FnItem> {
         match self {
-            Self::Event(_) => None,
+            Self::Event(_) | Self::Property(_) => None,
             Self::Function(func) => Some(func),
         }
     }
 
+    pub fn as_property(&self) -> Option<&PropItem> {
+        match self {
+            Self::Event(_) | Self::Function(_) => None,
+            Self::Property(prop) => Some(prop),
+        }
+    }
+
     pub fn layout(&self, options: &Options) -> TokenStream {
         match self {
             Self::Event(ev) => ev.layout(options),
             Self::Function(func) => func.layout(options),
+            Self::Property(_) => TokenStream::new(),
         }
     }
 
@@ -35,6 +44,7 @@ impl ServiceItem {
         match self {
             Self::Event(ev) => ev.add_references(references),
             Self::Function(func) => func.add_references(references),
+            Self::Property(_) => {}
         }
     }
 }
@@ -45,10 +55,13 @@ impl Parse for ServiceItem {
         begin.call(Attribute::parse_outer)?;
 
         let lookahead = begin.lookahead1();
-        if lookahead.peek(kw::event) {
+        if lookahead.peek(kw::event) || lookahead.peek(kw::retained) || lookahead.peek(kw::reliable)
+        {
             input.parse().map(Self::Event)
         } else if lookahead.peek(Token![fn]) {
             input.parse().map(Self::Function)
+        } else if lookahead.peek(kw::property) {
+            input.parse().map(Self::Property)
         } else {
             Err(lookahead.error())
         }
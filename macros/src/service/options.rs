@@ -9,7 +9,9 @@ pub(super) struct Options {
     function_non_exhaustive: bool,
     introspection: bool,
     introspection_if: Option<LitStr>,
+    mocks: bool,
     schema: Option<LitStr>,
+    doc: Vec<Attribute>,
 }
 
 impl Options {
@@ -41,9 +43,17 @@ impl Options {
         self.introspection_if.as_ref()
     }
 
+    pub fn mocks(&self) -> bool {
+        self.mocks
+    }
+
     pub fn schema(&self) -> Option<&LitStr> {
         self.schema.as_ref()
     }
+
+    pub fn doc(&self) -> &[Attribute] {
+        &self.doc
+    }
 }
 
 impl Parse for Options {
@@ -57,9 +67,16 @@ impl Parse for Options {
         let mut function_non_exhaustive = true;
         let mut introspection = false;
         let mut introspection_if = None;
+        let mut mocks = false;
         let mut schema = None;
+        let mut doc = Vec::new();
 
         for attr in attrs {
+            if attr.path().is_ident("doc") {
+                doc.push(attr);
+                continue;
+            }
+
             if !attr.path().is_ident("aldrin") {
                 return Err(Error::new_spanned(attr, "extected attribute `aldrin`"));
             }
@@ -88,6 +105,9 @@ impl Parse for Options {
                     introspection_if = meta.value()?.parse().map(Some)?;
                     introspection = true;
                     Ok(())
+                } else if meta.path.is_ident("mocks") {
+                    mocks = true;
+                    Ok(())
                 } else if meta.path.is_ident("schema") {
                     schema = meta.value()?.parse().map(Some)?;
                     Ok(())
@@ -106,7 +126,9 @@ impl Parse for Options {
                 function_non_exhaustive,
                 introspection,
                 introspection_if,
+                mocks,
                 schema,
+                doc,
             })
         } else {
             Err(input.error("the attribute `schema` is required to derive Introspectable"))
@@ -0,0 +1,256 @@
+use super::{kw, Options};
+use heck::ToUpperCamelCase;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Ident, LitInt, Result, Token, Type};
+
+pub(super) struct PropItem {
+    doc: Vec<Attribute>,
+    ident: Ident,
+    field: Ident,
+    set: Ident,
+    clear: Ident,
+    subscribe: Ident,
+    unsubscribe: Ident,
+    watch: Ident,
+    variant: Ident,
+    id: LitInt,
+    ty: Type,
+}
+
+impl PropItem {
+    pub fn gen_proxy_field(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let field = &self.field;
+        let ty = &self.ty;
+
+        quote! {
+            #[doc(hidden)]
+            #field: #krate::PropertySubscriber<#ty>,
+        }
+    }
+
+    pub fn gen_proxy_field_init(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let field = &self.field;
+        let id = &self.id;
+
+        quote! {
+            #field: #krate::PropertySubscriber::new(#id),
+        }
+    }
+
+    pub fn gen_proxy_field_clone(&self) -> TokenStream {
+        let field = &self.field;
+
+        quote! {
+            #field: self.#field.clone_with(&inner),
+        }
+    }
+
+    pub fn gen_proxy_fns(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let doc = &self.doc;
+        let ident = &self.ident;
+        let field = &self.field;
+        let subscribe = &self.subscribe;
+        let unsubscribe = &self.unsubscribe;
+        let watch = &self.watch;
+        let ty = &self.ty;
+
+        quote! {
+            #(#doc)*
+            pub fn #ident(&self) -> ::std::option::Option<&#ty> {
+                self.#field.get()
+            }
+
+            pub async fn #subscribe(&mut self) -> ::std::result::Result<(), #krate::Error> {
+                self.#field.subscribe(&self.inner).await
+            }
+
+            pub async fn #unsubscribe(&mut self) -> ::std::result::Result<(), #krate::Error> {
+                self.#field.unsubscribe(&self.inner).await
+            }
+
+            pub fn #watch(&mut self) -> #krate::PropertyWatch<#ty> {
+                self.#field.watch()
+            }
+        }
+    }
+
+    pub fn gen_subscribe_call(&self) -> TokenStream {
+        let id = &self.id;
+        quote! { self.inner.subscribe(#id).await?; }
+    }
+
+    pub fn gen_poll_fetch(&self, event: &Ident) -> TokenStream {
+        let field = &self.field;
+        let variant = &self.variant;
+
+        quote! {
+            match self.#field.poll_fetch(cx) {
+                ::std::task::Poll::Ready(::std::result::Result::Ok(true)) => {
+                    break ::std::task::Poll::Ready(
+                        ::std::option::Option::Some(
+                            ::std::result::Result::Ok(
+                                #event::#variant(self.#field.get().cloned()),
+                            ),
+                        ),
+                    );
+                }
+
+                ::std::task::Poll::Ready(::std::result::Result::Err(e)) => {
+                    break ::std::task::Poll::Ready(
+                        ::std::option::Option::Some(::std::result::Result::Err(e)),
+                    );
+                }
+
+                ::std::task::Poll::Ready(::std::result::Result::Ok(false))
+                | ::std::task::Poll::Pending => {}
+            }
+        }
+    }
+
+    pub fn gen_next_event_match_arm(&self, event: &Ident) -> TokenStream {
+        let id = &self.id;
+        let field = &self.field;
+        let variant = &self.variant;
+
+        quote! {
+            #id => match self.#field.handle_event(&self.inner, &ev) {
+                ::std::result::Result::Ok(true) => {
+                    break ::std::task::Poll::Ready(
+                        ::std::option::Option::Some(
+                            ::std::result::Result::Ok(
+                                #event::#variant(self.#field.get().cloned()),
+                            ),
+                        ),
+                    );
+                }
+
+                ::std::result::Result::Ok(false) => {}
+
+                ::std::result::Result::Err(e) => {
+                    break ::std::task::Poll::Ready(
+                        ::std::option::Option::Some(::std::result::Result::Err(e)),
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn gen_variant(&self) -> TokenStream {
+        let doc = &self.doc;
+        let variant = &self.variant;
+        let ty = &self.ty;
+
+        quote! { #(#doc)* #variant(::std::option::Option<#ty>), }
+    }
+
+    pub fn gen_service_field(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let field = &self.field;
+        let ty = &self.ty;
+
+        quote! {
+            #[doc(hidden)]
+            #field: #krate::PropertyPublisher<#ty>,
+        }
+    }
+
+    pub fn gen_service_field_init(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let field = &self.field;
+        let id = &self.id;
+
+        quote! {
+            #field: #krate::PropertyPublisher::new(#id),
+        }
+    }
+
+    pub fn gen_service_fns(&self, options: &Options) -> TokenStream {
+        let krate = options.krate();
+        let doc = &self.doc;
+        let ident = &self.ident;
+        let field = &self.field;
+        let set = &self.set;
+        let clear = &self.clear;
+        let ty = &self.ty;
+
+        quote! {
+            #(#doc)*
+            pub fn #ident(&self) -> ::std::option::Option<&#ty> {
+                self.#field.get()
+            }
+
+            pub fn #set(&mut self, value: #ty) -> ::std::result::Result<(), #krate::Error> {
+                self.#field.set(&self.inner, value)
+            }
+
+            pub fn #clear(&mut self) -> ::std::result::Result<(), #krate::Error> {
+                self.#field.clear(&self.inner)
+            }
+        }
+    }
+
+    pub fn gen_next_call_match_arm(&self) -> TokenStream {
+        let id = &self.id;
+        let field = &self.field;
+
+        quote! {
+            #id => {
+                let _ = self.#field.reply(call.into_promise());
+                continue;
+            }
+        }
+    }
+
+    pub fn gen_runtime_entries(&self, options: &Options) -> (TokenStream, TokenStream) {
+        let krate = options.krate();
+        let id = &self.id;
+        let name = self.ident.unraw().to_string();
+
+        (
+            quote! { #krate::ServiceFunction::new(#id, #name), },
+            quote! { #krate::ServiceEvent::new(#id, #name), },
+        )
+    }
+}
+
+impl Parse for PropItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let doc = super::parse_doc(input)?;
+        input.parse::<kw::property>()?;
+        let ident = input.parse::<Ident>()?;
+        input.parse::<Token![@]>()?;
+        let id = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![;]>()?;
+
+        let name = ident.unraw();
+        let field = Ident::new(&format!("property_{name}"), ident.span());
+        let set = Ident::new_raw(&format!("set_{name}"), ident.span());
+        let clear = Ident::new_raw(&format!("clear_{name}"), ident.span());
+        let subscribe = Ident::new_raw(&format!("subscribe_{name}"), ident.span());
+        let unsubscribe = Ident::new_raw(&format!("unsubscribe_{name}"), ident.span());
+        let watch = Ident::new_raw(&format!("watch_{name}"), ident.span());
+        let variant = Ident::new_raw(&name.to_string().to_upper_camel_case(), ident.span());
+
+        Ok(Self {
+            doc,
+            ident,
+            field,
+            set,
+            clear,
+            subscribe,
+            unsubscribe,
+            watch,
+            variant,
+            id,
+            ty,
+        })
+    }
+}
@@ -24,6 +24,33 @@ mod raw_identifiers {
     }
 }
 
+#[allow(dead_code)]
+mod doc_comments {
+    use aldrin::core::ServiceUuid;
+    use aldrin::service;
+    use uuid::uuid;
+
+    service! {
+        /// A documented service.
+        #[aldrin(schema = "test", introspection, mocks)]
+        pub service Documented {
+            uuid = ServiceUuid(uuid!("0a8c4c5f-1d2b-4f0e-9a57-52b4e3c7d6a1"));
+            version = 1;
+
+            /// A documented function.
+            fn func @ 1 {
+                args = u32;
+            }
+
+            /// A documented event.
+            event ev @ 2 = String;
+
+            /// A documented property.
+            property prop @ 3 = u32;
+        }
+    }
+}
+
 #[test]
 fn raw_identifiers() {
     let introspection = raw_identifiers::r#extern::introspection();
@@ -1,15 +1,19 @@
 mod array_len;
 mod attribute;
 mod const_def;
+mod const_expr;
 mod definition;
+mod doc_string;
 mod enum_def;
 mod ident;
 mod import_stmt;
 mod key_type_name;
+mod lit_float;
 mod lit_int;
 mod lit_string;
 mod lit_uuid;
 mod named_ref;
+mod newtype_def;
 mod schema_name;
 mod service_def;
 mod struct_def;
@@ -17,19 +21,22 @@ mod type_name;
 mod type_name_or_inline;
 
 pub use array_len::{ArrayLen, ArrayLenValue};
-pub use attribute::Attribute;
+pub use attribute::{Attribute, AttributeOption, AttributeOptionKind};
 pub use const_def::{ConstDef, ConstValue};
+pub use const_expr::{ConstExpr, ConstExprField, ConstExprKind, ConstExprMapEntry, TypedConst};
 pub use definition::Definition;
-pub use enum_def::{EnumDef, EnumVariant, InlineEnum};
+pub use enum_def::{EnumDef, EnumFallback, EnumVariant, InlineEnum};
 pub use ident::Ident;
 pub use import_stmt::ImportStmt;
 pub use key_type_name::{KeyTypeName, KeyTypeNameKind};
+pub use lit_float::LitFloat;
 pub use lit_int::{LitInt, LitPosInt};
 pub use lit_string::LitString;
 pub use lit_uuid::LitUuid;
 pub use named_ref::{NamedRef, NamedRefKind};
+pub use newtype_def::NewtypeDef;
 pub use schema_name::SchemaName;
-pub use service_def::{EventDef, FunctionDef, FunctionPart, ServiceDef, ServiceItem};
-pub use struct_def::{InlineStruct, StructDef, StructField};
+pub use service_def::{EventDef, FunctionDef, FunctionPart, PropertyDef, ServiceDef, ServiceItem};
+pub use struct_def::{InlineStruct, StructDef, StructFallback, StructField};
 pub use type_name::{TypeName, TypeNameKind};
 pub use type_name_or_inline::TypeNameOrInline;
@@ -1,7 +1,8 @@
 use super::{LitPosInt, NamedRef};
 use crate::error::{
     ConstIntNotFound, ExpectedConstIntFoundService, ExpectedConstIntFoundString,
-    ExpectedConstIntFoundType, ExpectedConstIntFoundUuid, InvalidArrayLen,
+    ExpectedConstIntFoundType, ExpectedConstIntFoundTypedConst, ExpectedConstIntFoundUuid,
+    InvalidArrayLen,
 };
 use crate::grammar::Rule;
 use crate::validate::Validate;
@@ -66,6 +67,7 @@ impl ArrayLenValue {
                 ExpectedConstIntFoundService::validate(ty, validate);
                 ExpectedConstIntFoundString::validate(ty, validate);
                 ExpectedConstIntFoundType::validate(ty, validate);
+                ExpectedConstIntFoundTypedConst::validate(ty, validate);
                 ExpectedConstIntFoundUuid::validate(ty, validate);
 
                 ty.validate(validate);
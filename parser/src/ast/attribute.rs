@@ -1,4 +1,4 @@
-use super::Ident;
+use super::{Ident, LitString};
 use crate::grammar::Rule;
 use crate::Span;
 use pest::iterators::Pair;
@@ -7,7 +7,7 @@ use pest::iterators::Pair;
 pub struct Attribute {
     span: Span,
     name: Ident,
-    options: Vec<Ident>,
+    options: Vec<AttributeOption>,
 }
 
 impl Attribute {
@@ -31,7 +31,7 @@ impl Attribute {
 
         for pair in pairs {
             match pair.as_rule() {
-                Rule::ident => att.options.push(Ident::parse(pair)),
+                Rule::attribute_option => att.options.push(AttributeOption::parse(pair)),
                 Rule::tok_par_open
                 | Rule::tok_par_close
                 | Rule::tok_comma
@@ -51,7 +51,75 @@ impl Attribute {
         &self.name
     }
 
-    pub fn options(&self) -> &[Ident] {
+    pub fn options(&self) -> &[AttributeOption] {
         &self.options
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct AttributeOption {
+    span: Span,
+    kind: AttributeOptionKind,
+}
+
+impl AttributeOption {
+    fn parse(pair: Pair<Rule>) -> Self {
+        assert_eq!(pair.as_rule(), Rule::attribute_option);
+
+        let span = Span::from_pair(&pair);
+
+        let mut pairs = pair.into_inner();
+        let pair = pairs.next().unwrap();
+        let kind = AttributeOptionKind::parse(pair);
+
+        Self { span, kind }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn kind(&self) -> &AttributeOptionKind {
+        &self.kind
+    }
+
+    pub fn ident(&self) -> Option<&Ident> {
+        match self.kind {
+            AttributeOptionKind::Ident(ref ident) | AttributeOptionKind::List(ref ident, _) => {
+                Some(ident)
+            }
+
+            AttributeOptionKind::String(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum AttributeOptionKind {
+    Ident(Ident),
+    List(Ident, Vec<Ident>),
+    String(LitString),
+}
+
+impl AttributeOptionKind {
+    fn parse(pair: Pair<Rule>) -> Self {
+        match pair.as_rule() {
+            Rule::ident => Self::Ident(Ident::parse(pair)),
+            Rule::lit_string => Self::String(LitString::parse(pair)),
+
+            Rule::attribute_list => {
+                let mut pairs = pair.into_inner();
+                let ident = Ident::parse(pairs.next().unwrap());
+
+                let args = pairs
+                    .filter(|pair| pair.as_rule() == Rule::ident)
+                    .map(Ident::parse)
+                    .collect();
+
+                Self::List(ident, args)
+            }
+
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use super::{Ident, LitInt, LitString, LitUuid};
+use super::{
+    doc_string, ConstExprKind, Ident, LitInt, LitString, LitUuid, TypeNameKind, TypedConst,
+};
 use crate::error::InvalidConstValue;
 use crate::grammar::Rule;
 use crate::validate::Validate;
@@ -9,6 +11,7 @@ use pest::iterators::Pair;
 #[derive(Debug, Clone)]
 pub struct ConstDef {
     span: Span,
+    doc: Option<String>,
     name: Ident,
     value_span: Span,
     value: ConstValue,
@@ -19,20 +22,27 @@ impl ConstDef {
         assert_eq!(pair.as_rule(), Rule::const_def);
 
         let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
 
         let mut pairs = pair.into_inner();
         pairs.next().unwrap(); // Skip keyword.
 
         let name = Ident::parse(pairs.next().unwrap());
 
-        pairs.next().unwrap(); // Skip =.
-
-        let value_pair = pairs.next().unwrap();
-        let value_span = Span::from_pair(&value_pair);
-        let value = ConstValue::parse(value_pair);
+        let pair = pairs.next().unwrap();
+        let (value_span, value) = if pair.as_rule() == Rule::const_typed {
+            let value = TypedConst::parse(pair);
+            (value.expr().span(), ConstValue::Typed(Box::new(value)))
+        } else {
+            // Skip =.
+            let value_pair = pairs.next().unwrap();
+            let value_span = Span::from_pair(&value_pair);
+            (value_span, ConstValue::parse(value_pair))
+        };
 
         Self {
             span,
+            doc,
             name,
             value_span,
             value,
@@ -50,6 +60,10 @@ impl ConstDef {
         self.span
     }
 
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn name(&self) -> &Ident {
         &self.name
     }
@@ -75,6 +89,7 @@ pub enum ConstValue {
     I64(LitInt),
     String(LitString),
     Uuid(LitUuid),
+    Typed(Box<TypedConst>),
 }
 
 impl ConstValue {
@@ -104,7 +119,46 @@ impl ConstValue {
         }
     }
 
+    /// Returns the literal of an integer constant.
+    ///
+    /// This covers both the `u32(64)` form and typed constants of a built-in integer type, such as
+    /// `u32 = 64`.
+    pub fn as_int(&self) -> Option<&LitInt> {
+        match self {
+            Self::U8(lit)
+            | Self::I8(lit)
+            | Self::U16(lit)
+            | Self::I16(lit)
+            | Self::U32(lit)
+            | Self::I32(lit)
+            | Self::U64(lit)
+            | Self::I64(lit) => Some(lit),
+
+            Self::Typed(value) => match (value.type_name().kind(), value.expr().kind()) {
+                (
+                    TypeNameKind::U8
+                    | TypeNameKind::I8
+                    | TypeNameKind::U16
+                    | TypeNameKind::I16
+                    | TypeNameKind::U32
+                    | TypeNameKind::I32
+                    | TypeNameKind::U64
+                    | TypeNameKind::I64,
+                    ConstExprKind::Int(lit),
+                ) => Some(lit),
+
+                _ => None,
+            },
+
+            Self::String(_) | Self::Uuid(_) => None,
+        }
+    }
+
     fn validate(&self, validate: &mut Validate) {
         InvalidConstValue::validate(self, validate);
+
+        if let Self::Typed(value) = self {
+            value.validate(validate);
+        }
     }
 }
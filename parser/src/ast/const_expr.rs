@@ -0,0 +1,219 @@
+use super::{Ident, LitFloat, LitInt, LitString, LitUuid, TypeName};
+use crate::error::InvalidConstExpr;
+use crate::grammar::Rule;
+use crate::validate::Validate;
+use crate::Span;
+use pest::iterators::Pair;
+
+#[derive(Debug, Clone)]
+pub struct TypedConst {
+    span: Span,
+    type_name: TypeName,
+    expr: ConstExpr,
+}
+
+impl TypedConst {
+    pub(crate) fn parse(pair: Pair<Rule>) -> Self {
+        assert_eq!(pair.as_rule(), Rule::const_typed);
+
+        let span = Span::from_pair(&pair);
+
+        let mut pairs = pair.into_inner();
+        pairs.next().unwrap(); // Skip :.
+
+        let type_name = TypeName::parse(pairs.next().unwrap());
+
+        pairs.next().unwrap(); // Skip =.
+
+        let expr = ConstExpr::parse(pairs.next().unwrap());
+
+        Self {
+            span,
+            type_name,
+            expr,
+        }
+    }
+
+    pub(crate) fn validate(&self, validate: &mut Validate) {
+        InvalidConstExpr::validate(&self.type_name, &self.expr, validate);
+
+        self.type_name.validate(validate);
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn type_name(&self) -> &TypeName {
+        &self.type_name
+    }
+
+    pub fn expr(&self) -> &ConstExpr {
+        &self.expr
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstExpr {
+    span: Span,
+    kind: ConstExprKind,
+}
+
+impl ConstExpr {
+    pub(crate) fn parse(pair: Pair<Rule>) -> Self {
+        assert_eq!(pair.as_rule(), Rule::const_expr);
+
+        let span = Span::from_pair(&pair);
+
+        let mut pairs = pair.into_inner();
+        let pair = pairs.next().unwrap();
+        let kind = ConstExprKind::parse(pair);
+
+        Self { span, kind }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn kind(&self) -> &ConstExprKind {
+        &self.kind
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConstExprKind {
+    Int(LitInt),
+    Float(LitFloat),
+    String(LitString),
+    Uuid(LitUuid),
+    Ident(Ident),
+    Variant(Ident, Box<ConstExpr>),
+    List(Vec<ConstExpr>),
+    Map(Vec<ConstExprMapEntry>),
+    Struct(Vec<ConstExprField>),
+}
+
+impl ConstExprKind {
+    fn parse(pair: Pair<Rule>) -> Self {
+        match pair.as_rule() {
+            Rule::lit_int => Self::Int(LitInt::parse(pair)),
+            Rule::lit_float => Self::Float(LitFloat::parse(pair)),
+            Rule::lit_string => Self::String(LitString::parse(pair)),
+            Rule::lit_uuid => Self::Uuid(LitUuid::parse(pair)),
+            Rule::ident => Self::Ident(Ident::parse(pair)),
+
+            Rule::const_expr_variant => {
+                let mut pairs = pair.into_inner();
+                let name = Ident::parse(pairs.next().unwrap());
+                pairs.next().unwrap(); // Skip (.
+                let value = ConstExpr::parse(pairs.next().unwrap());
+
+                Self::Variant(name, Box::new(value))
+            }
+
+            Rule::const_expr_list => {
+                let mut elems = Vec::new();
+                for pair in pair.into_inner() {
+                    if pair.as_rule() == Rule::const_expr {
+                        elems.push(ConstExpr::parse(pair));
+                    }
+                }
+
+                Self::List(elems)
+            }
+
+            Rule::const_expr_map => {
+                let mut entries = Vec::new();
+                for pair in pair.into_inner() {
+                    if pair.as_rule() == Rule::const_expr_map_entry {
+                        entries.push(ConstExprMapEntry::parse(pair));
+                    }
+                }
+
+                Self::Map(entries)
+            }
+
+            Rule::const_expr_struct => {
+                let mut fields = Vec::new();
+                for pair in pair.into_inner() {
+                    if pair.as_rule() == Rule::const_expr_field {
+                        fields.push(ConstExprField::parse(pair));
+                    }
+                }
+
+                Self::Struct(fields)
+            }
+
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstExprMapEntry {
+    span: Span,
+    key: ConstExpr,
+    value: ConstExpr,
+}
+
+impl ConstExprMapEntry {
+    fn parse(pair: Pair<Rule>) -> Self {
+        assert_eq!(pair.as_rule(), Rule::const_expr_map_entry);
+
+        let span = Span::from_pair(&pair);
+
+        let mut pairs = pair.into_inner();
+        let key = ConstExpr::parse(pairs.next().unwrap());
+        pairs.next().unwrap(); // Skip ->.
+        let value = ConstExpr::parse(pairs.next().unwrap());
+
+        Self { span, key, value }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn key(&self) -> &ConstExpr {
+        &self.key
+    }
+
+    pub fn value(&self) -> &ConstExpr {
+        &self.value
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstExprField {
+    span: Span,
+    name: Ident,
+    value: ConstExpr,
+}
+
+impl ConstExprField {
+    fn parse(pair: Pair<Rule>) -> Self {
+        assert_eq!(pair.as_rule(), Rule::const_expr_field);
+
+        let span = Span::from_pair(&pair);
+
+        let mut pairs = pair.into_inner();
+        let name = Ident::parse(pairs.next().unwrap());
+        pairs.next().unwrap(); // Skip =.
+        let value = ConstExpr::parse(pairs.next().unwrap());
+
+        Self { span, name, value }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    pub fn value(&self) -> &ConstExpr {
+        &self.value
+    }
+}
@@ -0,0 +1,36 @@
+use crate::grammar::Rule;
+use pest::iterators::Pair;
+
+/// Extracts the doc string of the item at `pair`.
+///
+/// Doc strings are consecutive `///` comments directly preceding an item, which must start on its
+/// own line. Comments starting with `////` are ordinary comments.
+pub(super) fn parse(pair: &Pair<Rule>) -> Option<String> {
+    let before = &pair.get_input()[..pair.as_span().start()];
+
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    if !before[line_start..].trim().is_empty() {
+        return None;
+    }
+
+    let mut lines = before[..line_start]
+        .lines()
+        .rev()
+        .map_while(|line| {
+            let line = line.trim().strip_prefix("///")?;
+
+            if line.starts_with('/') {
+                None
+            } else {
+                Some(line.strip_prefix(' ').unwrap_or(line))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    lines.reverse();
+    Some(lines.join("\n"))
+}
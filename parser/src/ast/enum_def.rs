@@ -1,4 +1,4 @@
-use super::{Attribute, Ident, LitPosInt, TypeName};
+use super::{doc_string, Attribute, Ident, LitPosInt, TypeName};
 use crate::error::{
     DuplicateEnumVariant, DuplicateEnumVariantId, EmptyEnum, InvalidEnumVariantId, RecursiveEnum,
 };
@@ -11,9 +11,11 @@ use pest::iterators::Pair;
 #[derive(Debug, Clone)]
 pub struct EnumDef {
     span: Span,
+    doc: Option<String>,
     attrs: Vec<Attribute>,
     name: Ident,
     vars: Vec<EnumVariant>,
+    fallback: Option<EnumFallback>,
 }
 
 impl EnumDef {
@@ -21,6 +23,7 @@ impl EnumDef {
         assert_eq!(pair.as_rule(), Rule::enum_def);
 
         let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
 
         let mut pairs = pair.into_inner();
 
@@ -39,9 +42,11 @@ impl EnumDef {
         pairs.next().unwrap(); // Skip {.
 
         let mut vars = Vec::new();
+        let mut fallback = None;
         for pair in pairs {
             match pair.as_rule() {
                 Rule::enum_variant => vars.push(EnumVariant::parse(pair)),
+                Rule::enum_fallback => fallback = Some(EnumFallback::parse(pair)),
                 Rule::tok_cur_close => break,
                 _ => unreachable!(),
             }
@@ -49,14 +54,22 @@ impl EnumDef {
 
         Self {
             span,
+            doc,
             attrs,
             name,
             vars,
+            fallback,
         }
     }
 
     pub(crate) fn validate(&self, validate: &mut Validate) {
-        DuplicateEnumVariant::validate(&self.vars, self.name.span(), Some(&self.name), validate);
+        DuplicateEnumVariant::validate(
+            &self.vars,
+            self.fallback.as_ref(),
+            self.name.span(),
+            Some(&self.name),
+            validate,
+        );
         DuplicateEnumVariantId::validate(&self.vars, self.name.span(), Some(&self.name), validate);
         EmptyEnum::validate(&self.vars, self.span, Some(&self.name), validate);
         NonCamelCaseEnum::validate(self, validate);
@@ -66,12 +79,20 @@ impl EnumDef {
         for var in &self.vars {
             var.validate(validate);
         }
+
+        if let Some(ref fallback) = self.fallback {
+            fallback.validate(validate);
+        }
     }
 
     pub fn span(&self) -> Span {
         self.span
     }
 
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn attributes(&self) -> &[Attribute] {
         &self.attrs
     }
@@ -83,6 +104,10 @@ impl EnumDef {
     pub fn variants(&self) -> &[EnumVariant] {
         &self.vars
     }
+
+    pub fn fallback(&self) -> Option<&EnumFallback> {
+        self.fallback.as_ref()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -90,6 +115,7 @@ pub struct InlineEnum {
     span: Span,
     kw_span: Span,
     vars: Vec<EnumVariant>,
+    fallback: Option<EnumFallback>,
 }
 
 impl InlineEnum {
@@ -106,9 +132,11 @@ impl InlineEnum {
         pairs.next().unwrap(); // Skip {.
 
         let mut vars = Vec::new();
+        let mut fallback = None;
         for pair in pairs {
             match pair.as_rule() {
                 Rule::enum_variant => vars.push(EnumVariant::parse(pair)),
+                Rule::enum_fallback => fallback = Some(EnumFallback::parse(pair)),
                 Rule::tok_cur_close => break,
                 _ => unreachable!(),
             }
@@ -118,17 +146,28 @@ impl InlineEnum {
             span,
             kw_span,
             vars,
+            fallback,
         }
     }
 
     pub(crate) fn validate(&self, validate: &mut Validate) {
-        DuplicateEnumVariant::validate(&self.vars, self.kw_span, None, validate);
+        DuplicateEnumVariant::validate(
+            &self.vars,
+            self.fallback.as_ref(),
+            self.kw_span,
+            None,
+            validate,
+        );
         DuplicateEnumVariantId::validate(&self.vars, self.kw_span, None, validate);
         EmptyEnum::validate(&self.vars, self.span, None, validate);
 
         for var in &self.vars {
             var.validate(validate);
         }
+
+        if let Some(ref fallback) = self.fallback {
+            fallback.validate(validate);
+        }
     }
 
     pub fn span(&self) -> Span {
@@ -142,11 +181,16 @@ impl InlineEnum {
     pub fn variants(&self) -> &[EnumVariant] {
         &self.vars
     }
+
+    pub fn fallback(&self) -> Option<&EnumFallback> {
+        self.fallback.as_ref()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct EnumVariant {
     span: Span,
+    doc: Option<String>,
     name: Ident,
     id: LitPosInt,
     var_type: Option<TypeName>,
@@ -157,6 +201,7 @@ impl EnumVariant {
         assert_eq!(pair.as_rule(), Rule::enum_variant);
 
         let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
 
         let mut pairs = pair.into_inner();
 
@@ -180,6 +225,7 @@ impl EnumVariant {
 
         Self {
             span,
+            doc,
             name,
             id,
             var_type,
@@ -188,7 +234,7 @@ impl EnumVariant {
 
     fn validate(&self, validate: &mut Validate) {
         InvalidEnumVariantId::validate(self, validate);
-        NonCamelCaseEnumVariant::validate(self, validate);
+        NonCamelCaseEnumVariant::validate(&self.name, validate);
 
         self.name.validate(validate);
         if let Some(ref var_type) = self.var_type {
@@ -200,6 +246,10 @@ impl EnumVariant {
         self.span
     }
 
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn name(&self) -> &Ident {
         &self.name
     }
@@ -212,3 +262,46 @@ impl EnumVariant {
         self.var_type.as_ref()
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct EnumFallback {
+    span: Span,
+    doc: Option<String>,
+    name: Ident,
+}
+
+impl EnumFallback {
+    fn parse(pair: Pair<Rule>) -> Self {
+        assert_eq!(pair.as_rule(), Rule::enum_fallback);
+
+        let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
+
+        let mut pairs = pair.into_inner();
+
+        pairs.next().unwrap(); // Skip keyword.
+
+        let pair = pairs.next().unwrap();
+        let name = Ident::parse(pair);
+
+        Self { span, doc, name }
+    }
+
+    fn validate(&self, validate: &mut Validate) {
+        NonCamelCaseEnumVariant::validate(&self.name, validate);
+
+        self.name.validate(validate);
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    pub fn name(&self) -> &Ident {
+        &self.name
+    }
+}
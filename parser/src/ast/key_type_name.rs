@@ -1,4 +1,9 @@
+use super::NamedRef;
+use crate::error::{
+    ExpectedTypeFoundConst, ExpectedTypeFoundService, InvalidKeyType, TypeNotFound,
+};
 use crate::grammar::Rule;
+use crate::validate::Validate;
 use crate::Span;
 use pest::iterators::Pair;
 
@@ -21,6 +26,10 @@ impl KeyTypeName {
         Self { span, kind }
     }
 
+    pub(crate) fn validate(&self, validate: &mut Validate) {
+        self.kind.validate(validate);
+    }
+
     pub fn span(&self) -> Span {
         self.span
     }
@@ -30,7 +39,7 @@ impl KeyTypeName {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone)]
 pub enum KeyTypeNameKind {
     U8,
     I8,
@@ -42,6 +51,7 @@ pub enum KeyTypeNameKind {
     I64,
     String,
     Uuid,
+    Ref(NamedRef),
 }
 
 impl KeyTypeNameKind {
@@ -57,7 +67,31 @@ impl KeyTypeNameKind {
             Rule::kw_i64 => Self::I64,
             Rule::kw_string => Self::String,
             Rule::kw_uuid => Self::Uuid,
+            Rule::named_ref => Self::Ref(NamedRef::parse(pair)),
             _ => unreachable!(),
         }
     }
+
+    fn validate(&self, validate: &mut Validate) {
+        match self {
+            Self::Ref(ty) => {
+                TypeNotFound::validate(ty, validate);
+                ExpectedTypeFoundService::validate(ty, validate);
+                ExpectedTypeFoundConst::validate(ty, validate);
+                InvalidKeyType::validate(ty, validate);
+                ty.validate(validate);
+            }
+
+            Self::U8
+            | Self::I8
+            | Self::U16
+            | Self::I16
+            | Self::U32
+            | Self::I32
+            | Self::U64
+            | Self::I64
+            | Self::String
+            | Self::Uuid => {}
+        }
+    }
 }
@@ -1,7 +1,7 @@
-use super::{Ident, LitPosInt, LitUuid, TypeNameOrInline};
+use super::{doc_string, Ident, LitPosInt, LitUuid, TypeName, TypeNameOrInline};
 use crate::error::{
     DuplicateEventId, DuplicateFunctionId, DuplicateServiceItem, InvalidEventId, InvalidFunctionId,
-    InvalidServiceUuid, InvalidServiceVersion,
+    InvalidIntroducedIn, InvalidServiceUuid, InvalidServiceVersion,
 };
 use crate::grammar::Rule;
 use crate::validate::Validate;
@@ -12,6 +12,7 @@ use pest::iterators::Pair;
 #[derive(Debug, Clone)]
 pub struct ServiceDef {
     span: Span,
+    doc: Option<String>,
     name: Ident,
     uuid: LitUuid,
     ver: LitPosInt,
@@ -23,6 +24,7 @@ impl ServiceDef {
         assert_eq!(pair.as_rule(), Rule::service_def);
 
         let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
 
         let mut pairs = pair.into_inner();
         pairs.next().unwrap(); // Skip keyword.
@@ -49,6 +51,7 @@ impl ServiceDef {
 
         Self {
             span,
+            doc,
             name,
             uuid,
             ver,
@@ -80,6 +83,7 @@ impl ServiceDef {
         DuplicateServiceItem::validate(self, validate);
         DuplicateFunctionId::validate(self, validate);
         DuplicateEventId::validate(self, validate);
+        InvalidIntroducedIn::validate(self, validate);
         NonCamelCaseService::validate(self, validate);
 
         self.name.validate(validate);
@@ -92,6 +96,10 @@ impl ServiceDef {
         self.span
     }
 
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn name(&self) -> &Ident {
         &self.name
     }
@@ -110,9 +118,11 @@ impl ServiceDef {
 }
 
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum ServiceItem {
     Function(FunctionDef),
     Event(EventDef),
+    Property(PropertyDef),
 }
 
 impl ServiceItem {
@@ -123,6 +133,7 @@ impl ServiceItem {
         match pair.as_rule() {
             Rule::fn_def => Self::Function(FunctionDef::parse(pair)),
             Rule::event_def => Self::Event(EventDef::parse(pair)),
+            Rule::property_def => Self::Property(PropertyDef::parse(pair)),
             _ => unreachable!(),
         }
     }
@@ -131,6 +142,7 @@ impl ServiceItem {
         match self {
             Self::Function(i) => i.validate(validate),
             Self::Event(i) => i.validate(validate),
+            Self::Property(i) => i.validate(validate),
         }
     }
 
@@ -138,6 +150,7 @@ impl ServiceItem {
         match self {
             Self::Function(i) => i.span(),
             Self::Event(i) => i.span(),
+            Self::Property(i) => i.span(),
         }
     }
 
@@ -145,6 +158,7 @@ impl ServiceItem {
         match self {
             Self::Function(i) => i.name(),
             Self::Event(i) => i.name(),
+            Self::Property(i) => i.name(),
         }
     }
 }
@@ -152,10 +166,13 @@ impl ServiceItem {
 #[derive(Debug, Clone)]
 pub struct FunctionDef {
     span: Span,
+    doc: Option<String>,
     name: Ident,
     id: LitPosInt,
+    introduced_in: Option<LitPosInt>,
     args: Option<FunctionPart>,
     ok: Option<FunctionPart>,
+    stream: Option<FunctionPart>,
     err: Option<FunctionPart>,
 }
 
@@ -164,6 +181,7 @@ impl FunctionDef {
         assert_eq!(pair.as_rule(), Rule::fn_def);
 
         let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
 
         let mut pairs = pair.into_inner();
 
@@ -177,14 +195,18 @@ impl FunctionDef {
         let pair = pairs.next().unwrap();
         let id = LitPosInt::parse(pair);
 
+        let mut introduced_in = None;
         let mut args = None;
         let mut ok = None;
+        let mut stream = None;
         let mut err = None;
         for pair in pairs {
             match pair.as_rule() {
+                Rule::introduced_in => introduced_in = Some(parse_introduced_in(pair)),
                 Rule::tok_cur_open => {}
                 Rule::fn_args => args = Some(FunctionPart::parse(pair)),
                 Rule::fn_ok => ok = Some(FunctionPart::parse(pair)),
+                Rule::fn_stream => stream = Some(FunctionPart::parse(pair)),
                 Rule::fn_err => err = Some(FunctionPart::parse(pair)),
                 Rule::tok_cur_close | Rule::tok_term => break,
                 _ => unreachable!(),
@@ -193,10 +215,13 @@ impl FunctionDef {
 
         Self {
             span,
+            doc,
             name,
             id,
+            introduced_in,
             args,
             ok,
+            stream,
             err,
         }
     }
@@ -215,6 +240,10 @@ impl FunctionDef {
             ok.validate(validate);
         }
 
+        if let Some(ref stream) = self.stream {
+            stream.validate(validate);
+        }
+
         if let Some(ref err) = self.err {
             err.validate(validate);
         }
@@ -224,6 +253,10 @@ impl FunctionDef {
         self.span
     }
 
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn name(&self) -> &Ident {
         &self.name
     }
@@ -232,6 +265,10 @@ impl FunctionDef {
         &self.id
     }
 
+    pub fn introduced_in(&self) -> Option<&LitPosInt> {
+        self.introduced_in.as_ref()
+    }
+
     pub fn args(&self) -> Option<&FunctionPart> {
         self.args.as_ref()
     }
@@ -240,6 +277,10 @@ impl FunctionDef {
         self.ok.as_ref()
     }
 
+    pub fn stream(&self) -> Option<&FunctionPart> {
+        self.stream.as_ref()
+    }
+
     pub fn err(&self) -> Option<&FunctionPart> {
         self.err.as_ref()
     }
@@ -256,6 +297,7 @@ impl FunctionPart {
         assert!(
             (pair.as_rule() == Rule::fn_args)
                 || (pair.as_rule() == Rule::fn_ok)
+                || (pair.as_rule() == Rule::fn_stream)
                 || (pair.as_rule() == Rule::fn_err)
         );
 
@@ -288,8 +330,12 @@ impl FunctionPart {
 #[derive(Debug, Clone)]
 pub struct EventDef {
     span: Span,
+    doc: Option<String>,
+    retained: bool,
+    reliable: bool,
     name: Ident,
     id: LitPosInt,
+    introduced_in: Option<LitPosInt>,
     event_type: Option<TypeNameOrInline>,
 }
 
@@ -298,10 +344,23 @@ impl EventDef {
         assert_eq!(pair.as_rule(), Rule::event_def);
 
         let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
 
         let mut pairs = pair.into_inner();
 
-        pairs.next().unwrap(); // Skip keyword.
+        let pair = pairs.next().unwrap();
+        let (retained, reliable) = match pair.as_rule() {
+            Rule::kw_retained => {
+                pairs.next().unwrap(); // Skip keyword.
+                (true, false)
+            }
+            Rule::kw_reliable => {
+                pairs.next().unwrap(); // Skip keyword.
+                (false, true)
+            }
+            Rule::kw_event => (false, false),
+            _ => unreachable!(),
+        };
 
         let pair = pairs.next().unwrap();
         let name = Ident::parse(pair);
@@ -311,7 +370,15 @@ impl EventDef {
         let pair = pairs.next().unwrap();
         let id = LitPosInt::parse(pair);
 
-        let pair = pairs.next().unwrap();
+        let mut pair = pairs.next().unwrap();
+        let introduced_in = if pair.as_rule() == Rule::introduced_in {
+            let introduced_in = parse_introduced_in(pair);
+            pair = pairs.next().unwrap();
+            Some(introduced_in)
+        } else {
+            None
+        };
+
         let event_type = match pair.as_rule() {
             Rule::tok_eq => {
                 let pair = pairs.next().unwrap();
@@ -323,8 +390,12 @@ impl EventDef {
 
         Self {
             span,
+            doc,
+            retained,
+            reliable,
             name,
             id,
+            introduced_in,
             event_type,
         }
     }
@@ -343,6 +414,10 @@ impl EventDef {
         self.span
     }
 
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn name(&self) -> &Ident {
         &self.name
     }
@@ -351,7 +426,95 @@ impl EventDef {
         &self.id
     }
 
+    pub fn introduced_in(&self) -> Option<&LitPosInt> {
+        self.introduced_in.as_ref()
+    }
+
+    pub fn retained(&self) -> bool {
+        self.retained
+    }
+
+    pub fn reliable(&self) -> bool {
+        self.reliable
+    }
+
     pub fn event_type(&self) -> Option<&TypeNameOrInline> {
         self.event_type.as_ref()
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct PropertyDef {
+    span: Span,
+    doc: Option<String>,
+    name: Ident,
+    id: LitPosInt,
+    property_type: TypeName,
+}
+
+impl PropertyDef {
+    fn parse(pair: Pair<Rule>) -> Self {
+        assert_eq!(pair.as_rule(), Rule::property_def);
+
+        let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
+
+        let mut pairs = pair.into_inner();
+
+        pairs.next().unwrap(); // Skip keyword.
+
+        let pair = pairs.next().unwrap();
+        let name = Ident::parse(pair);
+
+        pairs.next().unwrap(); // Skip @.
+
+        let pair = pairs.next().unwrap();
+        let id = LitPosInt::parse(pair);
+
+        pairs.next().unwrap(); // Skip =.
+
+        let pair = pairs.next().unwrap();
+        let property_type = TypeName::parse(pair);
+
+        Self {
+            span,
+            doc,
+            name,
+            id,
+            property_type,
+        }
+    }
+
+    fn validate(&self, validate: &mut Validate) {
+        self.name.validate(validate);
+        self.property_type.validate(validate);
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    pub fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    pub fn id(&self) -> &LitPosInt {
+        &self.id
+    }
+
+    pub fn property_type(&self) -> &TypeName {
+        &self.property_type
+    }
+}
+
+fn parse_introduced_in(pair: Pair<Rule>) -> LitPosInt {
+    assert_eq!(pair.as_rule(), Rule::introduced_in);
+    let mut pairs = pair.into_inner();
+    pairs.next().unwrap(); // Skip keyword.
+    let pair = pairs.next().unwrap();
+    LitPosInt::parse(pair)
+}
@@ -1,4 +1,4 @@
-use super::{Attribute, Ident, LitPosInt, TypeName};
+use super::{doc_string, Attribute, Ident, LitPosInt, TypeName};
 use crate::error::{
     DuplicateStructField, DuplicateStructFieldId, InvalidStructFieldId, RecursiveStruct,
 };
@@ -11,9 +11,11 @@ use pest::iterators::Pair;
 #[derive(Debug, Clone)]
 pub struct StructDef {
     span: Span,
+    doc: Option<String>,
     attrs: Vec<Attribute>,
     name: Ident,
     fields: Vec<StructField>,
+    fallback: Option<StructFallback>,
 }
 
 impl StructDef {
@@ -21,6 +23,7 @@ impl StructDef {
         assert_eq!(pair.as_rule(), Rule::struct_def);
 
         let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
 
         let mut pairs = pair.into_inner();
 
@@ -39,9 +42,11 @@ impl StructDef {
         pairs.next().unwrap(); // Skip {.
 
         let mut fields = Vec::new();
+        let mut fallback = None;
         for pair in pairs {
             match pair.as_rule() {
                 Rule::struct_field => fields.push(StructField::parse(pair)),
+                Rule::struct_fallback => fallback = Some(StructFallback::parse(pair)),
                 Rule::tok_cur_close => break,
                 _ => unreachable!(),
             }
@@ -49,14 +54,22 @@ impl StructDef {
 
         Self {
             span,
+            doc,
             attrs,
             name,
             fields,
+            fallback,
         }
     }
 
     pub(crate) fn validate(&self, validate: &mut Validate) {
-        DuplicateStructField::validate(&self.fields, self.name.span(), Some(&self.name), validate);
+        DuplicateStructField::validate(
+            &self.fields,
+            self.fallback.as_ref(),
+            self.name.span(),
+            Some(&self.name),
+            validate,
+        );
         DuplicateStructFieldId::validate(
             &self.fields,
             self.name.span(),
@@ -70,12 +83,20 @@ impl StructDef {
         for field in &self.fields {
             field.validate(validate);
         }
+
+        if let Some(ref fallback) = self.fallback {
+            fallback.validate(validate);
+        }
     }
 
     pub fn span(&self) -> Span {
         self.span
     }
 
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn attributes(&self) -> &[Attribute] {
         &self.attrs
     }
@@ -87,6 +108,10 @@ impl StructDef {
     pub fn fields(&self) -> &[StructField] {
         &self.fields
     }
+
+    pub fn fallback(&self) -> Option<&StructFallback> {
+        self.fallback.as_ref()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -94,6 +119,7 @@ pub struct InlineStruct {
     span: Span,
     kw_span: Span,
     fields: Vec<StructField>,
+    fallback: Option<StructFallback>,
 }
 
 impl InlineStruct {
@@ -110,9 +136,11 @@ impl InlineStruct {
         pairs.next().unwrap(); // Skip {.
 
         let mut fields = Vec::new();
+        let mut fallback = None;
         for pair in pairs {
             match pair.as_rule() {
                 Rule::struct_field => fields.push(StructField::parse(pair)),
+                Rule::struct_fallback => fallback = Some(StructFallback::parse(pair)),
                 Rule::tok_cur_close => break,
                 _ => unreachable!(),
             }
@@ -122,16 +150,27 @@ impl InlineStruct {
             span,
             kw_span,
             fields,
+            fallback,
         }
     }
 
     pub(crate) fn validate(&self, validate: &mut Validate) {
-        DuplicateStructField::validate(&self.fields, self.kw_span, None, validate);
+        DuplicateStructField::validate(
+            &self.fields,
+            self.fallback.as_ref(),
+            self.kw_span,
+            None,
+            validate,
+        );
         DuplicateStructFieldId::validate(&self.fields, self.kw_span, None, validate);
 
         for field in &self.fields {
             field.validate(validate);
         }
+
+        if let Some(ref fallback) = self.fallback {
+            fallback.validate(validate);
+        }
     }
 
     pub fn span(&self) -> Span {
@@ -145,11 +184,16 @@ impl InlineStruct {
     pub fn fields(&self) -> &[StructField] {
         &self.fields
     }
+
+    pub fn fallback(&self) -> Option<&StructFallback> {
+        self.fallback.as_ref()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct StructField {
     span: Span,
+    doc: Option<String>,
     req: bool,
     name: Ident,
     id: LitPosInt,
@@ -161,6 +205,7 @@ impl StructField {
         assert_eq!(pair.as_rule(), Rule::struct_field);
 
         let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
 
         let mut pairs = pair.into_inner();
 
@@ -192,6 +237,7 @@ impl StructField {
 
         Self {
             span,
+            doc,
             req,
             name,
             id,
@@ -201,7 +247,7 @@ impl StructField {
 
     fn validate(&self, validate: &mut Validate) {
         InvalidStructFieldId::validate(self, validate);
-        NonSnakeCaseStructField::validate(self, validate);
+        NonSnakeCaseStructField::validate(&self.name, validate);
 
         self.name.validate(validate);
         self.field_type.validate(validate);
@@ -211,6 +257,10 @@ impl StructField {
         self.span
     }
 
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
     pub fn required(&self) -> bool {
         self.req
     }
@@ -227,3 +277,46 @@ impl StructField {
         &self.field_type
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct StructFallback {
+    span: Span,
+    doc: Option<String>,
+    name: Ident,
+}
+
+impl StructFallback {
+    fn parse(pair: Pair<Rule>) -> Self {
+        assert_eq!(pair.as_rule(), Rule::struct_fallback);
+
+        let span = Span::from_pair(&pair);
+        let doc = doc_string::parse(&pair);
+
+        let mut pairs = pair.into_inner();
+
+        pairs.next().unwrap(); // Skip keyword.
+
+        let pair = pairs.next().unwrap();
+        let name = Ident::parse(pair);
+
+        Self { span, doc, name }
+    }
+
+    fn validate(&self, validate: &mut Validate) {
+        NonSnakeCaseStructField::validate(&self.name, validate);
+
+        self.name.validate(validate);
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    pub fn name(&self) -> &Ident {
+        &self.name
+    }
+}
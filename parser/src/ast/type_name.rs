@@ -63,6 +63,7 @@ pub enum TypeNameKind {
     Set(KeyTypeName),
     Sender(Box<TypeName>),
     Receiver(Box<TypeName>),
+    Broadcast(Box<TypeName>),
     Lifetime,
     Unit,
     Result(Box<TypeName>, Box<TypeName>),
@@ -162,6 +163,15 @@ impl TypeNameKind {
                 Self::Receiver(Box::new(TypeName::parse(pair)))
             }
 
+            Rule::broadcast_type => {
+                let mut pairs = pair.into_inner();
+                pairs.next().unwrap(); // Skip keyword.
+                pairs.next().unwrap(); // Skip <.
+                let pair = pairs.next().unwrap();
+
+                Self::Broadcast(Box::new(TypeName::parse(pair)))
+            }
+
             Rule::result_type => {
                 let mut pairs = pair.into_inner();
                 pairs.next().unwrap(); // Skip keyword.
@@ -200,9 +210,16 @@ impl TypeNameKind {
             Self::Option(ty)
             | Self::Box(ty)
             | Self::Vec(ty)
-            | Self::Map(_, ty)
             | Self::Sender(ty)
-            | Self::Receiver(ty) => ty.validate(validate),
+            | Self::Receiver(ty)
+            | Self::Broadcast(ty) => ty.validate(validate),
+
+            Self::Map(key, ty) => {
+                key.validate(validate);
+                ty.validate(validate);
+            }
+
+            Self::Set(key) => key.validate(validate),
 
             Self::Result(ok, err) => {
                 ok.validate(validate);
@@ -238,7 +255,6 @@ impl TypeNameKind {
             | Self::ServiceId
             | Self::Value
             | Self::Bytes
-            | Self::Set(_)
             | Self::Lifetime
             | Self::Unit => {}
         }
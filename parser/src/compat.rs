@@ -0,0 +1,655 @@
+//! Compatibility checks between two versions of a schema.
+//!
+//! [`check`] compares an old and a new version of a schema and reports all changes, that would
+//! break existing code or peers on the bus, which still use the old version.
+//!
+//! Items are matched by name. An item, that cannot be found by name, but whose id is used by a
+//! different item in the new schema, is considered renamed.
+
+#[cfg(test)]
+mod test;
+
+use crate::ast::{
+    ArrayLenValue, Definition, EnumVariant, EventDef, FunctionDef, FunctionPart, KeyTypeName,
+    KeyTypeNameKind, NamedRef, NamedRefKind, PropertyDef, ServiceDef, ServiceItem, StructField,
+    TypeName, TypeNameKind, TypeNameOrInline,
+};
+use crate::Schema;
+use std::fmt;
+use uuid::Uuid;
+
+/// Checks two versions of a schema for breaking changes.
+///
+/// Only the definitions of the schemas themselves are compared. Types referenced from other
+/// schemas are compared by name.
+pub fn check(old: &Schema, new: &Schema) -> Vec<BreakingChange> {
+    let mut check = Check::new();
+
+    for old_def in old.definitions() {
+        let name = old_def.name().value();
+
+        match new.definitions().iter().find(|d| d.name().value() == name) {
+            Some(new_def) => check.definition(old_def, new_def),
+            None => check.push(name, def_item_kind(old_def), ChangeKind::Removed),
+        }
+    }
+
+    check.changes
+}
+
+/// A breaking change between two versions of a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakingChange {
+    path: String,
+    item: ItemKind,
+    kind: ChangeKind,
+}
+
+impl BreakingChange {
+    /// Returns the path of the affected item in the old schema.
+    ///
+    /// Paths consist of the names of the item and its parents, separated by `.`, e.g.
+    /// `Service.function.args`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the kind of the affected item.
+    pub fn item(&self) -> ItemKind {
+        self.item
+    }
+
+    /// Returns the kind of the change.
+    pub fn kind(&self) -> &ChangeKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} `{}` ", self.item, self.path)?;
+
+        match self.kind {
+            ChangeKind::Removed => f.write_str("was removed"),
+            ChangeKind::Renamed(ref new) => write!(f, "was renamed to `{new}`"),
+            ChangeKind::KindChanged(new) => write!(f, "was changed into {new}"),
+            ChangeKind::IdChanged(old, new) => write!(f, "changed its id from {old} to {new}"),
+
+            ChangeKind::TypeChanged(ref old, ref new) => write!(
+                f,
+                "changed its type from `{}` to `{}`",
+                old.as_deref().unwrap_or("none"),
+                new.as_deref().unwrap_or("none"),
+            ),
+
+            ChangeKind::BecameRequired => f.write_str("became required"),
+            ChangeKind::RequiredAdded => f.write_str("was added as required"),
+            ChangeKind::UuidChanged(old, new) => write!(f, "changed its uuid from {old} to {new}"),
+
+            ChangeKind::VersionDecreased(old, new) => {
+                write!(f, "decreased its version from {old} to {new}")
+            }
+
+            ChangeKind::VersionNotIncreased(version) => write!(
+                f,
+                "was extended without increasing its version (still {version})"
+            ),
+        }
+    }
+}
+
+/// Kind of an item affected by a [`BreakingChange`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ItemKind {
+    /// A struct definition or inline struct.
+    Struct,
+
+    /// An enum definition or inline enum.
+    Enum,
+
+    /// A service definition.
+    Service,
+
+    /// A constant.
+    Const,
+
+    /// A newtype definition.
+    Newtype,
+
+    /// A field of a struct.
+    Field,
+
+    /// A variant of an enum.
+    Variant,
+
+    /// A function of a service.
+    Function,
+
+    /// The args, ok, stream or err part of a function.
+    FunctionPart,
+
+    /// An event of a service.
+    Event,
+
+    /// A property of a service.
+    Property,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Struct => f.write_str("struct"),
+            Self::Enum => f.write_str("enum"),
+            Self::Service => f.write_str("service"),
+            Self::Const => f.write_str("const"),
+            Self::Newtype => f.write_str("newtype"),
+            Self::Field => f.write_str("field"),
+            Self::Variant => f.write_str("variant"),
+            Self::Function => f.write_str("function"),
+            Self::FunctionPart => f.write_str("function part"),
+            Self::Event => f.write_str("event"),
+            Self::Property => f.write_str("property"),
+        }
+    }
+}
+
+/// Kind of a [`BreakingChange`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChangeKind {
+    /// The item was removed.
+    Removed,
+
+    /// The item was renamed.
+    Renamed(String),
+
+    /// The item was changed to a different kind of item.
+    KindChanged(ItemKind),
+
+    /// The id of the item was changed.
+    IdChanged(u32, u32),
+
+    /// The type of the item was changed.
+    ///
+    /// `None` means that the item had or has no type.
+    TypeChanged(Option<String>, Option<String>),
+
+    /// An optional field became required.
+    BecameRequired,
+
+    /// A required field was added.
+    RequiredAdded,
+
+    /// The uuid of a service was changed.
+    UuidChanged(Uuid, Uuid),
+
+    /// The version of a service was decreased.
+    VersionDecreased(u32, u32),
+
+    /// Functions or events were added to a service without increasing its version.
+    VersionNotIncreased(u32),
+}
+
+struct Check {
+    changes: Vec<BreakingChange>,
+}
+
+impl Check {
+    fn new() -> Self {
+        Self {
+            changes: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, path: impl Into<String>, item: ItemKind, kind: ChangeKind) {
+        self.changes.push(BreakingChange {
+            path: path.into(),
+            item,
+            kind,
+        });
+    }
+
+    fn definition(&mut self, old: &Definition, new: &Definition) {
+        let name = old.name().value();
+
+        match (old, new) {
+            (Definition::Struct(old), Definition::Struct(new)) => {
+                self.fields(name, old.fields(), new.fields())
+            }
+
+            (Definition::Enum(old), Definition::Enum(new)) => {
+                self.variants(name, old.variants(), new.variants())
+            }
+
+            (Definition::Service(old), Definition::Service(new)) => self.service(old, new),
+            (Definition::Const(_), Definition::Const(_)) => {}
+
+            (Definition::Newtype(old), Definition::Newtype(new)) => {
+                let old = type_name(old.target_type());
+                let new = type_name(new.target_type());
+
+                if old != new {
+                    self.push(
+                        name,
+                        ItemKind::Newtype,
+                        ChangeKind::TypeChanged(Some(old), Some(new)),
+                    );
+                }
+            }
+
+            _ => self.push(
+                name,
+                def_item_kind(old),
+                ChangeKind::KindChanged(def_item_kind(new)),
+            ),
+        }
+    }
+
+    fn fields(&mut self, path: &str, old: &[StructField], new: &[StructField]) {
+        for old_field in old {
+            let name = old_field.name().value();
+            let path = format!("{path}.{name}");
+            let old_id = parse_id(old_field.id().value());
+
+            let Some(new_field) = new.iter().find(|f| f.name().value() == name) else {
+                let renamed = new.iter().find(|f| {
+                    (parse_id(f.id().value()) == old_id)
+                        && !old.iter().any(|o| o.name().value() == f.name().value())
+                });
+
+                match renamed {
+                    Some(new_field) => self.push(
+                        path,
+                        ItemKind::Field,
+                        ChangeKind::Renamed(new_field.name().value().to_owned()),
+                    ),
+
+                    None => self.push(path, ItemKind::Field, ChangeKind::Removed),
+                }
+
+                continue;
+            };
+
+            let new_id = parse_id(new_field.id().value());
+            if old_id != new_id {
+                self.push(
+                    &path,
+                    ItemKind::Field,
+                    ChangeKind::IdChanged(old_id, new_id),
+                );
+            }
+
+            let old_ty = type_name(old_field.field_type());
+            let new_ty = type_name(new_field.field_type());
+            if old_ty != new_ty {
+                self.push(
+                    &path,
+                    ItemKind::Field,
+                    ChangeKind::TypeChanged(Some(old_ty), Some(new_ty)),
+                );
+            }
+
+            if !old_field.required() && new_field.required() {
+                self.push(path, ItemKind::Field, ChangeKind::BecameRequired);
+            }
+        }
+
+        for new_field in new {
+            if !new_field.required() {
+                continue;
+            }
+
+            let name = new_field.name().value();
+            let id = parse_id(new_field.id().value());
+
+            let exists = old
+                .iter()
+                .any(|f| (f.name().value() == name) || (parse_id(f.id().value()) == id));
+
+            if !exists {
+                self.push(
+                    format!("{path}.{name}"),
+                    ItemKind::Field,
+                    ChangeKind::RequiredAdded,
+                );
+            }
+        }
+    }
+
+    fn variants(&mut self, path: &str, old: &[EnumVariant], new: &[EnumVariant]) {
+        for old_var in old {
+            let name = old_var.name().value();
+            let path = format!("{path}.{name}");
+            let old_id = parse_id(old_var.id().value());
+
+            let Some(new_var) = new.iter().find(|v| v.name().value() == name) else {
+                let renamed = new.iter().find(|v| {
+                    (parse_id(v.id().value()) == old_id)
+                        && !old.iter().any(|o| o.name().value() == v.name().value())
+                });
+
+                match renamed {
+                    Some(new_var) => self.push(
+                        path,
+                        ItemKind::Variant,
+                        ChangeKind::Renamed(new_var.name().value().to_owned()),
+                    ),
+
+                    None => self.push(path, ItemKind::Variant, ChangeKind::Removed),
+                }
+
+                continue;
+            };
+
+            let new_id = parse_id(new_var.id().value());
+            if old_id != new_id {
+                self.push(
+                    &path,
+                    ItemKind::Variant,
+                    ChangeKind::IdChanged(old_id, new_id),
+                );
+            }
+
+            let old_ty = old_var.variant_type().map(type_name);
+            let new_ty = new_var.variant_type().map(type_name);
+            if old_ty != new_ty {
+                self.push(
+                    path,
+                    ItemKind::Variant,
+                    ChangeKind::TypeChanged(old_ty, new_ty),
+                );
+            }
+        }
+    }
+
+    fn service(&mut self, old: &ServiceDef, new: &ServiceDef) {
+        let path = old.name().value();
+
+        let old_uuid = old.uuid().value();
+        let new_uuid = new.uuid().value();
+        if old_uuid != new_uuid {
+            self.push(
+                path,
+                ItemKind::Service,
+                ChangeKind::UuidChanged(old_uuid, new_uuid),
+            );
+        }
+
+        for old_item in old.items() {
+            let name = old_item.name().value();
+            let item_path = format!("{path}.{name}");
+
+            match (old_item, find_item(new.items(), old_item)) {
+                (ServiceItem::Function(old), Some(ServiceItem::Function(new))) => {
+                    self.function(&item_path, old, new)
+                }
+
+                (ServiceItem::Event(old), Some(ServiceItem::Event(new))) => {
+                    self.event(&item_path, old, new)
+                }
+
+                (ServiceItem::Property(old), Some(ServiceItem::Property(new))) => {
+                    self.property(&item_path, old, new)
+                }
+
+                (_, Some(new_item)) => self.push(
+                    item_path,
+                    item_kind(old_item),
+                    ChangeKind::KindChanged(item_kind(new_item)),
+                ),
+
+                (_, None) => {
+                    let old_id = item_id(old_item);
+
+                    let renamed = new.items().iter().find(|i| {
+                        (item_kind(i) == item_kind(old_item))
+                            && (item_id(i) == old_id)
+                            && !old
+                                .items()
+                                .iter()
+                                .any(|o| o.name().value() == i.name().value())
+                    });
+
+                    match renamed {
+                        Some(new_item) => self.push(
+                            item_path,
+                            item_kind(old_item),
+                            ChangeKind::Renamed(new_item.name().value().to_owned()),
+                        ),
+
+                        None => self.push(item_path, item_kind(old_item), ChangeKind::Removed),
+                    }
+                }
+            }
+        }
+
+        let old_version = parse_id(old.version().value());
+        let new_version = parse_id(new.version().value());
+
+        if new_version < old_version {
+            self.push(
+                path,
+                ItemKind::Service,
+                ChangeKind::VersionDecreased(old_version, new_version),
+            );
+        } else if new_version == old_version {
+            let extended = new.items().iter().any(|new_item| {
+                !old.items().iter().any(|old_item| {
+                    (item_kind(old_item) == item_kind(new_item))
+                        && ((old_item.name().value() == new_item.name().value())
+                            || (item_id(old_item) == item_id(new_item)))
+                })
+            });
+
+            if extended {
+                self.push(
+                    path,
+                    ItemKind::Service,
+                    ChangeKind::VersionNotIncreased(old_version),
+                );
+            }
+        }
+    }
+
+    fn function(&mut self, path: &str, old: &FunctionDef, new: &FunctionDef) {
+        let old_id = parse_id(old.id().value());
+        let new_id = parse_id(new.id().value());
+        if old_id != new_id {
+            self.push(
+                path,
+                ItemKind::Function,
+                ChangeKind::IdChanged(old_id, new_id),
+            );
+        }
+
+        self.function_part(&format!("{path}.args"), old.args(), new.args());
+        self.function_part(&format!("{path}.ok"), old.ok(), new.ok());
+        self.function_part(&format!("{path}.stream"), old.stream(), new.stream());
+        self.function_part(&format!("{path}.err"), old.err(), new.err());
+    }
+
+    fn function_part(
+        &mut self,
+        path: &str,
+        old: Option<&FunctionPart>,
+        new: Option<&FunctionPart>,
+    ) {
+        self.type_or_inline(
+            path,
+            ItemKind::FunctionPart,
+            old.map(FunctionPart::part_type),
+            new.map(FunctionPart::part_type),
+        );
+    }
+
+    fn event(&mut self, path: &str, old: &EventDef, new: &EventDef) {
+        let old_id = parse_id(old.id().value());
+        let new_id = parse_id(new.id().value());
+        if old_id != new_id {
+            self.push(path, ItemKind::Event, ChangeKind::IdChanged(old_id, new_id));
+        }
+
+        self.type_or_inline(path, ItemKind::Event, old.event_type(), new.event_type());
+    }
+
+    fn property(&mut self, path: &str, old: &PropertyDef, new: &PropertyDef) {
+        let old_id = parse_id(old.id().value());
+        let new_id = parse_id(new.id().value());
+        if old_id != new_id {
+            self.push(
+                path,
+                ItemKind::Property,
+                ChangeKind::IdChanged(old_id, new_id),
+            );
+        }
+
+        let old_ty = type_name(old.property_type());
+        let new_ty = type_name(new.property_type());
+        if old_ty != new_ty {
+            self.push(
+                path,
+                ItemKind::Property,
+                ChangeKind::TypeChanged(Some(old_ty), Some(new_ty)),
+            );
+        }
+    }
+
+    fn type_or_inline(
+        &mut self,
+        path: &str,
+        item: ItemKind,
+        old: Option<&TypeNameOrInline>,
+        new: Option<&TypeNameOrInline>,
+    ) {
+        match (old, new) {
+            (Some(TypeNameOrInline::Struct(old)), Some(TypeNameOrInline::Struct(new))) => {
+                self.fields(path, old.fields(), new.fields())
+            }
+
+            (Some(TypeNameOrInline::Enum(old)), Some(TypeNameOrInline::Enum(new))) => {
+                self.variants(path, old.variants(), new.variants())
+            }
+
+            _ => {
+                let old = old.map(type_or_inline_name);
+                let new = new.map(type_or_inline_name);
+
+                if old != new {
+                    self.push(path, item, ChangeKind::TypeChanged(old, new));
+                }
+            }
+        }
+    }
+}
+
+fn parse_id(id: &str) -> u32 {
+    id.parse().unwrap_or(u32::MAX)
+}
+
+fn def_item_kind(def: &Definition) -> ItemKind {
+    match def {
+        Definition::Struct(_) => ItemKind::Struct,
+        Definition::Enum(_) => ItemKind::Enum,
+        Definition::Service(_) => ItemKind::Service,
+        Definition::Const(_) => ItemKind::Const,
+        Definition::Newtype(_) => ItemKind::Newtype,
+    }
+}
+
+fn item_kind(item: &ServiceItem) -> ItemKind {
+    match item {
+        ServiceItem::Function(_) => ItemKind::Function,
+        ServiceItem::Event(_) => ItemKind::Event,
+        ServiceItem::Property(_) => ItemKind::Property,
+    }
+}
+
+fn item_id(item: &ServiceItem) -> u32 {
+    match item {
+        ServiceItem::Function(func) => parse_id(func.id().value()),
+        ServiceItem::Event(ev) => parse_id(ev.id().value()),
+        ServiceItem::Property(prop) => parse_id(prop.id().value()),
+    }
+}
+
+fn find_item<'a>(items: &'a [ServiceItem], item: &ServiceItem) -> Option<&'a ServiceItem> {
+    let name = item.name().value();
+
+    items
+        .iter()
+        .filter(|i| i.name().value() == name)
+        .find(|i| item_kind(i) == item_kind(item))
+        .or_else(|| items.iter().find(|i| i.name().value() == name))
+}
+
+fn type_or_inline_name(ty: &TypeNameOrInline) -> String {
+    match ty {
+        TypeNameOrInline::TypeName(ty) => type_name(ty),
+        TypeNameOrInline::Struct(_) => "struct { ... }".to_owned(),
+        TypeNameOrInline::Enum(_) => "enum { ... }".to_owned(),
+    }
+}
+
+fn type_name(ty: &TypeName) -> String {
+    match ty.kind() {
+        TypeNameKind::Bool => "bool".to_owned(),
+        TypeNameKind::U8 => "u8".to_owned(),
+        TypeNameKind::I8 => "i8".to_owned(),
+        TypeNameKind::U16 => "u16".to_owned(),
+        TypeNameKind::I16 => "i16".to_owned(),
+        TypeNameKind::U32 => "u32".to_owned(),
+        TypeNameKind::I32 => "i32".to_owned(),
+        TypeNameKind::U64 => "u64".to_owned(),
+        TypeNameKind::I64 => "i64".to_owned(),
+        TypeNameKind::F32 => "f32".to_owned(),
+        TypeNameKind::F64 => "f64".to_owned(),
+        TypeNameKind::String => "string".to_owned(),
+        TypeNameKind::Uuid => "uuid".to_owned(),
+        TypeNameKind::ObjectId => "object_id".to_owned(),
+        TypeNameKind::ServiceId => "service_id".to_owned(),
+        TypeNameKind::Value => "value".to_owned(),
+        TypeNameKind::Option(ty) => format!("option<{}>", type_name(ty)),
+        TypeNameKind::Box(ty) => format!("box<{}>", type_name(ty)),
+        TypeNameKind::Vec(ty) => format!("vec<{}>", type_name(ty)),
+        TypeNameKind::Bytes => "bytes".to_owned(),
+        TypeNameKind::Map(k, v) => format!("map<{} -> {}>", key_type_name(k), type_name(v)),
+        TypeNameKind::Set(ty) => format!("set<{}>", key_type_name(ty)),
+        TypeNameKind::Sender(ty) => format!("sender<{}>", type_name(ty)),
+        TypeNameKind::Receiver(ty) => format!("receiver<{}>", type_name(ty)),
+        TypeNameKind::Broadcast(ty) => format!("broadcast<{}>", type_name(ty)),
+        TypeNameKind::Lifetime => "lifetime".to_owned(),
+        TypeNameKind::Unit => "unit".to_owned(),
+        TypeNameKind::Result(ok, err) => format!("result<{}, {}>", type_name(ok), type_name(err)),
+
+        TypeNameKind::Array(ty, len) => match len.value() {
+            ArrayLenValue::Literal(len) => format!("[{}; {}]", type_name(ty), len.value()),
+            ArrayLenValue::Ref(len) => format!("[{}; {}]", type_name(ty), named_ref_name(len)),
+        },
+
+        TypeNameKind::Ref(ty) => named_ref_name(ty),
+    }
+}
+
+fn key_type_name(ty: &KeyTypeName) -> String {
+    match ty.kind() {
+        KeyTypeNameKind::U8 => "u8".to_owned(),
+        KeyTypeNameKind::I8 => "i8".to_owned(),
+        KeyTypeNameKind::U16 => "u16".to_owned(),
+        KeyTypeNameKind::I16 => "i16".to_owned(),
+        KeyTypeNameKind::U32 => "u32".to_owned(),
+        KeyTypeNameKind::I32 => "i32".to_owned(),
+        KeyTypeNameKind::U64 => "u64".to_owned(),
+        KeyTypeNameKind::I64 => "i64".to_owned(),
+        KeyTypeNameKind::String => "string".to_owned(),
+        KeyTypeNameKind::Uuid => "uuid".to_owned(),
+        KeyTypeNameKind::Ref(ty) => named_ref_name(ty),
+    }
+}
+
+fn named_ref_name(ty: &NamedRef) -> String {
+    match ty.kind() {
+        NamedRefKind::Intern(ty) => ty.value().to_owned(),
+        NamedRefKind::Extern(schema, ty) => format!("{}::{}", schema.value(), ty.value()),
+    }
+}
@@ -0,0 +1,113 @@
+use super::{check, BreakingChange, ChangeKind, ItemKind};
+use crate::Parser;
+use uuid::uuid;
+
+fn check_schemas(name: &str) -> Vec<BreakingChange> {
+    let parser = Parser::new();
+
+    let old = parser.parse(format!("test/compat/{name}_old.aldrin"));
+    assert!(old.errors().is_empty());
+
+    let new = parser.parse(format!("test/compat/{name}_new.aldrin"));
+    assert!(new.errors().is_empty());
+
+    check(old.main_schema(), new.main_schema())
+}
+
+fn change(path: &str, item: ItemKind, kind: ChangeKind) -> BreakingChange {
+    BreakingChange {
+        path: path.to_owned(),
+        item,
+        kind,
+    }
+}
+
+#[test]
+fn compatible() {
+    assert_eq!(check_schemas("compatible"), []);
+}
+
+#[test]
+fn breaking() {
+    let changes = check_schemas("breaking");
+
+    assert_eq!(
+        changes,
+        [
+            change(
+                "Person.age",
+                ItemKind::Field,
+                ChangeKind::TypeChanged(Some("u32".to_owned()), Some("u64".to_owned())),
+            ),
+            change(
+                "Person.nick",
+                ItemKind::Field,
+                ChangeKind::Renamed("nickname".to_owned()),
+            ),
+            change("Person.email", ItemKind::Field, ChangeKind::BecameRequired),
+            change("Person.phone", ItemKind::Field, ChangeKind::Removed),
+            change("Person.address", ItemKind::Field, ChangeKind::RequiredAdded),
+            change(
+                "Shape.Circle",
+                ItemKind::Variant,
+                ChangeKind::TypeChanged(Some("f64".to_owned()), Some("f32".to_owned())),
+            ),
+            change(
+                "Shape.Square",
+                ItemKind::Variant,
+                ChangeKind::IdChanged(2, 3)
+            ),
+            change("Shape.Point", ItemKind::Variant, ChangeKind::Removed),
+            change("Removed", ItemKind::Struct, ChangeKind::Removed),
+            change(
+                "Kind",
+                ItemKind::Struct,
+                ChangeKind::KindChanged(ItemKind::Enum),
+            ),
+            change(
+                "Registry.add.ok",
+                ItemKind::FunctionPart,
+                ChangeKind::TypeChanged(None, Some("u32".to_owned())),
+            ),
+            change(
+                "Registry.add.err.Exists",
+                ItemKind::Variant,
+                ChangeKind::Renamed("AlreadyExists".to_owned()),
+            ),
+            change(
+                "Registry.remove",
+                ItemKind::Function,
+                ChangeKind::IdChanged(2, 4),
+            ),
+            change("Registry.clear", ItemKind::Function, ChangeKind::Removed),
+            change(
+                "Registry.added",
+                ItemKind::Event,
+                ChangeKind::TypeChanged(Some("Person".to_owned()), None),
+            ),
+            change(
+                "Registry",
+                ItemKind::Service,
+                ChangeKind::VersionDecreased(2, 1),
+            ),
+            change(
+                "Other",
+                ItemKind::Service,
+                ChangeKind::UuidChanged(
+                    uuid!("7b0c5cf3-0a27-4f0d-9d0e-2f3a5a5c4b10"),
+                    uuid!("0e8a7a3e-4a4b-4d2e-9b36-5f2b1f8d6c77"),
+                ),
+            ),
+            change(
+                "Other",
+                ItemKind::Service,
+                ChangeKind::VersionNotIncreased(1),
+            ),
+        ]
+    );
+
+    assert_eq!(
+        changes[1].to_string(),
+        "field `Person.nick` was renamed to `nickname`"
+    );
+}
@@ -101,6 +101,7 @@ pub enum Style {
 #[derive(Debug, Clone)]
 pub struct Formatted<'a> {
     kind: DiagnosticKind,
+    span: Option<Span>,
     intro: Line<'a>,
     lines: Vec<Line<'a>>,
 }
@@ -111,6 +112,13 @@ impl<'a> Formatted<'a> {
         self.kind
     }
 
+    /// Span of the diagnostic's main location, if it has one.
+    ///
+    /// The span refers to the schema given by [`Diagnostic::schema_name`].
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
     /// Short one-line summary.
     ///
     /// The summary begins with a lower-case letter and doesn't end with any punctuation.
@@ -256,6 +264,7 @@ impl<'a> Iterator for Chunks<'a> {
 
 pub(crate) struct Formatter<'a> {
     kind: DiagnosticKind,
+    span: Option<Span>,
     intro: Line<'a>,
     lines: Vec<UnpaddedLine<'a>>,
     padding: usize,
@@ -286,6 +295,7 @@ impl<'a> Formatter<'a> {
 
         Formatter {
             kind: diagnostic.kind(),
+            span: None,
             intro,
             lines: Vec::new(),
             padding: 0,
@@ -303,6 +313,7 @@ impl<'a> Formatter<'a> {
 
         Formatted {
             kind: self.kind,
+            span: self.span,
             intro: self.intro,
             lines,
         }
@@ -319,6 +330,10 @@ impl<'a> Formatter<'a> {
     where
         S: Into<Cow<'a, str>>,
     {
+        if is_main_block && self.span.is_none() {
+            self.span = Some(indicator);
+        }
+
         self.location(schema.path(), location, is_main_block);
 
         let source = match schema.source() {
@@ -428,6 +443,13 @@ impl<'a> Formatter<'a> {
     where
         P: AsRef<Path>,
     {
+        if self.span.is_none() {
+            self.span = Some(Span {
+                from: location,
+                to: location,
+            });
+        }
+
         self.location_impl(path, location, "-->")
     }
 
@@ -0,0 +1,932 @@
+//! Incremental, error-tolerant parsing of single schema documents.
+//!
+//! A [`Document`] holds the source of a single schema, e.g. a file opened in an editor, and is
+//! meant as the foundation of tooling such as a language server. In contrast to the
+//! [`Parser`](crate::Parser), it never gives up on errors:
+//!
+//! - The whole source is split into [`Token`]s, including comments, all of which retain their
+//!   positions. This also works for sources with syntax errors.
+//! - Top-level items, i.e. imports and definitions, are parsed individually. An item with a syntax
+//!   error is reported as a [`SyntaxError`] and skipped, while all other items remain usable. If
+//!   braces are unbalanced, a new item starts at the next line that begins with a top-level
+//!   keyword or an attribute.
+//! - After an [edit](Document::edit), only those items whose text has changed are parsed again.
+//!
+//! Documents neither resolve imports nor validate definitions. Queries like
+//! [`Document::definition_at`] thus only resolve names within the document itself. References to
+//! other schemas are reported with their schema name, so that they can be resolved by the caller.
+//!
+//! All positions passed to and returned from a `Document` are byte indices into its source.
+//! [`Document::position`] and [`Document::index`] convert between indices and lines and columns.
+
+mod lexer;
+#[cfg(test)]
+mod test;
+
+use crate::ast::{
+    ArrayLenValue, ConstValue, Definition, EnumFallback, EnumVariant, ImportStmt, KeyTypeName,
+    KeyTypeNameKind, NamedRef, ServiceItem, StructFallback, StructField, TypeName, TypeNameKind,
+    TypeNameOrInline,
+};
+use crate::error::{expected_reason, Expected};
+use crate::grammar::{Grammar, Rule};
+use crate::{LineCol, Position, Span};
+use pest::Parser;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::mem;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Source of a single schema with incremental, error-tolerant parsing.
+///
+/// See the [module documentation](self) for more information.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin_parser::document::{Document, SymbolKind};
+///
+/// let mut doc = Document::new("struct Foo { bar @1 = Baz; }\nstruct Baz {}\nstruct {\n");
+///
+/// // The last definition is invalid, but the others are still available.
+/// assert_eq!(doc.errors().len(), 1);
+/// assert_eq!(doc.symbols().len(), 2);
+///
+/// // `Baz` in `Foo` refers to the second definition.
+/// let baz = doc.source().find("Baz").unwrap();
+/// let def = doc.definition_at(baz).unwrap();
+/// assert_eq!(def.kind, SymbolKind::Struct);
+/// assert_eq!(def.span.from.line_col.line, 2);
+///
+/// // Fix the error.
+/// let len = doc.source().len();
+/// doc.edit(len - 2..len - 2, "Qux ");
+/// doc.edit(doc.source().len()..doc.source().len(), "}\n");
+/// assert!(doc.errors().is_empty());
+/// assert_eq!(doc.symbols().len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Document {
+    source: String,
+    lines: Vec<usize>,
+    tokens: Vec<Token>,
+    items: Vec<Item>,
+    symbols: Vec<Symbol>,
+    references: Vec<Reference>,
+    errors: Vec<SyntaxError>,
+}
+
+impl Document {
+    /// Creates a new document and parses `source`.
+    pub fn new(source: impl Into<String>) -> Self {
+        let mut doc = Self {
+            source: source.into(),
+            lines: Vec::new(),
+            tokens: Vec::new(),
+            items: Vec::new(),
+            symbols: Vec::new(),
+            references: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        doc.update(Vec::new());
+        doc
+    }
+
+    /// Returns the source of the document.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Replaces the whole source of the document.
+    ///
+    /// Items, that are unchanged by this, are not parsed again.
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        let items = mem::take(&mut self.items);
+        self.source = source.into();
+        self.update(items);
+    }
+
+    /// Replaces `range` of the source with `text`.
+    ///
+    /// Only items, whose text is changed by this, are parsed again.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the start or end of `range` are out of bounds or do not lie on a
+    /// `char` boundary.
+    pub fn edit(&mut self, range: Range<usize>, text: &str) {
+        let items = mem::take(&mut self.items);
+        self.source.replace_range(range, text);
+        self.update(items);
+    }
+
+    /// Returns all tokens of the document, including comments.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// Returns all syntax errors of the document.
+    pub fn errors(&self) -> &[SyntaxError] {
+        &self.errors
+    }
+
+    /// Returns the symbols of the document.
+    ///
+    /// These are all imports and all valid definitions in the order of their appearance. Fields,
+    /// variants and service items are returned as children of their definitions.
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Returns all references to named types and constants in the order of their appearance.
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// Returns the symbol, whose name is at `index`.
+    ///
+    /// This function also finds children, such as fields or service items.
+    pub fn symbol_at(&self, index: usize) -> Option<&Symbol> {
+        fn find(symbols: &[Symbol], index: usize) -> Option<&Symbol> {
+            symbols.iter().find_map(|symbol| {
+                if contains(symbol.name_span, index) {
+                    Some(symbol)
+                } else if contains(symbol.span, index) {
+                    find(&symbol.children, index)
+                } else {
+                    None
+                }
+            })
+        }
+
+        find(&self.symbols, index)
+    }
+
+    /// Returns the reference at `index`.
+    pub fn reference_at(&self, index: usize) -> Option<&Reference> {
+        self.references.iter().find(|r| contains(r.span, index))
+    }
+
+    /// Finds the definition of the name at `index`.
+    ///
+    /// If `index` is at a reference, then the symbol it refers to is returned. Otherwise, the
+    /// symbol, whose name is at `index`, is returned.
+    ///
+    /// References to other schemas cannot be resolved, but their schema name resolves to the
+    /// import.
+    pub fn definition_at(&self, index: usize) -> Option<&Symbol> {
+        let Some(reference) = self.reference_at(index) else {
+            return self.symbol_at(index);
+        };
+
+        match (&reference.schema, reference.schema_span) {
+            (Some(schema), Some(schema_span)) => {
+                if contains(schema_span, index) {
+                    self.find_symbol(schema, |kind| kind == SymbolKind::Import)
+                } else {
+                    None
+                }
+            }
+
+            _ => self.find_symbol(&reference.name, |kind| kind != SymbolKind::Import),
+        }
+    }
+
+    /// Finds all references to the name at `index`.
+    ///
+    /// `index` can be at a reference or at the name of a definition. For imports, all references
+    /// to the imported schema are returned.
+    pub fn references_at(&self, index: usize) -> Vec<&Reference> {
+        let Some(symbol) = self.definition_at(index) else {
+            return Vec::new();
+        };
+
+        match symbol.kind {
+            SymbolKind::Import => self
+                .references
+                .iter()
+                .filter(|r| r.schema.as_deref() == Some(&symbol.name))
+                .collect(),
+
+            SymbolKind::Struct
+            | SymbolKind::Enum
+            | SymbolKind::Service
+            | SymbolKind::Const
+            | SymbolKind::Newtype => self
+                .references
+                .iter()
+                .filter(|r| r.schema.is_none() && (r.name == symbol.name))
+                .collect(),
+
+            SymbolKind::Field
+            | SymbolKind::Variant
+            | SymbolKind::Function
+            | SymbolKind::Event
+            | SymbolKind::Property => Vec::new(),
+        }
+    }
+
+    /// Converts a byte index into a position.
+    ///
+    /// Indices past the end of the source are clamped and indices inside of a `char` are moved to
+    /// its start.
+    pub fn position(&self, index: usize) -> Position {
+        to_position(&self.source, &self.lines, index)
+    }
+
+    /// Converts a line and column into a byte index.
+    ///
+    /// Returns `None` if `line_col` is not within the source. The position directly after the end
+    /// of a line is considered to be within the source.
+    pub fn index(&self, line_col: LineCol) -> Option<usize> {
+        let start = *self.lines.get(line_col.line.checked_sub(1)?)?;
+        let line = self.source[start..].split('\n').next().unwrap();
+        let column = line_col.column.checked_sub(1)?;
+
+        line.char_indices()
+            .map(|(i, _)| i)
+            .chain([line.len()])
+            .nth(column)
+            .map(|i| start + i)
+    }
+
+    fn find_symbol(&self, name: &str, kind: impl Fn(SymbolKind) -> bool) -> Option<&Symbol> {
+        self.symbols
+            .iter()
+            .find(|symbol| kind(symbol.kind) && (symbol.name == name))
+    }
+
+    fn update(&mut self, old_items: Vec<Item>) {
+        let cache = old_items
+            .iter()
+            .map(|item| (item.parsed.text.as_str(), &item.parsed))
+            .collect::<HashMap<_, _>>();
+
+        self.lines = [0]
+            .into_iter()
+            .chain(self.source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+
+        let tokens = lexer::lex(&self.source);
+
+        self.items = split(&self.source, &tokens)
+            .into_iter()
+            .map(|range| {
+                let text = &self.source[range.clone()];
+
+                let parsed = match cache.get(text) {
+                    Some(&parsed) => parsed.clone(),
+                    None => Arc::new(ParsedItem::parse(text)),
+                };
+
+                Item {
+                    start: range.start,
+                    parsed,
+                }
+            })
+            .collect();
+
+        self.tokens = tokens
+            .into_iter()
+            .map(|(kind, range)| Token {
+                kind,
+                span: to_span(&self.source, &self.lines, range),
+            })
+            .collect();
+
+        let mut builder = Builder {
+            source: &self.source,
+            lines: &self.lines,
+            offset: 0,
+            symbols: Vec::new(),
+            references: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        for item in &self.items {
+            builder.item(item);
+        }
+
+        self.symbols = builder.symbols;
+        self.references = builder.references;
+        self.errors = builder.errors;
+    }
+}
+
+/// Token of a [`Document`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Token {
+    /// Kind of the token.
+    pub kind: TokenKind,
+
+    /// Span of the token.
+    pub span: Span,
+}
+
+/// Kind of a [`Token`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TokenKind {
+    /// A keyword, e.g. `struct` or `u32`.
+    Keyword,
+
+    /// An identifier.
+    Ident,
+
+    /// An integer literal.
+    LitInt,
+
+    /// A floating point literal.
+    LitFloat,
+
+    /// A string literal.
+    LitString,
+
+    /// A UUID literal.
+    LitUuid,
+
+    /// Punctuation, e.g. `{` or `->`.
+    Punct,
+
+    /// A comment.
+    Comment,
+
+    /// A doc comment, i.e. a comment starting with exactly 3 slashes.
+    DocComment,
+
+    /// A character, that cannot start any token, or an unterminated string literal.
+    Unknown,
+}
+
+/// Named item of a [`Document`], e.g. a definition or a struct field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Symbol {
+    /// Kind of the symbol.
+    pub kind: SymbolKind,
+
+    /// Name of the symbol.
+    ///
+    /// For imports, this is the name of the imported schema.
+    pub name: String,
+
+    /// Span of the whole symbol, excluding doc comments.
+    pub span: Span,
+
+    /// Span of the symbol's name.
+    pub name_span: Span,
+
+    /// Nested symbols, e.g. the fields of a struct.
+    ///
+    /// Fields and variants of inline structs and enums are children of the function or event.
+    pub children: Vec<Self>,
+}
+
+/// Kind of a [`Symbol`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SymbolKind {
+    /// An import statement.
+    Import,
+
+    /// A struct definition.
+    Struct,
+
+    /// An enum definition.
+    Enum,
+
+    /// A service definition.
+    Service,
+
+    /// A constant definition.
+    Const,
+
+    /// A newtype definition.
+    Newtype,
+
+    /// A struct field.
+    Field,
+
+    /// An enum variant.
+    Variant,
+
+    /// A function of a service.
+    Function,
+
+    /// An event of a service.
+    Event,
+
+    /// A property of a service.
+    Property,
+}
+
+/// Reference to a named type or constant in a [`Document`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Reference {
+    /// Span of the whole reference.
+    pub span: Span,
+
+    /// Name of the schema, if the reference is of the form `schema::Name`.
+    pub schema: Option<String>,
+
+    /// Span of the schema name.
+    pub schema_span: Option<Span>,
+
+    /// Name of the referenced type or constant.
+    pub name: String,
+
+    /// Span of the name.
+    pub name_span: Span,
+}
+
+/// Syntax error in a [`Document`].
+///
+/// The [`Display`](fmt::Display) implementation formats the expected tokens, e.g. "expected `}` or
+/// an identifier".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    /// Position of the error.
+    pub position: Position,
+
+    /// Set of expected tokens at the position.
+    pub expected: BTreeSet<Expected>,
+
+    /// Span of the item, that contains the error.
+    ///
+    /// The whole item is skipped when computing symbols and references.
+    pub item_span: Span,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&expected_reason(&self.expected))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Item {
+    start: usize,
+    parsed: Arc<ParsedItem>,
+}
+
+#[derive(Debug)]
+struct ParsedItem {
+    text: String,
+    ast: Result<ItemAst, ItemError>,
+}
+
+impl ParsedItem {
+    fn parse(text: &str) -> Self {
+        use pest::error::ErrorVariant;
+
+        let ast = match Grammar::parse(Rule::item, text) {
+            Ok(mut pairs) => {
+                let pair = pairs.next().unwrap();
+
+                match pair.as_rule() {
+                    Rule::import_stmt => Ok(ItemAst::Import(ImportStmt::parse(pair))),
+                    Rule::def => Ok(ItemAst::Definition(Definition::parse(pair))),
+                    _ => unreachable!(),
+                }
+            }
+
+            Err(e) => {
+                let index = Position::from_pest_error(&e).index;
+
+                let positives = match e.variant {
+                    ErrorVariant::ParsingError { positives, .. } => positives,
+                    ErrorVariant::CustomError { .. } => unreachable!(),
+                };
+
+                let mut expected = BTreeSet::new();
+                for rule in positives {
+                    Expected::add(rule, &mut expected);
+                }
+
+                Err(ItemError { index, expected })
+            }
+        };
+
+        Self {
+            text: text.to_owned(),
+            ast,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+enum ItemAst {
+    Import(ImportStmt),
+    Definition(Definition),
+}
+
+#[derive(Debug)]
+struct ItemError {
+    index: usize,
+    expected: BTreeSet<Expected>,
+}
+
+/// Converts parsed items into symbols and references.
+///
+/// The spans of the items' ASTs are relative to the start of each item.
+struct Builder<'a> {
+    source: &'a str,
+    lines: &'a [usize],
+    offset: usize,
+    symbols: Vec<Symbol>,
+    references: Vec<Reference>,
+    errors: Vec<SyntaxError>,
+}
+
+impl Builder<'_> {
+    fn item(&mut self, item: &Item) {
+        self.offset = item.start;
+
+        match item.parsed.ast {
+            Ok(ItemAst::Import(ref import)) => {
+                let symbol = Symbol {
+                    kind: SymbolKind::Import,
+                    name: import.schema_name().value().to_owned(),
+                    span: self.span(import.span()),
+                    name_span: self.span(import.schema_name().span()),
+                    children: Vec::new(),
+                };
+
+                self.symbols.push(symbol);
+            }
+
+            Ok(ItemAst::Definition(ref def)) => {
+                let symbol = self.definition(def);
+                self.symbols.push(symbol);
+            }
+
+            Err(ref e) => {
+                let range = item.start..item.start + item.parsed.text.len();
+
+                self.errors.push(SyntaxError {
+                    position: to_position(self.source, self.lines, item.start + e.index),
+                    expected: e.expected.clone(),
+                    item_span: to_span(self.source, self.lines, range),
+                });
+            }
+        }
+    }
+
+    fn definition(&mut self, def: &Definition) -> Symbol {
+        let (kind, children) = match def {
+            Definition::Struct(d) => (SymbolKind::Struct, self.fields(d.fields(), d.fallback())),
+            Definition::Enum(d) => (SymbolKind::Enum, self.variants(d.variants(), d.fallback())),
+
+            Definition::Service(d) => {
+                let children = d
+                    .items()
+                    .iter()
+                    .map(|item| self.service_item(item))
+                    .collect();
+
+                (SymbolKind::Service, children)
+            }
+
+            Definition::Const(d) => {
+                if let ConstValue::Typed(typed) = d.value() {
+                    self.type_name(typed.type_name());
+                }
+
+                (SymbolKind::Const, Vec::new())
+            }
+
+            Definition::Newtype(d) => {
+                self.type_name(d.target_type());
+                (SymbolKind::Newtype, Vec::new())
+            }
+        };
+
+        Symbol {
+            kind,
+            name: def.name().value().to_owned(),
+            span: self.span(def.span()),
+            name_span: self.span(def.name().span()),
+            children,
+        }
+    }
+
+    fn fields(&mut self, fields: &[StructField], fallback: Option<&StructFallback>) -> Vec<Symbol> {
+        let mut symbols = fields
+            .iter()
+            .map(|field| {
+                self.type_name(field.field_type());
+
+                Symbol {
+                    kind: SymbolKind::Field,
+                    name: field.name().value().to_owned(),
+                    span: self.span(field.span()),
+                    name_span: self.span(field.name().span()),
+                    children: Vec::new(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(fallback) = fallback {
+            symbols.push(Symbol {
+                kind: SymbolKind::Field,
+                name: fallback.name().value().to_owned(),
+                span: self.span(fallback.span()),
+                name_span: self.span(fallback.name().span()),
+                children: Vec::new(),
+            });
+        }
+
+        symbols
+    }
+
+    fn variants(
+        &mut self,
+        variants: &[EnumVariant],
+        fallback: Option<&EnumFallback>,
+    ) -> Vec<Symbol> {
+        let mut symbols = variants
+            .iter()
+            .map(|var| {
+                if let Some(var_type) = var.variant_type() {
+                    self.type_name(var_type);
+                }
+
+                Symbol {
+                    kind: SymbolKind::Variant,
+                    name: var.name().value().to_owned(),
+                    span: self.span(var.span()),
+                    name_span: self.span(var.name().span()),
+                    children: Vec::new(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(fallback) = fallback {
+            symbols.push(Symbol {
+                kind: SymbolKind::Variant,
+                name: fallback.name().value().to_owned(),
+                span: self.span(fallback.span()),
+                name_span: self.span(fallback.name().span()),
+                children: Vec::new(),
+            });
+        }
+
+        symbols
+    }
+
+    fn service_item(&mut self, item: &ServiceItem) -> Symbol {
+        let (kind, children) = match item {
+            ServiceItem::Function(func) => {
+                let children = [func.args(), func.ok(), func.stream(), func.err()]
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|part| self.type_name_or_inline(part.part_type()))
+                    .collect();
+
+                (SymbolKind::Function, children)
+            }
+
+            ServiceItem::Event(ev) => {
+                let children = match ev.event_type() {
+                    Some(ty) => self.type_name_or_inline(ty),
+                    None => Vec::new(),
+                };
+
+                (SymbolKind::Event, children)
+            }
+
+            ServiceItem::Property(prop) => {
+                self.type_name(prop.property_type());
+                (SymbolKind::Property, Vec::new())
+            }
+        };
+
+        Symbol {
+            kind,
+            name: item.name().value().to_owned(),
+            span: self.span(item.span()),
+            name_span: self.span(item.name().span()),
+            children,
+        }
+    }
+
+    fn type_name_or_inline(&mut self, ty: &TypeNameOrInline) -> Vec<Symbol> {
+        match ty {
+            TypeNameOrInline::TypeName(ty) => {
+                self.type_name(ty);
+                Vec::new()
+            }
+
+            TypeNameOrInline::Struct(s) => self.fields(s.fields(), s.fallback()),
+            TypeNameOrInline::Enum(e) => self.variants(e.variants(), e.fallback()),
+        }
+    }
+
+    fn type_name(&mut self, ty: &TypeName) {
+        match ty.kind() {
+            TypeNameKind::Option(ty)
+            | TypeNameKind::Box(ty)
+            | TypeNameKind::Vec(ty)
+            | TypeNameKind::Sender(ty)
+            | TypeNameKind::Receiver(ty)
+            | TypeNameKind::Broadcast(ty) => self.type_name(ty),
+
+            TypeNameKind::Map(key, ty) => {
+                self.key_type_name(key);
+                self.type_name(ty);
+            }
+
+            TypeNameKind::Set(key) => self.key_type_name(key),
+
+            TypeNameKind::Result(ok, err) => {
+                self.type_name(ok);
+                self.type_name(err);
+            }
+
+            TypeNameKind::Array(ty, len) => {
+                self.type_name(ty);
+
+                if let ArrayLenValue::Ref(named_ref) = len.value() {
+                    self.named_ref(named_ref);
+                }
+            }
+
+            TypeNameKind::Ref(named_ref) => self.named_ref(named_ref),
+
+            TypeNameKind::Bool
+            | TypeNameKind::U8
+            | TypeNameKind::I8
+            | TypeNameKind::U16
+            | TypeNameKind::I16
+            | TypeNameKind::U32
+            | TypeNameKind::I32
+            | TypeNameKind::U64
+            | TypeNameKind::I64
+            | TypeNameKind::F32
+            | TypeNameKind::F64
+            | TypeNameKind::String
+            | TypeNameKind::Uuid
+            | TypeNameKind::ObjectId
+            | TypeNameKind::ServiceId
+            | TypeNameKind::Value
+            | TypeNameKind::Bytes
+            | TypeNameKind::Lifetime
+            | TypeNameKind::Unit => {}
+        }
+    }
+
+    fn key_type_name(&mut self, ty: &KeyTypeName) {
+        if let KeyTypeNameKind::Ref(named_ref) = ty.kind() {
+            self.named_ref(named_ref);
+        }
+    }
+
+    fn named_ref(&mut self, named_ref: &NamedRef) {
+        let reference = Reference {
+            span: self.span(named_ref.span()),
+            schema: named_ref.schema().map(|s| s.value().to_owned()),
+            schema_span: named_ref.schema().map(|s| self.span(s.span())),
+            name: named_ref.ident().value().to_owned(),
+            name_span: self.span(named_ref.ident().span()),
+        };
+
+        self.references.push(reference);
+    }
+
+    fn span(&self, span: Span) -> Span {
+        let range = self.offset + span.from.index..self.offset + span.to.index;
+        to_span(self.source, self.lines, range)
+    }
+}
+
+/// Splits tokens into the byte ranges of top-level items.
+///
+/// Items include their leading doc comments.
+fn split(source: &str, tokens: &[(TokenKind, Range<usize>)]) -> Vec<Range<usize>> {
+    #[derive(PartialEq, Eq)]
+    enum Terminator {
+        Brace,
+        Semicolon,
+    }
+
+    struct Current {
+        range: Range<usize>,
+        depth: usize,
+        terminator: Option<Terminator>,
+    }
+
+    let mut items = Vec::new();
+    let mut current: Option<Current> = None;
+    let mut docs = None;
+
+    for (kind, range) in tokens {
+        match kind {
+            TokenKind::Comment => {
+                docs = None;
+                continue;
+            }
+
+            TokenKind::DocComment => {
+                if current.is_none() && docs.is_none() {
+                    docs = Some(range.start);
+                }
+
+                continue;
+            }
+
+            _ => {}
+        }
+
+        let text = &source[range.clone()];
+
+        let starts_item = matches!(
+            text,
+            "import" | "struct" | "enum" | "service" | "const" | "newtype" | "#"
+        ) && ((range.start == 0)
+            || (source.as_bytes()[range.start - 1] == b'\n'));
+
+        if let Some(ref cur) = current {
+            if starts_item && (cur.terminator.is_some() || (cur.depth > 0)) {
+                items.push(cur.range.clone());
+                current = None;
+            }
+        }
+
+        let cur = current.get_or_insert_with(|| Current {
+            range: docs.take().unwrap_or(range.start)..range.end,
+            depth: 0,
+            terminator: None,
+        });
+
+        cur.range.end = range.end;
+
+        if (*kind == TokenKind::Keyword) && (cur.depth == 0) && cur.terminator.is_none() {
+            cur.terminator = match text {
+                "struct" | "enum" | "service" => Some(Terminator::Brace),
+                _ => Some(Terminator::Semicolon),
+            };
+        }
+
+        let ends_item = match text {
+            "{" | "(" | "[" => {
+                cur.depth += 1;
+                false
+            }
+
+            "}" | ")" | "]" => {
+                cur.depth = cur.depth.saturating_sub(1);
+                (text == "}") && (cur.depth == 0) && (cur.terminator != Some(Terminator::Semicolon))
+            }
+
+            ";" => (cur.depth == 0) && (cur.terminator != Some(Terminator::Brace)),
+            _ => false,
+        };
+
+        if ends_item {
+            items.push(cur.range.clone());
+            current = None;
+        }
+    }
+
+    if let Some(cur) = current {
+        items.push(cur.range);
+    }
+
+    items
+}
+
+fn to_position(source: &str, lines: &[usize], index: usize) -> Position {
+    let mut index = index.min(source.len());
+    while !source.is_char_boundary(index) {
+        index -= 1;
+    }
+
+    let line = lines.partition_point(|&start| start <= index);
+    let start = lines[line - 1];
+    let column = source[start..index].chars().count() + 1;
+
+    Position {
+        index,
+        line_col: LineCol { line, column },
+    }
+}
+
+fn to_span(source: &str, lines: &[usize], range: Range<usize>) -> Span {
+    Span {
+        from: to_position(source, lines, range.start),
+        to: to_position(source, lines, range.end),
+    }
+}
+
+fn contains(span: Span, index: usize) -> bool {
+    (span.from.index <= index) && (index <= span.to.index)
+}
@@ -0,0 +1,193 @@
+use super::TokenKind;
+use std::ops::Range;
+
+const KEYWORDS: &[&str] = &[
+    "args",
+    "bool",
+    "box",
+    "broadcast",
+    "bytes",
+    "const",
+    "enum",
+    "err",
+    "event",
+    "f32",
+    "f64",
+    "fallback",
+    "fn",
+    "i16",
+    "i32",
+    "i64",
+    "i8",
+    "import",
+    "introduced_in",
+    "lifetime",
+    "map",
+    "newtype",
+    "object_id",
+    "ok",
+    "option",
+    "property",
+    "receiver",
+    "reliable",
+    "required",
+    "result",
+    "retained",
+    "sender",
+    "service",
+    "service_id",
+    "set",
+    "stream",
+    "string",
+    "struct",
+    "u16",
+    "u32",
+    "u64",
+    "u8",
+    "unit",
+    "uuid",
+    "value",
+    "vec",
+    "version",
+];
+
+/// Splits `source` into tokens.
+///
+/// Lexing never fails. Characters, that cannot start any token, are returned as
+/// [`TokenKind::Unknown`].
+pub(super) fn lex(source: &str) -> Vec<(TokenKind, Range<usize>)> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while let Some(c) = source[pos..].chars().next() {
+        let start = pos;
+
+        let kind = if c.is_whitespace() {
+            pos += c.len_utf8();
+            continue;
+        } else if source[pos..].starts_with("//") {
+            pos += source[pos..].find('\n').unwrap_or(source.len() - pos);
+            let comment = &source[start..pos];
+
+            if comment.starts_with("///") && !comment.starts_with("////") {
+                TokenKind::DocComment
+            } else {
+                TokenKind::Comment
+            }
+        } else if c == '"' {
+            lex_string(source, &mut pos)
+        } else if let Some(len) = uuid_len(&bytes[pos..]) {
+            pos += len;
+            TokenKind::LitUuid
+        } else if c.is_ascii_alphabetic() {
+            pos += ident_len(&bytes[pos..]);
+
+            if KEYWORDS.contains(&&source[start..pos]) {
+                TokenKind::Keyword
+            } else {
+                TokenKind::Ident
+            }
+        } else if c.is_ascii_digit()
+            || ((c == '-') && bytes.get(pos + 1).is_some_and(u8::is_ascii_digit))
+        {
+            lex_number(bytes, &mut pos)
+        } else if source[pos..].starts_with("->") || source[pos..].starts_with("::") {
+            pos += 2;
+            TokenKind::Punct
+        } else if ";=()<>:#[],{}@".contains(c) {
+            pos += 1;
+            TokenKind::Punct
+        } else {
+            pos += c.len_utf8();
+            TokenKind::Unknown
+        };
+
+        tokens.push((kind, start..pos));
+    }
+
+    tokens
+}
+
+fn lex_string(source: &str, pos: &mut usize) -> TokenKind {
+    let mut chars = source[*pos + 1..].char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                *pos += i + 2;
+                return TokenKind::LitString;
+            }
+
+            '\n' => break,
+
+            '\\' if chars.clone().next().is_some_and(|(_, c)| c == '"') => {
+                chars.next();
+            }
+
+            _ => {}
+        }
+    }
+
+    // Unterminated strings extend to the end of the line.
+    *pos += source[*pos..].find('\n').unwrap_or(source.len() - *pos);
+    TokenKind::Unknown
+}
+
+fn lex_number(bytes: &[u8], pos: &mut usize) -> TokenKind {
+    if bytes[*pos] == b'-' {
+        *pos += 1;
+    }
+
+    *pos += digits_len(&bytes[*pos..]);
+
+    if (bytes.get(*pos) == Some(&b'.')) && bytes.get(*pos + 1).is_some_and(u8::is_ascii_digit) {
+        *pos += 1;
+        *pos += digits_len(&bytes[*pos..]);
+        TokenKind::LitFloat
+    } else {
+        TokenKind::LitInt
+    }
+}
+
+fn uuid_len(bytes: &[u8]) -> Option<usize> {
+    const GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+
+    let mut len = 0;
+    for (i, &group) in GROUPS.iter().enumerate() {
+        if i > 0 {
+            if bytes.get(len) != Some(&b'-') {
+                return None;
+            }
+
+            len += 1;
+        }
+
+        let hex = bytes[len..]
+            .iter()
+            .take_while(|b| b.is_ascii_hexdigit())
+            .count();
+        if hex != group {
+            return None;
+        }
+
+        len += group;
+    }
+
+    if ident_len(&bytes[len..]) > 0 {
+        None
+    } else {
+        Some(len)
+    }
+}
+
+fn ident_len(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .take_while(|&&b| b.is_ascii_alphanumeric() || (b == b'_'))
+        .count()
+}
+
+fn digits_len(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+}
@@ -0,0 +1,252 @@
+use super::{Document, SymbolKind, TokenKind};
+use crate::error::Expected;
+use crate::LineCol;
+use std::sync::Arc;
+
+const SOURCE: &str = r#"import other;
+
+/// A struct.
+#[rust(impl_copy)]
+struct Foo {
+    bar @1 = vec<Bar>;
+    baz @2 = other::Baz;
+}
+
+// Comment.
+enum Bar {
+    A @0;
+    B @1 = map<string -> Foo>;
+}
+
+const LEN = u32(4);
+
+newtype Arr = [Foo; LEN];
+
+service Svc {
+    uuid = 6d8ae1b6-2f5b-4ba1-8a6e-2b49ac6e5c6e;
+    version = 1;
+
+    fn get @1 {
+        args = struct {
+            key @1 = Bar;
+        }
+
+        ok = Foo;
+    }
+
+    event changed @1 = Foo;
+}
+"#;
+
+fn index_of(doc: &Document, needle: &str, nth: usize) -> usize {
+    doc.source().match_indices(needle).nth(nth).unwrap().0
+}
+
+#[test]
+fn symbols() {
+    let doc = Document::new(SOURCE);
+    assert!(doc.errors().is_empty());
+
+    let symbols = doc
+        .symbols()
+        .iter()
+        .map(|s| (s.kind, s.name.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        symbols,
+        [
+            (SymbolKind::Import, "other"),
+            (SymbolKind::Struct, "Foo"),
+            (SymbolKind::Enum, "Bar"),
+            (SymbolKind::Const, "LEN"),
+            (SymbolKind::Newtype, "Arr"),
+            (SymbolKind::Service, "Svc"),
+        ]
+    );
+
+    let foo = &doc.symbols()[1];
+    assert_eq!(foo.span.from.line_col, LineCol { line: 4, column: 1 });
+    assert_eq!(foo.name_span.from.line_col, LineCol { line: 5, column: 8 });
+    assert_eq!(foo.children.len(), 2);
+    assert_eq!(foo.children[1].kind, SymbolKind::Field);
+    assert_eq!(foo.children[1].name, "baz");
+
+    let svc = &doc.symbols()[5];
+    assert_eq!(svc.children.len(), 2);
+    assert_eq!(svc.children[0].kind, SymbolKind::Function);
+    assert_eq!(svc.children[0].children[0].name, "key");
+    assert_eq!(svc.children[1].kind, SymbolKind::Event);
+
+    let references = doc
+        .references()
+        .iter()
+        .map(|r| (r.schema.as_deref(), r.name.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        references,
+        [
+            (None, "Bar"),
+            (Some("other"), "Baz"),
+            (None, "Foo"),
+            (None, "Foo"),
+            (None, "LEN"),
+            (None, "Bar"),
+            (None, "Foo"),
+            (None, "Foo"),
+        ]
+    );
+}
+
+#[test]
+fn tokens() {
+    let doc = Document::new("/// Doc.\nconst X = i8(-1); // Comment.\n$ \"unterminated\n");
+
+    let tokens = doc
+        .tokens()
+        .iter()
+        .map(|t| (t.kind, &doc.source()[t.span.from.index..t.span.to.index]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tokens,
+        [
+            (TokenKind::DocComment, "/// Doc."),
+            (TokenKind::Keyword, "const"),
+            (TokenKind::Ident, "X"),
+            (TokenKind::Punct, "="),
+            (TokenKind::Keyword, "i8"),
+            (TokenKind::Punct, "("),
+            (TokenKind::LitInt, "-1"),
+            (TokenKind::Punct, ")"),
+            (TokenKind::Punct, ";"),
+            (TokenKind::Comment, "// Comment."),
+            (TokenKind::Unknown, "$"),
+            (TokenKind::Unknown, "\"unterminated"),
+        ]
+    );
+
+    assert_eq!(
+        doc.tokens()[10].span.from.line_col,
+        LineCol { line: 3, column: 1 }
+    );
+}
+
+#[test]
+fn error_recovery() {
+    let doc = Document::new(
+        "struct Foo {\n    bar @1 = ;\n}\n\nstruct Bar {\n    foo @1 = Foo;\n\nenum Baz {\n    A @0;\n}\n",
+    );
+
+    let symbols = doc
+        .symbols()
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(symbols, ["Baz"]);
+
+    assert_eq!(doc.errors().len(), 2);
+
+    let err = &doc.errors()[0];
+    assert_eq!(
+        err.position.line_col,
+        LineCol {
+            line: 2,
+            column: 14
+        }
+    );
+    assert!(err.expected.contains(&Expected::Ident));
+    assert!(err.to_string().starts_with("expected "));
+    assert_eq!(err.item_span.to.line_col, LineCol { line: 3, column: 2 });
+
+    let err = &doc.errors()[1];
+    assert_eq!(err.item_span.from.line_col, LineCol { line: 5, column: 1 });
+    assert_eq!(
+        err.item_span.to.line_col,
+        LineCol {
+            line: 6,
+            column: 18
+        }
+    );
+    assert!(err.expected.contains(&Expected::Token("}")));
+}
+
+#[test]
+fn definition_and_references() {
+    let doc = Document::new(SOURCE);
+
+    let def = doc.definition_at(index_of(&doc, "Bar", 0) + 1).unwrap();
+    assert_eq!(def.kind, SymbolKind::Enum);
+    assert_eq!(def.name, "Bar");
+
+    let def = doc.definition_at(index_of(&doc, "LEN", 1)).unwrap();
+    assert_eq!(def.kind, SymbolKind::Const);
+
+    let def = doc.definition_at(index_of(&doc, "other", 1)).unwrap();
+    assert_eq!(def.kind, SymbolKind::Import);
+    assert!(doc.definition_at(index_of(&doc, "Baz", 0)).is_none());
+
+    let def = doc.definition_at(index_of(&doc, "key", 0)).unwrap();
+    assert_eq!(def.kind, SymbolKind::Field);
+
+    let refs = doc.references_at(index_of(&doc, "Foo", 0));
+    assert_eq!(refs.len(), 4);
+    assert!(refs.iter().all(|r| r.name == "Foo"));
+    assert_eq!(
+        refs[0].span.from.line_col,
+        LineCol {
+            line: 13,
+            column: 26
+        }
+    );
+
+    let refs = doc.references_at(index_of(&doc, "other", 0));
+    assert_eq!(refs.len(), 1);
+    assert_eq!(refs[0].name, "Baz");
+
+    assert!(doc.references_at(index_of(&doc, "key", 0)).is_empty());
+}
+
+#[test]
+fn incremental() {
+    let mut doc = Document::new(SOURCE);
+    let items = doc.items.clone();
+
+    let pos = index_of(&doc, "A @0", 0);
+    doc.edit(pos..pos + 1, "C");
+
+    assert_eq!(doc.items.len(), items.len());
+    for (i, (old, new)) in items.iter().zip(&doc.items).enumerate() {
+        assert_eq!(Arc::ptr_eq(&old.parsed, &new.parsed), i != 2);
+    }
+
+    assert_eq!(doc.symbols()[2].children[0].name, "C");
+
+    let services = doc.symbols()[5].span;
+    assert_eq!(
+        services.from.line_col,
+        LineCol {
+            line: 20,
+            column: 1
+        }
+    );
+
+    doc.edit(0..0, "\n\n");
+    assert_eq!(doc.symbols()[5].span.from.line_col.line, 22);
+    for (old, new) in items.iter().zip(&doc.items).skip(3) {
+        assert!(Arc::ptr_eq(&old.parsed, &new.parsed));
+    }
+}
+
+#[test]
+fn positions() {
+    let doc = Document::new("a\näb\n\nc");
+
+    assert_eq!(doc.position(4).line_col, LineCol { line: 2, column: 2 });
+    assert_eq!(doc.position(6).line_col, LineCol { line: 3, column: 1 });
+    assert_eq!(doc.position(100).index, doc.source().len());
+
+    assert_eq!(doc.index(LineCol { line: 2, column: 2 }), Some(4));
+    assert_eq!(doc.index(LineCol { line: 2, column: 3 }), Some(5));
+    assert_eq!(doc.index(LineCol { line: 2, column: 4 }), None);
+    assert_eq!(doc.index(LineCol { line: 4, column: 1 }), Some(7));
+    assert_eq!(doc.index(LineCol { line: 5, column: 1 }), None);
+}
@@ -12,16 +12,20 @@ mod empty_enum;
 mod expected_const_int_found_service;
 mod expected_const_int_found_string;
 mod expected_const_int_found_type;
+mod expected_const_int_found_typed_const;
 mod expected_const_int_found_uuid;
 mod expected_ident_found_reserved;
 mod expected_type_found_const;
 mod expected_type_found_service;
 mod import_not_found;
 mod invalid_array_len;
+mod invalid_const_expr;
 mod invalid_const_value;
 mod invalid_enum_variant_id;
 mod invalid_event_id;
 mod invalid_function_id;
+mod invalid_introduced_in;
+mod invalid_key_type;
 mod invalid_schema_name;
 mod invalid_service_uuid;
 mod invalid_service_version;
@@ -49,24 +53,29 @@ pub use empty_enum::EmptyEnum;
 pub use expected_const_int_found_service::ExpectedConstIntFoundService;
 pub use expected_const_int_found_string::ExpectedConstIntFoundString;
 pub use expected_const_int_found_type::ExpectedConstIntFoundType;
+pub use expected_const_int_found_typed_const::ExpectedConstIntFoundTypedConst;
 pub use expected_const_int_found_uuid::ExpectedConstIntFoundUuid;
 pub use expected_ident_found_reserved::ExpectedIdentFoundReserved;
 pub use expected_type_found_const::ExpectedTypeFoundConst;
 pub use expected_type_found_service::ExpectedTypeFoundService;
 pub use import_not_found::ImportNotFound;
 pub use invalid_array_len::InvalidArrayLen;
+pub use invalid_const_expr::{InvalidConstExpr, InvalidConstExprKind};
 pub use invalid_const_value::InvalidConstValue;
 pub use invalid_enum_variant_id::InvalidEnumVariantId;
 pub use invalid_event_id::InvalidEventId;
 pub use invalid_function_id::InvalidFunctionId;
+pub use invalid_introduced_in::InvalidIntroducedIn;
+pub use invalid_key_type::InvalidKeyType;
 pub use invalid_schema_name::InvalidSchemaName;
 pub use invalid_service_uuid::InvalidServiceUuid;
 pub use invalid_service_version::InvalidServiceVersion;
 pub use invalid_struct_field_id::InvalidStructFieldId;
+pub(crate) use invalid_syntax::expected_reason;
 pub use invalid_syntax::{Expected, InvalidSyntax};
 pub use io_error::IoError;
 pub use missing_import::MissingImport;
-pub use recursive_type::{RecursiveEnum, RecursiveStruct};
+pub use recursive_type::{RecursiveEnum, RecursiveNewtype, RecursiveStruct};
 pub use type_not_found::TypeNotFound;
 
 #[derive(Debug)]
@@ -86,16 +95,20 @@ pub enum Error {
     ExpectedConstIntFoundService(ExpectedConstIntFoundService),
     ExpectedConstIntFoundString(ExpectedConstIntFoundString),
     ExpectedConstIntFoundType(ExpectedConstIntFoundType),
+    ExpectedConstIntFoundTypedConst(ExpectedConstIntFoundTypedConst),
     ExpectedConstIntFoundUuid(ExpectedConstIntFoundUuid),
     ExpectedIdentFoundReserved(ExpectedIdentFoundReserved),
     ExpectedTypeFoundConst(ExpectedTypeFoundConst),
     ExpectedTypeFoundService(ExpectedTypeFoundService),
     ImportNotFound(ImportNotFound),
     InvalidArrayLen(InvalidArrayLen),
+    InvalidConstExpr(InvalidConstExpr),
     InvalidConstValue(InvalidConstValue),
     InvalidEnumVariantId(InvalidEnumVariantId),
     InvalidEventId(InvalidEventId),
     InvalidFunctionId(InvalidFunctionId),
+    InvalidIntroducedIn(InvalidIntroducedIn),
+    InvalidKeyType(InvalidKeyType),
     InvalidSchemaName(InvalidSchemaName),
     InvalidServiceUuid(InvalidServiceUuid),
     InvalidServiceVersion(InvalidServiceVersion),
@@ -104,6 +117,7 @@ pub enum Error {
     IoError(IoError),
     MissingImport(MissingImport),
     RecursiveEnum(RecursiveEnum),
+    RecursiveNewtype(RecursiveNewtype),
     RecursiveStruct(RecursiveStruct),
     TypeNotFound(TypeNotFound),
 }
@@ -129,16 +143,20 @@ impl Diagnostic for Error {
             Self::ExpectedConstIntFoundService(e) => e.schema_name(),
             Self::ExpectedConstIntFoundString(e) => e.schema_name(),
             Self::ExpectedConstIntFoundType(e) => e.schema_name(),
+            Self::ExpectedConstIntFoundTypedConst(e) => e.schema_name(),
             Self::ExpectedConstIntFoundUuid(e) => e.schema_name(),
             Self::ExpectedIdentFoundReserved(e) => e.schema_name(),
             Self::ExpectedTypeFoundConst(e) => e.schema_name(),
             Self::ExpectedTypeFoundService(e) => e.schema_name(),
             Self::ImportNotFound(e) => e.schema_name(),
             Self::InvalidArrayLen(e) => e.schema_name(),
+            Self::InvalidConstExpr(e) => e.schema_name(),
             Self::InvalidConstValue(e) => e.schema_name(),
             Self::InvalidEnumVariantId(e) => e.schema_name(),
             Self::InvalidEventId(e) => e.schema_name(),
             Self::InvalidFunctionId(e) => e.schema_name(),
+            Self::InvalidIntroducedIn(e) => e.schema_name(),
+            Self::InvalidKeyType(e) => e.schema_name(),
             Self::InvalidSchemaName(e) => e.schema_name(),
             Self::InvalidServiceUuid(e) => e.schema_name(),
             Self::InvalidServiceVersion(e) => e.schema_name(),
@@ -147,6 +165,7 @@ impl Diagnostic for Error {
             Self::IoError(e) => e.schema_name(),
             Self::MissingImport(e) => e.schema_name(),
             Self::RecursiveEnum(e) => e.schema_name(),
+            Self::RecursiveNewtype(e) => e.schema_name(),
             Self::RecursiveStruct(e) => e.schema_name(),
             Self::TypeNotFound(e) => e.schema_name(),
         }
@@ -168,16 +187,20 @@ impl Diagnostic for Error {
             Self::ExpectedConstIntFoundService(e) => e.format(parsed),
             Self::ExpectedConstIntFoundString(e) => e.format(parsed),
             Self::ExpectedConstIntFoundType(e) => e.format(parsed),
+            Self::ExpectedConstIntFoundTypedConst(e) => e.format(parsed),
             Self::ExpectedConstIntFoundUuid(e) => e.format(parsed),
             Self::ExpectedIdentFoundReserved(e) => e.format(parsed),
             Self::ExpectedTypeFoundConst(e) => e.format(parsed),
             Self::ExpectedTypeFoundService(e) => e.format(parsed),
             Self::ImportNotFound(e) => e.format(parsed),
             Self::InvalidArrayLen(e) => e.format(parsed),
+            Self::InvalidConstExpr(e) => e.format(parsed),
             Self::InvalidConstValue(e) => e.format(parsed),
             Self::InvalidEnumVariantId(e) => e.format(parsed),
             Self::InvalidEventId(e) => e.format(parsed),
             Self::InvalidFunctionId(e) => e.format(parsed),
+            Self::InvalidIntroducedIn(e) => e.format(parsed),
+            Self::InvalidKeyType(e) => e.format(parsed),
             Self::InvalidSchemaName(e) => e.format(parsed),
             Self::InvalidServiceUuid(e) => e.format(parsed),
             Self::InvalidServiceVersion(e) => e.format(parsed),
@@ -186,6 +209,7 @@ impl Diagnostic for Error {
             Self::IoError(e) => e.format(parsed),
             Self::MissingImport(e) => e.format(parsed),
             Self::RecursiveEnum(e) => e.format(parsed),
+            Self::RecursiveNewtype(e) => e.format(parsed),
             Self::RecursiveStruct(e) => e.format(parsed),
             Self::TypeNotFound(e) => e.format(parsed),
         }
@@ -1,5 +1,5 @@
 use super::Error;
-use crate::ast::{EnumVariant, Ident};
+use crate::ast::{EnumFallback, EnumVariant, Ident};
 use crate::diag::{Diagnostic, DiagnosticKind, Formatted, Formatter};
 use crate::validate::Validate;
 use crate::{util, Parsed, Span};
@@ -16,6 +16,7 @@ pub struct DuplicateEnumVariant {
 impl DuplicateEnumVariant {
     pub(crate) fn validate(
         vars: &[EnumVariant],
+        fallback: Option<&EnumFallback>,
         enum_span: Span,
         ident: Option<&Ident>,
         validate: &mut Validate,
@@ -33,6 +34,23 @@ impl DuplicateEnumVariant {
                 })
             },
         );
+
+        let Some(fallback) = fallback else {
+            return;
+        };
+
+        if let Some(first) = vars
+            .iter()
+            .find(|var| var.name().value() == fallback.name().value())
+        {
+            validate.add_error(Self {
+                schema_name: validate.schema_name().to_owned(),
+                duplicate: fallback.name().clone(),
+                first: first.name().span(),
+                enum_span,
+                enum_ident: ident.cloned(),
+            });
+        }
     }
 
     pub fn duplicate(&self) -> &Ident {
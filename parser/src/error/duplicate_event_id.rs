@@ -16,27 +16,26 @@ pub struct DuplicateEventId {
 impl DuplicateEventId {
     pub(crate) fn validate(service: &ServiceDef, validate: &mut Validate) {
         let events = service.items().iter().filter_map(|item| match item {
-            ServiceItem::Event(ev) => Some(ev),
+            ServiceItem::Event(item) => Some(item.id()),
+            ServiceItem::Property(prop) => Some(prop.id()),
             _ => None,
         });
 
-        let mut max_id = events
-            .clone()
-            .fold(0, |cur, ev| match ev.id().value().parse() {
-                Ok(id) if id > cur => id,
-                _ => cur,
-            });
+        let mut max_id = events.clone().fold(0, |cur, id| match id.value().parse() {
+            Ok(id) if id > cur => id,
+            _ => cur,
+        });
 
         util::find_duplicates(
             events,
-            |ev| ev.id().value(),
+            |id| id.value(),
             |duplicate, first| {
                 max_id += 1;
                 let free_id = max_id;
                 validate.add_error(Self {
                     schema_name: validate.schema_name().to_owned(),
-                    duplicate: duplicate.id().clone(),
-                    first: first.id().span(),
+                    duplicate: duplicate.clone(),
+                    first: first.span(),
                     service_ident: service.name().clone(),
                     free_id,
                 })
@@ -1,5 +1,5 @@
 use super::Error;
-use crate::ast::{Ident, StructField};
+use crate::ast::{Ident, StructFallback, StructField};
 use crate::diag::{Diagnostic, DiagnosticKind, Formatted, Formatter};
 use crate::validate::Validate;
 use crate::{util, Parsed, Span};
@@ -16,6 +16,7 @@ pub struct DuplicateStructField {
 impl DuplicateStructField {
     pub(crate) fn validate(
         fields: &[StructField],
+        fallback: Option<&StructFallback>,
         struct_span: Span,
         ident: Option<&Ident>,
         validate: &mut Validate,
@@ -33,6 +34,23 @@ impl DuplicateStructField {
                 })
             },
         );
+
+        let Some(fallback) = fallback else {
+            return;
+        };
+
+        if let Some(first) = fields
+            .iter()
+            .find(|field| field.name().value() == fallback.name().value())
+        {
+            validate.add_error(Self {
+                schema_name: validate.schema_name().to_owned(),
+                duplicate: fallback.name().clone(),
+                first: first.name().span(),
+                struct_span,
+                struct_ident: ident.cloned(),
+            });
+        }
     }
 
     pub fn duplicate(&self) -> &Ident {
@@ -7,6 +7,7 @@ use crate::Parsed;
 const RESERVED: &[&str] = &[
     "bool",
     "box",
+    "broadcast",
     "bytes",
     "const",
     "enum",
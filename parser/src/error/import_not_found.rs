@@ -22,14 +22,9 @@ impl ImportNotFound {
         }
 
         let tried = validate
-            .schema_paths()
+            .resolvers()
             .iter()
-            .map(|p| {
-                let mut tried = p.clone();
-                tried.push(import_stmt.schema_name().value());
-                tried.set_extension("aldrin");
-                tried
-            })
+            .filter_map(|r| r.candidate(import_stmt.schema_name().value()))
             .collect();
 
         validate.add_error(Self {
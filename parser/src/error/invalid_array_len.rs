@@ -1,5 +1,5 @@
 use super::Error;
-use crate::ast::{ArrayLen, ArrayLenValue, ConstValue, Ident, NamedRefKind};
+use crate::ast::{ArrayLen, ArrayLenValue, Ident, NamedRefKind};
 use crate::diag::{Diagnostic, DiagnosticKind, Formatted, Formatter};
 use crate::validate::Validate;
 use crate::Parsed;
@@ -44,20 +44,11 @@ impl InvalidArrayLen {
                         return;
                     }
 
-                    match const_def.value() {
-                        ConstValue::U8(lit)
-                        | ConstValue::I8(lit)
-                        | ConstValue::U16(lit)
-                        | ConstValue::I16(lit)
-                        | ConstValue::U32(lit)
-                        | ConstValue::I32(lit)
-                        | ConstValue::U64(lit)
-                        | ConstValue::I64(lit) => {
-                            res = Some((lit.value(), Some((schema.name(), const_def.name()))))
-                        }
-
-                        ConstValue::String(_) | ConstValue::Uuid(_) => return,
-                    }
+                    let Some(lit) = const_def.value().as_int() else {
+                        return;
+                    };
+
+                    res = Some((lit.value(), Some((schema.name(), const_def.name()))));
                 }
 
                 if let Some(res) = res {
@@ -0,0 +1,513 @@
+use super::Error;
+use crate::ast::{
+    ArrayLenValue, ConstExpr, ConstExprKind, Definition, KeyTypeName, KeyTypeNameKind, NamedRef,
+    NamedRefKind, TypeName, TypeNameKind,
+};
+use crate::diag::{Diagnostic, DiagnosticKind, Formatted, Formatter};
+use crate::validate::Validate;
+use crate::{Parsed, Schema, Span};
+use std::collections::HashSet;
+
+#[derive(Debug)]
+pub struct InvalidConstExpr {
+    schema_name: String,
+    span: Span,
+    kind: InvalidConstExprKind,
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum InvalidConstExprKind {
+    TypeMismatch(String),
+
+    IntOutOfRange(&'static str),
+
+    UnsupportedType(String),
+
+    UnknownField(String),
+
+    DuplicateField(String),
+
+    MissingField(String),
+
+    UnknownVariant(String),
+
+    ArrayLenMismatch(u32),
+}
+
+impl InvalidConstExpr {
+    pub(crate) fn validate(ty: &TypeName, expr: &ConstExpr, validate: &mut Validate) {
+        let schema = validate.get_current_schema();
+        Check {
+            validate,
+            newtype_depth: 0,
+        }
+        .check(schema, ty, expr);
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn kind(&self) -> &InvalidConstExprKind {
+        &self.kind
+    }
+}
+
+impl Diagnostic for InvalidConstExpr {
+    fn kind(&self) -> DiagnosticKind {
+        DiagnosticKind::Error
+    }
+
+    fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    fn format<'a>(&'a self, parsed: &'a Parsed) -> Formatted<'a> {
+        let (summary, help) = match self.kind {
+            InvalidConstExprKind::TypeMismatch(ref ty) => {
+                (format!("expected a value of type `{ty}`"), None)
+            }
+
+            InvalidConstExprKind::IntOutOfRange(ty) => {
+                let (min, max) = int_range(ty);
+                (
+                    format!("integer literal out of range for `{ty}`"),
+                    Some(format!(
+                        "{ty} values must be in the range from {min} to {max}"
+                    )),
+                )
+            }
+
+            InvalidConstExprKind::UnsupportedType(ref ty) => {
+                (format!("type `{ty}` cannot be used in constants"), None)
+            }
+
+            InvalidConstExprKind::UnknownField(ref name) => {
+                (format!("unknown struct field `{name}`"), None)
+            }
+
+            InvalidConstExprKind::DuplicateField(ref name) => (
+                format!("struct field `{name}` specified more than once"),
+                None,
+            ),
+
+            InvalidConstExprKind::MissingField(ref name) => {
+                (format!("missing required struct field `{name}`"), None)
+            }
+
+            InvalidConstExprKind::UnknownVariant(ref name) => {
+                (format!("unknown enum variant `{name}`"), None)
+            }
+
+            InvalidConstExprKind::ArrayLenMismatch(len) => {
+                (format!("expected an array of {len} elements"), None)
+            }
+        };
+
+        let mut fmt = Formatter::new(self, summary);
+
+        if let Some(schema) = parsed.get_schema(&self.schema_name) {
+            let text = match self.kind {
+                InvalidConstExprKind::UnsupportedType(_) => "type used here",
+                _ => "invalid value",
+            };
+
+            fmt.main_block(schema, self.span.from, self.span, text);
+        }
+
+        if let Some(help) = help {
+            fmt.help(help);
+        }
+
+        fmt.format()
+    }
+}
+
+impl From<InvalidConstExpr> for Error {
+    fn from(e: InvalidConstExpr) -> Self {
+        Self::InvalidConstExpr(e)
+    }
+}
+
+// Upper bound on how many nested newtypes are followed. Recursive newtypes are reported
+// separately.
+const MAX_NEWTYPE_DEPTH: usize = 32;
+
+struct Check<'a, 'b> {
+    validate: &'b mut Validate<'a>,
+    newtype_depth: usize,
+}
+
+impl<'a> Check<'a, '_> {
+    fn error(&mut self, span: Span, kind: InvalidConstExprKind) {
+        let schema_name = self.validate.schema_name().to_owned();
+
+        self.validate.add_error(InvalidConstExpr {
+            schema_name,
+            span,
+            kind,
+        });
+    }
+
+    fn mismatch(&mut self, expr: &ConstExpr, ty: &TypeName) {
+        self.error(
+            expr.span(),
+            InvalidConstExprKind::TypeMismatch(type_desc(ty)),
+        );
+    }
+
+    fn check(&mut self, schema: &'a Schema, ty: &TypeName, expr: &ConstExpr) {
+        match (ty.kind(), expr.kind()) {
+            (TypeNameKind::Bool, ConstExprKind::Ident(ident))
+                if (ident.value() == "true") || (ident.value() == "false") => {}
+
+            (TypeNameKind::U8, ConstExprKind::Int(lit)) => self.int::<u8>("u8", lit),
+            (TypeNameKind::I8, ConstExprKind::Int(lit)) => self.int::<i8>("i8", lit),
+            (TypeNameKind::U16, ConstExprKind::Int(lit)) => self.int::<u16>("u16", lit),
+            (TypeNameKind::I16, ConstExprKind::Int(lit)) => self.int::<i16>("i16", lit),
+            (TypeNameKind::U32, ConstExprKind::Int(lit)) => self.int::<u32>("u32", lit),
+            (TypeNameKind::I32, ConstExprKind::Int(lit)) => self.int::<i32>("i32", lit),
+            (TypeNameKind::U64, ConstExprKind::Int(lit)) => self.int::<u64>("u64", lit),
+            (TypeNameKind::I64, ConstExprKind::Int(lit)) => self.int::<i64>("i64", lit),
+
+            (
+                TypeNameKind::F32 | TypeNameKind::F64,
+                ConstExprKind::Int(_) | ConstExprKind::Float(_),
+            ) => {}
+
+            (TypeNameKind::String, ConstExprKind::String(_)) => {}
+            (TypeNameKind::Uuid, ConstExprKind::Uuid(_)) => {}
+
+            (
+                TypeNameKind::ObjectId
+                | TypeNameKind::ServiceId
+                | TypeNameKind::Value
+                | TypeNameKind::Sender(_)
+                | TypeNameKind::Receiver(_)
+                | TypeNameKind::Broadcast(_)
+                | TypeNameKind::Lifetime
+                | TypeNameKind::Unit,
+                _,
+            ) => self.error(
+                ty.span(),
+                InvalidConstExprKind::UnsupportedType(type_desc(ty)),
+            ),
+
+            (TypeNameKind::Option(_), ConstExprKind::Ident(ident)) if ident.value() == "none" => {}
+            (TypeNameKind::Option(ty), _) | (TypeNameKind::Box(ty), _) => {
+                self.check(schema, ty, expr)
+            }
+
+            (TypeNameKind::Bytes, ConstExprKind::List(elems)) => {
+                for elem in elems {
+                    match elem.kind() {
+                        ConstExprKind::Int(lit) => self.int::<u8>("u8", lit),
+                        _ => self.error(
+                            elem.span(),
+                            InvalidConstExprKind::TypeMismatch("u8".to_owned()),
+                        ),
+                    }
+                }
+            }
+
+            (TypeNameKind::Vec(ty), ConstExprKind::List(elems)) => {
+                for elem in elems {
+                    self.check(schema, ty, elem);
+                }
+            }
+
+            (TypeNameKind::Array(elem_ty, len), ConstExprKind::List(elems)) => {
+                for elem in elems {
+                    self.check(schema, elem_ty, elem);
+                }
+
+                if let Some(len) = self.array_len(schema, len.value()) {
+                    if usize::try_from(len).ok() != Some(elems.len()) {
+                        self.error(expr.span(), InvalidConstExprKind::ArrayLenMismatch(len));
+                    }
+                }
+            }
+
+            (TypeNameKind::Set(key), ConstExprKind::List(elems)) => {
+                for elem in elems {
+                    self.check_key(schema, key, elem);
+                }
+            }
+
+            (TypeNameKind::Map(key, ty), ConstExprKind::Map(entries)) => {
+                for entry in entries {
+                    self.check_key(schema, key, entry.key());
+                    self.check(schema, ty, entry.value());
+                }
+            }
+
+            // `{}` is parsed as an empty struct literal.
+            (TypeNameKind::Map(_, _), ConstExprKind::Struct(fields)) if fields.is_empty() => {}
+
+            (TypeNameKind::Result(ok, err), ConstExprKind::Variant(name, value)) => {
+                match name.value() {
+                    "ok" => self.check(schema, ok, value),
+                    "err" => self.check(schema, err, value),
+                    _ => self.mismatch(expr, ty),
+                }
+            }
+
+            (TypeNameKind::Ref(named_ref), _) => self.check_ref(schema, ty, named_ref, expr),
+
+            _ => self.mismatch(expr, ty),
+        }
+    }
+
+    fn check_ref(
+        &mut self,
+        schema: &'a Schema,
+        ty: &TypeName,
+        named_ref: &NamedRef,
+        expr: &ConstExpr,
+    ) {
+        // Unresolvable types are reported elsewhere.
+        let Some((schema, def)) = self.resolve(schema, named_ref) else {
+            return;
+        };
+
+        match (def, expr.kind()) {
+            (Definition::Struct(struct_def), ConstExprKind::Struct(fields)) => {
+                let mut seen = HashSet::new();
+
+                for field in fields {
+                    let name = field.name();
+
+                    let Some(def_field) = struct_def
+                        .fields()
+                        .iter()
+                        .find(|f| f.name().value() == name.value())
+                    else {
+                        self.error(
+                            name.span(),
+                            InvalidConstExprKind::UnknownField(name.value().to_owned()),
+                        );
+                        continue;
+                    };
+
+                    if !seen.insert(name.value()) {
+                        self.error(
+                            name.span(),
+                            InvalidConstExprKind::DuplicateField(name.value().to_owned()),
+                        );
+                        continue;
+                    }
+
+                    self.check(schema, def_field.field_type(), field.value());
+                }
+
+                for def_field in struct_def.fields() {
+                    if def_field.required() && !seen.contains(def_field.name().value()) {
+                        self.error(
+                            expr.span(),
+                            InvalidConstExprKind::MissingField(def_field.name().value().to_owned()),
+                        );
+                    }
+                }
+            }
+
+            (Definition::Enum(enum_def), ConstExprKind::Ident(name)) => {
+                match enum_def
+                    .variants()
+                    .iter()
+                    .find(|v| v.name().value() == name.value())
+                {
+                    Some(var) if var.variant_type().is_none() => {}
+                    Some(_) => self.mismatch(expr, ty),
+
+                    None => self.error(
+                        name.span(),
+                        InvalidConstExprKind::UnknownVariant(name.value().to_owned()),
+                    ),
+                }
+            }
+
+            (Definition::Enum(enum_def), ConstExprKind::Variant(name, value)) => {
+                match enum_def
+                    .variants()
+                    .iter()
+                    .find(|v| v.name().value() == name.value())
+                {
+                    Some(var) => match var.variant_type() {
+                        Some(var_ty) => self.check(schema, var_ty, value),
+                        None => self.mismatch(expr, ty),
+                    },
+
+                    None => self.error(
+                        name.span(),
+                        InvalidConstExprKind::UnknownVariant(name.value().to_owned()),
+                    ),
+                }
+            }
+
+            (Definition::Struct(_) | Definition::Enum(_), _) => self.mismatch(expr, ty),
+
+            (Definition::Newtype(newtype_def), _) => {
+                self.check_newtype(schema, newtype_def.target_type(), expr)
+            }
+
+            // Refs to services and constants are reported elsewhere.
+            (Definition::Service(_) | Definition::Const(_), _) => {}
+        }
+    }
+
+    fn check_newtype(&mut self, schema: &'a Schema, target_type: &TypeName, expr: &ConstExpr) {
+        if self.newtype_depth < MAX_NEWTYPE_DEPTH {
+            self.newtype_depth += 1;
+            self.check(schema, target_type, expr);
+            self.newtype_depth -= 1;
+        }
+    }
+
+    fn check_key(&mut self, schema: &'a Schema, key: &KeyTypeName, expr: &ConstExpr) {
+        match (key.kind(), expr.kind()) {
+            (KeyTypeNameKind::U8, ConstExprKind::Int(lit)) => self.int::<u8>("u8", lit),
+            (KeyTypeNameKind::I8, ConstExprKind::Int(lit)) => self.int::<i8>("i8", lit),
+            (KeyTypeNameKind::U16, ConstExprKind::Int(lit)) => self.int::<u16>("u16", lit),
+            (KeyTypeNameKind::I16, ConstExprKind::Int(lit)) => self.int::<i16>("i16", lit),
+            (KeyTypeNameKind::U32, ConstExprKind::Int(lit)) => self.int::<u32>("u32", lit),
+            (KeyTypeNameKind::I32, ConstExprKind::Int(lit)) => self.int::<i32>("i32", lit),
+            (KeyTypeNameKind::U64, ConstExprKind::Int(lit)) => self.int::<u64>("u64", lit),
+            (KeyTypeNameKind::I64, ConstExprKind::Int(lit)) => self.int::<i64>("i64", lit),
+            (KeyTypeNameKind::String, ConstExprKind::String(_)) => {}
+            (KeyTypeNameKind::Uuid, ConstExprKind::Uuid(_)) => {}
+
+            (KeyTypeNameKind::Ref(named_ref), _) => {
+                // Unresolvable types and invalid key types are reported elsewhere.
+                if let Some((schema, Definition::Newtype(newtype_def))) =
+                    self.resolve(schema, named_ref)
+                {
+                    self.check_newtype(schema, newtype_def.target_type(), expr);
+                }
+            }
+
+            _ => self.error(
+                expr.span(),
+                InvalidConstExprKind::TypeMismatch(key_type_desc(key)),
+            ),
+        }
+    }
+
+    fn int<T: std::str::FromStr>(&mut self, ty: &'static str, lit: &crate::ast::LitInt) {
+        if lit.value().parse::<T>().is_err() {
+            self.error(lit.span(), InvalidConstExprKind::IntOutOfRange(ty));
+        }
+    }
+
+    fn resolve(
+        &self,
+        schema: &'a Schema,
+        named_ref: &NamedRef,
+    ) -> Option<(&'a Schema, &'a Definition)> {
+        let (schema, ident) = match named_ref.kind() {
+            NamedRefKind::Intern(ident) => (schema, ident),
+
+            NamedRefKind::Extern(schema, ident) => {
+                (self.validate.get_schema(schema.value())?, ident)
+            }
+        };
+
+        let def = schema
+            .definitions()
+            .iter()
+            .find(|def| def.name().value() == ident.value())?;
+
+        Some((schema, def))
+    }
+
+    fn array_len(&self, schema: &'a Schema, len: &ArrayLenValue) -> Option<u32> {
+        match len {
+            ArrayLenValue::Literal(lit) => lit.value().parse().ok(),
+
+            ArrayLenValue::Ref(named_ref) => {
+                let (_, def) = self.resolve(schema, named_ref)?;
+
+                def.as_const()?.value().as_int()?.value().parse().ok()
+            }
+        }
+    }
+}
+
+fn int_range(ty: &str) -> (i64, u64) {
+    match ty {
+        "u8" => (u8::MIN as i64, u8::MAX as u64),
+        "i8" => (i8::MIN as i64, i8::MAX as u64),
+        "u16" => (u16::MIN as i64, u16::MAX as u64),
+        "i16" => (i16::MIN as i64, i16::MAX as u64),
+        "u32" => (u32::MIN as i64, u32::MAX as u64),
+        "i32" => (i32::MIN as i64, i32::MAX as u64),
+        "u64" => (u64::MIN as i64, u64::MAX),
+        "i64" => (i64::MIN, i64::MAX as u64),
+        _ => unreachable!(),
+    }
+}
+
+fn type_desc(ty: &TypeName) -> String {
+    match ty.kind() {
+        TypeNameKind::Bool => "bool".to_owned(),
+        TypeNameKind::U8 => "u8".to_owned(),
+        TypeNameKind::I8 => "i8".to_owned(),
+        TypeNameKind::U16 => "u16".to_owned(),
+        TypeNameKind::I16 => "i16".to_owned(),
+        TypeNameKind::U32 => "u32".to_owned(),
+        TypeNameKind::I32 => "i32".to_owned(),
+        TypeNameKind::U64 => "u64".to_owned(),
+        TypeNameKind::I64 => "i64".to_owned(),
+        TypeNameKind::F32 => "f32".to_owned(),
+        TypeNameKind::F64 => "f64".to_owned(),
+        TypeNameKind::String => "string".to_owned(),
+        TypeNameKind::Uuid => "uuid".to_owned(),
+        TypeNameKind::ObjectId => "object_id".to_owned(),
+        TypeNameKind::ServiceId => "service_id".to_owned(),
+        TypeNameKind::Value => "value".to_owned(),
+        TypeNameKind::Option(ty) => format!("option<{}>", type_desc(ty)),
+        TypeNameKind::Box(ty) => format!("box<{}>", type_desc(ty)),
+        TypeNameKind::Vec(ty) => format!("vec<{}>", type_desc(ty)),
+        TypeNameKind::Bytes => "bytes".to_owned(),
+        TypeNameKind::Map(k, v) => format!("map<{} -> {}>", key_type_desc(k), type_desc(v)),
+        TypeNameKind::Set(ty) => format!("set<{}>", key_type_desc(ty)),
+        TypeNameKind::Sender(ty) => format!("sender<{}>", type_desc(ty)),
+        TypeNameKind::Receiver(ty) => format!("receiver<{}>", type_desc(ty)),
+        TypeNameKind::Broadcast(ty) => format!("broadcast<{}>", type_desc(ty)),
+        TypeNameKind::Lifetime => "lifetime".to_owned(),
+        TypeNameKind::Unit => "unit".to_owned(),
+        TypeNameKind::Result(ok, err) => format!("result<{}, {}>", type_desc(ok), type_desc(err)),
+
+        TypeNameKind::Array(ty, len) => match len.value() {
+            ArrayLenValue::Literal(lit) => format!("[{}; {}]", type_desc(ty), lit.value()),
+            ArrayLenValue::Ref(named_ref) => {
+                format!("[{}; {}]", type_desc(ty), named_ref_desc(named_ref))
+            }
+        },
+
+        TypeNameKind::Ref(named_ref) => named_ref_desc(named_ref),
+    }
+}
+
+fn key_type_desc(ty: &KeyTypeName) -> String {
+    match ty.kind() {
+        KeyTypeNameKind::U8 => "u8".to_owned(),
+        KeyTypeNameKind::I8 => "i8".to_owned(),
+        KeyTypeNameKind::U16 => "u16".to_owned(),
+        KeyTypeNameKind::I16 => "i16".to_owned(),
+        KeyTypeNameKind::U32 => "u32".to_owned(),
+        KeyTypeNameKind::I32 => "i32".to_owned(),
+        KeyTypeNameKind::U64 => "u64".to_owned(),
+        KeyTypeNameKind::I64 => "i64".to_owned(),
+        KeyTypeNameKind::String => "string".to_owned(),
+        KeyTypeNameKind::Uuid => "uuid".to_owned(),
+        KeyTypeNameKind::Ref(named_ref) => named_ref_desc(named_ref),
+    }
+}
+
+fn named_ref_desc(named_ref: &NamedRef) -> String {
+    match named_ref.kind() {
+        NamedRefKind::Intern(ident) => ident.value().to_owned(),
+        NamedRefKind::Extern(schema, ident) => format!("{}::{}", schema.value(), ident.value()),
+    }
+}
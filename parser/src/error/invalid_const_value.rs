@@ -21,7 +21,7 @@ impl InvalidConstValue {
             ConstValue::I32(v) => v.value().parse::<i32>().is_err(),
             ConstValue::U64(v) => v.value().parse::<u64>().is_err(),
             ConstValue::I64(v) => v.value().parse::<i64>().is_err(),
-            ConstValue::String(_) | ConstValue::Uuid(_) => false,
+            ConstValue::String(_) | ConstValue::Uuid(_) | ConstValue::Typed(_) => false,
         };
 
         if is_err {
@@ -56,7 +56,7 @@ impl Diagnostic for InvalidConstValue {
             ConstValue::I32(ref v) => ("i32", v, i32::MIN as i64, i32::MAX as u64),
             ConstValue::U64(ref v) => ("u64", v, u64::MIN as i64, u64::MAX),
             ConstValue::I64(ref v) => ("i64", v, i64::MIN, i64::MAX as u64),
-            ConstValue::String(_) | ConstValue::Uuid(_) => unreachable!(),
+            ConstValue::String(_) | ConstValue::Uuid(_) | ConstValue::Typed(_) => unreachable!(),
         };
 
         let mut fmt = Formatter::new(
@@ -0,0 +1,101 @@
+use super::Error;
+use crate::ast::{Ident, LitPosInt, ServiceDef, ServiceItem};
+use crate::diag::{Diagnostic, DiagnosticKind, Formatted, Formatter};
+use crate::validate::Validate;
+use crate::Parsed;
+
+#[derive(Debug)]
+pub struct InvalidIntroducedIn {
+    schema_name: String,
+    introduced_in: LitPosInt,
+    item_ident: Ident,
+    svc_ver: LitPosInt,
+}
+
+impl InvalidIntroducedIn {
+    pub(crate) fn validate(service_def: &ServiceDef, validate: &mut Validate) {
+        let svc_ver = service_def.version().value().parse::<u32>().ok();
+
+        for item in service_def.items() {
+            let (introduced_in, item_ident) = match item {
+                ServiceItem::Function(func) => (func.introduced_in(), func.name()),
+                ServiceItem::Event(ev) => (ev.introduced_in(), ev.name()),
+                ServiceItem::Property(_) => continue,
+            };
+
+            let Some(introduced_in) = introduced_in else {
+                continue;
+            };
+
+            match (introduced_in.value().parse::<u32>(), svc_ver) {
+                (Ok(introduced_in), Some(svc_ver)) if introduced_in <= svc_ver => continue,
+                (Ok(_), None) => continue,
+                _ => {}
+            }
+
+            validate.add_error(Self {
+                schema_name: validate.schema_name().to_owned(),
+                introduced_in: introduced_in.clone(),
+                item_ident: item_ident.clone(),
+                svc_ver: service_def.version().clone(),
+            });
+        }
+    }
+
+    pub fn introduced_in(&self) -> &LitPosInt {
+        &self.introduced_in
+    }
+
+    pub fn item_ident(&self) -> &Ident {
+        &self.item_ident
+    }
+
+    pub fn service_version(&self) -> &LitPosInt {
+        &self.svc_ver
+    }
+}
+
+impl Diagnostic for InvalidIntroducedIn {
+    fn kind(&self) -> DiagnosticKind {
+        DiagnosticKind::Error
+    }
+
+    fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    fn format<'a>(&'a self, parsed: &'a Parsed) -> Formatted<'a> {
+        let mut fmt = Formatter::new(
+            self,
+            format!(
+                "invalid version `{}` for `{}`",
+                self.introduced_in.value(),
+                self.item_ident.value(),
+            ),
+        );
+
+        if let Some(schema) = parsed.get_schema(&self.schema_name) {
+            fmt.main_block(
+                schema,
+                self.introduced_in.span().from,
+                self.introduced_in.span(),
+                "version defined here",
+            )
+            .info_block(
+                schema,
+                self.svc_ver.span().from,
+                self.svc_ver.span(),
+                "service version defined here",
+            );
+        }
+
+        fmt.note("items cannot be introduced in a version later than the service's version");
+        fmt.format()
+    }
+}
+
+impl From<InvalidIntroducedIn> for Error {
+    fn from(e: InvalidIntroducedIn) -> Self {
+        Self::InvalidIntroducedIn(e)
+    }
+}
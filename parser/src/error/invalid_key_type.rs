@@ -0,0 +1,118 @@
+use super::Error;
+use crate::ast::{Definition, NamedRef, NamedRefKind, TypeNameKind};
+use crate::diag::{Diagnostic, DiagnosticKind, Formatted, Formatter};
+use crate::validate::Validate;
+use crate::{Parsed, Schema};
+
+// Upper bound on how many newtypes are followed. Recursive newtypes are reported separately.
+const MAX_DEPTH: usize = 32;
+
+#[derive(Debug)]
+pub struct InvalidKeyType {
+    schema_name: String,
+    named_ref: NamedRef,
+}
+
+impl InvalidKeyType {
+    pub(crate) fn validate(named_ref: &NamedRef, validate: &mut Validate) {
+        let schema = validate.get_current_schema();
+
+        if is_key_type(schema, named_ref, validate, 0) {
+            return;
+        }
+
+        validate.add_error(Self {
+            schema_name: validate.schema_name().to_owned(),
+            named_ref: named_ref.clone(),
+        });
+    }
+
+    pub fn named_ref(&self) -> &NamedRef {
+        &self.named_ref
+    }
+}
+
+fn is_key_type(schema: &Schema, named_ref: &NamedRef, validate: &Validate, depth: usize) -> bool {
+    let (schema, ident) = match named_ref.kind() {
+        NamedRefKind::Intern(ident) => (schema, ident),
+
+        NamedRefKind::Extern(schema, ident) => {
+            let Some(schema) = validate.get_schema(schema.value()) else {
+                return true;
+            };
+
+            (schema, ident)
+        }
+    };
+
+    let Some(def) = schema
+        .definitions()
+        .iter()
+        .find(|def| def.name().value() == ident.value())
+    else {
+        return true;
+    };
+
+    match def {
+        Definition::Newtype(newtype_def) => match newtype_def.target_type().kind() {
+            TypeNameKind::U8
+            | TypeNameKind::I8
+            | TypeNameKind::U16
+            | TypeNameKind::I16
+            | TypeNameKind::U32
+            | TypeNameKind::I32
+            | TypeNameKind::U64
+            | TypeNameKind::I64
+            | TypeNameKind::String
+            | TypeNameKind::Uuid => true,
+
+            TypeNameKind::Ref(named_ref) => {
+                (depth >= MAX_DEPTH) || is_key_type(schema, named_ref, validate, depth + 1)
+            }
+
+            _ => false,
+        },
+
+        Definition::Struct(_) | Definition::Enum(_) => false,
+
+        // Services and constants are reported by other errors.
+        Definition::Service(_) | Definition::Const(_) => true,
+    }
+}
+
+impl Diagnostic for InvalidKeyType {
+    fn kind(&self) -> DiagnosticKind {
+        DiagnosticKind::Error
+    }
+
+    fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    fn format<'a>(&'a self, parsed: &'a Parsed) -> Formatted<'a> {
+        let name = match self.named_ref.kind() {
+            NamedRefKind::Intern(ident) => ident.value().to_owned(),
+            NamedRefKind::Extern(schema, ident) => format!("{}::{}", schema.value(), ident.value()),
+        };
+
+        let mut fmt = Formatter::new(self, format!("invalid key type `{name}`"));
+
+        if let Some(schema) = parsed.get_schema(&self.schema_name) {
+            fmt.main_block(
+                schema,
+                self.named_ref.span().from,
+                self.named_ref.span(),
+                "key type used here",
+            );
+        }
+
+        fmt.help("only integers, strings, uuids and newtypes of these can be used as keys");
+        fmt.format()
+    }
+}
+
+impl From<InvalidKeyType> for Error {
+    fn from(e: InvalidKeyType) -> Self {
+        Self::InvalidKeyType(e)
+    }
+}
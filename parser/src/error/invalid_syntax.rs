@@ -57,46 +57,7 @@ impl Diagnostic for InvalidSyntax {
     }
 
     fn format<'a>(&'a self, parsed: &'a Parsed) -> Formatted<'a> {
-        let mut reason = "expected ".to_owned();
-
-        let mut iter = self.expected.iter().peekable();
-        let mut first = true;
-        let mut eof = false;
-        while let Some(expected) = iter.next() {
-            let expected: Cow<'static, str> = match expected {
-                Expected::Eof => {
-                    eof = true;
-                    continue;
-                }
-                Expected::Ident => "an identifier".into(),
-                Expected::Keyword(kw) => format!("`{kw}`").into(),
-                Expected::LitInt => "an integer literal".into(),
-                Expected::LitPosInt => "a positive integer literal".into(),
-                Expected::LitString => "a string literal".into(),
-                Expected::LitUuid => "a uuid literal".into(),
-                Expected::SchemaName => "a schema name".into(),
-                Expected::Token(tok) => format!("`{tok}`").into(),
-            };
-
-            if first {
-                first = false;
-            } else if iter.peek().is_some() || eof {
-                reason.push_str(", ");
-            } else {
-                reason.push_str(" or ");
-            }
-
-            reason.push_str(&expected);
-        }
-
-        if eof {
-            if first {
-                reason.push_str("end of file");
-            } else {
-                reason.push_str(" or end of file");
-            }
-        }
-
+        let reason = expected_reason(&self.expected);
         let mut fmt = Formatter::new(self, reason);
 
         if let Some(schema) = parsed.get_schema(&self.schema_name) {
@@ -118,6 +79,51 @@ impl Diagnostic for InvalidSyntax {
     }
 }
 
+/// Formats a set of expected tokens, e.g. "expected `}` or an identifier".
+pub(crate) fn expected_reason(expected: &BTreeSet<Expected>) -> String {
+    let mut reason = "expected ".to_owned();
+
+    let mut iter = expected.iter().peekable();
+    let mut first = true;
+    let mut eof = false;
+    while let Some(expected) = iter.next() {
+        let expected: Cow<'static, str> = match expected {
+            Expected::Eof => {
+                eof = true;
+                continue;
+            }
+            Expected::Ident => "an identifier".into(),
+            Expected::Keyword(kw) => format!("`{kw}`").into(),
+            Expected::LitInt => "an integer literal".into(),
+            Expected::LitPosInt => "a positive integer literal".into(),
+            Expected::LitString => "a string literal".into(),
+            Expected::LitUuid => "a uuid literal".into(),
+            Expected::SchemaName => "a schema name".into(),
+            Expected::Token(tok) => format!("`{tok}`").into(),
+        };
+
+        if first {
+            first = false;
+        } else if iter.peek().is_some() || eof {
+            reason.push_str(", ");
+        } else {
+            reason.push_str(" or ");
+        }
+
+        reason.push_str(&expected);
+    }
+
+    if eof {
+        if first {
+            reason.push_str("end of file");
+        } else {
+            reason.push_str(" or end of file");
+        }
+    }
+
+    reason
+}
+
 impl From<InvalidSyntax> for Error {
     fn from(e: InvalidSyntax) -> Self {
         Self::InvalidSyntax(e)
@@ -138,7 +144,7 @@ pub enum Expected {
 }
 
 impl Expected {
-    fn add(rule: Rule, set: &mut BTreeSet<Self>) {
+    pub(crate) fn add(rule: Rule, set: &mut BTreeSet<Self>) {
         const CONST_VALUE: &[Expected] = &[
             Expected::Keyword("i16"),
             Expected::Keyword("i32"),
@@ -152,9 +158,19 @@ impl Expected {
             Expected::Keyword("uuid"),
         ];
 
+        const CONST_EXPR: &[Expected] = &[
+            Expected::Ident,
+            Expected::LitInt,
+            Expected::LitString,
+            Expected::LitUuid,
+            Expected::Token("["),
+            Expected::Token("{"),
+        ];
+
         const DEF: &[Expected] = &[
             Expected::Keyword("const"),
             Expected::Keyword("enum"),
+            Expected::Keyword("newtype"),
             Expected::Keyword("service"),
             Expected::Keyword("struct"),
             Expected::Token("#"),
@@ -163,6 +179,7 @@ impl Expected {
         const TYPE_NAME: &[Expected] = &[
             Expected::Ident,
             Expected::Keyword("bool"),
+            Expected::Keyword("broadcast"),
             Expected::Keyword("bytes"),
             Expected::Keyword("f32"),
             Expected::Keyword("f64"),
@@ -195,6 +212,7 @@ impl Expected {
         const INLINE: &[Expected] = &[Expected::Keyword("enum"), Expected::Keyword("struct")];
 
         const KEY_TYPE_NAME: &[Expected] = &[
+            Expected::Ident,
             Expected::Keyword("i16"),
             Expected::Keyword("i32"),
             Expected::Keyword("i64"),
@@ -205,6 +223,7 @@ impl Expected {
             Expected::Keyword("u64"),
             Expected::Keyword("u8"),
             Expected::Keyword("uuid"),
+            Expected::SchemaName,
         ];
 
         const ARRAY_LEN: &[Expected] =
@@ -214,17 +233,25 @@ impl Expected {
         let add: &[&[Self]] = match rule {
             Rule::EOI => &[&[Expected::Eof]],
             Rule::array_len => &[ARRAY_LEN],
+            Rule::attribute_option => &[&[Expected::Ident, Expected::LitString]],
+            Rule::const_expr => &[CONST_EXPR],
             Rule::const_value => &[CONST_VALUE],
             Rule::def => &[DEF],
+            Rule::enum_fallback => &[&[Expected::Keyword("fallback")]],
             Rule::ident => &[&[Expected::Ident]],
+            Rule::introduced_in => &[&[Expected::Keyword("introduced_in")]],
             Rule::key_type_name => &[KEY_TYPE_NAME],
             Rule::kw_args => &[&[Expected::Keyword("args")]],
             Rule::kw_enum => &[&[Expected::Keyword("enum")]],
+            Rule::kw_fallback => &[&[Expected::Keyword("fallback")]],
             Rule::kw_err => &[&[Expected::Keyword("err")]],
             Rule::kw_import => &[&[Expected::Keyword("import")]],
+            Rule::kw_introduced_in => &[&[Expected::Keyword("introduced_in")]],
+            Rule::kw_newtype => &[&[Expected::Keyword("newtype")]],
             Rule::kw_object_id => &[&[Expected::Keyword("object_id")]],
             Rule::kw_ok => &[&[Expected::Keyword("ok")]],
             Rule::kw_service_id => &[&[Expected::Keyword("service_id")]],
+            Rule::kw_stream => &[&[Expected::Keyword("stream")]],
             Rule::kw_struct => &[&[Expected::Keyword("struct")]],
             Rule::kw_uuid => &[&[Expected::Keyword("uuid")]],
             Rule::kw_version => &[&[Expected::Keyword("version")]],
@@ -234,11 +261,13 @@ impl Expected {
             Rule::lit_uuid => &[&[Expected::LitUuid]],
             Rule::schema_name => &[&[Expected::SchemaName]],
             Rule::service_item => &[&[Expected::Keyword("fn"), Expected::Keyword("event")]],
+            Rule::struct_fallback => &[&[Expected::Keyword("fallback")]],
             Rule::struct_field => &[&[Expected::Keyword("required"), Expected::Ident]],
             Rule::tok_ang_close => &[&[Expected::Token(">")]],
             Rule::tok_ang_open => &[&[Expected::Token("<")]],
             Rule::tok_arrow => &[&[Expected::Token("->")]],
             Rule::tok_at => &[&[Expected::Token("@")]],
+            Rule::tok_colon => &[&[Expected::Token(":")]],
             Rule::tok_comma => &[&[Expected::Token(",")]],
             Rule::tok_cur_close => &[&[Expected::Token("}")]],
             Rule::tok_cur_open => &[&[Expected::Token("{")]],
@@ -0,0 +1,981 @@
+//! Machine-readable intermediate representation of parsed schemas.
+//!
+//! The IR is a self-contained, resolved view of a [`Parsed`] set of schemas, meant for tools that
+//! want to consume schemas without re-implementing the parser, e.g. linters, documentation or code
+//! generators for other languages. It is created with [`Parsed::to_ir`].
+//!
+//! Compared to the [`ast`](crate::ast), all ids and integer constants are parsed, array lengths
+//! are resolved to their values and all references to named types carry the name of the schema
+//! they are defined in.
+//!
+//! Doc comments are taken from consecutive lines starting with `///` immediately before a
+//! definition, field, variant or service item.
+//!
+//! With the `serde` feature, all types implement `Serialize` and `Deserialize`. The resulting
+//! format is stable within an IR [`VERSION`]. Enums are represented with an internal `kind` tag,
+//! using `snake_case` names.
+
+#[cfg(test)]
+mod test;
+
+use crate::ast::{
+    self, ArrayLenValue, ConstExprKind, KeyTypeNameKind, NamedRef, ServiceItem, TypeNameKind,
+    TypeNameOrInline,
+};
+use crate::{Parsed, Span};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Version of the IR format.
+///
+/// The version is incremented whenever the IR changes in an incompatible way.
+pub const VERSION: u32 = 1;
+
+/// Resolved intermediate representation of a set of schemas.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ir {
+    /// Version of the IR format (see [`VERSION`]).
+    pub version: u32,
+
+    /// Name of the main schema.
+    pub main_schema: String,
+
+    /// The main schema and all schemas it imports, directly or indirectly, sorted by name.
+    pub schemas: Vec<Schema>,
+}
+
+impl Ir {
+    /// Creates the IR of a set of parsed schemas.
+    ///
+    /// Returns `None` if `parsed` contains any errors.
+    pub fn new(parsed: &Parsed) -> Option<Self> {
+        if !parsed.errors().is_empty() {
+            return None;
+        }
+
+        let mut schemas = parsed
+            .schemas()
+            .map(|schema| Lower::new(parsed, schema).schema())
+            .collect::<Vec<_>>();
+
+        schemas.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Some(Self {
+            version: VERSION,
+            main_schema: parsed.main_schema().name().to_owned(),
+            schemas,
+        })
+    }
+
+    /// Returns the main schema.
+    pub fn main_schema(&self) -> &Schema {
+        self.get_schema(&self.main_schema).unwrap()
+    }
+
+    /// Returns a schema by name.
+    pub fn get_schema(&self, name: &str) -> Option<&Schema> {
+        self.schemas.iter().find(|schema| schema.name == name)
+    }
+}
+
+/// A single schema.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Schema {
+    /// Name of the schema.
+    pub name: String,
+
+    /// Path of the file, from which the schema was parsed.
+    pub path: PathBuf,
+
+    /// Names of all schemas imported by this schema.
+    pub imports: Vec<String>,
+
+    /// All definitions in the order they appear in the schema.
+    pub definitions: Vec<Definition>,
+}
+
+/// A top-level definition.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Definition {
+    /// A struct definition.
+    Struct(Struct),
+
+    /// An enum definition.
+    Enum(Enum),
+
+    /// A service definition.
+    Service(Service),
+
+    /// A constant.
+    Const(Const),
+
+    /// A newtype definition.
+    Newtype(Newtype),
+}
+
+impl Definition {
+    /// Returns the name of the definition.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Struct(def) => &def.name,
+            Self::Enum(def) => &def.name,
+            Self::Service(def) => &def.name,
+            Self::Const(def) => &def.name,
+            Self::Newtype(def) => &def.name,
+        }
+    }
+}
+
+/// A struct definition.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Struct {
+    pub name: String,
+    pub doc: Option<String>,
+    pub span: Span,
+    pub attributes: Vec<Attribute>,
+    pub fields: Vec<Field>,
+
+    /// Fallback field, which collects all unknown fields.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fallback: Option<Fallback>,
+}
+
+/// A newtype definition.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Newtype {
+    pub name: String,
+    pub doc: Option<String>,
+    pub span: Span,
+    pub attributes: Vec<Attribute>,
+    pub target_type: TypeName,
+}
+
+/// An attribute, e.g. `#[rust(impl_copy)]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Attribute {
+    pub name: String,
+    pub options: Vec<AttributeOption>,
+}
+
+/// An option of an attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum AttributeOption {
+    /// A plain identifier, e.g. `impl_copy`.
+    Ident { name: String },
+
+    /// An identifier with arguments, e.g. `derive(Hash, PartialOrd)`.
+    List { name: String, args: Vec<String> },
+
+    /// A string literal, e.g. `"repr(u8)"`.
+    String { value: String },
+}
+
+/// A field of a struct.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Field {
+    pub name: String,
+    pub id: u32,
+    pub required: bool,
+    pub doc: Option<String>,
+    pub span: Span,
+    pub field_type: TypeName,
+}
+
+/// An enum definition.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Enum {
+    pub name: String,
+    pub doc: Option<String>,
+    pub span: Span,
+    pub attributes: Vec<Attribute>,
+    pub variants: Vec<Variant>,
+
+    /// Fallback variant, which is selected for all unknown variants.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fallback: Option<Fallback>,
+}
+
+/// A variant of an enum.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Variant {
+    pub name: String,
+    pub id: u32,
+    pub doc: Option<String>,
+    pub span: Span,
+    pub variant_type: Option<TypeName>,
+}
+
+/// A fallback field of a struct or a fallback variant of an enum.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Fallback {
+    pub name: String,
+    pub doc: Option<String>,
+    pub span: Span,
+}
+
+/// A service definition.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Service {
+    pub name: String,
+    pub doc: Option<String>,
+    pub span: Span,
+    pub uuid: Uuid,
+    pub version: u32,
+
+    /// Functions, events and properties in the order they appear in the schema.
+    pub items: Vec<Item>,
+}
+
+/// An item of a service.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Item {
+    /// A function.
+    Function(Function),
+
+    /// An event.
+    Event(Event),
+
+    /// A property, which uses its id for both a function and an event.
+    Property(Property),
+}
+
+/// A function of a service.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Function {
+    pub name: String,
+    pub id: u32,
+
+    /// Service version, in which the function was introduced.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub introduced_in: Option<u32>,
+
+    pub doc: Option<String>,
+    pub span: Span,
+    pub args: Option<TypeOrInline>,
+    pub ok: Option<TypeOrInline>,
+    pub stream: Option<TypeOrInline>,
+    pub err: Option<TypeOrInline>,
+}
+
+/// An event of a service.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Event {
+    pub name: String,
+    pub id: u32,
+
+    /// Service version, in which the event was introduced.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub introduced_in: Option<u32>,
+
+    pub retained: bool,
+    pub reliable: bool,
+    pub doc: Option<String>,
+    pub span: Span,
+    pub event_type: Option<TypeOrInline>,
+}
+
+/// A property of a service.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Property {
+    pub name: String,
+    pub id: u32,
+    pub doc: Option<String>,
+    pub span: Span,
+    pub property_type: TypeName,
+}
+
+/// A type name or an inline struct or enum.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum TypeOrInline {
+    /// A type name.
+    TypeName { type_name: TypeName },
+
+    /// An inline struct.
+    InlineStruct {
+        fields: Vec<Field>,
+
+        #[cfg_attr(feature = "serde", serde(default))]
+        fallback: Option<Fallback>,
+    },
+
+    /// An inline enum.
+    InlineEnum {
+        variants: Vec<Variant>,
+
+        #[cfg_attr(feature = "serde", serde(default))]
+        fallback: Option<Fallback>,
+    },
+}
+
+/// A type name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum TypeName {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+    String,
+    Uuid,
+    ObjectId,
+    ServiceId,
+    Value,
+    Option {
+        elem: Box<Self>,
+    },
+    Box {
+        elem: Box<Self>,
+    },
+    Vec {
+        elem: Box<Self>,
+    },
+    Bytes,
+    Map {
+        key: KeyType,
+        value: Box<Self>,
+    },
+    Set {
+        key: KeyType,
+    },
+    Sender {
+        elem: Box<Self>,
+    },
+    Receiver {
+        elem: Box<Self>,
+    },
+    Broadcast {
+        elem: Box<Self>,
+    },
+    Lifetime,
+    Unit,
+    Result {
+        ok: Box<Self>,
+        err: Box<Self>,
+    },
+
+    /// An array.
+    ///
+    /// `len` is always resolved. `len_ref` is set if the length was given by a constant.
+    Array {
+        elem: Box<Self>,
+        len: u32,
+        len_ref: Option<Ref>,
+    },
+
+    /// A reference to a named type.
+    Ref(Ref),
+}
+
+/// Type of the keys of maps and sets.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum KeyType {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    String,
+    Uuid,
+
+    /// A reference to a newtype over a key type.
+    Ref(Ref),
+}
+
+/// A resolved reference to a named definition.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ref {
+    /// Name of the schema, in which the definition is located.
+    pub schema: String,
+
+    /// Name of the definition.
+    pub name: String,
+}
+
+/// A constant.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Const {
+    pub name: String,
+    pub doc: Option<String>,
+    pub span: Span,
+    pub value: ConstValue,
+}
+
+/// Value of a constant.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ConstValue {
+    U8 {
+        value: u8,
+    },
+    I8 {
+        value: i8,
+    },
+    U16 {
+        value: u16,
+    },
+    I16 {
+        value: i16,
+    },
+    U32 {
+        value: u32,
+    },
+    I32 {
+        value: i32,
+    },
+    U64 {
+        value: u64,
+    },
+    I64 {
+        value: i64,
+    },
+
+    /// A string, as written in the schema, but without the surrounding quotes.
+    String {
+        value: String,
+    },
+
+    Uuid {
+        value: Uuid,
+    },
+
+    /// A constant of an arbitrary type.
+    Typed {
+        type_name: TypeName,
+        expr: ConstExpr,
+    },
+}
+
+/// Expression of a typed constant.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ConstExpr {
+    Int {
+        value: i128,
+    },
+    Float {
+        value: f64,
+    },
+
+    /// A string, as written in the schema, but without the surrounding quotes.
+    String {
+        value: String,
+    },
+
+    Uuid {
+        value: Uuid,
+    },
+
+    /// A unit variant of an enum.
+    Ident {
+        name: String,
+    },
+
+    /// A variant of an enum with a value.
+    Variant {
+        name: String,
+        value: Box<Self>,
+    },
+
+    List {
+        elems: Vec<Self>,
+    },
+    Map {
+        entries: Vec<ConstExprMapEntry>,
+    },
+    Struct {
+        fields: Vec<ConstExprField>,
+    },
+}
+
+/// An entry of a map expression.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConstExprMapEntry {
+    pub key: ConstExpr,
+    pub value: ConstExpr,
+}
+
+/// A field of a struct expression.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ConstExprField {
+    pub name: String,
+    pub value: ConstExpr,
+}
+
+/// Lowers the AST of a single schema to the IR.
+///
+/// The schemas must be free of errors. Ids and integers have therefore already been validated.
+struct Lower<'a> {
+    parsed: &'a Parsed,
+    schema: &'a crate::Schema,
+}
+
+impl<'a> Lower<'a> {
+    fn new(parsed: &'a Parsed, schema: &'a crate::Schema) -> Self {
+        Self { parsed, schema }
+    }
+
+    fn schema(&self) -> Schema {
+        Schema {
+            name: self.schema.name().to_owned(),
+            path: self.schema.path().to_owned(),
+            imports: self
+                .schema
+                .imports()
+                .iter()
+                .map(|import| import.schema_name().value().to_owned())
+                .collect(),
+            definitions: self
+                .schema
+                .definitions()
+                .iter()
+                .map(|def| self.definition(def))
+                .collect(),
+        }
+    }
+
+    fn definition(&self, def: &ast::Definition) -> Definition {
+        match def {
+            ast::Definition::Struct(def) => Definition::Struct(Struct {
+                name: def.name().value().to_owned(),
+                doc: def.doc().map(ToOwned::to_owned),
+                span: def.span(),
+                attributes: attributes(def.attributes()),
+                fields: self.fields(def.fields()),
+                fallback: def
+                    .fallback()
+                    .map(|f| fallback(f.name(), f.doc(), f.span())),
+            }),
+
+            ast::Definition::Enum(def) => Definition::Enum(Enum {
+                name: def.name().value().to_owned(),
+                doc: def.doc().map(ToOwned::to_owned),
+                span: def.span(),
+                attributes: attributes(def.attributes()),
+                variants: self.variants(def.variants()),
+                fallback: def
+                    .fallback()
+                    .map(|f| fallback(f.name(), f.doc(), f.span())),
+            }),
+
+            ast::Definition::Service(def) => Definition::Service(Service {
+                name: def.name().value().to_owned(),
+                doc: def.doc().map(ToOwned::to_owned),
+                span: def.span(),
+                uuid: def.uuid().value(),
+                version: parse_int(def.version().value()),
+                items: def.items().iter().map(|item| self.item(item)).collect(),
+            }),
+
+            ast::Definition::Const(def) => Definition::Const(Const {
+                name: def.name().value().to_owned(),
+                doc: def.doc().map(ToOwned::to_owned),
+                span: def.span(),
+                value: self.const_value(def.value()),
+            }),
+
+            ast::Definition::Newtype(def) => Definition::Newtype(Newtype {
+                name: def.name().value().to_owned(),
+                doc: def.doc().map(ToOwned::to_owned),
+                span: def.span(),
+                attributes: attributes(def.attributes()),
+                target_type: self.type_name(def.target_type()),
+            }),
+        }
+    }
+
+    fn fields(&self, fields: &[ast::StructField]) -> Vec<Field> {
+        fields
+            .iter()
+            .map(|field| Field {
+                name: field.name().value().to_owned(),
+                id: parse_int(field.id().value()),
+                required: field.required(),
+                doc: field.doc().map(ToOwned::to_owned),
+                span: field.span(),
+                field_type: self.type_name(field.field_type()),
+            })
+            .collect()
+    }
+
+    fn variants(&self, variants: &[ast::EnumVariant]) -> Vec<Variant> {
+        variants
+            .iter()
+            .map(|var| Variant {
+                name: var.name().value().to_owned(),
+                id: parse_int(var.id().value()),
+                doc: var.doc().map(ToOwned::to_owned),
+                span: var.span(),
+                variant_type: var.variant_type().map(|ty| self.type_name(ty)),
+            })
+            .collect()
+    }
+
+    fn item(&self, item: &ServiceItem) -> Item {
+        match item {
+            ServiceItem::Function(func) => Item::Function(Function {
+                name: func.name().value().to_owned(),
+                id: parse_int(func.id().value()),
+                introduced_in: func.introduced_in().map(|ver| parse_int(ver.value())),
+                doc: func.doc().map(ToOwned::to_owned),
+                span: func.span(),
+                args: func
+                    .args()
+                    .map(|part| self.type_or_inline(part.part_type())),
+                ok: func.ok().map(|part| self.type_or_inline(part.part_type())),
+                stream: func
+                    .stream()
+                    .map(|part| self.type_or_inline(part.part_type())),
+                err: func.err().map(|part| self.type_or_inline(part.part_type())),
+            }),
+
+            ServiceItem::Event(ev) => Item::Event(Event {
+                name: ev.name().value().to_owned(),
+                id: parse_int(ev.id().value()),
+                introduced_in: ev.introduced_in().map(|ver| parse_int(ver.value())),
+                retained: ev.retained(),
+                reliable: ev.reliable(),
+                doc: ev.doc().map(ToOwned::to_owned),
+                span: ev.span(),
+                event_type: ev.event_type().map(|ty| self.type_or_inline(ty)),
+            }),
+
+            ServiceItem::Property(prop) => Item::Property(Property {
+                name: prop.name().value().to_owned(),
+                id: parse_int(prop.id().value()),
+                doc: prop.doc().map(ToOwned::to_owned),
+                span: prop.span(),
+                property_type: self.type_name(prop.property_type()),
+            }),
+        }
+    }
+
+    fn type_or_inline(&self, ty: &TypeNameOrInline) -> TypeOrInline {
+        match ty {
+            TypeNameOrInline::TypeName(ty) => TypeOrInline::TypeName {
+                type_name: self.type_name(ty),
+            },
+
+            TypeNameOrInline::Struct(ty) => TypeOrInline::InlineStruct {
+                fields: self.fields(ty.fields()),
+                fallback: ty.fallback().map(|f| fallback(f.name(), f.doc(), f.span())),
+            },
+
+            TypeNameOrInline::Enum(ty) => TypeOrInline::InlineEnum {
+                variants: self.variants(ty.variants()),
+                fallback: ty.fallback().map(|f| fallback(f.name(), f.doc(), f.span())),
+            },
+        }
+    }
+
+    fn type_name(&self, ty: &ast::TypeName) -> TypeName {
+        match ty.kind() {
+            TypeNameKind::Bool => TypeName::Bool,
+            TypeNameKind::U8 => TypeName::U8,
+            TypeNameKind::I8 => TypeName::I8,
+            TypeNameKind::U16 => TypeName::U16,
+            TypeNameKind::I16 => TypeName::I16,
+            TypeNameKind::U32 => TypeName::U32,
+            TypeNameKind::I32 => TypeName::I32,
+            TypeNameKind::U64 => TypeName::U64,
+            TypeNameKind::I64 => TypeName::I64,
+            TypeNameKind::F32 => TypeName::F32,
+            TypeNameKind::F64 => TypeName::F64,
+            TypeNameKind::String => TypeName::String,
+            TypeNameKind::Uuid => TypeName::Uuid,
+            TypeNameKind::ObjectId => TypeName::ObjectId,
+            TypeNameKind::ServiceId => TypeName::ServiceId,
+            TypeNameKind::Value => TypeName::Value,
+
+            TypeNameKind::Option(ty) => TypeName::Option {
+                elem: Box::new(self.type_name(ty)),
+            },
+
+            TypeNameKind::Box(ty) => TypeName::Box {
+                elem: Box::new(self.type_name(ty)),
+            },
+
+            TypeNameKind::Vec(ty) => TypeName::Vec {
+                elem: Box::new(self.type_name(ty)),
+            },
+
+            TypeNameKind::Bytes => TypeName::Bytes,
+
+            TypeNameKind::Map(key, ty) => TypeName::Map {
+                key: self.key_type(key.kind()),
+                value: Box::new(self.type_name(ty)),
+            },
+
+            TypeNameKind::Set(key) => TypeName::Set {
+                key: self.key_type(key.kind()),
+            },
+
+            TypeNameKind::Sender(ty) => TypeName::Sender {
+                elem: Box::new(self.type_name(ty)),
+            },
+
+            TypeNameKind::Receiver(ty) => TypeName::Receiver {
+                elem: Box::new(self.type_name(ty)),
+            },
+
+            TypeNameKind::Broadcast(ty) => TypeName::Broadcast {
+                elem: Box::new(self.type_name(ty)),
+            },
+
+            TypeNameKind::Lifetime => TypeName::Lifetime,
+            TypeNameKind::Unit => TypeName::Unit,
+
+            TypeNameKind::Result(ok, err) => TypeName::Result {
+                ok: Box::new(self.type_name(ok)),
+                err: Box::new(self.type_name(err)),
+            },
+
+            TypeNameKind::Array(ty, len) => {
+                let (len, len_ref) = match len.value() {
+                    ArrayLenValue::Literal(len) => (parse_int(len.value()), None),
+
+                    ArrayLenValue::Ref(named_ref) => {
+                        let len_ref = self.named_ref(named_ref);
+                        (self.resolve_array_len(&len_ref), Some(len_ref))
+                    }
+                };
+
+                TypeName::Array {
+                    elem: Box::new(self.type_name(ty)),
+                    len,
+                    len_ref,
+                }
+            }
+
+            TypeNameKind::Ref(named_ref) => TypeName::Ref(self.named_ref(named_ref)),
+        }
+    }
+
+    fn key_type(&self, kind: &KeyTypeNameKind) -> KeyType {
+        match kind {
+            KeyTypeNameKind::U8 => KeyType::U8,
+            KeyTypeNameKind::I8 => KeyType::I8,
+            KeyTypeNameKind::U16 => KeyType::U16,
+            KeyTypeNameKind::I16 => KeyType::I16,
+            KeyTypeNameKind::U32 => KeyType::U32,
+            KeyTypeNameKind::I32 => KeyType::I32,
+            KeyTypeNameKind::U64 => KeyType::U64,
+            KeyTypeNameKind::I64 => KeyType::I64,
+            KeyTypeNameKind::String => KeyType::String,
+            KeyTypeNameKind::Uuid => KeyType::Uuid,
+            KeyTypeNameKind::Ref(named_ref) => KeyType::Ref(self.named_ref(named_ref)),
+        }
+    }
+
+    fn named_ref(&self, named_ref: &NamedRef) -> Ref {
+        let schema = named_ref
+            .schema()
+            .map(|schema| schema.value())
+            .unwrap_or(self.schema.name());
+
+        Ref {
+            schema: schema.to_owned(),
+            name: named_ref.ident().value().to_owned(),
+        }
+    }
+
+    fn resolve_array_len(&self, len_ref: &Ref) -> u32 {
+        let value = self
+            .parsed
+            .get_schema(&len_ref.schema)
+            .and_then(|schema| {
+                schema
+                    .definitions()
+                    .iter()
+                    .find(|def| def.name().value() == len_ref.name)
+            })
+            .and_then(ast::Definition::as_const)
+            .map(ast::ConstDef::value)
+            .unwrap();
+
+        parse_int(value.as_int().unwrap().value())
+    }
+
+    fn const_value(&self, value: &ast::ConstValue) -> ConstValue {
+        match value {
+            ast::ConstValue::U8(lit) => ConstValue::U8 {
+                value: parse_int(lit.value()),
+            },
+
+            ast::ConstValue::I8(lit) => ConstValue::I8 {
+                value: parse_int(lit.value()),
+            },
+
+            ast::ConstValue::U16(lit) => ConstValue::U16 {
+                value: parse_int(lit.value()),
+            },
+
+            ast::ConstValue::I16(lit) => ConstValue::I16 {
+                value: parse_int(lit.value()),
+            },
+
+            ast::ConstValue::U32(lit) => ConstValue::U32 {
+                value: parse_int(lit.value()),
+            },
+
+            ast::ConstValue::I32(lit) => ConstValue::I32 {
+                value: parse_int(lit.value()),
+            },
+
+            ast::ConstValue::U64(lit) => ConstValue::U64 {
+                value: parse_int(lit.value()),
+            },
+
+            ast::ConstValue::I64(lit) => ConstValue::I64 {
+                value: parse_int(lit.value()),
+            },
+
+            ast::ConstValue::String(lit) => ConstValue::String {
+                value: lit.value().to_owned(),
+            },
+
+            ast::ConstValue::Uuid(lit) => ConstValue::Uuid { value: lit.value() },
+
+            ast::ConstValue::Typed(typed) => ConstValue::Typed {
+                type_name: self.type_name(typed.type_name()),
+                expr: const_expr(typed.expr()),
+            },
+        }
+    }
+}
+
+fn fallback(name: &ast::Ident, doc: Option<&str>, span: Span) -> Fallback {
+    Fallback {
+        name: name.value().to_owned(),
+        doc: doc.map(ToOwned::to_owned),
+        span,
+    }
+}
+
+fn attributes(attrs: &[ast::Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .map(|attr| Attribute {
+            name: attr.name().value().to_owned(),
+            options: attr.options().iter().map(attribute_option).collect(),
+        })
+        .collect()
+}
+
+fn attribute_option(opt: &ast::AttributeOption) -> AttributeOption {
+    match opt.kind() {
+        ast::AttributeOptionKind::Ident(name) => AttributeOption::Ident {
+            name: name.value().to_owned(),
+        },
+
+        ast::AttributeOptionKind::List(name, args) => AttributeOption::List {
+            name: name.value().to_owned(),
+            args: args.iter().map(|arg| arg.value().to_owned()).collect(),
+        },
+
+        ast::AttributeOptionKind::String(value) => AttributeOption::String {
+            value: value.value().to_owned(),
+        },
+    }
+}
+
+fn const_expr(expr: &ast::ConstExpr) -> ConstExpr {
+    match expr.kind() {
+        ConstExprKind::Int(lit) => ConstExpr::Int {
+            value: parse_int(lit.value()),
+        },
+
+        ConstExprKind::Float(lit) => ConstExpr::Float {
+            value: lit.value().parse().unwrap(),
+        },
+
+        ConstExprKind::String(lit) => ConstExpr::String {
+            value: lit.value().to_owned(),
+        },
+
+        ConstExprKind::Uuid(lit) => ConstExpr::Uuid { value: lit.value() },
+
+        ConstExprKind::Ident(ident) => ConstExpr::Ident {
+            name: ident.value().to_owned(),
+        },
+
+        ConstExprKind::Variant(ident, value) => ConstExpr::Variant {
+            name: ident.value().to_owned(),
+            value: Box::new(const_expr(value)),
+        },
+
+        ConstExprKind::List(elems) => ConstExpr::List {
+            elems: elems.iter().map(const_expr).collect(),
+        },
+
+        ConstExprKind::Map(entries) => ConstExpr::Map {
+            entries: entries
+                .iter()
+                .map(|entry| ConstExprMapEntry {
+                    key: const_expr(entry.key()),
+                    value: const_expr(entry.value()),
+                })
+                .collect(),
+        },
+
+        ConstExprKind::Struct(fields) => ConstExpr::Struct {
+            fields: fields
+                .iter()
+                .map(|field| ConstExprField {
+                    name: field.name().value().to_owned(),
+                    value: const_expr(field.value()),
+                })
+                .collect(),
+        },
+    }
+}
+
+/// Parses an integer, that has already been validated.
+fn parse_int<T: std::str::FromStr>(value: &str) -> T {
+    match value.parse() {
+        Ok(value) => value,
+        Err(_) => unreachable!(),
+    }
+}
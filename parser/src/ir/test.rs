@@ -0,0 +1,293 @@
+use super::{
+    AttributeOption, ConstExpr, ConstExprField, ConstValue, Definition, Item, KeyType, Ref,
+    TypeName, TypeOrInline, VERSION,
+};
+use crate::Parser;
+use uuid::uuid;
+
+fn ir() -> super::Ir {
+    let mut parser = Parser::new();
+    parser.add_schema_path("test/ir");
+
+    let parsed = parser.parse("test/ir/ir.aldrin");
+    assert!(parsed.errors().is_empty());
+
+    parsed.to_ir().unwrap()
+}
+
+fn named(schema: &str, name: &str) -> Ref {
+    Ref {
+        schema: schema.to_owned(),
+        name: name.to_owned(),
+    }
+}
+
+#[test]
+fn schemas() {
+    let ir = ir();
+
+    assert_eq!(ir.version, VERSION);
+    assert_eq!(ir.main_schema, "ir");
+    assert_eq!(ir.main_schema().imports, ["ir_import"]);
+
+    let names = ir.schemas.iter().map(|s| &s.name).collect::<Vec<_>>();
+    assert_eq!(names, ["ir", "ir_import"]);
+
+    let names = ir
+        .main_schema()
+        .definitions
+        .iter()
+        .map(Definition::name)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        ["TAGS", "Person", "Event", "DEFAULT", "Persons", "PersonId", "Roster"]
+    );
+}
+
+#[test]
+fn struct_def() {
+    let ir = ir();
+
+    let Definition::Struct(ref person) = ir.main_schema().definitions[1] else {
+        panic!();
+    };
+
+    assert_eq!(
+        person.doc.as_deref(),
+        Some("A person.\n\nPersons have a name and an age.")
+    );
+    assert_eq!(person.attributes[0].name, "rust");
+    assert_eq!(
+        person.attributes[0].options,
+        [
+            AttributeOption::Ident {
+                name: "impl_copy".to_owned()
+            },
+            AttributeOption::List {
+                name: "derive".to_owned(),
+                args: vec!["Hash".to_owned()],
+            },
+        ]
+    );
+    assert_eq!(person.attributes[1].name, "rust_attrs");
+    assert_eq!(
+        person.attributes[1].options,
+        [AttributeOption::String {
+            value: "must_use".to_owned()
+        }]
+    );
+
+    let name = &person.fields[0];
+    assert_eq!(name.name, "name");
+    assert_eq!(name.id, 1);
+    assert!(name.required);
+    assert_eq!(name.doc.as_deref(), Some("The person's name."));
+    assert_eq!(name.field_type, TypeName::String);
+    assert_eq!(name.span.from.line_col.line, 13);
+
+    let age = &person.fields[1];
+    assert!(!age.required);
+    assert_eq!(age.doc, None);
+    assert_eq!(age.field_type, TypeName::Ref(named("ir_import", "Age")));
+
+    let tags = &person.fields[2];
+    assert_eq!(tags.doc, None);
+    assert_eq!(
+        tags.field_type,
+        TypeName::Array {
+            elem: Box::new(TypeName::String),
+            len: 4,
+            len_ref: Some(named("ir", "TAGS")),
+        }
+    );
+
+    assert_eq!(
+        person.fields[3].field_type,
+        TypeName::Map {
+            key: KeyType::String,
+            value: Box::new(TypeName::Array {
+                elem: Box::new(TypeName::U8),
+                len: 2,
+                len_ref: None,
+            }),
+        }
+    );
+}
+
+#[test]
+fn enum_def() {
+    let ir = ir();
+
+    let Definition::Enum(ref event) = ir.main_schema().definitions[2] else {
+        panic!();
+    };
+
+    assert_eq!(event.doc, None);
+    assert_eq!(event.variants[0].name, "Created");
+    assert_eq!(event.variants[0].variant_type, None);
+    assert_eq!(event.variants[1].id, 2);
+    assert_eq!(event.variants[1].doc.as_deref(), Some("Data changed."));
+    assert_eq!(
+        event.variants[1].variant_type,
+        Some(TypeName::Ref(named("ir", "Person")))
+    );
+
+    let fallback = event.fallback.as_ref().unwrap();
+    assert_eq!(fallback.name, "Unknown");
+    assert_eq!(fallback.doc.as_deref(), Some("Events of newer versions."));
+}
+
+#[test]
+fn consts() {
+    let ir = ir();
+
+    let Definition::Const(ref tags) = ir.main_schema().definitions[0] else {
+        panic!();
+    };
+
+    assert_eq!(tags.doc.as_deref(), Some("Length of `Person::tags`."));
+    assert_eq!(tags.value, ConstValue::U8 { value: 4 });
+
+    let Definition::Const(ref default) = ir.main_schema().definitions[3] else {
+        panic!();
+    };
+
+    assert_eq!(
+        default.value,
+        ConstValue::Typed {
+            type_name: TypeName::Ref(named("ir", "Person")),
+            expr: ConstExpr::Struct {
+                fields: vec![
+                    ConstExprField {
+                        name: "name".to_owned(),
+                        value: ConstExpr::String {
+                            value: "foo".to_owned(),
+                        },
+                    },
+                    ConstExprField {
+                        name: "age".to_owned(),
+                        value: ConstExpr::Struct {
+                            fields: vec![ConstExprField {
+                                name: "years".to_owned(),
+                                value: ConstExpr::Int { value: 3 },
+                            }],
+                        },
+                    },
+                ],
+            },
+        }
+    );
+
+    let Definition::Const(ref max_age) = ir.get_schema("ir_import").unwrap().definitions[0] else {
+        panic!();
+    };
+
+    assert_eq!(max_age.value, ConstValue::U32 { value: 150 });
+}
+
+#[test]
+fn service_def() {
+    let ir = ir();
+
+    let Definition::Service(ref svc) = ir.main_schema().definitions[4] else {
+        panic!();
+    };
+
+    assert_eq!(svc.doc.as_deref(), Some("Manages persons."));
+    assert_eq!(svc.uuid, uuid!("3a4d3d31-1b1e-4a79-9c9c-2e5fb57e2e8d"));
+    assert_eq!(svc.version, 2);
+    assert_eq!(svc.items.len(), 4);
+
+    let Item::Function(ref add) = svc.items[0] else {
+        panic!();
+    };
+
+    assert_eq!(add.id, 1);
+    assert_eq!(add.introduced_in, None);
+    assert_eq!(add.doc.as_deref(), Some("Adds a person."));
+    assert_eq!(
+        add.args,
+        Some(TypeOrInline::TypeName {
+            type_name: TypeName::Ref(named("ir", "Person")),
+        })
+    );
+    assert_eq!(
+        add.ok,
+        Some(TypeOrInline::TypeName {
+            type_name: TypeName::U32,
+        })
+    );
+    assert_eq!(add.stream, None);
+
+    let Some(TypeOrInline::InlineEnum {
+        ref variants,
+        ref fallback,
+    }) = add.err
+    else {
+        panic!();
+    };
+
+    assert_eq!(variants[0].name, "Duplicate");
+    assert_eq!(*fallback, None);
+
+    let Item::Event(ref added) = svc.items[1] else {
+        panic!();
+    };
+
+    assert!(added.reliable);
+    assert!(!added.retained);
+
+    let Item::Event(ref count) = svc.items[2] else {
+        panic!();
+    };
+
+    assert!(count.retained);
+    assert!(!count.reliable);
+    assert_eq!(count.id, 2);
+    assert_eq!(count.introduced_in, Some(2));
+
+    let Item::Property(ref name) = svc.items[3] else {
+        panic!();
+    };
+
+    assert_eq!(name.id, 3);
+    assert_eq!(
+        name.property_type,
+        TypeName::Option {
+            elem: Box::new(TypeName::String),
+        }
+    );
+}
+
+#[test]
+fn newtype_def() {
+    let ir = ir();
+
+    let Definition::Newtype(ref person_id) = ir.main_schema().definitions[5] else {
+        panic!();
+    };
+
+    assert_eq!(person_id.doc.as_deref(), Some("Identifies a person."));
+    assert_eq!(person_id.target_type, TypeName::String);
+
+    let Definition::Newtype(ref roster) = ir.main_schema().definitions[6] else {
+        panic!();
+    };
+
+    assert_eq!(roster.doc, None);
+    assert_eq!(
+        roster.target_type,
+        TypeName::Map {
+            key: KeyType::Ref(named("ir", "PersonId")),
+            value: Box::new(TypeName::Ref(named("ir", "Person"))),
+        }
+    );
+}
+
+#[test]
+fn errors() {
+    let parser = Parser::new();
+    let parsed = parser.parse("test/ui/invalid_const_expr.aldrin");
+    assert!(parsed.to_ir().is_none());
+}
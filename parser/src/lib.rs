@@ -11,8 +11,12 @@ mod util;
 mod validate;
 
 pub mod ast;
+pub mod compat;
 pub mod diag;
+pub mod document;
 pub mod error;
+pub mod ir;
+pub mod resolver;
 pub mod warning;
 
 pub use diag::Diagnostic;
@@ -1,5 +1,8 @@
 use crate::error::DuplicateServiceUuid;
+use crate::error::IoError;
+use crate::ir::Ir;
 use crate::issues::Issues;
+use crate::resolver::{DirResolver, Resolver};
 use crate::validate::Validate;
 use crate::{Error, Schema, Warning};
 use std::collections::hash_map::{Entry, HashMap};
@@ -7,13 +10,13 @@ use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub struct Parser {
-    schema_paths: Vec<PathBuf>,
+    resolvers: Vec<Box<dyn Resolver>>,
 }
 
 impl Parser {
     pub fn new() -> Self {
         Self {
-            schema_paths: Vec::new(),
+            resolvers: Vec::new(),
         }
     }
 
@@ -21,7 +24,18 @@ impl Parser {
     where
         P: Into<PathBuf>,
     {
-        self.schema_paths.push(path.into());
+        self.add_resolver(DirResolver::new(path));
+    }
+
+    /// Adds a resolver for imported schemas.
+    ///
+    /// Resolvers are queried in the reverse order in which they were added. See the
+    /// [`resolver`](crate::resolver) module for more information.
+    pub fn add_resolver<R>(&mut self, resolver: R)
+    where
+        R: Resolver + 'static,
+    {
+        self.resolvers.push(Box::new(resolver));
     }
 
     pub fn parse<P>(&self, schema_path: P) -> Parsed
@@ -30,7 +44,39 @@ impl Parser {
     {
         let mut issues = Issues::default();
         let main_schema = Schema::parse(schema_path, &mut issues);
+        self.parse_impl(main_schema, issues)
+    }
 
+    /// Parses a schema from memory.
+    ///
+    /// This is like [`parse`](Self::parse), except that the main schema's source is given by
+    /// `source` instead of being read from `schema_path`. The path is still used to determine the
+    /// schema's name and it is reported in diagnostics. Imported schemas are resolved as usual.
+    ///
+    /// This is useful e.g. for editors, which need to check schemas that have not been saved yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin_parser::Parser;
+    ///
+    /// let parser = Parser::new();
+    /// let parsed = parser.parse_source("foo.aldrin", "struct Foo {}");
+    ///
+    /// assert!(parsed.errors().is_empty());
+    /// assert_eq!(parsed.main_schema().name(), "foo");
+    /// ```
+    pub fn parse_source<P, S>(&self, schema_path: P, source: S) -> Parsed
+    where
+        P: AsRef<Path>,
+        S: Into<String>,
+    {
+        let mut issues = Issues::default();
+        let main_schema = Schema::parse_source(schema_path, source.into(), &mut issues);
+        self.parse_impl(main_schema, issues)
+    }
+
+    fn parse_impl(&self, main_schema: Schema, issues: Issues) -> Parsed {
         let mut parsed = Parsed {
             main_schema: main_schema.name().to_owned(),
             schemas: HashMap::new(),
@@ -53,8 +99,13 @@ impl Parser {
             };
 
             let schema_path = match self.find_schema(entry.key()) {
-                Some(schema_path) => schema_path,
-                None => continue,
+                Ok(Some(schema_path)) => schema_path,
+                Ok(None) => continue,
+
+                Err(e) => {
+                    parsed.issues.add_error(IoError::new(entry.key(), e));
+                    continue;
+                }
             };
 
             let schema = Schema::parse(schema_path, &mut parsed.issues);
@@ -67,21 +118,18 @@ impl Parser {
             entry.insert(schema);
         }
 
-        parsed.validate(&self.schema_paths);
+        parsed.validate(&self.resolvers);
         parsed
     }
 
-    fn find_schema(&self, schema_name: &str) -> Option<PathBuf> {
-        for mut path in self.schema_paths.iter().rev().cloned() {
-            path.push(schema_name);
-            path.set_extension("aldrin");
-
-            if path.is_file() {
-                return Some(path);
+    fn find_schema(&self, schema_name: &str) -> std::io::Result<Option<PathBuf>> {
+        for resolver in self.resolvers.iter().rev() {
+            if let Some(path) = resolver.resolve(schema_name)? {
+                return Ok(Some(path));
             }
         }
 
-        None
+        Ok(None)
     }
 }
 
@@ -99,7 +147,7 @@ pub struct Parsed {
 }
 
 impl Parsed {
-    fn validate(&mut self, schema_paths: &[PathBuf]) {
+    fn validate(&mut self, resolvers: &[Box<dyn Resolver>]) {
         DuplicateServiceUuid::validate(self.schemas.values(), &mut self.issues);
 
         for (schema_name, schema) in &self.schemas {
@@ -109,7 +157,7 @@ impl Parsed {
                 &mut self.issues,
                 &self.schemas,
                 is_main_schema,
-                schema_paths,
+                resolvers,
             );
             schema.validate(&mut validate);
         }
@@ -123,6 +171,18 @@ impl Parsed {
         self.schemas.get(schema_name)
     }
 
+    /// Returns all schemas, including the main schema and all imported ones.
+    pub fn schemas(&self) -> impl Iterator<Item = &Schema> + '_ {
+        self.schemas.values()
+    }
+
+    /// Converts the parsed schemas into their [intermediate representation](crate::ir).
+    ///
+    /// Returns `None` if there are any errors.
+    pub fn to_ir(&self) -> Option<Ir> {
+        Ir::new(self)
+    }
+
     pub fn errors(&self) -> &[Error] {
         self.issues.errors()
     }
@@ -0,0 +1,371 @@
+//! Resolving schema imports to files.
+//!
+//! When a schema imports another schema, the [`Parser`](crate::Parser) asks its resolvers to find
+//! the imported schema by name. Resolvers are queried in the reverse order in which they were added,
+//! i.e. later resolvers take precedence.
+//!
+//! The following resolvers are provided:
+//!
+//! - [`DirResolver`] looks up schemas in a local directory. This is what
+//!   [`Parser::add_schema_path`](crate::Parser::add_schema_path) uses.
+//! - [`GitResolver`] fetches a git repository at a specific revision into a local cache and then
+//!   looks up schemas in it. This requires the `git` command to be available.
+//! - [`Lockfile`] describes a set of the above in a file, such that they can be shared between
+//!   tools and the `generate!` macro.
+//!
+//! Custom resolvers can be implemented with the [`Resolver`] trait.
+
+#[cfg(test)]
+mod test;
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Resolves schema names to local files.
+pub trait Resolver: fmt::Debug {
+    /// Resolves a schema name to the path of a local file.
+    ///
+    /// Returns `Ok(None)` if this resolver doesn't know the schema. Errors are reported as
+    /// diagnostics by the parser.
+    fn resolve(&self, schema_name: &str) -> Result<Option<PathBuf>>;
+
+    /// Returns the path, at which this resolver would expect the schema.
+    ///
+    /// This is used only in diagnostics, when a schema cannot be found.
+    fn candidate(&self, schema_name: &str) -> Option<PathBuf>;
+}
+
+impl<T: Resolver + ?Sized> Resolver for Box<T> {
+    fn resolve(&self, schema_name: &str) -> Result<Option<PathBuf>> {
+        (**self).resolve(schema_name)
+    }
+
+    fn candidate(&self, schema_name: &str) -> Option<PathBuf> {
+        (**self).candidate(schema_name)
+    }
+}
+
+/// Resolves schemas in a local directory.
+///
+/// A schema `foo` is resolved to the file `foo.aldrin` in the directory.
+#[derive(Debug, Clone)]
+pub struct DirResolver {
+    dir: PathBuf,
+}
+
+impl DirResolver {
+    /// Creates a new `DirResolver` for a directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Resolver for DirResolver {
+    fn resolve(&self, schema_name: &str) -> Result<Option<PathBuf>> {
+        let path = schema_file(&self.dir, schema_name);
+
+        if path.is_file() {
+            Ok(Some(path))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn candidate(&self, schema_name: &str) -> Option<PathBuf> {
+        Some(schema_file(&self.dir, schema_name))
+    }
+}
+
+/// Resolves schemas in a git repository.
+///
+/// The repository is cloned on first use into a cache directory and checked out at the given
+/// revision. Subsequent uses, also by other processes, reuse the checkout. Use a commit hash as the
+/// revision to make builds reproducible; branches and tags are resolved only once and never updated
+/// afterwards.
+///
+/// The cache directory defaults to `aldrin-schemas` in the system's temporary directory.
+#[derive(Debug, Clone)]
+pub struct GitResolver {
+    url: String,
+    rev: String,
+    subdir: PathBuf,
+    cache_dir: PathBuf,
+}
+
+impl GitResolver {
+    /// Creates a new `GitResolver` for a repository at a specific revision.
+    pub fn new(url: impl Into<String>, rev: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            rev: rev.into(),
+            subdir: PathBuf::new(),
+            cache_dir: default_cache_dir(),
+        }
+    }
+
+    /// Sets the directory inside the repository, in which schemas are located.
+    ///
+    /// By default, schemas are looked up at the root of the repository.
+    pub fn with_subdir(mut self, subdir: impl Into<PathBuf>) -> Self {
+        self.subdir = subdir.into();
+        self
+    }
+
+    /// Sets the directory, in which repositories are cached.
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    /// Returns the URL of the repository.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the revision.
+    pub fn rev(&self) -> &str {
+        &self.rev
+    }
+
+    /// Returns the directory inside the repository, in which schemas are located.
+    pub fn subdir(&self) -> &Path {
+        &self.subdir
+    }
+
+    /// Returns the directory, into which the repository is checked out.
+    pub fn checkout_dir(&self) -> PathBuf {
+        self.cache_dir
+            .join(sanitize(&self.url))
+            .join(sanitize(&self.rev))
+    }
+
+    /// Fetches the repository, unless it has already been fetched.
+    ///
+    /// Returns the directory, into which the repository is checked out.
+    pub fn fetch(&self) -> Result<PathBuf> {
+        let checkout_dir = self.checkout_dir();
+        if checkout_dir.is_dir() {
+            return Ok(checkout_dir);
+        }
+
+        // Check out into a temporary directory first, so that concurrent or aborted fetches never
+        // leave a partial checkout behind.
+        let mut tmp_dir = checkout_dir.clone().into_os_string();
+        tmp_dir.push(format!(".tmp-{}", std::process::id()));
+        let tmp_dir = PathBuf::from(tmp_dir);
+
+        if tmp_dir.exists() {
+            fs::remove_dir_all(&tmp_dir)?;
+        }
+
+        fs::create_dir_all(&self.cache_dir)?;
+
+        let res = git(Command::new("git")
+            .args(["clone", "--quiet", "--no-checkout", &self.url])
+            .arg(&tmp_dir))
+        .and_then(|()| {
+            git(Command::new("git")
+                .arg("-C")
+                .arg(&tmp_dir)
+                .args(["checkout", "--quiet", "--detach", &self.rev]))
+        });
+
+        if let Err(e) = res {
+            let _ = fs::remove_dir_all(&tmp_dir);
+            return Err(Error::new(
+                e.kind(),
+                format!("failed to fetch `{}` at `{}`: {e}", self.url, self.rev),
+            ));
+        }
+
+        match fs::rename(&tmp_dir, &checkout_dir) {
+            Ok(()) => Ok(checkout_dir),
+
+            // Another process may have won the race.
+            Err(_) if checkout_dir.is_dir() => {
+                let _ = fs::remove_dir_all(&tmp_dir);
+                Ok(checkout_dir)
+            }
+
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Resolver for GitResolver {
+    fn resolve(&self, schema_name: &str) -> Result<Option<PathBuf>> {
+        let dir = self.fetch()?.join(&self.subdir);
+        DirResolver::new(dir).resolve(schema_name)
+    }
+
+    fn candidate(&self, schema_name: &str) -> Option<PathBuf> {
+        let dir = self.checkout_dir().join(&self.subdir);
+        Some(schema_file(&dir, schema_name))
+    }
+}
+
+/// A set of resolvers described in a file.
+///
+/// Lockfiles pin schema dependencies to specific sources, such that they can be shared across
+/// repositories. Each non-empty line, that doesn't start with `#`, describes one source:
+///
+/// ```text
+/// # Schemas vendored into this repository.
+/// path vendor/schemas
+///
+/// # Schemas from a git repository at a fixed revision, optionally in a subdirectory.
+/// git https://example.com/schemas.git 3f8a0c1e2b7d9f4a6c5e8b1d0a2f7c9e4b6d8a1f schemas
+/// ```
+///
+/// Relative paths are interpreted relative to the directory of the lockfile. As with the parser,
+/// later lines take precedence over earlier ones.
+#[derive(Debug, Clone)]
+pub struct Lockfile {
+    sources: Vec<Source>,
+}
+
+impl Lockfile {
+    /// Loads a lockfile.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        Self::parse(&contents, base_dir).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid lockfile `{}`: {e}", path.display()),
+            )
+        })
+    }
+
+    /// Parses a lockfile.
+    ///
+    /// Relative paths are interpreted relative to `base_dir`.
+    pub fn parse(contents: &str, base_dir: impl AsRef<Path>) -> Result<Self> {
+        let base_dir = base_dir.as_ref();
+        let mut sources = Vec::new();
+
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("line {}: invalid source `{line}`", i + 1),
+                )
+            };
+
+            let mut words = line.split_whitespace();
+
+            let source = match words.next() {
+                Some("path") => {
+                    let dir = words.next().ok_or_else(invalid)?;
+                    Source::Path(base_dir.join(dir))
+                }
+
+                Some("git") => {
+                    let url = words.next().ok_or_else(invalid)?;
+                    let rev = words.next().ok_or_else(invalid)?;
+                    let subdir = words.next().unwrap_or("");
+
+                    Source::Git {
+                        url: url.to_owned(),
+                        rev: rev.to_owned(),
+                        subdir: PathBuf::from(subdir),
+                    }
+                }
+
+                _ => return Err(invalid()),
+            };
+
+            if words.next().is_some() {
+                return Err(invalid());
+            }
+
+            sources.push(source);
+        }
+
+        Ok(Self { sources })
+    }
+
+    /// Returns the resolvers described by this lockfile.
+    ///
+    /// Git repositories are cached in `cache_dir` or the default cache directory if `None` (see
+    /// [`GitResolver`]).
+    pub fn resolvers(&self, cache_dir: Option<&Path>) -> Vec<Box<dyn Resolver>> {
+        self.sources
+            .iter()
+            .map(|source| match source {
+                Source::Path(dir) => Box::new(DirResolver::new(dir)) as Box<dyn Resolver>,
+
+                Source::Git { url, rev, subdir } => {
+                    let mut resolver = GitResolver::new(url, rev).with_subdir(subdir);
+
+                    if let Some(cache_dir) = cache_dir {
+                        resolver = resolver.with_cache_dir(cache_dir);
+                    }
+
+                    Box::new(resolver)
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    Path(PathBuf),
+
+    Git {
+        url: String,
+        rev: String,
+        subdir: PathBuf,
+    },
+}
+
+fn schema_file(dir: &Path, schema_name: &str) -> PathBuf {
+    let mut path = dir.join(schema_name);
+    path.set_extension("aldrin");
+    path
+}
+
+fn default_cache_dir() -> PathBuf {
+    env::temp_dir().join("aldrin-schemas")
+}
+
+/// Turns an arbitrary string into a single, safe path component.
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || (c == '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn git(cmd: &mut Command) -> Result<()> {
+    let output = cmd.output()?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(Error::new(ErrorKind::Other, stderr.trim().to_owned()))
+    }
+}
@@ -0,0 +1,155 @@
+use super::{DirResolver, GitResolver, Lockfile, Resolver, Source};
+use crate::{Error, Parser};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn tmp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aldrin-parser-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap().trim().to_owned()
+}
+
+#[test]
+fn dir_resolver() {
+    let resolver = DirResolver::new("test/ir");
+
+    assert_eq!(
+        resolver.resolve("ir_import").unwrap(),
+        Some(PathBuf::from("test/ir/ir_import.aldrin"))
+    );
+    assert_eq!(resolver.resolve("foo").unwrap(), None);
+    assert_eq!(
+        resolver.candidate("foo"),
+        Some(PathBuf::from("test/ir/foo.aldrin"))
+    );
+}
+
+#[test]
+fn parse_lockfile() {
+    let lockfile = Lockfile::parse(
+        "# Comment\n\
+         \n\
+         path vendor\n\
+         git https://example.com/schemas.git 1234abcd schemas\n\
+         git https://example.com/other.git main\n",
+        "base",
+    )
+    .unwrap();
+
+    assert_eq!(
+        lockfile.sources,
+        [
+            Source::Path(PathBuf::from("base/vendor")),
+            Source::Git {
+                url: "https://example.com/schemas.git".to_owned(),
+                rev: "1234abcd".to_owned(),
+                subdir: PathBuf::from("schemas"),
+            },
+            Source::Git {
+                url: "https://example.com/other.git".to_owned(),
+                rev: "main".to_owned(),
+                subdir: PathBuf::new(),
+            },
+        ]
+    );
+
+    assert_eq!(lockfile.resolvers(None).len(), 3);
+}
+
+#[test]
+fn invalid_lockfile() {
+    assert!(Lockfile::parse("path", "").is_err());
+    assert!(Lockfile::parse("git https://example.com/schemas.git", "").is_err());
+    assert!(Lockfile::parse("git url rev subdir extra", "").is_err());
+    assert!(Lockfile::parse("svn url", "").is_err());
+}
+
+#[test]
+fn git_resolver() {
+    let dir = tmp_dir("git-resolver");
+
+    let repo = dir.join("repo");
+    fs::create_dir_all(repo.join("schemas")).unwrap();
+    fs::write(
+        repo.join("schemas/remote.aldrin"),
+        "struct Remote { field @ 1 = u32; }\n",
+    )
+    .unwrap();
+
+    git(&repo, &["init", "--quiet"]);
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "--quiet", "-m", "Initial commit"]);
+    let rev = git(&repo, &["rev-parse", "HEAD"]);
+
+    // Changes after the pinned revision must not be visible.
+    fs::write(repo.join("schemas/later.aldrin"), "").unwrap();
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "--quiet", "-m", "Later commit"]);
+
+    let main = dir.join("main.aldrin");
+    fs::write(
+        &main,
+        "import remote;\nstruct Main { remote @ 1 = remote::Remote; }\n",
+    )
+    .unwrap();
+
+    let lockfile = dir.join("aldrin.lock");
+    fs::write(&lockfile, format!("git {} {rev} schemas\n", repo.display())).unwrap();
+
+    let mut parser = Parser::new();
+    for resolver in Lockfile::load(&lockfile)
+        .unwrap()
+        .resolvers(Some(&dir.join("cache")))
+    {
+        parser.add_resolver(resolver);
+    }
+
+    let parsed = parser.parse(&main);
+    assert!(parsed.errors().is_empty());
+    assert!(parsed.get_schema("remote").is_some());
+
+    let resolver = GitResolver::new(repo.to_str().unwrap(), &rev)
+        .with_subdir("schemas")
+        .with_cache_dir(dir.join("cache"));
+    assert_eq!(resolver.resolve("later").unwrap(), None);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn git_resolver_error() {
+    let dir = tmp_dir("git-resolver-error");
+
+    let main = dir.join("main.aldrin");
+    fs::write(&main, "import remote;\n").unwrap();
+
+    let mut parser = Parser::new();
+    parser.add_resolver(
+        GitResolver::new(dir.join("missing").to_str().unwrap(), "main")
+            .with_cache_dir(dir.join("cache")),
+    );
+
+    let parsed = parser.parse(&main);
+    assert!(parsed
+        .errors()
+        .iter()
+        .any(|e| matches!(e, Error::IoError(_))));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
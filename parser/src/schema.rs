@@ -51,26 +51,48 @@ impl Schema {
             source
         };
 
+        schema.parse_source_impl(source, issues);
+        schema
+    }
+
+    pub(crate) fn parse_source<P>(schema_path: P, source: String, issues: &mut Issues) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let schema_path = schema_path.as_ref();
+
+        let mut schema = Self {
+            name: Self::parse_file_name(schema_path, issues),
+            path: schema_path.to_owned(),
+            source: None,
+            imports: Vec::new(),
+            defs: Vec::new(),
+        };
+
+        schema.parse_source_impl(source, issues);
+        schema
+    }
+
+    fn parse_source_impl(&mut self, source: String, issues: &mut Issues) {
         let pairs = match Grammar::parse(Rule::file, &source) {
             Ok(pairs) => pairs,
             Err(e) => {
-                schema.source = Some(source);
-                issues.add_error(InvalidSyntax::new(&schema.name, e));
-                return schema;
+                self.source = Some(source);
+                issues.add_error(InvalidSyntax::new(&self.name, e));
+                return;
             }
         };
 
         for pair in pairs {
             match pair.as_rule() {
-                Rule::import_stmt => schema.imports.push(ImportStmt::parse(pair)),
-                Rule::def => schema.defs.push(Definition::parse(pair)),
+                Rule::import_stmt => self.imports.push(ImportStmt::parse(pair)),
+                Rule::def => self.defs.push(Definition::parse(pair)),
                 Rule::EOI => break,
                 _ => unreachable!(),
             }
         }
 
-        schema.source = Some(source);
-        schema
+        self.source = Some(source);
     }
 
     fn parse_file_name<P>(path: P, issues: &mut Issues) -> String
@@ -4,6 +4,7 @@ use std::iter::Skip;
 use std::str::Lines;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineCol {
     pub line: usize,
     pub column: usize,
@@ -17,6 +18,7 @@ impl LineCol {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub index: usize,
     pub line_col: LineCol,
@@ -48,6 +50,7 @@ impl Position {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Span {
     pub from: Position,
     pub to: Position,
@@ -62,7 +65,7 @@ impl Span {
         }
     }
 
-    pub fn lines(self, text: &str) -> SpanLines {
+    pub fn lines(self, text: &str) -> SpanLines<'_> {
         SpanLines {
             span: self,
             lines: text.lines().skip(self.from.line_col.line - 1),
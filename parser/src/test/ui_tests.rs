@@ -1,17 +1,23 @@
 ui_test!(const_int_not_found);
 ui_test!(did_you_mean_builtin);
+ui_test!(duplicate_fallback);
+ui_test!(duplicate_property_id);
 ui_test!(error_empty_enum);
 ui_test!(expected_const_int_found_service);
 ui_test!(expected_const_int_found_string);
 ui_test!(expected_const_int_found_type);
+ui_test!(expected_const_int_found_typed_const);
 ui_test!(expected_const_int_found_uuid);
 ui_test!(expected_ident_found_reserved);
 ui_test!(expected_type_found_const);
 ui_test!(expected_type_found_service);
 ui_test!(grapheme_width);
 ui_test!(invalid_array_len);
+ui_test!(invalid_const_expr);
 ui_test!(invalid_event_id);
 ui_test!(invalid_function_id);
+ui_test!(invalid_introduced_in);
+ui_test!(invalid_key_type);
 ui_test!(recursive1);
 ui_test!(recursive2_a);
 ui_test!(sender_receiver_imports);
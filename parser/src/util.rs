@@ -1,4 +1,4 @@
-use crate::ast::{ConstValue, Definition};
+use crate::ast::Definition;
 use crate::Schema;
 use std::cmp::Ordering;
 use std::collections::HashMap;
@@ -54,6 +54,7 @@ pub fn did_you_mean_type<'a>(
     let candidates = schema.definitions().iter().filter_map(|d| match d {
         Definition::Struct(d) => Some(d.name().value()),
         Definition::Enum(d) => Some(d.name().value()),
+        Definition::Newtype(d) => Some(d.name().value()),
         Definition::Service(_) | Definition::Const(_) => None,
     });
 
@@ -69,19 +70,7 @@ pub fn did_you_mean_const_int<'a>(schema: &'a Schema, name: &str) -> Option<&'a
         .definitions()
         .iter()
         .filter_map(Definition::as_const)
-        .filter(|const_def| {
-            matches!(
-                const_def.value(),
-                ConstValue::U8(_)
-                    | ConstValue::I8(_)
-                    | ConstValue::U16(_)
-                    | ConstValue::I16(_)
-                    | ConstValue::U32(_)
-                    | ConstValue::I32(_)
-                    | ConstValue::U64(_)
-                    | ConstValue::I64(_)
-            )
-        })
+        .filter(|const_def| const_def.value().as_int().is_some())
         .map(|d| d.name().value());
 
     did_you_mean(candidates, name)
@@ -1,14 +1,14 @@
 use crate::issues::Issues;
+use crate::resolver::Resolver;
 use crate::{Error, Schema, Warning};
 use std::collections::HashMap;
-use std::path::PathBuf;
 
 pub(crate) struct Validate<'a> {
     schema_name: &'a str,
     issues: &'a mut Issues,
     schemas: &'a HashMap<String, Schema>,
     is_main_schema: bool,
-    schema_paths: &'a [PathBuf],
+    resolvers: &'a [Box<dyn Resolver>],
 }
 
 impl<'a> Validate<'a> {
@@ -17,14 +17,14 @@ impl<'a> Validate<'a> {
         issues: &'a mut Issues,
         schemas: &'a HashMap<String, Schema>,
         is_main_schema: bool,
-        schema_paths: &'a [PathBuf],
+        resolvers: &'a [Box<dyn Resolver>],
     ) -> Self {
         Validate {
             schema_name,
             issues,
             schemas,
             is_main_schema,
-            schema_paths,
+            resolvers,
         }
     }
 
@@ -58,7 +58,7 @@ impl<'a> Validate<'a> {
         self.get_schema(self.schema_name).unwrap()
     }
 
-    pub fn schema_paths(&self) -> &'a [PathBuf] {
-        self.schema_paths
+    pub fn resolvers(&self) -> &'a [Box<dyn Resolver>] {
+        self.resolvers
     }
 }
@@ -1,8 +1,9 @@
 use super::Warning;
 use crate::ast::{
     ArrayLen, ArrayLenValue, Definition, EnumDef, EnumVariant, EventDef, FunctionDef, FunctionPart,
-    ImportStmt, InlineEnum, InlineStruct, NamedRef, NamedRefKind, SchemaName, ServiceDef,
-    ServiceItem, StructDef, StructField, TypeName, TypeNameKind, TypeNameOrInline,
+    ImportStmt, InlineEnum, InlineStruct, KeyTypeName, KeyTypeNameKind, NamedRef, NamedRefKind,
+    SchemaName, ServiceDef, ServiceItem, StructDef, StructField, TypeName, TypeNameKind,
+    TypeNameOrInline,
 };
 use crate::diag::{Diagnostic, DiagnosticKind, Formatted, Formatter};
 use crate::validate::Validate;
@@ -39,6 +40,7 @@ impl UnusedImport {
             Definition::Enum(d) => Self::visit_enum(d, schema_name),
             Definition::Service(d) => Self::visit_service(d, schema_name),
             Definition::Const(_) => false,
+            Definition::Newtype(d) => Self::visit_type_name(d.target_type(), schema_name),
         }
     }
 
@@ -91,6 +93,7 @@ impl UnusedImport {
         match item {
             ServiceItem::Function(func) => Self::visit_function(func, schema_name),
             ServiceItem::Event(ev) => Self::visit_event(ev, schema_name),
+            ServiceItem::Property(prop) => Self::visit_type_name(prop.property_type(), schema_name),
         }
     }
 
@@ -107,6 +110,12 @@ impl UnusedImport {
             }
         }
 
+        if let Some(stream) = func.stream() {
+            if Self::visit_function_part(stream, schema_name) {
+                return true;
+            }
+        }
+
         if let Some(err) = func.err() {
             if Self::visit_function_part(err, schema_name) {
                 return true;
@@ -140,14 +149,21 @@ impl UnusedImport {
             TypeNameKind::Option(ty)
             | TypeNameKind::Box(ty)
             | TypeNameKind::Vec(ty)
-            | TypeNameKind::Map(_, ty)
             | TypeNameKind::Sender(ty)
-            | TypeNameKind::Receiver(ty) => Self::visit_type_name(ty, schema_name),
+            | TypeNameKind::Receiver(ty)
+            | TypeNameKind::Broadcast(ty) => Self::visit_type_name(ty, schema_name),
 
             TypeNameKind::Array(ty, len) => {
                 Self::visit_type_name(ty, schema_name) || Self::visit_array_len(len, schema_name)
             }
 
+            TypeNameKind::Map(key, ty) => {
+                Self::visit_key_type_name(key, schema_name)
+                    || Self::visit_type_name(ty, schema_name)
+            }
+
+            TypeNameKind::Set(key) => Self::visit_key_type_name(key, schema_name),
+
             TypeNameKind::Result(ok, err) => {
                 Self::visit_type_name(ok, schema_name) || Self::visit_type_name(err, schema_name)
             }
@@ -171,12 +187,28 @@ impl UnusedImport {
             | TypeNameKind::ServiceId
             | TypeNameKind::Value
             | TypeNameKind::Bytes
-            | TypeNameKind::Set(_)
             | TypeNameKind::Lifetime
             | TypeNameKind::Unit => false,
         }
     }
 
+    fn visit_key_type_name(ty: &KeyTypeName, schema_name: &SchemaName) -> bool {
+        match ty.kind() {
+            KeyTypeNameKind::Ref(ty) => Self::visit_named_ref(ty, schema_name),
+
+            KeyTypeNameKind::U8
+            | KeyTypeNameKind::I8
+            | KeyTypeNameKind::U16
+            | KeyTypeNameKind::I16
+            | KeyTypeNameKind::U32
+            | KeyTypeNameKind::I32
+            | KeyTypeNameKind::U64
+            | KeyTypeNameKind::I64
+            | KeyTypeNameKind::String
+            | KeyTypeNameKind::Uuid => false,
+        }
+    }
+
     fn visit_named_ref(ty: &NamedRef, schema_name: &SchemaName) -> bool {
         match ty.kind() {
             NamedRefKind::Intern(_) => false,
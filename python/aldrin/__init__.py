@@ -0,0 +1,74 @@
+"""Runtime support for Python code generated by `aldrin-gen python`.
+
+Generated modules import this package as `aldrin` by default. A different name can be chosen with
+`aldrin-gen python --package`.
+"""
+
+from . import types
+from ._wire import DeserializeError, SerializeError
+from .client import (
+    Call,
+    CallError,
+    CallFailed,
+    Client,
+    ConnectError,
+    DisconnectedError,
+    Error,
+    Event,
+    Object,
+    Proxy,
+    Receiver,
+    Service,
+    event,
+    function,
+    prop,
+)
+from .types import Enum, enum, field, struct, variant
+from .values import (
+    Err,
+    LifetimeId,
+    ObjectId,
+    Ok,
+    Result,
+    SerializedValue,
+    ServiceId,
+    UnboundReceiver,
+    UnboundSender,
+)
+
+__version__ = "0.10.0"
+
+__all__ = [
+    "Call",
+    "CallError",
+    "CallFailed",
+    "Client",
+    "ConnectError",
+    "DeserializeError",
+    "DisconnectedError",
+    "Enum",
+    "Err",
+    "Error",
+    "Event",
+    "LifetimeId",
+    "Object",
+    "ObjectId",
+    "Ok",
+    "Proxy",
+    "Receiver",
+    "Result",
+    "SerializeError",
+    "SerializedValue",
+    "Service",
+    "ServiceId",
+    "UnboundReceiver",
+    "UnboundSender",
+    "enum",
+    "event",
+    "field",
+    "function",
+    "prop",
+    "struct",
+    "types",
+    "variant",
+]
@@ -0,0 +1,254 @@
+"""Low-level buffers for Aldrin's binary value format."""
+
+from __future__ import annotations
+
+import struct
+import typing
+import uuid
+
+
+class SerializeError(Exception):
+    """A value could not be serialized."""
+
+
+class DeserializeError(Exception):
+    """A value could not be deserialized."""
+
+
+# Discriminants of serialized values. These mirror `ValueKind` in aldrin-core.
+NONE = 0
+SOME = 1
+BOOL = 2
+U8 = 3
+I8 = 4
+U16 = 5
+I16 = 6
+U32 = 7
+I32 = 8
+U64 = 9
+I64 = 10
+F32 = 11
+F64 = 12
+STRING = 13
+UUID = 14
+OBJECT_ID = 15
+SERVICE_ID = 16
+VEC = 17
+BYTES = 18
+U8_MAP = 19
+I8_MAP = 20
+U16_MAP = 21
+I16_MAP = 22
+U32_MAP = 23
+I32_MAP = 24
+U64_MAP = 25
+I64_MAP = 26
+STRING_MAP = 27
+UUID_MAP = 28
+U8_SET = 29
+I8_SET = 30
+U16_SET = 31
+I16_SET = 32
+U32_SET = 33
+I32_SET = 34
+U64_SET = 35
+I64_SET = 36
+STRING_SET = 37
+UUID_SET = 38
+STRUCT = 39
+ENUM = 40
+SENDER = 41
+RECEIVER = 42
+
+_MAX_DEPTH = 32
+
+
+def zigzag_encode(n: int, bits: int) -> int:
+    return ((n << 1) ^ (n >> (bits - 1))) & ((1 << bits) - 1)
+
+
+def zigzag_decode(n: int) -> int:
+    return (n >> 1) ^ -(n & 1)
+
+
+class Writer:
+    __slots__ = ("buf",)
+
+    def __init__(self) -> None:
+        self.buf = bytearray()
+
+    def u8(self, n: int) -> None:
+        self.buf.append(n)
+
+    def raw(self, data: bytes) -> None:
+        self.buf += data
+
+    def varint(self, n: int, size: int) -> None:
+        data = n.to_bytes(size, "little")
+
+        for i in range(size - 1):
+            if data[size - 1 - i] != 0:
+                self.buf.append(255 - i)
+                self.buf += data[: size - i]
+                return
+
+        if data[0] > 255 - size:
+            self.buf.append(256 - size)
+
+        self.buf.append(data[0])
+
+    def u32(self, n: int) -> None:
+        self.varint(n, 4)
+
+    def uuid(self, value: uuid.UUID) -> None:
+        self.buf += value.bytes
+
+    def string(self, value: str) -> None:
+        data = value.encode("utf-8")
+        self.u32(len(data))
+        self.buf += data
+
+    def f32(self, value: float) -> None:
+        self.buf += struct.pack("<f", value)
+
+    def f64(self, value: float) -> None:
+        self.buf += struct.pack("<d", value)
+
+    def getvalue(self) -> bytes:
+        return bytes(self.buf)
+
+
+class Reader:
+    __slots__ = ("data", "pos", "value")
+
+    def __init__(self, data: bytes, pos: int = 0, value: typing.Optional[bytes] = None) -> None:
+        self.data = memoryview(data)
+        self.pos = pos
+
+        # Value of messages, that carry one, e.g. the arguments of a function call.
+        self.value = value
+
+    def remaining(self) -> int:
+        return len(self.data) - self.pos
+
+    def take(self, n: int) -> bytes:
+        if n > self.remaining():
+            raise DeserializeError("unexpected end of input")
+
+        data = bytes(self.data[self.pos : self.pos + n])
+        self.pos += n
+        return data
+
+    def skip(self, n: int) -> None:
+        if n > self.remaining():
+            raise DeserializeError("unexpected end of input")
+
+        self.pos += n
+
+    def u8(self) -> int:
+        if self.pos >= len(self.data):
+            raise DeserializeError("unexpected end of input")
+
+        n = self.data[self.pos]
+        self.pos += 1
+        return n
+
+    def peek(self) -> int:
+        if self.pos >= len(self.data):
+            raise DeserializeError("unexpected end of input")
+
+        return self.data[self.pos]
+
+    def varint(self, size: int) -> int:
+        first = self.u8()
+
+        if first > 255 - size:
+            return int.from_bytes(self.take(first + size - 255), "little")
+        else:
+            return first
+
+    def u32(self) -> int:
+        return self.varint(4)
+
+    def uuid(self) -> uuid.UUID:
+        return uuid.UUID(bytes=self.take(16))
+
+    def string(self) -> str:
+        try:
+            return self.take(self.u32()).decode("utf-8")
+        except UnicodeDecodeError as e:
+            raise DeserializeError("invalid UTF-8 in string") from e
+
+    def f32(self) -> float:
+        return struct.unpack("<f", self.take(4))[0]
+
+    def f64(self) -> float:
+        return struct.unpack("<d", self.take(8))[0]
+
+    def expect(self, kind: int) -> None:
+        actual = self.u8()
+        if actual != kind:
+            raise DeserializeError(f"unexpected value kind {actual}, expected {kind}")
+
+    def skip_value(self, depth: int = 0) -> None:
+        """Skips a single value of any kind."""
+
+        if depth > _MAX_DEPTH:
+            raise DeserializeError("too deeply nested")
+
+        kind = self.u8()
+
+        if kind == NONE:
+            pass
+        elif kind == SOME:
+            self.skip_value(depth + 1)
+        elif kind in (BOOL, U8, I8):
+            self.skip(1)
+        elif kind in (U16, I16):
+            self.varint(2)
+        elif kind in (U32, I32):
+            self.varint(4)
+        elif kind in (U64, I64):
+            self.varint(8)
+        elif kind == F32:
+            self.skip(4)
+        elif kind == F64:
+            self.skip(8)
+        elif kind in (STRING, BYTES):
+            self.skip(self.u32())
+        elif kind in (UUID, SENDER, RECEIVER):
+            self.skip(16)
+        elif kind == OBJECT_ID:
+            self.skip(32)
+        elif kind == SERVICE_ID:
+            self.skip(64)
+        elif kind == VEC:
+            for _ in range(self.u32()):
+                self.skip_value(depth + 1)
+        elif U8_MAP <= kind <= UUID_MAP:
+            for _ in range(self.u32()):
+                self._skip_key(kind - U8_MAP)
+                self.skip_value(depth + 1)
+        elif U8_SET <= kind <= UUID_SET:
+            for _ in range(self.u32()):
+                self._skip_key(kind - U8_SET)
+        elif kind == STRUCT:
+            for _ in range(self.u32()):
+                self.u32()
+                self.skip_value(depth + 1)
+        elif kind == ENUM:
+            self.u32()
+            self.skip_value(depth + 1)
+        else:
+            raise DeserializeError(f"invalid value kind {kind}")
+
+    def _skip_key(self, index: int) -> None:
+        # Indices follow the order u8, i8, u16, i16, u32, i32, u64, i64, string, uuid.
+        if index < 2:
+            self.skip(1)
+        elif index < 8:
+            self.varint(2 << (index // 2 - 1))
+        elif index == 8:
+            self.skip(self.u32())
+        else:
+            self.skip(16)
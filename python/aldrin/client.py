@@ -0,0 +1,797 @@
+"""Asyncio-based client for Aldrin brokers.
+
+The client speaks protocol version 1.14 over any asyncio stream, e.g. a TCP connection. It supports
+objects, services, function calls, events and properties. Channels and streaming functions are not
+supported yet.
+"""
+
+from __future__ import annotations
+
+import asyncio
+import dataclasses
+import keyword
+import logging
+import typing
+import uuid as _uuid
+
+from . import _wire, types
+from ._wire import DeserializeError, Reader, Writer
+from .values import ObjectId, SerializedValue, ServiceId
+
+_log = logging.getLogger(__name__)
+
+PROTOCOL_MAJOR: typing.Final = 1
+PROTOCOL_MINOR: typing.Final = 14
+
+_CONNECT_REPLY_OK = 0
+_CONNECT_REPLY_REJECTED = 1
+
+# Message kinds. These mirror `MessageKind` in aldrin-core.
+_SHUTDOWN = 2
+_CREATE_OBJECT = 3
+_CREATE_OBJECT_REPLY = 4
+_DESTROY_OBJECT = 5
+_DESTROY_OBJECT_REPLY = 6
+_CREATE_SERVICE = 7
+_CREATE_SERVICE_REPLY = 8
+_DESTROY_SERVICE = 9
+_DESTROY_SERVICE_REPLY = 10
+_CALL_FUNCTION = 11
+_CALL_FUNCTION_REPLY = 12
+_SUBSCRIBE_EVENT = 13
+_SUBSCRIBE_EVENT_REPLY = 14
+_UNSUBSCRIBE_EVENT = 15
+_EMIT_EVENT = 16
+_SERVICE_DESTROYED = 32
+_CREATE_BUS_LISTENER = 33
+_CREATE_BUS_LISTENER_REPLY = 34
+_DESTROY_BUS_LISTENER = 35
+_DESTROY_BUS_LISTENER_REPLY = 36
+_ADD_BUS_LISTENER_FILTER = 37
+_START_BUS_LISTENER = 40
+_START_BUS_LISTENER_REPLY = 41
+_EMIT_BUS_EVENT = 44
+_BUS_LISTENER_CURRENT_FINISHED = 45
+_CONNECT2 = 46
+_CONNECT_REPLY2 = 47
+
+_WITH_VALUE = frozenset([_CALL_FUNCTION, _CALL_FUNCTION_REPLY, _EMIT_EVENT, _CONNECT_REPLY2])
+
+_REPLY_OK = 0
+_REPLY_ERR = 1
+_REPLY_REASONS = {
+    2: "aborted",
+    3: "invalid service",
+    4: "invalid function",
+    5: "invalid arguments",
+    6: "overloaded",
+}
+
+_BUS_EVENT_SERVICE_CREATED = 2
+_FILTER_ANY_OBJECT_SPECIFIC_SERVICE = 4
+_FILTER_SPECIFIC_OBJECT_SPECIFIC_SERVICE = 5
+_SCOPE_ALL = 2
+
+
+class Error(Exception):
+    """Base class of all errors raised by the client."""
+
+
+class ConnectError(Error):
+    """The broker rejected the connection."""
+
+
+class DisconnectedError(Error):
+    """The connection to the broker was closed."""
+
+
+class CallError(Error):
+    """A function call returned an error.
+
+    Handlers raise this with a value of the function's error type to reply with an error. Proxies
+    raise it when a call returned an error.
+    """
+
+    def __init__(self, value: typing.Any = None) -> None:
+        super().__init__(value)
+        self.value = value
+
+
+class CallFailed(Error):
+    """A function call failed without returning a value, e.g. because the service is gone."""
+
+    def __init__(self, reason: str) -> None:
+        super().__init__(reason)
+        self.reason = reason
+
+
+@dataclasses.dataclass(frozen=True)
+class function:
+    """Describes a function of a service."""
+
+    name: str
+    args: typing.Any = None
+    ok: typing.Any = None
+    stream: typing.Any = None
+    err: typing.Any = None
+
+
+@dataclasses.dataclass(frozen=True)
+class event:
+    """Describes an event of a service."""
+
+    name: str
+    ty: typing.Any = None
+
+
+@dataclasses.dataclass(frozen=True)
+class prop:
+    """Describes a property of a service.
+
+    Properties use an event and a function with the same id. The event publishes changes and the
+    function fetches the current value.
+    """
+
+    name: str
+    ty: typing.Any
+
+
+@dataclasses.dataclass(frozen=True)
+class Event:
+    """An event received by a proxy."""
+
+    id: int
+    name: str
+    value: typing.Any
+
+
+@dataclasses.dataclass(frozen=True)
+class Call:
+    """Information about a function call, which is passed to handlers."""
+
+    function: int
+    name: str
+    service: Service
+
+
+# Generic alias used in annotations of streaming functions.
+Receiver = typing.AsyncIterator
+
+
+def _method_name(name: str) -> str:
+    return f"{name}_" if keyword.iskeyword(name) else name
+
+
+def _frame(kind: int, fields: Writer, value: typing.Optional[bytes] = None) -> bytes:
+    header = Writer()
+    body = fields.getvalue()
+
+    if value is None:
+        length = 5 + len(body)
+        header.raw(length.to_bytes(4, "little"))
+        header.u8(kind)
+    else:
+        length = 9 + len(value) + len(body)
+        header.raw(length.to_bytes(4, "little"))
+        header.u8(kind)
+        header.raw(len(value).to_bytes(4, "little"))
+        header.raw(value)
+
+    header.raw(body)
+    return header.getvalue()
+
+
+class Client:
+    """Connection to an Aldrin broker.
+
+    Use `Client.connect` to connect over TCP or `Client.from_streams` for any other asyncio stream.
+    """
+
+    def __init__(self, reader: asyncio.StreamReader, writer: asyncio.StreamWriter) -> None:
+        self._reader = reader
+        self._writer = writer
+        self._serial = 0
+        self._pending: typing.Dict[typing.Tuple[int, int], asyncio.Future[Reader]] = {}
+        self._services: typing.Dict[_uuid.UUID, typing.Tuple[Service, typing.Any]] = {}
+        self._subscriptions: typing.Dict[typing.Tuple[_uuid.UUID, int], typing.Set[Proxy]] = {}
+        self._bus_listeners: typing.Dict[_uuid.UUID, asyncio.Queue[typing.Any]] = {}
+        self._calls: typing.Set[asyncio.Task[None]] = set()
+        self._closed = False
+        self._task: typing.Optional[asyncio.Task[None]] = None
+
+    @classmethod
+    async def connect(cls, host: str, port: int, *, name: typing.Optional[str] = None) -> Client:
+        """Connects to a broker over TCP."""
+
+        reader, writer = await asyncio.open_connection(host, port)
+        return await cls.from_streams(reader, writer, name=name)
+
+    @classmethod
+    async def from_streams(
+        cls,
+        reader: asyncio.StreamReader,
+        writer: asyncio.StreamWriter,
+        *,
+        name: typing.Optional[str] = None,
+    ) -> Client:
+        """Performs the handshake with a broker over an already established stream."""
+
+        client = cls(reader, writer)
+        await client._handshake(name)
+        client._task = asyncio.get_running_loop().create_task(client._run())
+        return client
+
+    async def __aenter__(self) -> Client:
+        return self
+
+    async def __aexit__(self, *args: typing.Any) -> None:
+        await self.close()
+
+    async def close(self) -> None:
+        """Shuts down the connection and waits until the broker has acknowledged it."""
+
+        if not self._closed:
+            self._send(_SHUTDOWN, Writer())
+
+        if self._task is not None:
+            await self._task
+
+    async def create_object(self, uuid: typing.Optional[_uuid.UUID] = None) -> Object:
+        """Creates a new object, with a random UUID if none is specified."""
+
+        uuid = uuid or _uuid.uuid4()
+        serial, fields = self._begin_request()
+        fields.uuid(uuid)
+        reply = await self._request(_CREATE_OBJECT, _CREATE_OBJECT_REPLY, serial, fields)
+
+        if reply.u8() != 0:
+            raise Error(f"object {uuid} exists already")
+
+        return Object(self, ObjectId(uuid, reply.uuid()))
+
+    async def find_service(
+        self,
+        service_uuid: _uuid.UUID,
+        object_uuid: typing.Optional[_uuid.UUID] = None,
+        *,
+        wait: bool = True,
+    ) -> typing.Optional[ServiceId]:
+        """Finds a service by its UUID and optionally its object's UUID.
+
+        Waits for the service to be created when `wait` is true. Otherwise, returns `None` when no
+        such service exists currently.
+        """
+
+        serial, fields = self._begin_request()
+        reply = await self._request(
+            _CREATE_BUS_LISTENER, _CREATE_BUS_LISTENER_REPLY, serial, fields
+        )
+        cookie = reply.uuid()
+        queue: asyncio.Queue[typing.Any] = asyncio.Queue()
+        self._bus_listeners[cookie] = queue
+
+        try:
+            fields = Writer()
+            fields.uuid(cookie)
+
+            if object_uuid is None:
+                fields.u8(_FILTER_ANY_OBJECT_SPECIFIC_SERVICE)
+            else:
+                fields.u8(_FILTER_SPECIFIC_OBJECT_SPECIFIC_SERVICE)
+                fields.uuid(object_uuid)
+
+            fields.uuid(service_uuid)
+            self._send(_ADD_BUS_LISTENER_FILTER, fields)
+
+            serial, fields = self._begin_request()
+            fields.uuid(cookie)
+            fields.u8(_SCOPE_ALL)
+            reply = await self._request(
+                _START_BUS_LISTENER, _START_BUS_LISTENER_REPLY, serial, fields
+            )
+
+            if reply.u8() != 0:
+                raise Error("failed to start bus listener")
+
+            while True:
+                item = await queue.get()
+
+                if isinstance(item, ServiceId):
+                    return item
+                elif item is None and not wait:
+                    return None
+                elif isinstance(item, Exception):
+                    raise item
+        finally:
+            del self._bus_listeners[cookie]
+
+            if not self._closed:
+                serial, fields = self._begin_request()
+                fields.uuid(cookie)
+                self._send(_DESTROY_BUS_LISTENER, fields)
+
+    async def _handshake(self, name: typing.Optional[str]) -> None:
+        data = Writer()
+        data.u8(_wire.STRUCT)
+        data.u32(1 if name is None else 2)
+
+        # Field 0 (user data) is always present.
+        data.u32(0)
+        data.u8(_wire.NONE)
+
+        if name is not None:
+            data.u32(6)
+            data.u8(_wire.SOME)
+            types.STRING.encode(data, name)
+
+        fields = Writer()
+        fields.u32(PROTOCOL_MAJOR)
+        fields.u32(PROTOCOL_MINOR)
+        self._send(_CONNECT2, fields, data.getvalue())
+
+        kind, msg = await self._read_message()
+        if kind != _CONNECT_REPLY2:
+            raise ConnectError(f"unexpected message {kind} during handshake")
+
+        result = msg.u8()
+        if result == _CONNECT_REPLY_OK:
+            return
+        elif result == _CONNECT_REPLY_REJECTED:
+            raise ConnectError("connection rejected")
+        else:
+            raise ConnectError("incompatible protocol version")
+
+    def _begin_request(self) -> typing.Tuple[int, Writer]:
+        self._serial = (self._serial + 1) & 0xFFFFFFFF
+        fields = Writer()
+        fields.u32(self._serial)
+        return self._serial, fields
+
+    async def _request(
+        self,
+        kind: int,
+        reply_kind: int,
+        serial: int,
+        fields: Writer,
+        value: typing.Optional[bytes] = None,
+    ) -> Reader:
+        if self._closed:
+            raise DisconnectedError()
+
+        future: asyncio.Future[Reader] = asyncio.get_running_loop().create_future()
+        self._pending[(reply_kind, serial)] = future
+        self._send(kind, fields, value)
+        await self._writer.drain()
+        return await future
+
+    def _send(self, kind: int, fields: Writer, value: typing.Optional[bytes] = None) -> None:
+        if self._closed:
+            raise DisconnectedError()
+
+        self._writer.write(_frame(kind, fields, value))
+
+    async def _read_message(self) -> typing.Tuple[int, Reader]:
+        header = await self._reader.readexactly(4)
+        length = int.from_bytes(header, "little")
+
+        if length < 5:
+            raise DeserializeError(f"invalid message length {length}")
+
+        data = header + await self._reader.readexactly(length - 4)
+        kind = data[4]
+
+        if kind in _WITH_VALUE:
+            value_len = int.from_bytes(data[5:9], "little")
+            return kind, Reader(data, 9 + value_len, data[9 : 9 + value_len])
+        else:
+            return kind, Reader(data, 5)
+
+    async def _run(self) -> None:
+        try:
+            while True:
+                try:
+                    kind, msg = await self._read_message()
+                except (asyncio.IncompleteReadError, ConnectionError):
+                    break
+
+                if kind == _SHUTDOWN:
+                    break
+
+                self._dispatch(kind, msg)
+        finally:
+            self._closed = True
+            self._writer.close()
+            self._shut_down()
+
+    def _shut_down(self) -> None:
+        for future in self._pending.values():
+            if not future.done():
+                future.set_exception(DisconnectedError())
+
+        self._pending.clear()
+
+        for proxies in self._subscriptions.values():
+            for proxy in proxies:
+                proxy._events.put_nowait(None)
+
+        self._subscriptions.clear()
+
+        for queue in self._bus_listeners.values():
+            queue.put_nowait(DisconnectedError())
+
+    def _dispatch(self, kind: int, msg: Reader) -> None:
+        if kind in (
+            _CREATE_OBJECT_REPLY,
+            _DESTROY_OBJECT_REPLY,
+            _CREATE_SERVICE_REPLY,
+            _DESTROY_SERVICE_REPLY,
+            _CALL_FUNCTION_REPLY,
+            _SUBSCRIBE_EVENT_REPLY,
+            _CREATE_BUS_LISTENER_REPLY,
+            _DESTROY_BUS_LISTENER_REPLY,
+            _START_BUS_LISTENER_REPLY,
+        ):
+            future = self._pending.pop((kind, msg.u32()), None)
+            if future is not None and not future.done():
+                future.set_result(msg)
+        elif kind == _CALL_FUNCTION:
+            self._call_function(msg)
+        elif kind == _EMIT_EVENT:
+            self._emit_event(msg)
+        elif kind == _SERVICE_DESTROYED:
+            self._service_destroyed(msg.uuid())
+        elif kind == _EMIT_BUS_EVENT:
+            self._emit_bus_event(msg)
+        elif kind == _BUS_LISTENER_CURRENT_FINISHED:
+            queue = self._bus_listeners.get(msg.uuid())
+            if queue is not None:
+                queue.put_nowait(None)
+
+        # All other messages, e.g. the broker telling a service about subscribers, are not needed
+        # by this client and ignored.
+
+    def _call_function(self, msg: Reader) -> None:
+        value = msg.value
+        serial = msg.u32()
+        cookie = msg.uuid()
+        function_id = msg.u32()
+
+        entry = self._services.get(cookie)
+        if entry is None:
+            self._reply(serial, 3)
+            return
+
+        service, handler = entry
+        task = asyncio.get_running_loop().create_task(
+            self._handle_call(service, handler, serial, function_id, value)
+        )
+        self._calls.add(task)
+        task.add_done_callback(self._calls.discard)
+
+    async def _handle_call(
+        self, service: Service, handler: typing.Any, serial: int, function_id: int, value: bytes
+    ) -> None:
+        # Fetches of properties are answered without involving the handler.
+        desc = service.PROPERTIES.get(function_id)
+        if desc is not None:
+            update = service._properties.get(function_id, (0, None))
+            self._reply(serial, _REPLY_OK, types.serialize(types.PropertyUpdate(desc.ty), update))
+            return
+
+        func = service.FUNCTIONS.get(function_id)
+        if func is None:
+            self._reply(serial, 4)
+            return
+
+        if func.stream is not None:
+            _log.warning("streaming function `%s` is not supported", func.name)
+            self._reply(serial, 2)
+            return
+
+        try:
+            args = types.deserialize(types.UNIT if func.args is None else func.args, value)
+        except DeserializeError:
+            self._reply(serial, 5)
+            return
+
+        call = Call(function_id, func.name, service)
+        method = getattr(handler, _method_name(func.name))
+
+        try:
+            if func.args is None:
+                result = await method(call)
+            else:
+                result = await method(call, args)
+        except CallError as e:
+            if func.err is None:
+                self._reply(serial, 2)
+            else:
+                self._reply(serial, _REPLY_ERR, types.serialize(func.err, e.value))
+
+            return
+        except Exception:
+            _log.exception("handler of `%s` failed", func.name)
+            self._reply(serial, 2)
+            return
+
+        ok = types.UNIT if func.ok is None else func.ok
+        self._reply(serial, _REPLY_OK, types.serialize(ok, result))
+
+    def _reply(self, serial: int, kind: int, value: bytes = bytes([_wire.NONE])) -> None:
+        if self._closed:
+            return
+
+        fields = Writer()
+        fields.u32(serial)
+        fields.u8(kind)
+        self._send(_CALL_FUNCTION_REPLY, fields, value)
+
+    def _emit_event(self, msg: Reader) -> None:
+        value = msg.value
+        cookie = msg.uuid()
+        event_id = msg.u32()
+
+        for proxy in self._subscriptions.get((cookie, event_id), ()):
+            proxy._deliver(event_id, value)
+
+    def _service_destroyed(self, cookie: _uuid.UUID) -> None:
+        for (svc_cookie, event_id), proxies in list(self._subscriptions.items()):
+            if svc_cookie == cookie:
+                del self._subscriptions[(svc_cookie, event_id)]
+
+                for proxy in proxies:
+                    proxy._events.put_nowait(None)
+
+    def _emit_bus_event(self, msg: Reader) -> None:
+        if msg.u8() != _wire.SOME:
+            return
+
+        queue = self._bus_listeners.get(msg.uuid())
+        if queue is None or msg.u8() != _BUS_EVENT_SERVICE_CREATED:
+            return
+
+        object_id = ObjectId(msg.uuid(), msg.uuid())
+        queue.put_nowait(ServiceId(object_id, msg.uuid(), msg.uuid()))
+
+
+class Object:
+    """An object owned by this client."""
+
+    def __init__(self, client: Client, id: ObjectId) -> None:
+        self.client = client
+        self.id = id
+
+    async def destroy(self) -> None:
+        serial, fields = self.client._begin_request()
+        fields.uuid(self.id.cookie)
+        await self.client._request(_DESTROY_OBJECT, _DESTROY_OBJECT_REPLY, serial, fields)
+
+
+class Service:
+    """Base class of generated services.
+
+    Create a service with `await MyService.create(obj, handler)`, where `handler` implements the
+    generated `MyServiceHandler` class.
+    """
+
+    UUID: typing.ClassVar[_uuid.UUID]
+    VERSION: typing.ClassVar[int]
+    FUNCTIONS: typing.ClassVar[typing.Dict[int, function]]
+    EVENTS: typing.ClassVar[typing.Dict[int, event]]
+    PROPERTIES: typing.ClassVar[typing.Dict[int, prop]]
+
+    def __init__(self, obj: Object, id: ServiceId) -> None:
+        self.object = obj
+        self.id = id
+        self._properties: typing.Dict[int, typing.Tuple[int, typing.Any]] = {}
+
+    @classmethod
+    async def create(cls, obj: Object, handler: typing.Any) -> typing.Any:
+        client = obj.client
+        serial, fields = client._begin_request()
+        fields.uuid(obj.id.cookie)
+        fields.uuid(cls.UUID)
+        fields.u32(cls.VERSION)
+        reply = await client._request(_CREATE_SERVICE, _CREATE_SERVICE_REPLY, serial, fields)
+
+        if reply.u8() != 0:
+            raise Error(f"failed to create service {cls.UUID}")
+
+        service = cls(obj, ServiceId(obj.id, cls.UUID, reply.uuid()))
+        client._services[service.id.cookie] = (service, handler)
+        return service
+
+    async def destroy(self) -> None:
+        client = self.object.client
+        client._services.pop(self.id.cookie, None)
+        serial, fields = client._begin_request()
+        fields.uuid(self.id.cookie)
+        await client._request(_DESTROY_SERVICE, _DESTROY_SERVICE_REPLY, serial, fields)
+
+    def _emit(self, event_id: int, value: typing.Any) -> None:
+        ev = self.EVENTS[event_id]
+        self._send_event(event_id, types.serialize(types.UNIT if ev.ty is None else ev.ty, value))
+
+    def _property(self, property_id: int) -> typing.Any:
+        return self._properties.get(property_id, (0, None))[1]
+
+    def _set_property(self, property_id: int, value: typing.Any) -> None:
+        seq = self._properties.get(property_id, (0, None))[0] + 1
+        self._properties[property_id] = (seq, value)
+
+        ty = types.PropertyUpdate(self.PROPERTIES[property_id].ty)
+        self._send_event(property_id, types.serialize(ty, (seq, value)))
+
+    def _send_event(self, event_id: int, data: bytes) -> None:
+        fields = Writer()
+        fields.uuid(self.id.cookie)
+        fields.u32(event_id)
+        self.object.client._send(_EMIT_EVENT, fields, data)
+
+
+class Proxy:
+    """Base class of generated proxies.
+
+    Received events can be awaited with `next_event` or iterated with `async for ev in proxy`.
+    Changes of subscribed properties are received as events as well.
+    """
+
+    UUID: typing.ClassVar[_uuid.UUID]
+    VERSION: typing.ClassVar[int]
+    FUNCTIONS: typing.ClassVar[typing.Dict[int, function]]
+    EVENTS: typing.ClassVar[typing.Dict[int, event]]
+    PROPERTIES: typing.ClassVar[typing.Dict[int, prop]]
+
+    def __init__(self, client: Client, id: ServiceId) -> None:
+        self.client = client
+        self.id = id
+        self._events: asyncio.Queue[typing.Optional[Event]] = asyncio.Queue()
+        self._properties: typing.Dict[int, typing.Tuple[int, typing.Any]] = {}
+
+    @classmethod
+    async def find(
+        cls, client: Client, object_uuid: typing.Optional[_uuid.UUID] = None
+    ) -> typing.Any:
+        """Waits for a service of this type and creates a proxy for it."""
+
+        id = await client.find_service(cls.UUID, object_uuid)
+        assert id is not None
+        return cls(client, id)
+
+    async def next_event(self) -> typing.Optional[Event]:
+        """Returns the next event or `None` when the service or the client is gone."""
+
+        return await self._events.get()
+
+    def __aiter__(self) -> Proxy:
+        return self
+
+    async def __anext__(self) -> Event:
+        ev = await self.next_event()
+
+        if ev is None:
+            raise StopAsyncIteration
+        else:
+            return ev
+
+    async def _call(self, function_id: int, args: typing.Any) -> typing.Any:
+        func = self.FUNCTIONS[function_id]
+
+        if func.stream is not None:
+            raise NotImplementedError("streaming functions are not supported yet")
+
+        value = types.serialize(types.UNIT if func.args is None else func.args, args)
+        kind, value = await self._call_raw(function_id, value)
+
+        if kind == _REPLY_OK:
+            return types.deserialize(types.UNIT if func.ok is None else func.ok, value)
+        elif kind == _REPLY_ERR:
+            if func.err is None:
+                raise CallError(SerializedValue(value))
+            else:
+                raise CallError(types.deserialize(func.err, value))
+        else:
+            raise CallFailed(_REPLY_REASONS.get(kind, "unknown error"))
+
+    async def _call_raw(self, function_id: int, value: bytes) -> typing.Tuple[int, bytes]:
+        client = self.client
+        serial, fields = client._begin_request()
+        fields.uuid(self.id.cookie)
+        fields.u32(function_id)
+        reply = await client._request(
+            _CALL_FUNCTION, _CALL_FUNCTION_REPLY, serial, fields, value
+        )
+
+        kind = reply.u8()
+        return (kind, reply.value)
+
+    def _property(self, property_id: int) -> typing.Any:
+        return self._properties.get(property_id, (0, None))[1]
+
+    async def _subscribe_property(self, property_id: int) -> None:
+        """Subscribes to a property and fetches its current value."""
+
+        await self._subscribe(property_id)
+
+        kind, value = await self._call_raw(property_id, types.serialize(types.UNIT, None))
+        if kind != _REPLY_OK:
+            raise CallFailed(_REPLY_REASONS.get(kind, "unknown error"))
+
+        ty = types.PropertyUpdate(self.PROPERTIES[property_id].ty)
+        self._update_property(property_id, types.deserialize(ty, value))
+
+    def _update_property(self, property_id: int, update: typing.Tuple[int, typing.Any]) -> None:
+        seq, value = update
+        current = self._properties.get(property_id)
+
+        # Updates may arrive both as events and as the reply of the initial fetch.
+        if current is not None and seq <= current[0]:
+            return
+
+        self._properties[property_id] = update
+        self._events.put_nowait(Event(property_id, self.PROPERTIES[property_id].name, value))
+
+    async def _subscribe(self, event_id: int) -> None:
+        client = self.client
+        key = (self.id.cookie, event_id)
+        proxies = client._subscriptions.get(key)
+
+        if proxies is None:
+            serial = client._begin_request()[0]
+            fields = Writer()
+            fields.u8(_wire.SOME)
+            fields.u32(serial)
+            fields.uuid(self.id.cookie)
+            fields.u32(event_id)
+            reply = await client._request(
+                _SUBSCRIBE_EVENT, _SUBSCRIBE_EVENT_REPLY, serial, fields
+            )
+
+            if reply.u8() != 0:
+                raise CallFailed("invalid service")
+
+            proxies = client._subscriptions.setdefault(key, set())
+
+        proxies.add(self)
+
+    async def _unsubscribe(self, event_id: int) -> None:
+        client = self.client
+        key = (self.id.cookie, event_id)
+        proxies = client._subscriptions.get(key)
+
+        if proxies is None or self not in proxies:
+            return
+
+        proxies.discard(self)
+
+        if not proxies:
+            del client._subscriptions[key]
+            fields = Writer()
+            fields.uuid(self.id.cookie)
+            fields.u32(event_id)
+            client._send(_UNSUBSCRIBE_EVENT, fields)
+            await client._writer.drain()
+
+    def _deliver(self, event_id: int, value: bytes) -> None:
+        desc = self.PROPERTIES.get(event_id)
+        if desc is not None:
+            try:
+                update = types.deserialize(types.PropertyUpdate(desc.ty), value)
+            except DeserializeError:
+                _log.warning("failed to deserialize property `%s`", desc.name)
+                return
+
+            self._update_property(event_id, update)
+            return
+
+        ev = self.EVENTS.get(event_id)
+        if ev is None:
+            return
+
+        try:
+            decoded = types.deserialize(types.UNIT if ev.ty is None else ev.ty, value)
+        except DeserializeError:
+            _log.warning("failed to deserialize event `%s`", ev.name)
+            return
+
+        self._events.put_nowait(Event(event_id, ev.name, decoded))
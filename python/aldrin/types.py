@@ -0,0 +1,756 @@
+"""Type descriptors used by generated code to serialize and deserialize values.
+
+Every descriptor has an `encode(writer, value)` and a `decode(reader)` method. Descriptors of types,
+that can be used as keys of maps and sets, additionally provide `encode_key` and `decode_key`.
+"""
+
+from __future__ import annotations
+
+import dataclasses
+import math
+import typing
+import uuid as _uuid
+
+from . import _wire
+from ._wire import DeserializeError, Reader, SerializeError, Writer
+from .values import (
+    Err,
+    LifetimeId,
+    ObjectId,
+    Ok,
+    SerializedValue,
+    ServiceId,
+    UnboundReceiver,
+    UnboundSender,
+)
+
+__all__ = [
+    "Array",
+    "BOOL",
+    "BYTES",
+    "F32",
+    "F64",
+    "I16",
+    "I32",
+    "I64",
+    "I8",
+    "LIFETIME",
+    "Map",
+    "OBJECT_ID",
+    "Option",
+    "PropertyUpdate",
+    "Receiver",
+    "Ref",
+    "Result",
+    "SERVICE_ID",
+    "STRING",
+    "Sender",
+    "Set",
+    "U16",
+    "U32",
+    "U64",
+    "U8",
+    "UNIT",
+    "UUID",
+    "VALUE",
+    "Vec",
+    "deserialize",
+    "resolve",
+    "serialize",
+]
+
+
+def resolve(ty: typing.Any) -> typing.Any:
+    """Returns the descriptor of `ty`, which may also be a generated struct or enum class."""
+
+    return getattr(ty, "__aldrin_type__", ty)
+
+
+def serialize(ty: typing.Any, value: typing.Any) -> bytes:
+    """Serializes `value` as type `ty`."""
+
+    writer = Writer()
+    resolve(ty).encode(writer, value)
+    return writer.getvalue()
+
+
+def deserialize(ty: typing.Any, data: bytes) -> typing.Any:
+    """Deserializes a value of type `ty` and rejects trailing bytes."""
+
+    reader = Reader(data)
+    value = resolve(ty).decode(reader)
+
+    if reader.remaining() != 0:
+        raise DeserializeError("trailing data after value")
+
+    return value
+
+
+class _Unit:
+    def encode(self, w: Writer, value: None) -> None:
+        if value is not None:
+            raise SerializeError(f"expected None, got {value!r}")
+
+        w.u8(_wire.NONE)
+
+    def decode(self, r: Reader) -> None:
+        r.expect(_wire.NONE)
+
+
+class _Bool:
+    def encode(self, w: Writer, value: bool) -> None:
+        if not isinstance(value, bool):
+            raise SerializeError(f"expected bool, got {value!r}")
+
+        w.u8(_wire.BOOL)
+        w.u8(int(value))
+
+    def decode(self, r: Reader) -> bool:
+        r.expect(_wire.BOOL)
+        return r.u8() != 0
+
+
+class _Int:
+    def __init__(self, kind: int, bits: int, signed: bool, map_kind: int, set_kind: int) -> None:
+        self.kind = kind
+        self.bits = bits
+        self.signed = signed
+        self.map_kind = map_kind
+        self.set_kind = set_kind
+
+        if signed:
+            self.min = -(1 << (bits - 1))
+            self.max = (1 << (bits - 1)) - 1
+        else:
+            self.min = 0
+            self.max = (1 << bits) - 1
+
+    def encode(self, w: Writer, value: int) -> None:
+        w.u8(self.kind)
+        self.encode_key(w, value)
+
+    def decode(self, r: Reader) -> int:
+        r.expect(self.kind)
+        return self.decode_key(r)
+
+    def encode_key(self, w: Writer, value: int) -> None:
+        if isinstance(value, bool) or not isinstance(value, int):
+            raise SerializeError(f"expected int, got {value!r}")
+
+        if not self.min <= value <= self.max:
+            raise SerializeError(f"{value} is out of range")
+
+        if self.bits == 8:
+            w.u8(value & 0xFF)
+        elif self.signed:
+            w.varint(_wire.zigzag_encode(value, self.bits), self.bits // 8)
+        else:
+            w.varint(value, self.bits // 8)
+
+    def decode_key(self, r: Reader) -> int:
+        if self.bits == 8:
+            n = r.u8()
+            return n - 256 if self.signed and n > 127 else n
+
+        n = r.varint(self.bits // 8)
+        if n >> self.bits:
+            raise DeserializeError("varint out of range")
+
+        return _wire.zigzag_decode(n) if self.signed else n
+
+
+class _Float:
+    def __init__(self, kind: int) -> None:
+        self.kind = kind
+
+    def encode(self, w: Writer, value: float) -> None:
+        if isinstance(value, bool) or not isinstance(value, (int, float)):
+            raise SerializeError(f"expected float, got {value!r}")
+
+        w.u8(self.kind)
+
+        if self.kind == _wire.F32:
+            if math.isfinite(value) and abs(value) > 3.4028234663852886e38:
+                raise SerializeError(f"{value} is out of range")
+
+            w.f32(value)
+        else:
+            w.f64(value)
+
+    def decode(self, r: Reader) -> float:
+        r.expect(self.kind)
+        return r.f32() if self.kind == _wire.F32 else r.f64()
+
+
+class _String:
+    map_kind = _wire.STRING_MAP
+    set_kind = _wire.STRING_SET
+
+    def encode(self, w: Writer, value: str) -> None:
+        w.u8(_wire.STRING)
+        self.encode_key(w, value)
+
+    def decode(self, r: Reader) -> str:
+        r.expect(_wire.STRING)
+        return r.string()
+
+    def encode_key(self, w: Writer, value: str) -> None:
+        if not isinstance(value, str):
+            raise SerializeError(f"expected str, got {value!r}")
+
+        w.string(value)
+
+    def decode_key(self, r: Reader) -> str:
+        return r.string()
+
+
+class _Uuid:
+    map_kind = _wire.UUID_MAP
+    set_kind = _wire.UUID_SET
+
+    def encode(self, w: Writer, value: _uuid.UUID) -> None:
+        w.u8(_wire.UUID)
+        self.encode_key(w, value)
+
+    def decode(self, r: Reader) -> _uuid.UUID:
+        r.expect(_wire.UUID)
+        return r.uuid()
+
+    def encode_key(self, w: Writer, value: _uuid.UUID) -> None:
+        if not isinstance(value, _uuid.UUID):
+            raise SerializeError(f"expected uuid.UUID, got {value!r}")
+
+        w.uuid(value)
+
+    def decode_key(self, r: Reader) -> _uuid.UUID:
+        return r.uuid()
+
+
+class _ObjectId:
+    def encode(self, w: Writer, value: ObjectId) -> None:
+        if not isinstance(value, ObjectId):
+            raise SerializeError(f"expected ObjectId, got {value!r}")
+
+        w.u8(_wire.OBJECT_ID)
+        w.uuid(value.uuid)
+        w.uuid(value.cookie)
+
+    def decode(self, r: Reader) -> ObjectId:
+        r.expect(_wire.OBJECT_ID)
+        return ObjectId(r.uuid(), r.uuid())
+
+
+class _ServiceId:
+    def encode(self, w: Writer, value: ServiceId) -> None:
+        if not isinstance(value, ServiceId):
+            raise SerializeError(f"expected ServiceId, got {value!r}")
+
+        w.u8(_wire.SERVICE_ID)
+        w.uuid(value.object_id.uuid)
+        w.uuid(value.object_id.cookie)
+        w.uuid(value.uuid)
+        w.uuid(value.cookie)
+
+    def decode(self, r: Reader) -> ServiceId:
+        r.expect(_wire.SERVICE_ID)
+        object_id = ObjectId(r.uuid(), r.uuid())
+        return ServiceId(object_id, r.uuid(), r.uuid())
+
+
+class _Lifetime:
+    def encode(self, w: Writer, value: LifetimeId) -> None:
+        if not isinstance(value, LifetimeId):
+            raise SerializeError(f"expected LifetimeId, got {value!r}")
+
+        OBJECT_ID.encode(w, value.object_id)
+
+    def decode(self, r: Reader) -> LifetimeId:
+        return LifetimeId(OBJECT_ID.decode(r))
+
+
+class _Value:
+    def encode(self, w: Writer, value: SerializedValue) -> None:
+        if not isinstance(value, SerializedValue):
+            raise SerializeError(f"expected SerializedValue, got {value!r}")
+
+        w.raw(value.data)
+
+    def decode(self, r: Reader) -> SerializedValue:
+        start = r.pos
+        r.skip_value()
+        return SerializedValue(bytes(r.data[start : r.pos]))
+
+
+class _Bytes:
+    def encode(self, w: Writer, value: bytes) -> None:
+        if not isinstance(value, (bytes, bytearray, memoryview)):
+            raise SerializeError(f"expected bytes, got {value!r}")
+
+        w.u8(_wire.BYTES)
+        w.u32(len(value))
+        w.raw(value)
+
+    def decode(self, r: Reader) -> bytes:
+        r.expect(_wire.BYTES)
+        return r.take(r.u32())
+
+
+UNIT: typing.Final = _Unit()
+BOOL: typing.Final = _Bool()
+U8: typing.Final = _Int(_wire.U8, 8, False, _wire.U8_MAP, _wire.U8_SET)
+I8: typing.Final = _Int(_wire.I8, 8, True, _wire.I8_MAP, _wire.I8_SET)
+U16: typing.Final = _Int(_wire.U16, 16, False, _wire.U16_MAP, _wire.U16_SET)
+I16: typing.Final = _Int(_wire.I16, 16, True, _wire.I16_MAP, _wire.I16_SET)
+U32: typing.Final = _Int(_wire.U32, 32, False, _wire.U32_MAP, _wire.U32_SET)
+I32: typing.Final = _Int(_wire.I32, 32, True, _wire.I32_MAP, _wire.I32_SET)
+U64: typing.Final = _Int(_wire.U64, 64, False, _wire.U64_MAP, _wire.U64_SET)
+I64: typing.Final = _Int(_wire.I64, 64, True, _wire.I64_MAP, _wire.I64_SET)
+F32: typing.Final = _Float(_wire.F32)
+F64: typing.Final = _Float(_wire.F64)
+STRING: typing.Final = _String()
+UUID: typing.Final = _Uuid()
+OBJECT_ID: typing.Final = _ObjectId()
+SERVICE_ID: typing.Final = _ServiceId()
+LIFETIME: typing.Final = _Lifetime()
+VALUE: typing.Final = _Value()
+BYTES: typing.Final = _Bytes()
+
+
+class Option:
+    def __init__(self, inner: typing.Any) -> None:
+        self.inner = inner
+
+    def encode(self, w: Writer, value: typing.Any) -> None:
+        if value is None:
+            w.u8(_wire.NONE)
+        else:
+            w.u8(_wire.SOME)
+            resolve(self.inner).encode(w, value)
+
+    def decode(self, r: Reader) -> typing.Any:
+        kind = r.u8()
+
+        if kind == _wire.NONE:
+            return None
+        elif kind == _wire.SOME:
+            return resolve(self.inner).decode(r)
+        else:
+            raise DeserializeError(f"unexpected value kind {kind}, expected an option")
+
+
+class Vec:
+    def __init__(self, elem: typing.Any) -> None:
+        self.elem = elem
+
+    def encode(self, w: Writer, value: typing.Sequence[typing.Any]) -> None:
+        if isinstance(value, (str, bytes)) or not isinstance(value, typing.Sequence):
+            raise SerializeError(f"expected a sequence, got {value!r}")
+
+        elem = resolve(self.elem)
+        w.u8(_wire.VEC)
+        w.u32(len(value))
+
+        for item in value:
+            elem.encode(w, item)
+
+    def decode(self, r: Reader) -> typing.List[typing.Any]:
+        r.expect(_wire.VEC)
+        elem = resolve(self.elem)
+        return [elem.decode(r) for _ in range(r.u32())]
+
+
+class Array(Vec):
+    def __init__(self, elem: typing.Any, len: int) -> None:
+        super().__init__(elem)
+        self.len = len
+
+    def encode(self, w: Writer, value: typing.Sequence[typing.Any]) -> None:
+        if len(value) != self.len:
+            raise SerializeError(f"expected {self.len} elements, got {len(value)}")
+
+        super().encode(w, value)
+
+    def decode(self, r: Reader) -> typing.List[typing.Any]:
+        value = super().decode(r)
+
+        if len(value) != self.len:
+            raise DeserializeError(f"expected {self.len} elements, got {len(value)}")
+
+        return value
+
+
+class Map:
+    def __init__(self, key: typing.Any, value: typing.Any) -> None:
+        self.key = key
+        self.value = value
+
+    def encode(self, w: Writer, value: typing.Mapping[typing.Any, typing.Any]) -> None:
+        if not isinstance(value, typing.Mapping):
+            raise SerializeError(f"expected a mapping, got {value!r}")
+
+        key = resolve(self.key)
+        val = resolve(self.value)
+        w.u8(key.map_kind)
+        w.u32(len(value))
+
+        for k, v in value.items():
+            key.encode_key(w, k)
+            val.encode(w, v)
+
+    def decode(self, r: Reader) -> typing.Dict[typing.Any, typing.Any]:
+        key = resolve(self.key)
+        val = resolve(self.value)
+        r.expect(key.map_kind)
+        res = {}
+
+        for _ in range(r.u32()):
+            k = key.decode_key(r)
+            res[k] = val.decode(r)
+
+        return res
+
+
+class Set:
+    def __init__(self, key: typing.Any) -> None:
+        self.key = key
+
+    def encode(self, w: Writer, value: typing.AbstractSet[typing.Any]) -> None:
+        if not isinstance(value, typing.AbstractSet):
+            raise SerializeError(f"expected a set, got {value!r}")
+
+        key = resolve(self.key)
+        w.u8(key.set_kind)
+        w.u32(len(value))
+
+        for k in value:
+            key.encode_key(w, k)
+
+    def decode(self, r: Reader) -> typing.Set[typing.Any]:
+        key = resolve(self.key)
+        r.expect(key.set_kind)
+        return {key.decode_key(r) for _ in range(r.u32())}
+
+
+class Sender:
+    def __init__(self, item: typing.Any) -> None:
+        self.item = item
+
+    def encode(self, w: Writer, value: UnboundSender[typing.Any]) -> None:
+        if not isinstance(value, UnboundSender):
+            raise SerializeError(f"expected UnboundSender, got {value!r}")
+
+        w.u8(_wire.SENDER)
+        w.uuid(value.cookie)
+
+    def decode(self, r: Reader) -> UnboundSender[typing.Any]:
+        r.expect(_wire.SENDER)
+        return UnboundSender(r.uuid())
+
+
+class Receiver:
+    def __init__(self, item: typing.Any) -> None:
+        self.item = item
+
+    def encode(self, w: Writer, value: UnboundReceiver[typing.Any]) -> None:
+        if not isinstance(value, UnboundReceiver):
+            raise SerializeError(f"expected UnboundReceiver, got {value!r}")
+
+        w.u8(_wire.RECEIVER)
+        w.uuid(value.cookie)
+
+    def decode(self, r: Reader) -> UnboundReceiver[typing.Any]:
+        r.expect(_wire.RECEIVER)
+        return UnboundReceiver(r.uuid())
+
+
+class Result:
+    def __init__(self, ok: typing.Any, err: typing.Any) -> None:
+        self.ok = ok
+        self.err = err
+
+    def encode(self, w: Writer, value: typing.Any) -> None:
+        w.u8(_wire.ENUM)
+
+        if isinstance(value, Ok):
+            w.u32(0)
+            resolve(self.ok).encode(w, value.value)
+        elif isinstance(value, Err):
+            w.u32(1)
+            resolve(self.err).encode(w, value.value)
+        else:
+            raise SerializeError(f"expected Ok or Err, got {value!r}")
+
+    def decode(self, r: Reader) -> typing.Any:
+        r.expect(_wire.ENUM)
+        variant = r.u32()
+
+        if variant == 0:
+            return Ok(resolve(self.ok).decode(r))
+        elif variant == 1:
+            return Err(resolve(self.err).decode(r))
+        else:
+            raise DeserializeError(f"invalid result variant {variant}")
+
+
+class PropertyUpdate:
+    """Full update of a property, which is a tuple of its sequence number and optional value.
+
+    Deltas are not supported, because properties declared in schemas are only updated with full
+    values.
+    """
+
+    def __init__(self, inner: typing.Any) -> None:
+        self.inner = inner
+
+    def encode(self, w: Writer, value: typing.Tuple[int, typing.Any]) -> None:
+        seq, inner = value
+        w.u8(_wire.STRUCT)
+        w.u32(2)
+        w.u32(0)
+        U64.encode(w, seq)
+        w.u32(1)
+        Option(self.inner).encode(w, inner)
+
+    def decode(self, r: Reader) -> typing.Tuple[int, typing.Any]:
+        r.expect(_wire.STRUCT)
+        seq = None
+        value = None
+        has_value = False
+
+        for _ in range(r.u32()):
+            id = r.u32()
+
+            if id == 0:
+                seq = U64.decode(r)
+            elif id == 1:
+                value = Option(self.inner).decode(r)
+                has_value = True
+            else:
+                r.skip_value()
+
+        if seq is None or not has_value:
+            raise DeserializeError("expected a full property update")
+
+        return (seq, value)
+
+
+class Ref:
+    """Refers lazily to a generated struct or enum, which may not be defined yet."""
+
+    def __init__(self, get: typing.Callable[[], typing.Any]) -> None:
+        self.get = get
+
+    def encode(self, w: Writer, value: typing.Any) -> None:
+        resolve(self.get()).encode(w, value)
+
+    def decode(self, r: Reader) -> typing.Any:
+        return resolve(self.get()).decode(r)
+
+
+@dataclasses.dataclass(frozen=True)
+class _FieldInfo:
+    id: int
+    ty: typing.Any
+    optional: bool
+
+
+def field(id: int, ty: typing.Any, *, optional: bool = False) -> typing.Any:
+    """Declares a field of a generated struct."""
+
+    metadata = {"aldrin": _FieldInfo(id, ty, optional)}
+
+    if optional:
+        return dataclasses.field(default=None, metadata=metadata)
+    else:
+        return dataclasses.field(metadata=metadata)
+
+
+class _Struct:
+    def __init__(self, cls: type) -> None:
+        self.cls = cls
+        self._fields: typing.Optional[typing.List[typing.Tuple[str, _FieldInfo]]] = None
+
+    @property
+    def fields(self) -> typing.List[typing.Tuple[str, _FieldInfo]]:
+        if self._fields is None:
+            self._fields = [
+                (f.name, f.metadata["aldrin"])
+                for f in dataclasses.fields(self.cls)
+                if "aldrin" in f.metadata
+            ]
+
+        return self._fields
+
+    def encode(self, w: Writer, value: typing.Any) -> None:
+        if not isinstance(value, self.cls):
+            raise SerializeError(f"expected {self.cls.__name__}, got {value!r}")
+
+        present = []
+        for name, info in self.fields:
+            field_value = getattr(value, name)
+
+            if field_value is None and info.optional:
+                continue
+
+            present.append((info, field_value))
+
+        w.u8(_wire.STRUCT)
+        w.u32(len(present))
+
+        # Optional fields are serialized as options, when they are present.
+        for info, field_value in present:
+            w.u32(info.id)
+
+            if info.optional:
+                w.u8(_wire.SOME)
+
+            resolve(info.ty).encode(w, field_value)
+
+    def decode(self, r: Reader) -> typing.Any:
+        r.expect(_wire.STRUCT)
+        by_id = {info.id: (name, info) for name, info in self.fields}
+        values = {}
+
+        for _ in range(r.u32()):
+            id = r.u32()
+            known = by_id.get(id)
+
+            if known is None:
+                r.skip_value()
+            else:
+                name, info = known
+
+                if info.optional:
+                    values[name] = Option(info.ty).decode(r)
+                else:
+                    values[name] = resolve(info.ty).decode(r)
+
+        for name, info in self.fields:
+            if not info.optional and name not in values:
+                raise DeserializeError(f"required field `{name}` is missing")
+
+        return self.cls(**values)
+
+
+def struct(*, schema: str) -> typing.Callable[[type], type]:
+    """Class decorator for generated structs.
+
+    It must be applied on top of `dataclasses.dataclass`.
+    """
+
+    def decorate(cls: type) -> type:
+        cls.__aldrin_type__ = _Struct(cls)  # type: ignore[attr-defined]
+        cls.__aldrin_schema__ = schema  # type: ignore[attr-defined]
+        return cls
+
+    return decorate
+
+
+@dataclasses.dataclass(frozen=True)
+class variant:
+    """Declares a variant of a generated enum."""
+
+    id: int
+    ty: typing.Any = None
+
+
+class _VariantConstructor:
+    def __init__(self, cls: type, name: str, info: variant) -> None:
+        self.cls = cls
+        self.name = name
+        self.id = info.id
+        self.ty = info.ty
+
+    def __call__(self, value: typing.Any) -> typing.Any:
+        return self.cls(self.name, self.id, value)
+
+    def __repr__(self) -> str:
+        return f"{self.cls.__name__}.{self.name}"
+
+
+class Enum:
+    """Base class of generated enums.
+
+    Variants without a value are instances of the enum, e.g. `Mode.Off`. Variants with a value are
+    constructed by calling them, e.g. `Mode.On(5)`.
+    """
+
+    __slots__ = ("name", "id", "value")
+    __aldrin_variants__: typing.ClassVar[typing.Dict[int, typing.Tuple[str, typing.Any]]]
+
+    def __init__(self, name: str, id: int, value: typing.Any = None) -> None:
+        self.name = name
+        self.id = id
+        self.value = value
+
+    def __init_subclass__(cls, **kwargs: typing.Any) -> None:
+        super().__init_subclass__(**kwargs)
+        cls.__aldrin_variants__ = {}
+
+        for name, info in list(vars(cls).items()):
+            if not isinstance(info, variant):
+                continue
+
+            cls.__aldrin_variants__[info.id] = (name, info.ty)
+
+            if info.ty is None:
+                setattr(cls, name, cls(name, info.id))
+            else:
+                setattr(cls, name, _VariantConstructor(cls, name, info))
+
+    def __eq__(self, other: object) -> bool:
+        return (
+            type(self) is type(other)
+            and self.id == other.id  # type: ignore[attr-defined]
+            and self.value == other.value  # type: ignore[attr-defined]
+        )
+
+    def __hash__(self) -> int:
+        return hash((type(self), self.id))
+
+    def __repr__(self) -> str:
+        if self.__aldrin_variants__[self.id][1] is None:
+            return f"{type(self).__name__}.{self.name}"
+        else:
+            return f"{type(self).__name__}.{self.name}({self.value!r})"
+
+
+class _Enum:
+    def __init__(self, cls: typing.Type[Enum]) -> None:
+        self.cls = cls
+
+    def encode(self, w: Writer, value: Enum) -> None:
+        if not isinstance(value, self.cls):
+            raise SerializeError(f"expected {self.cls.__name__}, got {value!r}")
+
+        ty = self.cls.__aldrin_variants__[value.id][1]
+        w.u8(_wire.ENUM)
+        w.u32(value.id)
+        resolve(UNIT if ty is None else ty).encode(w, value.value)
+
+    def decode(self, r: Reader) -> Enum:
+        r.expect(_wire.ENUM)
+        id = r.u32()
+        known = self.cls.__aldrin_variants__.get(id)
+
+        if known is None:
+            raise DeserializeError(f"invalid variant {id} of {self.cls.__name__}")
+
+        name, ty = known
+
+        if ty is None:
+            UNIT.decode(r)
+            return getattr(self.cls, name)
+        else:
+            return self.cls(name, id, resolve(ty).decode(r))
+
+
+def enum(*, schema: str) -> typing.Callable[[type], type]:
+    """Class decorator for generated enums."""
+
+    def decorate(cls: type) -> type:
+        cls.__aldrin_type__ = _Enum(cls)  # type: ignore[arg-type, attr-defined]
+        cls.__aldrin_schema__ = schema  # type: ignore[attr-defined]
+        return cls
+
+    return decorate
@@ -0,0 +1,95 @@
+"""Value types, which have no direct Python equivalent."""
+
+from __future__ import annotations
+
+import dataclasses
+import typing
+import uuid
+
+T = typing.TypeVar("T")
+E = typing.TypeVar("E")
+
+
+@dataclasses.dataclass(frozen=True)
+class ObjectId:
+    """Id of an object on the bus."""
+
+    uuid: uuid.UUID
+    cookie: uuid.UUID
+
+
+@dataclasses.dataclass(frozen=True)
+class ServiceId:
+    """Id of a service on the bus."""
+
+    object_id: ObjectId
+    uuid: uuid.UUID
+    cookie: uuid.UUID
+
+
+@dataclasses.dataclass(frozen=True)
+class LifetimeId:
+    """Id of a lifetime scope, which is backed by an object."""
+
+    object_id: ObjectId
+
+
+@dataclasses.dataclass(frozen=True)
+class UnboundSender(typing.Generic[T]):
+    """Sender of a channel, which has not been claimed by this client.
+
+    Channels are not supported yet by the Python runtime. Values of this type can only be passed
+    through unmodified.
+    """
+
+    cookie: uuid.UUID
+
+
+@dataclasses.dataclass(frozen=True)
+class UnboundReceiver(typing.Generic[T]):
+    """Receiver of a channel, which has not been claimed by this client.
+
+    See `UnboundSender` for limitations.
+    """
+
+    cookie: uuid.UUID
+
+
+class Result(typing.Generic[T, E]):
+    """Base class of `Ok` and `Err`."""
+
+    __slots__ = ()
+
+    def is_ok(self) -> bool:
+        return isinstance(self, Ok)
+
+    def is_err(self) -> bool:
+        return isinstance(self, Err)
+
+
+@dataclasses.dataclass(frozen=True)
+class Ok(Result[T, typing.Any]):
+    value: T
+
+
+@dataclasses.dataclass(frozen=True)
+class Err(Result[typing.Any, E]):
+    value: E
+
+
+@dataclasses.dataclass(frozen=True)
+class SerializedValue:
+    """A value in serialized form, whose type is not known statically."""
+
+    data: bytes
+
+    @classmethod
+    def serialize(cls, ty: typing.Any, value: typing.Any) -> SerializedValue:
+        from . import types
+
+        return cls(types.serialize(ty, value))
+
+    def deserialize(self, ty: typing.Any) -> typing.Any:
+        from . import types
+
+        return types.deserialize(ty, self.data)
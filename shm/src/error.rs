@@ -0,0 +1,25 @@
+use aldrin_core::message::{MessageDeserializeError, MessageSerializeError};
+use std::io::Error as IoError;
+use thiserror::Error;
+
+/// Error of a [`ShmTransport`](crate::ShmTransport).
+#[derive(Error, Debug)]
+pub enum ShmError {
+    /// An IO error occurred.
+    ///
+    /// This is also used when the other side of the transport disconnects.
+    #[error(transparent)]
+    Io(#[from] IoError),
+
+    /// The peer sent an invalid handshake.
+    #[error("invalid handshake")]
+    InvalidHandshake,
+
+    /// A message failed to serialize.
+    #[error(transparent)]
+    Serialize(#[from] MessageSerializeError),
+
+    /// A message failed to deserialize.
+    #[error(transparent)]
+    Deserialize(#[from] MessageDeserializeError),
+}
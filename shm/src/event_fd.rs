@@ -0,0 +1,93 @@
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::task::{ready, Context, Poll};
+use tokio::io::unix::AsyncFd;
+
+/// Non-blocking eventfd, that is registered with Tokio's reactor.
+#[derive(Debug)]
+pub(crate) struct EventFd {
+    fd: AsyncFd<OwnedFd>,
+}
+
+impl EventFd {
+    pub fn create() -> IoResult<OwnedFd> {
+        // SAFETY: eventfd has no safety requirements.
+        let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+
+        if fd >= 0 {
+            // SAFETY: fd is a newly created and valid file descriptor.
+            Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+        } else {
+            Err(IoError::last_os_error())
+        }
+    }
+
+    pub fn new(fd: OwnedFd) -> IoResult<Self> {
+        // The O_NONBLOCK flag is shared between all duplicates of a file descriptor. It is set again
+        // here nonetheless, because the fd may have been received from an untrusted peer.
+        set_nonblocking(fd.as_raw_fd())?;
+        AsyncFd::new(fd).map(|fd| Self { fd })
+    }
+
+    /// Wakes up the side, that waits on this eventfd.
+    pub fn signal(&self) -> IoResult<()> {
+        let buf = 1u64.to_ne_bytes();
+
+        // SAFETY: buf is valid for reads of 8 bytes.
+        let res = unsafe { libc::write(self.fd.as_raw_fd(), buf.as_ptr().cast(), buf.len()) };
+
+        if res >= 0 {
+            Ok(())
+        } else {
+            let err = IoError::last_os_error();
+
+            // The counter would overflow. The other side will be woken up in any case.
+            if err.kind() == IoErrorKind::WouldBlock {
+                Ok(())
+            } else {
+                Err(err)
+            }
+        }
+    }
+
+    /// Waits until the eventfd has been signaled and resets it.
+    pub fn poll_wait(&self, cx: &mut Context) -> Poll<IoResult<()>> {
+        loop {
+            let mut guard = ready!(self.fd.poll_read_ready(cx))?;
+
+            match guard.try_io(|fd| read(fd.as_raw_fd())) {
+                Ok(res) => return Poll::Ready(res),
+                Err(_) => continue,
+            }
+        }
+    }
+}
+
+fn read(fd: RawFd) -> IoResult<()> {
+    let mut buf = [0; 8];
+
+    // SAFETY: buf is valid for writes of 8 bytes.
+    let res = unsafe { libc::read(fd, buf.as_mut_ptr().cast(), buf.len()) };
+
+    if res >= 0 {
+        Ok(())
+    } else {
+        Err(IoError::last_os_error())
+    }
+}
+
+fn set_nonblocking(fd: RawFd) -> IoResult<()> {
+    // SAFETY: fcntl with F_GETFL has no safety requirements.
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    if flags < 0 {
+        return Err(IoError::last_os_error());
+    }
+
+    // SAFETY: fcntl with F_SETFL has no safety requirements.
+    let res = unsafe { libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) };
+    if res < 0 {
+        return Err(IoError::last_os_error());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
+use std::mem;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::ptr;
+use tokio::io::Interest;
+use tokio::net::UnixStream;
+
+/// Number of file descriptors, that are passed during the handshake.
+///
+/// These are the memfd of the region, followed by the data and space eventfds of ring 0 and ring 1.
+pub(crate) const NUM_FDS: usize = 5;
+
+/// Single byte, that accompanies the file descriptors.
+const HANDSHAKE_BYTE: u8 = 0xa1;
+
+/// Buffer for control messages, that is suitably aligned for `cmsghdr`.
+#[repr(C, align(8))]
+struct CmsgBuf([u8; 64]);
+
+pub(crate) async fn send(sock: &UnixStream, fds: [&OwnedFd; NUM_FDS]) -> IoResult<()> {
+    let fds = fds.map(AsRawFd::as_raw_fd);
+
+    loop {
+        sock.writable().await?;
+
+        match sock.try_io(Interest::WRITABLE, || send_fds(sock.as_raw_fd(), &fds)) {
+            Ok(()) => break Ok(()),
+            Err(e) if e.kind() == IoErrorKind::WouldBlock => continue,
+            Err(e) => break Err(e),
+        }
+    }
+}
+
+pub(crate) async fn recv(sock: &UnixStream) -> IoResult<Option<[OwnedFd; NUM_FDS]>> {
+    loop {
+        sock.readable().await?;
+
+        match sock.try_io(Interest::READABLE, || recv_fds(sock.as_raw_fd())) {
+            Ok(fds) => break Ok(fds),
+            Err(e) if e.kind() == IoErrorKind::WouldBlock => continue,
+            Err(e) => break Err(e),
+        }
+    }
+}
+
+fn send_fds(sock: RawFd, fds: &[RawFd; NUM_FDS]) -> IoResult<()> {
+    let mut data = [HANDSHAKE_BYTE];
+    let mut iov = libc::iovec {
+        iov_base: data.as_mut_ptr().cast(),
+        iov_len: data.len(),
+    };
+
+    let fds_len = mem::size_of_val(fds) as u32;
+    let mut cmsg_buf = CmsgBuf([0; 64]);
+
+    // SAFETY: CMSG_SPACE has no safety requirements.
+    let cmsg_space = unsafe { libc::CMSG_SPACE(fds_len) } as usize;
+    assert!(cmsg_space <= cmsg_buf.0.len());
+
+    // SAFETY: All-zero is a valid msghdr.
+    let mut msg = unsafe { mem::zeroed::<libc::msghdr>() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = cmsg_buf.0.as_mut_ptr().cast();
+    msg.msg_controllen = cmsg_space as _;
+
+    // SAFETY: msg_control points to a buffer large enough for one control message with fds.
+    unsafe {
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(fds_len) as _;
+        ptr::copy_nonoverlapping(fds.as_ptr(), libc::CMSG_DATA(cmsg).cast(), fds.len());
+    }
+
+    // SAFETY: msg and all buffers it points to are valid.
+    let res = unsafe { libc::sendmsg(sock, &msg, libc::MSG_NOSIGNAL) };
+
+    match res {
+        1 => Ok(()),
+        0 => Err(IoErrorKind::WriteZero.into()),
+        _ => Err(IoError::last_os_error()),
+    }
+}
+
+fn recv_fds(sock: RawFd) -> IoResult<Option<[OwnedFd; NUM_FDS]>> {
+    let mut data = [0];
+    let mut iov = libc::iovec {
+        iov_base: data.as_mut_ptr().cast(),
+        iov_len: data.len(),
+    };
+
+    let mut cmsg_buf = CmsgBuf([0; 64]);
+
+    // SAFETY: All-zero is a valid msghdr.
+    let mut msg = unsafe { mem::zeroed::<libc::msghdr>() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = cmsg_buf.0.as_mut_ptr().cast();
+    msg.msg_controllen = cmsg_buf.0.len() as _;
+
+    // SAFETY: msg and all buffers it points to are valid.
+    let res = unsafe { libc::recvmsg(sock, &mut msg, libc::MSG_CMSG_CLOEXEC) };
+
+    match res {
+        1 => {}
+        0 => return Err(IoErrorKind::UnexpectedEof.into()),
+        _ => return Err(IoError::last_os_error()),
+    }
+
+    // Take ownership of all received file descriptors first, such that they are closed if the
+    // handshake turns out to be invalid.
+    let mut fds = Vec::new();
+
+    // SAFETY: The kernel has filled in the control messages in msg_control.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+
+        while !cmsg.is_null() {
+            if ((*cmsg).cmsg_level == libc::SOL_SOCKET) && ((*cmsg).cmsg_type == libc::SCM_RIGHTS) {
+                let data = libc::CMSG_DATA(cmsg);
+                let len = (*cmsg).cmsg_len as usize - (data as usize - cmsg as usize);
+
+                for i in 0..(len / mem::size_of::<RawFd>()) {
+                    let fd = data.cast::<RawFd>().add(i).read_unaligned();
+                    fds.push(OwnedFd::from_raw_fd(fd));
+                }
+            }
+
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+
+    if (data[0] != HANDSHAKE_BYTE) || ((msg.msg_flags & libc::MSG_CTRUNC) != 0) {
+        return Ok(None);
+    }
+
+    Ok(fds.try_into().ok())
+}
@@ -0,0 +1,58 @@
+//! Shared-memory transport for Aldrin.
+//!
+//! This crate provides [`ShmTransport`], an [`AsyncTransport`](aldrin_core::transport::AsyncTransport)
+//! for clients and brokers running on the same Linux host. Messages are exchanged through two ring
+//! buffers in a shared memory region, one for each direction. Wakeups are signaled with eventfds
+//! and only when the other side is actually waiting.
+//!
+//! The transport avoids the kernel, but it is not zero-copy. The sender serializes each message
+//! into a local buffer and copies it into the ring. The receiver copies it out of the ring again
+//! before deserializing it, such that the space in the ring can be reused immediately.
+//!
+//! Connections are set up over a Unix domain socket, which is called the bootstrap socket. One side
+//! creates the shared memory region and passes it, together with the eventfds, to the other side.
+//! The bootstrap socket is then kept open for the lifetime of the transport, so that both sides
+//! notice when the other one goes away.
+//!
+//! Brokers typically accept connections with a [`ShmListener`], while clients use [`connect`].
+//! Both sides can also be set up on already connected sockets with [`ShmTransport::create`] and
+//! [`ShmTransport::open`].
+//!
+//! This crate requires a Tokio runtime with IO enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use aldrin_shm::ShmTransport;
+//! use tokio::net::UnixStream;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), aldrin_shm::ShmError> {
+//! let (broker_sock, client_sock) = UnixStream::pair()?;
+//!
+//! let (broker_transport, client_transport) = tokio::try_join!(
+//!     ShmTransport::create(broker_sock, 64 * 1024),
+//!     ShmTransport::open(client_sock),
+//! )?;
+//!
+//! assert_eq!(broker_transport.capacity(), client_transport.capacity());
+//! # Ok(())
+//! # }
+//! ```
+
+#![cfg(target_os = "linux")]
+#![deny(missing_debug_implementations)]
+#![deny(missing_docs)]
+
+mod error;
+mod event_fd;
+mod handshake;
+mod listener;
+mod region;
+#[cfg(test)]
+mod test;
+mod transport;
+
+pub use error::ShmError;
+pub use listener::{connect, ShmListener};
+pub use transport::{ShmTransport, DEFAULT_CAPACITY};
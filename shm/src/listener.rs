@@ -0,0 +1,80 @@
+use crate::{ShmError, ShmTransport, DEFAULT_CAPACITY};
+use std::io::Result as IoResult;
+use std::path::Path;
+use tokio::net::{UnixListener, UnixStream};
+
+/// Listener for shared memory connections.
+///
+/// The listener accepts connections on a Unix domain socket and creates a new shared memory region
+/// for each of them. Clients connect with [`connect`].
+///
+/// # Examples
+///
+/// ```
+/// use aldrin_shm::ShmListener;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), aldrin_shm::ShmError> {
+/// # let dir = std::env::temp_dir().join(format!("aldrin-shm-doc-{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir)?;
+/// # let path = dir.join("broker.sock");
+/// let listener = ShmListener::bind(&path)?;
+///
+/// let (transport, client) = tokio::try_join!(listener.accept(), aldrin_shm::connect(&path))?;
+///
+/// assert_eq!(transport.capacity(), client.capacity());
+/// # std::fs::remove_dir_all(&dir)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ShmListener {
+    listener: UnixListener,
+    capacity: usize,
+}
+
+impl ShmListener {
+    /// Binds a new listener to a path.
+    ///
+    /// Transports are created with a capacity of [`DEFAULT_CAPACITY`] bytes per ring buffer.
+    pub fn bind(path: impl AsRef<Path>) -> IoResult<Self> {
+        UnixListener::bind(path).map(Self::from_listener)
+    }
+
+    /// Creates a listener from a bound Unix domain socket.
+    pub fn from_listener(listener: UnixListener) -> Self {
+        Self {
+            listener,
+            capacity: DEFAULT_CAPACITY,
+        }
+    }
+
+    /// Returns the capacity of the ring buffers of newly accepted transports.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the capacity of the ring buffers of newly accepted transports.
+    ///
+    /// See [`ShmTransport::create`] for how the capacity is adjusted.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+    }
+
+    /// Accepts a new connection.
+    pub async fn accept(&self) -> Result<ShmTransport, ShmError> {
+        let (sock, _) = self.listener.accept().await?;
+        ShmTransport::create(sock, self.capacity).await
+    }
+
+    /// Returns the underlying Unix domain socket.
+    pub fn listener(&self) -> &UnixListener {
+        &self.listener
+    }
+}
+
+/// Connects to a [`ShmListener`].
+pub async fn connect(path: impl AsRef<Path>) -> Result<ShmTransport, ShmError> {
+    let sock = UnixStream::connect(path).await?;
+    ShmTransport::open(sock).await
+}
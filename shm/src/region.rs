@@ -0,0 +1,353 @@
+use std::io::{Error as IoError, Result as IoResult};
+use std::mem::{self, MaybeUninit};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::ptr::{self, NonNull};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+const MAGIC: u64 = u64::from_le_bytes(*b"ALDRNSHM");
+const VERSION: u32 = 1;
+
+pub(crate) const MIN_CAPACITY: usize = 4 * 1024;
+pub(crate) const MAX_CAPACITY: usize = 1 << 30;
+
+/// Layout of the beginning of the shared memory region.
+///
+/// The data of both rings follows directly after this, starting with ring 0.
+#[repr(C)]
+struct Layout {
+    magic: u64,
+    version: u32,
+    capacity: u32,
+    rings: [RingHeader; 2],
+}
+
+#[repr(C, align(64))]
+struct RingHeader {
+    /// Position of the reader.
+    head: AtomicU32,
+
+    /// Position of the writer.
+    tail: AtomicU32,
+
+    reader_waiting: AtomicU32,
+    writer_waiting: AtomicU32,
+}
+
+/// Memory mapped shared memory region.
+#[derive(Debug)]
+pub(crate) struct Region {
+    ptr: NonNull<u8>,
+    len: usize,
+    capacity: u32,
+}
+
+// SAFETY: Region owns its mapping and all access to it goes through atomics or raw pointers.
+unsafe impl Send for Region {}
+unsafe impl Sync for Region {}
+
+impl Region {
+    /// Creates a new region, with rings of at least `capacity` bytes each.
+    pub fn create(capacity: usize) -> IoResult<(Self, OwnedFd)> {
+        let capacity = capacity
+            .clamp(MIN_CAPACITY, MAX_CAPACITY)
+            .next_power_of_two();
+        let len = mem::size_of::<Layout>() + 2 * capacity;
+
+        // SAFETY: The name is a valid nul-terminated string.
+        let fd = unsafe {
+            libc::memfd_create(
+                b"aldrin-shm\0".as_ptr().cast(),
+                libc::MFD_CLOEXEC | libc::MFD_ALLOW_SEALING,
+            )
+        };
+        if fd < 0 {
+            return Err(IoError::last_os_error());
+        }
+
+        // SAFETY: fd is a newly created and valid file descriptor.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // SAFETY: ftruncate has no safety requirements.
+        let res = unsafe { libc::ftruncate(fd.as_raw_fd(), len as libc::off_t) };
+        if res < 0 {
+            return Err(IoError::last_os_error());
+        }
+
+        // Sealing the size prevents the peer from truncating the memfd, which would cause SIGBUS on
+        // our side.
+        // SAFETY: fcntl with F_ADD_SEALS has no safety requirements.
+        let res = unsafe {
+            libc::fcntl(
+                fd.as_raw_fd(),
+                libc::F_ADD_SEALS,
+                libc::F_SEAL_SHRINK | libc::F_SEAL_GROW | libc::F_SEAL_SEAL,
+            )
+        };
+        if res < 0 {
+            return Err(IoError::last_os_error());
+        }
+
+        let ptr = map(&fd, len)?;
+        let layout = ptr.cast::<Layout>().as_ptr();
+
+        // SAFETY: The mapping is large enough for Layout, suitably aligned and not yet shared. The
+        // memory of a new memfd is zeroed, which is a valid initial state for the rings.
+        unsafe {
+            ptr::addr_of_mut!((*layout).magic).write(MAGIC);
+            ptr::addr_of_mut!((*layout).version).write(VERSION);
+            ptr::addr_of_mut!((*layout).capacity).write(capacity as u32);
+        }
+
+        let region = Self {
+            ptr,
+            len,
+            capacity: capacity as u32,
+        };
+
+        Ok((region, fd))
+    }
+
+    /// Opens a region, that has been created by the peer.
+    ///
+    /// Returns `None` if the region is invalid.
+    pub fn open(fd: &OwnedFd) -> IoResult<Option<Self>> {
+        // SAFETY: stat is valid for writes.
+        let mut stat = unsafe { mem::zeroed::<libc::stat>() };
+
+        // SAFETY: fstat has no further safety requirements.
+        let res = unsafe { libc::fstat(fd.as_raw_fd(), &mut stat) };
+        if res < 0 {
+            return Err(IoError::last_os_error());
+        }
+
+        // The peer could truncate the memfd at any time if it weren't sealed.
+        // SAFETY: fcntl with F_GET_SEALS has no safety requirements.
+        let seals = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GET_SEALS) };
+        if seals < 0 {
+            return Err(IoError::last_os_error());
+        }
+        if (seals & libc::F_SEAL_SHRINK) == 0 {
+            return Ok(None);
+        }
+
+        let len = stat.st_size as usize;
+        if len < mem::size_of::<Layout>() {
+            return Ok(None);
+        }
+
+        let ptr = map(fd, len)?;
+        let layout = ptr.cast::<Layout>().as_ptr();
+
+        // SAFETY: The mapping is large enough for Layout and suitably aligned. The header is not
+        // modified after the region has been shared.
+        let (magic, version, capacity) = unsafe {
+            (
+                ptr::addr_of!((*layout).magic).read(),
+                ptr::addr_of!((*layout).version).read(),
+                ptr::addr_of!((*layout).capacity).read(),
+            )
+        };
+
+        let region = Self { ptr, len, capacity };
+
+        let valid = (magic == MAGIC)
+            && (version == VERSION)
+            && capacity.is_power_of_two()
+            && (capacity as usize >= MIN_CAPACITY)
+            && (capacity as usize <= MAX_CAPACITY)
+            && (len == mem::size_of::<Layout>() + 2 * capacity as usize);
+
+        if valid {
+            Ok(Some(region))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity as usize
+    }
+
+    /// Returns one of the two rings.
+    ///
+    /// # Safety
+    ///
+    /// Each ring must have at most one reader and one writer across both sides of the region.
+    pub unsafe fn ring(&self, index: usize) -> Ring {
+        debug_assert!(index < 2);
+        let layout = self.ptr.cast::<Layout>().as_ptr();
+
+        // SAFETY: The region was validated to contain Layout followed by the data of 2 rings.
+        unsafe {
+            Ring {
+                header: ptr::addr_of!((*layout).rings[index]),
+                data: self
+                    .ptr
+                    .as_ptr()
+                    .add(mem::size_of::<Layout>() + index * self.capacity as usize),
+                capacity: self.capacity,
+            }
+        }
+    }
+}
+
+impl Drop for Region {
+    fn drop(&mut self) {
+        // SAFETY: ptr and len describe a mapping, that was created in map.
+        unsafe {
+            libc::munmap(self.ptr.as_ptr().cast(), self.len);
+        }
+    }
+}
+
+fn map(fd: &OwnedFd, len: usize) -> IoResult<NonNull<u8>> {
+    // SAFETY: A new mapping doesn't alias any existing memory.
+    let ptr = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED,
+            fd.as_raw_fd(),
+            0,
+        )
+    };
+
+    if ptr == libc::MAP_FAILED {
+        Err(IoError::last_os_error())
+    } else {
+        Ok(NonNull::new(ptr.cast()).unwrap())
+    }
+}
+
+/// Single-producer single-consumer ring buffer of bytes inside a [`Region`].
+///
+/// Positions are free-running counters, that wrap around at `u32::MAX`. Because the capacity is a
+/// power of two, the offset into the data is simply the position modulo the capacity.
+///
+/// The waiting flags tell the other side whether it must signal the corresponding eventfd. Setting
+/// a flag and checking the positions afterwards (and vice versa) is done with sequentially
+/// consistent atomics, such that no wakeup is ever lost.
+#[derive(Debug)]
+pub(crate) struct Ring {
+    header: *const RingHeader,
+    data: *mut u8,
+    capacity: u32,
+}
+
+// SAFETY: Ring points into a Region, which must outlive it.
+unsafe impl Send for Ring {}
+unsafe impl Sync for Ring {}
+
+impl Ring {
+    fn header(&self) -> &RingHeader {
+        // SAFETY: The header is valid for as long as the region is mapped.
+        unsafe { &*self.header }
+    }
+
+    /// Reads as many bytes as are available and fit into `dst`.
+    ///
+    /// The bytes are copied, because the writer may reuse the space as soon as this returns.
+    ///
+    /// Returns the number of bytes, that have been read and initialized.
+    pub fn read(&self, dst: &mut [MaybeUninit<u8>]) -> usize {
+        let header = self.header();
+        let head = header.head.load(Ordering::Relaxed);
+        let tail = header.tail.load(Ordering::Acquire);
+
+        // A misbehaving peer could set the tail to anything.
+        let available = tail.wrapping_sub(head).min(self.capacity) as usize;
+        let len = available.min(dst.len());
+
+        if len > 0 {
+            // SAFETY: The writer will not touch these bytes until head is advanced.
+            unsafe {
+                self.copy(head, len, |src, off, n| {
+                    ptr::copy_nonoverlapping(src, dst.as_mut_ptr().add(off).cast(), n)
+                });
+            }
+
+            header
+                .head
+                .store(head.wrapping_add(len as u32), Ordering::SeqCst);
+        }
+
+        len
+    }
+
+    /// Writes as many bytes of `src` as there is free space.
+    ///
+    /// Returns the number of bytes, that have been written.
+    pub fn write(&self, src: &[u8]) -> usize {
+        let header = self.header();
+        let head = header.head.load(Ordering::Acquire);
+        let tail = header.tail.load(Ordering::Relaxed);
+
+        let used = tail.wrapping_sub(head).min(self.capacity);
+        let len = ((self.capacity - used) as usize).min(src.len());
+
+        if len > 0 {
+            // SAFETY: The reader will not touch these bytes until tail is advanced.
+            unsafe {
+                self.copy(tail, len, |dst, off, n| {
+                    ptr::copy_nonoverlapping(src.as_ptr().add(off), dst, n)
+                });
+            }
+
+            header
+                .tail
+                .store(tail.wrapping_add(len as u32), Ordering::SeqCst);
+        }
+
+        len
+    }
+
+    /// Calls `f` with up to two contiguous parts of the data, starting at position `pos`.
+    ///
+    /// `f` gets the pointer into the ring, the offset into the user buffer and the length.
+    unsafe fn copy(&self, pos: u32, len: usize, mut f: impl FnMut(*mut u8, usize, usize)) {
+        let start = (pos & (self.capacity - 1)) as usize;
+        let first = len.min(self.capacity as usize - start);
+
+        // SAFETY: start + first is at most capacity and the remainder wraps around to the start.
+        unsafe {
+            f(self.data.add(start), 0, first);
+        }
+
+        if first < len {
+            f(self.data, first, len - first);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let header = self.header();
+        header.head.load(Ordering::SeqCst) == header.tail.load(Ordering::SeqCst)
+    }
+
+    pub fn is_full(&self) -> bool {
+        let header = self.header();
+        let head = header.head.load(Ordering::SeqCst);
+        let tail = header.tail.load(Ordering::SeqCst);
+        tail.wrapping_sub(head) >= self.capacity
+    }
+
+    pub fn set_reader_waiting(&self, waiting: bool) {
+        self.header()
+            .reader_waiting
+            .store(waiting as u32, Ordering::SeqCst);
+    }
+
+    pub fn reader_waiting(&self) -> bool {
+        self.header().reader_waiting.load(Ordering::SeqCst) != 0
+    }
+
+    pub fn set_writer_waiting(&self, waiting: bool) {
+        self.header()
+            .writer_waiting
+            .store(waiting as u32, Ordering::SeqCst);
+    }
+
+    pub fn writer_waiting(&self) -> bool {
+        self.header().writer_waiting.load(Ordering::SeqCst) != 0
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{ShmListener, ShmTransport};
+use aldrin::Client;
+use aldrin_broker::Broker;
+use aldrin_core::message::{CallFunction, Message, Sync};
+use aldrin_core::transport::AsyncTransportExt;
+use aldrin_core::{CallPriority, ObjectUuid, SerializedValue, ServiceCookie};
+use tokio::net::UnixStream;
+
+async fn pair(capacity: usize) -> (ShmTransport, ShmTransport) {
+    let (sock1, sock2) = UnixStream::pair().unwrap();
+
+    tokio::try_join!(
+        ShmTransport::create(sock1, capacity),
+        ShmTransport::open(sock2),
+    )
+    .unwrap()
+}
+
+fn call(serial: u32) -> Message {
+    let value = SerializedValue::serialize(&vec![serial as u8; 20 * 1024]).unwrap();
+
+    Message::CallFunction(CallFunction {
+        serial,
+        service_cookie: ServiceCookie::NIL,
+        function: serial,
+        value,
+        trace_context: None,
+        priority: CallPriority::Normal,
+    })
+}
+
+#[tokio::test]
+async fn messages_larger_than_capacity() {
+    const NUM: u32 = 100;
+
+    let (mut t1, mut t2) = pair(0).await;
+    assert_eq!(t1.capacity(), 4096);
+
+    let send = |mut t: ShmTransport| async move {
+        for serial in 0..NUM {
+            t.send_and_flush(call(serial)).await.unwrap();
+        }
+
+        t
+    };
+
+    let receive = |mut t: ShmTransport| async move {
+        for serial in 0..NUM {
+            assert_eq!(t.receive().await.unwrap(), call(serial));
+        }
+
+        t
+    };
+
+    (t1, t2) = tokio::join!(send(t1), receive(t2));
+    tokio::join!(receive(t1), send(t2));
+}
+
+#[tokio::test]
+async fn disconnect() {
+    let (mut t1, mut t2) = pair(0).await;
+
+    t1.send_and_flush(Sync { serial: 1 }).await.unwrap();
+    drop(t1);
+
+    assert_eq!(
+        t2.receive().await.unwrap(),
+        Message::Sync(Sync { serial: 1 })
+    );
+    t2.receive().await.unwrap_err();
+    t2.send_and_flush(Sync { serial: 2 }).await.unwrap();
+}
+
+#[tokio::test]
+async fn broker_and_client() {
+    let dir = std::env::temp_dir().join(format!("aldrin-shm-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("broker.sock");
+
+    let broker = Broker::new();
+    let mut handle = broker.handle().clone();
+    let broker = tokio::spawn(broker.run());
+
+    let listener = ShmListener::bind(&path).unwrap();
+    let (transport, client) = tokio::try_join!(listener.accept(), crate::connect(&path)).unwrap();
+
+    let (conn, client) = tokio::join!(handle.connect(transport), Client::connect(client));
+    let conn = tokio::spawn(conn.unwrap().run());
+    let client = client.unwrap();
+    let client_handle = client.handle().clone();
+    let client = tokio::spawn(client.run());
+
+    let obj = client_handle
+        .create_object(ObjectUuid::new_v4())
+        .await
+        .unwrap();
+    obj.destroy().await.unwrap();
+
+    client_handle.shutdown();
+    client.await.unwrap().unwrap();
+    conn.await.unwrap().unwrap();
+
+    handle.shutdown().await;
+    broker.await.unwrap();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
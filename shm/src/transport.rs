@@ -0,0 +1,240 @@
+use crate::event_fd::EventFd;
+use crate::handshake;
+use crate::region::{Region, Ring};
+use crate::ShmError;
+use aldrin_core::message::{Message, MessageOps, Packetizer};
+use aldrin_core::transport::AsyncTransport;
+use bytes::{Buf, BytesMut};
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+use std::os::fd::OwnedFd;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::net::UnixStream;
+
+/// Default capacity of each ring buffer in bytes.
+pub const DEFAULT_CAPACITY: usize = 1024 * 1024;
+
+/// Transport over shared memory.
+///
+/// See the [crate-level documentation](crate) for more information.
+#[derive(Debug)]
+pub struct ShmTransport {
+    rx: Ring,
+    tx: Ring,
+
+    /// Signaled by the peer when `rx` has new data.
+    rx_data: EventFd,
+
+    /// Signaled by us when `rx` has free space.
+    rx_space: EventFd,
+
+    /// Signaled by us when `tx` has new data.
+    tx_data: EventFd,
+
+    /// Signaled by the peer when `tx` has free space.
+    tx_space: EventFd,
+
+    sock: UnixStream,
+    packetizer: Packetizer,
+    write_buf: BytesMut,
+    region: Region,
+}
+
+impl ShmTransport {
+    /// Creates a new shared memory region and passes it to the peer.
+    ///
+    /// The capacity of each of the two ring buffers is at least `capacity` bytes. It is rounded up
+    /// to the next power of two and clamped to the range from 4 KiB to 1 GiB.
+    ///
+    /// The peer must call [`open`](Self::open) on the other end of `sock`.
+    pub async fn create(sock: UnixStream, capacity: usize) -> Result<Self, ShmError> {
+        let (region, memfd) = Region::create(capacity)?;
+
+        let fds = [
+            EventFd::create()?,
+            EventFd::create()?,
+            EventFd::create()?,
+            EventFd::create()?,
+        ];
+
+        handshake::send(&sock, [&memfd, &fds[0], &fds[1], &fds[2], &fds[3]]).await?;
+
+        // The creator writes into ring 0 and reads from ring 1.
+        let [tx_data, tx_space, rx_data, rx_space] = fds;
+        Self::new(sock, region, 1, [rx_data, rx_space, tx_data, tx_space])
+    }
+
+    /// Opens a shared memory region, that was created by the peer.
+    ///
+    /// The peer must call [`create`](Self::create) on the other end of `sock`.
+    pub async fn open(sock: UnixStream) -> Result<Self, ShmError> {
+        let [memfd, fds @ ..] = handshake::recv(&sock)
+            .await?
+            .ok_or(ShmError::InvalidHandshake)?;
+
+        let region = Region::open(&memfd)?.ok_or(ShmError::InvalidHandshake)?;
+
+        // The opener writes into ring 1 and reads from ring 0.
+        let [rx_data, rx_space, tx_data, tx_space] = fds;
+        Self::new(sock, region, 0, [rx_data, rx_space, tx_data, tx_space])
+    }
+
+    fn new(
+        sock: UnixStream,
+        region: Region,
+        rx: usize,
+        fds: [OwnedFd; 4],
+    ) -> Result<Self, ShmError> {
+        let [rx_data, rx_space, tx_data, tx_space] = fds;
+
+        // SAFETY: Each side reads from one ring and writes into the other.
+        let (rx, tx) = unsafe { (region.ring(rx), region.ring(1 - rx)) };
+
+        Ok(Self {
+            rx,
+            tx,
+            rx_data: EventFd::new(rx_data)?,
+            rx_space: EventFd::new(rx_space)?,
+            tx_data: EventFd::new(tx_data)?,
+            tx_space: EventFd::new(tx_space)?,
+            sock,
+            packetizer: Packetizer::new(),
+            write_buf: BytesMut::new(),
+            region,
+        })
+    }
+
+    /// Returns the capacity of each ring buffer in bytes.
+    pub fn capacity(&self) -> usize {
+        self.region.capacity()
+    }
+
+    /// Checks whether the peer has closed the bootstrap socket.
+    fn poll_hangup(&self, cx: &mut Context) -> Poll<Result<(), ShmError>> {
+        loop {
+            ready!(self.sock.poll_read_ready(cx))?;
+
+            let mut buf = [0];
+            match self.sock.try_read(&mut buf) {
+                Ok(0) => return Poll::Ready(Ok(())),
+                Ok(_) => return Poll::Ready(Err(ShmError::InvalidHandshake)),
+                Err(e) if e.kind() == IoErrorKind::WouldBlock => continue,
+                Err(e) => return Poll::Ready(Err(e.into())),
+            }
+        }
+    }
+}
+
+impl AsyncTransport for ShmTransport {
+    type Error = ShmError;
+
+    fn receive_poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Message, ShmError>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(buf) = this.packetizer.next_message() {
+                return Poll::Ready(
+                    Message::deserialize_message(buf).map_err(ShmError::Deserialize),
+                );
+            }
+
+            let len = this.rx.read(this.packetizer.spare_capacity_mut());
+            if len > 0 {
+                // SAFETY: The first len bytes have been initialized.
+                unsafe {
+                    this.packetizer.bytes_written(len);
+                }
+
+                if this.rx.writer_waiting() {
+                    this.rx_space.signal()?;
+                }
+
+                continue;
+            }
+
+            this.rx.set_reader_waiting(true);
+            if !this.rx.is_empty() {
+                this.rx.set_reader_waiting(false);
+                continue;
+            }
+
+            if let Poll::Ready(res) = this.rx_data.poll_wait(cx) {
+                this.rx.set_reader_waiting(false);
+                res?;
+                continue;
+            }
+
+            if let Poll::Ready(res) = this.poll_hangup(cx) {
+                res?;
+
+                // Data written before the peer went away must still be received.
+                if this.rx.is_empty() {
+                    return Poll::Ready(Err(IoError::from(IoErrorKind::UnexpectedEof).into()));
+                } else {
+                    continue;
+                }
+            }
+
+            return Poll::Pending;
+        }
+    }
+
+    fn send_poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), ShmError>> {
+        if self.write_buf.len() >= self.capacity() {
+            self.send_poll_flush(cx)
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn send_start(self: Pin<&mut Self>, msg: Message) -> Result<(), ShmError> {
+        let this = self.get_mut();
+        let msg = msg.serialize_message()?;
+
+        if this.write_buf.is_empty() {
+            this.write_buf = msg;
+        } else {
+            this.write_buf.extend_from_slice(&msg);
+        }
+
+        Ok(())
+    }
+
+    fn send_poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), ShmError>> {
+        let this = self.get_mut();
+
+        while !this.write_buf.is_empty() {
+            let len = this.tx.write(&this.write_buf);
+            if len > 0 {
+                this.write_buf.advance(len);
+
+                if this.tx.reader_waiting() {
+                    this.tx_data.signal()?;
+                }
+
+                continue;
+            }
+
+            this.tx.set_writer_waiting(true);
+            if !this.tx.is_full() {
+                this.tx.set_writer_waiting(false);
+                continue;
+            }
+
+            if let Poll::Ready(res) = this.tx_space.poll_wait(cx) {
+                this.tx.set_writer_waiting(false);
+                res?;
+                continue;
+            }
+
+            if let Poll::Ready(res) = this.poll_hangup(cx) {
+                res?;
+                return Poll::Ready(Err(IoError::from(IoErrorKind::BrokenPipe).into()));
+            }
+
+            return Poll::Pending;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
@@ -15,12 +15,17 @@ mod test;
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
+pub mod traffic;
+pub mod transcript;
+
 use aldrin::{Client, Handle};
 use aldrin_broker::{Broker, BrokerHandle, Connection, ConnectionHandle};
-use aldrin_core::channel::{self, Disconnected};
+use aldrin_core::channel::{self, BoundedMetrics, Disconnected};
 use aldrin_core::transport::{AsyncTransportExt, BoxedTransport};
 use futures_util::future;
 use std::ops::{Deref, DerefMut};
+use traffic::{TrafficRecorder, TrafficTap};
+use transcript::Transcript;
 
 // For tests directly in aldrin_broker and aldrin.
 #[doc(hidden)]
@@ -74,9 +79,44 @@ impl TestBroker {
     /// Add a new client to the broker.
     pub async fn add_client(&mut self) -> TestClient {
         let (t1, t2) = channel::unbounded();
+        self.connect_client(t1.boxed(), t2.boxed(), None).await
+    }
+
+    /// Add a new client to the broker, which is connected with a [`Bounded`](channel::Bounded)
+    /// transport.
+    ///
+    /// The [`BoundedMetrics`] of both directions are available with
+    /// [`TestClient::client_metrics`] and [`TestClient::connection_metrics`].
+    pub async fn add_bounded_client(&mut self, fifo_size: usize) -> TestClient {
+        let (t1, t2) = channel::bounded(fifo_size);
+        let metrics = (t1.metrics(), t2.metrics());
+        self.connect_client(t1.boxed(), t2.boxed(), Some(metrics))
+            .await
+    }
+
+    /// Add a new client to the broker, whose interaction is recorded in a [`Transcript`].
+    ///
+    /// All lines recorded for this client are prefixed with `label`. See the
+    /// [`transcript` module](transcript) for more information.
+    pub async fn add_recorded_client(
+        &mut self,
+        transcript: &Transcript,
+        label: impl Into<String>,
+    ) -> TestClient {
+        let (t1, t2) = channel::unbounded();
+        let t1 = transcript.record(label, t1);
+        self.connect_client(t1.boxed(), t2.boxed(), None).await
+    }
 
-        let client = Client::connect(t1.boxed());
-        let conn = self.handle.connect(t2.boxed());
+    async fn connect_client(
+        &mut self,
+        t1: BoxedTransport<'static, Disconnected>,
+        t2: BoxedTransport<'static, Disconnected>,
+        metrics: Option<(BoundedMetrics, BoundedMetrics)>,
+    ) -> TestClient {
+        let tap = TrafficTap::new();
+        let client = Client::connect(tap.wrap(t1).boxed());
+        let conn = self.handle.connect(t2);
 
         let (client, conn) = future::join(client, conn).await;
         let client = client.expect("client failed to connect");
@@ -84,7 +124,7 @@ impl TestBroker {
         let conn = conn.expect("connection failed to establish");
         let connection_handle = conn.handle().clone();
 
-        TestClient::new(handle, connection_handle, client, conn)
+        TestClient::new(handle, connection_handle, client, conn, metrics, tap)
     }
 }
 
@@ -123,6 +163,8 @@ pub struct TestClient {
     connection_handle: ConnectionHandle,
     client: Option<Client<BoxedTransport<'static, Disconnected>>>,
     conn: Option<Connection<BoxedTransport<'static, Disconnected>>>,
+    metrics: Option<(BoundedMetrics, BoundedMetrics)>,
+    tap: TrafficTap,
 }
 
 impl TestClient {
@@ -131,12 +173,16 @@ impl TestClient {
         connection_handle: ConnectionHandle,
         client: Client<BoxedTransport<'static, Disconnected>>,
         conn: Connection<BoxedTransport<'static, Disconnected>>,
+        metrics: Option<(BoundedMetrics, BoundedMetrics)>,
+        tap: TrafficTap,
     ) -> Self {
         Self {
             handle,
             connection_handle,
             client: Some(client),
             conn: Some(conn),
+            metrics,
+            tap,
         }
     }
 
@@ -150,6 +196,33 @@ impl TestClient {
         &self.connection_handle
     }
 
+    /// Returns the metrics of the messages sent by the client to the broker.
+    ///
+    /// This is only available for clients added with [`TestBroker::add_bounded_client`].
+    pub fn client_metrics(&self) -> Option<&BoundedMetrics> {
+        self.metrics.as_ref().map(|(client, _)| client)
+    }
+
+    /// Returns the metrics of the messages sent by the broker to the client.
+    ///
+    /// This is only available for clients added with [`TestBroker::add_bounded_client`].
+    pub fn connection_metrics(&self) -> Option<&BoundedMetrics> {
+        self.metrics.as_ref().map(|(_, conn)| conn)
+    }
+
+    /// Attaches a [`TrafficRecorder`] to the client.
+    ///
+    /// All messages sent and received by the client from now on are recorded. Any previously
+    /// attached recorder is replaced. See the [`traffic` module](traffic) for more information.
+    pub fn attach_traffic_recorder(&self, recorder: &TrafficRecorder) {
+        self.tap.attach(recorder);
+    }
+
+    /// Detaches the [`TrafficRecorder`] from the client.
+    pub fn detach_traffic_recorder(&self) {
+        self.tap.detach();
+    }
+
     /// Takes the `Client` out of this struct.
     ///
     /// After creating a [`TestClient`], the actual [`Client`] must be taken out and
@@ -3,16 +3,32 @@
 //! The types in this module are conceptually identical to the ones in the top-level crate, but are
 //! more convenient if you use Tokio, because they all automatically spawn the required tasks.
 
+mod link;
 #[cfg(test)]
 mod test;
 
+use crate::traffic::TrafficRecorder;
+use crate::transcript::Transcript;
 use aldrin::error::RunError;
+use aldrin::mock::Mock;
 use aldrin::Handle;
 use aldrin_broker::{BrokerHandle, ConnectionError, ConnectionHandle};
-use aldrin_core::channel::Disconnected;
+use aldrin_core::channel::{BoundedMetrics, Disconnected};
+use std::future::{self, Future};
+use std::mem;
 use std::ops::{Deref, DerefMut};
+use std::panic;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 
+pub use link::TestLink;
+
+type ClientJoinHandle = JoinHandle<Result<(), RunError<Disconnected>>>;
+type ConnectionJoinHandle = JoinHandle<Result<(), ConnectionError<Disconnected>>>;
+
 /// Tokio-based broker for use in tests.
 ///
 /// This type is a simple wrapper around [`aldrin_broker::Broker`] and
@@ -24,6 +40,7 @@ use tokio::task::JoinHandle;
 pub struct TestBroker {
     inner: crate::TestBroker,
     join: Option<JoinHandle<()>>,
+    supervised: Vec<(Handle, Arc<Mutex<Tasks>>)>,
 }
 
 impl TestBroker {
@@ -34,6 +51,7 @@ impl TestBroker {
         Self {
             join: Some(tokio::spawn(inner.take_broker().run())),
             inner,
+            supervised: Vec::new(),
         }
     }
 
@@ -53,6 +71,7 @@ impl TestBroker {
     /// This function will panic if the [`Broker`](aldrin_broker::Broker) task has already been
     /// joined or attempted to join (see notes above as well).
     pub async fn join(&mut self) {
+        self.join_supervised().await;
         self.inner.shutdown().await;
         self.join.take().expect("already joined").await.unwrap();
     }
@@ -68,6 +87,7 @@ impl TestBroker {
     /// This function will panic if the [`Broker`](aldrin_broker::Broker) task has already been
     /// joined or attempted to join (see notes above as well).
     pub async fn join_idle(&mut self) {
+        self.join_supervised().await;
         self.inner.shutdown_idle().await;
         self.join.take().expect("already joined").await.unwrap();
     }
@@ -77,6 +97,92 @@ impl TestBroker {
         let inner = self.inner.add_client().await;
         TestClient::new(inner)
     }
+
+    /// Creates a new `Client`, whose tasks are supervised by the broker.
+    ///
+    /// Like with [`add_client`](Self::add_client), the client and its connection are spawned
+    /// automatically. Additionally, the broker keeps track of both tasks. When the broker is joined
+    /// (see [`join`](Self::join) and [`join_idle`](Self::join_idle)), all supervised clients, that
+    /// have not been joined yet, are shut down and joined as well. Panics and errors of the tasks
+    /// are propagated, such that they fail the test.
+    ///
+    /// Supervised clients can thus be dropped at any time, without losing errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use aldrin_test::tokio::TestBroker;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut broker = TestBroker::new();
+    /// let client = broker.add_spawned_client().await;
+    ///
+    /// client.sync_broker().await.unwrap();
+    /// drop(client);
+    ///
+    /// // Shuts down and joins the client as well.
+    /// broker.join().await;
+    /// # }
+    /// ```
+    pub async fn add_spawned_client(&mut self) -> TestClient {
+        let client = self.add_client().await;
+
+        self.supervised
+            .push((client.handle().clone(), client.tasks.clone()));
+
+        client
+    }
+
+    async fn join_supervised(&mut self) {
+        for (handle, tasks) in mem::take(&mut self.supervised) {
+            handle.shutdown();
+
+            let (client, conn) = {
+                let mut tasks = tasks.lock().unwrap();
+                (tasks.client.take(), tasks.conn.take())
+            };
+
+            if let Some(client) = client {
+                client.await.unwrap().unwrap();
+            }
+
+            if let Some(conn) = conn {
+                conn.await.unwrap().unwrap();
+            }
+        }
+    }
+
+    /// Creates a new `Client`, which is connected with a [`Bounded`](aldrin_core::channel::Bounded)
+    /// transport.
+    ///
+    /// The [`BoundedMetrics`] of both directions are available with
+    /// [`TestClient::client_metrics`] and [`TestClient::connection_metrics`].
+    pub async fn add_bounded_client(&mut self, fifo_size: usize) -> TestClient {
+        let inner = self.inner.add_bounded_client(fifo_size).await;
+        TestClient::new(inner)
+    }
+
+    /// Creates a new `Client`, whose interaction is recorded in a [`Transcript`].
+    ///
+    /// All lines recorded for this client are prefixed with `label`. See the
+    /// [`transcript` module](crate::transcript) for more information.
+    pub async fn add_recorded_client(
+        &mut self,
+        transcript: &Transcript,
+        label: impl Into<String>,
+    ) -> TestClient {
+        let inner = self.inner.add_recorded_client(transcript, label).await;
+        TestClient::new(inner)
+    }
+
+    /// Links this broker to another one.
+    ///
+    /// All services are mirrored between the two brokers. The link can be partitioned and healed
+    /// to simulate an unreliable connection. See [`TestLink`] for more information.
+    pub async fn link(&mut self, other: &mut Self) -> TestLink {
+        TestLink::new(self, other).await
+    }
 }
 
 impl Default for TestBroker {
@@ -106,16 +212,19 @@ impl DerefMut for TestBroker {
 #[derive(Debug)]
 pub struct TestClient {
     inner: crate::TestClient,
-    client: Option<JoinHandle<Result<(), RunError<Disconnected>>>>,
-    conn: Option<JoinHandle<Result<(), ConnectionError<Disconnected>>>>,
+    tasks: Arc<Mutex<Tasks>>,
 }
 
 impl TestClient {
     fn new(mut inner: crate::TestClient) -> Self {
-        Self {
+        let tasks = Tasks {
             client: Some(tokio::spawn(inner.take_client().run())),
             conn: Some(tokio::spawn(inner.take_connection().run())),
+        };
+
+        Self {
             inner,
+            tasks: Arc::new(Mutex::new(tasks)),
         }
     }
 
@@ -129,6 +238,34 @@ impl TestClient {
         self.inner.connection()
     }
 
+    /// Returns the metrics of the messages sent by the client to the broker.
+    ///
+    /// This is only available for clients added with [`TestBroker::add_bounded_client`].
+    pub fn client_metrics(&self) -> Option<&BoundedMetrics> {
+        self.inner.client_metrics()
+    }
+
+    /// Returns the metrics of the messages sent by the broker to the client.
+    ///
+    /// This is only available for clients added with [`TestBroker::add_bounded_client`].
+    pub fn connection_metrics(&self) -> Option<&BoundedMetrics> {
+        self.inner.connection_metrics()
+    }
+
+    /// Attaches a [`TrafficRecorder`] to the client.
+    ///
+    /// All messages sent and received by the client from now on are recorded. Any previously
+    /// attached recorder is replaced. See the [`traffic` module](crate::traffic) for more
+    /// information.
+    pub fn attach_traffic_recorder(&self, recorder: &TrafficRecorder) {
+        self.inner.attach_traffic_recorder(recorder);
+    }
+
+    /// Detaches the [`TrafficRecorder`] from the client.
+    pub fn detach_traffic_recorder(&self) {
+        self.inner.detach_traffic_recorder();
+    }
+
     /// Shuts down the client and joins the client and connection tasks.
     ///
     /// This function cannot be canceled in a meaningful way after it has been polled once, because
@@ -146,8 +283,8 @@ impl TestClient {
     }
 
     async fn join_client(&mut self) {
-        self.client
-            .take()
+        let client = self.tasks.lock().unwrap().client.take();
+        client
             .expect("client already joined")
             .await
             .unwrap()
@@ -155,9 +292,8 @@ impl TestClient {
     }
 
     async fn join_connection(&mut self) {
-        self.conn
-            .take()
-            .expect("connection already joined")
+        let conn = self.tasks.lock().unwrap().conn.take();
+        conn.expect("connection already joined")
             .await
             .unwrap()
             .unwrap();
@@ -171,3 +307,79 @@ impl Deref for TestClient {
         &self.inner
     }
 }
+
+#[derive(Debug)]
+struct Tasks {
+    client: Option<ClientJoinHandle>,
+    conn: Option<ConnectionJoinHandle>,
+}
+
+/// Spawns a task that answers all calls to a mock service.
+///
+/// The task runs until either [`SpawnedMock::stop`] is called or the service is destroyed. Mocks
+/// are generated by the `service!` macro with the `#[aldrin(mocks)]` attribute, or by
+/// `aldrin::generate!` with `mocks = true`.
+pub fn spawn_mock<M>(mut mock: M) -> SpawnedMock<M>
+where
+    M: Mock + Send + 'static,
+{
+    let (stop_send, mut stop_recv) = oneshot::channel();
+
+    let join = tokio::spawn(async move {
+        future::poll_fn(|cx| loop {
+            if Pin::new(&mut stop_recv).poll(cx).is_ready() {
+                break Poll::Ready(());
+            }
+
+            match mock.poll_handle_call(cx) {
+                Poll::Ready(Some(_)) => {}
+                Poll::Ready(None) => break Poll::Ready(()),
+                Poll::Pending => break Poll::Pending,
+            }
+        })
+        .await;
+
+        mock
+    });
+
+    SpawnedMock {
+        stop: stop_send,
+        join,
+    }
+}
+
+/// Mock service running in a separate task.
+///
+/// See [`spawn_mock`].
+#[derive(Debug)]
+pub struct SpawnedMock<M> {
+    stop: oneshot::Sender<()>,
+    join: JoinHandle<M>,
+}
+
+impl<M: Mock> SpawnedMock<M> {
+    /// Stops answering calls and returns the mock.
+    ///
+    /// # Panics
+    ///
+    /// If the task has panicked (e.g. because of an unexpected call), then the panic is propagated
+    /// to the caller.
+    pub async fn stop(self) -> M {
+        let _ = self.stop.send(());
+
+        match self.join.await {
+            Ok(mock) => mock,
+            Err(e) => panic::resume_unwind(e.into_panic()),
+        }
+    }
+
+    /// Stops answering calls and verifies all expectations of the mock.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the task has panicked or if some expectation has not been called the
+    /// expected number of times.
+    pub async fn checkpoint(self) {
+        self.stop().await.checkpoint();
+    }
+}
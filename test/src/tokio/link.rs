@@ -0,0 +1,397 @@
+use super::{TestBroker, TestClient};
+use aldrin::core::{BusEvent, BusListenerFilter, BusListenerScope, ObjectUuid, ServiceId};
+use aldrin::low_level::{Call, Event, Proxy, Service, ServiceInfo};
+use aldrin::{BusListener, Handle, Object};
+use futures_util::future::FutureExt;
+use std::collections::{HashMap, HashSet};
+use std::future;
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+type Mirrored = Arc<Mutex<HashSet<ObjectUuid>>>;
+
+/// Test-only relay between two [`TestBroker`]s.
+///
+/// A `TestLink` connects a client to each of the two brokers. It mirrors all services of one
+/// broker onto the other one, in both directions. Function calls on mirrored services are
+/// forwarded to the original service and events of the original service are re-emitted on the
+/// mirror. This allows testing cross-bus logic without real networking.
+///
+/// The link can be [partitioned](Self::partition), which destroys all mirrored objects and
+/// services, as if the other broker had become unreachable. [Healing](Self::heal) the link
+/// mirrors all services again.
+///
+/// Objects are mirrored with their UUIDs, but with new cookies. Services are mirrored with their
+/// UUIDs and versions.
+///
+/// # Examples
+///
+/// ```
+/// use aldrin::core::{ObjectUuid, ServiceUuid};
+/// use aldrin::low_level::ServiceInfo;
+/// use aldrin_test::tokio::TestBroker;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let mut broker1 = TestBroker::new();
+/// let mut broker2 = TestBroker::new();
+/// let link = broker1.link(&mut broker2).await;
+///
+/// let client1 = broker1.add_client().await;
+/// let client2 = broker2.add_client().await;
+///
+/// let obj_uuid = ObjectUuid::new_v4();
+/// let svc_uuid = ServiceUuid::new_v4();
+/// let obj = client1.create_object(obj_uuid).await.unwrap();
+/// let svc = obj.create_service(svc_uuid, ServiceInfo::new(0)).await.unwrap();
+///
+/// // The service is mirrored onto the second broker.
+/// link.sync().await;
+/// let mirror = client2.find_specific_object(obj_uuid, &[svc_uuid]).await.unwrap();
+/// assert!(mirror.is_some());
+///
+/// // Partitioning the link destroys the mirror.
+/// link.partition().await;
+/// let mirror = client2.find_specific_object(obj_uuid, &[svc_uuid]).await.unwrap();
+/// assert!(mirror.is_none());
+/// # link.join().await;
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TestLink {
+    relays: [Relay; 2],
+}
+
+impl TestLink {
+    pub(super) async fn new(broker1: &mut TestBroker, broker2: &mut TestBroker) -> Self {
+        let client1 = broker1.add_client().await;
+        let client2 = broker2.add_client().await;
+
+        let mirrored1 = Mirrored::default();
+        let mirrored2 = Mirrored::default();
+
+        let relay1 = Relay::new(&client1, &client2, &mirrored1, &mirrored2).await;
+        let relay2 = Relay::new(&client2, &client1, &mirrored2, &mirrored1).await;
+
+        Self {
+            relays: [relay1.with_client(client1), relay2.with_client(client2)],
+        }
+    }
+
+    /// Waits until all services, that currently exist, have been mirrored.
+    ///
+    /// Mirroring happens asynchronously. This function ensures that all services, that were
+    /// created before it was called, have been mirrored (unless the link is partitioned).
+    pub async fn sync(&self) {
+        for relay in &self.relays {
+            relay.command(Command::Sync).await;
+        }
+    }
+
+    /// Partitions the link.
+    ///
+    /// All mirrored objects and services are destroyed on both brokers. Pending calls to mirrored
+    /// services are aborted. When this function returns, the partition is in effect.
+    pub async fn partition(&self) {
+        for relay in &self.relays {
+            relay.command(Command::Partition).await;
+        }
+    }
+
+    /// Heals a partitioned link.
+    ///
+    /// All services, that currently exist, are mirrored again. When this function returns, all
+    /// mirrors have been created.
+    pub async fn heal(&self) {
+        for relay in &self.relays {
+            relay.command(Command::Heal).await;
+        }
+    }
+
+    /// Shuts down the link and joins its clients.
+    ///
+    /// All mirrored objects and services are destroyed.
+    pub async fn join(self) {
+        for relay in self.relays {
+            relay.join().await;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Relay {
+    commands: mpsc::UnboundedSender<(Command, oneshot::Sender<()>)>,
+    join: JoinHandle<()>,
+    client: Option<TestClient>,
+}
+
+impl Relay {
+    async fn new(
+        src: &Handle,
+        dst: &Handle,
+        mirrored_src: &Mirrored,
+        mirrored_dst: &Mirrored,
+    ) -> Self {
+        let mut bus_listener = src.create_bus_listener().await.unwrap();
+        bus_listener
+            .add_filter(BusListenerFilter::any_object_any_service())
+            .unwrap();
+        bus_listener.start(BusListenerScope::All).await.unwrap();
+
+        let (commands, recv) = mpsc::unbounded_channel();
+
+        let state = RelayState {
+            src: src.clone(),
+            dst: dst.clone(),
+            bus_listener,
+            mirrored_src: mirrored_src.clone(),
+            mirrored_dst: mirrored_dst.clone(),
+            sources: HashSet::new(),
+            objects: HashMap::new(),
+            services: HashMap::new(),
+            partitioned: false,
+        };
+
+        Self {
+            commands,
+            join: tokio::spawn(state.run(recv)),
+            client: None,
+        }
+    }
+
+    fn with_client(mut self, client: TestClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    async fn command(&self, command: Command) {
+        let (send, recv) = oneshot::channel();
+        self.commands.send((command, send)).unwrap();
+        recv.await.unwrap();
+    }
+
+    async fn join(mut self) {
+        drop(self.commands);
+        self.join.await.unwrap();
+        self.client.take().unwrap().join().await;
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Command {
+    Sync,
+    Partition,
+    Heal,
+}
+
+enum Input {
+    Command(Command, oneshot::Sender<()>),
+    BusEvent(BusEvent),
+}
+
+struct RelayState {
+    src: Handle,
+    dst: Handle,
+    bus_listener: BusListener,
+    mirrored_src: Mirrored,
+    mirrored_dst: Mirrored,
+    sources: HashSet<ServiceId>,
+    objects: HashMap<ObjectUuid, (Object, usize)>,
+    services: HashMap<ServiceId, JoinHandle<()>>,
+    partitioned: bool,
+}
+
+impl RelayState {
+    async fn run(mut self, mut commands: mpsc::UnboundedReceiver<(Command, oneshot::Sender<()>)>) {
+        loop {
+            let bus_listener = &mut self.bus_listener;
+            let input = future::poll_fn(|cx| {
+                if let Poll::Ready(cmd) = commands.poll_recv(cx) {
+                    return Poll::Ready(cmd.map(|(cmd, ack)| Input::Command(cmd, ack)));
+                }
+
+                bus_listener
+                    .poll_next_event(cx)
+                    .map(|ev| ev.map(Input::BusEvent))
+            })
+            .await;
+
+            let (cmd, ack) = match input {
+                Some(Input::Command(cmd, ack)) => (cmd, ack),
+
+                Some(Input::BusEvent(ev)) => {
+                    self.handle_bus_event(ev).await;
+                    continue;
+                }
+
+                None => break,
+            };
+
+            match cmd {
+                Command::Sync => {
+                    // The reply to the sync arrives after all bus events, that were emitted before.
+                    let _ = self.src.sync_broker().await;
+
+                    while let Some(ev) = self.bus_listener.next_event().now_or_never().flatten() {
+                        self.handle_bus_event(ev).await;
+                    }
+                }
+
+                Command::Partition => {
+                    self.partitioned = true;
+
+                    for id in self.services.keys().copied().collect::<Vec<_>>() {
+                        self.unmirror(id);
+                    }
+
+                    let _ = self.dst.sync_broker().await;
+                }
+
+                Command::Heal => {
+                    self.partitioned = false;
+
+                    for id in self.sources.iter().copied().collect::<Vec<_>>() {
+                        self.mirror(id).await;
+                    }
+                }
+            }
+
+            let _ = ack.send(());
+        }
+
+        for id in self.services.keys().copied().collect::<Vec<_>>() {
+            self.unmirror(id);
+        }
+    }
+
+    async fn handle_bus_event(&mut self, ev: BusEvent) {
+        match ev {
+            BusEvent::ServiceCreated(id) => {
+                if self
+                    .mirrored_src
+                    .lock()
+                    .unwrap()
+                    .contains(&id.object_id.uuid)
+                {
+                    return;
+                }
+
+                self.sources.insert(id);
+
+                if !self.partitioned {
+                    self.mirror(id).await;
+                }
+            }
+
+            BusEvent::ServiceDestroyed(id) => {
+                self.sources.remove(&id);
+                self.unmirror(id);
+            }
+
+            BusEvent::ObjectCreated(_) | BusEvent::ObjectDestroyed(_) => {}
+        }
+    }
+
+    async fn mirror(&mut self, id: ServiceId) {
+        if self.services.contains_key(&id) {
+            return;
+        }
+
+        let Ok(proxy) = Proxy::new(&self.src, id).await else {
+            return;
+        };
+
+        let uuid = id.object_id.uuid;
+        if !self.objects.contains_key(&uuid) {
+            self.mirrored_dst.lock().unwrap().insert(uuid);
+
+            let Ok(obj) = Object::new(&self.dst, uuid).await else {
+                return;
+            };
+
+            self.objects.insert(uuid, (obj, 0));
+        }
+
+        let (obj, num) = self.objects.get_mut(&uuid).unwrap();
+
+        let mut info = ServiceInfo::new(proxy.version());
+        if let Some(type_id) = proxy.type_id() {
+            info = info.set_type_id(type_id);
+        }
+
+        let Ok(svc) = obj.create_service(id.uuid, info).await else {
+            if *num == 0 {
+                self.objects.remove(&uuid);
+            }
+
+            return;
+        };
+
+        *num += 1;
+        self.services.insert(id, tokio::spawn(forward(proxy, svc)));
+    }
+
+    fn unmirror(&mut self, id: ServiceId) {
+        let Some(join) = self.services.remove(&id) else {
+            return;
+        };
+
+        join.abort();
+
+        let uuid = id.object_id.uuid;
+        let (_, num) = self.objects.get_mut(&uuid).unwrap();
+        *num -= 1;
+
+        if *num == 0 {
+            self.objects.remove(&uuid);
+        }
+    }
+}
+
+async fn forward(mut proxy: Proxy, mut svc: Service) {
+    let _ = proxy.subscribe_all().await;
+
+    loop {
+        let next = future::poll_fn(|cx| {
+            if let Poll::Ready(ev) = proxy.poll_next_event(cx) {
+                return Poll::Ready(ev.map(Next::Event));
+            }
+
+            svc.poll_next_call(cx).map(|call| call.map(Next::Call))
+        })
+        .await;
+
+        match next {
+            Some(Next::Event(ev)) => {
+                let _ = svc.emit(ev.id(), ev.args());
+            }
+
+            Some(Next::Call(call)) => {
+                let function = call.id();
+                let (args, promise) = call.into_args_and_promise();
+                let reply = proxy.call(function, &args);
+
+                tokio::spawn(async move {
+                    match reply.await {
+                        Ok(res) => {
+                            let _ = promise.set(res.as_ref());
+                        }
+
+                        Err(_) => {
+                            let _ = promise.abort();
+                        }
+                    }
+                });
+            }
+
+            None => break,
+        }
+    }
+}
+
+enum Next {
+    Event(Event),
+    Call(Call),
+}
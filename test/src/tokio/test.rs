@@ -1,4 +1,6 @@
-use super::TestBroker;
+use super::{spawn_mock, TestBroker};
+use crate::traffic::{Direction, TrafficRecorder};
+use crate::transcript::Transcript;
 use std::time::Duration;
 use tokio::time;
 
@@ -16,3 +18,408 @@ async fn shutdown_idle() {
 
     time::timeout(Duration::from_secs(1), test).await.unwrap();
 }
+
+aldrin::generate!("examples/calculator.aldrin", mocks = true);
+
+#[tokio::test]
+async fn golden_transcript() {
+    use aldrin::core::ObjectUuid;
+    use calculator::{
+        Calculator, CalculatorAddArgs, CalculatorAddError, CalculatorFunction, CalculatorProxy,
+    };
+
+    let transcript = Transcript::new();
+    transcript.name_service(Calculator::UUID, "Calculator");
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_recorded_client(&transcript, "client").await;
+    let server = broker.add_recorded_client(&transcript, "server").await;
+
+    let obj = server.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = Calculator::new(&obj).await.unwrap();
+    let id = svc.id();
+
+    tokio::spawn(async move {
+        while let Some(Ok(call)) = svc.next_call().await {
+            match call {
+                CalculatorFunction::Add(args, promise) => match args.lhs.checked_add(args.rhs) {
+                    Some(sum) => promise.ok(sum).unwrap(),
+                    None => promise.err(&CalculatorAddError::Overflow).unwrap(),
+                },
+            }
+        }
+    });
+
+    let proxy = CalculatorProxy::new(&client, id).await.unwrap();
+
+    transcript.note("regular addition");
+    let sum = proxy
+        .add(&CalculatorAddArgs { lhs: 1, rhs: 2 })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sum, 3);
+
+    transcript.note("overflow");
+    let err = proxy
+        .add(&CalculatorAddArgs {
+            lhs: i32::MAX,
+            rhs: 1,
+        })
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(err, CalculatorAddError::Overflow));
+
+    transcript.assert_golden(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/golden/calculator.txt"
+    ));
+}
+
+#[tokio::test]
+async fn generated_service_interceptor() {
+    use aldrin::core::message::CallFunctionResult;
+    use aldrin::core::ObjectUuid;
+    use aldrin::low_level::{Call, Intercept, Interceptor};
+    use aldrin::Error;
+    use calculator::{Calculator, CalculatorAddArgs, CalculatorFunction, CalculatorProxy};
+
+    struct DenyNegative;
+
+    impl Interceptor for DenyNegative {
+        fn on_call(&self, call: &mut Call) -> Intercept {
+            match call.deserialize::<CalculatorAddArgs>() {
+                Ok(args) if (args.lhs < 0) || (args.rhs < 0) => {
+                    Intercept::Reply(CallFunctionResult::InvalidArgs)
+                }
+
+                _ => Intercept::Continue,
+            }
+        }
+    }
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = Calculator::new(&obj).await.unwrap();
+    svc.add_interceptor(DenyNegative);
+    let id = svc.id();
+
+    tokio::spawn(async move {
+        while let Some(Ok(call)) = svc.next_call().await {
+            match call {
+                CalculatorFunction::Add(args, promise) => {
+                    promise.ok(args.lhs + args.rhs).unwrap();
+                }
+            }
+        }
+    });
+
+    let proxy = CalculatorProxy::new(&client, id).await.unwrap();
+
+    let sum = proxy
+        .add(&CalculatorAddArgs { lhs: 1, rhs: 2 })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sum, 3);
+
+    let err = proxy
+        .add(&CalculatorAddArgs { lhs: -1, rhs: 2 })
+        .await
+        .unwrap_err();
+    assert!(matches!(err.without_context(), Error::InvalidArguments(_)));
+}
+
+#[tokio::test]
+async fn generated_proxy_layer() {
+    use aldrin::core::ObjectUuid;
+    use aldrin::low_level::{OutgoingCall, ProxyLayer};
+    use aldrin::Error;
+    use calculator::{Calculator, CalculatorAddArgs, CalculatorFunction, CalculatorProxy};
+
+    struct DenyNegative;
+
+    impl ProxyLayer for DenyNegative {
+        fn on_call(&self, call: &mut OutgoingCall) -> Result<(), Error> {
+            match call.args().deserialize::<CalculatorAddArgs>() {
+                Ok(args) if (args.lhs < 0) || (args.rhs < 0) => {
+                    Err(Error::invalid_arguments(call.function(), None))
+                }
+
+                _ => Ok(()),
+            }
+        }
+    }
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = Calculator::new(&obj).await.unwrap();
+    let id = svc.id();
+
+    tokio::spawn(async move {
+        while let Some(Ok(call)) = svc.next_call().await {
+            match call {
+                CalculatorFunction::Add(args, promise) => {
+                    promise.ok(args.lhs + args.rhs).unwrap();
+                }
+            }
+        }
+    });
+
+    let proxy = CalculatorProxy::new(&client, id)
+        .await
+        .unwrap()
+        .with_layer(DenyNegative);
+
+    let sum = proxy
+        .add(&CalculatorAddArgs { lhs: 1, rhs: 2 })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sum, 3);
+
+    let err = proxy
+        .add(&CalculatorAddArgs { lhs: -1, rhs: 2 })
+        .await
+        .unwrap_err();
+    assert!(matches!(err.without_context(), Error::InvalidArguments(_)));
+}
+
+#[tokio::test]
+async fn mock_service() {
+    use aldrin::core::ObjectUuid;
+    use calculator::{CalculatorAddArgs, CalculatorAddError, CalculatorProxy, MockCalculator};
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut mock = MockCalculator::new(&obj).await.unwrap();
+    let id = mock.id();
+
+    mock.expect_add()
+        .withf(|args| args.lhs == i32::MAX)
+        .times(1)
+        .return_const(Err(CalculatorAddError::Overflow));
+
+    mock.expect_add()
+        .times(2)
+        .returning(|args| Ok(args.lhs + args.rhs));
+
+    let mock = spawn_mock(mock);
+    let proxy = CalculatorProxy::new(&client, id).await.unwrap();
+
+    let sum = proxy
+        .add(&CalculatorAddArgs { lhs: 1, rhs: 2 })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sum, 3);
+
+    let err = proxy
+        .add(&CalculatorAddArgs {
+            lhs: i32::MAX,
+            rhs: 1,
+        })
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(err, CalculatorAddError::Overflow));
+
+    let sum = proxy
+        .add(&CalculatorAddArgs { lhs: 3, rhs: 4 })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sum, 7);
+
+    let mut mock = mock.stop().await;
+    assert_eq!(mock.add_calls(), 3);
+    mock.checkpoint();
+}
+
+#[tokio::test]
+#[should_panic(expected = "expected `Calculator::add` to be called 2 time(s)")]
+async fn mock_service_unsatisfied() {
+    use aldrin::core::ObjectUuid;
+    use calculator::{CalculatorAddArgs, CalculatorProxy, MockCalculator};
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+
+    let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut mock = MockCalculator::new(&obj).await.unwrap();
+    let id = mock.id();
+
+    mock.expect_add().times(2).return_const(Ok(0));
+
+    let mock = spawn_mock(mock);
+    let proxy = CalculatorProxy::new(&client, id).await.unwrap();
+
+    proxy
+        .add(&CalculatorAddArgs { lhs: 1, rhs: 2 })
+        .await
+        .unwrap()
+        .unwrap();
+
+    mock.checkpoint().await;
+}
+
+#[tokio::test]
+async fn bounded_client_metrics() {
+    let test = async {
+        let mut broker = TestBroker::new();
+        let mut client = broker.add_bounded_client(4).await;
+
+        client.sync_broker().await.unwrap();
+
+        let client_metrics = client.client_metrics().unwrap();
+        let conn_metrics = client.connection_metrics().unwrap();
+        assert!(client_metrics.max_queued() > 0);
+        assert!(conn_metrics.max_queued() > 0);
+        assert_eq!(client_metrics.blocked_sends(), 0);
+
+        client.join().await;
+        broker.join_idle().await;
+    };
+
+    time::timeout(Duration::from_secs(1), test).await.unwrap();
+}
+
+#[tokio::test]
+async fn spawned_clients() {
+    let test = async {
+        let mut broker = TestBroker::new();
+        let client1 = broker.add_spawned_client().await;
+        let mut client2 = broker.add_spawned_client().await;
+
+        client1.sync_broker().await.unwrap();
+        drop(client1);
+
+        client2.join().await;
+        broker.join_idle().await;
+    };
+
+    time::timeout(Duration::from_secs(1), test).await.unwrap();
+}
+
+#[tokio::test]
+async fn traffic_recorder() {
+    use aldrin::core::message::Message;
+    use aldrin::core::ObjectUuid;
+    use calculator::{Calculator, CalculatorAddArgs, CalculatorFunction, CalculatorProxy};
+
+    let mut broker = TestBroker::new();
+    let client = broker.add_client().await;
+    let server = broker.add_client().await;
+
+    let recorder = TrafficRecorder::new();
+    client.attach_traffic_recorder(&recorder);
+
+    let obj = server.create_object(ObjectUuid::new_v4()).await.unwrap();
+    let mut svc = Calculator::new(&obj).await.unwrap();
+    let id = svc.id();
+
+    tokio::spawn(async move {
+        while let Some(Ok(CalculatorFunction::Add(args, promise))) = svc.next_call().await {
+            promise.ok(args.lhs + args.rhs).unwrap();
+        }
+    });
+
+    let proxy = CalculatorProxy::new(&client, id).await.unwrap();
+    let sum = proxy
+        .add(&CalculatorAddArgs { lhs: 1, rhs: 2 })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sum, 3);
+
+    recorder.assert_call_made(id, 1);
+    assert_eq!(recorder.calls_made().len(), 1);
+    assert!(recorder.calls_received().is_empty());
+    assert!(recorder.events_emitted().is_empty());
+
+    assert!(recorder.messages().iter().any(|msg| {
+        (msg.direction() == Direction::Received)
+            && matches!(msg.message(), Message::CallFunctionReply(_))
+    }));
+    assert!(recorder.messages().iter().all(|msg| msg.connection() == 0));
+
+    client.detach_traffic_recorder();
+    recorder.clear();
+    proxy
+        .add(&CalculatorAddArgs { lhs: 1, rhs: 2 })
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(recorder.messages().is_empty());
+
+    // Every attachment is recorded as a new connection.
+    server.attach_traffic_recorder(&recorder);
+    proxy
+        .add(&CalculatorAddArgs { lhs: 1, rhs: 2 })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(recorder.calls_received().len(), 1);
+    assert!(recorder.messages().iter().all(|msg| msg.connection() == 1));
+}
+
+#[tokio::test]
+async fn linked_brokers() {
+    use aldrin::core::ObjectUuid;
+    use calculator::{Calculator, CalculatorAddArgs, CalculatorFunction, CalculatorProxy};
+
+    let mut broker1 = TestBroker::new();
+    let mut broker2 = TestBroker::new();
+    let link = broker1.link(&mut broker2).await;
+
+    let client1 = broker1.add_client().await;
+    let client2 = broker2.add_client().await;
+
+    let obj_uuid = ObjectUuid::new_v4();
+    let obj = client1.create_object(obj_uuid).await.unwrap();
+    let mut svc = Calculator::new(&obj).await.unwrap();
+
+    tokio::spawn(async move {
+        while let Some(Ok(CalculatorFunction::Add(args, promise))) = svc.next_call().await {
+            promise.ok(args.lhs + args.rhs).unwrap();
+        }
+    });
+
+    link.sync().await;
+    let (_, [id]) = client2
+        .find_specific_object(obj_uuid, &[Calculator::UUID])
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proxy = CalculatorProxy::new(&client2, id).await.unwrap();
+    let sum = proxy
+        .add(&CalculatorAddArgs { lhs: 1, rhs: 2 })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sum, 3);
+
+    link.partition().await;
+    let mirror = client2
+        .find_specific_object(obj_uuid, &[Calculator::UUID])
+        .await
+        .unwrap();
+    assert!(mirror.is_none());
+
+    link.heal().await;
+    let mirror = client2
+        .find_specific_object(obj_uuid, &[Calculator::UUID])
+        .await
+        .unwrap();
+    assert!(mirror.is_some());
+
+    link.join().await;
+}
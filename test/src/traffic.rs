@@ -0,0 +1,238 @@
+//! Recording of the messages exchanged between clients and the broker
+//!
+//! A [`TrafficRecorder`] records all [`Message`s](Message) that a client sends to or receives from
+//! the broker. Unlike a [`Transcript`](crate::transcript::Transcript), which renders a curated
+//! subset of messages into text, the recorder keeps the messages themselves and provides helpers
+//! for querying and asserting on them.
+//!
+//! Recorders can be attached to any client with
+//! [`TestClient::attach_traffic_recorder`](crate::TestClient::attach_traffic_recorder) (or
+//! [`tokio::TestClient::attach_traffic_recorder`](crate::tokio::TestClient::attach_traffic_recorder))
+//! at any time. Only messages passing through the client's transport afterwards are recorded.
+//!
+//! Messages are recorded as [`RecordedMessage`s](RecordedMessage) of
+//! [`aldrin_core::recording`]. Each attachment of a recorder to a client is assigned a new
+//! connection id, starting at 0, and timestamps are relative to the creation of the recorder.
+//!
+//! # Examples
+//!
+//! ```
+//! use aldrin::core::message::Message;
+//! use aldrin::core::ObjectUuid;
+//! use aldrin_test::tokio::TestBroker;
+//! use aldrin_test::traffic::TrafficRecorder;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let mut broker = TestBroker::new();
+//! let client = broker.add_client().await;
+//!
+//! let recorder = TrafficRecorder::new();
+//! client.attach_traffic_recorder(&recorder);
+//!
+//! let obj = client.create_object(ObjectUuid::new_v4()).await.unwrap();
+//!
+//! assert!(matches!(recorder.sent()[..], [Message::CreateObject(_)]));
+//! assert!(matches!(recorder.received()[..], [Message::CreateObjectReply(_)]));
+//! # }
+//! ```
+
+use aldrin_core::message::{CallFunction, EmitEvent, Message};
+use aldrin_core::recording::{Tap, TapTransport};
+use aldrin_core::ServiceId;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+
+pub use aldrin_core::recording::{Direction, RecordedMessage};
+
+/// Recorder of the messages exchanged between clients and the broker.
+///
+/// `TrafficRecorder` is cheap to clone. All clones refer to the same recording. The same recorder
+/// can be attached to multiple clients.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug, Clone)]
+pub struct TrafficRecorder {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl TrafficRecorder {
+    /// Creates a new empty recorder.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                start: Instant::now(),
+                next_connection: 0,
+                messages: Vec::new(),
+            })),
+        }
+    }
+
+    /// Returns all recorded messages in the order in which they were recorded.
+    pub fn messages(&self) -> Vec<RecordedMessage> {
+        self.lock().messages.clone()
+    }
+
+    /// Returns all messages sent by the recorded clients.
+    pub fn sent(&self) -> Vec<Message> {
+        self.filter(Direction::Sent, |msg| Some(msg.clone()))
+    }
+
+    /// Returns all messages received by the recorded clients.
+    pub fn received(&self) -> Vec<Message> {
+        self.filter(Direction::Received, |msg| Some(msg.clone()))
+    }
+
+    /// Returns all function calls made by the recorded clients.
+    pub fn calls_made(&self) -> Vec<CallFunction> {
+        self.filter(Direction::Sent, |msg| match msg {
+            Message::CallFunction(msg) => Some(msg.clone()),
+            _ => None,
+        })
+    }
+
+    /// Returns all function calls received by the recorded clients.
+    pub fn calls_received(&self) -> Vec<CallFunction> {
+        self.filter(Direction::Received, |msg| match msg {
+            Message::CallFunction(msg) => Some(msg.clone()),
+            _ => None,
+        })
+    }
+
+    /// Returns all events emitted by the recorded clients.
+    pub fn events_emitted(&self) -> Vec<EmitEvent> {
+        self.filter(Direction::Sent, |msg| match msg {
+            Message::EmitEvent(msg) => Some(msg.clone()),
+            _ => None,
+        })
+    }
+
+    /// Returns all events received by the recorded clients.
+    pub fn events_received(&self) -> Vec<EmitEvent> {
+        self.filter(Direction::Received, |msg| match msg {
+            Message::EmitEvent(msg) => Some(msg.clone()),
+            _ => None,
+        })
+    }
+
+    /// Asserts that a recorded client called a function on a service.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no such call was recorded.
+    #[track_caller]
+    pub fn assert_call_made(&self, service: ServiceId, function: u32) {
+        let found = self
+            .calls_made()
+            .iter()
+            .any(|call| (call.service_cookie == service.cookie) && (call.function == function));
+
+        assert!(
+            found,
+            "no call of function {function} on service {} was recorded",
+            service.uuid,
+        );
+    }
+
+    /// Asserts that a recorded client emitted an event on a service.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if no such event was recorded.
+    #[track_caller]
+    pub fn assert_event_emitted(&self, service: ServiceId, event: u32) {
+        let found = self
+            .events_emitted()
+            .iter()
+            .any(|ev| (ev.service_cookie == service.cookie) && (ev.event == event));
+
+        assert!(
+            found,
+            "no event {event} on service {} was recorded",
+            service.uuid,
+        );
+    }
+
+    /// Clears all recorded messages.
+    pub fn clear(&self) {
+        self.lock().messages.clear();
+    }
+
+    fn next_connection(&self) -> u32 {
+        let mut inner = self.lock();
+        let connection = inner.next_connection;
+        inner.next_connection += 1;
+        connection
+    }
+
+    fn record(&self, connection: u32, direction: Direction, message: &Message) {
+        let mut inner = self.lock();
+        let timestamp = inner.start.elapsed();
+
+        inner.messages.push(RecordedMessage::new(
+            timestamp,
+            connection,
+            direction,
+            message.clone(),
+        ));
+    }
+
+    fn filter<T>(&self, direction: Direction, f: impl Fn(&Message) -> Option<T>) -> Vec<T> {
+        self.lock()
+            .messages
+            .iter()
+            .filter(|msg| msg.direction() == direction)
+            .filter_map(|msg| f(msg.message()))
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap()
+    }
+}
+
+impl Default for TrafficRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    start: Instant,
+    next_connection: u32,
+    messages: Vec<RecordedMessage>,
+}
+
+/// Slot for attaching a `TrafficRecorder` to a client after its transport has been created.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TrafficTap {
+    recorder: Arc<Mutex<Option<(TrafficRecorder, u32)>>>,
+}
+
+impl TrafficTap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn attach(&self, recorder: &TrafficRecorder) {
+        let connection = recorder.next_connection();
+        *self.recorder.lock().unwrap() = Some((recorder.clone(), connection));
+    }
+
+    pub fn detach(&self) {
+        *self.recorder.lock().unwrap() = None;
+    }
+
+    pub fn wrap<T>(&self, transport: T) -> TapTransport<T, Self> {
+        TapTransport::new(transport, self.clone())
+    }
+}
+
+impl Tap for TrafficTap {
+    fn tap(&mut self, direction: Direction, msg: &Message) {
+        if let Some((ref recorder, connection)) = *self.recorder.lock().unwrap() {
+            recorder.record(connection, direction, msg);
+        }
+    }
+}
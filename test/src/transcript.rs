@@ -0,0 +1,553 @@
+//! Golden transcripts of the interaction between clients and services
+//!
+//! A [`Transcript`] records function calls, replies and events as seen by one or more clients
+//! into a human-readable text. Clients are recorded by adding them to a broker with
+//! [`TestBroker::add_recorded_client`](crate::TestBroker::add_recorded_client) (or
+//! [`tokio::TestBroker::add_recorded_client`](crate::tokio::TestBroker::add_recorded_client)).
+//!
+//! The transcript can then be compared with a checked-in golden file with
+//! [`Transcript::assert_golden`]. Setting the environment variable `ALDRIN_BLESS=1` writes the
+//! recorded transcript to the golden file instead.
+//!
+//! All values are rendered in a deterministic way. In particular, UUIDs, cookies and the entries
+//! of maps and sets, which would otherwise differ between test runs, are replaced by stable
+//! aliases or sorted. Lines are recorded in the order in which they pass through the clients'
+//! transports. Tests should therefore drive the interaction sequentially (e.g. by awaiting each
+//! reply) for the transcript to be reproducible.
+//!
+//! # Format
+//!
+//! Each line starts with the label of the client, followed by `->` for messages sent by the
+//! client and `<-` for messages received by it:
+//!
+//! ```text
+//! client -> call Calculator.1 #0: Struct { 1: I32(1), 2: I32(2) }
+//! server <- call Calculator.1 #0: Struct { 1: I32(1), 2: I32(2) }
+//! server -> reply Calculator.1 #0: ok I32(3)
+//! client <- reply Calculator.1 #0: ok I32(3)
+//! ```
+//!
+//! Services are named by the label given to [`Transcript::name_service`], or by their UUID. Calls
+//! are identified by their serial number, which is local to each client.
+
+use aldrin_core::message::{CallFunctionResult, CreateServiceResult, Message};
+use aldrin_core::recording::{Direction, Tap, TapTransport};
+use aldrin_core::{SerializedValue, ServiceCookie, ServiceUuid, Value};
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// Environment variable, which causes golden files to be (re-)written.
+pub const BLESS_ENV: &str = "ALDRIN_BLESS";
+
+/// Recording of the interaction between clients and services.
+///
+/// `Transcript` is cheap to clone. All clones refer to the same recording.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Debug, Clone, Default)]
+pub struct Transcript {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl Transcript {
+    /// Creates a new empty transcript.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name, with which a service is referred to in the transcript.
+    ///
+    /// Generated services can be named with e.g. `transcript.name_service(Calculator::UUID,
+    /// "Calculator")`. If the same service is created multiple times, all but the first instance
+    /// get a numeric suffix (`Calculator#2`).
+    ///
+    /// Names are assigned when a service is created by a recorded client. They must thus be set
+    /// before that.
+    pub fn name_service(&self, uuid: ServiceUuid, name: impl Into<String>) {
+        self.lock().service_names.insert(uuid, name.into());
+    }
+
+    /// Adds a free-form line to the transcript.
+    ///
+    /// This can be used to separate different parts of a test.
+    pub fn note(&self, note: impl fmt::Display) {
+        self.lock().lines.push(format!("# {note}"));
+    }
+
+    /// Returns the recorded lines.
+    pub fn lines(&self) -> Vec<String> {
+        self.lock().lines.clone()
+    }
+
+    /// Clears all recorded lines.
+    ///
+    /// Service names and aliases are kept.
+    pub fn clear(&self) {
+        self.lock().lines.clear();
+    }
+
+    /// Compares the transcript with a golden file.
+    ///
+    /// If the environment variable `ALDRIN_BLESS` is set to a value other than `0`, then the golden
+    /// file is written instead.
+    ///
+    /// The path is typically constructed from the `CARGO_MANIFEST_DIR` environment variable, e.g.
+    /// `concat!(env!("CARGO_MANIFEST_DIR"), "/golden/calculator.txt")`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics with a diff between the golden file and the transcript, if the two
+    /// differ, or when the golden file cannot be read.
+    #[track_caller]
+    pub fn assert_golden(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let actual = self.to_string();
+
+        if std::env::var_os(BLESS_ENV).is_some_and(|v| !v.is_empty() && v != "0") {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).unwrap();
+            }
+
+            fs::write(path, actual).unwrap();
+            return;
+        }
+
+        let expected = match fs::read_to_string(path) {
+            Ok(expected) => expected,
+
+            Err(e) => panic!(
+                "failed to read golden file `{}`: {e}\n\
+                 rerun with `{BLESS_ENV}=1` to create it\n\n\
+                 transcript:\n{actual}",
+                path.display(),
+            ),
+        };
+
+        if expected != actual {
+            let diff = similar::TextDiff::from_lines(&expected, &actual)
+                .unified_diff()
+                .context_radius(3)
+                .header("golden", "transcript")
+                .to_string();
+
+            panic!(
+                "transcript differs from golden file `{}`\n\
+                 rerun with `{BLESS_ENV}=1` to update it\n\n{diff}",
+                path.display(),
+            );
+        }
+    }
+
+    pub(crate) fn record<T>(
+        &self,
+        label: impl Into<String>,
+        transport: T,
+    ) -> TapTransport<T, TranscriptTap> {
+        let recorder = TranscriptTap {
+            transcript: self.clone(),
+            label: label.into(),
+            calls_sent: HashMap::new(),
+            calls_received: HashMap::new(),
+            services_created: HashMap::new(),
+        };
+
+        TapTransport::new(transport, recorder)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap()
+    }
+}
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in &self.lock().lines {
+            writeln!(f, "{line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    lines: Vec<String>,
+    service_names: HashMap<ServiceUuid, String>,
+    service_instances: HashMap<ServiceUuid, usize>,
+    services: Aliases<ServiceCookie>,
+    uuids: Aliases<Uuid>,
+    channels: Aliases<Uuid>,
+}
+
+impl Inner {
+    fn service_created(&mut self, uuid: ServiceUuid, cookie: ServiceCookie) {
+        let instance = self.service_instances.entry(uuid).or_default();
+        *instance += 1;
+
+        let mut name = self
+            .service_names
+            .get(&uuid)
+            .cloned()
+            .unwrap_or_else(|| uuid.to_string());
+
+        if *instance > 1 {
+            write!(name, "#{instance}").unwrap();
+        }
+
+        self.services.insert(cookie, name);
+    }
+
+    fn service(&mut self, cookie: ServiceCookie) -> String {
+        self.services.get(cookie, "service")
+    }
+
+    fn value(&mut self, value: &SerializedValue) -> String {
+        let mut buf = String::new();
+
+        match value.deserialize::<Value>() {
+            Ok(value) => self.fmt_value(&mut buf, &value).unwrap(),
+            Err(e) => write!(buf, "<invalid value: {e}>").unwrap(),
+        }
+
+        buf
+    }
+
+    fn fmt_value(&mut self, buf: &mut String, value: &Value) -> fmt::Result {
+        match value {
+            Value::None => buf.write_str("None"),
+
+            Value::Some(value) => {
+                buf.write_str("Some(")?;
+                self.fmt_value(buf, value)?;
+                buf.write_char(')')
+            }
+
+            Value::Bool(value) => write!(buf, "Bool({value})"),
+            Value::U8(value) => write!(buf, "U8({value})"),
+            Value::I8(value) => write!(buf, "I8({value})"),
+            Value::U16(value) => write!(buf, "U16({value})"),
+            Value::I16(value) => write!(buf, "I16({value})"),
+            Value::U32(value) => write!(buf, "U32({value})"),
+            Value::I32(value) => write!(buf, "I32({value})"),
+            Value::U64(value) => write!(buf, "U64({value})"),
+            Value::I64(value) => write!(buf, "I64({value})"),
+            Value::F32(value) => write!(buf, "F32({value:?})"),
+            Value::F64(value) => write!(buf, "F64({value:?})"),
+            Value::String(value) => write!(buf, "String({value:?})"),
+            Value::Uuid(value) => write!(buf, "Uuid({})", self.uuids.get(*value, "uuid")),
+
+            Value::ObjectId(value) => {
+                write!(buf, "ObjectId({})", self.uuids.get(value.uuid.0, "uuid"))
+            }
+
+            Value::ServiceId(value) => {
+                let service = self.service(value.cookie);
+                write!(buf, "ServiceId({service})")
+            }
+
+            Value::Vec(values) => {
+                buf.write_str("Vec [")?;
+
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        buf.write_str(", ")?;
+                    }
+
+                    self.fmt_value(buf, value)?;
+                }
+
+                buf.write_char(']')
+            }
+
+            Value::Bytes(value) => write!(buf, "Bytes({value:?})"),
+            Value::U8Map(map) => self.fmt_map(buf, "U8Map", map, |k| k.to_string()),
+            Value::I8Map(map) => self.fmt_map(buf, "I8Map", map, |k| k.to_string()),
+            Value::U16Map(map) => self.fmt_map(buf, "U16Map", map, |k| k.to_string()),
+            Value::I16Map(map) => self.fmt_map(buf, "I16Map", map, |k| k.to_string()),
+            Value::U32Map(map) => self.fmt_map(buf, "U32Map", map, |k| k.to_string()),
+            Value::I32Map(map) => self.fmt_map(buf, "I32Map", map, |k| k.to_string()),
+            Value::U64Map(map) => self.fmt_map(buf, "U64Map", map, |k| k.to_string()),
+            Value::I64Map(map) => self.fmt_map(buf, "I64Map", map, |k| k.to_string()),
+            Value::StringMap(map) => self.fmt_map(buf, "StringMap", map, |k| format!("{k:?}")),
+
+            Value::UuidMap(map) => {
+                let map = map
+                    .iter()
+                    .map(|(k, v)| (self.uuids.get(*k, "uuid"), v))
+                    .collect::<HashMap<_, _>>();
+
+                self.fmt_map(buf, "UuidMap", &map, Clone::clone)
+            }
+
+            Value::U8Set(set) => fmt_set(buf, "U8Set", set.iter().map(ToString::to_string)),
+            Value::I8Set(set) => fmt_set(buf, "I8Set", set.iter().map(ToString::to_string)),
+            Value::U16Set(set) => fmt_set(buf, "U16Set", set.iter().map(ToString::to_string)),
+            Value::I16Set(set) => fmt_set(buf, "I16Set", set.iter().map(ToString::to_string)),
+            Value::U32Set(set) => fmt_set(buf, "U32Set", set.iter().map(ToString::to_string)),
+            Value::I32Set(set) => fmt_set(buf, "I32Set", set.iter().map(ToString::to_string)),
+            Value::U64Set(set) => fmt_set(buf, "U64Set", set.iter().map(ToString::to_string)),
+            Value::I64Set(set) => fmt_set(buf, "I64Set", set.iter().map(ToString::to_string)),
+
+            Value::StringSet(set) => {
+                fmt_set(buf, "StringSet", set.iter().map(|v| format!("{v:?}")))
+            }
+
+            Value::UuidSet(set) => {
+                let set = set
+                    .iter()
+                    .map(|v| self.uuids.get(*v, "uuid"))
+                    .collect::<Vec<_>>();
+
+                fmt_set(buf, "UuidSet", set.into_iter())
+            }
+
+            Value::Struct(value) => self.fmt_map(buf, "Struct", &value.0, |k| k.to_string()),
+
+            Value::Enum(value) => {
+                write!(buf, "Enum({}, ", value.variant)?;
+                self.fmt_value(buf, &value.value)?;
+                buf.write_char(')')
+            }
+
+            Value::Sender(value) => {
+                write!(buf, "Sender({})", self.channels.get(value.0, "channel"))
+            }
+
+            Value::Receiver(value) => {
+                write!(buf, "Receiver({})", self.channels.get(value.0, "channel"))
+            }
+        }
+    }
+
+    fn fmt_map<K: Ord>(
+        &mut self,
+        buf: &mut String,
+        name: &str,
+        map: &HashMap<K, impl std::borrow::Borrow<Value>>,
+        fmt_key: impl Fn(&K) -> String,
+    ) -> fmt::Result {
+        let mut entries = map.iter().collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        write!(buf, "{name} {{")?;
+
+        for (i, (key, value)) in entries.into_iter().enumerate() {
+            if i > 0 {
+                buf.write_char(',')?;
+            }
+
+            write!(buf, " {}: ", fmt_key(key))?;
+            self.fmt_value(buf, value.borrow())?;
+        }
+
+        buf.write_str(" }")
+    }
+}
+
+fn fmt_set(buf: &mut String, name: &str, values: impl Iterator<Item = String>) -> fmt::Result {
+    let mut values = values.collect::<Vec<_>>();
+    values.sort();
+    write!(buf, "{name} {{ {} }}", values.join(", "))
+}
+
+#[derive(Debug)]
+struct Aliases<K> {
+    aliases: HashMap<K, String>,
+    next: usize,
+}
+
+impl<K: Eq + Hash> Aliases<K> {
+    fn insert(&mut self, key: K, alias: String) {
+        self.aliases.insert(key, alias);
+    }
+
+    fn get(&mut self, key: K, prefix: &str) -> String {
+        let next = &mut self.next;
+
+        self.aliases
+            .entry(key)
+            .or_insert_with(|| {
+                *next += 1;
+                format!("{prefix}#{next}")
+            })
+            .clone()
+    }
+}
+
+impl<K> Default for Aliases<K> {
+    fn default() -> Self {
+        Self {
+            aliases: HashMap::new(),
+            next: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TranscriptTap {
+    transcript: Transcript,
+    label: String,
+    calls_sent: HashMap<u32, (ServiceCookie, u32)>,
+    calls_received: HashMap<u32, (ServiceCookie, u32)>,
+    services_created: HashMap<u32, ServiceUuid>,
+}
+
+impl TranscriptTap {
+    fn sent(&mut self, msg: &Message) {
+        let mut inner = self.transcript.lock();
+        let label = &self.label;
+
+        let line = match msg {
+            Message::CallFunction(msg) => {
+                self.calls_sent
+                    .insert(msg.serial, (msg.service_cookie, msg.function));
+
+                format!(
+                    "{label} -> call {}.{} #{}: {}",
+                    inner.service(msg.service_cookie),
+                    msg.function,
+                    msg.serial,
+                    inner.value(&msg.value),
+                )
+            }
+
+            Message::CallFunctionReply(msg) => {
+                let Some((service, function)) = self.calls_received.remove(&msg.serial) else {
+                    return;
+                };
+
+                format!(
+                    "{label} -> reply {}.{function} #{}: {}",
+                    inner.service(service),
+                    msg.serial,
+                    result(&mut inner, &msg.result),
+                )
+            }
+
+            Message::AbortFunctionCall(msg) => {
+                let Some(&(service, function)) = self.calls_sent.get(&msg.serial) else {
+                    return;
+                };
+
+                format!(
+                    "{label} -> abort {}.{function} #{}",
+                    inner.service(service),
+                    msg.serial,
+                )
+            }
+
+            Message::EmitEvent(msg) => format!(
+                "{label} -> event {}.{}: {}",
+                inner.service(msg.service_cookie),
+                msg.event,
+                inner.value(&msg.value),
+            ),
+
+            Message::CreateService(msg) => {
+                self.services_created.insert(msg.serial, msg.uuid);
+                return;
+            }
+
+            Message::CreateService2(msg) => {
+                self.services_created.insert(msg.serial, msg.uuid);
+                return;
+            }
+
+            _ => return,
+        };
+
+        inner.lines.push(line);
+    }
+
+    fn received(&mut self, msg: &Message) {
+        let mut inner = self.transcript.lock();
+        let label = &self.label;
+
+        let line = match msg {
+            Message::CallFunction(msg) => {
+                self.calls_received
+                    .insert(msg.serial, (msg.service_cookie, msg.function));
+
+                format!(
+                    "{label} <- call {}.{} #{}: {}",
+                    inner.service(msg.service_cookie),
+                    msg.function,
+                    msg.serial,
+                    inner.value(&msg.value),
+                )
+            }
+
+            Message::CallFunctionReply(msg) => {
+                let Some((service, function)) = self.calls_sent.remove(&msg.serial) else {
+                    return;
+                };
+
+                format!(
+                    "{label} <- reply {}.{function} #{}: {}",
+                    inner.service(service),
+                    msg.serial,
+                    result(&mut inner, &msg.result),
+                )
+            }
+
+            Message::AbortFunctionCall(msg) => {
+                let Some(&(service, function)) = self.calls_received.get(&msg.serial) else {
+                    return;
+                };
+
+                format!(
+                    "{label} <- abort {}.{function} #{}",
+                    inner.service(service),
+                    msg.serial,
+                )
+            }
+
+            Message::EmitEvent(msg) => format!(
+                "{label} <- event {}.{}: {}",
+                inner.service(msg.service_cookie),
+                msg.event,
+                inner.value(&msg.value),
+            ),
+
+            Message::CreateServiceReply(msg) => {
+                if let Some(uuid) = self.services_created.remove(&msg.serial) {
+                    if let CreateServiceResult::Ok(cookie) = msg.result {
+                        inner.service_created(uuid, cookie);
+                    }
+                }
+
+                return;
+            }
+
+            _ => return,
+        };
+
+        inner.lines.push(line);
+    }
+}
+
+fn result(inner: &mut Inner, result: &CallFunctionResult) -> String {
+    match result {
+        CallFunctionResult::Ok(value) => format!("ok {}", inner.value(value)),
+        CallFunctionResult::Err(value) => format!("err {}", inner.value(value)),
+        CallFunctionResult::Aborted => "aborted".to_owned(),
+        CallFunctionResult::InvalidService => "invalid service".to_owned(),
+        CallFunctionResult::InvalidFunction => "invalid function".to_owned(),
+        CallFunctionResult::InvalidArgs => "invalid args".to_owned(),
+        CallFunctionResult::Overloaded => "overloaded".to_owned(),
+    }
+}
+
+impl Tap for TranscriptTap {
+    fn tap(&mut self, direction: Direction, msg: &Message) {
+        match direction {
+            Direction::Sent => self.sent(msg),
+            Direction::Received => self.received(msg),
+        }
+    }
+}
@@ -0,0 +1,679 @@
+// Client for Aldrin brokers over WebSockets.
+//
+// The client speaks protocol version 1.14. Every WebSocket message carries one or more complete or
+// partial Aldrin messages, so the broker side can be any WebSocket endpoint that forwards the byte
+// stream, e.g. a generic WebSocket-to-TCP proxy in front of a broker's TCP listener.
+// Properties are supported on the client side. Channels and streaming functions are not supported
+// yet.
+
+import {
+    DeserializeError,
+    Reader,
+    SOME,
+    STRING,
+    Serializer,
+    UNIT,
+    Writer,
+    deserialize,
+    optional,
+    propertyUpdate,
+    serialize,
+} from "./serialization.js";
+
+export const PROTOCOL_MAJOR = 1;
+export const PROTOCOL_MINOR = 14;
+
+const CONNECT_REPLY_OK = 0;
+const CONNECT_REPLY_REJECTED = 1;
+
+// Message kinds. These mirror `MessageKind` in aldrin-core.
+const SHUTDOWN = 2;
+const CALL_FUNCTION = 11;
+const CALL_FUNCTION_REPLY = 12;
+const SUBSCRIBE_EVENT = 13;
+const SUBSCRIBE_EVENT_REPLY = 14;
+const UNSUBSCRIBE_EVENT = 15;
+const EMIT_EVENT = 16;
+const SERVICE_DESTROYED = 32;
+const CREATE_BUS_LISTENER = 33;
+const CREATE_BUS_LISTENER_REPLY = 34;
+const DESTROY_BUS_LISTENER = 35;
+const DESTROY_BUS_LISTENER_REPLY = 36;
+const ADD_BUS_LISTENER_FILTER = 37;
+const START_BUS_LISTENER = 40;
+const START_BUS_LISTENER_REPLY = 41;
+const EMIT_BUS_EVENT = 44;
+const BUS_LISTENER_CURRENT_FINISHED = 45;
+const CONNECT2 = 46;
+const CONNECT_REPLY2 = 47;
+
+const WITH_VALUE = new Set([CALL_FUNCTION, CALL_FUNCTION_REPLY, EMIT_EVENT, CONNECT_REPLY2]);
+
+const REPLY_OK = 0;
+const REPLY_ERR = 1;
+const REPLY_REASONS = new Map([
+    [2, "aborted"],
+    [3, "invalid service"],
+    [4, "invalid function"],
+    [5, "invalid arguments"],
+    [6, "overloaded"],
+]);
+
+const BUS_EVENT_SERVICE_CREATED = 2;
+const FILTER_ANY_OBJECT_SPECIFIC_SERVICE = 4;
+const FILTER_SPECIFIC_OBJECT_SPECIFIC_SERVICE = 5;
+const SCOPE_ALL = 2;
+
+/** Base class of all errors raised by the client. */
+export class AldrinError extends Error {
+    constructor(message) {
+        super(message);
+        this.name = "AldrinError";
+    }
+}
+
+/** The broker rejected the connection. */
+export class ConnectError extends AldrinError {
+    constructor(message) {
+        super(message);
+        this.name = "ConnectError";
+    }
+}
+
+/** The connection to the broker was closed. */
+export class DisconnectedError extends AldrinError {
+    constructor() {
+        super("disconnected");
+        this.name = "DisconnectedError";
+    }
+}
+
+/** A function call returned an error. */
+export class CallError extends AldrinError {
+    constructor(value) {
+        super("function call returned an error");
+        this.name = "CallError";
+        this.value = value;
+    }
+}
+
+/** A function call failed without returning a value, e.g. because the service is gone. */
+export class CallFailed extends AldrinError {
+    constructor(reason) {
+        super(reason);
+        this.name = "CallFailed";
+        this.reason = reason;
+    }
+}
+
+function frame(kind, fields, value) {
+    const body = fields.bytes();
+    const writer = new Writer();
+
+    if (value === undefined) {
+        writer.fixedU32(5 + body.length);
+        writer.u8(kind);
+    } else {
+        writer.fixedU32(9 + value.length + body.length);
+        writer.u8(kind);
+        writer.fixedU32(value.length);
+        writer.raw(value);
+    }
+
+    writer.raw(body);
+    return writer.bytes();
+}
+
+function connectData(name) {
+    const serializer = new Serializer();
+    const struct = serializer.serializeStruct(name === undefined ? 1 : 2);
+
+    // Field 0 (user data) is always present.
+    struct.serializeField(0, UNIT, null);
+
+    if (name !== undefined) {
+        struct.serializeField(6, optional(STRING), name);
+    }
+
+    struct.finish();
+    return serializer.writer.bytes();
+}
+
+/** Connection to an Aldrin broker. */
+export class Client {
+    constructor(socket) {
+        this._socket = socket;
+        this._buffer = new Uint8Array(0);
+        this._serial = 0;
+        this._pending = new Map();
+        this._subscriptions = new Map();
+        this._busListeners = new Map();
+        this._closed = false;
+        this._handshake = null;
+
+        this.closed = new Promise((resolve) => {
+            this._resolveClosed = resolve;
+        });
+    }
+
+    /**
+     * Connects to a broker at a WebSocket URL.
+     *
+     * The WebSocket implementation can be chosen with the `WebSocket` option. It defaults to the
+     * global `WebSocket`.
+     */
+    static connect(url, options = {}) {
+        const WebSocketImpl = options.WebSocket ?? globalThis.WebSocket;
+
+        if (WebSocketImpl === undefined) {
+            return Promise.reject(new AldrinError("no WebSocket implementation available"));
+        }
+
+        return Client.fromWebSocket(new WebSocketImpl(url), options);
+    }
+
+    /** Performs the handshake with a broker over a WebSocket, which may still be connecting. */
+    static fromWebSocket(socket, options = {}) {
+        const client = new Client(socket);
+        socket.binaryType = "arraybuffer";
+
+        return new Promise((resolve, reject) => {
+            client._handshake = { resolve, reject };
+
+            const start = () => {
+                const fields = new Writer();
+                fields.u32(PROTOCOL_MAJOR);
+                fields.u32(PROTOCOL_MINOR);
+                client._send(CONNECT2, fields, connectData(options.name));
+            };
+
+            socket.addEventListener("message", (ev) => client._receive(new Uint8Array(ev.data)));
+            socket.addEventListener("close", () => client._shutDown());
+            socket.addEventListener("error", () => client._shutDown());
+
+            if (socket.readyState === 1) {
+                start();
+            } else {
+                socket.addEventListener("open", start);
+            }
+        });
+    }
+
+    /** Shuts down the connection and waits until the broker has acknowledged it. */
+    async close() {
+        if (!this._closed) {
+            this._send(SHUTDOWN, new Writer());
+        }
+
+        await this.closed;
+    }
+
+    /**
+     * Finds a service by its UUID and optionally its object's UUID.
+     *
+     * Waits for the service to be created when `wait` is true. Otherwise, resolves to `null`, when
+     * no such service exists currently.
+     */
+    async findService(serviceUuid, objectUuid, wait = true) {
+        const [serial, fields] = this._beginRequest();
+        const reply = await this._request(
+            CREATE_BUS_LISTENER,
+            CREATE_BUS_LISTENER_REPLY,
+            serial,
+            fields,
+        );
+        const cookie = reply.uuid();
+        const queue = new Queue();
+        this._busListeners.set(cookie, queue);
+
+        try {
+            const filter = new Writer();
+            filter.uuid(cookie);
+
+            if (objectUuid === undefined) {
+                filter.u8(FILTER_ANY_OBJECT_SPECIFIC_SERVICE);
+            } else {
+                filter.u8(FILTER_SPECIFIC_OBJECT_SPECIFIC_SERVICE);
+                filter.uuid(objectUuid);
+            }
+
+            filter.uuid(serviceUuid);
+            this._send(ADD_BUS_LISTENER_FILTER, filter);
+
+            const [serial, fields] = this._beginRequest();
+            fields.uuid(cookie);
+            fields.u8(SCOPE_ALL);
+            const reply = await this._request(
+                START_BUS_LISTENER,
+                START_BUS_LISTENER_REPLY,
+                serial,
+                fields,
+            );
+
+            if (reply.u8() !== 0) {
+                throw new AldrinError("failed to start bus listener");
+            }
+
+            for (;;) {
+                const item = await queue.get();
+
+                if (item instanceof Error) {
+                    throw item;
+                } else if (item !== null) {
+                    return item;
+                } else if (!wait) {
+                    return null;
+                }
+            }
+        } finally {
+            this._busListeners.delete(cookie);
+
+            if (!this._closed) {
+                const [serial, fields] = this._beginRequest();
+                fields.uuid(cookie);
+                this._request(DESTROY_BUS_LISTENER, DESTROY_BUS_LISTENER_REPLY, serial, fields)
+                    .catch(() => {});
+            }
+        }
+    }
+
+    _beginRequest() {
+        this._serial = (this._serial + 1) >>> 0;
+        const fields = new Writer();
+        fields.u32(this._serial);
+        return [this._serial, fields];
+    }
+
+    _request(kind, replyKind, serial, fields, value) {
+        if (this._closed) {
+            return Promise.reject(new DisconnectedError());
+        }
+
+        return new Promise((resolve, reject) => {
+            this._pending.set(`${replyKind}:${serial}`, { resolve, reject });
+            this._send(kind, fields, value);
+        });
+    }
+
+    _send(kind, fields, value) {
+        if (this._closed) {
+            throw new DisconnectedError();
+        }
+
+        this._socket.send(frame(kind, fields, value));
+    }
+
+    _receive(data) {
+        const buffer = new Uint8Array(this._buffer.length + data.length);
+        buffer.set(this._buffer);
+        buffer.set(data, this._buffer.length);
+        let pos = 0;
+
+        try {
+            while (buffer.length - pos >= 4) {
+                const len = new DataView(buffer.buffer, pos).getUint32(0, true);
+
+                if (len < 5) {
+                    throw new DeserializeError(`invalid message length ${len}`);
+                } else if (buffer.length - pos < len) {
+                    break;
+                }
+
+                this._message(buffer.subarray(pos, pos + len));
+                pos += len;
+            }
+        } catch (e) {
+            this._fail(e);
+            return;
+        }
+
+        this._buffer = buffer.slice(pos);
+    }
+
+    _message(data) {
+        const kind = data[4];
+        let msg;
+
+        if (WITH_VALUE.has(kind)) {
+            const valueLen = new DataView(data.buffer, data.byteOffset + 5).getUint32(0, true);
+            msg = new Reader(data, 9 + valueLen);
+            msg.value = data.slice(9, 9 + valueLen);
+        } else {
+            msg = new Reader(data, 5);
+        }
+
+        if (this._handshake !== null) {
+            this._finishHandshake(kind, msg);
+        } else if (kind === SHUTDOWN) {
+            this._socket.close();
+            this._shutDown();
+        } else {
+            this._dispatch(kind, msg);
+        }
+    }
+
+    _finishHandshake(kind, msg) {
+        const { resolve, reject } = this._handshake;
+        this._handshake = null;
+
+        if (kind !== CONNECT_REPLY2) {
+            reject(new ConnectError(`unexpected message ${kind} during handshake`));
+            this._socket.close();
+            return;
+        }
+
+        const result = msg.u8();
+
+        if (result === CONNECT_REPLY_OK) {
+            resolve(this);
+        } else if (result === CONNECT_REPLY_REJECTED) {
+            reject(new ConnectError("connection rejected"));
+        } else {
+            reject(new ConnectError("incompatible protocol version"));
+        }
+    }
+
+    _fail(error) {
+        this._socket.close();
+        this._shutDown(error);
+    }
+
+    _shutDown(error = new DisconnectedError()) {
+        if (this._closed) {
+            return;
+        }
+
+        this._closed = true;
+
+        if (this._handshake !== null) {
+            this._handshake.reject(error);
+            this._handshake = null;
+        }
+
+        for (const { reject } of this._pending.values()) {
+            reject(error);
+        }
+
+        this._pending.clear();
+        this._subscriptions.clear();
+
+        for (const queue of this._busListeners.values()) {
+            queue.put(error);
+        }
+
+        this._resolveClosed();
+    }
+
+    _dispatch(kind, msg) {
+        switch (kind) {
+            case CALL_FUNCTION_REPLY:
+            case SUBSCRIBE_EVENT_REPLY:
+            case CREATE_BUS_LISTENER_REPLY:
+            case DESTROY_BUS_LISTENER_REPLY:
+            case START_BUS_LISTENER_REPLY: {
+                const key = `${kind}:${msg.u32()}`;
+                const pending = this._pending.get(key);
+
+                if (pending !== undefined) {
+                    this._pending.delete(key);
+                    pending.resolve(msg);
+                }
+
+                break;
+            }
+
+            case EMIT_EVENT: {
+                const value = msg.value;
+                const cookie = msg.uuid();
+                const eventId = msg.u32();
+
+                for (const proxy of this._subscriptions.get(`${cookie}:${eventId}`) ?? []) {
+                    proxy._deliver(eventId, value);
+                }
+
+                break;
+            }
+
+            case SERVICE_DESTROYED: {
+                const cookie = msg.uuid();
+
+                for (const key of this._subscriptions.keys()) {
+                    if (key.startsWith(`${cookie}:`)) {
+                        this._subscriptions.delete(key);
+                    }
+                }
+
+                break;
+            }
+
+            case EMIT_BUS_EVENT: {
+                if (msg.u8() !== SOME) {
+                    break;
+                }
+
+                const queue = this._busListeners.get(msg.uuid());
+
+                if (queue !== undefined && msg.u8() === BUS_EVENT_SERVICE_CREATED) {
+                    const object = { uuid: msg.uuid(), cookie: msg.uuid() };
+                    queue.put({ object, uuid: msg.uuid(), cookie: msg.uuid() });
+                }
+
+                break;
+            }
+
+            case BUS_LISTENER_CURRENT_FINISHED:
+                this._busListeners.get(msg.uuid())?.put(null);
+                break;
+
+            // All other messages, e.g. the broker telling a service about subscribers, are not
+            // needed by this client and ignored.
+        }
+    }
+}
+
+class Queue {
+    constructor() {
+        this.items = [];
+        this.waiters = [];
+    }
+
+    put(item) {
+        const waiter = this.waiters.shift();
+
+        if (waiter === undefined) {
+            this.items.push(item);
+        } else {
+            waiter(item);
+        }
+    }
+
+    get() {
+        if (this.items.length > 0) {
+            return Promise.resolve(this.items.shift());
+        }
+
+        return new Promise((resolve) => this.waiters.push(resolve));
+    }
+}
+
+/** Base class of generated proxies. */
+export class Proxy {
+    constructor(client, id) {
+        this.client = client;
+        this.id = id;
+        this._listeners = new Map();
+        this._properties = new Map();
+    }
+
+    /** Waits for a service of this type and creates a proxy for it. */
+    static async find(client, objectUuid) {
+        const id = await client.findService(this.UUID, objectUuid);
+        return new this(client, id);
+    }
+
+    async _call(functionId, argsCodec, args, okCodec, errCodec) {
+        const value = serialize(argsCodec, args);
+        const [serial, fields] = this.client._beginRequest();
+        fields.uuid(this.id.cookie);
+        fields.u32(functionId);
+
+        const reply = await this.client._request(
+            CALL_FUNCTION,
+            CALL_FUNCTION_REPLY,
+            serial,
+            fields,
+            value,
+        );
+        const kind = reply.u8();
+
+        if (kind === REPLY_OK) {
+            return deserialize(okCodec, reply.value);
+        } else if (kind === REPLY_ERR) {
+            throw new CallError(deserialize(errCodec, reply.value));
+        } else {
+            throw new CallFailed(REPLY_REASONS.get(kind) ?? "unknown error");
+        }
+    }
+
+    _callStream() {
+        return Promise.reject(new AldrinError("streaming functions are not supported yet"));
+    }
+
+    async _subscribe(eventId) {
+        const client = this.client;
+        const key = `${this.id.cookie}:${eventId}`;
+        let proxies = client._subscriptions.get(key);
+
+        if (proxies === undefined) {
+            const [serial] = client._beginRequest();
+            const fields = new Writer();
+            fields.u8(SOME);
+            fields.u32(serial);
+            fields.uuid(this.id.cookie);
+            fields.u32(eventId);
+            const reply = await client._request(
+                SUBSCRIBE_EVENT,
+                SUBSCRIBE_EVENT_REPLY,
+                serial,
+                fields,
+            );
+
+            if (reply.u8() !== 0) {
+                throw new CallFailed("invalid service");
+            }
+
+            proxies = client._subscriptions.get(key) ?? new Set();
+            client._subscriptions.set(key, proxies);
+        }
+
+        proxies.add(this);
+    }
+
+    async _unsubscribe(eventId) {
+        const client = this.client;
+        const key = `${this.id.cookie}:${eventId}`;
+        const proxies = client._subscriptions.get(key);
+
+        if (proxies === undefined || !proxies.delete(this)) {
+            return;
+        }
+
+        if (proxies.size === 0) {
+            client._subscriptions.delete(key);
+            const fields = new Writer();
+            fields.uuid(this.id.cookie);
+            fields.u32(eventId);
+            client._send(UNSUBSCRIBE_EVENT, fields);
+        }
+    }
+
+    _onEvent(eventId, codec, listener) {
+        const entry = { codec, listener };
+        let listeners = this._listeners.get(eventId);
+
+        if (listeners === undefined) {
+            listeners = new Set();
+            this._listeners.set(eventId, listeners);
+        }
+
+        listeners.add(entry);
+        return () => listeners.delete(entry);
+    }
+
+    _property(propertyId) {
+        return this._properties.get(propertyId)?.value ?? null;
+    }
+
+    /** Subscribes to a property and fetches its current value. */
+    async _subscribeProperty(propertyId, codec) {
+        const state = this._propertyState(propertyId);
+        state.codec = propertyUpdate(codec);
+
+        await this._subscribe(propertyId);
+        const update = await this._call(propertyId, UNIT, null, state.codec, UNIT);
+        this._updateProperty(state, update);
+    }
+
+    _onProperty(propertyId, listener) {
+        const listeners = this._propertyState(propertyId).listeners;
+        listeners.add(listener);
+        return () => listeners.delete(listener);
+    }
+
+    _propertyState(propertyId) {
+        let state = this._properties.get(propertyId);
+
+        if (state === undefined) {
+            state = { codec: null, seq: null, value: null, listeners: new Set() };
+            this._properties.set(propertyId, state);
+        }
+
+        return state;
+    }
+
+    _updateProperty(state, update) {
+        // Updates may arrive both as events and as the reply of the initial fetch.
+        if (state.seq !== null && update.seq <= state.seq) {
+            return;
+        }
+
+        state.seq = update.seq;
+        state.value = update.value;
+
+        for (const listener of state.listeners) {
+            listener(update.value);
+        }
+    }
+
+    _deliver(eventId, value) {
+        const state = this._properties.get(eventId);
+
+        if (state?.codec) {
+            try {
+                this._updateProperty(state, deserialize(state.codec, value));
+            } catch (e) {
+                if (!(e instanceof DeserializeError)) {
+                    throw e;
+                }
+            }
+
+            return;
+        }
+
+        for (const { codec, listener } of this._listeners.get(eventId) ?? []) {
+            let decoded;
+
+            try {
+                decoded = deserialize(codec, value);
+            } catch (e) {
+                if (e instanceof DeserializeError) {
+                    continue;
+                }
+
+                throw e;
+            }
+
+            listener(decoded);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+// Runtime support for TypeScript code generated by `aldrin-gen typescript`.
+
+export class SerializeError extends Error {}
+export class DeserializeError extends Error {}
+
+/** Serializes and deserializes values of type `T`. */
+export interface Codec<T> {
+    serialize(serializer: Serializer, value: T): void;
+    deserialize(deserializer: Deserializer): T;
+}
+
+/** Serializes and deserializes keys of maps and sets. */
+export interface KeyOps<T> {
+    readonly mapKind: number;
+    readonly setKind: number;
+    serialize(serializer: Serializer, value: T): void;
+    deserialize(deserializer: Deserializer): T;
+}
+
+/** Codec of a type, that can be used as the key of maps and sets. */
+export interface KeyCodec<T> extends Codec<T> {
+    readonly key: KeyOps<T>;
+}
+
+export class Serializer {
+    constructor();
+    serializeStruct(numFields: number): StructSerializer;
+    serializeEnum<T>(id: number, codec: Codec<T>, value: T): void;
+}
+
+export interface StructSerializer {
+    serializeField<T>(id: number, codec: Codec<T>, value: T): void;
+    finish(): void;
+}
+
+export class Deserializer {
+    constructor(bytes: Uint8Array);
+    deserializeStruct(): StructDeserializer;
+    deserializeEnum(): EnumDeserializer;
+    skip(): void;
+    finish(): void;
+}
+
+export interface StructDeserializer {
+    hasMoreFields(): boolean;
+    deserializeField(): FieldDeserializer;
+    finish(): void;
+}
+
+export interface FieldDeserializer {
+    readonly id: number;
+    deserialize<T>(codec: Codec<T>): T;
+    skip(): void;
+}
+
+export interface EnumDeserializer {
+    readonly id: number;
+    deserialize<T>(codec: Codec<T>): T;
+    skip(): void;
+}
+
+export function serialize<T>(codec: Codec<T>, value: T): Uint8Array;
+export function deserialize<T>(codec: Codec<T>, bytes: Uint8Array): T;
+export function required<T>(value: T | undefined, id: number): T;
+
+export interface ObjectId {
+    readonly uuid: string;
+    readonly cookie: string;
+}
+
+export interface ServiceId {
+    readonly object: ObjectId;
+    readonly uuid: string;
+    readonly cookie: string;
+}
+
+export type LifetimeId = ObjectId;
+
+export type Result<T, E> = { ok: true; value: T } | { ok: false; error: E };
+
+/** A value in serialized form, whose type is not known statically. */
+export class SerializedValue {
+    constructor(bytes: Uint8Array);
+    readonly bytes: Uint8Array;
+    static serialize<T>(codec: Codec<T>, value: T): SerializedValue;
+    deserialize<T>(codec: Codec<T>): T;
+}
+
+export class UnboundSender<T> {
+    constructor(cookie: string);
+    readonly cookie: string;
+    private readonly _item?: T;
+}
+
+export class UnboundReceiver<T> {
+    constructor(cookie: string);
+    readonly cookie: string;
+    private readonly _item?: T;
+}
+
+export type Receiver<T> = AsyncIterable<T>;
+
+export const UNIT: Codec<null>;
+export const BOOL: Codec<boolean>;
+export const U8: KeyCodec<number>;
+export const I8: KeyCodec<number>;
+export const U16: KeyCodec<number>;
+export const I16: KeyCodec<number>;
+export const U32: KeyCodec<number>;
+export const I32: KeyCodec<number>;
+export const U64: KeyCodec<bigint>;
+export const I64: KeyCodec<bigint>;
+export const F32: Codec<number>;
+export const F64: Codec<number>;
+export const STRING: KeyCodec<string>;
+export const UUID: KeyCodec<string>;
+export const OBJECT_ID: Codec<ObjectId>;
+export const SERVICE_ID: Codec<ServiceId>;
+export const LIFETIME: Codec<LifetimeId>;
+export const VALUE: Codec<SerializedValue>;
+export const BYTES: Codec<Uint8Array>;
+
+export function option<T>(codec: Codec<T>): Codec<T | null>;
+export function optional<T>(codec: Codec<T>): Codec<T | undefined>;
+export function vec<T>(codec: Codec<T>): Codec<Array<T>>;
+export function array<T>(codec: Codec<T>, len: number): Codec<Array<T>>;
+export function map<K, V>(key: KeyCodec<K>, codec: Codec<V>): Codec<Map<K, V>>;
+export function set<K>(key: KeyCodec<K>): Codec<Set<K>>;
+export function sender<T>(codec: Codec<T>): Codec<UnboundSender<T>>;
+export function receiver<T>(codec: Codec<T>): Codec<UnboundReceiver<T>>;
+export function result<T, E>(ok: Codec<T>, err: Codec<E>): Codec<Result<T, E>>;
+
+/** Full update of a property. A value of `null` indicates, that the property has not been set. */
+export interface PropertyUpdate<T> {
+    seq: bigint;
+    value: T | null;
+}
+
+export function propertyUpdate<T>(codec: Codec<T>): Codec<PropertyUpdate<T>>;
+
+export const PROTOCOL_MAJOR: number;
+export const PROTOCOL_MINOR: number;
+
+/** Base class of all errors raised by the client. */
+export class AldrinError extends Error {}
+
+/** The broker rejected the connection. */
+export class ConnectError extends AldrinError {}
+
+/** The connection to the broker was closed. */
+export class DisconnectedError extends AldrinError {}
+
+/** A function call returned an error. */
+export class CallError<E = unknown> extends AldrinError {
+    readonly value: E;
+}
+
+/** A function call failed without returning a value, e.g. because the service is gone. */
+export class CallFailed extends AldrinError {
+    readonly reason: string;
+}
+
+export interface ConnectOptions {
+    /** Name of the client, which is shown e.g. by the broker's statistics. */
+    name?: string;
+
+    /** WebSocket implementation, which defaults to the global `WebSocket`. */
+    WebSocket?: { new (url: string | URL): WebSocket };
+}
+
+/** Connection to an Aldrin broker. */
+export class Client {
+    private constructor();
+
+    /** Resolves when the connection has been closed. */
+    readonly closed: Promise<void>;
+
+    /** Connects to a broker at a WebSocket URL. */
+    static connect(url: string | URL, options?: ConnectOptions): Promise<Client>;
+
+    /** Performs the handshake with a broker over a WebSocket, which may still be connecting. */
+    static fromWebSocket(socket: WebSocket, options?: ConnectOptions): Promise<Client>;
+
+    /** Shuts down the connection and waits until the broker has acknowledged it. */
+    close(): Promise<void>;
+
+    /** Finds a service by its UUID and optionally its object's UUID. */
+    findService(serviceUuid: string, objectUuid?: string): Promise<ServiceId>;
+    findService(
+        serviceUuid: string,
+        objectUuid: string | undefined,
+        wait: false,
+    ): Promise<ServiceId | null>;
+}
+
+/** Base class of generated proxies. */
+export class Proxy {
+    constructor(client: Client, id: ServiceId);
+
+    readonly client: Client;
+    readonly id: ServiceId;
+
+    /** Waits for a service of this type and creates a proxy for it. */
+    static find<P extends Proxy>(
+        this: { new (client: Client, id: ServiceId): P; readonly UUID: string },
+        client: Client,
+        objectUuid?: string,
+    ): Promise<P>;
+
+    protected _call<A, T, E>(
+        id: number,
+        argsCodec: Codec<A>,
+        args: A,
+        okCodec: Codec<T>,
+        errCodec: Codec<E>,
+    ): Promise<T>;
+
+    protected _callStream<A, T, E>(
+        id: number,
+        argsCodec: Codec<A>,
+        args: A,
+        itemCodec: Codec<T>,
+        errCodec: Codec<E>,
+    ): Promise<Receiver<T>>;
+
+    protected _subscribe(id: number): Promise<void>;
+    protected _unsubscribe(id: number): Promise<void>;
+    protected _onEvent<T>(id: number, codec: Codec<T>, listener: (value: T) => void): () => void;
+
+    protected _property<T>(id: number): T | null;
+    protected _subscribeProperty<T>(id: number, codec: Codec<T>): Promise<void>;
+    protected _onProperty<T>(id: number, listener: (value: T | null) => void): () => void;
+}
@@ -0,0 +1,55 @@
+// Runtime support for TypeScript code generated by `aldrin-gen typescript`.
+
+export {
+    BOOL,
+    BYTES,
+    DeserializeError,
+    Deserializer,
+    F32,
+    F64,
+    I16,
+    I32,
+    I64,
+    I8,
+    LIFETIME,
+    OBJECT_ID,
+    SERVICE_ID,
+    STRING,
+    SerializeError,
+    SerializedValue,
+    Serializer,
+    U16,
+    U32,
+    U64,
+    U8,
+    UNIT,
+    UUID,
+    UnboundReceiver,
+    UnboundSender,
+    VALUE,
+    array,
+    deserialize,
+    map,
+    option,
+    optional,
+    propertyUpdate,
+    receiver,
+    required,
+    result,
+    sender,
+    serialize,
+    set,
+    vec,
+} from "./serialization.js";
+
+export {
+    AldrinError,
+    CallError,
+    CallFailed,
+    Client,
+    ConnectError,
+    DisconnectedError,
+    PROTOCOL_MAJOR,
+    PROTOCOL_MINOR,
+    Proxy,
+} from "./client.js";
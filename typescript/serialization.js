@@ -0,0 +1,1071 @@
+// Aldrin's binary value format and the codecs used by generated code.
+
+export class SerializeError extends Error {
+    constructor(message) {
+        super(message);
+        this.name = "SerializeError";
+    }
+}
+
+export class DeserializeError extends Error {
+    constructor(message) {
+        super(message);
+        this.name = "DeserializeError";
+    }
+}
+
+// Discriminants of serialized values. These mirror `ValueKind` in aldrin-core.
+export const NONE = 0;
+export const SOME = 1;
+const BOOL_KIND = 2;
+const U8_KIND = 3;
+const I8_KIND = 4;
+const U16_KIND = 5;
+const I16_KIND = 6;
+const U32_KIND = 7;
+const I32_KIND = 8;
+const U64_KIND = 9;
+const I64_KIND = 10;
+const F32_KIND = 11;
+const F64_KIND = 12;
+const STRING_KIND = 13;
+const UUID_KIND = 14;
+const OBJECT_ID_KIND = 15;
+const SERVICE_ID_KIND = 16;
+const VEC_KIND = 17;
+const BYTES_KIND = 18;
+const U8_MAP = 19;
+const UUID_MAP = 28;
+const U8_SET = 29;
+const UUID_SET = 38;
+export const STRUCT_KIND = 39;
+const ENUM_KIND = 40;
+const SENDER_KIND = 41;
+const RECEIVER_KIND = 42;
+
+const MAX_DEPTH = 32;
+const UUID_REGEX = /^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$/i;
+
+const textEncoder = new TextEncoder();
+const textDecoder = new TextDecoder("utf-8", { fatal: true });
+
+function leBytes(n, size) {
+    const bytes = new Uint8Array(8);
+    const view = new DataView(bytes.buffer);
+
+    if (size === 8) {
+        view.setBigUint64(0, BigInt.asUintN(64, n), true);
+    } else {
+        view.setUint32(0, n >>> 0, true);
+    }
+
+    return bytes;
+}
+
+function parseUuid(value) {
+    if (typeof value !== "string" || !UUID_REGEX.test(value)) {
+        throw new SerializeError(`expected a UUID, got ${String(value)}`);
+    }
+
+    const hex = value.replaceAll("-", "");
+    const bytes = new Uint8Array(16);
+
+    for (let i = 0; i < 16; i++) {
+        bytes[i] = parseInt(hex.slice(2 * i, 2 * i + 2), 16);
+    }
+
+    return bytes;
+}
+
+function formatUuid(bytes) {
+    const hex = Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("");
+    const parts = [hex.slice(0, 8), hex.slice(8, 12), hex.slice(12, 16), hex.slice(16, 20)];
+    return `${parts.join("-")}-${hex.slice(20)}`;
+}
+
+export class Writer {
+    constructor() {
+        this.buf = new Uint8Array(64);
+        this.len = 0;
+    }
+
+    reserve(n) {
+        if (this.len + n <= this.buf.length) {
+            return;
+        }
+
+        let capacity = this.buf.length * 2;
+        while (capacity < this.len + n) {
+            capacity *= 2;
+        }
+
+        const buf = new Uint8Array(capacity);
+        buf.set(this.buf.subarray(0, this.len));
+        this.buf = buf;
+    }
+
+    u8(n) {
+        this.reserve(1);
+        this.buf[this.len++] = n;
+    }
+
+    raw(bytes) {
+        this.reserve(bytes.length);
+        this.buf.set(bytes, this.len);
+        this.len += bytes.length;
+    }
+
+    fixedU32(n) {
+        this.raw(leBytes(n, 4).subarray(0, 4));
+    }
+
+    varint(n, size) {
+        const bytes = leBytes(n, size);
+
+        for (let i = 0; i < size - 1; i++) {
+            if (bytes[size - 1 - i] !== 0) {
+                this.u8(255 - i);
+                this.raw(bytes.subarray(0, size - i));
+                return;
+            }
+        }
+
+        if (bytes[0] > 255 - size) {
+            this.u8(256 - size);
+        }
+
+        this.u8(bytes[0]);
+    }
+
+    u32(n) {
+        this.varint(n, 4);
+    }
+
+    uuid(value) {
+        this.raw(parseUuid(value));
+    }
+
+    string(value) {
+        const bytes = textEncoder.encode(value);
+        this.u32(bytes.length);
+        this.raw(bytes);
+    }
+
+    f32(value) {
+        const bytes = new Uint8Array(4);
+        new DataView(bytes.buffer).setFloat32(0, value, true);
+        this.raw(bytes);
+    }
+
+    f64(value) {
+        const bytes = new Uint8Array(8);
+        new DataView(bytes.buffer).setFloat64(0, value, true);
+        this.raw(bytes);
+    }
+
+    bytes() {
+        return this.buf.slice(0, this.len);
+    }
+}
+
+export class Reader {
+    constructor(bytes, pos = 0) {
+        this.buf = bytes;
+        this.pos = pos;
+    }
+
+    remaining() {
+        return this.buf.length - this.pos;
+    }
+
+    take(n) {
+        if (n > this.remaining()) {
+            throw new DeserializeError("unexpected end of input");
+        }
+
+        const bytes = this.buf.slice(this.pos, this.pos + n);
+        this.pos += n;
+        return bytes;
+    }
+
+    skip(n) {
+        if (n > this.remaining()) {
+            throw new DeserializeError("unexpected end of input");
+        }
+
+        this.pos += n;
+    }
+
+    u8() {
+        if (this.pos >= this.buf.length) {
+            throw new DeserializeError("unexpected end of input");
+        }
+
+        return this.buf[this.pos++];
+    }
+
+    fixedU32() {
+        const bytes = this.take(4);
+        return new DataView(bytes.buffer).getUint32(0, true);
+    }
+
+    varint(size) {
+        const first = this.u8();
+
+        if (first <= 255 - size) {
+            return size === 8 ? BigInt(first) : first;
+        }
+
+        const bytes = new Uint8Array(8);
+        bytes.set(this.take(first + size - 255));
+        const view = new DataView(bytes.buffer);
+        return size === 8 ? view.getBigUint64(0, true) : view.getUint32(0, true);
+    }
+
+    u32() {
+        return this.varint(4);
+    }
+
+    uuid() {
+        return formatUuid(this.take(16));
+    }
+
+    string() {
+        try {
+            return textDecoder.decode(this.take(this.u32()));
+        } catch (e) {
+            if (e instanceof DeserializeError) {
+                throw e;
+            }
+
+            throw new DeserializeError("invalid UTF-8 in string");
+        }
+    }
+
+    f32() {
+        return new DataView(this.take(4).buffer).getFloat32(0, true);
+    }
+
+    f64() {
+        return new DataView(this.take(8).buffer).getFloat64(0, true);
+    }
+
+    expect(kind) {
+        const actual = this.u8();
+
+        if (actual !== kind) {
+            throw new DeserializeError(`unexpected value kind ${actual}, expected ${kind}`);
+        }
+    }
+
+    skipValue(depth = 0) {
+        if (depth > MAX_DEPTH) {
+            throw new DeserializeError("too deeply nested");
+        }
+
+        const kind = this.u8();
+
+        switch (kind) {
+            case NONE:
+                break;
+            case SOME:
+                this.skipValue(depth + 1);
+                break;
+            case BOOL_KIND:
+            case U8_KIND:
+            case I8_KIND:
+                this.skip(1);
+                break;
+            case U16_KIND:
+            case I16_KIND:
+                this.varint(2);
+                break;
+            case U32_KIND:
+            case I32_KIND:
+                this.varint(4);
+                break;
+            case U64_KIND:
+            case I64_KIND:
+                this.varint(8);
+                break;
+            case F32_KIND:
+                this.skip(4);
+                break;
+            case F64_KIND:
+                this.skip(8);
+                break;
+            case STRING_KIND:
+            case BYTES_KIND:
+                this.skip(this.u32());
+                break;
+            case UUID_KIND:
+            case SENDER_KIND:
+            case RECEIVER_KIND:
+                this.skip(16);
+                break;
+            case OBJECT_ID_KIND:
+                this.skip(32);
+                break;
+            case SERVICE_ID_KIND:
+                this.skip(64);
+                break;
+            case VEC_KIND:
+                for (let n = this.u32(); n > 0; n--) {
+                    this.skipValue(depth + 1);
+                }
+                break;
+            case STRUCT_KIND:
+                for (let n = this.u32(); n > 0; n--) {
+                    this.u32();
+                    this.skipValue(depth + 1);
+                }
+                break;
+            case ENUM_KIND:
+                this.u32();
+                this.skipValue(depth + 1);
+                break;
+            default:
+                if (kind >= U8_MAP && kind <= UUID_MAP) {
+                    for (let n = this.u32(); n > 0; n--) {
+                        this.skipKey(kind - U8_MAP);
+                        this.skipValue(depth + 1);
+                    }
+                } else if (kind >= U8_SET && kind <= UUID_SET) {
+                    for (let n = this.u32(); n > 0; n--) {
+                        this.skipKey(kind - U8_SET);
+                    }
+                } else {
+                    throw new DeserializeError(`invalid value kind ${kind}`);
+                }
+        }
+    }
+
+    // Indices follow the order u8, i8, u16, i16, u32, i32, u64, i64, string, uuid.
+    skipKey(index) {
+        if (index < 2) {
+            this.skip(1);
+        } else if (index < 8) {
+            this.varint(2 << ((index >> 1) - 1));
+        } else if (index === 8) {
+            this.skip(this.u32());
+        } else {
+            this.skip(16);
+        }
+    }
+}
+
+export class Serializer {
+    constructor(writer = new Writer()) {
+        this.writer = writer;
+    }
+
+    serializeStruct(numFields) {
+        this.writer.u8(STRUCT_KIND);
+        this.writer.u32(numFields);
+        return new StructSerializer(this, numFields);
+    }
+
+    serializeEnum(id, codec, value) {
+        this.writer.u8(ENUM_KIND);
+        this.writer.u32(id);
+        codec.serialize(this, value);
+    }
+}
+
+class StructSerializer {
+    constructor(serializer, numFields) {
+        this.serializer = serializer;
+        this.remaining = numFields;
+    }
+
+    serializeField(id, codec, value) {
+        if (this.remaining === 0) {
+            throw new SerializeError("too many struct fields");
+        }
+
+        this.remaining -= 1;
+        this.serializer.writer.u32(id);
+        codec.serialize(this.serializer, value);
+    }
+
+    finish() {
+        if (this.remaining !== 0) {
+            throw new SerializeError("too few struct fields");
+        }
+    }
+}
+
+export class Deserializer {
+    constructor(reader, depth = 0) {
+        this.reader = reader instanceof Reader ? reader : new Reader(reader);
+        this.depth = depth;
+    }
+
+    deserializeStruct() {
+        this.reader.expect(STRUCT_KIND);
+        return new StructDeserializer(this, this.reader.u32());
+    }
+
+    deserializeEnum() {
+        this.reader.expect(ENUM_KIND);
+        return new EnumDeserializer(this, this.reader.u32());
+    }
+
+    nested() {
+        if (this.depth >= MAX_DEPTH) {
+            throw new DeserializeError("too deeply nested");
+        }
+
+        return new Deserializer(this.reader, this.depth + 1);
+    }
+
+    skip() {
+        this.reader.skipValue(this.depth);
+    }
+
+    finish() {
+        if (this.reader.remaining() !== 0) {
+            throw new DeserializeError("trailing data after value");
+        }
+    }
+}
+
+class StructDeserializer {
+    constructor(deserializer, numFields) {
+        this.deserializer = deserializer;
+        this.remaining = numFields;
+    }
+
+    hasMoreFields() {
+        return this.remaining > 0;
+    }
+
+    deserializeField() {
+        if (this.remaining === 0) {
+            throw new DeserializeError("no more struct fields");
+        }
+
+        this.remaining -= 1;
+        const id = this.deserializer.reader.u32();
+        return new FieldDeserializer(this.deserializer.nested(), id);
+    }
+
+    finish() {
+        while (this.hasMoreFields()) {
+            this.deserializeField().skip();
+        }
+    }
+}
+
+class FieldDeserializer {
+    constructor(deserializer, id) {
+        this.deserializer = deserializer;
+        this.id = id;
+    }
+
+    deserialize(codec) {
+        return codec.deserialize(this.deserializer);
+    }
+
+    skip() {
+        this.deserializer.skip();
+    }
+}
+
+class EnumDeserializer {
+    constructor(deserializer, id) {
+        this.deserializer = deserializer.nested();
+        this.id = id;
+    }
+
+    deserialize(codec) {
+        return codec.deserialize(this.deserializer);
+    }
+
+    skip() {
+        this.deserializer.skip();
+    }
+}
+
+/** Serializes `value` with `codec`. */
+export function serialize(codec, value) {
+    const serializer = new Serializer();
+    codec.serialize(serializer, value);
+    return serializer.writer.bytes();
+}
+
+/** Deserializes a value with `codec` and rejects trailing bytes. */
+export function deserialize(codec, bytes) {
+    const deserializer = new Deserializer(bytes);
+    const value = codec.deserialize(deserializer);
+    deserializer.finish();
+    return value;
+}
+
+export function required(value, id) {
+    if (value === undefined) {
+        throw new DeserializeError(`required field ${id} is missing`);
+    }
+
+    return value;
+}
+
+export class SerializedValue {
+    constructor(bytes) {
+        this.bytes = bytes;
+    }
+
+    static serialize(codec, value) {
+        return new SerializedValue(serialize(codec, value));
+    }
+
+    deserialize(codec) {
+        return deserialize(codec, this.bytes);
+    }
+}
+
+export class UnboundSender {
+    constructor(cookie) {
+        this.cookie = cookie;
+    }
+}
+
+export class UnboundReceiver {
+    constructor(cookie) {
+        this.cookie = cookie;
+    }
+}
+
+function checkType(value, type, name) {
+    if (typeof value !== type) {
+        throw new SerializeError(`expected ${name}, got ${String(value)}`);
+    }
+}
+
+export const UNIT = {
+    serialize(serializer, value) {
+        if (value !== null && value !== undefined) {
+            throw new SerializeError(`expected null, got ${String(value)}`);
+        }
+
+        serializer.writer.u8(NONE);
+    },
+
+    deserialize(deserializer) {
+        deserializer.reader.expect(NONE);
+        return null;
+    },
+};
+
+export const BOOL = {
+    serialize(serializer, value) {
+        checkType(value, "boolean", "a boolean");
+        serializer.writer.u8(BOOL_KIND);
+        serializer.writer.u8(value ? 1 : 0);
+    },
+
+    deserialize(deserializer) {
+        deserializer.reader.expect(BOOL_KIND);
+        return deserializer.reader.u8() !== 0;
+    },
+};
+
+function intCodec(kind, bits, signed, mapKind, setKind) {
+    const big = bits === 64;
+    const size = bits / 8;
+    const min = signed ? -(2 ** (bits - 1)) : 0;
+    const max = signed ? 2 ** (bits - 1) - 1 : 2 ** bits - 1;
+
+    const key = {
+        mapKind,
+        setKind,
+
+        serialize(serializer, value) {
+            const writer = serializer.writer;
+
+            if (big) {
+                checkType(value, "bigint", "a bigint");
+
+                if (value < BigInt(min) || value > BigInt(max)) {
+                    throw new SerializeError(`${value} is out of range`);
+                }
+            } else if (!Number.isInteger(value) || value < min || value > max) {
+                throw new SerializeError(`expected an integer in ${min}..=${max}, got ${value}`);
+            }
+
+            if (bits === 8) {
+                writer.u8(value & 0xff);
+            } else if (!signed) {
+                writer.varint(value, size);
+            } else if (big) {
+                writer.varint(BigInt.asUintN(64, (value << 1n) ^ (value >> 63n)), size);
+            } else {
+                writer.varint(((value << 1) ^ (value >> (bits - 1))) >>> 0, size);
+            }
+        },
+
+        deserialize(deserializer) {
+            const reader = deserializer.reader;
+
+            if (bits === 8) {
+                const n = reader.u8();
+                return signed ? (n << 24) >> 24 : n;
+            }
+
+            const n = reader.varint(size);
+
+            if (!big && n > 2 ** bits - 1) {
+                throw new DeserializeError("varint out of range");
+            } else if (!signed) {
+                return n;
+            } else if (big) {
+                return (n >> 1n) ^ -(n & 1n);
+            } else {
+                return (n >>> 1) ^ -(n & 1);
+            }
+        },
+    };
+
+    return {
+        key,
+
+        serialize(serializer, value) {
+            serializer.writer.u8(kind);
+            key.serialize(serializer, value);
+        },
+
+        deserialize(deserializer) {
+            deserializer.reader.expect(kind);
+            return key.deserialize(deserializer);
+        },
+    };
+}
+
+export const U8 = intCodec(U8_KIND, 8, false, 19, 29);
+export const I8 = intCodec(I8_KIND, 8, true, 20, 30);
+export const U16 = intCodec(U16_KIND, 16, false, 21, 31);
+export const I16 = intCodec(I16_KIND, 16, true, 22, 32);
+export const U32 = intCodec(U32_KIND, 32, false, 23, 33);
+export const I32 = intCodec(I32_KIND, 32, true, 24, 34);
+export const U64 = intCodec(U64_KIND, 64, false, 25, 35);
+export const I64 = intCodec(I64_KIND, 64, true, 26, 36);
+
+export const F32 = {
+    serialize(serializer, value) {
+        checkType(value, "number", "a number");
+
+        if (Number.isFinite(value) && Math.abs(value) > 3.4028234663852886e38) {
+            throw new SerializeError(`${value} is out of range`);
+        }
+
+        serializer.writer.u8(F32_KIND);
+        serializer.writer.f32(value);
+    },
+
+    deserialize(deserializer) {
+        deserializer.reader.expect(F32_KIND);
+        return deserializer.reader.f32();
+    },
+};
+
+export const F64 = {
+    serialize(serializer, value) {
+        checkType(value, "number", "a number");
+        serializer.writer.u8(F64_KIND);
+        serializer.writer.f64(value);
+    },
+
+    deserialize(deserializer) {
+        deserializer.reader.expect(F64_KIND);
+        return deserializer.reader.f64();
+    },
+};
+
+const STRING_KEY = {
+    mapKind: 27,
+    setKind: 37,
+
+    serialize(serializer, value) {
+        checkType(value, "string", "a string");
+        serializer.writer.string(value);
+    },
+
+    deserialize(deserializer) {
+        return deserializer.reader.string();
+    },
+};
+
+export const STRING = {
+    key: STRING_KEY,
+
+    serialize(serializer, value) {
+        serializer.writer.u8(STRING_KIND);
+        STRING_KEY.serialize(serializer, value);
+    },
+
+    deserialize(deserializer) {
+        deserializer.reader.expect(STRING_KIND);
+        return deserializer.reader.string();
+    },
+};
+
+export const UUID = {
+    key: {
+        mapKind: 28,
+        setKind: 38,
+
+        serialize(serializer, value) {
+            serializer.writer.uuid(value);
+        },
+
+        deserialize(deserializer) {
+            return deserializer.reader.uuid();
+        },
+    },
+
+    serialize(serializer, value) {
+        serializer.writer.u8(UUID_KIND);
+        serializer.writer.uuid(value);
+    },
+
+    deserialize(deserializer) {
+        deserializer.reader.expect(UUID_KIND);
+        return deserializer.reader.uuid();
+    },
+};
+
+export const OBJECT_ID = {
+    serialize(serializer, value) {
+        serializer.writer.u8(OBJECT_ID_KIND);
+        serializer.writer.uuid(value.uuid);
+        serializer.writer.uuid(value.cookie);
+    },
+
+    deserialize(deserializer) {
+        const reader = deserializer.reader;
+        reader.expect(OBJECT_ID_KIND);
+        return { uuid: reader.uuid(), cookie: reader.uuid() };
+    },
+};
+
+export const SERVICE_ID = {
+    serialize(serializer, value) {
+        const writer = serializer.writer;
+        writer.u8(SERVICE_ID_KIND);
+        writer.uuid(value.object.uuid);
+        writer.uuid(value.object.cookie);
+        writer.uuid(value.uuid);
+        writer.uuid(value.cookie);
+    },
+
+    deserialize(deserializer) {
+        const reader = deserializer.reader;
+        reader.expect(SERVICE_ID_KIND);
+        const object = { uuid: reader.uuid(), cookie: reader.uuid() };
+        return { object, uuid: reader.uuid(), cookie: reader.uuid() };
+    },
+};
+
+export const LIFETIME = OBJECT_ID;
+
+export const VALUE = {
+    serialize(serializer, value) {
+        if (!(value instanceof SerializedValue)) {
+            throw new SerializeError(`expected a SerializedValue, got ${String(value)}`);
+        }
+
+        serializer.writer.raw(value.bytes);
+    },
+
+    deserialize(deserializer) {
+        const reader = deserializer.reader;
+        const start = reader.pos;
+        deserializer.skip();
+        return new SerializedValue(reader.buf.slice(start, reader.pos));
+    },
+};
+
+export const BYTES = {
+    serialize(serializer, value) {
+        if (!(value instanceof Uint8Array)) {
+            throw new SerializeError(`expected a Uint8Array, got ${String(value)}`);
+        }
+
+        serializer.writer.u8(BYTES_KIND);
+        serializer.writer.u32(value.length);
+        serializer.writer.raw(value);
+    },
+
+    deserialize(deserializer) {
+        const reader = deserializer.reader;
+        reader.expect(BYTES_KIND);
+        return reader.take(reader.u32());
+    },
+};
+
+/** Codec of `option<T>`, which maps `null` to none. */
+export function option(codec) {
+    return {
+        serialize(serializer, value) {
+            if (value === null) {
+                serializer.writer.u8(NONE);
+            } else {
+                serializer.writer.u8(SOME);
+                codec.serialize(serializer, value);
+            }
+        },
+
+        deserialize(deserializer) {
+            const kind = deserializer.reader.u8();
+
+            if (kind === NONE) {
+                return null;
+            } else if (kind === SOME) {
+                return codec.deserialize(deserializer.nested());
+            } else {
+                throw new DeserializeError(`unexpected value kind ${kind}, expected an option`);
+            }
+        },
+    };
+}
+
+/** Codec of optional struct fields, which maps `undefined` to none. */
+export function optional(codec) {
+    return {
+        serialize(serializer, value) {
+            if (value === undefined) {
+                serializer.writer.u8(NONE);
+            } else {
+                serializer.writer.u8(SOME);
+                codec.serialize(serializer, value);
+            }
+        },
+
+        deserialize(deserializer) {
+            const kind = deserializer.reader.u8();
+
+            if (kind === NONE) {
+                return undefined;
+            } else if (kind === SOME) {
+                return codec.deserialize(deserializer.nested());
+            } else {
+                throw new DeserializeError(`unexpected value kind ${kind}, expected an option`);
+            }
+        },
+    };
+}
+
+export function vec(codec) {
+    return {
+        serialize(serializer, value) {
+            if (!Array.isArray(value)) {
+                throw new SerializeError(`expected an array, got ${String(value)}`);
+            }
+
+            serializer.writer.u8(VEC_KIND);
+            serializer.writer.u32(value.length);
+
+            for (const elem of value) {
+                codec.serialize(serializer, elem);
+            }
+        },
+
+        deserialize(deserializer) {
+            const reader = deserializer.reader;
+            reader.expect(VEC_KIND);
+            const nested = deserializer.nested();
+            const value = [];
+
+            for (let n = reader.u32(); n > 0; n--) {
+                value.push(codec.deserialize(nested));
+            }
+
+            return value;
+        },
+    };
+}
+
+export function array(codec, len) {
+    const inner = vec(codec);
+
+    return {
+        serialize(serializer, value) {
+            if (Array.isArray(value) && value.length !== len) {
+                throw new SerializeError(`expected ${len} elements, got ${value.length}`);
+            }
+
+            inner.serialize(serializer, value);
+        },
+
+        deserialize(deserializer) {
+            const value = inner.deserialize(deserializer);
+
+            if (value.length !== len) {
+                throw new DeserializeError(`expected ${len} elements, got ${value.length}`);
+            }
+
+            return value;
+        },
+    };
+}
+
+export function map(keyCodec, codec) {
+    return {
+        serialize(serializer, value) {
+            if (!(value instanceof Map)) {
+                throw new SerializeError(`expected a Map, got ${String(value)}`);
+            }
+
+            const key = keyCodec.key;
+            serializer.writer.u8(key.mapKind);
+            serializer.writer.u32(value.size);
+
+            for (const [k, v] of value) {
+                key.serialize(serializer, k);
+                codec.serialize(serializer, v);
+            }
+        },
+
+        deserialize(deserializer) {
+            const key = keyCodec.key;
+            const reader = deserializer.reader;
+            reader.expect(key.mapKind);
+            const nested = deserializer.nested();
+            const value = new Map();
+
+            for (let n = reader.u32(); n > 0; n--) {
+                const k = key.deserialize(deserializer);
+                value.set(k, codec.deserialize(nested));
+            }
+
+            return value;
+        },
+    };
+}
+
+export function set(keyCodec) {
+    return {
+        serialize(serializer, value) {
+            if (!(value instanceof Set)) {
+                throw new SerializeError(`expected a Set, got ${String(value)}`);
+            }
+
+            const key = keyCodec.key;
+            serializer.writer.u8(key.setKind);
+            serializer.writer.u32(value.size);
+
+            for (const k of value) {
+                key.serialize(serializer, k);
+            }
+        },
+
+        deserialize(deserializer) {
+            const key = keyCodec.key;
+            deserializer.reader.expect(key.setKind);
+            const value = new Set();
+
+            for (let n = deserializer.reader.u32(); n > 0; n--) {
+                value.add(key.deserialize(deserializer));
+            }
+
+            return value;
+        },
+    };
+}
+
+function channelCodec(kind, Class) {
+    return {
+        serialize(serializer, value) {
+            if (!(value instanceof Class)) {
+                throw new SerializeError(`expected an ${Class.name}, got ${String(value)}`);
+            }
+
+            serializer.writer.u8(kind);
+            serializer.writer.uuid(value.cookie);
+        },
+
+        deserialize(deserializer) {
+            deserializer.reader.expect(kind);
+            return new Class(deserializer.reader.uuid());
+        },
+    };
+}
+
+export function sender(_codec) {
+    return channelCodec(SENDER_KIND, UnboundSender);
+}
+
+export function receiver(_codec) {
+    return channelCodec(RECEIVER_KIND, UnboundReceiver);
+}
+
+export function result(okCodec, errCodec) {
+    return {
+        serialize(serializer, value) {
+            if (value.ok) {
+                serializer.serializeEnum(0, okCodec, value.value);
+            } else {
+                serializer.serializeEnum(1, errCodec, value.error);
+            }
+        },
+
+        deserialize(deserializer) {
+            const variant = deserializer.deserializeEnum();
+
+            switch (variant.id) {
+                case 0:
+                    return { ok: true, value: variant.deserialize(okCodec) };
+                case 1:
+                    return { ok: false, error: variant.deserialize(errCodec) };
+                default:
+                    throw new DeserializeError(`invalid result variant ${variant.id}`);
+            }
+        },
+    };
+}
+
+/**
+ * Codec of full property updates, which carry a sequence number and an optional value.
+ *
+ * Deltas are not supported, because properties declared in schemas are only updated with full
+ * values.
+ */
+export function propertyUpdate(codec) {
+    const valueCodec = option(codec);
+
+    return {
+        serialize(serializer, update) {
+            const struct = serializer.serializeStruct(2);
+            struct.serializeField(0, U64, update.seq);
+            struct.serializeField(1, valueCodec, update.value);
+            struct.finish();
+        },
+
+        deserialize(deserializer) {
+            const struct = deserializer.deserializeStruct();
+            let seq;
+            let value;
+
+            while (struct.hasMoreFields()) {
+                const field = struct.deserializeField();
+
+                switch (field.id) {
+                    case 0:
+                        seq = field.deserialize(U64);
+                        break;
+                    case 1:
+                        value = field.deserialize(valueCodec);
+                        break;
+                    default:
+                        field.skip();
+                }
+            }
+
+            if (seq === undefined || value === undefined) {
+                throw new DeserializeError("expected a full property update");
+            }
+
+            return { seq, value };
+        },
+    };
+}